@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::process::Stdio;
 
@@ -6,14 +7,21 @@ use serde_json::json;
 use tokio::process::Command;
 use tokio::sync::mpsc;
 
+use crate::agent::diagnostics::capture_stderr;
+use crate::agent::edit_review;
 use crate::agent::error::AgentError;
 use crate::agent::events::{
-    AgentEvent, AssistantMessageEvent, ControlRequestEvent, ErrorEvent, SessionInitEvent,
-    TokenUsage, ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent, TurnFailedEvent,
+    AgentEvent, AssistantMessageEvent, ControlRequestEvent, ErrorEvent, ReasoningEvent,
+    SessionInitEvent, SubagentCompletedEvent, SubagentEvent, SubagentStartedEvent, TokenUsage,
+    ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent, TurnFailedEvent,
 };
+use crate::agent::replay::AgentRecorder;
 use crate::agent::runner::{AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType};
 use crate::agent::session::SessionId;
-use crate::agent::stream::{ClaudeRawEvent, JsonlStreamParser};
+use crate::agent::stream::{ClaudeControlRequestType, ClaudeRawEvent, JsonlStreamParser};
+
+/// Tool that spawns a subagent in Claude Code
+pub(crate) const SUBAGENT_TOOL_NAME: &str = "Task";
 
 pub struct ClaudeCodeRunner {
     binary_path: PathBuf,
@@ -35,7 +43,7 @@ impl ClaudeCodeRunner {
         which::which("claude").ok()
     }
 
-    fn build_command(&self, config: &AgentStartConfig) -> Command {
+    fn build_command(&self, config: &AgentStartConfig) -> std::io::Result<Command> {
         let mut cmd = Command::new(&self.binary_path);
 
         let use_stream_input = config
@@ -60,10 +68,15 @@ impl ClaudeCodeRunner {
         cmd.arg("--permission-mode")
             .arg(config.agent_mode.as_permission_mode());
 
-        // Allowed tools
-        if !config.allowed_tools.is_empty() {
-            cmd.arg("--allowedTools")
-                .arg(config.allowed_tools.join(","));
+        // Allowed tools; reviewed edit tools must go through permission requests
+        let allowed_tools: Vec<&str> = config
+            .allowed_tools
+            .iter()
+            .map(String::as_str)
+            .filter(|tool| !(config.review_edits && edit_review::is_reviewed_tool(tool)))
+            .collect();
+        if !allowed_tools.is_empty() {
+            cmd.arg("--allowedTools").arg(allowed_tools.join(","));
         }
 
         // Resume session if provided
@@ -76,8 +89,16 @@ impl ClaudeCodeRunner {
             cmd.arg("--model").arg(model);
         }
 
-        // Working directory
+        // Custom base instructions
+        if let Some(system_prompt) = config.system_prompt.as_deref().filter(|p| !p.is_empty()) {
+            cmd.arg("--append-system-prompt").arg(system_prompt);
+        }
+
+        // Working directory, plus linked directories the agent may also edit
         cmd.current_dir(&config.working_dir);
+        for dir in &config.extra_dirs {
+            cmd.arg("--add-dir").arg(dir);
+        }
 
         // Input format override (e.g. stream-json for structured input)
         if let Some(format) = &config.input_format {
@@ -89,12 +110,17 @@ impl ClaudeCodeRunner {
             cmd.arg(arg);
         }
 
+        // Per-agent environment (proxies, gateways, etc.)
+        cmd.envs(config.env.clone());
+
         // Use "--" to signal end of flags, so prompts starting with "-" (like "- [ ] task")
         // are not interpreted as CLI arguments
         if !use_stream_input && !config.prompt.is_empty() {
             cmd.arg("--").arg(&config.prompt);
         }
 
+        let mut cmd = config.execution_target.wrap(cmd)?;
+
         // Stdio setup for JSONL capture / streaming input
         let needs_stdin = config
             .input_format
@@ -109,7 +135,7 @@ impl ClaudeCodeRunner {
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
 
-        cmd
+        Ok(cmd)
     }
 
     /// Convert Claude-specific event to unified AgentEvent(s)
@@ -166,6 +192,11 @@ impl ClaudeCodeRunner {
                     })];
                 }
 
+                // Thinking blocks arrive ahead of the text they explain
+                if let Some(text) = assistant.extract_thinking() {
+                    events.push(AgentEvent::AssistantReasoning(ReasoningEvent { text }));
+                }
+
                 // Extract text content
                 let text = assistant.extract_text().unwrap_or_default();
                 if !text.is_empty() {
@@ -184,7 +215,7 @@ impl ClaudeCodeRunner {
                     }));
                 }
 
-                events
+                Self::wrap_subagent_events(events, assistant.parent_tool_use_id)
             }
             ClaudeRawEvent::ToolUse(tool) => {
                 let tool_name = tool.tool.or(tool.name).unwrap_or_default();
@@ -243,13 +274,22 @@ impl ClaudeCodeRunner {
                 }
                 // Result event always signals turn completion
                 // Use default values if usage is not provided
+                // Claude reports cache reads/writes separately from input_tokens;
+                // fold them in so input_tokens covers the whole prompt
                 let usage = res
                     .usage
-                    .map(|u| TokenUsage {
-                        input_tokens: u.input_tokens.unwrap_or(0),
-                        output_tokens: u.output_tokens.unwrap_or(0),
-                        cached_tokens: 0,
-                        total_tokens: u.input_tokens.unwrap_or(0) + u.output_tokens.unwrap_or(0),
+                    .map(|u| {
+                        let cache_read = u.cache_read_input_tokens.unwrap_or(0);
+                        let cache_creation = u.cache_creation_input_tokens.unwrap_or(0);
+                        let input = u.input_tokens.unwrap_or(0) + cache_creation + cache_read;
+                        let output = u.output_tokens.unwrap_or(0);
+                        TokenUsage {
+                            input_tokens: input,
+                            output_tokens: output,
+                            cached_tokens: cache_read,
+                            cache_creation_tokens: cache_creation,
+                            total_tokens: input + output,
+                        }
                     })
                     .unwrap_or_default();
 
@@ -276,13 +316,65 @@ impl ClaudeCodeRunner {
                         error: if is_error { Some(content) } else { None },
                     }));
                 }
-                events
+                Self::wrap_subagent_events(events, user.parent_tool_use_id)
             }
             ClaudeRawEvent::ControlRequest(_) => vec![],
             ClaudeRawEvent::Unknown => vec![],
         }
     }
 
+    /// Wrap events emitted from inside a subagent so they stay attached to
+    /// the Task call that spawned it.
+    fn wrap_subagent_events(
+        events: Vec<AgentEvent>,
+        parent_tool_use_id: Option<String>,
+    ) -> Vec<AgentEvent> {
+        let Some(parent_tool_id) = parent_tool_use_id else {
+            return events;
+        };
+        events
+            .into_iter()
+            .map(|event| {
+                AgentEvent::Subagent(SubagentEvent {
+                    parent_tool_id: parent_tool_id.clone(),
+                    event: Box::new(event),
+                })
+            })
+            .collect()
+    }
+
+    /// Turn Task tool calls into subagent lifecycle events. The Task result
+    /// only carries its tool ID, so open subagents are tracked across events.
+    fn track_subagent(event: AgentEvent, open_subagents: &mut HashSet<String>) -> AgentEvent {
+        match event {
+            AgentEvent::ToolStarted(tool) if tool.tool_name == SUBAGENT_TOOL_NAME => {
+                open_subagents.insert(tool.tool_id.clone());
+                let field = |key: &str| {
+                    tool.arguments
+                        .get(key)
+                        .and_then(|value| value.as_str())
+                        .map(str::to_string)
+                };
+                AgentEvent::SubagentStarted(SubagentStartedEvent {
+                    description: field("description").unwrap_or_default(),
+                    subagent_type: field("subagent_type"),
+                    prompt: field("prompt"),
+                    tool_id: tool.tool_id,
+                })
+            }
+            AgentEvent::ToolCompleted(tool) if open_subagents.contains(&tool.tool_id) => {
+                open_subagents.remove(&tool.tool_id);
+                AgentEvent::SubagentCompleted(SubagentCompletedEvent {
+                    tool_id: tool.tool_id,
+                    success: tool.success,
+                    result: tool.result,
+                    error: tool.error,
+                })
+            }
+            other => other,
+        }
+    }
+
     fn build_control_initialize_jsonl() -> String {
         let payload = json!({
             "type": "control_request",
@@ -322,6 +414,48 @@ impl Default for ClaudeCodeRunner {
     }
 }
 
+/// Converts recorded Claude stdout lines back into agent events for replay
+#[derive(Default)]
+pub(crate) struct ClaudeReplayDecoder {
+    open_subagents: HashSet<String>,
+}
+
+impl ClaudeReplayDecoder {
+    pub(crate) fn decode(&mut self, line: &str) -> Vec<AgentEvent> {
+        let raw_event = match serde_json::from_str::<ClaudeRawEvent>(line) {
+            Ok(raw_event) => raw_event,
+            Err(e) => {
+                tracing::warn!("Failed to parse replayed Claude line: {e}. Line: {line}");
+                return Vec::new();
+            }
+        };
+
+        if let ClaudeRawEvent::ControlRequest(request) = raw_event {
+            // Non-interactive requests were already answered during the recorded run
+            return match request.request {
+                ClaudeControlRequestType::CanUseTool {
+                    tool_name,
+                    input,
+                    tool_use_id,
+                } if ClaudeCodeRunner::is_interactive_tool(&tool_name) => {
+                    vec![AgentEvent::ControlRequest(ControlRequestEvent {
+                        request_id: request.request_id,
+                        tool_name,
+                        tool_use_id,
+                        input,
+                    })]
+                }
+                _ => Vec::new(),
+            };
+        }
+
+        ClaudeCodeRunner::convert_event(raw_event)
+            .into_iter()
+            .map(|event| ClaudeCodeRunner::track_subagent(event, &mut self.open_subagents))
+            .collect()
+    }
+}
+
 #[async_trait]
 impl AgentRunner for ClaudeCodeRunner {
     fn agent_type(&self) -> AgentType {
@@ -329,7 +463,7 @@ impl AgentRunner for ClaudeCodeRunner {
     }
 
     async fn start(&self, config: AgentStartConfig) -> Result<AgentHandle, AgentError> {
-        let mut cmd = self.build_command(&config);
+        let mut cmd = self.build_command(&config)?;
         let mut child = cmd.spawn()?;
 
         let use_stream_input = config
@@ -409,11 +543,16 @@ impl AgentRunner for ClaudeCodeRunner {
 
         let pid = child.id().ok_or(AgentError::ProcessSpawnFailed)?;
         let stdout = child.stdout.take().ok_or(AgentError::StdoutCaptureFailed)?;
-        let stderr = child.stderr.take();
+        // Drain stderr while the process runs so lines show up in diagnostics live
+        let stderr_task = child
+            .stderr
+            .take()
+            .map(|stderr| tokio::spawn(capture_stderr(stderr, config.stderr.clone(), "claude")));
 
         let (tx, rx) = mpsc::channel::<AgentEvent>(256);
         let tx_for_monitor = tx.clone();
         let control_tx = input_tx.clone();
+        let review_edits = config.review_edits;
 
         // Spawn JSONL parser task
         tokio::spawn(async move {
@@ -421,8 +560,9 @@ impl AgentRunner for ClaudeCodeRunner {
             let tx_for_parser = tx.clone();
 
             // Parse raw events
+            let recorder = AgentRecorder::from_env(AgentType::Claude);
             let parse_handle = tokio::spawn(async move {
-                if let Err(e) = JsonlStreamParser::parse_stream(stdout, raw_tx).await {
+                if let Err(e) = JsonlStreamParser::parse_stream(stdout, raw_tx, recorder).await {
                     if let Err(send_err) = tx_for_parser
                         .send(AgentEvent::Error(ErrorEvent {
                             message: format!("Stream parsing error: {}", e),
@@ -441,6 +581,7 @@ impl AgentRunner for ClaudeCodeRunner {
             });
 
             // Convert and forward events
+            let mut open_subagents = HashSet::new();
             'outer: while let Some(raw_event) = raw_rx.recv().await {
                 if let ClaudeRawEvent::ControlRequest(request) = &raw_event {
                     match &request.request {
@@ -449,7 +590,9 @@ impl AgentRunner for ClaudeCodeRunner {
                             input,
                             tool_use_id,
                         } => {
-                            if Self::is_interactive_tool(tool_name) {
+                            if Self::is_interactive_tool(tool_name)
+                                || (review_edits && edit_review::is_reviewed_tool(tool_name))
+                            {
                                 let event = AgentEvent::ControlRequest(ControlRequestEvent {
                                     request_id: request.request_id.clone(),
                                     tool_name: tool_name.clone(),
@@ -510,6 +653,7 @@ impl AgentRunner for ClaudeCodeRunner {
                 }
 
                 for event in Self::convert_event(raw_event) {
+                    let event = Self::track_subagent(event, &mut open_subagents);
                     if tx.send(event).await.is_err() {
                         break 'outer;
                     }
@@ -523,40 +667,25 @@ impl AgentRunner for ClaudeCodeRunner {
 
         // Monitor process exit and capture stderr
         tokio::spawn(async move {
-            use tokio::io::AsyncReadExt;
-
             let status = child.wait().await;
 
-            // Read stderr if available
-            let stderr_content = if let Some(mut stderr) = stderr {
-                let mut buf = String::new();
-                if let Err(e) = stderr.read_to_string(&mut buf).await {
-                    tracing::debug!(error = %e, "Failed to read Claude stderr");
-                }
-                buf
-            } else {
-                String::new()
+            let stderr_content = match stderr_task {
+                Some(task) => task.await.unwrap_or_else(|err| {
+                    tracing::debug!(error = %err, "Claude stderr task failed to join");
+                    String::new()
+                }),
+                None => String::new(),
             };
 
             // Check if process failed
             match status {
                 Ok(exit_status) if !exit_status.success() => {
-                    let error_msg = if stderr_content.is_empty() {
-                        format!("Claude process exited with status: {}", exit_status)
-                    } else {
-                        format!(
-                            "Claude process failed ({}): {}",
-                            exit_status,
-                            stderr_content.trim()
-                        )
-                    };
                     if let Err(send_err) = tx_for_monitor
-                        .send(AgentEvent::Error(ErrorEvent {
-                            message: error_msg,
-                            is_fatal: true,
-                            code: None,
-                            details: None,
-                        }))
+                        .send(AgentError::crash_event(
+                            "Claude",
+                            exit_status,
+                            &stderr_content,
+                        ))
                         .await
                     {
                         tracing::debug!(
@@ -693,11 +822,14 @@ mod tests {
                 usage: Some(ClaudeUsage {
                     input_tokens: Some(100),
                     output_tokens: Some(50),
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: None,
                 }),
             }),
             text: None,
             session_id: Some("50884eed-28b7-431e-9ad8-78b326696ae7".to_string()),
             error: None,
+            parent_tool_use_id: None,
         });
 
         let events = ClaudeCodeRunner::convert_event(raw);
@@ -712,6 +844,86 @@ mod tests {
         }
     }
 
+    /// Test that thinking blocks produce AssistantReasoning ahead of the reply
+    #[test]
+    fn test_convert_assistant_thinking_event() {
+        let raw = ClaudeRawEvent::Assistant(ClaudeAssistantEvent {
+            message: Some(ClaudeMessageObject {
+                model: None,
+                id: None,
+                role: Some("assistant".to_string()),
+                content: Some(vec![
+                    ClaudeContentBlock::Thinking {
+                        thinking: "Check the config first.".to_string(),
+                    },
+                    ClaudeContentBlock::Text {
+                        text: "Done.".to_string(),
+                    },
+                ]),
+                stop_reason: None,
+                usage: None,
+            }),
+            text: None,
+            session_id: None,
+            error: None,
+            parent_tool_use_id: None,
+        });
+
+        let events = ClaudeCodeRunner::convert_event(raw);
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            AgentEvent::AssistantReasoning(reasoning) => {
+                assert_eq!(reasoning.text, "Check the config first.");
+            }
+            other => panic!("Expected AssistantReasoning, got {:?}", other),
+        }
+        assert!(matches!(events[1], AgentEvent::AssistantMessage(_)));
+    }
+
+    /// Test that Task tool calls become subagent lifecycle events and that
+    /// events tagged with parent_tool_use_id are nested under them
+    #[test]
+    fn test_subagent_events_are_tracked_and_nested() {
+        let lines = [
+            r#"{"type":"assistant","message":{"content":[{"type":"tool_use","id":"task_1","name":"Task","input":{"description":"Find callers","subagent_type":"Explore","prompt":"Search"}}]}}"#,
+            r#"{"type":"assistant","parent_tool_use_id":"task_1","message":{"content":[{"type":"tool_use","id":"grep_1","name":"Grep","input":{"pattern":"foo"}}]}}"#,
+            r#"{"type":"user","parent_tool_use_id":"task_1","message":{"content":[{"type":"tool_result","tool_use_id":"grep_1","content":"a.rs"}]}}"#,
+            r#"{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"task_1","content":"Found 1 caller"}]}}"#,
+        ];
+
+        let mut open_subagents = HashSet::new();
+        let events: Vec<AgentEvent> = lines
+            .iter()
+            .map(|line| serde_json::from_str::<ClaudeRawEvent>(line).unwrap())
+            .flat_map(ClaudeCodeRunner::convert_event)
+            .map(|event| ClaudeCodeRunner::track_subagent(event, &mut open_subagents))
+            .collect();
+
+        assert_eq!(events.len(), 4);
+        match &events[0] {
+            AgentEvent::SubagentStarted(started) => {
+                assert_eq!(started.tool_id, "task_1");
+                assert_eq!(started.description, "Find callers");
+                assert_eq!(started.subagent_type.as_deref(), Some("Explore"));
+            }
+            other => panic!("Expected SubagentStarted, got {:?}", other),
+        }
+        for event in &events[1..3] {
+            match event {
+                AgentEvent::Subagent(nested) => assert_eq!(nested.parent_tool_id, "task_1"),
+                other => panic!("Expected Subagent, got {:?}", other),
+            }
+        }
+        match &events[3] {
+            AgentEvent::SubagentCompleted(completed) => {
+                assert_eq!(completed.tool_id, "task_1");
+                assert_eq!(completed.result.as_deref(), Some("Found 1 caller"));
+            }
+            other => panic!("Expected SubagentCompleted, got {:?}", other),
+        }
+        assert!(open_subagents.is_empty());
+    }
+
     /// Test that a result event with is_error produces TurnCompleted
     /// Note: The is_error field is currently not used to emit an error event
     #[test]
@@ -725,6 +937,8 @@ mod tests {
             usage: Some(ClaudeUsage {
                 input_tokens: Some(0),
                 output_tokens: Some(0),
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
             }),
         });
 
@@ -741,6 +955,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_result_usage_folds_cache_tokens_into_input() {
+        let raw = ClaudeRawEvent::Result(ClaudeResultEvent {
+            result: Some("Done".to_string()),
+            output: None,
+            is_error: Some(false),
+            error: None,
+            session_id: Some("test-session".to_string()),
+            usage: Some(ClaudeUsage {
+                input_tokens: Some(10),
+                output_tokens: Some(200),
+                cache_creation_input_tokens: Some(1_000),
+                cache_read_input_tokens: Some(30_000),
+            }),
+        });
+
+        match &ClaudeCodeRunner::convert_event(raw)[0] {
+            AgentEvent::TurnCompleted(completed) => {
+                assert_eq!(completed.usage.input_tokens, 31_010);
+                assert_eq!(completed.usage.cached_tokens, 30_000);
+                assert_eq!(completed.usage.cache_creation_tokens, 1_000);
+                assert_eq!(completed.usage.total_tokens, 31_210);
+            }
+            other => panic!("Expected TurnCompleted, got {:?}", other),
+        }
+    }
+
     /// Test the full auth failure sequence conversion
     /// This simulates what happens when Claude CLI returns an auth error
     #[test]
@@ -765,6 +1006,7 @@ mod tests {
                 text: None,
                 session_id: Some("test-session".to_string()),
                 error: Some("authentication_failed".to_string()),
+                parent_tool_use_id: None,
             }),
             ClaudeRawEvent::Result(ClaudeResultEvent {
                 result: Some("Invalid API key · Please run /login".to_string()),
@@ -775,6 +1017,8 @@ mod tests {
                 usage: Some(ClaudeUsage {
                     input_tokens: Some(0),
                     output_tokens: Some(0),
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: None,
                 }),
             }),
         ];
@@ -819,6 +1063,7 @@ mod tests {
             text: None,
             session_id: Some("test-session".to_string()),
             error: Some("authentication_failed".to_string()),
+            parent_tool_use_id: None,
         });
 
         let events = ClaudeCodeRunner::convert_event(raw);
@@ -859,6 +1104,7 @@ mod tests {
             text: None,
             session_id: Some("test-session".to_string()),
             error: None,
+            parent_tool_use_id: None,
         });
 
         let events = ClaudeCodeRunner::convert_event(raw);
@@ -891,7 +1137,7 @@ mod tests {
             PathBuf::from("/tmp"),
         );
 
-        let cmd = runner.build_command(&config);
+        let cmd = runner.build_command(&config).unwrap();
         let args = get_command_args(&cmd);
 
         // Find the position of "--" and verify the prompt comes after it
@@ -926,7 +1172,7 @@ mod tests {
         };
         let config = AgentStartConfig::new("Hello, can you help me?", PathBuf::from("/tmp"));
 
-        let cmd = runner.build_command(&config);
+        let cmd = runner.build_command(&config).unwrap();
         let args = get_command_args(&cmd);
 
         // Should still contain "--" for consistency
@@ -951,7 +1197,7 @@ mod tests {
         let config = AgentStartConfig::new("- continue with this task", PathBuf::from("/tmp"))
             .with_resume(SessionId::from_string("session-123".to_string()));
 
-        let cmd = runner.build_command(&config);
+        let cmd = runner.build_command(&config).unwrap();
         let args = get_command_args(&cmd);
 
         // Check command structure includes --resume, --, and prompt in correct order
@@ -996,7 +1242,7 @@ mod tests {
         };
         let config = AgentStartConfig::new("", PathBuf::from("/tmp"));
 
-        let cmd = runner.build_command(&config);
+        let cmd = runner.build_command(&config).unwrap();
         let args = get_command_args(&cmd);
 
         // Should NOT contain "--" when prompt is empty
@@ -1006,4 +1252,102 @@ mod tests {
             args
         );
     }
+
+    /// Test that profile flags land before the prompt and env vars are applied
+    #[test]
+    fn test_profile_args_and_env_applied() {
+        let runner = ClaudeCodeRunner {
+            binary_path: PathBuf::from("/usr/bin/claude"),
+        };
+        let config = AgentStartConfig::new("Hello", PathBuf::from("/tmp"))
+            .with_additional_args(vec!["--dangerously-skip-permissions".to_string()])
+            .with_env(vec![(
+                "ANTHROPIC_BASE_URL".to_string(),
+                "https://gateway.example.com".to_string(),
+            )]);
+
+        let cmd = runner.build_command(&config).unwrap();
+        let args = get_command_args(&cmd);
+
+        let flag_pos = args
+            .iter()
+            .position(|a| a == "--dangerously-skip-permissions")
+            .expect("profile flag missing");
+        let double_dash_pos = args.iter().position(|a| a == "--").expect("'--' missing");
+        assert!(flag_pos < double_dash_pos, "Args: {:?}", args);
+
+        let base_url = cmd
+            .as_std()
+            .get_envs()
+            .find(|(key, _)| *key == "ANTHROPIC_BASE_URL")
+            .and_then(|(_, value)| value);
+        assert_eq!(
+            base_url,
+            Some(std::ffi::OsStr::new("https://gateway.example.com"))
+        );
+    }
+
+    #[test]
+    fn test_edit_review_withholds_edit_tools() {
+        let runner = ClaudeCodeRunner {
+            binary_path: PathBuf::from("/usr/bin/claude"),
+        };
+        let tools = vec!["Read".to_string(), "Edit".to_string(), "Write".to_string()];
+        let config = AgentStartConfig::new("Hello", PathBuf::from("/tmp"))
+            .with_tools(tools.clone())
+            .with_edit_review(true);
+
+        let args = get_command_args(&runner.build_command(&config).unwrap());
+        let pos = args
+            .iter()
+            .position(|a| a == "--allowedTools")
+            .expect("--allowedTools missing");
+        assert_eq!(args[pos + 1], "Read");
+
+        let config = AgentStartConfig::new("Hello", PathBuf::from("/tmp")).with_tools(tools);
+        let args = get_command_args(&runner.build_command(&config).unwrap());
+        assert!(
+            args.contains(&"Read,Edit,Write".to_string()),
+            "Args: {:?}",
+            args
+        );
+    }
+
+    #[test]
+    fn test_system_prompt_appended() {
+        let runner = ClaudeCodeRunner {
+            binary_path: PathBuf::from("/usr/bin/claude"),
+        };
+        let config = AgentStartConfig::new("Hello", PathBuf::from("/tmp"))
+            .with_system_prompt("Always answer in French.");
+
+        let cmd = runner.build_command(&config).unwrap();
+        let args = get_command_args(&cmd);
+
+        let flag_pos = args
+            .iter()
+            .position(|a| a == "--append-system-prompt")
+            .expect("system prompt flag missing");
+        assert_eq!(args[flag_pos + 1], "Always answer in French.");
+        let double_dash_pos = args.iter().position(|a| a == "--").expect("'--' missing");
+        assert!(flag_pos < double_dash_pos, "Args: {:?}", args);
+    }
+
+    #[test]
+    fn test_extra_dirs_are_added() {
+        let runner = ClaudeCodeRunner {
+            binary_path: PathBuf::from("/usr/bin/claude"),
+        };
+        let config = AgentStartConfig::new("Hello", PathBuf::from("/work/api"))
+            .with_extra_dirs(vec![PathBuf::from("/work/client")]);
+
+        let args = get_command_args(&runner.build_command(&config).unwrap());
+        let flag_pos = args
+            .iter()
+            .position(|a| a == "--add-dir")
+            .expect("--add-dir missing");
+        assert_eq!(args[flag_pos + 1], "/work/client");
+        let double_dash_pos = args.iter().position(|a| a == "--").expect("'--' missing");
+        assert!(flag_pos < double_dash_pos, "Args: {:?}", args);
+    }
 }
@@ -25,13 +25,20 @@ use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{ChildStdin, Command};
 use tokio::sync::{mpsc, oneshot, Mutex};
 
+use crate::agent::diagnostics::capture_stderr;
 use crate::agent::error::AgentError;
 use crate::agent::events::{
-    AgentEvent, AssistantMessageEvent, CommandOutputEvent, ContextCompactionEvent, ErrorEvent,
-    FileChangedEvent, FileOperation, ReasoningEvent, SessionInitEvent, TokenUsage, TokenUsageEvent,
-    ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent, TurnFailedEvent,
+    AgentEvent, AssistantMessageEvent, CommandOutputDeltaEvent, CommandOutputEvent,
+    ContextCompactionEvent, ErrorEvent, FileChangedEvent, FileOperation, ReasoningEvent,
+    SessionInitEvent, TokenUsage, TokenUsageEvent, ToolCompletedEvent, ToolStartedEvent,
+    TurnCompletedEvent, TurnFailedEvent,
+};
+use crate::agent::exec_target::ExecutionTarget;
+use crate::agent::replay::AgentRecorder;
+use crate::agent::runner::{
+    AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType, CodexApprovalPolicy,
+    CodexSandboxMode,
 };
-use crate::agent::runner::{AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType};
 use crate::agent::session::SessionId;
 
 const CODEX_NPX_PACKAGE: &str = "@openai/codex";
@@ -105,15 +112,56 @@ impl JsonRpcPeer {
 
 #[derive(Default)]
 struct CodexEventState {
-    exec_command_by_id: HashMap<String, String>,
-    exec_output_by_id: HashMap<String, String>,
     last_usage: Option<TokenUsage>,
+    /// Thread totals when the current turn started (TokenCount is cumulative)
+    turn_start_usage: Option<TokenUsage>,
     last_total_tokens: Option<i64>,
     pending_compaction: bool,
     message_stream_source: Option<MessageStreamSource>,
     reasoning_stream_source: Option<ReasoningStreamSource>,
 }
 
+/// Converts recorded app-server output lines back into agent events for replay
+#[derive(Default)]
+pub(crate) struct CodexReplayDecoder {
+    state: CodexEventState,
+}
+
+impl CodexReplayDecoder {
+    pub(crate) fn decode(&mut self, line: &str) -> Vec<AgentEvent> {
+        match serde_json::from_str::<JSONRPCMessage>(line) {
+            Ok(JSONRPCMessage::Notification(notification))
+                if notification.method.starts_with("codex/event/") =>
+            {
+                let Some(params) = notification.params else {
+                    return Vec::new();
+                };
+                match serde_json::from_value::<CodexNotificationParams>(params) {
+                    Ok(codex_params) => {
+                        CodexCliRunner::convert_event(&codex_params.msg, &mut self.state)
+                    }
+                    Err(err) => {
+                        tracing::warn!(error = %err, "Failed to parse replayed codex event");
+                        Vec::new()
+                    }
+                }
+            }
+            Ok(JSONRPCMessage::Error(err)) => vec![AgentEvent::Error(ErrorEvent {
+                message: format!("[Error {}] {}", err.error.code, err.error.message),
+                is_fatal: true,
+                code: None,
+                details: None,
+            })],
+            // Responses and approval requests were handled during the recorded run
+            Ok(_) => Vec::new(),
+            Err(err) => {
+                tracing::warn!(error = %err, "Non-JSON line in replayed codex output");
+                Vec::new()
+            }
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum MessageStreamSource {
     LegacyDelta,
@@ -159,58 +207,96 @@ impl CodexCliRunner {
         }
     }
 
-    fn approval_policy() -> AskForApproval {
-        match std::env::var("CODEX_APPROVAL_POLICY")
-            .unwrap_or_else(|_| "never".to_string())
-            .to_lowercase()
-            .as_str()
-        {
-            "untrusted" => AskForApproval::UnlessTrusted,
-            "on-failure" => AskForApproval::OnFailure,
-            "on-request" => AskForApproval::OnRequest,
-            "never" => AskForApproval::Never,
-            _ => AskForApproval::Never,
+    fn approval_policy(config: &AgentStartConfig) -> AskForApproval {
+        let policy = config.codex_approval.unwrap_or_else(|| {
+            std::env::var("CODEX_APPROVAL_POLICY")
+                .ok()
+                .and_then(|value| CodexApprovalPolicy::parse(&value))
+                .unwrap_or_default()
+        });
+        match policy {
+            CodexApprovalPolicy::Untrusted => AskForApproval::UnlessTrusted,
+            CodexApprovalPolicy::OnFailure => AskForApproval::OnFailure,
+            CodexApprovalPolicy::OnRequest => AskForApproval::OnRequest,
+            CodexApprovalPolicy::Never => AskForApproval::Never,
         }
     }
 
-    fn sandbox_mode() -> SandboxMode {
-        match std::env::var("CODEX_SANDBOX_MODE")
-            .unwrap_or_else(|_| "danger-full-access".to_string())
-            .to_lowercase()
-            .as_str()
-        {
-            "read-only" => SandboxMode::ReadOnly,
-            "workspace-write" => SandboxMode::WorkspaceWrite,
-            "danger-full-access" => SandboxMode::DangerFullAccess,
-            _ => SandboxMode::DangerFullAccess,
+    fn sandbox_mode(config: &AgentStartConfig) -> SandboxMode {
+        let mode = config.codex_sandbox.unwrap_or_else(|| {
+            std::env::var("CODEX_SANDBOX_MODE")
+                .ok()
+                .and_then(|value| CodexSandboxMode::parse(&value))
+                .unwrap_or_default()
+        });
+        match mode {
+            CodexSandboxMode::ReadOnly => SandboxMode::ReadOnly,
+            CodexSandboxMode::WorkspaceWrite => SandboxMode::WorkspaceWrite,
+            CodexSandboxMode::DangerFullAccess => SandboxMode::DangerFullAccess,
+        }
+    }
+
+    /// `-c` override letting the workspace-write sandbox also edit the
+    /// linked directories
+    fn writable_roots_args(config: &AgentStartConfig) -> Vec<String> {
+        if config.extra_dirs.is_empty() {
+            return Vec::new();
         }
+        let roots: Vec<String> = config
+            .extra_dirs
+            .iter()
+            .map(|dir| Value::from(dir.to_string_lossy().into_owned()).to_string())
+            .collect();
+        vec![
+            "-c".to_string(),
+            format!(
+                "sandbox_workspace_write.writable_roots=[{}]",
+                roots.join(",")
+            ),
+        ]
     }
 
-    fn build_codex_command(&self, cwd: &PathBuf) -> io::Result<Command> {
+    fn build_codex_command(&self, config: &AgentStartConfig) -> io::Result<Command> {
         let mut cmd = Command::new(&self.binary_path);
+        // Extra flags are global options, so they go before the subcommand
+        cmd.args(&config.additional_args);
+        cmd.args(Self::writable_roots_args(config));
         cmd.arg("app-server");
-        cmd.current_dir(cwd);
+        cmd.current_dir(&config.working_dir);
+        cmd.env("NODE_NO_WARNINGS", "1");
+        cmd.env("NO_COLOR", "1");
+        cmd.envs(config.env.clone());
+        let mut cmd = config.execution_target.wrap(cmd)?;
         cmd.stdin(std::process::Stdio::piped());
         cmd.stdout(std::process::Stdio::piped());
         cmd.stderr(std::process::Stdio::piped());
-        cmd.env("NODE_NO_WARNINGS", "1");
-        cmd.env("NO_COLOR", "1");
         Ok(cmd)
     }
 
-    fn build_npx_command(&self, cwd: &PathBuf) -> io::Result<Command> {
+    fn build_npx_command(&self, config: &AgentStartConfig) -> io::Result<Command> {
         let mut cmd = Command::new("npx");
-        cmd.args(["-y", &Self::npx_package(), "app-server"]);
-        cmd.current_dir(cwd);
+        cmd.args(["-y", &Self::npx_package()]);
+        cmd.args(&config.additional_args);
+        cmd.args(Self::writable_roots_args(config));
+        cmd.arg("app-server");
+        cmd.current_dir(&config.working_dir);
+        cmd.env("NODE_NO_WARNINGS", "1");
+        cmd.env("NO_COLOR", "1");
+        cmd.envs(config.env.clone());
+        let mut cmd = config.execution_target.wrap(cmd)?;
         cmd.stdin(std::process::Stdio::piped());
         cmd.stdout(std::process::Stdio::piped());
         cmd.stderr(std::process::Stdio::piped());
-        cmd.env("NODE_NO_WARNINGS", "1");
-        cmd.env("NO_COLOR", "1");
         Ok(cmd)
     }
 
-    fn build_input_items(prompt: &str, images: &[PathBuf]) -> io::Result<Vec<InputItem>> {
+    /// Prompt items for Codex. Images are passed by path when Codex runs on
+    /// the host; a container can't see host files, so they're inlined instead.
+    fn build_input_items(
+        prompt: &str,
+        images: &[PathBuf],
+        images_by_path: bool,
+    ) -> io::Result<Vec<InputItem>> {
         let mut items = Vec::new();
         if !prompt.trim().is_empty() {
             items.push(InputItem::Text {
@@ -218,8 +304,14 @@ impl CodexCliRunner {
             });
         }
         for image in images {
-            let image_url = Self::encode_image_as_data_url(image)?;
-            items.push(InputItem::Image { image_url });
+            if images_by_path {
+                items.push(InputItem::LocalImage {
+                    path: image.clone(),
+                });
+            } else {
+                let image_url = Self::encode_image_as_data_url(image)?;
+                items.push(InputItem::Image { image_url });
+            }
         }
         Ok(items)
     }
@@ -257,12 +349,25 @@ impl CodexCliRunner {
     fn convert_event(event: &EventMsg, state: &mut CodexEventState) -> Vec<AgentEvent> {
         match event {
             EventMsg::TurnStarted(_) => {
+                state.turn_start_usage = state.last_usage.clone();
                 state.message_stream_source = None;
                 state.reasoning_stream_source = None;
                 vec![AgentEvent::TurnStarted]
             }
             EventMsg::TurnComplete(_) => {
-                let usage = state.last_usage.clone().unwrap_or_default();
+                // Report this turn's share so per-turn sums (and costs) don't double count
+                let total = state.last_usage.clone().unwrap_or_default();
+                let start = state
+                    .turn_start_usage
+                    .replace(total.clone())
+                    .unwrap_or_default();
+                let usage = TokenUsage {
+                    input_tokens: (total.input_tokens - start.input_tokens).max(0),
+                    output_tokens: (total.output_tokens - start.output_tokens).max(0),
+                    cached_tokens: (total.cached_tokens - start.cached_tokens).max(0),
+                    cache_creation_tokens: 0,
+                    total_tokens: (total.total_tokens - start.total_tokens).max(0),
+                };
                 state.message_stream_source = None;
                 state.reasoning_stream_source = None;
                 vec![AgentEvent::TurnCompleted(TurnCompletedEvent { usage })]
@@ -363,13 +468,6 @@ impl CodexCliRunner {
             },
             EventMsg::ExecCommandBegin(cmd) => {
                 let command_str = cmd.command.join(" ");
-                state
-                    .exec_command_by_id
-                    .insert(cmd.call_id.clone(), command_str.clone());
-                state
-                    .exec_output_by_id
-                    .insert(cmd.call_id.clone(), String::new());
-
                 vec![AgentEvent::ToolStarted(ToolStartedEvent {
                     tool_name: "Bash".to_string(),
                     tool_id: cmd.call_id.clone(),
@@ -377,22 +475,9 @@ impl CodexCliRunner {
                 })]
             }
             EventMsg::ExecCommandOutputDelta(delta) => {
-                let chunk = String::from_utf8_lossy(&delta.chunk).to_string();
-                let entry = state
-                    .exec_output_by_id
-                    .entry(delta.call_id.clone())
-                    .or_default();
-                entry.push_str(&chunk);
-                let command = state
-                    .exec_command_by_id
-                    .get(&delta.call_id)
-                    .cloned()
-                    .unwrap_or_default();
-                vec![AgentEvent::CommandOutput(CommandOutputEvent {
-                    command,
-                    output: entry.clone(),
-                    exit_code: None,
-                    is_streaming: true,
+                vec![AgentEvent::CommandOutputDelta(CommandOutputDeltaEvent {
+                    tool_id: delta.call_id.clone(),
+                    chunk: String::from_utf8_lossy(&delta.chunk).to_string(),
                 })]
             }
             EventMsg::ExecCommandEnd(end) => {
@@ -402,8 +487,6 @@ impl CodexCliRunner {
                     format!("{}{}", end.stdout, end.stderr)
                 };
                 let command = end.command.join(" ");
-                state.exec_output_by_id.remove(&end.call_id);
-                state.exec_command_by_id.remove(&end.call_id);
                 vec![AgentEvent::CommandOutput(CommandOutputEvent {
                     command,
                     output,
@@ -507,6 +590,8 @@ impl CodexCliRunner {
                         input_tokens: total.input_tokens,
                         output_tokens: total.output_tokens,
                         cached_tokens: total.cached_input_tokens,
+                        // OpenAI doesn't report (or surcharge) cache writes
+                        cache_creation_tokens: 0,
                         total_tokens: total.total_tokens,
                     };
                     let context_window = info.model_context_window;
@@ -585,8 +670,9 @@ impl CodexCliRunner {
         conversation_id: codex_protocol::ThreadId,
         prompt: &str,
         images: &[PathBuf],
+        images_by_path: bool,
     ) -> io::Result<()> {
-        let items = Self::build_input_items(prompt, images)?;
+        let items = Self::build_input_items(prompt, images, images_by_path)?;
         if items.is_empty() {
             return Ok(());
         }
@@ -601,9 +687,12 @@ impl CodexCliRunner {
         Ok(())
     }
 
-    async fn spawn_app_server(&self, cwd: &PathBuf) -> Result<tokio::process::Child, AgentError> {
+    async fn spawn_app_server(
+        &self,
+        config: &AgentStartConfig,
+    ) -> Result<tokio::process::Child, AgentError> {
         if self.binary_path.exists() {
-            let mut cmd = self.build_codex_command(cwd)?;
+            let mut cmd = self.build_codex_command(config)?;
             match cmd.spawn() {
                 Ok(child) => return Ok(child),
                 Err(err) => {
@@ -612,7 +701,7 @@ impl CodexCliRunner {
             }
         }
 
-        let mut cmd = self.build_npx_command(cwd)?;
+        let mut cmd = self.build_npx_command(config)?;
         let child = cmd.spawn()?;
         Ok(child)
     }
@@ -625,7 +714,7 @@ impl AgentRunner for CodexCliRunner {
     }
 
     async fn start(&self, config: AgentStartConfig) -> Result<AgentHandle, AgentError> {
-        let mut child = self.spawn_app_server(&config.working_dir).await?;
+        let mut child = self.spawn_app_server(&config).await?;
         let pid = child.id().ok_or(AgentError::ProcessSpawnFailed)?;
 
         let stdin = child
@@ -633,7 +722,10 @@ impl AgentRunner for CodexCliRunner {
             .take()
             .ok_or_else(|| AgentError::Io(io::Error::other("failed to capture stdin")))?;
         let stdout = child.stdout.take().ok_or(AgentError::StdoutCaptureFailed)?;
-        let stderr = child.stderr.take();
+        let stderr_task = child
+            .stderr
+            .take()
+            .map(|stderr| tokio::spawn(capture_stderr(stderr, config.stderr.clone(), "codex")));
 
         let peer = JsonRpcPeer::new(stdin);
 
@@ -647,6 +739,7 @@ impl AgentRunner for CodexCliRunner {
             let mut reader = BufReader::new(stdout);
             let mut buffer = String::new();
             let mut state = CodexEventState::default();
+            let mut recorder = AgentRecorder::from_env(AgentType::Codex);
 
             loop {
                 buffer.clear();
@@ -657,6 +750,9 @@ impl AgentRunner for CodexCliRunner {
                         if line.is_empty() {
                             continue;
                         }
+                        if let Some(recorder) = recorder.as_mut() {
+                            recorder.record(line);
+                        }
 
                         match serde_json::from_str::<JSONRPCMessage>(line) {
                             Ok(JSONRPCMessage::Response(response)) => {
@@ -799,10 +895,10 @@ impl AgentRunner for CodexCliRunner {
                         model_provider: None,
                         profile: None,
                         cwd: Some(config.working_dir.to_string_lossy().to_string()),
-                        approval_policy: Some(Self::approval_policy()),
-                        sandbox: Some(Self::sandbox_mode()),
+                        approval_policy: Some(Self::approval_policy(&config)),
+                        sandbox: Some(Self::sandbox_mode(&config)),
                         config: None,
-                        base_instructions: None,
+                        base_instructions: config.system_prompt.clone(),
                         developer_instructions: None,
                         compact_prompt: None,
                         include_apply_patch_tool: None,
@@ -821,10 +917,10 @@ impl AgentRunner for CodexCliRunner {
                     model: config.model.clone(),
                     profile: None,
                     cwd: Some(config.working_dir.to_string_lossy().to_string()),
-                    approval_policy: Some(Self::approval_policy()),
-                    sandbox: Some(Self::sandbox_mode()),
+                    approval_policy: Some(Self::approval_policy(&config)),
+                    sandbox: Some(Self::sandbox_mode(&config)),
                     config: None,
-                    base_instructions: None,
+                    base_instructions: config.system_prompt.clone(),
                     include_apply_patch_tool: None,
                     model_provider: None,
                     compact_prompt: None,
@@ -864,6 +960,7 @@ impl AgentRunner for CodexCliRunner {
         let (input_tx, mut input_rx) = mpsc::channel::<AgentInput>(32);
         let input_peer = peer.clone();
         let input_conversation_id = conversation_id;
+        let images_by_path = config.execution_target == ExecutionTarget::Host;
         tokio::spawn(async move {
             while let Some(input) = input_rx.recv().await {
                 match input {
@@ -873,6 +970,7 @@ impl AgentRunner for CodexCliRunner {
                             input_conversation_id,
                             &text,
                             &images,
+                            images_by_path,
                         )
                         .await
                         {
@@ -893,43 +991,36 @@ impl AgentRunner for CodexCliRunner {
 
         // Send initial prompt if present
         if !config.prompt.trim().is_empty() || !config.images.is_empty() {
-            Self::send_user_message(&peer, conversation_id, &config.prompt, &config.images).await?;
+            Self::send_user_message(
+                &peer,
+                conversation_id,
+                &config.prompt,
+                &config.images,
+                images_by_path,
+            )
+            .await?;
         }
 
         // Monitor process and capture stderr on failure
         tokio::spawn(async move {
-            use tokio::io::AsyncReadExt;
-
             let status = child.wait().await;
 
-            let stderr_content = if let Some(mut stderr) = stderr {
-                let mut buf = String::new();
-                if let Err(err) = stderr.read_to_string(&mut buf).await {
-                    tracing::debug!(error = %err, "Failed to read Codex stderr");
-                }
-                buf
-            } else {
-                String::new()
+            let stderr_content = match stderr_task {
+                Some(task) => task.await.unwrap_or_else(|err| {
+                    tracing::debug!(error = %err, "Codex stderr task failed to join");
+                    String::new()
+                }),
+                None => String::new(),
             };
 
             match status {
                 Ok(exit_status) if !exit_status.success() => {
-                    let error_msg = if stderr_content.is_empty() {
-                        format!("Codex process exited with status: {}", exit_status)
-                    } else {
-                        format!(
-                            "Codex process failed ({}): {}",
-                            exit_status,
-                            stderr_content.trim()
-                        )
-                    };
                     if let Err(err) = tx_for_monitor
-                        .send(AgentEvent::Error(ErrorEvent {
-                            message: error_msg,
-                            is_fatal: true,
-                            code: None,
-                            details: None,
-                        }))
+                        .send(AgentError::crash_event(
+                            "Codex",
+                            exit_status,
+                            &stderr_content,
+                        ))
                         .await
                     {
                         tracing::debug!(
@@ -1047,9 +1138,44 @@ mod tests {
             .save(&path)
             .expect("failed to write temp image");
 
-        let items = CodexCliRunner::build_input_items("hello", &[PathBuf::from(&path)]).unwrap();
+        let images = [PathBuf::from(&path)];
+        let items = CodexCliRunner::build_input_items("hello", &images, false).unwrap();
         assert_eq!(items.len(), 2);
         assert!(matches!(items[0], InputItem::Text { .. }));
         assert!(matches!(items[1], InputItem::Image { .. }));
+
+        let items = CodexCliRunner::build_input_items("hello", &images, true).unwrap();
+        assert!(matches!(&items[1], InputItem::LocalImage { path: local } if *local == images[0]));
+    }
+
+    #[test]
+    fn test_session_overrides_select_sandbox_and_approval() {
+        let config = AgentStartConfig::new("hi", PathBuf::from("/tmp"))
+            .with_codex_sandbox(CodexSandboxMode::ReadOnly)
+            .with_codex_approval(CodexApprovalPolicy::OnRequest);
+
+        assert!(matches!(
+            CodexCliRunner::sandbox_mode(&config),
+            SandboxMode::ReadOnly
+        ));
+        assert!(matches!(
+            CodexCliRunner::approval_policy(&config),
+            AskForApproval::OnRequest
+        ));
+    }
+
+    #[test]
+    fn test_extra_dirs_become_writable_roots() {
+        let config = AgentStartConfig::new("hi", PathBuf::from("/work/api"));
+        assert!(CodexCliRunner::writable_roots_args(&config).is_empty());
+
+        let config = config.with_extra_dirs(vec![PathBuf::from("/work/client")]);
+        assert_eq!(
+            CodexCliRunner::writable_roots_args(&config),
+            vec![
+                "-c".to_string(),
+                r#"sandbox_workspace_write.writable_roots=["/work/client"]"#.to_string(),
+            ]
+        );
     }
 }
@@ -0,0 +1,150 @@
+//! Per-session capture of agent process stderr.
+//!
+//! CLI agents report auth failures, runtime warnings, and crashes on stderr,
+//! which never reaches the event stream. Runners copy each stderr line into a
+//! [`StderrBuffer`] owned by the session so it can be inspected after a run.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+
+/// Default number of stderr lines kept per session
+pub const DEFAULT_STDERR_LINES: usize = 200;
+
+/// A single captured stderr line
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StderrLine {
+    pub timestamp: DateTime<Utc>,
+    pub line: String,
+}
+
+/// Bounded, shareable buffer holding the most recent stderr lines.
+///
+/// Clones share the same storage, so a runner can write while the UI reads.
+#[derive(Debug, Clone)]
+pub struct StderrBuffer {
+    lines: Arc<Mutex<VecDeque<StderrLine>>>,
+    capacity: usize,
+}
+
+impl StderrBuffer {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            lines: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Append a line, dropping the oldest one once the buffer is full
+    pub fn push(&self, line: impl Into<String>) {
+        let entry = StderrLine {
+            timestamp: Utc::now(),
+            line: line.into(),
+        };
+        let mut lines = self.lock();
+        while lines.len() >= self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(entry);
+    }
+
+    /// Snapshot of the buffered lines, oldest first
+    pub fn lines(&self) -> Vec<StderrLine> {
+        self.lock().iter().cloned().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<StderrLine>> {
+        // A panic while holding the lock can't leave the deque inconsistent
+        self.lines
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for StderrBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_STDERR_LINES)
+    }
+}
+
+/// Read an agent's stderr to the end, recording each line in `buffer`.
+///
+/// Returns the full stderr text so runners can still build failure messages.
+pub async fn capture_stderr<R>(reader: R, buffer: Option<StderrBuffer>, agent: &str) -> String
+where
+    R: AsyncRead + Unpin,
+{
+    let mut reader = BufReader::new(reader);
+    let mut content = String::new();
+    let mut raw = Vec::new();
+    loop {
+        raw.clear();
+        match reader.read_until(b'\n', &mut raw).await {
+            Ok(0) => break,
+            Ok(_) => {
+                let line = String::from_utf8_lossy(&raw);
+                content.push_str(&line);
+                let trimmed = line.trim_end();
+                if !trimmed.is_empty() {
+                    if let Some(buffer) = buffer.as_ref() {
+                        buffer.push(trimmed);
+                    }
+                }
+            }
+            Err(err) => {
+                tracing::debug!(error = %err, agent, "Failed to read agent stderr");
+                break;
+            }
+        }
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stderr_buffer_keeps_most_recent_lines() {
+        let buffer = StderrBuffer::new(2);
+        let reader = buffer.clone();
+        buffer.push("one");
+        buffer.push("two");
+        buffer.push("three");
+
+        let lines: Vec<String> = reader.lines().into_iter().map(|l| l.line).collect();
+        assert_eq!(lines, vec!["two".to_string(), "three".to_string()]);
+
+        reader.clear();
+        assert!(buffer.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_capture_stderr_records_lines_and_returns_text() {
+        let buffer = StderrBuffer::new(10);
+        let input: &[u8] = b"warning: deprecated\n\nError: not logged in";
+        let content = capture_stderr(input, Some(buffer.clone()), "test").await;
+
+        assert_eq!(content, "warning: deprecated\n\nError: not logged in");
+        let lines: Vec<String> = buffer.lines().into_iter().map(|l| l.line).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "warning: deprecated".to_string(),
+                "Error: not logged in".to_string()
+            ]
+        );
+    }
+}
@@ -0,0 +1,481 @@
+//! Hunk-by-hunk review of file edits proposed by Claude's edit tools.
+//!
+//! With edit review on, `Edit`, `MultiEdit` and `Write` calls reach the UI as
+//! permission requests instead of being allowed automatically. The proposed
+//! file is computed here by applying the tool input to the file on disk, then
+//! diffed into hunks the user accepts, rejects or rewrites. Whatever survives
+//! review is handed back to the tool as its (possibly rewritten) input, so the
+//! worktree is only ever touched by the agent's own tool.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::util::{normalize_workspace_path, resolve_workspace_path};
+
+/// Claude tools whose calls are held for review
+pub const REVIEWED_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write"];
+
+/// Unchanged lines shown around each hunk
+const CONTEXT_LINES: usize = 3;
+
+/// Largest line-pair table diffed exactly; bigger rewrites become one hunk
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// Whether calls to `tool_name` are held for review
+pub fn is_reviewed_tool(tool_name: &str) -> bool {
+    REVIEWED_TOOLS.contains(&tool_name)
+}
+
+#[derive(Error, Debug)]
+pub enum EditReviewError {
+    #[error("Missing `{0}` in tool input")]
+    MissingField(&'static str),
+    #[error("Tool {0} doesn't edit files")]
+    UnsupportedTool(String),
+    #[error("Text to replace not found in {0}")]
+    NotFound(String),
+    #[error("Text to replace occurs {count} times in {path}")]
+    NotUnique { path: String, count: usize },
+    #[error("{0} already exists")]
+    AlreadyExists(String),
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// One contiguous change; lines keep their line endings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    /// Index of the first replaced line in the original file
+    pub old_start: usize,
+    pub old_lines: Vec<String>,
+    pub new_lines: Vec<String>,
+    /// Unchanged lines just before the hunk
+    pub context_before: Vec<String>,
+    /// Unchanged lines just after the hunk
+    pub context_after: Vec<String>,
+}
+
+impl Hunk {
+    /// The hunk's proposed text, as offered for editing
+    pub fn new_text(&self) -> String {
+        self.new_lines.concat()
+    }
+}
+
+/// What the user decided for a hunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HunkDecision {
+    Accept,
+    Reject,
+    /// Replace the hunk's lines with this text instead
+    Edited(String),
+}
+
+/// What to tell the tool once review is done
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewOutcome {
+    /// Every hunk accepted as proposed: run the tool unchanged
+    AsProposed,
+    /// Nothing survived review: deny the tool call
+    Rejected,
+    /// Run the tool with input rewritten to produce `content`
+    Reviewed {
+        content: String,
+        updated_input: Value,
+        accepted: usize,
+        edited: usize,
+        rejected: usize,
+    },
+}
+
+/// An edit tool call awaiting review
+#[derive(Debug, Clone)]
+pub struct EditReview {
+    pub tool_name: String,
+    /// Tool input as requested by the agent
+    pub input: Value,
+    /// Path relative to the workspace root when inside it
+    pub display_path: String,
+    /// File contents before the edit (`None` when the edit creates the file)
+    pub original: Option<String>,
+    pub hunks: Vec<Hunk>,
+    /// One decision per hunk; every hunk starts accepted
+    pub decisions: Vec<HunkDecision>,
+}
+
+impl EditReview {
+    /// Compute the change an edit tool call would make to the file on disk.
+    pub fn from_tool_call(
+        tool_name: &str,
+        input: &Value,
+        root: &Path,
+    ) -> Result<Self, EditReviewError> {
+        if !is_reviewed_tool(tool_name) {
+            return Err(EditReviewError::UnsupportedTool(tool_name.to_string()));
+        }
+        let raw_path = str_field(input, "file_path")?;
+        let display_path = normalize_workspace_path(raw_path, Some(root));
+        let original = match fs::read_to_string(resolve_workspace_path(raw_path, root)) {
+            Ok(content) => Some(content),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err.into()),
+        };
+
+        let proposed = match tool_name {
+            "Write" => str_field(input, "content")?.to_string(),
+            "Edit" => apply_replacement(original.as_deref(), input, &display_path)?,
+            _ => {
+                let edits = input
+                    .get("edits")
+                    .and_then(Value::as_array)
+                    .ok_or(EditReviewError::MissingField("edits"))?;
+                let mut content = original.clone();
+                for edit in edits {
+                    content = Some(apply_replacement(content.as_deref(), edit, &display_path)?);
+                }
+                content.unwrap_or_default()
+            }
+        };
+
+        let hunks = diff_hunks(original.as_deref().unwrap_or(""), &proposed);
+        Ok(Self {
+            tool_name: tool_name.to_string(),
+            input: input.clone(),
+            display_path,
+            original,
+            decisions: vec![HunkDecision::Accept; hunks.len()],
+            hunks,
+        })
+    }
+
+    /// Whether the edit creates a new file
+    pub fn creates_file(&self) -> bool {
+        self.original.is_none()
+    }
+
+    /// The file as it would be written with the current decisions
+    pub fn reviewed_content(&self) -> String {
+        let original = self.original.as_deref().unwrap_or("");
+        let lines: Vec<&str> = original.split_inclusive('\n').collect();
+        let mut content = String::with_capacity(original.len());
+        let mut next = 0;
+        for (hunk, decision) in self.hunks.iter().zip(&self.decisions) {
+            content.push_str(&lines[next..hunk.old_start].concat());
+            match decision {
+                HunkDecision::Accept => content.push_str(&hunk.new_lines.concat()),
+                HunkDecision::Reject => content.push_str(&hunk.old_lines.concat()),
+                HunkDecision::Edited(text) => {
+                    content.push_str(&fit_line_ending(text, &hunk.new_text()))
+                }
+            }
+            next = hunk.old_start + hunk.old_lines.len();
+        }
+        content.push_str(&lines[next..].concat());
+        content
+    }
+
+    /// Resolve the decisions into the reply for the tool call
+    pub fn outcome(&self) -> ReviewOutcome {
+        let count =
+            |wanted: fn(&HunkDecision) -> bool| self.decisions.iter().filter(|d| wanted(d)).count();
+        let accepted = count(|d| matches!(d, HunkDecision::Accept));
+        let edited = count(|d| matches!(d, HunkDecision::Edited(_)));
+        let rejected = count(|d| matches!(d, HunkDecision::Reject));
+
+        if accepted == self.decisions.len() {
+            return ReviewOutcome::AsProposed;
+        }
+        let content = self.reviewed_content();
+        let unchanged = match &self.original {
+            Some(original) => *original == content,
+            None => accepted == 0 && edited == 0,
+        };
+        if unchanged {
+            return ReviewOutcome::Rejected;
+        }
+        ReviewOutcome::Reviewed {
+            updated_input: self.updated_input(&content),
+            content,
+            accepted,
+            edited,
+            rejected,
+        }
+    }
+
+    /// Tool input that makes the same tool write `content` instead.
+    ///
+    /// Edit-style tools replace the whole original text, which is trivially
+    /// unique in the file.
+    fn updated_input(&self, content: &str) -> Value {
+        let file_path = self.input.get("file_path").cloned().unwrap_or(Value::Null);
+        let old_string = self.original.as_deref().unwrap_or("");
+        match self.tool_name.as_str() {
+            "Write" => json!({ "file_path": file_path, "content": content }),
+            "Edit" => json!({
+                "file_path": file_path,
+                "old_string": old_string,
+                "new_string": content,
+            }),
+            _ => json!({
+                "file_path": file_path,
+                "edits": [{ "old_string": old_string, "new_string": content }],
+            }),
+        }
+    }
+}
+
+fn str_field<'a>(input: &'a Value, field: &'static str) -> Result<&'a str, EditReviewError> {
+    input
+        .get(field)
+        .and_then(Value::as_str)
+        .ok_or(EditReviewError::MissingField(field))
+}
+
+/// Apply one `old_string` -> `new_string` replacement the way Claude's Edit
+/// tool does: the text must occur exactly once unless `replace_all` is set,
+/// and an empty `old_string` creates the file.
+fn apply_replacement(
+    content: Option<&str>,
+    edit: &Value,
+    path: &str,
+) -> Result<String, EditReviewError> {
+    let old = str_field(edit, "old_string")?;
+    let new = str_field(edit, "new_string")?;
+    let replace_all = edit
+        .get("replace_all")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+
+    if old.is_empty() {
+        return match content {
+            None | Some("") => Ok(new.to_string()),
+            Some(_) => Err(EditReviewError::AlreadyExists(path.to_string())),
+        };
+    }
+    let content = content.ok_or_else(|| EditReviewError::NotFound(path.to_string()))?;
+    match content.matches(old).count() {
+        0 => Err(EditReviewError::NotFound(path.to_string())),
+        1 => Ok(content.replacen(old, new, 1)),
+        _ if replace_all => Ok(content.replace(old, new)),
+        count => Err(EditReviewError::NotUnique {
+            path: path.to_string(),
+            count,
+        }),
+    }
+}
+
+/// Keep an edited hunk's trailing newline consistent with the proposal, since
+/// editors add or drop the final newline on save.
+fn fit_line_ending(text: &str, proposed: &str) -> String {
+    match (proposed.ends_with('\n'), text.ends_with('\n')) {
+        (true, false) if !text.is_empty() => format!("{}\n", text),
+        (false, true) => text[..text.len() - 1].to_string(),
+        _ => text.to_string(),
+    }
+}
+
+/// Line diff of `old` against `new`, one hunk per contiguous change
+pub fn diff_hunks(old: &str, new: &str) -> Vec<Hunk> {
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+
+    // Trim the common prefix and suffix so the table only covers the change
+    let prefix = old_lines
+        .iter()
+        .zip(&new_lines)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old_lines[prefix..old_lines.len() - suffix];
+    let b = &new_lines[prefix..new_lines.len() - suffix];
+
+    if a.is_empty() && b.is_empty() {
+        return Vec::new();
+    }
+
+    // Changed regions as (old_start, old_end, new_start, new_end)
+    let mut regions = Vec::new();
+    if a.is_empty() || b.is_empty() || a.len() * b.len() > MAX_DIFF_CELLS {
+        regions.push((0, a.len(), 0, b.len()));
+    } else {
+        // lcs[i][j] = length of the longest common subsequence of a[i..], b[j..]
+        let width = b.len() + 1;
+        let mut lcs = vec![0u32; (a.len() + 1) * width];
+        for i in (0..a.len()).rev() {
+            for j in (0..b.len()).rev() {
+                lcs[i * width + j] = if a[i] == b[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        let mut open: Option<(usize, usize)> = None;
+        while i < a.len() || j < b.len() {
+            if i < a.len() && j < b.len() && a[i] == b[j] {
+                if let Some((start_i, start_j)) = open.take() {
+                    regions.push((start_i, i, start_j, j));
+                }
+                i += 1;
+                j += 1;
+                continue;
+            }
+            if open.is_none() {
+                open = Some((i, j));
+            }
+            if j == b.len() || (i < a.len() && lcs[(i + 1) * width + j] >= lcs[i * width + j + 1]) {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        if let Some((start_i, start_j)) = open {
+            regions.push((start_i, a.len(), start_j, b.len()));
+        }
+    }
+
+    let owned = |lines: &[&str]| lines.iter().map(|line| line.to_string()).collect();
+    regions
+        .into_iter()
+        .map(|(old_start, old_end, new_start, new_end)| {
+            let old_start = old_start + prefix;
+            let old_end = old_end + prefix;
+            Hunk {
+                old_start,
+                old_lines: owned(&old_lines[old_start..old_end]),
+                new_lines: owned(&new_lines[new_start + prefix..new_end + prefix]),
+                context_before: owned(
+                    &old_lines[old_start.saturating_sub(CONTEXT_LINES)..old_start],
+                ),
+                context_after: owned(
+                    &old_lines[old_end..(old_end + CONTEXT_LINES).min(old_lines.len())],
+                ),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const ORIGINAL: &str = "fn a() {}\n\nfn b() {}\n\nfn c() {}\n\nfn d() {}\n\nfn e() {}\n";
+
+    fn review_for(input: Value, tool: &str) -> (tempfile::TempDir, EditReview) {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("lib.rs"), ORIGINAL).unwrap();
+        let review = EditReview::from_tool_call(tool, &input, dir.path()).unwrap();
+        (dir, review)
+    }
+
+    #[test]
+    fn test_write_splits_into_hunks_and_merges_decisions() {
+        let proposed = ORIGINAL
+            .replace("fn a() {}", "fn a() { 1 }")
+            .replace("fn e() {}", "fn e() { 5 }");
+        let (_dir, mut review) = review_for(
+            json!({ "file_path": "lib.rs", "content": proposed }),
+            "Write",
+        );
+        assert_eq!(review.display_path, "lib.rs");
+        assert_eq!(review.hunks.len(), 2);
+        assert_eq!(review.hunks[1].old_start, 8);
+        assert_eq!(review.hunks[1].context_before.len(), 3);
+        assert_eq!(review.outcome(), ReviewOutcome::AsProposed);
+
+        review.decisions[1] = HunkDecision::Reject;
+        let expected = ORIGINAL.replace("fn a() {}", "fn a() { 1 }");
+        match review.outcome() {
+            ReviewOutcome::Reviewed {
+                content,
+                updated_input,
+                accepted,
+                rejected,
+                ..
+            } => {
+                assert_eq!(content, expected);
+                assert_eq!(updated_input["content"], json!(expected));
+                assert_eq!((accepted, rejected), (1, 1));
+            }
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+
+        review.decisions[0] = HunkDecision::Reject;
+        assert_eq!(review.outcome(), ReviewOutcome::Rejected);
+    }
+
+    #[test]
+    fn test_edited_hunk_rewrites_edit_input() {
+        let (_dir, mut review) = review_for(
+            json!({
+                "file_path": "lib.rs",
+                "old_string": "fn c() {}",
+                "new_string": "fn c() { todo!() }",
+            }),
+            "Edit",
+        );
+        assert_eq!(review.hunks.len(), 1);
+        assert_eq!(review.hunks[0].new_text(), "fn c() { todo!() }\n");
+
+        // Editors may drop the trailing newline; it's restored
+        review.decisions[0] = HunkDecision::Edited("fn c() { 3 }".to_string());
+        let expected = ORIGINAL.replace("fn c() {}", "fn c() { 3 }");
+        match review.outcome() {
+            ReviewOutcome::Reviewed {
+                content,
+                updated_input,
+                edited,
+                ..
+            } => {
+                assert_eq!(content, expected);
+                assert_eq!(edited, 1);
+                assert_eq!(updated_input["old_string"], json!(ORIGINAL));
+                assert_eq!(updated_input["new_string"], json!(expected));
+            }
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_edit_that_cannot_apply_is_an_error() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("lib.rs"), ORIGINAL).unwrap();
+        let missing = json!({ "file_path": "lib.rs", "old_string": "fn z", "new_string": "" });
+        assert!(matches!(
+            EditReview::from_tool_call("Edit", &missing, dir.path()),
+            Err(EditReviewError::NotFound(_))
+        ));
+        let ambiguous = json!({ "file_path": "lib.rs", "old_string": "() {}", "new_string": "" });
+        assert!(matches!(
+            EditReview::from_tool_call("Edit", &ambiguous, dir.path()),
+            Err(EditReviewError::NotUnique { count: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn test_new_file_is_one_hunk() {
+        let dir = tempdir().unwrap();
+        let input = json!({
+            "file_path": dir.path().join("new.rs").to_string_lossy(),
+            "edits": [{ "old_string": "", "new_string": "a\nb\n" }],
+        });
+        let mut review = EditReview::from_tool_call("MultiEdit", &input, dir.path()).unwrap();
+        assert!(review.creates_file());
+        assert_eq!(review.display_path, "new.rs");
+        assert_eq!(review.hunks.len(), 1);
+        assert_eq!(review.hunks[0].new_lines, vec!["a\n", "b\n"]);
+        review.decisions[0] = HunkDecision::Reject;
+        assert_eq!(review.outcome(), ReviewOutcome::Rejected);
+    }
+}
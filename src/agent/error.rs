@@ -1,5 +1,12 @@
+use std::process::ExitStatus;
+
 use thiserror::Error;
 
+use super::events::{AgentEvent, ErrorEvent};
+
+/// [`ErrorEvent::code`] marking an agent process that exited unsuccessfully
+pub const AGENT_CRASHED_CODE: &str = "agent_crashed";
+
 #[derive(Error, Debug)]
 pub enum AgentError {
     #[error("Failed to spawn agent process")]
@@ -35,3 +42,70 @@ pub enum AgentError {
     #[error("Configuration error: {0}")]
     Config(String),
 }
+
+impl AgentError {
+    /// [`AgentError::Crashed`] for a failed process. Processes killed by a
+    /// signal report `128 + signal`, as shells do.
+    pub fn crashed(status: ExitStatus) -> Self {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            if let Some(signal) = status.signal() {
+                return AgentError::Crashed(128 + signal);
+            }
+        }
+        AgentError::Crashed(status.code().unwrap_or(-1))
+    }
+
+    /// Fatal event reporting that `agent`'s process exited with `status`
+    /// before finishing. `stderr` is appended when the process wrote any.
+    pub fn crash_event(agent: &str, status: ExitStatus, stderr: &str) -> AgentEvent {
+        let crash = Self::crashed(status);
+        let exit_code = match crash {
+            AgentError::Crashed(code) => code,
+            _ => -1,
+        };
+        let stderr = stderr.trim();
+        let message = if stderr.is_empty() {
+            format!("{} process failed: {}", agent, crash)
+        } else {
+            format!("{} process failed: {}: {}", agent, crash, stderr)
+        };
+        AgentEvent::Error(ErrorEvent {
+            message,
+            is_fatal: true,
+            code: Some(AGENT_CRASHED_CODE.to_string()),
+            details: Some(serde_json::json!({ "exit_code": exit_code })),
+        })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+
+    #[test]
+    fn test_crash_event_reports_exit_code_and_stderr() {
+        // Raw wait status: exit code in the high byte
+        let event = AgentError::crash_event("Claude", ExitStatus::from_raw(2 << 8), "auth\n");
+        let AgentEvent::Error(err) = event else {
+            panic!("expected error event");
+        };
+        assert!(err.is_fatal);
+        assert_eq!(err.code.as_deref(), Some(AGENT_CRASHED_CODE));
+        assert_eq!(
+            err.message,
+            "Claude process failed: Agent crashed with exit code: 2: auth"
+        );
+    }
+
+    #[test]
+    fn test_crashed_maps_signals_like_a_shell() {
+        // Raw wait status: terminating signal in the low bits (SIGKILL)
+        assert!(matches!(
+            AgentError::crashed(ExitStatus::from_raw(9)),
+            AgentError::Crashed(137)
+        ));
+    }
+}
@@ -30,6 +30,15 @@ pub enum AgentEvent {
     /// Tool use completed
     ToolCompleted(ToolCompletedEvent),
 
+    /// Subagent spawned by a Task tool call
+    SubagentStarted(SubagentStartedEvent),
+
+    /// Subagent finished (its Task tool call completed)
+    SubagentCompleted(SubagentCompletedEvent),
+
+    /// Event emitted from inside a running subagent
+    Subagent(SubagentEvent),
+
     /// Control request (permission prompt) from agent runtime
     ControlRequest(ControlRequestEvent),
 
@@ -39,6 +48,9 @@ pub enum AgentEvent {
     /// Command execution output
     CommandOutput(CommandOutputEvent),
 
+    /// Incremental output from a command that is still running
+    CommandOutputDelta(CommandOutputDeltaEvent),
+
     /// Token usage update
     TokenUsage(TokenUsageEvent),
 
@@ -64,9 +76,13 @@ impl AgentEvent {
             AgentEvent::AssistantReasoning(_) => "AssistantReasoning",
             AgentEvent::ToolStarted(_) => "ToolStarted",
             AgentEvent::ToolCompleted(_) => "ToolCompleted",
+            AgentEvent::SubagentStarted(_) => "SubagentStarted",
+            AgentEvent::SubagentCompleted(_) => "SubagentCompleted",
+            AgentEvent::Subagent(_) => "Subagent",
             AgentEvent::ControlRequest(_) => "ControlRequest",
             AgentEvent::FileChanged(_) => "FileChanged",
             AgentEvent::CommandOutput(_) => "CommandOutput",
+            AgentEvent::CommandOutputDelta(_) => "CommandOutputDelta",
             AgentEvent::TokenUsage(_) => "TokenUsage",
             AgentEvent::ContextCompaction(_) => "ContextCompaction",
             AgentEvent::Error(_) => "Error",
@@ -117,6 +133,30 @@ pub struct ToolCompletedEvent {
     pub error: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubagentStartedEvent {
+    /// ID of the Task tool call that owns the subagent
+    pub tool_id: String,
+    pub description: String,
+    pub subagent_type: Option<String>,
+    pub prompt: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubagentCompletedEvent {
+    pub tool_id: String,
+    pub success: bool,
+    pub result: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubagentEvent {
+    /// ID of the Task tool call that owns the subagent
+    pub parent_tool_id: String,
+    pub event: Box<AgentEvent>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ControlRequestEvent {
     pub request_id: String,
@@ -147,11 +187,23 @@ pub struct CommandOutputEvent {
     pub is_streaming: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandOutputDeltaEvent {
+    /// ID of the ToolStarted call this output belongs to
+    pub tool_id: String,
+    /// Output produced since the previous delta
+    pub chunk: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TokenUsage {
     pub input_tokens: i64,
     pub output_tokens: i64,
+    /// Portion of `input_tokens` read from the prompt cache
     pub cached_tokens: i64,
+    /// Portion of `input_tokens` written to the prompt cache
+    #[serde(default)]
+    pub cache_creation_tokens: i64,
     pub total_tokens: i64,
 }
 
@@ -0,0 +1,372 @@
+//! Where agent processes run: on the host, in a Docker container or in a
+//! devcontainer.
+//!
+//! Runners build their command as if it ran on the host; [`ExecutionTarget::wrap`]
+//! then rewrites it into the equivalent `docker run` / `devcontainer exec`
+//! invocation. The workspace is mounted at the same path inside the container
+//! so tool-reported paths stay valid on the host; a worktree's shared git
+//! directory is mounted beside it so git works in the container. Stdio is not
+//! carried over: runners configure it on the wrapped command.
+
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tokio::process::Command;
+
+/// Label on Docker containers holding the workspace path they were started for
+pub const DOCKER_WORKDIR_LABEL: &str = "conduit.workdir";
+
+/// Execution target for agent processes, configured per repository
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ExecutionTarget {
+    /// Run the agent CLI directly (default)
+    #[default]
+    Host,
+    /// Run the agent CLI in a throwaway container from `image`
+    Docker {
+        image: String,
+        /// Extra `-v` bind mounts (`host:container[:opts]`), e.g. agent credentials
+        mounts: Vec<String>,
+        /// Extra flags passed to `docker run` before the image
+        docker_args: Vec<String>,
+    },
+    /// Run the agent CLI in the workspace's running devcontainer
+    DevContainer,
+}
+
+impl ExecutionTarget {
+    /// Rewrite `cmd` to run on this target. Program, arguments, environment
+    /// and working directory are preserved; stdio must be set afterwards.
+    /// Fails when the workspace can't work on this target.
+    pub fn wrap(&self, cmd: Command) -> io::Result<Command> {
+        let std_cmd = cmd.as_std();
+        let program = container_program(std_cmd.get_program());
+        let args: Vec<OsString> = std_cmd.get_args().map(OsString::from).collect();
+        let envs: Vec<(OsString, OsString)> = std_cmd
+            .get_envs()
+            .filter_map(|(key, value)| Some((key.to_os_string(), value?.to_os_string())))
+            .collect();
+        let dir = std_cmd
+            .get_current_dir()
+            .map(Path::to_path_buf)
+            .or_else(|| std::env::current_dir().ok());
+
+        match self {
+            ExecutionTarget::Host => Ok(cmd),
+            ExecutionTarget::Docker {
+                image,
+                mounts,
+                docker_args,
+            } => {
+                let mut wrapped = Command::new("docker");
+                wrapped.args(["run", "--rm", "-i", "--init"]);
+                if let Some(dir) = &dir {
+                    let mut volume = dir.clone().into_os_string();
+                    volume.push(":");
+                    volume.push(dir);
+                    wrapped.arg("-v").arg(volume);
+                    // A worktree's `.git` file points into the main repository
+                    if let Some(common) =
+                        git_common_dir(dir).filter(|common| !common.starts_with(dir))
+                    {
+                        let mut volume = common.clone().into_os_string();
+                        volume.push(":");
+                        volume.push(&common);
+                        wrapped.arg("-v").arg(volume);
+                    }
+                    wrapped.arg("-w").arg(dir);
+                    let mut label = OsString::from(format!("{}=", DOCKER_WORKDIR_LABEL));
+                    label.push(dir);
+                    wrapped.arg("--label").arg(label);
+                    wrapped.current_dir(dir);
+                }
+                for mount in mounts {
+                    wrapped.arg("-v").arg(expand_home(mount));
+                }
+                wrapped.args(docker_args);
+                // `-e KEY` forwards the value from docker's own environment,
+                // keeping secrets out of the process list
+                for (key, value) in envs {
+                    wrapped.arg("-e").arg(&key);
+                    wrapped.env(key, value);
+                }
+                wrapped.arg(image);
+                wrapped.arg(program);
+                wrapped.args(args);
+                Ok(wrapped)
+            }
+            ExecutionTarget::DevContainer => {
+                let dir = dir.ok_or_else(|| {
+                    io::Error::other("devcontainer target needs a working directory")
+                })?;
+                // The devcontainer only mounts the workspace folder, so a
+                // worktree's `.git` file would point outside the container
+                if dir.join(".git").is_file() {
+                    return Err(io::Error::other(format!(
+                        "{} is a git worktree, which can't run in a devcontainer; use a checkout workspace or the docker target",
+                        dir.display()
+                    )));
+                }
+                let mut wrapped = Command::new("devcontainer");
+                wrapped.arg("exec");
+                wrapped.arg("--workspace-folder").arg(&dir);
+                wrapped.current_dir(&dir);
+                if !envs.is_empty() {
+                    // `--remote-env KEY=VALUE` would put secrets in the process
+                    // list; the values go through a private file the container
+                    // shell reads and deletes before starting the program
+                    let env_file = write_env_file(&dir, &envs)?;
+                    wrapped.args(["sh", "-c", ENV_FILE_SCRIPT]);
+                    wrapped.arg(env_file);
+                }
+                wrapped.arg(program);
+                wrapped.args(args);
+                Ok(wrapped)
+            }
+        }
+    }
+}
+
+/// Reads the env file named by `$0` into the environment, deletes it and
+/// execs the program in `$@`
+const ENV_FILE_SCRIPT: &str =
+    r#"script=$(cat "$0") || exit 1; rm -f "$0"; eval "$script"; exec "$@""#;
+
+/// Write `envs` as shell exports to a file only the owner can read, in the
+/// workspace's `.git` directory (mounted in the container, never committed).
+/// Returns its path relative to the workspace folder.
+fn write_env_file(dir: &Path, envs: &[(OsString, OsString)]) -> io::Result<PathBuf> {
+    let mut script = String::new();
+    for (key, value) in envs {
+        let (Some(key), Some(value)) = (key.to_str(), value.to_str()) else {
+            tracing::warn!(key = ?key, "Skipping non-UTF-8 environment variable for devcontainer");
+            continue;
+        };
+        if !is_shell_name(key) {
+            tracing::warn!(
+                key,
+                "Skipping environment variable with an invalid name for devcontainer"
+            );
+            continue;
+        }
+        script.push_str(&format!(
+            "export {}='{}'\n",
+            key,
+            value.replace('\'', r"'\''")
+        ));
+    }
+
+    let relative = Path::new(".git").join(format!("conduit-env-{}", uuid::Uuid::new_v4()));
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let path = dir.join(&relative);
+    let written = options
+        .open(&path)
+        .and_then(|mut file| io::Write::write_all(&mut file, script.as_bytes()));
+    if let Err(err) = written {
+        return Err(io::Error::new(
+            err.kind(),
+            format!("failed to write {}: {}", path.display(), err),
+        ));
+    }
+    Ok(relative)
+}
+
+fn is_shell_name(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The repository's shared git directory when `dir` is in a worktree, found
+/// from its `.git` file (`gitdir: ...`) and that directory's `commondir`
+fn git_common_dir(dir: &Path) -> Option<PathBuf> {
+    let dot_git = dir
+        .ancestors()
+        .map(|d| d.join(".git"))
+        .find(|p| p.exists())?;
+    if !dot_git.is_file() {
+        return None;
+    }
+    let contents = std::fs::read_to_string(&dot_git).ok()?;
+    let gitdir = contents.trim().strip_prefix("gitdir:")?.trim();
+    let gitdir = dot_git.parent()?.join(gitdir);
+    let common = match std::fs::read_to_string(gitdir.join("commondir")) {
+        Ok(common) => gitdir.join(common.trim()),
+        Err(_) => gitdir,
+    };
+    match common.canonicalize() {
+        Ok(common) => Some(common),
+        Err(err) => {
+            tracing::warn!(
+                path = %common.display(),
+                error = %err,
+                "Worktree's git directory not found; it won't be mounted"
+            );
+            None
+        }
+    }
+}
+
+/// Host binaries are resolved to absolute paths; inside the container only the
+/// name is meaningful, so it's looked up on the container's `PATH`
+fn container_program(program: &std::ffi::OsStr) -> OsString {
+    Path::new(program)
+        .file_name()
+        .map(OsString::from)
+        .unwrap_or_else(|| program.to_os_string())
+}
+
+/// Expand `~` in the host side of a `host:container` mount
+fn expand_home(mount: &str) -> String {
+    match (mount.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => format!("{}/{}", home.to_string_lossy(), rest),
+        _ => mount.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args_of(cmd: &Command) -> Vec<String> {
+        cmd.as_std()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    fn agent_command() -> Command {
+        let mut cmd = Command::new("/usr/local/bin/claude");
+        cmd.args(["--print", "--", "hello"]);
+        cmd.current_dir("/work/repo");
+        cmd.env("ANTHROPIC_BASE_URL", "http://proxy");
+        cmd
+    }
+
+    #[test]
+    fn test_docker_wraps_command_with_workspace_mount() {
+        let target = ExecutionTarget::Docker {
+            image: "dev:latest".to_string(),
+            mounts: vec!["/cache:/cache:ro".to_string()],
+            docker_args: vec!["--network=host".to_string()],
+        };
+        let wrapped = target.wrap(agent_command()).unwrap();
+        assert_eq!(wrapped.as_std().get_program(), "docker");
+        assert_eq!(
+            args_of(&wrapped),
+            [
+                "run",
+                "--rm",
+                "-i",
+                "--init",
+                "-v",
+                "/work/repo:/work/repo",
+                "-w",
+                "/work/repo",
+                "--label",
+                "conduit.workdir=/work/repo",
+                "-v",
+                "/cache:/cache:ro",
+                "--network=host",
+                "-e",
+                "ANTHROPIC_BASE_URL",
+                "dev:latest",
+                "claude",
+                "--print",
+                "--",
+                "hello",
+            ]
+        );
+        // The value reaches the container through docker's environment
+        assert!(wrapped
+            .as_std()
+            .get_envs()
+            .any(|(key, value)| key == "ANTHROPIC_BASE_URL" && value.is_some()));
+    }
+
+    #[test]
+    fn test_docker_mounts_worktree_git_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let repo_git = root.path().join("repo/.git");
+        let gitdir = repo_git.join("worktrees/feature");
+        std::fs::create_dir_all(&gitdir).unwrap();
+        std::fs::write(gitdir.join("commondir"), "../..\n").unwrap();
+        let worktree = root.path().join("workspaces/feature");
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", gitdir.display()),
+        )
+        .unwrap();
+
+        let mut cmd = Command::new("claude");
+        cmd.current_dir(&worktree);
+        let target = ExecutionTarget::Docker {
+            image: "dev".to_string(),
+            mounts: Vec::new(),
+            docker_args: Vec::new(),
+        };
+        let args = args_of(&target.wrap(cmd).unwrap());
+        let common = repo_git.canonicalize().unwrap();
+        let mount = format!("{}:{}", common.display(), common.display());
+        assert!(args.contains(&mount), "{args:?}");
+    }
+
+    #[test]
+    fn test_devcontainer_keeps_env_values_out_of_args() {
+        let workspace = tempfile::tempdir().unwrap();
+        std::fs::create_dir(workspace.path().join(".git")).unwrap();
+        let mut cmd = agent_command();
+        cmd.current_dir(workspace.path());
+        cmd.env("API_KEY", "it's secret");
+
+        let wrapped = ExecutionTarget::DevContainer.wrap(cmd).unwrap();
+        assert_eq!(wrapped.as_std().get_program(), "devcontainer");
+        let args = args_of(&wrapped);
+        assert_eq!(
+            args[..5],
+            [
+                "exec",
+                "--workspace-folder",
+                &workspace.path().to_string_lossy(),
+                "sh",
+                "-c"
+            ]
+        );
+        assert_eq!(args[5], ENV_FILE_SCRIPT);
+        assert_eq!(args[7..], ["claude", "--print", "--", "hello"]);
+        assert!(!args
+            .iter()
+            .any(|arg| arg.contains("secret") || arg.contains("proxy")));
+
+        let env_file = std::fs::read_to_string(workspace.path().join(&args[6])).unwrap();
+        assert!(env_file.contains("export ANTHROPIC_BASE_URL='http://proxy'\n"));
+        assert!(env_file.contains(r"export API_KEY='it'\''s secret'"));
+    }
+
+    #[test]
+    fn test_devcontainer_refuses_worktrees() {
+        let workspace = tempfile::tempdir().unwrap();
+        std::fs::write(workspace.path().join(".git"), "gitdir: /elsewhere\n").unwrap();
+        let mut cmd = agent_command();
+        cmd.current_dir(workspace.path());
+        let err = ExecutionTarget::DevContainer.wrap(cmd).unwrap_err();
+        assert!(err.to_string().contains("worktree"));
+    }
+
+    #[test]
+    fn test_host_leaves_command_unchanged() {
+        let wrapped = ExecutionTarget::Host.wrap(agent_command()).unwrap();
+        assert_eq!(wrapped.as_std().get_program(), "/usr/local/bin/claude");
+        assert_eq!(args_of(&wrapped), ["--print", "--", "hello"]);
+    }
+}
@@ -17,6 +17,7 @@ use tokio::sync::mpsc;
 use tokio_util::compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt};
 use tokio_util::io::ReaderStream;
 
+use crate::agent::diagnostics::capture_stderr;
 use crate::agent::error::AgentError;
 use crate::agent::events::{
     AgentEvent, AssistantMessageEvent, ErrorEvent, ReasoningEvent, SessionInitEvent,
@@ -298,7 +299,11 @@ impl GeminiCliRunner {
         ))
     }
 
-    fn build_command(&self, config: &AgentStartConfig, resolved: &ResolvedCommand) -> Command {
+    fn build_command(
+        &self,
+        config: &AgentStartConfig,
+        resolved: &ResolvedCommand,
+    ) -> std::io::Result<Command> {
         let mut cmd = Command::new(&resolved.bin);
         cmd.args(&resolved.base_args);
         cmd.arg("--experimental-acp");
@@ -313,12 +318,14 @@ impl GeminiCliRunner {
         }
 
         cmd.current_dir(&config.working_dir);
+        cmd.env("NODE_NO_WARNINGS", "1");
+        cmd.envs(config.env.clone());
+
+        let mut cmd = config.execution_target.wrap(cmd)?;
         cmd.stdin(Stdio::piped());
         cmd.stdout(Stdio::piped());
         cmd.stderr(Stdio::piped());
-        cmd.env("NODE_NO_WARNINGS", "1");
-
-        cmd
+        Ok(cmd)
     }
 
     fn cache_path() -> Option<PathBuf> {
@@ -474,12 +481,15 @@ impl AgentRunner for GeminiCliRunner {
         })
         .await;
 
-        let mut cmd = self.build_command(&config, &resolved);
+        let mut cmd = self.build_command(&config, &resolved)?;
         let mut child = cmd.spawn()?;
 
         let pid = child.id().ok_or(AgentError::ProcessSpawnFailed)?;
         let stdout = child.stdout.take().ok_or(AgentError::StdoutCaptureFailed)?;
-        let stderr = child.stderr.take();
+        let stderr_task = child
+            .stderr
+            .take()
+            .map(|stderr| tokio::spawn(capture_stderr(stderr, config.stderr.clone(), "gemini")));
         let child_stdin = child.stdin.take().ok_or_else(|| {
             AgentError::Config("Failed to capture stdin for Gemini CLI".to_string())
         })?;
@@ -712,37 +722,23 @@ impl AgentRunner for GeminiCliRunner {
         });
 
         tokio::spawn(async move {
-            use tokio::io::AsyncReadExt;
-
             let status = child.wait().await;
-            let stderr_content = if let Some(mut stderr) = stderr {
-                let mut buf = String::new();
-                if let Err(err) = stderr.read_to_string(&mut buf).await {
-                    tracing::debug!(error = %err, "Failed to read Gemini stderr");
-                }
-                buf
-            } else {
-                String::new()
+            let stderr_content = match stderr_task {
+                Some(task) => task.await.unwrap_or_else(|err| {
+                    tracing::debug!(error = %err, "Gemini stderr task failed to join");
+                    String::new()
+                }),
+                None => String::new(),
             };
 
             match status {
                 Ok(exit_status) if !exit_status.success() => {
-                    let message = if stderr_content.is_empty() {
-                        format!("Gemini process exited with status: {}", exit_status)
-                    } else {
-                        format!(
-                            "Gemini process failed ({}): {}",
-                            exit_status,
-                            stderr_content.trim()
-                        )
-                    };
                     if let Err(send_err) = tx_for_monitor
-                        .send(AgentEvent::Error(ErrorEvent {
-                            message,
-                            is_fatal: true,
-                            code: None,
-                            details: None,
-                        }))
+                        .send(AgentError::crash_event(
+                            "Gemini",
+                            exit_status,
+                            &stderr_content,
+                        ))
                         .await
                     {
                         tracing::debug!(
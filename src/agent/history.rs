@@ -3,7 +3,7 @@
 //! Reads chat history from agent files for session restoration.
 //! - Claude Code: ~/.claude/projects/{project-path}/{session-id}.jsonl
 //! - Codex CLI: ~/.codex/sessions/YYYY/MM/DD/rollout-*-{session-id}.jsonl
-//! - Gemini CLI: not supported yet
+//! - Gemini CLI: ~/.gemini/tmp/{project-hash}/chats/session-*-{session-id-prefix}.json
 
 use std::collections::HashMap;
 use std::fs::{self, File};
@@ -306,6 +306,33 @@ fn build_turn_summary(
     }
 }
 
+/// Fill each turn summary with the shell commands run earlier in its turn
+fn add_turn_commands(messages: &mut [ChatMessage]) {
+    let mut turn_start = 0;
+    for idx in 0..messages.len() {
+        if messages[idx].role != MessageRole::Summary {
+            continue;
+        }
+        let (turn, rest) = messages.split_at_mut(idx);
+        if let Some(summary) = rest[0].summary.as_mut() {
+            for msg in &turn[turn_start..] {
+                if msg.role != MessageRole::Tool || msg.tool_name.as_deref() != Some("Bash") {
+                    continue;
+                }
+                if let Some(command) = msg.tool_args.as_deref().filter(|args| !args.is_empty()) {
+                    summary.add_command(
+                        command,
+                        msg.exit_code.map(|code| code == 0),
+                        msg.exit_code,
+                        &msg.content,
+                    );
+                }
+            }
+        }
+        turn_start = idx + 1;
+    }
+}
+
 /// Debug entry for history loading - shows what happened to each JSONL line
 #[derive(Debug, Clone)]
 pub struct HistoryDebugEntry {
@@ -976,6 +1003,7 @@ fn build_claude_messages(
         messages.push(ChatMessage::turn_summary(summary));
     }
 
+    add_turn_commands(&mut messages);
     messages
 }
 
@@ -1208,6 +1236,22 @@ fn convert_claude_entry_with_tools(
                         };
                         messages.push(display.to_chat_message());
                     } else if let Some(blocks) = content.as_array() {
+                        // Thinking blocks precede the reply they led to
+                        let thinking: Vec<&str> = blocks
+                            .iter()
+                            .filter(|block| {
+                                block.get("type").and_then(|t| t.as_str()) == Some("thinking")
+                            })
+                            .filter_map(|block| block.get("thinking").and_then(|t| t.as_str()))
+                            .filter(|text| !text.trim().is_empty())
+                            .collect();
+                        if !thinking.is_empty() {
+                            let display = MessageDisplay::Reasoning {
+                                content: thinking.join("\n"),
+                            };
+                            messages.push(display.to_chat_message());
+                        }
+
                         // Extract only text blocks as assistant message
                         let texts: Vec<String> = blocks
                             .iter()
@@ -1281,7 +1325,8 @@ fn format_tool_args(tool_name: &str, input: &Value) -> String {
     let fallback = || serde_json::to_string(input).unwrap_or_default();
 
     match tool_name {
-        "Bash" | "exec_command" | "shell" | "local_shell_call" | "command_execution" => {
+        "Bash" | "exec_command" | "shell" | "local_shell_call" | "command_execution"
+        | "run_shell_command" => {
             // Extract command from input
             input
                 .get("command")
@@ -1305,7 +1350,7 @@ fn format_tool_args(tool_name: &str, input: &Value) -> String {
                 .map(|s| s.to_string())
                 .unwrap_or_else(fallback)
         }
-        "Edit" => {
+        "Edit" | "replace" => {
             // Extract file path
             input
                 .get("file_path")
@@ -1649,6 +1694,7 @@ pub fn parse_codex_history_file_with_debug(
         }
     }
 
+    add_turn_commands(&mut messages);
     Ok((messages, debug_entries))
 }
 
@@ -1965,6 +2011,351 @@ pub fn load_opencode_history_for_dir_with_debug(
     Ok((session_id, messages, debug_entries, session_file))
 }
 
+/// Load Gemini CLI history with debug information
+///
+/// Gemini stores each chat as a single JSON document under
+/// `~/.gemini/tmp/{project-hash}/chats/`, named after the first eight
+/// characters of the session ID.
+pub fn load_gemini_history_with_debug(
+    session_id: &str,
+) -> Result<(Vec<ChatMessage>, Vec<HistoryDebugEntry>, PathBuf), HistoryError> {
+    let home = dirs::home_dir().ok_or(HistoryError::HomeNotFound)?;
+    let tmp_dir = home.join(".gemini/tmp");
+
+    if !tmp_dir.exists() {
+        return Err(HistoryError::SessionNotFound(session_id.to_string()));
+    }
+
+    let session_file = find_gemini_session_file(&tmp_dir, session_id)?;
+    let (messages, debug_entries) = parse_gemini_history_file_with_debug(&session_file)?;
+    Ok((messages, debug_entries, session_file))
+}
+
+/// Find a Gemini chat file whose `sessionId` matches, checking the most
+/// recently modified candidate first.
+fn find_gemini_session_file(tmp_dir: &Path, session_id: &str) -> Result<PathBuf, HistoryError> {
+    let short_id: String = session_id.chars().take(8).collect();
+    let mut candidates = Vec::new();
+
+    for project_entry in fs::read_dir(tmp_dir)?.flatten() {
+        let chats_dir = project_entry.path().join("chats");
+        if !chats_dir.is_dir() {
+            continue;
+        }
+
+        for file_entry in fs::read_dir(&chats_dir).into_iter().flatten().flatten() {
+            let file_path = file_entry.path();
+            let Some(name) = file_path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if name.starts_with("session-") && name.ends_with(".json") && name.contains(&short_id) {
+                let modified = file_entry
+                    .metadata()
+                    .and_then(|metadata| metadata.modified())
+                    .ok();
+                candidates.push((modified, file_path));
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| b.0.cmp(&a.0));
+
+    for (_, path) in candidates {
+        let raw = match fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(error) => {
+                tracing::warn!(
+                    path = %path.display(),
+                    error = %error,
+                    "Failed to read Gemini chat file"
+                );
+                continue;
+            }
+        };
+        let value: Value = match serde_json::from_str(&raw) {
+            Ok(value) => value,
+            Err(error) => {
+                tracing::warn!(
+                    path = %path.display(),
+                    error = %error,
+                    "Failed to parse Gemini chat file"
+                );
+                continue;
+            }
+        };
+        if value.get("sessionId").and_then(|id| id.as_str()) == Some(session_id) {
+            return Ok(path);
+        }
+    }
+
+    Err(HistoryError::SessionNotFound(session_id.to_string()))
+}
+
+/// Parse a Gemini chat JSON file with debug information
+fn parse_gemini_history_file_with_debug(
+    path: &Path,
+) -> Result<(Vec<ChatMessage>, Vec<HistoryDebugEntry>), HistoryError> {
+    let raw = fs::read_to_string(path).map_err(|error| {
+        HistoryError::IoError(io_error_with_context(
+            error,
+            format!("Failed to read Gemini chat file {}", path.display()),
+        ))
+    })?;
+    let document: Value = serde_json::from_str(&raw).map_err(|error| {
+        HistoryError::ParseError(format!(
+            "Failed to parse Gemini chat file {}: {}",
+            path.display(),
+            error
+        ))
+    })?;
+    let entries = document
+        .get("messages")
+        .and_then(|m| m.as_array())
+        .ok_or_else(|| {
+            HistoryError::ParseError(format!(
+                "Gemini chat file {} has no messages array",
+                path.display()
+            ))
+        })?;
+
+    let mut messages = Vec::new();
+    let mut debug_entries = Vec::new();
+    let mut turn_started_at: Option<DateTime<Utc>> = None;
+    let mut turn_ended_at: Option<DateTime<Utc>> = None;
+    let mut turn_usage: Option<(u64, u64)> = None;
+
+    for (idx, entry) in entries.iter().enumerate() {
+        let entry_type = entry
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or("unknown");
+        let content = gemini_text_content(entry.get("content").unwrap_or(&Value::Null));
+        let (status, reason) = match entry_type {
+            "user" => {
+                if content.trim().is_empty() {
+                    ("SKIP", "user message empty".to_string())
+                } else {
+                    if let Some(summary) =
+                        build_turn_summary(turn_started_at, turn_ended_at, turn_usage)
+                    {
+                        messages.push(ChatMessage::turn_summary(summary));
+                    }
+                    turn_started_at = parse_timestamp(entry);
+                    turn_ended_at = None;
+                    turn_usage = None;
+                    messages.push(
+                        MessageDisplay::User {
+                            content: content.clone(),
+                        }
+                        .to_chat_message(),
+                    );
+                    (
+                        "INCLUDE",
+                        format!("user: {}", truncate_preview(&content, 50)),
+                    )
+                }
+            }
+            "gemini" => {
+                let before = messages.len();
+                let thoughts = gemini_thoughts(entry);
+                if !thoughts.trim().is_empty() {
+                    messages
+                        .push(MessageDisplay::Reasoning { content: thoughts }.to_chat_message());
+                }
+                if !content.trim().is_empty() {
+                    messages.push(
+                        MessageDisplay::Assistant {
+                            content: content.clone(),
+                            is_streaming: false,
+                        }
+                        .to_chat_message(),
+                    );
+                }
+                let tool_calls = entry
+                    .get("toolCalls")
+                    .and_then(|t| t.as_array())
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                for tool_call in tool_calls {
+                    if let Some(tool_message) = gemini_tool_message(tool_call) {
+                        messages.push(tool_message);
+                    }
+                }
+
+                if let Some(usage) = extract_gemini_usage(entry) {
+                    let (input, output) = turn_usage.unwrap_or((0, 0));
+                    turn_usage = Some((
+                        input.saturating_add(usage.0),
+                        output.saturating_add(usage.1),
+                    ));
+                }
+                if let Some(timestamp) = parse_timestamp(entry) {
+                    turn_ended_at = Some(timestamp);
+                }
+
+                let added = messages.len() - before;
+                if added == 0 {
+                    ("SKIP", "gemini message empty".to_string())
+                } else {
+                    (
+                        "INCLUDE",
+                        format!(
+                            "gemini: {} message(s), {} tool call(s)",
+                            added,
+                            tool_calls.len()
+                        ),
+                    )
+                }
+            }
+            "error" => {
+                if content.trim().is_empty() {
+                    ("SKIP", "error message empty".to_string())
+                } else {
+                    messages.push(
+                        MessageDisplay::Error {
+                            content: content.clone(),
+                        }
+                        .to_chat_message(),
+                    );
+                    (
+                        "INCLUDE",
+                        format!("error: {}", truncate_preview(&content, 50)),
+                    )
+                }
+            }
+            "info" | "warning" => ("SKIP", format!("{entry_type} notice")),
+            other => ("SKIP", format!("unsupported type: {other}")),
+        };
+
+        debug_entries.push(HistoryDebugEntry {
+            line_number: idx,
+            entry_type: entry_type.to_string(),
+            status: status.to_string(),
+            reason,
+            raw_json: entry.clone(),
+        });
+    }
+
+    if let Some(summary) = build_turn_summary(turn_started_at, turn_ended_at, turn_usage) {
+        messages.push(ChatMessage::turn_summary(summary));
+    }
+
+    Ok((messages, debug_entries))
+}
+
+/// Gemini content is either a plain string or a list of `{ "text": ... }` parts
+fn gemini_text_content(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => {
+            let mut text = String::new();
+            for part in parts {
+                if part.get("thought").and_then(|t| t.as_bool()) == Some(true) {
+                    continue;
+                }
+                if let Some(chunk) = part.get("text").and_then(|t| t.as_str()) {
+                    append_output(&mut text, chunk);
+                }
+            }
+            text
+        }
+        Value::Object(_) => content
+            .get("text")
+            .and_then(|t| t.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        _ => String::new(),
+    }
+}
+
+fn gemini_thoughts(entry: &Value) -> String {
+    let mut text = String::new();
+    let Some(thoughts) = entry.get("thoughts").and_then(|t| t.as_array()) else {
+        return text;
+    };
+    for thought in thoughts {
+        let subject = thought
+            .get("subject")
+            .and_then(|s| s.as_str())
+            .unwrap_or("");
+        let description = thought
+            .get("description")
+            .and_then(|d| d.as_str())
+            .unwrap_or("");
+        let chunk = match (subject.is_empty(), description.is_empty()) {
+            (false, false) => format!("**{}**\n{}", subject, description),
+            (false, true) => format!("**{}**", subject),
+            (true, false) => description.to_string(),
+            (true, true) => continue,
+        };
+        append_output(&mut text, &chunk);
+    }
+    text
+}
+
+fn extract_gemini_usage(entry: &Value) -> Option<(u64, u64)> {
+    let tokens = entry.get("tokens")?;
+    let input = tokens.get("input").and_then(|v| v.as_u64())?;
+    let output = tokens.get("output").and_then(|v| v.as_u64())?;
+    Some((input, output))
+}
+
+fn gemini_tool_message(tool_call: &Value) -> Option<ChatMessage> {
+    let name = tool_call.get("name").and_then(|n| n.as_str())?;
+    let status = tool_call
+        .get("status")
+        .and_then(|s| s.as_str())
+        .unwrap_or("");
+    let args = tool_call
+        .get("args")
+        .map(|args| format_tool_args(name, args))
+        .unwrap_or_default();
+
+    // Prefer the user-facing display text; fall back to the function response
+    let mut output = tool_call
+        .get("resultDisplay")
+        .and_then(|r| r.as_str())
+        .unwrap_or_default()
+        .to_string();
+    if output.trim().is_empty() {
+        output = tool_call
+            .get("result")
+            .and_then(|r| r.as_array())
+            .map(|parts| {
+                let mut text = String::new();
+                for part in parts {
+                    let response = part.get("functionResponse").and_then(|f| f.get("response"));
+                    let chunk = response
+                        .and_then(|r| r.get("output").or_else(|| r.get("error")))
+                        .map(extract_tool_result_content)
+                        .unwrap_or_default();
+                    append_output(&mut text, &chunk);
+                }
+                text
+            })
+            .unwrap_or_default();
+    }
+    if output.trim().is_empty() && !status.is_empty() {
+        output = format!("status: {}", status);
+    }
+
+    let exit_code = match status {
+        "error" => Some(1),
+        _ => None,
+    };
+
+    Some(
+        MessageDisplay::Tool {
+            name: MessageDisplay::tool_display_name_owned(name),
+            args,
+            output,
+            exit_code,
+            file_size: None,
+        }
+        .to_chat_message(),
+    )
+}
+
 /// Create a truncated preview of text for debug output
 fn truncate_preview(text: &str, max_len: usize) -> String {
     let preview: String = text.chars().take(max_len).collect();
@@ -2150,6 +2541,40 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_turn_summaries_list_commands_from_their_turn() {
+        let mut failing = ChatMessage::tool("Bash", "cargo test", "1 failed");
+        failing.exit_code = Some(101);
+        let mut messages = vec![
+            ChatMessage::user("first"),
+            failing,
+            ChatMessage::tool("Read", "src/lib.rs", "fn main() {}"),
+            ChatMessage::turn_summary(TurnSummary::new().with_duration(5)),
+            ChatMessage::user("second"),
+            ChatMessage::tool("Bash", "ls", "Cargo.toml"),
+            ChatMessage::turn_summary(TurnSummary::new().with_duration(2)),
+        ];
+
+        add_turn_commands(&mut messages);
+
+        let commands = |idx: usize| -> Vec<String> {
+            messages[idx]
+                .summary
+                .as_ref()
+                .unwrap()
+                .commands
+                .iter()
+                .map(|run| run.command.clone())
+                .collect()
+        };
+        assert_eq!(commands(3), vec!["cargo test"]);
+        assert_eq!(commands(6), vec!["ls"]);
+        assert_eq!(
+            messages[3].summary.as_ref().unwrap().verification(),
+            Some(crate::ui::components::VerifyOutcome::Failed)
+        );
+    }
+
     #[test]
     fn test_convert_claude_user_entry() {
         let entry = serde_json::json!({
@@ -2708,4 +3133,100 @@ mod tests {
         assert_eq!(messages[0].role, MessageRole::Error);
         assert!(messages[0].content.contains("Model missing"));
     }
+
+    fn write_gemini_chat(dir: &Path, session_id: &str, messages: Value) -> PathBuf {
+        let chats_dir = dir.join("abc123").join("chats");
+        fs::create_dir_all(&chats_dir).unwrap();
+        let short_id: String = session_id.chars().take(8).collect();
+        let path = chats_dir.join(format!("session-2025-01-01T10-00-{short_id}.json"));
+        fs::write(
+            &path,
+            serde_json::json!({
+                "sessionId": session_id,
+                "projectHash": "abc123",
+                "messages": messages,
+            })
+            .to_string(),
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn test_gemini_history_with_tool_calls() {
+        let temp = TempDir::new().unwrap();
+        let session_id = "0f1e2d3c-aaaa-bbbb-cccc-123456789abc";
+        let path = write_gemini_chat(
+            temp.path(),
+            session_id,
+            serde_json::json!([
+                {
+                    "id": "m1",
+                    "type": "user",
+                    "timestamp": "2025-01-01T10:00:00Z",
+                    "content": "List the files"
+                },
+                {
+                    "id": "m2",
+                    "type": "gemini",
+                    "timestamp": "2025-01-01T10:00:05Z",
+                    "content": "Here they are.",
+                    "thoughts": [{"subject": "Listing", "description": "Run ls"}],
+                    "tokens": {"input": 120, "output": 30},
+                    "toolCalls": [{
+                        "id": "call_1",
+                        "name": "run_shell_command",
+                        "args": {"command": "ls"},
+                        "status": "success",
+                        "result": [{
+                            "functionResponse": {
+                                "id": "call_1",
+                                "name": "run_shell_command",
+                                "response": {"output": "Cargo.toml\nsrc"}
+                            }
+                        }]
+                    }]
+                },
+                {"id": "m3", "type": "info", "content": "Model switched"}
+            ]),
+        );
+
+        let (messages, debug_entries) = parse_gemini_history_file_with_debug(&path).unwrap();
+
+        let roles: Vec<_> = messages.iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            vec![
+                MessageRole::User,
+                MessageRole::Reasoning,
+                MessageRole::Assistant,
+                MessageRole::Tool,
+                MessageRole::Summary,
+            ]
+        );
+        assert_eq!(messages[3].tool_args.as_deref(), Some("ls"));
+        assert!(messages[3].content.contains("Cargo.toml"));
+        let summary = messages[4].summary.as_ref().unwrap();
+        assert_eq!(summary.input_tokens, 120);
+        assert_eq!(summary.output_tokens, 30);
+        assert_eq!(summary.duration_secs, 5);
+
+        assert_eq!(debug_entries.len(), 3);
+        assert_eq!(debug_entries[1].status, "INCLUDE");
+        assert_eq!(debug_entries[2].status, "SKIP");
+    }
+
+    #[test]
+    fn test_find_gemini_session_file_checks_session_id() {
+        let temp = TempDir::new().unwrap();
+        let session_id = "0f1e2d3c-aaaa-bbbb-cccc-123456789abc";
+        let path = write_gemini_chat(temp.path(), session_id, serde_json::json!([]));
+
+        assert_eq!(
+            find_gemini_session_file(temp.path(), session_id).unwrap(),
+            path
+        );
+        // Same eight-character prefix but a different session
+        assert!(find_gemini_session_file(temp.path(), "0f1e2d3c-ffff").is_err());
+    }
 }
@@ -0,0 +1,943 @@
+//! Runner for local OpenAI-compatible chat-completions servers (Ollama, LM Studio).
+//!
+//! There is no agent CLI to spawn: conduit drives the conversation itself,
+//! offering the model a minimal tool set (`read_file`, `write_file`, `bash`)
+//! scoped to the working directory and translating every step into the same
+//! `AgentEvent` stream the CLI runners produce.
+
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Value};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+
+use crate::agent::error::AgentError;
+use crate::agent::events::{
+    AgentEvent, AssistantMessageEvent, ErrorEvent, FileChangedEvent, FileOperation,
+    SessionInitEvent, TokenUsage, ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent,
+    TurnFailedEvent,
+};
+use crate::agent::runner::{
+    AgentHandle, AgentInput, AgentMode, AgentRunner, AgentStartConfig, AgentType, NO_PID,
+};
+use crate::agent::session::SessionId;
+use crate::agent::ModelRegistry;
+
+/// Local models can take minutes on a cold start or a long prompt
+const LOCAL_REQUEST_TIMEOUT: Duration = Duration::from_secs(600);
+const LOCAL_BASH_TIMEOUT: Duration = Duration::from_secs(120);
+/// Tool output beyond this is truncated before it goes back to the model
+const LOCAL_TOOL_OUTPUT_MAX_CHARS: usize = 30_000;
+const LOCAL_LOG_PREVIEW_CHARS: usize = 200;
+
+const LOCAL_SYSTEM_PROMPT: &str = "You are a coding assistant working in a local repository. \
+Use the provided tools to inspect files, edit them and run shell commands instead of guessing. \
+Paths are relative to the working directory. Keep answers concise.";
+
+const LOCAL_PLAN_MODE_PROMPT: &str = "You are in Plan mode: only read files and propose a plan. \
+Do not modify anything.";
+
+async fn send_event_or_log(
+    event_tx: &mpsc::Sender<AgentEvent>,
+    event: AgentEvent,
+    context: &'static str,
+) -> bool {
+    if let Err(err) = event_tx.send(event).await {
+        tracing::debug!(error = %err, context, "Local model event channel closed");
+        return false;
+    }
+    true
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    let char_count = text.chars().count();
+    if char_count <= max_chars {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_chars).collect();
+    format!("{truncated}...(truncated, {char_count} chars)")
+}
+
+/// Chat message in the OpenAI wire format.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ChatMessage {
+    role: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tool_calls: Vec<ToolCall>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tool_call_id: Option<String>,
+}
+
+impl ChatMessage {
+    fn text(role: &str, content: impl Into<String>) -> Self {
+        Self {
+            role: role.to_string(),
+            content: Some(content.into()),
+            ..Default::default()
+        }
+    }
+
+    fn tool_result(tool_call_id: &str, content: String) -> Self {
+        Self {
+            role: "tool".to_string(),
+            content: Some(content),
+            tool_call_id: Some(tool_call_id.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ToolCall {
+    #[serde(default)]
+    id: String,
+    #[serde(rename = "type", default = "function_call_type")]
+    kind: String,
+    function: FunctionCall,
+}
+
+fn function_call_type() -> String {
+    "function".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FunctionCall {
+    name: String,
+    /// JSON-encoded arguments (some servers send an object instead of a string)
+    #[serde(default, deserialize_with = "arguments_as_string")]
+    arguments: String,
+}
+
+fn arguments_as_string<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match Value::deserialize(deserializer)? {
+        Value::String(text) => text,
+        Value::Null => String::new(),
+        other => other.to_string(),
+    })
+}
+
+#[derive(Debug, Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: &'a [ChatMessage],
+    tools: Vec<Value>,
+    stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    #[serde(default)]
+    choices: Vec<ChatChoice>,
+    #[serde(default)]
+    usage: Option<ChatUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChatUsage {
+    #[serde(default)]
+    prompt_tokens: i64,
+    #[serde(default)]
+    completion_tokens: i64,
+    #[serde(default)]
+    total_tokens: i64,
+}
+
+/// Tools offered to the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LocalTool {
+    ReadFile,
+    WriteFile,
+    Bash,
+}
+
+impl LocalTool {
+    fn for_mode(mode: AgentMode) -> &'static [LocalTool] {
+        match mode {
+            AgentMode::Build => &[LocalTool::ReadFile, LocalTool::WriteFile, LocalTool::Bash],
+            AgentMode::Plan => &[LocalTool::ReadFile],
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "read_file" => Some(LocalTool::ReadFile),
+            "write_file" => Some(LocalTool::WriteFile),
+            "bash" => Some(LocalTool::Bash),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            LocalTool::ReadFile => "read_file",
+            LocalTool::WriteFile => "write_file",
+            LocalTool::Bash => "bash",
+        }
+    }
+
+    /// Name shown in the transcript (matches the Claude tool renderers)
+    fn display_name(&self) -> &'static str {
+        match self {
+            LocalTool::ReadFile => "Read",
+            LocalTool::WriteFile => "Write",
+            LocalTool::Bash => "Bash",
+        }
+    }
+
+    fn definition(&self) -> Value {
+        let (description, parameters) = match self {
+            LocalTool::ReadFile => (
+                "Read a text file from the working directory.",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string", "description": "File path relative to the working directory" }
+                    },
+                    "required": ["path"]
+                }),
+            ),
+            LocalTool::WriteFile => (
+                "Create or overwrite a text file in the working directory.",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string", "description": "File path relative to the working directory" },
+                        "content": { "type": "string", "description": "Full new file contents" }
+                    },
+                    "required": ["path", "content"]
+                }),
+            ),
+            LocalTool::Bash => (
+                "Run a shell command in the working directory and return its output.",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "command": { "type": "string", "description": "Command to run with sh -c" }
+                    },
+                    "required": ["command"]
+                }),
+            ),
+        };
+        json!({
+            "type": "function",
+            "function": {
+                "name": self.name(),
+                "description": description,
+                "parameters": parameters,
+            }
+        })
+    }
+
+    /// Arguments in the shape the transcript renderers expect
+    fn display_arguments(&self, args: &Value) -> Value {
+        let arg = |key: &str| args.get(key).cloned().unwrap_or(Value::Null);
+        match self {
+            LocalTool::ReadFile => json!({ "file_path": arg("path") }),
+            LocalTool::WriteFile => json!({ "file_path": arg("path"), "content": arg("content") }),
+            LocalTool::Bash => json!({ "command": arg("command") }),
+        }
+    }
+}
+
+/// Resolve a model-supplied path inside `working_dir`, rejecting anything
+/// that would land outside it.
+fn resolve_path(working_dir: &Path, path: &str) -> Result<PathBuf, String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err("Path is empty".to_string());
+    }
+    let candidate = Path::new(trimmed);
+    let relative = if candidate.is_absolute() {
+        candidate
+            .strip_prefix(working_dir)
+            .map_err(|_| format!("{} is outside the working directory", trimmed))?
+    } else {
+        candidate
+    };
+
+    let mut resolved = working_dir.to_path_buf();
+    let mut depth = 0usize;
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => {
+                resolved.push(part);
+                depth += 1;
+            }
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if depth == 0 {
+                    return Err(format!("{} is outside the working directory", trimmed));
+                }
+                resolved.pop();
+                depth -= 1;
+            }
+            Component::RootDir | Component::Prefix(_) => {
+                return Err(format!("{} is outside the working directory", trimmed));
+            }
+        }
+    }
+    Ok(resolved)
+}
+
+/// Connection settings for the local server.
+#[derive(Debug, Clone)]
+struct LocalEndpoint {
+    base_url: String,
+    api_key: Option<String>,
+    max_tool_rounds: usize,
+}
+
+/// Runner for a local OpenAI-compatible chat-completions endpoint
+pub struct LocalModelRunner {
+    endpoint: Option<LocalEndpoint>,
+    client: Client,
+}
+
+impl LocalModelRunner {
+    /// Runner without an endpoint; reports itself unavailable.
+    pub fn new() -> Self {
+        Self {
+            endpoint: None,
+            client: Client::new(),
+        }
+    }
+
+    pub fn with_endpoint(
+        base_url: impl Into<String>,
+        api_key: Option<String>,
+        max_tool_rounds: usize,
+    ) -> Self {
+        Self {
+            endpoint: Some(LocalEndpoint {
+                base_url: base_url.into().trim_end_matches('/').to_string(),
+                api_key,
+                max_tool_rounds: max_tool_rounds.max(1),
+            }),
+            client: Client::new(),
+        }
+    }
+
+    fn system_prompt(config: &AgentStartConfig) -> String {
+        let mut prompt = LOCAL_SYSTEM_PROMPT.to_string();
+        prompt.push_str(&format!(
+            "\nWorking directory: {}",
+            config.working_dir.display()
+        ));
+        if config.agent_mode == AgentMode::Plan {
+            prompt.push_str(&format!("\n\n{}", LOCAL_PLAN_MODE_PROMPT));
+        }
+        if let Some(custom) = config
+            .system_prompt
+            .as_deref()
+            .filter(|p| !p.trim().is_empty())
+        {
+            prompt.push_str(&format!("\n\n{}", custom));
+        }
+        prompt
+    }
+}
+
+impl Default for LocalModelRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One conversation with the local model, alive for the lifetime of the handle.
+struct LocalSession {
+    client: Client,
+    endpoint: LocalEndpoint,
+    working_dir: PathBuf,
+    agent_mode: AgentMode,
+    model: String,
+    messages: Vec<ChatMessage>,
+    event_tx: mpsc::Sender<AgentEvent>,
+    next_call_id: u64,
+    /// Fired by `stop`/`kill`; ends the session at the next step
+    cancel: CancellationToken,
+}
+
+impl LocalSession {
+    /// Run one user turn to completion. Returns false once the event
+    /// receiver is gone or the session was cancelled, and it should end.
+    async fn run_turn(&mut self, prompt: String) -> bool {
+        if !send_event_or_log(
+            &self.event_tx,
+            AgentEvent::TurnStarted,
+            "local_turn_started",
+        )
+        .await
+        {
+            return false;
+        }
+        self.messages.push(ChatMessage::text("user", prompt));
+
+        let mut usage = TokenUsage::default();
+        for _ in 0..self.endpoint.max_tool_rounds {
+            if self.is_cancelled() {
+                return false;
+            }
+            let response = match self.complete().await {
+                Ok(response) => response,
+                Err(_) if self.is_cancelled() => return false,
+                Err(error) => return self.fail_turn(error).await,
+            };
+            if let Some(step) = response.usage {
+                usage.input_tokens += step.prompt_tokens;
+                usage.output_tokens += step.completion_tokens;
+                usage.total_tokens += if step.total_tokens > 0 {
+                    step.total_tokens
+                } else {
+                    step.prompt_tokens + step.completion_tokens
+                };
+            }
+            let Some(mut message) = response.choices.into_iter().next().map(|c| c.message) else {
+                return self
+                    .fail_turn("Local model returned no choices".to_string())
+                    .await;
+            };
+            message.role = "assistant".to_string();
+            for call in &mut message.tool_calls {
+                if call.id.is_empty() {
+                    self.next_call_id += 1;
+                    call.id = format!("local-call-{}", self.next_call_id);
+                }
+            }
+            let tool_calls = message.tool_calls.clone();
+
+            if let Some(text) = message.content.as_deref().filter(|t| !t.trim().is_empty()) {
+                let event = AgentEvent::AssistantMessage(AssistantMessageEvent {
+                    text: text.to_string(),
+                    is_final: tool_calls.is_empty(),
+                });
+                if !send_event_or_log(&self.event_tx, event, "local_assistant_message").await {
+                    return false;
+                }
+            }
+            self.messages.push(message);
+
+            if tool_calls.is_empty() {
+                return send_event_or_log(
+                    &self.event_tx,
+                    AgentEvent::TurnCompleted(TurnCompletedEvent { usage }),
+                    "local_turn_completed",
+                )
+                .await;
+            }
+
+            for call in tool_calls {
+                if self.is_cancelled() {
+                    return false;
+                }
+                let Some(output) = self.run_tool_call(&call).await else {
+                    return false;
+                };
+                self.messages
+                    .push(ChatMessage::tool_result(&call.id, output));
+            }
+        }
+
+        self.fail_turn(format!(
+            "Stopped after {} tool rounds without a final answer",
+            self.endpoint.max_tool_rounds
+        ))
+        .await
+    }
+
+    fn is_cancelled(&self) -> bool {
+        if self.cancel.is_cancelled() {
+            tracing::debug!(model = %self.model, "Local model session cancelled");
+            return true;
+        }
+        false
+    }
+
+    async fn fail_turn(&self, error: String) -> bool {
+        tracing::warn!(error = %error, model = %self.model, "Local model turn failed");
+        send_event_or_log(
+            &self.event_tx,
+            AgentEvent::TurnFailed(TurnFailedEvent { error }),
+            "local_turn_failed",
+        )
+        .await
+    }
+
+    /// Request the next assistant message, giving up early if the session is
+    /// dropped or cancelled.
+    async fn complete(&self) -> Result<ChatResponse, String> {
+        let url = format!("{}/chat/completions", self.endpoint.base_url);
+        let tools = LocalTool::for_mode(self.agent_mode)
+            .iter()
+            .map(LocalTool::definition)
+            .collect();
+        let body = ChatRequest {
+            model: &self.model,
+            messages: &self.messages,
+            tools,
+            stream: false,
+        };
+        let mut request = self
+            .client
+            .post(&url)
+            .timeout(LOCAL_REQUEST_TIMEOUT)
+            .json(&body);
+        if let Some(api_key) = self.endpoint.api_key.as_deref() {
+            request = request.bearer_auth(api_key);
+        }
+
+        let exchange = async {
+            let response = request
+                .send()
+                .await
+                .map_err(|err| format!("Could not reach local model server at {}: {}", url, err))?;
+            let status = response.status();
+            let text = response
+                .text()
+                .await
+                .map_err(|err| format!("Failed to read local model response: {}", err))?;
+            Ok::<_, String>((status, text))
+        };
+        let (status, text) = tokio::select! {
+            exchange = exchange => exchange?,
+            _ = self.event_tx.closed() => return Err("Session closed".to_string()),
+            _ = self.cancel.cancelled() => return Err("Session cancelled".to_string()),
+        };
+        if !status.is_success() {
+            return Err(format!(
+                "Local model server returned {}: {}",
+                status,
+                truncate_chars(text.trim(), LOCAL_LOG_PREVIEW_CHARS)
+            ));
+        }
+        serde_json::from_str(&text).map_err(|err| {
+            format!(
+                "Unexpected local model response ({}): {}",
+                err,
+                truncate_chars(&text, LOCAL_LOG_PREVIEW_CHARS)
+            )
+        })
+    }
+
+    /// Execute one tool call, emitting its start/completion events.
+    /// Returns the text handed back to the model, or None if the session ended.
+    async fn run_tool_call(&mut self, call: &ToolCall) -> Option<String> {
+        let tool = LocalTool::parse(&call.function.name)
+            .filter(|tool| LocalTool::for_mode(self.agent_mode).contains(tool));
+        let args: Value = if call.function.arguments.trim().is_empty() {
+            json!({})
+        } else {
+            match serde_json::from_str(&call.function.arguments) {
+                Ok(args) => args,
+                Err(err) => {
+                    return Some(format!(
+                        "Error: arguments for {} are not valid JSON: {}",
+                        call.function.name, err
+                    ));
+                }
+            }
+        };
+
+        let (tool_name, display_args) = match tool {
+            Some(tool) => (
+                tool.display_name().to_string(),
+                tool.display_arguments(&args),
+            ),
+            None => (call.function.name.clone(), args.clone()),
+        };
+        let started = AgentEvent::ToolStarted(ToolStartedEvent {
+            tool_name,
+            tool_id: call.id.clone(),
+            arguments: display_args,
+        });
+        if !send_event_or_log(&self.event_tx, started, "local_tool_started").await {
+            return None;
+        }
+
+        let result = match tool {
+            Some(tool) => self.execute(tool, &args).await,
+            None => Err(format!(
+                "Tool {} is not available{}",
+                call.function.name,
+                if self.agent_mode == AgentMode::Plan {
+                    " in Plan mode"
+                } else {
+                    ""
+                }
+            )),
+        };
+        let (success, output) = match result {
+            Ok(output) => (true, output),
+            Err(error) => (false, error),
+        };
+        let output = truncate_chars(&output, LOCAL_TOOL_OUTPUT_MAX_CHARS);
+        let completed = AgentEvent::ToolCompleted(ToolCompletedEvent {
+            tool_id: call.id.clone(),
+            success,
+            result: success.then(|| output.clone()),
+            error: (!success).then(|| output.clone()),
+        });
+        if !send_event_or_log(&self.event_tx, completed, "local_tool_completed").await {
+            return None;
+        }
+
+        Some(if success {
+            output
+        } else {
+            format!("Error: {}", output)
+        })
+    }
+
+    async fn execute(&self, tool: LocalTool, args: &Value) -> Result<String, String> {
+        let arg = |key: &str| {
+            args.get(key)
+                .and_then(Value::as_str)
+                .ok_or_else(|| format!("Missing string argument '{}'", key))
+        };
+        match tool {
+            LocalTool::ReadFile => {
+                let path = resolve_path(&self.working_dir, arg("path")?)?;
+                tokio::fs::read_to_string(&path)
+                    .await
+                    .map_err(|err| format!("Failed to read {}: {}", path.display(), err))
+            }
+            LocalTool::WriteFile => {
+                let path = resolve_path(&self.working_dir, arg("path")?)?;
+                let content = arg("content")?;
+                let existed = tokio::fs::try_exists(&path).await.unwrap_or(false);
+                if let Some(parent) = path.parent() {
+                    tokio::fs::create_dir_all(parent)
+                        .await
+                        .map_err(|err| format!("Failed to create {}: {}", parent.display(), err))?;
+                }
+                tokio::fs::write(&path, content)
+                    .await
+                    .map_err(|err| format!("Failed to write {}: {}", path.display(), err))?;
+                let changed = AgentEvent::FileChanged(FileChangedEvent {
+                    path: path.to_string_lossy().to_string(),
+                    operation: if existed {
+                        FileOperation::Update
+                    } else {
+                        FileOperation::Create
+                    },
+                });
+                send_event_or_log(&self.event_tx, changed, "local_file_changed").await;
+                Ok(format!(
+                    "Wrote {} bytes to {}",
+                    content.len(),
+                    path.display()
+                ))
+            }
+            LocalTool::Bash => {
+                let command = arg("command")?;
+                let child = Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .current_dir(&self.working_dir)
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped())
+                    .kill_on_drop(true)
+                    .spawn()
+                    .map_err(|err| format!("Failed to run command: {}", err))?;
+                // Dropping the child on cancel kills it (kill_on_drop)
+                let output = tokio::select! {
+                    output = timeout(LOCAL_BASH_TIMEOUT, child.wait_with_output()) => output,
+                    _ = self.cancel.cancelled() => return Err("Command interrupted".to_string()),
+                }
+                .map_err(|_| format!("Command timed out after {}s", LOCAL_BASH_TIMEOUT.as_secs()))?
+                .map_err(|err| format!("Failed to run command: {}", err))?;
+
+                let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                if !stderr.trim().is_empty() {
+                    if !text.is_empty() && !text.ends_with('\n') {
+                        text.push('\n');
+                    }
+                    text.push_str(&stderr);
+                }
+                if output.status.success() {
+                    Ok(text)
+                } else {
+                    let code = output
+                        .status
+                        .code()
+                        .map_or_else(|| "signal".to_string(), |code| code.to_string());
+                    Err(format!("Command exited with {}\n{}", code, text))
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl AgentRunner for LocalModelRunner {
+    fn agent_type(&self) -> AgentType {
+        AgentType::Local
+    }
+
+    async fn start(&self, config: AgentStartConfig) -> Result<AgentHandle, AgentError> {
+        let endpoint = self.endpoint.clone().ok_or_else(|| {
+            AgentError::Config("No local model endpoint configured ([local_model])".to_string())
+        })?;
+        if !config.images.is_empty() {
+            return Err(AgentError::NotSupported(
+                "Local models don't support image attachments".to_string(),
+            ));
+        }
+
+        let model = config
+            .model
+            .clone()
+            .filter(|model| !model.trim().is_empty())
+            .unwrap_or_else(|| ModelRegistry::default_model(AgentType::Local));
+        let (event_tx, event_rx) = mpsc::channel::<AgentEvent>(256);
+        let (input_tx, mut input_rx) = mpsc::channel::<AgentInput>(16);
+        let cancel = CancellationToken::new();
+
+        let mut session = LocalSession {
+            client: self.client.clone(),
+            endpoint,
+            working_dir: config.working_dir.clone(),
+            agent_mode: config.agent_mode,
+            model,
+            messages: vec![ChatMessage::text("system", Self::system_prompt(&config))],
+            event_tx,
+            next_call_id: 0,
+            cancel: cancel.clone(),
+        };
+        let prompt = config.prompt;
+
+        tokio::spawn(async move {
+            let init = AgentEvent::SessionInit(SessionInitEvent {
+                session_id: SessionId::new(),
+                model: Some(session.model.clone()),
+            });
+            if !send_event_or_log(&session.event_tx, init, "local_session_init").await {
+                return;
+            }
+            if !session.run_turn(prompt).await {
+                return;
+            }
+
+            loop {
+                let input = tokio::select! {
+                    input = input_rx.recv() => input,
+                    _ = session.cancel.cancelled() => None,
+                };
+                let Some(input) = input else {
+                    return;
+                };
+                match input {
+                    AgentInput::CodexPrompt {
+                        text,
+                        images,
+                        model,
+                    } => {
+                        if !images.is_empty()
+                            && !send_event_or_log(
+                                &session.event_tx,
+                                AgentEvent::Error(ErrorEvent {
+                                    message: "Local models don't support image attachments."
+                                        .to_string(),
+                                    is_fatal: false,
+                                    code: None,
+                                    details: None,
+                                }),
+                                "local_images_unsupported",
+                            )
+                            .await
+                        {
+                            return;
+                        }
+                        if let Some(model) = model.filter(|model| !model.trim().is_empty()) {
+                            session.model = model;
+                        }
+                        if !session.run_turn(text).await {
+                            return;
+                        }
+                    }
+                    other => {
+                        tracing::debug!(?other, "Ignoring input unsupported by local model runner");
+                    }
+                }
+            }
+        });
+
+        Ok(AgentHandle::new(event_rx, NO_PID, Some(input_tx)).with_cancel(cancel))
+    }
+
+    async fn send_input(&self, handle: &AgentHandle, input: AgentInput) -> Result<(), AgentError> {
+        let input_tx = handle.input_tx.as_ref().ok_or(AgentError::ChannelClosed)?;
+        input_tx
+            .send(input)
+            .await
+            .map_err(|_| AgentError::ChannelClosed)
+    }
+
+    async fn stop(&self, handle: &AgentHandle) -> Result<(), AgentError> {
+        // No process to signal: cancel the session task, which also stops an
+        // in-flight request or command
+        if let Some(cancel) = &handle.cancel {
+            cancel.cancel();
+        }
+        Ok(())
+    }
+
+    async fn kill(&self, handle: &AgentHandle) -> Result<(), AgentError> {
+        self.stop(handle).await
+    }
+
+    fn is_available(&self) -> bool {
+        self.endpoint.is_some()
+    }
+
+    fn binary_path(&self) -> Option<PathBuf> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_path_stays_in_working_dir() {
+        let root = Path::new("/repo");
+        assert_eq!(
+            resolve_path(root, "src/main.rs").unwrap(),
+            PathBuf::from("/repo/src/main.rs")
+        );
+        assert_eq!(
+            resolve_path(root, "./src/../Cargo.toml").unwrap(),
+            PathBuf::from("/repo/Cargo.toml")
+        );
+        assert_eq!(
+            resolve_path(root, "/repo/README.md").unwrap(),
+            PathBuf::from("/repo/README.md")
+        );
+        assert!(resolve_path(root, "../secrets").is_err());
+        assert!(resolve_path(root, "/etc/passwd").is_err());
+        assert!(resolve_path(root, "  ").is_err());
+    }
+
+    #[test]
+    fn test_parse_response_with_tool_calls() {
+        // Arguments as a JSON string (OpenAI) and as an object (some local servers)
+        let body = r#"{
+            "choices": [{
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [
+                        {"id": "call_1", "type": "function",
+                         "function": {"name": "read_file", "arguments": "{\"path\":\"a.rs\"}"}},
+                        {"function": {"name": "bash", "arguments": {"command": "ls"}}}
+                    ]
+                }
+            }],
+            "usage": {"prompt_tokens": 12, "completion_tokens": 3, "total_tokens": 15}
+        }"#;
+        let response: ChatResponse = serde_json::from_str(body).unwrap();
+        let message = &response.choices[0].message;
+        assert_eq!(message.tool_calls.len(), 2);
+        assert_eq!(
+            message.tool_calls[0].function.arguments,
+            r#"{"path":"a.rs"}"#
+        );
+        assert_eq!(
+            message.tool_calls[1].function.arguments,
+            r#"{"command":"ls"}"#
+        );
+        assert_eq!(message.tool_calls[1].kind, "function");
+        assert_eq!(response.usage.unwrap().total_tokens, 15);
+
+        // Round-trips without the optional fields the server may reject
+        let sent = serde_json::to_value(ChatMessage::text("user", "hi")).unwrap();
+        assert_eq!(sent, json!({"role": "user", "content": "hi"}));
+    }
+
+    #[test]
+    fn test_plan_mode_only_offers_read_file() {
+        assert_eq!(LocalTool::for_mode(AgentMode::Plan), &[LocalTool::ReadFile]);
+        assert_eq!(LocalTool::for_mode(AgentMode::Build).len(), 3);
+        for tool in LocalTool::for_mode(AgentMode::Build) {
+            assert_eq!(LocalTool::parse(tool.name()), Some(*tool));
+        }
+    }
+
+    fn session(
+        working_dir: &Path,
+        cancel: CancellationToken,
+    ) -> (LocalSession, mpsc::Receiver<AgentEvent>) {
+        let (event_tx, event_rx) = mpsc::channel(16);
+        let session = LocalSession {
+            client: Client::new(),
+            endpoint: LocalEndpoint {
+                base_url: "http://127.0.0.1:9".to_string(),
+                api_key: None,
+                max_tool_rounds: 4,
+            },
+            working_dir: working_dir.to_path_buf(),
+            agent_mode: AgentMode::Build,
+            model: "test".to_string(),
+            messages: Vec::new(),
+            event_tx,
+            next_call_id: 0,
+            cancel,
+        };
+        (session, event_rx)
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_session_ends_turn_without_failing_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let cancel = CancellationToken::new();
+        let (mut session, mut events) = session(dir.path(), cancel.clone());
+        cancel.cancel();
+
+        assert!(!session.run_turn("hi".to_string()).await);
+        drop(session);
+        assert!(matches!(events.recv().await, Some(AgentEvent::TurnStarted)));
+        assert!(events.recv().await.is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cancel_interrupts_running_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let cancel = CancellationToken::new();
+        let (session, _events) = session(dir.path(), cancel.clone());
+        tokio::spawn({
+            let cancel = cancel.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                cancel.cancel();
+            }
+        });
+
+        let result = timeout(
+            Duration::from_secs(5),
+            session.execute(LocalTool::Bash, &json!({ "command": "sleep 30" })),
+        )
+        .await
+        .expect("cancel should stop the command");
+        assert_eq!(result, Err("Command interrupted".to_string()));
+    }
+}
@@ -330,6 +330,7 @@ impl MockEventBuilder {
                     input_tokens,
                     output_tokens,
                     cached_tokens: 0,
+                    cache_creation_tokens: 0,
                     total_tokens: input_tokens + output_tokens,
                 },
             }));
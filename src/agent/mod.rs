@@ -1,30 +1,46 @@
 pub mod claude;
 pub mod codex;
+pub mod diagnostics;
 pub mod display;
+pub mod edit_review;
 pub mod error;
 pub mod events;
+pub mod exec_target;
 pub mod gemini;
 pub mod history;
+pub mod local;
 pub mod mock;
+pub mod model_discovery;
 pub mod models;
 pub mod opencode;
+pub mod replay;
 pub mod runner;
 pub mod session;
 pub mod stream;
+pub mod triage;
 
 pub use claude::ClaudeCodeRunner;
 pub use codex::CodexCliRunner;
+pub use diagnostics::{StderrBuffer, StderrLine, DEFAULT_STDERR_LINES};
 pub use display::MessageDisplay;
-pub use error::AgentError;
+pub use edit_review::{EditReview, HunkDecision, ReviewOutcome};
+pub use error::{AgentError, AGENT_CRASHED_CODE};
 pub use events::*;
+pub use exec_target::ExecutionTarget;
 pub use gemini::GeminiCliRunner;
 pub use history::{
-    load_claude_history_with_debug, load_codex_history_with_debug,
+    load_claude_history_with_debug, load_codex_history_with_debug, load_gemini_history_with_debug,
     load_opencode_history_for_dir_with_debug, load_opencode_history_with_debug, HistoryDebugEntry,
     HistoryError,
 };
+pub use local::LocalModelRunner;
 pub use mock::{MockAgentRunner, MockConfig, MockEventBuilder, MockStartError};
-pub use models::{ModelInfo, ModelRegistry};
+pub use models::{ModelInfo, ModelPricing, ModelRegistry};
 pub use opencode::OpencodeRunner;
-pub use runner::{AgentHandle, AgentInput, AgentMode, AgentRunner, AgentStartConfig, AgentType};
+pub use replay::{AgentRecorder, ReplayRunner};
+pub use runner::{
+    AgentCapabilities, AgentHandle, AgentInput, AgentMode, AgentRunner, AgentStartConfig,
+    AgentType, CodexApprovalPolicy, CodexSandboxMode, NO_PID,
+};
 pub use session::{SessionId, SessionMetadata, SessionStatus};
+pub use triage::FailureKind;
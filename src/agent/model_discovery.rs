@@ -0,0 +1,378 @@
+//! Model discovery from installed agent CLIs.
+//!
+//! The built-in tables in [`ModelRegistry`](crate::agent::ModelRegistry) lag
+//! behind agent releases. Claude Code names its current models in
+//! `claude --help`, and the Codex app-server answers `model/list`, so both are
+//! queried (at most once a day, cached on disk) and merged into the registry.
+
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::agent::AgentType;
+
+const MODEL_CACHE_TTL_SECS: u64 = 60 * 60 * 24;
+
+/// Codex app-server can take a while to boot on a cold npm cache
+const CODEX_DISCOVERY_TIMEOUT: Duration = Duration::from_secs(15);
+
+const CODEX_MODEL_LIST_REQUEST_ID: i64 = 2;
+
+/// A model reported by an agent CLI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscoveredModel {
+    pub id: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ModelCache {
+    generated_at: u64,
+    models: Vec<DiscoveredModel>,
+}
+
+/// Models the agent CLI at `binary` reports, served from a day-old cache
+/// unless `force` is set. Falls back to a stale cache when discovery fails.
+pub fn load_discovered_models(
+    agent_type: AgentType,
+    binary: &Path,
+    force: bool,
+) -> Vec<DiscoveredModel> {
+    let cache_file = cache_path(agent_type);
+    if !force {
+        if let Some(cache) = cache_file.as_deref().and_then(load_cache) {
+            if cache_is_fresh(&cache) {
+                return cache.models;
+            }
+        }
+    }
+
+    let discovered = match agent_type {
+        AgentType::Claude => discover_claude_models(binary),
+        AgentType::Codex => discover_codex_models(binary),
+        _ => return Vec::new(),
+    };
+    match discovered {
+        Ok(models) if !models.is_empty() => {
+            if let Some(path) = &cache_file {
+                if let Err(err) = save_cache(path, &models) {
+                    tracing::debug!(agent = %agent_type, error = %err, "Failed to save model cache");
+                }
+            }
+            models
+        }
+        Ok(_) => Vec::new(),
+        Err(err) => {
+            tracing::debug!(agent = %agent_type, error = %err, "Failed to discover models");
+            cache_file
+                .as_deref()
+                .and_then(load_cache)
+                .map(|cache| cache.models)
+                .unwrap_or_default()
+        }
+    }
+}
+
+fn discover_claude_models(binary: &Path) -> io::Result<Vec<DiscoveredModel>> {
+    let output = Command::new(binary)
+        .arg("--help")
+        .stdin(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "claude --help failed with status {:?}",
+            output.status.code()
+        )));
+    }
+    Ok(parse_claude_help(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Pull the quoted model names out of the `--model` option description, e.g.
+/// "Provide an alias for the latest model (e.g. 'sonnet' or 'opus') or a
+/// model's full name (e.g. 'claude-sonnet-4-5-20250929')."
+fn parse_claude_help(help: &str) -> Vec<DiscoveredModel> {
+    let mut description = String::new();
+    let mut in_model_option = false;
+    for line in help.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('-') {
+            if in_model_option {
+                break;
+            }
+            in_model_option = trimmed.starts_with("--model ") || trimmed.starts_with("--model,");
+        }
+        if in_model_option {
+            description.push_str(trimmed);
+            description.push(' ');
+        }
+    }
+
+    let mut models: Vec<DiscoveredModel> = Vec::new();
+    for candidate in description.split('\'').skip(1).step_by(2) {
+        let valid = !candidate.is_empty()
+            && candidate.chars().all(|c| {
+                c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '.' | '[' | ']')
+            });
+        if !valid || models.iter().any(|model| model.id == candidate) {
+            continue;
+        }
+        models.push(DiscoveredModel {
+            id: candidate.to_string(),
+            display_name: claude_display_name(candidate),
+            description: "Reported by claude --help".to_string(),
+        });
+    }
+    models
+}
+
+/// `claude-opus-4-1-20250805` -> `Opus 4.1 (20250805)`; aliases are just capitalized.
+/// The snapshot date stays so pinned models don't read like the built-in aliases.
+fn claude_display_name(id: &str) -> String {
+    let mut parts = id.strip_prefix("claude-").unwrap_or(id).split('-');
+    let Some(family) = parts.next() else {
+        return id.to_string();
+    };
+    let mut chars = family.chars();
+    let mut name = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+        None => return id.to_string(),
+    };
+    let (dates, version): (Vec<&str>, Vec<&str>) = parts
+        .filter(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+        .partition(|part| part.len() == 8);
+    if !version.is_empty() {
+        name.push_str(&format!(" {}", version.join(".")));
+    }
+    if let Some(date) = dates.first() {
+        name.push_str(&format!(" ({})", date));
+    }
+    name
+}
+
+/// Ask `codex app-server` for its model list. This speaks raw JSON-RPC rather
+/// than the typed protocol so discovery keeps working across protocol revisions.
+fn discover_codex_models(binary: &Path) -> io::Result<Vec<DiscoveredModel>> {
+    let mut child = Command::new(binary)
+        .arg("app-server")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    let result = (|| {
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| io::Error::other("codex app-server stdin unavailable"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| io::Error::other("codex app-server stdout unavailable"))?;
+
+        let requests = [
+            json!({
+                "id": 1,
+                "method": "initialize",
+                "params": {
+                    "clientInfo": {
+                        "name": "conduit",
+                        "title": "Conduit",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    }
+                }
+            }),
+            json!({ "method": "initialized" }),
+            json!({ "id": CODEX_MODEL_LIST_REQUEST_ID, "method": "model/list", "params": {} }),
+        ];
+        for request in requests {
+            writeln!(stdin, "{}", request)?;
+        }
+        stdin.flush()?;
+
+        // Read on a helper thread so a hung app-server can't block startup
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                let Ok(message) = serde_json::from_str::<Value>(&line) else {
+                    continue;
+                };
+                if message.get("id").and_then(Value::as_i64) == Some(CODEX_MODEL_LIST_REQUEST_ID) {
+                    if tx.send(message).is_err() {
+                        tracing::debug!("Codex model discovery gave up before the response");
+                    }
+                    break;
+                }
+            }
+        });
+
+        let response = rx
+            .recv_timeout(CODEX_DISCOVERY_TIMEOUT)
+            .map_err(|_| io::Error::other("timed out waiting for model/list"))?;
+        if let Some(error) = response.get("error") {
+            return Err(io::Error::other(format!("model/list failed: {}", error)));
+        }
+        Ok(parse_codex_model_list(
+            response.get("result").unwrap_or(&Value::Null),
+        ))
+    })();
+
+    if let Err(err) = child.kill() {
+        tracing::debug!(error = %err, "Failed to stop codex app-server after model discovery");
+    }
+    if let Err(err) = child.wait() {
+        tracing::debug!(error = %err, "Failed to reap codex app-server after model discovery");
+    }
+    result
+}
+
+fn parse_codex_model_list(result: &Value) -> Vec<DiscoveredModel> {
+    let entries = result
+        .get("data")
+        .or_else(|| result.get("models"))
+        .and_then(Value::as_array);
+    let Some(entries) = entries else {
+        return Vec::new();
+    };
+
+    let text = |entry: &Value, keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| entry.get(*key).and_then(Value::as_str))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let mut models: Vec<DiscoveredModel> = Vec::new();
+    for entry in entries {
+        let Some(id) = text(entry, &["model", "id"]) else {
+            continue;
+        };
+        if models.iter().any(|model| model.id == id) {
+            continue;
+        }
+        models.push(DiscoveredModel {
+            display_name: text(entry, &["displayName", "display_name"])
+                .unwrap_or_else(|| id.clone()),
+            description: text(entry, &["description"]).unwrap_or_default(),
+            id,
+        });
+    }
+    models
+}
+
+fn cache_path(agent_type: AgentType) -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| {
+        dir.join("conduit")
+            .join(format!("{}_models.json", agent_type.as_str()))
+    })
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn load_cache(path: &Path) -> Option<ModelCache> {
+    let data = match fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return None,
+        Err(err) => {
+            tracing::debug!(path = %path.display(), error = %err, "Failed to read model cache");
+            return None;
+        }
+    };
+    match serde_json::from_str(&data) {
+        Ok(cache) => Some(cache),
+        Err(err) => {
+            tracing::debug!(path = %path.display(), error = %err, "Failed to parse model cache");
+            None
+        }
+    }
+}
+
+fn save_cache(path: &Path, models: &[DiscoveredModel]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let cache = ModelCache {
+        generated_at: now_secs(),
+        models: models.to_vec(),
+    };
+    let payload = serde_json::to_string_pretty(&cache)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    fs::write(path, payload)
+}
+
+fn cache_is_fresh(cache: &ModelCache) -> bool {
+    now_secs().saturating_sub(cache.generated_at) <= MODEL_CACHE_TTL_SECS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_claude_help_reads_model_option_only() {
+        let help = "\
+Options:
+  --verbose                        Override verbose mode setting from config
+  --model <model>                  Model for the current session. Provide an alias for the
+                                   latest model (e.g. 'sonnet' or 'opus') or a model's full
+                                   name (e.g. 'claude-sonnet-4-5-20250929').
+  --fallback-model <model>         Enable automatic fallback (e.g. 'claude-haiku-4-5')
+";
+        let ids: Vec<String> = parse_claude_help(help)
+            .into_iter()
+            .map(|model| model.id)
+            .collect();
+        assert_eq!(ids, vec!["sonnet", "opus", "claude-sonnet-4-5-20250929"]);
+    }
+
+    #[test]
+    fn test_claude_display_name_drops_prefix_and_date() {
+        assert_eq!(
+            claude_display_name("claude-opus-4-1-20250805"),
+            "Opus 4.1 (20250805)"
+        );
+        assert_eq!(claude_display_name("claude-haiku-4-5"), "Haiku 4.5");
+        assert_eq!(claude_display_name("sonnet"), "Sonnet");
+    }
+
+    #[test]
+    fn test_parse_codex_model_list_prefers_model_slug() {
+        let result = json!({
+            "data": [
+                {
+                    "id": "gpt-5.3-codex",
+                    "model": "gpt-5.3-codex",
+                    "displayName": "GPT-5.3-Codex",
+                    "description": "Newest Codex model",
+                    "isDefault": true
+                },
+                { "id": "gpt-5.2" },
+                { "id": "gpt-5.2" },
+                { "displayName": "missing id" }
+            ],
+            "nextCursor": null
+        });
+        let models = parse_codex_model_list(&result);
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].id, "gpt-5.3-codex");
+        assert_eq!(models[0].display_name, "GPT-5.3-Codex");
+        assert_eq!(models[0].description, "Newest Codex model");
+        assert_eq!(models[1].display_name, "gpt-5.2");
+        assert!(parse_codex_model_list(&Value::Null).is_empty());
+    }
+}
@@ -1,11 +1,58 @@
 //! Model configuration and registry
 
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::{OnceLock, RwLock};
 
 use tracing::error;
 
+use crate::agent::model_discovery::{load_discovered_models, DiscoveredModel};
 use crate::agent::opencode::load_opencode_models;
-use crate::agent::AgentType;
+use crate::agent::{AgentType, TokenUsage};
+
+/// API list prices in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    pub input_per_million: f64,
+    /// Input tokens served from the prompt cache
+    pub cached_input_per_million: f64,
+    /// Input tokens written to the prompt cache
+    pub cache_write_per_million: f64,
+    pub output_per_million: f64,
+}
+
+impl ModelPricing {
+    /// Pricing with cache writes billed like regular input, which is how
+    /// providers without a write surcharge (OpenAI, Gemini) charge them
+    pub const fn new(input: f64, cached_input: f64, output: f64) -> Self {
+        Self {
+            input_per_million: input,
+            cached_input_per_million: cached_input,
+            cache_write_per_million: input,
+            output_per_million: output,
+        }
+    }
+
+    pub const fn with_cache_write(mut self, cache_write: f64) -> Self {
+        self.cache_write_per_million = cache_write;
+        self
+    }
+
+    /// Cost of `usage` in USD. Cache reads and writes are billed at their own
+    /// rates and the rest of the input at the full rate.
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        let input = usage.input_tokens.max(0);
+        let cache_read = usage.cached_tokens.clamp(0, input);
+        let cache_write = usage.cache_creation_tokens.clamp(0, input - cache_read);
+        let uncached = input - cache_read - cache_write;
+        let output = usage.output_tokens.max(0);
+        (uncached as f64 * self.input_per_million
+            + cache_read as f64 * self.cached_input_per_million
+            + cache_write as f64 * self.cache_write_per_million
+            + output as f64 * self.output_per_million)
+            / 1_000_000.0
+    }
+}
 
 /// Information about a model
 #[derive(Debug, Clone)]
@@ -24,6 +71,8 @@ pub struct ModelInfo {
     pub agent_type: AgentType,
     /// Maximum context window in tokens
     pub context_window: i64,
+    /// List pricing, when known (local and discovered models have none)
+    pub pricing: Option<ModelPricing>,
 }
 
 impl ModelInfo {
@@ -43,6 +92,7 @@ impl ModelInfo {
             is_default: false,
             agent_type,
             context_window,
+            pricing: None,
         }
     }
 
@@ -50,6 +100,11 @@ impl ModelInfo {
         self.is_default = true;
         self
     }
+
+    pub fn with_pricing(mut self, pricing: ModelPricing) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
 }
 
 /// Registry of available models for each agent type
@@ -71,6 +126,76 @@ impl ModelRegistry {
 
     const OPENCODE_DEFAULT_MODEL_ID: &'static str = "default";
 
+    /// Default context window for local models (conservative; servers vary)
+    pub const LOCAL_CONTEXT_WINDOW: i64 = 32_000;
+
+    /// Model requested when `[local_model]` lists no models
+    const LOCAL_FALLBACK_MODEL_ID: &'static str = "llama3.1";
+
+    fn local_store() -> &'static RwLock<Vec<ModelInfo>> {
+        static LOCAL_MODELS: OnceLock<RwLock<Vec<ModelInfo>>> = OnceLock::new();
+        LOCAL_MODELS.get_or_init(|| RwLock::new(Vec::new()))
+    }
+
+    fn local_model(id: &str) -> ModelInfo {
+        ModelInfo::new(
+            AgentType::Local,
+            id,
+            id,
+            id,
+            "Local model",
+            Self::LOCAL_CONTEXT_WINDOW,
+        )
+    }
+
+    /// Replace the local models with those configured in `[local_model]`.
+    /// The first one becomes the default; an empty list offers a fallback model.
+    pub fn set_local_models(model_ids: Vec<String>) {
+        let mut models: Vec<ModelInfo> = Vec::new();
+        for id in model_ids {
+            let id = id.trim();
+            if id.is_empty() || models.iter().any(|model| model.id == id) {
+                continue;
+            }
+            models.push(Self::local_model(id));
+        }
+        if models.is_empty() {
+            models.push(Self::local_model(Self::LOCAL_FALLBACK_MODEL_ID));
+        }
+        if let Some(first) = models.first_mut() {
+            first.is_default = true;
+        }
+        let mut store = match Self::local_store().write() {
+            Ok(guard) => guard,
+            Err(err) => {
+                error!(error = %err, "local_store poisoned in set_local_models");
+                err.into_inner()
+            }
+        };
+        *store = models;
+    }
+
+    pub fn clear_local_models() {
+        let mut store = match Self::local_store().write() {
+            Ok(guard) => guard,
+            Err(err) => {
+                error!(error = %err, "local_store poisoned in clear_local_models");
+                err.into_inner()
+            }
+        };
+        store.clear();
+    }
+
+    pub fn local_models() -> Vec<ModelInfo> {
+        match Self::local_store().read() {
+            Ok(guard) => guard.clone(),
+            Err(err) => {
+                error!(error = %err, "local_store poisoned in local_models");
+                Vec::new()
+            }
+        }
+    }
+
     fn opencode_store() -> &'static RwLock<Vec<ModelInfo>> {
         static OPENCODE_MODELS: OnceLock<RwLock<Vec<ModelInfo>>> = OnceLock::new();
         OPENCODE_MODELS.get_or_init(|| RwLock::new(Vec::new()))
@@ -170,9 +295,85 @@ impl ModelRegistry {
         }
     }
 
+    fn discovered_store() -> &'static RwLock<HashMap<AgentType, Vec<ModelInfo>>> {
+        static DISCOVERED_MODELS: OnceLock<RwLock<HashMap<AgentType, Vec<ModelInfo>>>> =
+            OnceLock::new();
+        DISCOVERED_MODELS.get_or_init(|| RwLock::new(HashMap::new()))
+    }
+
+    /// Record models reported by an agent CLI. Built-in entries keep their
+    /// curated names and defaults; anything new is offered after them.
+    pub fn set_discovered_models(agent_type: AgentType, discovered: Vec<DiscoveredModel>) {
+        let context_window = Self::default_context_window(agent_type);
+        let models = discovered
+            .into_iter()
+            .map(|model| {
+                ModelInfo::new(
+                    agent_type,
+                    &model.id,
+                    &model.display_name,
+                    &model.id,
+                    &model.description,
+                    context_window,
+                )
+            })
+            .collect();
+        let mut store = match Self::discovered_store().write() {
+            Ok(guard) => guard,
+            Err(err) => {
+                error!(error = %err, "discovered_store poisoned in set_discovered_models");
+                err.into_inner()
+            }
+        };
+        store.insert(agent_type, models);
+    }
+
+    /// Query the installed Claude Code and Codex CLIs for their current models.
+    /// Blocking (spawns the CLIs); results are cached for a day unless `force`.
+    pub fn refresh_cli_models(
+        claude_binary: Option<PathBuf>,
+        codex_binary: Option<PathBuf>,
+        force: bool,
+    ) {
+        for (agent_type, binary) in [
+            (AgentType::Claude, claude_binary),
+            (AgentType::Codex, codex_binary),
+        ] {
+            let Some(binary) = binary else {
+                continue;
+            };
+            let models = load_discovered_models(agent_type, &binary, force);
+            if models.is_empty() {
+                continue;
+            }
+            tracing::debug!(agent = %agent_type, count = models.len(), "Discovered CLI models");
+            Self::set_discovered_models(agent_type, models);
+        }
+    }
+
+    /// Append discovered models that aren't already in `builtin` (by ID or alias)
+    fn with_discovered(agent_type: AgentType, mut builtin: Vec<ModelInfo>) -> Vec<ModelInfo> {
+        let discovered = match Self::discovered_store().read() {
+            Ok(guard) => guard.get(&agent_type).cloned().unwrap_or_default(),
+            Err(err) => {
+                error!(error = %err, "discovered_store poisoned in with_discovered");
+                Vec::new()
+            }
+        };
+        for model in discovered {
+            let known = builtin
+                .iter()
+                .any(|existing| existing.id == model.id || existing.alias == model.id);
+            if !known {
+                builtin.push(model);
+            }
+        }
+        builtin
+    }
+
     /// Get available models for Claude Code
     pub fn claude_models() -> Vec<ModelInfo> {
-        vec![
+        let builtin = vec![
             ModelInfo::new(
                 AgentType::Claude,
                 "opus",
@@ -181,7 +382,8 @@ impl ModelRegistry {
                 "Most powerful, best for complex reasoning",
                 Self::CLAUDE_CONTEXT_WINDOW,
             )
-            .as_default(),
+            .as_default()
+            .with_pricing(ModelPricing::new(5.0, 0.5, 25.0).with_cache_write(6.25)),
             ModelInfo::new(
                 AgentType::Claude,
                 "sonnet",
@@ -189,7 +391,8 @@ impl ModelRegistry {
                 "sonnet",
                 "Fast and capable, best for most tasks",
                 Self::CLAUDE_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(ModelPricing::new(3.0, 0.3, 15.0).with_cache_write(3.75)),
             ModelInfo::new(
                 AgentType::Claude,
                 "haiku",
@@ -197,13 +400,15 @@ impl ModelRegistry {
                 "haiku",
                 "Fastest, great for simple tasks",
                 Self::CLAUDE_CONTEXT_WINDOW,
-            ),
-        ]
+            )
+            .with_pricing(ModelPricing::new(1.0, 0.1, 5.0).with_cache_write(1.25)),
+        ];
+        Self::with_discovered(AgentType::Claude, builtin)
     }
 
     /// Get available models for Codex CLI
     pub fn codex_models() -> Vec<ModelInfo> {
-        vec![
+        let builtin = vec![
             ModelInfo::new(
                 AgentType::Codex,
                 "gpt-5.2-codex",
@@ -212,7 +417,8 @@ impl ModelRegistry {
                 "Latest Codex model",
                 Self::CODEX_CONTEXT_WINDOW,
             )
-            .as_default(),
+            .as_default()
+            .with_pricing(ModelPricing::new(1.75, 0.175, 14.0)),
             ModelInfo::new(
                 AgentType::Codex,
                 "gpt-5.2",
@@ -220,7 +426,8 @@ impl ModelRegistry {
                 "gpt-5.2",
                 "Fast and efficient",
                 Self::CODEX_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(ModelPricing::new(1.75, 0.175, 14.0)),
             ModelInfo::new(
                 AgentType::Codex,
                 "gpt-5.1-codex-max",
@@ -228,8 +435,10 @@ impl ModelRegistry {
                 "gpt-5.1-codex-max",
                 "Maximum capability",
                 Self::CODEX_CONTEXT_WINDOW,
-            ),
-        ]
+            )
+            .with_pricing(ModelPricing::new(1.25, 0.125, 10.0)),
+        ];
+        Self::with_discovered(AgentType::Codex, builtin)
     }
 
     /// Get available models for Gemini CLI
@@ -243,7 +452,8 @@ impl ModelRegistry {
                 "Highest quality Gemini model",
                 Self::GEMINI_CONTEXT_WINDOW,
             )
-            .as_default(),
+            .as_default()
+            .with_pricing(ModelPricing::new(1.25, 0.125, 10.0)),
             ModelInfo::new(
                 AgentType::Gemini,
                 "gemini-2.5-flash",
@@ -251,7 +461,8 @@ impl ModelRegistry {
                 "gemini-2.5-flash",
                 "Fast and capable Gemini model",
                 Self::GEMINI_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(ModelPricing::new(0.3, 0.03, 2.5)),
             ModelInfo::new(
                 AgentType::Gemini,
                 "gemini-2.5-flash-lite",
@@ -259,7 +470,8 @@ impl ModelRegistry {
                 "gemini-2.5-flash-lite",
                 "Lowest-latency Gemini model",
                 Self::GEMINI_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(ModelPricing::new(0.1, 0.01, 0.4)),
             ModelInfo::new(
                 AgentType::Gemini,
                 "gemini-3-pro-preview",
@@ -267,7 +479,8 @@ impl ModelRegistry {
                 "gemini-3-pro-preview",
                 "Preview Gemini 3 model",
                 Self::GEMINI_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(ModelPricing::new(2.0, 0.2, 12.0)),
             ModelInfo::new(
                 AgentType::Gemini,
                 "gemini-3-flash-preview",
@@ -275,7 +488,8 @@ impl ModelRegistry {
                 "gemini-3-flash-preview",
                 "Preview Gemini 3 flash model",
                 Self::GEMINI_CONTEXT_WINDOW,
-            ),
+            )
+            .with_pricing(ModelPricing::new(0.5, 0.05, 3.0)),
         ]
     }
 
@@ -285,6 +499,7 @@ impl ModelRegistry {
         models.extend(Self::codex_models());
         models.extend(Self::gemini_models());
         models.extend(Self::opencode_models());
+        models.extend(Self::local_models());
         models
     }
 
@@ -295,6 +510,7 @@ impl ModelRegistry {
             AgentType::Codex => Self::codex_models(),
             AgentType::Gemini => Self::gemini_models(),
             AgentType::Opencode => Self::opencode_models(),
+            AgentType::Local => Self::local_models(),
         }
     }
 
@@ -305,6 +521,11 @@ impl ModelRegistry {
             AgentType::Codex => "gpt-5.2-codex".to_string(),
             AgentType::Gemini => "gemini-2.5-pro".to_string(),
             AgentType::Opencode => Self::OPENCODE_DEFAULT_MODEL_ID.to_string(),
+            AgentType::Local => Self::local_models()
+                .into_iter()
+                .find(|model| model.is_default)
+                .map(|model| model.id)
+                .unwrap_or_else(|| Self::LOCAL_FALLBACK_MODEL_ID.to_string()),
         }
     }
 
@@ -331,11 +552,38 @@ impl ModelRegistry {
             ));
         }
 
+        if agent_type == AgentType::Local {
+            // Whatever the local server has pulled is fair game
+            let trimmed = id_or_alias.trim();
+            if trimmed.is_empty() {
+                return None;
+            }
+            return Some(
+                Self::local_models()
+                    .into_iter()
+                    .find(|m| m.id == trimmed)
+                    .unwrap_or_else(|| Self::local_model(trimmed)),
+            );
+        }
+
         Self::models_for(agent_type)
             .into_iter()
             .find(|m| m.id == id_or_alias || m.alias == id_or_alias)
     }
 
+    /// Pricing for a session's model (`None` = the agent's default model)
+    pub fn pricing(agent_type: AgentType, model_id: Option<&str>) -> Option<ModelPricing> {
+        let model_id = model_id
+            .map(str::to_string)
+            .unwrap_or_else(|| Self::default_model(agent_type));
+        Self::find_model(agent_type, &model_id).and_then(|model| model.pricing)
+    }
+
+    /// Estimated USD cost of `usage` on a model, if its pricing is known
+    pub fn cost(agent_type: AgentType, model_id: Option<&str>, usage: &TokenUsage) -> Option<f64> {
+        Self::pricing(agent_type, model_id).map(|pricing| pricing.cost(usage))
+    }
+
     /// Get the icon for an agent type
     pub fn agent_icon(agent_type: AgentType) -> &'static str {
         match agent_type {
@@ -343,6 +591,7 @@ impl ModelRegistry {
             AgentType::Codex => "◎",
             AgentType::Gemini => "◆",
             AgentType::Opencode => "◍",
+            AgentType::Local => "▣",
         }
     }
 
@@ -353,6 +602,7 @@ impl ModelRegistry {
             AgentType::Codex => "Codex",
             AgentType::Gemini => "Gemini",
             AgentType::Opencode => "OpenCode",
+            AgentType::Local => "Local",
         }
     }
 
@@ -370,6 +620,70 @@ impl ModelRegistry {
             AgentType::Codex => Self::CODEX_CONTEXT_WINDOW,
             AgentType::Gemini => Self::GEMINI_CONTEXT_WINDOW,
             AgentType::Opencode => Self::OPENCODE_CONTEXT_WINDOW,
+            AgentType::Local => Self::LOCAL_CONTEXT_WINDOW,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pricing_bills_cached_input_at_cached_rate() {
+        let pricing = ModelPricing::new(3.0, 0.3, 15.0);
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            cached_tokens: 400_000,
+            cache_creation_tokens: 0,
+            total_tokens: 1_100_000,
+        };
+        // 600k uncached * $3 + 400k cached * $0.30 + 100k output * $15
+        let cost = pricing.cost(&usage);
+        assert!((cost - (1.8 + 0.12 + 1.5)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pricing_bills_cache_writes_at_write_rate() {
+        let pricing = ModelPricing::new(3.0, 0.3, 15.0).with_cache_write(3.75);
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 0,
+            cached_tokens: 500_000,
+            cache_creation_tokens: 400_000,
+            total_tokens: 1_000_000,
+        };
+        // 100k uncached * $3 + 500k read * $0.30 + 400k written * $3.75
+        let cost = pricing.cost(&usage);
+        assert!((cost - (0.3 + 0.15 + 1.5)).abs() < 1e-9);
+
+        // Without a surcharge, writes cost the same as plain input
+        let flat = ModelPricing::new(3.0, 0.3, 15.0).cost(&usage);
+        assert!((flat - (1.5 + 0.15)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cost_uses_the_sessions_model() {
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 0,
+            cached_tokens: 0,
+            cache_creation_tokens: 0,
+            total_tokens: 1_000_000,
+        };
+        let opus = ModelRegistry::cost(AgentType::Claude, Some("opus"), &usage).unwrap();
+        let haiku = ModelRegistry::cost(AgentType::Claude, Some("haiku"), &usage).unwrap();
+        assert!((opus - 5.0).abs() < 1e-9);
+        assert!((haiku - 1.0).abs() < 1e-9);
+        // Unset model falls back to the agent default (opus)
+        assert_eq!(
+            ModelRegistry::cost(AgentType::Claude, None, &usage),
+            Some(opus)
+        );
+        assert_eq!(
+            ModelRegistry::cost(AgentType::Local, Some("llama3.1"), &usage),
+            None
+        );
+    }
+}
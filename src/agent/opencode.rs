@@ -21,9 +21,9 @@ use tokio::time::timeout;
 use crate::agent::display::MessageDisplay;
 use crate::agent::error::AgentError;
 use crate::agent::events::{
-    AgentEvent, AssistantMessageEvent, ErrorEvent, QuestionOption, ReasoningEvent,
-    SessionInitEvent, ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent, TurnFailedEvent,
-    UserQuestion,
+    AgentEvent, AssistantMessageEvent, CommandOutputDeltaEvent, ErrorEvent, QuestionOption,
+    ReasoningEvent, SessionInitEvent, ToolCompletedEvent, ToolStartedEvent, TurnCompletedEvent,
+    TurnFailedEvent, UserQuestion,
 };
 use crate::agent::runner::{AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType};
 use crate::agent::session::SessionId;
@@ -208,6 +208,9 @@ struct ToolState {
     output: Option<Value>,
     #[serde(default)]
     error: Option<String>,
+    /// Tool-specific progress data (bash reports partial output here while running)
+    #[serde(default)]
+    metadata: Option<Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -596,6 +599,8 @@ impl OpenCodeClient {
 struct OpencodeEventState {
     started_tools: HashSet<String>,
     message_roles: HashMap<String, String>,
+    /// Partial output already forwarded for running tools
+    tool_outputs: HashMap<String, String>,
 }
 
 pub struct OpencodeRunner {
@@ -633,16 +638,18 @@ impl OpencodeRunner {
         cmd.arg("--port").arg("0");
         cmd.args(&config.additional_args);
         cmd.current_dir(&config.working_dir);
-        cmd.stdin(Stdio::null());
-        cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::piped());
         cmd.env("NO_COLOR", "1");
         cmd.env("OPENCODE_CLIENT", "conduit");
 
         if std::env::var("OPENCODE_PERMISSION").is_err() {
             cmd.env("OPENCODE_PERMISSION", r#"{"*":"allow"}"#);
         }
+        cmd.envs(config.env.clone());
 
+        let mut cmd = config.execution_target.wrap(cmd)?;
+        cmd.stdin(Stdio::null());
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
         Ok(cmd)
     }
 
@@ -1352,7 +1359,7 @@ impl OpencodeRunner {
                                                         &event_tx,
                                                         AgentEvent::ToolStarted(ToolStartedEvent {
                                                             tool_name,
-                                                            tool_id,
+                                                            tool_id: tool_id.clone(),
                                                             arguments,
                                                         }),
                                                         "opencode_tool_started",
@@ -1362,6 +1369,44 @@ impl OpencodeRunner {
                                                         return;
                                                     }
                                                 }
+                                                let partial = state_info
+                                                    .metadata
+                                                    .as_ref()
+                                                    .and_then(|m| m.get("output"))
+                                                    .and_then(|v| v.as_str());
+                                                if let Some(partial) = partial {
+                                                    // Only forward appended text; a rewritten
+                                                    // (e.g. truncated) output is left to the
+                                                    // final result
+                                                    let chunk =
+                                                        match state.tool_outputs.get(&tool_id) {
+                                                            None => Some(partial.to_string()),
+                                                            Some(previous) => partial
+                                                                .strip_prefix(previous.as_str())
+                                                                .map(str::to_string),
+                                                        }
+                                                        .filter(|chunk| !chunk.is_empty());
+                                                    if let Some(chunk) = chunk {
+                                                        state.tool_outputs.insert(
+                                                            tool_id.clone(),
+                                                            partial.to_string(),
+                                                        );
+                                                        if !send_event_or_log(
+                                                            &event_tx,
+                                                            AgentEvent::CommandOutputDelta(
+                                                                CommandOutputDeltaEvent {
+                                                                    tool_id,
+                                                                    chunk,
+                                                                },
+                                                            ),
+                                                            "opencode_tool_output_delta",
+                                                        )
+                                                        .await
+                                                        {
+                                                            return;
+                                                        }
+                                                    }
+                                                }
                                             }
                                             Some("completed") => {
                                                 let result = state_info.output.map(|output| {
@@ -1386,6 +1431,7 @@ impl OpencodeRunner {
                                                     return;
                                                 }
                                                 state.started_tools.remove(&tool_id);
+                                                state.tool_outputs.remove(&tool_id);
                                             }
                                             Some("error") => {
                                                 let error =
@@ -1405,6 +1451,7 @@ impl OpencodeRunner {
                                                     return;
                                                 }
                                                 state.started_tools.remove(&tool_id);
+                                                state.tool_outputs.remove(&tool_id);
                                             }
                                             _ => {}
                                         }
@@ -1862,6 +1909,7 @@ impl AgentRunner for OpencodeRunner {
 
         if let Some(stderr) = stderr {
             let event_tx_for_stderr = event_tx.clone();
+            let stderr_buffer = config.stderr.clone();
             tokio::spawn(async move {
                 let mut reader = BufReader::new(stderr);
                 let mut line = String::new();
@@ -1882,6 +1930,9 @@ impl AgentRunner for OpencodeRunner {
                                 continue;
                             }
                             tracing::debug!("OpenCode stderr: {}", trimmed);
+                            if let Some(buffer) = stderr_buffer.as_ref() {
+                                buffer.push(trimmed);
+                            }
 
                             // NOTE: OpenCode stderr format is parsed heuristically. Keep in sync with upstream.
                             if trimmed.contains("ProviderModelNotFoundError")
@@ -2054,15 +2105,7 @@ impl AgentRunner for OpencodeRunner {
                     );
                     let _ = send_event_or_log(
                         &event_tx_for_wait,
-                        AgentEvent::Error(ErrorEvent {
-                            message: format!(
-                                "OpenCode server exited with status {:?}",
-                                status.code()
-                            ),
-                            is_fatal: true,
-                            code: None,
-                            details: None,
-                        }),
+                        AgentError::crash_event("OpenCode server", status, ""),
                         "opencode_server_exit",
                     )
                     .await;
@@ -2583,6 +2626,7 @@ mod tests {
             input: None,
             output: Some(serde_json::json!({"message": "ignored"})),
             error: Some("boom".to_string()),
+            metadata: None,
         };
 
         let message = OpencodeRunner::extract_tool_error_message(&state);
@@ -2599,6 +2643,7 @@ mod tests {
                 "error": {"message": "Invalid input"}
             })),
             error: None,
+            metadata: None,
         };
 
         let message = OpencodeRunner::extract_tool_error_message(&state);
@@ -2613,6 +2658,7 @@ mod tests {
             input: None,
             output: Some(serde_json::Value::String("bad args".to_string())),
             error: None,
+            metadata: None,
         };
 
         let message = OpencodeRunner::extract_tool_error_message(&state);
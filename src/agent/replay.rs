@@ -0,0 +1,453 @@
+//! Agent event capture and replay for offline development
+//!
+//! Setting `CONDUIT_RECORD_AGENT_DIR` makes the Claude and Codex runners write
+//! every raw stdout line to a fixture file in that directory, together with
+//! its offset from the start of the run. [`ReplayRunner`] plays a fixture back
+//! through the same event conversion with the original timing, so UI work and
+//! tests can run without the agent CLIs installed.
+//!
+//! Setting `CONDUIT_REPLAY_FIXTURE` to a fixture path makes Conduit use a
+//! `ReplayRunner` in place of the agent the fixture was recorded from.
+//! `CONDUIT_REPLAY_SPEED` scales playback (`2` is twice as fast, `0` replays
+//! without delays).
+//!
+//! # Example
+//! ```no_run
+//! use conduit::agent::replay::ReplayRunner;
+//! use conduit::agent::{AgentRunner, AgentStartConfig};
+//! use std::path::PathBuf;
+//!
+//! #[tokio::test]
+//! async fn test_replayed_session() {
+//!     let runner = ReplayRunner::from_fixture("tests/fixtures/claude-basic.jsonl")
+//!         .unwrap()
+//!         .with_speed(0.0);
+//!     let config = AgentStartConfig::new("ignored", PathBuf::from("/tmp"));
+//!     let mut handle = runner.start(config).await.unwrap();
+//!
+//!     while let Some(event) = handle.events.recv().await {
+//!         // Assert on events...
+//!     }
+//! }
+//! ```
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use crate::agent::claude::ClaudeReplayDecoder;
+use crate::agent::codex::CodexReplayDecoder;
+use crate::agent::error::AgentError;
+use crate::agent::events::AgentEvent;
+use crate::agent::runner::{
+    AgentHandle, AgentInput, AgentRunner, AgentStartConfig, AgentType, NO_PID,
+};
+
+/// Directory that recorded fixtures are written to (record mode is off when unset)
+pub const RECORD_DIR_ENV: &str = "CONDUIT_RECORD_AGENT_DIR";
+/// Fixture to replay instead of running the recorded agent
+pub const REPLAY_FIXTURE_ENV: &str = "CONDUIT_REPLAY_FIXTURE";
+/// Playback speed multiplier for the replayed fixture
+pub const REPLAY_SPEED_ENV: &str = "CONDUIT_REPLAY_SPEED";
+
+/// Current fixture format version
+const FIXTURE_VERSION: u32 = 1;
+
+/// First line of a fixture file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureHeader {
+    pub version: u32,
+    /// Agent whose output was recorded
+    pub agent: AgentType,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// A single raw agent output line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureEntry {
+    /// Milliseconds since the recording started
+    pub offset_ms: u64,
+    /// Raw JSONL line as emitted by the agent
+    pub line: String,
+}
+
+/// A parsed fixture file
+#[derive(Debug, Clone)]
+pub struct ReplayFixture {
+    pub header: FixtureHeader,
+    pub entries: Vec<FixtureEntry>,
+}
+
+impl ReplayFixture {
+    /// Parse fixture contents (a header line followed by one entry per line)
+    pub fn parse(contents: &str) -> Result<Self, AgentError> {
+        let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+        let header_line = lines
+            .next()
+            .ok_or_else(|| AgentError::Config("Replay fixture is empty".to_string()))?;
+        let header: FixtureHeader = serde_json::from_str(header_line)?;
+        if header.version != FIXTURE_VERSION {
+            return Err(AgentError::Config(format!(
+                "Unsupported replay fixture version {} (expected {})",
+                header.version, FIXTURE_VERSION
+            )));
+        }
+
+        let entries = lines
+            .map(serde_json::from_str::<FixtureEntry>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { header, entries })
+    }
+
+    /// Read and parse a fixture file
+    pub fn load(path: &Path) -> Result<Self, AgentError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+}
+
+/// Writes raw agent output lines to a fixture file
+pub struct AgentRecorder {
+    writer: BufWriter<File>,
+    path: PathBuf,
+    started_at: Instant,
+}
+
+impl AgentRecorder {
+    /// Create a fixture at `path` and write its header
+    pub fn create(path: impl Into<PathBuf>, agent_type: AgentType) -> std::io::Result<Self> {
+        let path = path.into();
+        let mut writer = BufWriter::new(File::create(&path)?);
+        let header = FixtureHeader {
+            version: FIXTURE_VERSION,
+            agent: agent_type,
+            recorded_at: Utc::now(),
+        };
+        serde_json::to_writer(&mut writer, &header)?;
+        writer.write_all(b"\n")?;
+        writer.flush()?;
+
+        Ok(Self {
+            writer,
+            path,
+            started_at: Instant::now(),
+        })
+    }
+
+    /// Start recording into `CONDUIT_RECORD_AGENT_DIR` when record mode is enabled
+    pub fn from_env(agent_type: AgentType) -> Option<Self> {
+        let dir = PathBuf::from(std::env::var_os(RECORD_DIR_ENV)?);
+        if let Err(err) = fs::create_dir_all(&dir) {
+            tracing::warn!(
+                error = %err,
+                dir = %dir.display(),
+                "Failed to create agent recording directory"
+            );
+            return None;
+        }
+
+        let file_name = format!(
+            "{}-{}.jsonl",
+            agent_type.as_str(),
+            Utc::now().format("%Y%m%d-%H%M%S%.3f")
+        );
+        let path = dir.join(file_name);
+        match Self::create(&path, agent_type) {
+            Ok(recorder) => {
+                tracing::info!(path = %path.display(), "Recording agent output");
+                Some(recorder)
+            }
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    path = %path.display(),
+                    "Failed to create agent recording"
+                );
+                None
+            }
+        }
+    }
+
+    /// Append a raw line. Each line is flushed so killed sessions keep their fixture.
+    pub fn record(&mut self, line: &str) {
+        let entry = FixtureEntry {
+            offset_ms: u64::try_from(self.started_at.elapsed().as_millis()).unwrap_or(u64::MAX),
+            line: line.to_string(),
+        };
+        let result = serde_json::to_writer(&mut self.writer, &entry)
+            .map_err(std::io::Error::from)
+            .and_then(|()| self.writer.write_all(b"\n"))
+            .and_then(|()| self.writer.flush());
+        if let Err(err) = result {
+            tracing::warn!(
+                error = %err,
+                path = %self.path.display(),
+                "Failed to record agent output line"
+            );
+        }
+    }
+
+    /// Path of the fixture being written
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Converts recorded lines back into agent events
+enum ReplayDecoder {
+    Claude(ClaudeReplayDecoder),
+    Codex(CodexReplayDecoder),
+}
+
+impl ReplayDecoder {
+    fn for_agent(agent_type: AgentType) -> Option<Self> {
+        match agent_type {
+            AgentType::Claude => Some(Self::Claude(ClaudeReplayDecoder::default())),
+            AgentType::Codex => Some(Self::Codex(CodexReplayDecoder::default())),
+            AgentType::Gemini | AgentType::Opencode | AgentType::Local => None,
+        }
+    }
+
+    fn decode(&mut self, line: &str) -> Vec<AgentEvent> {
+        match self {
+            Self::Claude(decoder) => decoder.decode(line),
+            Self::Codex(decoder) => decoder.decode(line),
+        }
+    }
+}
+
+/// Agent runner that replays a recorded fixture
+///
+/// Every `start` replays the whole fixture regardless of the prompt. Input
+/// sent to the handle is ignored.
+pub struct ReplayRunner {
+    agent_type: AgentType,
+    fixture_path: PathBuf,
+    speed: f64,
+}
+
+impl ReplayRunner {
+    /// Create a runner replaying `fixture_path` as `agent_type`
+    pub fn new(agent_type: AgentType, fixture_path: impl Into<PathBuf>) -> Self {
+        Self {
+            agent_type,
+            fixture_path: fixture_path.into(),
+            speed: 1.0,
+        }
+    }
+
+    /// Create a runner for the agent recorded in the fixture header
+    pub fn from_fixture(fixture_path: impl Into<PathBuf>) -> Result<Self, AgentError> {
+        let fixture_path = fixture_path.into();
+        let fixture = ReplayFixture::load(&fixture_path)?;
+        if ReplayDecoder::for_agent(fixture.header.agent).is_none() {
+            return Err(AgentError::NotSupported(format!(
+                "Replay is not supported for {}",
+                fixture.header.agent.display_name()
+            )));
+        }
+        Ok(Self::new(fixture.header.agent, fixture_path))
+    }
+
+    /// Create a runner from `CONDUIT_REPLAY_FIXTURE` and `CONDUIT_REPLAY_SPEED`
+    pub fn from_env() -> Option<Self> {
+        let path = std::env::var_os(REPLAY_FIXTURE_ENV)?;
+        let mut runner = match Self::from_fixture(PathBuf::from(&path)) {
+            Ok(runner) => runner,
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    path = ?path,
+                    "Failed to load replay fixture"
+                );
+                return None;
+            }
+        };
+
+        if let Ok(speed) = std::env::var(REPLAY_SPEED_ENV) {
+            match speed.parse::<f64>() {
+                Ok(speed) if speed >= 0.0 => runner = runner.with_speed(speed),
+                _ => tracing::warn!(speed, "Ignoring invalid replay speed"),
+            }
+        }
+
+        tracing::info!(
+            agent = runner.agent_type.as_str(),
+            path = %runner.fixture_path.display(),
+            "Replaying agent fixture instead of running the agent"
+        );
+        Some(runner)
+    }
+
+    /// Scale playback speed (`0` replays without delays)
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Path of the replayed fixture
+    pub fn fixture_path(&self) -> &Path {
+        &self.fixture_path
+    }
+}
+
+#[async_trait]
+impl AgentRunner for ReplayRunner {
+    fn agent_type(&self) -> AgentType {
+        self.agent_type
+    }
+
+    async fn start(&self, _config: AgentStartConfig) -> Result<AgentHandle, AgentError> {
+        let contents = tokio::fs::read_to_string(&self.fixture_path).await?;
+        let fixture = ReplayFixture::parse(&contents)?;
+        if fixture.header.agent != self.agent_type {
+            return Err(AgentError::Config(format!(
+                "Replay fixture was recorded from {}, not {}",
+                fixture.header.agent.display_name(),
+                self.agent_type.display_name()
+            )));
+        }
+        let mut decoder = ReplayDecoder::for_agent(self.agent_type).ok_or_else(|| {
+            AgentError::NotSupported(format!(
+                "Replay is not supported for {}",
+                self.agent_type.display_name()
+            ))
+        })?;
+
+        let (tx, rx) = mpsc::channel::<AgentEvent>(256);
+        let speed = self.speed;
+
+        tokio::spawn(async move {
+            let mut elapsed_ms = 0;
+            for entry in fixture.entries {
+                if speed > 0.0 && entry.offset_ms > elapsed_ms {
+                    let delay_ms = (entry.offset_ms - elapsed_ms) as f64 / speed;
+                    tokio::time::sleep(Duration::from_secs_f64(delay_ms / 1000.0)).await;
+                }
+                elapsed_ms = elapsed_ms.max(entry.offset_ms);
+
+                for event in decoder.decode(&entry.line) {
+                    if tx.send(event).await.is_err() {
+                        return; // Receiver dropped
+                    }
+                }
+            }
+        });
+
+        Ok(AgentHandle::new(rx, NO_PID, None))
+    }
+
+    async fn send_input(&self, _handle: &AgentHandle, input: AgentInput) -> Result<(), AgentError> {
+        tracing::debug!(?input, "Ignoring input sent to replayed agent");
+        Ok(())
+    }
+
+    async fn stop(&self, _handle: &AgentHandle) -> Result<(), AgentError> {
+        Ok(())
+    }
+
+    async fn kill(&self, _handle: &AgentHandle) -> Result<(), AgentError> {
+        Ok(())
+    }
+
+    fn is_available(&self) -> bool {
+        self.fixture_path.is_file()
+    }
+
+    fn binary_path(&self) -> Option<PathBuf> {
+        Some(self.fixture_path.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_fixture(dir: &Path, agent_type: AgentType, lines: &[&str]) -> PathBuf {
+        let path = dir.join("fixture.jsonl");
+        let mut recorder = AgentRecorder::create(&path, agent_type).unwrap();
+        for line in lines {
+            recorder.record(line);
+        }
+        path
+    }
+
+    #[test]
+    fn test_recorder_writes_header_and_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = record_fixture(dir.path(), AgentType::Claude, &[r#"{"a":1}"#, r#"{"b":2}"#]);
+
+        let fixture = ReplayFixture::load(&path).unwrap();
+        assert_eq!(fixture.header.version, FIXTURE_VERSION);
+        assert_eq!(fixture.header.agent, AgentType::Claude);
+        assert_eq!(fixture.entries.len(), 2);
+        assert_eq!(fixture.entries[0].line, r#"{"a":1}"#);
+        assert!(fixture.entries[0].offset_ms <= fixture.entries[1].offset_ms);
+    }
+
+    #[tokio::test]
+    async fn test_replay_claude_fixture_emits_converted_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = record_fixture(
+            dir.path(),
+            AgentType::Claude,
+            &[
+                r#"{"type":"system","subtype":"init","session_id":"replay-1","model":"claude-sonnet"}"#,
+                r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Hello from the fixture"}]}}"#,
+                "not json",
+            ],
+        );
+
+        let runner = ReplayRunner::from_fixture(&path).unwrap().with_speed(0.0);
+        assert_eq!(runner.agent_type(), AgentType::Claude);
+        assert!(runner.is_available());
+
+        let config = AgentStartConfig::new("ignored", PathBuf::from("/tmp"));
+        let mut handle = runner.start(config).await.unwrap();
+        assert_eq!(handle.pid, NO_PID);
+
+        let mut events = Vec::new();
+        while let Some(event) = handle.events.recv().await {
+            events.push(event);
+        }
+
+        assert!(matches!(
+            &events[0],
+            AgentEvent::SessionInit(init) if init.session_id.as_str() == "replay-1"
+        ));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            AgentEvent::AssistantMessage(msg) if msg.text == "Hello from the fixture"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_replay_rejects_mismatched_agent() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = record_fixture(dir.path(), AgentType::Claude, &[]);
+
+        let runner = ReplayRunner::new(AgentType::Codex, &path);
+        let config = AgentStartConfig::new("ignored", PathBuf::from("/tmp"));
+        assert!(matches!(
+            runner.start(config).await,
+            Err(AgentError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_agent_fixture_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = record_fixture(dir.path(), AgentType::Gemini, &[]);
+
+        assert!(matches!(
+            ReplayRunner::from_fixture(&path),
+            Err(AgentError::NotSupported(_))
+        ));
+    }
+}
@@ -2,9 +2,12 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 
+use crate::agent::diagnostics::StderrBuffer;
 use crate::agent::error::AgentError;
 use crate::agent::events::AgentEvent;
+use crate::agent::exec_target::ExecutionTarget;
 use crate::agent::session::SessionId;
 
 /// Agent type identifier
@@ -14,6 +17,8 @@ pub enum AgentType {
     Codex,
     Gemini,
     Opencode,
+    /// Local OpenAI-compatible chat-completions endpoint (Ollama, LM Studio)
+    Local,
 }
 
 /// Agent mode (Build vs Plan)
@@ -69,12 +74,169 @@ impl AgentMode {
     }
 }
 
+/// Codex sandbox policy for commands the agent runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CodexSandboxMode {
+    ReadOnly,
+    WorkspaceWrite,
+    #[default]
+    DangerFullAccess,
+}
+
+impl CodexSandboxMode {
+    pub const ALL: [CodexSandboxMode; 3] = [
+        CodexSandboxMode::ReadOnly,
+        CodexSandboxMode::WorkspaceWrite,
+        CodexSandboxMode::DangerFullAccess,
+    ];
+
+    /// String representation for storage and the Codex CLI
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CodexSandboxMode::ReadOnly => "read-only",
+            CodexSandboxMode::WorkspaceWrite => "workspace-write",
+            CodexSandboxMode::DangerFullAccess => "danger-full-access",
+        }
+    }
+
+    /// Display name for the UI
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CodexSandboxMode::ReadOnly => "Read-only",
+            CodexSandboxMode::WorkspaceWrite => "Workspace write",
+            CodexSandboxMode::DangerFullAccess => "Full access",
+        }
+    }
+
+    /// Parse from string (None if unrecognized)
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.as_str() == s.to_lowercase())
+    }
+
+    /// Cycle to the next sandbox mode
+    pub fn next(&self) -> Self {
+        match self {
+            CodexSandboxMode::ReadOnly => CodexSandboxMode::WorkspaceWrite,
+            CodexSandboxMode::WorkspaceWrite => CodexSandboxMode::DangerFullAccess,
+            CodexSandboxMode::DangerFullAccess => CodexSandboxMode::ReadOnly,
+        }
+    }
+}
+
+/// Codex approval policy (when the agent asks before running commands)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CodexApprovalPolicy {
+    Untrusted,
+    OnFailure,
+    OnRequest,
+    #[default]
+    Never,
+}
+
+impl CodexApprovalPolicy {
+    pub const ALL: [CodexApprovalPolicy; 4] = [
+        CodexApprovalPolicy::Untrusted,
+        CodexApprovalPolicy::OnFailure,
+        CodexApprovalPolicy::OnRequest,
+        CodexApprovalPolicy::Never,
+    ];
+
+    /// String representation for storage and the Codex CLI
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CodexApprovalPolicy::Untrusted => "untrusted",
+            CodexApprovalPolicy::OnFailure => "on-failure",
+            CodexApprovalPolicy::OnRequest => "on-request",
+            CodexApprovalPolicy::Never => "never",
+        }
+    }
+
+    /// Display name for the UI
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            CodexApprovalPolicy::Untrusted => "Untrusted",
+            CodexApprovalPolicy::OnFailure => "On failure",
+            CodexApprovalPolicy::OnRequest => "On request",
+            CodexApprovalPolicy::Never => "Never",
+        }
+    }
+
+    /// Parse from string (None if unrecognized)
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|policy| policy.as_str() == s.to_lowercase())
+    }
+
+    /// Cycle to the next approval policy
+    pub fn next(&self) -> Self {
+        match self {
+            CodexApprovalPolicy::Untrusted => CodexApprovalPolicy::OnFailure,
+            CodexApprovalPolicy::OnFailure => CodexApprovalPolicy::OnRequest,
+            CodexApprovalPolicy::OnRequest => CodexApprovalPolicy::Never,
+            CodexApprovalPolicy::Never => CodexApprovalPolicy::Untrusted,
+        }
+    }
+}
+
+/// Features an agent integration supports.
+///
+/// UI layers consult this instead of matching on `AgentType` so new agents
+/// only need to describe themselves here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct AgentCapabilities {
+    /// Image attachments on prompts
+    pub supports_images: bool,
+    /// Resuming a previous agent session
+    pub supports_resume: bool,
+    /// Read-only Plan mode
+    pub supports_plan_mode: bool,
+    /// Interrupting a running turn and continuing the same session
+    pub supports_interrupts: bool,
+    /// MCP tool calls surfaced in the transcript
+    pub supports_mcp: bool,
+}
+
+impl AgentCapabilities {
+    pub fn for_agent(agent_type: AgentType) -> Self {
+        match agent_type {
+            AgentType::Claude | AgentType::Codex => Self {
+                supports_images: true,
+                supports_resume: true,
+                supports_plan_mode: true,
+                supports_interrupts: true,
+                supports_mcp: true,
+            },
+            AgentType::Gemini => Self {
+                supports_images: false,
+                supports_resume: false,
+                supports_plan_mode: true,
+                supports_interrupts: false,
+                supports_mcp: false,
+            },
+            AgentType::Opencode => Self {
+                supports_images: false,
+                supports_resume: true,
+                supports_plan_mode: false,
+                supports_interrupts: true,
+                supports_mcp: false,
+            },
+            AgentType::Local => Self {
+                supports_images: false,
+                supports_resume: false,
+                supports_plan_mode: true,
+                supports_interrupts: false,
+                supports_mcp: false,
+            },
+        }
+    }
+}
+
 impl AgentType {
     pub fn supports_plan_mode(&self) -> bool {
-        matches!(
-            self,
-            AgentType::Claude | AgentType::Codex | AgentType::Gemini
-        )
+        AgentCapabilities::for_agent(*self).supports_plan_mode
     }
 
     pub fn as_str(&self) -> &'static str {
@@ -83,15 +245,25 @@ impl AgentType {
             AgentType::Codex => "codex",
             AgentType::Gemini => "gemini",
             AgentType::Opencode => "opencode",
+            AgentType::Local => "local",
         }
     }
 
+    /// Lenient parse: case-insensitive, and anything unrecognized is Claude.
+    /// Use [`Self::from_name`] for user config, where a typo should be caught.
     pub fn parse(s: &str) -> Self {
-        match s.to_lowercase().as_str() {
-            "codex" => AgentType::Codex,
-            "gemini" => AgentType::Gemini,
-            "opencode" => AgentType::Opencode,
-            _ => AgentType::Claude,
+        Self::from_name(&s.to_lowercase()).unwrap_or(AgentType::Claude)
+    }
+
+    /// The agent called exactly `name` (`claude`, `codex`, ...), or `None`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "claude" => Some(AgentType::Claude),
+            "codex" => Some(AgentType::Codex),
+            "gemini" => Some(AgentType::Gemini),
+            "opencode" => Some(AgentType::Opencode),
+            "local" | "ollama" => Some(AgentType::Local),
+            _ => None,
         }
     }
 
@@ -101,6 +273,7 @@ impl AgentType {
             AgentType::Codex => "Codex CLI",
             AgentType::Gemini => "Gemini CLI",
             AgentType::Opencode => "OpenCode",
+            AgentType::Local => "Local model",
         }
     }
 }
@@ -116,6 +289,8 @@ impl std::fmt::Display for AgentType {
 pub struct AgentStartConfig {
     pub prompt: String,
     pub working_dir: PathBuf,
+    /// Directories outside `working_dir` the agent may also read and edit
+    pub extra_dirs: Vec<PathBuf>,
     pub allowed_tools: Vec<String>,
     pub resume_session: Option<SessionId>,
     pub timeout_ms: Option<u64>,
@@ -130,6 +305,20 @@ pub struct AgentStartConfig {
     pub input_format: Option<String>,
     /// Optional stdin payload for structured input (e.g. JSONL)
     pub stdin_payload: Option<String>,
+    /// Extra environment variables for the spawned process
+    pub env: Vec<(String, String)>,
+    /// Codex sandbox mode override (None = runner default)
+    pub codex_sandbox: Option<CodexSandboxMode>,
+    /// Codex approval policy override (None = runner default)
+    pub codex_approval: Option<CodexApprovalPolicy>,
+    /// Custom base instructions appended to the agent's system prompt
+    pub system_prompt: Option<String>,
+    /// Session buffer receiving the agent process's stderr lines
+    pub stderr: Option<StderrBuffer>,
+    /// Hold file edits for user review instead of allowing them (Claude only)
+    pub review_edits: bool,
+    /// Where the agent process runs (host, Docker, devcontainer)
+    pub execution_target: ExecutionTarget,
 }
 
 impl AgentStartConfig {
@@ -137,6 +326,7 @@ impl AgentStartConfig {
         Self {
             prompt: prompt.into(),
             working_dir,
+            extra_dirs: Vec::new(),
             allowed_tools: vec![],
             resume_session: None,
             timeout_ms: None,
@@ -146,6 +336,13 @@ impl AgentStartConfig {
             agent_mode: AgentMode::default(),
             input_format: None,
             stdin_payload: None,
+            env: Vec::new(),
+            codex_sandbox: None,
+            codex_approval: None,
+            system_prompt: None,
+            stderr: None,
+            review_edits: false,
+            execution_target: ExecutionTarget::default(),
         }
     }
 
@@ -169,6 +366,11 @@ impl AgentStartConfig {
         self
     }
 
+    pub fn with_extra_dirs(mut self, dirs: Vec<PathBuf>) -> Self {
+        self.extra_dirs = dirs;
+        self
+    }
+
     pub fn with_images(mut self, images: Vec<PathBuf>) -> Self {
         self.images = images;
         self
@@ -188,6 +390,46 @@ impl AgentStartConfig {
         self.stdin_payload = Some(payload.into());
         self
     }
+
+    pub fn with_additional_args(mut self, args: Vec<String>) -> Self {
+        self.additional_args.extend(args);
+        self
+    }
+
+    pub fn with_env(mut self, env: Vec<(String, String)>) -> Self {
+        self.env.extend(env);
+        self
+    }
+
+    pub fn with_codex_sandbox(mut self, sandbox: CodexSandboxMode) -> Self {
+        self.codex_sandbox = Some(sandbox);
+        self
+    }
+
+    pub fn with_codex_approval(mut self, approval: CodexApprovalPolicy) -> Self {
+        self.codex_approval = Some(approval);
+        self
+    }
+
+    pub fn with_system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    pub fn with_stderr_buffer(mut self, buffer: StderrBuffer) -> Self {
+        self.stderr = Some(buffer);
+        self
+    }
+
+    pub fn with_edit_review(mut self, enabled: bool) -> Self {
+        self.review_edits = enabled;
+        self
+    }
+
+    pub fn with_execution_target(mut self, target: ExecutionTarget) -> Self {
+        self.execution_target = target;
+        self
+    }
 }
 
 /// Input payload for running agents.
@@ -208,6 +450,9 @@ pub enum AgentInput {
     },
 }
 
+/// PID reported by handles that are not backed by an OS process (e.g. fixture replay)
+pub const NO_PID: u32 = 0;
+
 /// Handle to a running agent process
 pub struct AgentHandle {
     /// Receiver for agent events
@@ -218,6 +463,8 @@ pub struct AgentHandle {
     pub pid: u32,
     /// Optional input channel for streaming stdin payloads
     pub input_tx: Option<mpsc::Sender<AgentInput>>,
+    /// Stops agents that run in-process and have no PID to signal
+    pub cancel: Option<CancellationToken>,
 }
 
 impl AgentHandle {
@@ -231,9 +478,16 @@ impl AgentHandle {
             session_id: None,
             pid,
             input_tx,
+            cancel: None,
         }
     }
 
+    /// Attach the token that stops an in-process agent
+    pub fn with_cancel(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
     pub fn set_session_id(&mut self, session_id: SessionId) {
         self.session_id = Some(session_id);
     }
@@ -266,4 +520,9 @@ pub trait AgentRunner: Send + Sync {
 
     /// Get the path to the agent binary
     fn binary_path(&self) -> Option<PathBuf>;
+
+    /// Features this runner supports
+    fn capabilities(&self) -> AgentCapabilities {
+        AgentCapabilities::for_agent(self.agent_type())
+    }
 }
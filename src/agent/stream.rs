@@ -4,13 +4,19 @@ use tokio::process::ChildStdout;
 use tokio::sync::mpsc;
 
 use crate::agent::error::AgentError;
+use crate::agent::replay::AgentRecorder;
+use crate::util::chaos;
 
 /// Generic JSONL stream parser
 pub struct JsonlStreamParser;
 
 impl JsonlStreamParser {
-    /// Parse JSONL from stdout and send to channel
-    pub async fn parse_stream<T>(stdout: ChildStdout, tx: mpsc::Sender<T>) -> Result<(), AgentError>
+    /// Parse JSONL from stdout and send to channel, optionally recording each raw line
+    pub async fn parse_stream<T>(
+        stdout: ChildStdout,
+        tx: mpsc::Sender<T>,
+        mut recorder: Option<AgentRecorder>,
+    ) -> Result<(), AgentError>
     where
         T: for<'de> Deserialize<'de> + Send + 'static,
     {
@@ -24,6 +30,10 @@ impl JsonlStreamParser {
 
             // Log raw JSONL lines at trace level for debugging
             tracing::trace!("JSONL raw line: {}", &line);
+            if let Some(recorder) = recorder.as_mut() {
+                recorder.record(&line);
+            }
+            let line = chaos::corrupt_line(&line).unwrap_or(line);
 
             match serde_json::from_str::<T>(&line) {
                 Ok(event) => {
@@ -94,6 +104,9 @@ pub struct ClaudeAssistantEvent {
     pub session_id: Option<String>,
     /// Error type (e.g., "authentication_failed")
     pub error: Option<String>,
+    /// Task tool call that spawned this event's subagent (None = main agent)
+    #[serde(default)]
+    pub parent_tool_use_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -111,6 +124,8 @@ pub struct ClaudeMessageObject {
 pub enum ClaudeContentBlock {
     #[serde(rename = "text")]
     Text { text: String },
+    #[serde(rename = "thinking")]
+    Thinking { thinking: String },
     #[serde(rename = "tool_use")]
     ToolUse {
         id: String,
@@ -151,6 +166,25 @@ impl ClaudeAssistantEvent {
         self.text.clone()
     }
 
+    /// Extract the thinking content from this event, if any
+    pub fn extract_thinking(&self) -> Option<String> {
+        let content = self.message.as_ref()?.content.as_ref()?;
+        let thinking: Vec<&str> = content
+            .iter()
+            .filter_map(|block| match block {
+                ClaudeContentBlock::Thinking { thinking } if !thinking.trim().is_empty() => {
+                    Some(thinking.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        if thinking.is_empty() {
+            None
+        } else {
+            Some(thinking.join("\n"))
+        }
+    }
+
     /// Extract tool_use blocks from this event's content
     pub fn extract_tool_uses(&self) -> Vec<ExtractedToolUse> {
         if let Some(ref msg) = self.message {
@@ -195,6 +229,9 @@ pub struct ClaudeToolResultEvent {
 pub struct ClaudeUserEvent {
     pub message: Option<ClaudeUserMessage>,
     pub tool_use_result: Option<ClaudeToolUseResultData>,
+    /// Task tool call that spawned this event's subagent (None = main agent)
+    #[serde(default)]
+    pub parent_tool_use_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -315,6 +352,8 @@ pub struct ClaudeResultEvent {
 pub struct ClaudeUsage {
     pub input_tokens: Option<i64>,
     pub output_tokens: Option<i64>,
+    pub cache_creation_input_tokens: Option<i64>,
+    pub cache_read_input_tokens: Option<i64>,
 }
 
 // ============================================================================
@@ -0,0 +1,198 @@
+//! Classification of failed turns into categories with a known remedy.
+//!
+//! Agents report failures as free-form text (and sometimes an error code), so
+//! classification is keyword based. Anything unrecognised stays unclassified
+//! and is shown as a plain error.
+
+use std::time::Duration;
+
+use super::events::ErrorEvent;
+use super::runner::AgentType;
+
+/// Wait before retrying a rate-limited turn when the error gives no hint
+pub const DEFAULT_RATE_LIMIT_WAIT: Duration = Duration::from_secs(30);
+
+/// Longest wait honoured from a retry hint; longer limits need a manual retry
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(15 * 60);
+
+const AUTH_PATTERNS: &[&str] = &[
+    "401",
+    "unauthorized",
+    "authentication_error",
+    "authentication failed",
+    "invalid api key",
+    "invalid x-api-key",
+    "please run /login",
+    "not logged in",
+    "login required",
+    "token has expired",
+    "token expired",
+    "credentials have expired",
+];
+
+const BINARY_MISSING_PATTERNS: &[&str] = &[
+    "binary not found",
+    "command not found",
+    "no such file or directory (os error 2)",
+    "not found in path",
+];
+
+const RATE_LIMIT_PATTERNS: &[&str] = &[
+    "429",
+    "rate limit",
+    "rate_limit",
+    "ratelimit",
+    "too many requests",
+    "usage limit",
+    "quota exceeded",
+    "resource_exhausted",
+    "overloaded",
+];
+
+/// Known failure category for a turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    /// Credentials are missing or expired; logging in again fixes it
+    AuthExpired,
+    /// The agent CLI isn't installed or isn't where Conduit looks
+    BinaryMissing,
+    /// The provider is throttling; waiting and retrying fixes it
+    RateLimited { retry_after: Duration },
+}
+
+impl FailureKind {
+    /// Classify an agent error event
+    pub fn classify(error: &ErrorEvent) -> Option<Self> {
+        Self::classify_message(&error.message, error.code.as_deref())
+    }
+
+    /// Classify an error message, optionally with the agent's error code
+    pub fn classify_message(message: &str, code: Option<&str>) -> Option<Self> {
+        let haystack = match code {
+            Some(code) => format!("{} {}", code, message).to_lowercase(),
+            None => message.to_lowercase(),
+        };
+        let matches = |patterns: &[&str]| patterns.iter().any(|p| haystack.contains(p));
+
+        // Missing binaries are checked first: their messages can mention auth
+        // or limits only incidentally (e.g. a path containing "429")
+        if matches(BINARY_MISSING_PATTERNS) {
+            Some(FailureKind::BinaryMissing)
+        } else if matches(RATE_LIMIT_PATTERNS) {
+            let retry_after = parse_retry_after(&haystack)
+                .unwrap_or(DEFAULT_RATE_LIMIT_WAIT)
+                .min(MAX_RATE_LIMIT_WAIT);
+            Some(FailureKind::RateLimited { retry_after })
+        } else if matches(AUTH_PATTERNS) {
+            Some(FailureKind::AuthExpired)
+        } else {
+            None
+        }
+    }
+
+    /// One-line summary for the suggestion card
+    pub fn summary(&self, agent: AgentType) -> String {
+        match self {
+            FailureKind::AuthExpired => {
+                format!("{} needs you to log in again.", agent.display_name())
+            }
+            FailureKind::BinaryMissing => {
+                format!("{} couldn't be started.", agent.display_name())
+            }
+            FailureKind::RateLimited { .. } => {
+                format!("{} is rate limited.", agent.display_name())
+            }
+        }
+    }
+}
+
+/// Arguments that start an agent CLI's interactive login, if it has one
+pub fn login_args(agent: AgentType) -> Option<&'static [&'static str]> {
+    match agent {
+        AgentType::Claude => Some(&["/login"]),
+        AgentType::Codex => Some(&["login"]),
+        AgentType::Opencode => Some(&["auth", "login"]),
+        // Gemini prompts for auth when started interactively
+        AgentType::Gemini => Some(&[]),
+        AgentType::Local => None,
+    }
+}
+
+/// Parse hints like "retry after 20s" or "try again in 2 minutes"
+fn parse_retry_after(text: &str) -> Option<Duration> {
+    const MARKERS: &[&str] = &["retry after", "retry-after:", "try again in", "retry in"];
+    MARKERS.iter().find_map(|marker| {
+        let rest = text[text.find(marker)? + marker.len()..].trim_start();
+        let digits_end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let value: u64 = rest[..digits_end].parse().ok()?;
+        let unit = rest[digits_end..].trim_start();
+        let secs = if unit.starts_with("ms") || unit.starts_with("milli") {
+            value.div_ceil(1000)
+        } else if unit.starts_with('m') {
+            value.saturating_mul(60)
+        } else if unit.starts_with('h') {
+            value.saturating_mul(3600)
+        } else {
+            value
+        };
+        Some(Duration::from_secs(secs.max(1)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classifies_common_failures() {
+        assert_eq!(
+            FailureKind::classify_message("Invalid API key · Please run /login", None),
+            Some(FailureKind::AuthExpired)
+        );
+        assert_eq!(
+            FailureKind::classify_message("Agent error: Agent binary not found: claude", None),
+            Some(FailureKind::BinaryMissing)
+        );
+        assert_eq!(
+            FailureKind::classify_message("stream error: 429 Too Many Requests", None),
+            Some(FailureKind::RateLimited {
+                retry_after: DEFAULT_RATE_LIMIT_WAIT
+            })
+        );
+        assert_eq!(
+            FailureKind::classify_message("request failed", Some("rate_limit_error")),
+            Some(FailureKind::RateLimited {
+                retry_after: DEFAULT_RATE_LIMIT_WAIT
+            })
+        );
+        assert_eq!(
+            FailureKind::classify_message("Claude process failed: exit code 1", None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_honours_retry_hint() {
+        assert_eq!(
+            FailureKind::classify_message("Rate limit reached, try again in 2 minutes", None),
+            Some(FailureKind::RateLimited {
+                retry_after: Duration::from_secs(120)
+            })
+        );
+        assert_eq!(
+            FailureKind::classify_message("429: retry after 1500ms", None),
+            Some(FailureKind::RateLimited {
+                retry_after: Duration::from_secs(2)
+            })
+        );
+        // Day-long limits are capped; the card still offers a retry
+        assert_eq!(
+            FailureKind::classify_message("Usage limit hit. Try again in 20 hours", None),
+            Some(FailureKind::RateLimited {
+                retry_after: MAX_RATE_LIMIT_WAIT
+            })
+        );
+    }
+}
@@ -32,8 +32,10 @@ pub fn default_keybindings() -> KeybindingConfig {
     bind(&mut config.global, "M-S-f", Action::ForkSession); // Alt+Shift+F for fork
     bind(&mut config.global, "C-z", Action::Suspend);
 
-    // Note: Ctrl+C is handled specially in app.rs for double-press detection
+    bind(&mut config.global, "C-c", Action::ClearOrQuit);
     bind(&mut config.global, "C-g", Action::ToggleViewMode);
+    bind(&mut config.global, "M-l", Action::ToggleTimeline);
+    bind(&mut config.global, "M-o", Action::ToggleDiffView);
     bind(&mut config.global, "C-o", Action::ShowModelSelector);
     bind(&mut config.global, "M-i", Action::OpenSessionImport);
 
@@ -51,6 +53,7 @@ pub fn default_keybindings() -> KeybindingConfig {
 
     // Close tab with Alt+Shift+W (Ctrl+Shift doesn't work reliably in terminals)
     bind(&mut config.global, "M-S-w", Action::CloseTab);
+    bind(&mut config.global, "M-S-n", Action::RenameTab);
 
     // Tab/workspace switching with Alt+Tab and Alt+Shift+Tab
     config.global.insert(
@@ -72,11 +75,30 @@ pub fn default_keybindings() -> KeybindingConfig {
     bind(&mut config.global, "M-d", Action::DeleteWordForward);
     bind(&mut config.global, "M-<BS>", Action::DeleteWordBack);
     bind(&mut config.global, "M-p", Action::ToggleMetrics);
+    bind(&mut config.global, "M-S-o", Action::ToggleFrameDiagnostics);
+    bind(&mut config.global, "M-z", Action::ToggleZenMode);
     bind(&mut config.global, "M-g", Action::DumpDebugState);
+    bind(&mut config.global, "M-S-g", Action::ShowDiagnostics);
+    bind(&mut config.global, "M-S-r", Action::RestartAgent);
+    bind(&mut config.global, "M-S-p", Action::NudgeAgent);
+    bind(&mut config.global, "M-S-x", Action::ApplyFailureRemedy);
+    bind(&mut config.global, "M-S-a", Action::AnnotateImage);
+    bind(&mut config.global, "M-S-m", Action::ToggleVoiceInput);
+    bind(&mut config.global, "M-S-s", Action::ToggleReadAloud);
+    bind(&mut config.global, "M-S-l", Action::ToggleResponseLanguage);
+    bind(&mut config.global, "M-S-e", Action::EditPastPrompt);
+    bind(&mut config.global, "M-S-u", Action::RollbackToPastPrompt);
     bind(&mut config.global, "M-t", Action::ShowThemePicker);
     bind(&mut config.global, "M-n", Action::NewWorkspaceUnderCursor);
     bind(&mut config.global, "M-q", Action::OpenQueueEditor);
+    bind(&mut config.global, "M-S-q", Action::ExpandQueue);
+    bind(&mut config.global, "M-S-h", Action::ToggleQueuePanel);
     bind(&mut config.global, "M-e", Action::EditPromptExternal);
+    bind(&mut config.global, "M-w", Action::ToggleCodeWrap);
+    bind(&mut config.global, "M-r", Action::ToggleReasoning);
+    bind(&mut config.global, "M-x", Action::ToggleToolOutput);
+    bind(&mut config.global, "M-u", Action::ToggleExpandTools);
+    bind(&mut config.global, "M-S-t", Action::ToggleTimestamps);
 
     // Agent mode toggle (Build/Plan) - Ctrl+\
     //
@@ -98,6 +120,10 @@ pub fn default_keybindings() -> KeybindingConfig {
     bind(&mut config.global, "M-S-c", Action::CopyWorkspacePath);
     // Copy selection to clipboard
     bind(&mut config.global, "M-c", Action::CopySelection);
+    // Pick a whole message to copy
+    bind(&mut config.global, "M-y", Action::SelectMessages);
+    // Recent copies
+    bind(&mut config.global, "M-S-y", Action::ShowClipboardHistory);
 
     // Ctrl+Arrow for scrolling
     config.global.insert(
@@ -183,6 +209,13 @@ pub fn default_keybindings() -> KeybindingConfig {
     );
     bind(chat, "M-<Up>", Action::ScrollPrevUserMessage);
     bind(chat, "M-<Down>", Action::ScrollNextUserMessage);
+    bind(chat, "M-j", Action::JumpToDuplicateAnswer);
+    // Undo/redo - Ctrl+_ (most terminals report it as Ctrl+7, like Ctrl+/)
+    // and Alt+_. Ctrl+Z stays bound to suspend.
+    bind(chat, "C-7", Action::Undo);
+    bind(chat, "C-_", Action::Undo);
+    bind(chat, "M-_", Action::Redo);
+    bind(chat, "M-S-i", Action::RemoveAttachment);
 
     // Tab toggles Plan/Build mode
     chat.insert(
@@ -228,6 +261,16 @@ pub fn default_keybindings() -> KeybindingConfig {
         KeyCombo::new(KeyCode::End, KeyModifiers::NONE),
         Action::ScrollToBottom,
     );
+    scrolling.insert(
+        KeyCombo::new(KeyCode::Left, KeyModifiers::NONE),
+        Action::ScrollLeft(4),
+    );
+    scrolling.insert(
+        KeyCombo::new(KeyCode::Right, KeyModifiers::NONE),
+        Action::ScrollRight(4),
+    );
+    bind(scrolling, "h", Action::ScrollLeft(4));
+    bind(scrolling, "l", Action::ScrollRight(4));
     bind(scrolling, "g", Action::ScrollToTop);
     bind(scrolling, "G", Action::ScrollToBottom);
     scrolling.insert(
@@ -236,6 +279,64 @@ pub fn default_keybindings() -> KeybindingConfig {
     );
     bind(scrolling, "q", Action::Cancel);
     bind(scrolling, "i", Action::Cancel);
+    bind(scrolling, "v", Action::SelectMessages);
+    // Jump between prompt boundaries
+    bind(scrolling, "[", Action::ScrollPrevUserMessage);
+    bind(scrolling, "]", Action::ScrollNextUserMessage);
+    bind(scrolling, "M-<Up>", Action::ScrollPrevUserMessage);
+    bind(scrolling, "M-<Down>", Action::ScrollNextUserMessage);
+
+    // ========== Message Selection Mode ==========
+    let message_select = config.context.entry(KeyContext::MessageSelect).or_default();
+
+    message_select.insert(
+        KeyCombo::new(KeyCode::Up, KeyModifiers::NONE),
+        Action::SelectPrev,
+    );
+    message_select.insert(
+        KeyCombo::new(KeyCode::Down, KeyModifiers::NONE),
+        Action::SelectNext,
+    );
+    bind(message_select, "k", Action::SelectPrev);
+    bind(message_select, "j", Action::SelectNext);
+    bind(message_select, "y", Action::CopySelectedMessage);
+    message_select.insert(
+        KeyCombo::new(KeyCode::Enter, KeyModifiers::NONE),
+        Action::CopySelectedMessage,
+    );
+    message_select.insert(
+        KeyCombo::new(KeyCode::Esc, KeyModifiers::NONE),
+        Action::Cancel,
+    );
+    bind(message_select, "q", Action::Cancel);
+
+    // ========== Clipboard History ==========
+    let clipboard_history = config
+        .context
+        .entry(KeyContext::ClipboardHistory)
+        .or_default();
+
+    clipboard_history.insert(
+        KeyCombo::new(KeyCode::Up, KeyModifiers::NONE),
+        Action::SelectPrev,
+    );
+    clipboard_history.insert(
+        KeyCombo::new(KeyCode::Down, KeyModifiers::NONE),
+        Action::SelectNext,
+    );
+    bind(clipboard_history, "k", Action::SelectPrev);
+    bind(clipboard_history, "j", Action::SelectNext);
+    bind(clipboard_history, "y", Action::RecopyClipboardEntry);
+    clipboard_history.insert(
+        KeyCombo::new(KeyCode::Enter, KeyModifiers::NONE),
+        Action::RecopyClipboardEntry,
+    );
+    bind(clipboard_history, "p", Action::PasteClipboardEntry);
+    clipboard_history.insert(
+        KeyCombo::new(KeyCode::Esc, KeyModifiers::NONE),
+        Action::Cancel,
+    );
+    bind(clipboard_history, "q", Action::Cancel);
 
     // ========== Queue Editing Mode ==========
     let queue = config.context.entry(KeyContext::QueueEditing).or_default();
@@ -272,6 +373,10 @@ pub fn default_keybindings() -> KeybindingConfig {
         KeyCombo::new(KeyCode::Char('j'), KeyModifiers::ALT),
         Action::QueueMoveDown,
     );
+    queue.insert(
+        KeyCombo::new(KeyCode::Tab, KeyModifiers::NONE),
+        Action::ExpandQueue,
+    );
 
     // ========== Sidebar Navigation ==========
     let sidebar = config.context.entry(KeyContext::Sidebar).or_default();
@@ -508,6 +613,42 @@ pub fn default_keybindings() -> KeybindingConfig {
         Action::MoveCursorEnd,
     );
 
+    // ========== Rename Tab Dialog ==========
+    let rename_tab = config.context.entry(KeyContext::RenameTab).or_default();
+
+    rename_tab.insert(
+        KeyCombo::new(KeyCode::Enter, KeyModifiers::NONE),
+        Action::Confirm,
+    );
+    rename_tab.insert(
+        KeyCombo::new(KeyCode::Esc, KeyModifiers::NONE),
+        Action::Cancel,
+    );
+    rename_tab.insert(
+        KeyCombo::new(KeyCode::Backspace, KeyModifiers::NONE),
+        Action::Backspace,
+    );
+    rename_tab.insert(
+        KeyCombo::new(KeyCode::Delete, KeyModifiers::NONE),
+        Action::Delete,
+    );
+    rename_tab.insert(
+        KeyCombo::new(KeyCode::Left, KeyModifiers::NONE),
+        Action::MoveCursorLeft,
+    );
+    rename_tab.insert(
+        KeyCombo::new(KeyCode::Right, KeyModifiers::NONE),
+        Action::MoveCursorRight,
+    );
+    rename_tab.insert(
+        KeyCombo::new(KeyCode::Home, KeyModifiers::NONE),
+        Action::MoveCursorStart,
+    );
+    rename_tab.insert(
+        KeyCombo::new(KeyCode::End, KeyModifiers::NONE),
+        Action::MoveCursorEnd,
+    );
+
     // ========== Raw Events View ==========
     let raw = config.context.entry(KeyContext::RawEvents).or_default();
 
@@ -552,6 +693,69 @@ pub fn default_keybindings() -> KeybindingConfig {
     // Copy selected event
     bind(raw, "c", Action::EventDetailCopy);
 
+    // Detail panel folding and path filter
+    bind(raw, "z", Action::EventDetailFold);
+    bind(raw, "Z", Action::EventDetailUnfold);
+    bind(raw, "f", Action::EventDetailFilter);
+
+    // Search across event payloads
+    bind(raw, "/", Action::RawEventsSearch);
+    bind(raw, "n", Action::RawEventsSearchNext);
+    bind(raw, "N", Action::RawEventsSearchPrev);
+
+    // Stick to the newest event, or hold the selection while events arrive
+    bind(raw, "F", Action::RawEventsToggleFollow);
+
+    // ========== Timeline View ==========
+    let timeline = config.context.entry(KeyContext::Timeline).or_default();
+
+    timeline.insert(
+        KeyCombo::new(KeyCode::Up, KeyModifiers::NONE),
+        Action::ScrollUp(1),
+    );
+    timeline.insert(
+        KeyCombo::new(KeyCode::Down, KeyModifiers::NONE),
+        Action::ScrollDown(1),
+    );
+    bind(timeline, "k", Action::ScrollUp(1));
+    bind(timeline, "j", Action::ScrollDown(1));
+    bind(timeline, "g", Action::ScrollToTop);
+    bind(timeline, "G", Action::ScrollToBottom);
+    timeline.insert(
+        KeyCombo::new(KeyCode::PageUp, KeyModifiers::NONE),
+        Action::ScrollPageUp,
+    );
+    timeline.insert(
+        KeyCombo::new(KeyCode::PageDown, KeyModifiers::NONE),
+        Action::ScrollPageDown,
+    );
+
+    // ========== Diff View ==========
+    let diff = config.context.entry(KeyContext::DiffView).or_default();
+
+    diff.insert(
+        KeyCombo::new(KeyCode::Up, KeyModifiers::NONE),
+        Action::ScrollUp(1),
+    );
+    diff.insert(
+        KeyCombo::new(KeyCode::Down, KeyModifiers::NONE),
+        Action::ScrollDown(1),
+    );
+    bind(diff, "k", Action::ScrollUp(1));
+    bind(diff, "j", Action::ScrollDown(1));
+    bind(diff, "g", Action::ScrollToTop);
+    bind(diff, "G", Action::ScrollToBottom);
+    diff.insert(
+        KeyCombo::new(KeyCode::PageUp, KeyModifiers::NONE),
+        Action::ScrollPageUp,
+    );
+    diff.insert(
+        KeyCombo::new(KeyCode::PageDown, KeyModifiers::NONE),
+        Action::ScrollPageDown,
+    );
+    bind(diff, "q", Action::ToggleDiffView);
+    bind(diff, "<Esc>", Action::ToggleDiffView);
+
     // ========== Command Mode ==========
     let command = config.context.entry(KeyContext::Command).or_default();
 
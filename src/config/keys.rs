@@ -110,8 +110,14 @@ pub enum KeyContext {
     AddRepository,
     /// Setting base directory
     BaseDir,
+    /// Renaming the current tab
+    RenameTab,
     /// Raw events debug view
     RawEvents,
+    /// Session timeline view
+    Timeline,
+    /// Session diff view
+    DiffView,
     /// Command mode (typing :command)
     Command,
     /// Help dialog
@@ -124,6 +130,10 @@ pub enum KeyContext {
     ThemePicker,
     /// Queue editor (inline)
     QueueEditing,
+    /// Selecting a whole chat message to copy
+    MessageSelect,
+    /// Clipboard history overlay
+    ClipboardHistory,
 }
 
 impl KeyContext {
@@ -138,16 +148,42 @@ impl KeyContext {
             KeyContext::ModelSelector,
             KeyContext::AddRepository,
             KeyContext::BaseDir,
+            KeyContext::RenameTab,
             KeyContext::RawEvents,
+            KeyContext::Timeline,
+            KeyContext::DiffView,
             KeyContext::Command,
             KeyContext::HelpDialog,
             KeyContext::SessionImport,
             KeyContext::CommandPalette,
             KeyContext::ThemePicker,
             KeyContext::QueueEditing,
+            KeyContext::MessageSelect,
+            KeyContext::ClipboardHistory,
         ]
     }
 
+    /// Name of the `[keys.<name>]` section configuring this context, if any
+    pub fn config_section(&self) -> Option<&'static str> {
+        match self {
+            KeyContext::Chat => Some("chat"),
+            KeyContext::Scrolling => Some("scrolling"),
+            KeyContext::Sidebar => Some("sidebar"),
+            KeyContext::Dialog => Some("dialog"),
+            KeyContext::ProjectPicker => Some("project_picker"),
+            KeyContext::ModelSelector => Some("model_selector"),
+            KeyContext::AddRepository => Some("add_repository"),
+            KeyContext::BaseDir => Some("base_dir"),
+            KeyContext::RawEvents => Some("raw_events"),
+            KeyContext::Timeline => Some("timeline"),
+            KeyContext::DiffView => Some("diff_view"),
+            KeyContext::QueueEditing => Some("queue"),
+            KeyContext::MessageSelect => Some("message_select"),
+            KeyContext::ClipboardHistory => Some("clipboard_history"),
+            _ => None,
+        }
+    }
+
     /// Convert from InputMode to KeyContext
     pub fn from_input_mode(
         mode: crate::ui::events::InputMode,
@@ -163,6 +199,7 @@ impl KeyContext {
             InputMode::AddingRepository => return KeyContext::AddRepository,
             InputMode::SelectingModel => return KeyContext::ModelSelector,
             InputMode::SettingBaseDir => return KeyContext::BaseDir,
+            InputMode::RenamingTab => return KeyContext::RenameTab,
             InputMode::PickingProject => return KeyContext::ProjectPicker,
             InputMode::Confirming => return KeyContext::Dialog,
             InputMode::RemovingProject => return KeyContext::Dialog,
@@ -174,13 +211,18 @@ impl KeyContext {
             InputMode::MissingTool => return KeyContext::Dialog,
             InputMode::SelectingTheme => return KeyContext::ThemePicker,
             InputMode::QueueEditing => return KeyContext::QueueEditing,
+            InputMode::SelectingMessage => return KeyContext::MessageSelect,
+            InputMode::ClipboardHistory => return KeyContext::ClipboardHistory,
             // Non-modal modes - continue to check view mode
             InputMode::Normal | InputMode::Scrolling | InputMode::SidebarNavigation => {}
         }
 
-        // RawEvents view takes precedence for non-modal input modes
-        if view_mode == ViewMode::RawEvents {
-            return KeyContext::RawEvents;
+        // RawEvents, Timeline and Diff views take precedence for non-modal input modes
+        match view_mode {
+            ViewMode::RawEvents => return KeyContext::RawEvents,
+            ViewMode::Timeline => return KeyContext::Timeline,
+            ViewMode::Diff => return KeyContext::DiffView,
+            ViewMode::Chat => {}
         }
 
         // Standard non-modal input modes
@@ -222,23 +264,191 @@ impl KeybindingConfig {
         self.global.get(key)
     }
 
-    /// Merge user configuration on top of defaults
-    pub fn merge(&mut self, other: KeybindingConfig) {
-        // Merge global bindings
-        for (key, action) in other.global {
-            self.global.insert(key, action);
+    /// Apply user keybindings on top of the defaults.
+    ///
+    /// A remapped action loses its default keys in that context, so moving an
+    /// action to another key frees the old one; remaps without a key unbind
+    /// the action. Remaps are applied in order and the first one to claim a
+    /// key wins. Returns the conflicts found along the way.
+    pub fn apply_remaps(&mut self, remaps: &[KeyRemap]) -> Vec<KeymapIssue> {
+        let mut issues = Vec::new();
+
+        for remap in remaps {
+            self.bindings_mut(remap.context)
+                .retain(|_, action| action != &remap.action);
+        }
+
+        let mut claimed: HashMap<(Option<KeyContext>, KeyCombo), Action> = HashMap::new();
+        let mut displaced: Vec<(Option<KeyContext>, KeyCombo, Action, Action)> = Vec::new();
+        for remap in remaps {
+            let Some(key) = &remap.key else {
+                continue;
+            };
+            let slot = (remap.context, key.clone());
+            if let Some(kept) = claimed.get(&slot) {
+                issues.push(KeymapIssue::Conflict {
+                    context: remap.context,
+                    key: key.clone(),
+                    kept: kept.clone(),
+                    dropped: remap.action.clone(),
+                });
+                continue;
+            }
+            claimed.insert(slot, remap.action.clone());
+            if let Some(previous) = self
+                .bindings_mut(remap.context)
+                .insert(key.clone(), remap.action.clone())
+            {
+                displaced.push((remap.context, key.clone(), remap.action.clone(), previous));
+            }
+        }
+
+        // Taking a default action's key is fine as long as it keeps another one
+        for (context, key, action, previous) in displaced {
+            if !self
+                .bindings_mut(context)
+                .values()
+                .any(|bound| bound == &previous)
+            {
+                issues.push(KeymapIssue::Displaced {
+                    context,
+                    key,
+                    action,
+                    displaced: previous,
+                });
+            }
+        }
+
+        issues
+    }
+
+    /// Bindings for `context`, or the global bindings for `None`
+    fn bindings_mut(&mut self, context: Option<KeyContext>) -> &mut HashMap<KeyCombo, Action> {
+        match context {
+            Some(context) => self.context.entry(context).or_default(),
+            None => &mut self.global,
+        }
+    }
+}
+
+/// A user keybinding from config.toml
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyRemap {
+    /// Context section the binding was declared in (`None` for `[keys]`)
+    pub context: Option<KeyContext>,
+    pub action: Action,
+    /// `None` unbinds the action
+    pub key: Option<KeyCombo>,
+}
+
+/// Problem with the user's keybindings, reported on load and in the help
+/// dialog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeymapIssue {
+    /// Action name not recognized
+    UnknownAction {
+        context: Option<KeyContext>,
+        name: String,
+    },
+    /// Key notation could not be parsed
+    InvalidKey {
+        context: Option<KeyContext>,
+        action: String,
+        key: String,
+        error: KeyParseError,
+    },
+    /// Two user bindings claim the same key; `dropped` was ignored
+    Conflict {
+        context: Option<KeyContext>,
+        key: KeyCombo,
+        kept: Action,
+        dropped: Action,
+    },
+    /// A user binding took the only key of a default action
+    Displaced {
+        context: Option<KeyContext>,
+        key: KeyCombo,
+        action: Action,
+        displaced: Action,
+    },
+}
+
+impl KeymapIssue {
+    /// The key the issue is about, as written in the config
+    pub fn key_display(&self) -> String {
+        match self {
+            KeymapIssue::UnknownAction { .. } => "-".to_string(),
+            KeymapIssue::InvalidKey { key, .. } => key.clone(),
+            KeymapIssue::Conflict { key, .. } | KeymapIssue::Displaced { key, .. } => {
+                key.to_string()
+            }
         }
+    }
+}
 
-        // Merge context-specific bindings
-        for (ctx, bindings) in other.context {
-            let entry = self.context.entry(ctx).or_default();
-            for (key, action) in bindings {
-                entry.insert(key, action);
+impl fmt::Display for KeymapIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeymapIssue::UnknownAction { context, name } => {
+                write!(
+                    f,
+                    "{}: unknown action \"{}\"",
+                    section_label(*context),
+                    name
+                )
             }
+            KeymapIssue::InvalidKey {
+                context,
+                action,
+                key,
+                error,
+            } => write!(
+                f,
+                "{}: {} = \"{}\": {}",
+                section_label(*context),
+                action,
+                key,
+                error
+            ),
+            KeymapIssue::Conflict {
+                context,
+                key,
+                kept,
+                dropped,
+            } => write!(
+                f,
+                "{}: {} is bound to both \"{}\" and \"{}\"; ignoring \"{}\"",
+                section_label(*context),
+                key,
+                kept.description(),
+                dropped.description(),
+                dropped.description()
+            ),
+            KeymapIssue::Displaced {
+                context,
+                key,
+                action,
+                displaced,
+            } => write!(
+                f,
+                "{}: {} now runs \"{}\"; \"{}\" has no key",
+                section_label(*context),
+                key,
+                action.description(),
+                displaced.description()
+            ),
         }
     }
 }
 
+/// `[keys]` or `[keys.<section>]`
+fn section_label(context: Option<KeyContext>) -> String {
+    match context.and_then(|context| context.config_section()) {
+        Some(section) => format!("[keys.{}]", section),
+        None => "[keys]".to_string(),
+    }
+}
+
 /// Parse a vim-style key notation string into a KeyCombo
 ///
 /// Supported notation:
@@ -712,4 +922,107 @@ mod tests {
             "Ctrl+4 from key event should match parsed C-4"
         );
     }
+
+    fn remap(context: Option<KeyContext>, action: Action, key: Option<&str>) -> KeyRemap {
+        KeyRemap {
+            context,
+            action,
+            key: key.map(|key| parse_key_notation(key).unwrap()),
+        }
+    }
+
+    fn defaults() -> KeybindingConfig {
+        let mut config = KeybindingConfig::new();
+        config
+            .global
+            .insert(parse_key_notation("C-q").unwrap(), Action::Quit);
+        config
+            .global
+            .insert(parse_key_notation("C-w").unwrap(), Action::DeleteWordBack);
+        config
+            .global
+            .insert(parse_key_notation("M-S-w").unwrap(), Action::CloseTab);
+        config
+    }
+
+    #[test]
+    fn test_remap_replaces_default_key() {
+        let mut config = defaults();
+        let issues = config.apply_remaps(&[remap(None, Action::Quit, Some("M-q"))]);
+
+        assert!(issues.is_empty());
+        let ctrl_q = parse_key_notation("C-q").unwrap();
+        let alt_q = parse_key_notation("M-q").unwrap();
+        assert_eq!(config.get_action(&ctrl_q, KeyContext::Chat), None);
+        assert_eq!(
+            config.get_action(&alt_q, KeyContext::Chat),
+            Some(&Action::Quit)
+        );
+    }
+
+    #[test]
+    fn test_remap_without_key_unbinds() {
+        let mut config = defaults();
+        let issues = config.apply_remaps(&[remap(None, Action::Quit, None)]);
+
+        assert!(issues.is_empty());
+        assert!(!config.global.values().any(|action| action == &Action::Quit));
+    }
+
+    #[test]
+    fn test_remap_reports_displaced_default() {
+        let mut config = defaults();
+        let issues = config.apply_remaps(&[remap(None, Action::CloseTab, Some("C-w"))]);
+
+        let ctrl_w = parse_key_notation("C-w").unwrap();
+        assert_eq!(
+            config.get_action(&ctrl_w, KeyContext::Chat),
+            Some(&Action::CloseTab)
+        );
+        assert_eq!(
+            issues,
+            [KeymapIssue::Displaced {
+                context: None,
+                key: ctrl_w,
+                action: Action::CloseTab,
+                displaced: Action::DeleteWordBack,
+            }]
+        );
+
+        // Moving the displaced action too resolves it
+        let mut config = defaults();
+        let issues = config.apply_remaps(&[
+            remap(None, Action::CloseTab, Some("C-w")),
+            remap(None, Action::DeleteWordBack, Some("M-<BS>")),
+        ]);
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_remap_conflict_keeps_first() {
+        let mut config = defaults();
+        let issues = config.apply_remaps(&[
+            remap(Some(KeyContext::Chat), Action::Quit, Some("C-x")),
+            remap(Some(KeyContext::Chat), Action::CloseTab, Some("C-x")),
+        ]);
+
+        let ctrl_x = parse_key_notation("C-x").unwrap();
+        assert_eq!(
+            config.get_action(&ctrl_x, KeyContext::Chat),
+            Some(&Action::Quit)
+        );
+        assert_eq!(
+            issues,
+            [KeymapIssue::Conflict {
+                context: Some(KeyContext::Chat),
+                key: ctrl_x,
+                kept: Action::Quit,
+                dropped: Action::CloseTab,
+            }]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "[keys.chat]: C-x is bound to both \"Quit application\" and \"Close tab\"; ignoring \"Close tab\""
+        );
+    }
 }
@@ -3,8 +3,15 @@ pub mod keys;
 mod settings;
 
 pub use default_keys::default_keybindings;
-pub use keys::{parse_key_notation, KeyCombo, KeyContext, KeyParseError, KeybindingConfig};
+pub use keys::{
+    parse_key_notation, KeyCombo, KeyContext, KeyParseError, KeyRemap, KeybindingConfig,
+    KeymapIssue,
+};
 pub use settings::{
-    parse_action, save_default_model, save_theme_config, save_tool_path, Config, QueueDelivery,
-    QueueMode, SteerBehavior, SteerFallback, COMMAND_NAMES, EXAMPLE_CONFIG,
+    parse_action, save_default_model, save_theme_config, save_tool_path, AgentProfile,
+    BootstrapConfig, ChangeGuardConfig, ChatTimestamps, Config, FetchConfig, HookCommand,
+    HooksConfig, LocalModelConfig, NotificationsConfig, PersistenceConfig, PreviewConfig,
+    QueueDelivery, QueueMode, ReadAloudConfig, SecurityScanConfig, SessionTemplate, StallConfig,
+    SteerBehavior, SteerFallback, UsageStatsConfig, VoiceBackend, VoiceConfig, COMMAND_NAMES,
+    EXAMPLE_CONFIG, MAX_SESSION_TEMPLATES,
 };
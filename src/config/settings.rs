@@ -1,18 +1,20 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use serde::Deserialize;
 use toml_edit::{DocumentMut, Item, Table};
 
-use crate::agent::{AgentType, ModelRegistry};
+use crate::agent::{AgentType, ExecutionTarget, ModelRegistry};
+use crate::core::security_scan::{Scanner, Severity};
 use crate::git::WorkspaceMode;
 use crate::ui::action::Action;
 use crate::util::paths::config_path;
 use crate::util::tools::{Tool, ToolPaths};
 
 use super::default_keys::default_keybindings;
-use super::keys::{parse_key_notation, KeyContext, KeybindingConfig};
+use super::keys::{parse_key_notation, KeyContext, KeyRemap, KeybindingConfig, KeymapIssue};
 
 /// Example configuration file contents (bundled with the binary)
 pub const EXAMPLE_CONFIG: &str = include_str!("config.toml.example");
@@ -34,12 +36,10 @@ pub struct Config {
     pub show_cost: bool,
     /// Default allowed tools for Claude
     pub claude_allowed_tools: Vec<String>,
-    /// Claude model pricing (input tokens per $1M)
-    pub claude_input_cost_per_million: f64,
-    /// Claude model pricing (output tokens per $1M)
-    pub claude_output_cost_per_million: f64,
     /// Keybinding configuration
     pub keybindings: KeybindingConfig,
+    /// Problems found in the user's keybindings (shown in the help dialog)
+    pub keybinding_issues: Vec<KeymapIssue>,
     /// Configured paths for external tools (git, gh, claude, codex, gemini)
     pub tool_paths: ToolPaths,
     /// Theme name from config (None = use default)
@@ -58,6 +58,40 @@ pub struct Config {
     pub web_status: WebStatusConfig,
     /// Workspace defaults
     pub workspaces: WorkspacesConfig,
+    /// Quick-start templates shown on the splash screen
+    pub templates: Vec<SessionTemplate>,
+    /// Per-agent environment variables and extra CLI flags
+    pub agent_profiles: HashMap<AgentType, AgentProfile>,
+    /// Local OpenAI-compatible endpoint (None = local agent disabled)
+    pub local_model: Option<LocalModelConfig>,
+    /// Voice prompt input (speech-to-text); `None` disables it
+    pub voice: Option<VoiceConfig>,
+    /// Text-to-speech hook for completed turns; `None` disables it
+    pub read_aloud: Option<ReadAloudConfig>,
+    /// Shell commands run before prompts are sent and after turns complete
+    pub hooks: HooksConfig,
+    /// Human review of agent actions before they take effect
+    pub review: ReviewConfig,
+    /// Setup commands run in new workspaces before their first turn
+    pub bootstrap: BootstrapConfig,
+    /// Where agent processes run, per repository name (missing = host)
+    pub execution: HashMap<String, ExecutionTarget>,
+    /// Dev server preview detection and forwarding
+    pub preview: PreviewConfig,
+    /// Opt-in feature usage counting (`conduit stats`)
+    pub usage_stats: UsageStatsConfig,
+    /// What session transcripts are written to the database
+    pub persistence: PersistenceConfig,
+    /// Limits for pages attached with `/fetch`
+    pub fetch: FetchConfig,
+    /// Desktop notifications for background tabs
+    pub notifications: NotificationsConfig,
+    /// Detection of turns that stopped producing events
+    pub stall: StallConfig,
+    /// Security scanners run before a PR is created
+    pub security_scan: SecurityScanConfig,
+    /// Checks for large, binary or foreign-licensed files before a PR is created
+    pub change_guard: ChangeGuardConfig,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
@@ -78,12 +112,16 @@ pub enum QueueMode {
 pub struct QueueConfig {
     pub delivery: QueueDelivery,
     pub mode: QueueMode,
+    /// Maximum number of agents running a turn at once across all sessions
+    /// (`None` = unlimited). Extra prompts wait for a free slot.
+    pub max_concurrent_agents: Option<usize>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct TomlQueueConfig {
     pub delivery: Option<QueueDelivery>,
     pub mode: Option<QueueMode>,
+    pub max_concurrent_agents: Option<usize>,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
@@ -101,6 +139,29 @@ pub enum SteerFallback {
     Prompt,
 }
 
+/// How message timestamps are shown in the chat view gutter
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChatTimestamps {
+    #[default]
+    Off,
+    /// Local time the message arrived, e.g. `14:05`
+    Absolute,
+    /// Time since the message arrived, e.g. `12m`
+    Relative,
+}
+
+impl ChatTimestamps {
+    /// Next mode for toggle_timestamps: off → absolute → relative → off
+    pub fn next(self) -> Self {
+        match self {
+            Self::Off => Self::Absolute,
+            Self::Absolute => Self::Relative,
+            Self::Relative => Self::Off,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct SteerConfig {
     pub behavior: SteerBehavior,
@@ -119,9 +180,29 @@ pub struct SelectionConfig {
     pub clear_selection_after_copy: bool,
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Default line budget for the inline queue panel
+pub const DEFAULT_QUEUE_MAX_LINES: usize = 5;
+/// Messages longer than this keep only their start and end in the chat view
+pub const DEFAULT_MAX_MESSAGE_KB: usize = 1024;
+/// Chat history a session keeps in memory before offloading the oldest messages
+pub const DEFAULT_CHAT_MEMORY_MB: usize = 64;
+
+#[derive(Debug, Clone)]
 pub struct UiConfig {
     pub show_chat_scrollbar: bool,
+    pub show_reasoning: bool,
+    /// Lines the inline queue panel may take in the chat view (summary included)
+    pub queue_max_lines: usize,
+    /// Start with the queue panel collapsed to its one-line summary
+    pub queue_collapsed: bool,
+    /// Language toggle_response_language switches sessions to (e.g. "German")
+    pub response_language: Option<String>,
+    /// Longest message kept whole in the chat view, in KB (0 = unlimited)
+    pub max_message_kb: usize,
+    /// Chat history kept in memory per session, in MB (0 = unlimited)
+    pub chat_memory_mb: usize,
+    /// Message timestamps in the chat view gutter
+    pub chat_timestamps: ChatTimestamps,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -133,6 +214,13 @@ pub struct TomlSelectionConfig {
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct TomlUiConfig {
     pub show_chat_scrollbar: Option<bool>,
+    pub show_reasoning: Option<bool>,
+    pub queue_max_lines: Option<usize>,
+    pub queue_collapsed: Option<bool>,
+    pub response_language: Option<String>,
+    pub max_message_kb: Option<usize>,
+    pub chat_memory_mb: Option<usize>,
+    pub chat_timestamps: Option<ChatTimestamps>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -165,6 +253,629 @@ pub struct TomlWorkspacesConfig {
     pub archive_remote_prompt: Option<bool>,
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReviewConfig {
+    /// Hold Claude's file edits for hunk-by-hunk approval (default for new sessions)
+    pub edits: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlReviewConfig {
+    pub edits: Option<bool>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BootstrapConfig {
+    /// Run bootstrap commands when a workspace is created (false skips them)
+    pub enabled: bool,
+    /// Shell commands per repository name, run in order
+    pub commands: HashMap<String, Vec<String>>,
+}
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            commands: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlBootstrapConfig {
+    pub enabled: Option<bool>,
+    pub commands: Option<HashMap<String, Vec<String>>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PreviewConfig {
+    /// Watch tool output for dev servers and show a preview badge
+    pub enabled: bool,
+    /// Relay preview ports from containers (or to other machines) through a local listener
+    pub forward: bool,
+    /// Address forwarded previews listen on ("0.0.0.0" exposes them remotely)
+    pub bind: String,
+    /// Hostname used in forwarded preview URLs (None = bind address, or localhost)
+    pub host: Option<String>,
+}
+
+impl Default for PreviewConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            forward: false,
+            bind: "127.0.0.1".to_string(),
+            host: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlPreviewConfig {
+    pub enabled: Option<bool>,
+    pub forward: Option<bool>,
+    pub bind: Option<String>,
+    pub host: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UsageStatsConfig {
+    /// Count feature use per day in the local database
+    pub enabled: bool,
+    /// Where daily aggregates are POSTed (None = never leave this machine)
+    pub endpoint: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlUsageStatsConfig {
+    pub enabled: Option<bool>,
+    pub endpoint: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PersistenceConfig {
+    /// Write chat messages to the database as they complete
+    pub messages: bool,
+    /// Write raw agent events too (dropped oldest-first when writes fall
+    /// behind). Off by default: they're large and only useful for debugging.
+    pub raw_events: bool,
+}
+
+impl Default for PersistenceConfig {
+    fn default() -> Self {
+        Self {
+            messages: true,
+            raw_events: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlPersistenceConfig {
+    pub messages: Option<bool>,
+    pub raw_events: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchConfig {
+    /// Hosts `/fetch` may download from, subdomains included (empty = any host)
+    pub allowed_hosts: Vec<String>,
+    /// Largest response downloaded, in KB
+    pub max_download_kb: usize,
+    /// Most extracted text attached to a prompt, in KB
+    pub max_text_kb: usize,
+    /// Seconds to wait for the whole download
+    pub timeout_secs: u64,
+}
+
+impl Default for FetchConfig {
+    fn default() -> Self {
+        Self {
+            allowed_hosts: Vec::new(),
+            max_download_kb: 2048,
+            max_text_kb: 64,
+            timeout_secs: 20,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlFetchConfig {
+    pub allowed_hosts: Option<Vec<String>>,
+    pub max_download_kb: Option<usize>,
+    pub max_text_kb: Option<usize>,
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotificationsConfig {
+    /// Notify when a turn completes or fails in a tab that isn't active
+    pub enabled: bool,
+    /// Only notify while the terminal window doesn't have focus
+    pub only_when_unfocused: bool,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            only_when_unfocused: false,
+        }
+    }
+}
+
+impl NotificationsConfig {
+    /// Whether a background tab's turn ending should raise a notification
+    pub fn should_notify(&self, terminal_focused: bool) -> bool {
+        self.enabled && !(self.only_when_unfocused && terminal_focused)
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlNotificationsConfig {
+    pub enabled: Option<bool>,
+    pub only_when_unfocused: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StallConfig {
+    /// Seconds without agent events, while the process is alive and no tool
+    /// is running, before a turn is flagged as possibly stalled (0 = never)
+    pub after_secs: u64,
+}
+
+impl Default for StallConfig {
+    fn default() -> Self {
+        Self { after_secs: 180 }
+    }
+}
+
+impl StallConfig {
+    /// Quiet period after which a turn counts as possibly stalled
+    pub fn threshold(&self) -> Option<Duration> {
+        (self.after_secs > 0).then(|| Duration::from_secs(self.after_secs))
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlStallConfig {
+    pub after_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityScanConfig {
+    /// Scanners run during the PR preflight (empty = no scan)
+    pub scanners: Vec<Scanner>,
+    /// Findings at or above this severity block PR creation (None = never block)
+    pub block_at: Option<Severity>,
+    /// Findings below this severity are left out of the results
+    pub warn_at: Severity,
+}
+
+impl Default for SecurityScanConfig {
+    fn default() -> Self {
+        Self {
+            scanners: Vec::new(),
+            block_at: Some(Severity::High),
+            warn_at: Severity::Low,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangeGuardConfig {
+    /// Check changed files before a PR is created
+    pub enabled: bool,
+    /// Files larger than this are flagged, in KB (0 = no size limit)
+    pub max_file_kb: u64,
+}
+
+impl Default for ChangeGuardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_file_kb: 1024,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlChangeGuardConfig {
+    pub enabled: Option<bool>,
+    pub max_file_kb: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlSecurityScanConfig {
+    pub scanners: Option<Vec<Scanner>>,
+    /// Severity name, or "none" to only warn
+    pub block: Option<String>,
+    pub warn: Option<Severity>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlExecutionTarget {
+    /// "host", "docker" or "devcontainer"
+    pub target: Option<String>,
+    pub image: Option<String>,
+    pub mounts: Option<Vec<String>>,
+    pub docker_args: Option<Vec<String>>,
+}
+
+impl TomlExecutionTarget {
+    fn into_target(self) -> Result<ExecutionTarget, String> {
+        let target = self.target.as_deref().map(str::trim).unwrap_or("host");
+        match target.to_ascii_lowercase().as_str() {
+            "host" => Ok(ExecutionTarget::Host),
+            "docker" => {
+                let image = self
+                    .image
+                    .map(|image| image.trim().to_string())
+                    .filter(|image| !image.is_empty())
+                    .ok_or("image is required for the docker target")?;
+                Ok(ExecutionTarget::Docker {
+                    image,
+                    mounts: self.mounts.unwrap_or_default(),
+                    docker_args: self.docker_args.unwrap_or_default(),
+                })
+            }
+            "devcontainer" => Ok(ExecutionTarget::DevContainer),
+            other => Err(format!("unknown target '{}'", other)),
+        }
+    }
+}
+
+/// Maximum number of templates reachable from the splash screen (keys 1-9)
+pub const MAX_SESSION_TEMPLATES: usize = 9;
+
+/// Quick-start session template (agent + model + workspace + seed prompt)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionTemplate {
+    /// Label shown on the splash screen
+    pub name: String,
+    pub agent_type: AgentType,
+    /// Model ID (None = agent default)
+    pub model: Option<String>,
+    /// Workspace name, or `repo/workspace` to disambiguate
+    pub workspace: Option<String>,
+    /// Prompt pre-filled into the input box
+    pub prompt: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlSessionTemplate {
+    pub name: Option<String>,
+    pub agent: Option<String>,
+    pub model: Option<String>,
+    pub workspace: Option<String>,
+    pub prompt: Option<String>,
+}
+
+impl SessionTemplate {
+    /// Build a template from its TOML entry. Entries without an agent, or with
+    /// an agent name that isn't known, are skipped; agent names ignore case and
+    /// surrounding whitespace. Unknown models fall back to the agent default.
+    fn from_toml(entry: TomlSessionTemplate) -> Option<Self> {
+        let agent = entry.agent.as_deref()?;
+        let Some(agent_type) = AgentType::from_name(&agent.trim().to_lowercase()) else {
+            tracing::warn!(agent, "Skipping session template with unknown agent");
+            return None;
+        };
+        let model = entry
+            .model
+            .as_deref()
+            .and_then(|id| ModelRegistry::find_model(agent_type, id))
+            .map(|model| model.id);
+        let workspace = entry
+            .workspace
+            .map(|w| w.trim().to_string())
+            .filter(|w| !w.is_empty());
+        let prompt = entry.prompt.filter(|p| !p.trim().is_empty());
+        let name = entry
+            .name
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| match workspace.as_deref() {
+                Some(workspace) => format!("{} · {}", agent_type.display_name(), workspace),
+                None => agent_type.display_name().to_string(),
+            });
+        Some(Self {
+            name,
+            agent_type,
+            model,
+            workspace,
+            prompt,
+        })
+    }
+}
+
+/// Environment variables and extra CLI flags applied when spawning an agent
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentProfile {
+    pub env: Vec<(String, String)>,
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlAgentProfile {
+    pub env: Option<BTreeMap<String, String>>,
+    pub args: Option<Vec<String>>,
+}
+
+impl AgentProfile {
+    /// Profiles from the `[agents.<name>]` tables, keyed by agent. Tables
+    /// whose name isn't an agent are skipped with a warning.
+    fn from_toml_table(
+        agents: HashMap<String, TomlAgentProfile>,
+    ) -> HashMap<AgentType, AgentProfile> {
+        agents
+            .into_iter()
+            .filter_map(|(name, profile)| {
+                let Some(agent_type) = AgentType::from_name(&name) else {
+                    tracing::warn!(agent = %name, "Ignoring [agents] table for unknown agent");
+                    return None;
+                };
+                let profile = AgentProfile {
+                    env: profile.env.unwrap_or_default().into_iter().collect(),
+                    args: profile.args.unwrap_or_default(),
+                };
+                Some((agent_type, profile))
+            })
+            .collect()
+    }
+}
+
+/// Default endpoint for `[local_model]` (Ollama's OpenAI-compatible API)
+pub const DEFAULT_LOCAL_MODEL_URL: &str = "http://localhost:11434/v1";
+
+/// Default cap on tool calls a local model may chain in one turn
+pub const DEFAULT_LOCAL_MAX_TOOL_ROUNDS: usize = 20;
+
+/// Local OpenAI-compatible chat-completions endpoint (Ollama, LM Studio)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalModelConfig {
+    /// Base URL up to and including `/v1`
+    pub base_url: String,
+    /// Bearer token, for servers that require one
+    pub api_key: Option<String>,
+    /// Model names offered in the model picker (first = default)
+    pub models: Vec<String>,
+    /// Maximum tool-call rounds per turn before the turn is failed
+    pub max_tool_rounds: usize,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlLocalModelConfig {
+    pub base_url: Option<String>,
+    pub api_key: Option<String>,
+    pub models: Option<Vec<String>>,
+    pub max_tool_rounds: Option<usize>,
+}
+
+impl LocalModelConfig {
+    fn from_toml(entry: TomlLocalModelConfig) -> Self {
+        let base_url = entry
+            .base_url
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| DEFAULT_LOCAL_MODEL_URL.to_string());
+        Self {
+            base_url,
+            api_key: entry.api_key.filter(|key| !key.trim().is_empty()),
+            models: entry.models.unwrap_or_default(),
+            max_tool_rounds: entry
+                .max_tool_rounds
+                .filter(|rounds| *rounds > 0)
+                .unwrap_or(DEFAULT_LOCAL_MAX_TOOL_ROUNDS),
+        }
+    }
+}
+
+/// Default cap on a single voice recording
+pub const DEFAULT_VOICE_MAX_SECONDS: u64 = 120;
+
+/// Default model for `[voice]` with `backend = "api"`
+pub const DEFAULT_VOICE_API_MODEL: &str = "whisper-1";
+
+/// Where recorded audio gets transcribed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VoiceBackend {
+    /// Local whisper.cpp CLI (`whisper-cli`, formerly `main`)
+    WhisperCpp { binary: PathBuf, model: PathBuf },
+    /// OpenAI-compatible `/audio/transcriptions` endpoint
+    Api {
+        /// Base URL up to and including `/v1`
+        base_url: String,
+        api_key: Option<String>,
+        model: String,
+    },
+}
+
+/// Voice prompt input settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoiceConfig {
+    pub backend: VoiceBackend,
+    /// Spoken language hint (e.g. "en"); auto-detected when unset
+    pub language: Option<String>,
+    /// Recording stops on its own after this many seconds
+    pub max_seconds: u64,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlVoiceConfig {
+    /// "whisper_cpp" or "api"
+    pub backend: Option<String>,
+    pub whisper_binary: Option<PathBuf>,
+    pub whisper_model: Option<PathBuf>,
+    pub api_url: Option<String>,
+    pub api_key: Option<String>,
+    pub api_model: Option<String>,
+    pub language: Option<String>,
+    pub max_seconds: Option<u64>,
+}
+
+impl VoiceConfig {
+    fn from_toml(entry: TomlVoiceConfig) -> Result<Self, String> {
+        let non_empty = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+        let backend = match entry.backend.as_deref().map(str::trim) {
+            None | Some("whisper_cpp") | Some("whisper.cpp") => VoiceBackend::WhisperCpp {
+                binary: entry
+                    .whisper_binary
+                    .map(expand_home)
+                    .unwrap_or_else(|| PathBuf::from("whisper-cli")),
+                model: entry
+                    .whisper_model
+                    .map(expand_home)
+                    .ok_or("whisper_model is required for the whisper_cpp backend")?,
+            },
+            Some("api") => VoiceBackend::Api {
+                base_url: non_empty(entry.api_url)
+                    .map(|url| url.trim().trim_end_matches('/').to_string())
+                    .ok_or("api_url is required for the api backend")?,
+                api_key: non_empty(entry.api_key),
+                model: non_empty(entry.api_model)
+                    .unwrap_or_else(|| DEFAULT_VOICE_API_MODEL.to_string()),
+            },
+            Some(other) => {
+                return Err(format!(
+                    "unknown backend '{}' (expected \"whisper_cpp\" or \"api\")",
+                    other
+                ))
+            }
+        };
+        Ok(Self {
+            backend,
+            language: non_empty(entry.language),
+            max_seconds: entry
+                .max_seconds
+                .filter(|seconds| *seconds > 0)
+                .unwrap_or(DEFAULT_VOICE_MAX_SECONDS),
+        })
+    }
+}
+
+/// Default cap on how much of a response gets spoken
+pub const DEFAULT_READ_ALOUD_MAX_CHARS: usize = 2000;
+
+/// External TTS command fed the final assistant message of each turn
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadAloudConfig {
+    /// Program to run (`say`, `piper`, `espeak-ng`, ...); text arrives on stdin
+    pub command: String,
+    pub args: Vec<String>,
+    /// Longer responses are cut at a sentence boundary before this many chars
+    pub max_chars: usize,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlReadAloudConfig {
+    pub command: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub max_chars: Option<usize>,
+}
+
+impl ReadAloudConfig {
+    fn from_toml(entry: TomlReadAloudConfig) -> Result<Self, String> {
+        let command = entry
+            .command
+            .map(|command| command.trim().to_string())
+            .filter(|command| !command.is_empty())
+            .ok_or("command is required")?;
+        Ok(Self {
+            command,
+            args: entry.args.unwrap_or_default(),
+            max_chars: entry
+                .max_chars
+                .filter(|chars| *chars > 0)
+                .unwrap_or(DEFAULT_READ_ALOUD_MAX_CHARS),
+        })
+    }
+}
+
+/// Default time a hook command may run before it is killed
+pub const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 10;
+
+/// Prompt hook commands, in the order they run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HooksConfig {
+    /// Run before each prompt is sent; may rewrite or reject it
+    pub pre_submit: Vec<HookCommand>,
+    /// Run after each completed turn with the turn summary on stdin
+    pub post_turn: Vec<HookCommand>,
+}
+
+/// A single hook command; the JSON payload arrives on stdin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookCommand {
+    pub command: String,
+    pub args: Vec<String>,
+    pub timeout: Duration,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlHooksConfig {
+    pub pre_submit: Option<Vec<TomlHookCommand>>,
+    pub post_turn: Option<Vec<TomlHookCommand>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TomlHookCommand {
+    pub command: Option<String>,
+    pub args: Option<Vec<String>>,
+    pub timeout_secs: Option<u64>,
+}
+
+impl HookCommand {
+    fn from_toml(entry: TomlHookCommand) -> Result<Self, String> {
+        let command = entry
+            .command
+            .map(|command| command.trim().to_string())
+            .filter(|command| !command.is_empty())
+            .ok_or("command is required")?;
+        Ok(Self {
+            command,
+            args: entry.args.unwrap_or_default(),
+            timeout: Duration::from_secs(
+                entry
+                    .timeout_secs
+                    .filter(|secs| *secs > 0)
+                    .unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS),
+            ),
+        })
+    }
+}
+
+impl HooksConfig {
+    /// Invalid entries are skipped with a warning so one typo doesn't disable every hook
+    fn from_toml(entry: TomlHooksConfig) -> Self {
+        let parse = |stage: &str, entries: Option<Vec<TomlHookCommand>>| {
+            entries
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|entry| match HookCommand::from_toml(entry) {
+                    Ok(command) => Some(command),
+                    Err(err) => {
+                        tracing::warn!(error = %err, stage, "Ignoring invalid hook");
+                        None
+                    }
+                })
+                .collect()
+        };
+        Self {
+            pre_submit: parse("pre_submit", entry.pre_submit),
+            post_turn: parse("post_turn", entry.post_turn),
+        }
+    }
+}
+
+/// Expand a leading `~` so model paths can be written relative to home
+fn expand_home(path: PathBuf) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path,
+    }
+}
+
 /// TOML representation of default model
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct TomlDefaultModelConfig {
@@ -190,15 +901,15 @@ impl Default for Config {
                 "Grep".into(),
             ],
             // Claude Sonnet 3.5 pricing
-            claude_input_cost_per_million: 3.0,
-            claude_output_cost_per_million: 15.0,
             keybindings: default_keybindings(),
+            keybinding_issues: Vec::new(),
             tool_paths: ToolPaths::default(),
             theme_name: None,
             theme_path: None,
             queue: QueueConfig {
                 delivery: QueueDelivery::Separate,
                 mode: QueueMode::OneAtATime,
+                max_concurrent_agents: None,
             },
             steer: SteerConfig {
                 behavior: SteerBehavior::Hard,
@@ -210,6 +921,13 @@ impl Default for Config {
             },
             ui: UiConfig {
                 show_chat_scrollbar: false,
+                show_reasoning: true,
+                queue_max_lines: DEFAULT_QUEUE_MAX_LINES,
+                queue_collapsed: false,
+                response_language: None,
+                max_message_kb: DEFAULT_MAX_MESSAGE_KB,
+                chat_memory_mb: DEFAULT_CHAT_MEMORY_MB,
+                chat_timestamps: ChatTimestamps::Off,
             },
             web_status: WebStatusConfig {
                 initial_scan: true,
@@ -222,6 +940,23 @@ impl Default for Config {
                 archive_delete_branch: true,
                 archive_remote_prompt: true,
             },
+            templates: Vec::new(),
+            agent_profiles: HashMap::new(),
+            local_model: None,
+            voice: None,
+            read_aloud: None,
+            hooks: HooksConfig::default(),
+            review: ReviewConfig::default(),
+            bootstrap: BootstrapConfig::default(),
+            execution: HashMap::new(),
+            preview: PreviewConfig::default(),
+            usage_stats: UsageStatsConfig::default(),
+            persistence: PersistenceConfig::default(),
+            fetch: FetchConfig::default(),
+            notifications: NotificationsConfig::default(),
+            stall: StallConfig::default(),
+            security_scan: SecurityScanConfig::default(),
+            change_guard: ChangeGuardConfig::default(),
         }
     }
 }
@@ -260,8 +995,20 @@ pub struct TomlKeybindings {
     /// Raw events view keybindings
     pub raw_events: Option<HashMap<String, String>>,
 
+    /// Timeline view keybindings
+    pub timeline: Option<HashMap<String, String>>,
+
+    /// Diff view keybindings
+    pub diff_view: Option<HashMap<String, String>>,
+
     /// Queue editor keybindings
     pub queue: Option<HashMap<String, String>>,
+
+    /// Message selection keybindings
+    pub message_select: Option<HashMap<String, String>>,
+
+    /// Clipboard history keybindings
+    pub clipboard_history: Option<HashMap<String, String>>,
 }
 
 /// TOML representation of theme configuration
@@ -296,87 +1043,128 @@ pub struct TomlConfig {
     pub web_status: Option<TomlWebStatusConfig>,
     /// Workspace defaults
     pub workspaces: Option<TomlWorkspacesConfig>,
+    /// Splash screen session templates
+    pub templates: Option<Vec<TomlSessionTemplate>>,
+    /// Per-agent spawn profiles, keyed by agent name
+    pub agents: Option<HashMap<String, TomlAgentProfile>>,
+    /// Local OpenAI-compatible model endpoint
+    pub local_model: Option<TomlLocalModelConfig>,
+    /// Voice prompt input
+    pub voice: Option<TomlVoiceConfig>,
+    /// Read-aloud of assistant responses
+    pub read_aloud: Option<TomlReadAloudConfig>,
+    /// Prompt hooks
+    pub hooks: Option<TomlHooksConfig>,
+    /// Review of agent actions
+    pub review: Option<TomlReviewConfig>,
+    /// Workspace bootstrap commands
+    pub bootstrap: Option<TomlBootstrapConfig>,
+    /// Agent execution targets, keyed by repository name
+    pub execution: Option<HashMap<String, TomlExecutionTarget>>,
+    /// Dev server previews
+    pub preview: Option<TomlPreviewConfig>,
+    /// Opt-in usage statistics
+    pub usage_stats: Option<TomlUsageStatsConfig>,
+    /// Transcript persistence
+    pub persistence: Option<TomlPersistenceConfig>,
+    /// `/fetch` limits
+    pub fetch: Option<TomlFetchConfig>,
+    /// Desktop notifications
+    pub notifications: Option<TomlNotificationsConfig>,
+    /// Stalled-turn detection
+    pub stall: Option<TomlStallConfig>,
+    /// Security scans before PR creation
+    pub security_scan: Option<TomlSecurityScanConfig>,
+    /// Changed-file checks before PR creation
+    pub change_guard: Option<TomlChangeGuardConfig>,
 }
 
 impl TomlKeybindings {
-    /// Convert TOML keybindings to KeybindingConfig
-    fn to_keybinding_config(&self) -> KeybindingConfig {
-        let mut config = KeybindingConfig::new();
-
-        // Parse global bindings
-        for (action_name, key_str) in &self.global {
-            // Skip context sections (they're handled separately)
-            if matches!(
-                action_name.as_str(),
-                "chat"
-                    | "scrolling"
-                    | "sidebar"
-                    | "dialog"
-                    | "project_picker"
-                    | "model_selector"
-                    | "add_repository"
-                    | "base_dir"
-                    | "raw_events"
-                    | "queue"
-            ) {
-                continue;
-            }
-
-            if let (Ok(combo), Some(action)) =
-                (parse_key_notation(key_str), parse_action(action_name))
-            {
-                config.global.insert(combo, action);
-            }
-        }
+    /// Convert TOML keybindings to remaps, collecting entries that can't be used
+    fn to_remaps(&self) -> (Vec<KeyRemap>, Vec<KeymapIssue>) {
+        let mut remaps = Vec::new();
+        let mut issues = Vec::new();
+
+        // Parse global bindings, skipping context sections (handled separately)
+        let global: HashMap<String, String> = self
+            .global
+            .iter()
+            .filter(|(action_name, _)| {
+                !KeyContext::all_contexts()
+                    .iter()
+                    .any(|ctx| ctx.config_section() == Some(action_name.as_str()))
+            })
+            .map(|(action_name, key_str)| (action_name.clone(), key_str.clone()))
+            .collect();
+        parse_context_bindings(&mut remaps, &mut issues, None, &global);
 
         // Parse context-specific bindings
-        if let Some(chat) = &self.chat {
-            parse_context_bindings(&mut config, KeyContext::Chat, chat);
-        }
-        if let Some(scrolling) = &self.scrolling {
-            parse_context_bindings(&mut config, KeyContext::Scrolling, scrolling);
-        }
-        if let Some(sidebar) = &self.sidebar {
-            parse_context_bindings(&mut config, KeyContext::Sidebar, sidebar);
-        }
-        if let Some(dialog) = &self.dialog {
-            parse_context_bindings(&mut config, KeyContext::Dialog, dialog);
-        }
-        if let Some(picker) = &self.project_picker {
-            parse_context_bindings(&mut config, KeyContext::ProjectPicker, picker);
-        }
-        if let Some(model) = &self.model_selector {
-            parse_context_bindings(&mut config, KeyContext::ModelSelector, model);
-        }
-        if let Some(add_repo) = &self.add_repository {
-            parse_context_bindings(&mut config, KeyContext::AddRepository, add_repo);
-        }
-        if let Some(base_dir) = &self.base_dir {
-            parse_context_bindings(&mut config, KeyContext::BaseDir, base_dir);
-        }
-        if let Some(raw) = &self.raw_events {
-            parse_context_bindings(&mut config, KeyContext::RawEvents, raw);
-        }
-        if let Some(queue) = &self.queue {
-            parse_context_bindings(&mut config, KeyContext::QueueEditing, queue);
+        let sections = [
+            (KeyContext::Chat, &self.chat),
+            (KeyContext::Scrolling, &self.scrolling),
+            (KeyContext::Sidebar, &self.sidebar),
+            (KeyContext::Dialog, &self.dialog),
+            (KeyContext::ProjectPicker, &self.project_picker),
+            (KeyContext::ModelSelector, &self.model_selector),
+            (KeyContext::AddRepository, &self.add_repository),
+            (KeyContext::BaseDir, &self.base_dir),
+            (KeyContext::RawEvents, &self.raw_events),
+            (KeyContext::Timeline, &self.timeline),
+            (KeyContext::DiffView, &self.diff_view),
+            (KeyContext::QueueEditing, &self.queue),
+            (KeyContext::MessageSelect, &self.message_select),
+            (KeyContext::ClipboardHistory, &self.clipboard_history),
+        ];
+        for (context, bindings) in sections {
+            let Some(bindings) = bindings else {
+                continue;
+            };
+            parse_context_bindings(&mut remaps, &mut issues, Some(context), bindings);
         }
 
-        config
+        (remaps, issues)
     }
 }
 
-/// Parse context-specific keybindings
+/// Parse one keybinding section. Entries are sorted by action name so
+/// conflicts resolve the same way on every load; an empty key unbinds.
 fn parse_context_bindings(
-    config: &mut KeybindingConfig,
-    context: KeyContext,
+    remaps: &mut Vec<KeyRemap>,
+    issues: &mut Vec<KeymapIssue>,
+    context: Option<KeyContext>,
     bindings: &HashMap<String, String>,
 ) {
-    let context_map = config.context.entry(context).or_default();
-    for (action_name, key_str) in bindings {
-        if let (Ok(combo), Some(action)) = (parse_key_notation(key_str), parse_action(action_name))
-        {
-            context_map.insert(combo, action);
-        }
+    let mut entries: Vec<_> = bindings.iter().collect();
+    entries.sort();
+    for (action_name, key_str) in entries {
+        let Some(action) = parse_action(action_name) else {
+            issues.push(KeymapIssue::UnknownAction {
+                context,
+                name: action_name.clone(),
+            });
+            continue;
+        };
+        let key = if key_str.trim().is_empty() {
+            None
+        } else {
+            match parse_key_notation(key_str) {
+                Ok(combo) => Some(combo),
+                Err(error) => {
+                    issues.push(KeymapIssue::InvalidKey {
+                        context,
+                        action: action_name.clone(),
+                        key: key_str.clone(),
+                        error,
+                    });
+                    continue;
+                }
+            }
+        };
+        remaps.push(KeyRemap {
+            context,
+            action,
+            key,
+        });
     }
 }
 
@@ -385,21 +1173,45 @@ pub fn parse_action(name: &str) -> Option<Action> {
     match name {
         // Global
         "quit" => Some(Action::Quit),
+        "clear_or_quit" => Some(Action::ClearOrQuit),
         "toggle_sidebar" => Some(Action::ToggleSidebar),
         "new_project" => Some(Action::NewProject),
         "open_pr" => Some(Action::OpenPr),
         "fork_session" => Some(Action::ForkSession),
         "interrupt_agent" => Some(Action::InterruptAgent),
         "toggle_view_mode" => Some(Action::ToggleViewMode),
+        "toggle_timeline" => Some(Action::ToggleTimeline),
+        "toggle_diff_view" => Some(Action::ToggleDiffView),
         "show_model_selector" => Some(Action::ShowModelSelector),
         "show_theme_picker" => Some(Action::ShowThemePicker),
         "toggle_metrics" => Some(Action::ToggleMetrics),
+        "toggle_frame_diagnostics" => Some(Action::ToggleFrameDiagnostics),
+        "toggle_zen_mode" => Some(Action::ToggleZenMode),
+        "export_transcript_pdf" => Some(Action::ExportTranscriptPdf),
+        "export_transcript_markdown" => Some(Action::ExportTranscriptMarkdown),
+        "export_transcript_json" => Some(Action::ExportTranscriptJson),
         "dump_debug_state" => Some(Action::DumpDebugState),
+        "show_diagnostics" => Some(Action::ShowDiagnostics),
+        "restart_agent" => Some(Action::RestartAgent),
+        "nudge_agent" => Some(Action::NudgeAgent),
+        "apply_failure_remedy" => Some(Action::ApplyFailureRemedy),
+        "annotate_image" => Some(Action::AnnotateImage),
+        "toggle_voice_input" => Some(Action::ToggleVoiceInput),
+        "toggle_read_aloud" => Some(Action::ToggleReadAloud),
+        "toggle_response_language" => Some(Action::ToggleResponseLanguage),
+        "edit_past_prompt" => Some(Action::EditPastPrompt),
+        "rollback_to_past_prompt" => Some(Action::RollbackToPastPrompt),
         "suspend" => Some(Action::Suspend),
         "copy_selection" => Some(Action::CopySelection),
+        "select_messages" => Some(Action::SelectMessages),
+        "copy_selected_message" => Some(Action::CopySelectedMessage),
+        "show_clipboard_history" => Some(Action::ShowClipboardHistory),
+        "recopy_clipboard_entry" => Some(Action::RecopyClipboardEntry),
+        "paste_clipboard_entry" => Some(Action::PasteClipboardEntry),
 
         // Tab management
         "close_tab" => Some(Action::CloseTab),
+        "rename_tab" => Some(Action::RenameTab),
         "next_tab" => Some(Action::NextTab),
         "prev_tab" => Some(Action::PrevTab),
 
@@ -412,6 +1224,14 @@ pub fn parse_action(name: &str) -> Option<Action> {
         "scroll_to_bottom" => Some(Action::ScrollToBottom),
         "prev_user_message" => Some(Action::ScrollPrevUserMessage),
         "next_user_message" => Some(Action::ScrollNextUserMessage),
+        "jump_to_duplicate_answer" => Some(Action::JumpToDuplicateAnswer),
+        "scroll_left" => Some(Action::ScrollLeft(4)),
+        "scroll_right" => Some(Action::ScrollRight(4)),
+        "toggle_code_wrap" => Some(Action::ToggleCodeWrap),
+        "toggle_reasoning" => Some(Action::ToggleReasoning),
+        "toggle_tool_output" => Some(Action::ToggleToolOutput),
+        "toggle_expand_tools" => Some(Action::ToggleExpandTools),
+        "toggle_timestamps" => Some(Action::ToggleTimestamps),
 
         // Input editing
         "insert_newline" => Some(Action::InsertNewline),
@@ -421,6 +1241,9 @@ pub fn parse_action(name: &str) -> Option<Action> {
         "delete_word_forward" => Some(Action::DeleteWordForward),
         "delete_to_start" => Some(Action::DeleteToStart),
         "delete_to_end" => Some(Action::DeleteToEnd),
+        "undo" => Some(Action::Undo),
+        "redo" => Some(Action::Redo),
+        "remove_attachment" => Some(Action::RemoveAttachment),
         "move_cursor_left" => Some(Action::MoveCursorLeft),
         "move_cursor_right" => Some(Action::MoveCursorRight),
         "move_cursor_start" => Some(Action::MoveCursorStart),
@@ -439,6 +1262,8 @@ pub fn parse_action(name: &str) -> Option<Action> {
         "queue_move_down" => Some(Action::QueueMoveDown),
         "queue_edit" => Some(Action::QueueEdit),
         "queue_delete" => Some(Action::QueueDelete),
+        "toggle_queue_panel" => Some(Action::ToggleQueuePanel),
+        "expand_queue" => Some(Action::ExpandQueue),
         "edit_prompt_external" => Some(Action::EditPromptExternal),
 
         // Navigation
@@ -464,6 +1289,10 @@ pub fn parse_action(name: &str) -> Option<Action> {
         "raw_events_select_prev" => Some(Action::RawEventsSelectPrev),
         "raw_events_toggle_expand" => Some(Action::RawEventsToggleExpand),
         "raw_events_collapse" => Some(Action::RawEventsCollapse),
+        "raw_events_search" => Some(Action::RawEventsSearch),
+        "raw_events_search_next" => Some(Action::RawEventsSearchNext),
+        "raw_events_search_prev" => Some(Action::RawEventsSearchPrev),
+        "raw_events_toggle_follow" => Some(Action::RawEventsToggleFollow),
 
         // Dialog
         "confirm_yes" => Some(Action::ConfirmYes),
@@ -473,6 +1302,9 @@ pub fn parse_action(name: &str) -> Option<Action> {
 
         // Agent
         "select_agent" => Some(Action::SelectAgent),
+        "cycle_codex_sandbox" => Some(Action::CycleCodexSandbox),
+        "cycle_codex_approval" => Some(Action::CycleCodexApproval),
+        "toggle_edit_review" => Some(Action::ToggleEditReview),
 
         // Session import
         "open_session_import" | "import" => Some(Action::OpenSessionImport),
@@ -495,20 +1327,44 @@ pub fn parse_action(name: &str) -> Option<Action> {
 pub const COMMAND_NAMES: &[&str] = &[
     // Global
     "quit",
+    "clear_or_quit",
     "toggle_sidebar",
     "new_project",
     "open_pr",
     "fork_session",
     "interrupt_agent",
     "toggle_view_mode",
+    "toggle_timeline",
+    "toggle_diff_view",
     "show_model_selector",
     "show_theme_picker",
     "toggle_metrics",
+    "toggle_frame_diagnostics",
+    "toggle_zen_mode",
+    "export_transcript_pdf",
+    "export_transcript_markdown",
+    "export_transcript_json",
     "dump_debug_state",
+    "show_diagnostics",
+    "restart_agent",
+    "nudge_agent",
+    "apply_failure_remedy",
+    "annotate_image",
+    "toggle_voice_input",
+    "toggle_read_aloud",
+    "toggle_response_language",
+    "edit_past_prompt",
+    "rollback_to_past_prompt",
     "suspend",
     "copy_selection",
+    "select_messages",
+    "copy_selected_message",
+    "show_clipboard_history",
+    "recopy_clipboard_entry",
+    "paste_clipboard_entry",
     // Tab management
     "close_tab",
+    "rename_tab",
     "next_tab",
     "prev_tab",
     // Scrolling
@@ -520,6 +1376,14 @@ pub const COMMAND_NAMES: &[&str] = &[
     "scroll_to_bottom",
     "prev_user_message",
     "next_user_message",
+    "jump_to_duplicate_answer",
+    "scroll_left",
+    "scroll_right",
+    "toggle_code_wrap",
+    "toggle_reasoning",
+    "toggle_tool_output",
+    "toggle_expand_tools",
+    "toggle_timestamps",
     // Input editing
     "insert_newline",
     "backspace",
@@ -528,6 +1392,9 @@ pub const COMMAND_NAMES: &[&str] = &[
     "delete_word_forward",
     "delete_to_start",
     "delete_to_end",
+    "undo",
+    "redo",
+    "remove_attachment",
     "move_cursor_left",
     "move_cursor_right",
     "move_cursor_start",
@@ -546,6 +1413,8 @@ pub const COMMAND_NAMES: &[&str] = &[
     "queue_move_down",
     "queue_edit",
     "queue_delete",
+    "toggle_queue_panel",
+    "expand_queue",
     "edit_prompt_external",
     // Navigation
     "select_next",
@@ -568,6 +1437,10 @@ pub const COMMAND_NAMES: &[&str] = &[
     "raw_events_select_prev",
     "raw_events_toggle_expand",
     "raw_events_collapse",
+    "raw_events_search",
+    "raw_events_search_next",
+    "raw_events_search_prev",
+    "raw_events_toggle_follow",
     // Dialog
     "confirm_yes",
     "confirm_no",
@@ -575,6 +1448,9 @@ pub const COMMAND_NAMES: &[&str] = &[
     "toggle_details",
     // Agent
     "select_agent",
+    "cycle_codex_sandbox",
+    "cycle_codex_approval",
+    "toggle_edit_review",
     // Session import
     "open_session_import",
     "import",
@@ -619,10 +1495,14 @@ impl Config {
                         }
                     }
 
-                    // Merge user keybindings on top of defaults
+                    // Apply user keybindings on top of defaults
                     if let Some(keys) = toml_config.keys {
-                        let user_bindings = keys.to_keybinding_config();
-                        config.keybindings.merge(user_bindings);
+                        let (remaps, mut issues) = keys.to_remaps();
+                        issues.extend(config.keybindings.apply_remaps(&remaps));
+                        for issue in &issues {
+                            tracing::warn!("Keybinding issue in config.toml: {}", issue);
+                        }
+                        config.keybinding_issues = issues;
                     }
 
                     // Load tool paths if configured
@@ -644,6 +1524,10 @@ impl Config {
                         if let Some(mode) = queue.mode {
                             config.queue.mode = mode;
                         }
+                        if let Some(limit) = queue.max_concurrent_agents {
+                            // 0 disables the limit
+                            config.queue.max_concurrent_agents = (limit > 0).then_some(limit);
+                        }
                     }
 
                     // Load steering configuration
@@ -674,6 +1558,29 @@ impl Config {
                         if let Some(show_chat_scrollbar) = ui.show_chat_scrollbar {
                             config.ui.show_chat_scrollbar = show_chat_scrollbar;
                         }
+                        if let Some(show_reasoning) = ui.show_reasoning {
+                            config.ui.show_reasoning = show_reasoning;
+                        }
+                        if let Some(max_lines) = ui.queue_max_lines {
+                            // Room for the summary line plus at least one message
+                            config.ui.queue_max_lines = max_lines.max(2);
+                        }
+                        if let Some(collapsed) = ui.queue_collapsed {
+                            config.ui.queue_collapsed = collapsed;
+                        }
+                        config.ui.response_language = ui
+                            .response_language
+                            .map(|language| language.trim().to_string())
+                            .filter(|language| !language.is_empty());
+                        if let Some(max_message_kb) = ui.max_message_kb {
+                            config.ui.max_message_kb = max_message_kb;
+                        }
+                        if let Some(chat_memory_mb) = ui.chat_memory_mb {
+                            config.ui.chat_memory_mb = chat_memory_mb;
+                        }
+                        if let Some(chat_timestamps) = ui.chat_timestamps {
+                            config.ui.chat_timestamps = chat_timestamps;
+                        }
                     }
                     // Load web status configuration
                     if let Some(web_status) = toml_config.web_status {
@@ -705,6 +1612,191 @@ impl Config {
                             config.workspaces.archive_remote_prompt = remote_prompt;
                         }
                     }
+                    // Load splash screen session templates
+                    if let Some(templates) = toml_config.templates {
+                        config.templates = templates
+                            .into_iter()
+                            .filter_map(SessionTemplate::from_toml)
+                            .take(MAX_SESSION_TEMPLATES)
+                            .collect();
+                    }
+                    // Load per-agent spawn profiles
+                    if let Some(agents) = toml_config.agents {
+                        config.agent_profiles = AgentProfile::from_toml_table(agents);
+                    }
+                    // Load local model endpoint
+                    if let Some(local_model) = toml_config.local_model {
+                        config.local_model = Some(LocalModelConfig::from_toml(local_model));
+                    }
+                    // Load voice input settings
+                    if let Some(voice) = toml_config.voice {
+                        match VoiceConfig::from_toml(voice) {
+                            Ok(voice) => config.voice = Some(voice),
+                            Err(err) => {
+                                tracing::warn!(error = %err, "Ignoring invalid [voice] config");
+                            }
+                        }
+                    }
+                    // Load read-aloud hook
+                    if let Some(read_aloud) = toml_config.read_aloud {
+                        match ReadAloudConfig::from_toml(read_aloud) {
+                            Ok(read_aloud) => config.read_aloud = Some(read_aloud),
+                            Err(err) => {
+                                tracing::warn!(
+                                    error = %err,
+                                    "Ignoring invalid [read_aloud] config"
+                                );
+                            }
+                        }
+                    }
+                    // Load prompt hooks
+                    if let Some(hooks) = toml_config.hooks {
+                        config.hooks = HooksConfig::from_toml(hooks);
+                    }
+                    // Load review settings
+                    if let Some(review) = toml_config.review {
+                        if let Some(edits) = review.edits {
+                            config.review.edits = edits;
+                        }
+                    }
+                    // Load workspace bootstrap commands
+                    if let Some(bootstrap) = toml_config.bootstrap {
+                        if let Some(enabled) = bootstrap.enabled {
+                            config.bootstrap.enabled = enabled;
+                        }
+                        if let Some(commands) = bootstrap.commands {
+                            config.bootstrap.commands = commands;
+                        }
+                    }
+                    // Load per-repository execution targets
+                    for (repo, entry) in toml_config.execution.unwrap_or_default() {
+                        match entry.into_target() {
+                            Ok(target) => {
+                                config.execution.insert(repo, target);
+                            }
+                            Err(err) => {
+                                tracing::warn!(
+                                    error = %err,
+                                    repo = %repo,
+                                    "Ignoring invalid [execution] entry"
+                                );
+                            }
+                        }
+                    }
+                    // Load dev server preview settings
+                    if let Some(preview) = toml_config.preview {
+                        if let Some(enabled) = preview.enabled {
+                            config.preview.enabled = enabled;
+                        }
+                        if let Some(forward) = preview.forward {
+                            config.preview.forward = forward;
+                        }
+                        if let Some(bind) = preview.bind.filter(|bind| !bind.trim().is_empty()) {
+                            config.preview.bind = bind.trim().to_string();
+                        }
+                        config.preview.host = preview
+                            .host
+                            .map(|host| host.trim().to_string())
+                            .filter(|host| !host.is_empty());
+                    }
+
+                    if let Some(usage_stats) = toml_config.usage_stats {
+                        config.usage_stats.enabled = usage_stats.enabled.unwrap_or(false);
+                        config.usage_stats.endpoint = usage_stats
+                            .endpoint
+                            .map(|endpoint| endpoint.trim().to_string())
+                            .filter(|endpoint| !endpoint.is_empty())
+                            .filter(|endpoint| {
+                                let valid = endpoint.starts_with("https://")
+                                    || endpoint.starts_with("http://");
+                                if !valid {
+                                    tracing::warn!(
+                                        endpoint = %endpoint,
+                                        "Ignoring [usage_stats] endpoint: expected an http(s) URL"
+                                    );
+                                }
+                                valid
+                            });
+                    }
+
+                    if let Some(persistence) = toml_config.persistence {
+                        let defaults = PersistenceConfig::default();
+                        config.persistence = PersistenceConfig {
+                            messages: persistence.messages.unwrap_or(defaults.messages),
+                            raw_events: persistence.raw_events.unwrap_or(defaults.raw_events),
+                        };
+                    }
+
+                    if let Some(fetch) = toml_config.fetch {
+                        let defaults = FetchConfig::default();
+                        config.fetch = FetchConfig {
+                            allowed_hosts: fetch
+                                .allowed_hosts
+                                .unwrap_or_default()
+                                .into_iter()
+                                .map(|host| host.trim().trim_start_matches("*.").to_lowercase())
+                                .filter(|host| !host.is_empty())
+                                .collect(),
+                            max_download_kb: fetch
+                                .max_download_kb
+                                .filter(|kb| *kb > 0)
+                                .unwrap_or(defaults.max_download_kb),
+                            max_text_kb: fetch
+                                .max_text_kb
+                                .filter(|kb| *kb > 0)
+                                .unwrap_or(defaults.max_text_kb),
+                            timeout_secs: fetch
+                                .timeout_secs
+                                .filter(|secs| *secs > 0)
+                                .unwrap_or(defaults.timeout_secs),
+                        };
+                    }
+
+                    if let Some(notifications) = toml_config.notifications {
+                        let defaults = NotificationsConfig::default();
+                        config.notifications = NotificationsConfig {
+                            enabled: notifications.enabled.unwrap_or(defaults.enabled),
+                            only_when_unfocused: notifications
+                                .only_when_unfocused
+                                .unwrap_or(defaults.only_when_unfocused),
+                        };
+                    }
+
+                    if let Some(stall) = toml_config.stall {
+                        config.stall = StallConfig {
+                            after_secs: stall
+                                .after_secs
+                                .unwrap_or(StallConfig::default().after_secs),
+                        };
+                    }
+
+                    if let Some(security_scan) = toml_config.security_scan {
+                        let defaults = SecurityScanConfig::default();
+                        let block_at = match security_scan.block.as_deref().map(str::trim) {
+                            None => defaults.block_at,
+                            Some(label) if label.eq_ignore_ascii_case("none") => None,
+                            Some(label) => Severity::parse(label).or_else(|| {
+                                tracing::warn!(
+                                    block = label,
+                                    "Unknown security_scan.block severity; using the default"
+                                );
+                                defaults.block_at
+                            }),
+                        };
+                        config.security_scan = SecurityScanConfig {
+                            scanners: security_scan.scanners.unwrap_or(defaults.scanners),
+                            block_at,
+                            warn_at: security_scan.warn.unwrap_or(defaults.warn_at),
+                        };
+                    }
+
+                    if let Some(change_guard) = toml_config.change_guard {
+                        let defaults = ChangeGuardConfig::default();
+                        config.change_guard = ChangeGuardConfig {
+                            enabled: change_guard.enabled.unwrap_or(defaults.enabled),
+                            max_file_kb: change_guard.max_file_kb.unwrap_or(defaults.max_file_kb),
+                        };
+                    }
                 }
             }
         }
@@ -753,19 +1845,19 @@ impl Config {
         ModelRegistry::default_model(agent_type)
     }
 
+    /// Get the spawn profile (env + extra flags) for an agent
+    pub fn agent_profile(&self, agent_type: AgentType) -> AgentProfile {
+        self.agent_profiles
+            .get(&agent_type)
+            .cloned()
+            .unwrap_or_default()
+    }
+
     /// Update the default model for an agent in memory
     pub fn set_default_model(&mut self, agent_type: AgentType, model_id: String) {
         self.default_agent = agent_type;
         self.default_model = Some(model_id);
     }
-
-    /// Calculate cost for given token usage
-    pub fn calculate_cost(&self, input_tokens: i64, output_tokens: i64) -> f64 {
-        let input_cost = (input_tokens as f64 / 1_000_000.0) * self.claude_input_cost_per_million;
-        let output_cost =
-            (output_tokens as f64 / 1_000_000.0) * self.claude_output_cost_per_million;
-        input_cost + output_cost
-    }
 }
 
 /// Save a tool path to the config file
@@ -901,3 +1993,43 @@ pub fn save_default_model(agent_type: AgentType, model_id: &str) -> std::io::Res
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agent_profiles_skip_unknown_agents() {
+        let agents: HashMap<String, TomlAgentProfile> = toml::from_str(
+            r#"
+            [codex]
+            args = ["--full-auto"]
+
+            [gemeni]
+            args = ["--yolo"]
+            "#,
+        )
+        .unwrap();
+
+        let profiles = AgentProfile::from_toml_table(agents);
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[&AgentType::Codex].args, ["--full-auto"]);
+        assert!(!profiles.contains_key(&AgentType::Claude));
+    }
+
+    #[test]
+    fn test_template_with_unknown_agent_is_rejected() {
+        let template = |agent: &str| TomlSessionTemplate {
+            agent: Some(agent.to_string()),
+            ..Default::default()
+        };
+
+        assert!(SessionTemplate::from_toml(template("gemeni")).is_none());
+        let gemini = SessionTemplate::from_toml(template("gemini")).unwrap();
+        assert_eq!(gemini.agent_type, AgentType::Gemini);
+        let claude = SessionTemplate::from_toml(template("Claude")).unwrap();
+        assert_eq!(claude.agent_type, AgentType::Claude);
+        let codex = SessionTemplate::from_toml(template(" codex")).unwrap();
+        assert_eq!(codex.agent_type, AgentType::Codex);
+    }
+}
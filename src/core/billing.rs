@@ -0,0 +1,287 @@
+//! Cost allocation: per-turn spend tagged with a cost center, aggregated into
+//! monthly billing exports.
+//!
+//! Each priced turn is appended to the `cost_entries` ledger with the cost
+//! center in effect when it completed: the session's own tag, else its
+//! repository's. `conduit billing export --month YYYY-MM` sums the ledger per
+//! tag as CSV or JSON.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::data::{CostEntry, Repository};
+
+/// Tag used in exports for spend without a cost center
+pub const UNTAGGED: &str = "untagged";
+
+#[derive(Debug, Error)]
+pub enum BillingError {
+    #[error("invalid month \"{0}\", expected YYYY-MM")]
+    InvalidMonth(String),
+    #[error("database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("failed to encode export: {0}")]
+    Encode(#[from] serde_json::Error),
+}
+
+/// A calendar month in UTC, written `YYYY-MM`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BillingMonth {
+    year: i32,
+    month: u32,
+}
+
+impl BillingMonth {
+    /// First instant of the month
+    pub fn start(&self) -> DateTime<Utc> {
+        first_of_month(self.year, self.month)
+    }
+
+    /// First instant of the following month
+    pub fn end(&self) -> DateTime<Utc> {
+        if self.month == 12 {
+            first_of_month(self.year + 1, 1)
+        } else {
+            first_of_month(self.year, self.month + 1)
+        }
+    }
+}
+
+fn first_of_month(year: i32, month: u32) -> DateTime<Utc> {
+    // Only called with validated months, for which midnight on the 1st exists
+    Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0)
+        .single()
+        .unwrap_or_default()
+}
+
+impl FromStr for BillingMonth {
+    type Err = BillingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || BillingError::InvalidMonth(s.to_string());
+        let (year, month) = s.trim().split_once('-').ok_or_else(invalid)?;
+        if year.len() != 4 || month.len() != 2 {
+            return Err(invalid());
+        }
+        let year: i32 = year.parse().map_err(|_| invalid())?;
+        let month: u32 = month.parse().map_err(|_| invalid())?;
+        if !(1..=12).contains(&month) {
+            return Err(invalid());
+        }
+        Ok(Self { year, month })
+    }
+}
+
+impl fmt::Display for BillingMonth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.month)
+    }
+}
+
+/// Cost center for spend in a session: its own tag, else its repository's
+pub fn resolve_cost_center(
+    session_cost_center: Option<&str>,
+    repo: Option<&Repository>,
+) -> Option<String> {
+    session_cost_center
+        .or_else(|| repo.and_then(|repo| repo.cost_center.as_deref()))
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+}
+
+/// Spend for one cost center over the export period
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BillingRow {
+    pub cost_center: String,
+    /// Repositories that contributed spend, sorted
+    pub repositories: Vec<String>,
+    pub sessions: usize,
+    pub turns: usize,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost_usd: f64,
+}
+
+/// Monthly billing export
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BillingReport {
+    pub month: String,
+    pub total_cost_usd: f64,
+    pub cost_centers: Vec<BillingRow>,
+}
+
+impl BillingReport {
+    /// Sum `entries` per cost center, most expensive first
+    pub fn from_entries(month: BillingMonth, entries: &[CostEntry]) -> Self {
+        #[derive(Default)]
+        struct Totals {
+            repositories: BTreeSet<String>,
+            sessions: HashSet<Uuid>,
+            turns: usize,
+            input_tokens: i64,
+            output_tokens: i64,
+            cost_usd: f64,
+        }
+
+        let mut by_tag: BTreeMap<&str, Totals> = BTreeMap::new();
+        for entry in entries {
+            let totals = by_tag
+                .entry(entry.cost_center.as_deref().unwrap_or(UNTAGGED))
+                .or_default();
+            if let Some(repository) = &entry.repository {
+                totals.repositories.insert(repository.clone());
+            }
+            totals.sessions.insert(entry.session_id);
+            totals.turns += 1;
+            totals.input_tokens += entry.input_tokens;
+            totals.output_tokens += entry.output_tokens;
+            totals.cost_usd += entry.cost_usd;
+        }
+
+        let mut cost_centers: Vec<BillingRow> = by_tag
+            .into_iter()
+            .map(|(tag, totals)| BillingRow {
+                cost_center: tag.to_string(),
+                repositories: totals.repositories.into_iter().collect(),
+                sessions: totals.sessions.len(),
+                turns: totals.turns,
+                input_tokens: totals.input_tokens,
+                output_tokens: totals.output_tokens,
+                cost_usd: totals.cost_usd,
+            })
+            .collect();
+        // Stable sort keeps ties in tag order
+        cost_centers.sort_by(|a, b| b.cost_usd.total_cmp(&a.cost_usd));
+
+        Self {
+            month: month.to_string(),
+            total_cost_usd: cost_centers.iter().map(|row| row.cost_usd).sum(),
+            cost_centers,
+        }
+    }
+
+    /// One row per cost center with a header; repositories are `;`-separated
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "month,cost_center,repositories,sessions,turns,input_tokens,output_tokens,cost_usd\n",
+        );
+        for row in &self.cost_centers {
+            out.push_str(&format!(
+                "{},{},{},{},{},{},{},{:.6}\n",
+                self.month,
+                csv_field(&row.cost_center),
+                csv_field(&row.repositories.join(";")),
+                row.sessions,
+                row.turns,
+                row.input_tokens,
+                row.output_tokens,
+                row.cost_usd
+            ));
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> Result<String, BillingError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Quote a CSV field when it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentType;
+    use std::path::PathBuf;
+
+    fn entry(
+        session_id: Uuid,
+        cost_center: Option<&str>,
+        repository: &str,
+        cost: f64,
+    ) -> CostEntry {
+        CostEntry {
+            session_id,
+            repository: Some(repository.to_string()),
+            cost_center: cost_center.map(str::to_string),
+            agent_type: AgentType::Claude,
+            model: None,
+            input_tokens: 100,
+            output_tokens: 10,
+            cost_usd: cost,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_month_parsing_and_bounds() {
+        let month: BillingMonth = "2026-12".parse().unwrap();
+        assert_eq!(month.to_string(), "2026-12");
+        assert_eq!(month.start().to_rfc3339(), "2026-12-01T00:00:00+00:00");
+        assert_eq!(month.end().to_rfc3339(), "2027-01-01T00:00:00+00:00");
+
+        for invalid in ["2026-13", "2026-1", "26-01", "2026/01", ""] {
+            assert!(invalid.parse::<BillingMonth>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_session_tag_overrides_repository_tag() {
+        let mut repo = Repository::from_local_path("api", PathBuf::from("/tmp/api"));
+        repo.cost_center = Some("backend".to_string());
+
+        assert_eq!(
+            resolve_cost_center(None, Some(&repo)).as_deref(),
+            Some("backend")
+        );
+        assert_eq!(
+            resolve_cost_center(Some("research"), Some(&repo)).as_deref(),
+            Some("research")
+        );
+        assert_eq!(resolve_cost_center(Some("  "), None), None);
+    }
+
+    #[test]
+    fn test_report_aggregates_per_cost_center() {
+        let month: BillingMonth = "2026-09".parse().unwrap();
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        let entries = vec![
+            entry(first, Some("backend"), "api", 0.5),
+            entry(first, Some("backend"), "api", 0.25),
+            entry(second, Some("backend"), "worker, v2", 1.0),
+            entry(second, None, "web", 0.125),
+        ];
+
+        let report = BillingReport::from_entries(month, &entries);
+        assert_eq!(report.total_cost_usd, 1.875);
+        assert_eq!(report.cost_centers.len(), 2);
+        let backend = &report.cost_centers[0];
+        assert_eq!(backend.cost_center, "backend");
+        assert_eq!(backend.repositories, ["api", "worker, v2"]);
+        assert_eq!((backend.sessions, backend.turns), (2, 3));
+        assert_eq!(backend.input_tokens, 300);
+        assert_eq!(report.cost_centers[1].cost_center, UNTAGGED);
+
+        assert_eq!(
+            report.to_csv(),
+            "month,cost_center,repositories,sessions,turns,input_tokens,output_tokens,cost_usd\n\
+             2026-09,backend,\"api;worker, v2\",2,3,300,30,1.750000\n\
+             2026-09,untagged,web,1,1,100,10,0.125000\n"
+        );
+    }
+}
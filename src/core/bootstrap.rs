@@ -0,0 +1,263 @@
+//! Workspace bootstrap: per-repository setup commands (e.g. `npm ci`) run in a
+//! freshly created worktree before its first turn.
+//!
+//! Commands come from `[bootstrap.commands]` in config.toml, keyed by
+//! repository name, and run one after another through the platform shell, on
+//! the repository's execution target so containers build for their own
+//! platform. Output is streamed line by line; the first failing command stops
+//! the run.
+
+use std::path::Path;
+use std::process::Stdio;
+
+use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::agent::ExecutionTarget;
+use crate::config::Config;
+use crate::data::Repository;
+
+#[derive(Debug, Error)]
+pub enum BootstrapError {
+    #[error("failed to start `{command}`: {source}")]
+    Spawn {
+        command: String,
+        source: std::io::Error,
+    },
+    #[error("`{command}` {}", exit_description(.exit_code))]
+    Failed {
+        command: String,
+        exit_code: Option<i32>,
+    },
+    #[error("bootstrap skipped")]
+    Cancelled,
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+fn exit_description(exit_code: &Option<i32>) -> String {
+    match exit_code {
+        Some(code) => format!("exited with code {}", code),
+        None => "was terminated by a signal".to_string(),
+    }
+}
+
+/// Progress of a bootstrap run, reported as it happens
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BootstrapProgress {
+    /// Command `index` started
+    Started { index: usize, command: String },
+    /// A line of stdout or stderr from command `index`
+    Output { index: usize, line: String },
+    /// Command `index` exited
+    Finished {
+        index: usize,
+        exit_code: Option<i32>,
+    },
+}
+
+/// Bootstrap commands to run for a new workspace of `repo`; empty when
+/// bootstrapping is disabled or none are configured.
+pub fn bootstrap_commands(config: &Config, repo: &Repository) -> Vec<String> {
+    if !config.bootstrap.enabled {
+        return Vec::new();
+    }
+    config
+        .bootstrap
+        .commands
+        .get(&repo.name)
+        .map(|commands| {
+            commands
+                .iter()
+                .map(|command| command.trim())
+                .filter(|command| !command.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Run `commands` in `dir` on `target`, stopping at the first failure or when
+/// `cancel` fires (which kills the running command).
+pub async fn run_bootstrap(
+    commands: &[String],
+    dir: &Path,
+    target: &ExecutionTarget,
+    cancel: &CancellationToken,
+    mut on_progress: impl FnMut(BootstrapProgress),
+) -> Result<(), BootstrapError> {
+    for (index, command) in commands.iter().enumerate() {
+        on_progress(BootstrapProgress::Started {
+            index,
+            command: command.clone(),
+        });
+        let exit_code = run_command(command, dir, target, cancel, |line| {
+            on_progress(BootstrapProgress::Output { index, line })
+        })
+        .await?;
+        on_progress(BootstrapProgress::Finished { index, exit_code });
+        if exit_code != Some(0) {
+            return Err(BootstrapError::Failed {
+                command: command.clone(),
+                exit_code,
+            });
+        }
+    }
+    Ok(())
+}
+
+async fn run_command(
+    command: &str,
+    dir: &Path,
+    target: &ExecutionTarget,
+    cancel: &CancellationToken,
+    mut on_line: impl FnMut(String),
+) -> Result<Option<i32>, BootstrapError> {
+    // Containers are Linux whatever the host is
+    let (shell, flag) = if cfg!(windows) && *target == ExecutionTarget::Host {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut cmd = Command::new(shell);
+    cmd.arg(flag)
+        .arg(command)
+        .current_dir(dir)
+        .env("CONDUIT_BOOTSTRAP", "1");
+    let spawn_error = |source| BootstrapError::Spawn {
+        command: command.to_string(),
+        source,
+    };
+    let mut child = target
+        .wrap(cmd)
+        .map_err(spawn_error)?
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(spawn_error)?;
+
+    let (line_tx, mut line_rx) = mpsc::unbounded_channel();
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(stdout, line_tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(stderr, line_tx);
+    }
+
+    // Both readers drop their sender at EOF, which ends this loop
+    loop {
+        tokio::select! {
+            line = line_rx.recv() => match line {
+                Some(line) => on_line(line),
+                None => break,
+            },
+            _ = cancel.cancelled() => {
+                child.kill().await?;
+                return Err(BootstrapError::Cancelled);
+            }
+        }
+    }
+
+    tokio::select! {
+        status = child.wait() => Ok(status?.code()),
+        _ = cancel.cancelled() => {
+            child.kill().await?;
+            Err(BootstrapError::Cancelled)
+        }
+    }
+}
+
+fn forward_lines(
+    reader: impl AsyncRead + Unpin + Send + 'static,
+    tx: mpsc::UnboundedSender<String>,
+) {
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        loop {
+            match lines.next_line().await {
+                Ok(Some(line)) => {
+                    if tx.send(line).is_err() {
+                        break;
+                    }
+                }
+                Ok(None) => break,
+                Err(err) => {
+                    tracing::debug!(error = %err, "Failed to read bootstrap output");
+                    break;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_runs_commands_in_order_and_stops_at_failure() {
+        let dir = tempdir().unwrap();
+        let commands = vec![
+            "echo one > first.txt && echo built".to_string(),
+            "echo oops >&2; exit 3".to_string(),
+            "touch never.txt".to_string(),
+        ];
+        let mut progress = Vec::new();
+        let err = run_bootstrap(
+            &commands,
+            dir.path(),
+            &ExecutionTarget::Host,
+            &CancellationToken::new(),
+            |p| progress.push(p),
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(
+            err,
+            BootstrapError::Failed {
+                exit_code: Some(3),
+                ..
+            }
+        ));
+        assert!(dir.path().join("first.txt").exists());
+        assert!(!dir.path().join("never.txt").exists());
+        assert!(progress.contains(&BootstrapProgress::Output {
+            index: 0,
+            line: "built".to_string()
+        }));
+        assert!(progress.contains(&BootstrapProgress::Output {
+            index: 1,
+            line: "oops".to_string()
+        }));
+        assert_eq!(
+            progress.last(),
+            Some(&BootstrapProgress::Finished {
+                index: 1,
+                exit_code: Some(3)
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel_kills_running_command() {
+        let dir = tempdir().unwrap();
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let result = run_bootstrap(
+            &["sleep 30".to_string()],
+            dir.path(),
+            &ExecutionTarget::Host,
+            &cancel,
+            |_| {},
+        )
+        .await;
+        assert!(matches!(result, Err(BootstrapError::Cancelled)));
+    }
+}
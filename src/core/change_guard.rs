@@ -0,0 +1,501 @@
+//! Checks on the files a workspace branch adds or changes, run before a PR is
+//! created: files over a size threshold, binary blobs, and license headers
+//! that don't fit the repository's own license (e.g. GPL code copied into an
+//! MIT project). Flags don't fail anything by themselves; the PR flow asks
+//! the user to acknowledge them first.
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::process::Command;
+
+use thiserror::Error;
+
+use crate::config::ChangeGuardConfig;
+
+/// Bytes read from each file to detect binaries and license headers
+const HEADER_BYTES: usize = 8 * 1024;
+
+/// Lines of a file searched for a license header
+const HEADER_LINES: usize = 40;
+
+/// Files checked for the repository's license, in order
+const LICENSE_FILES: &[&str] = &[
+    "LICENSE",
+    "LICENSE.md",
+    "LICENSE.txt",
+    "LICENSE-MIT",
+    "LICENSE-APACHE",
+    "COPYING",
+];
+
+#[derive(Debug, Error)]
+pub enum ChangeGuardError {
+    #[error("failed to run `git {command}`: {source}")]
+    Spawn {
+        command: String,
+        source: std::io::Error,
+    },
+    #[error("`git {command}` failed: {stderr}")]
+    Git { command: String, stderr: String },
+}
+
+/// How strongly a license binds code that includes it, weakest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LicenseFamily {
+    /// MIT, Apache-2.0, BSD, ISC and the like
+    Permissive,
+    /// File-level copyleft: LGPL, MPL, EPL
+    WeakCopyleft,
+    /// GPL
+    StrongCopyleft,
+    /// AGPL
+    NetworkCopyleft,
+    /// "All rights reserved" without a license grant
+    Proprietary,
+}
+
+impl LicenseFamily {
+    /// Whether code under `self` can be added to a project under `project`
+    pub fn fits(self, project: LicenseFamily) -> bool {
+        match (self, project) {
+            (Self::Proprietary, _) => false,
+            (_, Self::Proprietary) => self <= Self::WeakCopyleft,
+            _ => self <= project,
+        }
+    }
+}
+
+/// License named by a header or license file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct License {
+    pub name: String,
+    pub family: LicenseFamily,
+}
+
+impl License {
+    /// Classify license text: an `SPDX-License-Identifier` line if there is
+    /// one, else well-known license wording. "All rights reserved" means
+    /// proprietary only when the text names no license.
+    pub fn detect(text: &str) -> Option<Self> {
+        if let Some(spdx) = text.lines().find_map(|line| {
+            line.split_once("SPDX-License-Identifier:")
+                .map(|(_, id)| id.trim().trim_end_matches("*/").trim())
+        }) {
+            return Self::from_spdx(spdx);
+        }
+
+        let lower = text.to_lowercase();
+        let phrases: &[(&str, &str, LicenseFamily)] = &[
+            (
+                "gnu affero general public license",
+                "AGPL",
+                LicenseFamily::NetworkCopyleft,
+            ),
+            (
+                "gnu lesser general public license",
+                "LGPL",
+                LicenseFamily::WeakCopyleft,
+            ),
+            (
+                "gnu library general public license",
+                "LGPL",
+                LicenseFamily::WeakCopyleft,
+            ),
+            (
+                "gnu general public license",
+                "GPL",
+                LicenseFamily::StrongCopyleft,
+            ),
+            (
+                "mozilla public license",
+                "MPL-2.0",
+                LicenseFamily::WeakCopyleft,
+            ),
+            ("eclipse public license", "EPL", LicenseFamily::WeakCopyleft),
+            ("apache license", "Apache-2.0", LicenseFamily::Permissive),
+            (
+                "permission is hereby granted, free of charge",
+                "MIT",
+                LicenseFamily::Permissive,
+            ),
+            (
+                "redistribution and use in source and binary forms",
+                "BSD",
+                LicenseFamily::Permissive,
+            ),
+            (
+                "permission to use, copy, modify, and/or distribute",
+                "ISC",
+                LicenseFamily::Permissive,
+            ),
+            // License names in one-line headers, e.g. "Licensed under the MIT
+            // License." after "All rights reserved."
+            ("mit license", "MIT", LicenseFamily::Permissive),
+            ("bsd-style license", "BSD", LicenseFamily::Permissive),
+            ("bsd license", "BSD", LicenseFamily::Permissive),
+            // Proprietary only once no license name matched above
+            (
+                "proprietary and confidential",
+                "proprietary",
+                LicenseFamily::Proprietary,
+            ),
+            (
+                "all rights reserved",
+                "proprietary",
+                LicenseFamily::Proprietary,
+            ),
+        ];
+        phrases
+            .iter()
+            .find(|(phrase, _, _)| lower.contains(phrase))
+            .map(|(_, name, family)| Self {
+                name: name.to_string(),
+                family: *family,
+            })
+    }
+
+    /// Classify an SPDX expression; with `OR` the most permissive choice wins
+    pub fn from_spdx(expression: &str) -> Option<Self> {
+        let family = expression
+            .split(" OR ")
+            .filter_map(|id| {
+                let id = id.trim().trim_matches(|c| c == '(' || c == ')');
+                let upper = id.to_ascii_uppercase();
+                if upper.starts_with("AGPL") {
+                    Some(LicenseFamily::NetworkCopyleft)
+                } else if upper.starts_with("LGPL")
+                    || upper.starts_with("MPL")
+                    || upper.starts_with("EPL")
+                    || upper.starts_with("CDDL")
+                {
+                    Some(LicenseFamily::WeakCopyleft)
+                } else if upper.starts_with("GPL") {
+                    Some(LicenseFamily::StrongCopyleft)
+                } else if [
+                    "MIT",
+                    "APACHE",
+                    "BSD",
+                    "0BSD",
+                    "ISC",
+                    "UNLICENSE",
+                    "ZLIB",
+                    "CC0",
+                ]
+                .iter()
+                .any(|prefix| upper.starts_with(prefix))
+                {
+                    Some(LicenseFamily::Permissive)
+                } else {
+                    None
+                }
+            })
+            .min()?;
+        Some(Self {
+            name: expression.trim().to_string(),
+            family,
+        })
+    }
+}
+
+/// Why a changed file was flagged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FlagKind {
+    Large { bytes: u64 },
+    Binary,
+    License { found: String, project: String },
+}
+
+/// A changed file the user should look at before the PR is created
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeFlag {
+    /// Path relative to the workspace
+    pub path: String,
+    pub kind: FlagKind,
+}
+
+impl ChangeFlag {
+    /// Identifies the flag across checks, for acknowledgements
+    pub fn key(&self) -> String {
+        let kind = match &self.kind {
+            FlagKind::Large { .. } => "large",
+            FlagKind::Binary => "binary",
+            FlagKind::License { .. } => "license",
+        };
+        format!("{kind}:{}", self.path)
+    }
+
+    pub fn describe(&self) -> String {
+        match &self.kind {
+            FlagKind::Large { bytes } => {
+                format!("{}: large file ({} KB)", self.path, bytes / 1024)
+            }
+            FlagKind::Binary => format!("{}: binary file", self.path),
+            FlagKind::License { found, project } => {
+                format!(
+                    "{}: {found} license header (project license: {project})",
+                    self.path
+                )
+            }
+        }
+    }
+}
+
+/// Check the files changed in the workspace at `dir` since it branched off
+/// `target_branch`, committed or not
+pub fn check(
+    dir: &Path,
+    target_branch: &str,
+    config: &ChangeGuardConfig,
+) -> Result<Vec<ChangeFlag>, ChangeGuardError> {
+    let project = project_license(dir).unwrap_or(License {
+        name: "unlicensed".to_string(),
+        family: LicenseFamily::Permissive,
+    });
+    let max_bytes = config.max_file_kb.saturating_mul(1024);
+    let mut flags = Vec::new();
+    for path in changed_files(dir, target_branch)? {
+        let full_path = dir.join(&path);
+        let metadata = match full_path.metadata() {
+            Ok(metadata) if metadata.is_file() => metadata,
+            Ok(_) => continue,
+            Err(err) => {
+                tracing::warn!(path = %full_path.display(), error = %err, "Failed to read changed file");
+                continue;
+            }
+        };
+        if max_bytes > 0 && metadata.len() > max_bytes {
+            flags.push(ChangeFlag {
+                path: path.clone(),
+                kind: FlagKind::Large {
+                    bytes: metadata.len(),
+                },
+            });
+        }
+        let head = match read_head(&full_path) {
+            Ok(head) => head,
+            Err(err) => {
+                tracing::warn!(path = %full_path.display(), error = %err, "Failed to read changed file");
+                continue;
+            }
+        };
+        if is_binary(&head) {
+            flags.push(ChangeFlag {
+                path,
+                kind: FlagKind::Binary,
+            });
+            continue;
+        }
+        if let Some(license) = header_license(&String::from_utf8_lossy(&head)) {
+            if !license.family.fits(project.family) {
+                flags.push(ChangeFlag {
+                    path,
+                    kind: FlagKind::License {
+                        found: license.name,
+                        project: project.name.clone(),
+                    },
+                });
+            }
+        }
+    }
+    Ok(flags)
+}
+
+/// Files added or modified since the merge base with `target_branch`, plus
+/// untracked ones. Falls back to changes since HEAD when there's no merge
+/// base (e.g. the target branch was never fetched).
+fn changed_files(dir: &Path, target_branch: &str) -> Result<BTreeSet<String>, ChangeGuardError> {
+    let base = match git(dir, &["merge-base", target_branch, "HEAD"]) {
+        Ok(base) => base.trim().to_string(),
+        Err(err) => {
+            tracing::info!(error = %err, "No merge base for the change guard; checking uncommitted changes");
+            "HEAD".to_string()
+        }
+    };
+    let tracked = git(
+        dir,
+        &["diff", "--name-only", "--diff-filter=AMR", "-z", &base],
+    )?;
+    let untracked = git(dir, &["ls-files", "--others", "--exclude-standard", "-z"])?;
+    Ok(tracked
+        .split('\0')
+        .chain(untracked.split('\0'))
+        .filter(|path| !path.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, ChangeGuardError> {
+    let command = args.join(" ");
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|source| ChangeGuardError::Spawn {
+            command: command.clone(),
+            source,
+        })?;
+    if !output.status.success() {
+        return Err(ChangeGuardError::Git {
+            command,
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn read_head(path: &Path) -> std::io::Result<Vec<u8>> {
+    let mut head = Vec::with_capacity(HEADER_BYTES);
+    File::open(path)?
+        .take(HEADER_BYTES as u64)
+        .read_to_end(&mut head)?;
+    Ok(head)
+}
+
+/// Same heuristic as git: a NUL byte near the start means binary
+fn is_binary(head: &[u8]) -> bool {
+    head.contains(&0)
+}
+
+/// License named in the comment header at the top of a source file
+fn header_license(text: &str) -> Option<License> {
+    let header: Vec<&str> = text.lines().take(HEADER_LINES).collect();
+    License::detect(&header.join("\n"))
+}
+
+/// The repository's license, from its license file or package manifest
+fn project_license(dir: &Path) -> Option<License> {
+    for name in LICENSE_FILES {
+        let Ok(head) = read_head(&dir.join(name)) else {
+            continue;
+        };
+        if let Some(license) = License::detect(&String::from_utf8_lossy(&head)) {
+            return Some(license);
+        }
+    }
+    manifest_license(dir)
+}
+
+/// `license` field of Cargo.toml or package.json
+fn manifest_license(dir: &Path) -> Option<License> {
+    if let Ok(manifest) = std::fs::read_to_string(dir.join("Cargo.toml")) {
+        let expression = manifest.lines().find_map(|line| {
+            let value = line.trim().strip_prefix("license")?.trim_start();
+            Some(
+                value
+                    .strip_prefix('=')?
+                    .trim()
+                    .trim_matches('"')
+                    .to_string(),
+            )
+        });
+        if let Some(license) = expression.and_then(|expr| License::from_spdx(&expr)) {
+            return Some(license);
+        }
+    }
+    let package = std::fs::read_to_string(dir.join("package.json")).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&package).ok()?;
+    License::from_spdx(package.get("license")?.as_str()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_license_detection_and_fit() {
+        let gpl =
+            License::detect("// SPDX-License-Identifier: GPL-3.0-or-later\nfn main() {}").unwrap();
+        assert_eq!(gpl.family, LicenseFamily::StrongCopyleft);
+        let dual = License::from_spdx("MIT OR Apache-2.0").unwrap();
+        assert_eq!(dual.family, LicenseFamily::Permissive);
+        let agpl = License::detect(
+            "/* This program is free software: you can redistribute it under the terms of\n * the GNU Affero General Public License */",
+        )
+        .unwrap();
+        assert_eq!(agpl.name, "AGPL");
+        assert_eq!(License::detect("fn main() {}"), None);
+
+        let microsoft = License::detect(
+            "// Copyright (c) Microsoft Corporation. All rights reserved.\n// Licensed under the MIT License.",
+        )
+        .unwrap();
+        assert_eq!(microsoft.name, "MIT");
+        assert_eq!(microsoft.family, LicenseFamily::Permissive);
+        let chromium = License::detect(
+            "// Copyright 2012 The Chromium Authors. All rights reserved.\n// Use of this source code is governed by a BSD-style license that can be\n// found in the LICENSE file.",
+        )
+        .unwrap();
+        assert_eq!(chromium.name, "BSD");
+        let apache = License::detect(
+            "# Copyright 2020 Example Inc. All rights reserved.\n# Licensed under the Apache License, Version 2.0",
+        )
+        .unwrap();
+        assert_eq!(apache.family, LicenseFamily::Permissive);
+        let proprietary =
+            License::detect("// Copyright 2024 Example Inc. All rights reserved.").unwrap();
+        assert_eq!(proprietary.family, LicenseFamily::Proprietary);
+
+        assert!(!gpl.family.fits(dual.family));
+        assert!(dual.family.fits(gpl.family));
+        assert!(!agpl.family.fits(LicenseFamily::StrongCopyleft));
+        assert!(LicenseFamily::WeakCopyleft.fits(LicenseFamily::Proprietary));
+        assert!(!LicenseFamily::Proprietary.fits(LicenseFamily::Proprietary));
+    }
+
+    #[test]
+    fn test_check_flags_large_binary_and_license_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let run = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {args:?} failed");
+        };
+        run(&["init", "-q", "-b", "main"]);
+        run(&["config", "user.email", "test@example.com"]);
+        run(&["config", "user.name", "Test"]);
+        std::fs::write(
+            dir.path().join("LICENSE"),
+            "MIT License\n\nPermission is hereby granted, free of charge, to any person",
+        )
+        .unwrap();
+        run(&["add", "."]);
+        run(&["commit", "-q", "-m", "init"]);
+        run(&["checkout", "-q", "-b", "feature"]);
+
+        std::fs::write(dir.path().join("big.txt"), "x".repeat(3 * 1024)).unwrap();
+        std::fs::write(dir.path().join("blob.bin"), [0u8, 1, 2, 3]).unwrap();
+        std::fs::write(
+            dir.path().join("copied.c"),
+            "/* SPDX-License-Identifier: GPL-2.0-only */\nint main() {}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("ok.rs"),
+            "// SPDX-License-Identifier: MIT\n",
+        )
+        .unwrap();
+        run(&["add", "big.txt", "copied.c"]);
+        run(&["commit", "-q", "-m", "agent work"]);
+
+        let config = ChangeGuardConfig {
+            enabled: true,
+            max_file_kb: 2,
+        };
+        let flags = check(dir.path(), "main", &config).unwrap();
+        let described: Vec<String> = flags.iter().map(ChangeFlag::describe).collect();
+        assert_eq!(
+            described,
+            [
+                "big.txt: large file (3 KB)",
+                "blob.bin: binary file",
+                "copied.c: GPL-2.0-only license header (project license: MIT)",
+            ]
+        );
+    }
+}
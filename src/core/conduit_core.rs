@@ -2,21 +2,33 @@
 
 use std::sync::Arc;
 
+use uuid::Uuid;
+
 use crate::agent::{
-    ClaudeCodeRunner, CodexCliRunner, GeminiCliRunner, ModelRegistry, OpencodeRunner,
+    AgentRunner, AgentType, ClaudeCodeRunner, CodexCliRunner, ExecutionTarget, GeminiCliRunner,
+    LocalModelRunner, ModelRegistry, OpencodeRunner, ReplayRunner, TokenUsage,
 };
 use crate::config::Config;
+use crate::core::billing::resolve_cost_center;
+use crate::core::hooks::{HookRegistry, PromptHook};
+use crate::core::previews::PreviewRegistry;
+use crate::core::repo_settings::resolve_repo_execution_target;
+use crate::core::usage_stats::UsageStats;
+use crate::core::write_behind::{WriteBehindQueue, WriteBehindStores};
 use crate::data::{
-    AppStateStore, Database, ForkSeedStore, RepositoryStore, SessionTabStore, WorkspaceStore,
+    AppStateStore, AttachmentStore, ChatArchiveStore, CostEntry, CostLedgerStore, Database,
+    ForkSeedStore, Repository, RepositoryStore, SessionReportStore, SessionShareStore,
+    SessionTabStore, TranscriptStore, UsageStatsStore, WorkspaceMemoryStore, WorkspaceStore,
+    ATTACHMENT_GRACE_PERIOD,
 };
 use crate::git::WorkspaceRepoManager;
-use crate::util::{Tool, ToolAvailability};
+use crate::util::{startup_profile, Tool, ToolAvailability};
 
 /// Core infrastructure for Conduit, shared between TUI and web interfaces.
 ///
 /// This struct owns all the foundational components:
 /// - Database connection and DAO stores for persistent data
-/// - Agent runners for Claude, Codex, Gemini, and OpenCode
+/// - Agent runners for Claude, Codex, Gemini, OpenCode, and local models
 /// - Configuration and tool availability
 /// - Worktree manager for git workspace operations
 pub struct ConduitCore {
@@ -26,6 +38,8 @@ pub struct ConduitCore {
     tools: ToolAvailability,
     /// Database connection (owned to keep connection alive)
     _database: Option<Database>,
+    /// Why the database couldn't be opened, if it couldn't
+    database_error: Option<String>,
     /// Repository DAO
     repo_store: Option<RepositoryStore>,
     /// Workspace DAO
@@ -36,6 +50,18 @@ pub struct ConduitCore {
     session_tab_store: Option<SessionTabStore>,
     /// Fork seed DAO (for persisting fork metadata)
     fork_seed_store: Option<ForkSeedStore>,
+    /// Session share DAO (for read-only transcript links)
+    session_share_store: Option<SessionShareStore>,
+    /// Session report DAO (final reports of closed sessions)
+    session_report_store: Option<SessionReportStore>,
+    /// Workspace memory DAO (shared context synced into agent memory files)
+    workspace_memory_store: Option<WorkspaceMemoryStore>,
+    /// Attachment DAO (pasted and uploaded images under `attachments/`)
+    attachment_store: Option<AttachmentStore>,
+    /// Billing ledger DAO (per-turn spend tagged with a cost center)
+    cost_ledger_store: Option<CostLedgerStore>,
+    /// Chat archive DAO (history moved out of memory by capped chat views)
+    chat_archive_store: Option<ChatArchiveStore>,
     /// Claude Code runner
     claude_runner: Arc<ClaudeCodeRunner>,
     /// Codex CLI runner
@@ -44,14 +70,27 @@ pub struct ConduitCore {
     gemini_runner: Arc<GeminiCliRunner>,
     /// OpenCode runner
     opencode_runner: Arc<OpencodeRunner>,
+    /// Local OpenAI-compatible model runner (unavailable unless `[local_model]` is set)
+    local_runner: Arc<LocalModelRunner>,
+    /// Fixture replay standing in for one agent (set via `CONDUIT_REPLAY_FIXTURE`)
+    replay_runner: Option<Arc<ReplayRunner>>,
     /// Worktree manager
     worktree_manager: WorkspaceRepoManager,
+    /// Pre-submit and post-turn prompt hooks
+    hooks: Arc<HookRegistry>,
+    /// Dev server previews detected per workspace
+    previews: PreviewRegistry,
+    /// Opt-in feature usage counts
+    usage_stats: UsageStats,
+    /// Background writer for transcripts and billing entries
+    write_behind: Option<WriteBehindQueue>,
 }
 
 impl ConduitCore {
     /// Create a new ConduitCore with the given configuration and tool availability.
-    pub fn new(config: Config, tools: ToolAvailability) -> Self {
+    pub fn new(config: Config, mut tools: ToolAvailability) -> Self {
         // Initialize database and DAOs
+        let mut database_error = None;
         let (
             database,
             repo_store,
@@ -59,13 +98,35 @@ impl ConduitCore {
             app_state_store,
             session_tab_store,
             fork_seed_store,
-        ) = match Database::open_default() {
+            session_share_store,
+            session_report_store,
+            workspace_memory_store,
+            attachment_store,
+            cost_ledger_store,
+            chat_archive_store,
+            usage_stats_store,
+            transcript_store,
+        ) = match startup_profile::phase("database", Database::open_default) {
             Ok(db) => {
                 let repo_store = RepositoryStore::new(db.connection());
                 let workspace_store = WorkspaceStore::new(db.connection());
                 let app_state_store = AppStateStore::new(db.connection());
                 let session_tab_store = SessionTabStore::new(db.connection());
                 let fork_seed_store = ForkSeedStore::new(db.connection());
+                let session_share_store = SessionShareStore::new(db.connection());
+                let session_report_store = SessionReportStore::new(db.connection());
+                let workspace_memory_store = WorkspaceMemoryStore::new(db.connection());
+                let attachment_store =
+                    AttachmentStore::new(db.connection(), crate::util::attachments_dir());
+                let cost_ledger_store = CostLedgerStore::new(db.connection());
+                let chat_archive_store = ChatArchiveStore::new(db.connection());
+                let usage_stats_store = UsageStatsStore::new(db.connection());
+                let transcript_store = TranscriptStore::new(db.connection());
+                match attachment_store.sweep(ATTACHMENT_GRACE_PERIOD) {
+                    Ok(0) => {}
+                    Ok(removed) => tracing::info!(removed, "Removed unreferenced attachments"),
+                    Err(e) => tracing::warn!(error = %e, "Failed to sweep attachments"),
+                }
                 (
                     Some(db),
                     Some(repo_store),
@@ -73,18 +134,30 @@ impl ConduitCore {
                     Some(app_state_store),
                     Some(session_tab_store),
                     Some(fork_seed_store),
+                    Some(session_share_store),
+                    Some(session_report_store),
+                    Some(workspace_memory_store),
+                    Some(attachment_store),
+                    Some(cost_ledger_store),
+                    Some(chat_archive_store),
+                    Some(usage_stats_store),
+                    Some(transcript_store),
                 )
             }
             Err(e) => {
                 tracing::warn!(error = %e, "Failed to open database");
-                (None, None, None, None, None, None)
+                database_error = Some(e.to_string());
+                (
+                    None, None, None, None, None, None, None, None, None, None, None, None, None,
+                    None,
+                )
             }
         };
 
         // Migrate old worktrees folder to workspaces (one-time migration)
         crate::util::migrate_worktrees_to_workspaces();
 
-        // Initialize worktree manager with managed directory (~/.conduit/workspaces)
+        // Initialize worktree manager with managed directory (<data>/workspaces)
         let worktree_manager =
             WorkspaceRepoManager::with_managed_dir(crate::util::workspaces_dir());
 
@@ -105,30 +178,66 @@ impl ConduitCore {
             Some(path) => Arc::new(OpencodeRunner::with_path(path.clone())),
             None => Arc::new(OpencodeRunner::new()),
         };
-
-        if tools.is_available(Tool::Opencode) {
-            let models = crate::agent::opencode::load_opencode_models(
-                tools.get_path(Tool::Opencode).cloned(),
-            );
-            ModelRegistry::set_opencode_models(models);
-        } else {
-            ModelRegistry::clear_opencode_models();
+        let local_runner = Arc::new(Self::build_local_runner(&config));
+        let replay_runner = ReplayRunner::from_env().map(Arc::new);
+        if let Some(replay_runner) = &replay_runner {
+            if let Some(tool) = Self::agent_tool(replay_runner.agent_type()) {
+                tools.set_available(tool, replay_runner.fixture_path().to_path_buf());
+            }
         }
 
+        Self::spawn_opencode_model_load(&tools);
+        Self::spawn_cli_model_refresh(&tools);
+        let hooks = Arc::new(HookRegistry::from_config(&config.hooks));
+        let usage_stats = UsageStats::new(&config.usage_stats, usage_stats_store.clone());
+        UsageStats::spawn_uploader(
+            &config.usage_stats,
+            usage_stats_store,
+            app_state_store.clone(),
+        );
+        let write_behind = match (transcript_store, cost_ledger_store.clone()) {
+            (Some(transcripts), Some(cost_ledger)) => {
+                match WriteBehindQueue::start(WriteBehindStores {
+                    transcripts,
+                    cost_ledger,
+                }) {
+                    Ok(queue) => Some(queue),
+                    Err(err) => {
+                        tracing::warn!(error = %err, "Failed to start the persistence writer");
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
         Self {
             config,
             tools,
             _database: database,
+            database_error,
             repo_store,
             workspace_store,
             app_state_store,
             session_tab_store,
             fork_seed_store,
+            session_share_store,
+            session_report_store,
+            workspace_memory_store,
+            attachment_store,
+            cost_ledger_store,
+            chat_archive_store,
             claude_runner,
             codex_runner,
             gemini_runner,
             opencode_runner,
+            local_runner,
+            replay_runner,
             worktree_manager,
+            hooks,
+            previews: PreviewRegistry::default(),
+            usage_stats,
+            write_behind,
         }
     }
 
@@ -137,6 +246,11 @@ impl ConduitCore {
         &self.config
     }
 
+    /// Why the database couldn't be opened; nothing is persisted when set.
+    pub fn database_error(&self) -> Option<&str> {
+        self.database_error.as_deref()
+    }
+
     /// Get the tool availability.
     pub fn tools(&self) -> &ToolAvailability {
         &self.tools
@@ -192,6 +306,46 @@ impl ConduitCore {
         self.fork_seed_store.clone()
     }
 
+    /// Get the session share store.
+    pub fn session_share_store(&self) -> Option<&SessionShareStore> {
+        self.session_share_store.as_ref()
+    }
+
+    /// Get the session report store.
+    pub fn session_report_store(&self) -> Option<&SessionReportStore> {
+        self.session_report_store.as_ref()
+    }
+
+    /// Get the workspace memory store.
+    pub fn workspace_memory_store(&self) -> Option<&WorkspaceMemoryStore> {
+        self.workspace_memory_store.as_ref()
+    }
+
+    /// Get the attachment store.
+    pub fn attachment_store(&self) -> Option<&AttachmentStore> {
+        self.attachment_store.as_ref()
+    }
+
+    /// Get a clone of the attachment store.
+    pub fn attachment_store_clone(&self) -> Option<AttachmentStore> {
+        self.attachment_store.clone()
+    }
+
+    /// Get the billing ledger store.
+    pub fn cost_ledger_store(&self) -> Option<&CostLedgerStore> {
+        self.cost_ledger_store.as_ref()
+    }
+
+    /// Get the chat archive store.
+    pub fn chat_archive_store(&self) -> Option<&ChatArchiveStore> {
+        self.chat_archive_store.as_ref()
+    }
+
+    /// Get the background persistence queue (None without a database).
+    pub fn write_behind(&self) -> Option<&WriteBehindQueue> {
+        self.write_behind.as_ref()
+    }
+
     /// Get the Claude runner.
     pub fn claude_runner(&self) -> &Arc<ClaudeCodeRunner> {
         &self.claude_runner
@@ -212,6 +366,196 @@ impl ConduitCore {
         &self.opencode_runner
     }
 
+    /// Get the local model runner.
+    pub fn local_runner(&self) -> &Arc<LocalModelRunner> {
+        &self.local_runner
+    }
+
+    /// Get the runner that starts sessions for an agent type.
+    ///
+    /// Returns the replay runner instead when a fixture was recorded from that agent.
+    pub fn runner_for(&self, agent_type: AgentType) -> Arc<dyn AgentRunner> {
+        if let Some(replay_runner) = &self.replay_runner {
+            if replay_runner.agent_type() == agent_type {
+                return replay_runner.clone();
+            }
+        }
+        match agent_type {
+            AgentType::Claude => self.claude_runner.clone(),
+            AgentType::Codex => self.codex_runner.clone(),
+            AgentType::Gemini => self.gemini_runner.clone(),
+            AgentType::Opencode => self.opencode_runner.clone(),
+            AgentType::Local => self.local_runner.clone(),
+        }
+    }
+
+    /// External CLI an agent type runs through (None for local models).
+    fn agent_tool(agent_type: AgentType) -> Option<Tool> {
+        match agent_type {
+            AgentType::Claude => Some(Tool::Claude),
+            AgentType::Codex => Some(Tool::Codex),
+            AgentType::Gemini => Some(Tool::Gemini),
+            AgentType::Opencode => Some(Tool::Opencode),
+            AgentType::Local => None,
+        }
+    }
+
+    /// Whether an agent type can start sessions right now.
+    pub fn is_agent_available(&self, agent_type: AgentType) -> bool {
+        match Self::agent_tool(agent_type) {
+            Some(tool) => self.tools.is_available(tool),
+            None => self.runner_for(agent_type).is_available(),
+        }
+    }
+
+    /// Refresh Claude/Codex models from the installed CLIs in the background;
+    /// the built-in tables serve until discovery finishes.
+    /// Discover OpenCode's models off the startup path; discovery may have to
+    /// run `opencode` when the model cache is stale.
+    fn spawn_opencode_model_load(tools: &ToolAvailability) {
+        if !tools.is_available(Tool::Opencode) {
+            ModelRegistry::clear_opencode_models();
+            return;
+        }
+        let binary = tools.get_path(Tool::Opencode).cloned();
+        let spawned = std::thread::Builder::new()
+            .name("conduit-opencode-models".to_string())
+            .spawn(move || {
+                let models = crate::agent::opencode::load_opencode_models(binary);
+                ModelRegistry::set_opencode_models(models);
+            });
+        if let Err(err) = spawned {
+            tracing::warn!(error = %err, "Failed to start OpenCode model discovery");
+        }
+    }
+
+    fn spawn_cli_model_refresh(tools: &ToolAvailability) {
+        let claude = tools.get_path(Tool::Claude).cloned();
+        let codex = tools.get_path(Tool::Codex).cloned();
+        if claude.is_none() && codex.is_none() {
+            return;
+        }
+        let spawned = std::thread::Builder::new()
+            .name("conduit-model-discovery".to_string())
+            .spawn(move || ModelRegistry::refresh_cli_models(claude, codex, false));
+        if let Err(err) = spawned {
+            tracing::warn!(error = %err, "Failed to start model discovery");
+        }
+    }
+
+    /// Build the local model runner from `[local_model]` and publish its models.
+    fn build_local_runner(config: &Config) -> LocalModelRunner {
+        match &config.local_model {
+            Some(local) => {
+                ModelRegistry::set_local_models(local.models.clone());
+                LocalModelRunner::with_endpoint(
+                    local.base_url.clone(),
+                    local.api_key.clone(),
+                    local.max_tool_rounds,
+                )
+            }
+            None => {
+                ModelRegistry::clear_local_models();
+                LocalModelRunner::new()
+            }
+        }
+    }
+
+    /// Get the prompt hooks (shared so they can run off the UI task).
+    pub fn hooks(&self) -> Arc<HookRegistry> {
+        self.hooks.clone()
+    }
+
+    /// Register a Rust hook after the configured shell hooks.
+    pub fn register_hook(&mut self, hook: Arc<dyn PromptHook>) {
+        Arc::make_mut(&mut self.hooks).register(hook);
+    }
+
+    /// Get the dev server preview registry (shared so it can be used off the UI task).
+    pub fn previews(&self) -> &PreviewRegistry {
+        &self.previews
+    }
+
+    /// Opt-in feature usage counts (no-ops unless `[usage_stats]` is enabled).
+    pub fn usage_stats(&self) -> &UsageStats {
+        &self.usage_stats
+    }
+
+    /// Execution target for agents working in `workspace_id`, from its
+    /// repository's `[execution]` entry. Falls back to the host when the
+    /// workspace or repository can't be loaded.
+    pub fn execution_target_for_workspace(&self, workspace_id: Uuid) -> ExecutionTarget {
+        self.repository_for_workspace(workspace_id)
+            .map(|repo| resolve_repo_execution_target(&self.config, &repo))
+            .unwrap_or(ExecutionTarget::Host)
+    }
+
+    /// Append a completed turn to the billing ledger, tagged with the session's
+    /// cost center or its repository's. Returns the turn's cost; unpriced
+    /// models record nothing.
+    pub fn record_turn_cost(
+        &self,
+        session_id: Uuid,
+        workspace_id: Option<Uuid>,
+        session_cost_center: Option<&str>,
+        agent_type: AgentType,
+        model: Option<&str>,
+        usage: &TokenUsage,
+    ) -> Option<f64> {
+        let cost_usd = ModelRegistry::cost(agent_type, model, usage)?;
+        let Some(store) = &self.cost_ledger_store else {
+            return Some(cost_usd);
+        };
+        let repo = workspace_id.and_then(|id| self.repository_for_workspace(id));
+        let entry = CostEntry {
+            session_id,
+            repository: repo.as_ref().map(|repo| repo.name.clone()),
+            cost_center: resolve_cost_center(session_cost_center, repo.as_ref()),
+            agent_type,
+            model: model.map(str::to_string),
+            input_tokens: usage.input_tokens,
+            output_tokens: usage.output_tokens,
+            cost_usd,
+            recorded_at: chrono::Utc::now(),
+        };
+        match &self.write_behind {
+            Some(queue) => queue.push_cost(entry),
+            None => {
+                if let Err(err) = store.record(&entry) {
+                    tracing::warn!(%session_id, error = %err, "Failed to record turn cost in billing ledger");
+                }
+            }
+        }
+        Some(cost_usd)
+    }
+
+    /// Repository owning `workspace_id`; `None` (with a warning on database
+    /// errors) when either can't be loaded.
+    fn repository_for_workspace(&self, workspace_id: Uuid) -> Option<Repository> {
+        let (Some(workspace_store), Some(repo_store)) = (&self.workspace_store, &self.repo_store)
+        else {
+            return None;
+        };
+        let workspace = match workspace_store.get_by_id(workspace_id) {
+            Ok(workspace) => workspace?,
+            Err(err) => {
+                tracing::warn!(%workspace_id, error = %err, "Failed to load workspace");
+                return None;
+            }
+        };
+        match repo_store.get_by_id(workspace.repository_id) {
+            Ok(repo) => repo,
+            Err(err) => {
+                tracing::warn!(
+                    repository_id = %workspace.repository_id,
+                    error = %err,
+                    "Failed to load workspace repository"
+                );
+                None
+            }
+        }
+    }
+
     /// Get the worktree manager.
     pub fn worktree_manager(&self) -> &WorkspaceRepoManager {
         &self.worktree_manager
@@ -262,5 +606,7 @@ impl ConduitCore {
         } else {
             ModelRegistry::clear_opencode_models();
         }
+        Self::spawn_cli_model_refresh(&self.tools);
+        self.local_runner = Arc::new(Self::build_local_runner(&self.config));
     }
 }
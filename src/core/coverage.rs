@@ -0,0 +1,426 @@
+//! Test coverage gap analysis.
+//!
+//! Measures line coverage of a workspace with the tool matching its language
+//! (`cargo llvm-cov` for Rust, coverage.py for Python), picks the
+//! least-covered files, and builds the prompt asking the agent to test them.
+//! Measuring again afterwards shows whether coverage actually went up.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use serde_json::Value;
+use thiserror::Error;
+use tokio::process::Command;
+use tokio_util::sync::CancellationToken;
+
+/// Files named in the prompt as the ones to test
+pub const TARGET_FILE_COUNT: usize = 5;
+
+/// Lines of a failed coverage run's stderr kept in the error
+const STDERR_TAIL_LINES: usize = 20;
+
+#[derive(Debug, Error)]
+pub enum CoverageError {
+    #[error("no Cargo.toml or Python project file in {}", .0.display())]
+    UnsupportedProject(PathBuf),
+    #[error("failed to run `{command}` ({hint}): {source}")]
+    Spawn {
+        command: String,
+        hint: &'static str,
+        source: std::io::Error,
+    },
+    #[error("`{command}` produced no coverage report:\n{stderr}")]
+    NoReport { command: String, stderr: String },
+    #[error("coverage run stopped")]
+    Cancelled,
+}
+
+/// Coverage tool used for a workspace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverageTool {
+    LlvmCov,
+    CoveragePy,
+}
+
+impl CoverageTool {
+    /// Tool for the project in `dir`, by its manifest
+    pub fn detect(dir: &Path) -> Option<Self> {
+        if dir.join("Cargo.toml").is_file() {
+            return Some(Self::LlvmCov);
+        }
+        [
+            "pyproject.toml",
+            "setup.py",
+            "setup.cfg",
+            "requirements.txt",
+        ]
+        .iter()
+        .any(|manifest| dir.join(manifest).is_file())
+        .then_some(Self::CoveragePy)
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::LlvmCov => "cargo llvm-cov",
+            Self::CoveragePy => "coverage.py",
+        }
+    }
+
+    /// Commands run in order; the last one prints the JSON report
+    fn commands(self) -> &'static [&'static [&'static str]] {
+        match self {
+            Self::LlvmCov => &[&["cargo", "llvm-cov", "--json", "--summary-only"]],
+            Self::CoveragePy => &[
+                &["coverage", "run", "-m", "pytest"],
+                &["coverage", "json", "-o", "-"],
+            ],
+        }
+    }
+
+    fn install_hint(self) -> &'static str {
+        match self {
+            Self::LlvmCov => "install it with `cargo install cargo-llvm-cov`",
+            Self::CoveragePy => "install it with `pip install coverage pytest`",
+        }
+    }
+}
+
+/// Covered and coverable line counts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LineCoverage {
+    pub covered: u64,
+    pub total: u64,
+}
+
+impl LineCoverage {
+    pub fn percent(self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.covered as f64 * 100.0 / self.total as f64
+        }
+    }
+
+    pub fn uncovered(self) -> u64 {
+        self.total.saturating_sub(self.covered)
+    }
+}
+
+impl std::fmt::Display for LineCoverage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.1}% ({}/{} lines)",
+            self.percent(),
+            self.covered,
+            self.total
+        )
+    }
+}
+
+/// Line coverage of one source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileCoverage {
+    /// Path relative to the workspace when inside it
+    pub path: String,
+    pub lines: LineCoverage,
+}
+
+/// Line coverage of a workspace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageReport {
+    pub tool: CoverageTool,
+    pub total: LineCoverage,
+    pub files: Vec<FileCoverage>,
+}
+
+impl CoverageReport {
+    /// Files with uncovered lines, least covered first (ties broken by the
+    /// number of uncovered lines)
+    pub fn least_covered(&self, count: usize) -> Vec<&FileCoverage> {
+        let mut files: Vec<&FileCoverage> = self
+            .files
+            .iter()
+            .filter(|file| file.lines.uncovered() > 0)
+            .collect();
+        files.sort_by(|a, b| {
+            a.lines
+                .percent()
+                .total_cmp(&b.lines.percent())
+                .then(b.lines.uncovered().cmp(&a.lines.uncovered()))
+        });
+        files.truncate(count);
+        files
+    }
+
+    pub fn file(&self, path: &str) -> Option<&FileCoverage> {
+        self.files.iter().find(|file| file.path == path)
+    }
+
+    /// Summary card listing the least-covered files
+    pub fn summary(&self, targets: &[&FileCoverage]) -> String {
+        let mut out = format!(
+            "Line coverage ({}): {}",
+            self.tool.display_name(),
+            self.total
+        );
+        if !targets.is_empty() {
+            out.push_str("\nLeast-covered files:");
+            for file in targets {
+                out.push_str(&format!("\n- {}: {}", file.path, file.lines));
+            }
+        }
+        out
+    }
+
+    /// Prompt asking the agent to test `targets`
+    pub fn prompt(&self, targets: &[&FileCoverage]) -> String {
+        format!(
+            "{}
+
+Add tests that cover the untested behavior of these files. Follow the project's existing test layout and style, don't delete or weaken existing tests, and only change production code where a small refactor is needed to make it testable. Run the test suite and make sure it passes. Conduit measures coverage again after your turn to confirm the improvement.",
+            self.summary(targets)
+        )
+    }
+
+    /// How coverage changed since `baseline`, overall and for `targets`
+    pub fn comparison(&self, baseline: &CoverageReport, targets: &[String]) -> String {
+        let delta = self.total.percent() - baseline.total.percent();
+        let mut out = if delta > 0.05 {
+            format!(
+                "Coverage improved: {:.1}% → {:.1}% (+{:.1} points).",
+                baseline.total.percent(),
+                self.total.percent(),
+                delta
+            )
+        } else {
+            format!(
+                "Coverage didn't improve: {:.1}% → {:.1}%. /coverage verify measures again after further changes.",
+                baseline.total.percent(),
+                self.total.percent()
+            )
+        };
+        for path in targets {
+            let before = baseline.file(path).map(|file| file.lines.percent());
+            let after = self.file(path).map(|file| file.lines.percent());
+            if let (Some(before), Some(after)) = (before, after) {
+                out.push_str(&format!("\n- {path}: {before:.1}% → {after:.1}%"));
+            }
+        }
+        out
+    }
+}
+
+/// Measure coverage of the workspace in `dir`; `cancel` kills the run.
+/// Failing tests don't fail the measurement as long as a report is produced.
+pub async fn measure(
+    dir: &Path,
+    tool: CoverageTool,
+    cancel: &CancellationToken,
+) -> Result<CoverageReport, CoverageError> {
+    let mut last = None;
+    for args in tool.commands() {
+        let command = args.join(" ");
+        let spawn_error = |source| CoverageError::Spawn {
+            command: command.clone(),
+            hint: tool.install_hint(),
+            source,
+        };
+        let child = Command::new(args[0])
+            .args(&args[1..])
+            .current_dir(dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(spawn_error)?;
+        let output = tokio::select! {
+            output = child.wait_with_output() => output.map_err(spawn_error)?,
+            _ = cancel.cancelled() => return Err(CoverageError::Cancelled),
+        };
+        if !output.status.success() {
+            tracing::info!(
+                command = %command,
+                status = %output.status,
+                "Coverage command exited with failure"
+            );
+        }
+        last = Some((command, output));
+    }
+    let Some((command, output)) = last else {
+        return Err(CoverageError::UnsupportedProject(dir.to_path_buf()));
+    };
+    parse_report(tool, &String::from_utf8_lossy(&output.stdout), dir).ok_or_else(|| {
+        CoverageError::NoReport {
+            command,
+            stderr: tail(&String::from_utf8_lossy(&output.stderr)),
+        }
+    })
+}
+
+fn tail(text: &str) -> String {
+    let lines: Vec<&str> = text.trim().lines().collect();
+    lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n")
+}
+
+/// Parse the tool's JSON report; paths are made relative to `dir`
+pub fn parse_report(tool: CoverageTool, json: &str, dir: &Path) -> Option<CoverageReport> {
+    let report: Value = serde_json::from_str(json.trim()).ok()?;
+    let (total, files) = match tool {
+        CoverageTool::LlvmCov => parse_llvm_cov(&report)?,
+        CoverageTool::CoveragePy => parse_coverage_py(&report)?,
+    };
+    let mut files: Vec<FileCoverage> = files
+        .into_iter()
+        .map(|(path, lines)| FileCoverage {
+            path: Path::new(&path)
+                .strip_prefix(dir)
+                .map(|relative| relative.display().to_string())
+                .unwrap_or(path),
+            lines,
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Some(CoverageReport { tool, total, files })
+}
+
+type ParsedReport = (LineCoverage, Vec<(String, LineCoverage)>);
+
+/// `cargo llvm-cov --json --summary-only`: `data[0].files[].summary.lines`
+fn parse_llvm_cov(report: &Value) -> Option<ParsedReport> {
+    let data = report.get("data")?.get(0)?;
+    let lines = |summary: &Value| -> Option<LineCoverage> {
+        let lines = summary.get("lines")?;
+        Some(LineCoverage {
+            covered: lines.get("covered")?.as_u64()?,
+            total: lines.get("count")?.as_u64()?,
+        })
+    };
+    let total = lines(data.get("totals")?)?;
+    let files = data
+        .get("files")?
+        .as_array()?
+        .iter()
+        .filter_map(|file| {
+            Some((
+                file.get("filename")?.as_str()?.to_string(),
+                lines(file.get("summary")?)?,
+            ))
+        })
+        .collect();
+    Some((total, files))
+}
+
+/// `coverage json`: `files.<path>.summary` and `totals`
+fn parse_coverage_py(report: &Value) -> Option<ParsedReport> {
+    let lines = |summary: &Value| -> Option<LineCoverage> {
+        Some(LineCoverage {
+            covered: summary.get("covered_lines")?.as_u64()?,
+            total: summary.get("num_statements")?.as_u64()?,
+        })
+    };
+    let total = lines(report.get("totals")?)?;
+    let files = report
+        .get("files")?
+        .as_object()?
+        .iter()
+        .filter_map(|(path, file)| Some((path.clone(), lines(file.get("summary")?)?)))
+        .collect();
+    Some((total, files))
+}
+
+/// Stage of a coverage task in a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoveragePhase {
+    /// Measuring the baseline
+    Measuring,
+    /// The agent is adding tests
+    Writing,
+    /// Measuring again to compare with the baseline
+    Verifying,
+    /// Compared; `/coverage verify` measures again
+    Reviewed,
+}
+
+/// A coverage task started with `/coverage`
+#[derive(Debug, Clone)]
+pub struct CoverageTask {
+    pub phase: CoveragePhase,
+    pub tool: CoverageTool,
+    pub baseline: Option<CoverageReport>,
+    /// Files the agent was asked to test
+    pub targets: Vec<String>,
+    /// Stops the running measurement
+    pub cancel: CancellationToken,
+}
+
+impl CoverageTask {
+    pub fn new(tool: CoverageTool) -> Self {
+        Self {
+            phase: CoveragePhase::Measuring,
+            tool,
+            baseline: None,
+            targets: Vec::new(),
+            cancel: CancellationToken::new(),
+        }
+    }
+
+    /// Whether a measurement is running
+    pub fn is_measuring(&self) -> bool {
+        matches!(
+            self.phase,
+            CoveragePhase::Measuring | CoveragePhase::Verifying
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_llvm_cov_report() {
+        let json = r#"{"data":[{"files":[
+            {"filename":"/work/app/src/lib.rs","summary":{"lines":{"count":40,"covered":30,"percent":75.0}}},
+            {"filename":"/work/app/src/parser.rs","summary":{"lines":{"count":20,"covered":2,"percent":10.0}}},
+            {"filename":"/work/app/src/main.rs","summary":{"lines":{"count":5,"covered":5,"percent":100.0}}}
+        ],"totals":{"lines":{"count":65,"covered":37,"percent":56.9}}}],"type":"llvm.coverage.json.export"}"#;
+        let report = parse_report(CoverageTool::LlvmCov, json, Path::new("/work/app")).unwrap();
+        assert_eq!(
+            report.total,
+            LineCoverage {
+                covered: 37,
+                total: 65
+            }
+        );
+        let targets: Vec<&str> = report
+            .least_covered(TARGET_FILE_COUNT)
+            .iter()
+            .map(|file| file.path.as_str())
+            .collect();
+        assert_eq!(targets, ["src/parser.rs", "src/lib.rs"]);
+    }
+
+    #[test]
+    fn test_comparison_against_baseline() {
+        let json = |covered: u64| {
+            format!(
+                r#"{{"files":{{"pkg/util.py":{{"summary":{{"covered_lines":{covered},"num_statements":10}}}}}},
+                "totals":{{"covered_lines":{covered},"num_statements":10}}}}"#
+            )
+        };
+        let dir = Path::new("/work/app");
+        let before = parse_report(CoverageTool::CoveragePy, &json(4), dir).unwrap();
+        let after = parse_report(CoverageTool::CoveragePy, &json(9), dir).unwrap();
+        let targets = ["pkg/util.py".to_string()];
+        assert_eq!(
+            after.comparison(&before, &targets),
+            "Coverage improved: 40.0% → 90.0% (+50.0 points).\n- pkg/util.py: 40.0% → 90.0%"
+        );
+        assert!(before
+            .comparison(&before, &targets)
+            .starts_with("Coverage didn't improve"));
+        assert!(parse_report(CoverageTool::CoveragePy, "error: no data", dir).is_none());
+    }
+}
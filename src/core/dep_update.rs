@@ -0,0 +1,835 @@
+//! Guided dependency updates.
+//!
+//! A scan reads the workspace's manifests (`Cargo.toml`, `package.json`),
+//! asks the package manager which direct dependencies are outdated, and
+//! groups them: compatible (minor and patch) updates together, major updates
+//! by package family. [`DepUpdateRun`] then walks the groups one at a time:
+//! the agent bumps a group on its own branch, Conduit runs the verification
+//! commands, and only a passing group gets a PR before the next one starts.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use thiserror::Error;
+
+/// Lines of failed verification output quoted back to the agent
+const FAILURE_TAIL_LINES: usize = 30;
+
+#[derive(Debug, Error)]
+pub enum DepUpdateError {
+    #[error("no Cargo.toml or package.json in {}", .0.display())]
+    NoManifest(PathBuf),
+    #[error("failed to read {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("failed to parse {}: {message}", path.display())]
+    Parse { path: PathBuf, message: String },
+    #[error("failed to run `{command}`: {source}")]
+    Spawn {
+        command: String,
+        source: std::io::Error,
+    },
+    #[error("`{command}` failed: {stderr}")]
+    Failed { command: String, stderr: String },
+}
+
+/// Package ecosystem of a manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+}
+
+impl Ecosystem {
+    pub fn manifest(self) -> &'static str {
+        match self {
+            Ecosystem::Cargo => "Cargo.toml",
+            Ecosystem::Npm => "package.json",
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Ecosystem::Cargo => "Cargo",
+            Ecosystem::Npm => "npm",
+        }
+    }
+
+    fn slug(self) -> &'static str {
+        match self {
+            Ecosystem::Cargo => "cargo",
+            Ecosystem::Npm => "npm",
+        }
+    }
+
+    /// How the agent should apply a bump
+    fn bump_hint(self) -> &'static str {
+        match self {
+            Ecosystem::Cargo => {
+                "Raise the version requirements in Cargo.toml where needed, then run `cargo update -p <name>` for each."
+            }
+            Ecosystem::Npm => "Run `npm install <name>@<version>` for each.",
+        }
+    }
+}
+
+/// A direct dependency with a newer release
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutdatedDependency {
+    pub name: String,
+    pub current: String,
+    pub latest: String,
+}
+
+impl OutdatedDependency {
+    /// Whether the update may break callers: the major version changes, or
+    /// the minor version of a 0.x release
+    pub fn is_major(&self) -> bool {
+        let (current, latest) = (version_parts(&self.current), version_parts(&self.latest));
+        match (current.first(), latest.first()) {
+            (Some(0), Some(0)) => current.get(1) != latest.get(1),
+            (current_major, latest_major) => current_major != latest_major,
+        }
+    }
+}
+
+/// Dependencies updated together on one branch, with one PR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateGroup {
+    pub name: String,
+    pub ecosystem: Ecosystem,
+    pub branch: String,
+    pub dependencies: Vec<OutdatedDependency>,
+    /// Commands that must pass before the group's PR is opened
+    pub verify: Vec<String>,
+}
+
+/// Outdated dependencies of a workspace, grouped for updating
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencyScan {
+    pub groups: Vec<UpdateGroup>,
+}
+
+impl DependencyScan {
+    pub fn dependency_count(&self) -> usize {
+        self.groups
+            .iter()
+            .map(|group| group.dependencies.len())
+            .sum()
+    }
+
+    /// Numbered plan shown before the run starts
+    pub fn plan(&self) -> String {
+        let mut out = format!(
+            "{} outdated dependencies in {} group(s):",
+            self.dependency_count(),
+            self.groups.len()
+        );
+        for (index, group) in self.groups.iter().enumerate() {
+            out.push_str(&format!(
+                "\n{}. {} ({})",
+                index + 1,
+                group.name,
+                group.branch
+            ));
+            for dependency in &group.dependencies {
+                out.push_str(&format!(
+                    "\n   {} {} → {}",
+                    dependency.name, dependency.current, dependency.latest
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// Scan `dir` for outdated direct dependencies. Runs the package managers,
+/// so call it off the UI thread.
+pub fn scan(dir: &Path) -> Result<DependencyScan, DepUpdateError> {
+    let mut groups = Vec::new();
+    let mut found_manifest = false;
+
+    let cargo_manifest = dir.join(Ecosystem::Cargo.manifest());
+    if cargo_manifest.is_file() {
+        found_manifest = true;
+        let direct = cargo_direct_dependencies(&cargo_manifest)?;
+        let output = run(dir, "cargo", &["update", "--dry-run", "--verbose"])?;
+        let outdated = parse_cargo_update(&output)
+            .into_iter()
+            .filter(|dependency| direct.contains(&dependency.name))
+            .collect();
+        groups.extend(group_updates(
+            Ecosystem::Cargo,
+            outdated,
+            &[
+                "cargo build --all-targets".to_string(),
+                "cargo test".to_string(),
+            ],
+        ));
+    }
+
+    let npm_manifest = dir.join(Ecosystem::Npm.manifest());
+    if npm_manifest.is_file() {
+        found_manifest = true;
+        let verify = npm_verify_commands(&read(&npm_manifest)?, &npm_manifest)?;
+        let output = run(dir, "npm", &["outdated", "--json"])?;
+        let outdated = parse_npm_outdated(&output).map_err(|message| DepUpdateError::Parse {
+            path: npm_manifest.clone(),
+            message,
+        })?;
+        groups.extend(group_updates(Ecosystem::Npm, outdated, &verify));
+    }
+
+    if !found_manifest {
+        return Err(DepUpdateError::NoManifest(dir.to_path_buf()));
+    }
+    Ok(DependencyScan { groups })
+}
+
+/// Output of a package manager command. `npm outdated` exits non-zero when
+/// anything is outdated, so only a failure without stdout is an error.
+fn run(dir: &Path, program: &str, args: &[&str]) -> Result<String, DepUpdateError> {
+    let command = format!("{program} {}", args.join(" "));
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|source| DepUpdateError::Spawn {
+            command: command.clone(),
+            source,
+        })?;
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    if !output.status.success() && stdout.trim().is_empty() {
+        return Err(DepUpdateError::Failed {
+            command,
+            stderr: stderr.trim().to_string(),
+        });
+    }
+    // Cargo reports on stderr
+    Ok(format!("{stdout}{stderr}"))
+}
+
+fn read(path: &Path) -> Result<String, DepUpdateError> {
+    std::fs::read_to_string(path).map_err(|source| DepUpdateError::Read {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Names of the crates a Cargo manifest (and its listed workspace members)
+/// depends on directly
+fn cargo_direct_dependencies(manifest: &Path) -> Result<HashSet<String>, DepUpdateError> {
+    let parse = |path: &Path| -> Result<toml::Value, DepUpdateError> {
+        toml::from_str(&read(path)?).map_err(|err| DepUpdateError::Parse {
+            path: path.to_path_buf(),
+            message: err.to_string(),
+        })
+    };
+    let root = parse(manifest)?;
+    let mut names = HashSet::new();
+    collect_cargo_dependencies(&root, &mut names);
+
+    let members = root
+        .get("workspace")
+        .and_then(|workspace| workspace.get("members"))
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_str)
+        // Glob members would need expanding; the lockfile scan still covers them
+        .filter(|member| !member.contains('*'));
+    let root_dir = manifest.parent().unwrap_or(Path::new("."));
+    for member in members {
+        let member_manifest = root_dir.join(member).join(Ecosystem::Cargo.manifest());
+        if member_manifest.is_file() {
+            collect_cargo_dependencies(&parse(&member_manifest)?, &mut names);
+        }
+    }
+    Ok(names)
+}
+
+fn collect_cargo_dependencies(manifest: &toml::Value, names: &mut HashSet<String>) {
+    const TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+    let mut tables: Vec<&toml::Value> = TABLES
+        .iter()
+        .filter_map(|table| manifest.get(table))
+        .collect();
+    if let Some(workspace) = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("dependencies"))
+    {
+        tables.push(workspace);
+    }
+    if let Some(targets) = manifest.get("target").and_then(toml::Value::as_table) {
+        for target in targets.values() {
+            tables.extend(TABLES.iter().filter_map(|table| target.get(table)));
+        }
+    }
+    for table in tables.into_iter().filter_map(toml::Value::as_table) {
+        for (key, spec) in table {
+            // `foo = { package = "bar" }` depends on the crate `bar`
+            let name = spec
+                .get("package")
+                .and_then(toml::Value::as_str)
+                .unwrap_or(key);
+            names.insert(name.to_string());
+        }
+    }
+}
+
+/// Updates listed by `cargo update --dry-run --verbose`: `Updating` lines
+/// are compatible updates, `Unchanged ... (available: ...)` lines need a
+/// version requirement bump
+pub fn parse_cargo_update(output: &str) -> Vec<OutdatedDependency> {
+    let mut seen = HashSet::new();
+    let mut outdated = Vec::new();
+    for line in output.lines() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let dependency = match tokens.as_slice() {
+            ["Updating", name, current, "->", latest, ..] => (name, current, *latest),
+            ["Unchanged", name, current, "(available:", latest, ..] => {
+                (name, current, latest.trim_end_matches(')'))
+            }
+            _ => continue,
+        };
+        let (name, current, latest) = dependency;
+        if seen.insert(name.to_string()) {
+            outdated.push(OutdatedDependency {
+                name: name.to_string(),
+                current: current.trim_start_matches('v').to_string(),
+                latest: latest.trim_start_matches('v').to_string(),
+            });
+        }
+    }
+    outdated
+}
+
+/// Updates listed by `npm outdated --json`
+pub fn parse_npm_outdated(output: &str) -> Result<Vec<OutdatedDependency>, String> {
+    if output.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    // npm appends warnings after the JSON object on some versions
+    let json = output
+        .rfind('}')
+        .map_or(output, |end| &output[..=end])
+        .trim();
+    let packages: BTreeMap<String, serde_json::Value> =
+        serde_json::from_str(json).map_err(|err| err.to_string())?;
+    Ok(packages
+        .into_iter()
+        .filter_map(|(name, info)| {
+            let current = info
+                .get("current")
+                .or_else(|| info.get("wanted"))?
+                .as_str()?;
+            let latest = info.get("latest")?.as_str()?;
+            (current != latest).then(|| OutdatedDependency {
+                name,
+                current: current.to_string(),
+                latest: latest.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Verification for an npm project: its `build` and `test` scripts
+fn npm_verify_commands(manifest: &str, path: &Path) -> Result<Vec<String>, DepUpdateError> {
+    let manifest: serde_json::Value =
+        serde_json::from_str(manifest).map_err(|err| DepUpdateError::Parse {
+            path: path.to_path_buf(),
+            message: err.to_string(),
+        })?;
+    let scripts = manifest.get("scripts");
+    let has_script = |name: &str| scripts.and_then(|scripts| scripts.get(name)).is_some();
+    let mut commands = Vec::new();
+    if has_script("build") {
+        commands.push("npm run build".to_string());
+    }
+    if has_script("test") {
+        commands.push("npm test".to_string());
+    }
+    Ok(commands)
+}
+
+/// Compatible updates go in one group; major updates are grouped by family
+/// (npm scope, or the crate name up to its first `-`/`_`)
+pub fn group_updates(
+    ecosystem: Ecosystem,
+    outdated: Vec<OutdatedDependency>,
+    verify: &[String],
+) -> Vec<UpdateGroup> {
+    let (major, compatible): (Vec<_>, Vec<_>) =
+        outdated.into_iter().partition(OutdatedDependency::is_major);
+    let mut groups = Vec::new();
+    if !compatible.is_empty() {
+        groups.push(UpdateGroup {
+            name: format!("{} minor and patch updates", ecosystem.display_name()),
+            ecosystem,
+            branch: format!("deps/{}-compatible", ecosystem.slug()),
+            dependencies: compatible,
+            verify: verify.to_vec(),
+        });
+    }
+    let mut families: BTreeMap<String, Vec<OutdatedDependency>> = BTreeMap::new();
+    for dependency in major {
+        families
+            .entry(family(&dependency.name).to_string())
+            .or_default()
+            .push(dependency);
+    }
+    for (family, dependencies) in families {
+        groups.push(UpdateGroup {
+            name: format!("{family} major update"),
+            ecosystem,
+            branch: format!("deps/{}-{}-major", ecosystem.slug(), slug(&family)),
+            dependencies,
+            verify: verify.to_vec(),
+        });
+    }
+    groups
+}
+
+fn family(name: &str) -> &str {
+    if name.starts_with('@') {
+        return name.split('/').next().unwrap_or(name);
+    }
+    name.split(['-', '_']).next().unwrap_or(name)
+}
+
+fn slug(name: &str) -> String {
+    name.chars()
+        .filter_map(|c| match c {
+            'a'..='z' | '0'..='9' => Some(c),
+            'A'..='Z' => Some(c.to_ascii_lowercase()),
+            '-' | '_' | '.' => Some('-'),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Leading numeric components of a version ("1.2.3-beta" → [1, 2, 3])
+fn version_parts(version: &str) -> Vec<u64> {
+    version
+        .trim_start_matches(['v', '^', '~', '='])
+        .split(['.', '-', '+'])
+        .map_while(|part| part.parse().ok())
+        .collect()
+}
+
+/// Where a dependency update run stands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DepUpdatePhase {
+    /// Scanned; waiting for the user to start
+    Planned,
+    /// The agent is bumping the current group
+    Updating,
+    /// Conduit is running the current group's verification commands
+    Verifying,
+    /// The agent is pushing the current group and opening its PR
+    OpeningPr,
+    /// Stopped on the current group until the user retries or skips it
+    Blocked(String),
+    /// Every group was handled
+    Done,
+}
+
+/// What the app should do after a run changes phase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DepUpdateStep {
+    /// Nothing to do
+    Idle,
+    /// Send this prompt to the agent
+    Prompt(String),
+    /// Run these verification commands
+    Verify(Vec<String>),
+    /// The run stopped on a group; show why
+    Blocked(String),
+    /// The run finished
+    Finished,
+}
+
+/// A dependency update run in one session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepUpdateRun {
+    pub groups: Vec<UpdateGroup>,
+    /// Branch every group branches off and targets
+    pub base_branch: Option<String>,
+    /// Index of the group being worked on
+    pub current: usize,
+    pub phase: DepUpdatePhase,
+    /// Groups whose PR was requested
+    pub completed: usize,
+    /// Groups the user skipped
+    pub skipped: usize,
+    /// Number of verification runs so far, for unique tool message IDs
+    pub verify_runs: usize,
+    /// Last lines of the running verification's output
+    pub verify_output: Vec<String>,
+}
+
+impl DepUpdateRun {
+    pub fn new(scan: DependencyScan) -> Self {
+        Self {
+            groups: scan.groups,
+            base_branch: None,
+            current: 0,
+            phase: DepUpdatePhase::Planned,
+            completed: 0,
+            skipped: 0,
+            verify_runs: 0,
+            verify_output: Vec::new(),
+        }
+    }
+
+    pub fn current_group(&self) -> Option<&UpdateGroup> {
+        self.groups.get(self.current)
+    }
+
+    /// Whether the run waits on the agent or the verification commands
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self.phase,
+            DepUpdatePhase::Updating | DepUpdatePhase::Verifying | DepUpdatePhase::OpeningPr
+        )
+    }
+
+    /// Start with the first group, branching off `base_branch`
+    pub fn start(&mut self, base_branch: String) -> DepUpdateStep {
+        if self.phase != DepUpdatePhase::Planned {
+            return DepUpdateStep::Idle;
+        }
+        self.base_branch = Some(base_branch);
+        self.begin_group()
+    }
+
+    /// The agent's turn ended (`succeeded` = it completed without error)
+    pub fn turn_finished(&mut self, succeeded: bool) -> DepUpdateStep {
+        match (&self.phase, succeeded) {
+            (DepUpdatePhase::Updating | DepUpdatePhase::OpeningPr, false) => {
+                self.block("The agent's turn failed.".to_string())
+            }
+            (DepUpdatePhase::Updating, true) => {
+                let Some(group) = self.current_group() else {
+                    return DepUpdateStep::Idle;
+                };
+                if group.verify.is_empty() {
+                    // Nothing to verify with; go straight to the PR
+                    return self.verification_finished(Ok(()));
+                }
+                let commands = group.verify.clone();
+                self.phase = DepUpdatePhase::Verifying;
+                self.verify_runs += 1;
+                self.verify_output.clear();
+                DepUpdateStep::Verify(commands)
+            }
+            (DepUpdatePhase::OpeningPr, true) => {
+                self.completed += 1;
+                self.current += 1;
+                self.begin_group()
+            }
+            _ => DepUpdateStep::Idle,
+        }
+    }
+
+    /// Keep the tail of the verification output for a failure report
+    pub fn record_verify_output(&mut self, line: String) {
+        if self.verify_output.len() == FAILURE_TAIL_LINES {
+            self.verify_output.remove(0);
+        }
+        self.verify_output.push(line);
+    }
+
+    /// The verification commands finished; `Err` carries the failure
+    pub fn verification_finished(&mut self, result: Result<(), String>) -> DepUpdateStep {
+        if !matches!(
+            self.phase,
+            DepUpdatePhase::Updating | DepUpdatePhase::Verifying
+        ) {
+            return DepUpdateStep::Idle;
+        }
+        match result {
+            Ok(()) => match self.current_group() {
+                Some(group) => {
+                    let prompt = pr_prompt(group, self.base());
+                    self.phase = DepUpdatePhase::OpeningPr;
+                    DepUpdateStep::Prompt(prompt)
+                }
+                None => DepUpdateStep::Idle,
+            },
+            Err(err) => {
+                let mut reason = format!("Verification failed: {err}");
+                if !self.verify_output.is_empty() {
+                    reason.push_str(&format!("\n{}", self.verify_output.join("\n")));
+                }
+                self.block(reason)
+            }
+        }
+    }
+
+    /// Ask the agent to fix what blocked the current group, then verify again
+    pub fn retry(&mut self) -> DepUpdateStep {
+        let DepUpdatePhase::Blocked(reason) = &self.phase else {
+            return DepUpdateStep::Idle;
+        };
+        let Some(group) = self.current_group() else {
+            return DepUpdateStep::Idle;
+        };
+        let prompt = format!(
+            "Updating {} on branch {} didn't go through:\n```\n{}\n```\nFix the problem on that branch, commit the fix, and make sure {} pass. Don't push yet.",
+            group.name,
+            group.branch,
+            reason,
+            verify_list(group)
+        );
+        self.phase = DepUpdatePhase::Updating;
+        DepUpdateStep::Prompt(prompt)
+    }
+
+    /// Leave the current group without a PR and move on
+    pub fn skip(&mut self) -> DepUpdateStep {
+        if !matches!(self.phase, DepUpdatePhase::Blocked(_)) {
+            return DepUpdateStep::Idle;
+        }
+        self.skipped += 1;
+        self.current += 1;
+        self.begin_group()
+    }
+
+    /// Summary of the run's progress for `/deps status`
+    pub fn status(&self) -> String {
+        let group = self.current_group().map(|group| {
+            format!(
+                "group {} of {}: {}",
+                self.current + 1,
+                self.groups.len(),
+                group.name
+            )
+        });
+        match (&self.phase, group) {
+            (DepUpdatePhase::Planned, _) => {
+                "Dependency updates are planned; /deps start begins.".to_string()
+            }
+            (DepUpdatePhase::Updating, Some(group)) => format!("The agent is updating {group}."),
+            (DepUpdatePhase::Verifying, Some(group)) => format!("Verifying {group}."),
+            (DepUpdatePhase::OpeningPr, Some(group)) => format!("Opening the PR for {group}."),
+            (DepUpdatePhase::Blocked(_), Some(group)) => format!("Stopped on {group}."),
+            _ => self.summary(),
+        }
+    }
+
+    /// Closing line once every group was handled
+    pub fn summary(&self) -> String {
+        format!(
+            "Dependency updates finished: {} PR(s) requested, {} group(s) skipped.",
+            self.completed, self.skipped
+        )
+    }
+
+    fn base(&self) -> &str {
+        self.base_branch.as_deref().unwrap_or("main")
+    }
+
+    fn begin_group(&mut self) -> DepUpdateStep {
+        let Some(group) = self.current_group() else {
+            self.phase = DepUpdatePhase::Done;
+            return DepUpdateStep::Finished;
+        };
+        let prompt = group_prompt(group, self.current, self.groups.len(), self.base());
+        self.phase = DepUpdatePhase::Updating;
+        DepUpdateStep::Prompt(prompt)
+    }
+
+    fn block(&mut self, reason: String) -> DepUpdateStep {
+        self.phase = DepUpdatePhase::Blocked(reason.clone());
+        DepUpdateStep::Blocked(reason)
+    }
+}
+
+fn verify_list(group: &UpdateGroup) -> String {
+    if group.verify.is_empty() {
+        "the project's checks".to_string()
+    } else {
+        group
+            .verify
+            .iter()
+            .map(|command| format!("`{command}`"))
+            .collect::<Vec<_>>()
+            .join(" and ")
+    }
+}
+
+/// Prompt asking the agent to bump one group on its own branch
+fn group_prompt(group: &UpdateGroup, index: usize, total: usize, base: &str) -> String {
+    let dependencies: Vec<String> = group
+        .dependencies
+        .iter()
+        .map(|dependency| {
+            format!(
+                "   - {} {} → {}",
+                dependency.name, dependency.current, dependency.latest
+            )
+        })
+        .collect();
+    let changelog_note = if group.dependencies.iter().any(OutdatedDependency::is_major) {
+        " These are major updates, so check the changelogs for breaking changes."
+    } else {
+        ""
+    };
+    format!(
+        r#"Dependency update {} of {}: {}.
+
+1. Make sure the working tree is clean (commit or stash leftovers), check out {base}, and create the branch {} from it.
+2. Update these {} dependencies:
+{}
+   {}
+3. Fix anything the updates break.{changelog_note}
+4. Run {} and make sure they pass.
+5. Commit with a message like "Update {}". Don't push yet; Conduit verifies the branch before the PR is opened."#,
+        index + 1,
+        total,
+        group.name,
+        group.branch,
+        group.ecosystem.display_name(),
+        dependencies.join("\n"),
+        group.ecosystem.bump_hint(),
+        verify_list(group),
+        group.name,
+    )
+}
+
+/// Prompt asking the agent to open the PR for a verified group
+fn pr_prompt(group: &UpdateGroup, base: &str) -> String {
+    format!(
+        "Verification passed for {}. Push {} to origin and open a PR with gh pr create --base {base}. Keep the title under 80 characters and list the updated dependencies in the description. Then check out {base} again.",
+        group.name, group.branch
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependency(name: &str, current: &str, latest: &str) -> OutdatedDependency {
+        OutdatedDependency {
+            name: name.to_string(),
+            current: current.to_string(),
+            latest: latest.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_cargo_update_dry_run() {
+        let output = "    Updating crates.io index
+     Locking 2 packages to latest compatible versions
+    Updating serde v1.0.200 -> v1.0.210
+    Updating serde v1.0.200 -> v1.0.210
+   Unchanged rand v0.8.5 (available: v0.9.0)
+note: to see how you depend on a package, run `cargo tree --invert --package <dep>@<ver>`";
+        assert_eq!(
+            parse_cargo_update(output),
+            vec![
+                dependency("serde", "1.0.200", "1.0.210"),
+                dependency("rand", "0.8.5", "0.9.0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_npm_outdated() {
+        let output = r#"{
+  "react": {"current": "18.2.0", "wanted": "18.3.1", "latest": "19.0.0"},
+  "left-pad": {"wanted": "1.3.0", "latest": "1.3.0"}
+}"#;
+        assert_eq!(
+            parse_npm_outdated(output).unwrap(),
+            vec![dependency("react", "18.2.0", "19.0.0")]
+        );
+        assert_eq!(parse_npm_outdated("").unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn test_major_updates_are_grouped_by_family() {
+        assert!(dependency("rand", "0.8.5", "0.9.0").is_major());
+        assert!(!dependency("serde", "1.0.200", "1.0.210").is_major());
+
+        let groups = group_updates(
+            Ecosystem::Npm,
+            vec![
+                dependency("@babel/core", "7.1.0", "8.0.0"),
+                dependency("lodash", "4.17.20", "4.17.21"),
+                dependency("@babel/preset-env", "7.1.0", "8.0.0"),
+            ],
+            &["npm test".to_string()],
+        );
+        let summary: Vec<(&str, &str, usize)> = groups
+            .iter()
+            .map(|group| {
+                (
+                    group.name.as_str(),
+                    group.branch.as_str(),
+                    group.dependencies.len(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("npm minor and patch updates", "deps/npm-compatible", 1),
+                ("@babel major update", "deps/npm-babel-major", 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_verifies_each_group_before_its_pr() {
+        let scan = DependencyScan {
+            groups: group_updates(
+                Ecosystem::Cargo,
+                vec![
+                    dependency("serde", "1.0.200", "1.0.210"),
+                    dependency("rand", "0.8.5", "0.9.0"),
+                ],
+                &["cargo test".to_string()],
+            ),
+        };
+        let mut run = DepUpdateRun::new(scan);
+        let DepUpdateStep::Prompt(prompt) = run.start("main".to_string()) else {
+            panic!("expected the first group's prompt");
+        };
+        assert!(prompt.contains("create the branch deps/cargo-compatible from it"));
+
+        assert_eq!(
+            run.turn_finished(true),
+            DepUpdateStep::Verify(vec!["cargo test".to_string()])
+        );
+        run.record_verify_output("test parser ... FAILED".to_string());
+        assert!(matches!(
+            run.verification_finished(Err("`cargo test` exited with code 101".to_string())),
+            DepUpdateStep::Blocked(reason) if reason.ends_with("test parser ... FAILED")
+        ));
+
+        assert!(matches!(run.retry(), DepUpdateStep::Prompt(_)));
+        assert!(matches!(run.turn_finished(true), DepUpdateStep::Verify(_)));
+        assert!(matches!(
+            run.verification_finished(Ok(())),
+            DepUpdateStep::Prompt(prompt) if prompt.contains("gh pr create --base main")
+        ));
+        assert!(matches!(
+            run.turn_finished(true),
+            DepUpdateStep::Prompt(prompt) if prompt.contains("deps/cargo-rand-major")
+        ));
+
+        assert!(matches!(
+            run.turn_finished(false),
+            DepUpdateStep::Blocked(_)
+        ));
+        assert_eq!(run.skip(), DepUpdateStep::Finished);
+        assert_eq!(run.phase, DepUpdatePhase::Done);
+        assert_eq!((run.completed, run.skipped), (1, 1));
+    }
+}
@@ -0,0 +1,505 @@
+//! Prompt hooks: plugin points around each agent turn.
+//!
+//! Pre-submit hooks see every user prompt before it reaches the agent and may
+//! rewrite it (e.g. inject ticket context) or reject it. Post-turn hooks run
+//! after `TurnCompleted` with a summary of the turn, for posting results to
+//! external systems.
+//!
+//! Hooks are either shell commands from `[hooks]` in config.toml or Rust
+//! trait objects registered on [`ConduitCore`](crate::core::ConduitCore).
+//! Shell hooks receive a JSON payload on stdin; a pre-submit hook's stdout
+//! (when non-empty) replaces the prompt and a non-zero exit rejects it.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use uuid::Uuid;
+
+use crate::agent::events::TokenUsage;
+use crate::config::{HookCommand, HooksConfig};
+
+/// When a hook runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookStage {
+    PreSubmit,
+    PostTurn,
+}
+
+impl HookStage {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HookStage::PreSubmit => "pre_submit",
+            HookStage::PostTurn => "post_turn",
+        }
+    }
+}
+
+/// Session a hook is running for
+#[derive(Debug, Clone, Serialize)]
+pub struct HookContext {
+    pub session_id: Uuid,
+    pub agent: String,
+    pub model: Option<String>,
+    pub working_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HookFileChange {
+    pub path: String,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+/// What happened in a completed turn
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TurnReport {
+    /// Wall-clock turn time, when the caller tracked it
+    pub duration_secs: Option<u64>,
+    pub usage: TokenUsage,
+    pub cost_usd: Option<f64>,
+    pub files_changed: Vec<HookFileChange>,
+    /// Final assistant message of the turn
+    pub last_message: Option<String>,
+}
+
+/// Result of a pre-submit hook
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreSubmit {
+    /// Send this (possibly rewritten) prompt on
+    Continue(String),
+    /// Don't send the prompt; the reason is shown to the user
+    Reject(String),
+}
+
+#[derive(Debug, Error)]
+pub enum HookError {
+    #[error("failed to run {command}: {source}")]
+    Spawn {
+        command: String,
+        source: std::io::Error,
+    },
+    #[error("{command} timed out after {secs}s")]
+    Timeout { command: String, secs: u64 },
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to encode hook payload: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Prompt rejected by a pre-submit hook (or by a hook that failed)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookRejection {
+    pub hook: String,
+    pub reason: String,
+}
+
+impl fmt::Display for HookRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.reason.is_empty() {
+            write!(f, "Prompt rejected by hook {}", self.hook)
+        } else {
+            write!(f, "Prompt rejected by hook {}: {}", self.hook, self.reason)
+        }
+    }
+}
+
+/// A plugin point around agent turns. Both stages default to no-ops so an
+/// implementation only overrides the ones it cares about.
+#[async_trait]
+pub trait PromptHook: Send + Sync {
+    /// Name used in logs and rejection messages
+    fn name(&self) -> &str;
+
+    /// Whether this hook does anything at `stage` (lets callers skip work)
+    fn handles(&self, _stage: HookStage) -> bool {
+        true
+    }
+
+    async fn pre_submit(&self, _ctx: &HookContext, prompt: String) -> Result<PreSubmit, HookError> {
+        Ok(PreSubmit::Continue(prompt))
+    }
+
+    async fn post_turn(&self, _ctx: &HookContext, _turn: &TurnReport) -> Result<(), HookError> {
+        Ok(())
+    }
+}
+
+/// Shell command hook configured under `[[hooks.pre_submit]]` or `[[hooks.post_turn]]`
+pub struct ShellHook {
+    stage: HookStage,
+    config: HookCommand,
+}
+
+#[derive(Serialize)]
+struct ShellPayload<'a> {
+    hook: HookStage,
+    #[serde(flatten)]
+    context: &'a HookContext,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompt: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    turn: Option<&'a TurnReport>,
+}
+
+struct ShellOutput {
+    success: bool,
+    stdout: String,
+    stderr: String,
+}
+
+impl ShellHook {
+    pub fn new(stage: HookStage, config: HookCommand) -> Self {
+        Self { stage, config }
+    }
+
+    async fn run(
+        &self,
+        ctx: &HookContext,
+        payload: &ShellPayload<'_>,
+    ) -> Result<ShellOutput, HookError> {
+        let input = serde_json::to_vec(payload)?;
+        let mut command = Command::new(&self.config.command);
+        command
+            .args(&self.config.args)
+            .env("CONDUIT_HOOK", self.stage.as_str())
+            .env("CONDUIT_SESSION_ID", ctx.session_id.to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(dir) = ctx.working_dir.as_ref().filter(|dir| dir.is_dir()) {
+            command.current_dir(dir);
+        }
+        let mut child = command.spawn().map_err(|source| HookError::Spawn {
+            command: self.config.command.clone(),
+            source,
+        })?;
+
+        // Write the payload while reading the output: a hook that echoes its
+        // input back would otherwise fill its output pipe and stop reading
+        // stdin. The timeout covers both. Dropping stdin after the write
+        // closes the pipe, so the hook sees EOF.
+        let stdin = child.stdin.take();
+        let write = async move {
+            let Some(mut stdin) = stdin else {
+                return Ok(());
+            };
+            match stdin.write_all(&input).await {
+                // Hooks that don't care about the payload may exit without reading it
+                Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+                result => result,
+            }
+        };
+        let run = async move {
+            let (written, output) = tokio::join!(write, child.wait_with_output());
+            written?;
+            output
+        };
+        let output = tokio::time::timeout(self.config.timeout, run)
+            .await
+            .map_err(|_| HookError::Timeout {
+                command: self.config.command.clone(),
+                secs: self.config.timeout.as_secs(),
+            })??;
+        Ok(ShellOutput {
+            success: output.status.success(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl PromptHook for ShellHook {
+    fn name(&self) -> &str {
+        &self.config.command
+    }
+
+    fn handles(&self, stage: HookStage) -> bool {
+        stage == self.stage
+    }
+
+    async fn pre_submit(&self, ctx: &HookContext, prompt: String) -> Result<PreSubmit, HookError> {
+        if self.stage != HookStage::PreSubmit {
+            return Ok(PreSubmit::Continue(prompt));
+        }
+        let payload = ShellPayload {
+            hook: HookStage::PreSubmit,
+            context: ctx,
+            prompt: Some(&prompt),
+            turn: None,
+        };
+        let output = self.run(ctx, &payload).await?;
+        if !output.success {
+            let reason = if output.stderr.is_empty() {
+                output.stdout.trim().to_string()
+            } else {
+                output.stderr
+            };
+            return Ok(PreSubmit::Reject(reason));
+        }
+        let rewritten = output.stdout.trim_end_matches(['\r', '\n']);
+        if rewritten.trim().is_empty() {
+            Ok(PreSubmit::Continue(prompt))
+        } else {
+            Ok(PreSubmit::Continue(rewritten.to_string()))
+        }
+    }
+
+    async fn post_turn(&self, ctx: &HookContext, turn: &TurnReport) -> Result<(), HookError> {
+        if self.stage != HookStage::PostTurn {
+            return Ok(());
+        }
+        let payload = ShellPayload {
+            hook: HookStage::PostTurn,
+            context: ctx,
+            prompt: None,
+            turn: Some(turn),
+        };
+        let output = self.run(ctx, &payload).await?;
+        if !output.success {
+            return Err(HookError::Other(format!(
+                "{} failed: {}",
+                self.config.command, output.stderr
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Ordered set of hooks shared by the TUI and web server
+#[derive(Clone, Default)]
+pub struct HookRegistry {
+    hooks: Vec<Arc<dyn PromptHook>>,
+}
+
+impl HookRegistry {
+    pub fn from_config(config: &HooksConfig) -> Self {
+        let mut registry = Self::default();
+        for command in &config.pre_submit {
+            registry.register(Arc::new(ShellHook::new(
+                HookStage::PreSubmit,
+                command.clone(),
+            )));
+        }
+        for command in &config.post_turn {
+            registry.register(Arc::new(ShellHook::new(
+                HookStage::PostTurn,
+                command.clone(),
+            )));
+        }
+        registry
+    }
+
+    /// Add a hook; hooks run in registration order
+    pub fn register(&mut self, hook: Arc<dyn PromptHook>) {
+        self.hooks.push(hook);
+    }
+
+    pub fn has(&self, stage: HookStage) -> bool {
+        self.hooks.iter().any(|hook| hook.handles(stage))
+    }
+
+    /// Pass `prompt` through every pre-submit hook in order. A hook that fails
+    /// to run rejects the prompt rather than letting it through unchecked.
+    pub async fn run_pre_submit(
+        &self,
+        ctx: &HookContext,
+        prompt: String,
+    ) -> Result<String, HookRejection> {
+        let mut prompt = prompt;
+        for hook in self
+            .hooks
+            .iter()
+            .filter(|hook| hook.handles(HookStage::PreSubmit))
+        {
+            match hook.pre_submit(ctx, prompt).await {
+                Ok(PreSubmit::Continue(next)) => prompt = next,
+                Ok(PreSubmit::Reject(reason)) => {
+                    return Err(HookRejection {
+                        hook: hook.name().to_string(),
+                        reason,
+                    });
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        session_id = %ctx.session_id,
+                        hook = hook.name(),
+                        error = %err,
+                        "Pre-submit hook failed"
+                    );
+                    return Err(HookRejection {
+                        hook: hook.name().to_string(),
+                        reason: err.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(prompt)
+    }
+
+    /// Run every post-turn hook; failures are logged and don't stop the rest
+    pub async fn run_post_turn(&self, ctx: &HookContext, turn: &TurnReport) {
+        for hook in self
+            .hooks
+            .iter()
+            .filter(|hook| hook.handles(HookStage::PostTurn))
+        {
+            if let Err(err) = hook.post_turn(ctx, turn).await {
+                tracing::warn!(
+                    session_id = %ctx.session_id,
+                    hook = hook.name(),
+                    error = %err,
+                    "Post-turn hook failed"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn context() -> HookContext {
+        HookContext {
+            session_id: Uuid::new_v4(),
+            agent: "claude".to_string(),
+            model: Some("opus".to_string()),
+            working_dir: None,
+        }
+    }
+
+    fn shell(script: &str) -> HookCommand {
+        HookCommand {
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    struct Tag(&'static str);
+
+    #[async_trait]
+    impl PromptHook for Tag {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        async fn pre_submit(
+            &self,
+            _ctx: &HookContext,
+            prompt: String,
+        ) -> Result<PreSubmit, HookError> {
+            Ok(PreSubmit::Continue(format!("{prompt} [{}]", self.0)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pre_submit_hooks_chain_in_order() {
+        let mut registry = HookRegistry::default();
+        registry.register(Arc::new(Tag("a")));
+        registry.register(Arc::new(Tag("b")));
+
+        let prompt = registry
+            .run_pre_submit(&context(), "fix it".to_string())
+            .await
+            .unwrap();
+        assert_eq!(prompt, "fix it [a] [b]");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_shell_hook_rewrites_or_rejects_prompt() {
+        let config = HooksConfig {
+            pre_submit: vec![shell(
+                r#"grep -q '"prompt":"ship it"' && echo "JIRA-42: ship it""#,
+            )],
+            post_turn: Vec::new(),
+        };
+        let registry = HookRegistry::from_config(&config);
+        assert!(registry.has(HookStage::PreSubmit));
+        assert!(!registry.has(HookStage::PostTurn));
+
+        let prompt = registry
+            .run_pre_submit(&context(), "ship it".to_string())
+            .await
+            .unwrap();
+        assert_eq!(prompt, "JIRA-42: ship it");
+
+        let rejected = registry
+            .run_pre_submit(&context(), "something else".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(rejected.hook, "sh");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_missing_hook_command_rejects_prompt() {
+        let config = HooksConfig {
+            pre_submit: vec![HookCommand {
+                command: "/nonexistent/conduit-hook".to_string(),
+                args: Vec::new(),
+                timeout: Duration::from_secs(5),
+            }],
+            post_turn: Vec::new(),
+        };
+        let registry = HookRegistry::from_config(&config);
+        let rejected = registry
+            .run_pre_submit(&context(), "hi".to_string())
+            .await
+            .unwrap_err();
+        assert!(rejected.reason.contains("failed to run"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_hook_echoing_a_large_payload_does_not_deadlock() {
+        // Echoes the payload to stderr as it reads, so its stderr pipe fills
+        // long before the payload is fully written
+        let config = HooksConfig {
+            pre_submit: vec![shell("cat >&2; echo rewritten")],
+            post_turn: Vec::new(),
+        };
+        let registry = HookRegistry::from_config(&config);
+        let prompt = "x".repeat(1 << 20);
+
+        let rewritten = registry.run_pre_submit(&context(), prompt).await.unwrap();
+        assert_eq!(rewritten, "rewritten");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_hook_that_never_reads_stdin_times_out() {
+        let mut hook = shell("sleep 30");
+        hook.timeout = Duration::from_millis(200);
+        let config = HooksConfig {
+            pre_submit: vec![hook],
+            post_turn: Vec::new(),
+        };
+        let registry = HookRegistry::from_config(&config);
+        // Far more than a pipe buffer, so the write can't finish
+        let prompt = "x".repeat(1 << 20);
+
+        let rejected = tokio::time::timeout(
+            Duration::from_secs(5),
+            registry.run_pre_submit(&context(), prompt),
+        )
+        .await
+        .expect("hook timeout should cover the stdin write")
+        .unwrap_err();
+        assert!(rejected.reason.contains("timed out"));
+    }
+}
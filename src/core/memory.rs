@@ -0,0 +1,284 @@
+//! Shared workspace memory synced into each agent's native memory file.
+//!
+//! Conduit keeps one shared context per workspace and writes it into
+//! `CLAUDE.md` and `AGENTS.md` between marker comments, so whatever else the
+//! files contain is left alone. Syncing is planned first; the plan carries a
+//! line diff per file that is shown before anything is written.
+
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::agent::edit_review::diff_hunks;
+
+/// Native memory files the shared context is synced into
+pub const MEMORY_FILES: [&str; 2] = ["CLAUDE.md", "AGENTS.md"];
+
+const BLOCK_START: &str = "<!-- conduit:memory:start -->";
+const BLOCK_END: &str = "<!-- conduit:memory:end -->";
+
+#[derive(Debug, Error)]
+pub enum MemoryError {
+    #[error("failed to read {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("failed to write {}: {source}", path.display())]
+    Write {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// One line of a memory file diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Context(String),
+    Added(String),
+    Removed(String),
+}
+
+/// Planned update of one memory file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryFilePlan {
+    pub file_name: &'static str,
+    pub path: PathBuf,
+    /// File contents today (None = the file doesn't exist yet)
+    pub current: Option<String>,
+    pub proposed: String,
+}
+
+impl MemoryFilePlan {
+    pub fn is_unchanged(&self) -> bool {
+        self.current.as_deref() == Some(self.proposed.as_str())
+    }
+
+    /// Whether a sync writes this file; a missing file the sync would leave
+    /// empty isn't created
+    pub fn needs_write(&self) -> bool {
+        !self.is_unchanged() && !(self.current.is_none() && self.proposed.is_empty())
+    }
+
+    pub fn diff(&self) -> Vec<DiffLine> {
+        line_diff(self.current.as_deref().unwrap_or_default(), &self.proposed)
+    }
+}
+
+/// The shared context currently synced into `contents`, if it has a managed block
+pub fn managed_block(contents: &str) -> Option<&str> {
+    let start = contents.find(BLOCK_START)? + BLOCK_START.len();
+    let end = start + contents[start..].find(BLOCK_END)?;
+    Some(contents[start..end].trim_matches('\n'))
+}
+
+/// `existing` with its managed block replaced by `shared`, or the block
+/// appended when the file has none. An empty `shared` removes the block.
+pub fn with_managed_block(existing: Option<&str>, shared: &str) -> String {
+    let shared = shared.trim();
+    let block = format!("{BLOCK_START}\n{shared}\n{BLOCK_END}\n");
+    let existing = existing.unwrap_or_default();
+
+    let span = existing.find(BLOCK_START).and_then(|start| {
+        let end = start + existing[start..].find(BLOCK_END)? + BLOCK_END.len();
+        let end = end + usize::from(existing[end..].starts_with('\n'));
+        Some((start, end))
+    });
+    match span {
+        Some((start, end)) if shared.is_empty() => {
+            format!("{}{}", &existing[..start], &existing[end..])
+        }
+        Some((start, end)) => format!("{}{block}{}", &existing[..start], &existing[end..]),
+        None if shared.is_empty() => existing.to_string(),
+        None if existing.trim().is_empty() => block,
+        None => format!("{}\n\n{block}", existing.trim_end()),
+    }
+}
+
+/// The shared context already synced into the first memory file that has a
+/// managed block
+pub fn synced_content(plans: &[MemoryFilePlan]) -> Option<&str> {
+    plans
+        .iter()
+        .find_map(|plan| plan.current.as_deref().and_then(managed_block))
+}
+
+/// Work out how each memory file in `workspace` changes when `shared` is synced
+pub fn plan_sync(workspace: &Path, shared: &str) -> Result<Vec<MemoryFilePlan>, MemoryError> {
+    MEMORY_FILES
+        .iter()
+        .map(|file_name| {
+            let path = workspace.join(file_name);
+            let current = match std::fs::read_to_string(&path) {
+                Ok(contents) => Some(contents),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+                Err(source) => return Err(MemoryError::Read { path, source }),
+            };
+            let proposed = with_managed_block(current.as_deref(), shared);
+            Ok(MemoryFilePlan {
+                file_name,
+                path,
+                current,
+                proposed,
+            })
+        })
+        .collect()
+}
+
+/// Write the files a plan changes, returning their names
+pub fn apply_sync(plans: &[MemoryFilePlan]) -> Result<Vec<&'static str>, MemoryError> {
+    let mut written = Vec::new();
+    for plan in plans.iter().filter(|plan| plan.needs_write()) {
+        std::fs::write(&plan.path, &plan.proposed).map_err(|source| MemoryError::Write {
+            path: plan.path.clone(),
+            source,
+        })?;
+        written.push(plan.file_name);
+    }
+    Ok(written)
+}
+
+/// Line diff of `old` and `new`, from the same size-capped differ as edit
+/// review
+pub fn line_diff(old: &str, new: &str) -> Vec<DiffLine> {
+    // A missing final newline shouldn't turn the last line into a change
+    let terminated = |text: &str| {
+        if text.is_empty() || text.ends_with('\n') {
+            text.to_string()
+        } else {
+            format!("{text}\n")
+        }
+    };
+    let unchanged: Vec<&str> = old.lines().collect();
+    let line = |text: &String| text.trim_end_matches(['\n', '\r']).to_string();
+
+    let mut diff = Vec::new();
+    let mut next = 0;
+    for hunk in diff_hunks(&terminated(old), &terminated(new)) {
+        diff.extend(
+            unchanged[next..hunk.old_start]
+                .iter()
+                .map(|text| DiffLine::Context(text.to_string())),
+        );
+        diff.extend(
+            hunk.old_lines
+                .iter()
+                .map(|text| DiffLine::Removed(line(text))),
+        );
+        diff.extend(
+            hunk.new_lines
+                .iter()
+                .map(|text| DiffLine::Added(line(text))),
+        );
+        next = hunk.old_start + hunk.old_lines.len();
+    }
+    diff.extend(
+        unchanged[next..]
+            .iter()
+            .map(|text| DiffLine::Context(text.to_string())),
+    );
+    diff
+}
+
+/// Unified-style rendering of a diff, keeping `context` unchanged lines
+/// around each change
+pub fn format_diff(diff: &[DiffLine], context: usize) -> String {
+    let near_change = |idx: usize| {
+        let start = idx.saturating_sub(context);
+        let end = (idx + context + 1).min(diff.len());
+        diff[start..end]
+            .iter()
+            .any(|line| !matches!(line, DiffLine::Context(_)))
+    };
+    let mut out = String::new();
+    let mut skipped = false;
+    for (idx, line) in diff.iter().enumerate() {
+        let (prefix, text) = match line {
+            DiffLine::Context(_) if !near_change(idx) => {
+                skipped = true;
+                continue;
+            }
+            DiffLine::Context(text) => (' ', text),
+            DiffLine::Added(text) => ('+', text),
+            DiffLine::Removed(text) => ('-', text),
+        };
+        if skipped {
+            out.push_str("…\n");
+            skipped = false;
+        }
+        out.push(prefix);
+        out.push_str(text);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_managed_block_is_replaced_in_place() {
+        let existing = "# Project\n\nUse tabs.\n";
+        let first = with_managed_block(Some(existing), "Run cargo test");
+        assert_eq!(
+            first,
+            "# Project\n\nUse tabs.\n\n<!-- conduit:memory:start -->\nRun cargo test\n<!-- conduit:memory:end -->\n"
+        );
+        assert_eq!(managed_block(&first), Some("Run cargo test"));
+
+        let edited = format!("{first}\nNotes kept by hand.\n");
+        let second = with_managed_block(Some(&edited), "Run cargo nextest");
+        assert!(second.starts_with("# Project\n\nUse tabs.\n"));
+        assert!(second.ends_with("\nNotes kept by hand.\n"));
+        assert_eq!(managed_block(&second), Some("Run cargo nextest"));
+
+        assert_eq!(
+            with_managed_block(Some(&second), ""),
+            "# Project\n\nUse tabs.\n\n\nNotes kept by hand.\n"
+        );
+    }
+
+    #[test]
+    fn test_plan_previews_and_writes_only_changed_files() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("CLAUDE.md"), "# Claude\n").unwrap();
+
+        let plans = plan_sync(dir.path(), "Prefer small commits").unwrap();
+        assert_eq!(plans.len(), 2);
+        assert_eq!(
+            plans[0].diff().first(),
+            Some(&DiffLine::Context("# Claude".to_string()))
+        );
+        assert!(plans[1].current.is_none());
+        assert!(plans[1]
+            .diff()
+            .contains(&DiffLine::Added("Prefer small commits".to_string())));
+
+        assert_eq!(apply_sync(&plans).unwrap(), vec!["CLAUDE.md", "AGENTS.md"]);
+        let agents = std::fs::read_to_string(dir.path().join("AGENTS.md")).unwrap();
+        assert_eq!(managed_block(&agents), Some("Prefer small commits"));
+
+        let replanned = plan_sync(dir.path(), "Prefer small commits").unwrap();
+        assert!(replanned.iter().all(MemoryFilePlan::is_unchanged));
+        assert!(apply_sync(&replanned).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_format_diff_elides_distant_context() {
+        let old = "a\nb\nc\nd\ne\nf";
+        let new = "a\nb\nc\nd\ne\nF";
+        assert_eq!(format_diff(&line_diff(old, new), 1), "…\n e\n-f\n+F\n");
+    }
+
+    #[test]
+    fn test_line_diff_of_large_rewrite_stays_bounded() {
+        let old: String = (0..3000).map(|i| format!("old {i}\n")).collect();
+        let new: String = (0..3000).map(|i| format!("new {i}\n")).collect();
+        let diff = line_diff(&old, &new);
+        assert_eq!(diff.len(), 6000);
+        assert_eq!(diff[0], DiffLine::Removed("old 0".to_string()));
+        assert_eq!(diff[3000], DiffLine::Added("new 0".to_string()));
+    }
+}
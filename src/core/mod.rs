@@ -6,10 +6,23 @@
 //! - Configuration and tool availability
 //! - Worktree management
 
+pub mod billing;
+pub mod bootstrap;
+pub mod change_guard;
 mod conduit_core;
+pub mod coverage;
+pub mod dep_update;
 pub mod dto;
+pub mod hooks;
+pub mod memory;
+pub mod previews;
 mod repo_settings;
+pub mod security_scan;
 pub mod services;
+pub mod usage_stats;
+pub mod write_behind;
 
 pub use conduit_core::ConduitCore;
-pub use repo_settings::{resolve_repo_workspace_settings, RepoWorkspaceSettings};
+pub use repo_settings::{
+    resolve_repo_execution_target, resolve_repo_workspace_settings, RepoWorkspaceSettings,
+};
@@ -0,0 +1,392 @@
+//! Dev server previews per workspace.
+//!
+//! Tool output is scanned for dev server announcements (see
+//! [`crate::util::preview`]); each new port becomes a [`Preview`] on the
+//! workspace. When agents run in a container, or previews should be reachable
+//! from other machines, a preview can be forwarded: a local listener relays
+//! connections to the server's address. Previews whose server stops accepting
+//! connections are dropped by [`PreviewRegistry::prune`].
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::process::Command;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::agent::exec_target::DOCKER_WORKDIR_LABEL;
+use crate::agent::{AgentEvent, ExecutionTarget};
+use crate::config::PreviewConfig;
+use crate::util::preview::DetectedPreview;
+
+/// How long a liveness probe waits for the dev server to accept
+const PROBE_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// Container label the devcontainer CLI sets to the workspace folder
+const DEVCONTAINER_FOLDER_LABEL: &str = "devcontainer.local_folder";
+
+/// A dev server running in a workspace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Preview {
+    pub port: u16,
+    /// URL as announced by the server, with the host normalized to localhost
+    pub url: String,
+    /// URL of the local relay when the preview is forwarded
+    pub forwarded_url: Option<String>,
+    /// Session whose tool output announced the server
+    pub session_id: Uuid,
+}
+
+impl Preview {
+    /// URL to open in a browser: the forwarded one when forwarding is active
+    pub fn open_url(&self) -> &str {
+        self.forwarded_url.as_deref().unwrap_or(&self.url)
+    }
+}
+
+struct Entry {
+    preview: Preview,
+    /// Where the server can be reached for liveness probes (unknown for
+    /// container servers until they are forwarded)
+    probe_addr: Option<SocketAddr>,
+    /// Stops the relay of a forwarded preview
+    forward: Option<CancellationToken>,
+}
+
+impl Drop for Entry {
+    fn drop(&mut self) {
+        if let Some(forward) = &self.forward {
+            forward.cancel();
+        }
+    }
+}
+
+/// Previews of all workspaces; cheap to clone and shared between tasks
+#[derive(Clone, Default)]
+pub struct PreviewRegistry {
+    workspaces: Arc<Mutex<HashMap<Uuid, Vec<Entry>>>>,
+}
+
+impl PreviewRegistry {
+    /// Record detected servers; returns the previews that are new to the workspace
+    pub fn record(
+        &self,
+        workspace_id: Uuid,
+        session_id: Uuid,
+        target: &ExecutionTarget,
+        detected: Vec<DetectedPreview>,
+    ) -> Vec<Preview> {
+        let mut workspaces = self.workspaces.lock();
+        let entries = workspaces.entry(workspace_id).or_default();
+        let mut added = Vec::new();
+        for detected in detected {
+            if entries
+                .iter()
+                .any(|entry| entry.preview.port == detected.port)
+            {
+                continue;
+            }
+            let preview = Preview {
+                port: detected.port,
+                url: detected.url,
+                forwarded_url: None,
+                session_id,
+            };
+            entries.push(Entry {
+                preview: preview.clone(),
+                probe_addr: matches!(target, ExecutionTarget::Host)
+                    .then(|| SocketAddr::from((Ipv4Addr::LOCALHOST, detected.port))),
+                forward: None,
+            });
+            added.push(preview);
+        }
+        added
+    }
+
+    /// Previews of a workspace, oldest first
+    pub fn list(&self, workspace_id: Uuid) -> Vec<Preview> {
+        self.workspaces
+            .lock()
+            .get(&workspace_id)
+            .map(|entries| entries.iter().map(|entry| entry.preview.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Workspaces that currently have previews
+    pub fn workspace_ids(&self) -> Vec<Uuid> {
+        self.workspaces
+            .lock()
+            .iter()
+            .filter(|(_, entries)| !entries.is_empty())
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Drop previews whose server no longer accepts connections, stopping
+    /// their relays; returns the remaining previews
+    pub async fn prune(&self, workspace_id: Uuid) -> Vec<Preview> {
+        let probes: Vec<(u16, SocketAddr)> = self
+            .workspaces
+            .lock()
+            .get(&workspace_id)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| Some((entry.preview.port, entry.probe_addr?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut stopped = Vec::new();
+        for (port, addr) in probes {
+            if !is_listening(addr).await {
+                stopped.push(port);
+            }
+        }
+
+        let mut workspaces = self.workspaces.lock();
+        let Some(entries) = workspaces.get_mut(&workspace_id) else {
+            return Vec::new();
+        };
+        entries.retain(|entry| !stopped.contains(&entry.preview.port));
+        let remaining = entries.iter().map(|entry| entry.preview.clone()).collect();
+        if entries.is_empty() {
+            workspaces.remove(&workspace_id);
+        }
+        remaining
+    }
+
+    /// Attach a running relay to a preview; the relay is stopped if the
+    /// preview was pruned in the meantime
+    fn attach_forward(
+        &self,
+        workspace_id: Uuid,
+        port: u16,
+        forwarded_url: String,
+        upstream: SocketAddr,
+        cancel: CancellationToken,
+    ) -> Option<Preview> {
+        let mut workspaces = self.workspaces.lock();
+        let Some(entry) = workspaces
+            .get_mut(&workspace_id)
+            .and_then(|entries| entries.iter_mut().find(|e| e.preview.port == port))
+        else {
+            cancel.cancel();
+            return None;
+        };
+        if let Some(previous) = entry.forward.replace(cancel) {
+            previous.cancel();
+        }
+        entry.probe_addr = Some(upstream);
+        entry.preview.forwarded_url = Some(forwarded_url);
+        Some(entry.preview.clone())
+    }
+}
+
+/// Tool output in an agent event that may announce a dev server
+pub fn event_output(event: &AgentEvent) -> Option<&str> {
+    match event {
+        AgentEvent::CommandOutput(cmd) => Some(&cmd.output),
+        AgentEvent::CommandOutputDelta(delta) => Some(&delta.chunk),
+        AgentEvent::ToolCompleted(tool) if tool.success => tool.result.as_deref(),
+        _ => None,
+    }
+}
+
+/// Whether previews on `target` need a relay to be reachable
+pub fn needs_forward(config: &PreviewConfig, target: &ExecutionTarget) -> bool {
+    config.forward && (!matches!(target, ExecutionTarget::Host) || !is_loopback(&config.bind))
+}
+
+fn is_loopback(bind: &str) -> bool {
+    bind == "localhost" || bind.parse::<IpAddr>().is_ok_and(|addr| addr.is_loopback())
+}
+
+/// Start relaying `config.bind` to the preview on `port` and attach the relay
+/// to the registered preview. `workdir` identifies the workspace's container.
+pub async fn forward_preview(
+    registry: &PreviewRegistry,
+    config: &PreviewConfig,
+    target: &ExecutionTarget,
+    workspace_id: Uuid,
+    workdir: &Path,
+    port: u16,
+) -> Result<Option<Preview>, String> {
+    let upstream = upstream_addr(target, workdir, port).await?;
+    let listener = TcpListener::bind((config.bind.as_str(), 0))
+        .await
+        .map_err(|e| format!("Failed to listen on {}: {}", config.bind, e))?;
+    let local = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to read relay address: {}", e))?;
+    let host = match &config.host {
+        Some(host) => host.clone(),
+        None if local.ip().is_unspecified() || local.ip().is_loopback() => "localhost".to_string(),
+        None => local.ip().to_string(),
+    };
+
+    let Some(url) = registry
+        .list(workspace_id)
+        .into_iter()
+        .find(|preview| preview.port == port)
+        .map(|preview| preview.url)
+    else {
+        return Ok(None);
+    };
+    let forwarded_url = url.replacen(
+        &format!("localhost:{}", port),
+        &format!("{}:{}", host, local.port()),
+        1,
+    );
+
+    let cancel = CancellationToken::new();
+    spawn_relay(listener, upstream, cancel.clone());
+    Ok(registry.attach_forward(workspace_id, port, forwarded_url, upstream, cancel))
+}
+
+fn spawn_relay(listener: TcpListener, upstream: SocketAddr, cancel: CancellationToken) {
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((mut inbound, _)) => {
+                        tokio::spawn(async move {
+                            let relayed = match TcpStream::connect(upstream).await {
+                                Ok(mut outbound) => {
+                                    tokio::io::copy_bidirectional(&mut inbound, &mut outbound)
+                                        .await
+                                        .map(|_| ())
+                                }
+                                Err(err) => Err(err),
+                            };
+                            if let Err(err) = relayed {
+                                tracing::debug!(%upstream, error = %err, "Preview relay connection ended");
+                            }
+                        });
+                    }
+                    Err(err) => {
+                        tracing::warn!(%upstream, error = %err, "Preview relay stopped accepting");
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Address the dev server listens on, as seen from this machine
+async fn upstream_addr(
+    target: &ExecutionTarget,
+    workdir: &Path,
+    port: u16,
+) -> Result<SocketAddr, String> {
+    let label = match target {
+        ExecutionTarget::Host => return Ok(SocketAddr::from((Ipv4Addr::LOCALHOST, port))),
+        ExecutionTarget::Docker { .. } => DOCKER_WORKDIR_LABEL,
+        ExecutionTarget::DevContainer => DEVCONTAINER_FOLDER_LABEL,
+    };
+    let filter = format!("label={}={}", label, workdir.display());
+    let ids = docker_output(&["ps", "-q", "--filter", &filter]).await?;
+    let id = ids
+        .lines()
+        .next()
+        .ok_or_else(|| format!("No running container for {}", workdir.display()))?;
+    let ips = docker_output(&[
+        "inspect",
+        "-f",
+        "{{range .NetworkSettings.Networks}}{{.IPAddress}} {{end}}",
+        id,
+    ])
+    .await?;
+    // Host-networked containers have no address of their own
+    let ip = ips
+        .split_whitespace()
+        .find_map(|ip| ip.parse::<IpAddr>().ok())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    Ok(SocketAddr::new(ip, port))
+}
+
+async fn docker_output(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("docker")
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run docker: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "docker {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+async fn is_listening(addr: SocketAddr) -> bool {
+    matches!(
+        tokio::time::timeout(PROBE_TIMEOUT, TcpStream::connect(addr)).await,
+        Ok(Ok(_))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::preview::detect_previews;
+
+    #[tokio::test]
+    async fn test_records_new_ports_and_prunes_stopped_servers() {
+        let registry = PreviewRegistry::default();
+        let workspace_id = Uuid::new_v4();
+        let session_id = Uuid::new_v4();
+        let server = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = server.local_addr().unwrap().port();
+
+        let announced = format!("Listening on http://127.0.0.1:{}/", port);
+        let added = registry.record(
+            workspace_id,
+            session_id,
+            &ExecutionTarget::Host,
+            detect_previews(&announced),
+        );
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].url, format!("http://localhost:{}/", port));
+        // The same server announced again isn't new
+        assert!(registry
+            .record(
+                workspace_id,
+                session_id,
+                &ExecutionTarget::Host,
+                detect_previews(&announced),
+            )
+            .is_empty());
+
+        assert_eq!(registry.prune(workspace_id).await.len(), 1);
+        drop(server);
+        assert!(registry.prune(workspace_id).await.is_empty());
+        assert!(registry.workspace_ids().is_empty());
+    }
+
+    #[test]
+    fn test_forwarding_only_when_needed() {
+        let mut config = PreviewConfig::default();
+        let docker = ExecutionTarget::Docker {
+            image: "dev".to_string(),
+            mounts: Vec::new(),
+            docker_args: Vec::new(),
+        };
+        assert!(!needs_forward(&config, &docker));
+        config.forward = true;
+        assert!(needs_forward(&config, &docker));
+        assert!(!needs_forward(&config, &ExecutionTarget::Host));
+        config.bind = "0.0.0.0".to_string();
+        assert!(needs_forward(&config, &ExecutionTarget::Host));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::agent::ExecutionTarget;
 use crate::config::Config;
 use crate::data::Repository;
 use crate::git::WorkspaceMode;
@@ -21,3 +22,12 @@ pub fn resolve_repo_workspace_settings(
             .archive_remote_prompt_or(config.workspaces.archive_remote_prompt),
     }
 }
+
+/// Where agents for `repo` run; repositories without an `[execution]` entry use the host
+pub fn resolve_repo_execution_target(config: &Config, repo: &Repository) -> ExecutionTarget {
+    config
+        .execution
+        .get(&repo.name)
+        .cloned()
+        .unwrap_or_default()
+}
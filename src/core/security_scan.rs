@@ -0,0 +1,564 @@
+//! Security scans run before PR creation.
+//!
+//! Optional scanners check the workspace during the PR preflight: cargo-audit
+//! for Rust dependencies, npm audit for Node dependencies and gitleaks for
+//! secrets in the branch's commits. Findings at or above the configured
+//! blocking severity stop PR creation until they're fixed; lower ones are
+//! shown as warnings.
+
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::config::SecurityScanConfig;
+
+/// Lines of a failed scanner's stderr kept in the report
+const STDERR_TAIL_LINES: usize = 10;
+
+/// Scanner that can run during the PR preflight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scanner {
+    CargoAudit,
+    NpmAudit,
+    Gitleaks,
+}
+
+impl Scanner {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::CargoAudit => "cargo-audit",
+            Self::NpmAudit => "npm audit",
+            Self::Gitleaks => "gitleaks",
+        }
+    }
+
+    /// Whether the workspace has anything for this scanner to check
+    fn applies(self, dir: &Path) -> bool {
+        match self {
+            Self::CargoAudit => dir.join("Cargo.lock").is_file(),
+            Self::NpmAudit => dir.join("package-lock.json").is_file(),
+            Self::Gitleaks => dir.join(".git").exists(),
+        }
+    }
+
+    pub fn install_hint(self) -> &'static str {
+        match self {
+            Self::CargoAudit => "install it with `cargo install cargo-audit`",
+            Self::NpmAudit => "install Node.js to get npm",
+            Self::Gitleaks => "see https://github.com/gitleaks/gitleaks#installing",
+        }
+    }
+}
+
+/// Severity of a finding, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    /// Parse a severity label, including npm's `info` and `moderate`
+    pub fn parse(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "info" | "low" => Some(Self::Low),
+            "moderate" | "medium" => Some(Self::Medium),
+            "high" => Some(Self::High),
+            "critical" => Some(Self::Critical),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One issue reported by a scanner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub scanner: Scanner,
+    pub severity: Severity,
+    /// Advisory or rule ID, e.g. `RUSTSEC-2024-0001` or `aws-access-token`
+    pub id: String,
+    pub title: String,
+    /// Affected package and version, or file and line
+    pub location: String,
+    /// How to fix it, when the scanner knows
+    pub fix: Option<String>,
+}
+
+impl Finding {
+    fn line(&self) -> String {
+        let mut line = format!(
+            "[{}] {} {}: {} ({})",
+            self.severity,
+            self.scanner.display_name(),
+            self.id,
+            self.title,
+            self.location
+        );
+        if let Some(fix) = &self.fix {
+            line.push_str(&format!(". Fix: {fix}"));
+        }
+        line
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanOutcome {
+    Findings(Vec<Finding>),
+    NotInstalled,
+    Failed(String),
+}
+
+/// What one scanner reported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScannerResult {
+    pub scanner: Scanner,
+    /// Command line shown with the result
+    pub command: String,
+    pub outcome: ScanOutcome,
+}
+
+/// Results of the configured scanners and the thresholds they're judged by
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityScanReport {
+    pub results: Vec<ScannerResult>,
+    /// Findings at or above this severity block PR creation (None = never)
+    pub block_at: Option<Severity>,
+    /// Findings below this severity aren't shown
+    pub warn_at: Severity,
+}
+
+impl SecurityScanReport {
+    fn findings(&self) -> impl Iterator<Item = &Finding> {
+        self.results
+            .iter()
+            .filter_map(|result| match &result.outcome {
+                ScanOutcome::Findings(findings) => Some(findings),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    fn is_blocking(&self, finding: &Finding) -> bool {
+        self.block_at
+            .is_some_and(|block_at| finding.severity >= block_at)
+    }
+
+    /// Findings that stop PR creation
+    pub fn blocking(&self) -> Vec<&Finding> {
+        self.findings()
+            .filter(|finding| self.is_blocking(finding))
+            .collect()
+    }
+
+    /// Findings shown but not blocking
+    pub fn warnings(&self) -> Vec<&Finding> {
+        self.findings()
+            .filter(|finding| finding.severity >= self.warn_at && !self.is_blocking(finding))
+            .collect()
+    }
+
+    /// Chat output for one scanner's findings and whether any are shown
+    pub fn result_output(&self, result: &ScannerResult) -> (String, bool) {
+        let ScanOutcome::Findings(findings) = &result.outcome else {
+            return (String::new(), false);
+        };
+        let mut shown: Vec<&Finding> = findings
+            .iter()
+            .filter(|finding| finding.severity >= self.warn_at)
+            .collect();
+        shown.sort_by(|a, b| b.severity.cmp(&a.severity));
+        let mut lines: Vec<String> = shown
+            .iter()
+            .map(|finding| {
+                let marker = if self.is_blocking(finding) {
+                    "blocking"
+                } else {
+                    "warning"
+                };
+                format!("{marker}: {}", finding.line())
+            })
+            .collect();
+        let hidden = findings.len() - shown.len();
+        if hidden > 0 {
+            lines.push(format!(
+                "{hidden} finding(s) below {} severity not shown",
+                self.warn_at
+            ));
+        }
+        if findings.is_empty() {
+            lines.push("No findings".to_string());
+        }
+        (lines.join("\n"), !shown.is_empty())
+    }
+
+    /// Prompt asking the agent to fix the blocking and warning findings
+    pub fn fix_prompt(&self) -> String {
+        let mut findings = self.blocking();
+        findings.extend(self.warnings());
+        let list: Vec<String> = findings
+            .iter()
+            .map(|finding| format!("- {}", finding.line()))
+            .collect();
+        format!(
+            "Security scanners found these issues in the workspace before a pull request was created:
+
+{}
+
+Fix them: upgrade vulnerable dependencies to patched versions (updating the lockfiles), and remove leaked secrets from the code, reading them from the environment or configuration instead. Leaked secrets are already in the branch's commits, so rewrite those commits without the secret before anything is pushed, and tell the user to rotate it. Don't push or create the PR. Run the tests, then summarize which findings were fixed and which couldn't be.",
+            list.join("\n")
+        )
+    }
+}
+
+/// Run the configured scanners on the workspace in `dir`. `target_branch`
+/// bounds the commits gitleaks checks. None when no scanners are configured.
+pub fn scan(
+    dir: &Path,
+    target_branch: &str,
+    config: &SecurityScanConfig,
+) -> Option<SecurityScanReport> {
+    if config.scanners.is_empty() {
+        return None;
+    }
+    let results = config
+        .scanners
+        .iter()
+        .filter(|scanner| scanner.applies(dir))
+        .map(|&scanner| run_scanner(scanner, dir, target_branch))
+        .collect();
+    Some(SecurityScanReport {
+        results,
+        block_at: config.block_at,
+        warn_at: config.warn_at,
+    })
+}
+
+fn run_scanner(scanner: Scanner, dir: &Path, target_branch: &str) -> ScannerResult {
+    let outcome = match scanner {
+        Scanner::CargoAudit => run_json(dir, &["cargo", "audit", "--json"])
+            .map(|output| parse_scanner_output(&output, parse_cargo_audit)),
+        Scanner::NpmAudit => run_json(dir, &["npm", "audit", "--json"])
+            .map(|output| parse_scanner_output(&output, parse_npm_audit)),
+        Scanner::Gitleaks => run_gitleaks(dir, target_branch),
+    };
+    let outcome = outcome.unwrap_or_else(|err| {
+        if err.kind() == ErrorKind::NotFound {
+            ScanOutcome::NotInstalled
+        } else {
+            ScanOutcome::Failed(err.to_string())
+        }
+    });
+    if let ScanOutcome::Failed(err) = &outcome {
+        tracing::warn!(scanner = scanner.display_name(), error = %err, "Security scanner failed");
+    }
+    ScannerResult {
+        scanner,
+        command: scanner_command(scanner, target_branch),
+        outcome,
+    }
+}
+
+fn scanner_command(scanner: Scanner, target_branch: &str) -> String {
+    match scanner {
+        Scanner::CargoAudit => "cargo audit --json".to_string(),
+        Scanner::NpmAudit => "npm audit --json".to_string(),
+        Scanner::Gitleaks => format!("gitleaks detect --log-opts={target_branch}..HEAD"),
+    }
+}
+
+/// Run a scanner that prints JSON. Both scanners exit non-zero when they find
+/// something, so the exit status alone doesn't mean the run failed.
+fn run_json(dir: &Path, args: &[&str]) -> std::io::Result<Output> {
+    let output = Command::new(args[0])
+        .args(&args[1..])
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .output()?;
+    // Cargo itself exists even when the audit subcommand doesn't
+    if String::from_utf8_lossy(&output.stderr).contains("no such command") {
+        return Err(ErrorKind::NotFound.into());
+    }
+    Ok(output)
+}
+
+fn parse_scanner_output(output: &Output, parse: fn(&str) -> Option<Vec<Finding>>) -> ScanOutcome {
+    match parse(&String::from_utf8_lossy(&output.stdout)) {
+        Some(findings) => ScanOutcome::Findings(findings),
+        None => ScanOutcome::Failed(format!(
+            "exited with {} and no report:\n{}",
+            output.status,
+            tail(&String::from_utf8_lossy(&output.stderr))
+        )),
+    }
+}
+
+fn run_gitleaks(dir: &Path, target_branch: &str) -> std::io::Result<ScanOutcome> {
+    let report = tempfile::NamedTempFile::new()?;
+    // `--exit-code 0` keeps leaks from looking like a failed run
+    let output = Command::new("gitleaks")
+        .arg("detect")
+        .arg("--no-banner")
+        .arg("--redact")
+        .args(["--exit-code", "0", "--report-format", "json"])
+        .arg("--report-path")
+        .arg(report.path())
+        .arg(format!("--log-opts={target_branch}..HEAD"))
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        return Ok(ScanOutcome::Failed(format!(
+            "exited with {}:\n{}",
+            output.status,
+            tail(&String::from_utf8_lossy(&output.stderr))
+        )));
+    }
+    let json = std::fs::read_to_string(report.path())?;
+    Ok(match parse_gitleaks(&json) {
+        Some(findings) => ScanOutcome::Findings(findings),
+        None => ScanOutcome::Failed("unreadable gitleaks report".to_string()),
+    })
+}
+
+fn tail(text: &str) -> String {
+    let lines: Vec<&str> = text.trim().lines().collect();
+    lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n")
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+/// `cargo audit --json`. Advisories carry no severity of their own, so
+/// vulnerabilities count as high; unsound crates are medium and other
+/// warnings (unmaintained, yanked) low.
+pub fn parse_cargo_audit(json: &str) -> Option<Vec<Finding>> {
+    let report: Value = serde_json::from_str(json.trim()).ok()?;
+    let package = |item: &Value| {
+        let package = item.get("package").unwrap_or(&Value::Null);
+        format!(
+            "{} {}",
+            str_field(package, "name"),
+            str_field(package, "version")
+        )
+    };
+    let mut findings: Vec<Finding> = report
+        .get("vulnerabilities")?
+        .get("list")?
+        .as_array()?
+        .iter()
+        .map(|item| {
+            let advisory = item.get("advisory").unwrap_or(&Value::Null);
+            let patched: Vec<&str> = item
+                .pointer("/versions/patched")
+                .and_then(Value::as_array)
+                .map(|versions| versions.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            Finding {
+                scanner: Scanner::CargoAudit,
+                severity: Severity::High,
+                id: str_field(advisory, "id").to_string(),
+                title: str_field(advisory, "title").to_string(),
+                location: package(item),
+                fix: (!patched.is_empty()).then(|| format!("upgrade to {}", patched.join(" or "))),
+            }
+        })
+        .collect();
+
+    if let Some(warnings) = report.get("warnings").and_then(Value::as_object) {
+        for (kind, items) in warnings {
+            let severity = if kind == "unsound" {
+                Severity::Medium
+            } else {
+                Severity::Low
+            };
+            for item in items.as_array().into_iter().flatten() {
+                let advisory = item.get("advisory").unwrap_or(&Value::Null);
+                let id = str_field(advisory, "id");
+                let title = str_field(advisory, "title");
+                findings.push(Finding {
+                    scanner: Scanner::CargoAudit,
+                    severity,
+                    id: if id.is_empty() {
+                        kind.clone()
+                    } else {
+                        id.to_string()
+                    },
+                    title: if title.is_empty() {
+                        format!("{kind} crate")
+                    } else {
+                        title.to_string()
+                    },
+                    location: package(item),
+                    fix: None,
+                });
+            }
+        }
+    }
+    Some(findings)
+}
+
+/// `npm audit --json` (npm 7+): one entry per vulnerable package
+pub fn parse_npm_audit(json: &str) -> Option<Vec<Finding>> {
+    let report: Value = serde_json::from_str(json.trim()).ok()?;
+    let findings = report
+        .get("vulnerabilities")?
+        .as_object()?
+        .iter()
+        .map(|(name, vuln)| {
+            let via = vuln.get("via").and_then(Value::as_array);
+            // `via` holds advisories, or names of the dependencies that
+            // bring one in
+            let advisory = via.and_then(|via| via.iter().find(|entry| entry.is_object()));
+            let (id, title) = match advisory {
+                Some(advisory) => (
+                    str_field(advisory, "url").to_string(),
+                    str_field(advisory, "title").to_string(),
+                ),
+                None => {
+                    let through: Vec<&str> = via
+                        .map(|via| via.iter().filter_map(Value::as_str).collect())
+                        .unwrap_or_default();
+                    (
+                        name.clone(),
+                        format!("vulnerable through {}", through.join(", ")),
+                    )
+                }
+            };
+            let fix = match vuln.get("fixAvailable") {
+                Some(Value::Bool(true)) => Some("run `npm audit fix`".to_string()),
+                Some(fix @ Value::Object(_)) => {
+                    let major = if fix.get("isSemVerMajor").and_then(Value::as_bool) == Some(true) {
+                        " (major version)"
+                    } else {
+                        ""
+                    };
+                    Some(format!(
+                        "upgrade {} to {}{major}",
+                        str_field(fix, "name"),
+                        str_field(fix, "version")
+                    ))
+                }
+                _ => None,
+            };
+            Finding {
+                scanner: Scanner::NpmAudit,
+                severity: Severity::parse(str_field(vuln, "severity")).unwrap_or(Severity::Medium),
+                id,
+                title,
+                location: format!("{name} {}", str_field(vuln, "range")),
+                fix,
+            }
+        })
+        .collect();
+    Some(findings)
+}
+
+/// gitleaks JSON report: every leaked secret is critical
+pub fn parse_gitleaks(json: &str) -> Option<Vec<Finding>> {
+    let report: Value = serde_json::from_str(json.trim()).ok()?;
+    let findings = report
+        .as_array()?
+        .iter()
+        .map(|leak| {
+            let commit: String = str_field(leak, "Commit").chars().take(8).collect();
+            let line = leak.get("StartLine").and_then(Value::as_u64).unwrap_or(0);
+            Finding {
+                scanner: Scanner::Gitleaks,
+                severity: Severity::Critical,
+                id: str_field(leak, "RuleID").to_string(),
+                title: str_field(leak, "Description").to_string(),
+                location: format!("{}:{line} in {commit}", str_field(leak, "File")),
+                fix: Some("remove the secret, rewrite the commit and rotate it".to_string()),
+            }
+        })
+        .collect();
+    Some(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn npm_report(findings: Vec<Finding>) -> SecurityScanReport {
+        SecurityScanReport {
+            results: vec![ScannerResult {
+                scanner: Scanner::NpmAudit,
+                command: "npm audit --json".to_string(),
+                outcome: ScanOutcome::Findings(findings),
+            }],
+            block_at: Some(Severity::High),
+            warn_at: Severity::Medium,
+        }
+    }
+
+    #[test]
+    fn test_parse_npm_audit_and_thresholds() {
+        let json = r#"{"auditReportVersion":2,"vulnerabilities":{
+            "lodash":{"name":"lodash","severity":"critical","via":[{"source":1,"title":"Prototype Pollution","url":"https://github.com/advisories/GHSA-1","severity":"critical"}],"range":"<4.17.21","fixAvailable":true},
+            "express":{"name":"express","severity":"moderate","via":["qs"],"range":"4.0.0 - 4.17.2","fixAvailable":{"name":"express","version":"4.21.0","isSemVerMajor":false}},
+            "debug":{"name":"debug","severity":"low","via":[{"title":"ReDoS","url":"https://github.com/advisories/GHSA-2"}],"range":"<2.6.9","fixAvailable":false}
+        }}"#;
+        let findings = parse_npm_audit(json).unwrap();
+        assert_eq!(findings.len(), 3);
+        let express = findings
+            .iter()
+            .find(|f| f.location.starts_with("express"))
+            .unwrap();
+        assert_eq!(express.severity, Severity::Medium);
+        assert_eq!(express.title, "vulnerable through qs");
+        assert_eq!(express.fix.as_deref(), Some("upgrade express to 4.21.0"));
+
+        let report = npm_report(findings);
+        let blocking: Vec<&str> = report.blocking().iter().map(|f| f.title.as_str()).collect();
+        assert_eq!(blocking, ["Prototype Pollution"]);
+        assert_eq!(report.warnings().len(), 1);
+        let (output, shown) = report.result_output(&report.results[0]);
+        assert!(shown);
+        assert!(output
+            .starts_with("blocking: [critical] npm audit https://github.com/advisories/GHSA-1"));
+        assert!(output.ends_with("1 finding(s) below medium severity not shown"));
+        assert!(!report.fix_prompt().contains("ReDoS"));
+    }
+
+    #[test]
+    fn test_parse_cargo_audit_and_gitleaks() {
+        let json = r#"{"vulnerabilities":{"found":true,"count":1,"list":[
+            {"advisory":{"id":"RUSTSEC-2024-0001","title":"Use after free","cvss":null},"package":{"name":"foo","version":"0.1.0"},"versions":{"patched":[">=0.1.5"]}}
+        ]},"warnings":{"unmaintained":[{"kind":"unmaintained","package":{"name":"bar","version":"1.0.0"},"advisory":{"id":"RUSTSEC-2023-0002","title":"bar is unmaintained"}}]}}"#;
+        let findings = parse_cargo_audit(json).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].severity, Severity::High);
+        assert_eq!(findings[0].location, "foo 0.1.0");
+        assert_eq!(findings[0].fix.as_deref(), Some("upgrade to >=0.1.5"));
+        assert_eq!(findings[1].severity, Severity::Low);
+
+        let json = r#"[{"Description":"AWS Access Key","StartLine":12,"File":"src/config.rs","Commit":"0123456789abcdef","RuleID":"aws-access-token","Secret":"REDACTED"}]"#;
+        let leaks = parse_gitleaks(json).unwrap();
+        assert_eq!(leaks[0].severity, Severity::Critical);
+        assert_eq!(leaks[0].location, "src/config.rs:12 in 01234567");
+        assert_eq!(parse_gitleaks("[]").unwrap(), Vec::new());
+    }
+}
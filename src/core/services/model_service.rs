@@ -12,6 +12,7 @@ impl ModelService {
             AgentType::Codex,
             AgentType::Gemini,
             AgentType::Opencode,
+            AgentType::Local,
         ];
         let (default_agent, default_model) = ConfigService::default_model(core);
 
@@ -2,7 +2,7 @@ use chrono::Utc;
 use rusqlite::{params, Error as SqliteError, ErrorCode, Result as SqliteResult};
 use uuid::Uuid;
 
-use crate::agent::{AgentMode, AgentType, ModelRegistry};
+use crate::agent::{AgentMode, AgentType, CodexApprovalPolicy, CodexSandboxMode, ModelRegistry};
 use crate::core::services::error::ServiceError;
 use crate::core::ConduitCore;
 use crate::data::{
@@ -16,6 +16,8 @@ pub struct CreateSessionParams {
     pub workspace_id: Option<Uuid>,
     pub agent_type: AgentType,
     pub model: Option<String>,
+    /// Base instructions; None falls back to the workspace repository's default
+    pub system_prompt: Option<String>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -23,6 +25,10 @@ pub struct UpdateSessionParams {
     pub model: Option<String>,
     pub agent_type: Option<AgentType>,
     pub agent_mode: Option<AgentMode>,
+    pub codex_sandbox: Option<CodexSandboxMode>,
+    pub codex_approval: Option<CodexApprovalPolicy>,
+    /// Billing tag for the session's spend (empty string clears)
+    pub cost_center: Option<String>,
 }
 
 pub struct SessionService;
@@ -92,7 +98,18 @@ impl SessionService {
             Some(core.config().default_model_for(params.agent_type))
         };
 
-        let session = SessionTab::new(0, params.agent_type, params.workspace_id, None, model, None);
+        let system_prompt = match params.system_prompt {
+            Some(prompt) => Some(prompt.trim().to_string()).filter(|p| !p.is_empty()),
+            None => params
+                .workspace_id
+                .map(|workspace_id| Self::repository_system_prompt(core, workspace_id))
+                .transpose()?
+                .flatten(),
+        };
+
+        let mut session =
+            SessionTab::new(0, params.agent_type, params.workspace_id, None, model, None);
+        session.system_prompt = system_prompt;
 
         let session = store
             .create_with_next_index(session)
@@ -101,6 +118,29 @@ impl SessionService {
         Ok(session)
     }
 
+    /// Default base instructions configured on the repository owning `workspace_id`.
+    fn repository_system_prompt(
+        core: &ConduitCore,
+        workspace_id: Uuid,
+    ) -> Result<Option<String>, ServiceError> {
+        let workspace_store = core
+            .workspace_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+        let repo_store = core
+            .repo_store()
+            .ok_or_else(|| ServiceError::Internal("Database not available".to_string()))?;
+        let Some(workspace) = workspace_store
+            .get_by_id(workspace_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get workspace: {}", e)))?
+        else {
+            return Ok(None);
+        };
+        let repo = repo_store
+            .get_by_id(workspace.repository_id)
+            .map_err(|e| ServiceError::Internal(format!("Failed to get repository: {}", e)))?;
+        Ok(repo.and_then(|repo| repo.system_prompt))
+    }
+
     pub fn update_session(
         core: &ConduitCore,
         id: Uuid,
@@ -123,11 +163,13 @@ impl SessionService {
                 && params.model.is_some()
                 && params.agent_type.is_none()
                 && params.agent_mode.is_none();
-            let allow_opencode_model_change = session.agent_type == AgentType::Opencode
-                && params.model.is_some()
-                && params.agent_type.is_none()
-                && params.agent_mode.is_none();
-            if !allow_model_repair && !allow_opencode_model_change {
+            // These runners take a per-prompt model override
+            let allow_live_model_change =
+                matches!(session.agent_type, AgentType::Opencode | AgentType::Local)
+                    && params.model.is_some()
+                    && params.agent_type.is_none()
+                    && params.agent_mode.is_none();
+            if !allow_model_repair && !allow_live_model_change {
                 return Err(ServiceError::InvalidInput(
                     "Cannot change session settings while a run is active".to_string(),
                 ));
@@ -142,6 +184,25 @@ impl SessionService {
         if agent_type_changed && !session.agent_type.supports_plan_mode() {
             session.agent_mode = None;
         }
+        if agent_type_changed && session.agent_type != AgentType::Codex {
+            session.codex_sandbox = None;
+            session.codex_approval = None;
+        }
+
+        if (params.codex_sandbox.is_some() || params.codex_approval.is_some())
+            && session.agent_type != AgentType::Codex
+        {
+            return Err(ServiceError::InvalidInput(
+                "Sandbox mode and approval policy are only supported for Codex sessions"
+                    .to_string(),
+            ));
+        }
+        if let Some(mode) = params.codex_sandbox {
+            session.codex_sandbox = Some(mode.as_str().to_string());
+        }
+        if let Some(policy) = params.codex_approval {
+            session.codex_approval = Some(policy.as_str().to_string());
+        }
 
         if let Some(agent_mode) = params.agent_mode {
             if !session.agent_type.supports_plan_mode() {
@@ -152,6 +213,11 @@ impl SessionService {
             session.agent_mode = Some(agent_mode.as_str().to_string());
         }
 
+        if let Some(cost_center) = params.cost_center {
+            let cost_center = cost_center.trim();
+            session.cost_center = (!cost_center.is_empty()).then(|| cost_center.to_string());
+        }
+
         if let Some(model_id) = params.model {
             if ModelRegistry::find_model(session.agent_type, &model_id).is_none() {
                 return Err(ServiceError::InvalidInput(format!(
@@ -421,6 +487,13 @@ impl SessionService {
             .update(&session)
             .map_err(|e| ServiceError::Internal(format!("Failed to update session: {}", e)))?;
 
+        if let Some(attachments) = core.attachment_store() {
+            let paths = message.images.iter().map(|img| &img.path);
+            if let Err(e) = attachments.retain_paths(paths) {
+                tracing::warn!(error = %e, session_id = %id, "Failed to retain queued attachments");
+            }
+        }
+
         Ok(message)
     }
 
@@ -503,6 +576,13 @@ impl SessionService {
             .update(&session)
             .map_err(|e| ServiceError::Internal(format!("Failed to update session: {}", e)))?;
 
+        if let Some(attachments) = core.attachment_store() {
+            let paths = removed.images.iter().map(|img| &img.path);
+            if let Err(e) = attachments.release_paths(paths) {
+                tracing::warn!(error = %e, session_id = %id, "Failed to release queued attachments");
+            }
+        }
+
         Ok(removed)
     }
 
@@ -0,0 +1,292 @@
+//! Opt-in usage statistics.
+//!
+//! Off unless `[usage_stats] enabled = true`. Feature use (actions, completed
+//! turns per agent) is counted in memory, flushed into the local database per
+//! UTC day and shown by `conduit stats`. Counts only leave the machine when an
+//! `endpoint` is configured: once a day, previous complete days are POSTed as
+//! aggregates of feature names and counts plus the Conduit version and OS.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::agent::AgentType;
+use crate::config::UsageStatsConfig;
+use crate::data::{AppStateStore, UsageStatsStore};
+use crate::util::network;
+
+/// App state key holding the last day whose counts were uploaded
+pub const UPLOADED_THROUGH_KEY: &str = "usage_stats_uploaded_through";
+
+/// Version of the upload payload
+const REPORT_SCHEMA: u32 = 1;
+/// How often in-memory counts are written to the database
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+/// How often the uploader checks for complete days to send
+const UPLOAD_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+/// Days older than this are never uploaded, e.g. after the endpoint was down
+const MAX_UPLOAD_DAYS: u64 = 30;
+
+/// Counts feature use when enabled; every call is a no-op otherwise
+pub struct UsageStats {
+    enabled: bool,
+    store: Option<UsageStatsStore>,
+    pending: Mutex<PendingCounts>,
+}
+
+struct PendingCounts {
+    counts: HashMap<String, u64>,
+    last_flush: Instant,
+}
+
+impl UsageStats {
+    pub fn new(config: &UsageStatsConfig, store: Option<UsageStatsStore>) -> Self {
+        Self {
+            enabled: config.enabled && store.is_some(),
+            store,
+            pending: Mutex::new(PendingCounts {
+                counts: HashMap::new(),
+                last_flush: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Count one use of `feature` (e.g. `action.new_tab`)
+    pub fn record(&self, feature: &str) {
+        if !self.enabled {
+            return;
+        }
+        let mut pending = self.pending.lock().unwrap();
+        *pending.counts.entry(feature.to_string()).or_default() += 1;
+        if pending.last_flush.elapsed() >= FLUSH_INTERVAL {
+            self.flush_pending(&mut pending);
+        }
+    }
+
+    /// Count a completed agent turn
+    pub fn record_turn(&self, agent_type: AgentType) {
+        if self.enabled {
+            self.record(&format!("turn.{}", agent_type.as_str()));
+        }
+    }
+
+    /// Write in-memory counts to the database
+    pub fn flush(&self) {
+        if !self.enabled {
+            return;
+        }
+        let mut pending = self.pending.lock().unwrap();
+        self.flush_pending(&mut pending);
+    }
+
+    fn flush_pending(&self, pending: &mut PendingCounts) {
+        pending.last_flush = Instant::now();
+        let Some(store) = &self.store else {
+            return;
+        };
+        if pending.counts.is_empty() {
+            return;
+        }
+        match store.add_counts(Utc::now().date_naive(), &pending.counts) {
+            Ok(()) => pending.counts.clear(),
+            Err(err) => tracing::warn!(error = %err, "Failed to save usage statistics"),
+        }
+    }
+
+    /// Start the daily upload when an endpoint is configured. Needs a Tokio
+    /// runtime; without one nothing is sent.
+    pub fn spawn_uploader(
+        config: &UsageStatsConfig,
+        store: Option<UsageStatsStore>,
+        app_state: Option<AppStateStore>,
+    ) {
+        let (true, Some(endpoint), Some(store), Some(app_state)) =
+            (config.enabled, config.endpoint.clone(), store, app_state)
+        else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            tracing::warn!("No async runtime; usage statistics will not be uploaded");
+            return;
+        };
+        runtime.spawn(async move {
+            let client = reqwest::Client::new();
+            loop {
+                // Also delays the first upload past startup
+                tokio::time::sleep(UPLOAD_CHECK_INTERVAL).await;
+                // Counts wait in the database until the network is back
+                if !network::should_attempt() {
+                    continue;
+                }
+                match upload_pending(&client, &endpoint, &store, &app_state).await {
+                    Ok(()) => {}
+                    Err(err)
+                        if err
+                            .downcast_ref::<reqwest::Error>()
+                            .is_some_and(network::is_network_error) =>
+                    {
+                        // Not recorded as offline: the endpoint alone may be down
+                        tracing::debug!(error = %err, "Usage statistics upload deferred while offline");
+                    }
+                    Err(err) => {
+                        tracing::warn!(error = %err, "Failed to upload usage statistics");
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl Drop for UsageStats {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// Payload POSTed to the configured endpoint
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageReport {
+    pub schema: u32,
+    pub conduit_version: &'static str,
+    pub os: &'static str,
+    pub days: Vec<UsageDay>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageDay {
+    /// UTC day, `YYYY-MM-DD`
+    pub day: String,
+    pub counts: BTreeMap<String, i64>,
+}
+
+/// Report of the complete days after `uploaded_through` (at most
+/// [`MAX_UPLOAD_DAYS`] back); today is still being counted and is left out.
+pub fn pending_report(
+    store: &UsageStatsStore,
+    uploaded_through: Option<NaiveDate>,
+    today: NaiveDate,
+) -> rusqlite::Result<UsageReport> {
+    let oldest = today - chrono::Days::new(MAX_UPLOAD_DAYS);
+    let start = uploaded_through
+        .and_then(|day| day.succ_opt())
+        .map_or(oldest, |day| day.max(oldest));
+    let days = store
+        .daily_between(start, today)?
+        .into_iter()
+        .map(|(day, counts)| UsageDay {
+            day: day.to_string(),
+            counts,
+        })
+        .collect();
+    Ok(UsageReport {
+        schema: REPORT_SCHEMA,
+        conduit_version: env!("CARGO_PKG_VERSION"),
+        os: std::env::consts::OS,
+        days,
+    })
+}
+
+async fn upload_pending(
+    client: &reqwest::Client,
+    endpoint: &str,
+    store: &UsageStatsStore,
+    app_state: &AppStateStore,
+) -> anyhow::Result<()> {
+    let today = Utc::now().date_naive();
+    let Some(yesterday) = today.pred_opt() else {
+        return Ok(());
+    };
+    let uploaded_through = match app_state.get(UPLOADED_THROUGH_KEY)? {
+        Some(value) => match value.parse::<NaiveDate>() {
+            Ok(day) => Some(day),
+            Err(err) => {
+                tracing::warn!(value = %value, error = %err, "Ignoring invalid usage upload marker");
+                None
+            }
+        },
+        None => None,
+    };
+    if uploaded_through.is_some_and(|day| day >= yesterday) {
+        return Ok(());
+    }
+
+    let report = pending_report(store, uploaded_through, today)?;
+    if !report.days.is_empty() {
+        client
+            .post(endpoint)
+            .timeout(Duration::from_secs(30))
+            .json(&report)
+            .send()
+            .await?
+            .error_for_status()?;
+        network::record_success();
+        tracing::info!(days = report.days.len(), "Uploaded usage statistics");
+    }
+    app_state.set(UPLOADED_THROUGH_KEY, &yesterday.to_string())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use tempfile::tempdir;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    #[test]
+    fn test_counts_are_kept_only_when_enabled() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = UsageStatsStore::new(db.connection());
+        let today = Utc::now().date_naive();
+        let tomorrow = today.succ_opt().unwrap();
+
+        let stats = UsageStats::new(&UsageStatsConfig::default(), Some(store.clone()));
+        stats.record("action.new_tab");
+        stats.flush();
+        assert!(store.totals_between(today, tomorrow).unwrap().is_empty());
+
+        let config = UsageStatsConfig {
+            enabled: true,
+            endpoint: None,
+        };
+        let stats = UsageStats::new(&config, Some(store.clone()));
+        stats.record("action.new_tab");
+        stats.record_turn(AgentType::Codex);
+        // Dropping flushes what's still in memory
+        drop(stats);
+        let totals = store.totals_between(today, tomorrow).unwrap();
+        assert_eq!(totals.len(), 2);
+    }
+
+    #[test]
+    fn test_pending_report_covers_complete_days_after_marker() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = UsageStatsStore::new(db.connection());
+        for d in [10, 11, 12] {
+            let counts = HashMap::from([("turn.claude".to_string(), u64::from(d))]);
+            store.add_counts(day(d), &counts).unwrap();
+        }
+
+        // Day 12 is today: still being counted
+        let report = pending_report(&store, Some(day(10)), day(12)).unwrap();
+        assert_eq!(report.schema, REPORT_SCHEMA);
+        assert_eq!(report.days.len(), 1);
+        assert_eq!(report.days[0].day, "2026-10-11");
+        assert_eq!(report.days[0].counts["turn.claude"], 11);
+
+        let report = pending_report(&store, None, day(12)).unwrap();
+        assert_eq!(report.days.len(), 2);
+    }
+}
@@ -0,0 +1,373 @@
+//! Write-behind persistence queue.
+//!
+//! Chat messages, raw agent events and billing entries are handed to a
+//! bounded in-memory queue and written by a background thread in batches, so
+//! the event handler doesn't wait on SQLite in normal use. Messages and costs
+//! are durable: when their queue is full, producers block until the writer
+//! makes room, and since the UI thread is the producer, the UI stalls for as
+//! long as the disk lags. Raw events are best-effort: under pressure the
+//! oldest queued event is dropped and counted. Shutdown flushes whatever is
+//! still queued.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::data::{
+    CostEntry, CostLedgerStore, TranscriptEvent, TranscriptMessage, TranscriptStore,
+};
+
+/// Messages and costs queued before producers wait for the writer
+const DURABLE_CAPACITY: usize = 4096;
+/// Raw events queued before the oldest are dropped
+const EVENT_CAPACITY: usize = 2048;
+/// Most records written in one transaction
+const BATCH_SIZE: usize = 256;
+/// How long the writer lets a partial batch fill up
+const BATCH_DELAY: Duration = Duration::from_millis(200);
+/// How long a flush waits for the writer before giving up
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A write that must not be lost
+enum DurableWrite {
+    Message(TranscriptMessage),
+    Cost(CostEntry),
+}
+
+#[derive(Default)]
+struct QueueState {
+    durable: VecDeque<DurableWrite>,
+    events: VecDeque<TranscriptEvent>,
+    /// The writer holds a batch it hasn't finished writing
+    in_flight: bool,
+    /// A flush is waiting: write without the batch delay
+    flush_requested: bool,
+    closed: bool,
+    dropped_events: u64,
+}
+
+impl QueueState {
+    fn is_idle(&self) -> bool {
+        self.durable.is_empty() && self.events.is_empty() && !self.in_flight
+    }
+
+    fn batch_ready(&self) -> bool {
+        self.closed || self.flush_requested || self.durable.len() + self.events.len() >= BATCH_SIZE
+    }
+}
+
+struct Shared {
+    state: Mutex<QueueState>,
+    /// Signalled whenever the queue or the writer's progress changes
+    changed: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap()
+    }
+}
+
+/// Stores the background writer persists to
+pub struct WriteBehindStores {
+    pub transcripts: TranscriptStore,
+    pub cost_ledger: CostLedgerStore,
+}
+
+/// Bounded queue in front of the transcript and billing stores
+pub struct WriteBehindQueue {
+    shared: Arc<Shared>,
+    writer: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl WriteBehindQueue {
+    /// Start the background writer
+    pub fn start(stores: WriteBehindStores) -> std::io::Result<Self> {
+        let shared = Arc::new(Shared {
+            state: Mutex::new(QueueState::default()),
+            changed: Condvar::new(),
+        });
+        let writer = std::thread::Builder::new()
+            .name("conduit-write-behind".to_string())
+            .spawn({
+                let shared = Arc::clone(&shared);
+                move || run_writer(&shared, &stores)
+            })?;
+        Ok(Self {
+            shared,
+            writer: Mutex::new(Some(writer)),
+        })
+    }
+
+    /// Queue a chat message. Blocks the calling thread while the durable
+    /// queue is full, so a UI-thread caller freezes until the writer catches up.
+    pub fn push_message(&self, message: TranscriptMessage) {
+        self.push_durable(DurableWrite::Message(message));
+    }
+
+    /// Queue a billing ledger entry; blocks like [`Self::push_message`]
+    pub fn push_cost(&self, entry: CostEntry) {
+        self.push_durable(DurableWrite::Cost(entry));
+    }
+
+    /// Queue a raw event, dropping the oldest queued event when full
+    pub fn push_event(&self, event: TranscriptEvent) {
+        let mut state = self.shared.lock();
+        if state.closed {
+            tracing::warn!(session_id = %event.session_id, "Write-behind queue closed; dropping raw event");
+            return;
+        }
+        if state.events.len() >= EVENT_CAPACITY {
+            state.events.pop_front();
+            state.dropped_events += 1;
+            if state.dropped_events.is_power_of_two() {
+                tracing::warn!(
+                    dropped = state.dropped_events,
+                    "Persistence is falling behind; dropping oldest raw events"
+                );
+            }
+        }
+        state.events.push_back(event);
+        self.shared.changed.notify_all();
+    }
+
+    /// Raw events dropped so far because the queue was full
+    pub fn dropped_events(&self) -> u64 {
+        self.shared.lock().dropped_events
+    }
+
+    /// Wait until everything queued so far is written. Returns `false` when
+    /// the writer didn't finish within the flush timeout.
+    pub fn flush(&self) -> bool {
+        let deadline = Instant::now() + FLUSH_TIMEOUT;
+        let mut state = self.shared.lock();
+        state.flush_requested = true;
+        self.shared.changed.notify_all();
+        while !state.is_idle() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                tracing::warn!(
+                    queued = state.durable.len() + state.events.len(),
+                    "Timed out flushing the write-behind queue"
+                );
+                state.flush_requested = false;
+                return false;
+            }
+            state = self
+                .shared
+                .changed
+                .wait_timeout(state, remaining)
+                .unwrap()
+                .0;
+        }
+        state.flush_requested = false;
+        true
+    }
+
+    /// Flush, then stop the writer. Later pushes are dropped with a warning.
+    pub fn shutdown(&self) {
+        let Some(writer) = self.writer.lock().unwrap().take() else {
+            return;
+        };
+        self.flush();
+        self.shared.lock().closed = true;
+        self.shared.changed.notify_all();
+        if writer.join().is_err() {
+            tracing::error!("Write-behind writer thread panicked");
+        }
+    }
+
+    /// Wait on the condvar until there is room (or the queue closes). No
+    /// timeout: dropping a message or cost entry is worse than a stall.
+    fn push_durable(&self, write: DurableWrite) {
+        let mut state = self
+            .shared
+            .changed
+            .wait_while(self.shared.lock(), |state| {
+                !state.closed && state.durable.len() >= DURABLE_CAPACITY
+            })
+            .unwrap();
+        if state.closed {
+            tracing::warn!("Write-behind queue closed; dropping write");
+            return;
+        }
+        state.durable.push_back(write);
+        self.shared.changed.notify_all();
+    }
+}
+
+impl Drop for WriteBehindQueue {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn run_writer(shared: &Shared, stores: &WriteBehindStores) {
+    loop {
+        let (durable, events) = {
+            let mut state = shared
+                .changed
+                .wait_while(shared.lock(), |state| {
+                    !state.closed && state.durable.is_empty() && state.events.is_empty()
+                })
+                .unwrap();
+            if !state.batch_ready() {
+                state = shared
+                    .changed
+                    .wait_timeout_while(state, BATCH_DELAY, |state| !state.batch_ready())
+                    .unwrap()
+                    .0;
+            }
+            if state.closed && state.durable.is_empty() && state.events.is_empty() {
+                return;
+            }
+            let durable_count = state.durable.len().min(BATCH_SIZE);
+            let durable: Vec<DurableWrite> = state.durable.drain(..durable_count).collect();
+            let event_count = state.events.len().min(BATCH_SIZE - durable_count);
+            let events: Vec<TranscriptEvent> = state.events.drain(..event_count).collect();
+            state.in_flight = true;
+            // Producers waiting for room can continue
+            shared.changed.notify_all();
+            (durable, events)
+        };
+
+        write_batch(stores, durable, &events);
+
+        shared.lock().in_flight = false;
+        shared.changed.notify_all();
+    }
+}
+
+fn write_batch(stores: &WriteBehindStores, durable: Vec<DurableWrite>, events: &[TranscriptEvent]) {
+    let mut messages = Vec::new();
+    for write in durable {
+        match write {
+            DurableWrite::Message(message) => messages.push(message),
+            DurableWrite::Cost(entry) => {
+                if let Err(err) = stores.cost_ledger.record(&entry) {
+                    tracing::warn!(
+                        session_id = %entry.session_id,
+                        error = %err,
+                        "Failed to record turn cost in billing ledger"
+                    );
+                }
+            }
+        }
+    }
+    if messages.is_empty() && events.is_empty() {
+        return;
+    }
+    if let Err(err) = stores.transcripts.insert_batch(&messages, events) {
+        tracing::warn!(
+            messages = messages.len(),
+            events = events.len(),
+            error = %err,
+            "Failed to persist transcript batch"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentType;
+    use crate::data::Database;
+    use chrono::Utc;
+    use tempfile::tempdir;
+    use uuid::Uuid;
+
+    fn event(session_id: Uuid, n: usize) -> TranscriptEvent {
+        TranscriptEvent {
+            session_id,
+            direction: "received".to_string(),
+            event_type: format!("Event{n}"),
+            payload_json: "{}".to_string(),
+            recorded_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_flush_writes_everything_queued() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let transcripts = TranscriptStore::new(db.connection());
+        let cost_ledger = CostLedgerStore::new(db.connection());
+        let queue = WriteBehindQueue::start(WriteBehindStores {
+            transcripts: transcripts.clone(),
+            cost_ledger: cost_ledger.clone(),
+        })
+        .unwrap();
+        let session_id = Uuid::new_v4();
+
+        for n in 0..300 {
+            queue.push_message(TranscriptMessage {
+                session_id,
+                role: "User".to_string(),
+                message_json: format!("{{\"n\":{n}}}"),
+                recorded_at: Utc::now(),
+            });
+        }
+        queue.push_event(event(session_id, 0));
+        queue.push_cost(CostEntry {
+            session_id,
+            repository: None,
+            cost_center: None,
+            agent_type: AgentType::Claude,
+            model: None,
+            input_tokens: 10,
+            output_tokens: 1,
+            cost_usd: 0.5,
+            recorded_at: Utc::now(),
+        });
+        assert!(queue.flush());
+
+        let messages = transcripts.messages_for_session(session_id).unwrap();
+        assert_eq!(messages.len(), 300);
+        assert_eq!(messages[299].message_json, "{\"n\":299}");
+        assert_eq!(transcripts.events_for_session(session_id).unwrap().len(), 1);
+        let start = Utc::now() - chrono::Duration::hours(1);
+        let end = Utc::now() + chrono::Duration::hours(1);
+        assert_eq!(cost_ledger.entries_between(start, end).unwrap().len(), 1);
+
+        // Nothing is accepted once shut down
+        queue.shutdown();
+        queue.push_event(event(session_id, 1));
+        assert_eq!(transcripts.events_for_session(session_id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_full_event_queue_drops_oldest() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let transcripts = TranscriptStore::new(db.connection());
+        let queue = WriteBehindQueue::start(WriteBehindStores {
+            transcripts: transcripts.clone(),
+            cost_ledger: CostLedgerStore::new(db.connection()),
+        })
+        .unwrap();
+        let session_id = Uuid::new_v4();
+
+        // Hold the connection so the writer can't drain the queue
+        let conn = db.connection();
+        let guard = conn.lock().unwrap();
+        for n in 0..EVENT_CAPACITY + BATCH_SIZE + 10 {
+            queue.push_event(event(session_id, n));
+        }
+        drop(guard);
+        assert!(queue.flush());
+
+        let dropped = queue.dropped_events();
+        assert!(dropped >= 10, "{dropped}");
+        let events = transcripts.events_for_session(session_id).unwrap();
+        assert_eq!(
+            events.len() as u64 + dropped,
+            (EVENT_CAPACITY + BATCH_SIZE + 10) as u64
+        );
+        // The newest events survive
+        assert_eq!(
+            events.last().unwrap().event_type,
+            format!("Event{}", EVENT_CAPACITY + BATCH_SIZE + 9)
+        );
+    }
+}
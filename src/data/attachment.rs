@@ -0,0 +1,411 @@
+//! Managed attachment storage
+//!
+//! Images pasted or uploaded into a session are copied to
+//! `<attachments dir>/<session id>/<attachment id>.<ext>` and tracked in the
+//! `attachments` table. Queued and sent messages hold references; files
+//! nobody references, or whose session is gone, are removed by [`AttachmentStore::sweep`].
+
+use super::models::{attachment_extension, Attachment};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, Result as SqliteResult};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// How long an unreferenced or orphaned attachment survives before a sweep
+/// deletes it. Covers drafts still sitting in an input box and TUI sessions
+/// that haven't been saved yet.
+pub const ATTACHMENT_GRACE_PERIOD: Duration = Duration::hours(24);
+
+#[derive(Error, Debug)]
+pub enum AttachmentError {
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error("Attachment I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Unsupported attachment media type: {0}")]
+    UnsupportedMediaType(String),
+}
+
+/// Data access object for session attachments and their files
+#[derive(Clone)]
+pub struct AttachmentStore {
+    conn: Arc<Mutex<Connection>>,
+    root: PathBuf,
+}
+
+impl AttachmentStore {
+    /// Create a new AttachmentStore storing files under `root`
+    pub fn new(conn: Arc<Mutex<Connection>>, root: PathBuf) -> Self {
+        Self { conn, root }
+    }
+
+    /// Directory holding all attachment files
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Path of an attachment's file on disk
+    pub fn file_path(&self, attachment: &Attachment) -> PathBuf {
+        self.root
+            .join(attachment.session_id.to_string())
+            .join(format!("{}.{}", attachment.id, attachment.extension()))
+    }
+
+    /// Store raw bytes as a new, unreferenced attachment
+    pub fn create_from_bytes(
+        &self,
+        session_id: Uuid,
+        bytes: &[u8],
+        media_type: &str,
+    ) -> Result<Attachment, AttachmentError> {
+        let attachment = self.new_attachment(session_id, media_type, bytes.len() as u64)?;
+        let path = self.file_path(&attachment);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, bytes)?;
+        self.insert_or_remove_file(&attachment, &path)?;
+        Ok(attachment)
+    }
+
+    /// Move an existing file (e.g. a clipboard tempfile) into the store
+    pub fn import_file(
+        &self,
+        session_id: Uuid,
+        source: &Path,
+        media_type: &str,
+    ) -> Result<Attachment, AttachmentError> {
+        let size = fs::metadata(source)?.len();
+        let attachment = self.new_attachment(session_id, media_type, size)?;
+        let path = self.file_path(&attachment);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Rename fails across filesystems (tmpfs /tmp is common); fall back to copy
+        if fs::rename(source, &path).is_err() {
+            fs::copy(source, &path)?;
+            if let Err(err) = fs::remove_file(source) {
+                tracing::debug!(
+                    error = %err,
+                    path = %source.display(),
+                    "Failed to remove imported attachment source"
+                );
+            }
+        }
+        self.insert_or_remove_file(&attachment, &path)?;
+        Ok(attachment)
+    }
+
+    /// Get an attachment by ID
+    pub fn get_by_id(&self, id: Uuid) -> SqliteResult<Option<Attachment>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, media_type, size_bytes, ref_count, created_at
+             FROM attachments WHERE id = ?1",
+        )?;
+
+        let mut rows = stmt.query(params![id.to_string()])?;
+        if let Some(row) = rows.next()? {
+            Ok(Some(Self::row_to_attachment(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// List a session's attachments, oldest first
+    pub fn get_by_session(&self, session_id: Uuid) -> SqliteResult<Vec<Attachment>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, media_type, size_bytes, ref_count, created_at
+             FROM attachments WHERE session_id = ?1 ORDER BY created_at",
+        )?;
+
+        let attachments = stmt
+            .query_map(params![session_id.to_string()], Self::row_to_attachment)?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        Ok(attachments)
+    }
+
+    /// Attachment ID for a path inside the store, if it is one of ours
+    pub fn id_for_path(&self, path: &Path) -> Option<Uuid> {
+        if !path.starts_with(&self.root) {
+            return None;
+        }
+        path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| Uuid::parse_str(stem).ok())
+    }
+
+    /// Add a message reference to each managed path. Other paths are ignored.
+    pub fn retain_paths<'a>(
+        &self,
+        paths: impl IntoIterator<Item = &'a PathBuf>,
+    ) -> SqliteResult<()> {
+        self.adjust_refs(paths, 1)
+    }
+
+    /// Drop a message reference from each managed path. Other paths are ignored.
+    pub fn release_paths<'a>(
+        &self,
+        paths: impl IntoIterator<Item = &'a PathBuf>,
+    ) -> SqliteResult<()> {
+        self.adjust_refs(paths, -1)
+    }
+
+    /// Delete attachments that are unreferenced or whose session no longer
+    /// exists, once they are older than `grace`. Returns how many were removed.
+    pub fn sweep(&self, grace: Duration) -> Result<usize, AttachmentError> {
+        let stale = self.get_stale(Utc::now() - grace)?;
+
+        let mut removed = 0;
+        for attachment in &stale {
+            let path = self.file_path(attachment);
+            match fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    tracing::warn!(
+                        error = %err,
+                        path = %path.display(),
+                        "Failed to delete attachment file; keeping record"
+                    );
+                    continue;
+                }
+            }
+            let conn = self.conn.lock().unwrap();
+            conn.execute(
+                "DELETE FROM attachments WHERE id = ?1",
+                params![attachment.id.to_string()],
+            )?;
+            removed += 1;
+
+            // Drop the session directory once its last file is gone
+            if let Some(dir) = path.parent() {
+                if fs::read_dir(dir).is_ok_and(|mut entries| entries.next().is_none()) {
+                    if let Err(err) = fs::remove_dir(dir) {
+                        tracing::debug!(
+                            error = %err,
+                            path = %dir.display(),
+                            "Failed to remove empty attachment directory"
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Attachments created before `cutoff` that nothing references or whose
+    /// session row is gone
+    fn get_stale(&self, cutoff: DateTime<Utc>) -> SqliteResult<Vec<Attachment>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, media_type, size_bytes, ref_count, created_at
+             FROM attachments
+             WHERE created_at < ?1
+               AND (ref_count <= 0 OR session_id NOT IN (SELECT id FROM session_tabs))",
+        )?;
+
+        let attachments = stmt
+            .query_map(params![cutoff.to_rfc3339()], Self::row_to_attachment)?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        Ok(attachments)
+    }
+
+    fn new_attachment(
+        &self,
+        session_id: Uuid,
+        media_type: &str,
+        size_bytes: u64,
+    ) -> Result<Attachment, AttachmentError> {
+        if attachment_extension(media_type).is_none() {
+            return Err(AttachmentError::UnsupportedMediaType(
+                media_type.to_string(),
+            ));
+        }
+        Ok(Attachment {
+            id: Uuid::new_v4(),
+            session_id,
+            media_type: media_type.to_string(),
+            size_bytes,
+            ref_count: 0,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Record the attachment, deleting the just-written file if that fails
+    fn insert_or_remove_file(
+        &self,
+        attachment: &Attachment,
+        path: &Path,
+    ) -> Result<(), AttachmentError> {
+        if let Err(err) = self.insert(attachment) {
+            if let Err(remove_err) = fs::remove_file(path) {
+                tracing::warn!(
+                    error = %remove_err,
+                    path = %path.display(),
+                    "Failed to remove attachment file after insert failure"
+                );
+            }
+            return Err(err.into());
+        }
+        Ok(())
+    }
+
+    fn insert(&self, attachment: &Attachment) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO attachments (id, session_id, media_type, size_bytes, ref_count, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                attachment.id.to_string(),
+                attachment.session_id.to_string(),
+                attachment.media_type,
+                attachment.size_bytes as i64,
+                attachment.ref_count,
+                attachment.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn adjust_refs<'a>(
+        &self,
+        paths: impl IntoIterator<Item = &'a PathBuf>,
+        delta: i64,
+    ) -> SqliteResult<()> {
+        let ids: Vec<Uuid> = paths
+            .into_iter()
+            .filter_map(|path| self.id_for_path(path))
+            .collect();
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let conn = self.conn.lock().unwrap();
+        for id in ids {
+            conn.execute(
+                "UPDATE attachments SET ref_count = MAX(ref_count + ?2, 0) WHERE id = ?1",
+                params![id.to_string(), delta],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Convert a database row to an Attachment
+    fn row_to_attachment(row: &rusqlite::Row) -> SqliteResult<Attachment> {
+        let id_str: String = row.get(0)?;
+        let session_id_str: String = row.get(1)?;
+        let size_bytes: i64 = row.get(3)?;
+        let created_at_str: String = row.get(5)?;
+
+        Ok(Attachment {
+            id: Self::parse_uuid(&id_str, 0)?,
+            session_id: Self::parse_uuid(&session_id_str, 1)?,
+            media_type: row.get(2)?,
+            size_bytes: size_bytes.max(0) as u64,
+            ref_count: row.get(4)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+
+    fn parse_uuid(value: &str, column: usize) -> SqliteResult<Uuid> {
+        Uuid::parse_str(value).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(
+                column,
+                rusqlite::types::Type::Text,
+                Box::new(e),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentType;
+    use crate::data::{Database, SessionTab, SessionTabStore};
+    use tempfile::tempdir;
+
+    fn setup() -> (
+        tempfile::TempDir,
+        Database,
+        SessionTabStore,
+        AttachmentStore,
+    ) {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let tabs = SessionTabStore::new(db.connection());
+        let store = AttachmentStore::new(db.connection(), dir.path().join("attachments"));
+        (dir, db, tabs, store)
+    }
+
+    #[test]
+    fn test_import_moves_file_into_session_dir() {
+        let (dir, _db, _tabs, store) = setup();
+        let session_id = Uuid::new_v4();
+        let source = dir.path().join("clipboard.png");
+        fs::write(&source, b"png").unwrap();
+
+        let attachment = store.import_file(session_id, &source, "image/png").unwrap();
+        let path = store.file_path(&attachment);
+
+        assert!(!source.exists());
+        assert_eq!(fs::read(&path).unwrap(), b"png");
+        assert!(path.starts_with(store.root().join(session_id.to_string())));
+        assert_eq!(store.id_for_path(&path), Some(attachment.id));
+        assert_eq!(store.id_for_path(&source), None);
+        assert_eq!(store.get_by_session(session_id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_rejects_unsupported_media_type() {
+        let (_dir, _db, _tabs, store) = setup();
+        let result = store.create_from_bytes(Uuid::new_v4(), b"%PDF", "application/pdf");
+        assert!(matches!(
+            result,
+            Err(AttachmentError::UnsupportedMediaType(_))
+        ));
+    }
+
+    #[test]
+    fn test_sweep_keeps_referenced_attachments_of_live_sessions() {
+        let (_dir, _db, tabs, store) = setup();
+        let tab = SessionTab::new(0, AgentType::Claude, None, None, None, None);
+        tabs.create(&tab).unwrap();
+
+        let kept = store
+            .create_from_bytes(tab.id, b"kept", "image/png")
+            .unwrap();
+        let unreferenced = store
+            .create_from_bytes(tab.id, b"draft", "image/png")
+            .unwrap();
+        let orphan = store
+            .create_from_bytes(Uuid::new_v4(), b"orphan", "image/jpeg")
+            .unwrap();
+
+        let kept_path = store.file_path(&kept);
+        store.retain_paths([&kept_path]).unwrap();
+        store.retain_paths([&store.file_path(&orphan)]).unwrap();
+
+        // Nothing is old enough yet
+        assert_eq!(store.sweep(ATTACHMENT_GRACE_PERIOD).unwrap(), 0);
+
+        assert_eq!(store.sweep(Duration::seconds(-1)).unwrap(), 2);
+        assert!(kept_path.exists());
+        assert!(store.get_by_id(unreferenced.id).unwrap().is_none());
+        assert!(!store.file_path(&orphan).exists());
+
+        // Once the last reference is gone the file goes too
+        store.release_paths([&kept_path]).unwrap();
+        assert_eq!(store.sweep(Duration::seconds(-1)).unwrap(), 1);
+        assert!(!kept_path.exists());
+    }
+}
@@ -0,0 +1,96 @@
+//! Chat archive data access object
+//!
+//! Holds chat messages a session moved out of memory, as JSON, until the view
+//! loads them back or the tab closes.
+
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Data access object for offloaded chat history
+#[derive(Clone)]
+pub struct ChatArchiveStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl ChatArchiveStore {
+    /// Create a new ChatArchiveStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Store a batch of serialized messages; returns its ID
+    pub fn insert(
+        &self,
+        session_id: Uuid,
+        message_count: usize,
+        messages_json: &str,
+    ) -> SqliteResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO chat_archive (session_id, message_count, messages, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                session_id.to_string(),
+                i64::try_from(message_count).unwrap_or(i64::MAX),
+                messages_json,
+                Utc::now().to_rfc3339(),
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Serialized messages of batch `id`
+    pub fn get(&self, id: i64) -> SqliteResult<Option<String>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT messages FROM chat_archive WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+        .optional()
+    }
+
+    /// Delete batch `id`
+    pub fn delete(&self, id: i64) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM chat_archive WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// Delete every batch of a session
+    pub fn delete_session(&self, session_id: Uuid) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM chat_archive WHERE session_id = ?1",
+            params![session_id.to_string()],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_insert_get_and_delete_session() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = ChatArchiveStore::new(db.connection());
+        let session_id = Uuid::new_v4();
+
+        let first = store.insert(session_id, 2, "[1,2]").unwrap();
+        let second = store.insert(session_id, 1, "[3]").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(store.get(first).unwrap().as_deref(), Some("[1,2]"));
+
+        store.delete(first).unwrap();
+        assert_eq!(store.get(first).unwrap(), None);
+        store.delete_session(session_id).unwrap();
+        assert_eq!(store.get(second).unwrap(), None);
+    }
+}
@@ -0,0 +1,129 @@
+//! Billing ledger data access object
+
+use super::models::CostEntry;
+use crate::agent::AgentType;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Result as SqliteResult};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Data access object for the per-turn cost ledger
+#[derive(Clone)]
+pub struct CostLedgerStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl CostLedgerStore {
+    /// Create a new CostLedgerStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Append a priced turn to the ledger
+    pub fn record(&self, entry: &CostEntry) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO cost_entries (session_id, repository, cost_center, agent_type, model, input_tokens, output_tokens, cost_usd, recorded_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                entry.session_id.to_string(),
+                entry.repository,
+                entry.cost_center,
+                entry.agent_type.as_str(),
+                entry.model,
+                entry.input_tokens,
+                entry.output_tokens,
+                entry.cost_usd,
+                entry.recorded_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Entries recorded in `[start, end)`, oldest first
+    pub fn entries_between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> SqliteResult<Vec<CostEntry>> {
+        let conn = self.conn.lock().unwrap();
+        // RFC 3339 timestamps in UTC sort lexicographically
+        let mut stmt = conn.prepare(
+            "SELECT session_id, repository, cost_center, agent_type, model, input_tokens, output_tokens, cost_usd, recorded_at
+             FROM cost_entries WHERE recorded_at >= ?1 AND recorded_at < ?2 ORDER BY recorded_at, id",
+        )?;
+        let entries = stmt
+            .query_map(
+                params![start.to_rfc3339(), end.to_rfc3339()],
+                Self::row_to_entry,
+            )?
+            .collect::<SqliteResult<Vec<_>>>()?;
+        Ok(entries)
+    }
+
+    fn row_to_entry(row: &rusqlite::Row) -> SqliteResult<CostEntry> {
+        let session_id_str: String = row.get("session_id")?;
+        let agent_type_str: String = row.get("agent_type")?;
+        let recorded_at_str: String = row.get("recorded_at")?;
+        Ok(CostEntry {
+            session_id: Uuid::parse_str(&session_id_str).unwrap_or_else(|_| Uuid::nil()),
+            repository: row.get("repository")?,
+            cost_center: row.get("cost_center")?,
+            agent_type: AgentType::parse(&agent_type_str),
+            model: row.get("model")?,
+            input_tokens: row.get("input_tokens")?,
+            output_tokens: row.get("output_tokens")?,
+            cost_usd: row.get("cost_usd")?,
+            recorded_at: DateTime::parse_from_rfc3339(&recorded_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use chrono::TimeZone;
+    use tempfile::tempdir;
+
+    fn entry(cost_center: Option<&str>, cost_usd: f64, recorded_at: DateTime<Utc>) -> CostEntry {
+        CostEntry {
+            session_id: Uuid::new_v4(),
+            repository: Some("conduit".to_string()),
+            cost_center: cost_center.map(str::to_string),
+            agent_type: AgentType::Claude,
+            model: Some("sonnet".to_string()),
+            input_tokens: 1_000,
+            output_tokens: 200,
+            cost_usd,
+            recorded_at,
+        }
+    }
+
+    #[test]
+    fn test_entries_between_is_half_open() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = CostLedgerStore::new(db.connection());
+
+        let september = Utc.with_ymd_and_hms(2026, 9, 1, 0, 0, 0).unwrap();
+        let october = Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap();
+        let inside = entry(Some("platform"), 0.5, september);
+        store.record(&inside).unwrap();
+        store
+            .record(&entry(
+                None,
+                0.25,
+                Utc.with_ymd_and_hms(2026, 8, 31, 23, 59, 59).unwrap(),
+            ))
+            .unwrap();
+        store
+            .record(&entry(Some("platform"), 1.0, october))
+            .unwrap();
+
+        let entries = store.entries_between(september, october).unwrap();
+        assert_eq!(entries, vec![inside]);
+    }
+}
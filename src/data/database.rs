@@ -1,11 +1,17 @@
 //! SQLite database management
 
-use rusqlite::{params, Connection, Result as SqliteResult};
+use rusqlite::{params, Connection, ErrorCode, OpenFlags, Result as SqliteResult};
 use sha2::{Digest, Sha256};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
 
+use crate::util::chaos::{self, Fault};
+
+/// Schema version stored in `PRAGMA user_version`. Bump it with each
+/// migration so older builds refuse databases they don't understand.
+pub const SCHEMA_VERSION: i32 = 25;
+
 /// SQL schema for creating tables
 const SCHEMA: &str = r#"
 CREATE TABLE IF NOT EXISTS repositories (
@@ -17,7 +23,9 @@ CREATE TABLE IF NOT EXISTS repositories (
     archive_delete_branch INTEGER,
     archive_remote_prompt INTEGER,
     created_at TEXT NOT NULL,
-    updated_at TEXT NOT NULL
+    updated_at TEXT NOT NULL,
+    system_prompt TEXT,
+    cost_center TEXT
 );
 
 CREATE TABLE IF NOT EXISTS workspaces (
@@ -60,6 +68,16 @@ CREATE TABLE IF NOT EXISTS session_tabs (
     fork_seed_id TEXT,
     title TEXT,
     title_generated INTEGER NOT NULL DEFAULT 0,
+    codex_sandbox TEXT,
+    codex_approval TEXT,
+    system_prompt TEXT,
+    total_cost_usd REAL NOT NULL DEFAULT 0,
+    response_language TEXT,
+    response_preset TEXT,
+    cost_center TEXT,
+    working_dir TEXT,
+    custom_title TEXT,
+    linked_workspaces TEXT NOT NULL DEFAULT '[]',
     FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE SET NULL
 );
 
@@ -79,10 +97,121 @@ CREATE TABLE IF NOT EXISTS fork_seeds (
     token_estimate INTEGER NOT NULL,
     context_window INTEGER NOT NULL,
     seed_ack_filtered INTEGER NOT NULL DEFAULT 0,
+    delivered_session_id TEXT,
     FOREIGN KEY (parent_workspace_id) REFERENCES workspaces(id) ON DELETE SET NULL
 );
 
 CREATE INDEX IF NOT EXISTS idx_fork_seeds_parent_session ON fork_seeds(parent_session_id);
+CREATE INDEX IF NOT EXISTS idx_fork_seeds_hash ON fork_seeds(seed_prompt_hash);
+
+CREATE TABLE IF NOT EXISTS session_shares (
+    id TEXT PRIMARY KEY,
+    session_id TEXT NOT NULL,
+    token TEXT NOT NULL UNIQUE,
+    redact_tool_output INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL,
+    revoked_at TEXT,
+    FOREIGN KEY (session_id) REFERENCES session_tabs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_session_shares_session ON session_shares(session_id);
+
+-- No foreign key on session_id: the TUI only writes session_tabs rows when it
+-- saves state, so attachments can exist before their session row does.
+-- Orphans are removed by AttachmentStore::sweep instead.
+CREATE TABLE IF NOT EXISTS attachments (
+    id TEXT PRIMARY KEY,
+    session_id TEXT NOT NULL,
+    media_type TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    ref_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_attachments_session ON attachments(session_id);
+
+-- Billing ledger: one row per priced turn. Repository name and cost center are
+-- copied in when the turn completes so the ledger outlives sessions and
+-- repositories, and retagging doesn't rewrite past spend.
+CREATE TABLE IF NOT EXISTS cost_entries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id TEXT NOT NULL,
+    repository TEXT,
+    cost_center TEXT,
+    agent_type TEXT NOT NULL,
+    model TEXT,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    cost_usd REAL NOT NULL,
+    recorded_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_cost_entries_recorded ON cost_entries(recorded_at);
+
+-- Chat history offloaded from sessions over their memory cap
+CREATE TABLE IF NOT EXISTS chat_archive (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id TEXT NOT NULL,
+    message_count INTEGER NOT NULL,
+    messages TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_chat_archive_session ON chat_archive(session_id);
+
+-- Final report of each closed or archived session. No foreign key on
+-- session_id: a tab can close before the TUI first saves its session_tabs row.
+CREATE TABLE IF NOT EXISTS session_reports (
+    session_id TEXT PRIMARY KEY,
+    title TEXT,
+    total_cost_usd REAL NOT NULL DEFAULT 0,
+    turns INTEGER NOT NULL DEFAULT 0,
+    files_changed TEXT NOT NULL DEFAULT '[]',
+    pr_number INTEGER,
+    pr_url TEXT,
+    checks_passed INTEGER,
+    created_at TEXT NOT NULL
+);
+
+-- Shared context Conduit syncs into each workspace's CLAUDE.md and AGENTS.md
+CREATE TABLE IF NOT EXISTS workspace_memory (
+    workspace_id TEXT PRIMARY KEY,
+    content TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE CASCADE
+);
+
+-- Chat messages of each session, written behind the UI by the persistence queue
+CREATE TABLE IF NOT EXISTS transcript_messages (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id TEXT NOT NULL,
+    role TEXT NOT NULL,
+    message TEXT NOT NULL,
+    recorded_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcript_messages_session ON transcript_messages(session_id, id);
+
+-- Raw agent events of each session (oldest dropped first under load)
+CREATE TABLE IF NOT EXISTS transcript_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id TEXT NOT NULL,
+    direction TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    recorded_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcript_events_session ON transcript_events(session_id, id);
+
+-- Opt-in usage statistics: feature use counted per UTC day
+CREATE TABLE IF NOT EXISTS usage_counts (
+    day TEXT NOT NULL,
+    feature TEXT NOT NULL,
+    count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (day, feature)
+);
 "#;
 
 #[derive(Error, Debug)]
@@ -95,6 +224,37 @@ pub enum DatabaseError {
     CreateDir(std::io::Error),
     #[error("Lock poisoned")]
     LockPoisoned,
+    #[error("Database file is corrupted: {0}")]
+    Corrupted(String),
+    #[error(
+        "Database was written by a newer Conduit (schema version {found}, this build supports {supported})"
+    )]
+    NewerSchema { found: i32, supported: i32 },
+    #[error("Failed to back up database: {0}")]
+    Backup(std::io::Error),
+}
+
+impl DatabaseError {
+    /// The file itself is unusable; `Database::recover` can back it up and
+    /// rebuild it.
+    pub fn needs_recovery(&self) -> bool {
+        matches!(self, Self::Corrupted(_) | Self::NewerSchema { .. })
+    }
+
+    /// Report SQLite corruption errors as [`DatabaseError::Corrupted`]
+    fn classify(self) -> Self {
+        match self {
+            Self::Sqlite(err) if is_corruption(&err) => Self::Corrupted(err.to_string()),
+            other => other,
+        }
+    }
+}
+
+fn is_corruption(err: &rusqlite::Error) -> bool {
+    matches!(
+        err.sqlite_error_code(),
+        Some(ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase)
+    )
 }
 
 /// Database connection wrapper
@@ -119,6 +279,10 @@ impl Database {
             std::fs::create_dir_all(parent).map_err(DatabaseError::CreateDir)?;
         }
 
+        Self::open_and_initialize(path).map_err(DatabaseError::classify)
+    }
+
+    fn open_and_initialize(path: PathBuf) -> Result<Self, DatabaseError> {
         let conn = Connection::open(&path)?;
         conn.execute_batch("PRAGMA foreign_keys = ON;")?;
 
@@ -128,10 +292,16 @@ impl Database {
         };
 
         db.initialize()?;
+        if chaos::is_enabled() {
+            // After migrations, so chaos doesn't stop startup; a rolled-back
+            // commit fails its statement with SQLITE_CONSTRAINT_COMMITHOOK
+            let conn = db.conn.lock().map_err(|_| DatabaseError::LockPoisoned)?;
+            conn.commit_hook(Some(|| chaos::inject(Fault::DbFailure)));
+        }
         Ok(db)
     }
 
-    /// Open database in the default location (~/.conduit/conduit.db)
+    /// Open database in the default location (<data>/conduit.db)
     pub fn open_default() -> Result<Self, DatabaseError> {
         Self::open(crate::util::database_path())
     }
@@ -139,6 +309,13 @@ impl Database {
     /// Initialize the database schema
     fn initialize(&self) -> Result<(), DatabaseError> {
         let conn = self.conn.lock().map_err(|_| DatabaseError::LockPoisoned)?;
+        let found: i32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if found > SCHEMA_VERSION {
+            return Err(DatabaseError::NewerSchema {
+                found,
+                supported: SCHEMA_VERSION,
+            });
+        }
         conn.execute_batch(SCHEMA)?;
         drop(conn);
 
@@ -549,9 +726,229 @@ WHERE id IN (SELECT id FROM ranked WHERE rn > 1);
             )?;
         }
 
+        // Migration 13: Add codex_sandbox column to session_tabs table
+        let has_codex_sandbox: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('session_tabs') WHERE name='codex_sandbox'",
+                [],
+                |row| row.get::<_, i64>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_codex_sandbox {
+            conn.execute("ALTER TABLE session_tabs ADD COLUMN codex_sandbox TEXT", [])?;
+        }
+
+        // Migration 14: Add codex_approval column to session_tabs table
+        let has_codex_approval: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('session_tabs') WHERE name='codex_approval'",
+                [],
+                |row| row.get::<_, i64>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_codex_approval {
+            conn.execute(
+                "ALTER TABLE session_tabs ADD COLUMN codex_approval TEXT",
+                [],
+            )?;
+        }
+
+        // Migration 15: Add system_prompt column for per-session base instructions
+        let has_system_prompt: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('session_tabs') WHERE name='system_prompt'",
+                [],
+                |row| row.get::<_, i64>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_system_prompt {
+            conn.execute("ALTER TABLE session_tabs ADD COLUMN system_prompt TEXT", [])?;
+        }
+
+        // Migration 16: Add system_prompt column for per-repository base instructions
+        let has_repo_system_prompt: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('repositories') WHERE name='system_prompt'",
+                [],
+                |row| row.get::<_, i64>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_repo_system_prompt {
+            conn.execute("ALTER TABLE repositories ADD COLUMN system_prompt TEXT", [])?;
+        }
+
+        // Migration 17: Add total_cost_usd column for persisted session spend
+        let has_total_cost: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('session_tabs') WHERE name='total_cost_usd'",
+                [],
+                |row| row.get::<_, i64>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_total_cost {
+            conn.execute(
+                "ALTER TABLE session_tabs ADD COLUMN total_cost_usd REAL NOT NULL DEFAULT 0",
+                [],
+            )?;
+        }
+
+        // Migration 18: Add response_language column for the per-session language directive
+        let has_response_language: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('session_tabs') WHERE name='response_language'",
+                [],
+                |row| row.get::<_, i64>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_response_language {
+            conn.execute(
+                "ALTER TABLE session_tabs ADD COLUMN response_language TEXT",
+                [],
+            )?;
+        }
+
+        // Migration 19: Add response_preset column for per-session response style presets
+        let has_response_preset: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('session_tabs') WHERE name='response_preset'",
+                [],
+                |row| row.get::<_, i64>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_response_preset {
+            conn.execute(
+                "ALTER TABLE session_tabs ADD COLUMN response_preset TEXT",
+                [],
+            )?;
+        }
+
+        // Migration 20: Add cost_center column for per-repository billing tags
+        let has_repo_cost_center: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('repositories') WHERE name='cost_center'",
+                [],
+                |row| row.get::<_, i64>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_repo_cost_center {
+            conn.execute("ALTER TABLE repositories ADD COLUMN cost_center TEXT", [])?;
+        }
+
+        // Migration 21: Add cost_center column for per-session billing tags
+        let has_session_cost_center: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('session_tabs') WHERE name='cost_center'",
+                [],
+                |row| row.get::<_, i64>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_session_cost_center {
+            conn.execute("ALTER TABLE session_tabs ADD COLUMN cost_center TEXT", [])?;
+        }
+
+        // Migration 22: Add working_dir column for tabs outside any workspace
+        let has_session_working_dir: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('session_tabs') WHERE name='working_dir'",
+                [],
+                |row| row.get::<_, i64>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_session_working_dir {
+            conn.execute("ALTER TABLE session_tabs ADD COLUMN working_dir TEXT", [])?;
+        }
+
+        // Migration 23: Add custom_title column for user-renamed tabs
+        let has_custom_title: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('session_tabs') WHERE name='custom_title'",
+                [],
+                |row| row.get::<_, i64>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_custom_title {
+            conn.execute("ALTER TABLE session_tabs ADD COLUMN custom_title TEXT", [])?;
+        }
+
+        // Migration 24: Record which agent session acknowledged a fork seed
+        let has_delivered_session: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('fork_seeds') WHERE name='delivered_session_id'",
+                [],
+                |row| row.get::<_, i64>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_delivered_session {
+            conn.execute(
+                "ALTER TABLE fork_seeds ADD COLUMN delivered_session_id TEXT",
+                [],
+            )?;
+        }
+
+        // Migration 25: Add linked_workspaces column for sessions spanning several repos
+        let has_linked_workspaces: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('session_tabs') WHERE name='linked_workspaces'",
+                [],
+                |row| row.get::<_, i64>(0).map(|c| c > 0),
+            )
+            .unwrap_or(false);
+
+        if !has_linked_workspaces {
+            conn.execute(
+                "ALTER TABLE session_tabs ADD COLUMN linked_workspaces TEXT NOT NULL DEFAULT '[]'",
+                [],
+            )?;
+        }
+
+        conn.execute_batch(&format!("PRAGMA user_version = {SCHEMA_VERSION};"))?;
+
         Ok(())
     }
 
+    /// Run SQLite's full integrity check on the database at `path` without
+    /// modifying it. Returns the problems found; empty means healthy.
+    pub fn check(path: &Path) -> Result<Vec<String>, DatabaseError> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let version: i32 = match conn.query_row("PRAGMA user_version", [], |row| row.get(0)) {
+            Ok(version) => version,
+            Err(err) if is_corruption(&err) => return Ok(vec![err.to_string()]),
+            Err(err) => return Err(err.into()),
+        };
+        let mut problems = Vec::new();
+        if version > SCHEMA_VERSION {
+            problems.push(
+                DatabaseError::NewerSchema {
+                    found: version,
+                    supported: SCHEMA_VERSION,
+                }
+                .to_string(),
+            );
+        }
+        let result = conn.prepare("PRAGMA integrity_check").and_then(|mut stmt| {
+            stmt.query_map([], |row| row.get::<_, String>(0))?
+                .collect::<SqliteResult<Vec<_>>>()
+        });
+        match result {
+            Ok(lines) => problems.extend(lines.into_iter().filter(|line| line != "ok")),
+            Err(err) if is_corruption(&err) => problems.push(err.to_string()),
+            Err(err) => return Err(err.into()),
+        }
+        Ok(problems)
+    }
+
     /// Get a reference to the connection (for DAOs)
     pub fn connection(&self) -> Arc<Mutex<Connection>> {
         self.conn.clone()
@@ -4,9 +4,37 @@ use super::models::ForkSeed;
 use crate::agent::AgentType;
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, Result as SqliteResult};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+/// Store a seed prompt in `dir` under its hash. Identical seeds share one
+/// file, so forking the same transcript again doesn't write it twice.
+pub fn cache_seed_prompt(dir: &Path, hash: &str, seed_prompt: &str) -> io::Result<PathBuf> {
+    let path = dir.join(format!("{hash}.txt"));
+    if path.is_file() {
+        return Ok(path);
+    }
+    fs::create_dir_all(dir)?;
+    let tmp_path = dir.join(format!("{hash}.txt.tmp"));
+    fs::write(&tmp_path, seed_prompt)?;
+    fs::rename(&tmp_path, &path)?;
+    Ok(path)
+}
+
+/// Whether `path` is a hash-keyed seed file written by [`cache_seed_prompt`].
+/// Those can be shared by several forks and must not be removed after one
+/// of them is sent.
+pub fn is_cached_seed_path(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "txt")
+        && path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| stem.len() == 64 && stem.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
 /// Data access object for fork seed operations
 #[derive(Clone)]
 pub struct ForkSeedStore {
@@ -23,8 +51,8 @@ impl ForkSeedStore {
     pub fn create(&self, seed: &ForkSeed) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
-            "INSERT INTO fork_seeds (id, agent_type, parent_session_id, parent_workspace_id, created_at, seed_prompt_hash, seed_prompt_path, token_estimate, context_window, seed_ack_filtered)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            "INSERT INTO fork_seeds (id, agent_type, parent_session_id, parent_workspace_id, created_at, seed_prompt_hash, seed_prompt_path, token_estimate, context_window, seed_ack_filtered, delivered_session_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
             params![
                 seed.id.to_string(),
                 seed.agent_type.as_str(),
@@ -36,6 +64,7 @@ impl ForkSeedStore {
                 seed.token_estimate,
                 seed.context_window,
                 if seed.seed_ack_filtered { 1 } else { 0 },
+                seed.delivered_session_id,
             ],
         )?;
         Ok(())
@@ -45,7 +74,7 @@ impl ForkSeedStore {
     pub fn get_by_id(&self, id: Uuid) -> SqliteResult<Option<ForkSeed>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, agent_type, parent_session_id, parent_workspace_id, created_at, seed_prompt_hash, seed_prompt_path, token_estimate, context_window, seed_ack_filtered
+            "SELECT id, agent_type, parent_session_id, parent_workspace_id, created_at, seed_prompt_hash, seed_prompt_path, token_estimate, context_window, seed_ack_filtered, delivered_session_id
              FROM fork_seeds WHERE id = ?1",
         )?;
 
@@ -57,6 +86,16 @@ impl ForkSeedStore {
         }
     }
 
+    /// Record the agent session that acknowledged the seed
+    pub fn mark_delivered(&self, id: Uuid, agent_session_id: &str) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE fork_seeds SET delivered_session_id = ?2 WHERE id = ?1",
+            params![id.to_string(), agent_session_id],
+        )?;
+        Ok(())
+    }
+
     /// Delete a fork seed by ID
     pub fn delete(&self, id: Uuid) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
@@ -109,6 +148,54 @@ impl ForkSeedStore {
             token_estimate: row.get(7)?,
             context_window: row.get(8)?,
             seed_ack_filtered: seed_ack_filtered != 0,
+            delivered_session_id: row.get(10)?,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_cache_seed_prompt_reuses_file_for_same_hash() {
+        let dir = tempdir().unwrap();
+        let hash = "a".repeat(64);
+        let first = cache_seed_prompt(dir.path(), &hash, "seed").unwrap();
+        let second = cache_seed_prompt(dir.path(), &hash, "ignored").unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(fs::read_to_string(&first).unwrap(), "seed");
+        assert!(is_cached_seed_path(&first));
+        assert!(!is_cached_seed_path(
+            &dir.path().join(format!("{}.txt", Uuid::new_v4()))
+        ));
+    }
+
+    #[test]
+    fn test_mark_delivered_round_trips() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = ForkSeedStore::new(db.connection());
+
+        let seed = ForkSeed::new(
+            AgentType::Claude,
+            Some("parent".to_string()),
+            None,
+            "b".repeat(64),
+            Some("/tmp/seed.txt".to_string()),
+            100,
+            200_000,
+        );
+        store.create(&seed).unwrap();
+        let loaded = store.get_by_id(seed.id).unwrap().unwrap();
+        assert_eq!(loaded.seed_prompt_path.as_deref(), Some("/tmp/seed.txt"));
+        assert_eq!(loaded.delivered_session_id, None);
+
+        store.mark_delivered(seed.id, "agent-1").unwrap();
+        let loaded = store.get_by_id(seed.id).unwrap().unwrap();
+        assert_eq!(loaded.delivered_session_id.as_deref(), Some("agent-1"));
+    }
+}
@@ -3,20 +3,39 @@
 //! This module provides SQLite-based storage for repositories and workspaces.
 
 mod app_state;
+mod attachment;
+mod chat_archive;
+mod cost_ledger;
 mod database;
 mod fork_seed;
 mod models;
+mod recovery;
 mod repository;
+mod session_report;
+mod session_share;
 mod session_tab;
+mod transcript;
+mod usage_stats;
 mod workspace;
+mod workspace_memory;
 
 pub use app_state::AppStateStore;
-pub use database::Database;
-pub use fork_seed::ForkSeedStore;
+pub use attachment::{AttachmentError, AttachmentStore, ATTACHMENT_GRACE_PERIOD};
+pub use chat_archive::ChatArchiveStore;
+pub use cost_ledger::CostLedgerStore;
+pub use database::{Database, DatabaseError, SCHEMA_VERSION};
+pub use fork_seed::{cache_seed_prompt, is_cached_seed_path, ForkSeedStore};
 pub use models::{
-    ForkSeed, QueuedImageAttachment, QueuedMessage, QueuedMessageMode, Repository, SessionTab,
-    Workspace,
+    attachment_extension, Attachment, CostEntry, ForkSeed, QueuedImageAttachment, QueuedMessage,
+    QueuedMessageMode, Repository, SessionReport, SessionShare, SessionTab, Workspace,
+    WorkspaceMemory,
 };
+pub use recovery::{recover_database, RecoveryReport, SalvagedTable};
 pub use repository::RepositoryStore;
+pub use session_report::SessionReportStore;
+pub use session_share::SessionShareStore;
 pub use session_tab::SessionTabStore;
+pub use transcript::{TranscriptEvent, TranscriptMessage, TranscriptStore};
+pub use usage_stats::UsageStatsStore;
 pub use workspace::WorkspaceStore;
+pub use workspace_memory::WorkspaceMemoryStore;
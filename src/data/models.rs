@@ -1,6 +1,8 @@
 //! Data models for repositories and workspaces
 
+use base64::Engine as _;
 use chrono::{DateTime, Utc};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use uuid::Uuid;
@@ -60,6 +62,10 @@ pub struct Repository {
     pub created_at: DateTime<Utc>,
     /// Last time the repository was modified
     pub updated_at: DateTime<Utc>,
+    /// Default base instructions for new sessions in this repository
+    pub system_prompt: Option<String>,
+    /// Billing tag for spend in this repository's sessions
+    pub cost_center: Option<String>,
 }
 
 impl Repository {
@@ -76,6 +82,8 @@ impl Repository {
             archive_remote_prompt: None,
             created_at: now,
             updated_at: now,
+            system_prompt: None,
+            cost_center: None,
         }
     }
 
@@ -92,6 +100,8 @@ impl Repository {
             archive_remote_prompt: None,
             created_at: now,
             updated_at: now,
+            system_prompt: None,
+            cost_center: None,
         }
     }
 
@@ -216,6 +226,27 @@ pub struct SessionTab {
     pub title: Option<String>,
     /// Whether a title has already been generated and persisted
     pub title_generated: bool,
+    /// Codex sandbox mode override
+    pub codex_sandbox: Option<String>,
+    /// Codex approval policy override
+    pub codex_approval: Option<String>,
+    /// Custom base instructions appended to the agent's system prompt
+    pub system_prompt: Option<String>,
+    /// Estimated USD spend accumulated across turns
+    pub total_cost_usd: f64,
+    /// Language the agent is asked to respond in (e.g. "German")
+    pub response_language: Option<String>,
+    /// Response style preset name (e.g. "concise")
+    pub response_preset: Option<String>,
+    /// Billing tag overriding the repository's cost center
+    pub cost_center: Option<String>,
+    /// Working directory for tabs not tied to a workspace (e.g. imported
+    /// conversations), so the agent resumes in the right project
+    pub working_dir: Option<PathBuf>,
+    /// Name the user gave the tab; shown instead of the generated title
+    pub custom_title: Option<String>,
+    /// Workspaces of other repositories the session also works in
+    pub linked_workspace_ids: Vec<Uuid>,
 }
 
 impl SessionTab {
@@ -246,10 +277,180 @@ impl SessionTab {
             fork_seed_id: None,
             title: None,
             title_generated: false,
+            codex_sandbox: None,
+            codex_approval: None,
+            system_prompt: None,
+            total_cost_usd: 0.0,
+            response_language: None,
+            response_preset: None,
+            cost_center: None,
+            working_dir: None,
+            custom_title: None,
+            linked_workspace_ids: Vec::new(),
         }
     }
 }
 
+/// One priced agent turn in the billing ledger
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostEntry {
+    /// Session tab the turn ran in
+    pub session_id: Uuid,
+    /// Repository name when the turn completed
+    pub repository: Option<String>,
+    /// Cost-center tag when the turn completed (session tag, else repository tag)
+    pub cost_center: Option<String>,
+    /// Agent that ran the turn
+    pub agent_type: AgentType,
+    /// Model the turn was priced with
+    pub model: Option<String>,
+    /// Input tokens
+    pub input_tokens: i64,
+    /// Output tokens
+    pub output_tokens: i64,
+    /// Estimated USD cost of the turn
+    pub cost_usd: f64,
+    /// When the turn completed
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// What a session accomplished, recorded when it is closed or archived so
+/// finished work stays legible long after its history is gone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionReport {
+    /// Session tab ID
+    pub session_id: Uuid,
+    /// Custom or generated title at close time
+    pub title: Option<String>,
+    /// Estimated USD spend over the session
+    pub total_cost_usd: f64,
+    /// Number of prompts sent
+    pub turns: u32,
+    /// Paths changed during the session, in the order first touched
+    pub files_changed: Vec<String>,
+    /// PR opened for the session's branch
+    pub pr_number: Option<i32>,
+    /// Link to that PR, when known
+    pub pr_url: Option<String>,
+    /// Whether the last test or check command passed (None = none ran)
+    pub checks_passed: Option<bool>,
+    /// When the report was generated
+    pub created_at: DateTime<Utc>,
+}
+
+impl SessionReport {
+    /// One-line digest such as "5 turns · $1.20 · PR #12 · checks passed"
+    pub fn headline(&self) -> String {
+        let mut parts = vec![match self.turns {
+            1 => "1 turn".to_string(),
+            n => format!("{n} turns"),
+        }];
+        if self.total_cost_usd > 0.0 {
+            parts.push(format!("${:.2}", self.total_cost_usd));
+        }
+        if !self.files_changed.is_empty() {
+            parts.push(match self.files_changed.len() {
+                1 => "1 file".to_string(),
+                n => format!("{n} files"),
+            });
+        }
+        if let Some(number) = self.pr_number {
+            parts.push(format!("PR #{number}"));
+        }
+        match self.checks_passed {
+            Some(true) => parts.push("checks passed".to_string()),
+            Some(false) => parts.push("checks failed".to_string()),
+            None => {}
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Shared context of a workspace, synced into its agents' memory files
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceMemory {
+    pub workspace_id: Uuid,
+    pub content: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A revocable, read-only link to a session transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionShare {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Shared session tab ID
+    pub session_id: Uuid,
+    /// Secret token embedded in the share URL
+    pub token: String,
+    /// Whether tool outputs are hidden from viewers
+    pub redact_tool_output: bool,
+    /// When the share was created
+    pub created_at: DateTime<Utc>,
+    /// When the share stops resolving
+    pub expires_at: DateTime<Utc>,
+    /// When the share was revoked (None = still valid until expiry)
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl SessionShare {
+    /// Create a new share with a freshly generated token
+    pub fn new(session_id: Uuid, expires_at: DateTime<Utc>, redact_tool_output: bool) -> Self {
+        let mut bytes = [0u8; 24];
+        rand::rng().fill(&mut bytes);
+        Self {
+            id: Uuid::new_v4(),
+            session_id,
+            token: base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes),
+            redact_tool_output,
+            created_at: Utc::now(),
+            expires_at,
+            revoked_at: None,
+        }
+    }
+
+    /// Check whether the share can still be viewed at `now`
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && now < self.expires_at
+    }
+}
+
+/// An image (or other file) attached to a session's messages, stored under
+/// the managed attachments directory
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Attachment {
+    /// Unique identifier (also the file stem on disk)
+    pub id: Uuid,
+    /// Owning session tab ID
+    pub session_id: Uuid,
+    /// MIME type, e.g. "image/png"
+    pub media_type: String,
+    /// Size of the stored file in bytes
+    pub size_bytes: u64,
+    /// Number of queued or sent messages referencing this attachment
+    pub ref_count: i64,
+    /// When the attachment was stored
+    pub created_at: DateTime<Utc>,
+}
+
+impl Attachment {
+    /// File extension used for this attachment's media type
+    pub fn extension(&self) -> &'static str {
+        attachment_extension(&self.media_type).unwrap_or("bin")
+    }
+}
+
+/// File extension for a supported attachment media type
+pub fn attachment_extension(media_type: &str) -> Option<&'static str> {
+    match media_type {
+        "image/png" => Some("png"),
+        "image/jpeg" | "image/jpg" => Some("jpg"),
+        "image/webp" => Some("webp"),
+        "image/gif" => Some("gif"),
+        _ => None,
+    }
+}
+
 /// Metadata for a forked session seed prompt
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForkSeed {
@@ -273,6 +474,8 @@ pub struct ForkSeed {
     pub context_window: i64,
     /// Whether the first assistant reply should be suppressed
     pub seed_ack_filtered: bool,
+    /// Agent session that acknowledged the seed, once it has been sent
+    pub delivered_session_id: Option<String>,
 }
 
 impl ForkSeed {
@@ -296,6 +499,7 @@ impl ForkSeed {
             token_estimate,
             context_window,
             seed_ack_filtered: true,
+            delivered_session_id: None,
         }
     }
 }
@@ -0,0 +1,293 @@
+//! Recovery for a database that can't be opened: the damaged file is moved
+//! aside as a backup, a fresh database is created in its place, and every row
+//! that can still be read from the backup is copied over.
+
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Result as SqliteResult};
+
+use super::database::{Database, DatabaseError};
+
+/// Rows copied from one table of the damaged database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SalvagedTable {
+    pub name: String,
+    /// Rows copied into the rebuilt database
+    pub rows: usize,
+    /// Reading stopped early because of damage; later rows were lost
+    pub incomplete: bool,
+}
+
+/// Outcome of [`recover_database`]
+#[derive(Debug, Clone)]
+pub struct RecoveryReport {
+    /// Where the damaged file was moved
+    pub backup_path: PathBuf,
+    pub tables: Vec<SalvagedTable>,
+}
+
+impl RecoveryReport {
+    /// Total rows copied into the rebuilt database
+    pub fn salvaged_rows(&self) -> usize {
+        self.tables.iter().map(|table| table.rows).sum()
+    }
+}
+
+/// Back up the database at `path`, rebuild it with the current schema and copy
+/// over what's still readable. Columns the current schema doesn't know (from a
+/// newer Conduit) are left behind in the backup.
+pub fn recover_database(path: &Path) -> Result<(Database, RecoveryReport), DatabaseError> {
+    let backup_path = backup_path_for(path);
+    move_with_sidecars(path, &backup_path).map_err(DatabaseError::Backup)?;
+    tracing::warn!(
+        path = %path.display(),
+        backup = %backup_path.display(),
+        "Moved damaged database aside; rebuilding"
+    );
+
+    let db = Database::open(path.to_path_buf())?;
+    let readable = Connection::open(&backup_path).and_then(|old| {
+        old.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| {
+            row.get::<_, i64>(0)
+        })?;
+        Ok(old)
+    });
+    let tables = match readable {
+        Ok(old) => {
+            let conn = db.connection();
+            let conn = conn.lock().map_err(|_| DatabaseError::LockPoisoned)?;
+            salvage_tables(&old, &conn)?
+        }
+        Err(err) => {
+            tracing::warn!(error = %err, "Database backup is unreadable; nothing salvaged");
+            Vec::new()
+        }
+    };
+
+    Ok((
+        db,
+        RecoveryReport {
+            backup_path,
+            tables,
+        },
+    ))
+}
+
+fn backup_path_for(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "conduit.db".to_string());
+    path.with_file_name(format!(
+        "{}.damaged-{}",
+        file_name,
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+/// Move the database and its WAL/shared-memory files, keeping them paired so
+/// the backup still sees committed WAL pages
+fn move_with_sidecars(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::rename(from, to)?;
+    for suffix in ["-wal", "-shm"] {
+        let sidecar = sidecar_path(from, suffix);
+        if sidecar.exists() {
+            std::fs::rename(&sidecar, sidecar_path(to, suffix))?;
+        }
+    }
+    Ok(())
+}
+
+fn sidecar_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Copy every table of the fresh schema from `old` into `new`
+fn salvage_tables(old: &Connection, new: &Connection) -> Result<Vec<SalvagedTable>, DatabaseError> {
+    let tables: Vec<String> = new
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name")?
+        .query_map([], |row| row.get(0))?
+        .collect::<SqliteResult<_>>()?;
+
+    // Rows are copied table by table, so references can't be checked until
+    // everything is in
+    new.execute_batch("PRAGMA foreign_keys = OFF; BEGIN;")?;
+    let mut salvaged = Vec::new();
+    for table in tables {
+        if let Some(result) = salvage_table(old, new, &table)? {
+            salvaged.push(result);
+        }
+    }
+    new.execute_batch("COMMIT; PRAGMA foreign_keys = ON;")?;
+    Ok(salvaged)
+}
+
+/// Copy one table; `None` when the backup doesn't have it
+fn salvage_table(
+    old: &Connection,
+    new: &Connection,
+    table: &str,
+) -> Result<Option<SalvagedTable>, DatabaseError> {
+    let old_columns = match table_columns(old, table) {
+        Ok(columns) if columns.is_empty() => return Ok(None),
+        Ok(columns) => columns,
+        Err(err) => {
+            tracing::warn!(table, error = %err, "Failed to read table layout from backup");
+            return Ok(Some(SalvagedTable {
+                name: table.to_string(),
+                rows: 0,
+                incomplete: true,
+            }));
+        }
+    };
+    let columns: Vec<String> = table_columns(new, table)?
+        .into_iter()
+        .filter(|column| old_columns.contains(column))
+        .collect();
+    if columns.is_empty() {
+        return Ok(None);
+    }
+    let column_list = columns
+        .iter()
+        .map(|column| quote_identifier(column))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let placeholders = vec!["?"; columns.len()].join(", ");
+    let mut insert = new.prepare(&format!(
+        "INSERT OR IGNORE INTO {} ({column_list}) VALUES ({placeholders})",
+        quote_identifier(table)
+    ))?;
+
+    let mut result = SalvagedTable {
+        name: table.to_string(),
+        rows: 0,
+        incomplete: false,
+    };
+    let select = format!("SELECT {column_list} FROM {}", quote_identifier(table));
+    let mut stmt = match old.prepare(&select) {
+        Ok(stmt) => stmt,
+        Err(err) => {
+            tracing::warn!(table, error = %err, "Failed to read table from backup");
+            result.incomplete = true;
+            return Ok(Some(result));
+        }
+    };
+    let mut rows = match stmt.query([]) {
+        Ok(rows) => rows,
+        Err(err) => {
+            tracing::warn!(table, error = %err, "Failed to read table from backup");
+            result.incomplete = true;
+            return Ok(Some(result));
+        }
+    };
+    loop {
+        let row = match rows.next() {
+            Ok(Some(row)) => row,
+            Ok(None) => break,
+            Err(err) => {
+                // Rows past a damaged page can't be reached by this scan
+                tracing::warn!(table, error = %err, "Stopped reading damaged table");
+                result.incomplete = true;
+                break;
+            }
+        };
+        let values = (0..columns.len())
+            .map(|index| row.get::<_, Value>(index))
+            .collect::<SqliteResult<Vec<_>>>();
+        let values = match values {
+            Ok(values) => values,
+            Err(err) => {
+                tracing::warn!(table, error = %err, "Skipping unreadable row");
+                result.incomplete = true;
+                continue;
+            }
+        };
+        match insert.execute(params_from_iter(values)) {
+            Ok(inserted) => result.rows += inserted,
+            Err(err) => {
+                tracing::warn!(table, error = %err, "Skipping row that no longer fits the schema");
+                result.incomplete = true;
+            }
+        }
+    }
+    Ok(Some(result))
+}
+
+fn table_columns(conn: &Connection, table: &str) -> SqliteResult<Vec<String>> {
+    conn.prepare("SELECT name FROM pragma_table_info(?1)")?
+        .query_map([table], |row| row.get(0))?
+        .collect()
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{Repository, RepositoryStore};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_garbage_file_is_reported_as_corrupted() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("conduit.db");
+        std::fs::write(&path, vec![0x5a; 8192]).unwrap();
+
+        let err = Database::open(path.clone()).unwrap_err();
+        assert!(matches!(err, DatabaseError::Corrupted(_)), "{err}");
+        assert!(err.needs_recovery());
+        assert!(!Database::check(&path).unwrap().is_empty());
+
+        let (db, report) = recover_database(&path).unwrap();
+        assert!(report.backup_path.exists());
+        assert_eq!(report.salvaged_rows(), 0);
+        assert!(RepositoryStore::new(db.connection())
+            .get_all()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_recover_newer_schema_keeps_rows() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("conduit.db");
+        {
+            let db = Database::open(path.clone()).unwrap();
+            RepositoryStore::new(db.connection())
+                .create(&Repository::from_local_path(
+                    "demo",
+                    PathBuf::from("/tmp/demo"),
+                ))
+                .unwrap();
+            db.with_connection(|conn| {
+                conn.execute_batch(
+                    "ALTER TABLE repositories ADD COLUMN added_later TEXT;
+                     PRAGMA user_version = 999;",
+                )
+            })
+            .unwrap();
+        }
+
+        let err = Database::open(path.clone()).unwrap_err();
+        assert!(matches!(err, DatabaseError::NewerSchema { found: 999, .. }));
+
+        let (db, report) = recover_database(&path).unwrap();
+        let repositories = report
+            .tables
+            .iter()
+            .find(|table| table.name == "repositories")
+            .unwrap();
+        assert_eq!(repositories.rows, 1);
+        assert!(!repositories.incomplete);
+        let repos = RepositoryStore::new(db.connection()).get_all().unwrap();
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].name, "demo");
+        assert!(Database::check(&path).unwrap().is_empty());
+    }
+}
@@ -25,8 +25,8 @@ impl RepositoryStore {
     pub fn create(&self, repo: &Repository) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
-            "INSERT INTO repositories (id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            "INSERT INTO repositories (id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at, system_prompt, cost_center)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
             params![
                 repo.id.to_string(),
                 repo.name,
@@ -39,6 +39,8 @@ impl RepositoryStore {
                 repo.archive_remote_prompt.map(|value| value as i32),
                 repo.created_at.to_rfc3339(),
                 repo.updated_at.to_rfc3339(),
+                repo.system_prompt,
+                repo.cost_center,
             ],
         )?;
         Ok(())
@@ -48,7 +50,7 @@ impl RepositoryStore {
     pub fn get_by_id(&self, id: Uuid) -> SqliteResult<Option<Repository>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at
+            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at, system_prompt, cost_center
              FROM repositories WHERE id = ?1",
         )?;
 
@@ -64,7 +66,7 @@ impl RepositoryStore {
     pub fn get_all(&self) -> SqliteResult<Vec<Repository>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at
+            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at, system_prompt, cost_center
              FROM repositories ORDER BY name",
         )?;
 
@@ -79,7 +81,7 @@ impl RepositoryStore {
     pub fn update(&self, repo: &Repository) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
-            "UPDATE repositories SET name = ?2, base_path = ?3, repository_url = ?4, workspace_mode = ?5, archive_delete_branch = ?6, archive_remote_prompt = ?7, updated_at = ?8
+            "UPDATE repositories SET name = ?2, base_path = ?3, repository_url = ?4, workspace_mode = ?5, archive_delete_branch = ?6, archive_remote_prompt = ?7, updated_at = ?8, system_prompt = ?9, cost_center = ?10
              WHERE id = ?1",
             params![
                 repo.id.to_string(),
@@ -90,6 +92,8 @@ impl RepositoryStore {
                 repo.archive_delete_branch.map(|value| value as i32),
                 repo.archive_remote_prompt.map(|value| value as i32),
                 Utc::now().to_rfc3339(),
+                repo.system_prompt,
+                repo.cost_center,
             ],
         )?;
         Ok(())
@@ -122,7 +126,7 @@ impl RepositoryStore {
         let conn = self.conn.lock().unwrap();
         let path_str = path.to_string_lossy().to_string();
         let mut stmt = conn.prepare(
-            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at
+            "SELECT id, name, base_path, repository_url, workspace_mode, archive_delete_branch, archive_remote_prompt, created_at, updated_at, system_prompt, cost_center
              FROM repositories WHERE base_path = ?1",
         )?;
 
@@ -176,6 +180,8 @@ impl RepositoryStore {
             updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
                 .map(|dt| dt.with_timezone(&Utc))
                 .unwrap_or_else(|_| Utc::now()),
+            system_prompt: row.get(9)?,
+            cost_center: row.get(10)?,
         })
     }
 
@@ -205,6 +211,26 @@ impl RepositoryStore {
         )?;
         Ok(())
     }
+
+    /// Set or clear the default base instructions for new sessions.
+    pub fn update_system_prompt(&self, id: Uuid, system_prompt: Option<&str>) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE repositories SET system_prompt = ?2, updated_at = ?3 WHERE id = ?1",
+            params![id.to_string(), system_prompt, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Set or clear the billing tag for spend in this repository.
+    pub fn update_cost_center(&self, id: Uuid, cost_center: Option<&str>) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE repositories SET cost_center = ?2, updated_at = ?3 WHERE id = ?1",
+            params![id.to_string(), cost_center, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -259,4 +285,23 @@ mod tests {
         dao.delete(repo.id).unwrap();
         assert!(dao.get_by_id(repo.id).unwrap().is_none());
     }
+
+    #[test]
+    fn test_update_system_prompt() {
+        let (_dir, _db, dao) = setup_db();
+        let repo = Repository::from_local_path("prompted", PathBuf::from("/tmp/prompted"));
+        dao.create(&repo).unwrap();
+
+        dao.update_system_prompt(repo.id, Some("Prefer small commits."))
+            .unwrap();
+        let retrieved = dao.get_by_id(repo.id).unwrap().unwrap();
+        assert_eq!(
+            retrieved.system_prompt.as_deref(),
+            Some("Prefer small commits.")
+        );
+
+        dao.update_system_prompt(repo.id, None).unwrap();
+        let retrieved = dao.get_by_id(repo.id).unwrap().unwrap();
+        assert!(retrieved.system_prompt.is_none());
+    }
 }
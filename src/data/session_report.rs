@@ -0,0 +1,142 @@
+//! Session report data access object
+//!
+//! Keeps one final report per closed or archived session; closing the same
+//! session again replaces it.
+
+use super::models::SessionReport;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Data access object for session reports
+#[derive(Clone)]
+pub struct SessionReportStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SessionReportStore {
+    /// Create a new SessionReportStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Store a report, replacing any earlier report of the same session
+    pub fn save(&self, report: &SessionReport) -> SqliteResult<()> {
+        let files_changed = serde_json::to_string(&report.files_changed)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO session_reports
+             (session_id, title, total_cost_usd, turns, files_changed, pr_number, pr_url, checks_passed, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                report.session_id.to_string(),
+                report.title,
+                report.total_cost_usd,
+                report.turns,
+                files_changed,
+                report.pr_number,
+                report.pr_url,
+                report.checks_passed,
+                report.created_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Report of a session, if it was closed with one
+    pub fn get_by_session(&self, session_id: Uuid) -> SqliteResult<Option<SessionReport>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT session_id, title, total_cost_usd, turns, files_changed, pr_number, pr_url, checks_passed, created_at
+             FROM session_reports WHERE session_id = ?1",
+            params![session_id.to_string()],
+            Self::row_to_report,
+        )
+        .optional()
+    }
+
+    /// Every stored report, by session ID
+    pub fn get_all(&self) -> SqliteResult<HashMap<Uuid, SessionReport>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT session_id, title, total_cost_usd, turns, files_changed, pr_number, pr_url, checks_passed, created_at
+             FROM session_reports",
+        )?;
+        let reports = stmt
+            .query_map([], Self::row_to_report)?
+            .map(|report| report.map(|report| (report.session_id, report)))
+            .collect::<SqliteResult<HashMap<_, _>>>()?;
+        Ok(reports)
+    }
+
+    /// Convert a database row to a SessionReport
+    fn row_to_report(row: &rusqlite::Row) -> SqliteResult<SessionReport> {
+        let session_id_str: String = row.get(0)?;
+        let files_changed_json: String = row.get(4)?;
+        let created_at_str: String = row.get(8)?;
+
+        let session_id = Uuid::parse_str(&session_id_str).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+        let files_changed = serde_json::from_str(&files_changed_json).unwrap_or_else(|e| {
+            tracing::warn!(%session_id, error = %e, "Unreadable files list in session report");
+            Vec::new()
+        });
+
+        Ok(SessionReport {
+            session_id,
+            title: row.get(1)?,
+            total_cost_usd: row.get(2)?,
+            turns: row.get(3)?,
+            files_changed,
+            pr_number: row.get(5)?,
+            pr_url: row.get(6)?,
+            checks_passed: row.get(7)?,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use tempfile::tempdir;
+
+    fn report(session_id: Uuid, turns: u32) -> SessionReport {
+        SessionReport {
+            session_id,
+            title: Some("Fix login".to_string()),
+            total_cost_usd: 1.25,
+            turns,
+            files_changed: vec!["src/auth.rs".to_string()],
+            pr_number: Some(12),
+            pr_url: Some("https://github.com/o/r/pull/12".to_string()),
+            checks_passed: Some(true),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_save_replaces_and_round_trips() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = SessionReportStore::new(db.connection());
+        let session_id = Uuid::new_v4();
+
+        store.save(&report(session_id, 3)).unwrap();
+        store.save(&report(session_id, 5)).unwrap();
+
+        let found = store.get_by_session(session_id).unwrap().unwrap();
+        assert_eq!(found.turns, 5);
+        assert_eq!(found.files_changed, vec!["src/auth.rs".to_string()]);
+        assert_eq!(found.checks_passed, Some(true));
+        assert_eq!(store.get_all().unwrap().len(), 1);
+        assert!(store.get_by_session(Uuid::new_v4()).unwrap().is_none());
+    }
+}
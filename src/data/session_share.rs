@@ -0,0 +1,202 @@
+//! Session share link data access object
+
+use super::models::SessionShare;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Result as SqliteResult};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Data access object for session share links
+#[derive(Clone)]
+pub struct SessionShareStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SessionShareStore {
+    /// Create a new SessionShareStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Insert a new share link
+    pub fn create(&self, share: &SessionShare) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO session_shares (id, session_id, token, redact_tool_output, created_at, expires_at, revoked_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                share.id.to_string(),
+                share.session_id.to_string(),
+                share.token,
+                if share.redact_tool_output { 1 } else { 0 },
+                share.created_at.to_rfc3339(),
+                share.expires_at.to_rfc3339(),
+                share.revoked_at.map(|at| at.to_rfc3339()),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Get a share link by ID
+    pub fn get_by_id(&self, id: Uuid) -> SqliteResult<Option<SessionShare>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, token, redact_tool_output, created_at, expires_at, revoked_at
+             FROM session_shares WHERE id = ?1",
+        )?;
+
+        let mut rows = stmt.query(params![id.to_string()])?;
+        if let Some(row) = rows.next()? {
+            Ok(Some(Self::row_to_share(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Get a share link by its URL token (including revoked or expired links)
+    pub fn get_by_token(&self, token: &str) -> SqliteResult<Option<SessionShare>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, token, redact_tool_output, created_at, expires_at, revoked_at
+             FROM session_shares WHERE token = ?1",
+        )?;
+
+        let mut rows = stmt.query(params![token])?;
+        if let Some(row) = rows.next()? {
+            Ok(Some(Self::row_to_share(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// List share links for a session, newest first
+    pub fn get_by_session(&self, session_id: Uuid) -> SqliteResult<Vec<SessionShare>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, token, redact_tool_output, created_at, expires_at, revoked_at
+             FROM session_shares WHERE session_id = ?1 ORDER BY created_at DESC",
+        )?;
+
+        let shares = stmt
+            .query_map(params![session_id.to_string()], Self::row_to_share)?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        Ok(shares)
+    }
+
+    /// Revoke a share link so its token no longer resolves
+    pub fn revoke(&self, id: Uuid) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE session_shares SET revoked_at = COALESCE(revoked_at, ?2) WHERE id = ?1",
+            params![id.to_string(), Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Convert a database row to a SessionShare
+    fn row_to_share(row: &rusqlite::Row) -> SqliteResult<SessionShare> {
+        let id_str: String = row.get(0)?;
+        let session_id_str: String = row.get(1)?;
+        let redact_tool_output: i64 = row.get(3)?;
+        let created_at_str: String = row.get(4)?;
+        let expires_at_str: String = row.get(5)?;
+        let revoked_at_str: Option<String> = row.get(6)?;
+
+        Ok(SessionShare {
+            id: Self::parse_uuid(&id_str, 0)?,
+            session_id: Self::parse_uuid(&session_id_str, 1)?,
+            token: row.get(2)?,
+            redact_tool_output: redact_tool_output != 0,
+            created_at: Self::parse_timestamp(&created_at_str, 4)?,
+            expires_at: Self::parse_timestamp(&expires_at_str, 5)?,
+            revoked_at: revoked_at_str
+                .map(|value| Self::parse_timestamp(&value, 6))
+                .transpose()?,
+        })
+    }
+
+    fn parse_uuid(value: &str, column: usize) -> SqliteResult<Uuid> {
+        Uuid::parse_str(value).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(
+                column,
+                rusqlite::types::Type::Text,
+                Box::new(e),
+            )
+        })
+    }
+
+    // Share validity depends on these timestamps, so reject unparsable values
+    // instead of substituting "now" like the other stores do.
+    fn parse_timestamp(value: &str, column: usize) -> SqliteResult<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(value)
+            .map(|dt| dt.with_timezone(&Utc))
+            .map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    column,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentType;
+    use crate::data::{Database, SessionTab, SessionTabStore};
+    use chrono::Duration;
+    use tempfile::tempdir;
+
+    fn setup_db() -> (
+        tempfile::TempDir,
+        Database,
+        SessionTabStore,
+        SessionShareStore,
+    ) {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let tabs = SessionTabStore::new(db.connection());
+        let shares = SessionShareStore::new(db.connection());
+        (dir, db, tabs, shares)
+    }
+
+    #[test]
+    fn test_create_lookup_and_revoke() {
+        let (_dir, _db, tabs, shares) = setup_db();
+        let tab = SessionTab::new(0, AgentType::Claude, None, None, None, None);
+        tabs.create(&tab).unwrap();
+
+        let share = SessionShare::new(tab.id, Utc::now() + Duration::hours(1), true);
+        shares.create(&share).unwrap();
+
+        let found = shares.get_by_token(&share.token).unwrap().unwrap();
+        assert_eq!(found.id, share.id);
+        assert!(found.redact_tool_output);
+        assert!(found.is_active_at(Utc::now()));
+
+        shares.revoke(share.id).unwrap();
+        let revoked = shares.get_by_id(share.id).unwrap().unwrap();
+        assert!(!revoked.is_active_at(Utc::now()));
+        assert_eq!(shares.get_by_session(tab.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_shares_deleted_with_session() {
+        let (_dir, _db, tabs, shares) = setup_db();
+        let tab = SessionTab::new(0, AgentType::Codex, None, None, None, None);
+        tabs.create(&tab).unwrap();
+        let share = SessionShare::new(tab.id, Utc::now() + Duration::hours(1), false);
+        shares.create(&share).unwrap();
+
+        tabs.delete(tab.id).unwrap();
+        assert!(shares.get_by_token(&share.token).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_expired_share_is_inactive() {
+        let share = SessionShare::new(Uuid::new_v4(), Utc::now() - Duration::minutes(1), false);
+        assert!(!share.is_active_at(Utc::now()));
+    }
+}
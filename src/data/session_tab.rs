@@ -1,9 +1,12 @@
 //! Session tab data access object
 
 use super::models::{QueuedMessage, SessionTab};
+use super::transcript::TranscriptStore;
 use crate::agent::AgentType;
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection, Result as SqliteResult};
+use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tracing::warn;
 use uuid::Uuid;
@@ -39,9 +42,10 @@ impl SessionTabStore {
         let conn = self.conn.lock().unwrap();
         let queued_messages = serialize_queued_messages(&tab.queued_messages);
         let input_history = serialize_input_history(&tab.input_history);
+        let linked_workspaces = serialize_linked_workspaces(&tab.linked_workspace_ids);
         conn.execute(
-            "INSERT INTO session_tabs (id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
+            "INSERT INTO session_tabs (id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated, codex_sandbox, codex_approval, system_prompt, total_cost_usd, response_language, response_preset, cost_center, working_dir, custom_title, linked_workspaces)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27)
              ON CONFLICT(id) DO UPDATE SET
                tab_index = excluded.tab_index,
                is_open = excluded.is_open,
@@ -57,7 +61,17 @@ impl SessionTabStore {
                input_history = excluded.input_history,
                fork_seed_id = excluded.fork_seed_id,
                title = excluded.title,
-               title_generated = excluded.title_generated",
+               title_generated = excluded.title_generated,
+               codex_sandbox = excluded.codex_sandbox,
+               codex_approval = excluded.codex_approval,
+               system_prompt = excluded.system_prompt,
+               total_cost_usd = excluded.total_cost_usd,
+               response_language = excluded.response_language,
+               response_preset = excluded.response_preset,
+               cost_center = excluded.cost_center,
+               working_dir = excluded.working_dir,
+               custom_title = excluded.custom_title,
+               linked_workspaces = excluded.linked_workspaces",
             params![
                 tab.id.to_string(),
                 tab.tab_index,
@@ -76,6 +90,18 @@ impl SessionTabStore {
                 tab.fork_seed_id.map(|id| id.to_string()),
                 tab.title,
                 if tab.title_generated { 1 } else { 0 },
+                tab.codex_sandbox,
+                tab.codex_approval,
+                tab.system_prompt,
+                tab.total_cost_usd,
+                tab.response_language,
+                tab.response_preset,
+                tab.cost_center,
+                tab.working_dir
+                    .as_ref()
+                    .map(|path| path.to_string_lossy().into_owned()),
+                tab.custom_title,
+                linked_workspaces,
             ],
         )?;
         Ok(())
@@ -84,9 +110,10 @@ impl SessionTabStore {
     fn insert_with_conn(conn: &Connection, tab: &SessionTab) -> SqliteResult<()> {
         let queued_messages = serialize_queued_messages(&tab.queued_messages);
         let input_history = serialize_input_history(&tab.input_history);
+        let linked_workspaces = serialize_linked_workspaces(&tab.linked_workspace_ids);
         conn.execute(
-            "INSERT INTO session_tabs (id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+            "INSERT INTO session_tabs (id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated, codex_sandbox, codex_approval, system_prompt, total_cost_usd, response_language, response_preset, cost_center, working_dir, custom_title, linked_workspaces)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27)",
             params![
                 tab.id.to_string(),
                 tab.tab_index,
@@ -105,6 +132,18 @@ impl SessionTabStore {
                 tab.fork_seed_id.map(|id| id.to_string()),
                 tab.title,
                 if tab.title_generated { 1 } else { 0 },
+                tab.codex_sandbox,
+                tab.codex_approval,
+                tab.system_prompt,
+                tab.total_cost_usd,
+                tab.response_language,
+                tab.response_preset,
+                tab.cost_center,
+                tab.working_dir
+                    .as_ref()
+                    .map(|path| path.to_string_lossy().into_owned()),
+                tab.custom_title,
+                linked_workspaces,
             ],
         )?;
         Ok(())
@@ -113,9 +152,10 @@ impl SessionTabStore {
     pub(crate) fn update_with_conn(conn: &Connection, tab: &SessionTab) -> SqliteResult<()> {
         let queued_messages = serialize_queued_messages(&tab.queued_messages);
         let input_history = serialize_input_history(&tab.input_history);
+        let linked_workspaces = serialize_linked_workspaces(&tab.linked_workspace_ids);
         conn.execute(
             "UPDATE session_tabs SET tab_index = ?2, is_open = ?3, workspace_id = ?4, agent_type = ?5, agent_mode = ?6,
-             agent_session_id = ?7, model = ?8, model_invalid = ?9, pr_number = ?10, pending_user_message = ?11, queued_messages = ?12, input_history = ?13, fork_seed_id = ?14, title = ?15 WHERE id = ?1",
+             agent_session_id = ?7, model = ?8, model_invalid = ?9, pr_number = ?10, pending_user_message = ?11, queued_messages = ?12, input_history = ?13, fork_seed_id = ?14, title = ?15, codex_sandbox = ?16, codex_approval = ?17, system_prompt = ?18, total_cost_usd = ?19, response_language = ?20, response_preset = ?21, cost_center = ?22, working_dir = ?23, custom_title = ?24, linked_workspaces = ?25 WHERE id = ?1",
             params![
                 tab.id.to_string(),
                 tab.tab_index,
@@ -132,6 +172,18 @@ impl SessionTabStore {
                 input_history,
                 tab.fork_seed_id.map(|id| id.to_string()),
                 tab.title,
+                tab.codex_sandbox,
+                tab.codex_approval,
+                tab.system_prompt,
+                tab.total_cost_usd,
+                tab.response_language,
+                tab.response_preset,
+                tab.cost_center,
+                tab.working_dir
+                    .as_ref()
+                    .map(|path| path.to_string_lossy().into_owned()),
+                tab.custom_title,
+                linked_workspaces,
             ],
         )?;
         Ok(())
@@ -144,7 +196,7 @@ impl SessionTabStore {
         // sessions closed, but older DBs may still contain "open" sessions pointing at archived
         // workspaces.)
         let mut stmt = conn.prepare(
-            "SELECT st.id, st.tab_index, st.is_open, st.workspace_id, st.agent_type, st.agent_mode, st.agent_session_id, st.model, st.model_invalid, st.pr_number, st.created_at, st.pending_user_message, st.queued_messages, st.input_history, st.fork_seed_id, st.title, st.title_generated
+            "SELECT st.id, st.tab_index, st.is_open, st.workspace_id, st.agent_type, st.agent_mode, st.agent_session_id, st.model, st.model_invalid, st.pr_number, st.created_at, st.pending_user_message, st.queued_messages, st.input_history, st.fork_seed_id, st.title, st.title_generated, st.codex_sandbox, st.codex_approval, st.system_prompt, st.total_cost_usd, st.response_language, st.response_preset, st.cost_center, st.working_dir, st.custom_title, st.linked_workspaces
              FROM session_tabs st
              LEFT JOIN workspaces w ON st.workspace_id = w.id
              WHERE st.is_open = 1
@@ -163,11 +215,67 @@ impl SessionTabStore {
         Ok(tabs)
     }
 
+    /// Get every saved session tab, open or closed, newest first. Sessions of
+    /// archived workspaces are left out.
+    pub fn get_all_including_closed(&self) -> SqliteResult<Vec<SessionTab>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT st.id, st.tab_index, st.is_open, st.workspace_id, st.agent_type, st.agent_mode, st.agent_session_id, st.model, st.model_invalid, st.pr_number, st.created_at, st.pending_user_message, st.queued_messages, st.input_history, st.fork_seed_id, st.title, st.title_generated, st.codex_sandbox, st.codex_approval, st.system_prompt, st.total_cost_usd, st.response_language, st.response_preset, st.cost_center, st.working_dir, st.custom_title, st.linked_workspaces
+             FROM session_tabs st
+             LEFT JOIN workspaces w ON st.workspace_id = w.id
+             WHERE st.workspace_id IS NULL
+                OR (w.id IS NOT NULL AND w.archived_at IS NULL)
+             ORDER BY st.created_at DESC",
+        )?;
+
+        let tabs = stmt
+            .query_map([], Self::row_to_session_tab)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(tabs)
+    }
+
+    /// Get the `limit` most recently active saved sessions with when they
+    /// were last active: the latest transcript message, or the creation time
+    /// for sessions without a persisted transcript. Sessions of archived
+    /// workspaces are left out.
+    pub fn get_recent(&self, limit: usize) -> SqliteResult<Vec<(SessionTab, DateTime<Utc>)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT st.id, st.tab_index, st.is_open, st.workspace_id, st.agent_type, st.agent_mode, st.agent_session_id, st.model, st.model_invalid, st.pr_number, st.created_at, st.pending_user_message, st.queued_messages, st.input_history, st.fork_seed_id, st.title, st.title_generated, st.codex_sandbox, st.codex_approval, st.system_prompt, st.total_cost_usd, st.response_language, st.response_preset, st.cost_center, st.working_dir, st.custom_title, st.linked_workspaces,
+                    COALESCE(
+                        (SELECT MAX(tm.recorded_at) FROM transcript_messages tm WHERE tm.session_id = st.id),
+                        st.created_at
+                    ) AS last_active_at
+             FROM session_tabs st
+             LEFT JOIN workspaces w ON st.workspace_id = w.id
+             WHERE st.workspace_id IS NULL
+                OR (w.id IS NOT NULL AND w.archived_at IS NULL)
+             ORDER BY last_active_at DESC
+             LIMIT ?1",
+        )?;
+
+        let tabs = stmt
+            .query_map(params![limit as i64], |row| {
+                let tab = Self::row_to_session_tab(row)?;
+                let last_active: String = row.get("last_active_at")?;
+                let last_active = DateTime::parse_from_rfc3339(&last_active)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or(tab.created_at);
+                Ok((tab, last_active))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(tabs)
+    }
+
     /// Get a session tab by ID
     pub fn get_by_id(&self, id: Uuid) -> SqliteResult<Option<SessionTab>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated
+            "SELECT id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated, codex_sandbox, codex_approval, system_prompt, total_cost_usd, response_language, response_preset, cost_center, working_dir, custom_title, linked_workspaces
              FROM session_tabs WHERE id = ?1",
         )?;
 
@@ -184,9 +292,10 @@ impl SessionTabStore {
         let conn = self.conn.lock().unwrap();
         let queued_messages = serialize_queued_messages(&tab.queued_messages);
         let input_history = serialize_input_history(&tab.input_history);
+        let linked_workspaces = serialize_linked_workspaces(&tab.linked_workspace_ids);
         conn.execute(
             "UPDATE session_tabs SET tab_index = ?2, is_open = ?3, workspace_id = ?4, agent_type = ?5, agent_mode = ?6,
-             agent_session_id = ?7, model = ?8, model_invalid = ?9, pr_number = ?10, pending_user_message = ?11, queued_messages = ?12, input_history = ?13, fork_seed_id = ?14, title = ?15, title_generated = ?16 WHERE id = ?1",
+             agent_session_id = ?7, model = ?8, model_invalid = ?9, pr_number = ?10, pending_user_message = ?11, queued_messages = ?12, input_history = ?13, fork_seed_id = ?14, title = ?15, title_generated = ?16, codex_sandbox = ?17, codex_approval = ?18, system_prompt = ?19, total_cost_usd = ?20, response_language = ?21, response_preset = ?22, cost_center = ?23, working_dir = ?24, custom_title = ?25, linked_workspaces = ?26 WHERE id = ?1",
             params![
                 tab.id.to_string(),
                 tab.tab_index,
@@ -204,26 +313,42 @@ impl SessionTabStore {
                 tab.fork_seed_id.map(|id| id.to_string()),
                 tab.title,
                 if tab.title_generated { 1 } else { 0 },
+                tab.codex_sandbox,
+                tab.codex_approval,
+                tab.system_prompt,
+                tab.total_cost_usd,
+                tab.response_language,
+                tab.response_preset,
+                tab.cost_center,
+                tab.working_dir
+                    .as_ref()
+                    .map(|path| path.to_string_lossy().into_owned()),
+                tab.custom_title,
+                linked_workspaces,
             ],
         )?;
         Ok(())
     }
 
-    /// Delete a session tab
+    /// Delete a session tab and its transcript
     pub fn delete(&self, id: Uuid) -> SqliteResult<()> {
-        let conn = self.conn.lock().unwrap();
-        conn.execute(
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute(
             "DELETE FROM session_tabs WHERE id = ?1",
             params![id.to_string()],
         )?;
-        Ok(())
+        TranscriptStore::delete_session_with_conn(&tx, id)?;
+        tx.commit()
     }
 
-    /// Clear all session tabs
+    /// Clear all session tabs and their transcripts
     pub fn clear_all(&self) -> SqliteResult<()> {
-        let conn = self.conn.lock().unwrap();
-        conn.execute("DELETE FROM session_tabs", [])?;
-        Ok(())
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM session_tabs", [])?;
+        TranscriptStore::clear_all_with_conn(&tx)?;
+        tx.commit()
     }
 
     /// Get count of session tabs
@@ -245,7 +370,7 @@ impl SessionTabStore {
         workspace_id: Uuid,
     ) -> SqliteResult<Option<SessionTab>> {
         let mut stmt = conn.prepare(
-            "SELECT id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated
+            "SELECT id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated, codex_sandbox, codex_approval, system_prompt, total_cost_usd, response_language, response_preset, cost_center, working_dir, custom_title, linked_workspaces
              FROM session_tabs WHERE workspace_id = ?1 ORDER BY is_open DESC, created_at DESC LIMIT 1",
         )?;
 
@@ -267,7 +392,7 @@ impl SessionTabStore {
         workspace_id: Uuid,
     ) -> SqliteResult<Option<SessionTab>> {
         let mut stmt = conn.prepare(
-            "SELECT id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated
+            "SELECT id, tab_index, is_open, workspace_id, agent_type, agent_mode, agent_session_id, model, model_invalid, pr_number, created_at, pending_user_message, queued_messages, input_history, fork_seed_id, title, title_generated, codex_sandbox, codex_approval, system_prompt, total_cost_usd, response_language, response_preset, cost_center, working_dir, custom_title, linked_workspaces
              FROM session_tabs WHERE workspace_id = ?1 AND is_open = 1 ORDER BY created_at DESC LIMIT 1",
         )?;
 
@@ -289,6 +414,16 @@ impl SessionTabStore {
         Ok(())
     }
 
+    /// Add to a session's accumulated cost.
+    pub fn add_cost(&self, id: Uuid, delta_usd: f64) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE session_tabs SET total_cost_usd = total_cost_usd + ?2 WHERE id = ?1",
+            params![id.to_string(), delta_usd],
+        )?;
+        Ok(())
+    }
+
     /// Set open/closed state for all sessions under a workspace.
     pub fn set_open_by_workspace(&self, workspace_id: Uuid, is_open: bool) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
@@ -299,6 +434,18 @@ impl SessionTabStore {
         Ok(())
     }
 
+    /// Agent session IDs of every saved tab, open or closed
+    pub fn agent_session_ids(&self) -> SqliteResult<HashSet<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT agent_session_id FROM session_tabs WHERE agent_session_id IS NOT NULL",
+        )?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<SqliteResult<HashSet<String>>>()?;
+        Ok(ids)
+    }
+
     /// Allocate the next tab index value.
     pub fn next_tab_index(&self) -> SqliteResult<i32> {
         let conn = self.conn.lock().unwrap();
@@ -358,6 +505,9 @@ impl SessionTabStore {
         let fork_seed_id_str: Option<String> = row.get("fork_seed_id")?;
         let title_generated: i64 = row.get("title_generated")?;
         let model_invalid: i64 = row.get("model_invalid")?;
+        let working_dir: Option<String> = row.get("working_dir")?;
+        let linked_workspaces_json: Option<String> = row.get("linked_workspaces")?;
+        let linked_workspace_ids = deserialize_linked_workspaces(linked_workspaces_json.as_deref());
 
         Ok(SessionTab {
             id: Uuid::parse_str(&id_str).unwrap_or_else(|_| Uuid::new_v4()),
@@ -379,6 +529,16 @@ impl SessionTabStore {
             fork_seed_id: fork_seed_id_str.and_then(|s| Uuid::parse_str(&s).ok()),
             title: row.get("title")?,
             title_generated: title_generated != 0,
+            codex_sandbox: row.get("codex_sandbox")?,
+            codex_approval: row.get("codex_approval")?,
+            system_prompt: row.get("system_prompt")?,
+            total_cost_usd: row.get("total_cost_usd")?,
+            response_language: row.get("response_language")?,
+            response_preset: row.get("response_preset")?,
+            cost_center: row.get("cost_center")?,
+            working_dir: working_dir.map(PathBuf::from),
+            custom_title: row.get("custom_title")?,
+            linked_workspace_ids,
         })
     }
 }
@@ -417,6 +577,23 @@ fn deserialize_input_history(raw: Option<&str>) -> Vec<String> {
     }
 }
 
+fn serialize_linked_workspaces(ids: &[Uuid]) -> String {
+    serde_json::to_string(ids).unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "Failed to serialize linked_workspaces");
+        "[]".to_string()
+    })
+}
+
+fn deserialize_linked_workspaces(raw: Option<&str>) -> Vec<Uuid> {
+    match raw {
+        Some(value) => serde_json::from_str::<Vec<Uuid>>(value).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to deserialize linked_workspaces");
+            Vec::new()
+        }),
+        None => Vec::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -473,6 +650,19 @@ mod tests {
         assert_eq!(retrieved.queued_messages, tab.queued_messages);
     }
 
+    #[test]
+    fn test_add_cost_accumulates() {
+        let (_dir, _db, dao) = setup_db();
+        let mut tab = SessionTab::new(0, AgentType::Claude, None, None, None, None);
+        tab.total_cost_usd = 0.25;
+        dao.create(&tab).unwrap();
+
+        dao.add_cost(tab.id, 0.5).unwrap();
+        let retrieved = dao.get_by_id(tab.id).unwrap().unwrap();
+
+        assert!((retrieved.total_cost_usd - 0.75).abs() < 1e-9);
+    }
+
     #[test]
     fn test_get_all_ordered() {
         let (_dir, _db, dao) = setup_db();
@@ -490,18 +680,107 @@ mod tests {
     }
 
     #[test]
-    fn test_clear_all() {
+    fn test_get_all_including_closed() {
         let (_dir, _db, dao) = setup_db();
 
+        let open = SessionTab::new(0, AgentType::Claude, None, None, None, None);
+        let mut closed = SessionTab::new(1, AgentType::Codex, None, None, None, None);
+        closed.is_open = false;
+        closed.created_at = open.created_at + chrono::Duration::seconds(1);
+        dao.create(&open).unwrap();
+        dao.create(&closed).unwrap();
+
+        assert_eq!(dao.get_all().unwrap().len(), 1);
+        let all = dao.get_all_including_closed().unwrap();
+        let ids: Vec<_> = all.iter().map(|tab| tab.id).collect();
+        assert_eq!(ids, vec![closed.id, open.id]);
+    }
+
+    #[test]
+    fn test_get_recent_orders_by_last_activity() {
+        let (_dir, db, dao) = setup_db();
+
+        let older = SessionTab::new(0, AgentType::Claude, None, None, None, None);
+        let mut newer = SessionTab::new(1, AgentType::Codex, None, None, None, None);
+        newer.created_at = older.created_at + chrono::Duration::seconds(1);
+        dao.create(&older).unwrap();
+        dao.create(&newer).unwrap();
+
+        let ids = |recent: Vec<(SessionTab, DateTime<Utc>)>| -> Vec<Uuid> {
+            recent.into_iter().map(|(tab, _)| tab.id).collect()
+        };
+        assert_eq!(ids(dao.get_recent(5).unwrap()), vec![newer.id, older.id]);
+
+        let resumed_at = newer.created_at + chrono::Duration::minutes(5);
+        let message = crate::data::TranscriptMessage {
+            session_id: older.id,
+            role: "User".to_string(),
+            message_json: "{}".to_string(),
+            recorded_at: resumed_at,
+        };
+        crate::data::TranscriptStore::new(db.connection())
+            .insert_batch(&[message], &[])
+            .unwrap();
+        let recent = dao.get_recent(1).unwrap();
+        assert_eq!(recent[0].1, resumed_at);
+        assert_eq!(ids(recent), vec![older.id]);
+    }
+
+    fn transcript_message(session_id: Uuid) -> crate::data::TranscriptMessage {
+        crate::data::TranscriptMessage {
+            session_id,
+            role: "User".to_string(),
+            message_json: "{}".to_string(),
+            recorded_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_clear_all() {
+        let (_dir, db, dao) = setup_db();
+        let transcripts = TranscriptStore::new(db.connection());
+
         let tab1 = SessionTab::new(0, AgentType::Claude, None, None, None, None);
         let tab2 = SessionTab::new(1, AgentType::Codex, None, None, None, None);
 
         dao.create(&tab1).unwrap();
         dao.create(&tab2).unwrap();
+        transcripts
+            .insert_batch(&[transcript_message(tab1.id)], &[])
+            .unwrap();
         assert_eq!(dao.count().unwrap(), 2);
 
         dao.clear_all().unwrap();
         assert_eq!(dao.count().unwrap(), 0);
+        assert!(transcripts
+            .messages_for_session(tab1.id)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_delete_removes_only_that_sessions_transcript() {
+        let (_dir, db, dao) = setup_db();
+        let transcripts = TranscriptStore::new(db.connection());
+
+        let deleted = SessionTab::new(0, AgentType::Claude, None, None, None, None);
+        let kept = SessionTab::new(1, AgentType::Codex, None, None, None, None);
+        dao.create(&deleted).unwrap();
+        dao.create(&kept).unwrap();
+        transcripts
+            .insert_batch(
+                &[transcript_message(deleted.id), transcript_message(kept.id)],
+                &[],
+            )
+            .unwrap();
+
+        dao.delete(deleted.id).unwrap();
+        assert!(dao.get_by_id(deleted.id).unwrap().is_none());
+        assert!(transcripts
+            .messages_for_session(deleted.id)
+            .unwrap()
+            .is_empty());
+        assert_eq!(transcripts.messages_for_session(kept.id).unwrap().len(), 1);
     }
 
     #[test]
@@ -534,4 +813,39 @@ mod tests {
         assert_eq!(retrieved.queued_messages.len(), 1);
         assert_eq!(retrieved.input_history, tab.input_history);
     }
+
+    #[test]
+    fn test_custom_title_survives_upsert() {
+        let (_dir, _db, dao) = setup_db();
+        let mut tab = SessionTab::new(0, AgentType::Claude, None, None, None, None);
+        tab.title = Some("Generated title".to_string());
+        tab.custom_title = Some("Release prep".to_string());
+        dao.upsert(&tab).unwrap();
+
+        let retrieved = dao.get_by_id(tab.id).unwrap().unwrap();
+        assert_eq!(retrieved.custom_title.as_deref(), Some("Release prep"));
+        assert_eq!(retrieved.title.as_deref(), Some("Generated title"));
+
+        tab.custom_title = None;
+        dao.upsert(&tab).unwrap();
+        assert_eq!(dao.get_by_id(tab.id).unwrap().unwrap().custom_title, None);
+    }
+
+    #[test]
+    fn test_linked_workspaces_roundtrip() {
+        let (_dir, _db, dao) = setup_db();
+        let mut tab = SessionTab::new(0, AgentType::Claude, None, None, None, None);
+        dao.create(&tab).unwrap();
+        assert!(dao
+            .get_by_id(tab.id)
+            .unwrap()
+            .unwrap()
+            .linked_workspace_ids
+            .is_empty());
+
+        tab.linked_workspace_ids = vec![Uuid::new_v4(), Uuid::new_v4()];
+        dao.update(&tab).unwrap();
+        let retrieved = dao.get_by_id(tab.id).unwrap().unwrap();
+        assert_eq!(retrieved.linked_workspace_ids, tab.linked_workspace_ids);
+    }
 }
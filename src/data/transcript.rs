@@ -0,0 +1,223 @@
+//! Transcript data access object
+//!
+//! Chat messages (as JSON) and raw agent events of each session, written in
+//! batches by the [`WriteBehindQueue`](super::WriteBehindQueue).
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Result as SqliteResult, Row};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// A chat message as stored in the transcript
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptMessage {
+    pub session_id: Uuid,
+    /// Message role, e.g. `User` or `Tool`
+    pub role: String,
+    /// The serialized chat message
+    pub message_json: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// A raw agent event as stored in the transcript
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptEvent {
+    pub session_id: Uuid,
+    /// `sent` or `received`
+    pub direction: String,
+    pub event_type: String,
+    pub payload_json: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Data access object for persisted transcripts
+#[derive(Clone)]
+pub struct TranscriptStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl TranscriptStore {
+    /// Create a new TranscriptStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Append messages and events in one transaction
+    pub fn insert_batch(
+        &self,
+        messages: &[TranscriptMessage],
+        events: &[TranscriptEvent],
+    ) -> SqliteResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO transcript_messages (session_id, role, message, recorded_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for message in messages {
+                stmt.execute(params![
+                    message.session_id.to_string(),
+                    message.role,
+                    message.message_json,
+                    message.recorded_at.to_rfc3339(),
+                ])?;
+            }
+            let mut stmt = tx.prepare(
+                "INSERT INTO transcript_events (session_id, direction, event_type, payload, recorded_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            for event in events {
+                stmt.execute(params![
+                    event.session_id.to_string(),
+                    event.direction,
+                    event.event_type,
+                    event.payload_json,
+                    event.recorded_at.to_rfc3339(),
+                ])?;
+            }
+        }
+        tx.commit()
+    }
+
+    /// Messages of a session in the order they were written
+    pub fn messages_for_session(&self, session_id: Uuid) -> SqliteResult<Vec<TranscriptMessage>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT session_id, role, message, recorded_at FROM transcript_messages
+             WHERE session_id = ?1 ORDER BY id",
+        )?;
+        let messages = stmt
+            .query_map(params![session_id.to_string()], |row| {
+                Ok(TranscriptMessage {
+                    session_id: parse_uuid(row, 0)?,
+                    role: row.get(1)?,
+                    message_json: row.get(2)?,
+                    recorded_at: parse_timestamp(row, 3)?,
+                })
+            })?
+            .collect::<SqliteResult<Vec<_>>>()?;
+        Ok(messages)
+    }
+
+    /// Raw events of a session in the order they were written
+    pub fn events_for_session(&self, session_id: Uuid) -> SqliteResult<Vec<TranscriptEvent>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT session_id, direction, event_type, payload, recorded_at FROM transcript_events
+             WHERE session_id = ?1 ORDER BY id",
+        )?;
+        let events = stmt
+            .query_map(params![session_id.to_string()], |row| {
+                Ok(TranscriptEvent {
+                    session_id: parse_uuid(row, 0)?,
+                    direction: row.get(1)?,
+                    event_type: row.get(2)?,
+                    payload_json: row.get(3)?,
+                    recorded_at: parse_timestamp(row, 4)?,
+                })
+            })?
+            .collect::<SqliteResult<Vec<_>>>()?;
+        Ok(events)
+    }
+
+    /// Delete everything stored for a session
+    pub fn delete_session(&self, session_id: Uuid) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        Self::delete_session_with_conn(&conn, session_id)
+    }
+
+    /// Delete a session's transcript on a connection the caller already holds,
+    /// e.g. inside the transaction that deletes its session tab
+    pub(crate) fn delete_session_with_conn(
+        conn: &Connection,
+        session_id: Uuid,
+    ) -> SqliteResult<()> {
+        let session_id = session_id.to_string();
+        conn.execute(
+            "DELETE FROM transcript_messages WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        conn.execute(
+            "DELETE FROM transcript_events WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        Ok(())
+    }
+
+    /// Delete every transcript on a connection the caller already holds
+    pub(crate) fn clear_all_with_conn(conn: &Connection) -> SqliteResult<()> {
+        conn.execute("DELETE FROM transcript_messages", [])?;
+        conn.execute("DELETE FROM transcript_events", [])?;
+        Ok(())
+    }
+}
+
+fn parse_uuid(row: &Row<'_>, index: usize) -> SqliteResult<Uuid> {
+    let value: String = row.get(index)?;
+    Uuid::parse_str(&value).map_err(|err| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(err))
+    })
+}
+
+fn parse_timestamp(row: &Row<'_>, index: usize) -> SqliteResult<DateTime<Utc>> {
+    let value: String = row.get(index)?;
+    DateTime::parse_from_rfc3339(&value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|err| {
+            rusqlite::Error::FromSqlConversionFailure(
+                index,
+                rusqlite::types::Type::Text,
+                Box::new(err),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_insert_batch_and_read_back_per_session() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = TranscriptStore::new(db.connection());
+        let session_id = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        let message = |session_id, role: &str| TranscriptMessage {
+            session_id,
+            role: role.to_string(),
+            message_json: format!("{{\"role\":\"{role}\"}}"),
+            recorded_at: Utc::now(),
+        };
+        let event = TranscriptEvent {
+            session_id,
+            direction: "received".to_string(),
+            event_type: "TurnStarted".to_string(),
+            payload_json: "{}".to_string(),
+            recorded_at: Utc::now(),
+        };
+        store
+            .insert_batch(
+                &[
+                    message(session_id, "User"),
+                    message(other, "User"),
+                    message(session_id, "Assistant"),
+                ],
+                &[event.clone()],
+            )
+            .unwrap();
+
+        let messages = store.messages_for_session(session_id).unwrap();
+        let roles: Vec<&str> = messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["User", "Assistant"]);
+        assert_eq!(store.events_for_session(session_id).unwrap(), [event]);
+
+        store.delete_session(session_id).unwrap();
+        assert!(store.messages_for_session(session_id).unwrap().is_empty());
+        assert_eq!(store.messages_for_session(other).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,147 @@
+//! Usage statistics data access object
+
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, Result as SqliteResult};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+/// Storage format of `usage_counts.day`; sorts chronologically as text
+const DAY_FORMAT: &str = "%Y-%m-%d";
+
+/// Data access object for daily feature usage counts
+#[derive(Clone)]
+pub struct UsageStatsStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl UsageStatsStore {
+    /// Create a new UsageStatsStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Add `counts` to the totals for `day`
+    pub fn add_counts(&self, day: NaiveDate, counts: &HashMap<String, u64>) -> SqliteResult<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO usage_counts (day, feature, count) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(day, feature) DO UPDATE SET count = count + excluded.count",
+            )?;
+            let day = day.format(DAY_FORMAT).to_string();
+            for (feature, count) in counts {
+                let count = i64::try_from(*count).unwrap_or(i64::MAX);
+                stmt.execute(params![day, feature, count])?;
+            }
+        }
+        tx.commit()
+    }
+
+    /// Per-feature totals for days in `[start, end)`, most used first
+    pub fn totals_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> SqliteResult<Vec<(String, i64)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT feature, SUM(count) AS total FROM usage_counts
+             WHERE day >= ?1 AND day < ?2
+             GROUP BY feature ORDER BY total DESC, feature",
+        )?;
+        let totals = stmt
+            .query_map(
+                params![
+                    start.format(DAY_FORMAT).to_string(),
+                    end.format(DAY_FORMAT).to_string()
+                ],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )?
+            .collect::<SqliteResult<Vec<_>>>()?;
+        Ok(totals)
+    }
+
+    /// Counts per day for days in `[start, end)`; days without use are absent
+    pub fn daily_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> SqliteResult<BTreeMap<NaiveDate, BTreeMap<String, i64>>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT day, feature, count FROM usage_counts WHERE day >= ?1 AND day < ?2")?;
+        let mut rows = stmt.query(params![
+            start.format(DAY_FORMAT).to_string(),
+            end.format(DAY_FORMAT).to_string()
+        ])?;
+
+        let mut days: BTreeMap<NaiveDate, BTreeMap<String, i64>> = BTreeMap::new();
+        while let Some(row) = rows.next()? {
+            let day: String = row.get(0)?;
+            let Ok(day) = NaiveDate::parse_from_str(&day, DAY_FORMAT) else {
+                tracing::warn!(day = %day, "Skipping usage counts with an invalid day");
+                continue;
+            };
+            days.entry(day)
+                .or_default()
+                .insert(row.get(1)?, row.get(2)?);
+        }
+        Ok(days)
+    }
+
+    /// Delete all recorded counts
+    pub fn clear_all(&self) -> SqliteResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM usage_counts", [])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Database;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_counts_accumulate_per_day() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let store = UsageStatsStore::new(db.connection());
+
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+        let tuesday = NaiveDate::from_ymd_opt(2026, 10, 13).unwrap();
+        let counts = |pairs: &[(&str, u64)]| -> HashMap<String, u64> {
+            pairs.iter().map(|(f, c)| (f.to_string(), *c)).collect()
+        };
+        store
+            .add_counts(
+                monday,
+                &counts(&[("action.new_tab", 2), ("turn.claude", 5)]),
+            )
+            .unwrap();
+        store
+            .add_counts(monday, &counts(&[("turn.claude", 1)]))
+            .unwrap();
+        store
+            .add_counts(tuesday, &counts(&[("action.new_tab", 4)]))
+            .unwrap();
+
+        assert_eq!(
+            store.totals_between(monday, tuesday).unwrap(),
+            [
+                ("turn.claude".to_string(), 6),
+                ("action.new_tab".to_string(), 2)
+            ]
+        );
+        let days = store
+            .daily_between(monday, tuesday.succ_opt().unwrap())
+            .unwrap();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[&tuesday]["action.new_tab"], 4);
+
+        store.clear_all().unwrap();
+        assert!(store.daily_between(monday, tuesday).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,63 @@
+//! Workspace memory data access object
+//!
+//! Holds the shared context Conduit syncs into each workspace's agent memory
+//! files; the files themselves are written by `core::memory`.
+
+use super::models::WorkspaceMemory;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Data access object for workspace memory
+#[derive(Clone)]
+pub struct WorkspaceMemoryStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl WorkspaceMemoryStore {
+    /// Create a new WorkspaceMemoryStore
+    pub fn new(conn: Arc<Mutex<Connection>>) -> Self {
+        Self { conn }
+    }
+
+    /// Shared context of a workspace, if one was saved
+    pub fn get(&self, workspace_id: Uuid) -> SqliteResult<Option<WorkspaceMemory>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT content, updated_at FROM workspace_memory WHERE workspace_id = ?1",
+            params![workspace_id.to_string()],
+            |row| {
+                let updated_at_str: String = row.get(1)?;
+                Ok(WorkspaceMemory {
+                    workspace_id,
+                    content: row.get(0)?,
+                    updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                })
+            },
+        )
+        .optional()
+    }
+
+    /// Save the shared context of a workspace, replacing the previous one
+    pub fn save(&self, workspace_id: Uuid, content: &str) -> SqliteResult<WorkspaceMemory> {
+        let memory = WorkspaceMemory {
+            workspace_id,
+            content: content.to_string(),
+            updated_at: Utc::now(),
+        };
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO workspace_memory (workspace_id, content, updated_at)
+             VALUES (?1, ?2, ?3)",
+            params![
+                workspace_id.to_string(),
+                memory.content,
+                memory.updated_at.to_rfc3339()
+            ],
+        )?;
+        Ok(memory)
+    }
+}
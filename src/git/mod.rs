@@ -1,16 +1,23 @@
 //! Git operations module
 
+mod patch_series;
 mod pr;
+mod snapshot;
 mod status;
 mod workspace_mode;
 mod workspace_repo;
 mod worktree;
 
+pub use patch_series::{
+    apply_patch_series, branch_exists, export_patch_series, has_commit, origin_url, PatchSeries,
+    PatchSeriesError,
+};
 pub use pr::{
-    CheckState, CheckStatus, MergeReadiness, MergeableStatus, PrManager, PrPreflightResult,
-    PrState, PrStatus, ReviewDecision,
+    CheckState, CheckStatus, LinkedPrPreflight, MergeReadiness, MergeableStatus, PrManager,
+    PrPreflightResult, PrState, PrStatus, ReviewDecision,
 };
-pub use status::GitDiffStats;
+pub use snapshot::{capture_worktree, restore_worktree, RestoreSummary, SnapshotError};
+pub use status::{GitDiffStats, UpstreamStatus};
 pub use workspace_mode::WorkspaceMode;
 pub use workspace_repo::WorkspaceRepoManager;
-pub use worktree::{WorktreeInfo, WorktreeManager};
+pub use worktree::{WorktreeError, WorktreeInfo, WorktreeManager};
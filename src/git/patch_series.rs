@@ -0,0 +1,214 @@
+//! Patch series for session bundles: the commits a workspace branch added on
+//! top of its upstream base, as `git format-patch` mail, and replaying them
+//! with `git am` in another clone.
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use thiserror::Error;
+
+/// Upstream refs tried, in order, when looking for the branch's base
+const BASE_CANDIDATES: [&str; 4] = ["origin/main", "origin/master", "main", "master"];
+
+#[derive(Error, Debug)]
+pub enum PatchSeriesError {
+    #[error("Git command failed: {0}")]
+    CommandFailed(String),
+    #[error("No main or master branch to diff against")]
+    NoBase,
+    #[error("Patches didn't apply: {0}")]
+    ApplyFailed(String),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Commits on a branch since it left its upstream base
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchSeries {
+    /// Upstream ref the base was taken from, e.g. `origin/main`
+    pub base_ref: String,
+    /// Commit the series applies on top of
+    pub base_commit: String,
+    /// Number of commits in the series
+    pub commit_count: usize,
+    /// `git format-patch --stdout` output; empty when there are no commits
+    pub mbox: String,
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, PatchSeriesError> {
+    let output = Command::new("git").args(args).current_dir(dir).output()?;
+    if !output.status.success() {
+        return Err(PatchSeriesError::CommandFailed(format!(
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Collect the commits `HEAD` has on top of the repository's main branch
+pub fn export_patch_series(worktree: &Path) -> Result<PatchSeries, PatchSeriesError> {
+    let base_ref = BASE_CANDIDATES
+        .iter()
+        .find(|candidate| has_commit(worktree, candidate))
+        .ok_or(PatchSeriesError::NoBase)?;
+    let base_commit = git(worktree, &["merge-base", "HEAD", base_ref])?
+        .trim()
+        .to_string();
+    let range = format!("{base_commit}..HEAD");
+    let commit_count = git(worktree, &["rev-list", "--count", &range])?
+        .trim()
+        .parse()
+        .unwrap_or(0);
+    let mbox = if commit_count == 0 {
+        String::new()
+    } else {
+        git(worktree, &["format-patch", "--stdout", "--binary", &range])?
+    };
+    Ok(PatchSeries {
+        base_ref: base_ref.to_string(),
+        base_commit,
+        commit_count,
+        mbox,
+    })
+}
+
+/// Whether `rev` names a commit in the repository at `dir`
+pub fn has_commit(dir: &Path, rev: &str) -> bool {
+    git(
+        dir,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{rev}^{{commit}}"),
+        ],
+    )
+    .is_ok()
+}
+
+/// Whether a local branch called `branch` exists
+pub fn branch_exists(dir: &Path, branch: &str) -> bool {
+    git(
+        dir,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("refs/heads/{branch}"),
+        ],
+    )
+    .is_ok()
+}
+
+/// URL of the `origin` remote, if there is one
+pub fn origin_url(dir: &Path) -> Option<String> {
+    git(dir, &["remote", "get-url", "origin"])
+        .ok()
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
+/// Commit a patch series onto the branch checked out at `worktree`. A series
+/// that doesn't apply is rolled back so the branch is left as it was.
+pub fn apply_patch_series(worktree: &Path, mbox: &str) -> Result<(), PatchSeriesError> {
+    if mbox.trim().is_empty() {
+        return Ok(());
+    }
+    let mut child = Command::new("git")
+        .args(["am", "--3way", "--keep-cr"])
+        .current_dir(worktree)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(mbox.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if let Err(err) = git(worktree, &["am", "--abort"]) {
+        tracing::warn!(
+            worktree = %worktree.display(),
+            error = %err,
+            "Failed to abort git am after a patch didn't apply"
+        );
+    }
+    Err(PatchSeriesError::ApplyFailed(stderr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn run_git(path: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn init_repo(path: &Path) {
+        run_git(path, &["init", "-b", "main"]);
+        run_git(path, &["config", "user.email", "test@example.com"]);
+        run_git(path, &["config", "user.name", "Test"]);
+        fs::write(path.join("README.md"), "hello\n").unwrap();
+        run_git(path, &["add", "."]);
+        run_git(path, &["commit", "-m", "initial"]);
+    }
+
+    #[test]
+    fn test_export_and_apply_round_trip() {
+        let origin = tempdir().unwrap();
+        init_repo(origin.path());
+        run_git(origin.path(), &["checkout", "-b", "feature"]);
+        fs::write(origin.path().join("README.md"), "hello\nworld\n").unwrap();
+        fs::write(origin.path().join("notes.txt"), "todo\n").unwrap();
+        run_git(origin.path(), &["add", "."]);
+        run_git(origin.path(), &["commit", "-m", "Add notes"]);
+
+        let series = export_patch_series(origin.path()).unwrap();
+        assert_eq!(series.base_ref, "main");
+        assert_eq!(series.commit_count, 1);
+        assert!(series.mbox.contains("Subject: [PATCH] Add notes"));
+
+        let clone = tempdir().unwrap();
+        run_git(
+            clone.path(),
+            &[
+                "clone",
+                "--branch",
+                "main",
+                origin.path().to_str().unwrap(),
+                ".",
+            ],
+        );
+        run_git(clone.path(), &["config", "user.email", "test@example.com"]);
+        run_git(clone.path(), &["config", "user.name", "Test"]);
+        assert!(has_commit(clone.path(), &series.base_commit));
+        assert!(!branch_exists(clone.path(), "feature"));
+
+        apply_patch_series(clone.path(), &series.mbox).unwrap();
+        assert_eq!(
+            fs::read_to_string(clone.path().join("notes.txt")).unwrap(),
+            "todo\n"
+        );
+        let subject = git(clone.path(), &["log", "-1", "--format=%s"]).unwrap();
+        assert_eq!(subject.trim(), "Add notes");
+    }
+}
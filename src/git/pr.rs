@@ -3,7 +3,7 @@
 //! This module provides preflight checks and prompt generation for PR creation.
 //! The actual git/gh commands are executed by Claude Sonnet.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
@@ -11,6 +11,8 @@ use std::time::{Duration, Instant};
 use serde::Deserialize;
 use tracing::warn;
 
+use crate::util::network;
+
 #[derive(Debug, Clone, Copy)]
 pub struct GhStatus {
     pub installed: bool,
@@ -36,7 +38,15 @@ fn refresh_gh_status() -> GhStatus {
 
     let authenticated = if installed {
         match Command::new("gh").args(["auth", "status"]).output() {
-            Ok(output) => output.status.success(),
+            Ok(output) if output.status.success() => true,
+            Ok(output) => {
+                // Offline the token can't be checked; the calls that need it will say so
+                let offline = network::is_network_failure(&String::from_utf8_lossy(&output.stderr));
+                if offline {
+                    network::record_failure();
+                }
+                offline
+            }
             Err(error) => {
                 warn!(error = %error, "Failed to run gh auth status");
                 false
@@ -289,6 +299,27 @@ pub struct PrPreflightResult {
     pub existing_pr: Option<PrStatus>,
 }
 
+/// Preflight result for one of a session's linked repositories
+#[derive(Debug, Clone)]
+pub struct LinkedPrPreflight {
+    /// Repository name shown to the user and the agent
+    pub name: String,
+    pub working_dir: PathBuf,
+    pub preflight: PrPreflightResult,
+}
+
+impl LinkedPrPreflight {
+    /// Whether this repository still needs a PR of its own
+    pub fn needs_pr(&self) -> bool {
+        !self.preflight.on_main_branch
+            && !self
+                .preflight
+                .existing_pr
+                .as_ref()
+                .is_some_and(|pr| pr.exists)
+    }
+}
+
 /// JSON structure for a single status check from statusCheckRollup
 /// Can be either a CheckRun (uses status/conclusion) or a StatusContext (uses state)
 ///
@@ -486,6 +517,12 @@ impl PrManager {
             .ok()?;
 
         if !output.status.success() {
+            // Offline says nothing about the PR; leave the last known status
+            if network::is_network_failure(&String::from_utf8_lossy(&output.stderr)) {
+                network::record_failure();
+                return None;
+            }
+            network::record_success();
             // No PR exists for this branch
             return Some(PrStatus {
                 exists: false,
@@ -500,6 +537,7 @@ impl PrManager {
             });
         }
 
+        network::record_success();
         let json_str = String::from_utf8_lossy(&output.stdout);
         if let Ok(pr) = serde_json::from_str::<GhPrView>(&json_str) {
             let state = PrState::from_gh_json(&pr.state, pr.is_draft, pr.merged_at.as_deref());
@@ -634,6 +672,76 @@ Follow these exact steps to create a PR:
             base_branch,
         )
     }
+
+    /// Generate the prompt for a task spanning several repositories: one PR
+    /// per repository, each linking to the others
+    pub fn generate_linked_pr_prompt(
+        preflight: &PrPreflightResult,
+        working_dir: &Path,
+        linked: &[LinkedPrPreflight],
+    ) -> String {
+        let mut repos = vec![Self::linked_repo_line(
+            &format!("{} (current directory)", working_dir.display()),
+            preflight,
+        )];
+        for repo in linked {
+            let label = format!("{} ({})", repo.working_dir.display(), repo.name);
+            if repo.preflight.on_main_branch {
+                repos.push(format!(
+                    "- {label}: on {}, skip it",
+                    repo.preflight.branch_name
+                ));
+            } else {
+                repos.push(Self::linked_repo_line(&label, &repo.preflight));
+            }
+        }
+
+        format!(
+            r#"The user likes the state of the code.
+
+This task spans {} repositories. Each one with changes needs its own PR, and the PRs must link to each other.
+
+{}
+
+The user requested linked PRs.
+
+Follow these exact steps in every repository that needs a PR, running each command from that repository's directory:
+
+1. Run git diff to review uncommitted changes
+2. Commit them with a clear, descriptive commit message
+3. Push to origin
+4. Use git diff <target>... to review the PR diff
+5. Use gh pr create --base <target> to create a PR. Keep the title under 80 characters and the description under five sentences.
+
+Then use gh pr edit to add a "Related PRs" line to every PR's description, listing the URLs of the other PRs (including ones that already existed).
+If any of these steps fail, explain what went wrong."#,
+            linked.len() + 1,
+            repos.join("\n"),
+        )
+    }
+
+    fn linked_repo_line(label: &str, preflight: &PrPreflightResult) -> String {
+        if let Some(pr) = preflight.existing_pr.as_ref().filter(|pr| pr.exists) {
+            return format!(
+                "- {label}: PR #{} already open at {}",
+                pr.number.unwrap_or(0),
+                pr.url.as_deref().unwrap_or("unknown URL")
+            );
+        }
+        let base_branch = preflight
+            .target_branch
+            .strip_prefix("origin/")
+            .unwrap_or(&preflight.target_branch);
+        let upstream_note = if preflight.has_upstream {
+            ""
+        } else {
+            ", no upstream branch yet"
+        };
+        format!(
+            "- {label}: branch {}, target {}, {} uncommitted changes{}",
+            preflight.branch_name, base_branch, preflight.uncommitted_count, upstream_note
+        )
+    }
 }
 
 #[cfg(test)]
@@ -702,6 +810,45 @@ mod tests {
         assert!(prompt.contains("gh pr create --base main"));
     }
 
+    #[test]
+    fn test_generate_linked_pr_prompt() {
+        let preflight = |branch: &str| PrPreflightResult {
+            gh_installed: true,
+            gh_authenticated: true,
+            on_main_branch: PrManager::is_main_branch(branch),
+            branch_name: branch.to_string(),
+            target_branch: "origin/main".to_string(),
+            uncommitted_count: 2,
+            has_upstream: true,
+            existing_pr: None,
+        };
+        let linked = vec![
+            LinkedPrPreflight {
+                name: "client".to_string(),
+                working_dir: PathBuf::from("/work/client"),
+                preflight: preflight("feature/orders"),
+            },
+            LinkedPrPreflight {
+                name: "docs".to_string(),
+                working_dir: PathBuf::from("/work/docs"),
+                preflight: preflight("main"),
+            },
+        ];
+        assert!(linked[0].needs_pr());
+        assert!(!linked[1].needs_pr());
+
+        let prompt = PrManager::generate_linked_pr_prompt(
+            &preflight("feature/orders-api"),
+            Path::new("/work/api"),
+            &linked,
+        );
+        assert!(prompt.contains("spans 3 repositories"));
+        assert!(prompt.contains("/work/api (current directory): branch feature/orders-api"));
+        assert!(prompt.contains("/work/client (client): branch feature/orders, target main"));
+        assert!(prompt.contains("/work/docs (docs): on main, skip it"));
+        assert!(prompt.contains("Related PRs"));
+    }
+
     #[test]
     fn test_get_current_branch_detached() {
         let dir = tempdir().unwrap();
@@ -0,0 +1,202 @@
+//! Worktree snapshots taken before each prompt so a turn's file changes can
+//! be rolled back.
+//!
+//! Snapshots are tree objects written through a scratch index, so the real
+//! index, HEAD and the stash are never touched. Untracked files are included;
+//! ignored files are not. Restoring rewrites files only - commits the agent
+//! made in the meantime stay in history.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tempfile::TempDir;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("Git command failed: {0}")]
+    CommandFailed(String),
+    #[error("Not a git repository: {0}")]
+    NotAGitRepo(PathBuf),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// What a rollback changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestoreSummary {
+    /// Files rewritten or recreated from the snapshot
+    pub restored: usize,
+    /// Files created after the snapshot that were deleted
+    pub removed: usize,
+}
+
+/// Index file in a private temp dir; git refuses to read an empty file as an
+/// index, so the path must not exist until git (or a copy) creates it.
+struct ScratchIndex {
+    _dir: TempDir,
+    path: PathBuf,
+}
+
+impl ScratchIndex {
+    fn new() -> Result<Self, SnapshotError> {
+        let dir = tempfile::Builder::new()
+            .prefix("conduit-snapshot-")
+            .tempdir()?;
+        let path = dir.path().join("index");
+        Ok(Self { _dir: dir, path })
+    }
+}
+
+fn git(dir: &Path, index: Option<&Path>, args: &[&str]) -> Result<String, SnapshotError> {
+    let mut command = Command::new("git");
+    command.args(args).current_dir(dir);
+    if let Some(index) = index {
+        command.env("GIT_INDEX_FILE", index);
+    }
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(SnapshotError::CommandFailed(format!(
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn toplevel(dir: &Path) -> Option<PathBuf> {
+    git(dir, None, &["rev-parse", "--show-toplevel"])
+        .ok()
+        .map(|out| PathBuf::from(out.trim()))
+}
+
+/// Write the current worktree to a tree object and return its id.
+///
+/// Returns `Ok(None)` when `dir` isn't inside a git worktree.
+pub fn capture_worktree(dir: &Path) -> Result<Option<String>, SnapshotError> {
+    let Some(root) = toplevel(dir) else {
+        return Ok(None);
+    };
+    let scratch = ScratchIndex::new()?;
+    // Seed from the real index so unchanged files aren't rehashed
+    let real_index = git(&root, None, &["rev-parse", "--git-path", "index"])?;
+    let real_index = root.join(real_index.trim());
+    if real_index.exists() {
+        fs::copy(&real_index, &scratch.path)?;
+    }
+    git(&root, Some(&scratch.path), &["add", "-A"])?;
+    let tree = git(&root, Some(&scratch.path), &["write-tree"])?;
+    Ok(Some(tree.trim().to_string()))
+}
+
+/// Put the worktree back to `tree`: changed and deleted files are restored
+/// and files created since are removed.
+pub fn restore_worktree(dir: &Path, tree: &str) -> Result<RestoreSummary, SnapshotError> {
+    let root = toplevel(dir).ok_or_else(|| SnapshotError::NotAGitRepo(dir.to_path_buf()))?;
+    let current =
+        capture_worktree(&root)?.ok_or_else(|| SnapshotError::NotAGitRepo(root.clone()))?;
+
+    let changes = git(
+        &root,
+        None,
+        &[
+            "diff-tree",
+            "-r",
+            "-z",
+            "--no-renames",
+            "--name-status",
+            tree,
+            &current,
+        ],
+    )?;
+    let mut summary = RestoreSummary::default();
+    let mut fields = changes.split('\0').filter(|field| !field.is_empty());
+    while let (Some(status), Some(path)) = (fields.next(), fields.next()) {
+        if status == "A" {
+            match fs::remove_file(root.join(path)) {
+                Ok(()) => summary.removed += 1,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        } else {
+            summary.restored += 1;
+        }
+    }
+
+    let scratch = ScratchIndex::new()?;
+    git(&root, Some(&scratch.path), &["read-tree", tree])?;
+    git(&root, Some(&scratch.path), &["checkout-index", "-a", "-f"])?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn run_git(path: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .output()
+            .expect("failed to run git");
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[test]
+    fn test_restore_undoes_changes_since_snapshot() {
+        let dir = tempdir().unwrap();
+        let path = dir.path();
+        run_git(path, &["init"]);
+        run_git(path, &["config", "user.email", "test@test.com"]);
+        run_git(path, &["config", "user.name", "Test"]);
+        fs::write(path.join("README.md"), "# Test").unwrap();
+        fs::write(path.join("gone.txt"), "bye").unwrap();
+        run_git(path, &["add", "."]);
+        run_git(path, &["commit", "-m", "Initial commit"]);
+        // Uncommitted work from before the turn is part of the snapshot
+        fs::write(path.join("draft.txt"), "wip").unwrap();
+
+        let tree = capture_worktree(path).unwrap().expect("inside a repo");
+
+        fs::write(path.join("README.md"), "# Rewritten").unwrap();
+        fs::remove_file(path.join("gone.txt")).unwrap();
+        fs::write(path.join("new.rs"), "fn main() {}").unwrap();
+
+        let summary = restore_worktree(path, &tree).unwrap();
+        assert_eq!(
+            summary,
+            RestoreSummary {
+                restored: 2,
+                removed: 1
+            }
+        );
+        assert_eq!(
+            fs::read_to_string(path.join("README.md")).unwrap(),
+            "# Test"
+        );
+        assert_eq!(fs::read_to_string(path.join("gone.txt")).unwrap(), "bye");
+        assert_eq!(fs::read_to_string(path.join("draft.txt")).unwrap(), "wip");
+        assert!(!path.join("new.rs").exists());
+        // The real index is untouched: the draft is still untracked
+        let status = Command::new("git")
+            .args(["status", "--porcelain"])
+            .current_dir(path)
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&status.stdout), "?? draft.txt\n");
+    }
+
+    #[test]
+    fn test_capture_outside_repo_is_none() {
+        let dir = tempdir().unwrap();
+        assert_eq!(capture_worktree(dir.path()).unwrap(), None);
+    }
+}
@@ -89,6 +89,52 @@ impl GitDiffStats {
     }
 }
 
+/// How far the current branch has drifted from its upstream
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UpstreamStatus {
+    /// Local commits not yet pushed
+    pub ahead: usize,
+    /// Upstream commits not yet pulled
+    pub behind: usize,
+}
+
+impl UpstreamStatus {
+    /// Ahead/behind counts against the branch's upstream, or `None` when the
+    /// branch has no upstream (or is detached)
+    pub fn from_working_dir(working_dir: &Path) -> Option<Self> {
+        let output = Command::new("git")
+            .args([
+                "--no-optional-locks",
+                "rev-list",
+                "--left-right",
+                "--count",
+                "HEAD...@{upstream}",
+            ])
+            .current_dir(working_dir)
+            .output();
+
+        match output {
+            Ok(o) if o.status.success() => {
+                Self::parse_left_right_count(&String::from_utf8_lossy(&o.stdout))
+            }
+            Ok(_) => None,
+            Err(e) => {
+                tracing::debug!(error = %e, "Failed to run git rev-list for upstream status");
+                None
+            }
+        }
+    }
+
+    /// Parse `git rev-list --left-right --count HEAD...@{upstream}` output
+    /// Format: "<ahead>\t<behind>"
+    fn parse_left_right_count(output: &str) -> Option<Self> {
+        let mut counts = output.split_whitespace().map(|n| n.parse().ok());
+        let ahead = counts.next()??;
+        let behind = counts.next()??;
+        Some(Self { ahead, behind })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,4 +194,21 @@ mod tests {
         };
         assert!(with_deletions.has_changes());
     }
+
+    #[test]
+    fn test_parse_left_right_count() {
+        assert_eq!(
+            UpstreamStatus::parse_left_right_count("2\t1\n"),
+            Some(UpstreamStatus {
+                ahead: 2,
+                behind: 1
+            })
+        );
+        assert_eq!(
+            UpstreamStatus::parse_left_right_count("0\t0"),
+            Some(UpstreamStatus::default())
+        );
+        assert_eq!(UpstreamStatus::parse_left_right_count(""), None);
+        assert_eq!(UpstreamStatus::parse_left_right_count("3"), None);
+    }
 }
@@ -1,9 +1,12 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use conduit::{
     config::save_tool_path,
     ui::terminal_guard,
-    util::{self, Tool, ToolAvailability},
+    util::{
+        self, chaos, startup_profile, InstanceError, InstanceInfo, InstanceKind, InstanceLock,
+        Tool, ToolAvailability,
+    },
     App, Config,
 };
 use std::fs::{self, OpenOptions};
@@ -13,10 +16,40 @@ use std::path::{Path, PathBuf};
 #[command(name = "conduit")]
 #[command(about = "Multi-agent TUI for Claude Code, Codex CLI, Gemini CLI, and OpenCode")]
 struct Cli {
-    /// Custom data directory (default: ~/.conduit)
+    /// Data directory; also holds config, state and cache unless they are
+    /// set separately (env: CONDUIT_DATA_DIR)
     #[arg(long, value_name = "PATH")]
     data_dir: Option<PathBuf>,
 
+    /// Configuration directory (env: CONDUIT_CONFIG_DIR)
+    #[arg(long, value_name = "PATH")]
+    config_dir: Option<PathBuf>,
+
+    /// State directory for logs and debug dumps (env: CONDUIT_STATE_DIR)
+    #[arg(long, value_name = "PATH")]
+    state_dir: Option<PathBuf>,
+
+    /// Cache directory (env: CONDUIT_CACHE_DIR)
+    #[arg(long, value_name = "PATH")]
+    cache_dir: Option<PathBuf>,
+
+    /// Print how long each startup phase took after the TUI exits
+    #[arg(long)]
+    profile_startup: bool,
+
+    /// Inject faults for resilience testing, e.g. `agent_error=0.02,db=0.01`
+    #[arg(long, value_name = "SPEC", hide = true, value_parser = chaos::ChaosConfig::parse)]
+    chaos: Option<chaos::ChaosConfig>,
+
+    /// Seed for `--chaos` decisions; the seed used is logged when omitted
+    #[arg(long, value_name = "SEED", hide = true, requires = "chaos")]
+    chaos_seed: Option<u64>,
+
+    /// If another Conduit already owns the data directory, watch its
+    /// sessions instead of exiting (no agents are started)
+    #[arg(long)]
+    observe: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -26,13 +59,16 @@ enum Commands {
     /// Debug keyboard input - shows raw key events as you press them
     DebugKeys,
 
+    /// Print the directories and files Conduit uses
+    Paths,
+
     /// Migrate a VSCode theme to Conduit TOML format
     MigrateTheme {
         /// Path to VSCode theme JSON file
         #[arg(value_name = "INPUT")]
         input: PathBuf,
 
-        /// Output path (default: ~/.conduit/themes/<name>.toml)
+        /// Output path (default: <config>/themes/<name>.toml)
         #[arg(short, long, value_name = "OUTPUT")]
         output: Option<PathBuf>,
 
@@ -50,20 +86,166 @@ enum Commands {
         /// Port to listen on
         #[arg(short, long, default_value_t = 3000)]
         port: u16,
+
+        /// Disable gzip/brotli response compression
+        #[arg(long)]
+        no_compression: bool,
+    },
+
+    /// Show locally recorded usage statistics (see [usage_stats] in config.toml)
+    Stats {
+        /// Number of days to summarize, including today
+        #[arg(long, default_value_t = 30)]
+        days: u32,
+
+        /// Delete all recorded usage statistics
+        #[arg(long)]
+        clear: bool,
+    },
+
+    /// Report token spend per cost center
+    Billing {
+        #[command(subcommand)]
+        command: BillingCommand,
+    },
+
+    /// Import conversations started outside Conduit as resumable tabs
+    Import {
+        /// Agent whose conversations to scan
+        #[arg(long, value_enum)]
+        agent: ImportAgent,
+
+        /// Only conversations from this project directory (or below it)
+        #[arg(long, value_name = "PATH")]
+        project: Option<PathBuf>,
+
+        /// Most conversations to list, newest first
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+
+        /// Import every listed conversation without asking
+        #[arg(long)]
+        all: bool,
+
+        /// Conversation IDs (or unique prefixes) to import without asking
+        #[arg(value_name = "ID")]
+        ids: Vec<String>,
+    },
+
+    /// Check or repair the session database
+    Db {
+        #[command(subcommand)]
+        command: DbCommand,
+    },
+
+    /// Share a session and its branch with a teammate as a bundle file
+    Bundle {
+        #[command(subcommand)]
+        command: BundleCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum BundleCommand {
+    /// Write a session's transcript and branch commits to a bundle file
+    Export {
+        /// Session tab ID (or unique prefix) or workspace name
+        #[arg(value_name = "SESSION")]
+        session: String,
+
+        /// Output path (default: <data>/exports/<workspace>.conduit-bundle)
+        #[arg(short, long, value_name = "OUTPUT")]
+        output: Option<PathBuf>,
+    },
+
+    /// Recreate a bundle's branch as a new workspace and open its session
+    Import {
+        /// Bundle file to import
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Local clone to import into (default: the repository matching the
+        /// bundle's origin URL)
+        #[arg(long, value_name = "PATH")]
+        repo: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DbCommand {
+    /// Run a full integrity check without changing anything
+    Check,
+
+    /// Back up the database and rebuild it, keeping every row still readable
+    Recover,
+}
+
+#[derive(Subcommand)]
+enum BillingCommand {
+    /// Export a month's spend aggregated per cost-center tag
+    Export {
+        /// Calendar month in UTC, e.g. 2026-09
+        #[arg(long, value_name = "YYYY-MM")]
+        month: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = BillingFormat::Csv)]
+        format: BillingFormat,
+
+        /// Write to a file instead of stdout
+        #[arg(short, long, value_name = "OUTPUT")]
+        output: Option<PathBuf>,
     },
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum ImportAgent {
+    Claude,
+    Codex,
+    Opencode,
+}
+
+impl ImportAgent {
+    fn display_name(self) -> &'static str {
+        match self {
+            ImportAgent::Claude => "Claude Code",
+            ImportAgent::Codex => "Codex CLI",
+            ImportAgent::Opencode => "OpenCode",
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum BillingFormat {
+    Csv,
+    Json,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    if cli.profile_startup {
+        startup_profile::enable();
+    }
+    if let Some(config) = cli.chaos {
+        chaos::enable(config, cli.chaos_seed);
+    }
 
     // Initialize data directory FIRST before any other setup
-    util::init_data_dir(cli.data_dir);
+    util::init_dirs(util::DirOverrides {
+        config: cli.config_dir,
+        data: cli.data_dir,
+        state: cli.state_dir,
+        cache: cli.cache_dir,
+    });
 
     match cli.command {
         Some(Commands::DebugKeys) => {
             run_debug_keys()?;
         }
+        Some(Commands::Paths) => {
+            run_paths();
+        }
         Some(Commands::MigrateTheme {
             input,
             output,
@@ -71,11 +253,47 @@ async fn main() -> Result<()> {
         }) => {
             run_migrate_theme(&input, output.as_deref(), palette)?;
         }
-        Some(Commands::Serve { host, port }) => {
-            run_web_server(host, port).await?;
+        Some(Commands::Serve {
+            host,
+            port,
+            no_compression,
+        }) => {
+            run_web_server(host, port, !no_compression).await?;
+        }
+        Some(Commands::Stats { days, clear }) => {
+            run_stats(days, clear)?;
         }
+        Some(Commands::Billing {
+            command:
+                BillingCommand::Export {
+                    month,
+                    format,
+                    output,
+                },
+        }) => {
+            run_billing_export(&month, format, output.as_deref())?;
+        }
+        Some(Commands::Import {
+            agent,
+            project,
+            limit,
+            all,
+            ids,
+        }) => {
+            run_import(agent, project.as_deref(), limit, all, &ids)?;
+        }
+        Some(Commands::Db { command }) => match command {
+            DbCommand::Check => run_db_check()?,
+            DbCommand::Recover => run_db_recover()?,
+        },
+        Some(Commands::Bundle { command }) => match command {
+            BundleCommand::Export { session, output } => {
+                run_bundle_export(&session, output.as_deref())?
+            }
+            BundleCommand::Import { file, repo } => run_bundle_import(&file, repo.as_deref())?,
+        },
         None => {
-            run_app().await?;
+            run_app(cli.observe).await?;
         }
     }
 
@@ -83,11 +301,11 @@ async fn main() -> Result<()> {
 }
 
 /// Run the main application
-async fn run_app() -> Result<()> {
+async fn run_app(observe: bool) -> Result<()> {
     // Install panic hook to restore terminal state before printing panic message
     terminal_guard::install_panic_hook();
 
-    // Initialize logging to file (~/.conduit/logs/conduit.log)
+    // Initialize logging to file (<state>/logs/conduit.log)
     fs::create_dir_all(util::logs_dir())?;
 
     let log_file = OpenOptions::new()
@@ -104,15 +322,35 @@ async fn run_app() -> Result<()> {
         .with_writer(log_file)
         .with_ansi(false) // Disable ANSI colors in log file
         .init();
+    chaos::log_settings();
 
-    // Create config (loads from ~/.conduit/config.toml if present)
-    let config = Config::load();
+    // One process owns the data directory; the lock is held until exit
+    let (_instance_lock, observing) = match claim_data_dir(InstanceKind::Tui, observe)? {
+        DataDirClaim::Owner(lock) => (Some(lock), None),
+        DataDirClaim::Observer(owner) => (None, Some(owner)),
+    };
+
+    // A damaged database gets a recovery prompt before the TUI starts;
+    // an observer leaves that to the owner
+    if observing.is_none() && !startup_profile::phase("database check", check_database)? {
+        return Ok(());
+    }
+
+    // Create config (loads from <config>/config.toml if present)
+    let config = startup_profile::phase("config", Config::load);
 
     // Initialize theme from config
-    conduit::ui::components::init_theme(config.theme_name.as_deref(), config.theme_path.as_deref());
+    startup_profile::phase("theme", || {
+        conduit::ui::components::init_theme(
+            config.theme_name.as_deref(),
+            config.theme_path.as_deref(),
+        )
+    });
 
     // Detect tool availability
-    let mut tools = ToolAvailability::detect(&config.tool_paths);
+    let mut tools = startup_profile::phase("tool detection", || {
+        ToolAvailability::detect(&config.tool_paths)
+    });
 
     // Check MANDATORY requirement: git
     // Conduit exists for git worktree management, cannot function without git
@@ -131,8 +369,8 @@ async fn run_app() -> Result<()> {
         }
     }
 
-    // Check critical requirement: at least one agent
-    if !tools.has_any_agent() {
+    // Check critical requirement: at least one agent (a configured local model counts)
+    if !tools.has_any_agent() && config.local_model.is_none() {
         // Prefer Claude, but accept any available agent
         let preferred_agent = Tool::Claude;
         match run_blocking_tool_dialog(preferred_agent, &tools)? {
@@ -165,7 +403,394 @@ async fn run_app() -> Result<()> {
 
     // Create and run app with tool availability
     let mut app = App::new(config, tools);
-    app.run().await
+    if let Some(owner) = observing {
+        app.observe(owner);
+    }
+    let result = app.run().await;
+    if let Some(report) = startup_profile::report() {
+        eprint!("{report}");
+    }
+    result
+}
+
+/// How this process relates to the data directory
+enum DataDirClaim {
+    /// This process owns it until the lock is dropped
+    Owner(InstanceLock),
+    /// Another process owns it and this one only watches
+    Observer(InstanceInfo),
+}
+
+/// Take ownership of the data directory, or explain who already has it
+fn claim_data_dir(kind: InstanceKind, observe: bool) -> Result<DataDirClaim> {
+    match util::acquire_instance_lock(kind) {
+        Ok(lock) => Ok(DataDirClaim::Owner(lock)),
+        Err(InstanceError::Held(owner)) if observe && kind == InstanceKind::Tui => {
+            Ok(DataDirClaim::Observer(owner))
+        }
+        Err(err @ InstanceError::Held(_)) => {
+            let hint = match kind {
+                InstanceKind::Tui => {
+                    "Quit it first, or run `conduit --observe` to watch its sessions without starting agents."
+                }
+                InstanceKind::Serve => "Quit it first, or start this one with another --data-dir.",
+            };
+            anyhow::bail!("{err}\n{hint}")
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Open the database once before the TUI starts. When it's damaged or from a
+/// newer Conduit, ask whether to recover it, continue without saving, or quit.
+/// Returns whether to start the TUI.
+fn check_database() -> Result<bool> {
+    use conduit::data::Database;
+    use std::io::{BufRead, IsTerminal, Write};
+
+    let err = match Database::open_default() {
+        Ok(_) => return Ok(true),
+        // Anything else is reported in the TUI, which runs without persistence
+        Err(err) if !err.needs_recovery() => return Ok(true),
+        Err(err) => err,
+    };
+    let path = util::database_path();
+    eprintln!("Conduit can't use its database at {}:", path.display());
+    eprintln!("  {}", err);
+    if !std::io::stdin().is_terminal() {
+        anyhow::bail!("Run `conduit db recover` to back up and rebuild the database");
+    }
+
+    eprintln!();
+    eprintln!("  [r] Back it up and rebuild it, keeping everything still readable");
+    eprintln!("  [c] Continue without saving tabs, history or costs");
+    eprintln!("  [q] Quit");
+    loop {
+        eprint!("Choice [r/c/q]: ");
+        std::io::stderr().flush()?;
+        let mut answer = String::new();
+        if std::io::stdin().lock().read_line(&mut answer)? == 0 {
+            return Ok(false);
+        }
+        match answer.trim().to_ascii_lowercase().as_str() {
+            "r" => {
+                recover_database(&path)?;
+                return Ok(true);
+            }
+            "c" => return Ok(true),
+            "q" => return Ok(false),
+            _ => {}
+        }
+    }
+}
+
+/// List conversations from the agent's own history and register the chosen
+/// ones as open tabs for the next TUI start
+fn run_import(
+    agent: ImportAgent,
+    project: Option<&Path>,
+    limit: usize,
+    all: bool,
+    ids: &[String],
+) -> Result<()> {
+    use conduit::data::{Database, SessionTabStore, WorkspaceStore};
+    use conduit::session::{
+        discover_claude_sessions, discover_codex_sessions, discover_opencode_sessions,
+        parse_selection, register_sessions, unregistered_sessions, ExternalSession,
+    };
+    use std::io::{BufRead, IsTerminal, Write};
+
+    let db =
+        Database::open_default().map_err(|e| anyhow::anyhow!("Failed to open database: {}", e))?;
+    let tabs = SessionTabStore::new(db.connection());
+    let workspaces = WorkspaceStore::new(db.connection());
+
+    let discovered = match agent {
+        ImportAgent::Claude => discover_claude_sessions(),
+        ImportAgent::Codex => discover_codex_sessions(),
+        ImportAgent::Opencode => discover_opencode_sessions(),
+    };
+    let mut sessions = unregistered_sessions(discovered, &tabs.agent_session_ids()?);
+    if let Some(project) = project {
+        let project = fs::canonicalize(project).unwrap_or_else(|_| project.to_path_buf());
+        sessions.retain(|session| {
+            session
+                .project
+                .as_deref()
+                .is_some_and(|dir| Path::new(dir).starts_with(&project))
+        });
+    }
+    if sessions.is_empty() {
+        println!("No {} conversations left to import.", agent.display_name());
+        return Ok(());
+    }
+
+    let selected: Vec<ExternalSession> = if !ids.is_empty() {
+        let mut selected = Vec::new();
+        for id in ids {
+            let matches: Vec<&ExternalSession> = sessions
+                .iter()
+                .filter(|session| session.id.starts_with(id.as_str()) || session.resume_id() == *id)
+                .collect();
+            match matches.as_slice() {
+                [session] => selected.push((*session).clone()),
+                [] => anyhow::bail!("No unimported conversation matches {}", id),
+                _ => anyhow::bail!(
+                    "{} matches {} conversations; use more of the ID",
+                    id,
+                    matches.len()
+                ),
+            }
+        }
+        selected
+    } else {
+        sessions.truncate(limit);
+        if all {
+            sessions
+        } else {
+            println!(
+                "{} conversations not yet in Conduit (newest first):",
+                agent.display_name()
+            );
+            println!();
+            print_import_list(&sessions);
+            println!();
+            if !std::io::stdin().is_terminal() {
+                println!("Pass conversation IDs or --all to import them.");
+                return Ok(());
+            }
+            let indices = loop {
+                print!("Import which? (e.g. 1 3-5, all; empty to cancel): ");
+                std::io::stdout().flush()?;
+                let mut answer = String::new();
+                std::io::stdin().lock().read_line(&mut answer)?;
+                match parse_selection(&answer, sessions.len()) {
+                    Ok(indices) => break indices,
+                    Err(err) => println!("{}", err),
+                }
+            };
+            indices
+                .into_iter()
+                .map(|index| sessions[index].clone())
+                .collect()
+        }
+    };
+    if selected.is_empty() {
+        println!("Nothing imported.");
+        return Ok(());
+    }
+
+    let registered = register_sessions(&selected, &tabs, &workspaces)?;
+    println!(
+        "Imported {} conversation(s). They open as tabs the next time you start Conduit.",
+        registered.len()
+    );
+    Ok(())
+}
+
+fn print_import_list(sessions: &[conduit::session::ExternalSession]) {
+    let project_width = sessions
+        .iter()
+        .filter_map(|session| session.project_name())
+        .map(|name| name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .clamp("PROJECT".len(), 24);
+    println!(
+        "  {:>3}  {:<8}  {:<14}  {:<project_width$}  {:>5}  PREVIEW",
+        "#", "ID", "WHEN", "PROJECT", "MSGS"
+    );
+    for (index, session) in sessions.iter().enumerate() {
+        let short_id: String = session.id.chars().take(8).collect();
+        let project: String = session
+            .project_name()
+            .unwrap_or_else(|| "-".to_string())
+            .chars()
+            .take(project_width)
+            .collect();
+        println!(
+            "  {:>3}  {:<8}  {:<14}  {:<project_width$}  {:>5}  {}",
+            index + 1,
+            short_id,
+            session.relative_time(),
+            project,
+            session.message_count,
+            session.truncated_display(60)
+        );
+    }
+}
+
+/// Export a session, found by tab ID prefix or workspace name, as a bundle
+fn run_bundle_export(session: &str, output: Option<&Path>) -> Result<()> {
+    use conduit::data::{Database, RepositoryStore, SessionTabStore, WorkspaceStore};
+    use conduit::session::export_bundle;
+
+    let db =
+        Database::open_default().map_err(|e| anyhow::anyhow!("Failed to open database: {}", e))?;
+    let tabs = SessionTabStore::new(db.connection());
+    let workspaces = WorkspaceStore::new(db.connection());
+    let repos = RepositoryStore::new(db.connection());
+
+    let mut matches = Vec::new();
+    for tab in tabs.get_all()? {
+        let Some(workspace_id) = tab.workspace_id else {
+            continue;
+        };
+        let Some(workspace) = workspaces.get_by_id(workspace_id)? else {
+            continue;
+        };
+        if tab.id.to_string().starts_with(session) || workspace.name == session {
+            matches.push((tab, workspace));
+        }
+    }
+    let (tab, workspace) = match matches.len() {
+        1 => matches.remove(0),
+        0 => anyhow::bail!("No session in a workspace matches {}", session),
+        count => anyhow::bail!("{} matches {} sessions; use more of the ID", session, count),
+    };
+    let repo = repos
+        .get_by_id(workspace.repository_id)?
+        .ok_or_else(|| anyhow::anyhow!("Repository for workspace {} not found", workspace.name))?;
+
+    let exported = export_bundle(&tab, &workspace, &repo)?;
+    for warning in &exported.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    let path = match output {
+        Some(path) => path.to_path_buf(),
+        None => util::exports_dir().join(format!("{}.conduit-bundle", workspace.name)),
+    };
+    exported.bundle.write(&path)?;
+    println!(
+        "Wrote {} message(s) and {} commit(s) on {} to {}",
+        exported.bundle.messages.len(),
+        exported.bundle.commit_count,
+        exported.bundle.branch,
+        path.display()
+    );
+    Ok(())
+}
+
+/// Import a bundle into a new workspace of the matching local repository
+fn run_bundle_import(file: &Path, repo_path: Option<&Path>) -> Result<()> {
+    use conduit::core::resolve_repo_workspace_settings;
+    use conduit::data::{
+        Database, ForkSeedStore, RepositoryStore, SessionTabStore, WorkspaceStore,
+    };
+    use conduit::session::{find_repository, import_bundle, BundleStores, SessionBundle};
+
+    let bundle = SessionBundle::read(file)?;
+    let db =
+        Database::open_default().map_err(|e| anyhow::anyhow!("Failed to open database: {}", e))?;
+    let repos = RepositoryStore::new(db.connection());
+
+    let repo = match repo_path {
+        Some(path) => {
+            let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+            repos.get_by_path(&path)?.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{} isn't a Conduit project; add it in Conduit first",
+                    path.display()
+                )
+            })?
+        }
+        None => find_repository(&bundle, &repos.get_all()?).ok_or_else(|| {
+            anyhow::anyhow!(
+                "No Conduit project matches {}; pass --repo with your local clone",
+                bundle
+                    .repository
+                    .remote_url
+                    .as_deref()
+                    .unwrap_or(&bundle.repository.name)
+            )
+        })?,
+    };
+    let base_path = repo
+        .base_path
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("Project {} has no local clone", repo.name))?;
+    let mode = resolve_repo_workspace_settings(&Config::load(), &repo).mode;
+
+    let workspaces = WorkspaceStore::new(db.connection());
+    let tabs = SessionTabStore::new(db.connection());
+    let fork_seeds = ForkSeedStore::new(db.connection());
+    let stores = BundleStores {
+        workspaces: &workspaces,
+        tabs: &tabs,
+        fork_seeds: &fork_seeds,
+    };
+    let imported = import_bundle(&bundle, &repo, &base_path, mode, &stores)?;
+    println!(
+        "Created workspace {} on branch {} with {} commit(s) at {}",
+        imported.workspace.name,
+        imported.workspace.branch,
+        bundle.commit_count,
+        imported.workspace.path.display()
+    );
+    println!("The session opens as a tab the next time you start Conduit.");
+    Ok(())
+}
+
+/// Report the database's integrity check
+fn run_db_check() -> Result<()> {
+    use conduit::data::Database;
+
+    let path = util::database_path();
+    if !path.exists() {
+        println!("No database at {} yet.", path.display());
+        return Ok(());
+    }
+    let problems =
+        Database::check(&path).map_err(|e| anyhow::anyhow!("Failed to check database: {}", e))?;
+    if problems.is_empty() {
+        println!("{} is healthy.", path.display());
+        return Ok(());
+    }
+    println!("{} has problems:", path.display());
+    for problem in &problems {
+        println!("  {}", problem);
+    }
+    anyhow::bail!("Run `conduit db recover` to back up and rebuild the database")
+}
+
+/// Back up and rebuild the database
+fn run_db_recover() -> Result<()> {
+    if let Some(owner) = util::instance_owner() {
+        anyhow::bail!(
+            "Conduit is running: {}\nQuit it before recovering the database.",
+            owner.describe()
+        );
+    }
+    let path = util::database_path();
+    if !path.exists() {
+        println!("No database at {}; nothing to recover.", path.display());
+        return Ok(());
+    }
+    recover_database(&path)
+}
+
+fn recover_database(path: &Path) -> Result<()> {
+    let (_db, report) = conduit::data::recover_database(path)
+        .map_err(|e| anyhow::anyhow!("Failed to recover database: {}", e))?;
+    eprintln!("Moved the old database to {}", report.backup_path.display());
+    eprintln!(
+        "Rebuilt {} with {} salvaged row(s).",
+        path.display(),
+        report.salvaged_rows()
+    );
+    let damaged: Vec<&str> = report
+        .tables
+        .iter()
+        .filter(|table| table.incomplete)
+        .map(|table| table.name.as_str())
+        .collect();
+    if !damaged.is_empty() {
+        eprintln!(
+            "Some rows couldn't be read from: {}. They remain in the backup.",
+            damaged.join(", ")
+        );
+    }
+    Ok(())
 }
 
 /// Run a blocking dialog to get a tool path from the user
@@ -270,7 +895,121 @@ fn run_blocking_tool_dialog(tool: Tool, _tools: &ToolAvailability) -> Result<Opt
     Ok(result)
 }
 
+/// Print the directory layout and the main files inside it
+fn run_paths() {
+    let layout = util::dir_layout();
+    println!("config   {}", layout.config.display());
+    println!("data     {}", layout.data.display());
+    println!("state    {}", layout.state.display());
+    println!("cache    {}", layout.cache.display());
+    println!();
+    println!("config file  {}", util::config_path().display());
+    println!("themes       {}", util::themes_dir().display());
+    println!("database     {}", util::database_path().display());
+    println!("workspaces   {}", util::workspaces_dir().display());
+    println!("attachments  {}", util::attachments_dir().display());
+    println!("exports      {}", util::exports_dir().display());
+    println!("log file     {}", util::log_file_path().display());
+    println!("debug dumps  {}", util::debug_dir().display());
+    if layout.legacy {
+        println!();
+        println!("Using the single ~/.conduit folder from an earlier install.");
+    }
+}
+
 /// Run the theme migration command
+/// Print feature usage counts, or delete them with `--clear`
+fn run_stats(days: u32, clear: bool) -> Result<()> {
+    use conduit::core::usage_stats::UPLOADED_THROUGH_KEY;
+    use conduit::data::{AppStateStore, Database, UsageStatsStore};
+
+    let config = Config::load();
+    let db =
+        Database::open_default().map_err(|e| anyhow::anyhow!("Failed to open database: {}", e))?;
+    let store = UsageStatsStore::new(db.connection());
+
+    if clear {
+        store.clear_all()?;
+        println!("Deleted all recorded usage statistics.");
+        return Ok(());
+    }
+
+    let settings = &config.usage_stats;
+    match (settings.enabled, settings.endpoint.as_deref()) {
+        (false, _) => println!(
+            "Usage statistics are off. Set `enabled = true` under [usage_stats] in config.toml to count feature use locally."
+        ),
+        (true, None) => println!("Usage statistics are on and kept on this machine."),
+        (true, Some(endpoint)) => {
+            let uploaded_through = AppStateStore::new(db.connection())
+                .get(UPLOADED_THROUGH_KEY)?
+                .unwrap_or_else(|| "never".to_string());
+            println!(
+                "Usage statistics are on; daily totals are sent to {} (sent through: {}).",
+                endpoint, uploaded_through
+            );
+        }
+    }
+
+    let today = chrono::Utc::now().date_naive();
+    let tomorrow = today
+        .succ_opt()
+        .ok_or_else(|| anyhow::anyhow!("Date out of range"))?;
+    let start = today - chrono::Days::new(u64::from(days.saturating_sub(1)));
+    let totals = store.totals_between(start, tomorrow)?;
+    println!();
+    if totals.is_empty() {
+        println!("No usage recorded from {} to {}.", start, today);
+        return Ok(());
+    }
+    println!("Usage from {} to {}:", start, today);
+    println!();
+    let width = totals
+        .iter()
+        .map(|(feature, _)| feature.len())
+        .max()
+        .unwrap_or(0)
+        .max("FEATURE".len());
+    println!("  {:<width$}  {:>8}", "FEATURE", "COUNT", width = width);
+    for (feature, count) in totals {
+        println!("  {:<width$}  {:>8}", feature, count, width = width);
+    }
+    Ok(())
+}
+
+/// Export a month of the cost ledger as CSV or JSON
+fn run_billing_export(month: &str, format: BillingFormat, output: Option<&Path>) -> Result<()> {
+    use conduit::core::billing::{BillingMonth, BillingReport};
+    use conduit::data::{CostLedgerStore, Database};
+
+    let month: BillingMonth = month.parse()?;
+    let db =
+        Database::open_default().map_err(|e| anyhow::anyhow!("Failed to open database: {}", e))?;
+    let entries = CostLedgerStore::new(db.connection())
+        .entries_between(month.start(), month.end())
+        .map_err(|e| anyhow::anyhow!("Failed to read cost ledger: {}", e))?;
+
+    let report = BillingReport::from_entries(month, &entries);
+    let rendered = match format {
+        BillingFormat::Csv => report.to_csv(),
+        BillingFormat::Json => report.to_json()? + "\n",
+    };
+
+    match output {
+        Some(path) => {
+            fs::write(path, rendered)?;
+            eprintln!(
+                "Wrote {} cost center(s) for {} to {}",
+                report.cost_centers.len(),
+                report.month,
+                path.display()
+            );
+        }
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
+
 fn run_migrate_theme(input: &Path, output: Option<&Path>, extract_palette: bool) -> Result<()> {
     use conduit::ui::components::theme::migrate::{
         migrate_vscode_theme, write_theme_file, MigrateOptions,
@@ -297,8 +1036,8 @@ fn run_migrate_theme(input: &Path, output: Option<&Path>, extract_palette: bool)
     let output_path = if let Some(path) = output {
         path.to_path_buf()
     } else {
-        // Default to ~/.conduit/themes/<sanitized-name>.toml
-        let themes_dir = util::data_dir().join("themes");
+        // Default to <config>/themes/<sanitized-name>.toml
+        let themes_dir = util::themes_dir();
         let sanitized_name: String = result
             .name
             .chars()
@@ -323,7 +1062,10 @@ fn run_migrate_theme(input: &Path, output: Option<&Path>, extract_palette: bool)
     println!("  Type: {}", if result.is_light { "light" } else { "dark" });
     println!("  Output: {}", output_path.display());
     println!();
-    println!("To use this theme, add to your ~/.conduit/config.toml:");
+    println!(
+        "To use this theme, add to your {}:",
+        util::config_path().display()
+    );
     println!("  [theme]");
     println!(
         "  name = \"{}\"",
@@ -337,7 +1079,7 @@ fn run_migrate_theme(input: &Path, output: Option<&Path>, extract_palette: bool)
 }
 
 /// Run the web server
-async fn run_web_server(host: String, port: u16) -> Result<()> {
+async fn run_web_server(host: String, port: u16, compression: bool) -> Result<()> {
     use conduit::core::ConduitCore;
     use conduit::web::{run_server, ServerConfig, WebAppState};
 
@@ -349,6 +1091,12 @@ async fn run_web_server(host: String, port: u16) -> Result<()> {
                 .from_env_lossy(),
         )
         .init();
+    chaos::log_settings();
+
+    // Held until the server stops
+    let DataDirClaim::Owner(_instance_lock) = claim_data_dir(InstanceKind::Serve, false)? else {
+        unreachable!("serve never asks to observe");
+    };
 
     // Create config
     let config = Config::load();
@@ -358,6 +1106,7 @@ async fn run_web_server(host: String, port: u16) -> Result<()> {
 
     // Create ConduitCore
     let core = ConduitCore::new(config, tools);
+    core.usage_stats().record("launch.web");
 
     // Create web app state
     let state = WebAppState::new(core);
@@ -367,6 +1116,7 @@ async fn run_web_server(host: String, port: u16) -> Result<()> {
         host,
         port,
         cors_permissive: true,
+        compression,
     };
 
     // Run server
@@ -379,8 +1129,9 @@ async fn run_web_server(host: String, port: u16) -> Result<()> {
 fn run_debug_keys() -> Result<()> {
     use crossterm::{
         event::{
-            self, Event, KeyCode, KeyEvent, KeyModifiers, KeyboardEnhancementFlags,
-            PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+            self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEvent,
+            KeyModifiers, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+            PushKeyboardEnhancementFlags,
         },
         execute,
         terminal::{
@@ -393,7 +1144,7 @@ fn run_debug_keys() -> Result<()> {
     // Set up terminal
     enable_raw_mode()?;
     let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
 
     // Enable Kitty keyboard protocol for proper Ctrl+Shift detection
     // REPORT_ALL_KEYS_AS_ESCAPE_CODES is required for full modifier detection
@@ -544,7 +1295,7 @@ fn run_debug_keys() -> Result<()> {
         }
     }
     disable_raw_mode()?;
-    execute!(stdout, LeaveAlternateScreen)?;
+    execute!(stdout, DisableBracketedPaste, LeaveAlternateScreen)?;
 
     println!("Key debug mode exited.");
 
@@ -0,0 +1,379 @@
+//! Session bundles: a portable file holding a session's transcript and the
+//! commits its branch added, so a teammate can recreate the workspace and
+//! pick the conversation up on their machine.
+//!
+//! Used by `conduit bundle export` and `conduit bundle import`. An imported
+//! session starts as a fork: the transcript is replayed to a fresh agent
+//! session through a fork seed the first time its tab is opened.
+
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::agent::{AgentType, ModelRegistry};
+use crate::data::{
+    ForkSeed, ForkSeedStore, Repository, SessionTab, SessionTabStore, Workspace, WorkspaceStore,
+};
+use crate::git::{
+    apply_patch_series, branch_exists, export_patch_series, has_commit, origin_url,
+    PatchSeriesError, WorkspaceMode, WorkspaceRepoManager, WorktreeError,
+};
+use crate::ui::app_prompt;
+use crate::ui::components::ChatMessage;
+use crate::util::names::{generate_branch_name, generate_workspace_name, get_git_username};
+use crate::util::{fork_seeds_dir, workspaces_dir};
+use crate::web::handlers::sessions::load_history_for_session;
+
+/// Bundle layout version written by this build
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+#[derive(Error, Debug)]
+pub enum BundleError {
+    #[error("Bundle format version {0} isn't supported by this version of Conduit")]
+    UnsupportedVersion(u32),
+    #[error("Base commit {0} isn't in this repository; fetch it and try again")]
+    MissingBase(String),
+    #[error("Git error: {0}")]
+    Git(#[from] PatchSeriesError),
+    #[error("Workspace error: {0}")]
+    Workspace(#[from] WorktreeError),
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+    #[error("Invalid bundle: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Repository a bundle was exported from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleRepository {
+    pub name: String,
+    /// `origin` URL, used to find the matching clone on import
+    pub remote_url: Option<String>,
+}
+
+/// Agent settings the imported session starts with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleSession {
+    pub agent_type: AgentType,
+    pub agent_mode: Option<String>,
+    pub model: Option<String>,
+    pub title: Option<String>,
+}
+
+/// A session transcript plus its branch as a patch series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionBundle {
+    pub format_version: u32,
+    pub created_at: DateTime<Utc>,
+    pub repository: BundleRepository,
+    /// Branch the session worked on
+    pub branch: String,
+    /// Upstream ref the patches were taken against, e.g. `origin/main`
+    pub base_ref: String,
+    /// Commit the patches apply on top of
+    pub base_commit: String,
+    pub commit_count: usize,
+    /// `git format-patch` mail for the branch's commits
+    pub patches: String,
+    pub session: BundleSession,
+    pub messages: Vec<ChatMessage>,
+}
+
+impl SessionBundle {
+    /// Read a bundle file, rejecting layouts newer than this build understands
+    pub fn read(path: &Path) -> Result<Self, BundleError> {
+        let bundle: Self = serde_json::from_slice(&fs::read(path)?)?;
+        if bundle.format_version > BUNDLE_FORMAT_VERSION {
+            return Err(BundleError::UnsupportedVersion(bundle.format_version));
+        }
+        Ok(bundle)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), BundleError> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// An exported bundle and anything it couldn't capture
+pub struct ExportedBundle {
+    pub bundle: SessionBundle,
+    pub warnings: Vec<String>,
+}
+
+/// Capture a session's transcript and its workspace branch. Uncommitted
+/// changes aren't part of the patch series, so they only produce a warning.
+pub fn export_bundle(
+    tab: &SessionTab,
+    workspace: &Workspace,
+    repo: &Repository,
+) -> Result<ExportedBundle, BundleError> {
+    let mut warnings = Vec::new();
+    let manager = WorkspaceRepoManager::with_managed_dir(workspaces_dir());
+    match manager.get_branch_status(&workspace.path) {
+        Ok(status) if status.is_dirty => warnings.push(
+            "Uncommitted changes aren't included; commit them and export again to share them."
+                .to_string(),
+        ),
+        Ok(_) => {}
+        Err(err) => tracing::warn!(
+            workspace = %workspace.path.display(),
+            error = %err,
+            "Failed to check workspace for uncommitted changes"
+        ),
+    }
+
+    let series = export_patch_series(&workspace.path)?;
+    let messages = load_history_for_session(tab);
+    if messages.is_empty() {
+        warnings.push("No transcript was found for this session.".to_string());
+    }
+
+    let remote_url = origin_url(&workspace.path).or_else(|| repo.repository_url.clone());
+    Ok(ExportedBundle {
+        bundle: SessionBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            created_at: Utc::now(),
+            repository: BundleRepository {
+                name: repo.name.clone(),
+                remote_url,
+            },
+            branch: workspace.branch.clone(),
+            base_ref: series.base_ref,
+            base_commit: series.base_commit,
+            commit_count: series.commit_count,
+            patches: series.mbox,
+            session: BundleSession {
+                agent_type: tab.agent_type,
+                agent_mode: tab.agent_mode.clone(),
+                model: tab.model.clone(),
+                title: tab.custom_title.clone().or_else(|| tab.title.clone()),
+            },
+            messages,
+        },
+        warnings,
+    })
+}
+
+/// Reduce a git remote URL to `host/owner/repo` so SSH and HTTPS clones of
+/// the same repository compare equal
+pub fn normalize_remote_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    let without_scheme = match url.split_once("://") {
+        Some((_, rest)) => rest.to_string(),
+        // scp-like syntax: git@host:owner/repo
+        None => url.replacen(':', "/", 1),
+    };
+    let without_user = match without_scheme.split_once('@') {
+        Some((user, rest)) if !user.contains('/') => rest,
+        _ => without_scheme.as_str(),
+    };
+    without_user.to_ascii_lowercase()
+}
+
+/// Find the local repository a bundle came from by its `origin` URL
+pub fn find_repository(bundle: &SessionBundle, repos: &[Repository]) -> Option<Repository> {
+    let wanted = normalize_remote_url(bundle.repository.remote_url.as_deref()?);
+    repos
+        .iter()
+        .find(|repo| {
+            let url = repo
+                .base_path
+                .as_deref()
+                .and_then(origin_url)
+                .or_else(|| repo.repository_url.clone());
+            url.is_some_and(|url| normalize_remote_url(&url) == wanted)
+        })
+        .cloned()
+}
+
+/// Stores an import writes to
+pub struct BundleStores<'a> {
+    pub workspaces: &'a WorkspaceStore,
+    pub tabs: &'a SessionTabStore,
+    pub fork_seeds: &'a ForkSeedStore,
+}
+
+/// Workspace and open tab created from a bundle
+pub struct ImportedBundle {
+    pub workspace: Workspace,
+    pub tab: SessionTab,
+}
+
+/// Recreate a bundle's branch as a new workspace of `repo` and open a tab that
+/// seeds a fresh agent session with the transcript when it's first shown
+pub fn import_bundle(
+    bundle: &SessionBundle,
+    repo: &Repository,
+    base_path: &Path,
+    mode: WorkspaceMode,
+    stores: &BundleStores<'_>,
+) -> Result<ImportedBundle, BundleError> {
+    if !has_commit(base_path, &bundle.base_commit) {
+        return Err(BundleError::MissingBase(bundle.base_commit.clone()));
+    }
+
+    let existing_names = stores.workspaces.get_all_names_by_repository(repo.id)?;
+    let workspace_name = generate_workspace_name(&existing_names);
+    // Worktree creation reuses an existing branch, so never collide with one
+    let branch = if bundle.branch.is_empty() || branch_exists(base_path, &bundle.branch) {
+        generate_branch_name(&get_git_username(), &workspace_name)
+    } else {
+        bundle.branch.clone()
+    };
+
+    let manager = WorkspaceRepoManager::with_managed_dir(workspaces_dir());
+    let workspace_path = manager.create_workspace_from_branch(
+        mode,
+        base_path,
+        &bundle.base_commit,
+        &branch,
+        &workspace_name,
+    )?;
+    let workspace = Workspace::new(repo.id, &workspace_name, &branch, workspace_path);
+
+    let result = apply_patch_series(&workspace.path, &bundle.patches)
+        .map_err(BundleError::from)
+        .and_then(|()| {
+            stores.workspaces.create(&workspace)?;
+            Ok(())
+        });
+    if let Err(err) = result {
+        remove_workspace(&manager, mode, base_path, &workspace);
+        return Err(err);
+    }
+
+    let tab = create_seeded_tab(bundle, &workspace, stores)?;
+    Ok(ImportedBundle { workspace, tab })
+}
+
+fn create_seeded_tab(
+    bundle: &SessionBundle,
+    workspace: &Workspace,
+    stores: &BundleStores<'_>,
+) -> Result<SessionTab, BundleError> {
+    let agent_type = bundle.session.agent_type;
+    let seed_prompt = app_prompt::build_fork_seed_prompt(&bundle.messages);
+    let model_id = bundle
+        .session
+        .model
+        .clone()
+        .unwrap_or_else(|| ModelRegistry::default_model(agent_type));
+    let token_estimate = ((seed_prompt.chars().count().max(1) as f64) / 4.0).ceil() as i64;
+
+    let mut fork_seed = ForkSeed::new(
+        agent_type,
+        None,
+        None,
+        app_prompt::compute_seed_prompt_hash(&seed_prompt),
+        None,
+        token_estimate,
+        ModelRegistry::context_window(agent_type, &model_id),
+    );
+    let seeds_dir = fork_seeds_dir();
+    fs::create_dir_all(&seeds_dir)?;
+    let seed_path = seeds_dir.join(format!("{}.txt", fork_seed.id));
+    fs::write(&seed_path, &seed_prompt)?;
+    fork_seed.seed_prompt_path = Some(seed_path.to_string_lossy().into_owned());
+    stores.fork_seeds.create(&fork_seed)?;
+
+    let mut tab = SessionTab::new(
+        0,
+        agent_type,
+        Some(workspace.id),
+        None,
+        bundle.session.model.clone(),
+        None,
+    );
+    tab.agent_mode = bundle.session.agent_mode.clone();
+    tab.fork_seed_id = Some(fork_seed.id);
+    tab.title = bundle.session.title.clone();
+    tab.title_generated = tab.title.is_some();
+    Ok(stores.tabs.create_with_next_index(tab)?)
+}
+
+fn remove_workspace(
+    manager: &WorkspaceRepoManager,
+    mode: WorkspaceMode,
+    base_path: &Path,
+    workspace: &Workspace,
+) {
+    if let Err(err) = manager.remove_workspace(mode, base_path, &workspace.path) {
+        tracing::error!(
+            error = %err,
+            base_path = %base_path.display(),
+            workspace_path = %workspace.path.display(),
+            "Failed to clean up worktree after bundle import failed"
+        );
+    }
+    if let Err(err) = manager.delete_branch(mode, base_path, &workspace.path, &workspace.branch) {
+        tracing::error!(
+            error = %err,
+            base_path = %base_path.display(),
+            branch = %workspace.branch,
+            "Failed to delete branch after bundle import failed"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_remote_url_matches_ssh_and_https() {
+        let expected = "github.com/boozelee/conduit";
+        assert_eq!(
+            normalize_remote_url("git@github.com:BoozeLee/conduit.git"),
+            expected
+        );
+        assert_eq!(
+            normalize_remote_url("https://github.com/BoozeLee/conduit"),
+            expected
+        );
+        assert_eq!(
+            normalize_remote_url("ssh://git@github.com/BoozeLee/conduit.git/"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_read_rejects_newer_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.conduit-bundle");
+        let bundle = SessionBundle {
+            format_version: BUNDLE_FORMAT_VERSION + 1,
+            created_at: Utc::now(),
+            repository: BundleRepository {
+                name: "conduit".to_string(),
+                remote_url: None,
+            },
+            branch: "feature".to_string(),
+            base_ref: "origin/main".to_string(),
+            base_commit: "abc123".to_string(),
+            commit_count: 0,
+            patches: String::new(),
+            session: BundleSession {
+                agent_type: AgentType::Claude,
+                agent_mode: None,
+                model: None,
+                title: None,
+            },
+            messages: Vec::new(),
+        };
+        bundle.write(&path).unwrap();
+        assert!(matches!(
+            SessionBundle::read(&path),
+            Err(BundleError::UnsupportedVersion(v)) if v == BUNDLE_FORMAT_VERSION + 1
+        ));
+    }
+}
@@ -32,7 +32,7 @@ pub struct SessionCache {
 impl SessionCache {
     /// Get the cache file path
     pub fn cache_path() -> PathBuf {
-        crate::util::data_dir().join("sessions_cache.json")
+        crate::util::cache_dir().join("sessions_cache.json")
     }
 
     /// Load cache from disk, returning empty cache if missing/corrupt
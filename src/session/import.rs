@@ -36,52 +36,57 @@ pub struct ExternalSession {
     pub file_path: PathBuf,
 }
 
+/// How long ago `timestamp` was, e.g. "2 hours ago"
+pub fn relative_time(timestamp: DateTime<Utc>) -> String {
+    let now = Utc::now();
+    let duration = now.signed_duration_since(timestamp);
+
+    let minutes = duration.num_minutes();
+    let hours = duration.num_hours();
+    let days = duration.num_days();
+
+    if minutes < 1 {
+        "just now".to_string()
+    } else if minutes < 60 {
+        format!("{} min ago", minutes)
+    } else if hours < 24 {
+        if hours == 1 {
+            "1 hour ago".to_string()
+        } else {
+            format!("{} hours ago", hours)
+        }
+    } else if days == 1 {
+        "Yesterday".to_string()
+    } else if days < 7 {
+        format!("{} days ago", days)
+    } else if days < 30 {
+        let weeks = days / 7;
+        if weeks == 1 {
+            "1 week ago".to_string()
+        } else {
+            format!("{} weeks ago", weeks)
+        }
+    } else if days < 365 {
+        let months = days / 30;
+        if months == 1 {
+            "1 month ago".to_string()
+        } else {
+            format!("{} months ago", months)
+        }
+    } else {
+        let years = days / 365;
+        if years == 1 {
+            "1 year ago".to_string()
+        } else {
+            format!("{} years ago", years)
+        }
+    }
+}
+
 impl ExternalSession {
     /// Get a relative time string (e.g., "2 hours ago")
     pub fn relative_time(&self) -> String {
-        let now = Utc::now();
-        let duration = now.signed_duration_since(self.timestamp);
-
-        let minutes = duration.num_minutes();
-        let hours = duration.num_hours();
-        let days = duration.num_days();
-
-        if minutes < 1 {
-            "just now".to_string()
-        } else if minutes < 60 {
-            format!("{} min ago", minutes)
-        } else if hours < 24 {
-            if hours == 1 {
-                "1 hour ago".to_string()
-            } else {
-                format!("{} hours ago", hours)
-            }
-        } else if days == 1 {
-            "Yesterday".to_string()
-        } else if days < 7 {
-            format!("{} days ago", days)
-        } else if days < 30 {
-            let weeks = days / 7;
-            if weeks == 1 {
-                "1 week ago".to_string()
-            } else {
-                format!("{} weeks ago", weeks)
-            }
-        } else if days < 365 {
-            let months = days / 30;
-            if months == 1 {
-                "1 month ago".to_string()
-            } else {
-                format!("{} months ago", months)
-            }
-        } else {
-            let years = days / 365;
-            if years == 1 {
-                "1 year ago".to_string()
-            } else {
-                format!("{} years ago", years)
-            }
-        }
+        relative_time(self.timestamp)
     }
 
     /// Get a truncated display string
@@ -102,6 +107,15 @@ impl ExternalSession {
         }
     }
 
+    /// ID the agent resumes this conversation by (the session file's name)
+    pub fn resume_id(&self) -> String {
+        self.file_path
+            .file_stem()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_string()
+    }
+
     /// Get the project name (last component of path)
     pub fn project_name(&self) -> Option<String> {
         self.project.as_ref().and_then(|p| {
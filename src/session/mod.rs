@@ -1,14 +1,21 @@
 //! Session management module
 //!
 //! This module provides utilities for discovering and importing
-//! sessions from external agents (Claude Code, Codex CLI, Gemini CLI, and OpenCode).
+//! sessions from external agents (Claude Code, Codex CLI, Gemini CLI, and OpenCode),
+//! and for sharing sessions as bundles.
 
+pub mod bundle;
 pub mod cache;
 pub mod import;
+pub mod register;
 
+pub use bundle::{
+    export_bundle, find_repository, import_bundle, BundleError, BundleStores, SessionBundle,
+};
 pub use cache::{get_file_mtime, SessionCache};
 pub use import::{
     discover_all_sessions, discover_claude_sessions, discover_codex_sessions,
     discover_opencode_sessions, discover_sessions_incremental, ExternalSession,
     SessionDiscoveryUpdate,
 };
+pub use register::{parse_selection, register_sessions, unregistered_sessions};
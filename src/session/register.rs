@@ -0,0 +1,181 @@
+//! Registering discovered conversations as Conduit sessions
+//!
+//! Used by `conduit import`: each selected conversation becomes an open tab
+//! that the TUI restores on its next start and can resume with the agent.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use rusqlite::Result as SqliteResult;
+
+use crate::data::{SessionTab, SessionTabStore, WorkspaceStore};
+use crate::session::import::ExternalSession;
+
+/// Longest imported tab title, in characters
+const TITLE_MAX_CHARS: usize = 60;
+
+/// Conversations not yet registered as Conduit sessions, newest first
+pub fn unregistered_sessions(
+    mut sessions: Vec<ExternalSession>,
+    registered_ids: &HashSet<String>,
+) -> Vec<ExternalSession> {
+    sessions.retain(|session| !registered_ids.contains(&session.resume_id()));
+    sessions.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    sessions
+}
+
+/// Save each conversation as an open tab. A conversation is attached to the
+/// Conduit workspace at its project path unless that workspace already has an
+/// open tab; otherwise the tab just remembers the project directory.
+pub fn register_sessions(
+    sessions: &[ExternalSession],
+    tabs: &SessionTabStore,
+    workspaces: &WorkspaceStore,
+) -> SqliteResult<Vec<SessionTab>> {
+    let mut registered = Vec::with_capacity(sessions.len());
+    let mut claimed_workspaces = HashSet::new();
+    for session in sessions {
+        let project = session.project.as_deref().map(PathBuf::from);
+        let mut workspace_id = None;
+        if let Some(project) = project.as_deref() {
+            if let Some(workspace) = workspaces.get_by_path(project)? {
+                // Only one open tab per workspace
+                if !workspace.is_archived()
+                    && tabs.get_open_by_workspace_id(workspace.id)?.is_none()
+                    && claimed_workspaces.insert(workspace.id)
+                {
+                    workspace_id = Some(workspace.id);
+                }
+            }
+        }
+        let mut tab = SessionTab::new(
+            0,
+            session.agent_type,
+            workspace_id,
+            Some(session.resume_id()),
+            None,
+            None,
+        );
+        if workspace_id.is_none() {
+            tab.working_dir = project;
+        }
+        tab.title = Some(session.truncated_display(TITLE_MAX_CHARS));
+        registered.push(tabs.create_with_next_index(tab)?);
+    }
+    Ok(registered)
+}
+
+/// Parse a selection like `1 3-5, 8` into zero-based indices into a list of
+/// `count` items. `all` selects everything.
+pub fn parse_selection(input: &str, count: usize) -> Result<Vec<usize>, String> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("all") {
+        return Ok((0..count).collect());
+    }
+    let mut selected = Vec::new();
+    for part in input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+    {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (parse_number(start, count)?, parse_number(end, count)?),
+            None => {
+                let number = parse_number(part, count)?;
+                (number, number)
+            }
+        };
+        if start > end {
+            return Err(format!("Range {part} is backwards"));
+        }
+        for index in start - 1..end {
+            if !selected.contains(&index) {
+                selected.push(index);
+            }
+        }
+    }
+    Ok(selected)
+}
+
+fn parse_number(text: &str, count: usize) -> Result<usize, String> {
+    match text.trim().parse::<usize>() {
+        Ok(number) if (1..=count).contains(&number) => Ok(number),
+        _ => Err(format!("{text} isn't a number from 1 to {count}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentType;
+    use crate::data::{Database, Repository, RepositoryStore, Workspace};
+    use chrono::{Duration, Utc};
+    use tempfile::tempdir;
+
+    fn external(id: &str, project: &str, age_hours: i64) -> ExternalSession {
+        ExternalSession {
+            id: id.to_string(),
+            agent_type: AgentType::Claude,
+            display: format!("Conversation {id}"),
+            project: Some(project.to_string()),
+            timestamp: Utc::now() - Duration::hours(age_hours),
+            message_count: 4,
+            file_path: PathBuf::from(format!("/tmp/{id}.jsonl")),
+        }
+    }
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!(parse_selection("1 3-4, 2", 5), Ok(vec![0, 2, 3, 1]));
+        assert_eq!(parse_selection("all", 3), Ok(vec![0, 1, 2]));
+        assert_eq!(parse_selection("", 3), Ok(vec![]));
+        assert!(parse_selection("0", 3).is_err());
+        assert!(parse_selection("2-4", 3).is_err());
+        assert!(parse_selection("3-1", 3).is_err());
+    }
+
+    #[test]
+    fn test_register_sessions() {
+        let dir = tempdir().unwrap();
+        let db = Database::open(dir.path().join("test.db")).unwrap();
+        let tabs = SessionTabStore::new(db.connection());
+        let workspaces = WorkspaceStore::new(db.connection());
+        let repo = Repository::from_local_path("demo", PathBuf::from("/tmp/demo"));
+        RepositoryStore::new(db.connection()).create(&repo).unwrap();
+        let workspace = Workspace::new_default(repo.id, "main", "main", PathBuf::from("/tmp/demo"));
+        workspaces.create(&workspace).unwrap();
+
+        let sessions = unregistered_sessions(
+            vec![
+                external("older", "/tmp/elsewhere", 5),
+                external("newer", "/tmp/demo", 1),
+                external("known", "/tmp/demo", 2),
+                external("oldest", "/tmp/demo", 9),
+            ],
+            &HashSet::from(["known".to_string()]),
+        );
+        assert_eq!(sessions.len(), 3);
+        assert_eq!(sessions[0].id, "newer");
+
+        let registered = register_sessions(&sessions, &tabs, &workspaces).unwrap();
+        assert_eq!(registered[0].workspace_id, Some(workspace.id));
+        assert_eq!(registered[0].working_dir, None);
+        assert_eq!(registered[1].workspace_id, None);
+        assert_eq!(
+            registered[1].working_dir,
+            Some(PathBuf::from("/tmp/elsewhere"))
+        );
+        // The workspace already has a tab, so this one only keeps the directory
+        assert_eq!(registered[2].workspace_id, None);
+        assert_eq!(registered[2].working_dir, Some(PathBuf::from("/tmp/demo")));
+
+        let restored = tabs.get_all().unwrap();
+        assert_eq!(restored.len(), 3);
+        assert_eq!(restored[0].agent_session_id.as_deref(), Some("newer"));
+        assert_eq!(restored[1].title.as_deref(), Some("Conversation older"));
+        assert_eq!(
+            restored[1].working_dir,
+            Some(PathBuf::from("/tmp/elsewhere"))
+        );
+        assert!(tabs.agent_session_ids().unwrap().contains("older"));
+    }
+}
@@ -14,6 +14,8 @@ pub enum Action {
     // ========== Global Actions ==========
     /// Quit the application
     Quit,
+    /// Clear the input; pressed twice, interrupt the agent and quit
+    ClearOrQuit,
     /// Toggle sidebar visibility
     ToggleSidebar,
     /// Open new project dialog
@@ -28,24 +30,70 @@ pub enum Action {
     InterruptAgent,
     /// Toggle between Chat and RawEvents view
     ToggleViewMode,
+    /// Toggle the session timeline view
+    ToggleTimeline,
+    /// Toggle the full-screen diff of the session's file edits
+    ToggleDiffView,
     /// Show model selector dialog
     ShowModelSelector,
     /// Show theme picker dialog
     ShowThemePicker,
     /// Toggle performance metrics display
     ToggleMetrics,
+    /// Toggle the per-section frame timing overlay
+    ToggleFrameDiagnostics,
+    /// Toggle zen mode (hide tab bar, status bar, and footer)
+    ToggleZenMode,
+    /// Export the active session transcript to PDF (via HTML)
+    ExportTranscriptPdf,
+    /// Export the active session transcript to Markdown
+    ExportTranscriptMarkdown,
+    /// Export the active session transcript to JSON
+    ExportTranscriptJson,
     /// Dump debug state to file
     DumpDebugState,
+    /// Show captured agent stderr for the active session
+    ShowDiagnostics,
+    /// Restart a crashed agent, or interrupt a stalled turn, and resend the last prompt
+    RestartAgent,
+    /// Ask an agent whose turn looks stalled to continue or report what blocks it
+    NudgeAgent,
+    /// Apply the suggested fix for a classified failure (login, setup, retry)
+    ApplyFailureRemedy,
+    /// Crop or mark up the last attached image (opens `:annotate`)
+    AnnotateImage,
+    /// Start or stop recording a voice prompt
+    ToggleVoiceInput,
+    /// Turn spoken read-aloud of completed responses on or off
+    ToggleReadAloud,
+    /// Switch the session's response language directive on or off
+    ToggleResponseLanguage,
+    /// Put a past prompt (last jumped to, else the latest) back in the input
+    EditPastPrompt,
+    /// Roll the workspace back to before a past prompt, then edit it
+    RollbackToPastPrompt,
     /// Suspend the application (Ctrl+Z)
     Suspend,
     /// Copy current workspace path to clipboard
     CopyWorkspacePath,
     /// Copy active selection to clipboard
     CopySelection,
+    /// Highlight a whole chat message to copy it (j/k to move, y to copy)
+    SelectMessages,
+    /// Copy the highlighted chat message to clipboard
+    CopySelectedMessage,
+    /// Show recent copies to copy or paste one again
+    ShowClipboardHistory,
+    /// Copy the highlighted clipboard history entry again
+    RecopyClipboardEntry,
+    /// Insert the highlighted clipboard history entry into the input
+    PasteClipboardEntry,
 
     // ========== Tab Management ==========
     /// Close current tab
     CloseTab,
+    /// Give the current tab a custom name
+    RenameTab,
     /// Switch to next tab
     NextTab,
     /// Switch to previous tab
@@ -74,6 +122,22 @@ pub enum Action {
     ScrollPrevUserMessage,
     /// Jump to next user message in chat
     ScrollNextUserMessage,
+    /// Jump to the earlier prompt a held-back duplicate repeats
+    JumpToDuplicateAnswer,
+    /// Scroll unwrapped code lines left by N columns
+    ScrollLeft(u16),
+    /// Scroll unwrapped code lines right by N columns
+    ScrollRight(u16),
+    /// Toggle line wrapping for code blocks and tool output
+    ToggleCodeWrap,
+    /// Collapse or expand reasoning blocks
+    ToggleReasoning,
+    /// Collapse or expand the tool output block in view
+    ToggleToolOutput,
+    /// Expand all tool output, or collapse long output again
+    ToggleExpandTools,
+    /// Cycle message timestamps: off, absolute, relative
+    ToggleTimestamps,
 
     // ========== Input Box Editing ==========
     /// Insert a newline (for multi-line input)
@@ -90,6 +154,12 @@ pub enum Action {
     DeleteToStart,
     /// Delete from cursor to end of line
     DeleteToEnd,
+    /// Undo the last edit of the input box
+    Undo,
+    /// Redo the last undone edit of the input box
+    Redo,
+    /// Remove the last image attached to the input box
+    RemoveAttachment,
     /// Move cursor left one character
     MoveCursorLeft,
     /// Move cursor right one character
@@ -126,6 +196,10 @@ pub enum Action {
     QueueEdit,
     /// Delete selected queued message
     QueueDelete,
+    /// Collapse/expand the inline queue panel to a one-line summary
+    ToggleQueuePanel,
+    /// Open the queue editor as a full management overlay
+    ExpandQueue,
     /// Edit prompt in external editor
     EditPromptExternal,
 
@@ -170,6 +244,14 @@ pub enum Action {
     RawEventsToggleExpand,
     /// Collapse expanded event
     RawEventsCollapse,
+    /// Search serialized event payloads
+    RawEventsSearch,
+    /// Select the next event matching the search
+    RawEventsSearchNext,
+    /// Select the previous event matching the search
+    RawEventsSearchPrev,
+    /// Toggle sticking to the newest event as events arrive
+    RawEventsToggleFollow,
 
     // ========== Event Detail Panel ==========
     /// Toggle event detail panel visibility
@@ -188,6 +270,12 @@ pub enum Action {
     EventDetailScrollToBottom,
     /// Copy selected event JSON to clipboard
     EventDetailCopy,
+    /// Fold one more level of nested JSON in event detail panel
+    EventDetailFold,
+    /// Unfold one level of nested JSON in event detail panel
+    EventDetailUnfold,
+    /// Focus the jq-like path filter in event detail panel
+    EventDetailFilter,
 
     // ========== Confirmation Dialog ==========
     /// Confirm yes in dialog
@@ -204,6 +292,12 @@ pub enum Action {
     SelectAgent,
     /// Toggle between Build and Plan mode
     ToggleAgentMode,
+    /// Cycle the Codex sandbox mode for the active session
+    CycleCodexSandbox,
+    /// Cycle the Codex approval policy for the active session
+    CycleCodexApproval,
+    /// Toggle hunk-by-hunk review of file edits for the active session
+    ToggleEditReview,
 
     // ========== Session Import ==========
     /// Open session import picker
@@ -226,12 +320,105 @@ pub enum Action {
     OpenCommandPalette,
 }
 
+/// Every action the command palette offers, in registry order.
+///
+/// Context-only actions (text editing, list navigation, dialog buttons) are
+/// left out since they do nothing outside their own input mode.
+pub const PALETTE_ACTIONS: &[Action] = &[
+    // Global
+    Action::Quit,
+    Action::ToggleSidebar,
+    Action::NewProject,
+    Action::NewWorkspaceUnderCursor,
+    Action::OpenPr,
+    Action::ForkSession,
+    Action::InterruptAgent,
+    Action::ToggleViewMode,
+    Action::ToggleTimeline,
+    Action::ToggleDiffView,
+    Action::ShowModelSelector,
+    Action::ShowThemePicker,
+    Action::ToggleMetrics,
+    Action::ToggleFrameDiagnostics,
+    Action::ToggleZenMode,
+    Action::ExportTranscriptPdf,
+    Action::ExportTranscriptMarkdown,
+    Action::ExportTranscriptJson,
+    Action::DumpDebugState,
+    Action::ShowDiagnostics,
+    Action::RestartAgent,
+    Action::NudgeAgent,
+    Action::ApplyFailureRemedy,
+    Action::AnnotateImage,
+    Action::ToggleVoiceInput,
+    Action::ToggleReadAloud,
+    Action::ToggleResponseLanguage,
+    Action::EditPastPrompt,
+    Action::RollbackToPastPrompt,
+    Action::CopyWorkspacePath,
+    Action::CopySelection,
+    Action::SelectMessages,
+    Action::ShowClipboardHistory,
+    // Tab management
+    Action::CloseTab,
+    Action::RenameTab,
+    Action::NextTab,
+    Action::PrevTab,
+    // Scrolling (page-level)
+    Action::ScrollPageUp,
+    Action::ScrollPageDown,
+    Action::ScrollToTop,
+    Action::ScrollToBottom,
+    Action::ToggleCodeWrap,
+    Action::ToggleReasoning,
+    Action::ToggleToolOutput,
+    Action::ToggleExpandTools,
+    Action::ToggleTimestamps,
+    // Input editing
+    Action::EditPromptExternal,
+    // Sidebar
+    Action::EnterSidebarMode,
+    Action::AddRepository,
+    Action::OpenSettings,
+    Action::ArchiveOrRemove,
+    // Agent/Session
+    Action::ToggleAgentMode,
+    Action::CycleCodexSandbox,
+    Action::CycleCodexApproval,
+    Action::ToggleEditReview,
+    Action::OpenQueueEditor,
+    Action::ExpandQueue,
+    Action::ToggleQueuePanel,
+    Action::OpenSessionImport,
+    Action::ShowHelp,
+];
+
 impl Action {
+    /// Variant name in snake_case for usage statistics (`SwitchToTab(3)` ->
+    /// `switch_to_tab`); variant data such as paths is never included
+    pub fn usage_name(&self) -> String {
+        let debug = format!("{:?}", self);
+        let variant = debug.split(['(', ' ', '{']).next().unwrap_or_default();
+        let mut name = String::with_capacity(variant.len() + 4);
+        for (i, c) in variant.chars().enumerate() {
+            if c.is_ascii_uppercase() {
+                if i > 0 {
+                    name.push('_');
+                }
+                name.push(c.to_ascii_lowercase());
+            } else {
+                name.push(c);
+            }
+        }
+        name
+    }
+
     /// Get a human-readable description of the action
     pub fn description(&self) -> &'static str {
         match self {
             // Global
             Action::Quit => "Quit application",
+            Action::ClearOrQuit => "Clear input (press twice to quit)",
             Action::ToggleSidebar => "Toggle sidebar",
             Action::NewProject => "New project",
             Action::NewWorkspaceUnderCursor => "New workspace (current project)",
@@ -239,16 +426,39 @@ impl Action {
             Action::ForkSession => "Fork session",
             Action::InterruptAgent => "Interrupt agent",
             Action::ToggleViewMode => "Toggle view mode",
+            Action::ToggleTimeline => "Toggle timeline view",
+            Action::ToggleDiffView => "Toggle diff view",
             Action::ShowModelSelector => "Select model",
             Action::ShowThemePicker => "Change theme",
             Action::ToggleMetrics => "Toggle metrics",
+            Action::ToggleFrameDiagnostics => "Toggle frame diagnostics",
+            Action::ToggleZenMode => "Toggle zen mode",
+            Action::ExportTranscriptPdf => "Export transcript to PDF",
+            Action::ExportTranscriptMarkdown => "Export transcript to Markdown",
+            Action::ExportTranscriptJson => "Export transcript to JSON",
             Action::DumpDebugState => "Dump debug state",
+            Action::ShowDiagnostics => "Show agent diagnostics",
+            Action::RestartAgent => "Restart agent and resend last prompt",
+            Action::NudgeAgent => "Nudge a stalled agent to continue",
+            Action::ApplyFailureRemedy => "Fix failed turn",
+            Action::AnnotateImage => "Annotate attached image",
+            Action::ToggleVoiceInput => "Record voice prompt",
+            Action::ToggleReadAloud => "Toggle read-aloud",
+            Action::ToggleResponseLanguage => "Toggle response language",
+            Action::EditPastPrompt => "Edit and resend past prompt",
+            Action::RollbackToPastPrompt => "Roll back to past prompt",
             Action::Suspend => "Suspend",
             Action::CopyWorkspacePath => "Copy workspace path",
             Action::CopySelection => "Copy selection",
+            Action::SelectMessages => "Select a message to copy",
+            Action::CopySelectedMessage => "Copy selected message",
+            Action::ShowClipboardHistory => "Clipboard history",
+            Action::RecopyClipboardEntry => "Copy clipboard entry again",
+            Action::PasteClipboardEntry => "Paste clipboard entry into input",
 
             // Tab management
             Action::CloseTab => "Close tab",
+            Action::RenameTab => "Rename tab",
             Action::NextTab => "Next tab",
             Action::PrevTab => "Previous tab",
             Action::SwitchToTab(_) => "Switch to tab",
@@ -265,6 +475,14 @@ impl Action {
             Action::ScrollToBottom => "Scroll to bottom",
             Action::ScrollPrevUserMessage => "Previous user message",
             Action::ScrollNextUserMessage => "Next user message",
+            Action::JumpToDuplicateAnswer => "Jump to earlier answer",
+            Action::ScrollLeft(_) => "Scroll left",
+            Action::ScrollRight(_) => "Scroll right",
+            Action::ToggleCodeWrap => "Toggle code line wrap",
+            Action::ToggleReasoning => "Collapse/expand reasoning",
+            Action::ToggleToolOutput => "Collapse/expand tool output",
+            Action::ToggleExpandTools => "Expand/collapse all tool output",
+            Action::ToggleTimestamps => "Cycle message timestamps",
 
             // Input editing
             Action::InsertNewline => "Insert newline",
@@ -274,6 +492,9 @@ impl Action {
             Action::DeleteWordForward => "Delete word forward",
             Action::DeleteToStart => "Delete to start",
             Action::DeleteToEnd => "Delete to end",
+            Action::Undo => "Undo edit",
+            Action::Redo => "Redo edit",
+            Action::RemoveAttachment => "Remove last attached image",
             Action::MoveCursorLeft => "Move left",
             Action::MoveCursorRight => "Move right",
             Action::MoveCursorStart => "Move to start",
@@ -292,6 +513,8 @@ impl Action {
             Action::QueueMoveDown => "Move queued message down",
             Action::QueueEdit => "Edit queued message",
             Action::QueueDelete => "Delete queued message",
+            Action::ToggleQueuePanel => "Collapse/expand queue panel",
+            Action::ExpandQueue => "Manage queue",
             Action::EditPromptExternal => "Edit prompt in external editor",
 
             // List/Tree navigation
@@ -317,6 +540,10 @@ impl Action {
             Action::RawEventsSelectPrev => "Select previous event",
             Action::RawEventsToggleExpand => "Toggle expand",
             Action::RawEventsCollapse => "Collapse event",
+            Action::RawEventsSearch => "Search events",
+            Action::RawEventsSearchNext => "Next matching event",
+            Action::RawEventsSearchPrev => "Previous matching event",
+            Action::RawEventsToggleFollow => "Toggle follow newest event",
 
             // Event detail panel
             Action::EventDetailToggle => "Toggle detail panel",
@@ -327,6 +554,9 @@ impl Action {
             Action::EventDetailScrollToTop => "Panel to top",
             Action::EventDetailScrollToBottom => "Panel to bottom",
             Action::EventDetailCopy => "Copy event JSON",
+            Action::EventDetailFold => "Fold nested JSON",
+            Action::EventDetailUnfold => "Unfold nested JSON",
+            Action::EventDetailFilter => "Filter by JSON path",
 
             // Confirmation
             Action::ConfirmYes => "Yes",
@@ -337,6 +567,9 @@ impl Action {
             // Agent
             Action::SelectAgent => "Select agent",
             Action::ToggleAgentMode => "Toggle Build/Plan mode",
+            Action::CycleCodexSandbox => "Cycle Codex sandbox mode",
+            Action::CycleCodexApproval => "Cycle Codex approval policy",
+            Action::ToggleEditReview => "Toggle edit review",
 
             // Session import
             Action::OpenSessionImport => "Import session",
@@ -361,56 +594,21 @@ impl Action {
                 | Action::ShowModelSelector
                 | Action::ShowThemePicker
                 | Action::OpenQueueEditor
+                | Action::ExpandQueue
                 | Action::OpenSessionImport
                 | Action::ShowHelp
+                | Action::ShowDiagnostics
                 | Action::AddRepository
                 | Action::OpenSettings
                 | Action::OpenCommandPalette
                 | Action::ForkSession
+                | Action::ShowClipboardHistory
         )
     }
 
     /// Returns true if this action should appear in the command palette
     pub fn show_in_palette(&self) -> bool {
-        matches!(
-            self,
-            // Global
-            Action::Quit
-                | Action::ToggleSidebar
-                | Action::NewProject
-                | Action::NewWorkspaceUnderCursor
-                | Action::OpenPr
-                | Action::ForkSession
-                | Action::InterruptAgent
-                | Action::ToggleViewMode
-                | Action::ShowModelSelector
-                | Action::ShowThemePicker
-                | Action::ToggleMetrics
-                | Action::DumpDebugState
-                | Action::CopyWorkspacePath
-                | Action::CopySelection
-                // Tab management
-                | Action::CloseTab
-                | Action::NextTab
-                | Action::PrevTab
-                // Scrolling (page-level)
-                | Action::ScrollPageUp
-                | Action::ScrollPageDown
-                | Action::ScrollToTop
-                | Action::ScrollToBottom
-                // Input editing
-                | Action::EditPromptExternal
-                // Sidebar
-                | Action::EnterSidebarMode
-                | Action::AddRepository
-                | Action::OpenSettings
-                | Action::ArchiveOrRemove
-                // Agent/Session
-                | Action::ToggleAgentMode
-                | Action::OpenQueueEditor
-                | Action::OpenSessionImport
-                | Action::ShowHelp
-        )
+        PALETTE_ACTIONS.contains(self)
     }
 
     /// Get palette description (with "..." suffix for dialogs)
@@ -1,16 +1,18 @@
 use std::env;
 use std::fs::File;
 use std::io::{self, Write};
-use std::path::{Component, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::process::Command;
 use std::process::Stdio;
-use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use anyhow::anyhow;
 use chrono::Utc;
 use crossterm::{
-    event::{EnableMouseCapture, Event, EventStream, KeyCode, KeyModifiers, MouseEventKind},
+    event::{
+        EnableBracketedPaste, EnableFocusChange, EnableMouseCapture, Event, EventStream, KeyCode,
+        KeyModifiers, MouseEventKind,
+    },
     execute,
     terminal::{enable_raw_mode, EnterAlternateScreen},
 };
@@ -24,46 +26,77 @@ use ratatui::{
 use tempfile::Builder;
 use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 use unicode_width::UnicodeWidthStr;
 use uuid::Uuid;
 
-use crate::agent::events::UserQuestion;
+use crate::agent::edit_review::{self, EditReview, ReviewOutcome};
+use crate::agent::events::{TokenUsage, UserQuestion};
+use crate::agent::triage;
 use crate::agent::{
     load_claude_history_with_debug, load_codex_history_with_debug,
-    load_opencode_history_for_dir_with_debug, load_opencode_history_with_debug, AgentEvent,
-    AgentInput, AgentMode, AgentRunner, AgentStartConfig, AgentType, ClaudeCodeRunner,
-    CodexCliRunner, GeminiCliRunner, HistoryDebugEntry, MessageDisplay, ModelRegistry,
-    OpencodeRunner, SessionId,
+    load_opencode_history_for_dir_with_debug, load_opencode_history_with_debug, AgentCapabilities,
+    AgentEvent, AgentInput, AgentMode, AgentStartConfig, AgentType, CodexApprovalPolicy,
+    CodexSandboxMode, ExecutionTarget, FailureKind, HistoryDebugEntry, MessageDisplay,
+    ModelRegistry, SessionId, AGENT_CRASHED_CODE, NO_PID,
 };
-use crate::config::{parse_action, parse_key_notation, Config, KeyContext, COMMAND_NAMES};
+use crate::config::{parse_action, ChatTimestamps, Config, KeyContext, COMMAND_NAMES};
+use crate::core::bootstrap::{
+    bootstrap_commands, run_bootstrap, BootstrapError, BootstrapProgress,
+};
+use crate::core::change_guard::{self, ChangeFlag};
+use crate::core::coverage;
+use crate::core::dep_update;
+use crate::core::hooks::{HookContext, HookFileChange, HookStage, TurnReport};
+use crate::core::previews::{self, forward_preview};
 use crate::core::resolve_repo_workspace_settings;
+use crate::core::security_scan::{self, SecurityScanReport};
 use crate::core::ConduitCore;
 use crate::data::{
-    AppStateStore, ForkSeed, ForkSeedStore, QueuedImageAttachment, QueuedMessage,
-    QueuedMessageMode, Repository, RepositoryStore, SessionTab, SessionTabStore, WorkspaceStore,
+    cache_seed_prompt, AppStateStore, AttachmentStore, ForkSeed, ForkSeedStore,
+    QueuedImageAttachment, QueuedMessage, QueuedMessageMode, Repository, RepositoryStore,
+    SessionTab, SessionTabStore, WorkspaceStore,
+};
+use crate::git::{
+    capture_worktree, restore_worktree, LinkedPrPreflight, PrManager, PrStatus, WorkspaceMode,
+    WorkspaceRepoManager,
 };
-use crate::git::{PrManager, PrStatus, WorkspaceMode, WorkspaceRepoManager};
+use crate::session::import::relative_time;
 use crate::ui::action::Action;
-use crate::ui::app_prompt;
+use crate::ui::app_prompt::{self, ResponsePreset};
 use crate::ui::app_queue;
-use crate::ui::app_state::{AppState, PendingForkRequest};
-use crate::ui::capabilities::AgentCapabilities;
+use crate::ui::app_state::{AppState, PendingForkRequest, VoiceRecording};
+use crate::ui::clipboard_history;
 use crate::ui::components::{
-    dialog_content_area, AddRepoDialog, AgentSelector, BaseDirDialog, ChatMessage, CommandPalette,
-    ConfirmationContext, ConfirmationDialog, ConfirmationType, DefaultModelSelection, ErrorDialog,
-    EventDirection, GlobalFooter, HelpDialog, InlinePromptState, InlinePromptType, MessageRole,
-    MissingToolDialog, ModelSelector, ProcessingState, ProjectPicker, PromptAnswer, RawEventsClick,
+    border_focused, dialog_content_area, find_pr_numbers, AddRepoDialog, AgentSelector,
+    BaseDirDialog, ChatMessage, CommandPalette, ConfirmationContext, ConfirmationDialog,
+    ConfirmationType, DefaultModelSelection, DiffView, ErrorDialog, EventDirection, FooterContext,
+    GlobalFooter, HelpDialog, InlinePromptState, InlinePromptType, MessageRole, MissingToolDialog,
+    ModelSelector, ProcessingState, ProjectPicker, PromptAnswer, RawEventsClick, RenameTabDialog,
     SessionHeader, SessionImportPicker, Sidebar, SidebarData, SlashCommand, SlashMenu, TabBar,
-    TabBarHitTarget, ThemePicker, SIDEBAR_HEADER_ROWS,
+    TabBarHitTarget, ThemePicker, TimelineView, SIDEBAR_HEADER_ROWS,
 };
+use crate::ui::duplicate_prompt::{find_duplicate_prompt, normalize_prompt, DuplicateWarning};
 use crate::ui::effect::Effect;
 use crate::ui::events::{
-    AppEvent, ForkWorkspaceCreated, InputMode, RemoveProjectResult, TitleGeneratedResult, ViewMode,
-    WorkspaceArchived, WorkspaceCreated,
+    AppEvent, BootstrapOutcome, ForkWorkspaceCreated, InputMode, RemoveProjectResult,
+    TitleGeneratedResult, ViewMode, WorkspaceArchived, WorkspaceCreated,
 };
-use crate::ui::session::AgentSession;
+use crate::ui::frame_diagnostics::{self, DrawSection};
+use crate::ui::image_annotation;
+use crate::ui::session::{AgentSession, DeferredHistory};
+use crate::ui::session_report::build_session_report;
 use crate::ui::terminal_guard::TerminalGuard;
-use crate::util::ToolAvailability;
+use crate::ui::transcript_export::{self, TranscriptFormat};
+use crate::util::chaos;
+use crate::util::desktop_notify;
+use crate::util::network;
+use crate::util::preview::detect_previews;
+use crate::util::read_aloud;
+use crate::util::startup_profile;
+use crate::util::url_fetch;
+use crate::util::voice::{self, VoiceRecorder};
+use crate::util::{resolve_workspace_path, InstanceInfo, ToolAvailability};
 
 mod app_actions_confirm;
 mod app_actions_confirmation;
@@ -79,9 +112,32 @@ mod app_actions_scroll;
 mod app_actions_sidebar;
 mod app_actions_submit;
 mod app_actions_tabs;
+mod app_bundle_seed;
+mod app_change_guard;
+mod app_chat_memory;
+mod app_clipboard_history;
+mod app_coverage;
+mod app_deferred_history;
+mod app_dep_updates;
 mod app_input;
+mod app_linked_workspaces;
+mod app_message_select;
+mod app_persistence;
+mod app_previews;
+mod app_quit;
 mod app_scroll;
+mod app_security_scan;
 mod app_selection;
+mod app_sidebar_sessions;
+mod app_slots;
+mod app_stalls;
+mod app_subagents;
+mod app_workspace_memory;
+
+use app_coverage::CoverageCommand;
+use app_dep_updates::DepsCommand;
+use app_linked_workspaces::LinkCommand;
+use app_workspace_memory::MemoryCommand;
 
 #[cfg(target_os = "macos")]
 const PROC_PIDTBSDINFO: libc::c_int = 3;
@@ -143,6 +199,14 @@ struct AskUserQuestionWrapper {
 struct ExitPlanModeWrapper {
     plan: String,
 }
+
+/// Results of the optional checks the PR preflight runs before a new PR is
+/// offered
+#[derive(Default)]
+struct PrChecks {
+    security: Option<SecurityScanReport>,
+    change_flags: Option<Result<Vec<ChangeFlag>, String>>,
+}
 // 20s allows slow CLI agents to shut down on congested machines without UI hangs.
 const AGENT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(20);
 // 500ms grace keeps UI responsive while giving SIGTERM a brief chance to exit.
@@ -154,6 +218,16 @@ const SHELL_COMMAND_OUTPUT_LIMIT: usize = 1024 * 1024;
 // Bound process reaping after a timeout.
 const SHELL_COMMAND_REAP_TIMEOUT: Duration = Duration::from_secs(2);
 const PLAN_MODE_INLINE_REMINDER_ENV: &str = "CONDUIT_PLAN_MODE_INLINE_REMINDER";
+// Input prefix that sets the session's base instructions instead of prompting.
+const SYSTEM_PROMPT_COMMAND: &str = "/system";
+const RESPONSE_PRESET_COMMAND: &str = "/style";
+const FETCH_COMMAND: &str = "/fetch";
+const EXPORT_COMMAND: &str = "/export";
+const MEMORY_COMMAND: &str = "/memory";
+const LINK_COMMAND: &str = "/link";
+const UNLINK_COMMAND: &str = "/unlink";
+const DEPS_COMMAND: &str = "/deps";
+const COVERAGE_COMMAND: &str = "/coverage";
 
 /// Main application state
 pub struct App {
@@ -242,28 +316,28 @@ impl App {
         self.core.fork_seed_store_clone()
     }
 
-    /// Get the Claude runner.
-    #[inline]
-    fn claude_runner(&self) -> &Arc<ClaudeCodeRunner> {
-        self.core.claude_runner()
-    }
-
-    /// Get the Codex runner.
-    #[inline]
-    fn codex_runner(&self) -> &Arc<CodexCliRunner> {
-        self.core.codex_runner()
-    }
-
-    /// Get the Gemini runner.
-    #[inline]
-    fn gemini_runner(&self) -> &Arc<GeminiCliRunner> {
-        self.core.gemini_runner()
+    /// Count a queued or sent message's reference to its managed attachments.
+    pub(super) fn retain_attachments(&self, paths: &[PathBuf]) {
+        if paths.is_empty() {
+            return;
+        }
+        if let Some(store) = self.core.attachment_store() {
+            if let Err(e) = store.retain_paths(paths) {
+                tracing::warn!(error = %e, "Failed to retain attachments");
+            }
+        }
     }
 
-    /// Get the OpenCode runner.
-    #[inline]
-    fn opencode_runner(&self) -> &Arc<OpencodeRunner> {
-        self.core.opencode_runner()
+    /// Drop a reference taken by [`Self::retain_attachments`].
+    pub(super) fn release_attachments(&self, paths: &[PathBuf]) {
+        if paths.is_empty() {
+            return;
+        }
+        if let Some(store) = self.core.attachment_store() {
+            if let Err(e) = store.release_paths(paths) {
+                tracing::warn!(error = %e, "Failed to release attachments");
+            }
+        }
     }
 
     /// Get the worktree manager.
@@ -295,12 +369,18 @@ impl App {
     fn refresh_runners(&mut self) {
         self.core.refresh_runners();
         let tools = self.tools().clone();
+        let local_model = self.config().local_model.is_some();
         self.state
             .agent_selector_state
-            .update_available_agents(&tools);
+            .update_available_agents(&tools, local_model);
     }
 }
 
+/// Chat tool ID for the `index`th workspace bootstrap command
+fn bootstrap_tool_id(index: usize) -> String {
+    format!("bootstrap-{index}")
+}
+
 fn send_app_event(
     event_tx: &mpsc::UnboundedSender<AppEvent>,
     event: AppEvent,
@@ -321,6 +401,139 @@ fn send_app_event(
     }
 }
 
+/// Snapshot the worktree before a prompt reaches the agent so the turn can be
+/// rolled back later. Runs ahead of the send so the agent can't race it.
+async fn capture_prompt_snapshot(
+    event_tx: &mpsc::UnboundedSender<AppEvent>,
+    session_id: Uuid,
+    prompt_index: usize,
+    working_dir: PathBuf,
+) {
+    let result = tokio::task::spawn_blocking(move || capture_worktree(&working_dir)).await;
+    match result {
+        Ok(Ok(Some(tree))) => {
+            send_app_event(
+                event_tx,
+                AppEvent::PromptSnapshotTaken {
+                    session_id,
+                    prompt_index,
+                    tree,
+                },
+                "prompt_snapshot_taken",
+            );
+        }
+        Ok(Ok(None)) => {}
+        Ok(Err(err)) => {
+            tracing::warn!(%session_id, error = %err, "Failed to snapshot worktree before prompt");
+        }
+        Err(err) => {
+            tracing::warn!(%session_id, error = %err, "Worktree snapshot task failed");
+        }
+    }
+}
+
+/// Parse `/system <text>` input. Returns `Some(None)` when the instructions should be cleared.
+fn parse_system_prompt_command(input: &str) -> Option<Option<&str>> {
+    let rest = input.trim_start().strip_prefix(SYSTEM_PROMPT_COMMAND)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let text = rest.trim();
+    Some((!text.is_empty()).then_some(text))
+}
+
+/// Parse `<command> [arg]` input, returning the (possibly empty) argument.
+fn parse_argument_command<'a>(input: &'a str, command: &str) -> Option<&'a str> {
+    let rest = input.trim_start().strip_prefix(command)?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(rest.trim())
+}
+
+/// Parse `/style [preset]` input, returning the (possibly empty) argument.
+fn parse_response_preset_command(input: &str) -> Option<&str> {
+    parse_argument_command(input, RESPONSE_PRESET_COMMAND)
+}
+
+/// Parse `/fetch <url>` input, returning the (possibly empty) URL.
+fn parse_fetch_command(input: &str) -> Option<&str> {
+    parse_argument_command(input, FETCH_COMMAND)
+}
+
+/// Parse `/memory [show|edit|save <text>|sync]` input.
+fn parse_memory_command(input: &str) -> Option<Result<MemoryCommand, String>> {
+    parse_argument_command(input, MEMORY_COMMAND).map(MemoryCommand::parse)
+}
+
+/// Parse `/link [workspace]` and `/unlink [workspace]` input.
+fn parse_link_command(input: &str) -> Option<LinkCommand> {
+    parse_argument_command(input, LINK_COMMAND)
+        .map(LinkCommand::parse_link)
+        .or_else(|| parse_argument_command(input, UNLINK_COMMAND).map(LinkCommand::parse_unlink))
+}
+
+/// Parse `/deps [scan|start|status|retry|skip|stop]` input.
+fn parse_deps_command(input: &str) -> Option<Result<DepsCommand, String>> {
+    parse_argument_command(input, DEPS_COMMAND).map(DepsCommand::parse)
+}
+
+/// Parse `/coverage [start|verify|stop]` input.
+fn parse_coverage_command(input: &str) -> Option<Result<CoverageCommand, String>> {
+    parse_argument_command(input, COVERAGE_COMMAND).map(CoverageCommand::parse)
+}
+
+/// Parse `/export [md|json|pdf]` input; a bare `/export` writes Markdown.
+fn parse_export_command(input: &str) -> Option<Result<TranscriptFormat, String>> {
+    let arg = parse_argument_command(input, EXPORT_COMMAND)?;
+    if arg.is_empty() {
+        return Some(Ok(TranscriptFormat::Markdown));
+    }
+    Some(TranscriptFormat::parse(arg).ok_or_else(|| {
+        let options = TranscriptFormat::ALL
+            .iter()
+            .map(|format| format.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("Unknown export format '{arg}'. Options: {options}.")
+    }))
+}
+
+/// Apply a `/style` argument to a session and describe the outcome.
+///
+/// Naming the active preset again switches it off, so `/style concise` toggles.
+fn apply_response_preset_command(session: &mut AgentSession, arg: &str) -> String {
+    let options = ResponsePreset::ALL
+        .iter()
+        .map(|preset| preset.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if arg.is_empty() {
+        return match session.response_preset {
+            Some(preset) => format!(
+                "Response style: {}. Use /style <{options}> to switch or /style off to clear.",
+                preset.as_str()
+            ),
+            None => format!("No response style set. Use /style <{options}>."),
+        };
+    }
+    if arg.eq_ignore_ascii_case("off") {
+        session.response_preset = None;
+        return "Response style cleared.".to_string();
+    }
+    match ResponsePreset::parse(arg) {
+        Some(preset) if session.response_preset == Some(preset) => {
+            session.response_preset = None;
+            format!("Response style {} switched off.", preset.as_str())
+        }
+        Some(preset) => {
+            session.response_preset = Some(preset);
+            format!("Response style set to {}.", preset.as_str())
+        }
+        None => format!("Unknown response style \"{arg}\". Choose one of: {options}, off."),
+    }
+}
+
 impl App {
     // When true, selection drag auto-scrolls as soon as the cursor hits the first/last row.
     // When false, auto-scroll only starts after the cursor leaves the chat area.
@@ -355,22 +568,45 @@ impl App {
             event_rx,
             git_tracker,
         };
+        app.state.queue_collapsed = app.config().ui.queue_collapsed;
 
         // Update agent selector based on available tools
         let tools = app.tools().clone();
+        let local_model = app.config().local_model.is_some();
         app.state
             .agent_selector_state
-            .update_available_agents(&tools);
+            .update_available_agents(&tools, local_model);
 
         // Load sidebar data
-        app.refresh_sidebar_data();
+        startup_profile::phase("sidebar", || app.refresh_sidebar_data());
 
         // Restore session state
-        app.restore_session_state();
+        startup_profile::phase("session restore", || app.restore_session_state());
+        if app.state.tab_manager.is_empty() {
+            app.refresh_recent_sessions();
+        }
+
+        if let Some(error) = app.core.database_error().map(str::to_string) {
+            app.show_error_with_details(
+                "Database Unavailable",
+                "Conduit couldn't open its database, so tabs, history and costs won't be saved this run. Quit and run `conduit db recover` to back it up and rebuild it.",
+                &error,
+            );
+        }
 
         app
     }
 
+    /// Attach as an observer of the process that owns the data directory:
+    /// sessions are shown, but agents never start and tab state isn't saved.
+    pub fn observe(&mut self, owner: InstanceInfo) {
+        self.state.set_timed_footer_message(
+            format!("Observing {}; prompts are disabled", owner.describe()),
+            Duration::from_secs(10),
+        );
+        self.state.observing = Some(owner);
+    }
+
     /// Restore session state from database
     fn restore_session_state(&mut self) {
         tracing::info!("Restoring session state");
@@ -419,8 +655,7 @@ impl App {
 
         // Restore each tab
         for tab in saved_tabs {
-            let required_tool = Self::required_tool(tab.agent_type);
-            if !self.tools().is_available(required_tool) {
+            if let Some(required_tool) = self.missing_tool_for(tab.agent_type) {
                 self.show_missing_tool(
                     required_tool,
                     format!(
@@ -431,177 +666,8 @@ impl App {
                 break;
             }
 
-            let mut session = AgentSession::new(tab.agent_type);
-            session.id = tab.id;
-            session.workspace_id = tab.workspace_id;
-            session.model = tab.model;
-            session.model_invalid = tab.model_invalid;
-            session.pr_number = tab.pr_number.map(|n| n as u32);
-            session.fork_seed_id = tab.fork_seed_id;
-            // Restore AI-generated session title
-            session.title = tab.title.clone();
-            // Restore agent mode (defaults to Build if not set)
-            let parsed_mode = tab
-                .agent_mode
-                .as_deref()
-                .map(AgentMode::parse)
-                .unwrap_or_default();
-            session.agent_mode = Self::clamp_agent_mode(tab.agent_type, parsed_mode);
-
-            // Look up workspace to get working_dir, workspace_name, and project_name
-            if let Some(workspace_id) = tab.workspace_id {
-                if let Some(workspace_dao) = self.workspace_dao() {
-                    if let Ok(Some(workspace)) = workspace_dao.get_by_id(workspace_id) {
-                        session.working_dir = Some(workspace.path);
-                        session.workspace_name = Some(workspace.name.clone());
-
-                        // Look up repository for project name
-                        if let Some(repo_dao) = self.repo_dao() {
-                            if let Ok(Some(repo)) = repo_dao.get_by_id(workspace.repository_id) {
-                                session.project_name = Some(repo.name);
-                            }
-                        }
-                    }
-                }
-            }
-
-            // Set resume session ID if available
-            if let Some(ref session_id_str) = tab.agent_session_id {
-                let session_id = SessionId::from_string(session_id_str.clone());
-                session.resume_session_id = Some(session_id.clone());
-                session.agent_session_id = Some(session_id.clone());
-
-                // Load chat history from agent files
-                match tab.agent_type {
-                    AgentType::Claude => {
-                        if let Ok((msgs, debug_entries, file_path)) =
-                            load_claude_history_with_debug(session_id_str)
-                        {
-                            // Populate debug pane with history load info
-                            Self::populate_debug_from_history(
-                                &mut session.raw_events_view,
-                                &debug_entries,
-                                &file_path,
-                            );
-                            for msg in msgs {
-                                session.chat_view.push(msg);
-                            }
-                        }
-                    }
-                    AgentType::Codex => {
-                        if let Ok((msgs, debug_entries, file_path)) =
-                            load_codex_history_with_debug(session_id_str)
-                        {
-                            // Populate debug pane with history load info
-                            Self::populate_debug_from_history(
-                                &mut session.raw_events_view,
-                                &debug_entries,
-                                &file_path,
-                            );
-                            for msg in msgs {
-                                session.chat_view.push(msg);
-                            }
-                        }
-                    }
-                    AgentType::Gemini => {
-                        session.chat_view.push(
-                            MessageDisplay::System {
-                                content: "Gemini CLI history import isn't supported yet, so previous messages won't be shown.".to_string(),
-                            }
-                            .to_chat_message(),
-                        );
-                    }
-                    AgentType::Opencode => {
-                        if let Ok((msgs, debug_entries, file_path)) =
-                            load_opencode_history_with_debug(session_id_str)
-                        {
-                            Self::populate_debug_from_history(
-                                &mut session.raw_events_view,
-                                &debug_entries,
-                                &file_path,
-                            );
-                            for msg in msgs {
-                                session.chat_view.push(msg);
-                            }
-                        }
-                    }
-                }
-            } else if tab.agent_type == AgentType::Opencode {
-                if let Some(working_dir) = session.working_dir.as_ref() {
-                    if let Ok((session_id_str, msgs, debug_entries, file_path)) =
-                        load_opencode_history_for_dir_with_debug(working_dir)
-                    {
-                        let session_id = SessionId::from_string(session_id_str.clone());
-                        session.resume_session_id = Some(session_id.clone());
-                        session.agent_session_id = Some(session_id);
-
-                        Self::populate_debug_from_history(
-                            &mut session.raw_events_view,
-                            &debug_entries,
-                            &file_path,
-                        );
-                        for msg in msgs {
-                            session.chat_view.push(msg);
-                        }
-                    }
-                }
-            }
-
-            // Restore pending user message if it exists and isn't already in history
-            if let Some(ref pending) = tab.pending_user_message {
-                // Check if last user message in chat matches pending
-                let already_in_history = session
-                    .chat_view
-                    .messages()
-                    .iter()
-                    .rev()
-                    .find(|m| m.role == MessageRole::User)
-                    .map(|m| m.content.as_str() == pending.as_str())
-                    .unwrap_or(false);
-
-                if !already_in_history {
-                    let display = MessageDisplay::User {
-                        content: pending.clone(),
-                    };
-                    session.chat_view.push(display.to_chat_message());
-                    session.pending_user_message = Some(pending.clone());
-                }
-            }
-
-            if !tab.queued_messages.is_empty() {
-                session.queued_messages = tab.queued_messages.clone();
-            }
-
-            session.input_box.set_history(tab.input_history.clone());
-
-            // Derive fork_welcome_shown: if restoring a forked session that has messages,
-            // the welcome message was already shown in the previous session
-            if session.fork_seed_id.is_some() && !session.chat_view.messages().is_empty() {
-                session.fork_welcome_shown = true;
-            }
-
-            session.update_status();
-
-            // Register workspace with git tracker if available
-            let track_info = session.workspace_id.zip(session.working_dir.clone());
-            let sidebar_pr_update = session
-                .pr_number
-                .and_then(|pr_num| Self::apply_pr_number_to_session(&mut session, pr_num));
-
-            self.state.tab_manager.add_session(session);
-
-            if let Some((workspace_id, status)) = sidebar_pr_update {
-                self.state
-                    .sidebar_data
-                    .update_workspace_pr_status(workspace_id, Some(status));
-            }
-
-            // Track workspace after session is added
-            if let Some((workspace_id, working_dir)) = track_info {
-                if let Some(ref tracker) = self.git_tracker {
-                    tracker.track_workspace(workspace_id, working_dir);
-                }
-            }
+            let session = self.session_from_saved_tab(tab);
+            self.add_restored_session(session);
         }
 
         // Restore active tab
@@ -644,10 +710,129 @@ impl App {
         tracing::info!("Session state restoration complete");
     }
 
+    /// Build a tab's session from its saved row; chat history loads later,
+    /// when the tab is first shown.
+    fn session_from_saved_tab(&mut self, tab: SessionTab) -> AgentSession {
+        // History offloaded in a previous run is reloaded from the agent's files
+        self.discard_offloaded_chat_history(tab.id);
+        let mut session = AgentSession::new(tab.agent_type);
+        session.id = tab.id;
+        session
+            .chat_view
+            .set_show_reasoning(self.config().ui.show_reasoning);
+        session
+            .chat_view
+            .set_timestamps(self.config().ui.chat_timestamps);
+        session.workspace_id = tab.workspace_id;
+        session.model = tab.model;
+        session.model_invalid = tab.model_invalid;
+        session.codex_sandbox = tab
+            .codex_sandbox
+            .as_deref()
+            .and_then(CodexSandboxMode::parse);
+        session.codex_approval = tab
+            .codex_approval
+            .as_deref()
+            .and_then(CodexApprovalPolicy::parse);
+        session.system_prompt = tab.system_prompt;
+        session.response_language = tab.response_language;
+        session.response_preset = tab
+            .response_preset
+            .as_deref()
+            .and_then(ResponsePreset::parse);
+        session.cost_center = tab.cost_center;
+        session.total_cost = tab.total_cost_usd;
+        session.pr_number = tab.pr_number.map(|n| n as u32);
+        session.fork_seed_id = tab.fork_seed_id;
+        // Restore AI-generated session title and the user's tab name
+        session.title = tab.title.clone();
+        session.custom_title = tab.custom_title.clone();
+        session.linked_workspaces = self.restore_linked_workspaces(&tab.linked_workspace_ids);
+        // Restore agent mode (defaults to Build if not set)
+        let parsed_mode = tab
+            .agent_mode
+            .as_deref()
+            .map(AgentMode::parse)
+            .unwrap_or_default();
+        session.agent_mode = Self::clamp_agent_mode(tab.agent_type, parsed_mode);
+
+        // Look up workspace to get working_dir, workspace_name, and project_name
+        if let Some(workspace_id) = tab.workspace_id {
+            if let Some(workspace_dao) = self.workspace_dao() {
+                if let Ok(Some(workspace)) = workspace_dao.get_by_id(workspace_id) {
+                    session.working_dir = Some(workspace.path);
+                    session.workspace_name = Some(workspace.name.clone());
+
+                    // Look up repository for project name
+                    if let Some(repo_dao) = self.repo_dao() {
+                        if let Ok(Some(repo)) = repo_dao.get_by_id(workspace.repository_id) {
+                            session.project_name = Some(repo.name);
+                        }
+                    }
+                }
+            }
+        } else {
+            session.working_dir = tab.working_dir.clone();
+        }
+
+        // Set resume session ID if available
+        if let Some(ref session_id_str) = tab.agent_session_id {
+            let session_id = SessionId::from_string(session_id_str.clone());
+            session.resume_session_id = Some(session_id.clone());
+            session.agent_session_id = Some(session_id);
+        }
+        let fork_seed = self.load_fork_seed(tab.fork_seed_id);
+        session.fork_seed_hash = fork_seed.as_ref().map(|seed| seed.seed_prompt_hash.clone());
+        session.pending_seed_path =
+            Self::pending_seed_path(fork_seed.as_ref(), tab.agent_session_id.as_deref());
+
+        // Chat history is read from the agent's files when the tab is first
+        // shown, so restoring many tabs doesn't hold up the first frame
+        session.deferred_history = Some(DeferredHistory {
+            pending_user_message: tab.pending_user_message.clone(),
+        });
+
+        if !tab.queued_messages.is_empty() {
+            session.queued_messages = tab.queued_messages.clone();
+        }
+
+        session.input_box.set_history(tab.input_history.clone());
+
+        session.update_status();
+        session
+    }
+
+    /// Add a restored session as a tab and start tracking its workspace.
+    fn add_restored_session(&mut self, mut session: AgentSession) -> Option<usize> {
+        // Register workspace with git tracker if available
+        let track_info = session.workspace_id.zip(session.working_dir.clone());
+        let sidebar_pr_update = session
+            .pr_number
+            .and_then(|pr_num| Self::apply_pr_number_to_session(&mut session, pr_num));
+
+        let index = self.state.tab_manager.add_session(session);
+
+        if let Some((workspace_id, status)) = sidebar_pr_update {
+            self.state
+                .sidebar_data
+                .update_workspace_pr_status(workspace_id, Some(status));
+        }
+
+        // Track workspace after session is added
+        if let Some((workspace_id, working_dir)) = track_info {
+            if let Some(ref tracker) = self.git_tracker {
+                tracker.track_workspace(workspace_id, working_dir);
+            }
+        }
+
+        index
+    }
+
     /// Refresh sidebar data from database
     fn refresh_sidebar_data(&mut self) {
         // Capture current expansion state before rebuild
         let expanded_repos = self.state.sidebar_data.expanded_repo_ids();
+        let sessions_expanded = self.state.sidebar_data.sessions_expanded();
 
         // Collect all repo/workspace data first to avoid borrow conflicts
         type RepoWorkspaceData = Vec<(Uuid, String, Vec<(Uuid, String, String)>)>;
@@ -684,6 +869,10 @@ impl App {
                 .sidebar_data
                 .add_repository(repo_id, &repo_name, workspace_info);
         }
+        let sessions = self.sidebar_session_entries(&self.state.sidebar_data.workspace_names());
+        self.state
+            .sidebar_data
+            .set_sessions(sessions, sessions_expanded);
 
         // Restore expansion state
         for repo_id in expanded_repos {
@@ -828,8 +1017,14 @@ impl App {
                 tab.model_invalid = session.model_invalid;
                 // Preserve agent mode for session restoration
                 tab.agent_mode = Some(session.agent_mode.as_str().to_string());
-                // Preserve pending user message for interrupted sessions
-                tab.pending_user_message = session.pending_user_message.clone();
+                // Preserve pending user message for interrupted sessions, including
+                // tabs whose history was never loaded this run
+                tab.pending_user_message = session.pending_user_message.clone().or_else(|| {
+                    session
+                        .deferred_history
+                        .as_ref()
+                        .and_then(|deferred| deferred.pending_user_message.clone())
+                });
                 // Preserve queued messages for interrupted sessions
                 tab.queued_messages = session.queued_messages.clone();
                 // Preserve input history for arrow-up restoration
@@ -838,6 +1033,24 @@ impl App {
                 // Preserve AI-generated session title
                 tab.title = session.title.clone();
                 tab.title_generated = false;
+                tab.custom_title = session.custom_title.clone();
+                tab.linked_workspace_ids = session
+                    .linked_workspaces
+                    .iter()
+                    .map(|workspace| workspace.id)
+                    .collect();
+                // Preserve Codex sandbox/approval overrides
+                tab.codex_sandbox = session.codex_sandbox.map(|m| m.as_str().to_string());
+                tab.codex_approval = session.codex_approval.map(|p| p.as_str().to_string());
+                tab.system_prompt = session.system_prompt.clone();
+                tab.total_cost_usd = session.total_cost;
+                tab.response_language = session.response_language.clone();
+                tab.response_preset = session.response_preset.map(|p| p.as_str().to_string());
+                tab.cost_center = session.cost_center.clone();
+                // Workspace tabs get their directory from the workspace
+                if session.workspace_id.is_none() {
+                    tab.working_dir = session.working_dir.clone();
+                }
                 tab
             })
             .collect();
@@ -918,6 +1131,7 @@ impl App {
     /// Run the application main loop
     pub async fn run(&mut self) -> anyhow::Result<()> {
         self.spawn_shutdown_listeners();
+        self.core.usage_stats().record("launch.tui");
 
         // Setup terminal
         enable_raw_mode()?;
@@ -928,7 +1142,13 @@ impl App {
         // Create terminal guard AFTER enabling features - Drop will clean up on any exit path
         let mut guard = TerminalGuard::new(keyboard_enhancement_enabled);
 
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(
+            stdout,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableFocusChange,
+            EnableBracketedPaste
+        )?;
 
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
@@ -941,6 +1161,9 @@ impl App {
 
         // Best-effort persistence on any exit path.
         self.persist_session_state_on_exit();
+        self.flush_persistence();
+        // Agents don't outlive the app, however it was asked to quit
+        self.terminate_agents_on_exit();
 
         // Explicit cleanup with error handling (prevents double-cleanup in Drop)
         terminal.show_cursor()?;
@@ -982,6 +1205,9 @@ impl App {
     }
 
     fn persist_session_state_on_exit(&self) {
+        if self.state.observing.is_some() {
+            return;
+        }
         let snapshot = self.snapshot_session_state();
         Self::persist_session_state(
             snapshot,
@@ -1016,7 +1242,11 @@ impl App {
                 let draw_end = Instant::now();
                 self.state.metrics.draw_time = draw_end.duration_since(draw_start);
                 self.state.metrics.on_draw_end(draw_end);
+                self.state
+                    .frame_diagnostics
+                    .end_frame(self.state.metrics.draw_time);
                 self.state.need_redraw = false;
+                startup_profile::first_frame_drawn();
             }
 
             // Use shorter interval when animations are active, longer when idle
@@ -1133,6 +1363,7 @@ impl App {
                         }
                     }
                     self.state.metrics.event_time = event_start.elapsed();
+                    self.state.frame_diagnostics.record_event(self.state.metrics.event_time);
                 }
 
                 // Sleep until next tick time
@@ -1145,6 +1376,7 @@ impl App {
                     let event_start = Instant::now();
                     self.dispatch_event(event, terminal, guard).await?;
                     self.state.metrics.event_time = event_start.elapsed();
+                    self.state.frame_diagnostics.record_event(self.state.metrics.event_time);
                 }
             }
 
@@ -1179,7 +1411,32 @@ impl App {
                 if self.handle_tick() {
                     self.state.need_redraw = true;
                 }
-                Vec::new()
+                let mut effects = match self.dispatch_slot_waiters() {
+                    Ok(effects) => {
+                        if !effects.is_empty() {
+                            self.state.need_redraw = true;
+                        }
+                        effects
+                    }
+                    Err(err) => {
+                        tracing::warn!(error = %err, "Failed to start prompts waiting for a slot");
+                        Vec::new()
+                    }
+                };
+                // Imported sessions send their transcript once the tab is shown
+                self.send_pending_seed(&mut effects);
+                self.quit_if_idle(&mut effects);
+                // Recordings that hit their time limit (or lost the device) end on their own
+                if self
+                    .state
+                    .voice_recording
+                    .as_ref()
+                    .is_some_and(|recording| recording.recorder.is_finished())
+                {
+                    self.finish_voice_recording(&mut effects);
+                    self.state.need_redraw = true;
+                }
+                effects
             }
             _ => self.handle_app_event(event).await?,
         };
@@ -1193,6 +1450,12 @@ impl App {
         let mut state_changed = false;
         self.state.tick_count += 1;
 
+        let offline = network::is_offline();
+        if offline != self.state.offline {
+            self.state.offline = offline;
+            state_changed = true;
+        }
+
         // Tick footer Knight Rider spinner every 2 frames (~40ms at 50 FPS, matches opencode)
         if self.state.tick_count.is_multiple_of(2) {
             self.state.tick_footer_spinner();
@@ -1203,9 +1466,10 @@ impl App {
         let splash_visible = self.state.tab_manager.is_empty();
         if self.state.tick_count.is_multiple_of(3) {
             if splash_visible {
-                // Reset animation when transitioning back to splash screen
+                // Reset animation and recent sessions when transitioning back to splash screen
                 if !self.state.was_splash_visible {
                     self.state.logo_shine.reset();
+                    self.refresh_recent_sessions();
                 }
                 self.state.logo_shine.tick();
             }
@@ -1220,11 +1484,10 @@ impl App {
             if now.duration_since(last) > timeout {
                 self.state.last_ctrl_c_press = None;
                 // Clear associated message
-                if matches!(
-                    self.state.footer_message.as_deref(),
-                    Some("Press Ctrl+C again to interrupt and quit")
-                        | Some("Press Ctrl+C again to quit")
-                ) {
+                if self.state.footer_message.as_deref().is_some_and(|message| {
+                    message.ends_with(" again to interrupt and quit")
+                        || message.ends_with(" again to quit")
+                }) {
                     self.state.footer_message = None;
                     state_changed = true;
                 }
@@ -1264,11 +1527,26 @@ impl App {
             }
         }
 
+        if self.load_active_tab_history() {
+            state_changed = true;
+        }
+
         // Tick other animations every 6 frames (~100ms)
         if !self.state.tick_count.is_multiple_of(6) {
             return state_changed;
         }
 
+        self.prune_previews_if_due();
+        if self.flag_stalled_turns() {
+            state_changed = true;
+        }
+        if self.apply_chat_memory_limits_if_due() {
+            state_changed = true;
+        }
+        if self.load_offloaded_chat_history() {
+            state_changed = true;
+        }
+
         // Advance spinner frame for PR processing indicator
         self.state.spinner_frame = self.state.spinner_frame.wrapping_add(1);
 
@@ -1294,9 +1572,17 @@ impl App {
 
         if let Some(session) = self.state.tab_manager.active_session_mut() {
             session_id = Some(session.id);
+            // Skips the rest of a running workspace bootstrap
+            if let Some(cancel) = session.bootstrap_cancel.take() {
+                cancel.cancel();
+                session.update_status();
+            }
             pid = session.agent_pid.take();
             pid_start_time = session.agent_pid_start_time.take();
             session.agent_input_tx = None;
+            if let Some(cancel) = session.agent_cancel.take() {
+                cancel.cancel();
+            }
             // Clear any active inline prompt and pending permissions since the agent is gone
             session.inline_prompt = None;
             session.pending_tool_permissions.clear();
@@ -1591,6 +1877,9 @@ impl App {
                 }
                 pid = session.agent_pid.take();
                 pid_start_time = session.agent_pid_start_time.take();
+                if let Some(cancel) = session.agent_cancel.take() {
+                    cancel.cancel();
+                }
             }
         }
 
@@ -1616,6 +1905,13 @@ impl App {
             .map(|s| s.is_processing)
             .unwrap_or(false);
 
+        let key = GlobalFooter::key_label_for(
+            &self.config().keybindings,
+            KeyContext::from_input_mode(self.state.input_mode, self.state.view_mode),
+            &Action::ClearOrQuit,
+        )
+        .unwrap_or_else(|| ":clear_or_quit".to_string());
+
         tracing::debug!(
             "handle_ctrl_c_press: is_double={}, is_processing={}",
             is_double,
@@ -1632,7 +1928,8 @@ impl App {
             } else {
                 // First press: show warning
                 tracing::debug!("Ctrl+C: first press while processing, showing warning");
-                self.state.footer_message = Some("Press Ctrl+C again to interrupt and quit".into());
+                self.state.footer_message =
+                    Some(format!("Press {} again to interrupt and quit", key));
                 self.state.last_ctrl_c_press = Some(now);
             }
         } else if is_double {
@@ -1651,7 +1948,7 @@ impl App {
                 }
                 session.input_box.clear();
             }
-            self.state.footer_message = Some("Press Ctrl+C again to quit".into());
+            self.state.footer_message = Some(format!("Press {} again to quit", key));
             self.state.last_ctrl_c_press = Some(now);
         }
         tracing::debug!("footer_message after: {:?}", self.state.footer_message);
@@ -1713,6 +2010,11 @@ impl App {
         guard: &mut TerminalGuard,
     ) -> anyhow::Result<Vec<Effect>> {
         let mut effects = Vec::new();
+        if self.core.usage_stats().is_enabled() {
+            self.core
+                .usage_stats()
+                .record(&format!("action.{}", action.usage_name()));
+        }
         match action {
             // ========== Global Actions ==========
             Action::ToggleSidebar
@@ -1723,23 +2025,64 @@ impl App {
                 self.handle_sidebar_action(action, &mut effects);
             }
             Action::Quit
+            | Action::ClearOrQuit
             | Action::NewProject
             | Action::NewWorkspaceUnderCursor
             | Action::ForkSession
             | Action::InterruptAgent
             | Action::ToggleViewMode
+            | Action::ToggleTimeline
+            | Action::ToggleDiffView
             | Action::ShowModelSelector
             | Action::ShowThemePicker
             | Action::OpenSessionImport
             | Action::ImportSession
             | Action::CycleImportFilter
             | Action::ToggleMetrics
+            | Action::ToggleFrameDiagnostics
+            | Action::ToggleZenMode
+            | Action::ExportTranscriptPdf
+            | Action::ExportTranscriptMarkdown
+            | Action::ExportTranscriptJson
             | Action::ToggleAgentMode
+            | Action::CycleCodexSandbox
+            | Action::CycleCodexApproval
+            | Action::ToggleEditReview
             | Action::DumpDebugState
+            | Action::ShowDiagnostics
+            | Action::RestartAgent
+            | Action::NudgeAgent
+            | Action::AnnotateImage
+            | Action::ToggleVoiceInput
+            | Action::ToggleReadAloud
+            | Action::ToggleResponseLanguage
+            | Action::EditPastPrompt
+            | Action::RollbackToPastPrompt
             | Action::CopyWorkspacePath
             | Action::CopySelection => {
                 self.handle_global_action(action, &mut effects);
             }
+            Action::SelectMessages => {
+                self.start_message_selection();
+            }
+            Action::CopySelectedMessage => {
+                if self.state.input_mode == InputMode::SelectingMessage {
+                    self.copy_selected_message(&mut effects);
+                }
+            }
+            Action::ShowClipboardHistory => {
+                self.show_clipboard_history();
+            }
+            Action::RecopyClipboardEntry => {
+                if self.state.input_mode == InputMode::ClipboardHistory {
+                    self.recopy_clipboard_entry(&mut effects);
+                }
+            }
+            Action::PasteClipboardEntry => {
+                if self.state.input_mode == InputMode::ClipboardHistory {
+                    self.paste_clipboard_entry();
+                }
+            }
             Action::OpenPr => {
                 if let Some(effect) = self.handle_pr_action() {
                     effects.push(effect);
@@ -1756,7 +2099,11 @@ impl App {
             }
 
             // ========== Tab Management ==========
-            Action::CloseTab | Action::NextTab | Action::PrevTab | Action::SwitchToTab(_) => {
+            Action::CloseTab
+            | Action::RenameTab
+            | Action::NextTab
+            | Action::PrevTab
+            | Action::SwitchToTab(_) => {
                 self.handle_tab_action(action, &mut effects);
             }
 
@@ -1773,9 +2120,31 @@ impl App {
             | Action::ScrollToTop
             | Action::ScrollToBottom
             | Action::ScrollPrevUserMessage
-            | Action::ScrollNextUserMessage => {
+            | Action::ScrollNextUserMessage
+            | Action::JumpToDuplicateAnswer
+            | Action::ScrollLeft(_)
+            | Action::ScrollRight(_)
+            | Action::ToggleCodeWrap
+            | Action::ToggleReasoning
+            | Action::ToggleToolOutput
+            | Action::ToggleExpandTools => {
                 self.handle_scroll_action(action);
             }
+            Action::ToggleTimestamps => {
+                // One setting for every tab, so new tabs pick it up too
+                let mode = self.config().ui.chat_timestamps.next();
+                self.config_mut().ui.chat_timestamps = mode;
+                for session in self.state.tab_manager.sessions_mut() {
+                    session.chat_view.set_timestamps(mode);
+                }
+                let message = match mode {
+                    ChatTimestamps::Off => "Timestamps hidden",
+                    ChatTimestamps::Absolute => "Timestamps: time of day",
+                    ChatTimestamps::Relative => "Timestamps: time ago",
+                };
+                self.state
+                    .set_timed_footer_message(message.to_string(), Duration::from_secs(3));
+            }
 
             // ========== Input Box Editing ==========
             Action::InsertNewline
@@ -1785,6 +2154,9 @@ impl App {
             | Action::DeleteWordForward
             | Action::DeleteToStart
             | Action::DeleteToEnd
+            | Action::Undo
+            | Action::Redo
+            | Action::RemoveAttachment
             | Action::MoveCursorLeft
             | Action::MoveCursorRight
             | Action::MoveCursorStart
@@ -1805,9 +2177,17 @@ impl App {
             | Action::QueueMoveUp
             | Action::QueueMoveDown
             | Action::QueueEdit
-            | Action::QueueDelete => {
+            | Action::QueueDelete
+            | Action::ToggleQueuePanel
+            | Action::ExpandQueue => {
                 self.handle_queue_action(action);
             }
+            Action::ApplyFailureRemedy => match self.apply_failure_remedy(terminal, guard) {
+                Ok(mut remedy_effects) => effects.append(&mut remedy_effects),
+                Err(err) => {
+                    tracing::warn!(error = %err, "Failed to apply failure remedy");
+                }
+            },
             Action::EditPromptExternal => {
                 if let Err(err) = self.edit_prompt_external(terminal, guard) {
                     tracing::warn!(error = %err, "External editor failed");
@@ -1845,20 +2225,75 @@ impl App {
                             SlashCommand::NewSession => {
                                 self.start_new_session_in_place();
                             }
-                        }
-                    }
-                } else if self.state.input_mode == InputMode::CommandPalette {
-                    if let Some(entry) = self.state.command_palette_state.selected_entry() {
-                        let action = entry.action.clone();
-                        self.state.command_palette_state.hide();
-                        self.state.input_mode = InputMode::Normal;
-                        // Execute the selected action (avoid recursion if it's Confirm)
-                        if !matches!(action, Action::Confirm | Action::OpenCommandPalette) {
-                            effects.extend(
-                                Box::pin(self.execute_action(action, terminal, guard)).await?,
-                            );
-                        }
-                    }
+                            SlashCommand::SystemPrompt => {
+                                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                                    let current = session.system_prompt.clone().unwrap_or_default();
+                                    session.input_box.clear();
+                                    session
+                                        .input_box
+                                        .insert_str(&format!("{SYSTEM_PROMPT_COMMAND} {current}"));
+                                }
+                            }
+                            SlashCommand::ResponseStyle => {
+                                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                                    session.input_box.clear();
+                                    session
+                                        .input_box
+                                        .insert_str(&format!("{RESPONSE_PRESET_COMMAND} "));
+                                }
+                            }
+                            SlashCommand::Fetch => {
+                                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                                    session.input_box.clear();
+                                    session.input_box.insert_str(&format!("{FETCH_COMMAND} "));
+                                }
+                            }
+                            SlashCommand::Export => {
+                                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                                    session.input_box.clear();
+                                    session.input_box.insert_str(&format!("{EXPORT_COMMAND} "));
+                                }
+                            }
+                            SlashCommand::Memory => {
+                                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                                    session.input_box.clear();
+                                    session.input_box.insert_str(&format!("{MEMORY_COMMAND} "));
+                                }
+                            }
+                            SlashCommand::Link => {
+                                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                                    session.input_box.clear();
+                                    session.input_box.insert_str(&format!("{LINK_COMMAND} "));
+                                }
+                            }
+                            SlashCommand::Deps => {
+                                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                                    session.input_box.clear();
+                                    session.input_box.insert_str(&format!("{DEPS_COMMAND} "));
+                                }
+                            }
+                            SlashCommand::Coverage => {
+                                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                                    session.input_box.clear();
+                                    session
+                                        .input_box
+                                        .insert_str(&format!("{COVERAGE_COMMAND} "));
+                                }
+                            }
+                        }
+                    }
+                } else if self.state.input_mode == InputMode::CommandPalette {
+                    if let Some(entry) = self.state.command_palette_state.selected_entry() {
+                        let action = entry.action.clone();
+                        self.state.command_palette_state.hide();
+                        self.state.input_mode = InputMode::Normal;
+                        // Execute the selected action (avoid recursion if it's Confirm)
+                        if !matches!(action, Action::Confirm | Action::OpenCommandPalette) {
+                            effects.extend(
+                                Box::pin(self.execute_action(action, terminal, guard)).await?,
+                            );
+                        }
+                    }
                 } else {
                     self.handle_confirm_action(&mut effects)?;
                 }
@@ -1902,6 +2337,10 @@ impl App {
             | Action::RawEventsSelectPrev
             | Action::RawEventsToggleExpand
             | Action::RawEventsCollapse
+            | Action::RawEventsSearch
+            | Action::RawEventsSearchNext
+            | Action::RawEventsSearchPrev
+            | Action::RawEventsToggleFollow
             | Action::EventDetailToggle
             | Action::EventDetailScrollUp
             | Action::EventDetailScrollDown
@@ -1909,7 +2348,10 @@ impl App {
             | Action::EventDetailPageDown
             | Action::EventDetailScrollToTop
             | Action::EventDetailScrollToBottom
-            | Action::EventDetailCopy => {
+            | Action::EventDetailCopy
+            | Action::EventDetailFold
+            | Action::EventDetailUnfold
+            | Action::EventDetailFilter => {
                 self.handle_raw_events_action(action, &mut effects);
             }
 
@@ -1961,6 +2403,9 @@ impl App {
         for effect in effects {
             match effect {
                 Effect::SaveSessionState => {
+                    if self.state.observing.is_some() {
+                        continue;
+                    }
                     tracing::debug!("SaveSessionState effect triggered");
                     let snapshot = self.snapshot_session_state();
                     let session_tab_dao = self.session_tab_dao_clone();
@@ -1977,17 +2422,22 @@ impl App {
                     session_id,
                     agent_type,
                     config,
+                    snapshot_prompt,
                 } => {
-                    let runner: Arc<dyn AgentRunner> = match agent_type {
-                        AgentType::Claude => self.claude_runner().clone(),
-                        AgentType::Codex => self.codex_runner().clone(),
-                        AgentType::Gemini => self.gemini_runner().clone(),
-                        AgentType::Opencode => self.opencode_runner().clone(),
-                    };
+                    let runner = self.core.runner_for(agent_type);
 
                     let event_tx = self.event_tx.clone();
 
                     tokio::spawn(async move {
+                        if let Some(prompt_index) = snapshot_prompt {
+                            capture_prompt_snapshot(
+                                &event_tx,
+                                session_id,
+                                prompt_index,
+                                config.working_dir.clone(),
+                            )
+                            .await;
+                        }
                         match runner.start(config).await {
                             Ok(mut handle) => {
                                 // Send PID (and input channel when available) to main app for interrupt support
@@ -1999,11 +2449,54 @@ impl App {
                                         session_id,
                                         pid,
                                         input_tx,
+                                        cancel: handle.cancel.clone(),
                                     },
                                     "agent_started",
                                 );
 
                                 while let Some(event) = handle.events.recv().await {
+                                    if let Some(delay) = chaos::delay() {
+                                        tokio::time::sleep(delay).await;
+                                    }
+                                    if let Some(error) = chaos::agent_error() {
+                                        let crashed = matches!(
+                                            &error,
+                                            AgentEvent::Error(err) if err.is_fatal
+                                        );
+                                        send_app_event(
+                                            &event_tx,
+                                            AppEvent::Agent {
+                                                session_id,
+                                                event: error,
+                                            },
+                                            "chaos_agent_error",
+                                        );
+                                        if crashed {
+                                            // Stop the agent as if its process had died
+                                            match tokio::time::timeout(
+                                                AGENT_SHUTDOWN_TIMEOUT,
+                                                runner.kill(&handle),
+                                            )
+                                            .await
+                                            {
+                                                Ok(Ok(())) => {}
+                                                Ok(Err(kill_err)) => {
+                                                    tracing::warn!(
+                                                        session_id = %session_id,
+                                                        error = %kill_err,
+                                                        "Failed to kill agent for injected crash"
+                                                    );
+                                                }
+                                                Err(_) => {
+                                                    tracing::warn!(
+                                                        session_id = %session_id,
+                                                        "Timed out killing agent for injected crash"
+                                                    );
+                                                }
+                                            }
+                                            break;
+                                        }
+                                    }
                                     if !send_app_event(
                                         &event_tx,
                                         AppEvent::Agent { session_id, event },
@@ -2093,16 +2586,44 @@ impl App {
                 Effect::PrPreflight {
                     tab_index,
                     working_dir,
+                    linked_dirs,
+                    security_scan,
+                    change_guard,
                 } => {
                     let event_tx = self.event_tx.clone();
                     tokio::task::spawn_blocking(move || {
                         let result = PrManager::preflight_check(&working_dir);
+                        let linked = linked_dirs
+                            .into_iter()
+                            .map(|(name, working_dir)| LinkedPrPreflight {
+                                preflight: PrManager::preflight_check(&working_dir),
+                                name,
+                                working_dir,
+                            })
+                            .collect();
+                        // Only scan when the preflight will offer a new PR
+                        let offers_new_pr = result.gh_installed
+                            && result.gh_authenticated
+                            && !result.on_main_branch
+                            && !result.existing_pr.as_ref().is_some_and(|pr| pr.exists);
+                        let security = if offers_new_pr {
+                            security_scan::scan(&working_dir, &result.target_branch, &security_scan)
+                        } else {
+                            None
+                        };
+                        let change_flags = (offers_new_pr && change_guard.enabled).then(|| {
+                            change_guard::check(&working_dir, &result.target_branch, &change_guard)
+                                .map_err(|err| err.to_string())
+                        });
                         send_app_event(
                             &event_tx,
                             AppEvent::PrPreflightCompleted {
                                 tab_index,
                                 working_dir,
                                 result,
+                                linked,
+                                security,
+                                change_flags,
                             },
                             "pr_preflight_completed",
                         );
@@ -2120,6 +2641,299 @@ impl App {
                         );
                     });
                 }
+                Effect::TranscribeVoice {
+                    session_id,
+                    recording,
+                    config,
+                } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::spawn(async move {
+                        let result = match recording.to_whisper_wav() {
+                            Ok(wav) => voice::transcribe(&config, wav).await,
+                            Err(err) => Err(err),
+                        }
+                        .map_err(|err| {
+                            tracing::warn!(%session_id, error = %err, "Voice transcription failed");
+                            err.to_string()
+                        });
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::VoiceTranscribed { session_id, result },
+                            "voice_transcribed",
+                        );
+                    });
+                }
+                Effect::ReadAloud {
+                    session_id,
+                    text,
+                    config,
+                } => {
+                    self.stop_read_aloud();
+                    self.state.read_aloud_task = Some(tokio::spawn(async move {
+                        if let Err(err) = read_aloud::speak(&config, &text).await {
+                            tracing::warn!(%session_id, error = %err, "Read-aloud failed");
+                        }
+                    }));
+                }
+                Effect::DesktopNotification {
+                    session_id,
+                    summary,
+                    body,
+                } => {
+                    tokio::task::spawn_blocking(move || {
+                        if let Err(err) = desktop_notify::show(&summary, &body) {
+                            tracing::warn!(%session_id, error = %err, "Desktop notification failed");
+                        }
+                    });
+                }
+                Effect::FetchUrl {
+                    session_id,
+                    url,
+                    config,
+                } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::spawn(async move {
+                        let result = url_fetch::fetch_page(&url, &config).await.map_err(|err| {
+                            tracing::warn!(%session_id, %url, error = %err, "Fetching page failed");
+                            err.to_string()
+                        });
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::UrlFetched {
+                                session_id,
+                                url,
+                                result,
+                            },
+                            "url_fetched",
+                        );
+                    });
+                }
+                Effect::RunPreSubmitHooks {
+                    hooks,
+                    context,
+                    prompt,
+                    images,
+                    image_placeholders,
+                } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::spawn(async move {
+                        let result = hooks.run_pre_submit(&context, prompt.clone()).await;
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::PreSubmitHooksFinished {
+                                session_id: context.session_id,
+                                result,
+                                original_prompt: prompt,
+                                images,
+                                image_placeholders,
+                            },
+                            "pre_submit_hooks_finished",
+                        );
+                    });
+                }
+                Effect::RunPostTurnHooks {
+                    hooks,
+                    context,
+                    turn,
+                } => {
+                    tokio::spawn(async move {
+                        hooks.run_post_turn(&context, &turn).await;
+                    });
+                }
+                Effect::RetryFailedTurn { session_id, delay } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::spawn(async move {
+                        tokio::time::sleep(delay).await;
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::FailedTurnRetryDue { session_id },
+                            "failed_turn_retry_due",
+                        );
+                    });
+                }
+                Effect::RollbackWorkspace {
+                    session_id,
+                    prompt_index,
+                    working_dir,
+                    tree,
+                } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let result =
+                            restore_worktree(&working_dir, &tree).map_err(|err| err.to_string());
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::WorkspaceRolledBack {
+                                session_id,
+                                prompt_index,
+                                result,
+                            },
+                            "workspace_rolled_back",
+                        );
+                    });
+                }
+                Effect::RunBootstrap {
+                    session_id,
+                    working_dir,
+                    target,
+                    commands,
+                    cancel,
+                } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::spawn(async move {
+                        let result =
+                            run_bootstrap(&commands, &working_dir, &target, &cancel, |progress| {
+                                send_app_event(
+                                    &event_tx,
+                                    AppEvent::BootstrapProgress {
+                                        session_id,
+                                        progress,
+                                    },
+                                    "bootstrap_progress",
+                                );
+                            })
+                            .await;
+                        let outcome = match result {
+                            Ok(()) => BootstrapOutcome::Succeeded,
+                            Err(BootstrapError::Cancelled) => BootstrapOutcome::Skipped,
+                            Err(err) => {
+                                tracing::warn!(
+                                    %session_id,
+                                    working_dir = %working_dir.display(),
+                                    error = %err,
+                                    "Workspace bootstrap failed"
+                                );
+                                BootstrapOutcome::Failed(err.to_string())
+                            }
+                        };
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::BootstrapFinished {
+                                session_id,
+                                outcome,
+                            },
+                            "bootstrap_finished",
+                        );
+                    });
+                }
+                Effect::MeasureCoverage {
+                    session_id,
+                    working_dir,
+                    tool,
+                    cancel,
+                } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::spawn(async move {
+                        let result = coverage::measure(&working_dir, tool, &cancel)
+                            .await
+                            .map_err(|err| {
+                                tracing::warn!(
+                                    %session_id,
+                                    working_dir = %working_dir.display(),
+                                    error = %err,
+                                    "Coverage measurement failed"
+                                );
+                                err.to_string()
+                            });
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::CoverageMeasured { session_id, result },
+                            "coverage_measured",
+                        );
+                    });
+                }
+                Effect::ScanDependencies {
+                    session_id,
+                    working_dir,
+                } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let result = dep_update::scan(&working_dir).map_err(|err| {
+                            tracing::warn!(
+                                %session_id,
+                                working_dir = %working_dir.display(),
+                                error = %err,
+                                "Dependency scan failed"
+                            );
+                            err.to_string()
+                        });
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::DependenciesScanned { session_id, result },
+                            "dependencies_scanned",
+                        );
+                    });
+                }
+                Effect::RunDependencyVerification {
+                    session_id,
+                    working_dir,
+                    target,
+                    commands,
+                    cancel,
+                } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::spawn(async move {
+                        let result = run_bootstrap(&commands, &working_dir, &target, &cancel, |progress| {
+                            send_app_event(
+                                &event_tx,
+                                AppEvent::DependencyVerifyProgress {
+                                    session_id,
+                                    progress,
+                                },
+                                "dependency_verify_progress",
+                            );
+                        })
+                        .await
+                        .map_err(|err| {
+                            tracing::info!(%session_id, error = %err, "Dependency update verification failed");
+                            err.to_string()
+                        });
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::DependencyVerifyFinished { session_id, result },
+                            "dependency_verify_finished",
+                        );
+                    });
+                }
+                Effect::ForwardPreview {
+                    workspace_id,
+                    port,
+                    target,
+                    working_dir,
+                } => {
+                    let registry = self.core.previews().clone();
+                    let config = self.config().preview.clone();
+                    let event_tx = self.event_tx.clone();
+                    tokio::spawn(async move {
+                        match forward_preview(
+                            &registry,
+                            &config,
+                            &target,
+                            workspace_id,
+                            &working_dir,
+                            port,
+                        )
+                        .await
+                        {
+                            Ok(_) => send_app_event(
+                                &event_tx,
+                                AppEvent::PreviewsChanged {
+                                    workspace_id,
+                                    previews: registry.list(workspace_id),
+                                },
+                                "preview_forwarded",
+                            ),
+                            Err(err) => {
+                                tracing::warn!(
+                                    %workspace_id,
+                                    port,
+                                    error = %err,
+                                    "Failed to forward dev server preview"
+                                );
+                            }
+                        }
+                    });
+                }
                 Effect::DumpDebugState => {
                     let result = self.dump_debug_state();
                     send_app_event(
@@ -2128,6 +2942,28 @@ impl App {
                         "debug_dumped",
                     );
                 }
+                Effect::ExportTranscript {
+                    format,
+                    title,
+                    file_stem,
+                    messages,
+                } => {
+                    let event_tx = self.event_tx.clone();
+                    tokio::task::spawn_blocking(move || {
+                        let result = transcript_export::export_transcript_as(
+                            format,
+                            &title,
+                            &messages,
+                            &crate::util::exports_dir(),
+                            &file_stem,
+                        );
+                        send_app_event(
+                            &event_tx,
+                            AppEvent::TranscriptExported { result },
+                            "transcript_exported",
+                        );
+                    });
+                }
                 Effect::RunShellCommand {
                     session_id,
                     message_index,
@@ -2370,6 +3206,7 @@ impl App {
                             Ok(WorkspaceCreated {
                                 repo_id,
                                 workspace_id,
+                                bootstrap_commands: bootstrap_commands(&config, &repo),
                             })
                         })();
 
@@ -2816,16 +3653,15 @@ impl App {
                         );
                     });
                 }
-                Effect::CopyToClipboard(text) => {
-                    use arboard::Clipboard;
-                    match Clipboard::new() {
-                        Ok(mut clipboard) => {
-                            if let Err(e) = clipboard.set_text(text) {
-                                tracing::debug!(error = %e, "Failed to copy text to clipboard");
-                            }
-                        }
+                Effect::CopyToClipboard { text, source } => {
+                    match crate::ui::clipboard_copy::copy_text(&text) {
+                        Ok(_) => self.state.clipboard_history.push(text, source),
                         Err(e) => {
-                            tracing::debug!(error = %e, "Failed to initialize clipboard");
+                            tracing::warn!(error = %e, source, "Failed to copy text to clipboard");
+                            self.state.set_timed_footer_message(
+                                format!("Copy failed: {e}"),
+                                Duration::from_secs(5),
+                            );
                         }
                     }
                 }
@@ -2862,12 +3698,8 @@ impl App {
                         .unwrap_or_else(|| self.config().working_dir.clone());
 
                     // Load the session history into a new tab
-                    self.create_imported_session_tab(
-                        agent_type,
-                        session.file_path.clone(),
-                        working_dir,
-                    )
-                    .await?;
+                    self.create_imported_session_tab(agent_type, session.resume_id(), working_dir)
+                        .await?;
                 }
                 Effect::GenerateTitleAndBranch {
                     session_id,
@@ -2933,6 +3765,7 @@ impl App {
                     | InputMode::ShowingHelp
                     | InputMode::AddingRepository
                     | InputMode::SettingBaseDir
+                    | InputMode::RenamingTab
                     | InputMode::PickingProject
                     | InputMode::ShowingError
                     | InputMode::SelectingAgent
@@ -2942,6 +3775,7 @@ impl App {
                     | InputMode::SlashMenu
                     | InputMode::SelectingTheme
                     | InputMode::SelectingModel
+                    | InputMode::ClipboardHistory
             )
     }
 
@@ -3104,44 +3938,45 @@ impl App {
                     return None;
                 }
 
-                let mut expanded_path = match path {
-                    "~" => dirs::home_dir().expect("checked above"),
-                    _ => {
-                        if let Some(rest) =
-                            path.strip_prefix("~/").or_else(|| path.strip_prefix("~\\"))
-                        {
-                            dirs::home_dir()
-                                .map(|home| home.join(rest))
-                                .expect("checked above")
-                        } else {
-                            std::path::PathBuf::from(path)
-                        }
-                    }
-                };
-
                 // Resolve relative paths against the active workspace (fallback to config working dir)
-                if expanded_path.is_relative() {
-                    let base_dir = self
-                        .state
-                        .tab_manager
-                        .active_session()
-                        .and_then(|s| s.working_dir.clone())
-                        .unwrap_or_else(|| self.config().working_dir.clone());
-                    expanded_path = base_dir.join(expanded_path);
-                }
+                let base_dir = self
+                    .state
+                    .tab_manager
+                    .active_session()
+                    .and_then(|s| s.working_dir.clone())
+                    .unwrap_or_else(|| self.config().working_dir.clone());
+                let expanded_path = resolve_workspace_path(path, &base_dir);
 
                 return Some(Action::OpenFile(expanded_path));
             }
         }
 
-        let command_lower = command.to_lowercase();
+        if cmd.eq_ignore_ascii_case("annotate") {
+            self.annotate_last_image(rest);
+            return None;
+        }
+
+        if cmd.eq_ignore_ascii_case("cost-center") {
+            self.set_cost_center(rest);
+            return None;
+        }
+
+        if cmd.eq_ignore_ascii_case("lang") {
+            let language =
+                (!rest.is_empty() && !rest.eq_ignore_ascii_case("off")).then(|| rest.to_string());
+            self.set_response_language(language);
+            return None;
+        }
+
+        let command_lower = command.to_lowercase();
 
         // First check for built-in command aliases
         match command_lower.as_str() {
             "help" | "h" | "?" => {
                 self.state.close_overlays();
                 let keybindings = self.config().keybindings.clone();
-                self.state.help_dialog_state.show(&keybindings);
+                let issues = self.config().keybinding_issues.clone();
+                self.state.help_dialog_state.show(&keybindings, &issues);
                 self.state.input_mode = InputMode::ShowingHelp;
                 return None;
             }
@@ -3155,6 +3990,335 @@ impl App {
         parse_action(&command_lower)
     }
 
+    /// Crop or mark up the draft's most recently attached image (`:annotate <spec>`).
+    /// The edited copy replaces the attachment; the original file is left untouched.
+    fn annotate_last_image(&mut self, spec: &str) {
+        let annotations = match image_annotation::parse_annotations(spec) {
+            Ok(annotations) => annotations,
+            Err(err) => {
+                self.state
+                    .set_timed_footer_message(err.to_string(), Duration::from_secs(5));
+                return;
+            }
+        };
+
+        let attachment_store = self.core.attachment_store_clone();
+        let result = {
+            let Some(session) = self.state.tab_manager.active_session_mut() else {
+                return;
+            };
+            match session
+                .input_box
+                .last_attached_image()
+                .map(Path::to_path_buf)
+            {
+                None => Err("No attached image to annotate".to_string()),
+                Some(source) => Self::write_annotated_image(
+                    &source,
+                    &annotations,
+                    attachment_store.as_ref(),
+                    session.id,
+                )
+                .map(|(path, width, height)| {
+                    session
+                        .input_box
+                        .replace_attached_image(&source, path, width, height);
+                    format!("Image annotated ({}x{})", width, height)
+                })
+                .map_err(|err| {
+                    tracing::warn!(
+                        error = %err,
+                        path = %source.display(),
+                        "Failed to annotate image"
+                    );
+                    format!("Failed to annotate image: {}", err)
+                }),
+            }
+        };
+
+        let message = match result {
+            Ok(message) | Err(message) => message,
+        };
+        self.state
+            .set_timed_footer_message(message, Duration::from_secs(5));
+    }
+
+    /// Open the command line prefilled with `annotate ` for the draft's last image
+    fn start_image_annotation(&mut self) {
+        let has_image = self
+            .state
+            .tab_manager
+            .active_session()
+            .is_some_and(|session| session.input_box.last_attached_image().is_some());
+        if !has_image {
+            self.state.set_timed_footer_message(
+                "No attached image to annotate".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        }
+        self.state.command_buffer = "annotate ".to_string();
+        self.state.input_mode = InputMode::Command;
+        self.state.set_timed_footer_message(
+            image_annotation::ANNOTATE_USAGE.to_string(),
+            Duration::from_secs(10),
+        );
+    }
+
+    /// Start recording a voice prompt, or stop and transcribe the one in progress
+    fn toggle_voice_input(&mut self, effects: &mut Vec<Effect>) {
+        if self.state.voice_recording.is_some() {
+            self.finish_voice_recording(effects);
+            return;
+        }
+
+        let Some(max_seconds) = self.config().voice.as_ref().map(|voice| voice.max_seconds) else {
+            self.state.set_timed_footer_message(
+                "Voice input isn't configured (add a [voice] section to config.toml)".to_string(),
+                Duration::from_secs(5),
+            );
+            return;
+        };
+        let Some(session_id) = self.state.tab_manager.active_session().map(|s| s.id) else {
+            self.state
+                .set_timed_footer_message("No active session".to_string(), Duration::from_secs(3));
+            return;
+        };
+
+        match VoiceRecorder::start(Duration::from_secs(max_seconds)) {
+            Ok(recorder) => {
+                self.state.voice_recording = Some(VoiceRecording {
+                    session_id,
+                    recorder,
+                });
+                let key = GlobalFooter::key_label_for(
+                    &self.config().keybindings,
+                    KeyContext::Chat,
+                    &Action::ToggleVoiceInput,
+                )
+                .unwrap_or_else(|| ":toggle_voice_input".to_string());
+                self.state
+                    .set_footer_message(Some(format!("● Recording… press {} to stop", key)));
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "Failed to start voice recording");
+                self.state
+                    .set_timed_footer_message(err.to_string(), Duration::from_secs(5));
+            }
+        }
+    }
+
+    /// Stop the active recording and queue it for transcription
+    fn finish_voice_recording(&mut self, effects: &mut Vec<Effect>) {
+        let Some(VoiceRecording {
+            session_id,
+            recorder,
+        }) = self.state.voice_recording.take()
+        else {
+            return;
+        };
+        let Some(config) = self.config().voice.clone() else {
+            self.state.set_footer_message(None);
+            return;
+        };
+
+        match recorder.stop() {
+            Ok(recording) => {
+                self.state
+                    .set_footer_message(Some("Transcribing voice prompt…".to_string()));
+                effects.push(Effect::TranscribeVoice {
+                    session_id,
+                    recording,
+                    config,
+                });
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "Voice recording failed");
+                self.state
+                    .set_timed_footer_message(err.to_string(), Duration::from_secs(5));
+            }
+        }
+    }
+
+    /// Set or clear the active session's response language (`:lang <language>`)
+    fn set_response_language(&mut self, language: Option<String>) {
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            self.state
+                .set_timed_footer_message("No active session".to_string(), Duration::from_secs(3));
+            return;
+        };
+        let message = match &language {
+            Some(language) => format!("Responses will be in {}", language),
+            None => "Response language cleared".to_string(),
+        };
+        session.response_language = language;
+        self.state
+            .set_timed_footer_message(message, Duration::from_secs(3));
+    }
+
+    /// Tag the active session's spend with a cost center (`:cost-center <tag>`);
+    /// `off` falls back to the repository's tag and no argument shows the
+    /// current one.
+    fn set_cost_center(&mut self, arg: &str) {
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            self.state
+                .set_timed_footer_message("No active session".to_string(), Duration::from_secs(3));
+            return;
+        };
+        let message = if arg.is_empty() {
+            match &session.cost_center {
+                Some(tag) => format!("Cost center: {} (:cost-center off to clear)", tag),
+                None => "No session cost center; spend uses the repository's tag".to_string(),
+            }
+        } else if arg.eq_ignore_ascii_case("off") {
+            session.cost_center = None;
+            "Session cost center cleared".to_string()
+        } else {
+            session.cost_center = Some(arg.to_string());
+            format!("Spend in this session is billed to {}", arg)
+        };
+        self.state
+            .set_timed_footer_message(message, Duration::from_secs(3));
+    }
+
+    /// Switch the active session between the configured response language and none
+    fn toggle_response_language(&mut self) {
+        let is_set = self
+            .state
+            .tab_manager
+            .active_session()
+            .is_some_and(|session| session.response_language.is_some());
+        if is_set {
+            self.set_response_language(None);
+            return;
+        }
+        match self.config().ui.response_language.clone() {
+            Some(language) => self.set_response_language(Some(language)),
+            None => {
+                // Nothing configured to toggle to: ask for a language instead
+                self.state.command_buffer = "lang ".to_string();
+                self.state.input_mode = InputMode::Command;
+                self.state.set_timed_footer_message(
+                    "Usage: :lang <language> (set [ui] response_language to toggle directly)"
+                        .to_string(),
+                    Duration::from_secs(10),
+                );
+            }
+        }
+    }
+
+    /// Turn spoken responses on or off, silencing any response being read
+    fn toggle_read_aloud(&mut self) {
+        if self.config().read_aloud.is_none() {
+            self.state.set_timed_footer_message(
+                "Read-aloud isn't configured (add a [read_aloud] section to config.toml)"
+                    .to_string(),
+                Duration::from_secs(5),
+            );
+            return;
+        }
+        self.state.read_aloud_enabled = !self.state.read_aloud_enabled;
+        if !self.state.read_aloud_enabled {
+            self.stop_read_aloud();
+        }
+        let message = if self.state.read_aloud_enabled {
+            "Read-aloud on"
+        } else {
+            "Read-aloud off"
+        };
+        self.state
+            .set_timed_footer_message(message.to_string(), Duration::from_secs(3));
+    }
+
+    fn stop_read_aloud(&mut self) {
+        if let Some(task) = self.state.read_aloud_task.take() {
+            task.abort();
+        }
+    }
+
+    /// Final assistant message of the turn that just completed, as speakable text
+    fn read_aloud_text_for_turn(
+        session: &crate::ui::session::AgentSession,
+        max_chars: usize,
+    ) -> Option<String> {
+        let message = Self::last_assistant_message(session)?;
+        read_aloud::speakable_text(&message.content, max_chars)
+    }
+
+    /// Final assistant message since the last user prompt
+    fn last_assistant_message(session: &crate::ui::session::AgentSession) -> Option<&ChatMessage> {
+        session
+            .chat_view
+            .messages()
+            .iter()
+            .rev()
+            .take_while(|message| message.role != MessageRole::User)
+            .find(|message| message.role == MessageRole::Assistant)
+    }
+
+    /// Session details handed to prompt hooks
+    fn hook_context(
+        session: &crate::ui::session::AgentSession,
+        default_working_dir: &Path,
+    ) -> HookContext {
+        HookContext {
+            session_id: session.id,
+            agent: session.agent_type.as_str().to_string(),
+            model: session.model.clone(),
+            working_dir: Some(
+                session
+                    .working_dir
+                    .clone()
+                    .unwrap_or_else(|| default_working_dir.to_path_buf()),
+            ),
+        }
+    }
+
+    /// Summary of the turn that just completed, for post-turn hooks
+    fn turn_report(
+        session: &crate::ui::session::AgentSession,
+        usage: TokenUsage,
+        cost_usd: f64,
+    ) -> TurnReport {
+        let summary = &session.current_turn_summary;
+        let last_message =
+            Self::last_assistant_message(session).map(|message| message.content.clone());
+        TurnReport {
+            duration_secs: Some(summary.duration_secs),
+            usage,
+            cost_usd: Some(cost_usd),
+            files_changed: summary
+                .files_changed
+                .iter()
+                .map(|change| HookFileChange {
+                    path: change.filename.clone(),
+                    additions: change.additions,
+                    deletions: change.deletions,
+                })
+                .collect(),
+            last_message,
+        }
+    }
+
+    /// Render annotations onto a copy of `source`, stored as a new session
+    /// attachment when the database is available
+    fn write_annotated_image(
+        source: &Path,
+        annotations: &[image_annotation::Annotation],
+        store: Option<&AttachmentStore>,
+        session_id: Uuid,
+    ) -> anyhow::Result<(PathBuf, u32, u32)> {
+        let (png, width, height) = image_annotation::annotate_to_png(source, annotations)?;
+        let path = match store {
+            Some(store) => {
+                let attachment = store.create_from_bytes(session_id, &png, "image/png")?;
+                store.file_path(&attachment)
+            }
+            None => image_annotation::write_temp_png(&png)?,
+        };
+        Ok((path, width, height))
+    }
+
     /// Autocomplete the command buffer
     fn complete_command(&mut self) {
         let prefix = self.state.command_buffer.trim().to_lowercase();
@@ -3252,11 +4416,12 @@ impl App {
             return;
         }
 
-        // Get the repository name for the tab title
-        let project_name = self
+        // Get the repository name for the tab title and its default base instructions
+        let repo = self
             .repo_dao()
-            .and_then(|dao| dao.get_by_id(workspace.repository_id).ok().flatten())
-            .map(|repo| repo.name);
+            .and_then(|dao| dao.get_by_id(workspace.repository_id).ok().flatten());
+        let project_name = repo.as_ref().map(|repo| repo.name.clone());
+        let repo_system_prompt = repo.and_then(|repo| repo.system_prompt);
 
         // Check if there's a saved session for this workspace (to restore chat history)
         let saved_tab = self
@@ -3276,14 +4441,14 @@ impl App {
         let no_agents_available = !self.tools().is_available(crate::util::Tool::Claude)
             && !self.tools().is_available(crate::util::Tool::Codex)
             && !self.tools().is_available(crate::util::Tool::Gemini)
-            && !self.tools().is_available(crate::util::Tool::Opencode);
+            && !self.tools().is_available(crate::util::Tool::Opencode)
+            && self.config().local_model.is_none();
         let tab_agent_type = saved_tab
             .as_ref()
             .map(|saved| saved.agent_type)
             .unwrap_or_else(|| {
                 let default_agent = self.config().default_agent;
-                let default_tool = Self::required_tool(default_agent);
-                if self.tools().is_available(default_tool) {
+                if self.core.is_agent_available(default_agent) {
                     default_agent
                 } else if self.tools().is_available(crate::util::Tool::Claude) {
                     AgentType::Claude
@@ -3293,6 +4458,8 @@ impl App {
                     AgentType::Gemini
                 } else if self.tools().is_available(crate::util::Tool::Opencode) {
                     AgentType::Opencode
+                } else if self.config().local_model.is_some() {
+                    AgentType::Local
                 } else {
                     AgentType::Claude
                 }
@@ -3307,8 +4474,7 @@ impl App {
             Self::clamp_agent_mode(saved.agent_type, parsed_mode)
         });
 
-        let required_tool = Self::required_tool(tab_agent_type);
-        if !self.tools().is_available(required_tool) {
+        if let Some(required_tool) = self.missing_tool_for(tab_agent_type) {
             self.show_missing_tool(
                 required_tool,
                 if has_saved_session {
@@ -3348,14 +4514,30 @@ impl App {
 
         // Get default model before the mutable borrow
         let default_model = self.config().default_model_for(tab_agent_type);
+        let show_reasoning = self.config().ui.show_reasoning;
+        let chat_timestamps = self.config().ui.chat_timestamps;
 
         let session_tab_dao = self.session_tab_dao_clone();
+        let linked_workspaces = saved_tab
+            .as_ref()
+            .map(|saved| self.restore_linked_workspaces(&saved.linked_workspace_ids))
+            .unwrap_or_default();
+
+        let fork_seed = saved_tab
+            .as_ref()
+            .and_then(|saved| self.load_fork_seed(saved.fork_seed_id));
+        let pending_seed_path = saved_tab.as_ref().and_then(|saved| {
+            Self::pending_seed_path(fork_seed.as_ref(), saved.agent_session_id.as_deref())
+        });
 
         // Store workspace info in session and restore chat history if available
         if let Some(session) = self.state.tab_manager.active_session_mut() {
             session.workspace_id = Some(workspace_id);
             session.project_name = project_name;
             session.workspace_name = Some(workspace.name.clone());
+            session.system_prompt = repo_system_prompt;
+            session.chat_view.set_show_reasoning(show_reasoning);
+            session.chat_view.set_timestamps(chat_timestamps);
 
             // Restore saved session data if available
             if let Some(saved) = saved_tab.as_ref() {
@@ -3369,10 +4551,29 @@ impl App {
             if let Some(saved) = saved_tab {
                 session.set_agent_and_model(saved.agent_type, saved.model);
                 session.title = saved.title.clone();
+                session.custom_title = saved.custom_title.clone();
+                session.linked_workspaces = linked_workspaces;
                 if let Some(saved_mode) = saved_agent_mode {
                     session.agent_mode = saved_mode; // Pre-clamped above
                 }
                 session.fork_seed_id = saved.fork_seed_id;
+                session.fork_seed_hash = fork_seed.map(|seed| seed.seed_prompt_hash);
+                session.codex_sandbox = saved
+                    .codex_sandbox
+                    .as_deref()
+                    .and_then(CodexSandboxMode::parse);
+                session.codex_approval = saved
+                    .codex_approval
+                    .as_deref()
+                    .and_then(CodexApprovalPolicy::parse);
+                session.system_prompt = saved.system_prompt.clone();
+                session.response_language = saved.response_language.clone();
+                session.response_preset = saved
+                    .response_preset
+                    .as_deref()
+                    .and_then(ResponsePreset::parse);
+                session.cost_center = saved.cost_center.clone();
+                session.pending_seed_path = pending_seed_path;
 
                 // Restore chat history from agent files
                 if let Some(ref session_id_str) = saved.agent_session_id {
@@ -3393,7 +4594,7 @@ impl App {
                                     &file_path,
                                 );
                                 for msg in msgs {
-                                    session.chat_view.push(msg);
+                                    session.chat_view.push_history(msg);
                                 }
                             }
                         }
@@ -3408,7 +4609,7 @@ impl App {
                                     &file_path,
                                 );
                                 for msg in msgs {
-                                    session.chat_view.push(msg);
+                                    session.chat_view.push_history(msg);
                                 }
                             }
                         }
@@ -3430,10 +4631,18 @@ impl App {
                                     &file_path,
                                 );
                                 for msg in msgs {
-                                    session.chat_view.push(msg);
+                                    session.chat_view.push_history(msg);
                                 }
                             }
                         }
+                        AgentType::Local => {
+                            session.chat_view.push(
+                                MessageDisplay::System {
+                                    content: "Local model conversations aren't saved, so previous messages won't be shown.".to_string(),
+                                }
+                                .to_chat_message(),
+                            );
+                        }
                     }
                 } else if saved.agent_type == AgentType::Opencode {
                     if let Some(working_dir) = session.working_dir.as_ref() {
@@ -3450,7 +4659,7 @@ impl App {
                                 &file_path,
                             );
                             for msg in msgs {
-                                session.chat_view.push(msg);
+                                session.chat_view.push_history(msg);
                             }
                         }
                     }
@@ -3520,16 +4729,22 @@ impl App {
         }
     }
 
-    /// Map an agent type to its required tool.
-    fn required_tool(agent_type: AgentType) -> crate::util::Tool {
+    /// Map an agent type to its required tool (None for local models).
+    fn required_tool(agent_type: AgentType) -> Option<crate::util::Tool> {
         match agent_type {
-            AgentType::Claude => crate::util::Tool::Claude,
-            AgentType::Codex => crate::util::Tool::Codex,
-            AgentType::Gemini => crate::util::Tool::Gemini,
-            AgentType::Opencode => crate::util::Tool::Opencode,
+            AgentType::Claude => Some(crate::util::Tool::Claude),
+            AgentType::Codex => Some(crate::util::Tool::Codex),
+            AgentType::Gemini => Some(crate::util::Tool::Gemini),
+            AgentType::Opencode => Some(crate::util::Tool::Opencode),
+            AgentType::Local => None,
         }
     }
 
+    /// The tool an agent type needs, if it isn't available.
+    pub(super) fn missing_tool_for(&self, agent_type: AgentType) -> Option<crate::util::Tool> {
+        Self::required_tool(agent_type).filter(|tool| !self.tools().is_available(*tool))
+    }
+
     fn model_selector_defaults(&self) -> DefaultModelSelection {
         let agent_type = self.config().default_agent;
         DefaultModelSelection {
@@ -3570,6 +4785,96 @@ impl App {
     }
 
     /// Find the tab index for a workspace if it's already open
+    /// Run a new workspace's bootstrap commands in its tab. Prompts sent
+    /// before they finish are queued; interrupting the agent skips the rest.
+    fn start_workspace_bootstrap(
+        &mut self,
+        workspace_id: uuid::Uuid,
+        commands: Vec<String>,
+    ) -> Option<Effect> {
+        let session = self
+            .state
+            .tab_manager
+            .sessions_mut()
+            .find(|session| session.workspace_id == Some(workspace_id))?;
+        let working_dir = session.working_dir.clone()?;
+        let cancel = CancellationToken::new();
+        session.bootstrap_cancel = Some(cancel.clone());
+        session.update_status();
+        let session_id = session.id;
+        self.state.set_timed_footer_message(
+            "Bootstrapping workspace · interrupt to skip".to_string(),
+            Duration::from_secs(3),
+        );
+        Some(Effect::RunBootstrap {
+            session_id,
+            working_dir,
+            target: self.core.execution_target_for_workspace(workspace_id),
+            commands,
+            cancel,
+        })
+    }
+
+    fn finish_workspace_bootstrap(
+        &mut self,
+        session_id: Uuid,
+        outcome: BootstrapOutcome,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let Some(tab_index) = self.state.tab_manager.session_index_by_id(session_id) else {
+            tracing::debug!(%session_id, "BootstrapFinished for unknown session; ignoring");
+            return Ok(Vec::new());
+        };
+        let mut released_images = Vec::new();
+        let should_drain = {
+            let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+                return Ok(Vec::new());
+            };
+            session.bootstrap_cancel = None;
+            let should_drain = match &outcome {
+                BootstrapOutcome::Succeeded => true,
+                BootstrapOutcome::Skipped => {
+                    session
+                        .chat_view
+                        .push(ChatMessage::system("Workspace bootstrap skipped."));
+                    true
+                }
+                BootstrapOutcome::Failed(err) => {
+                    // Don't send queued prompts into a half-set-up workspace; hand
+                    // them back as a draft the user can send once it's fixed
+                    let held_as_draft =
+                        !session.queued_messages.is_empty() && session.input_box.is_empty();
+                    if held_as_draft {
+                        let held: Vec<QueuedMessage> = session.queued_messages.drain(..).collect();
+                        let (text, images, placeholders) = app_queue::build_queued_submission(
+                            &held,
+                            crate::config::QueueDelivery::Concat,
+                        );
+                        session.input_box.set_input_with_attachments(
+                            text,
+                            images.iter().cloned().zip(placeholders).collect(),
+                        );
+                        session.input_box.move_end();
+                        released_images = images;
+                    }
+                    let mut note = format!("Workspace bootstrap failed: {err}.");
+                    if held_as_draft {
+                        note.push_str(" Queued prompts were moved back to the input.");
+                    }
+                    session.chat_view.push(ChatMessage::error(note));
+                    false
+                }
+            };
+            session.update_status();
+            should_drain && !session.queued_messages.is_empty()
+        };
+        // Back to a draft; submitting again takes a fresh reference
+        self.release_attachments(&released_images);
+        if should_drain {
+            return self.drain_queue_for_tab(tab_index);
+        }
+        Ok(Vec::new())
+    }
+
     fn find_tab_for_workspace(&self, workspace_id: uuid::Uuid) -> Option<usize> {
         self.state.tab_manager.tabs().iter().position(|tab| {
             tab.as_agent()
@@ -3580,25 +4885,7 @@ impl App {
     /// Extract PR number from text containing a GitHub PR URL
     /// Looks for patterns like "github.com/owner/repo/pull/123"
     fn extract_pr_number_from_text(text: &str) -> Option<u32> {
-        // Look for GitHub PR URLs in the text
-        for word in text.split_whitespace() {
-            // Check if this word contains a GitHub PR URL
-            if let Some(pull_idx) = word.find("/pull/") {
-                // Extract the part after "/pull/"
-                let after_pull = &word[pull_idx + 6..];
-                // Parse the number (stop at any non-digit character)
-                let num_str: String = after_pull
-                    .chars()
-                    .take_while(|c| c.is_ascii_digit())
-                    .collect();
-                if !num_str.is_empty() {
-                    if let Ok(num) = num_str.parse::<u32>() {
-                        return Some(num);
-                    }
-                }
-            }
-        }
-        None
+        find_pr_numbers(text).into_iter().next()
     }
 
     /// Build a minimal PR status from a known PR number (used when full status is unavailable).
@@ -3762,16 +5049,24 @@ impl App {
         if matches!(&ctx, Some(ConfirmationContext::ForkSession { .. })) {
             self.state.pending_fork_request = None;
         }
+        // Cancelling the quit dialog also cancels an earlier choice to wait
+        if matches!(&ctx, Some(ConfirmationContext::QuitWithRunningAgents)) {
+            self.state.quit_when_idle = false;
+        }
 
         self.state.confirmation_dialog_state.hide();
 
         // Return appropriate input mode based on context
         match ctx {
-            // PR/Fork/Steer dialogs originated from chat view, return to Normal
+            // PR/Fork/Steer/Rollback dialogs originated from chat view, return to Normal
             Some(ConfirmationContext::CreatePullRequest { .. })
             | Some(ConfirmationContext::OpenExistingPr { .. })
             | Some(ConfirmationContext::ForkSession { .. })
-            | Some(ConfirmationContext::SteerFallback { .. }) => InputMode::Normal,
+            | Some(ConfirmationContext::SteerFallback { .. })
+            | Some(ConfirmationContext::RollbackPrompt { .. })
+            | Some(ConfirmationContext::FixSecurityFindings { .. })
+            | Some(ConfirmationContext::AcknowledgeChangeFlags { .. })
+            | Some(ConfirmationContext::QuitWithRunningAgents) => InputMode::Normal,
             // Sidebar operations return to sidebar navigation
             Some(ConfirmationContext::ArchiveWorkspace(_))
             | Some(ConfirmationContext::ArchiveWorkspaceRemoteDelete { .. })
@@ -3902,56 +5197,304 @@ impl App {
         self.state.input_mode = InputMode::ShowingError;
     }
 
-    /// Execute the archive workspace action after confirmation
-    fn execute_archive_workspace(
-        &mut self,
-        workspace_id: uuid::Uuid,
-        delete_remote: bool,
-    ) -> Effect {
-        self.mark_workspace_busy(workspace_id);
-        Effect::ArchiveWorkspace {
-            workspace_id,
-            delete_remote,
-        }
-    }
+    /// Show the active session's captured agent stderr
+    fn show_agent_diagnostics(&mut self) {
+        const MAX_DIALOG_LINES: usize = 20;
 
-    fn resolve_workspace_settings(
-        &self,
-        workspace_id: uuid::Uuid,
-    ) -> Option<(
-        crate::data::Workspace,
-        crate::core::RepoWorkspaceSettings,
-        Option<std::path::PathBuf>,
-    )> {
-        let workspace_dao = self.workspace_dao()?;
-        let repo_dao = self.repo_dao()?;
+        let Some(session) = self.state.tab_manager.active_session() else {
+            self.state
+                .set_timed_footer_message("No active session".to_string(), Duration::from_secs(3));
+            return;
+        };
+        let agent = session.agent_type.display_name();
+        let lines = session.stderr.lines();
+        if lines.is_empty() {
+            self.show_error(
+                "Agent Diagnostics",
+                &format!("{} hasn't written anything to stderr", agent),
+            );
+            return;
+        }
 
-        let workspace = match workspace_dao.get_by_id(workspace_id) {
-            Ok(workspace) => workspace?,
-            Err(err) => {
-                tracing::warn!(
-                    error = %err,
-                    workspace_id = %workspace_id,
-                    "Failed to load workspace"
-                );
-                return None;
-            }
+        let shown = &lines[lines.len().saturating_sub(MAX_DIALOG_LINES)..];
+        let message = format!(
+            "Last {} of {} stderr lines from {}",
+            shown.len(),
+            lines.len(),
+            agent
+        );
+        let details = shown
+            .iter()
+            .map(|entry| {
+                let time = entry.timestamp.with_timezone(&chrono::Local);
+                format!("{} {}", time.format("%H:%M:%S"), entry.line)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.show_error_with_details("Agent Diagnostics", &message, &details);
+        self.state.error_dialog_state.details_expanded = true;
+    }
+
+    /// Restart the active session's crashed agent, or interrupt its stalled
+    /// turn, by resending its last prompt
+    fn restart_crashed_agent(&mut self) -> anyhow::Result<Vec<Effect>> {
+        let mut stalled = false;
+        let outcome = match self.state.tab_manager.active_session_mut() {
+            None => Err("No active session"),
+            Some(session) if session.is_processing && session.stall_notified => {
+                stalled = true;
+                session.last_prompt.clone().ok_or("No prompt to resend")
+            }
+            Some(session) if session.is_processing => Err("Agent is still running"),
+            Some(session) if !session.agent_crashed => Err("No crashed agent to restart"),
+            Some(session) => Self::reset_for_resend(session),
         };
-        let repo = match repo_dao.get_by_id(workspace.repository_id) {
-            Ok(repo) => repo?,
-            Err(err) => {
-                tracing::warn!(
-                    error = %err,
-                    repository_id = %workspace.repository_id,
-                    "Failed to load repository"
-                );
-                return None;
+
+        let prompt = match outcome {
+            Ok(prompt) => prompt,
+            Err(message) => {
+                self.state
+                    .set_timed_footer_message(message.to_string(), Duration::from_secs(3));
+                return Ok(Vec::new());
             }
         };
-        let settings = resolve_repo_workspace_settings(self.config(), &repo);
-        let base_path = repo.base_path.clone();
-        Some((workspace, settings, base_path))
-    }
+
+        if stalled {
+            self.interrupt_agent();
+        }
+        let (text, images, placeholders) = app_queue::queued_to_submission(&prompt);
+        self.retain_attachments(&images);
+        self.submit_prompt(text, images, placeholders)
+    }
+
+    /// Clear a session's dead agent and hand back its last prompt for resending
+    fn reset_for_resend(session: &mut AgentSession) -> Result<QueuedMessage, &'static str> {
+        let prompt = session.last_prompt.clone().ok_or("No prompt to resend")?;
+        session.agent_crashed = false;
+        session.failure = None;
+        session.agent_pid = None;
+        session.agent_pid_start_time = None;
+        session.agent_input_tx = None;
+        session.agent_cancel = None;
+        Ok(prompt)
+    }
+
+    /// Show the suggestion card for a classified failure
+    fn push_failure_card(&mut self, tab_index: usize, kind: FailureKind, agent_type: AgentType) {
+        let key = GlobalFooter::key_label_for(
+            &self.config().keybindings,
+            KeyContext::Chat,
+            &Action::ApplyFailureRemedy,
+        )
+        .unwrap_or_else(|| ":apply_failure_remedy".to_string());
+        let remedy = match kind {
+            FailureKind::AuthExpired => match triage::login_args(agent_type) {
+                Some(args) => {
+                    let binary = Self::required_tool(agent_type)
+                        .map_or(agent_type.as_str(), |tool| tool.binary_name());
+                    let command = std::iter::once(binary)
+                        .chain(args.iter().copied())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    format!("run `{}`, then resend your last prompt", command)
+                }
+                None => "show agent diagnostics".to_string(),
+            },
+            FailureKind::BinaryMissing => match Self::required_tool(agent_type) {
+                Some(tool) => format!("set up the {} CLI", tool.display_name()),
+                None => "show agent diagnostics".to_string(),
+            },
+            FailureKind::RateLimited { retry_after } => format!(
+                "wait {}s and resend your last prompt",
+                retry_after.as_secs()
+            ),
+        };
+        if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
+            let display = MessageDisplay::System {
+                content: format!(
+                    "⚠️ {}\nPress {} to {}.",
+                    kind.summary(agent_type),
+                    key,
+                    remedy
+                ),
+            };
+            session.chat_view.push(display.to_chat_message());
+        }
+    }
+
+    /// Act on the active session's classified failure: log in again, open the
+    /// tool setup dialog, or wait out a rate limit, then resend the prompt
+    fn apply_failure_remedy(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+        guard: &mut TerminalGuard,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let default_working_dir = self.config().working_dir.clone();
+        let outcome = match self.state.tab_manager.active_session() {
+            None => return Ok(Vec::new()),
+            Some(session) if session.is_processing => Err("Agent is still running"),
+            Some(session) => match session.failure {
+                Some(kind) => Ok((
+                    kind,
+                    session.id,
+                    session.agent_type,
+                    session.working_dir.clone().unwrap_or(default_working_dir),
+                )),
+                None => Err("No failed turn to fix"),
+            },
+        };
+        let (kind, session_id, agent_type, working_dir) = match outcome {
+            Ok(failure) => failure,
+            Err(message) => {
+                self.state
+                    .set_timed_footer_message(message.to_string(), Duration::from_secs(3));
+                return Ok(Vec::new());
+            }
+        };
+
+        match kind {
+            FailureKind::AuthExpired => {
+                let login = triage::login_args(agent_type).zip(Self::required_tool(agent_type));
+                let Some((args, tool)) = login else {
+                    self.show_agent_diagnostics();
+                    return Ok(Vec::new());
+                };
+                let binary = self
+                    .tools()
+                    .get_path(tool)
+                    .cloned()
+                    .unwrap_or_else(|| PathBuf::from(tool.binary_name()));
+
+                guard.cleanup_for_suspend()?;
+                println!(
+                    "Logging in to {}. Exit the agent when done to return to Conduit.",
+                    agent_type.display_name()
+                );
+                let status = Command::new(&binary)
+                    .args(args)
+                    .current_dir(&working_dir)
+                    .status();
+                self.reinitialize_terminal(terminal)?;
+
+                match status {
+                    Ok(status) if status.success() => self.retry_failed_turn(session_id),
+                    Ok(status) => {
+                        self.state.set_timed_footer_message(
+                            format!("Login failed ({})", status),
+                            Duration::from_secs(5),
+                        );
+                        Ok(Vec::new())
+                    }
+                    Err(err) => {
+                        tracing::warn!(
+                            error = %err,
+                            binary = %binary.display(),
+                            "Failed to run agent login"
+                        );
+                        self.state.set_timed_footer_message(
+                            format!("Failed to run {}: {}", binary.display(), err),
+                            Duration::from_secs(5),
+                        );
+                        Ok(Vec::new())
+                    }
+                }
+            }
+            FailureKind::BinaryMissing => {
+                match Self::required_tool(agent_type) {
+                    Some(tool) => self.show_missing_tool(tool, kind.summary(agent_type)),
+                    None => self.show_agent_diagnostics(),
+                }
+                Ok(Vec::new())
+            }
+            FailureKind::RateLimited { retry_after } => {
+                self.state.set_timed_footer_message(
+                    format!("Retrying in {}s", retry_after.as_secs()),
+                    retry_after,
+                );
+                Ok(vec![Effect::RetryFailedTurn {
+                    session_id,
+                    delay: retry_after,
+                }])
+            }
+        }
+    }
+
+    /// Resend a failed turn's prompt, unless something was sent since
+    fn retry_failed_turn(&mut self, session_id: Uuid) -> anyhow::Result<Vec<Effect>> {
+        let Some(tab_index) = self.state.tab_manager.session_index_by_id(session_id) else {
+            return Ok(Vec::new());
+        };
+        let outcome = match self.state.tab_manager.session_mut(tab_index) {
+            Some(session) if session.is_processing || session.failure.is_none() => {
+                return Ok(Vec::new());
+            }
+            Some(session) => Self::reset_for_resend(session),
+            None => return Ok(Vec::new()),
+        };
+        let prompt = match outcome {
+            Ok(prompt) => prompt,
+            Err(message) => {
+                self.state
+                    .set_timed_footer_message(message.to_string(), Duration::from_secs(3));
+                return Ok(Vec::new());
+            }
+        };
+
+        let (text, images, placeholders) = app_queue::queued_to_submission(&prompt);
+        self.retain_attachments(&images);
+        self.submit_prompt_for_tab(tab_index, text, images, placeholders, false, None)
+    }
+
+    /// Execute the archive workspace action after confirmation
+    fn execute_archive_workspace(
+        &mut self,
+        workspace_id: uuid::Uuid,
+        delete_remote: bool,
+    ) -> Effect {
+        self.mark_workspace_busy(workspace_id);
+        Effect::ArchiveWorkspace {
+            workspace_id,
+            delete_remote,
+        }
+    }
+
+    fn resolve_workspace_settings(
+        &self,
+        workspace_id: uuid::Uuid,
+    ) -> Option<(
+        crate::data::Workspace,
+        crate::core::RepoWorkspaceSettings,
+        Option<std::path::PathBuf>,
+    )> {
+        let workspace_dao = self.workspace_dao()?;
+        let repo_dao = self.repo_dao()?;
+
+        let workspace = match workspace_dao.get_by_id(workspace_id) {
+            Ok(workspace) => workspace?,
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    workspace_id = %workspace_id,
+                    "Failed to load workspace"
+                );
+                return None;
+            }
+        };
+        let repo = match repo_dao.get_by_id(workspace.repository_id) {
+            Ok(repo) => repo?,
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    repository_id = %workspace.repository_id,
+                    "Failed to load repository"
+                );
+                return None;
+            }
+        };
+        let settings = resolve_repo_workspace_settings(self.config(), &repo);
+        let base_path = repo.base_path.clone();
+        Some((workspace, settings, base_path))
+    }
 
     fn prompt_archive_remote_delete(&mut self, workspace: &crate::data::Workspace) {
         self.state.close_overlays();
@@ -4103,15 +5646,46 @@ impl App {
 
     fn close_tab_at_index(&mut self, index: usize) {
         if let Some(session) = self.state.tab_manager.session(index) {
-            if let Some(session_tab_dao) = self.session_tab_dao_clone() {
+            let session_tab_dao = self
+                .session_tab_dao_clone()
+                .filter(|_| self.state.observing.is_none());
+            if let Some(session_tab_dao) = session_tab_dao {
                 if let Err(e) = session_tab_dao.set_open(session.id, false) {
                     tracing::warn!(error = %e, "Failed to mark session as closed");
                 }
             }
+            self.discard_offloaded_chat_history(session.id);
+            if self.state.observing.is_none() {
+                self.store_session_report(session);
+            }
         }
+        self.persist_session_messages(index);
         self.state.tab_manager.close_tab(index);
     }
 
+    /// Save the final report of a tab that is being closed
+    fn store_session_report(&self, session: &AgentSession) {
+        let Some(store) = self.core.session_report_store() else {
+            return;
+        };
+        let mut report = build_session_report(
+            session.id,
+            session
+                .custom_title
+                .clone()
+                .or_else(|| session.title.clone()),
+            session.total_cost,
+            session.pr_number.map(|n| n as i32),
+            session.chat_view.messages(),
+        );
+        // Older turns may have been offloaded from the chat view
+        report.turns = report.turns.max(session.turn_count);
+        report.pr_url = session.status_bar.pr_status().and_then(|pr| pr.url.clone());
+        if let Err(e) = store.save(&report) {
+            tracing::warn!(session_id = %session.id, error = %e, "Failed to store session report");
+        }
+    }
+
     /// Close any tabs that are using the specified workspace
     fn close_tabs_for_workspace(&mut self, workspace_id: uuid::Uuid) {
         // Unregister workspace from git tracker
@@ -4140,6 +5714,13 @@ impl App {
             self.close_tab_at_index(idx);
         }
 
+        // Other sessions stop linking the workspace
+        for session in self.state.tab_manager.sessions_mut() {
+            session
+                .linked_workspaces
+                .retain(|linked| linked.id != workspace_id);
+        }
+
         // Switch to sidebar navigation if all tabs are closed
         // But don't override if we're showing an error dialog
         if self.state.tab_manager.is_empty() && self.state.input_mode != InputMode::ShowingError {
@@ -4217,15 +5798,107 @@ impl App {
     fn create_tab_with_agent(&mut self, agent_type: AgentType) {
         self.state.tab_manager.new_tab(agent_type);
         let model_id = self.config().default_model_for(agent_type);
+        let show_reasoning = self.config().ui.show_reasoning;
+        let chat_timestamps = self.config().ui.chat_timestamps;
         if let Some(session) = self.state.tab_manager.active_session_mut() {
             session.model = Some(model_id);
             session.model_invalid = false;
+            session.chat_view.set_show_reasoning(show_reasoning);
+            session.chat_view.set_timestamps(chat_timestamps);
             session.init_context_for_model();
             session.update_status();
         }
         self.state.input_mode = InputMode::Normal;
     }
 
+    /// Start a session from a splash screen template (agent + model + workspace + prompt).
+    fn start_session_template(&mut self, index: usize) {
+        let Some(template) = self.config().templates.get(index).cloned() else {
+            return;
+        };
+
+        if let Some(required_tool) = self.missing_tool_for(template.agent_type) {
+            self.show_missing_tool(
+                required_tool,
+                format!(
+                    "{} is required to start the \"{}\" template.",
+                    required_tool.display_name(),
+                    template.name
+                ),
+            );
+            return;
+        }
+
+        if let Some(workspace_spec) = template.workspace.as_deref() {
+            let Some(workspace_id) = self.find_workspace_by_spec(workspace_spec) else {
+                self.show_error(
+                    "Template workspace not found",
+                    &format!(
+                        "No active workspace matches \"{}\". Use a workspace name or \"repo/workspace\".",
+                        workspace_spec
+                    ),
+                );
+                return;
+            };
+            let tab_count = self.state.tab_manager.len();
+            self.open_workspace_with_options(workspace_id, true);
+            if self.state.tab_manager.len() == tab_count {
+                return;
+            }
+        } else {
+            let tab_count = self.state.tab_manager.len();
+            self.create_tab_with_agent(template.agent_type);
+            if self.state.tab_manager.len() == tab_count {
+                return;
+            }
+        }
+
+        let model = template
+            .model
+            .clone()
+            .unwrap_or_else(|| self.config().default_model_for(template.agent_type));
+        if let Some(session) = self.state.tab_manager.active_session_mut() {
+            // Only override the agent on a fresh session; restored history stays as-is.
+            let is_fresh =
+                session.chat_view.messages().is_empty() && session.resume_session_id.is_none();
+            if is_fresh {
+                session.set_agent_and_model(template.agent_type, Some(model));
+                session.init_context_for_model();
+            }
+            if let Some(prompt) = template.prompt {
+                session.input_box.set_input(prompt);
+            }
+            session.update_status();
+        }
+        self.state.input_mode = InputMode::Normal;
+    }
+
+    /// Resolve a template workspace (`name` or `repo/name`) to an active workspace ID.
+    fn find_workspace_by_spec(&self, spec: &str) -> Option<uuid::Uuid> {
+        let workspaces = match self.workspace_dao()?.get_all() {
+            Ok(workspaces) => workspaces,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to list workspaces for session template");
+                return None;
+            }
+        };
+        let (repo_name, workspace_name) = match spec.split_once('/') {
+            Some((repo, name)) => (Some(repo), name),
+            None => (None, spec),
+        };
+        workspaces
+            .into_iter()
+            .filter(|workspace| workspace.name == workspace_name)
+            .find(|workspace| {
+                repo_name.is_none_or(|repo_name| {
+                    self.repo_dao()
+                        .and_then(|dao| dao.get_by_id(workspace.repository_id).ok().flatten())
+                        .is_some_and(|repo| repo.name == repo_name)
+                })
+            })
+            .map(|workspace| workspace.id)
+    }
+
     /// Replace the active session with a fresh one (same workspace, reset history).
     fn start_new_session_in_place(&mut self) {
         if self.state.tab_manager.is_empty() {
@@ -4278,6 +5951,12 @@ impl App {
             AgentSession::new(agent_type)
         };
         new_session.workspace_id = workspace_id;
+        new_session
+            .chat_view
+            .set_show_reasoning(self.config().ui.show_reasoning);
+        new_session
+            .chat_view
+            .set_timestamps(self.config().ui.chat_timestamps);
         new_session.project_name = project_name;
         new_session.workspace_name = workspace_name;
         new_session.pr_number = pr_number;
@@ -4298,16 +5977,9 @@ impl App {
     async fn create_imported_session_tab(
         &mut self,
         agent_type: AgentType,
-        session_file: std::path::PathBuf,
+        session_id_str: String,
         working_dir: std::path::PathBuf,
     ) -> anyhow::Result<()> {
-        // Extract session ID from the file path
-        let session_id_str = session_file
-            .file_stem()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown")
-            .to_string();
-
         // Create a new session with working directory
         let mut session = AgentSession::with_working_dir(agent_type, working_dir);
         // Set both resume and agent session IDs so the session can be restored after restart
@@ -4327,7 +5999,7 @@ impl App {
                         &file_path,
                     );
                     for msg in msgs {
-                        session.chat_view.push(msg);
+                        session.chat_view.push_history(msg);
                     }
                 }
             }
@@ -4341,7 +6013,7 @@ impl App {
                         &file_path,
                     );
                     for msg in msgs {
-                        session.chat_view.push(msg);
+                        session.chat_view.push_history(msg);
                     }
                 }
             }
@@ -4365,10 +6037,15 @@ impl App {
                         &file_path,
                     );
                     for msg in msgs {
-                        session.chat_view.push(msg);
+                        session.chat_view.push_history(msg);
                     }
                 }
             }
+            AgentType::Local => {
+                // Local model conversations are never written to disk
+                session.resume_session_id = None;
+                session.agent_session_id = None;
+            }
         }
 
         session.update_status();
@@ -4481,7 +6158,10 @@ impl App {
                                 RawEventsClick::SessionId => {
                                     if let Some(session_id) = session.raw_events_view.session_id() {
                                         let id_str = session_id.to_string();
-                                        effects.push(Effect::CopyToClipboard(id_str.clone()));
+                                        effects.push(Effect::CopyToClipboard {
+                                            text: id_str.clone(),
+                                            source: "session ID",
+                                        });
                                         self.state.set_timed_footer_message(
                                             format!("Copied session ID: {}", id_str),
                                             Duration::from_secs(3),
@@ -4584,10 +6264,14 @@ impl App {
 
             // Handle based on node type
             match node.node_type {
-                NodeType::Repository => {
+                NodeType::Repository | NodeType::SessionGroup => {
                     // Toggle expand/collapse
                     self.state.sidebar_data.toggle_at(clicked_index);
                 }
+                NodeType::Session => {
+                    // Same as workspaces: a double click also closes the sidebar
+                    self.open_saved_session(node.id, is_double_click);
+                }
                 NodeType::Workspace => {
                     // Single click: open workspace but keep sidebar open
                     // Double click: open workspace and close sidebar
@@ -4610,13 +6294,17 @@ impl App {
         let mut pr_numbers = Vec::with_capacity(sessions.len());
         let mut processing_flags = Vec::with_capacity(sessions.len());
         let mut attention_flags = Vec::with_capacity(sessions.len());
+        let mut failed_flags = Vec::with_capacity(sessions.len());
         let mut awaiting_response_flags = Vec::with_capacity(sessions.len());
+        let mut diff_stats = Vec::with_capacity(sessions.len());
         for session in sessions {
             pr_numbers.push(session.pr_number);
+            diff_stats.push(session.status_bar.git_diff_stats().clone());
             // Don't show processing spinner if awaiting response (inline prompt active)
             let has_inline_prompt = session.inline_prompt.is_some();
             processing_flags.push(session.is_processing && !has_inline_prompt);
             attention_flags.push(session.needs_attention);
+            failed_flags.push(session.turn_failed_unseen);
             awaiting_response_flags.push(has_inline_prompt);
         }
 
@@ -4631,6 +6319,8 @@ impl App {
             attention_flags,
             awaiting_response_flags,
         )
+        .with_failed_flags(failed_flags)
+        .with_diff_stats(diff_stats)
         .with_spinner_frame(self.state.spinner_frame)
         .with_scroll_offset(self.state.tab_bar_scroll)
     }
@@ -4727,9 +6417,11 @@ impl App {
             self.state.sidebar_state.set_focused(false);
         }
 
-        // Position cursor based on click
+        // Clicking an image chip removes it; elsewhere the click positions the cursor
         if let Some(session) = self.state.tab_manager.active_session_mut() {
-            session.input_box.set_cursor_from_click(x, y, input_area);
+            if !session.input_box.remove_attachment_at(x, y, input_area) {
+                session.input_box.set_cursor_from_click(x, y, input_area);
+            }
         }
     }
 
@@ -4975,8 +6667,7 @@ impl App {
             let clicked_row = (y - list_y) as usize;
             if self.state.model_selector_state.select_at_row(clicked_row) {
                 if let Some(model) = self.state.model_selector_state.selected_model().cloned() {
-                    let required_tool = Self::required_tool(model.agent_type);
-                    if !self.tools().is_available(required_tool) {
+                    if let Some(required_tool) = self.missing_tool_for(model.agent_type) {
                         self.show_missing_tool(
                             required_tool,
                             format!(
@@ -5058,75 +6749,39 @@ impl App {
         }
     }
 
-    /// Handle click in footer area
-    /// Returns an action to execute if a valid hint was clicked
-    fn handle_footer_click(&mut self, x: u16, _y: u16, footer_area: Rect) -> Option<Action> {
-        // Use the same hints as GlobalFooter to stay in sync
-        // Sidebar focus takes precedence over file viewer / view_mode
-        let hints: Vec<(&str, &str)> = if self.state.input_mode == InputMode::SidebarNavigation {
-            GlobalFooter::sidebar_hints()
-        } else if self.state.tab_manager.active_is_file() {
-            GlobalFooter::file_viewer_hints()
-        } else {
-            match self.state.view_mode {
-                ViewMode::Chat => GlobalFooter::chat_hints(),
-                ViewMode::RawEvents => GlobalFooter::raw_events_hints(),
+    /// Footer hint context for the current mode.
+    /// Sidebar focus takes precedence over the empty state, file viewer and view mode.
+    fn footer_context(&self) -> FooterContext {
+        let sidebar_focused = self.state.input_mode == InputMode::SidebarNavigation;
+        if self.state.tab_manager.is_empty() {
+            if sidebar_focused {
+                FooterContext::Sidebar
+            } else {
+                FooterContext::Empty
             }
-        };
-
-        // Calculate click position relative to footer
-        let relative_x = x.saturating_sub(footer_area.x) as usize;
-
-        // Match the layout from GlobalFooter::render:
-        // " [key] action   [key] action ..."
-        // Leading space = 1, key has " key " (len+2), action has " action" (len+1), spacing = 3
-        let mut current_x: usize = 1; // Leading space
-
-        for (key, action_name) in hints {
-            // Format: " key " (key.len + 2) + " action" (action_name.len + 1) + spacing (3)
-            let key_width = key.len() + 2;
-            let action_width = action_name.len() + 1;
-            let hint_width = key_width + action_width + 3;
-
-            if relative_x >= current_x && relative_x < current_x + hint_width {
-                // Clicked on this hint - look up action from keybinding config
-                return self.lookup_footer_action(key);
+        } else if self.state.tab_manager.active_is_file() {
+            if sidebar_focused {
+                FooterContext::Sidebar
+            } else {
+                FooterContext::FileViewer
             }
-            current_x += hint_width;
+        } else {
+            FooterContext::from_state(self.state.view_mode, self.state.input_mode, true)
         }
-        None
     }
 
-    /// Look up the action for a footer key hint using the keybinding config
-    fn lookup_footer_action(&self, key: &str) -> Option<Action> {
-        // Handle compound keys like "j/k" by taking the first one
-        let primary_key = key.split('/').next().unwrap_or(key);
-
-        // Special case for "CR" which should be "<CR>"
-        let key_notation = if primary_key == "CR" {
-            "<CR>".to_string()
-        } else {
-            primary_key.to_string()
-        };
-
-        // Parse the key notation
-        let key_combo = parse_key_notation(&key_notation).ok()?;
-
-        // Determine context from current mode
-        // Keep precedence aligned with handle_footer_click(): sidebar > file-viewer > view_mode
-        let context = if self.state.input_mode == InputMode::SidebarNavigation {
-            KeyContext::from_input_mode(self.state.input_mode, self.state.view_mode)
-        } else if self.state.tab_manager.active_is_file() {
-            KeyContext::Scrolling
-        } else {
-            KeyContext::from_input_mode(self.state.input_mode, self.state.view_mode)
-        };
+    /// Footer for the current mode, with hints taken from the user's keymap
+    fn global_footer(&self) -> GlobalFooter<'_> {
+        GlobalFooter::for_context(self.footer_context(), &self.config().keybindings)
+            .with_spinner(self.state.footer_spinner.as_ref())
+            .with_message(self.state.footer_message.as_deref())
+    }
 
-        // Look up action in keybinding config
-        self.config()
-            .keybindings
-            .get_action(&key_combo, context)
-            .cloned()
+    /// Handle click in footer area
+    /// Returns an action to execute if a valid hint was clicked
+    fn handle_footer_click(&self, x: u16, _y: u16, footer_area: Rect) -> Option<Action> {
+        // Same footer as rendered, so hit-testing matches the visible hints
+        self.global_footer().action_at(footer_area, x)
     }
 
     async fn handle_app_event(&mut self, event: AppEvent) -> anyhow::Result<Vec<Effect>> {
@@ -5151,8 +6806,20 @@ impl App {
                 tab_index,
                 working_dir,
                 result,
+                linked,
+                security,
+                change_flags,
             } => {
-                effects.extend(self.handle_pr_preflight_result(tab_index, working_dir, result));
+                effects.extend(self.handle_pr_preflight_result(
+                    tab_index,
+                    working_dir,
+                    result,
+                    linked,
+                    PrChecks {
+                        security,
+                        change_flags,
+                    },
+                ));
             }
             AppEvent::OpenPrCompleted { result: Err(err) } => {
                 self.show_error(
@@ -5173,36 +6840,207 @@ impl App {
                     self.show_error("Export Failed", &err);
                 }
             },
-            AppEvent::WorkspaceCreated { repo_id, result } => {
-                self.clear_repo_action_busy(repo_id);
-                match result {
-                    Ok(created) => {
-                        self.refresh_sidebar_data();
-                        self.state.sidebar_data.expand_repo(created.repo_id);
-                        if let Some(index) = self.find_workspace_index(created.workspace_id) {
-                            self.state.sidebar_state.tree_state.selected = index;
+            AppEvent::VoiceTranscribed { session_id, result } => {
+                let session = self.state.tab_manager.session_by_id_mut(session_id);
+                match (result, session) {
+                    (Ok(text), Some(session)) => {
+                        // Leave the transcript in the draft so it can be reviewed before sending
+                        let input = session.input_box.input();
+                        if !input.is_empty() && !input.ends_with(char::is_whitespace) {
+                            session.input_box.insert_char(' ');
                         }
-                        // Open workspace, close sidebar, and focus prompt box
-                        self.open_workspace_with_options(created.workspace_id, true);
+                        session.input_box.insert_str(&text);
+                        self.state.set_timed_footer_message(
+                            "Voice prompt transcribed - review and press Enter to send".to_string(),
+                            Duration::from_secs(5),
+                        );
                     }
-                    Err(err) => {
-                        self.show_error("Workspace Creation Failed", &err);
+                    (Ok(_), None) => {
+                        tracing::debug!(%session_id, "Dropping transcript for closed session");
+                        self.state.set_footer_message(None);
+                    }
+                    (Err(err), _) => {
+                        self.state
+                            .set_timed_footer_message(err, Duration::from_secs(5));
                     }
                 }
             }
-            AppEvent::ForkWorkspaceCreated {
-                parent_workspace_id,
+            AppEvent::UrlFetched {
+                session_id,
+                url,
                 result,
             } => {
-                self.clear_workspace_busy(parent_workspace_id);
+                self.state.set_footer_message(None);
+                let Some(session) = self.state.tab_manager.session_by_id_mut(session_id) else {
+                    tracing::debug!(%session_id, "Dropping fetched page for closed session");
+                    return Ok(effects);
+                };
+                let note = match result {
+                    Ok(page) => {
+                        let note = format!(
+                            "Attached \"{}\" ({} KB{}) from {}; it's sent with your next prompt.",
+                            page.title.as_deref().unwrap_or(&page.url),
+                            page.text.len().div_ceil(1024),
+                            if page.truncated { ", truncated" } else { "" },
+                            page.url
+                        );
+                        session.pending_context.push(page);
+                        note
+                    }
+                    Err(err) => format!("Couldn't fetch {url}: {err}"),
+                };
+                session.chat_view.push(ChatMessage::system(note));
+            }
+            AppEvent::PreSubmitHooksFinished {
+                session_id,
+                result,
+                original_prompt,
+                images,
+                image_placeholders,
+            } => {
+                self.state.set_footer_message(None);
+                let Some(tab_index) = self.state.tab_manager.session_index_by_id(session_id) else {
+                    tracing::debug!(%session_id, "Dropping hooked prompt for closed session");
+                    self.release_attachments(&images);
+                    return Ok(effects);
+                };
                 match result {
-                    Ok(created) => {
-                        self.refresh_sidebar_data();
-                        self.state.sidebar_data.expand_repo(created.repo_id);
-                        if let Some(index) = self.find_workspace_index(created.workspace_id) {
-                            self.state.sidebar_state.tree_state.selected = index;
+                    Ok(prompt) => {
+                        effects.extend(self.dispatch_prompt_for_tab(
+                            tab_index,
+                            prompt,
+                            images,
+                            image_placeholders,
+                            false,
+                            None,
+                        )?);
+                    }
+                    Err(rejection) => {
+                        // Back to a draft; submitting again takes a fresh reference
+                        self.release_attachments(&images);
+                        if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
+                            let display = MessageDisplay::Error {
+                                content: rejection.to_string(),
+                            };
+                            session.chat_view.push(display.to_chat_message());
+                            // Don't clobber anything typed while the hooks ran
+                            if session.input_box.input().is_empty() {
+                                let attachments =
+                                    images.into_iter().zip(image_placeholders).collect();
+                                session
+                                    .input_box
+                                    .set_input_with_attachments(original_prompt, attachments);
+                                session.input_box.move_end();
+                            }
                         }
-                        match self.finish_fork_session(created.workspace_id) {
+                    }
+                }
+            }
+            AppEvent::FailedTurnRetryDue { session_id } => {
+                effects.extend(self.retry_failed_turn(session_id)?);
+            }
+            AppEvent::PromptSnapshotTaken {
+                session_id,
+                prompt_index,
+                tree,
+            } => {
+                if let Some(session) = self.state.tab_manager.session_by_id_mut(session_id) {
+                    session.prompt_snapshots.insert(prompt_index, tree);
+                }
+            }
+            AppEvent::WorkspaceRolledBack {
+                session_id,
+                prompt_index,
+                result,
+            } => match result {
+                Ok(summary) => {
+                    let Some(session) = self.state.tab_manager.session_by_id_mut(session_id) else {
+                        return Ok(effects);
+                    };
+                    let display = MessageDisplay::System {
+                        content: format!(
+                            "Workspace rolled back to before prompt {}: {} file(s) restored, {} removed",
+                            prompt_index + 1,
+                            summary.restored,
+                            summary.removed
+                        ),
+                    };
+                    session.chat_view.push(display.to_chat_message());
+                    if let Some(text) = session.chat_view.user_message(prompt_index) {
+                        let text = text.to_string();
+                        session.input_box.set_input(text);
+                        session.input_box.move_end();
+                    }
+                    session.chat_view.scroll_to_bottom();
+                }
+                Err(err) => {
+                    self.show_error("Rollback Failed", &err);
+                }
+            },
+            AppEvent::TranscriptExported { result } => match result {
+                Ok(export) => match export.pdf_path {
+                    None if export.format != TranscriptFormat::Pdf => {
+                        self.show_error_with_details(
+                            "Transcript Exported",
+                            "Transcript has been written with tool calls and turn summaries.",
+                            &format!("File saved to:\n{}", export.path.display()),
+                        );
+                    }
+                    Some(pdf_path) => {
+                        self.show_error_with_details(
+                            "Transcript Exported",
+                            "Transcript has been printed to PDF.",
+                            &format!("File saved to:\n{}", pdf_path.display()),
+                        );
+                    }
+                    None => {
+                        self.show_error_with_details(
+                            "Transcript Exported (HTML)",
+                            "No PDF converter found (install wkhtmltopdf or Chromium). Open the HTML file in a browser to print it.",
+                            &format!("File saved to:\n{}", export.path.display()),
+                        );
+                    }
+                },
+                Err(err) => {
+                    self.show_error("Export Failed", &err);
+                }
+            },
+            AppEvent::WorkspaceCreated { repo_id, result } => {
+                self.clear_repo_action_busy(repo_id);
+                match result {
+                    Ok(created) => {
+                        self.refresh_sidebar_data();
+                        self.state.sidebar_data.expand_repo(created.repo_id);
+                        if let Some(index) = self.find_workspace_index(created.workspace_id) {
+                            self.state.sidebar_state.tree_state.selected = index;
+                        }
+                        // Open workspace, close sidebar, and focus prompt box
+                        self.open_workspace_with_options(created.workspace_id, true);
+                        if !created.bootstrap_commands.is_empty() {
+                            effects.extend(self.start_workspace_bootstrap(
+                                created.workspace_id,
+                                created.bootstrap_commands,
+                            ));
+                        }
+                    }
+                    Err(err) => {
+                        self.show_error("Workspace Creation Failed", &err);
+                    }
+                }
+            }
+            AppEvent::ForkWorkspaceCreated {
+                parent_workspace_id,
+                result,
+            } => {
+                self.clear_workspace_busy(parent_workspace_id);
+                match result {
+                    Ok(created) => {
+                        self.refresh_sidebar_data();
+                        self.state.sidebar_data.expand_repo(created.repo_id);
+                        if let Some(index) = self.find_workspace_index(created.workspace_id) {
+                            self.state.sidebar_state.tree_state.selected = index;
+                        }
+                        match self.finish_fork_session(created.workspace_id) {
                             Ok(mut fork_effects) => {
                                 effects.append(&mut fork_effects);
                             }
@@ -5330,6 +7168,7 @@ impl App {
                 session_id,
                 pid,
                 input_tx,
+                cancel,
             } => {
                 // Store the PID for interrupt support
                 let Some(tab_index) = self.state.tab_manager.session_index_by_id(session_id) else {
@@ -5340,9 +7179,13 @@ impl App {
                     return Ok(effects);
                 };
                 if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
-                    session.agent_pid = Some(pid);
-                    session.agent_pid_start_time = Self::pid_start_time(pid);
+                    // Replayed sessions have no process to signal on interrupt
+                    if pid != NO_PID {
+                        session.agent_pid = Some(pid);
+                        session.agent_pid_start_time = Self::pid_start_time(pid);
+                    }
                     session.agent_input_tx = input_tx;
+                    session.agent_cancel = cancel;
                     tracing::debug!(
                         session_id = %session_id,
                         "Agent started with PID {} for tab {}",
@@ -5371,15 +7214,21 @@ impl App {
                     return Ok(effects);
                 };
                 let is_active_tab = self.state.tab_manager.active_index() == tab_index;
+                let mut failed_turn = None;
                 if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
                     session.stop_processing();
                     session.chat_view.finalize_streaming();
                     session.tools_in_flight = 0;
                     session.set_processing_state(ProcessingState::Thinking);
                     session.agent_input_tx = None;
+                    session.failure = FailureKind::classify_message(&error, None);
+                    failed_turn = session.failure.map(|kind| (kind, session.agent_type));
                     let display = MessageDisplay::Error { content: error };
                     session.chat_view.push(display.to_chat_message());
                 }
+                if let Some((kind, agent_type)) = failed_turn {
+                    self.push_failure_card(tab_index, kind, agent_type);
+                }
                 if is_active_tab {
                     self.state.stop_footer_spinner();
                 }
@@ -5423,6 +7272,7 @@ impl App {
                         session.agent_pid = None;
                         session.agent_pid_start_time = None;
                         session.agent_input_tx = None;
+                        session.agent_cancel = None;
                         // Safety: don't let fork-seed suppression leak into future runs
                         session.suppress_next_assistant_reply = false;
                         session.suppress_next_turn_summary = false;
@@ -5502,6 +7352,62 @@ impl App {
                     );
                 }
             }
+            AppEvent::BootstrapProgress {
+                session_id,
+                progress,
+            } => {
+                let Some(session) = self.state.tab_manager.session_by_id_mut(session_id) else {
+                    tracing::debug!(%session_id, "BootstrapProgress for unknown session; ignoring");
+                    return Ok(effects);
+                };
+                match progress {
+                    BootstrapProgress::Started { index, command } => {
+                        let args = serde_json::json!({ "command": command }).to_string();
+                        session.chat_view.push(
+                            ChatMessage::tool("Bash", args, "Running...")
+                                .with_tool_id(bootstrap_tool_id(index)),
+                        );
+                    }
+                    BootstrapProgress::Output { index, line } => {
+                        session.chat_view.append_tool_output_by_id(
+                            &bootstrap_tool_id(index),
+                            &format!("{line}\n"),
+                        );
+                    }
+                    BootstrapProgress::Finished { index, exit_code } => {
+                        session
+                            .chat_view
+                            .finish_tool_output_by_id(&bootstrap_tool_id(index), exit_code);
+                    }
+                }
+            }
+            AppEvent::BootstrapFinished {
+                session_id,
+                outcome,
+            } => {
+                effects.extend(self.finish_workspace_bootstrap(session_id, outcome)?);
+            }
+            AppEvent::CoverageMeasured { session_id, result } => {
+                effects.extend(self.finish_coverage_measurement(session_id, result)?);
+            }
+            AppEvent::DependenciesScanned { session_id, result } => {
+                self.finish_dependency_scan(session_id, result);
+            }
+            AppEvent::DependencyVerifyProgress {
+                session_id,
+                progress,
+            } => {
+                self.record_dependency_verify_progress(session_id, progress);
+            }
+            AppEvent::DependencyVerifyFinished { session_id, result } => {
+                effects.extend(self.finish_dependency_verification(session_id, result)?);
+            }
+            AppEvent::PreviewsChanged {
+                workspace_id,
+                previews,
+            } => {
+                self.apply_previews(workspace_id, &previews);
+            }
             AppEvent::OpencodeQuestionResponseCompleted { session_id, result } => {
                 let is_active_tab = self
                     .state
@@ -5659,6 +7565,9 @@ impl App {
                         }
                     }
                 }
+                self.update_linked_workspaces(workspace_id, |linked| {
+                    linked.pr = status.clone();
+                });
                 // Update sidebar data when we have an accepted association or when not stale.
                 if !is_stale_pr || any_session_updated {
                     self.state
@@ -5688,6 +7597,9 @@ impl App {
                         session.status_bar.set_git_diff_stats(stats.clone());
                     }
                 }
+                self.update_linked_workspaces(workspace_id, |linked| {
+                    linked.git_stats = Some(stats.clone());
+                });
                 // Also update sidebar data
                 self.state
                     .sidebar_data
@@ -5709,6 +7621,16 @@ impl App {
                 }
                 self.apply_branch_update(workspace_id, branch);
             }
+            GitTrackerUpdate::UpstreamChanged {
+                workspace_id,
+                upstream,
+            } => {
+                for session in self.state.tab_manager.sessions_mut() {
+                    if session.workspace_id == Some(workspace_id) {
+                        session.status_bar.set_upstream(upstream);
+                    }
+                }
+            }
         }
     }
 
@@ -5718,6 +7640,9 @@ impl App {
                 session.status_bar.set_branch_name(branch.clone());
             }
         }
+        self.update_linked_workspaces(workspace_id, |linked| {
+            linked.branch = branch.clone();
+        });
         self.state
             .sidebar_data
             .update_workspace_branch(workspace_id, branch);
@@ -5751,10 +7676,22 @@ impl App {
                 | AgentEvent::AssistantReasoning(_)
                 | AgentEvent::ToolStarted(_)
                 | AgentEvent::ToolCompleted(_)
+                | AgentEvent::SubagentStarted(_)
+                | AgentEvent::SubagentCompleted(_)
+                | AgentEvent::Subagent(_)
                 | AgentEvent::CommandOutput(_)
+                | AgentEvent::CommandOutputDelta(_)
                 | AgentEvent::TurnCompleted(_)
                 | AgentEvent::TurnFailed(_)
         );
+        // Dev servers announced in tool output become workspace previews
+        let detected_previews = if self.config().preview.enabled {
+            previews::event_output(&event)
+                .map(detect_previews)
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
 
         // Track whether we need to stop footer spinner (done after session borrow ends)
         let mut should_stop_footer_spinner = false;
@@ -5762,6 +7699,32 @@ impl App {
         let mut pending_sidebar_pr_update: Option<(Uuid, PrStatus)> = None;
         let mut pending_model_invalidation = false;
         let mut should_drain_queue = false;
+        let mut crashed_agent: Option<AgentType> = None;
+        let mut failed_turn: Option<(FailureKind, AgentType)> = None;
+        let read_aloud_config = self
+            .config()
+            .read_aloud
+            .clone()
+            .filter(|_| is_active_tab && self.state.read_aloud_enabled);
+        let mut read_aloud_text: Option<String> = None;
+        let notify_background = !is_active_tab
+            && self
+                .config()
+                .notifications
+                .should_notify(self.state.terminal_focused);
+        let mut desktop_notification: Option<(String, String)> = None;
+        let hooks = self.core.hooks();
+        let run_post_turn_hooks = hooks.has(HookStage::PostTurn);
+        let default_working_dir = self.config().working_dir.clone();
+        let mut post_turn_hook: Option<(HookContext, TurnReport)> = None;
+        let mut billed_usage: Option<TokenUsage> = None;
+        let mut delivered_seed: Option<(Uuid, String)> = None;
+        let persist_raw_events = self.config().persistence.raw_events;
+        let ends_turn = matches!(
+            &event,
+            AgentEvent::TurnCompleted(_) | AgentEvent::TurnFailed(_)
+        );
+        let turn_succeeded = matches!(&event, AgentEvent::TurnCompleted(_));
 
         {
             let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
@@ -5803,7 +7766,17 @@ impl App {
                     }
                 },
             };
+            if persist_raw_events {
+                app_persistence::queue_raw_event(
+                    self.core.write_behind(),
+                    session_id,
+                    EventDirection::Received,
+                    &event_type,
+                    &raw_json,
+                );
+            }
             session.record_raw_event(EventDirection::Received, event_type, raw_json);
+            session.note_agent_activity();
 
             match event {
                 AgentEvent::SessionInit(init) => {
@@ -5814,9 +7787,14 @@ impl App {
                 }
                 AgentEvent::TurnStarted => {
                     session.is_processing = true;
+                    session.turn_failed_unseen = false;
+                    session.timeline.start_turn(Instant::now());
                     session.update_status();
                 }
                 AgentEvent::TurnCompleted(completed) => {
+                    let turn_usage = completed.usage.clone();
+                    billed_usage = Some(completed.usage.clone());
+                    let cost_before = session.total_cost;
                     session.add_usage(completed.usage);
                     session.stop_processing();
                     if session.inline_prompt.is_none() {
@@ -5836,6 +7814,13 @@ impl App {
                     // Add turn summary to chat
                     if session.suppress_next_turn_summary {
                         session.suppress_next_turn_summary = false;
+                        // The hidden turn was the seed acknowledgement
+                        delivered_seed = session.fork_seed_id.zip(
+                            session
+                                .agent_session_id
+                                .as_ref()
+                                .map(|id| id.as_str().to_string()),
+                        );
                     } else {
                         if session.pending_turn_summary.is_some() {
                             Self::flush_pending_agent_output(session);
@@ -5845,10 +7830,28 @@ impl App {
                         if session.chat_view.streaming_buffer().is_none() {
                             Self::flush_pending_agent_output(session);
                         }
+                        if let Some(config) = &read_aloud_config {
+                            read_aloud_text =
+                                Self::read_aloud_text_for_turn(session, config.max_chars);
+                        }
+                        if notify_background {
+                            desktop_notification =
+                                Some(desktop_notify::turn_notification(&session.tab_name(), None));
+                        }
+                        if run_post_turn_hooks {
+                            let cost = session.total_cost - cost_before;
+                            post_turn_hook = Some((
+                                Self::hook_context(session, &default_working_dir),
+                                Self::turn_report(session, turn_usage, cost),
+                            ));
+                        }
                     }
                 }
                 AgentEvent::TurnFailed(failed) => {
                     session.stop_processing();
+                    if !is_active_tab {
+                        session.turn_failed_unseen = true;
+                    }
                     session.chat_view.finalize_streaming();
                     session.tools_in_flight = 0;
                     session.set_processing_state(ProcessingState::Thinking);
@@ -5859,6 +7862,16 @@ impl App {
                     }
                     session.suppress_next_assistant_reply = false;
                     session.suppress_next_turn_summary = false;
+                    if let Some(kind) = FailureKind::classify_message(&failed.error, None) {
+                        session.failure = Some(kind);
+                        failed_turn = Some((kind, session.agent_type));
+                    }
+                    if notify_background {
+                        desktop_notification = Some(desktop_notify::turn_notification(
+                            &session.tab_name(),
+                            Some(&failed.error),
+                        ));
+                    }
                     let display = MessageDisplay::Error {
                         content: failed.error,
                     };
@@ -5978,6 +7991,18 @@ impl App {
                         // ToolStarted pairs with ToolCompleted for non-shell tools or CommandOutput
                         // for shell tools; these events are mutually exclusive in agent runners.
                         session.tools_in_flight = session.tools_in_flight.saturating_add(1);
+                        session.timeline.tool_started(
+                            Some(tool.tool_id.clone()),
+                            &tool.tool_name,
+                            Instant::now(),
+                        );
+                        if tool.tool_name.eq_ignore_ascii_case("bash") {
+                            if let Some(command) =
+                                tool.arguments.get("command").and_then(|c| c.as_str())
+                            {
+                                session.record_command_started(&tool.tool_id, command);
+                            }
+                        }
 
                         let args_str = if tool.arguments.is_null() {
                             String::new()
@@ -5992,7 +8017,9 @@ impl App {
                             exit_code: None,
                             file_size: None, // Only set for Read tool on images via update_last_tool
                         };
-                        session.chat_view.push(display.to_chat_message());
+                        session
+                            .chat_view
+                            .push(display.to_chat_message().with_tool_id(tool.tool_id.clone()));
                     }
                 }
                 AgentEvent::ControlRequest(request) => {
@@ -6001,7 +8028,68 @@ impl App {
                             .pending_tool_permissions
                             .insert(tool_use_id.clone(), request.request_id.clone());
 
-                        if let Some(response_payload) = session
+                        if edit_review::is_reviewed_tool(&request.tool_name) {
+                            let root = session
+                                .working_dir
+                                .clone()
+                                .unwrap_or_else(|| default_working_dir.clone());
+                            match EditReview::from_tool_call(
+                                &request.tool_name,
+                                &request.input,
+                                &root,
+                            ) {
+                                Ok(review) => {
+                                    session.inline_prompt =
+                                        Some(InlinePromptState::new_edit_review(
+                                            tool_use_id.clone(),
+                                            review,
+                                        ));
+                                    session.chat_view.scroll_to_bottom();
+                                    // Stop footer spinner while awaiting the review
+                                    should_stop_footer_spinner = true;
+                                }
+                                Err(err) => {
+                                    // Let it through: the tool fails the same way and
+                                    // tells the agent why
+                                    tracing::debug!(
+                                        tool_name = request.tool_name,
+                                        error = %err,
+                                        "Edit can't be reviewed; allowing it as proposed"
+                                    );
+                                    session.pending_tool_permissions.remove(&tool_use_id);
+                                    let response_payload = Self::build_permission_allow_response(
+                                        request.input.clone(),
+                                        Some(&tool_use_id),
+                                    );
+                                    match Self::build_control_response_jsonl(
+                                        &request.request_id,
+                                        response_payload,
+                                    ) {
+                                        Ok(jsonl) => {
+                                            if let Some(input_tx) = session.agent_input_tx.clone() {
+                                                tokio::spawn(async move {
+                                                    if let Err(err) = input_tx
+                                                        .send(AgentInput::ClaudeJsonl(jsonl))
+                                                        .await
+                                                    {
+                                                        tracing::warn!(
+                                                            "Failed to allow unreviewable edit: {}",
+                                                            err
+                                                        );
+                                                    }
+                                                });
+                                            }
+                                        }
+                                        Err(err) => {
+                                            tracing::warn!(
+                                                error = %err,
+                                                "Failed to build edit permission response"
+                                            );
+                                        }
+                                    }
+                                }
+                            }
+                        } else if let Some(response_payload) = session
                             .pending_tool_permission_responses
                             .remove(&tool_use_id)
                         {
@@ -6054,6 +8142,9 @@ impl App {
                             0
                         }
                     };
+                    session
+                        .timeline
+                        .tool_completed(&tool.tool_id, Instant::now());
 
                     // Track file changes for write/edit tools
                     if tool.success {
@@ -6067,22 +8158,43 @@ impl App {
                                 // Simple heuristic: look for file paths in result
                                 if let Some(filename) = Self::extract_filename(result) {
                                     // Rough estimate of changes (can be refined)
-                                    session.record_file_change(filename, 5, 2);
+                                    session.record_file_change(&filename, 5, 2);
                                 }
                             }
                         }
                     }
 
+                    session.record_command_finished(
+                        &tool.tool_id,
+                        tool.success,
+                        tool.result
+                            .as_deref()
+                            .or(tool.error.as_deref())
+                            .unwrap_or_default(),
+                    );
+
                     let output = if tool.success {
                         tool.result.unwrap_or_else(|| "Completed".to_string())
                     } else {
                         format!("Error: {}", tool.error.unwrap_or_default())
                     };
                     // Update the existing "Running..." message instead of pushing a new one
-                    if !session.chat_view.update_last_tool(output, None) {
+                    if !session
+                        .chat_view
+                        .update_tool_by_id(&tool.tool_id, output, None)
+                    {
                         tracing::warn!("ToolCompleted: no matching tool message found to update");
                     }
                 }
+                AgentEvent::SubagentStarted(started) => {
+                    Self::handle_subagent_started(session, started);
+                }
+                AgentEvent::SubagentCompleted(completed) => {
+                    Self::handle_subagent_completed(session, completed);
+                }
+                AgentEvent::Subagent(nested) => {
+                    Self::handle_subagent_event(session, nested.parent_tool_id, *nested.event);
+                }
                 AgentEvent::CommandOutput(cmd) => {
                     // Check for PR URL in command output (e.g., from gh pr create)
                     if session.pr_number.is_none() {
@@ -6100,6 +8212,7 @@ impl App {
                         tracing::warn!("CommandOutput: no matching tool message found to update");
                     }
                     if !cmd.is_streaming {
+                        session.record_command_output(&cmd.command, cmd.exit_code, &cmd.output);
                         session.tools_in_flight = match session.tools_in_flight.checked_sub(1) {
                             Some(value) => value,
                             None => {
@@ -6109,9 +8222,34 @@ impl App {
                                 0
                             }
                         };
+                        session.timeline.last_tool_completed(Instant::now());
+                    }
+                }
+                AgentEvent::CommandOutputDelta(delta) => {
+                    if !session
+                        .chat_view
+                        .append_tool_output_by_id(&delta.tool_id, &delta.chunk)
+                    {
+                        tracing::warn!(
+                            tool_id = %delta.tool_id,
+                            "CommandOutputDelta: no matching tool message found"
+                        );
                     }
                 }
                 AgentEvent::Error(err) => {
+                    if err.is_fatal && !is_active_tab {
+                        session.needs_attention = true;
+                        if notify_background {
+                            desktop_notification = Some(desktop_notify::turn_notification(
+                                &session.tab_name(),
+                                Some(&err.message),
+                            ));
+                        }
+                    }
+                    if let Some(kind) = FailureKind::classify(&err) {
+                        session.failure = Some(kind);
+                        failed_turn = Some((kind, session.agent_type));
+                    }
                     let display = MessageDisplay::Error {
                         content: err.message,
                     };
@@ -6122,6 +8260,14 @@ impl App {
                         session.update_status();
                         pending_model_invalidation = true;
                     }
+                    // Only a crash mid-turn is worth restarting; interrupts stop processing first
+                    if err.code.as_deref() == Some(AGENT_CRASHED_CODE)
+                        && session.is_processing
+                        && session.last_prompt.is_some()
+                    {
+                        session.agent_crashed = true;
+                        crashed_agent = Some(session.agent_type);
+                    }
                     if err.is_fatal {
                         session.stop_processing();
                         session.chat_view.finalize_streaming();
@@ -6156,6 +8302,9 @@ impl App {
                         session.chat_view.push(display.to_chat_message());
                     }
                 }
+                AgentEvent::FileChanged(changed) => {
+                    session.record_file_change(&changed.path, 0, 0);
+                }
                 AgentEvent::ContextCompaction(compaction_event) => {
                     use crate::agent::events::ContextWindowState;
                     session.handle_compaction(compaction_event.clone());
@@ -6183,6 +8332,10 @@ impl App {
                 .sidebar_data
                 .update_workspace_pr_status(workspace_id, Some(status));
         }
+        if !detected_previews.is_empty() {
+            let effects = self.record_previews(tab_index, detected_previews);
+            self.run_effects(effects).await?;
+        }
         if pending_model_invalidation {
             if let Some(session_tab_dao) = self.session_tab_dao_clone() {
                 if let Ok(Some(mut tab)) = session_tab_dao.get_by_id(session_id) {
@@ -6204,6 +8357,102 @@ impl App {
             }
         }
 
+        if let Some((kind, agent_type)) = failed_turn {
+            // The suggestion card replaces the generic restart hint
+            crashed_agent = None;
+            self.push_failure_card(tab_index, kind, agent_type);
+        }
+
+        if let Some(agent_type) = crashed_agent {
+            let key = GlobalFooter::key_label_for(
+                &self.config().keybindings,
+                KeyContext::Chat,
+                &Action::RestartAgent,
+            )
+            .unwrap_or_else(|| ":restart_agent".to_string());
+            if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
+                let display = MessageDisplay::System {
+                    content: format!(
+                        "Press {} to restart {} and resend your last prompt.",
+                        key,
+                        agent_type.display_name()
+                    ),
+                };
+                session.chat_view.push(display.to_chat_message());
+            }
+        }
+
+        if let Some((seed_id, agent_session_id)) = delivered_seed {
+            if let Some(fork_seed_dao) = self.fork_seed_dao() {
+                if let Err(err) = fork_seed_dao.mark_delivered(seed_id, &agent_session_id) {
+                    tracing::warn!(
+                        %seed_id,
+                        error = %err,
+                        "Failed to record fork seed delivery"
+                    );
+                }
+            }
+        }
+
+        if let Some(usage) = billed_usage {
+            if let Some(session) = self.state.tab_manager.session(tab_index) {
+                self.core.usage_stats().record_turn(session.agent_type);
+                self.core.record_turn_cost(
+                    session.id,
+                    session.workspace_id,
+                    session.cost_center.as_deref(),
+                    session.agent_type,
+                    session.model.as_deref(),
+                    &usage,
+                );
+            }
+        }
+
+        if let Some((context, turn)) = post_turn_hook {
+            self.run_effects(vec![Effect::RunPostTurnHooks {
+                hooks,
+                context,
+                turn,
+            }])
+            .await?;
+        }
+
+        if let (Some(config), Some(text)) = (read_aloud_config, read_aloud_text) {
+            self.run_effects(vec![Effect::ReadAloud {
+                session_id,
+                text,
+                config,
+            }])
+            .await?;
+        }
+
+        if let Some((summary, body)) = desktop_notification {
+            self.run_effects(vec![Effect::DesktopNotification {
+                session_id,
+                summary,
+                body,
+            }])
+            .await?;
+        }
+
+        // Dependency update runs and coverage tasks move on once the turn
+        // they prompted ends
+        if ends_turn {
+            match self.on_dep_update_turn_finished(session_id, turn_succeeded) {
+                Ok(effects) if !effects.is_empty() => {
+                    self.run_effects(effects).await?;
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    tracing::warn!(%session_id, error = %err, "Failed to advance dependency updates");
+                }
+            }
+            let effects = self.on_coverage_turn_finished(session_id, turn_succeeded);
+            if !effects.is_empty() {
+                self.run_effects(effects).await?;
+            }
+        }
+
         if should_drain_queue {
             match self.drain_queue_for_tab(tab_index) {
                 Ok(effects) if !effects.is_empty() => {
@@ -6216,6 +8465,13 @@ impl App {
             }
         }
 
+        // The turn's messages are final now
+        if ends_turn {
+            if let Some(tab_index) = self.state.tab_manager.session_index_by_id(session_id) {
+                self.persist_session_messages(tab_index);
+            }
+        }
+
         // Stop footer spinner after session borrow is released
         if should_stop_footer_spinner {
             self.state.stop_footer_spinner();
@@ -6636,29 +8892,120 @@ impl App {
         serde_json::Value::Object(response)
     }
 
-    fn build_ask_user_updated_input(
-        prompt: &InlinePromptState,
-        answers: &std::collections::HashMap<String, PromptAnswer>,
-    ) -> serde_json::Value {
-        let questions = match &prompt.prompt_type {
-            InlinePromptType::AskUserQuestion { questions } => questions.clone(),
-            _ => Vec::new(),
+    /// Answer the active session's held edit with the review outcome.
+    ///
+    /// Unlike other inline prompts this resumes the running turn as-is: the
+    /// edit is one tool call inside it, so turn tracking isn't restarted.
+    fn finish_edit_review(&mut self, outcome: ReviewOutcome) -> Vec<Effect> {
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return Vec::new();
+        };
+        let Some(prompt) = session.inline_prompt.take() else {
+            return Vec::new();
+        };
+        let tool_id = prompt.tool_id;
+        let InlinePromptType::EditReview { review } = prompt.prompt_type else {
+            tracing::warn!(tool_id, "Edit review answered for a different prompt");
+            return Vec::new();
+        };
+        let Some(request_id) = session.pending_tool_permissions.remove(&tool_id) else {
+            tracing::warn!(
+                tool_id,
+                "Edit review answered without a pending permission request"
+            );
+            return Vec::new();
         };
 
-        let mut answers_map = serde_json::Map::new();
-        for (question, answer) in answers {
-            let formatted = Self::format_prompt_answer(answer);
-            answers_map.insert(question.clone(), serde_json::Value::String(formatted));
+        let path = &review.display_path;
+        let (response_payload, note) = match outcome {
+            ReviewOutcome::AsProposed => (
+                Self::build_permission_allow_response(review.input.clone(), Some(&tool_id)),
+                None,
+            ),
+            ReviewOutcome::Rejected => (
+                Self::build_permission_deny_response(
+                    format!("The user reviewed this change to {} and rejected it.", path),
+                    Some(&tool_id),
+                ),
+                Some(format!("Rejected edit to {}", path)),
+            ),
+            ReviewOutcome::Reviewed {
+                updated_input,
+                accepted,
+                edited,
+                rejected,
+                ..
+            } => (
+                Self::build_permission_allow_response(updated_input, Some(&tool_id)),
+                Some(format!(
+                    "Applied reviewed edit to {}: {} hunk(s) accepted, {} edited, {} rejected",
+                    path, accepted, edited, rejected
+                )),
+            ),
+        };
+        if let Some(note) = note {
+            session
+                .chat_view
+                .push(MessageDisplay::System { content: note }.to_chat_message());
         }
 
-        serde_json::json!({
-            "questions": questions,
-            "answers": serde_json::Value::Object(answers_map),
-        })
-    }
-
-    fn build_exit_plan_updated_input(prompt: &InlinePromptState) -> serde_json::Value {
-        match &prompt.prompt_type {
+        let jsonl = match Self::build_control_response_jsonl(&request_id, response_payload) {
+            Ok(jsonl) => jsonl,
+            Err(err) => {
+                tracing::error!(error = %err, "Failed to build edit review response");
+                session.chat_view.push(
+                    MessageDisplay::Error {
+                        content: format!("Failed to send edit review: {}", err),
+                    }
+                    .to_chat_message(),
+                );
+                return Vec::new();
+            }
+        };
+        let Some(input_tx) = session.agent_input_tx.clone() else {
+            session.chat_view.push(
+                MessageDisplay::Error {
+                    content: "Cannot reply to edit review: missing streaming input channel. Try restarting the session.".to_string(),
+                }
+                .to_chat_message(),
+            );
+            return Vec::new();
+        };
+        tokio::spawn(async move {
+            if let Err(err) = input_tx.send(AgentInput::ClaudeJsonl(jsonl)).await {
+                tracing::warn!("Failed to send edit review response: {}", err);
+            }
+        });
+        session.set_processing_state(ProcessingState::Thinking);
+        if session.is_processing {
+            self.state.start_footer_spinner(None);
+        }
+        Vec::new()
+    }
+
+    fn build_ask_user_updated_input(
+        prompt: &InlinePromptState,
+        answers: &std::collections::HashMap<String, PromptAnswer>,
+    ) -> serde_json::Value {
+        let questions = match &prompt.prompt_type {
+            InlinePromptType::AskUserQuestion { questions } => questions.clone(),
+            _ => Vec::new(),
+        };
+
+        let mut answers_map = serde_json::Map::new();
+        for (question, answer) in answers {
+            let formatted = Self::format_prompt_answer(answer);
+            answers_map.insert(question.clone(), serde_json::Value::String(formatted));
+        }
+
+        serde_json::json!({
+            "questions": questions,
+            "answers": serde_json::Value::Object(answers_map),
+        })
+    }
+
+    fn build_exit_plan_updated_input(prompt: &InlinePromptState) -> serde_json::Value {
+        match &prompt.prompt_type {
             InlinePromptType::ExitPlanMode { plan_content, .. } => {
                 serde_json::json!({ "plan": plan_content })
             }
@@ -6768,6 +9115,10 @@ impl App {
         }
     }
 
+    /// Submit a prompt, passing it through any pre-submit hooks first.
+    ///
+    /// Hidden prompts and raw stdin payloads are internal (fork seeds, tool
+    /// results) and skip the hooks.
     fn submit_prompt_for_tab(
         &mut self,
         tab_index: usize,
@@ -6776,6 +9127,54 @@ impl App {
         image_placeholders: Vec<String>,
         hidden: bool,
         stdin_payload: Option<String>,
+    ) -> anyhow::Result<Vec<Effect>> {
+        if let Some(owner) = &self.state.observing {
+            let content = format!(
+                "This window only observes: {} owns these sessions. Send prompts from there.",
+                owner.describe()
+            );
+            if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
+                let display = MessageDisplay::Error { content };
+                session.chat_view.push(display.to_chat_message());
+            }
+            return Ok(Vec::new());
+        }
+        let hooks = self.core.hooks();
+        if hidden || stdin_payload.is_some() || !hooks.has(HookStage::PreSubmit) {
+            return self.dispatch_prompt_for_tab(
+                tab_index,
+                prompt,
+                images,
+                image_placeholders,
+                hidden,
+                stdin_payload,
+            );
+        }
+        let Some(session) = self.state.tab_manager.session(tab_index) else {
+            return Ok(Vec::new());
+        };
+        let context = Self::hook_context(session, &self.config().working_dir);
+        if self.state.tab_manager.active_index() == tab_index {
+            self.state
+                .set_footer_message(Some("Running prompt hooks...".to_string()));
+        }
+        Ok(vec![Effect::RunPreSubmitHooks {
+            hooks,
+            context,
+            prompt,
+            images,
+            image_placeholders,
+        }])
+    }
+
+    fn dispatch_prompt_for_tab(
+        &mut self,
+        tab_index: usize,
+        prompt: String,
+        images: Vec<PathBuf>,
+        image_placeholders: Vec<String>,
+        hidden: bool,
+        stdin_payload: Option<String>,
     ) -> anyhow::Result<Vec<Effect>> {
         let mut effects = Vec::new();
 
@@ -6876,15 +9275,31 @@ impl App {
 
         // Add user message to chat and start processing (after validation passes)
         // For hidden prompts (like fork seeds), skip showing in chat and pending_user_message
+        let mut snapshot_prompt = None;
         if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
             if !hidden {
                 let display = MessageDisplay::User {
                     content: display_prompt.clone(),
                 };
                 session.chat_view.push(display.to_chat_message());
+                snapshot_prompt = Some(session.chat_view.user_message_count() - 1);
                 // Store pending message for persistence (cleared on agent confirmation)
                 session.pending_user_message = Some(display_prompt.clone());
+                session.last_prompt = Some(QueuedMessage {
+                    id: Uuid::new_v4(),
+                    mode: QueuedMessageMode::FollowUp,
+                    text: display_prompt.clone(),
+                    images: images
+                        .iter()
+                        .cloned()
+                        .zip(image_placeholders.iter().cloned())
+                        .map(|(path, placeholder)| QueuedImageAttachment { path, placeholder })
+                        .collect(),
+                    created_at: Utc::now(),
+                });
             }
+            session.agent_crashed = false;
+            session.failure = None;
             session.start_processing();
         }
         if self.state.tab_manager.active_index() == tab_index {
@@ -6892,22 +9307,19 @@ impl App {
         }
 
         // Start agent
-        if matches!(agent_type, AgentType::Gemini | AgentType::Opencode) && !images.is_empty() {
+        let capabilities = self.state.tab_manager.session(tab_index).map_or_else(
+            || AgentCapabilities::for_agent(agent_type),
+            |session| session.capabilities,
+        );
+        if !capabilities.supports_images && !images.is_empty() {
             if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
                 session.stop_processing();
                 session.pending_user_message = None;
                 let display = MessageDisplay::Error {
-                    content: match agent_type {
-                        AgentType::Gemini => {
-                            "Image attachments aren't supported for Gemini in Conduit yet."
-                                .to_string()
-                        }
-                        AgentType::Opencode => {
-                            "Image attachments aren't supported for OpenCode in Conduit yet."
-                                .to_string()
-                        }
-                        _ => "Image attachments aren't supported for this agent.".to_string(),
-                    },
+                    content: format!(
+                        "Image attachments aren't supported for {} in Conduit yet.",
+                        agent_type.display_name()
+                    ),
                 };
                 session.chat_view.push(display.to_chat_message());
             }
@@ -6920,7 +9332,11 @@ impl App {
         // Strip placeholders for agents that send images out-of-band.
         if matches!(
             agent_type,
-            AgentType::Codex | AgentType::Claude | AgentType::Gemini | AgentType::Opencode
+            AgentType::Codex
+                | AgentType::Claude
+                | AgentType::Gemini
+                | AgentType::Opencode
+                | AgentType::Local
         ) {
             agent_prompt = Self::strip_image_placeholders(agent_prompt, &image_placeholders);
         }
@@ -6948,6 +9364,22 @@ impl App {
             if let Some(mode_prompt) = mode_prompt {
                 agent_prompt = Self::prepend_mode_prompt(&mode_prompt, &agent_prompt);
             }
+            let fetched = self
+                .state
+                .tab_manager
+                .session_mut(tab_index)
+                .map(|session| std::mem::take(&mut session.pending_context))
+                .unwrap_or_default();
+            agent_prompt = app_prompt::prepend_fetched_context(&agent_prompt, &fetched);
+            let (response_language, response_preset) = self
+                .state
+                .tab_manager
+                .session(tab_index)
+                .map(|session| (session.response_language.clone(), session.response_preset))
+                .unwrap_or_default();
+            agent_prompt =
+                app_prompt::append_language_directive(&agent_prompt, response_language.as_deref());
+            agent_prompt = app_prompt::append_preset_directive(&agent_prompt, response_preset);
         }
 
         // Record user input for debug view (post-processing)
@@ -6976,6 +9408,15 @@ impl App {
             debug_payload["images"] = serde_json::json!(image_paths);
         }
         if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
+            if self.core.config().persistence.raw_events {
+                app_persistence::queue_raw_event(
+                    self.core.write_behind(),
+                    session.id,
+                    EventDirection::Sent,
+                    "UserPrompt",
+                    &debug_payload,
+                );
+            }
             session.record_raw_event(EventDirection::Sent, "UserPrompt", debug_payload);
         }
 
@@ -6997,7 +9438,18 @@ impl App {
                 if let Some(ref input_tx) = session.agent_input_tx {
                     if let Some(payload) = stdin_payload.clone() {
                         let input_tx = input_tx.clone();
+                        let event_tx = self.event_tx.clone();
+                        let snapshot_dir = working_dir.clone();
                         tokio::spawn(async move {
+                            if let Some(prompt_index) = snapshot_prompt {
+                                capture_prompt_snapshot(
+                                    &event_tx,
+                                    session_id,
+                                    prompt_index,
+                                    snapshot_dir,
+                                )
+                                .await;
+                            }
                             if let Err(err) = input_tx.send(AgentInput::ClaudeJsonl(payload)).await
                             {
                                 tracing::warn!("Failed to send streaming prompt: {}", err);
@@ -7015,14 +9467,28 @@ impl App {
             }
         }
 
-        if matches!(agent_type, AgentType::Codex | AgentType::Opencode) {
+        if matches!(
+            agent_type,
+            AgentType::Codex | AgentType::Opencode | AgentType::Local
+        ) {
             let is_active_tab = self.state.tab_manager.active_index() == tab_index;
             if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
                 if let Some(ref input_tx) = session.agent_input_tx {
                     let input_tx = input_tx.clone();
                     let prompt_to_send = agent_prompt.clone();
                     let images_to_send = images.clone();
+                    let event_tx = self.event_tx.clone();
+                    let snapshot_dir = working_dir.clone();
                     tokio::spawn(async move {
+                        if let Some(prompt_index) = snapshot_prompt {
+                            capture_prompt_snapshot(
+                                &event_tx,
+                                session_id,
+                                prompt_index,
+                                snapshot_dir,
+                            )
+                            .await;
+                        }
                         let input = AgentInput::CodexPrompt {
                             text: prompt_to_send,
                             images: images_to_send,
@@ -7049,16 +9515,98 @@ impl App {
             agent_prompt.clone()
         };
 
+        let profile = self.config().agent_profile(agent_type);
         let mut config = AgentStartConfig::new(prompt_for_agent, working_dir)
             .with_tools(self.config().claude_allowed_tools.clone())
             .with_images(images)
-            .with_agent_mode(agent_mode);
+            .with_agent_mode(agent_mode)
+            .with_additional_args(profile.args)
+            .with_env(profile.env);
 
         // Add model if specified
         if let Some(model_id) = model {
             config = config.with_model(model_id);
         }
 
+        // Per-session Codex sandbox/approval overrides
+        if agent_type == AgentType::Codex {
+            if let Some(session) = self.state.tab_manager.session(tab_index) {
+                if let Some(mode) = session.codex_sandbox {
+                    config = config.with_codex_sandbox(mode);
+                }
+                if let Some(policy) = session.codex_approval {
+                    config = config.with_codex_approval(policy);
+                }
+            }
+        }
+
+        // Hold Claude's file edits for review
+        if agent_type == AgentType::Claude {
+            let review_edits = self
+                .state
+                .tab_manager
+                .session(tab_index)
+                .and_then(|session| session.edit_review)
+                .unwrap_or(self.config().review.edits);
+            config = config.with_edit_review(review_edits);
+        }
+
+        // Run in the repository's container when one is configured
+        if let Some(workspace_id) = self
+            .state
+            .tab_manager
+            .session(tab_index)
+            .and_then(|session| session.workspace_id)
+        {
+            config = config
+                .with_execution_target(self.core.execution_target_for_workspace(workspace_id));
+        }
+
+        // Linked repositories; containers only see the session's own workspace
+        let linked_layout = match self.state.tab_manager.session(tab_index) {
+            Some(session) if !session.linked_workspaces.is_empty() => {
+                if config.execution_target == ExecutionTarget::Host {
+                    config = config.with_extra_dirs(
+                        session
+                            .linked_workspaces
+                            .iter()
+                            .map(|linked| linked.path.clone())
+                            .collect(),
+                    );
+                    crate::ui::linked_workspaces::layout_note(
+                        &config.working_dir,
+                        &session.linked_workspaces,
+                    )
+                } else {
+                    tracing::warn!(
+                        session_id = %session.id,
+                        "Linked workspaces are not mounted into the agent's container; skipping them"
+                    );
+                    None
+                }
+            }
+            _ => None,
+        };
+
+        // Per-session base instructions, followed by the linked repository layout
+        let system_prompt = self
+            .state
+            .tab_manager
+            .session(tab_index)
+            .and_then(|session| session.system_prompt.clone());
+        let system_prompt = match (system_prompt, linked_layout) {
+            (Some(prompt), Some(layout)) => Some(format!("{prompt}\n\n{layout}")),
+            (prompt, layout) => prompt.or(layout),
+        };
+        if let Some(system_prompt) = system_prompt {
+            config = config.with_system_prompt(system_prompt);
+        }
+
+        // Capture agent stderr for the diagnostics view
+        if let Some(session) = self.state.tab_manager.session(tab_index) {
+            config = config.with_stderr_buffer(session.stderr.clone());
+        }
+
         // Structured stdin payload (used for tool results / stream-json input)
         if let Some(payload) = stdin_payload {
             config = config
@@ -7083,6 +9631,7 @@ impl App {
             session_id,
             agent_type,
             config,
+            snapshot_prompt,
         });
 
         // Generate title on first user message of a NEW session (no title yet, not already pending)
@@ -7133,11 +9682,19 @@ impl App {
         let mut footer_message: Option<String> = None;
         let mut shell_command: Option<(Uuid, usize, String, Option<PathBuf>)> = None;
         let mut shell_error: Option<String> = None;
+        let mut fetch_request: Option<(Uuid, String)> = None;
+        let mut export_format: Option<TranscriptFormat> = None;
+        let mut memory_command: Option<MemoryCommand> = None;
+        let mut link_command: Option<LinkCommand> = None;
+        let mut deps_command: Option<DepsCommand> = None;
+        let mut coverage_command: Option<CoverageCommand> = None;
         let mut queued_handled = false;
+        let mut retained_images: Vec<PathBuf> = Vec::new();
 
         // Extract config values before the mutable borrow
         let steer_behavior = self.config().steer.behavior;
         let steer_fallback = self.config().steer.fallback;
+        let wait_for_slot = self.should_wait_for_slot(self.state.tab_manager.active_index());
 
         {
             let Some(session) = self.state.tab_manager.active_session_mut() else {
@@ -7158,7 +9715,58 @@ impl App {
             let submission_image_paths = submission.image_paths;
             let submission_image_placeholders = submission.image_placeholders;
 
-            let handled_by_shell = session.input_box.is_shell_mode();
+            if let Some(system_prompt) = parse_system_prompt_command(&submission_text) {
+                session.system_prompt = system_prompt.map(str::to_string);
+                let note = if session.system_prompt.is_some() {
+                    "Base instructions set; they apply the next time the agent starts."
+                } else {
+                    "Base instructions cleared."
+                };
+                session.chat_view.push(ChatMessage::system(note));
+                queued_handled = true;
+            } else if let Some(arg) = parse_response_preset_command(&submission_text) {
+                let note = apply_response_preset_command(session, arg);
+                session.chat_view.push(ChatMessage::system(note));
+                queued_handled = true;
+            } else if let Some(url) = parse_fetch_command(&submission_text) {
+                if url.is_empty() {
+                    session.chat_view.push(ChatMessage::system(
+                        "Usage: /fetch <url> attaches the page's text to your next prompt.",
+                    ));
+                } else {
+                    fetch_request = Some((session.id, url.to_string()));
+                }
+                queued_handled = true;
+            } else if let Some(format) = parse_export_command(&submission_text) {
+                match format {
+                    Ok(format) => export_format = Some(format),
+                    Err(note) => session.chat_view.push(ChatMessage::system(note)),
+                }
+                queued_handled = true;
+            } else if let Some(command) = parse_memory_command(&submission_text) {
+                match command {
+                    Ok(command) => memory_command = Some(command),
+                    Err(note) => session.chat_view.push(ChatMessage::system(note)),
+                }
+                queued_handled = true;
+            } else if let Some(command) = parse_link_command(&submission_text) {
+                link_command = Some(command);
+                queued_handled = true;
+            } else if let Some(command) = parse_deps_command(&submission_text) {
+                match command {
+                    Ok(command) => deps_command = Some(command),
+                    Err(note) => session.chat_view.push(ChatMessage::system(note)),
+                }
+                queued_handled = true;
+            } else if let Some(command) = parse_coverage_command(&submission_text) {
+                match command {
+                    Ok(command) => coverage_command = Some(command),
+                    Err(note) => session.chat_view.push(ChatMessage::system(note)),
+                }
+                queued_handled = true;
+            }
+
+            let handled_by_shell = !queued_handled && session.input_box.is_shell_mode();
             if handled_by_shell {
                 let command = submission_text.trim().to_string();
                 if command.is_empty() {
@@ -7185,6 +9793,50 @@ impl App {
             }
 
             if !queued_handled {
+                // A prompt repeating an earlier one is held back once with a warning;
+                // submitting it again sends it
+                let prompt = normalize_prompt(&submission_text);
+                let confirmed = session
+                    .duplicate_warning
+                    .take()
+                    .is_some_and(|warning| warning.prompt == prompt);
+                let duplicate = (!confirmed)
+                    .then(|| find_duplicate_prompt(session.chat_view.messages(), &submission_text))
+                    .flatten();
+                if let Some(duplicate) = duplicate {
+                    session.input_box.set_input_with_attachments(
+                        submission_text.clone(),
+                        submission_image_paths
+                            .iter()
+                            .cloned()
+                            .zip(submission_image_placeholders.iter().cloned())
+                            .collect(),
+                    );
+                    session
+                        .chat_view
+                        .push(ChatMessage::system(duplicate.warning()));
+                    session.duplicate_warning = Some(DuplicateWarning {
+                        prompt,
+                        message_index: duplicate.message_index,
+                    });
+                    queued_handled = true;
+                }
+            }
+
+            // Past the duplicate check the prompt is committed: whether it is sent
+            // now, queued behind a turn or bootstrap, or parked for an agent slot,
+            // it references its images until it is sent or restored to the input
+            if !queued_handled {
+                retained_images = submission_image_paths.clone();
+            }
+
+            if !queued_handled {
+                let queued_images = submission_image_paths
+                    .iter()
+                    .cloned()
+                    .zip(submission_image_placeholders.iter().cloned())
+                    .map(|(path, placeholder)| QueuedImageAttachment { path, placeholder })
+                    .collect::<Vec<_>>();
                 let effective_mode = if mode == QueuedMessageMode::Steer
                     && steer_behavior == crate::config::SteerBehavior::Soft
                 {
@@ -7194,17 +9846,11 @@ impl App {
                 };
 
                 if session.is_processing {
-                    let images = submission_image_paths
-                        .iter()
-                        .cloned()
-                        .zip(submission_image_placeholders.iter().cloned())
-                        .map(|(path, placeholder)| QueuedImageAttachment { path, placeholder })
-                        .collect::<Vec<_>>();
                     let queued = QueuedMessage {
                         id: Uuid::new_v4(),
                         mode: effective_mode,
                         text: submission_text.clone(),
-                        images,
+                        images: queued_images.clone(),
                         created_at: Utc::now(),
                     };
 
@@ -7245,6 +9891,32 @@ impl App {
                     }
                 }
 
+                if !queued_handled && session.bootstrap_cancel.is_some() {
+                    session.queue_message(QueuedMessage {
+                        id: Uuid::new_v4(),
+                        mode: QueuedMessageMode::FollowUp,
+                        text: submission_text.clone(),
+                        images: queued_images.clone(),
+                        created_at: Utc::now(),
+                    });
+                    footer_message =
+                        Some("Queued until the workspace bootstrap finishes".to_string());
+                    queued_handled = true;
+                }
+
+                if !queued_handled && wait_for_slot {
+                    session.waiting_for_slot = true;
+                    session.queue_message(QueuedMessage {
+                        id: Uuid::new_v4(),
+                        mode: QueuedMessageMode::FollowUp,
+                        text: submission_text.clone(),
+                        images: queued_images.clone(),
+                        created_at: Utc::now(),
+                    });
+                    footer_message = Some("Waiting for a free agent slot".to_string());
+                    queued_handled = true;
+                }
+
                 if !queued_handled {
                     immediate_submit = Some((
                         submission_text,
@@ -7255,6 +9927,8 @@ impl App {
             }
         }
 
+        self.retain_attachments(&retained_images);
+
         if let Some(message) = shell_error {
             self.state
                 .set_timed_footer_message(message, Duration::from_secs(3));
@@ -7271,6 +9945,42 @@ impl App {
             return Ok(effects);
         }
 
+        if let Some(format) = export_format {
+            self.export_active_transcript(format, &mut effects);
+            return Ok(effects);
+        }
+
+        if let Some(command) = memory_command {
+            self.run_memory_command(command);
+            return Ok(effects);
+        }
+
+        if let Some(command) = link_command {
+            effects.extend(self.run_link_command(command));
+            return Ok(effects);
+        }
+
+        if let Some(command) = deps_command {
+            effects.extend(self.run_deps_command(command)?);
+            return Ok(effects);
+        }
+
+        if let Some(command) = coverage_command {
+            effects.extend(self.run_coverage_command(command));
+            return Ok(effects);
+        }
+
+        if let Some((session_id, url)) = fetch_request {
+            self.state
+                .set_footer_message(Some(format!("Fetching {url}…")));
+            effects.push(Effect::FetchUrl {
+                session_id,
+                url,
+                config: self.config().fetch.clone(),
+            });
+            return Ok(effects);
+        }
+
         if let Some(message) = footer_message {
             self.state
                 .set_timed_footer_message(message, Duration::from_secs(3));
@@ -7401,7 +10111,13 @@ impl App {
     ) -> anyhow::Result<()> {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(
+            stdout,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableFocusChange,
+            EnableBracketedPaste
+        )?;
         terminal.clear()?;
         Ok(())
     }
@@ -7444,7 +10160,7 @@ impl App {
             .prefix("conduit-prompt-")
             .suffix(".txt")
             .tempfile()?;
-        std::fs::write(temp.path(), expanded_input)?;
+        std::fs::write(temp.path(), &expanded_input)?;
 
         guard.cleanup_for_suspend()?;
 
@@ -7477,7 +10193,16 @@ impl App {
             return Ok(());
         }
 
-        let edited = std::fs::read_to_string(temp.path())?;
+        let edited = Self::prompt_from_editor(std::fs::read_to_string(temp.path())?);
+        if edited == expanded_input {
+            // Quit without saving; keep the draft and its undo history as they were
+            return Ok(());
+        }
+        // Images whose placeholder was deleted in the editor are dropped
+        let attachments = attachments
+            .into_iter()
+            .filter(|(_, placeholder)| edited.contains(placeholder.as_str()))
+            .collect();
         if let Some(session) = self.state.tab_manager.active_session_mut() {
             session
                 .input_box
@@ -7488,46 +10213,131 @@ impl App {
         Ok(())
     }
 
-    #[cfg(unix)]
-    fn suspend_app(
-        &mut self,
-        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-        guard: &mut TerminalGuard,
-    ) -> anyhow::Result<()> {
-        guard.cleanup_for_suspend()?;
-        let result = unsafe { libc::raise(libc::SIGTSTP) };
-        if result == -1 {
-            let err = io::Error::last_os_error();
-            self.reinitialize_terminal(terminal)?;
-            return Err(anyhow!("SIGTSTP failed: {}", err));
+    /// Drop the newline editors add at the end of the file on save
+    fn prompt_from_editor(mut edited: String) -> String {
+        if edited.ends_with('\n') {
+            edited.pop();
+            if edited.ends_with('\r') {
+                edited.pop();
+            }
         }
-        self.reinitialize_terminal(terminal)?;
-        Ok(())
+        edited
     }
 
-    #[cfg(not(unix))]
-    fn suspend_app(
+    /// Open an edit review hunk in the external editor and record the result
+    fn edit_review_hunk_external(
         &mut self,
-        _terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-        _guard: &mut TerminalGuard,
+        index: usize,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+        guard: &mut TerminalGuard,
     ) -> anyhow::Result<()> {
-        self.state.set_timed_footer_message(
-            "Suspend is not supported on this platform".to_string(),
-            Duration::from_secs(3),
-        );
-        Ok(())
-    }
-
-    /// Initiate fork session flow - validate and show confirmation dialog
-    fn initiate_fork_session(&mut self) {
-        let Some(session) = self.state.tab_manager.active_session() else {
-            return;
+        let Some(editor_parts) = self.resolve_external_editor() else {
+            self.state.set_timed_footer_message(
+                "Set $VISUAL or $EDITOR to edit hunks".to_string(),
+                Duration::from_secs(3),
+            );
+            return Ok(());
         };
 
-        if session.is_processing {
-            self.show_error("Cannot Fork", "Wait for the current response to finish.");
-            return;
-        }
+        let (text, extension) = {
+            let Some(prompt) = self
+                .state
+                .tab_manager
+                .active_session()
+                .and_then(|session| session.inline_prompt.as_ref())
+            else {
+                return Ok(());
+            };
+            let InlinePromptType::EditReview { review } = &prompt.prompt_type else {
+                return Ok(());
+            };
+            let Some(text) = prompt.hunk_edit_text(index) else {
+                return Ok(());
+            };
+            // Keep the file's extension so the editor highlights it
+            let extension = Path::new(&review.display_path)
+                .extension()
+                .map(|ext| format!(".{}", ext.to_string_lossy()))
+                .unwrap_or_else(|| ".txt".to_string());
+            (text, extension)
+        };
+
+        let temp = Builder::new()
+            .prefix("conduit-hunk-")
+            .suffix(&extension)
+            .tempfile()?;
+        std::fs::write(temp.path(), text)?;
+
+        guard.cleanup_for_suspend()?;
+        let status = {
+            let mut parts = editor_parts.into_iter();
+            match parts.next() {
+                Some(command) => Command::new(command).args(parts).arg(temp.path()).status(),
+                None => Err(io::Error::other("external editor is not configured")),
+            }
+        };
+        self.reinitialize_terminal(terminal)?;
+
+        if !status?.success() {
+            self.state.set_timed_footer_message(
+                "External editor cancelled".to_string(),
+                Duration::from_secs(3),
+            );
+            return Ok(());
+        }
+
+        let edited = std::fs::read_to_string(temp.path())?;
+        if let Some(prompt) = self
+            .state
+            .tab_manager
+            .active_session_mut()
+            .and_then(|session| session.inline_prompt.as_mut())
+        {
+            prompt.set_hunk_text(index, edited);
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn suspend_app(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+        guard: &mut TerminalGuard,
+    ) -> anyhow::Result<()> {
+        guard.cleanup_for_suspend()?;
+        let result = unsafe { libc::raise(libc::SIGTSTP) };
+        if result == -1 {
+            let err = io::Error::last_os_error();
+            self.reinitialize_terminal(terminal)?;
+            return Err(anyhow!("SIGTSTP failed: {}", err));
+        }
+        self.reinitialize_terminal(terminal)?;
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn suspend_app(
+        &mut self,
+        _terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+        _guard: &mut TerminalGuard,
+    ) -> anyhow::Result<()> {
+        self.state.set_timed_footer_message(
+            "Suspend is not supported on this platform".to_string(),
+            Duration::from_secs(3),
+        );
+        Ok(())
+    }
+
+    /// Initiate fork session flow - validate and show confirmation dialog
+    fn initiate_fork_session(&mut self) {
+        let Some(session) = self.state.tab_manager.active_session() else {
+            return;
+        };
+
+        if session.is_processing {
+            self.show_error("Cannot Fork", "Wait for the current response to finish.");
+            return;
+        }
 
         let parent_workspace_id = match session.workspace_id {
             Some(id) => id,
@@ -7564,7 +10374,19 @@ impl App {
             .get_current_branch(&workspace.path)
             .unwrap_or_else(|_| workspace.branch.clone());
 
-        let seed_prompt = app_prompt::build_fork_seed_prompt(session.chat_view.messages());
+        // Repeated forks of an unchanged transcript reuse the seed built last time
+        let Some(seed) = self
+            .state
+            .tab_manager
+            .active_session_mut()
+            .map(AgentSession::fork_seed)
+        else {
+            return;
+        };
+        let Some(session) = self.state.tab_manager.active_session() else {
+            return;
+        };
+        let seed_prompt = seed.seed_prompt;
 
         let model_id = session
             .model
@@ -7630,7 +10452,8 @@ impl App {
                 .as_ref()
                 .map(|s| s.as_str().to_string()),
             parent_workspace_id,
-            seed_prompt: Arc::from(seed_prompt),
+            seed_prompt,
+            seed_prompt_hash: seed.hash,
             token_estimate,
             context_window,
             fork_seed_id: None,
@@ -7677,13 +10500,29 @@ impl App {
             }
         };
 
-        let seed_prompt_hash = app_prompt::compute_seed_prompt_hash(&pending.seed_prompt);
+        // Keep the seed on disk under its hash so a forked tab that never
+        // reached its agent can still be seeded after a restart
+        let seed_prompt_path = match cache_seed_prompt(
+            &crate::util::fork_seeds_dir(),
+            &pending.seed_prompt_hash,
+            &pending.seed_prompt,
+        ) {
+            Ok(path) => Some(path.to_string_lossy().into_owned()),
+            Err(err) => {
+                tracing::warn!(
+                    hash = %pending.seed_prompt_hash,
+                    error = %err,
+                    "Failed to cache fork seed prompt"
+                );
+                None
+            }
+        };
         let fork_seed = ForkSeed::new(
             pending.agent_type,
             pending.parent_session_id.clone(),
             Some(pending.parent_workspace_id),
-            seed_prompt_hash,
-            None,
+            pending.seed_prompt_hash.clone(),
+            seed_prompt_path,
             pending.token_estimate,
             pending.context_window,
         );
@@ -7751,6 +10590,7 @@ impl App {
         session.model_invalid = false;
         session.agent_mode = pending.agent_mode;
         session.fork_seed_id = Some(fork_seed_id);
+        session.fork_seed_hash = Some(pending.seed_prompt_hash.clone());
         session.suppress_next_assistant_reply = true;
         session.suppress_next_turn_summary = true;
         session.update_status();
@@ -8055,6 +10895,8 @@ impl App {
         tab_index: usize,
         working_dir: std::path::PathBuf,
         preflight: crate::git::PrPreflightResult,
+        linked: Vec<LinkedPrPreflight>,
+        checks: PrChecks,
     ) -> Vec<Effect> {
         let effects = Vec::new();
         let mut sidebar_pr_update: Option<(Uuid, PrStatus)> = None;
@@ -8146,6 +10988,10 @@ impl App {
             }
         }
 
+        // With linked repositories still missing PRs, creating those takes
+        // precedence over opening the existing one
+        let linked_need_prs = linked.iter().any(LinkedPrPreflight::needs_pr);
+
         // If PR exists, show confirmation dialog to open in browser
         if let Some(ref pr) = preflight.existing_pr {
             if pr.exists {
@@ -8165,6 +11011,21 @@ impl App {
                     }
                 }
 
+                if linked_need_prs {
+                    if let Some((workspace_id, status)) = sidebar_pr_update.take() {
+                        self.state
+                            .sidebar_data
+                            .update_workspace_pr_status(workspace_id, Some(status));
+                    }
+                    return self.confirm_pr_creation(
+                        tab_index,
+                        working_dir,
+                        preflight,
+                        linked,
+                        checks,
+                    );
+                }
+
                 let pr_url = pr.url.clone().unwrap_or_else(|| "Unknown URL".to_string());
                 self.state.close_overlays();
                 self.state.confirmation_dialog_state.show(
@@ -8198,8 +11059,64 @@ impl App {
                 .clear_workspace_pr_status(workspace_id);
         }
 
+        self.confirm_pr_creation(tab_index, working_dir, preflight, linked, checks)
+    }
+
+    /// Ask before handing the PR workflow to the agent. Blocking security
+    /// findings and unacknowledged flagged changes are dealt with first.
+    fn confirm_pr_creation(
+        &mut self,
+        tab_index: usize,
+        working_dir: std::path::PathBuf,
+        preflight: crate::git::PrPreflightResult,
+        linked: Vec<LinkedPrPreflight>,
+        checks: PrChecks,
+    ) -> Vec<Effect> {
+        let PrChecks {
+            security,
+            change_flags,
+        } = checks;
+        if let Some(report) = security
+            .as_ref()
+            .filter(|report| !report.blocking().is_empty())
+        {
+            self.record_security_scan(tab_index, &working_dir, report);
+            self.confirm_security_fix(tab_index, working_dir, report);
+            return Vec::new();
+        }
+
         // Build warnings for confirmation dialog
         let mut warnings = Vec::new();
+        match change_flags {
+            Some(Ok(flags)) => {
+                let pending = self.record_change_flags(tab_index, &working_dir, flags);
+                if !pending.is_empty() {
+                    self.confirm_change_flags(
+                        tab_index,
+                        working_dir,
+                        preflight,
+                        linked,
+                        security,
+                        pending,
+                    );
+                    return Vec::new();
+                }
+            }
+            Some(Err(err)) => {
+                tracing::warn!(error = %err, "Failed to check changed files before PR creation");
+                warnings.push(format!("Changed files weren't checked: {err}"));
+            }
+            None => {}
+        }
+        if let Some(report) = security {
+            self.record_security_scan(tab_index, &working_dir, &report);
+            let count = report.warnings().len();
+            if count > 0 {
+                warnings.push(format!(
+                    "{count} security finding(s) below the blocking severity (listed in the chat)"
+                ));
+            }
+        }
         if preflight.uncommitted_count > 0 {
             warnings.push(format!(
                 "{} file(s) will be auto-committed",
@@ -8210,43 +11127,66 @@ impl App {
             warnings.push("Branch will be pushed to remote".to_string());
         }
 
+        let mut message = format!(
+            "Branch: {}\nTarget: {}",
+            preflight.branch_name, preflight.target_branch
+        );
+        for repo in &linked {
+            let state = if repo.needs_pr() {
+                format!(
+                    "{} → {}",
+                    repo.preflight.branch_name, repo.preflight.target_branch
+                )
+            } else if repo.preflight.on_main_branch {
+                format!("on {}, skipped", repo.preflight.branch_name)
+            } else {
+                "PR already open".to_string()
+            };
+            message.push_str(&format!("\nLinked: {} ({state})", repo.name));
+            if repo.needs_pr() && repo.preflight.uncommitted_count > 0 {
+                warnings.push(format!(
+                    "{} file(s) in {} will be auto-committed",
+                    repo.preflight.uncommitted_count, repo.name
+                ));
+            }
+        }
+        let confirm_label = if linked.iter().any(LinkedPrPreflight::needs_pr) {
+            "Create PRs"
+        } else {
+            "Create PR"
+        };
+
         // Show confirmation dialog (replace loading state)
         self.state.close_overlays();
         self.state.confirmation_dialog_state.show(
             "Create Pull Request",
-            format!(
-                "Branch: {}\nTarget: {}",
-                preflight.branch_name, preflight.target_branch
-            ),
+            message,
             warnings,
             ConfirmationType::Info,
-            "Create PR",
+            confirm_label,
             Some(ConfirmationContext::CreatePullRequest {
                 tab_index,
                 working_dir,
                 preflight,
+                linked,
             }),
         );
         // Already in Confirming mode
-        effects
+        Vec::new()
     }
 
-    /// Submit the PR workflow prompt to the current chat
-    fn submit_pr_workflow(
-        &mut self,
-        tab_index: usize,
-        working_dir: std::path::PathBuf,
-        preflight: crate::git::PrPreflightResult,
-    ) -> anyhow::Result<Vec<Effect>> {
-        let target_tab_index = self
-            .state
+    /// Tab of the session a PR flow started in. Tab indices may shift while
+    /// the preflight runs, so `tab_index` is only trusted if it still shows
+    /// `working_dir`.
+    fn pr_tab_index(&self, tab_index: usize, working_dir: &Path) -> Option<usize> {
+        self.state
             .tab_manager
             .session(tab_index)
             .and_then(|session| {
                 let matches_dir = session
                     .working_dir
                     .as_ref()
-                    .is_some_and(|dir| dir == &working_dir);
+                    .is_some_and(|dir| dir == working_dir);
                 matches_dir.then_some(tab_index)
             })
             .or_else(|| {
@@ -8258,11 +11198,26 @@ impl App {
                         session
                             .working_dir
                             .as_ref()
-                            .is_some_and(|dir| dir == &working_dir)
+                            .is_some_and(|dir| dir == working_dir)
                     })
-            });
+            })
+    }
+
+    /// Submit the PR workflow prompt to the current chat
+    fn submit_pr_workflow(
+        &mut self,
+        tab_index: usize,
+        working_dir: std::path::PathBuf,
+        preflight: crate::git::PrPreflightResult,
+        linked: Vec<LinkedPrPreflight>,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let target_tab_index = self.pr_tab_index(tab_index, &working_dir);
         // Generate prompt for PR creation
-        let prompt = PrManager::generate_pr_prompt(&preflight);
+        let prompt = if linked.is_empty() {
+            PrManager::generate_pr_prompt(&preflight)
+        } else {
+            PrManager::generate_linked_pr_prompt(&preflight, &working_dir, &linked)
+        };
 
         let Some(target_tab_index) = target_tab_index else {
             self.show_error(
@@ -8284,6 +11239,9 @@ impl App {
     }
 
     fn restore_queued_to_input(&mut self, message: crate::data::QueuedMessage) {
+        // Back to a draft; submitting again takes a fresh reference
+        let image_paths: Vec<PathBuf> = message.images.iter().map(|img| img.path.clone()).collect();
+        self.release_attachments(&image_paths);
         if let Some(session) = self.state.tab_manager.active_session_mut() {
             let attachments = message
                 .images
@@ -8324,9 +11282,120 @@ impl App {
         if let Some(session) = self.state.tab_manager.active_session_mut() {
             session.queue_selection = None;
         }
+        self.state.queue_overlay = false;
         self.state.input_mode = InputMode::Normal;
     }
 
+    /// Open the queue editor as a full overlay, or toggle the overlay when
+    /// the inline editor is already active.
+    fn expand_queue_editor(&mut self) {
+        if self.state.input_mode == InputMode::QueueEditing {
+            self.state.queue_overlay = !self.state.queue_overlay;
+            return;
+        }
+        self.open_queue_editor();
+        if self.state.input_mode == InputMode::QueueEditing {
+            self.state.queue_overlay = true;
+        }
+    }
+
+    /// Inline queue panel settings for the current config and toggle state
+    pub(super) fn queue_panel(&self) -> app_queue::QueuePanel {
+        let mut panel = app_queue::QueuePanel::new(
+            self.config().ui.queue_max_lines,
+            self.state.queue_collapsed,
+        );
+        panel.hidden = self.state.queue_overlay;
+        panel
+    }
+
+    /// Put a past prompt (the one last jumped to, else the latest) back in
+    /// the input for editing and resending
+    fn edit_past_prompt(&mut self) {
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return;
+        };
+        let Some((_, text)) = session.chat_view.target_user_message() else {
+            self.state
+                .set_timed_footer_message("No prompt to edit".to_string(), Duration::from_secs(3));
+            return;
+        };
+        let text = text.to_string();
+        session.input_box.set_input(text);
+        session.input_box.move_end();
+        self.state.input_mode = InputMode::Normal;
+    }
+
+    /// Ask to roll the workspace back to before a past prompt, then edit it
+    fn prompt_rollback_to_past_prompt(&mut self) {
+        let Some(session) = self.state.tab_manager.active_session() else {
+            return;
+        };
+        if session.is_processing {
+            self.state.set_timed_footer_message(
+                "Wait for the current turn to finish before rolling back".to_string(),
+                Duration::from_secs(5),
+            );
+            return;
+        }
+        let Some((prompt_index, text)) = session.chat_view.target_user_message() else {
+            self.state.set_timed_footer_message(
+                "No prompt to roll back to".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        };
+        if !session.prompt_snapshots.contains_key(&prompt_index) {
+            self.state.set_timed_footer_message(
+                "No workspace snapshot for that prompt".to_string(),
+                Duration::from_secs(5),
+            );
+            return;
+        }
+        let session_id = session.id;
+        let preview: String = text
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .take(60)
+            .collect();
+
+        self.state.close_overlays();
+        self.state.confirmation_dialog_state.show(
+            "Roll Back Workspace",
+            format!(
+                "Restore the workspace to how it was before this prompt?\n\"{}\"",
+                preview
+            ),
+            vec![
+                "Changes made since then are discarded, including your own.".to_string(),
+                "Files created since then are deleted.".to_string(),
+                "Commits are kept; only files are restored.".to_string(),
+            ],
+            ConfirmationType::Danger,
+            "Roll Back",
+            Some(ConfirmationContext::RollbackPrompt {
+                session_id,
+                prompt_index,
+            }),
+        );
+        self.state.input_mode = InputMode::Confirming;
+    }
+
+    fn confirm_prompt_rollback(&mut self, session_id: Uuid, prompt_index: usize) -> Option<Effect> {
+        let default_working_dir = self.config().working_dir.clone();
+        let session = self.state.tab_manager.session_by_id_mut(session_id)?;
+        let tree = session.prompt_snapshots.get(&prompt_index)?.clone();
+        let working_dir = session.working_dir.clone().unwrap_or(default_working_dir);
+        Some(Effect::RollbackWorkspace {
+            session_id,
+            prompt_index,
+            working_dir,
+            tree,
+        })
+    }
+
     fn show_steer_fallback_prompt(&mut self, message_id: Uuid) {
         self.state.close_overlays();
         self.state.confirmation_dialog_state.show(
@@ -8374,6 +11443,20 @@ impl App {
     }
 
     fn drain_queue_for_tab(&mut self, tab_index: usize) -> anyhow::Result<Vec<Effect>> {
+        if self.should_wait_for_slot(tab_index) {
+            if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
+                if !session.queued_messages.is_empty() {
+                    session.waiting_for_slot = true;
+                    session.update_status();
+                }
+            }
+            return Ok(Vec::new());
+        }
+        self.submit_queued_for_tab(tab_index)
+    }
+
+    /// Submit the next queued message(s) for a tab, ignoring the concurrent-agent limit.
+    fn submit_queued_for_tab(&mut self, tab_index: usize) -> anyhow::Result<Vec<Effect>> {
         let mut effects = Vec::new();
         let mut queued: Vec<QueuedMessage> = Vec::new();
         let (queue_mode, queue_delivery) = (self.config().queue.mode, self.config().queue.delivery);
@@ -8477,12 +11560,15 @@ impl App {
             (Rect::default(), size)
         };
 
+        // Zen mode hides the tab bar, session header, status bar, and footer
+        let zen_mode = self.zen_mode_active();
+
         // Split right area vertically to reserve bottom row for footer
         let right_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Min(5),    // Content area (chat + status bar + gap)
-                Constraint::Length(1), // Footer (only in content area)
+                Constraint::Min(5), // Content area (chat + status bar + gap)
+                Constraint::Length(if zen_mode { 0 } else { 1 }), // Footer (only in content area)
             ])
             .split(right_area);
 
@@ -8498,6 +11584,7 @@ impl App {
 
         // Render sidebar if visible
         if self.state.sidebar_state.visible {
+            let started = self.state.frame_diagnostics.start();
             let sidebar =
                 Sidebar::new(&self.state.sidebar_data).with_spinner_frame(self.state.spinner_frame);
             ratatui::widgets::StatefulWidget::render(
@@ -8506,17 +11593,24 @@ impl App {
                 f.buffer_mut(),
                 &mut self.state.sidebar_state,
             );
+            self.state
+                .frame_diagnostics
+                .record(DrawSection::Sidebar, started);
         }
 
         // Check if active tab is a file viewer - render it separately
         if self.state.tab_manager.active_is_file() {
+            let started = self.state.frame_diagnostics.start();
             self.render_file_viewer_tab(content_area, footer_area, f);
+            self.state
+                .frame_diagnostics
+                .record(DrawSection::Panels, started);
         } else {
             match self.state.view_mode {
                 ViewMode::Chat => {
                     // Handle empty state - no tabs open
                     if self.state.tab_manager.is_empty() {
-                        use crate::ui::components::{text_muted, FooterContext};
+                        use crate::ui::components::text_muted;
                         use ratatui::style::Style;
                         use ratatui::text::{Line, Span};
                         use ratatui::widgets::{Paragraph, Widget};
@@ -8577,6 +11671,84 @@ impl App {
                             )));
                         }
 
+                        // Session templates: one line per entry, plus a blank spacer
+                        let templates = &self.config().templates;
+                        if !templates.is_empty() {
+                            use crate::ui::components::{accent_primary, text_primary};
+                            lines.push(Line::from(""));
+                            for (index, template) in templates.iter().enumerate() {
+                                let mut details = vec![template.agent_type.as_str().to_string()];
+                                if let Some(model) = template.model.as_deref() {
+                                    details.push(model.to_string());
+                                }
+                                if let Some(workspace) = template.workspace.as_deref() {
+                                    details.push(workspace.to_string());
+                                }
+                                lines.push(Line::from(vec![
+                                    Span::styled(
+                                        format!("{}  ", index + 1),
+                                        Style::default().fg(accent_primary()),
+                                    ),
+                                    Span::styled(
+                                        template.name.clone(),
+                                        Style::default().fg(text_primary()),
+                                    ),
+                                    Span::styled(
+                                        format!("  {}", details.join(" · ")),
+                                        Style::default().fg(text_muted()),
+                                    ),
+                                ]));
+                            }
+                        }
+                        let template_lines = if templates.is_empty() {
+                            0
+                        } else {
+                            templates.len() as u16 + 1
+                        };
+
+                        // Recent sessions are numbered after the templates, up to 9
+                        let recent_count = self
+                            .state
+                            .recent_sessions
+                            .len()
+                            .min(9usize.saturating_sub(templates.len()));
+                        if recent_count > 0 {
+                            use crate::ui::components::{accent_primary, text_primary};
+                            lines.push(Line::from(""));
+                            for (offset, recent) in self
+                                .state
+                                .recent_sessions
+                                .iter()
+                                .take(recent_count)
+                                .enumerate()
+                            {
+                                let mut details = vec![recent.agent_type.as_str().to_string()];
+                                if let Some(location) = recent.location.as_deref() {
+                                    details.push(location.to_string());
+                                }
+                                details.push(relative_time(recent.last_active));
+                                lines.push(Line::from(vec![
+                                    Span::styled(
+                                        format!("{}  ", templates.len() + offset + 1),
+                                        Style::default().fg(accent_primary()),
+                                    ),
+                                    Span::styled(
+                                        recent.title.clone(),
+                                        Style::default().fg(text_primary()),
+                                    ),
+                                    Span::styled(
+                                        format!("  {}", details.join(" · ")),
+                                        Style::default().fg(text_muted()),
+                                    ),
+                                ]));
+                            }
+                        }
+                        let recent_lines = if recent_count == 0 {
+                            0
+                        } else {
+                            recent_count as u16 + 1
+                        };
+
                         let paragraph =
                             Paragraph::new(lines).alignment(ratatui::layout::Alignment::Center);
 
@@ -8584,7 +11756,10 @@ impl App {
                         let message_area = chunks[1];
                         // First-time: 7 logo + 3 blank + 1 message = 11 lines
                         // Returning: 7 logo + 3 blank + 5 message = 15 lines
-                        let text_height = if is_first_time { 11u16 } else { 15u16 };
+                        let text_height = (if is_first_time { 11u16 } else { 15u16 }
+                            + template_lines
+                            + recent_lines)
+                            .min(message_area.height);
                         let vertical_offset = message_area.height.saturating_sub(text_height) / 2;
                         let centered_area = Rect {
                             x: message_area.x,
@@ -8664,16 +11839,7 @@ impl App {
                         }
 
                         // Draw footer for empty state (sidebar-aware)
-                        let footer_context =
-                            if self.state.input_mode == InputMode::SidebarNavigation {
-                                FooterContext::Sidebar
-                            } else {
-                                FooterContext::Empty
-                            };
-                        let footer = GlobalFooter::for_context(footer_context)
-                            .with_spinner(self.state.footer_spinner.as_ref())
-                            .with_message(self.state.footer_message.as_deref());
-                        footer.render(footer_area, f.buffer_mut());
+                        self.global_footer().render(footer_area, f.buffer_mut());
 
                         return;
                     }
@@ -8694,6 +11860,10 @@ impl App {
                         .map(|s| s.inline_prompt.is_some())
                         .unwrap_or(false);
 
+                    // Zen mode keeps the input to a single row while it's empty
+                    if let Some(session) = self.state.tab_manager.active_session_mut() {
+                        session.input_box.set_compact(zen_mode);
+                    }
                     let input_height = if has_inline_prompt {
                         0 // No input box when inline prompt is active
                     } else if let Some(session) = self.state.tab_manager.active_session() {
@@ -8704,16 +11874,17 @@ impl App {
                         3 // Minimum height
                     };
 
-                    // When inline prompt is active, hide status bar and gap too
-                    let status_bar_height = if has_inline_prompt { 0 } else { 1 };
-                    let gap_height = if has_inline_prompt { 0 } else { 1 };
+                    // When inline prompt is active (or in zen mode), hide status bar and gap too
+                    let status_bar_height = if has_inline_prompt || zen_mode { 0 } else { 1 };
+                    let gap_height = if has_inline_prompt || zen_mode { 0 } else { 1 };
+                    let chrome_height = if zen_mode { 0 } else { 1 };
 
                     // Chat layout with session header, input box, status bar, and gap
                     let chunks = Layout::default()
                         .direction(Direction::Vertical)
                         .constraints([
-                            Constraint::Length(1),                 // Tab bar
-                            Constraint::Length(1),                 // Session header
+                            Constraint::Length(chrome_height),     // Tab bar
+                            Constraint::Length(chrome_height),     // Session header
                             Constraint::Min(5),                    // Chat view
                             Constraint::Length(input_height),      // Input box (dynamic)
                             Constraint::Length(status_bar_height), // Status bar (hidden during inline prompt)
@@ -8790,8 +11961,8 @@ impl App {
 
                     // Draw separator line in the gap row (▀ characters)
                     // Foreground = status bar bg, background = base bg (creates rounded bottom edge)
-                    // Skip when inline prompt is active (gap row is hidden)
-                    if !has_inline_prompt {
+                    // Skip when the gap row is hidden (inline prompt or zen mode)
+                    if gap_height > 0 {
                         use crate::ui::components::status_bar_bg;
                         for x in gap_area_inner.x..gap_area_inner.x + gap_area_inner.width {
                             buf[(x, gap_area_inner.y)]
@@ -8802,7 +11973,7 @@ impl App {
 
                     // Store layout areas for mouse hit-testing
                     // Set hidden areas to None when inline prompt is active to avoid hit-testing confusion
-                    self.state.tab_bar_area = Some(tab_bar_chunk);
+                    self.state.tab_bar_area = if zen_mode { None } else { Some(tab_bar_chunk) };
                     self.state.chat_area = Some(chat_chunk);
                     self.state.raw_events_area = None;
                     self.state.input_area = if has_inline_prompt {
@@ -8810,30 +11981,50 @@ impl App {
                     } else {
                         Some(input_area_inner)
                     };
-                    self.state.status_bar_area = if has_inline_prompt {
+                    self.state.status_bar_area = if has_inline_prompt || zen_mode {
                         None
                     } else {
                         Some(status_bar_area_inner)
                     };
-                    self.state.footer_area = Some(footer_area);
+                    self.state.footer_area = if zen_mode { None } else { Some(footer_area) };
 
-                    // Draw tab bar (unfocused when sidebar is focused)
-                    let tabs_focused = self.state.input_mode != InputMode::SidebarNavigation;
-                    self.ensure_tab_bar_scroll(tab_bar_chunk.width, tabs_focused);
-                    let tab_bar = self.build_tab_bar(tabs_focused);
-                    tab_bar.render(tab_bar_chunk, f.buffer_mut());
+                    if !zen_mode {
+                        let started = self.state.frame_diagnostics.start();
+                        // Draw tab bar (unfocused when sidebar is focused)
+                        let tabs_focused = self.state.input_mode != InputMode::SidebarNavigation;
+                        self.ensure_tab_bar_scroll(tab_bar_chunk.width, tabs_focused);
+                        let tab_bar = self.build_tab_bar(tabs_focused);
+                        tab_bar.render(tab_bar_chunk, f.buffer_mut());
 
-                    // Draw session header (below tab bar)
-                    let session_title = self
-                        .state
-                        .tab_manager
-                        .active_session()
-                        .and_then(|s| s.title.as_deref());
-                    SessionHeader::new(session_title).render(header_chunk, f.buffer_mut());
+                        // Draw session header (below tab bar)
+                        let active_session = self.state.tab_manager.active_session();
+                        SessionHeader::new(active_session.and_then(|s| s.display_title()))
+                            .with_language(
+                                active_session.and_then(|s| s.response_language.as_deref()),
+                            )
+                            .with_preset(
+                                active_session
+                                    .and_then(|s| s.response_preset)
+                                    .map(ResponsePreset::as_str),
+                            )
+                            .render(header_chunk, f.buffer_mut());
+                        self.state
+                            .frame_diagnostics
+                            .record(DrawSection::TabBar, started);
+                    }
 
                     // Draw active session components
                     let is_command_mode = self.state.input_mode == InputMode::Command;
                     let show_chat_scrollbar = self.config().ui.show_chat_scrollbar;
+                    let queue_panel = self.queue_panel();
+                    let show_cost = self.config().show_cost;
+                    let tabs_cost: f64 = self
+                        .state
+                        .tab_manager
+                        .sessions()
+                        .iter()
+                        .map(|session| session.total_cost)
+                        .sum();
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
                         // Use full chat area - prompt is now rendered as part of scrollable content
                         let chat_area = chat_chunk;
@@ -8852,8 +12043,12 @@ impl App {
                                 None
                             };
                         let input_mode = self.state.input_mode;
-                        let queue_lines =
-                            app_queue::build_queue_lines(session, chat_area.width, input_mode);
+                        let queue_lines = app_queue::build_queue_lines(
+                            session,
+                            chat_area.width,
+                            input_mode,
+                            queue_panel,
+                        );
 
                         // Build prompt lines from inline_prompt (renders as part of scrollable chat)
                         let prompt_lines = session
@@ -8861,6 +12056,7 @@ impl App {
                             .as_ref()
                             .map(|p| p.render_as_lines(chat_area.width as usize));
 
+                        let started = self.state.frame_diagnostics.start();
                         session.chat_view.render_with_indicator(
                             chat_area,
                             f.buffer_mut(),
@@ -8869,16 +12065,24 @@ impl App {
                             prompt_lines,
                             show_chat_scrollbar,
                         );
+                        self.state
+                            .frame_diagnostics
+                            .record(DrawSection::ChatView, started);
 
                         // Check if inline prompt is active
                         let has_inline_prompt = session.inline_prompt.is_some();
 
                         // Render input box (not in command mode, not when inline prompt active)
                         if !is_command_mode && !has_inline_prompt {
+                            let started = self.state.frame_diagnostics.start();
                             session.input_box.render(input_area_inner, f.buffer_mut());
+                            self.state
+                                .frame_diagnostics
+                                .record(DrawSection::InputBox, started);
                         }
-                        // Update and render status bar (skip when inline prompt is active)
-                        if !has_inline_prompt {
+                        // Update and render status bar (skip when inline prompt is active or in zen mode)
+                        if !has_inline_prompt && !zen_mode {
+                            let started = self.state.frame_diagnostics.start();
                             session.status_bar.set_metrics(
                                 self.state.show_metrics,
                                 self.state.metrics.draw_time,
@@ -8893,9 +12097,14 @@ impl App {
                             session
                                 .status_bar
                                 .set_spinner_frame(self.state.spinner_frame);
+                            session.status_bar.set_tabs_cost(show_cost, tabs_cost);
+                            session.status_bar.set_offline(self.state.offline);
                             session
                                 .status_bar
                                 .render(status_bar_area_inner, f.buffer_mut());
+                            self.state
+                                .frame_diagnostics
+                                .record(DrawSection::StatusBar, started);
                         }
 
                         // Set cursor position (accounting for scroll)
@@ -8929,14 +12138,13 @@ impl App {
                     }
 
                     // Draw footer (full width) - context-aware based on input mode
-                    let footer = GlobalFooter::from_state(
-                        self.state.view_mode,
-                        self.state.input_mode,
-                        !self.state.tab_manager.is_empty(),
-                    )
-                    .with_spinner(self.state.footer_spinner.as_ref())
-                    .with_message(self.state.footer_message.as_deref());
-                    footer.render(footer_area, f.buffer_mut());
+                    if !zen_mode {
+                        let started = self.state.frame_diagnostics.start();
+                        self.global_footer().render(footer_area, f.buffer_mut());
+                        self.state
+                            .frame_diagnostics
+                            .record(DrawSection::Footer, started);
+                    }
                 }
                 ViewMode::RawEvents => {
                     // Raw events layout - no input box, full height for events
@@ -8963,39 +12171,110 @@ impl App {
                     self.state.footer_area = Some(footer_area);
 
                     // Draw tab bar (unfocused when sidebar is focused)
+                    let started = self.state.frame_diagnostics.start();
                     let tabs_focused = self.state.input_mode != InputMode::SidebarNavigation;
                     self.ensure_tab_bar_scroll(tab_bar_chunk.width, tabs_focused);
                     let tab_bar = self.build_tab_bar(tabs_focused);
                     tab_bar.render(tab_bar_chunk, f.buffer_mut());
 
                     // Draw session header (below tab bar) - consistent with Chat view
-                    let session_title = self
-                        .state
-                        .tab_manager
-                        .active_session()
-                        .and_then(|s| s.title.as_deref());
-                    SessionHeader::new(session_title).render(header_chunk, f.buffer_mut());
+                    let active_session = self.state.tab_manager.active_session();
+                    SessionHeader::new(active_session.and_then(|s| s.display_title()))
+                        .with_language(active_session.and_then(|s| s.response_language.as_deref()))
+                        .with_preset(
+                            active_session
+                                .and_then(|s| s.response_preset)
+                                .map(ResponsePreset::as_str),
+                        )
+                        .render(header_chunk, f.buffer_mut());
+                    self.state
+                        .frame_diagnostics
+                        .record(DrawSection::TabBar, started);
 
                     // Draw raw events view
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
+                        let started = self.state.frame_diagnostics.start();
                         session
                             .raw_events_view
                             .render(raw_events_chunk, f.buffer_mut());
+                        self.state
+                            .frame_diagnostics
+                            .record(DrawSection::RawEvents, started);
                     }
 
                     // Draw footer (full width) - context-aware based on input mode
-                    let footer = GlobalFooter::from_state(
-                        self.state.view_mode,
-                        self.state.input_mode,
-                        !self.state.tab_manager.is_empty(),
-                    )
-                    .with_spinner(self.state.footer_spinner.as_ref())
-                    .with_message(self.state.footer_message.as_deref());
-                    footer.render(footer_area, f.buffer_mut());
+                    let started = self.state.frame_diagnostics.start();
+                    self.global_footer().render(footer_area, f.buffer_mut());
+                    self.state
+                        .frame_diagnostics
+                        .record(DrawSection::Footer, started);
+                }
+                ViewMode::Timeline | ViewMode::Diff => {
+                    // Timeline/diff layout - no input box, full height for the view
+                    let chunks = Layout::default()
+                        .direction(Direction::Vertical)
+                        .constraints([
+                            Constraint::Length(1), // Tab bar
+                            Constraint::Length(1), // Session header
+                            Constraint::Length(1), // Spacing
+                            Constraint::Min(5),    // Timeline or diff view
+                        ])
+                        .split(content_area);
+
+                    let tab_bar_chunk = chunks[0];
+                    let header_chunk = chunks[1];
+                    let view_chunk = chunks[3];
+
+                    // Store layout areas for mouse hit-testing (no input/status in this mode)
+                    self.state.tab_bar_area = Some(tab_bar_chunk);
+                    self.state.chat_area = None;
+                    self.state.raw_events_area = None;
+                    self.state.input_area = None;
+                    self.state.status_bar_area = None;
+                    self.state.footer_area = Some(footer_area);
+
+                    let started = self.state.frame_diagnostics.start();
+                    let tabs_focused = self.state.input_mode != InputMode::SidebarNavigation;
+                    self.ensure_tab_bar_scroll(tab_bar_chunk.width, tabs_focused);
+                    let tab_bar = self.build_tab_bar(tabs_focused);
+                    tab_bar.render(tab_bar_chunk, f.buffer_mut());
+
+                    if let Some(session) = self.state.tab_manager.active_session() {
+                        SessionHeader::new(session.display_title())
+                            .with_language(session.response_language.as_deref())
+                            .with_preset(session.response_preset.map(ResponsePreset::as_str))
+                            .render(header_chunk, f.buffer_mut());
+                        self.state
+                            .frame_diagnostics
+                            .record(DrawSection::TabBar, started);
+                        let started = self.state.frame_diagnostics.start();
+                        let view_area = Rect {
+                            x: view_chunk.x + 2,
+                            width: view_chunk.width.saturating_sub(4),
+                            ..view_chunk
+                        };
+                        if self.state.view_mode == ViewMode::Diff {
+                            DiffView::new(&session.diff_view).render(view_area, f.buffer_mut());
+                        } else {
+                            TimelineView::new(&session.timeline, Instant::now())
+                                .render(view_area, f.buffer_mut());
+                        }
+                        self.state
+                            .frame_diagnostics
+                            .record(DrawSection::Panels, started);
+                    }
+
+                    let started = self.state.frame_diagnostics.start();
+                    self.global_footer().render(footer_area, f.buffer_mut());
+                    self.state
+                        .frame_diagnostics
+                        .record(DrawSection::Footer, started);
                 }
             }
         } // end of else block for agent tab rendering
 
+        let dialogs_started = self.state.frame_diagnostics.start();
+
         // Draw agent selector dialog if needed
         if self.state.agent_selector_state.is_visible() {
             let selector = AgentSelector::new();
@@ -9008,6 +12287,22 @@ impl App {
             dialog.render(size, f.buffer_mut(), &self.state.add_repo_dialog_state);
         }
 
+        // Draw the expanded queue editor if open
+        if self.state.queue_overlay && self.state.input_mode == InputMode::QueueEditing {
+            if let Some(session) = self.state.tab_manager.active_session() {
+                app_queue::render_queue_overlay(size, f.buffer_mut(), session);
+            }
+        }
+
+        // Draw clipboard history if open
+        if self.state.input_mode == InputMode::ClipboardHistory {
+            clipboard_history::render_clipboard_history_overlay(
+                size,
+                f.buffer_mut(),
+                &self.state.clipboard_history,
+            );
+        }
+
         // Draw model selector dialog if open
         if self.state.model_selector_state.is_visible() {
             self.state.model_selector_state.update_viewport(size);
@@ -9024,6 +12319,12 @@ impl App {
             dialog.render(size, f.buffer_mut(), &self.state.base_dir_dialog_state);
         }
 
+        // Draw rename tab dialog if open
+        if self.state.rename_tab_dialog_state.is_visible() {
+            let dialog = RenameTabDialog::new();
+            dialog.render(size, f.buffer_mut(), &self.state.rename_tab_dialog_state);
+        }
+
         // Draw project picker if open
         if self.state.project_picker_state.is_visible() {
             let picker = ProjectPicker::new();
@@ -9071,7 +12372,7 @@ impl App {
         if self.state.input_mode == InputMode::RemovingProject {
             use crate::ui::components::Spinner;
             use ratatui::layout::Alignment;
-            use ratatui::style::{Color, Style};
+            use ratatui::style::Style;
             use ratatui::symbols::border;
             use ratatui::text::Line;
             use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget};
@@ -9092,7 +12393,7 @@ impl App {
             let block = Block::default()
                 .borders(Borders::ALL)
                 .border_set(border::ROUNDED)
-                .border_style(Style::default().fg(Color::Rgb(130, 170, 255)));
+                .border_style(Style::default().fg(border_focused()));
 
             let inner = block.inner(dialog_area);
             block.render(dialog_area, f.buffer_mut());
@@ -9100,13 +12401,32 @@ impl App {
             // Render spinner and message
             let spinner = Spinner::dots();
             let line = Line::from(vec![
-                spinner.span(Color::Rgb(130, 170, 255)),
+                spinner.span(border_focused()),
                 ratatui::text::Span::raw(" Removing project..."),
             ]);
 
             let para = Paragraph::new(line).alignment(Alignment::Center);
             para.render(inner, f.buffer_mut());
         }
+
+        self.state
+            .frame_diagnostics
+            .record(DrawSection::Dialogs, dialogs_started);
+        if self.state.frame_diagnostics.is_enabled() {
+            frame_diagnostics::render_frame_diagnostics_overlay(
+                size,
+                f.buffer_mut(),
+                &self.state.frame_diagnostics,
+            );
+        }
+    }
+
+    /// Whether zen mode applies to the current view (chat tabs only).
+    fn zen_mode_active(&self) -> bool {
+        self.state.zen_mode
+            && self.state.view_mode == ViewMode::Chat
+            && !self.state.tab_manager.is_empty()
+            && !self.state.tab_manager.active_is_file()
     }
 
     /// Render file viewer tab content
@@ -9116,9 +12436,7 @@ impl App {
         footer_area: Rect,
         f: &mut ratatui::Frame<'_>,
     ) {
-        use crate::ui::components::{
-            bg_base, text_muted, text_primary, FileViewerView, FooterContext, GlobalFooter,
-        };
+        use crate::ui::components::{bg_base, text_muted, text_primary, FileViewerView};
         use ratatui::style::Style;
         use ratatui::text::{Line, Span};
         use ratatui::widgets::{Paragraph, Widget};
@@ -9231,15 +12549,7 @@ impl App {
         }
 
         // Render footer (sidebar-aware)
-        let footer_context = if self.state.input_mode == InputMode::SidebarNavigation {
-            FooterContext::Sidebar
-        } else {
-            FooterContext::FileViewer
-        };
-        let footer = GlobalFooter::for_context(footer_context)
-            .with_spinner(self.state.footer_spinner.as_ref())
-            .with_message(self.state.footer_message.as_deref());
-        footer.render(footer_area, f.buffer_mut());
+        self.global_footer().render(footer_area, f.buffer_mut());
     }
 
     fn render_theme_picker(&mut self, size: Rect, buf: &mut ratatui::buffer::Buffer) {
@@ -9442,11 +12752,7 @@ impl App {
 
         let timestamp = Local::now().format("%Y%m%d_%H%M%S");
 
-        // Save to ~/.conduit/debug/ directory
-        let debug_dir = dirs::home_dir()
-            .unwrap_or_else(|| std::path::PathBuf::from("."))
-            .join(".conduit")
-            .join("debug");
+        let debug_dir = crate::util::debug_dir();
 
         // Create directory if it doesn't exist
         std::fs::create_dir_all(&debug_dir)
@@ -9483,6 +12789,7 @@ impl App {
                         "tool_name": msg.tool_name,
                         "tool_args": msg.tool_args,
                         "is_streaming": msg.is_streaming,
+                        "timestamp": msg.timestamp.map(|timestamp| timestamp.to_rfc3339()),
                         "has_summary": msg.summary.is_some(),
                         "summary": summary_data,
                     })
@@ -9510,11 +12817,16 @@ impl App {
                 "duration_secs": session.current_turn_summary.duration_secs,
                 "input_tokens": session.current_turn_summary.input_tokens,
                 "output_tokens": session.current_turn_summary.output_tokens,
+                "cache_read_tokens": session.current_turn_summary.cache_read_tokens,
+                "cache_write_tokens": session.current_turn_summary.cache_write_tokens,
                 "files_changed": session.current_turn_summary.files_changed.iter().map(|f| json!({
                     "filename": f.filename,
                     "additions": f.additions,
                     "deletions": f.deletions,
                 })).collect::<Vec<_>>(),
+                "commands": session.current_turn_summary.commands,
+                "verification": session.current_turn_summary.verification(),
+                "references": session.current_turn_summary.references,
             });
 
             sessions_data.push(json!({
@@ -9522,12 +12834,16 @@ impl App {
                 "id": session.id.to_string(),
                 "agent_type": format!("{:?}", session.agent_type),
                 "agent_session_id": session.agent_session_id.as_ref().map(|s| s.as_str().to_string()),
+                "fork_seed_id": session.fork_seed_id.map(|id| id.to_string()),
+                "fork_seed_hash": session.fork_seed_hash,
                 "is_processing": session.is_processing,
                 "turn_count": session.turn_count,
+                "total_cost_usd": session.total_cost,
                 "total_usage": {
                     "input_tokens": session.total_usage.input_tokens,
                     "output_tokens": session.total_usage.output_tokens,
                     "cached_tokens": session.total_usage.cached_tokens,
+                    "cache_creation_tokens": session.total_usage.cache_creation_tokens,
                     "total_tokens": session.total_usage.total_tokens,
                 },
                 "current_turn_summary": turn_summary,
@@ -10004,6 +13320,62 @@ mod tests {
         assert!(prompt.ends_with("Ready"));
     }
 
+    #[test]
+    fn test_build_fork_seed_prompt_trims_large_tool_output() {
+        use crate::ui::components::ChatMessage;
+
+        let output = format!("{}{}", "x".repeat(20_000), "LAST LINE");
+        let messages = vec![
+            ChatMessage::user("Run the build"),
+            ChatMessage::tool_with_exit("Bash", "cargo build", output, Some(0)),
+            ChatMessage::assistant("Build passed"),
+        ];
+
+        let prompt = app_prompt::build_fork_seed_prompt(&messages);
+
+        assert!(prompt.contains("bytes of output omitted"));
+        assert!(prompt.contains("LAST LINE"));
+        assert!(prompt.contains("Build passed"));
+        assert!(prompt.len() < 10_000);
+    }
+
+    #[test]
+    fn test_build_fork_seed_prompt_condenses_old_turns_to_fit() {
+        use crate::ui::components::ChatMessage;
+
+        let mut messages = Vec::new();
+        for turn in 0..200 {
+            messages.push(ChatMessage::user(format!("prompt {turn}")));
+            messages.push(ChatMessage::assistant(format!(
+                "thinking {turn} {}",
+                "t".repeat(4_000)
+            )));
+            messages.push(ChatMessage::tool(
+                "Read",
+                format!("file{turn}.rs"),
+                "r".repeat(2_000),
+            ));
+            messages.push(ChatMessage::assistant(format!(
+                "answer {turn} {}",
+                "a".repeat(4_000)
+            )));
+        }
+
+        let prompt = app_prompt::build_fork_seed_prompt(&messages);
+
+        assert!(prompt.len() <= app_prompt::MAX_SEED_PROMPT_SIZE);
+        assert!(!prompt.contains("[TRUNCATED"));
+        // The task and the latest turn survive in full
+        assert!(prompt.contains("prompt 0\n"));
+        assert!(prompt.contains("thinking 199"));
+        assert!(prompt.contains("answer 199"));
+        // Older turns keep only their prompt and final answer
+        assert!(prompt.contains("answer 0"));
+        assert!(!prompt.contains("thinking 0 "));
+        assert!(prompt.contains("1 tool call omitted (Read x1)"));
+        assert!(prompt.contains("omitted to fit the size limit"));
+    }
+
     #[test]
     fn test_strip_image_placeholders_removes_placeholders() {
         let prompt = "Hello [img] world".to_string();
@@ -10132,138 +13504,773 @@ mod tests {
         assert!(separate.contains("[Queued 2 of 2]"));
     }
 
-    #[test]
-    fn test_sanitize_title_collapses_whitespace_and_bounds_length() {
-        let title = "  Hello\n\tworld  ".to_string();
-        let cleaned = app_prompt::sanitize_title(&title);
-        assert_eq!(cleaned, "Hello world");
-
-        let long = "a".repeat(250);
-        let bounded = app_prompt::sanitize_title(&long);
-        assert!(bounded.chars().count() <= 200);
-
-        let empty = "\n\t\r".to_string();
-        let fallback = app_prompt::sanitize_title(&empty);
-        assert_eq!(fallback, "Untitled task");
+    fn queued_text(text: &str, images: usize) -> QueuedMessage {
+        QueuedMessage {
+            id: Uuid::new_v4(),
+            mode: QueuedMessageMode::FollowUp,
+            text: text.to_string(),
+            images: (0..images)
+                .map(|idx| QueuedImageAttachment {
+                    path: PathBuf::from(format!("/tmp/image-{idx}.png")),
+                    placeholder: format!("[Image #{}]", idx + 1),
+                })
+                .collect(),
+            created_at: Utc::now(),
+        }
     }
 
-    #[tokio::test]
-    async fn test_agent_event_routes_streaming_by_session_id_after_tab_close() {
-        let session_a = Uuid::new_v4();
-        let session_b = Uuid::new_v4();
-        let session_c = Uuid::new_v4();
-
-        let mut app = build_test_app_with_sessions(&[session_a, session_b, session_c]);
+    fn line_text(line: &ratatui::text::Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
 
-        // Close the first tab so indices shift: B -> 0, C -> 1
-        assert!(app.state.tab_manager.close_tab(0));
-        assert_eq!(
-            app.state.tab_manager.session_index_by_id(session_b),
-            Some(0)
-        );
+    #[test]
+    fn test_queue_summary_counts_images() {
+        let messages = vec![
+            queued_text("a", 1),
+            queued_text("b", 0),
+            queued_text("c", 1),
+        ];
+        assert_eq!(app_queue::queue_summary(&messages), "3 queued (2 images)");
+        assert_eq!(app_queue::queue_summary(&messages[1..2]), "1 queued");
         assert_eq!(
-            app.state.tab_manager.session_index_by_id(session_c),
-            Some(1)
+            app_queue::queue_summary(&messages[..1]),
+            "1 queued (1 image)"
         );
+    }
 
-        let event = AgentEvent::AssistantMessage(AssistantMessageEvent {
-            text: "message for B".to_string(),
-            is_final: false,
-        });
-
-        app.handle_agent_event(session_b, event).await.unwrap();
+    #[test]
+    fn test_queue_window_start_keeps_selection_visible() {
+        assert_eq!(app_queue::queue_window_start(3, 4, Some(2)), 0);
+        assert_eq!(app_queue::queue_window_start(10, 4, None), 0);
+        assert_eq!(app_queue::queue_window_start(10, 4, Some(2)), 0);
+        assert_eq!(app_queue::queue_window_start(10, 4, Some(5)), 2);
+        assert_eq!(app_queue::queue_window_start(10, 4, Some(9)), 6);
+        assert_eq!(app_queue::queue_window_start(10, 4, Some(42)), 6);
+    }
 
-        {
-            let session = app
-                .state
-                .tab_manager
-                .session_by_id_mut(session_b)
-                .expect("session B missing");
-            assert_eq!(session.chat_view.streaming_buffer(), Some("message for B"));
-            assert!(session.chat_view.messages().is_empty());
+    #[test]
+    fn test_build_queue_lines_respects_panel_budget() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+        let session = app
+            .state
+            .tab_manager
+            .active_session_mut()
+            .expect("session missing");
+        for idx in 0..3 {
+            session.queue_message(queued_text(&format!("message {idx}"), 0));
         }
 
-        {
-            let session = app
-                .state
-                .tab_manager
-                .session_by_id_mut(session_c)
-                .expect("session C missing");
-            assert!(session.chat_view.streaming_buffer().is_none());
-            assert!(session.chat_view.messages().is_empty());
+        let panel = app_queue::QueuePanel::new(5, false);
+        let lines = app_queue::build_queue_lines(session, 80, InputMode::Normal, panel)
+            .expect("queue lines missing");
+        assert_eq!(lines.len(), 3);
+        assert_eq!(line_text(&lines[0]), "  Queued: message 0");
+
+        for idx in 3..8 {
+            session.queue_message(queued_text(&format!("message {idx}"), 1));
         }
+        let lines = app_queue::build_queue_lines(session, 80, InputMode::Normal, panel)
+            .expect("queue lines missing");
+        assert_eq!(lines.len(), 5);
+        assert_eq!(line_text(&lines[0]), "▾ 8 queued (5 images) · 1–4 of 8");
+
+        session.queue_selection = Some(7);
+        let lines = app_queue::build_queue_lines(session, 80, InputMode::QueueEditing, panel)
+            .expect("queue lines missing");
+        assert_eq!(lines.len(), 5);
+        assert_eq!(line_text(&lines[0]), "▾ 8 queued (5 images) · 5–8 of 8");
+        assert!(line_text(&lines[4]).starts_with("› Queued: message 7"));
+
+        let collapsed = app_queue::QueuePanel::new(5, true);
+        let lines = app_queue::build_queue_lines(session, 80, InputMode::Normal, collapsed)
+            .expect("queue lines missing");
+        assert_eq!(lines.len(), 1);
+        assert_eq!(line_text(&lines[0]), "▸ 8 queued (5 images)");
+
+        // Editing always shows the messages, even when collapsed
+        let lines = app_queue::build_queue_lines(session, 80, InputMode::QueueEditing, collapsed)
+            .expect("queue lines missing");
+        assert_eq!(lines.len(), 5);
     }
 
-    #[tokio::test]
-    async fn test_agent_event_routes_reasoning_by_session_id_after_tab_close() {
-        let session_a = Uuid::new_v4();
-        let session_b = Uuid::new_v4();
-        let session_c = Uuid::new_v4();
+    #[test]
+    fn test_expand_queue_toggles_overlay_and_close_resets_it() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+        app.state
+            .tab_manager
+            .active_session_mut()
+            .expect("session missing")
+            .queue_message(queued_text("queued", 0));
 
-        let mut app = build_test_app_with_sessions(&[session_a, session_b, session_c]);
+        app.handle_queue_action(Action::ExpandQueue);
+        assert_eq!(app.state.input_mode, InputMode::QueueEditing);
+        assert!(app.state.queue_overlay);
+        assert!(app.queue_panel().hidden);
 
-        // Close the first tab so indices shift: B -> 0, C -> 1
-        assert!(app.state.tab_manager.close_tab(0));
-        assert_eq!(
-            app.state.tab_manager.session_index_by_id(session_b),
-            Some(0)
-        );
-        assert_eq!(
-            app.state.tab_manager.session_index_by_id(session_c),
-            Some(1)
-        );
+        app.handle_queue_action(Action::ExpandQueue);
+        assert_eq!(app.state.input_mode, InputMode::QueueEditing);
+        assert!(!app.state.queue_overlay);
 
-        let event = AgentEvent::AssistantReasoning(ReasoningEvent {
-            text: "thinking...".to_string(),
-        });
+        app.handle_queue_action(Action::ExpandQueue);
+        app.handle_queue_action(Action::CloseQueueEditor);
+        assert_eq!(app.state.input_mode, InputMode::Normal);
+        assert!(!app.state.queue_overlay);
 
-        app.handle_agent_event(session_b, event).await.unwrap();
+        app.handle_queue_action(Action::ToggleQueuePanel);
+        assert!(app.queue_panel().collapsed);
+    }
 
+    #[test]
+    fn test_queue_editor_reorders_edits_and_deletes() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
         {
             let session = app
                 .state
                 .tab_manager
-                .session_by_id_mut(session_b)
-                .expect("session B missing");
-            assert_eq!(
-                session
-                    .chat_view
-                    .streaming_message_for(MessageRole::Reasoning),
-                Some("thinking...")
-            );
+                .active_session_mut()
+                .expect("session missing");
+            session.queue_message(queued_text("first", 0));
+            session.queue_message(queued_text("second", 1));
+            session.queue_message(queued_text("third", 0));
         }
-
-        {
-            let session = app
-                .state
+        let texts = |app: &App| -> Vec<String> {
+            app.state
                 .tab_manager
-                .session_by_id_mut(session_c)
-                .expect("session C missing");
-            assert!(session
-                .chat_view
-                .streaming_message_for(MessageRole::Reasoning)
-                .is_none());
-        }
-    }
+                .active_session()
+                .expect("session missing")
+                .queued_messages
+                .iter()
+                .map(|msg| msg.text.clone())
+                .collect()
+        };
 
-    #[tokio::test]
-    async fn test_agent_event_routes_final_by_session_id_after_tab_close() {
-        let session_a = Uuid::new_v4();
-        let session_b = Uuid::new_v4();
-        let session_c = Uuid::new_v4();
+        // The editor opens on the last message
+        app.handle_queue_action(Action::OpenQueueEditor);
+        app.handle_queue_action(Action::QueueMoveUp);
+        app.handle_queue_action(Action::QueueMoveUp);
+        assert_eq!(texts(&app), ["third", "first", "second"]);
+        app.handle_queue_action(Action::QueueMoveDown);
+        assert_eq!(texts(&app), ["first", "third", "second"]);
 
-        let mut app = build_test_app_with_sessions(&[session_a, session_b, session_c]);
+        app.handle_queue_action(Action::QueueDelete);
+        assert_eq!(texts(&app), ["first", "second"]);
+        assert_eq!(app.state.input_mode, InputMode::QueueEditing);
 
-        // Close the first tab so indices shift: B -> 0, C -> 1
-        assert!(app.state.tab_manager.close_tab(0));
-        assert_eq!(
-            app.state.tab_manager.session_index_by_id(session_b),
-            Some(0)
-        );
-        assert_eq!(
-            app.state.tab_manager.session_index_by_id(session_c),
-            Some(1)
-        );
+        // A draft in the input box is never overwritten
+        app.state
+            .tab_manager
+            .active_session_mut()
+            .expect("session missing")
+            .input_box
+            .set_input("draft".to_string());
+        app.handle_queue_action(Action::QueueEdit);
+        assert_eq!(texts(&app), ["first", "second"]);
+        assert_eq!(app.state.input_mode, InputMode::QueueEditing);
+
+        let session = app
+            .state
+            .tab_manager
+            .active_session_mut()
+            .expect("session missing");
+        session.input_box.clear();
+        assert_eq!(session.queue_selection, Some(1));
+        app.handle_queue_action(Action::QueueEdit);
+        assert_eq!(texts(&app), ["first"]);
+        assert_eq!(app.state.input_mode, InputMode::Normal);
+        let session = app
+            .state
+            .tab_manager
+            .active_session()
+            .expect("session missing");
+        assert_eq!(session.input_box.input(), "second");
+        assert_eq!(
+            session.input_box.attachments_snapshot(),
+            vec![(PathBuf::from("/tmp/image-0.png"), "[Image #1]".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_flagged_changes_need_acknowledging_before_pr() {
+        use crate::core::change_guard::FlagKind;
+
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+        let working_dir = PathBuf::from("/tmp/workspace");
+        app.state
+            .tab_manager
+            .active_session_mut()
+            .expect("session missing")
+            .working_dir = Some(working_dir.clone());
+        let preflight = crate::git::PrPreflightResult {
+            gh_installed: true,
+            gh_authenticated: true,
+            on_main_branch: false,
+            branch_name: "feature".to_string(),
+            target_branch: "origin/main".to_string(),
+            uncommitted_count: 0,
+            has_upstream: true,
+            existing_pr: None,
+        };
+        let flags = vec![ChangeFlag {
+            path: "assets/model.bin".to_string(),
+            kind: FlagKind::Binary,
+        }];
+        let checks = |flags: &Vec<ChangeFlag>| PrChecks {
+            security: None,
+            change_flags: Some(Ok(flags.clone())),
+        };
+
+        app.confirm_pr_creation(
+            0,
+            working_dir.clone(),
+            preflight.clone(),
+            Vec::new(),
+            checks(&flags),
+        );
+        let Some(ConfirmationContext::AcknowledgeChangeFlags { keys, .. }) =
+            app.state.confirmation_dialog_state.context.clone()
+        else {
+            panic!("expected the flagged changes dialog");
+        };
+        assert_eq!(keys, ["binary:assets/model.bin"]);
+
+        app.acknowledge_change_flags(
+            0,
+            working_dir.clone(),
+            preflight.clone(),
+            Vec::new(),
+            None,
+            keys,
+        );
+        assert!(matches!(
+            app.state.confirmation_dialog_state.context,
+            Some(ConfirmationContext::CreatePullRequest { .. })
+        ));
+
+        // Acknowledged flags don't ask again, but stay listed for the diff view
+        app.confirm_pr_creation(0, working_dir, preflight, Vec::new(), checks(&flags));
+        assert!(matches!(
+            app.state.confirmation_dialog_state.context,
+            Some(ConfirmationContext::CreatePullRequest { .. })
+        ));
+        let session = app
+            .state
+            .tab_manager
+            .active_session()
+            .expect("session missing");
+        assert_eq!(session.change_flags, flags);
+    }
+
+    #[test]
+    fn test_show_diagnostics_lists_captured_stderr() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+        let mut effects = Vec::new();
+
+        app.handle_global_action(Action::ShowDiagnostics, &mut effects);
+        assert_eq!(app.state.input_mode, InputMode::ShowingError);
+        assert!(!app.state.error_dialog_state.has_details());
+
+        app.state.error_dialog_state.hide();
+        app.state
+            .tab_manager
+            .active_session()
+            .expect("session missing")
+            .stderr
+            .push("Error: not logged in");
+        app.handle_global_action(Action::ShowDiagnostics, &mut effects);
+
+        let dialog = &app.state.error_dialog_state;
+        assert!(dialog.details_expanded);
+        assert!(dialog.message.starts_with("Last 1 of 1 stderr lines"));
+        assert!(dialog
+            .details
+            .as_deref()
+            .is_some_and(|details| details.ends_with("Error: not logged in")));
+    }
+
+    #[test]
+    fn test_annotate_replaces_last_attached_image() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+        let mut effects = Vec::new();
+
+        app.handle_global_action(Action::AnnotateImage, &mut effects);
+        assert_eq!(app.state.input_mode, InputMode::Normal);
+        assert_eq!(
+            app.state.footer_message.as_deref(),
+            Some("No attached image to annotate")
+        );
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let source = dir.path().join("shot.png");
+        image::RgbaImage::new(40, 30)
+            .save(&source)
+            .expect("write test image");
+        app.state
+            .tab_manager
+            .active_session_mut()
+            .expect("session missing")
+            .input_box
+            .attach_image(source.clone(), 40, 30);
+
+        app.handle_global_action(Action::AnnotateImage, &mut effects);
+        assert_eq!(app.state.input_mode, InputMode::Command);
+        assert_eq!(app.state.command_buffer, "annotate ");
+
+        app.annotate_last_image("crop 0,0,20,10; box 2,2,8,4");
+        assert_eq!(
+            app.state.footer_message.as_deref(),
+            Some("Image annotated (20x10)")
+        );
+        let input_box = &app
+            .state
+            .tab_manager
+            .active_session()
+            .expect("session missing")
+            .input_box;
+        let annotated = input_box
+            .last_attached_image()
+            .expect("annotated image attached");
+        assert_ne!(annotated, source.as_path());
+        assert_eq!(image::image_dimensions(annotated).unwrap(), (20, 10));
+        assert!(input_box.input().contains(" 20x10]"));
+        assert!(!input_box.input().contains("shot.png"));
+    }
+
+    #[tokio::test]
+    async fn test_agent_crash_mid_turn_offers_restart() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+        let mut effects = Vec::new();
+
+        app.handle_global_action(Action::RestartAgent, &mut effects);
+        assert!(effects.is_empty());
+        assert_eq!(
+            app.state.footer_message.as_deref(),
+            Some("No crashed agent to restart")
+        );
+
+        {
+            let session = app
+                .state
+                .tab_manager
+                .session_by_id_mut(session_id)
+                .expect("session missing");
+            session.last_prompt = Some(QueuedMessage {
+                id: Uuid::new_v4(),
+                mode: QueuedMessageMode::FollowUp,
+                text: "fix the build".to_string(),
+                images: Vec::new(),
+                created_at: Utc::now(),
+            });
+            session.start_processing();
+        }
+
+        let event = AgentEvent::Error(crate::agent::events::ErrorEvent {
+            message: "Claude process failed: Agent crashed with exit code: 1".to_string(),
+            is_fatal: true,
+            code: Some(AGENT_CRASHED_CODE.to_string()),
+            details: None,
+        });
+        app.handle_agent_event(session_id, event).await.unwrap();
+
+        let session = app
+            .state
+            .tab_manager
+            .session_by_id_mut(session_id)
+            .expect("session missing");
+        assert!(session.agent_crashed);
+        assert!(!session.is_processing);
+        let hint = session
+            .chat_view
+            .messages()
+            .last()
+            .expect("restart hint missing");
+        assert!(hint.content.starts_with("Press M-S-"));
+        assert!(hint.content.ends_with("resend your last prompt."));
+    }
+
+    #[tokio::test]
+    async fn test_quiet_turn_is_flagged_once_until_the_agent_speaks() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+        let mut effects = Vec::new();
+        let threshold = app.config().stall.threshold().expect("stall detection on");
+        {
+            let session = app
+                .state
+                .tab_manager
+                .session_by_id_mut(session_id)
+                .expect("session missing");
+            session.start_processing();
+            session.agent_pid = Some(u32::MAX);
+            session.last_agent_event_at = Instant::now().checked_sub(threshold);
+        }
+
+        assert!(app.flag_stalled_turns());
+        assert!(!app.flag_stalled_turns());
+        let session = app
+            .state
+            .tab_manager
+            .session_by_id_mut(session_id)
+            .expect("session missing");
+        let card = session.chat_view.messages().last().expect("card missing");
+        assert!(card.content.contains("the turn may be stalled"));
+        assert!(card
+            .content
+            .ends_with("to interrupt and resend your prompt."));
+
+        app.handle_agent_event(session_id, AgentEvent::TurnStarted)
+            .await
+            .unwrap();
+        let session = app
+            .state
+            .tab_manager
+            .session_by_id_mut(session_id)
+            .expect("session missing");
+        assert!(!session.stall_notified);
+        session.agent_pid = None;
+        session.stop_processing();
+
+        app.handle_global_action(Action::NudgeAgent, &mut effects);
+        assert!(effects.is_empty());
+        assert_eq!(
+            app.state.footer_message.as_deref(),
+            Some("No stalled turn to nudge")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_classified_failure_shows_suggestion_card() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+        {
+            let session = app
+                .state
+                .tab_manager
+                .session_by_id_mut(session_id)
+                .expect("session missing");
+            session.last_prompt = Some(QueuedMessage {
+                id: Uuid::new_v4(),
+                mode: QueuedMessageMode::FollowUp,
+                text: "fix the build".to_string(),
+                images: Vec::new(),
+                created_at: Utc::now(),
+            });
+            session.start_processing();
+        }
+
+        let event = AgentEvent::Error(crate::agent::events::ErrorEvent {
+            message: "Claude process failed: Agent crashed with exit code: 1: \
+                      API Error: 429 rate_limit_error, retry after 10s"
+                .to_string(),
+            is_fatal: true,
+            code: Some(AGENT_CRASHED_CODE.to_string()),
+            details: None,
+        });
+        app.handle_agent_event(session_id, event).await.unwrap();
+
+        let session = app
+            .state
+            .tab_manager
+            .session_by_id_mut(session_id)
+            .expect("session missing");
+        assert_eq!(
+            session.failure,
+            Some(FailureKind::RateLimited {
+                retry_after: Duration::from_secs(10)
+            })
+        );
+        // The card replaces the generic restart hint
+        let card = session
+            .chat_view
+            .messages()
+            .last()
+            .expect("suggestion card missing");
+        assert!(card.content.contains("rate limited"));
+        assert!(card
+            .content
+            .ends_with("wait 10s and resend your last prompt."));
+
+        // Once the wait is over the prompt is resent and the failure cleared
+        app.retry_failed_turn(session_id).unwrap();
+        let session = app
+            .state
+            .tab_manager
+            .session_by_id_mut(session_id)
+            .expect("session missing");
+        assert!(session.failure.is_none());
+        assert!(!session.agent_crashed);
+    }
+
+    #[tokio::test]
+    async fn test_held_edit_waits_for_review() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+        let (input_tx, mut input_rx) = mpsc::channel(4);
+        {
+            let session = app
+                .state
+                .tab_manager
+                .session_by_id_mut(session_id)
+                .expect("session missing");
+            session.working_dir = Some(dir.path().to_path_buf());
+            session.agent_input_tx = Some(input_tx);
+            session.start_processing();
+        }
+
+        let event = AgentEvent::ControlRequest(crate::agent::events::ControlRequestEvent {
+            request_id: "req-1".to_string(),
+            tool_name: "Edit".to_string(),
+            tool_use_id: Some("tool-1".to_string()),
+            input: serde_json::json!({
+                "file_path": "lib.rs",
+                "old_string": "fn b() {}",
+                "new_string": "fn b() { 2 }",
+            }),
+        });
+        app.handle_agent_event(session_id, event).await.unwrap();
+        {
+            let session = app
+                .state
+                .tab_manager
+                .session_by_id_mut(session_id)
+                .expect("session missing");
+            let prompt = session
+                .inline_prompt
+                .as_ref()
+                .expect("review prompt missing");
+            let InlinePromptType::EditReview { review } = &prompt.prompt_type else {
+                panic!("expected an edit review prompt");
+            };
+            assert_eq!(review.hunks.len(), 1);
+        }
+        // Nothing reaches the agent until the review is answered
+        assert!(input_rx.try_recv().is_err());
+
+        app.finish_edit_review(ReviewOutcome::Rejected);
+        let Some(AgentInput::ClaudeJsonl(reply)) = input_rx.recv().await else {
+            panic!("review reply not sent");
+        };
+        assert!(reply.contains("\"req-1\""));
+        assert!(reply.contains("\"deny\""));
+        let session = app
+            .state
+            .tab_manager
+            .session_by_id_mut(session_id)
+            .expect("session missing");
+        assert!(session.inline_prompt.is_none());
+        assert!(session.pending_tool_permissions.is_empty());
+        // The file is untouched
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("lib.rs")).unwrap(),
+            "fn a() {}\nfn b() {}\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_voice_transcript_lands_in_draft_for_review() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+        let mut effects = Vec::new();
+
+        app.handle_global_action(Action::ToggleVoiceInput, &mut effects);
+        assert!(effects.is_empty());
+        assert!(app.state.voice_recording.is_none());
+        assert!(app
+            .state
+            .footer_message
+            .as_deref()
+            .is_some_and(|message| message.starts_with("Voice input isn't configured")));
+
+        app.state
+            .tab_manager
+            .session_by_id_mut(session_id)
+            .expect("session missing")
+            .input_box
+            .set_input("please".to_string());
+        let effects = app
+            .handle_app_event(AppEvent::VoiceTranscribed {
+                session_id,
+                result: Ok("run the tests".to_string()),
+            })
+            .await
+            .unwrap();
+        assert!(effects.is_empty());
+
+        let session = app
+            .state
+            .tab_manager
+            .session_by_id_mut(session_id)
+            .expect("session missing");
+        assert_eq!(session.input_box.input(), "please run the tests");
+        assert!(!session.is_processing);
+    }
+
+    #[test]
+    fn test_read_aloud_text_only_covers_latest_turn() {
+        let mut session = AgentSession::new(AgentType::Claude);
+        session
+            .chat_view
+            .push(ChatMessage::user("first prompt".to_string()));
+        session
+            .chat_view
+            .push(ChatMessage::assistant("Earlier answer.".to_string()));
+        session
+            .chat_view
+            .push(ChatMessage::user("second prompt".to_string()));
+        assert_eq!(App::read_aloud_text_for_turn(&session, 1000), None);
+
+        session
+            .chat_view
+            .push(ChatMessage::assistant("**All** tests pass".to_string()));
+        assert_eq!(
+            App::read_aloud_text_for_turn(&session, 1000).as_deref(),
+            Some("All tests pass.")
+        );
+    }
+
+    #[test]
+    fn test_sanitize_title_collapses_whitespace_and_bounds_length() {
+        let title = "  Hello\n\tworld  ".to_string();
+        let cleaned = app_prompt::sanitize_title(&title);
+        assert_eq!(cleaned, "Hello world");
+
+        let long = "a".repeat(250);
+        let bounded = app_prompt::sanitize_title(&long);
+        assert!(bounded.chars().count() <= 200);
+
+        let empty = "\n\t\r".to_string();
+        let fallback = app_prompt::sanitize_title(&empty);
+        assert_eq!(fallback, "Untitled task");
+    }
+
+    #[tokio::test]
+    async fn test_agent_event_routes_streaming_by_session_id_after_tab_close() {
+        let session_a = Uuid::new_v4();
+        let session_b = Uuid::new_v4();
+        let session_c = Uuid::new_v4();
+
+        let mut app = build_test_app_with_sessions(&[session_a, session_b, session_c]);
+
+        // Close the first tab so indices shift: B -> 0, C -> 1
+        assert!(app.state.tab_manager.close_tab(0));
+        assert_eq!(
+            app.state.tab_manager.session_index_by_id(session_b),
+            Some(0)
+        );
+        assert_eq!(
+            app.state.tab_manager.session_index_by_id(session_c),
+            Some(1)
+        );
+
+        let event = AgentEvent::AssistantMessage(AssistantMessageEvent {
+            text: "message for B".to_string(),
+            is_final: false,
+        });
+
+        app.handle_agent_event(session_b, event).await.unwrap();
+
+        {
+            let session = app
+                .state
+                .tab_manager
+                .session_by_id_mut(session_b)
+                .expect("session B missing");
+            assert_eq!(session.chat_view.streaming_buffer(), Some("message for B"));
+            assert!(session.chat_view.messages().is_empty());
+        }
+
+        {
+            let session = app
+                .state
+                .tab_manager
+                .session_by_id_mut(session_c)
+                .expect("session C missing");
+            assert!(session.chat_view.streaming_buffer().is_none());
+            assert!(session.chat_view.messages().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_agent_event_routes_reasoning_by_session_id_after_tab_close() {
+        let session_a = Uuid::new_v4();
+        let session_b = Uuid::new_v4();
+        let session_c = Uuid::new_v4();
+
+        let mut app = build_test_app_with_sessions(&[session_a, session_b, session_c]);
+
+        // Close the first tab so indices shift: B -> 0, C -> 1
+        assert!(app.state.tab_manager.close_tab(0));
+        assert_eq!(
+            app.state.tab_manager.session_index_by_id(session_b),
+            Some(0)
+        );
+        assert_eq!(
+            app.state.tab_manager.session_index_by_id(session_c),
+            Some(1)
+        );
+
+        let event = AgentEvent::AssistantReasoning(ReasoningEvent {
+            text: "thinking...".to_string(),
+        });
+
+        app.handle_agent_event(session_b, event).await.unwrap();
+
+        {
+            let session = app
+                .state
+                .tab_manager
+                .session_by_id_mut(session_b)
+                .expect("session B missing");
+            assert_eq!(
+                session
+                    .chat_view
+                    .streaming_message_for(MessageRole::Reasoning),
+                Some("thinking...")
+            );
+        }
+
+        {
+            let session = app
+                .state
+                .tab_manager
+                .session_by_id_mut(session_c)
+                .expect("session C missing");
+            assert!(session
+                .chat_view
+                .streaming_message_for(MessageRole::Reasoning)
+                .is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_agent_event_routes_final_by_session_id_after_tab_close() {
+        let session_a = Uuid::new_v4();
+        let session_b = Uuid::new_v4();
+        let session_c = Uuid::new_v4();
+
+        let mut app = build_test_app_with_sessions(&[session_a, session_b, session_c]);
+
+        // Close the first tab so indices shift: B -> 0, C -> 1
+        assert!(app.state.tab_manager.close_tab(0));
+        assert_eq!(
+            app.state.tab_manager.session_index_by_id(session_b),
+            Some(0)
+        );
+        assert_eq!(
+            app.state.tab_manager.session_index_by_id(session_c),
+            Some(1)
+        );
 
         let event = AgentEvent::AssistantMessage(AssistantMessageEvent {
             text: "message for B".to_string(),
@@ -10322,51 +14329,196 @@ mod tests {
         app.handle_list_action(Action::SelectPageDown);
         assert_eq!(app.state.session_import_state.list.selected, 10);
 
-        app.handle_list_action(Action::SelectPageUp);
-        assert_eq!(app.state.session_import_state.list.selected, 0);
+        app.handle_list_action(Action::SelectPageUp);
+        assert_eq!(app.state.session_import_state.list.selected, 0);
+    }
+
+    #[test]
+    fn test_handle_raw_events_toggle_expand_and_collapse() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+
+        {
+            let session = app
+                .state
+                .tab_manager
+                .active_session_mut()
+                .expect("session missing");
+            session.raw_events_view.push_event(
+                EventDirection::Received,
+                "test_event",
+                json!({ "ok": true }),
+            );
+            assert!(!session.raw_events_view.is_expanded());
+        }
+
+        let mut effects = Vec::new();
+        app.handle_raw_events_action(Action::RawEventsToggleExpand, &mut effects);
+        assert!(app
+            .state
+            .tab_manager
+            .active_session()
+            .expect("session missing")
+            .raw_events_view
+            .is_expanded());
+
+        app.handle_raw_events_action(Action::RawEventsCollapse, &mut effects);
+        assert!(!app
+            .state
+            .tab_manager
+            .active_session()
+            .expect("session missing")
+            .raw_events_view
+            .is_expanded());
+    }
+
+    #[test]
+    fn test_handle_raw_events_copy_selected_json() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+
+        {
+            let session = app
+                .state
+                .tab_manager
+                .active_session_mut()
+                .expect("session missing");
+            session.raw_events_view.push_event(
+                EventDirection::Received,
+                "test_event",
+                json!({ "foo": "bar" }),
+            );
+        }
+
+        let mut effects = Vec::new();
+        app.handle_raw_events_action(Action::EventDetailCopy, &mut effects);
+
+        let expected = serde_json::to_string_pretty(&json!({ "foo": "bar" })).unwrap();
+        assert!(matches!(
+            effects.as_slice(),
+            [Effect::CopyToClipboard { text: content, .. }] if content == &expected
+        ));
+    }
+
+    #[test]
+    fn test_raw_events_pausing_follow_keeps_selection() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+        let mut effects = Vec::new();
+
+        {
+            let view = &mut app
+                .state
+                .tab_manager
+                .active_session_mut()
+                .expect("session missing")
+                .raw_events_view;
+            view.push_event(EventDirection::Received, "a", json!({}));
+            view.push_event(EventDirection::Received, "b", json!({}));
+        }
+        app.handle_raw_events_action(Action::RawEventsToggleFollow, &mut effects);
+        {
+            let view = &mut app
+                .state
+                .tab_manager
+                .active_session_mut()
+                .expect("session missing")
+                .raw_events_view;
+            assert!(!view.is_auto_follow());
+            view.select_prev();
+            view.push_event(EventDirection::Received, "c", json!({}));
+            assert_eq!(view.selected_index(), 0);
+        }
+
+        app.handle_raw_events_action(Action::RawEventsToggleFollow, &mut effects);
+        let view = &app
+            .state
+            .tab_manager
+            .active_session()
+            .expect("session missing")
+            .raw_events_view;
+        assert!(view.is_auto_follow());
+        assert_eq!(view.selected_index(), 2);
     }
 
     #[test]
-    fn test_handle_raw_events_toggle_expand_and_collapse() {
+    fn test_raw_events_search_jumps_between_matching_events() {
         let session_id = Uuid::new_v4();
         let mut app = build_test_app_with_sessions(&[session_id]);
 
         {
-            let session = app
+            let view = &mut app
                 .state
                 .tab_manager
                 .active_session_mut()
-                .expect("session missing");
-            session.raw_events_view.push_event(
+                .expect("session missing")
+                .raw_events_view;
+            view.push_event(
                 EventDirection::Received,
-                "test_event",
-                json!({ "ok": true }),
+                "a",
+                json!({ "text": "Needle one" }),
             );
-            assert!(!session.raw_events_view.is_expanded());
+            view.push_event(EventDirection::Received, "b", json!({ "text": "hay" }));
+            view.push_event(
+                EventDirection::Received,
+                "c",
+                json!({ "text": "needle two" }),
+            );
+            view.push_event(EventDirection::Received, "d", json!({ "text": "hay" }));
         }
 
         let mut effects = Vec::new();
-        app.handle_raw_events_action(Action::RawEventsToggleExpand, &mut effects);
-        assert!(app
-            .state
-            .tab_manager
-            .active_session()
-            .expect("session missing")
-            .raw_events_view
-            .is_expanded());
+        app.handle_raw_events_action(Action::RawEventsSearch, &mut effects);
+        {
+            let view = &mut app
+                .state
+                .tab_manager
+                .active_session_mut()
+                .expect("session missing")
+                .raw_events_view;
+            assert!(view.is_search_focused());
+            for c in "NEEDLE".chars() {
+                view.handle_search_key(crossterm::event::KeyEvent::new(
+                    KeyCode::Char(c),
+                    KeyModifiers::NONE,
+                ));
+            }
+            view.handle_search_key(crossterm::event::KeyEvent::new(
+                KeyCode::Enter,
+                KeyModifiers::NONE,
+            ));
+            assert!(!view.is_search_focused());
+            // Search wraps from the last event to the first match
+            assert_eq!(view.selected_index(), 0);
+            assert_eq!(view.search_matches(), vec![0, 2]);
+        }
 
-        app.handle_raw_events_action(Action::RawEventsCollapse, &mut effects);
-        assert!(!app
-            .state
-            .tab_manager
-            .active_session()
-            .expect("session missing")
-            .raw_events_view
-            .is_expanded());
+        app.handle_raw_events_action(Action::RawEventsSearchNext, &mut effects);
+        assert_eq!(
+            app.state
+                .tab_manager
+                .active_session()
+                .unwrap()
+                .raw_events_view
+                .selected_index(),
+            2
+        );
+        app.handle_raw_events_action(Action::RawEventsSearchNext, &mut effects);
+        app.handle_raw_events_action(Action::RawEventsSearchPrev, &mut effects);
+        assert_eq!(
+            app.state
+                .tab_manager
+                .active_session()
+                .unwrap()
+                .raw_events_view
+                .selected_index(),
+            2
+        );
+        assert!(effects.is_empty());
     }
 
     #[test]
-    fn test_handle_raw_events_copy_selected_json() {
+    fn test_handle_raw_events_filter_copies_selected_path() {
         let session_id = Uuid::new_v4();
         let mut app = build_test_app_with_sessions(&[session_id]);
 
@@ -10379,17 +14531,39 @@ mod tests {
             session.raw_events_view.push_event(
                 EventDirection::Received,
                 "test_event",
-                json!({ "foo": "bar" }),
+                json!({ "params": { "msg": { "type": "agent_message" } } }),
             );
         }
 
         let mut effects = Vec::new();
-        app.handle_raw_events_action(Action::EventDetailCopy, &mut effects);
+        app.handle_raw_events_action(Action::EventDetailFilter, &mut effects);
+        {
+            let view = &mut app
+                .state
+                .tab_manager
+                .active_session_mut()
+                .expect("session missing")
+                .raw_events_view;
+            assert!(view.is_filter_focused());
+            assert!(view.is_detail_visible());
+            for c in ".params.msg.type".chars() {
+                view.handle_filter_key(crossterm::event::KeyEvent::new(
+                    KeyCode::Char(c),
+                    KeyModifiers::NONE,
+                ));
+            }
+            view.handle_filter_key(crossterm::event::KeyEvent::new(
+                KeyCode::Enter,
+                KeyModifiers::NONE,
+            ));
+            assert!(!view.is_filter_focused());
+            assert_eq!(view.filter(), ".params.msg.type");
+        }
 
-        let expected = serde_json::to_string_pretty(&json!({ "foo": "bar" })).unwrap();
+        app.handle_raw_events_action(Action::EventDetailCopy, &mut effects);
         assert!(matches!(
             effects.as_slice(),
-            [Effect::CopyToClipboard(content)] if content == &expected
+            [Effect::CopyToClipboard { text: content, .. }] if content == "\"agent_message\""
         ));
     }
 
@@ -10478,6 +14652,47 @@ mod tests {
         assert!(session.queued_messages.is_empty());
     }
 
+    #[test]
+    fn test_submit_waits_for_agent_slot() {
+        let busy_id = Uuid::new_v4();
+        let waiting_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[busy_id, waiting_id]);
+        app.config_mut().queue.max_concurrent_agents = Some(1);
+        app.state
+            .tab_manager
+            .session_mut(0)
+            .expect("session missing")
+            .is_processing = true;
+        app.state.tab_manager.switch_to(1);
+        app.state
+            .tab_manager
+            .active_session_mut()
+            .expect("session missing")
+            .input_box
+            .set_input("run the tests".to_string());
+
+        let effects = app
+            .handle_submit_action(QueuedMessageMode::FollowUp)
+            .expect("submit failed");
+
+        assert!(effects.is_empty());
+        let session = app.state.tab_manager.session(1).expect("session missing");
+        assert!(session.waiting_for_slot);
+        assert_eq!(session.queued_messages.len(), 1);
+        assert!(!session.is_processing);
+
+        // Still full: nothing is dispatched
+        app.dispatch_slot_waiters().expect("dispatch failed");
+        assert!(app.state.tab_manager.session(1).unwrap().waiting_for_slot);
+
+        app.state.tab_manager.session_mut(0).unwrap().is_processing = false;
+        app.dispatch_slot_waiters().expect("dispatch failed");
+
+        let session = app.state.tab_manager.session(1).expect("session missing");
+        assert!(!session.waiting_for_slot);
+        assert!(session.queued_messages.is_empty());
+    }
+
     #[test]
     fn test_handle_overlay_show_help() {
         let mut app = build_test_app_with_sessions(&[]);
@@ -10514,6 +14729,23 @@ mod tests {
         assert!(app.state.error_dialog_state.details_expanded);
     }
 
+    #[test]
+    fn test_session_template_requires_agent_tool() {
+        let mut app = build_test_app_with_sessions(&[]);
+        app.core.config_mut().templates = vec![crate::config::SessionTemplate {
+            name: "Review".to_string(),
+            agent_type: AgentType::Codex,
+            model: None,
+            workspace: None,
+            prompt: Some("Review the diff".to_string()),
+        }];
+
+        app.start_session_template(0);
+
+        assert!(app.state.tab_manager.is_empty());
+        assert!(app.state.missing_tool_dialog_state.is_visible());
+    }
+
     #[test]
     fn test_handle_overlay_select_agent_creates_tab() {
         let mut app = build_test_app_with_sessions(&[]);
@@ -10554,6 +14786,71 @@ mod tests {
         assert!(matches!(effects.as_slice(), [Effect::SaveSessionState]));
     }
 
+    #[test]
+    fn test_prompt_from_editor_drops_one_trailing_newline() {
+        assert_eq!(App::prompt_from_editor("fix it\n".to_string()), "fix it");
+        assert_eq!(App::prompt_from_editor("fix it\r\n".to_string()), "fix it");
+        assert_eq!(
+            App::prompt_from_editor("line one\n\n".to_string()),
+            "line one\n"
+        );
+        assert_eq!(App::prompt_from_editor(String::new()), "");
+    }
+
+    #[test]
+    fn test_quit_with_running_agents_asks_then_waits() {
+        let mut app = build_test_app_with_sessions(&[Uuid::new_v4(), Uuid::new_v4()]);
+        app.state
+            .tab_manager
+            .session_mut(1)
+            .expect("session missing")
+            .start_processing();
+        let mut effects = Vec::new();
+
+        app.handle_global_action(Action::Quit, &mut effects);
+        assert!(!app.state.should_quit);
+        assert!(effects.is_empty());
+        assert_eq!(app.state.input_mode, InputMode::Confirming);
+        assert!(matches!(
+            app.state.confirmation_dialog_state.context,
+            Some(ConfirmationContext::QuitWithRunningAgents)
+        ));
+
+        app.handle_confirmation_action(Action::ConfirmNo, &mut effects)
+            .unwrap();
+        assert!(app.state.quit_when_idle);
+        app.quit_if_idle(&mut effects);
+        assert!(!app.state.should_quit);
+
+        app.state
+            .tab_manager
+            .session_mut(1)
+            .expect("session missing")
+            .stop_processing();
+        app.quit_if_idle(&mut effects);
+        assert!(app.state.should_quit);
+        assert!(matches!(effects.as_slice(), [Effect::SaveSessionState]));
+    }
+
+    #[test]
+    fn test_quit_with_running_agents_kill_all() {
+        let mut app = build_test_app_with_sessions(&[Uuid::new_v4()]);
+        app.state
+            .tab_manager
+            .session_mut(0)
+            .expect("session missing")
+            .start_processing();
+        let mut effects = Vec::new();
+
+        app.handle_global_action(Action::Quit, &mut effects);
+        app.handle_confirmation_action(Action::ConfirmYes, &mut effects)
+            .unwrap();
+
+        assert!(app.state.should_quit);
+        let session = app.state.tab_manager.session(0).expect("session missing");
+        assert!(!session.is_processing);
+    }
+
     #[test]
     fn test_handle_global_toggle_view_mode() {
         let mut app = build_test_app_with_sessions(&[]);
@@ -10566,6 +14863,69 @@ mod tests {
         assert!(effects.is_empty());
     }
 
+    #[test]
+    fn test_toggle_diff_view_loads_completed_edits() {
+        let mut app = build_test_app_with_sessions(&[Uuid::new_v4()]);
+        let mut effects = Vec::new();
+        {
+            let session = app.state.tab_manager.active_session_mut().unwrap();
+            let args = json!({
+                "file_path": "src/lib.rs",
+                "old_string": "let a = 1;",
+                "new_string": "let a = 2;",
+            });
+            session.chat_view.push(
+                ChatMessage::tool("Edit", args.to_string(), "Running...").with_tool_id("edit-1"),
+            );
+            assert!(session.chat_view.update_tool_by_id(
+                "edit-1",
+                "The file src/lib.rs has been updated.".to_string(),
+                None
+            ));
+            assert_eq!(
+                session.chat_view.messages()[0].content,
+                "@@ -1 +1 @@\n-let a = 1;\n+let a = 2;\n"
+            );
+        }
+
+        app.handle_global_action(Action::ToggleDiffView, &mut effects);
+        assert_eq!(app.state.view_mode, ViewMode::Diff);
+        let session = app.state.tab_manager.active_session().unwrap();
+        assert_eq!(session.diff_view.diffs().len(), 1);
+        assert_eq!(session.diff_view.diffs()[0].path, "src/lib.rs");
+
+        app.handle_global_action(Action::ToggleDiffView, &mut effects);
+        assert_eq!(app.state.view_mode, ViewMode::Chat);
+        assert!(effects.is_empty());
+    }
+
+    #[test]
+    fn test_handle_global_toggle_timeline() {
+        let mut app = build_test_app_with_sessions(&[]);
+        let mut effects = Vec::new();
+
+        app.state.view_mode = ViewMode::RawEvents;
+        app.handle_global_action(Action::ToggleTimeline, &mut effects);
+        assert_eq!(app.state.view_mode, ViewMode::Timeline);
+
+        app.handle_global_action(Action::ToggleTimeline, &mut effects);
+        assert_eq!(app.state.view_mode, ViewMode::Chat);
+        assert!(effects.is_empty());
+    }
+
+    #[test]
+    fn test_zen_mode_only_applies_to_chat_tabs() {
+        let mut app = build_test_app_with_sessions(&[Uuid::new_v4()]);
+        let mut effects = Vec::new();
+
+        app.handle_global_action(Action::ToggleZenMode, &mut effects);
+        assert!(app.state.zen_mode);
+        assert!(app.zen_mode_active());
+
+        app.state.view_mode = ViewMode::RawEvents;
+        assert!(!app.zen_mode_active());
+    }
+
     #[test]
     fn test_handle_global_copy_workspace_path() {
         let session_id = Uuid::new_v4();
@@ -10585,7 +14945,7 @@ mod tests {
 
         assert!(matches!(
             effects.as_slice(),
-            [Effect::CopyToClipboard(content)] if content == "workspace"
+            [Effect::CopyToClipboard { text: content, .. }] if content == "workspace"
         ));
     }
 
@@ -10640,4 +15000,210 @@ mod tests {
         assert!(app.state.tab_manager.active_session().is_some());
         assert!(effects.is_empty());
     }
+
+    #[test]
+    fn test_append_language_directive() {
+        assert_eq!(
+            app_prompt::append_language_directive("fix the build", Some("German")),
+            "fix the build\n\n<system-reminder>Respond in German.</system-reminder>"
+        );
+        assert_eq!(
+            app_prompt::append_language_directive("fix the build", Some("  ")),
+            "fix the build"
+        );
+        assert_eq!(
+            app_prompt::append_language_directive("fix the build", None),
+            "fix the build"
+        );
+    }
+
+    #[test]
+    fn test_export_command_parses_format() {
+        assert_eq!(
+            parse_export_command("/export json"),
+            Some(Ok(TranscriptFormat::Json))
+        );
+        assert_eq!(
+            parse_export_command("/export"),
+            Some(Ok(TranscriptFormat::Markdown))
+        );
+        assert!(matches!(parse_export_command("/export docx"), Some(Err(_))));
+    }
+
+    #[test]
+    fn test_link_and_unlink_commands_are_told_apart() {
+        assert_eq!(
+            parse_link_command("/link acme-client"),
+            Some(LinkCommand::Link("acme-client".to_string()))
+        );
+        assert_eq!(
+            parse_link_command("/unlink acme-client"),
+            Some(LinkCommand::Unlink(Some("acme-client".to_string())))
+        );
+        assert_eq!(parse_link_command("/linked"), None);
+    }
+
+    #[test]
+    fn test_fetch_command_prepends_page_context() {
+        assert_eq!(
+            parse_fetch_command("/fetch https://example.com "),
+            Some("https://example.com")
+        );
+        assert_eq!(parse_fetch_command("/fetch"), Some(""));
+        assert_eq!(parse_fetch_command("/fetcher"), None);
+
+        let page = crate::util::url_fetch::FetchedPage {
+            url: "https://example.com/".to_string(),
+            title: Some("Example".to_string()),
+            text: "Hello".to_string(),
+            truncated: false,
+        };
+        assert_eq!(
+            app_prompt::prepend_fetched_context("summarize it", &[page]),
+            "<context source=\"https://example.com/\" title=\"Example\">\nHello\n</context>\n\nsummarize it"
+        );
+        assert_eq!(
+            app_prompt::prepend_fetched_context("summarize it", &[]),
+            "summarize it"
+        );
+    }
+
+    #[test]
+    fn test_lang_command_sets_and_clears_response_language() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+
+        app.state.command_buffer = "lang German".to_string();
+        assert!(app.execute_command().is_none());
+        assert_eq!(
+            app.state
+                .tab_manager
+                .active_session()
+                .and_then(|s| s.response_language.as_deref()),
+            Some("German")
+        );
+
+        // Toggling with a language set clears it
+        let mut effects = Vec::new();
+        app.handle_global_action(Action::ToggleResponseLanguage, &mut effects);
+        assert!(app
+            .state
+            .tab_manager
+            .active_session()
+            .is_some_and(|s| s.response_language.is_none()));
+
+        // Nothing configured to toggle to: prompt for one on the command line
+        app.handle_global_action(Action::ToggleResponseLanguage, &mut effects);
+        assert_eq!(app.state.input_mode, InputMode::Command);
+        assert_eq!(app.state.command_buffer, "lang ");
+    }
+
+    #[test]
+    fn test_cost_center_command_sets_and_clears_tag() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+
+        app.state.command_buffer = "cost-center research".to_string();
+        assert!(app.execute_command().is_none());
+        assert_eq!(
+            app.state
+                .tab_manager
+                .active_session()
+                .and_then(|s| s.cost_center.as_deref()),
+            Some("research")
+        );
+
+        app.state.command_buffer = "cost-center off".to_string();
+        assert!(app.execute_command().is_none());
+        assert!(app
+            .state
+            .tab_manager
+            .active_session()
+            .is_some_and(|s| s.cost_center.is_none()));
+    }
+
+    #[test]
+    fn test_edit_past_prompt_prefills_input() {
+        let session_id = Uuid::new_v4();
+        let mut app = build_test_app_with_sessions(&[session_id]);
+        let mut effects = Vec::new();
+
+        app.handle_global_action(Action::EditPastPrompt, &mut effects);
+        assert_eq!(
+            app.state.footer_message.as_deref(),
+            Some("No prompt to edit")
+        );
+
+        if let Some(session) = app.state.tab_manager.active_session_mut() {
+            session.chat_view.push(ChatMessage::user("fix the build"));
+            session.chat_view.push(ChatMessage::assistant("done"));
+        }
+        app.handle_global_action(Action::EditPastPrompt, &mut effects);
+        assert_eq!(
+            app.state
+                .tab_manager
+                .active_session()
+                .map(|s| s.input_box.input().to_string()),
+            Some("fix the build".to_string())
+        );
+
+        // No snapshot was taken for this prompt, so there's nothing to roll back to
+        app.handle_global_action(Action::RollbackToPastPrompt, &mut effects);
+        assert_eq!(
+            app.state.footer_message.as_deref(),
+            Some("No workspace snapshot for that prompt")
+        );
+        assert_ne!(app.state.input_mode, InputMode::Confirming);
+
+        if let Some(session) = app.state.tab_manager.active_session_mut() {
+            session.prompt_snapshots.insert(0, "tree".to_string());
+        }
+        app.handle_global_action(Action::RollbackToPastPrompt, &mut effects);
+        assert_eq!(app.state.input_mode, InputMode::Confirming);
+        assert!(effects.is_empty());
+    }
+
+    #[test]
+    fn test_style_command_toggles_response_preset() {
+        let mut session = AgentSession::new(AgentType::Claude);
+        assert_eq!(
+            parse_response_preset_command("/style  concise "),
+            Some("concise")
+        );
+        assert_eq!(parse_response_preset_command("/stylesheet"), None);
+
+        apply_response_preset_command(&mut session, "concise");
+        assert_eq!(session.response_preset, Some(ResponsePreset::Concise));
+        assert_eq!(
+            app_prompt::append_preset_directive("fix it", session.response_preset),
+            format!(
+                "fix it\n\n<system-reminder>{}</system-reminder>",
+                ResponsePreset::Concise.instruction()
+            )
+        );
+
+        // Naming the active preset again switches it off
+        apply_response_preset_command(&mut session, "concise");
+        assert_eq!(session.response_preset, None);
+
+        apply_response_preset_command(&mut session, "code_only");
+        assert_eq!(session.response_preset, Some(ResponsePreset::CodeOnly));
+        let note = apply_response_preset_command(&mut session, "haiku");
+        assert!(note.starts_with("Unknown response style"));
+        assert_eq!(session.response_preset, Some(ResponsePreset::CodeOnly));
+        apply_response_preset_command(&mut session, "off");
+        assert_eq!(session.response_preset, None);
+    }
+
+    #[test]
+    fn test_parse_system_prompt_command() {
+        assert_eq!(
+            parse_system_prompt_command("/system  Be terse. "),
+            Some(Some("Be terse."))
+        );
+        assert_eq!(parse_system_prompt_command("/system"), Some(None));
+        assert_eq!(parse_system_prompt_command("/system   "), Some(None));
+        assert_eq!(parse_system_prompt_command("/systematic review"), None);
+        assert_eq!(parse_system_prompt_command("explain /system"), None);
+    }
 }
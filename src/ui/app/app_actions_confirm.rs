@@ -27,9 +27,12 @@ impl App {
                             self.state.input_mode = InputMode::Normal;
                             self.state.sidebar_state.set_focused(false);
                         }
-                        NodeType::Repository => {
+                        NodeType::Repository | NodeType::SessionGroup => {
                             self.state.sidebar_data.toggle_at(selected);
                         }
+                        NodeType::Session => {
+                            self.open_saved_session(node.id, true);
+                        }
                     }
                 }
             }
@@ -38,8 +41,7 @@ impl App {
                     let model_id = model.id.clone();
                     let agent_type = model.agent_type;
                     let display_name = model.display_name.clone();
-                    let required_tool = Self::required_tool(agent_type);
-                    if !self.tools().is_available(required_tool) {
+                    if let Some(required_tool) = self.missing_tool_for(agent_type) {
                         self.show_missing_tool(
                             required_tool,
                             format!(
@@ -130,6 +132,15 @@ impl App {
                     self.state.input_mode = InputMode::PickingProject;
                 }
             }
+            InputMode::RenamingTab => {
+                let name = self.state.rename_tab_dialog_state.name();
+                self.state.rename_tab_dialog_state.hide();
+                self.state.input_mode = InputMode::Normal;
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    session.custom_title = name;
+                    effects.push(Effect::SaveSessionState);
+                }
+            }
             InputMode::Confirming => {
                 if let Some(context) = self.state.confirmation_dialog_state.context.clone() {
                     match context {
@@ -223,6 +234,7 @@ impl App {
                             tab_index,
                             working_dir,
                             preflight,
+                            linked,
                         } => {
                             if self.state.confirmation_dialog_state.is_confirm_selected() {
                                 self.state.confirmation_dialog_state.hide();
@@ -231,6 +243,43 @@ impl App {
                                     tab_index,
                                     working_dir,
                                     preflight,
+                                    linked,
+                                )?);
+                                return Ok(());
+                            }
+                        }
+                        ConfirmationContext::AcknowledgeChangeFlags {
+                            tab_index,
+                            working_dir,
+                            preflight,
+                            linked,
+                            security,
+                            keys,
+                        } => {
+                            if self.state.confirmation_dialog_state.is_confirm_selected() {
+                                effects.extend(self.acknowledge_change_flags(
+                                    tab_index,
+                                    working_dir,
+                                    preflight,
+                                    linked,
+                                    security,
+                                    keys,
+                                ));
+                                return Ok(());
+                            }
+                        }
+                        ConfirmationContext::FixSecurityFindings {
+                            tab_index,
+                            working_dir,
+                            prompt,
+                        } => {
+                            if self.state.confirmation_dialog_state.is_confirm_selected() {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::Normal;
+                                effects.extend(self.submit_security_fix(
+                                    tab_index,
+                                    working_dir,
+                                    prompt,
                                 )?);
                                 return Ok(());
                             }
@@ -251,6 +300,18 @@ impl App {
                                 return Ok(());
                             }
                         }
+                        ConfirmationContext::RollbackPrompt {
+                            session_id,
+                            prompt_index,
+                        } => {
+                            if self.state.confirmation_dialog_state.is_confirm_selected() {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::Normal;
+                                effects
+                                    .extend(self.confirm_prompt_rollback(session_id, prompt_index));
+                                return Ok(());
+                            }
+                        }
                         ConfirmationContext::ForkSession {
                             parent_workspace_id,
                             base_branch,
@@ -266,6 +327,15 @@ impl App {
                                 return Ok(());
                             }
                         }
+                        ConfirmationContext::QuitWithRunningAgents => {
+                            // Both buttons act here; Esc is the cancel
+                            if self.state.confirmation_dialog_state.is_confirm_selected() {
+                                self.kill_agents_and_quit(effects);
+                            } else {
+                                self.wait_then_quit();
+                            }
+                            return Ok(());
+                        }
                     }
                 }
                 // Cancel selected - dismiss the confirmation dialog
@@ -91,6 +91,7 @@ impl App {
                                 tab_index,
                                 working_dir,
                                 preflight,
+                                linked,
                             } => {
                                 self.state.confirmation_dialog_state.hide();
                                 self.state.input_mode = InputMode::Normal;
@@ -98,6 +99,37 @@ impl App {
                                     tab_index,
                                     working_dir,
                                     preflight,
+                                    linked,
+                                )?);
+                            }
+                            ConfirmationContext::AcknowledgeChangeFlags {
+                                tab_index,
+                                working_dir,
+                                preflight,
+                                linked,
+                                security,
+                                keys,
+                            } => {
+                                effects.extend(self.acknowledge_change_flags(
+                                    tab_index,
+                                    working_dir,
+                                    preflight,
+                                    linked,
+                                    security,
+                                    keys,
+                                ));
+                            }
+                            ConfirmationContext::FixSecurityFindings {
+                                tab_index,
+                                working_dir,
+                                prompt,
+                            } => {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::Normal;
+                                effects.extend(self.submit_security_fix(
+                                    tab_index,
+                                    working_dir,
+                                    prompt,
                                 )?);
                             }
                             ConfirmationContext::OpenExistingPr { working_dir, .. } => {
@@ -110,6 +142,15 @@ impl App {
                                 self.state.input_mode = InputMode::Normal;
                                 effects.extend(self.confirm_steer_fallback(message_id)?);
                             }
+                            ConfirmationContext::RollbackPrompt {
+                                session_id,
+                                prompt_index,
+                            } => {
+                                self.state.confirmation_dialog_state.hide();
+                                self.state.input_mode = InputMode::Normal;
+                                effects
+                                    .extend(self.confirm_prompt_rollback(session_id, prompt_index));
+                            }
                             ConfirmationContext::ForkSession {
                                 parent_workspace_id,
                                 base_branch,
@@ -122,6 +163,9 @@ impl App {
                                     effects.push(effect);
                                 }
                             }
+                            ConfirmationContext::QuitWithRunningAgents => {
+                                self.kill_agents_and_quit(effects);
+                            }
                         }
                     }
                 }
@@ -154,6 +198,9 @@ impl App {
                                 self.state.confirmation_dialog_state.hide();
                                 self.state.input_mode = InputMode::SidebarNavigation;
                             }
+                            ConfirmationContext::QuitWithRunningAgents => {
+                                self.wait_then_quit();
+                            }
                             _ => {
                                 self.state.input_mode = self.dismiss_confirmation_dialog();
                             }
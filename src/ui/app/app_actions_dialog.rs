@@ -34,6 +34,10 @@ impl App {
                     self.state.base_dir_dialog_state.hide();
                     self.state.input_mode = InputMode::Normal;
                 }
+                InputMode::RenamingTab => {
+                    self.state.rename_tab_dialog_state.hide();
+                    self.state.input_mode = InputMode::Normal;
+                }
                 InputMode::Confirming => {
                     self.state.input_mode = self.dismiss_confirmation_dialog();
                 }
@@ -71,6 +75,12 @@ impl App {
                 InputMode::QueueEditing => {
                     self.close_queue_editor();
                 }
+                InputMode::SelectingMessage => {
+                    self.exit_message_selection();
+                }
+                InputMode::ClipboardHistory => {
+                    self.state.input_mode = InputMode::Normal;
+                }
                 _ => {}
             },
             Action::AddRepository => match self.state.input_mode {
@@ -1,16 +1,20 @@
+use crate::agent::AgentType;
 use crate::ui::action::Action;
 use crate::ui::app::App;
 use crate::ui::app_state::SelectionDragTarget;
 use crate::ui::effect::Effect;
 use crate::ui::events::{InputMode, ViewMode};
+use crate::ui::transcript_export::TranscriptFormat;
 use std::time::Duration;
 
 impl App {
     pub(super) fn handle_global_action(&mut self, action: Action, effects: &mut Vec<Effect>) {
         match action {
             Action::Quit => {
-                self.state.should_quit = true;
-                effects.push(Effect::SaveSessionState);
+                self.request_quit(effects);
+            }
+            Action::ClearOrQuit => {
+                effects.extend(self.handle_ctrl_c_press());
             }
             Action::NewProject => {
                 self.open_project_picker_or_base_dir();
@@ -28,6 +32,7 @@ impl App {
                             NodeType::Repository => Some(node.id),
                             NodeType::Workspace => node.parent_id,
                             NodeType::Action(ActionType::NewWorkspace) => node.parent_id,
+                            NodeType::SessionGroup | NodeType::Session => None,
                         })
                 } else {
                     None
@@ -89,9 +94,34 @@ impl App {
             Action::ToggleViewMode => {
                 self.state.view_mode = match self.state.view_mode {
                     ViewMode::Chat => ViewMode::RawEvents,
-                    ViewMode::RawEvents => ViewMode::Chat,
+                    ViewMode::RawEvents | ViewMode::Timeline | ViewMode::Diff => ViewMode::Chat,
                 };
             }
+            Action::ToggleTimeline => {
+                self.state.view_mode = match self.state.view_mode {
+                    ViewMode::Timeline => ViewMode::Chat,
+                    ViewMode::Chat | ViewMode::RawEvents | ViewMode::Diff => ViewMode::Timeline,
+                };
+            }
+            Action::ToggleDiffView => {
+                if self.state.view_mode == ViewMode::Diff {
+                    self.state.view_mode = ViewMode::Chat;
+                } else if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    // Snapshot the edits so the view doesn't shift while reading
+                    session.diff_view.load(session.chat_view.messages());
+                    let flags = session
+                        .change_flags
+                        .iter()
+                        .map(|flag| {
+                            let acknowledged =
+                                session.acknowledged_change_flags.contains(&flag.key());
+                            (flag.clone(), acknowledged)
+                        })
+                        .collect();
+                    session.diff_view.set_flags(flags);
+                    self.state.view_mode = ViewMode::Diff;
+                }
+            }
             Action::ShowModelSelector => {
                 if let Some(session) = self.state.tab_manager.active_session() {
                     let model = session.model.clone();
@@ -140,6 +170,12 @@ impl App {
                 //     self.state.stop_footer_spinner();
                 // }
             }
+            Action::ToggleFrameDiagnostics => {
+                self.state.frame_diagnostics.toggle();
+            }
+            Action::ToggleZenMode => {
+                self.state.zen_mode = !self.state.zen_mode;
+            }
             Action::ToggleAgentMode => {
                 if let Some(session) = self.state.tab_manager.active_session_mut() {
                     // Only toggle when agent supports plan mode
@@ -149,14 +185,106 @@ impl App {
                     }
                 }
             }
+            Action::CycleCodexSandbox => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    if session.agent_type == AgentType::Codex {
+                        let mode = session.codex_sandbox.unwrap_or_default().next();
+                        session.codex_sandbox = Some(mode);
+                        self.state.set_timed_footer_message(
+                            format!(
+                                "Codex sandbox: {} (applies to next run)",
+                                mode.display_name()
+                            ),
+                            Duration::from_secs(3),
+                        );
+                    }
+                }
+            }
+            Action::CycleCodexApproval => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    if session.agent_type == AgentType::Codex {
+                        let policy = session.codex_approval.unwrap_or_default().next();
+                        session.codex_approval = Some(policy);
+                        self.state.set_timed_footer_message(
+                            format!(
+                                "Codex approvals: {} (applies to next run)",
+                                policy.display_name()
+                            ),
+                            Duration::from_secs(3),
+                        );
+                    }
+                }
+            }
+            Action::ToggleEditReview => {
+                let default = self.config().review.edits;
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    let message = if session.agent_type == AgentType::Claude {
+                        let enabled = !session.edit_review.unwrap_or(default);
+                        session.edit_review = Some(enabled);
+                        format!(
+                            "Edit review {} (applies to next run)",
+                            if enabled { "on" } else { "off" }
+                        )
+                    } else {
+                        "Edit review is only available for Claude sessions".to_string()
+                    };
+                    self.state
+                        .set_timed_footer_message(message, Duration::from_secs(3));
+                }
+            }
+            Action::ExportTranscriptPdf => {
+                self.export_active_transcript(TranscriptFormat::Pdf, effects);
+            }
+            Action::ExportTranscriptMarkdown => {
+                self.export_active_transcript(TranscriptFormat::Markdown, effects);
+            }
+            Action::ExportTranscriptJson => {
+                self.export_active_transcript(TranscriptFormat::Json, effects);
+            }
             Action::DumpDebugState => {
                 effects.push(Effect::DumpDebugState);
             }
+            Action::ShowDiagnostics => {
+                self.show_agent_diagnostics();
+            }
+            Action::AnnotateImage => {
+                self.start_image_annotation();
+            }
+            Action::ToggleVoiceInput => {
+                self.toggle_voice_input(effects);
+            }
+            Action::ToggleReadAloud => {
+                self.toggle_read_aloud();
+            }
+            Action::ToggleResponseLanguage => {
+                self.toggle_response_language();
+            }
+            Action::EditPastPrompt => {
+                self.edit_past_prompt();
+            }
+            Action::RollbackToPastPrompt => {
+                self.prompt_rollback_to_past_prompt();
+            }
+            Action::RestartAgent => match self.restart_crashed_agent() {
+                Ok(mut restart_effects) => effects.append(&mut restart_effects),
+                Err(err) => {
+                    tracing::warn!(error = %err, "Failed to restart crashed agent");
+                }
+            },
+            Action::NudgeAgent => match self.nudge_stalled_agent() {
+                Ok(mut nudge_effects) => effects.append(&mut nudge_effects),
+                Err(err) => {
+                    tracing::warn!(error = %err, "Failed to nudge stalled agent");
+                }
+            },
             Action::CopyWorkspacePath => {
                 if let Some(session) = self.state.tab_manager.active_session() {
                     if let Some(working_dir) = &session.working_dir {
                         let path_str = working_dir.display().to_string();
-                        effects.push(Effect::CopyToClipboard(path_str.clone()));
+                        effects.push(Effect::CopyToClipboard {
+                            text: path_str.clone(),
+                            source: "workspace path",
+                        });
                         self.state.set_timed_footer_message(
                             format!("Copied: {}", path_str),
                             Duration::from_secs(10),
@@ -171,7 +299,10 @@ impl App {
                     if session.input_box.has_selection() {
                         if let Some(text) = session.input_box.selected_text() {
                             copied = true;
-                            effects.push(Effect::CopyToClipboard(text));
+                            effects.push(Effect::CopyToClipboard {
+                                text,
+                                source: "input selection",
+                            });
                             if clear_after_copy {
                                 Self::clear_selection_for_target(
                                     session,
@@ -182,7 +313,10 @@ impl App {
                     } else if session.chat_view.has_selection() {
                         if let Some(text) = session.chat_view.copy_selection() {
                             copied = true;
-                            effects.push(Effect::CopyToClipboard(text));
+                            effects.push(Effect::CopyToClipboard {
+                                text,
+                                source: "chat selection",
+                            });
                             if clear_after_copy {
                                 Self::clear_selection_for_target(
                                     session,
@@ -208,4 +342,42 @@ impl App {
             _ => {}
         }
     }
+
+    /// Queue an export of the active session's transcript
+    pub(super) fn export_active_transcript(
+        &mut self,
+        format: TranscriptFormat,
+        effects: &mut Vec<Effect>,
+    ) {
+        let Some(session) = self.state.tab_manager.active_session() else {
+            return;
+        };
+        if session.chat_view.is_empty() {
+            self.state.set_timed_footer_message(
+                "Nothing to export yet".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        }
+        let title = session
+            .title
+            .clone()
+            .or_else(|| session.workspace_name.clone())
+            .unwrap_or_else(|| "Conduit session".to_string());
+        let file_stem = format!(
+            "transcript_{}_{}",
+            chrono::Local::now().format("%Y%m%d_%H%M%S"),
+            session.id.simple()
+        );
+        effects.push(Effect::ExportTranscript {
+            format,
+            title,
+            file_stem,
+            messages: session.chat_view.messages().to_vec(),
+        });
+        self.state.set_timed_footer_message(
+            "Exporting transcript...".to_string(),
+            Duration::from_secs(3),
+        );
+    }
 }
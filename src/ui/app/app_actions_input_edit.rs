@@ -1,6 +1,7 @@
 use crate::ui::action::Action;
 use crate::ui::app::App;
 use crate::ui::events::InputMode;
+use std::time::Duration;
 
 impl App {
     pub(super) fn handle_input_edit_action(&mut self, action: Action) {
@@ -43,6 +44,9 @@ impl App {
                 InputMode::SettingBaseDir => {
                     self.state.base_dir_dialog_state.delete_char();
                 }
+                InputMode::RenamingTab => {
+                    self.state.rename_tab_dialog_state.delete_char();
+                }
                 InputMode::AddingRepository => {
                     self.state.add_repo_dialog_state.delete_char();
                 }
@@ -72,6 +76,8 @@ impl App {
                     self.state.slash_menu_state.delete_forward();
                 } else if self.state.input_mode == InputMode::SettingBaseDir {
                     self.state.base_dir_dialog_state.delete_forward();
+                } else if self.state.input_mode == InputMode::RenamingTab {
+                    self.state.rename_tab_dialog_state.delete_forward();
                 } else if self.state.input_mode == InputMode::AddingRepository {
                     self.state.add_repo_dialog_state.delete_forward();
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
@@ -96,6 +102,37 @@ impl App {
                     session.input_box.delete_to_end();
                 }
             }
+            Action::Undo | Action::Redo => {
+                let undo = action == Action::Undo;
+                let Some(session) = self.state.tab_manager.active_session_mut() else {
+                    return;
+                };
+                let changed = if undo {
+                    session.input_box.undo()
+                } else {
+                    session.input_box.redo()
+                };
+                if !changed {
+                    let message = if undo {
+                        "Nothing to undo"
+                    } else {
+                        "Nothing to redo"
+                    };
+                    self.state
+                        .set_timed_footer_message(message.to_string(), Duration::from_secs(2));
+                }
+            }
+            Action::RemoveAttachment => {
+                let Some(session) = self.state.tab_manager.active_session_mut() else {
+                    return;
+                };
+                if !session.input_box.remove_last_attachment() {
+                    self.state.set_timed_footer_message(
+                        "No attached image to remove".to_string(),
+                        Duration::from_secs(2),
+                    );
+                }
+            }
             Action::MoveCursorLeft => {
                 if self.state.input_mode == InputMode::SelectingTheme {
                     self.state.theme_picker_state.move_left();
@@ -105,6 +142,8 @@ impl App {
                     self.state.add_repo_dialog_state.move_left();
                 } else if self.state.input_mode == InputMode::SettingBaseDir {
                     self.state.base_dir_dialog_state.move_left();
+                } else if self.state.input_mode == InputMode::RenamingTab {
+                    self.state.rename_tab_dialog_state.move_left();
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.input_box.move_left();
                 }
@@ -118,6 +157,8 @@ impl App {
                     self.state.add_repo_dialog_state.move_right();
                 } else if self.state.input_mode == InputMode::SettingBaseDir {
                     self.state.base_dir_dialog_state.move_right();
+                } else if self.state.input_mode == InputMode::RenamingTab {
+                    self.state.rename_tab_dialog_state.move_right();
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.input_box.move_right();
                 }
@@ -131,6 +172,8 @@ impl App {
                     self.state.add_repo_dialog_state.move_start();
                 } else if self.state.input_mode == InputMode::SettingBaseDir {
                     self.state.base_dir_dialog_state.move_start();
+                } else if self.state.input_mode == InputMode::RenamingTab {
+                    self.state.rename_tab_dialog_state.move_start();
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.input_box.move_start();
                 }
@@ -144,6 +187,8 @@ impl App {
                     self.state.add_repo_dialog_state.move_end();
                 } else if self.state.input_mode == InputMode::SettingBaseDir {
                     self.state.base_dir_dialog_state.move_end();
+                } else if self.state.input_mode == InputMode::RenamingTab {
+                    self.state.rename_tab_dialog_state.move_end();
                 } else if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.input_box.move_end();
                 }
@@ -39,6 +39,12 @@ impl App {
                         session.select_queue_next();
                     }
                 }
+                InputMode::SelectingMessage => {
+                    self.move_message_selection(false);
+                }
+                InputMode::ClipboardHistory => {
+                    self.state.clipboard_history.select_next();
+                }
                 _ => {}
             },
             Action::SelectPrev => match self.state.input_mode {
@@ -75,6 +81,12 @@ impl App {
                         session.select_queue_prev();
                     }
                 }
+                InputMode::SelectingMessage => {
+                    self.move_message_selection(true);
+                }
+                InputMode::ClipboardHistory => {
+                    self.state.clipboard_history.select_prev();
+                }
                 _ => {}
             },
             Action::SelectPageDown => {
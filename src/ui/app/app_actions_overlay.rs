@@ -1,3 +1,4 @@
+use crate::agent::AgentType;
 use crate::ui::action::Action;
 use crate::ui::app::App;
 use crate::ui::events::InputMode;
@@ -20,20 +21,23 @@ impl App {
             Action::ShowHelp => {
                 self.state.close_overlays();
                 let keybindings = self.config().keybindings.clone();
-                self.state.help_dialog_state.show(&keybindings);
+                let issues = self.config().keybinding_issues.clone();
+                self.state.help_dialog_state.show(&keybindings, &issues);
                 self.state.input_mode = InputMode::ShowingHelp;
             }
             Action::OpenCommandPalette => {
                 self.state.close_overlays();
                 let keybindings = self.config().keybindings.clone();
-                let supports_plan_mode = self
-                    .state
-                    .tab_manager
-                    .active_session()
-                    .is_some_and(|s| s.capabilities.supports_plan_mode);
-                self.state
-                    .command_palette_state
-                    .show(&keybindings, supports_plan_mode);
+                let active_session = self.state.tab_manager.active_session();
+                let supports_plan_mode =
+                    active_session.is_some_and(|s| s.capabilities.supports_plan_mode);
+                let is_codex_session =
+                    active_session.is_some_and(|s| s.agent_type == AgentType::Codex);
+                self.state.command_palette_state.show(
+                    &keybindings,
+                    supports_plan_mode,
+                    is_codex_session,
+                );
                 self.state.input_mode = InputMode::CommandPalette;
             }
             _ => {}
@@ -12,6 +12,11 @@ impl App {
             Some(d) => d.clone(),
             None => return None, // No working dir
         };
+        let linked_dirs = session
+            .linked_workspaces
+            .iter()
+            .map(|linked| (linked.display_name().to_string(), linked.path.clone()))
+            .collect();
 
         // Show loading dialog immediately
         self.state.close_overlays();
@@ -23,6 +28,9 @@ impl App {
         Some(Effect::PrPreflight {
             tab_index,
             working_dir,
+            linked_dirs,
+            security_scan: self.config().security_scan.clone(),
+            change_guard: self.config().change_guard,
         })
     }
 }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::ui::action::Action;
 use crate::ui::app::App;
 use crate::ui::events::InputMode;
@@ -11,6 +13,12 @@ impl App {
             Action::CloseQueueEditor => {
                 self.close_queue_editor();
             }
+            Action::ExpandQueue => {
+                self.expand_queue_editor();
+            }
+            Action::ToggleQueuePanel => {
+                self.state.queue_collapsed = !self.state.queue_collapsed;
+            }
             Action::QueueMoveUp => {
                 if self.state.input_mode == InputMode::QueueEditing {
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
@@ -29,6 +37,23 @@ impl App {
             }
             Action::QueueEdit => {
                 if self.state.input_mode == InputMode::QueueEditing {
+                    // Editing replaces the input box, so an unsent draft would be lost
+                    let has_draft =
+                        self.state
+                            .tab_manager
+                            .active_session()
+                            .is_some_and(|session| {
+                                !session.input_box.is_empty()
+                                    || session.input_box.last_attached_image().is_some()
+                            });
+                    if has_draft {
+                        self.state.set_timed_footer_message(
+                            "Send or clear the current draft before editing a queued message"
+                                .to_string(),
+                            Duration::from_secs(4),
+                        );
+                        return;
+                    }
                     let mut message = None;
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
                         message = session.dequeue_selected();
@@ -45,15 +70,21 @@ impl App {
             Action::QueueDelete => {
                 if self.state.input_mode == InputMode::QueueEditing {
                     let mut should_close = false;
+                    let mut removed = None;
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
                         if let Some(idx) = session.queue_selection {
-                            session.remove_queue_at(idx);
+                            removed = session.remove_queue_at(idx);
                         }
                         crate::ui::app_queue::clamp_queue_selection(session);
                         if session.queued_messages.is_empty() {
                             should_close = true;
                         }
                     }
+                    if let Some(message) = removed {
+                        let image_paths: Vec<_> =
+                            message.images.into_iter().map(|img| img.path).collect();
+                        self.release_attachments(&image_paths);
+                    }
                     if should_close {
                         self.close_queue_editor();
                     }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use crate::ui::action::Action;
 use crate::ui::app::App;
 use crate::ui::effect::Effect;
@@ -25,6 +27,44 @@ impl App {
                     session.raw_events_view.collapse();
                 }
             }
+            Action::RawEventsSearch => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    session.raw_events_view.focus_search();
+                }
+            }
+            Action::RawEventsSearchNext | Action::RawEventsSearchPrev => {
+                let Some(session) = self.state.tab_manager.active_session_mut() else {
+                    return;
+                };
+                let view = &mut session.raw_events_view;
+                if view.search().is_empty() {
+                    view.focus_search();
+                    return;
+                }
+                let found = if action == Action::RawEventsSearchNext {
+                    view.search_next()
+                } else {
+                    view.search_prev()
+                };
+                if !found {
+                    let message = format!("No events match \"{}\"", view.search());
+                    self.state
+                        .set_timed_footer_message(message, Duration::from_secs(3));
+                }
+            }
+            Action::RawEventsToggleFollow => {
+                let Some(session) = self.state.tab_manager.active_session_mut() else {
+                    return;
+                };
+                session.raw_events_view.toggle_auto_follow();
+                let message = if session.raw_events_view.is_auto_follow() {
+                    "Following new events"
+                } else {
+                    "Paused on the selected event"
+                };
+                self.state
+                    .set_timed_footer_message(message.to_string(), Duration::from_secs(3));
+            }
             Action::EventDetailToggle => {
                 if let Some(session) = self.state.tab_manager.active_session_mut() {
                     session.raw_events_view.toggle_detail();
@@ -92,10 +132,28 @@ impl App {
             Action::EventDetailCopy => {
                 if let Some(session) = self.state.tab_manager.active_session() {
                     if let Some(json) = session.raw_events_view.get_selected_json() {
-                        effects.push(Effect::CopyToClipboard(json));
+                        effects.push(Effect::CopyToClipboard {
+                            text: json,
+                            source: "event JSON",
+                        });
                     }
                 }
             }
+            Action::EventDetailFold => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    session.raw_events_view.fold_detail();
+                }
+            }
+            Action::EventDetailUnfold => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    session.raw_events_view.unfold_detail();
+                }
+            }
+            Action::EventDetailFilter => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    session.raw_events_view.focus_filter();
+                }
+            }
             _ => {}
         }
     }
@@ -1,7 +1,13 @@
 use crate::ui::action::Action;
 use crate::ui::app::App;
-use crate::ui::components::ChatView;
-use crate::ui::events::InputMode;
+use crate::ui::events::{InputMode, ViewMode};
+use std::time::Duration;
+
+/// Turns moved per page in the timeline view
+const TIMELINE_PAGE_TURNS: usize = 5;
+
+/// Rows moved per page in the diff view
+const DIFF_PAGE_ROWS: usize = 20;
 
 impl App {
     pub(super) fn handle_scroll_action(&mut self, action: Action) {
@@ -11,6 +17,19 @@ impl App {
             return;
         }
 
+        if self.state.view_mode == ViewMode::Timeline
+            && self.state.input_mode != InputMode::ShowingHelp
+        {
+            self.handle_timeline_scroll(&action);
+            return;
+        }
+
+        if self.state.view_mode == ViewMode::Diff && self.state.input_mode != InputMode::ShowingHelp
+        {
+            self.handle_diff_view_scroll(&action);
+            return;
+        }
+
         match action {
             Action::ScrollUp(n) => {
                 if self.state.input_mode == InputMode::ShowingHelp {
@@ -62,74 +81,167 @@ impl App {
                     session.chat_view.scroll_to_bottom();
                 }
             }
-            Action::ScrollPrevUserMessage => {
+            Action::ScrollLeft(n) => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    session.chat_view.scroll_left(n as usize);
+                }
+            }
+            Action::ScrollRight(n) => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    session.chat_view.scroll_right(n as usize);
+                }
+            }
+            Action::ToggleCodeWrap => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    let message = if session.chat_view.toggle_code_wrap() {
+                        "Code wrap on"
+                    } else {
+                        "Code wrap off (h/l or ←/→ to scroll in scroll mode)"
+                    };
+                    self.state
+                        .set_timed_footer_message(message.to_string(), Duration::from_secs(3));
+                }
+            }
+            Action::ToggleReasoning => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    let message = if session.chat_view.toggle_reasoning_collapsed() {
+                        "Reasoning collapsed"
+                    } else {
+                        "Reasoning expanded"
+                    };
+                    self.state
+                        .set_timed_footer_message(message.to_string(), Duration::from_secs(3));
+                }
+            }
+            Action::ToggleToolOutput => {
                 let show_chat_scrollbar = self.config().ui.show_chat_scrollbar;
-                if let (Some(session), Some(chat_area)) = (
+                let toggled = match (
                     self.state.tab_manager.active_session_mut(),
                     self.state.chat_area,
                 ) {
-                    if let Some(content) =
-                        ChatView::content_area_for(chat_area, show_chat_scrollbar)
-                    {
-                        let mut extra_len = 0usize;
-                        if session.is_processing {
-                            extra_len += 1;
-                        }
-                        if let Some(queue_lines) = crate::ui::app_queue::build_queue_lines(
-                            session,
-                            chat_area.width,
-                            self.state.input_mode,
-                        ) {
-                            extra_len += queue_lines.len();
-                        }
-                        if extra_len > 0 {
-                            extra_len += 1; // spacing line after extras
-                        }
-
-                        session.chat_view.scroll_to_prev_user_message(
-                            content.width,
-                            content.height as usize,
-                            extra_len,
-                        );
-                    }
+                    (Some(session), Some(chat_area)) => session
+                        .chat_view
+                        .tool_message_in_view(chat_area, show_chat_scrollbar)
+                        .and_then(|index| session.chat_view.toggle_tool_at(index)),
+                    _ => None,
+                };
+                if toggled.is_none() {
+                    self.state.set_timed_footer_message(
+                        "No tool output in view".to_string(),
+                        Duration::from_secs(3),
+                    );
+                }
+            }
+            Action::ToggleExpandTools => {
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    let message = if session.chat_view.toggle_tools_expanded() {
+                        "Tool output expanded"
+                    } else {
+                        "Long tool output collapsed"
+                    };
+                    self.state
+                        .set_timed_footer_message(message.to_string(), Duration::from_secs(3));
                 }
             }
-            Action::ScrollNextUserMessage => {
+            Action::ScrollPrevUserMessage
+            | Action::ScrollNextUserMessage
+            | Action::JumpToDuplicateAnswer => {
                 let show_chat_scrollbar = self.config().ui.show_chat_scrollbar;
+                let queue_panel = self.queue_panel();
+                let mut moved = None;
                 if let (Some(session), Some(chat_area)) = (
                     self.state.tab_manager.active_session_mut(),
                     self.state.chat_area,
                 ) {
-                    if let Some(content) =
-                        ChatView::content_area_for(chat_area, show_chat_scrollbar)
+                    if let Some(content) = session
+                        .chat_view
+                        .content_area_for(chat_area, show_chat_scrollbar)
                     {
                         let mut extra_len = 0usize;
                         if session.is_processing {
                             extra_len += 1;
                         }
-                        if let Some(queue_lines) = crate::ui::app_queue::build_queue_lines(
+                        extra_len += crate::ui::app_queue::queue_lines_len(
                             session,
                             chat_area.width,
                             self.state.input_mode,
-                        ) {
-                            extra_len += queue_lines.len();
-                        }
+                            queue_panel,
+                        );
                         if extra_len > 0 {
                             extra_len += 1; // spacing line after extras
                         }
 
-                        session.chat_view.scroll_to_next_user_message(
-                            content.width,
-                            content.height as usize,
-                            extra_len,
-                        );
+                        let width = content.width;
+                        let height = content.height as usize;
+                        moved = Some(match action {
+                            Action::JumpToDuplicateAnswer => {
+                                let duplicate = session
+                                    .duplicate_warning
+                                    .as_ref()
+                                    .map(|warning| warning.message_index);
+                                duplicate.is_some_and(|msg_idx| {
+                                    session.chat_view.scroll_to_user_message_at(
+                                        msg_idx, width, height, extra_len,
+                                    )
+                                })
+                            }
+                            Action::ScrollPrevUserMessage => session
+                                .chat_view
+                                .scroll_to_prev_user_message(width, height, extra_len),
+                            _ => session
+                                .chat_view
+                                .scroll_to_next_user_message(width, height, extra_len),
+                        });
                     }
                 }
+                if moved == Some(false) {
+                    let message = match action {
+                        Action::JumpToDuplicateAnswer => "No repeated prompt to jump to",
+                        Action::ScrollPrevUserMessage => "No earlier prompt",
+                        _ => "No later prompt",
+                    };
+                    self.state
+                        .set_timed_footer_message(message.to_string(), Duration::from_secs(2));
+                }
             }
             _ => {}
         }
     }
 
+    /// Handle scroll actions for the timeline view (one step per turn)
+    fn handle_timeline_scroll(&mut self, action: &Action) {
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return;
+        };
+        let timeline = &mut session.timeline;
+        match action {
+            Action::ScrollUp(n) => timeline.scroll_up(*n as usize),
+            Action::ScrollDown(n) => timeline.scroll_down(*n as usize),
+            Action::ScrollPageUp => timeline.scroll_up(TIMELINE_PAGE_TURNS),
+            Action::ScrollPageDown => timeline.scroll_down(TIMELINE_PAGE_TURNS),
+            Action::ScrollToTop => timeline.scroll_to_top(),
+            Action::ScrollToBottom => timeline.scroll_to_bottom(),
+            _ => {}
+        }
+    }
+
+    /// Handle scroll actions for the diff view (one step per row)
+    fn handle_diff_view_scroll(&mut self, action: &Action) {
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return;
+        };
+        let diff_view = &mut session.diff_view;
+        match action {
+            Action::ScrollUp(n) => diff_view.scroll_up(*n as usize),
+            Action::ScrollDown(n) => diff_view.scroll_down(*n as usize),
+            Action::ScrollPageUp => diff_view.scroll_up(DIFF_PAGE_ROWS),
+            Action::ScrollPageDown => diff_view.scroll_down(DIFF_PAGE_ROWS),
+            Action::ScrollToTop => diff_view.scroll_to_top(),
+            Action::ScrollToBottom => diff_view.scroll_to_bottom(),
+            _ => {}
+        }
+    }
+
     /// Handle scroll actions for file viewer
     fn handle_file_viewer_scroll(&mut self, action: &Action) {
         match action {
@@ -163,8 +275,16 @@ impl App {
                     file_session.scroll_to_bottom();
                 }
             }
-            // User message navigation doesn't apply to file viewer
-            Action::ScrollPrevUserMessage | Action::ScrollNextUserMessage => {}
+            // User message navigation, code wrapping, reasoning and tool output don't apply to file viewer
+            Action::ScrollPrevUserMessage
+            | Action::ScrollNextUserMessage
+            | Action::JumpToDuplicateAnswer
+            | Action::ScrollLeft(_)
+            | Action::ScrollRight(_)
+            | Action::ToggleCodeWrap
+            | Action::ToggleReasoning
+            | Action::ToggleToolOutput
+            | Action::ToggleExpandTools => {}
             _ => {}
         }
     }
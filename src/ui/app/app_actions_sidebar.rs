@@ -12,6 +12,7 @@ impl App {
             Action::ToggleSidebar => {
                 self.state.sidebar_state.toggle();
                 if self.state.sidebar_state.visible {
+                    self.refresh_sidebar_sessions();
                     self.state.sidebar_state.set_focused(true);
                     self.state.input_mode = InputMode::SidebarNavigation;
                     // Focus on the current tab's workspace if it has one
@@ -30,6 +31,7 @@ impl App {
                 }
             }
             Action::EnterSidebarMode => {
+                self.refresh_sidebar_sessions();
                 self.state.sidebar_state.show();
                 self.state.sidebar_state.set_focused(true);
                 self.state.input_mode = InputMode::SidebarNavigation;
@@ -57,9 +59,12 @@ impl App {
                                 self.state.input_mode = InputMode::Normal;
                                 self.state.sidebar_state.set_focused(false);
                             }
-                            NodeType::Repository => {
+                            NodeType::Repository | NodeType::SessionGroup => {
                                 self.state.sidebar_data.toggle_at(selected);
                             }
+                            NodeType::Session => {
+                                self.open_saved_session(node.id, true);
+                            }
                         }
                     }
                 }
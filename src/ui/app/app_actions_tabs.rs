@@ -44,6 +44,16 @@ impl App {
                 }
                 effects.push(Effect::SaveSessionState);
             }
+            Action::RenameTab => {
+                // File viewer tabs aren't persisted, so only agent tabs take a name
+                let Some(session) = self.state.tab_manager.active_session() else {
+                    return;
+                };
+                let current = session.custom_title.clone();
+                self.state.close_overlays();
+                self.state.rename_tab_dialog_state.show(current.as_deref());
+                self.state.input_mode = InputMode::RenamingTab;
+            }
             Action::NextTab => {
                 // Include sidebar in tab cycle when visible
                 if self.state.input_mode == InputMode::SidebarNavigation {
@@ -0,0 +1,101 @@
+//! Sessions imported from a bundle (`conduit bundle import`) start without an
+//! agent session: their transcript is stored as a fork seed prompt file and
+//! sent to a fresh agent the first time the tab is shown. Forked tabs whose
+//! seed never reached an agent are resumed the same way.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::data::{is_cached_seed_path, ForkSeed};
+use crate::ui::app::App;
+use crate::ui::effect::Effect;
+
+impl App {
+    /// Fork seed metadata for a tab, if it was forked or imported
+    pub(super) fn load_fork_seed(&self, fork_seed_id: Option<Uuid>) -> Option<ForkSeed> {
+        let seed_id = fork_seed_id?;
+        match self.fork_seed_dao()?.get_by_id(seed_id) {
+            Ok(seed) => seed,
+            Err(err) => {
+                tracing::warn!(%seed_id, error = %err, "Failed to load fork seed for restored tab");
+                None
+            }
+        }
+    }
+
+    /// Seed prompt file still waiting to be sent for a restored tab, if any.
+    /// Seeds an agent session already acknowledged are not sent again.
+    pub(super) fn pending_seed_path(
+        seed: Option<&ForkSeed>,
+        agent_session_id: Option<&str>,
+    ) -> Option<PathBuf> {
+        if agent_session_id.is_some() {
+            return None;
+        }
+        let seed = seed?;
+        if seed.delivered_session_id.is_some() {
+            return None;
+        }
+        let path = PathBuf::from(seed.seed_prompt_path.as_ref()?);
+        path.is_file().then_some(path)
+    }
+
+    /// Send the active tab's pending bundle seed prompt, if it has one.
+    pub(super) fn send_pending_seed(&mut self, effects: &mut Vec<Effect>) {
+        if self.state.observing.is_some() {
+            return;
+        }
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return;
+        };
+        if session.is_processing {
+            return;
+        }
+        let Some(path) = session.pending_seed_path.take() else {
+            return;
+        };
+        let seed_prompt = match std::fs::read_to_string(&path) {
+            Ok(seed_prompt) => seed_prompt,
+            Err(err) => {
+                tracing::warn!(
+                    path = %path.display(),
+                    error = %err,
+                    "Failed to read imported session seed prompt"
+                );
+                self.state.set_timed_footer_message(
+                    "Couldn't load the imported conversation".to_string(),
+                    Duration::from_secs(5),
+                );
+                return;
+            }
+        };
+        session.suppress_next_assistant_reply = true;
+        session.suppress_next_turn_summary = true;
+
+        match self.submit_prompt_hidden(seed_prompt, vec![], vec![]) {
+            Ok(seed_effects) => {
+                effects.extend(seed_effects);
+                // Hash-keyed seeds may be shared with other forks
+                if is_cached_seed_path(&path) {
+                    return;
+                }
+                if let Err(err) = std::fs::remove_file(&path) {
+                    tracing::warn!(
+                        path = %path.display(),
+                        error = %err,
+                        "Failed to remove sent seed prompt"
+                    );
+                }
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "Failed to send imported session seed prompt");
+                self.state.set_timed_footer_message(
+                    format!("Couldn't start the imported session: {err}"),
+                    Duration::from_secs(5),
+                );
+            }
+        }
+    }
+}
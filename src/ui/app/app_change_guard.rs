@@ -0,0 +1,122 @@
+//! Flagged changes in the PR flow. Large, binary or foreign-licensed files
+//! found by the preflight are kept on the session for the diff view, and a
+//! new PR is only offered once the user has acknowledged them.
+
+use std::path::{Path, PathBuf};
+
+use crate::core::change_guard::ChangeFlag;
+use crate::core::security_scan::SecurityScanReport;
+use crate::git::{LinkedPrPreflight, PrPreflightResult};
+use crate::ui::app::{App, PrChecks};
+use crate::ui::components::{ConfirmationContext, ConfirmationType};
+use crate::ui::effect::Effect;
+
+/// Flags listed in the dialog; the diff view lists all of them
+const DIALOG_FLAG_LIMIT: usize = 8;
+
+impl App {
+    /// Keep the flags on the session the PR is for and return the ones it
+    /// hasn't acknowledged yet
+    pub(super) fn record_change_flags(
+        &mut self,
+        tab_index: usize,
+        working_dir: &Path,
+        flags: Vec<ChangeFlag>,
+    ) -> Vec<ChangeFlag> {
+        let Some(session) = self
+            .pr_tab_index(tab_index, working_dir)
+            .and_then(|index| self.state.tab_manager.session_mut(index))
+        else {
+            tracing::debug!(
+                working_dir = %working_dir.display(),
+                "No session for flagged changes"
+            );
+            return flags;
+        };
+        let pending = flags
+            .iter()
+            .filter(|flag| !session.acknowledged_change_flags.contains(&flag.key()))
+            .cloned()
+            .collect();
+        session.change_flags = flags;
+        pending
+    }
+
+    /// Ask the user to acknowledge flagged files before the PR is offered
+    pub(super) fn confirm_change_flags(
+        &mut self,
+        tab_index: usize,
+        working_dir: PathBuf,
+        preflight: PrPreflightResult,
+        linked: Vec<LinkedPrPreflight>,
+        security: Option<SecurityScanReport>,
+        pending: Vec<ChangeFlag>,
+    ) {
+        let mut message = format!(
+            "{} changed file(s) need a look before this PR:",
+            pending.len()
+        );
+        for flag in pending.iter().take(DIALOG_FLAG_LIMIT) {
+            message.push_str(&format!("\n{}", flag.describe()));
+        }
+        if pending.len() > DIALOG_FLAG_LIMIT {
+            message.push_str(&format!(
+                "\n…and {} more",
+                pending.len() - DIALOG_FLAG_LIMIT
+            ));
+        }
+        message
+            .push_str("\n\nThe diff view lists them too. Acknowledge them and continue to the PR?");
+
+        self.state.close_overlays();
+        self.state.confirmation_dialog_state.show(
+            "Flagged Changes",
+            message,
+            vec![],
+            ConfirmationType::Warning,
+            "Acknowledge",
+            Some(ConfirmationContext::AcknowledgeChangeFlags {
+                tab_index,
+                working_dir,
+                preflight,
+                linked,
+                security,
+                keys: pending.iter().map(ChangeFlag::key).collect(),
+            }),
+        );
+        // Already in Confirming mode
+    }
+
+    /// Record the acknowledgement and continue to the PR dialog
+    pub(super) fn acknowledge_change_flags(
+        &mut self,
+        tab_index: usize,
+        working_dir: PathBuf,
+        preflight: PrPreflightResult,
+        linked: Vec<LinkedPrPreflight>,
+        security: Option<SecurityScanReport>,
+        keys: Vec<String>,
+    ) -> Vec<Effect> {
+        if let Some(session) = self
+            .pr_tab_index(tab_index, &working_dir)
+            .and_then(|index| self.state.tab_manager.session_mut(index))
+        {
+            tracing::info!(
+                session_id = %session.id,
+                count = keys.len(),
+                "Flagged changes acknowledged for PR"
+            );
+            session.acknowledged_change_flags.extend(keys);
+        }
+        self.confirm_pr_creation(
+            tab_index,
+            working_dir,
+            preflight,
+            linked,
+            PrChecks {
+                security,
+                change_flags: None,
+            },
+        )
+    }
+}
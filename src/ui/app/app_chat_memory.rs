@@ -0,0 +1,144 @@
+//! Chat memory limits: each session's chat view keeps at most
+//! `[ui] chat_memory_mb` of history in memory. Older messages move to the chat
+//! archive behind a placeholder and come back when the user scrolls up to it.
+
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use crate::ui::app::app_persistence::queue_messages;
+use crate::ui::app::App;
+use crate::ui::components::{ChatMemoryLimits, ChatMessage};
+
+/// How often chat views are checked against their memory limits
+const CHAT_MEMORY_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+impl App {
+    fn chat_memory_limits(&self) -> ChatMemoryLimits {
+        let ui = &self.config().ui;
+        ChatMemoryLimits {
+            max_message_bytes: ui.max_message_kb.saturating_mul(1024),
+            memory_cap_bytes: ui.chat_memory_mb.saturating_mul(1024 * 1024),
+        }
+    }
+
+    /// Periodically shorten oversized messages and offload history over the
+    /// cap; returns whether any chat view changed.
+    pub(super) fn apply_chat_memory_limits_if_due(&mut self) -> bool {
+        if self
+            .state
+            .last_chat_memory_check
+            .is_some_and(|last| last.elapsed() < CHAT_MEMORY_CHECK_INTERVAL)
+        {
+            return false;
+        }
+        self.state.last_chat_memory_check = Some(Instant::now());
+
+        let limits = self.chat_memory_limits();
+        if limits == ChatMemoryLimits::default() {
+            return false;
+        }
+        let archive = self.core.chat_archive_store().cloned();
+        let transcript_queue = self
+            .core
+            .write_behind()
+            .filter(|_| self.core.config().persistence.messages);
+        let mut changed = false;
+        for session in self.state.tab_manager.sessions_mut() {
+            let Some(range) = session.chat_view.apply_memory_limits(limits) else {
+                continue;
+            };
+            // Without a database the history has nowhere to go and stays put
+            let Some(archive) = &archive else {
+                continue;
+            };
+            // Persist them before they leave memory
+            let unpersisted = session.chat_view.take_unpersisted_in(range.clone());
+            queue_messages(transcript_queue, session.id, unpersisted);
+            let batch = &session.chat_view.messages()[range.clone()];
+            let json = match serde_json::to_string(batch) {
+                Ok(json) => json,
+                Err(err) => {
+                    tracing::warn!(session_id = %session.id, error = %err, "Failed to encode chat history for offloading");
+                    continue;
+                }
+            };
+            match archive.insert(session.id, batch.len(), &json) {
+                Ok(id) => {
+                    tracing::debug!(
+                        session_id = %session.id,
+                        chunk = id,
+                        messages = batch.len(),
+                        bytes = json.len(),
+                        "Offloaded chat history"
+                    );
+                    session.chat_view.offload(range, id);
+                    changed = true;
+                }
+                Err(err) => {
+                    tracing::warn!(session_id = %session.id, error = %err, "Failed to offload chat history");
+                }
+            }
+        }
+        changed
+    }
+
+    /// Load the newest offloaded batch back into the active session once the
+    /// user has scrolled up to its placeholder; returns whether it changed.
+    pub(super) fn load_offloaded_chat_history(&mut self) -> bool {
+        let Some(id) = self
+            .state
+            .tab_manager
+            .active_session()
+            .and_then(|session| session.chat_view.offloaded_chunk_to_load())
+        else {
+            return false;
+        };
+
+        let messages = self.core.chat_archive_store().and_then(|archive| {
+            let json = match archive.get(id) {
+                Ok(Some(json)) => json,
+                Ok(None) => {
+                    tracing::warn!(chunk = id, "Offloaded chat history is missing");
+                    return None;
+                }
+                Err(err) => {
+                    tracing::warn!(chunk = id, error = %err, "Failed to load offloaded chat history");
+                    return None;
+                }
+            };
+            match serde_json::from_str::<Vec<ChatMessage>>(&json) {
+                Ok(messages) => {
+                    if let Err(err) = archive.delete(id) {
+                        tracing::warn!(chunk = id, error = %err, "Failed to delete loaded chat history");
+                    }
+                    Some(messages)
+                }
+                Err(err) => {
+                    tracing::warn!(chunk = id, error = %err, "Failed to decode offloaded chat history");
+                    None
+                }
+            }
+        });
+        if messages.is_none() {
+            self.state.set_timed_footer_message(
+                "Earlier messages could not be loaded".to_string(),
+                Duration::from_secs(5),
+            );
+        }
+        if let Some(session) = self.state.tab_manager.active_session_mut() {
+            session.chat_view.restore_offloaded(id, messages);
+        }
+        true
+    }
+
+    /// Drop a session's offloaded history (tab closed or rebuilt from the agent's files)
+    pub(super) fn discard_offloaded_chat_history(&self, session_id: Uuid) {
+        let Some(archive) = self.core.chat_archive_store() else {
+            return;
+        };
+        if let Err(err) = archive.delete_session(session_id) {
+            tracing::warn!(%session_id, error = %err, "Failed to delete offloaded chat history");
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Clipboard history overlay: recent copies can be copied again or pasted
+//! into the input box.
+
+use std::time::Duration;
+
+use crate::ui::app::App;
+use crate::ui::effect::Effect;
+use crate::ui::events::InputMode;
+
+impl App {
+    /// Open the clipboard history with the newest copy highlighted
+    pub(super) fn show_clipboard_history(&mut self) {
+        if self.state.clipboard_history.is_empty() {
+            self.state
+                .set_timed_footer_message("Nothing copied yet".to_string(), Duration::from_secs(3));
+            return;
+        }
+        self.state.close_overlays();
+        self.state.clipboard_history.selected = 0;
+        self.state.input_mode = InputMode::ClipboardHistory;
+    }
+
+    /// Put the highlighted entry back on the clipboard and close the overlay
+    pub(super) fn recopy_clipboard_entry(&mut self, effects: &mut Vec<Effect>) {
+        let Some(entry) = self.state.clipboard_history.selected_entry() else {
+            return;
+        };
+        effects.push(Effect::CopyToClipboard {
+            text: entry.text.clone(),
+            source: entry.source,
+        });
+        self.state.input_mode = InputMode::Normal;
+        self.state
+            .set_timed_footer_message("Copied again".to_string(), Duration::from_secs(3));
+    }
+
+    /// Insert the highlighted entry at the input cursor and close the overlay
+    pub(super) fn paste_clipboard_entry(&mut self) {
+        let Some(text) = self
+            .state
+            .clipboard_history
+            .selected_entry()
+            .map(|entry| entry.text.clone())
+        else {
+            return;
+        };
+        self.state.input_mode = InputMode::Normal;
+        match self.state.tab_manager.active_session_mut() {
+            Some(session) => session.input_box.insert_str(&text),
+            None => self.state.set_timed_footer_message(
+                "No session to paste into".to_string(),
+                Duration::from_secs(3),
+            ),
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! `/coverage`: test coverage gap analysis. Coverage is measured in the
+//! workspace, the least-covered files are handed to the agent with a request
+//! to test them, and coverage is measured again when its turn ends to show
+//! whether it went up.
+
+use std::path::PathBuf;
+
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::core::coverage::{
+    CoverageError, CoveragePhase, CoverageReport, CoverageTask, CoverageTool, TARGET_FILE_COUNT,
+};
+use crate::ui::app::App;
+use crate::ui::components::ChatMessage;
+use crate::ui::effect::Effect;
+use crate::ui::session::AgentSession;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CoverageCommand {
+    /// Measure coverage and ask the agent to test the least-covered files
+    Start,
+    /// Measure again and compare with the baseline
+    Verify,
+    /// Stop the task and any running measurement
+    Stop,
+}
+
+impl CoverageCommand {
+    pub(super) fn parse(arg: &str) -> Result<Self, String> {
+        match arg {
+            "" | "start" => Ok(Self::Start),
+            "verify" => Ok(Self::Verify),
+            "stop" => Ok(Self::Stop),
+            _ => Err(format!(
+                "Unknown coverage command '{arg}'. Options: start, verify, stop."
+            )),
+        }
+    }
+}
+
+impl App {
+    pub(super) fn run_coverage_command(&mut self, command: CoverageCommand) -> Vec<Effect> {
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return Vec::new();
+        };
+        let Some(working_dir) = session.working_dir.clone() else {
+            session.chat_view.push(ChatMessage::system(
+                "Open a workspace session to analyze its test coverage.",
+            ));
+            return Vec::new();
+        };
+
+        match command {
+            CoverageCommand::Start => {
+                if session
+                    .coverage_task
+                    .as_ref()
+                    .is_some_and(|task| task.phase != CoveragePhase::Reviewed)
+                {
+                    session.chat_view.push(ChatMessage::system(
+                        "A coverage task is already running; /coverage stop ends it.",
+                    ));
+                    return Vec::new();
+                }
+                let Some(tool) = CoverageTool::detect(&working_dir) else {
+                    let err = CoverageError::UnsupportedProject(working_dir);
+                    session.chat_view.push(ChatMessage::system(format!(
+                        "Can't measure coverage: {err}. Rust (cargo llvm-cov) and Python (coverage.py) projects are supported."
+                    )));
+                    return Vec::new();
+                };
+                let task = CoverageTask::new(tool);
+                let effect = measure_effect(session, working_dir, tool, task.cancel.clone());
+                session.coverage_task = Some(task);
+                vec![effect]
+            }
+            CoverageCommand::Verify => {
+                let Some(task) = session
+                    .coverage_task
+                    .as_mut()
+                    .filter(|task| task.baseline.is_some() && !task.is_measuring())
+                else {
+                    session.chat_view.push(ChatMessage::system(
+                        "No coverage baseline to compare with. /coverage starts a coverage task.",
+                    ));
+                    return Vec::new();
+                };
+                task.phase = CoveragePhase::Verifying;
+                task.cancel = CancellationToken::new();
+                let (tool, cancel) = (task.tool, task.cancel.clone());
+                vec![measure_effect(session, working_dir, tool, cancel)]
+            }
+            CoverageCommand::Stop => {
+                let Some(task) = session.coverage_task.take() else {
+                    session
+                        .chat_view
+                        .push(ChatMessage::system("No coverage task is running."));
+                    return Vec::new();
+                };
+                task.cancel.cancel();
+                session
+                    .chat_view
+                    .push(ChatMessage::system("Coverage task stopped."));
+                Vec::new()
+            }
+        }
+    }
+
+    /// Act on a finished measurement: seed the agent after the baseline, or
+    /// report the change after a verification
+    pub(super) fn finish_coverage_measurement(
+        &mut self,
+        session_id: Uuid,
+        result: Result<CoverageReport, String>,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let Some(tab_index) = self.state.tab_manager.session_index_by_id(session_id) else {
+            tracing::debug!(%session_id, "CoverageMeasured for unknown session; ignoring");
+            return Ok(Vec::new());
+        };
+        let is_active_tab = self.state.tab_manager.active_index() == tab_index;
+        let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+            return Ok(Vec::new());
+        };
+        // Stopped tasks drop their state; nothing left to report
+        let Some(task) = session.coverage_task.as_mut() else {
+            return Ok(Vec::new());
+        };
+        if !is_active_tab {
+            session.needs_attention = true;
+        }
+
+        let report = match result {
+            Ok(report) => report,
+            Err(err) => {
+                let note = if task.phase == CoveragePhase::Verifying {
+                    task.phase = CoveragePhase::Reviewed;
+                    format!("Coverage measurement failed: {err}\n/coverage verify tries again.")
+                } else {
+                    session.coverage_task = None;
+                    format!("Coverage measurement failed: {err}")
+                };
+                session.chat_view.push(ChatMessage::error(note));
+                return Ok(Vec::new());
+            }
+        };
+
+        match task.phase {
+            CoveragePhase::Measuring => {
+                let targets = report.least_covered(TARGET_FILE_COUNT);
+                if targets.is_empty() {
+                    session.chat_view.push(ChatMessage::system(format!(
+                        "{}\nEvery measured file is fully covered.",
+                        report.summary(&[])
+                    )));
+                    session.coverage_task = None;
+                    return Ok(Vec::new());
+                }
+                let prompt = report.prompt(&targets);
+                task.targets = targets.iter().map(|file| file.path.clone()).collect();
+                task.phase = CoveragePhase::Writing;
+                tracing::info!(
+                    %session_id,
+                    coverage = report.total.percent(),
+                    targets = task.targets.len(),
+                    "Seeding coverage task"
+                );
+                task.baseline = Some(report);
+                self.submit_prompt_for_tab(tab_index, prompt, Vec::new(), Vec::new(), false, None)
+            }
+            CoveragePhase::Verifying => {
+                task.phase = CoveragePhase::Reviewed;
+                if let Some(baseline) = &task.baseline {
+                    let comparison = report.comparison(baseline, &task.targets);
+                    session.chat_view.push(ChatMessage::system(comparison));
+                }
+                Ok(Vec::new())
+            }
+            CoveragePhase::Writing | CoveragePhase::Reviewed => Ok(Vec::new()),
+        }
+    }
+
+    /// Measure again once the agent's test-writing turn ends. Queued prompts
+    /// go first; the measurement follows the turn that empties the queue.
+    pub(super) fn on_coverage_turn_finished(
+        &mut self,
+        session_id: Uuid,
+        succeeded: bool,
+    ) -> Vec<Effect> {
+        let Some(session) = self.state.tab_manager.session_by_id_mut(session_id) else {
+            return Vec::new();
+        };
+        if !session.queued_messages.is_empty() {
+            return Vec::new();
+        }
+        let Some(working_dir) = session.working_dir.clone() else {
+            return Vec::new();
+        };
+        let Some(task) = session
+            .coverage_task
+            .as_mut()
+            .filter(|task| task.phase == CoveragePhase::Writing)
+        else {
+            return Vec::new();
+        };
+        if !succeeded {
+            task.phase = CoveragePhase::Reviewed;
+            session.chat_view.push(ChatMessage::system(
+                "The agent's turn failed. /coverage verify measures the tests added so far.",
+            ));
+            return Vec::new();
+        }
+        task.phase = CoveragePhase::Verifying;
+        task.cancel = CancellationToken::new();
+        let (tool, cancel) = (task.tool, task.cancel.clone());
+        vec![measure_effect(session, working_dir, tool, cancel)]
+    }
+}
+
+/// Announce a measurement and build its effect
+fn measure_effect(
+    session: &mut AgentSession,
+    working_dir: PathBuf,
+    tool: CoverageTool,
+    cancel: CancellationToken,
+) -> Effect {
+    session.chat_view.push(ChatMessage::system(format!(
+        "Measuring coverage with {}; this runs the test suite…",
+        tool.display_name()
+    )));
+    Effect::MeasureCoverage {
+        session_id: session.id,
+        working_dir,
+        tool,
+        cancel,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_coverage_commands() {
+        assert_eq!(CoverageCommand::parse(""), Ok(CoverageCommand::Start));
+        assert_eq!(
+            CoverageCommand::parse("verify"),
+            Ok(CoverageCommand::Verify)
+        );
+        assert!(CoverageCommand::parse("report").is_err());
+    }
+}
@@ -0,0 +1,153 @@
+//! Restored tabs load their chat history lazily: startup only records what to
+//! load, and the agent's history files are read the first time a tab becomes
+//! active, after the first frame is on screen.
+
+use crate::agent::{
+    load_claude_history_with_debug, load_codex_history_with_debug,
+    load_opencode_history_for_dir_with_debug, load_opencode_history_with_debug, AgentType,
+    MessageDisplay, SessionId,
+};
+use crate::ui::app::App;
+use crate::ui::components::MessageRole;
+use crate::ui::session::{AgentSession, DeferredHistory};
+
+impl App {
+    /// Load the active tab's history if it hasn't been loaded yet; returns
+    /// whether anything changed.
+    pub(super) fn load_active_tab_history(&mut self) -> bool {
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return false;
+        };
+        let Some(deferred) = session.deferred_history.take() else {
+            return false;
+        };
+        let started = std::time::Instant::now();
+        Self::load_restored_history(session, deferred);
+        tracing::debug!(
+            session_id = %session.id,
+            messages = session.chat_view.messages().len(),
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            "Loaded deferred chat history"
+        );
+        true
+    }
+
+    fn load_restored_history(session: &mut AgentSession, deferred: DeferredHistory) {
+        if let Some(session_id_str) = session
+            .resume_session_id
+            .as_ref()
+            .map(|id| id.as_str().to_string())
+        {
+            // Load chat history from agent files
+            match session.agent_type {
+                AgentType::Claude => {
+                    if let Ok((msgs, debug_entries, file_path)) =
+                        load_claude_history_with_debug(&session_id_str)
+                    {
+                        // Populate debug pane with history load info
+                        Self::populate_debug_from_history(
+                            &mut session.raw_events_view,
+                            &debug_entries,
+                            &file_path,
+                        );
+                        for msg in msgs {
+                            session.chat_view.push_history(msg);
+                        }
+                    }
+                }
+                AgentType::Codex => {
+                    if let Ok((msgs, debug_entries, file_path)) =
+                        load_codex_history_with_debug(&session_id_str)
+                    {
+                        // Populate debug pane with history load info
+                        Self::populate_debug_from_history(
+                            &mut session.raw_events_view,
+                            &debug_entries,
+                            &file_path,
+                        );
+                        for msg in msgs {
+                            session.chat_view.push_history(msg);
+                        }
+                    }
+                }
+                AgentType::Gemini => {
+                    session.chat_view.push(
+                        MessageDisplay::System {
+                            content: "Gemini CLI history import isn't supported yet, so previous messages won't be shown.".to_string(),
+                        }
+                        .to_chat_message(),
+                    );
+                }
+                AgentType::Opencode => {
+                    if let Ok((msgs, debug_entries, file_path)) =
+                        load_opencode_history_with_debug(&session_id_str)
+                    {
+                        Self::populate_debug_from_history(
+                            &mut session.raw_events_view,
+                            &debug_entries,
+                            &file_path,
+                        );
+                        for msg in msgs {
+                            session.chat_view.push_history(msg);
+                        }
+                    }
+                }
+                AgentType::Local => {
+                    session.chat_view.push(
+                        MessageDisplay::System {
+                            content: "Local model conversations aren't saved, so previous messages won't be shown.".to_string(),
+                        }
+                        .to_chat_message(),
+                    );
+                }
+            }
+        } else if session.agent_type == AgentType::Opencode {
+            if let Some(working_dir) = session.working_dir.as_ref() {
+                if let Ok((session_id_str, msgs, debug_entries, file_path)) =
+                    load_opencode_history_for_dir_with_debug(working_dir)
+                {
+                    let session_id = SessionId::from_string(session_id_str);
+                    session.resume_session_id = Some(session_id.clone());
+                    session.agent_session_id = Some(session_id);
+
+                    Self::populate_debug_from_history(
+                        &mut session.raw_events_view,
+                        &debug_entries,
+                        &file_path,
+                    );
+                    for msg in msgs {
+                        session.chat_view.push_history(msg);
+                    }
+                }
+            }
+        }
+
+        // Restore pending user message if it exists and isn't already in history
+        if let Some(pending) = deferred.pending_user_message {
+            // Check if last user message in chat matches pending
+            let already_in_history = session
+                .chat_view
+                .messages()
+                .iter()
+                .rev()
+                .find(|m| m.role == MessageRole::User)
+                .is_some_and(|m| m.content == pending);
+
+            if !already_in_history {
+                let display = MessageDisplay::User {
+                    content: pending.clone(),
+                };
+                session.chat_view.push(display.to_chat_message());
+                session.pending_user_message = Some(pending);
+            }
+        }
+
+        // Derive fork_welcome_shown: if restoring a forked session that has messages,
+        // the welcome message was already shown in the previous session
+        if session.fork_seed_id.is_some() && !session.chat_view.messages().is_empty() {
+            session.fork_welcome_shown = true;
+        }
+
+        session.update_status();
+    }
+}
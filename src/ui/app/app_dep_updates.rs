@@ -0,0 +1,312 @@
+//! `/deps`: guided dependency updates. A scan lists the workspace's outdated
+//! dependencies in groups; `/deps start` then has the agent bump one group
+//! per branch, runs the group's verification commands itself, and only asks
+//! for the PR once they pass. Failures stop the run until the user retries or
+//! skips the group.
+
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::core::bootstrap::BootstrapProgress;
+use crate::core::dep_update::{DepUpdateRun, DepUpdateStep, DependencyScan};
+use crate::ui::app::App;
+use crate::ui::components::ChatMessage;
+use crate::ui::effect::Effect;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum DepsCommand {
+    /// Look for outdated dependencies and plan the groups
+    Scan,
+    /// Start working through the planned groups
+    Start,
+    /// Show where the run stands
+    Status,
+    /// Ask the agent to fix the group the run stopped on
+    Retry,
+    /// Leave the group the run stopped on and move on
+    Skip,
+    /// End the run
+    Stop,
+}
+
+impl DepsCommand {
+    pub(super) fn parse(arg: &str) -> Result<Self, String> {
+        match arg {
+            "" | "scan" => Ok(Self::Scan),
+            "start" => Ok(Self::Start),
+            "status" => Ok(Self::Status),
+            "retry" => Ok(Self::Retry),
+            "skip" => Ok(Self::Skip),
+            "stop" => Ok(Self::Stop),
+            _ => Err(format!(
+                "Unknown deps command '{arg}'. Options: scan, start, status, retry, skip, stop."
+            )),
+        }
+    }
+}
+
+impl App {
+    pub(super) fn run_deps_command(&mut self, command: DepsCommand) -> anyhow::Result<Vec<Effect>> {
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return Ok(Vec::new());
+        };
+        let Some(working_dir) = session.working_dir.clone() else {
+            session.chat_view.push(ChatMessage::system(
+                "Open a workspace session to update its dependencies.",
+            ));
+            return Ok(Vec::new());
+        };
+        let session_id = session.id;
+
+        let step = match (command, session.dep_update.as_mut()) {
+            (DepsCommand::Scan, Some(run)) if run.is_active() => {
+                session.chat_view.push(ChatMessage::system(
+                    "Dependency updates are in progress; /deps stop ends the run first.",
+                ));
+                return Ok(Vec::new());
+            }
+            (DepsCommand::Scan, _) => {
+                session.dep_update = None;
+                session
+                    .chat_view
+                    .push(ChatMessage::system("Scanning for outdated dependencies…"));
+                return Ok(vec![Effect::ScanDependencies {
+                    session_id,
+                    working_dir,
+                }]);
+            }
+            (DepsCommand::Stop, Some(_)) => {
+                if let Some(cancel) = session.dep_verify_cancel.take() {
+                    cancel.cancel();
+                }
+                session.dep_update = None;
+                session.chat_view.push(ChatMessage::system(
+                    "Dependency updates stopped. Branches and PRs created so far are left as they are.",
+                ));
+                return Ok(Vec::new());
+            }
+            (_, None) => {
+                session.chat_view.push(ChatMessage::system(
+                    "No dependency updates planned. /deps scans the workspace first.",
+                ));
+                return Ok(Vec::new());
+            }
+            (DepsCommand::Status, Some(run)) => {
+                let status = run.status();
+                session.chat_view.push(ChatMessage::system(status));
+                return Ok(Vec::new());
+            }
+            (DepsCommand::Start, Some(run)) => {
+                let Some(base_branch) = session.status_bar.branch_name().map(str::to_string) else {
+                    session.chat_view.push(ChatMessage::system(
+                        "Couldn't tell which branch the workspace is on; wait for git status and try again.",
+                    ));
+                    return Ok(Vec::new());
+                };
+                run.start(base_branch)
+            }
+            (DepsCommand::Retry, Some(run)) => run.retry(),
+            (DepsCommand::Skip, Some(run)) => run.skip(),
+        };
+
+        if step == DepUpdateStep::Idle {
+            let note = match command {
+                DepsCommand::Start => {
+                    "Dependency updates already started; /deps status shows where they are."
+                }
+                _ => "The dependency update run isn't stopped on a group.",
+            };
+            session.chat_view.push(ChatMessage::system(note));
+            return Ok(Vec::new());
+        }
+        self.apply_dep_update_step(session_id, step)
+    }
+
+    /// Show the scan result and keep it as the session's planned run
+    pub(super) fn finish_dependency_scan(
+        &mut self,
+        session_id: Uuid,
+        result: Result<DependencyScan, String>,
+    ) {
+        let Some(session) = self.state.tab_manager.session_by_id_mut(session_id) else {
+            tracing::debug!(%session_id, "DependenciesScanned for unknown session; ignoring");
+            return;
+        };
+        match result {
+            Ok(scan) if scan.groups.is_empty() => {
+                session.chat_view.push(ChatMessage::system(
+                    "All direct dependencies are up to date.",
+                ));
+            }
+            Ok(scan) => {
+                session.chat_view.push(ChatMessage::system(format!(
+                    "{}\n/deps start updates them group by group on their own branches, verifying each before its PR is opened.",
+                    scan.plan()
+                )));
+                session.dep_update = Some(DepUpdateRun::new(scan));
+            }
+            Err(err) => {
+                session
+                    .chat_view
+                    .push(ChatMessage::error(format!("Dependency scan failed: {err}")));
+            }
+        }
+    }
+
+    pub(super) fn record_dependency_verify_progress(
+        &mut self,
+        session_id: Uuid,
+        progress: BootstrapProgress,
+    ) {
+        let Some(session) = self.state.tab_manager.session_by_id_mut(session_id) else {
+            tracing::debug!(%session_id, "DependencyVerifyProgress for unknown session; ignoring");
+            return;
+        };
+        let Some(run) = session.dep_update.as_mut() else {
+            return;
+        };
+        // Each verification run gets its own tool IDs, so retries don't reuse
+        // the finished messages of earlier attempts
+        let verify_runs = run.verify_runs;
+        let tool_id = |index: usize| format!("deps-verify-{verify_runs}-{index}");
+        match progress {
+            BootstrapProgress::Started { index, command } => {
+                let args = serde_json::json!({ "command": command }).to_string();
+                session.chat_view.push(
+                    ChatMessage::tool("Bash", args, "Running...").with_tool_id(tool_id(index)),
+                );
+            }
+            BootstrapProgress::Output { index, line } => {
+                session
+                    .chat_view
+                    .append_tool_output_by_id(&tool_id(index), &format!("{line}\n"));
+                run.record_verify_output(line);
+            }
+            BootstrapProgress::Finished { index, exit_code } => {
+                session
+                    .chat_view
+                    .finish_tool_output_by_id(&tool_id(index), exit_code);
+            }
+        }
+    }
+
+    pub(super) fn finish_dependency_verification(
+        &mut self,
+        session_id: Uuid,
+        result: Result<(), String>,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let Some(session) = self.state.tab_manager.session_by_id_mut(session_id) else {
+            tracing::debug!(%session_id, "DependencyVerifyFinished for unknown session; ignoring");
+            return Ok(Vec::new());
+        };
+        session.dep_verify_cancel = None;
+        // Stopped runs drop their state; nothing left to advance
+        let Some(run) = session.dep_update.as_mut() else {
+            return Ok(Vec::new());
+        };
+        let step = run.verification_finished(result);
+        self.apply_dep_update_step(session_id, step)
+    }
+
+    /// Advance the session's dependency update run after an agent turn.
+    /// Queued prompts go first; the run continues when their turn ends.
+    pub(super) fn on_dep_update_turn_finished(
+        &mut self,
+        session_id: Uuid,
+        succeeded: bool,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let Some(session) = self.state.tab_manager.session_by_id_mut(session_id) else {
+            return Ok(Vec::new());
+        };
+        if !session.queued_messages.is_empty() {
+            return Ok(Vec::new());
+        }
+        let Some(run) = session.dep_update.as_mut() else {
+            return Ok(Vec::new());
+        };
+        let step = run.turn_finished(succeeded);
+        self.apply_dep_update_step(session_id, step)
+    }
+
+    fn apply_dep_update_step(
+        &mut self,
+        session_id: Uuid,
+        step: DepUpdateStep,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let Some(tab_index) = self.state.tab_manager.session_index_by_id(session_id) else {
+            return Ok(Vec::new());
+        };
+        let is_active_tab = self.state.tab_manager.active_index() == tab_index;
+        let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+            return Ok(Vec::new());
+        };
+        let group_name = session
+            .dep_update
+            .as_ref()
+            .and_then(DepUpdateRun::current_group)
+            .map(|group| group.name.clone())
+            .unwrap_or_default();
+
+        match step {
+            DepUpdateStep::Idle => Ok(Vec::new()),
+            DepUpdateStep::Prompt(prompt) => {
+                self.submit_prompt_for_tab(tab_index, prompt, Vec::new(), Vec::new(), false, None)
+            }
+            DepUpdateStep::Verify(commands) => {
+                let Some(working_dir) = session.working_dir.clone() else {
+                    tracing::warn!(%session_id, "Dependency update session has no working directory");
+                    return Ok(Vec::new());
+                };
+                tracing::info!(%session_id, group = %group_name, "Verifying dependency update group");
+                session
+                    .chat_view
+                    .push(ChatMessage::system(format!("Verifying {group_name}…")));
+                let cancel = CancellationToken::new();
+                session.dep_verify_cancel = Some(cancel.clone());
+                let target = session
+                    .workspace_id
+                    .map(|workspace_id| self.core.execution_target_for_workspace(workspace_id))
+                    .unwrap_or_default();
+                Ok(vec![Effect::RunDependencyVerification {
+                    session_id,
+                    working_dir,
+                    target,
+                    commands,
+                    cancel,
+                }])
+            }
+            DepUpdateStep::Blocked(reason) => {
+                tracing::info!(%session_id, group = %group_name, "Dependency updates stopped on a group");
+                session.chat_view.push(ChatMessage::error(format!(
+                    "Dependency updates stopped on {group_name}. {reason}\n/deps retry asks the agent to fix it, /deps skip moves on to the next group, /deps stop ends the run."
+                )));
+                if !is_active_tab {
+                    session.needs_attention = true;
+                }
+                Ok(Vec::new())
+            }
+            DepUpdateStep::Finished => {
+                if let Some(run) = session.dep_update.take() {
+                    session.chat_view.push(ChatMessage::system(run.summary()));
+                }
+                if !is_active_tab {
+                    session.needs_attention = true;
+                }
+                Ok(Vec::new())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_deps_commands() {
+        assert_eq!(DepsCommand::parse(""), Ok(DepsCommand::Scan));
+        assert_eq!(DepsCommand::parse("start"), Ok(DepsCommand::Start));
+        assert_eq!(DepsCommand::parse("skip"), Ok(DepsCommand::Skip));
+        assert!(DepsCommand::parse("upgrade").is_err());
+    }
+}
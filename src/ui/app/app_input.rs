@@ -5,11 +5,11 @@ use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 use std::io;
 
-use crate::agent::{AgentMode, AgentType, MessageDisplay};
+use crate::agent::{AgentMode, AgentType, MessageDisplay, ReviewOutcome};
 use crate::config::{KeyCombo, KeyContext};
 use crate::ui::action::Action;
 use crate::ui::app::App;
-use crate::ui::components::SIDEBAR_HEADER_ROWS;
+use crate::ui::components::{GlobalFooter, SIDEBAR_HEADER_ROWS};
 use crate::ui::effect::Effect;
 use crate::ui::events::{InputMode, ViewMode};
 use crate::ui::terminal_guard::TerminalGuard;
@@ -32,7 +32,14 @@ impl App {
                 terminal.autoresize()?;
                 Ok(Vec::new())
             }
-            Event::FocusGained | Event::FocusLost => Ok(Vec::new()),
+            Event::FocusGained => {
+                self.state.terminal_focused = true;
+                Ok(Vec::new())
+            }
+            Event::FocusLost => {
+                self.state.terminal_focused = false;
+                Ok(Vec::new())
+            }
         }
     }
 
@@ -48,9 +55,14 @@ impl App {
             return Ok(Vec::new());
         }
 
-        // Handle Ctrl+C with double-press detection (global)
-        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
-            tracing::debug!("Ctrl+C detected, calling handle_ctrl_c_press");
+        // Clear-or-quit (Ctrl+C by default) works even over inline prompts,
+        // so it is resolved before any other key handling
+        if self.config().keybindings.get_action(
+            &KeyCombo::from_key_event(&key),
+            KeyContext::from_input_mode(self.state.input_mode, self.state.view_mode),
+        ) == Some(&Action::ClearOrQuit)
+        {
+            tracing::debug!("Clear-or-quit key detected, calling handle_ctrl_c_press");
             let effects = self.handle_ctrl_c_press();
             return Ok(effects);
         }
@@ -58,7 +70,9 @@ impl App {
         // Handle inline prompt input (AskUserQuestion, ExitPlanMode)
         if let Some(session) = self.state.tab_manager.active_session_mut() {
             if let Some(ref mut prompt) = session.inline_prompt {
-                use crate::ui::components::{PromptAction, PromptResponse};
+                use crate::ui::components::{
+                    ChatMessage, InlinePromptType, PromptAction, PromptResponse,
+                };
 
                 match prompt.handle_key(key) {
                     PromptAction::Submit(response) => {
@@ -71,6 +85,15 @@ impl App {
                         // Clear the inline prompt
                         session.inline_prompt = None;
 
+                        // Keep the proposed plan in the transcript once the prompt closes
+                        if let InlinePromptType::ExitPlanMode { plan_content, .. } =
+                            &prompt_snapshot.prompt_type
+                        {
+                            session
+                                .chat_view
+                                .push(ChatMessage::plan(plan_content.clone()));
+                        }
+
                         // Handle the response - format as natural language for the model
                         let effects = if let (AgentType::Claude, true, Some(request_id)) = (
                             agent_type,
@@ -213,6 +236,24 @@ impl App {
                         };
                         return Ok(effects);
                     }
+                    PromptAction::EditReviewed(outcome) => {
+                        return Ok(self.finish_edit_review(outcome));
+                    }
+                    PromptAction::Cancel
+                        if matches!(prompt.prompt_type, InlinePromptType::EditReview { .. }) =>
+                    {
+                        return Ok(self.finish_edit_review(ReviewOutcome::Rejected));
+                    }
+                    PromptAction::EditHunk(index) => {
+                        if let Err(err) = self.edit_review_hunk_external(index, terminal, guard) {
+                            tracing::warn!(error = %err, "External editor failed");
+                            self.state.set_timed_footer_message(
+                                format!("External editor failed: {err}"),
+                                std::time::Duration::from_secs(3),
+                            );
+                        }
+                        return Ok(Vec::new());
+                    }
                     PromptAction::Cancel => {
                         let tool_id = prompt.tool_id.clone();
                         let pending_request_id = session.pending_tool_permissions.remove(&tool_id);
@@ -262,6 +303,20 @@ impl App {
             }
         }
 
+        // Raw events path filter captures typing while focused
+        if self.state.view_mode == ViewMode::RawEvents && !self.has_active_dialog() {
+            if let Some(session) = self.state.tab_manager.active_session_mut() {
+                if session.raw_events_view.is_filter_focused() {
+                    session.raw_events_view.handle_filter_key(key);
+                    return Ok(Vec::new());
+                }
+                if session.raw_events_view.is_search_focused() {
+                    session.raw_events_view.handle_search_key(key);
+                    return Ok(Vec::new());
+                }
+            }
+        }
+
         // Esc exits shell mode back to normal input
         if key.code == KeyCode::Esc
             && !self.has_active_dialog()
@@ -330,6 +385,28 @@ impl App {
             }
         }
 
+        // Splash screen: 1-9 start the matching template, then resume the
+        // recent sessions listed after the templates
+        if self.state.tab_manager.is_empty()
+            && self.state.input_mode == InputMode::Normal
+            && !self.has_active_dialog()
+            && key.modifiers.is_empty()
+        {
+            if let KeyCode::Char(c @ '1'..='9') = key.code {
+                let index = c as usize - '1' as usize;
+                let template_count = self.config().templates.len();
+                if index < template_count {
+                    self.start_session_template(index);
+                    return Ok(Vec::new());
+                }
+                if let Some(recent) = self.state.recent_sessions.get(index - template_count) {
+                    let session_id = recent.id;
+                    self.open_saved_session(session_id, false);
+                    return Ok(Vec::new());
+                }
+            }
+        }
+
         // Handle Ctrl+N and Ctrl+P when tabs are empty (works from any input mode)
         if self.state.tab_manager.is_empty() && !self.state.command_palette_state.is_visible() {
             let is_ctrl_n = (key.modifiers.contains(KeyModifiers::CONTROL)
@@ -361,12 +438,40 @@ impl App {
                 || key.modifiers.contains(KeyModifiers::ALT))
             && matches!(key.code, KeyCode::Char(c) if c.eq_ignore_ascii_case(&'v'))
         {
+            let attachment_store = self.core.attachment_store_clone();
+            let annotate_key = GlobalFooter::key_label_for(
+                &self.config().keybindings,
+                KeyContext::Chat,
+                &Action::AnnotateImage,
+            )
+            .unwrap_or_else(|| ":annotate_image".to_string());
             if let Some(session) = self.state.tab_manager.active_session_mut() {
                 match crate::ui::clipboard_paste::paste_image_to_temp_png() {
-                    Ok((path, info)) => {
+                    Ok((temp_path, info)) => {
+                        // Move the temp file into managed storage so it survives until dispatch
+                        let path = match attachment_store.as_ref().map(|store| {
+                            store
+                                .import_file(session.id, &temp_path, "image/png")
+                                .map(|attachment| store.file_path(&attachment))
+                        }) {
+                            Some(Ok(path)) => path,
+                            Some(Err(err)) => {
+                                tracing::warn!(
+                                    error = %err,
+                                    path = %temp_path.display(),
+                                    "Failed to store pasted image; using temp file"
+                                );
+                                temp_path
+                            }
+                            None => temp_path,
+                        };
                         session
                             .input_box
                             .attach_image(path, info.width, info.height);
+                        self.state.set_timed_footer_message(
+                            format!("Image attached · {} to crop or mark it up", annotate_key),
+                            std::time::Duration::from_secs(5),
+                        );
                     }
                     Err(err) => {
                         let display = MessageDisplay::Error {
@@ -464,6 +569,7 @@ impl App {
             KeyContext::Chat
                 | KeyContext::AddRepository
                 | KeyContext::BaseDir
+                | KeyContext::RenameTab
                 | KeyContext::ProjectPicker
                 | KeyContext::Command
                 | KeyContext::HelpDialog
@@ -501,7 +607,8 @@ impl App {
                     {
                         self.state.close_overlays();
                         let keybindings = self.config().keybindings.clone();
-                        self.state.help_dialog_state.show(&keybindings);
+                        let issues = self.config().keybinding_issues.clone();
+                        self.state.help_dialog_state.show(&keybindings, &issues);
                         self.state.input_mode = InputMode::ShowingHelp;
                         return;
                     }
@@ -521,6 +628,9 @@ impl App {
             InputMode::SettingBaseDir => {
                 self.state.base_dir_dialog_state.insert_char(c);
             }
+            InputMode::RenamingTab => {
+                self.state.rename_tab_dialog_state.insert_char(c);
+            }
             InputMode::PickingProject => {
                 self.state.project_picker_state.insert_char(c);
             }
@@ -549,6 +659,22 @@ impl App {
     pub(super) fn handle_paste_input(&mut self, pasted: String) {
         // Normalize line endings: CRLF → LF, then lone CR → LF
         let pasted = pasted.replace("\r\n", "\n").replace('\r', "\n");
+        if self.state.view_mode == ViewMode::RawEvents {
+            if let Some(session) = self.state.tab_manager.active_session_mut() {
+                if session.raw_events_view.is_filter_focused() {
+                    session
+                        .raw_events_view
+                        .insert_filter_str(&pasted.replace('\n', " "));
+                    return;
+                }
+                if session.raw_events_view.is_search_focused() {
+                    session
+                        .raw_events_view
+                        .insert_search_str(&pasted.replace('\n', " "));
+                    return;
+                }
+            }
+        }
         match self.state.input_mode {
             InputMode::Normal => {
                 if let Some(session) = self.state.tab_manager.active_session_mut() {
@@ -591,6 +717,12 @@ impl App {
                     self.state.base_dir_dialog_state.insert_char(ch);
                 }
             }
+            InputMode::RenamingTab => {
+                let sanitized = pasted.replace('\n', " ");
+                for ch in sanitized.chars() {
+                    self.state.rename_tab_dialog_state.insert_char(ch);
+                }
+            }
             InputMode::PickingProject => {
                 let sanitized = pasted.replace('\n', " ");
                 for ch in sanitized.chars() {
@@ -661,6 +793,14 @@ impl App {
                     self.state.theme_picker_state.select_prev();
                 } else if self.handle_tab_bar_wheel(x, y, true) {
                     return Ok(Vec::new());
+                } else if self.state.view_mode == ViewMode::Timeline {
+                    if let Some(session) = self.state.tab_manager.active_session_mut() {
+                        session.timeline.scroll_up(1);
+                    }
+                } else if self.state.view_mode == ViewMode::Diff {
+                    if let Some(session) = self.state.tab_manager.active_session_mut() {
+                        session.diff_view.scroll_up(3);
+                    }
                 } else if self.state.view_mode == ViewMode::RawEvents {
                     if let Some(session) = self.state.tab_manager.active_session_mut() {
                         if session.raw_events_view.is_detail_visible() {
@@ -694,6 +834,14 @@ impl App {
                     self.state.theme_picker_state.select_next();
                 } else if self.handle_tab_bar_wheel(x, y, false) {
                     return Ok(Vec::new());
+                } else if self.state.view_mode == ViewMode::Timeline {
+                    if let Some(session) = self.state.tab_manager.active_session_mut() {
+                        session.timeline.scroll_down(1);
+                    }
+                } else if self.state.view_mode == ViewMode::Diff {
+                    if let Some(session) = self.state.tab_manager.active_session_mut() {
+                        session.diff_view.scroll_down(3);
+                    }
                 } else if self.state.view_mode == ViewMode::RawEvents {
                     let list_height = self.raw_events_list_visible_height();
                     let detail_height = self.raw_events_detail_visible_height();
@@ -738,10 +886,13 @@ impl App {
             MouseEventKind::Up(MouseButton::Left) => {
                 self.state.scroll_drag = None;
                 if let Some(mut effects) = self.handle_selection_end() {
-                    // If no selection was made (simple click), check for clickable file paths
+                    // If no selection was made (simple click), check for clickable file paths,
+                    // then for a tool block to collapse or expand
                     if effects.is_empty() {
                         if let Some(path_effects) = self.handle_file_path_click(x, y) {
                             effects.extend(path_effects);
+                        } else {
+                            self.handle_tool_block_click(x, y);
                         }
                     }
                     return Ok(effects);
@@ -751,6 +902,7 @@ impl App {
                 if let Some(path_effects) = self.handle_file_path_click(x, y) {
                     return Ok(path_effects);
                 }
+                self.handle_tool_block_click(x, y);
                 Ok(Vec::new())
             }
             MouseEventKind::Moved => {
@@ -844,6 +996,30 @@ impl App {
         }
     }
 
+    /// Collapse or expand the tool block under a click in the chat area
+    fn handle_tool_block_click(&mut self, x: u16, y: u16) {
+        if self.state.view_mode != ViewMode::Chat {
+            return;
+        }
+        let Some(chat_area) = self.state.chat_area else {
+            return;
+        };
+        if !Self::point_in_rect(x, y, chat_area) {
+            return;
+        }
+        let show_chat_scrollbar = self.config().ui.show_chat_scrollbar;
+        let Some(session) = self.state.tab_manager.active_session_mut() else {
+            return;
+        };
+        if let Some(index) =
+            session
+                .chat_view
+                .tool_message_at_position(x, y, chat_area, show_chat_scrollbar)
+        {
+            session.chat_view.toggle_tool_at(index);
+        }
+    }
+
     /// Handle click on a file path in the chat area
     /// Returns Some(effects) if a file path was clicked and opened
     fn handle_file_path_click(&mut self, x: u16, y: u16) -> Option<Vec<Effect>> {
@@ -0,0 +1,280 @@
+//! `/link` and `/unlink`: bind workspaces of other repositories to the active
+//! session. Linked workspaces are tracked by the git tracker like the
+//! session's own, passed to the agent when it next starts, and included when
+//! the PR action opens PRs.
+
+use uuid::Uuid;
+
+use crate::data::Workspace;
+use crate::ui::app::App;
+use crate::ui::components::ChatMessage;
+use crate::ui::effect::Effect;
+use crate::ui::linked_workspaces::LinkedWorkspace;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum LinkCommand {
+    /// Show the linked workspaces and their git state
+    List,
+    /// Link the workspace with this name (or repository name)
+    Link(String),
+    /// Unlink one workspace, or all of them
+    Unlink(Option<String>),
+}
+
+impl LinkCommand {
+    pub(super) fn parse_link(arg: &str) -> Self {
+        if arg.is_empty() {
+            Self::List
+        } else {
+            Self::Link(arg.to_string())
+        }
+    }
+
+    pub(super) fn parse_unlink(arg: &str) -> Self {
+        Self::Unlink((!arg.is_empty()).then(|| arg.to_string()))
+    }
+}
+
+impl App {
+    pub(super) fn run_link_command(&mut self, command: LinkCommand) -> Vec<Effect> {
+        let Some(primary_id) = self
+            .state
+            .tab_manager
+            .active_session()
+            .and_then(|session| session.workspace_id)
+        else {
+            self.push_link_note("Open a workspace session to link other repositories.");
+            return Vec::new();
+        };
+
+        match command {
+            LinkCommand::List => {
+                let note = match self.state.tab_manager.active_session() {
+                    Some(session) if !session.linked_workspaces.is_empty() => {
+                        let lines: Vec<String> = session
+                            .linked_workspaces
+                            .iter()
+                            .map(|workspace| format!("- {}", workspace.summary()))
+                            .collect();
+                        format!("Linked workspaces:\n{}", lines.join("\n"))
+                    }
+                    _ => "No linked workspaces. Use /link <workspace> to add one from another repository."
+                        .to_string(),
+                };
+                self.push_link_note(note);
+                Vec::new()
+            }
+            LinkCommand::Link(query) => match self.resolve_linkable_workspace(primary_id, &query) {
+                Ok(linked) => {
+                    tracing::info!(
+                        workspace_id = %primary_id,
+                        linked_workspace_id = %linked.id,
+                        "Linked workspace to session"
+                    );
+                    if let Some(ref tracker) = self.git_tracker {
+                        tracker.track_workspace(linked.id, linked.path.clone());
+                    }
+                    let note = format!(
+                        "Linked {} at {}. The agent can work in it from its next start.",
+                        linked.display_name(),
+                        linked.path.display()
+                    );
+                    if let Some(session) = self.state.tab_manager.active_session_mut() {
+                        session.linked_workspaces.push(linked);
+                    }
+                    self.push_link_note(note);
+                    vec![Effect::SaveSessionState]
+                }
+                Err(note) => {
+                    self.push_link_note(note);
+                    Vec::new()
+                }
+            },
+            LinkCommand::Unlink(query) => {
+                let Some(session) = self.state.tab_manager.active_session_mut() else {
+                    return Vec::new();
+                };
+                let before = session.linked_workspaces.len();
+                match &query {
+                    Some(query) => session
+                        .linked_workspaces
+                        .retain(|workspace| !workspace.matches(query)),
+                    None => session.linked_workspaces.clear(),
+                }
+                let removed = before - session.linked_workspaces.len();
+                if removed == 0 {
+                    let note = match query {
+                        Some(query) => format!("No linked workspace named '{query}'."),
+                        None => "No linked workspaces to remove.".to_string(),
+                    };
+                    self.push_link_note(note);
+                    return Vec::new();
+                }
+                self.push_link_note(format!(
+                    "Unlinked {removed} workspace(s). The agent stops seeing them from its next start."
+                ));
+                vec![Effect::SaveSessionState]
+            }
+        }
+    }
+
+    /// Apply a git tracker update to every session that links `workspace_id`
+    pub(super) fn update_linked_workspaces(
+        &mut self,
+        workspace_id: Uuid,
+        update: impl Fn(&mut LinkedWorkspace),
+    ) {
+        for session in self.state.tab_manager.sessions_mut() {
+            for linked in session
+                .linked_workspaces
+                .iter_mut()
+                .filter(|linked| linked.id == workspace_id)
+            {
+                update(linked);
+            }
+        }
+    }
+
+    /// Resolve a saved tab's linked workspaces and start tracking them;
+    /// archived or deleted workspaces are dropped
+    pub(super) fn restore_linked_workspaces(&self, ids: &[Uuid]) -> Vec<LinkedWorkspace> {
+        let Some(workspace_dao) = self.workspace_dao() else {
+            return Vec::new();
+        };
+        let mut linked = Vec::new();
+        for &id in ids {
+            let workspace = match workspace_dao.get_by_id(id) {
+                Ok(Some(workspace)) if workspace.archived_at.is_none() => workspace,
+                Ok(_) => {
+                    tracing::info!(linked_workspace_id = %id, "Dropping link to missing or archived workspace");
+                    continue;
+                }
+                Err(err) => {
+                    tracing::warn!(linked_workspace_id = %id, error = %err, "Failed to load linked workspace");
+                    continue;
+                }
+            };
+            let repo_name = self
+                .repo_dao()
+                .and_then(|dao| dao.get_by_id(workspace.repository_id).ok().flatten())
+                .map(|repo| repo.name);
+            if let Some(ref tracker) = self.git_tracker {
+                tracker.track_workspace(workspace.id, workspace.path.clone());
+            }
+            linked.push(LinkedWorkspace::new(
+                workspace.id,
+                workspace.name,
+                repo_name,
+                workspace.path,
+            ));
+        }
+        linked
+    }
+
+    /// Find the workspace `query` names among other repositories' workspaces
+    fn resolve_linkable_workspace(
+        &self,
+        primary_id: Uuid,
+        query: &str,
+    ) -> Result<LinkedWorkspace, String> {
+        let Some(workspace_dao) = self.workspace_dao() else {
+            return Err("Linking workspaces is unavailable without a database.".to_string());
+        };
+        let workspaces = workspace_dao.get_all().map_err(|err| {
+            tracing::error!(error = %err, "Failed to load workspaces for /link");
+            format!("Failed to load workspaces: {err}")
+        })?;
+        let Some(primary_repo) = workspaces
+            .iter()
+            .find(|workspace| workspace.id == primary_id)
+            .map(|workspace| workspace.repository_id)
+        else {
+            return Err("This session's workspace no longer exists.".to_string());
+        };
+        let already_linked: Vec<Uuid> = self
+            .state
+            .tab_manager
+            .active_session()
+            .map(|session| session.linked_workspaces.iter().map(|w| w.id).collect())
+            .unwrap_or_default();
+
+        let candidates: Vec<(Workspace, Option<String>)> = workspaces
+            .into_iter()
+            .filter(|workspace| workspace.repository_id != primary_repo)
+            .map(|workspace| {
+                let repo_name = self
+                    .repo_dao()
+                    .and_then(|dao| dao.get_by_id(workspace.repository_id).ok().flatten())
+                    .map(|repo| repo.name);
+                (workspace, repo_name)
+            })
+            .collect();
+
+        // An exact workspace name wins over a repository name shared by several workspaces
+        let by_name: Vec<&(Workspace, Option<String>)> = candidates
+            .iter()
+            .filter(|(workspace, _)| workspace.name.eq_ignore_ascii_case(query))
+            .collect();
+        let matches = if by_name.is_empty() {
+            candidates
+                .iter()
+                .filter(|(_, repo)| {
+                    repo.as_deref()
+                        .is_some_and(|repo| repo.eq_ignore_ascii_case(query))
+                })
+                .collect()
+        } else {
+            by_name
+        };
+
+        match matches.as_slice() {
+            [] => Err(format!(
+                "No workspace named '{query}' in another repository. Workspaces of this session's repository can't be linked."
+            )),
+            [(workspace, _)] if already_linked.contains(&workspace.id) => {
+                Err(format!("{} is already linked.", workspace.name))
+            }
+            [(workspace, repo_name)] => Ok(LinkedWorkspace::new(
+                workspace.id,
+                workspace.name.clone(),
+                repo_name.clone(),
+                workspace.path.clone(),
+            )),
+            several => {
+                let names: Vec<&str> = several
+                    .iter()
+                    .map(|(workspace, _)| workspace.name.as_str())
+                    .collect();
+                Err(format!(
+                    "'{query}' matches several workspaces: {}. Link one by its workspace name.",
+                    names.join(", ")
+                ))
+            }
+        }
+    }
+
+    fn push_link_note(&mut self, note: impl Into<String>) {
+        if let Some(session) = self.state.tab_manager.active_session_mut() {
+            session.chat_view.push(ChatMessage::system(note));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_link_commands() {
+        assert_eq!(LinkCommand::parse_link(""), LinkCommand::List);
+        assert_eq!(
+            LinkCommand::parse_link("acme-client"),
+            LinkCommand::Link("acme-client".to_string())
+        );
+        assert_eq!(LinkCommand::parse_unlink(""), LinkCommand::Unlink(None));
+        assert_eq!(
+            LinkCommand::parse_unlink("acme-client"),
+            LinkCommand::Unlink(Some("acme-client".to_string()))
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Message selection mode: j/k move a highlight between the active chat's
+//! messages and y copies the highlighted one.
+
+use std::time::Duration;
+
+use crate::ui::app::App;
+use crate::ui::effect::Effect;
+use crate::ui::events::{InputMode, ViewMode};
+
+impl App {
+    /// Enter message selection with the last message in view highlighted
+    pub(super) fn start_message_selection(&mut self) {
+        if self.state.view_mode != ViewMode::Chat || self.state.tab_manager.active_is_file() {
+            return;
+        }
+        self.state.input_mode = InputMode::SelectingMessage;
+        if !self.move_message_selection(true) {
+            self.state.input_mode = InputMode::Normal;
+            self.state.set_timed_footer_message(
+                "No messages to select".to_string(),
+                Duration::from_secs(3),
+            );
+        }
+    }
+
+    /// Highlight the previous (`prev`) or next message; returns whether a
+    /// message is highlighted
+    pub(super) fn move_message_selection(&mut self, prev: bool) -> bool {
+        // Switched away from the chat since selecting started
+        if self.state.view_mode != ViewMode::Chat || self.state.tab_manager.active_is_file() {
+            self.exit_message_selection();
+            return false;
+        }
+        let show_chat_scrollbar = self.config().ui.show_chat_scrollbar;
+        match (
+            self.state.tab_manager.active_session_mut(),
+            self.state.chat_area,
+        ) {
+            (Some(session), Some(chat_area)) => {
+                session
+                    .chat_view
+                    .select_adjacent_message(chat_area, show_chat_scrollbar, prev)
+            }
+            _ => false,
+        }
+    }
+
+    /// Copy the highlighted message and leave selection mode
+    pub(super) fn copy_selected_message(&mut self, effects: &mut Vec<Effect>) {
+        let text = self
+            .state
+            .tab_manager
+            .active_session()
+            .and_then(|session| session.chat_view.selected_message_content())
+            .map(str::to_string);
+        let Some(text) = text else {
+            self.state.set_timed_footer_message(
+                "No message selected".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        };
+        effects.push(Effect::CopyToClipboard {
+            text,
+            source: "message",
+        });
+        self.exit_message_selection();
+        self.state
+            .set_timed_footer_message("Copied message".to_string(), Duration::from_secs(5));
+    }
+
+    /// Leave selection mode, dropping the highlight in every tab
+    pub(super) fn exit_message_selection(&mut self) {
+        for session in self.state.tab_manager.sessions_mut() {
+            session.chat_view.clear_message_selection();
+        }
+        self.state.input_mode = InputMode::Normal;
+    }
+}
@@ -0,0 +1,98 @@
+//! Session transcripts are written through the core's write-behind queue, so
+//! persisting never blocks the event loop. Raw events are queued as they
+//! arrive; chat messages once a turn ends, before they're offloaded, when
+//! their tab closes and on exit.
+
+use chrono::Utc;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::core::write_behind::WriteBehindQueue;
+use crate::data::{TranscriptEvent, TranscriptMessage};
+use crate::ui::app::App;
+use crate::ui::components::{ChatMessage, EventDirection};
+
+impl App {
+    /// Queue the chat messages of the session in tab `tab_index` that
+    /// haven't been persisted yet
+    pub(super) fn persist_session_messages(&mut self, tab_index: usize) {
+        if !self.config().persistence.messages || self.state.observing.is_some() {
+            return;
+        }
+        let queue = self.core.write_behind();
+        if let Some(session) = self.state.tab_manager.session_mut(tab_index) {
+            let messages = session.chat_view.take_unpersisted();
+            queue_messages(queue, session.id, messages);
+        }
+    }
+
+    /// Queue what's left of every session and wait for it to be written
+    pub(super) fn flush_persistence(&mut self) {
+        let Some(queue) = self.core.write_behind() else {
+            return;
+        };
+        if self.config().persistence.messages && self.state.observing.is_none() {
+            for session in self.state.tab_manager.sessions_mut() {
+                let messages = session.chat_view.take_unpersisted();
+                queue_messages(Some(queue), session.id, messages);
+            }
+        }
+        if !queue.flush() {
+            tracing::warn!("Some transcript writes may not have been saved");
+        }
+        let dropped = queue.dropped_events();
+        if dropped > 0 {
+            tracing::info!(dropped, "Raw events dropped while persistence fell behind");
+        }
+    }
+}
+
+/// Queue `messages` of `session_id`; a no-op without a queue
+pub(super) fn queue_messages(
+    queue: Option<&WriteBehindQueue>,
+    session_id: Uuid,
+    messages: Vec<ChatMessage>,
+) {
+    let Some(queue) = queue else {
+        return;
+    };
+    for message in messages {
+        let message_json = match serde_json::to_string(&message) {
+            Ok(json) => json,
+            Err(err) => {
+                tracing::warn!(%session_id, error = %err, "Failed to encode chat message for persisting");
+                continue;
+            }
+        };
+        queue.push_message(TranscriptMessage {
+            session_id,
+            role: format!("{:?}", message.role),
+            message_json,
+            recorded_at: message.timestamp.unwrap_or_else(Utc::now),
+        });
+    }
+}
+
+/// Queue a raw agent event of `session_id`; a no-op without a queue
+pub(super) fn queue_raw_event(
+    queue: Option<&WriteBehindQueue>,
+    session_id: Uuid,
+    direction: EventDirection,
+    event_type: &str,
+    payload: &Value,
+) {
+    let Some(queue) = queue else {
+        return;
+    };
+    let direction = match direction {
+        EventDirection::Sent => "sent",
+        EventDirection::Received => "received",
+    };
+    queue.push_event(TranscriptEvent {
+        session_id,
+        direction: direction.to_string(),
+        event_type: event_type.to_string(),
+        payload_json: payload.to_string(),
+        recorded_at: Utc::now(),
+    });
+}
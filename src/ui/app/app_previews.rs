@@ -0,0 +1,116 @@
+//! Dev server previews: servers announced in tool output show as a badge on
+//! every tab of the workspace, are forwarded when agents run in a container
+//! (`[preview] forward`), and disappear once the server stops.
+
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use crate::core::previews::{needs_forward, Preview};
+use crate::ui::app::{send_app_event, App};
+use crate::ui::effect::Effect;
+use crate::ui::events::AppEvent;
+use crate::util::preview::DetectedPreview;
+
+/// How often previews are checked for a server that stopped
+const PREVIEW_PRUNE_INTERVAL: Duration = Duration::from_secs(30);
+
+impl App {
+    /// Register servers announced by a session's tool output as previews of
+    /// its workspace; returns forwarding effects for new ones.
+    pub(super) fn record_previews(
+        &mut self,
+        tab_index: usize,
+        detected: Vec<DetectedPreview>,
+    ) -> Vec<Effect> {
+        let Some((session_id, workspace_id, working_dir)) = self
+            .state
+            .tab_manager
+            .session(tab_index)
+            .and_then(|session| {
+                Some((
+                    session.id,
+                    session.workspace_id?,
+                    session.working_dir.clone()?,
+                ))
+            })
+        else {
+            return Vec::new();
+        };
+
+        let target = self.core.execution_target_for_workspace(workspace_id);
+        let added = self
+            .core
+            .previews()
+            .record(workspace_id, session_id, &target, detected);
+        if added.is_empty() {
+            return Vec::new();
+        }
+        tracing::info!(
+            %workspace_id,
+            ports = ?added.iter().map(|preview| preview.port).collect::<Vec<_>>(),
+            "Detected dev server preview"
+        );
+        let previews = self.core.previews().list(workspace_id);
+        self.apply_previews(workspace_id, &previews);
+
+        if !needs_forward(&self.config().preview, &target) {
+            return Vec::new();
+        }
+        added
+            .into_iter()
+            .map(|preview| Effect::ForwardPreview {
+                workspace_id,
+                port: preview.port,
+                target: target.clone(),
+                working_dir: working_dir.clone(),
+            })
+            .collect()
+    }
+
+    /// Show a workspace's previews on all of its tabs
+    pub(super) fn apply_previews(&mut self, workspace_id: Uuid, previews: &[Preview]) {
+        let urls: Vec<String> = previews
+            .iter()
+            .map(|preview| preview.open_url().to_string())
+            .collect();
+        for session in self.state.tab_manager.sessions_mut() {
+            if session.workspace_id == Some(workspace_id) {
+                session.status_bar.set_previews(urls.clone());
+            }
+        }
+    }
+
+    /// Periodically drop previews whose server stopped; results arrive as
+    /// `AppEvent::PreviewsChanged`.
+    pub(super) fn prune_previews_if_due(&mut self) {
+        if self
+            .state
+            .last_preview_prune
+            .is_some_and(|last| last.elapsed() < PREVIEW_PRUNE_INTERVAL)
+        {
+            return;
+        }
+        self.state.last_preview_prune = Some(Instant::now());
+
+        let registry = self.core.previews().clone();
+        let workspace_ids = registry.workspace_ids();
+        if workspace_ids.is_empty() {
+            return;
+        }
+        let event_tx = self.event_tx.clone();
+        tokio::spawn(async move {
+            for workspace_id in workspace_ids {
+                let previews = registry.prune(workspace_id).await;
+                send_app_event(
+                    &event_tx,
+                    AppEvent::PreviewsChanged {
+                        workspace_id,
+                        previews,
+                    },
+                    "previews_pruned",
+                );
+            }
+        });
+    }
+}
@@ -0,0 +1,135 @@
+//! Quitting while agents are running. Quit asks first when any tab is mid-turn,
+//! offering to wait for the turns to end, to kill the agents, or to stay; and
+//! whatever the route out, agent processes are terminated on exit.
+
+use std::time::Duration;
+
+use crate::agent::MessageDisplay;
+use crate::ui::app::App;
+use crate::ui::components::{ConfirmationContext, ConfirmationType};
+use crate::ui::effect::Effect;
+use crate::ui::events::InputMode;
+
+/// Running tabs listed in the dialog
+const DIALOG_RUN_LIMIT: usize = 6;
+
+impl App {
+    /// Quit, asking first if any tab still has a running turn
+    pub(super) fn request_quit(&mut self, effects: &mut Vec<Effect>) {
+        let running: Vec<String> = self
+            .state
+            .tab_manager
+            .sessions()
+            .into_iter()
+            .filter(|session| session.is_processing)
+            .map(|session| session.tab_name())
+            .collect();
+        if running.is_empty() {
+            self.state.should_quit = true;
+            effects.push(Effect::SaveSessionState);
+            return;
+        }
+
+        let mut message = format!("{} agent(s) are still running:", running.len());
+        for name in running.iter().take(DIALOG_RUN_LIMIT) {
+            message.push_str(&format!("\n{name}"));
+        }
+        if running.len() > DIALOG_RUN_LIMIT {
+            message.push_str(&format!("\n…and {} more", running.len() - DIALOG_RUN_LIMIT));
+        }
+        message.push_str("\n\nWait for them to finish, or stop them and quit now?");
+
+        self.state.close_overlays();
+        self.state.confirmation_dialog_state.show(
+            "Agents Running",
+            message,
+            vec![],
+            ConfirmationType::Warning,
+            "Kill all & quit",
+            Some(ConfirmationContext::QuitWithRunningAgents),
+        );
+        self.state.confirmation_dialog_state.cancel_text = "Wait".to_string();
+        self.state.input_mode = InputMode::Confirming;
+    }
+
+    /// Quit on its own once the running turns have ended
+    pub(super) fn wait_then_quit(&mut self) {
+        self.state.confirmation_dialog_state.hide();
+        self.state.input_mode = InputMode::Normal;
+        self.state.quit_when_idle = true;
+        self.state.set_timed_footer_message(
+            "Quitting once the running agents finish".to_string(),
+            Duration::from_secs(5),
+        );
+    }
+
+    /// Stop every running turn and quit; the processes go in the exit cleanup
+    pub(super) fn kill_agents_and_quit(&mut self, effects: &mut Vec<Effect>) {
+        self.state.confirmation_dialog_state.hide();
+        self.state.input_mode = InputMode::Normal;
+        for session in self
+            .state
+            .tab_manager
+            .sessions_mut()
+            .filter(|session| session.is_processing)
+        {
+            Self::flush_pending_agent_output(session);
+            session.stop_processing();
+            session.chat_view.finalize_streaming();
+            let display = MessageDisplay::System {
+                content: "Interrupted".to_string(),
+            };
+            session.chat_view.push(display.to_chat_message());
+        }
+        self.state.should_quit = true;
+        effects.push(Effect::SaveSessionState);
+    }
+
+    /// Quit if waiting for the running turns and none are left
+    pub(super) fn quit_if_idle(&mut self, effects: &mut Vec<Effect>) {
+        if !self.state.quit_when_idle || self.state.should_quit {
+            return;
+        }
+        let busy = self
+            .state
+            .tab_manager
+            .sessions()
+            .iter()
+            .any(|session| session.is_processing);
+        if !busy {
+            tracing::info!("Running agents finished, quitting");
+            self.state.should_quit = true;
+            effects.push(Effect::SaveSessionState);
+        }
+    }
+
+    /// Terminate the agent processes still attached to any tab
+    pub(super) fn terminate_agents_on_exit(&mut self) {
+        let pids: Vec<(u32, Option<u64>)> = self
+            .state
+            .tab_manager
+            .sessions_mut()
+            .filter_map(|session| {
+                if let Some(cancel) = session.agent_cancel.take() {
+                    cancel.cancel();
+                }
+                let pid = session.agent_pid.take()?;
+                Some((pid, session.agent_pid_start_time.take()))
+            })
+            .collect();
+        if pids.is_empty() {
+            return;
+        }
+        tracing::info!(count = pids.len(), "Terminating agents on exit");
+        // Each may wait out its grace periods, so they run side by side
+        std::thread::scope(|scope| {
+            for (pid, pid_start_time) in pids {
+                scope.spawn(move || {
+                    if !Self::terminate_agent_pid(pid, pid_start_time, "exit") {
+                        tracing::warn!(pid, "Agent still running after exit");
+                    }
+                });
+            }
+        });
+    }
+}
@@ -276,11 +276,19 @@ impl App {
             ScrollDragTarget::Chat => {
                 let area = self.state.chat_area?;
                 let show_chat_scrollbar = self.config().ui.show_chat_scrollbar;
+                let queue_panel = self.queue_panel();
+                let input_mode = self.state.input_mode;
                 let session = self.state.tab_manager.active_session_mut()?;
+                let queue_len = crate::ui::app_queue::queue_lines_len(
+                    session,
+                    area.width,
+                    input_mode,
+                    queue_panel,
+                );
                 session.chat_view.scrollbar_metrics(
                     area,
                     session.is_processing,
-                    session.queued_messages.len(),
+                    queue_len,
                     show_chat_scrollbar,
                 )
             }
@@ -0,0 +1,130 @@
+//! Security scan results in the PR flow. Each scanner's findings are listed
+//! in the chat as a tool item, and findings at the blocking severity replace
+//! the PR dialog with an offer to have the agent fix them.
+
+use std::path::{Path, PathBuf};
+
+use crate::core::security_scan::{ScanOutcome, SecurityScanReport};
+use crate::ui::app::App;
+use crate::ui::components::{ChatMessage, ConfirmationContext, ConfirmationType};
+use crate::ui::effect::Effect;
+
+/// Blocking findings listed in the dialog; the chat lists all of them
+const DIALOG_FINDING_LIMIT: usize = 5;
+
+impl App {
+    /// List the scan results in the chat of the session the PR is for
+    pub(super) fn record_security_scan(
+        &mut self,
+        tab_index: usize,
+        working_dir: &Path,
+        report: &SecurityScanReport,
+    ) {
+        let Some(session) = self
+            .pr_tab_index(tab_index, working_dir)
+            .and_then(|index| self.state.tab_manager.session_mut(index))
+        else {
+            tracing::debug!(
+                working_dir = %working_dir.display(),
+                "No session for security scan results"
+            );
+            return;
+        };
+        for result in &report.results {
+            let name = result.scanner.display_name();
+            match &result.outcome {
+                ScanOutcome::Findings(_) => {
+                    let (output, has_findings) = report.result_output(result);
+                    let args = serde_json::json!({ "command": result.command }).to_string();
+                    session.chat_view.push(ChatMessage::tool_with_exit(
+                        "Bash",
+                        args,
+                        output,
+                        Some(i32::from(has_findings)),
+                    ));
+                }
+                ScanOutcome::NotInstalled => {
+                    session.chat_view.push(ChatMessage::system(format!(
+                        "Skipped {name}: it isn't installed ({}).",
+                        result.scanner.install_hint()
+                    )));
+                }
+                ScanOutcome::Failed(err) => {
+                    session
+                        .chat_view
+                        .push(ChatMessage::error(format!("{name} failed: {err}")));
+                }
+            }
+        }
+    }
+
+    /// Ask whether the agent should fix the findings blocking the PR
+    pub(super) fn confirm_security_fix(
+        &mut self,
+        tab_index: usize,
+        working_dir: PathBuf,
+        report: &SecurityScanReport,
+    ) {
+        let blocking = report.blocking();
+        let threshold = report
+            .block_at
+            .map(|severity| severity.as_str())
+            .unwrap_or_default();
+        let mut message = format!(
+            "{} finding(s) at {threshold} severity or above block this PR:",
+            blocking.len()
+        );
+        for finding in blocking.iter().take(DIALOG_FINDING_LIMIT) {
+            message.push_str(&format!(
+                "\n[{}] {} ({})",
+                finding.severity, finding.title, finding.location
+            ));
+        }
+        if blocking.len() > DIALOG_FINDING_LIMIT {
+            message.push_str(&format!(
+                "\n…and {} more, listed in the chat",
+                blocking.len() - DIALOG_FINDING_LIMIT
+            ));
+        }
+        message.push_str("\n\nAsk the agent to fix them? Create the PR again once they're fixed.");
+
+        self.state.close_overlays();
+        self.state.confirmation_dialog_state.show(
+            "Security Findings",
+            message,
+            vec![],
+            ConfirmationType::Danger,
+            "Fix findings",
+            Some(ConfirmationContext::FixSecurityFindings {
+                tab_index,
+                working_dir,
+                prompt: report.fix_prompt(),
+            }),
+        );
+        // Already in Confirming mode
+    }
+
+    /// Send the fix prompt to the session the PR was for
+    pub(super) fn submit_security_fix(
+        &mut self,
+        tab_index: usize,
+        working_dir: PathBuf,
+        prompt: String,
+    ) -> anyhow::Result<Vec<Effect>> {
+        let Some(target_tab_index) = self.pr_tab_index(tab_index, &working_dir) else {
+            self.show_error(
+                "Cannot Fix Findings",
+                "No session found for the scanned workspace.",
+            );
+            return Ok(Vec::new());
+        };
+        self.submit_prompt_for_tab(
+            target_tab_index,
+            prompt,
+            Vec::new(),
+            Vec::new(),
+            false,
+            None,
+        )
+    }
+}
@@ -141,7 +141,10 @@ impl App {
 
         let mut effects = Vec::new();
         if let Some(text) = copied_text {
-            effects.push(crate::ui::effect::Effect::CopyToClipboard(text));
+            effects.push(crate::ui::effect::Effect::CopyToClipboard {
+                text,
+                source: "selection",
+            });
             self.state.set_timed_footer_message(
                 "Copied selection".to_string(),
                 std::time::Duration::from_secs(5),
@@ -0,0 +1,189 @@
+//! The sidebar's "Sessions" group: every saved session tab, open or closed,
+//! so any of them can be brought back as a tab. The splash screen lists the
+//! most recently active of them.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::data::SessionTab;
+use crate::ui::app::App;
+use crate::ui::app_state::RecentSession;
+use crate::ui::events::InputMode;
+use crate::ui::session_report::report_suffix;
+
+/// Saved sessions listed on the splash screen
+const RECENT_SESSION_LIMIT: usize = 5;
+
+impl App {
+    /// Sidebar rows for saved sessions as `(id, label, suffix)`, newest first.
+    /// The suffix names the session's workspace or project directory, followed
+    /// by the final report of closed sessions.
+    pub(super) fn sidebar_session_entries(
+        &self,
+        workspace_names: &HashMap<Uuid, String>,
+    ) -> Vec<(Uuid, String, Option<String>)> {
+        let Some(session_tab_dao) = self.session_tab_dao() else {
+            return Vec::new();
+        };
+        let tabs = match session_tab_dao.get_all_including_closed() {
+            Ok(tabs) => tabs,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load saved sessions for the sidebar");
+                return Vec::new();
+            }
+        };
+        let reports = match self
+            .core
+            .session_report_store()
+            .map(|store| store.get_all())
+        {
+            Some(Ok(reports)) => reports,
+            Some(Err(e)) => {
+                tracing::warn!(error = %e, "Failed to load session reports for the sidebar");
+                HashMap::new()
+            }
+            None => HashMap::new(),
+        };
+        tabs.into_iter()
+            .map(|tab| {
+                let location = match tab.workspace_id {
+                    Some(workspace_id) => workspace_names.get(&workspace_id).cloned(),
+                    None => tab
+                        .working_dir
+                        .as_deref()
+                        .and_then(|dir| dir.file_name())
+                        .map(|name| name.to_string_lossy().into_owned()),
+                };
+                let report = reports.get(&tab.id).filter(|_| !tab.is_open);
+                let suffix = report_suffix(location, report);
+                (tab.id, Self::sidebar_session_label(&tab), suffix)
+            })
+            .collect()
+    }
+
+    /// Reload the sidebar's saved sessions, keeping the rest of the tree.
+    pub(super) fn refresh_sidebar_sessions(&mut self) {
+        let sessions = self.sidebar_session_entries(&self.state.sidebar_data.workspace_names());
+        let expanded = self.state.sidebar_data.sessions_expanded();
+        self.state.sidebar_data.set_sessions(sessions, expanded);
+    }
+
+    /// Reload the recently active sessions listed on the splash screen.
+    pub(super) fn refresh_recent_sessions(&mut self) {
+        let Some(session_tab_dao) = self.session_tab_dao() else {
+            return;
+        };
+        let recent = match session_tab_dao.get_recent(RECENT_SESSION_LIMIT) {
+            Ok(recent) => recent,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load recent sessions for the splash screen");
+                return;
+            }
+        };
+        let workspace_dao = self.workspace_dao();
+        self.state.recent_sessions = recent
+            .into_iter()
+            .map(|(tab, last_active)| {
+                let location = match tab.workspace_id {
+                    Some(workspace_id) => workspace_dao
+                        .as_ref()
+                        .and_then(|dao| dao.get_by_id(workspace_id).ok().flatten())
+                        .map(|workspace| workspace.name),
+                    None => tab
+                        .working_dir
+                        .as_deref()
+                        .and_then(|dir| dir.file_name())
+                        .map(|name| name.to_string_lossy().into_owned()),
+                };
+                RecentSession {
+                    id: tab.id,
+                    title: Self::sidebar_session_label(&tab),
+                    agent_type: tab.agent_type,
+                    location,
+                    last_active,
+                }
+            })
+            .collect();
+    }
+
+    fn sidebar_session_label(tab: &SessionTab) -> String {
+        tab.custom_title
+            .clone()
+            .or_else(|| tab.title.clone())
+            .unwrap_or_else(|| format!("{} session", tab.agent_type.display_name()))
+    }
+
+    /// Switch to a saved session's tab, reopening it first if it was closed.
+    pub(super) fn open_saved_session(&mut self, session_id: Uuid, close_sidebar: bool) {
+        if let Some(index) = self.state.tab_manager.session_index_by_id(session_id) {
+            self.state.tab_manager.switch_to(index);
+            self.finish_open_saved_session(close_sidebar);
+            return;
+        }
+
+        let Some(session_tab_dao) = self.session_tab_dao_clone() else {
+            return;
+        };
+        let tab = match session_tab_dao.get_by_id(session_id) {
+            Ok(Some(tab)) => tab,
+            Ok(None) => {
+                self.refresh_sidebar_data();
+                return;
+            }
+            Err(e) => {
+                tracing::warn!(%session_id, error = %e, "Failed to load saved session");
+                return;
+            }
+        };
+
+        // A workspace has one open tab at a time
+        if let Some(workspace_id) = tab.workspace_id {
+            if let Some(index) = self.find_tab_for_workspace(workspace_id) {
+                self.state.tab_manager.switch_to(index);
+                self.state.set_timed_footer_message(
+                    "Close this workspace's open tab to reopen another of its sessions".to_string(),
+                    Duration::from_secs(5),
+                );
+                self.finish_open_saved_session(close_sidebar);
+                return;
+            }
+        }
+
+        if let Some(required_tool) = self.missing_tool_for(tab.agent_type) {
+            self.show_missing_tool(
+                required_tool,
+                format!(
+                    "{} is required to open this session.",
+                    required_tool.display_name()
+                ),
+            );
+            return;
+        }
+
+        let session = self.session_from_saved_tab(tab);
+        let Some(index) = self.add_restored_session(session) else {
+            self.state.set_timed_footer_message(
+                "Maximum number of tabs reached".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        };
+        if self.state.observing.is_none() {
+            if let Err(e) = session_tab_dao.set_open(session_id, true) {
+                tracing::warn!(%session_id, error = %e, "Failed to mark saved session as open");
+            }
+        }
+        self.state.tab_manager.switch_to(index);
+        self.finish_open_saved_session(close_sidebar);
+    }
+
+    fn finish_open_saved_session(&mut self, close_sidebar: bool) {
+        self.sync_footer_spinner();
+        if close_sidebar {
+            self.state.sidebar_state.hide();
+            self.state.input_mode = InputMode::Normal;
+        }
+    }
+}
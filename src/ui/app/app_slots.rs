@@ -0,0 +1,83 @@
+//! Concurrent-agent limit (`queue.max_concurrent_agents`): prompts submitted
+//! while every slot is busy wait in their session queue and are started, oldest
+//! first, once another session's turn ends.
+
+use crate::ui::app::App;
+use crate::ui::effect::Effect;
+
+impl App {
+    /// Number of sessions currently running an agent turn.
+    pub(super) fn running_agent_count(&self) -> usize {
+        self.state
+            .tab_manager
+            .sessions()
+            .iter()
+            .filter(|session| session.is_processing)
+            .count()
+    }
+
+    /// Whether starting another turn would exceed the concurrent-agent limit.
+    pub(super) fn agent_slots_full(&self) -> bool {
+        self.config()
+            .queue
+            .max_concurrent_agents
+            .is_some_and(|limit| self.running_agent_count() >= limit)
+    }
+
+    /// Whether a new turn for `tab_index` has to wait: either every slot is
+    /// busy or another session is already waiting ahead of it.
+    pub(super) fn should_wait_for_slot(&self, tab_index: usize) -> bool {
+        if self.config().queue.max_concurrent_agents.is_none() {
+            return false;
+        }
+        self.agent_slots_full()
+            || self
+                .state
+                .tab_manager
+                .sessions()
+                .iter()
+                .enumerate()
+                .any(|(index, session)| index != tab_index && session.waiting_for_slot)
+    }
+
+    /// Start queued prompts for sessions waiting on a slot while slots remain.
+    pub(super) fn dispatch_slot_waiters(&mut self) -> anyhow::Result<Vec<Effect>> {
+        let mut effects = Vec::new();
+
+        // Oldest queued prompt goes first
+        let mut waiting: Vec<_> = self
+            .state
+            .tab_manager
+            .sessions()
+            .iter()
+            .enumerate()
+            .filter(|(_, session)| session.waiting_for_slot)
+            .map(|(index, session)| {
+                let queued_at = session.queued_messages.first().map(|msg| msg.created_at);
+                (queued_at, index)
+            })
+            .collect();
+        if waiting.is_empty() {
+            return Ok(effects);
+        }
+        waiting.sort();
+
+        for (_, tab_index) in waiting {
+            if self.agent_slots_full() {
+                break;
+            }
+            let Some(session) = self.state.tab_manager.session_mut(tab_index) else {
+                continue;
+            };
+            session.waiting_for_slot = false;
+            session.update_status();
+            // A session that started a turn some other way drains its queue when it ends
+            if session.is_processing || session.queued_messages.is_empty() {
+                continue;
+            }
+            effects.extend(self.submit_queued_for_tab(tab_index)?);
+        }
+
+        Ok(effects)
+    }
+}
@@ -0,0 +1,120 @@
+//! Stalled turns: a running turn whose agent process is alive but has sent
+//! no events for `[stall] after_secs` gets a card offering to keep waiting,
+//! nudge the agent, or interrupt and resend the prompt. Nothing is stopped
+//! until the user picks one.
+
+use std::time::{Duration, Instant};
+
+use crate::agent::MessageDisplay;
+use crate::config::KeyContext;
+use crate::ui::action::Action;
+use crate::ui::app::App;
+use crate::ui::components::GlobalFooter;
+use crate::ui::effect::Effect;
+
+/// Follow-up sent by `nudge_agent` after interrupting the stalled turn
+const NUDGE_PROMPT: &str = "You haven't produced any output for a while. \
+Continue with the task, or explain what is blocking you.";
+
+impl App {
+    /// Flag running turns that went quiet with a suggestion card; returns
+    /// whether any card was added
+    pub(super) fn flag_stalled_turns(&mut self) -> bool {
+        let Some(threshold) = self.config().stall.threshold() else {
+            return false;
+        };
+        let keymap = &self.config().keybindings;
+        let nudge_key = GlobalFooter::key_label_for(keymap, KeyContext::Chat, &Action::NudgeAgent)
+            .unwrap_or_else(|| ":nudge_agent".to_string());
+        let restart_key =
+            GlobalFooter::key_label_for(keymap, KeyContext::Chat, &Action::RestartAgent)
+                .unwrap_or_else(|| ":restart_agent".to_string());
+
+        let active_id = self
+            .state
+            .tab_manager
+            .active_session()
+            .map(|session| session.id);
+        let now = Instant::now();
+        let mut flagged = false;
+        for session in self.state.tab_manager.sessions_mut() {
+            if session.stall_notified || !session.is_stalled(now, threshold) {
+                continue;
+            }
+            session.stall_notified = true;
+            flagged = true;
+            tracing::info!(
+                session_id = %session.id,
+                agent = session.agent_type.as_str(),
+                quiet_secs = threshold.as_secs(),
+                "Turn looks stalled"
+            );
+            let options = if session.capabilities.supports_interrupts {
+                format!(
+                    "Keep waiting, press {} to nudge the agent, or {} to interrupt and resend your prompt.",
+                    nudge_key, restart_key
+                )
+            } else {
+                format!(
+                    "Keep waiting, or press {} to interrupt and resend your prompt.",
+                    restart_key
+                )
+            };
+            let display = MessageDisplay::System {
+                content: format!(
+                    "⏳ {} has sent nothing for {} — the turn may be stalled.\n{}",
+                    session.agent_type.display_name(),
+                    quiet_for(threshold),
+                    options
+                ),
+            };
+            session.chat_view.push(display.to_chat_message());
+            if active_id != Some(session.id) {
+                session.needs_attention = true;
+            }
+        }
+        flagged
+    }
+
+    /// Interrupt the active session's stalled turn and ask the agent to
+    /// continue; the last prompt stays available to `restart_agent`
+    pub(super) fn nudge_stalled_agent(&mut self) -> anyhow::Result<Vec<Effect>> {
+        let outcome = match self.state.tab_manager.active_session() {
+            None => Err("No active session".to_string()),
+            Some(session) if !session.is_processing || !session.stall_notified => {
+                Err("No stalled turn to nudge".to_string())
+            }
+            Some(session) if !session.capabilities.supports_interrupts => Err(format!(
+                "{} can't be nudged mid-turn; restart it instead",
+                session.agent_type.display_name()
+            )),
+            Some(session) => Ok(session.last_prompt.clone()),
+        };
+        let last_prompt = match outcome {
+            Ok(last_prompt) => last_prompt,
+            Err(message) => {
+                self.state
+                    .set_timed_footer_message(message, Duration::from_secs(3));
+                return Ok(Vec::new());
+            }
+        };
+
+        self.interrupt_agent();
+        let effects = self.submit_prompt(NUDGE_PROMPT.to_string(), Vec::new(), Vec::new())?;
+        // Interrupting and resending should repeat the user's prompt, not the nudge
+        if let Some(session) = self.state.tab_manager.active_session_mut() {
+            session.last_prompt = last_prompt;
+        }
+        Ok(effects)
+    }
+}
+
+/// Quiet period as shown on the card
+fn quiet_for(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 60 && secs.is_multiple_of(60) {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
@@ -0,0 +1,162 @@
+use std::time::Instant;
+
+use crate::agent::claude::SUBAGENT_TOOL_NAME;
+use crate::agent::events::{AgentEvent, SubagentCompletedEvent, SubagentStartedEvent};
+use crate::agent::MessageDisplay;
+use crate::ui::app::App;
+use crate::ui::components::{ChatMessage, MessageRole, ProcessingState};
+use crate::ui::session::AgentSession;
+
+impl App {
+    /// Open a subagent group headed by its Task call.
+    pub(super) fn handle_subagent_started(
+        session: &mut AgentSession,
+        started: SubagentStartedEvent,
+    ) {
+        session.set_processing_state(ProcessingState::ToolUse(SUBAGENT_TOOL_NAME.to_string()));
+        // Pairs with SubagentCompleted, like ToolStarted/ToolCompleted
+        session.tools_in_flight = session.tools_in_flight.saturating_add(1);
+        session.timeline.tool_started(
+            Some(started.tool_id.clone()),
+            SUBAGENT_TOOL_NAME,
+            Instant::now(),
+        );
+
+        let args = serde_json::json!({
+            "description": started.description,
+            "subagent_type": started.subagent_type,
+            "prompt": started.prompt,
+        });
+        let display = MessageDisplay::Tool {
+            name: MessageDisplay::tool_display_name_owned(SUBAGENT_TOOL_NAME),
+            args: args.to_string(),
+            output: "Running...".to_string(),
+            exit_code: None,
+            file_size: None,
+        };
+        session
+            .chat_view
+            .push(display.to_chat_message().with_tool_id(started.tool_id));
+    }
+
+    /// Close a subagent group, summarizing the tool calls it made.
+    pub(super) fn handle_subagent_completed(
+        session: &mut AgentSession,
+        completed: SubagentCompletedEvent,
+    ) {
+        session.set_processing_state(ProcessingState::Thinking);
+        session.tools_in_flight = match session.tools_in_flight.checked_sub(1) {
+            Some(value) => value,
+            None => {
+                tracing::warn!("tools_in_flight underflow on SubagentCompleted");
+                0
+            }
+        };
+        session
+            .timeline
+            .tool_completed(&completed.tool_id, Instant::now());
+
+        let tool_calls = session
+            .chat_view
+            .messages()
+            .iter()
+            .filter(|msg| {
+                msg.role == MessageRole::Tool
+                    && msg.parent_tool_id.as_deref() == Some(completed.tool_id.as_str())
+            })
+            .count();
+        let call_word = if tool_calls == 1 { "call" } else { "calls" };
+        let output = if completed.success {
+            format!(
+                "Subagent finished after {} tool {}\n\n{}",
+                tool_calls,
+                call_word,
+                completed.result.unwrap_or_default()
+            )
+        } else {
+            format!(
+                "Subagent failed after {} tool {}\n\nError: {}",
+                tool_calls,
+                call_word,
+                completed.error.unwrap_or_default()
+            )
+        };
+
+        if !session
+            .chat_view
+            .update_tool_by_id(&completed.tool_id, output, None)
+        {
+            tracing::warn!(
+                tool_id = %completed.tool_id,
+                "SubagentCompleted: no matching tool message found to update"
+            );
+        }
+    }
+
+    /// Render an event emitted inside a subagent within its group.
+    pub(super) fn handle_subagent_event(
+        session: &mut AgentSession,
+        parent_tool_id: String,
+        event: AgentEvent,
+    ) {
+        match event {
+            AgentEvent::AssistantMessage(msg) => {
+                if msg.text.trim().is_empty() {
+                    return;
+                }
+                session.chat_view.push_nested(
+                    ChatMessage::assistant(msg.text).with_parent_tool_id(parent_tool_id),
+                );
+            }
+            AgentEvent::AssistantReasoning(reasoning) => {
+                session.chat_view.push_nested(
+                    ChatMessage::reasoning(reasoning.text).with_parent_tool_id(parent_tool_id),
+                );
+            }
+            AgentEvent::ToolStarted(tool) => {
+                let args = if tool.arguments.is_null() {
+                    String::new()
+                } else {
+                    serde_json::to_string(&tool.arguments).unwrap_or_default()
+                };
+                let display = MessageDisplay::Tool {
+                    name: MessageDisplay::tool_display_name_owned(&tool.tool_name),
+                    args,
+                    output: "Running...".to_string(),
+                    exit_code: None,
+                    file_size: None,
+                };
+                session.chat_view.push_nested(
+                    display
+                        .to_chat_message()
+                        .with_tool_id(tool.tool_id)
+                        .with_parent_tool_id(parent_tool_id),
+                );
+            }
+            AgentEvent::ToolCompleted(tool) => {
+                let output = if tool.success {
+                    tool.result.unwrap_or_else(|| "Completed".to_string())
+                } else {
+                    format!("Error: {}", tool.error.unwrap_or_default())
+                };
+                if !session
+                    .chat_view
+                    .update_tool_by_id(&tool.tool_id, output, None)
+                {
+                    tracing::warn!(
+                        tool_id = %tool.tool_id,
+                        %parent_tool_id,
+                        "Subagent ToolCompleted: no matching tool message found to update"
+                    );
+                }
+            }
+            other => {
+                tracing::debug!(
+                    event_type = other.event_type_name(),
+                    %parent_tool_id,
+                    "Ignoring subagent event"
+                );
+            }
+        }
+    }
+}
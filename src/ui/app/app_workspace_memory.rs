@@ -0,0 +1,200 @@
+//! `/memory`: view, edit, and sync the workspace's shared context into the
+//! agents' memory files (`CLAUDE.md`, `AGENTS.md`). Saving only previews the
+//! change as a diff; files are written by `/memory sync`.
+
+use std::path::Path;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::core::memory::{self, MemoryFilePlan};
+use crate::ui::app::App;
+use crate::ui::components::ChatMessage;
+
+/// Unchanged lines kept around each change in previews
+const DIFF_CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum MemoryCommand {
+    /// Show the shared context and what a sync would change
+    Show,
+    /// Load the shared context into the input box for editing
+    Edit,
+    /// Replace the shared context
+    Save(String),
+    /// Write the shared context into the memory files
+    Sync,
+}
+
+impl MemoryCommand {
+    pub(super) fn parse(arg: &str) -> Result<Self, String> {
+        let (sub, rest) = arg
+            .split_once(char::is_whitespace)
+            .map_or((arg, ""), |(sub, rest)| (sub, rest.trim()));
+        match sub {
+            "" | "show" => Ok(Self::Show),
+            "edit" => Ok(Self::Edit),
+            "save" => Ok(Self::Save(rest.to_string())),
+            "sync" => Ok(Self::Sync),
+            _ => Err(format!(
+                "Unknown memory command '{sub}'. Options: show, edit, save <text>, sync."
+            )),
+        }
+    }
+}
+
+impl App {
+    pub(super) fn run_memory_command(&mut self, command: MemoryCommand) {
+        let Some((workspace_id, working_dir)) = self
+            .state
+            .tab_manager
+            .active_session()
+            .and_then(|session| Some((session.workspace_id?, session.working_dir.clone()?)))
+        else {
+            self.push_memory_note("Open a workspace session to manage its memory.");
+            return;
+        };
+        let Some(store) = self.core.workspace_memory_store() else {
+            self.push_memory_note("Workspace memory is unavailable without a database.");
+            return;
+        };
+        let saved = match store.get(workspace_id) {
+            Ok(saved) => saved.map(|saved| saved.content),
+            Err(err) => {
+                tracing::error!(%workspace_id, error = %err, "Failed to load workspace memory");
+                self.push_memory_note(format!("Failed to load workspace memory: {err}"));
+                return;
+            }
+        };
+
+        match command {
+            MemoryCommand::Show => {
+                let note = match preview_saved_or_synced(&working_dir, saved) {
+                    Ok(note) => note,
+                    Err(err) => read_failed(workspace_id, &err),
+                };
+                self.push_memory_note(note);
+            }
+            MemoryCommand::Edit => {
+                let content = saved.unwrap_or_default();
+                if let Some(session) = self.state.tab_manager.active_session_mut() {
+                    session.input_box.clear();
+                    session
+                        .input_box
+                        .insert_str(&format!("{} save {content}", super::MEMORY_COMMAND));
+                }
+            }
+            MemoryCommand::Save(content) => {
+                let note = match store.save(workspace_id, content.trim()) {
+                    Ok(saved) => match memory::plan_sync(&working_dir, &saved.content) {
+                        Ok(plans) => memory_preview(&saved.content, &plans),
+                        Err(err) => read_failed(workspace_id, &err),
+                    },
+                    Err(err) => {
+                        tracing::error!(%workspace_id, error = %err, "Failed to save workspace memory");
+                        format!("Failed to save workspace memory: {err}")
+                    }
+                };
+                self.push_memory_note(note);
+            }
+            MemoryCommand::Sync => {
+                let Some(content) = saved else {
+                    self.push_memory_note(
+                        "Nothing saved yet. Use /memory save <text> to set the shared context.",
+                    );
+                    return;
+                };
+                let written = memory::plan_sync(&working_dir, &content)
+                    .and_then(|plans| memory::apply_sync(&plans));
+                match written {
+                    Ok(written) if written.is_empty() => {
+                        self.push_memory_note("Memory files are already in sync.");
+                    }
+                    Ok(written) => {
+                        tracing::info!(%workspace_id, files = ?written, "Synced workspace memory");
+                        self.state.set_timed_footer_message(
+                            format!("Synced memory into {}", written.join(", ")),
+                            Duration::from_secs(3),
+                        );
+                    }
+                    Err(err) => {
+                        tracing::error!(%workspace_id, error = %err, "Failed to sync workspace memory");
+                        self.push_memory_note(format!("Failed to sync workspace memory: {err}"));
+                    }
+                }
+            }
+        }
+    }
+
+    fn push_memory_note(&mut self, note: impl Into<String>) {
+        if let Some(session) = self.state.tab_manager.active_session_mut() {
+            session.chat_view.push(ChatMessage::system(note));
+        }
+    }
+}
+
+/// Preview of the saved shared context; before anything is saved, the
+/// context already synced into the memory files is shown instead
+fn preview_saved_or_synced(
+    working_dir: &Path,
+    saved: Option<String>,
+) -> Result<String, memory::MemoryError> {
+    let content = match saved {
+        Some(content) => content,
+        None => {
+            let plans = memory::plan_sync(working_dir, "")?;
+            memory::synced_content(&plans)
+                .unwrap_or_default()
+                .to_string()
+        }
+    };
+    let plans = memory::plan_sync(working_dir, &content)?;
+    Ok(memory_preview(&content, &plans))
+}
+
+fn read_failed(workspace_id: Uuid, err: &memory::MemoryError) -> String {
+    tracing::error!(%workspace_id, error = %err, "Failed to read memory files");
+    format!("Failed to read memory files: {err}")
+}
+
+/// Shared context followed by the change a sync would make to each file
+fn memory_preview(content: &str, plans: &[MemoryFilePlan]) -> String {
+    let mut out = if content.is_empty() {
+        "Shared memory is empty.".to_string()
+    } else {
+        format!("Shared memory:\n{content}")
+    };
+    for plan in plans {
+        if !plan.needs_write() {
+            out.push_str(&format!("\n\n{}: in sync", plan.file_name));
+        } else {
+            out.push_str(&format!(
+                "\n\n{} would change:\n{}",
+                plan.file_name,
+                memory::format_diff(&plan.diff(), DIFF_CONTEXT_LINES).trim_end()
+            ));
+        }
+    }
+    if plans.iter().any(MemoryFilePlan::needs_write) {
+        out.push_str("\n\nRun /memory sync to write these changes.");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_memory_command() {
+        assert_eq!(MemoryCommand::parse(""), Ok(MemoryCommand::Show));
+        assert_eq!(MemoryCommand::parse("sync"), Ok(MemoryCommand::Sync));
+        assert_eq!(
+            MemoryCommand::parse("save Run cargo test\nbefore pushing"),
+            Ok(MemoryCommand::Save(
+                "Run cargo test\nbefore pushing".to_string()
+            ))
+        );
+        assert!(MemoryCommand::parse("push").is_err());
+    }
+}
@@ -1,6 +1,9 @@
+use std::borrow::Cow;
+
 use sha2::{Digest, Sha256};
 
-use crate::ui::components::{ChatMessage, MessageRole, TurnSummary};
+use crate::ui::components::{ChatMessage, MessageRole, TurnReference, TurnSummary, VerifyOutcome};
+use crate::util::url_fetch::FetchedPage;
 
 /// Maximum seed prompt size in bytes (500KB)
 pub const MAX_SEED_PROMPT_SIZE: usize = 500 * 1024;
@@ -59,7 +62,18 @@ fn truncate_to_char_boundary(s: &mut String, max_bytes: usize) {
     s.truncate(new_len);
 }
 
-/// Build a fork seed prompt from chat history
+/// Tool output longer than this is cut down to its head and tail in fork seeds
+const SEED_TOOL_OUTPUT_LIMIT: usize = 2 * 1024;
+
+/// Bytes kept from the end of a cut-down tool output
+const SEED_TOOL_OUTPUT_TAIL: usize = 512;
+
+/// Build a fork seed prompt from chat history.
+///
+/// When the transcript doesn't fit under [`MAX_SEED_PROMPT_SIZE`], older turns
+/// are condensed to their prompt and final answer first, then middle turns are
+/// dropped, so the most recent work survives intact. Only a single turn that
+/// is too large on its own gets cut off mid-text.
 pub fn build_fork_seed_prompt(messages: &[ChatMessage]) -> String {
     let mut prompt = String::new();
 
@@ -79,40 +93,253 @@ pub fn build_fork_seed_prompt(messages: &[ChatMessage]) -> String {
         .saturating_sub(prompt.len())
         .saturating_sub(SEED_CLOSING_INSTRUCTION.len());
 
-    let transcript_start = prompt.len();
+    let transcript = build_seed_transcript(messages, max_transcript_size);
+    if transcript.len() > max_transcript_size {
+        let max_without_suffix = max_transcript_size.saturating_sub(SEED_TRUNCATED_SUFFIX.len());
+        let mut transcript = transcript;
+        truncate_to_char_boundary(&mut transcript, max_without_suffix);
+        prompt.push_str(&transcript);
+        prompt.push_str(SEED_TRUNCATED_SUFFIX);
+        // SEED_TRUNCATED_SUFFIX already closes the tag, so just add final instruction
+        prompt.push_str("\n\n[END OF CONTEXT]\n\n");
+        prompt.push_str("IMPORTANT: The above was historical context from a previous session.\n");
+        prompt.push_str(
+            "You are starting a NEW forked session. Do NOT continue any tasks from the transcript.\n",
+        );
+        prompt.push_str(
+            "Acknowledge that you have received this context by replying ONLY with the single word: Ready",
+        );
+        return prompt;
+    }
+
+    prompt.push_str(&transcript);
+    // Add closing instruction for non-truncated case
+    prompt.push_str(SEED_CLOSING_INSTRUCTION);
+    prompt
+}
+
+/// How much of a turn goes into the seed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SeedTurnDetail {
+    Full,
+    /// User prompt, a one-line summary of the work, and the final answer
+    Condensed,
+    Omitted,
+}
+
+/// A user prompt and everything that followed it, rendered both ways
+struct SeedTurn {
+    full: String,
+    condensed: String,
+}
+
+/// Render the transcript, condensing and dropping turns until it fits
+/// `max_size`. The result can still exceed `max_size` when the latest turn
+/// alone is too large.
+fn build_seed_transcript(messages: &[ChatMessage], max_size: usize) -> String {
+    let turns: Vec<SeedTurn> = split_seed_turns(messages)
+        .into_iter()
+        .map(|turn| SeedTurn {
+            full: format_full_turn(turn),
+            condensed: format_condensed_turn(turn),
+        })
+        .collect();
+    let Some(last) = turns.len().checked_sub(1) else {
+        return String::new();
+    };
+
+    let mut detail = vec![SeedTurnDetail::Full; turns.len()];
+    let fits = |detail: &[SeedTurnDetail]| seed_turns_len(&turns, detail) <= max_size;
+
+    // Condense from the oldest turn, keeping the latest one whole
+    for idx in 0..last {
+        if fits(&detail) {
+            break;
+        }
+        detail[idx] = SeedTurnDetail::Condensed;
+    }
+    // Drop middle turns, keeping the first (usually the task) and the latest
+    for idx in 1..last {
+        if fits(&detail) {
+            break;
+        }
+        detail[idx] = SeedTurnDetail::Omitted;
+    }
+    if !fits(&detail) {
+        detail[last] = SeedTurnDetail::Condensed;
+    }
+    if last > 0 && !fits(&detail) {
+        detail[0] = SeedTurnDetail::Omitted;
+    }
+
+    render_seed_turns(&turns, &detail)
+}
+
+/// Turn texts and omission notes making up the transcript, in order
+fn seed_turn_parts<'a>(turns: &'a [SeedTurn], detail: &[SeedTurnDetail]) -> Vec<Cow<'a, str>> {
+    let mut parts = Vec::new();
+    let mut omitted = 0;
+    for (turn, detail) in turns.iter().zip(detail) {
+        let text = match detail {
+            SeedTurnDetail::Omitted => {
+                omitted += 1;
+                continue;
+            }
+            SeedTurnDetail::Full => &turn.full,
+            SeedTurnDetail::Condensed => &turn.condensed,
+        };
+        if omitted > 0 {
+            parts.push(Cow::Owned(omitted_turns_note(omitted)));
+            omitted = 0;
+        }
+        parts.push(Cow::Borrowed(text.as_str()));
+    }
+    if omitted > 0 {
+        parts.push(Cow::Owned(omitted_turns_note(omitted)));
+    }
+    parts
+}
+
+fn seed_turns_len(turns: &[SeedTurn], detail: &[SeedTurnDetail]) -> usize {
+    let parts = seed_turn_parts(turns, detail);
+    let separators = parts.len().saturating_sub(1) * 2;
+    parts.iter().map(|part| part.len()).sum::<usize>() + separators
+}
+
+fn render_seed_turns(turns: &[SeedTurn], detail: &[SeedTurnDetail]) -> String {
+    seed_turn_parts(turns, detail).join("\n\n")
+}
+
+fn omitted_turns_note(count: usize) -> String {
+    let noun = if count == 1 { "turn" } else { "turns" };
+    format!("[{count} {noun} omitted to fit the size limit]")
+}
 
+/// Group messages into turns, each starting at a user message
+fn split_seed_turns(messages: &[ChatMessage]) -> Vec<&[ChatMessage]> {
+    let mut turns = Vec::new();
+    let mut start = 0;
     for (idx, msg) in messages.iter().enumerate() {
-        if idx > 0 {
-            prompt.push_str("\n\n");
+        if msg.role == MessageRole::User && idx > start {
+            turns.push(&messages[start..idx]);
+            start = idx;
         }
-        prompt.push_str(&format_fork_message(msg));
-
-        // Check if transcript portion has exceeded the limit
-        let transcript_len = prompt.len() - transcript_start;
-        if transcript_len > max_transcript_size {
-            let max_without_suffix =
-                max_transcript_size.saturating_sub(SEED_TRUNCATED_SUFFIX.len());
-            // Truncate only the transcript portion
-            prompt.truncate(transcript_start + max_without_suffix);
-            truncate_to_char_boundary(&mut prompt, transcript_start + max_without_suffix);
-            prompt.push_str(SEED_TRUNCATED_SUFFIX);
-            // SEED_TRUNCATED_SUFFIX already closes the tag, so just add final instruction
-            prompt.push_str("\n\n[END OF CONTEXT]\n\n");
-            prompt
-                .push_str("IMPORTANT: The above was historical context from a previous session.\n");
-            prompt.push_str(
-                "You are starting a NEW forked session. Do NOT continue any tasks from the transcript.\n",
-            );
-            prompt.push_str(
-                "Acknowledge that you have received this context by replying ONLY with the single word: Ready",
-            );
-            return prompt;
+    }
+    if start < messages.len() {
+        turns.push(&messages[start..]);
+    }
+    turns
+}
+
+fn format_full_turn(turn: &[ChatMessage]) -> String {
+    turn.iter()
+        .map(|msg| {
+            let formatted = format_fork_message(msg);
+            if msg.role == MessageRole::Tool {
+                trim_seed_tool_output(formatted)
+            } else {
+                formatted
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// The turn's prompt, what happened in between, and its last answer
+fn format_condensed_turn(turn: &[ChatMessage]) -> String {
+    let prompt = turn.first().filter(|msg| msg.role == MessageRole::User);
+    let answer_idx = turn.iter().rposition(|msg| {
+        matches!(msg.role, MessageRole::Assistant | MessageRole::Plan) && !msg.content.is_empty()
+    });
+    let skip_first = usize::from(prompt.is_some());
+    let middle: Vec<&ChatMessage> = turn
+        .iter()
+        .enumerate()
+        .skip(skip_first)
+        .filter(|(idx, _)| Some(*idx) != answer_idx)
+        .map(|(_, msg)| msg)
+        .collect();
+
+    let mut parts = Vec::new();
+    if let Some(prompt) = prompt {
+        parts.push(format_fork_message(prompt));
+    }
+    if let Some(summary) = summarize_seed_messages(&middle) {
+        parts.push(format!("[role=summary]\n{summary}"));
+    }
+    if let Some(idx) = answer_idx {
+        parts.push(format_fork_message(&turn[idx]));
+    }
+    parts.join("\n\n")
+}
+
+/// One line describing condensed messages, e.g. tool calls and files changed
+fn summarize_seed_messages(messages: &[&ChatMessage]) -> Option<String> {
+    if messages.is_empty() {
+        return None;
+    }
+    let mut tools: Vec<(&str, usize)> = Vec::new();
+    let mut other = 0;
+    let mut summaries = Vec::new();
+    for msg in messages {
+        match msg.role {
+            MessageRole::Tool => {
+                let name = msg.tool_name.as_deref().unwrap_or("tool");
+                match tools.iter_mut().find(|(tool, _)| *tool == name) {
+                    Some((_, count)) => *count += 1,
+                    None => tools.push((name, 1)),
+                }
+            }
+            MessageRole::Summary => {
+                if let Some(summary) = &msg.summary {
+                    summaries.push(format_turn_summary_for_seed(summary));
+                }
+            }
+            _ => other += 1,
         }
     }
 
-    // Add closing instruction for non-truncated case
-    prompt.push_str(SEED_CLOSING_INSTRUCTION);
-    prompt
+    let mut parts = Vec::new();
+    if !tools.is_empty() {
+        let calls: usize = tools.iter().map(|(_, count)| count).sum();
+        let names = tools
+            .iter()
+            .map(|(name, count)| format!("{name} x{count}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let noun = if calls == 1 {
+            "tool call"
+        } else {
+            "tool calls"
+        };
+        parts.push(format!("{calls} {noun} omitted ({names})"));
+    }
+    if other > 0 {
+        let noun = if other == 1 { "message" } else { "messages" };
+        parts.push(format!("{other} intermediate {noun} omitted"));
+    }
+    parts.extend(summaries);
+    Some(parts.join("; "))
+}
+
+/// Keep the head and tail of a long tool output
+fn trim_seed_tool_output(mut formatted: String) -> String {
+    if formatted.len() <= SEED_TOOL_OUTPUT_LIMIT {
+        return formatted;
+    }
+    let mut tail_start = formatted.len() - SEED_TOOL_OUTPUT_TAIL;
+    while !formatted.is_char_boundary(tail_start) {
+        tail_start += 1;
+    }
+    let tail = formatted[tail_start..].to_string();
+    truncate_to_char_boundary(
+        &mut formatted,
+        SEED_TOOL_OUTPUT_LIMIT - SEED_TOOL_OUTPUT_TAIL,
+    );
+    let omitted = tail_start - formatted.len();
+    formatted.push_str(&format!("\n[... {omitted} bytes of output omitted ...]\n"));
+    formatted.push_str(&tail);
+    formatted
 }
 
 pub fn compute_seed_prompt_hash(seed_prompt: &str) -> String {
@@ -129,6 +356,104 @@ pub fn build_switch_prompt() -> &'static str {
     BUILD_SWITCH_PROMPT
 }
 
+/// Append the session's response-language directive to a prompt bound for the agent.
+/// The directive rides along with every prompt so it holds across resumes and agent switches.
+pub fn append_language_directive(prompt: &str, language: Option<&str>) -> String {
+    match language
+        .map(str::trim)
+        .filter(|language| !language.is_empty())
+    {
+        Some(language) => {
+            format!("{prompt}\n\n<system-reminder>Respond in {language}.</system-reminder>")
+        }
+        None => prompt.to_string(),
+    }
+}
+
+/// Managed response-style instructions a session can switch on instead of
+/// repeating the same directive in every prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponsePreset {
+    Concise,
+    Detailed,
+    CodeOnly,
+}
+
+impl ResponsePreset {
+    pub const ALL: [ResponsePreset; 3] = [
+        ResponsePreset::Concise,
+        ResponsePreset::Detailed,
+        ResponsePreset::CodeOnly,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ResponsePreset::Concise => "concise",
+            ResponsePreset::Detailed => "detailed",
+            ResponsePreset::CodeOnly => "code-only",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "concise" | "brief" => Some(ResponsePreset::Concise),
+            "detailed" | "verbose" => Some(ResponsePreset::Detailed),
+            "code-only" | "code_only" | "code" => Some(ResponsePreset::CodeOnly),
+            _ => None,
+        }
+    }
+
+    /// Instruction snippet sent to the agent
+    pub fn instruction(self) -> &'static str {
+        match self {
+            ResponsePreset::Concise => {
+                "Keep responses concise: lead with the answer, skip preamble and recaps, and prefer short bullet points over paragraphs."
+            }
+            ResponsePreset::Detailed => {
+                "Give detailed responses: explain your reasoning, call out trade-offs and alternatives you considered, and summarize what changed and why."
+            }
+            ResponsePreset::CodeOnly => {
+                "Respond with code only: no explanations or commentary outside code blocks unless the request cannot be answered with code."
+            }
+        }
+    }
+}
+
+/// Append the session's response-style preset to a prompt bound for the agent.
+pub fn append_preset_directive(prompt: &str, preset: Option<ResponsePreset>) -> String {
+    match preset {
+        Some(preset) => format!(
+            "{prompt}\n\n<system-reminder>{}</system-reminder>",
+            preset.instruction()
+        ),
+        None => prompt.to_string(),
+    }
+}
+
+/// Prepend pages attached with `/fetch` to a prompt bound for the agent, so
+/// agents without web access can answer from their text.
+pub fn prepend_fetched_context(prompt: &str, pages: &[FetchedPage]) -> String {
+    if pages.is_empty() {
+        return prompt.to_string();
+    }
+    let mut out = String::new();
+    for page in pages {
+        let title = page.title.as_deref().unwrap_or_default().replace('"', "'");
+        out.push_str(&format!(
+            "<context source=\"{}\" title=\"{title}\"{}>\n{}\n</context>\n\n",
+            page.url,
+            if page.truncated {
+                " truncated=\"true\""
+            } else {
+                ""
+            },
+            page.text
+        ));
+    }
+    out.push_str(prompt);
+    out
+}
+
 pub fn build_plan_mode_prompt_inline(plan_path: &str, exists: bool) -> String {
     let plan_line = if exists {
         format!(
@@ -198,8 +523,9 @@ NOTE: At any point in time through this workflow you should feel free to ask the
     )
 }
 
-fn format_fork_message(msg: &ChatMessage) -> String {
-    let role = match msg.role {
+/// Lowercase role name used in fork seeds and transcript exports
+pub fn message_role_name(role: MessageRole) -> &'static str {
+    match role {
         MessageRole::User => "user",
         MessageRole::Assistant => "assistant",
         MessageRole::Reasoning => "reasoning",
@@ -207,9 +533,12 @@ fn format_fork_message(msg: &ChatMessage) -> String {
         MessageRole::System => "system",
         MessageRole::Error => "error",
         MessageRole::Summary => "summary",
-    };
+        MessageRole::Plan => "plan",
+    }
+}
 
-    let mut header = format!("[role={}]", role);
+fn format_fork_message(msg: &ChatMessage) -> String {
+    let mut header = format!("[role={}]", message_role_name(msg.role));
 
     if msg.role == MessageRole::Tool {
         if let Some(name) = &msg.tool_name {
@@ -261,6 +590,23 @@ fn format_turn_summary_for_seed(summary: &TurnSummary) -> String {
             .join("; ");
         parts.push(format!("files=[{}]", files));
     }
+    if !summary.commands.is_empty() {
+        parts.push(format!("commands={}", summary.commands.len()));
+    }
+    match summary.verification() {
+        Some(VerifyOutcome::Passed) => parts.push("checks=passed".to_string()),
+        Some(VerifyOutcome::Failed) => parts.push("checks=failed".to_string()),
+        None => {}
+    }
+    if !summary.references.is_empty() {
+        let references = summary
+            .references
+            .iter()
+            .map(TurnReference::label)
+            .collect::<Vec<_>>()
+            .join("; ");
+        parts.push(format!("refs=[{}]", references));
+    }
     if parts.is_empty() {
         "summary".to_string()
     } else {
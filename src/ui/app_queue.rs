@@ -1,51 +1,84 @@
 use std::path::PathBuf;
 
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
+use ratatui::widgets::{Paragraph, Widget};
 use unicode_width::UnicodeWidthStr;
 
 use crate::data::QueuedMessage;
-use crate::ui::components::{bg_highlight, text_muted, text_primary};
+use crate::ui::components::{bg_highlight, text_muted, text_primary, DialogFrame};
 use crate::ui::events::InputMode;
 use crate::ui::session::AgentSession;
 
+/// Display settings for the inline queue panel shown above the input box
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueuePanel {
+    /// Lines the panel may take, summary line included
+    pub max_lines: usize,
+    /// Show only the one-line summary (ignored while editing the queue)
+    pub collapsed: bool,
+    /// Hide the inline panel entirely (the queue overlay is open)
+    pub hidden: bool,
+}
+
+impl QueuePanel {
+    pub fn new(max_lines: usize, collapsed: bool) -> Self {
+        Self {
+            max_lines: max_lines.max(2),
+            collapsed,
+            hidden: false,
+        }
+    }
+}
+
 pub fn build_queue_lines(
     session: &AgentSession,
     width: u16,
     input_mode: InputMode,
+    panel: QueuePanel,
 ) -> Option<Vec<Line<'static>>> {
-    if session.queued_messages.is_empty() {
+    if session.queued_messages.is_empty() || panel.hidden {
         return None;
     }
 
     let max_width = width.saturating_sub(2) as usize;
-    let selected = if input_mode == InputMode::QueueEditing {
+    let editing = input_mode == InputMode::QueueEditing;
+    let selected = if editing {
         session.queue_selection
     } else {
         None
     };
+    let summary = queue_summary(&session.queued_messages);
+    let summary_style = Style::default()
+        .fg(text_muted())
+        .add_modifier(Modifier::ITALIC);
 
-    let mut lines = Vec::new();
-    for (idx, msg) in session.queued_messages.iter().enumerate() {
-        let raw = msg.text.trim();
-        let mut preview = raw.lines().next().unwrap_or("").trim().to_string();
-        if preview.is_empty() {
-            preview = "<empty>".to_string();
-        }
-        if raw.contains('\n') {
-            preview.push_str(" ...");
-        }
-        let mut text = format!("{}: {}", msg.mode.label(), preview);
-        if !msg.images.is_empty() {
-            let count = msg.images.len();
-            let suffix = if count == 1 { "image" } else { "images" };
-            text.push_str(&format!(" [{} {}]", count, suffix));
-        }
+    if panel.collapsed && !editing {
+        let text = fit_queue_line(&format!("▸ {summary}"), max_width);
+        return Some(vec![Line::from(Span::styled(text, summary_style))]);
+    }
 
-        if max_width > 0 {
-            text = truncate_queue_line(&text, max_width);
-        }
+    let total = session.queued_messages.len();
+    let mut lines = Vec::new();
+    let range = if total <= panel.max_lines {
+        0..total
+    } else {
+        let window = panel.max_lines.saturating_sub(1).max(1);
+        let start = queue_window_start(total, window, selected);
+        let end = start + window;
+        let text = format!("▾ {summary} · {}–{} of {}", start + 1, end, total);
+        lines.push(Line::from(Span::styled(
+            fit_queue_line(&text, max_width),
+            summary_style,
+        )));
+        start..end
+    };
 
+    for idx in range {
+        let msg = &session.queued_messages[idx];
+        let text = fit_queue_line(&queue_message_preview(msg), max_width);
         let prefix = if selected == Some(idx) { "› " } else { "  " };
         let line_text = format!("{prefix}{text}");
         let style = if selected == Some(idx) {
@@ -64,6 +97,162 @@ pub fn build_queue_lines(
     Some(lines)
 }
 
+/// One-line description of the queue, e.g. "3 queued (2 images)"
+pub fn queue_summary(messages: &[QueuedMessage]) -> String {
+    let images: usize = messages.iter().map(|msg| msg.images.len()).sum();
+    let mut text = format!("{} queued", messages.len());
+    if images > 0 {
+        let suffix = if images == 1 { "image" } else { "images" };
+        text.push_str(&format!(" ({} {})", images, suffix));
+    }
+    text
+}
+
+/// First index of a `window`-sized slice of `len` items that keeps `selected` visible.
+///
+/// Without a selection the window sits at the front of the queue, which is
+/// where the next message to be sent lives.
+pub fn queue_window_start(len: usize, window: usize, selected: Option<usize>) -> usize {
+    if window == 0 || len <= window {
+        return 0;
+    }
+    let max_start = len - window;
+    match selected {
+        Some(idx) => idx.saturating_sub(window - 1).min(max_start),
+        None => 0,
+    }
+}
+
+fn queue_message_preview(msg: &QueuedMessage) -> String {
+    let raw = msg.text.trim();
+    let mut preview = raw.lines().next().unwrap_or("").trim().to_string();
+    if preview.is_empty() {
+        preview = "<empty>".to_string();
+    }
+    if raw.contains('\n') {
+        preview.push_str(" ...");
+    }
+    let mut text = format!("{}: {}", msg.mode.label(), preview);
+    if !msg.images.is_empty() {
+        let count = msg.images.len();
+        let suffix = if count == 1 { "image" } else { "images" };
+        text.push_str(&format!(" [{} {}]", count, suffix));
+    }
+    text
+}
+
+fn fit_queue_line(text: &str, max_width: usize) -> String {
+    if max_width > 0 {
+        truncate_queue_line(text, max_width)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Render the full-screen queue management overlay.
+///
+/// Unlike the inline panel, every message is shown with up to a few lines of
+/// its text, and the list scrolls to keep the selection in view.
+pub fn render_queue_overlay(area: Rect, buf: &mut Buffer, session: &AgentSession) {
+    const PREVIEW_LINES: usize = 3;
+
+    let title = format!("Queue · {}", queue_summary(&session.queued_messages));
+    let width = area.width.saturating_sub(8).clamp(40, 100);
+    let height = area.height.saturating_sub(4).max(8);
+    let frame = DialogFrame::new(&title, width, height).instructions(vec![
+        ("↑↓", "select"),
+        ("M-j/k", "move"),
+        ("Enter", "edit"),
+        ("Del", "delete"),
+        ("Esc", "close"),
+    ]);
+    let inner = frame.render(area, buf);
+    if inner.width == 0 || inner.height == 0 {
+        return;
+    }
+
+    if session.queued_messages.is_empty() {
+        Paragraph::new("Queue is empty")
+            .style(Style::default().fg(text_muted()))
+            .render(inner, buf);
+        return;
+    }
+
+    let text_width = inner.width.saturating_sub(4) as usize;
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let mut selected_range = 0..0;
+    for (idx, msg) in session.queued_messages.iter().enumerate() {
+        let is_selected = session.queue_selection == Some(idx);
+        let start = lines.len();
+
+        let mut header = format!("{}. {}", idx + 1, msg.mode.label());
+        if !msg.images.is_empty() {
+            let count = msg.images.len();
+            let suffix = if count == 1 { "image" } else { "images" };
+            header.push_str(&format!(" [{} {}]", count, suffix));
+        }
+        let marker = if is_selected { "› " } else { "  " };
+        let header_style = if is_selected {
+            Style::default()
+                .fg(text_primary())
+                .bg(bg_highlight())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(text_primary())
+        };
+        lines.push(Line::from(Span::styled(
+            format!("{marker}{header}"),
+            header_style,
+        )));
+
+        let body: Vec<&str> = msg.text.trim().lines().collect();
+        let body_style = if is_selected {
+            Style::default().fg(text_primary()).bg(bg_highlight())
+        } else {
+            Style::default().fg(text_muted())
+        };
+        let shown = body.len().min(PREVIEW_LINES);
+        for (line_idx, line) in body.iter().take(shown).enumerate() {
+            let mut text = line.trim_end().to_string();
+            if line_idx + 1 == shown && body.len() > shown {
+                text.push_str(" ...");
+            }
+            lines.push(Line::from(Span::styled(
+                format!("    {}", fit_queue_line(&text, text_width)),
+                body_style,
+            )));
+        }
+        if body.is_empty() {
+            lines.push(Line::from(Span::styled("    <empty>", body_style)));
+        }
+
+        if is_selected {
+            selected_range = start..lines.len();
+        }
+        lines.push(Line::from(""));
+    }
+
+    let visible = inner.height as usize;
+    let mut offset = 0usize;
+    if selected_range.end > visible {
+        offset = selected_range.end - visible;
+    }
+    offset = offset.min(selected_range.start);
+
+    let visible_lines: Vec<Line<'static>> = lines.into_iter().skip(offset).take(visible).collect();
+    Paragraph::new(visible_lines).render(inner, buf);
+}
+
+/// Number of chat lines the inline queue panel occupies
+pub fn queue_lines_len(
+    session: &AgentSession,
+    width: u16,
+    input_mode: InputMode,
+    panel: QueuePanel,
+) -> usize {
+    build_queue_lines(session, width, input_mode, panel).map_or(0, |lines| lines.len())
+}
+
 pub fn clamp_queue_selection(session: &mut AgentSession) {
     if session.queued_messages.is_empty() {
         session.queue_selection = None;
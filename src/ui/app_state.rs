@@ -2,17 +2,23 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Utc};
 use ratatui::layout::Rect;
 
 use crate::agent::{AgentMode, AgentType};
+use crate::ui::clipboard_history::ClipboardHistory;
 use crate::ui::components::{
     AddRepoDialogState, AgentSelectorState, BaseDirDialogState, CommandPaletteState,
     ConfirmationDialogState, ErrorDialogState, HelpDialogState, KnightRiderSpinner,
     LogoShineAnimation, MissingToolDialogState, ModelSelectorState, ProjectPickerState,
-    SessionImportPickerState, SidebarData, SidebarState, SlashMenuState, ThemePickerState,
+    RenameTabDialogState, SessionImportPickerState, SidebarData, SidebarState, SlashMenuState,
+    ThemePickerState,
 };
 use crate::ui::events::{InputMode, ViewMode};
+use crate::ui::frame_diagnostics::FrameDiagnostics;
 use crate::ui::tab_manager::TabManager;
+use crate::util::voice::VoiceRecorder;
+use crate::util::InstanceInfo;
 use uuid::Uuid;
 
 /// Performance metrics for monitoring frame timing.
@@ -153,6 +159,10 @@ impl PerformanceMetrics {
 /// UI state snapshot for the application.
 pub struct AppState {
     pub should_quit: bool,
+    /// Quit once no tab has a running turn (chosen in the quit dialog)
+    pub quit_when_idle: bool,
+    /// Background network calls are failing; shown in the status bar
+    pub offline: bool,
     /// Whether the UI needs to be redrawn on the next frame
     pub need_redraw: bool,
     pub tab_manager: TabManager,
@@ -167,6 +177,7 @@ pub struct AppState {
     pub theme_picker_state: ThemePickerState,
     pub agent_selector_state: AgentSelectorState,
     pub base_dir_dialog_state: BaseDirDialogState,
+    pub rename_tab_dialog_state: RenameTabDialogState,
     pub project_picker_state: ProjectPickerState,
     pub session_import_state: SessionImportPickerState,
     pub confirmation_dialog_state: ConfirmationDialogState,
@@ -175,6 +186,8 @@ pub struct AppState {
     pub missing_tool_dialog_state: MissingToolDialogState,
     pub command_palette_state: CommandPaletteState,
     pub slash_menu_state: SlashMenuState,
+    /// Recent copies, shown by `show_clipboard_history`
+    pub clipboard_history: ClipboardHistory,
     pub command_buffer: String,
     pub sidebar_area: Option<Rect>,
     pub tab_bar_area: Option<Rect>,
@@ -187,6 +200,14 @@ pub struct AppState {
     pub raw_events_area: Option<Rect>,
     pub metrics: PerformanceMetrics,
     pub show_metrics: bool,
+    /// Per-section draw timings, shown as an overlay when enabled
+    pub frame_diagnostics: FrameDiagnostics,
+    /// Zen mode: hide tab bar, status bar, and footer around the chat
+    pub zen_mode: bool,
+    /// Inline queue panel collapsed to its one-line summary
+    pub queue_collapsed: bool,
+    /// Queue editor shown as a full overlay instead of the inline panel
+    pub queue_overlay: bool,
     pub spinner_frame: usize,
     pub last_sidebar_click: Option<(Instant, usize)>,
     pub last_raw_events_click: Option<(Instant, usize)>,
@@ -200,12 +221,18 @@ pub struct AppState {
     pub footer_message_expires_at: Option<Instant>,
     /// Last Ctrl+C press time for double-press detection
     pub last_ctrl_c_press: Option<Instant>,
+    /// Last liveness check of dev server previews
+    pub last_preview_prune: Option<Instant>,
+    /// Last check of chat views against their memory limits
+    pub last_chat_memory_check: Option<Instant>,
     /// Last Esc press time for double-press detection
     pub last_esc_press: Option<Instant>,
     /// Logo shine animation for splash screen
     pub logo_shine: LogoShineAnimation,
     /// Track if splash screen was visible (for resetting shine animation)
     pub was_splash_visible: bool,
+    /// Recently active saved sessions listed on the splash screen
+    pub recent_sessions: Vec<RecentSession>,
     /// Pending fork request data (set during confirmation)
     pub pending_fork_request: Option<PendingForkRequest>,
     /// Workspace IDs with in-flight sidebar operations
@@ -220,6 +247,33 @@ pub struct AppState {
     pub busy_footer_message: Option<String>,
     /// Pending branch updates captured while workspaces are busy
     pub pending_branch_updates: HashMap<Uuid, Option<String>>,
+    /// Voice prompt currently being recorded
+    pub voice_recording: Option<VoiceRecording>,
+    /// Whether completed responses are spoken (only when `[read_aloud]` is configured)
+    pub read_aloud_enabled: bool,
+    /// Response currently being spoken; aborting it kills the TTS process
+    pub read_aloud_task: Option<tokio::task::JoinHandle<()>>,
+    /// Process that owns the data directory when this TUI only observes it
+    pub observing: Option<InstanceInfo>,
+    /// Whether the terminal window has focus (assumed until it reports otherwise)
+    pub terminal_focused: bool,
+}
+
+/// Microphone recording bound to the session whose input box receives the text
+pub struct VoiceRecording {
+    pub session_id: Uuid,
+    pub recorder: VoiceRecorder,
+}
+
+/// A saved session offered for resuming on the splash screen
+#[derive(Debug, Clone)]
+pub struct RecentSession {
+    pub id: Uuid,
+    pub title: String,
+    pub agent_type: AgentType,
+    /// Workspace name, or the project directory of sessions without one
+    pub location: Option<String>,
+    pub last_active: DateTime<Utc>,
 }
 
 /// Pending fork request data captured before workspace creation
@@ -232,6 +286,7 @@ pub struct PendingForkRequest {
     pub parent_workspace_id: Uuid,
     /// Uses Arc to avoid cloning large seed prompts during struct clones
     pub seed_prompt: Arc<str>,
+    pub seed_prompt_hash: String,
     pub token_estimate: i64,
     pub context_window: i64,
     pub fork_seed_id: Option<Uuid>,
@@ -247,6 +302,7 @@ impl std::fmt::Debug for PendingForkRequest {
             .field("parent_session_id", &self.parent_session_id)
             .field("parent_workspace_id", &self.parent_workspace_id)
             .field("seed_prompt_len", &self.seed_prompt.len())
+            .field("seed_prompt_hash", &self.seed_prompt_hash)
             .field("token_estimate", &self.token_estimate)
             .field("context_window", &self.context_window)
             .field("fork_seed_id", &self.fork_seed_id)
@@ -275,6 +331,8 @@ impl AppState {
     pub fn new(max_tabs: usize) -> Self {
         Self {
             should_quit: false,
+            quit_when_idle: false,
+            offline: false,
             need_redraw: true,
             tab_manager: TabManager::new(max_tabs),
             input_mode: InputMode::Normal,
@@ -288,6 +346,7 @@ impl AppState {
             theme_picker_state: ThemePickerState::default(),
             agent_selector_state: AgentSelectorState::new(),
             base_dir_dialog_state: BaseDirDialogState::new(),
+            rename_tab_dialog_state: RenameTabDialogState::new(),
             project_picker_state: ProjectPickerState::new(),
             session_import_state: SessionImportPickerState::new(),
             confirmation_dialog_state: ConfirmationDialogState::new(),
@@ -296,6 +355,7 @@ impl AppState {
             missing_tool_dialog_state: MissingToolDialogState::default(),
             command_palette_state: CommandPaletteState::new(),
             slash_menu_state: SlashMenuState::new(),
+            clipboard_history: ClipboardHistory::default(),
             command_buffer: String::new(),
             sidebar_area: None,
             tab_bar_area: None,
@@ -308,6 +368,10 @@ impl AppState {
             raw_events_area: None,
             metrics: PerformanceMetrics::new(),
             show_metrics: false,
+            frame_diagnostics: FrameDiagnostics::default(),
+            zen_mode: false,
+            queue_collapsed: false,
+            queue_overlay: false,
             spinner_frame: 0,
             last_sidebar_click: None,
             last_raw_events_click: None,
@@ -317,9 +381,12 @@ impl AppState {
             footer_message: None,
             footer_message_expires_at: None,
             last_ctrl_c_press: None,
+            last_preview_prune: None,
+            last_chat_memory_check: None,
             last_esc_press: None,
             logo_shine: LogoShineAnimation::new(),
             was_splash_visible: true, // Start on splash screen
+            recent_sessions: Vec::new(),
             pending_fork_request: None,
             busy_workspaces: HashSet::new(),
             busy_repos: HashSet::new(),
@@ -327,12 +394,18 @@ impl AppState {
             busy_footer_message_active: false,
             busy_footer_message: None,
             pending_branch_updates: HashMap::new(),
+            voice_recording: None,
+            read_aloud_enabled: true,
+            read_aloud_task: None,
+            observing: None,
+            terminal_focused: true,
         }
     }
 
     pub fn close_overlays(&mut self) {
         self.add_repo_dialog_state.hide();
         self.base_dir_dialog_state.hide();
+        self.rename_tab_dialog_state.hide();
         self.project_picker_state.hide();
         self.session_import_state.hide();
         self.model_selector_state.hide();
@@ -348,6 +421,7 @@ impl AppState {
 
     pub fn has_active_overlay(&self) -> bool {
         self.base_dir_dialog_state.is_visible()
+            || self.rename_tab_dialog_state.is_visible()
             || self.project_picker_state.is_visible()
             || self.add_repo_dialog_state.is_visible()
             || self.model_selector_state.is_visible()
@@ -360,6 +434,7 @@ impl AppState {
             || self.session_import_state.is_visible()
             || self.command_palette_state.is_visible()
             || self.slash_menu_state.is_visible()
+            || self.input_mode == InputMode::ClipboardHistory
     }
 
     /// Start footer spinner with optional message
@@ -0,0 +1,56 @@
+//! Copy text to the system clipboard, falling back to the terminal's OSC 52
+//! escape sequence when there is no system clipboard (e.g. over SSH).
+
+use std::io::{self, Write};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+/// How copied text reached the clipboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyMethod {
+    /// The system clipboard, via `arboard`
+    System,
+    /// Asked the terminal to set its clipboard (OSC 52)
+    Terminal,
+}
+
+/// Put `text` on the clipboard. Errors only when the OSC 52 fallback
+/// couldn't be written either.
+pub fn copy_text(text: &str) -> io::Result<CopyMethod> {
+    match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text)) {
+        Ok(()) => return Ok(CopyMethod::System),
+        Err(err) => {
+            tracing::debug!(error = %err, "System clipboard unavailable; using OSC 52");
+        }
+    }
+    let in_tmux = std::env::var_os("TMUX").is_some();
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(osc52_sequence(text, in_tmux).as_bytes())?;
+    stdout.flush()?;
+    Ok(CopyMethod::Terminal)
+}
+
+/// OSC 52 "set clipboard" sequence for `text`, wrapped in a tmux passthrough
+/// when running inside tmux
+fn osc52_sequence(text: &str, in_tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    if in_tmux {
+        format!("\x1bPtmux;\x1b{}\x1b\\", sequence)
+    } else {
+        sequence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+}
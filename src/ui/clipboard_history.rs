@@ -0,0 +1,203 @@
+//! Recent clipboard copies, so an earlier snippet can be copied again or
+//! pasted into the input after something else overwrote the clipboard.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local, Utc};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Paragraph, Widget};
+
+use crate::ui::app_queue::truncate_queue_line;
+use crate::ui::components::{bg_highlight, text_muted, text_primary, DialogFrame};
+
+/// Copies kept in the history
+pub const CLIPBOARD_HISTORY_LIMIT: usize = 20;
+
+/// One copied snippet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardEntry {
+    pub text: String,
+    /// What was copied, e.g. "message" or "workspace path"
+    pub source: &'static str,
+    pub copied_at: DateTime<Utc>,
+}
+
+/// Copies made in this run, newest first
+#[derive(Debug, Default)]
+pub struct ClipboardHistory {
+    entries: VecDeque<ClipboardEntry>,
+    /// Highlighted entry while the overlay is open
+    pub selected: usize,
+}
+
+impl ClipboardHistory {
+    /// Record a copy; copying the same text again moves it to the top
+    pub fn push(&mut self, text: String, source: &'static str) {
+        if let Some(idx) = self.entries.iter().position(|entry| entry.text == text) {
+            self.entries.remove(idx);
+        }
+        self.entries.push_front(ClipboardEntry {
+            text,
+            source,
+            copied_at: Utc::now(),
+        });
+        self.entries.truncate(CLIPBOARD_HISTORY_LIMIT);
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &ClipboardEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn selected_entry(&self) -> Option<&ClipboardEntry> {
+        self.entries.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+pub fn render_clipboard_history_overlay(area: Rect, buf: &mut Buffer, history: &ClipboardHistory) {
+    const PREVIEW_LINES: usize = 2;
+
+    let title = format!("Clipboard History · {}", history.len());
+    let width = area.width.saturating_sub(8).clamp(40, 100);
+    let height = area.height.saturating_sub(4).max(8);
+    let frame = DialogFrame::new(&title, width, height).instructions(vec![
+        ("↑↓", "select"),
+        ("Enter", "copy"),
+        ("p", "paste into input"),
+        ("Esc", "close"),
+    ]);
+    let inner = frame.render(area, buf);
+    if inner.width == 0 || inner.height == 0 {
+        return;
+    }
+
+    if history.is_empty() {
+        Paragraph::new("Nothing copied yet")
+            .style(Style::default().fg(text_muted()))
+            .render(inner, buf);
+        return;
+    }
+
+    let text_width = inner.width.saturating_sub(4) as usize;
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let mut selected_range = 0..0;
+    for (idx, entry) in history.entries().enumerate() {
+        let is_selected = history.selected == idx;
+        let start = lines.len();
+
+        let marker = if is_selected { "› " } else { "  " };
+        let header_style = if is_selected {
+            Style::default()
+                .fg(text_primary())
+                .bg(bg_highlight())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(text_primary())
+        };
+        let header = format!(
+            "{marker}{} · {}",
+            entry.source,
+            entry.copied_at.with_timezone(&Local).format("%H:%M:%S")
+        );
+        lines.push(Line::from(Span::styled(header, header_style)));
+
+        let body_style = if is_selected {
+            Style::default().fg(text_primary()).bg(bg_highlight())
+        } else {
+            Style::default().fg(text_muted())
+        };
+        let body: Vec<&str> = entry
+            .text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        let shown = body.len().min(PREVIEW_LINES);
+        for (line_idx, line) in body.iter().take(shown).enumerate() {
+            let mut text = line.trim_end().to_string();
+            if line_idx + 1 == shown && body.len() > shown {
+                text.push_str(" ...");
+            }
+            lines.push(Line::from(Span::styled(
+                format!("    {}", truncate_queue_line(&text, text_width)),
+                body_style,
+            )));
+        }
+        if body.is_empty() {
+            lines.push(Line::from(Span::styled("    <whitespace>", body_style)));
+        }
+
+        if is_selected {
+            selected_range = start..lines.len();
+        }
+        lines.push(Line::from(""));
+    }
+
+    let visible = inner.height as usize;
+    let mut offset = 0usize;
+    if selected_range.end > visible {
+        offset = selected_range.end - visible;
+    }
+    offset = offset.min(selected_range.start);
+
+    let visible_lines: Vec<Line<'static>> = lines.into_iter().skip(offset).take(visible).collect();
+    Paragraph::new(visible_lines).render(inner, buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_keeps_newest_unique_copies() {
+        let mut history = ClipboardHistory::default();
+        for idx in 0..CLIPBOARD_HISTORY_LIMIT + 5 {
+            history.push(format!("copy {idx}"), "message");
+        }
+        assert_eq!(history.len(), CLIPBOARD_HISTORY_LIMIT);
+        assert_eq!(
+            history.selected_entry().map(|entry| entry.text.as_str()),
+            Some("copy 24")
+        );
+
+        history.push("copy 10".to_string(), "selection");
+        let texts: Vec<_> = history.entries().map(|entry| entry.text.as_str()).collect();
+        assert_eq!(texts[0], "copy 10");
+        assert_eq!(texts.iter().filter(|text| **text == "copy 10").count(), 1);
+        assert_eq!(history.selected_entry().unwrap().source, "selection");
+    }
+
+    #[test]
+    fn test_selection_stays_in_bounds() {
+        let mut history = ClipboardHistory::default();
+        history.select_next();
+        assert_eq!(history.selected, 0);
+        history.push("a".to_string(), "message");
+        history.push("b".to_string(), "message");
+        history.select_next();
+        history.select_next();
+        assert_eq!(history.selected, 1);
+        history.select_prev();
+        history.select_prev();
+        assert_eq!(history.selected, 0);
+    }
+}
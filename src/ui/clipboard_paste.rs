@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[cfg(target_os = "macos")]
 use std::process::Command;
@@ -165,6 +165,71 @@ pub fn normalize_pasted_path(pasted: &str) -> Option<PathBuf> {
     Some(PathBuf::from(percent_decode(path)))
 }
 
+/// Paths a terminal inserted for files dropped onto it. A drop arrives as a
+/// paste of the file's path, quoted, backslash-escaped, or as a `file://`
+/// URL, with several files separated by spaces. Returns `None` unless every
+/// path is absolute and exists, so ordinary pasted text is left alone.
+pub fn dropped_paths(pasted: &str) -> Option<Vec<PathBuf>> {
+    let pasted = pasted.trim();
+    if pasted.is_empty() || pasted.contains('\n') {
+        return None;
+    }
+    let is_dropped = |path: &PathBuf| path.is_absolute() && path.exists();
+
+    // Some terminals paste a single path with its spaces unescaped
+    if let Some(path) = normalize_pasted_path(pasted).filter(is_dropped) {
+        return Some(vec![path]);
+    }
+    let paths = split_dropped_words(pasted)
+        .iter()
+        .map(|word| normalize_pasted_path(word).filter(is_dropped))
+        .collect::<Option<Vec<_>>>()?;
+    (!paths.is_empty()).then_some(paths)
+}
+
+/// Whether a dropped file should be attached as an image rather than mentioned
+pub fn is_image_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            IMAGE_EXTENSIONS
+                .iter()
+                .any(|image| ext.eq_ignore_ascii_case(image))
+        })
+}
+
+const IMAGE_EXTENSIONS: [&str; 6] = ["png", "jpg", "jpeg", "gif", "webp", "bmp"];
+
+/// Split on unquoted whitespace, honouring quotes and (outside Windows,
+/// where it separates paths) backslash escapes
+fn split_dropped_words(input: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', _) if !cfg!(windows) && quote != Some('\'') => {
+                if let Some(escaped) = chars.next() {
+                    word.push(escaped);
+                }
+            }
+            (c, Some(open)) if c == open => quote = None,
+            ('"' | '\'', None) => quote = Some(c),
+            (c, None) if c.is_whitespace() => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            (c, _) => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
 fn percent_decode(input: &str) -> String {
     let bytes = input.as_bytes();
     let mut decoded_bytes = Vec::with_capacity(bytes.len());
@@ -3,13 +3,15 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Style},
+    style::Style,
     symbols::border,
     widgets::{Block, Borders, Paragraph, Widget},
 };
 use std::path::PathBuf;
 
-use super::{DialogFrame, PathInputState, StatusLine};
+use super::{
+    accent_error, accent_success, text_muted, text_primary, DialogFrame, PathInputState, StatusLine,
+};
 
 /// State for the add repository dialog
 #[derive(Debug, Clone)]
@@ -180,17 +182,17 @@ impl AddRepoDialog {
         .split(inner);
 
         // Render label
-        let label =
-            Paragraph::new("Enter local repository path:").style(Style::default().fg(Color::White));
+        let label = Paragraph::new("Enter local repository path:")
+            .style(Style::default().fg(text_primary()));
         label.render(chunks[0], buf);
 
         // Render input field
         let input_style = if state.is_valid() {
-            Style::default().fg(Color::Green)
+            Style::default().fg(accent_success())
         } else if state.error().is_some() {
-            Style::default().fg(Color::Red)
+            Style::default().fg(accent_error())
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(text_primary())
         };
 
         let input_block = Block::default()
@@ -205,9 +207,9 @@ impl AddRepoDialog {
         state.path.text.render_with_placeholder(
             input_inner,
             buf,
-            Style::default().fg(Color::White),
+            Style::default().fg(text_primary()),
             "~/path/to/repo",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(text_muted()),
         );
 
         // Render status/error using StatusLine component
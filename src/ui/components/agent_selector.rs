@@ -3,7 +3,7 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Paragraph, Widget},
 };
@@ -12,8 +12,8 @@ use crate::agent::AgentType;
 use crate::util::{Tool, ToolAvailability};
 
 use super::{
-    dialog_bg, ensure_contrast_bg, ensure_contrast_fg, selected_bg, text_muted, text_primary,
-    DialogFrame,
+    accent_primary, dialog_bg, ensure_contrast_bg, ensure_contrast_fg, selected_bg, text_muted,
+    text_primary, DialogFrame,
 };
 
 /// State for the agent selector dialog
@@ -71,7 +71,9 @@ impl AgentSelectorState {
     }
 
     /// Create a new agent selector with only available agents
-    pub fn with_available_agents(tools: &ToolAvailability) -> Self {
+    ///
+    /// `local_model` adds the local model agent (set when `[local_model]` is configured).
+    pub fn with_available_agents(tools: &ToolAvailability, local_model: bool) -> Self {
         let mut agents = Vec::new();
 
         if tools.is_available(Tool::Claude) {
@@ -106,6 +108,14 @@ impl AgentSelectorState {
             });
         }
 
+        if local_model {
+            agents.push(AgentOption {
+                agent_type: AgentType::Local,
+                name: "Local model",
+                description: "Ollama / LM Studio via OpenAI-compatible API",
+            });
+        }
+
         // If no agents available (shouldn't happen if startup validation passed),
         // fall back to showing all options
         if agents.is_empty() {
@@ -120,7 +130,7 @@ impl AgentSelectorState {
     }
 
     /// Update the available agents list based on tool availability
-    pub fn update_available_agents(&mut self, tools: &ToolAvailability) {
+    pub fn update_available_agents(&mut self, tools: &ToolAvailability, local_model: bool) {
         let mut agents = Vec::new();
 
         if tools.is_available(Tool::Claude) {
@@ -155,6 +165,14 @@ impl AgentSelectorState {
             });
         }
 
+        if local_model {
+            agents.push(AgentOption {
+                agent_type: AgentType::Local,
+                name: "Local model",
+                description: "Ollama / LM Studio via OpenAI-compatible API",
+            });
+        }
+
         // Only update if we have at least one agent
         if !agents.is_empty() {
             self.agents = agents;
@@ -231,7 +249,7 @@ impl AgentSelector {
         }
 
         // Render dialog frame (instructions on bottom border)
-        let frame = DialogFrame::new("Select Agent", 44, 12).instructions(vec![
+        let frame = DialogFrame::new("Select Agent", 48, 15).instructions(vec![
             ("↑↓", "select"),
             ("Enter", "confirm"),
             ("Esc", "cancel"),
@@ -246,6 +264,7 @@ impl AgentSelector {
             Constraint::Length(2), // Codex option
             Constraint::Length(2), // Gemini option
             Constraint::Length(2), // OpenCode option
+            Constraint::Length(2), // Local model option
         ])
         .split(inner);
 
@@ -258,7 +277,7 @@ impl AgentSelector {
         let selected_fg = ensure_contrast_fg(text_primary(), selected_bg, 4.5);
         for (i, agent) in state.agents.iter().enumerate() {
             let chunk_idx = i + 2; // Skip header and spacing
-            if chunk_idx >= chunks.len() - 1 {
+            if chunk_idx >= chunks.len() {
                 break;
             }
 
@@ -268,7 +287,7 @@ impl AgentSelector {
             let name_line = Line::from(vec![
                 Span::styled(
                     if is_selected { " ▶ " } else { "   " },
-                    Style::default().fg(Color::Cyan),
+                    Style::default().fg(accent_primary()),
                 ),
                 Span::styled(
                     agent.name,
@@ -3,13 +3,15 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Style},
+    style::Style,
     symbols::border,
     widgets::{Block, Borders, Paragraph, Widget},
 };
 use std::path::PathBuf;
 
-use super::{DialogFrame, PathInputState, StatusLine};
+use super::{
+    accent_error, accent_success, text_muted, text_primary, DialogFrame, PathInputState, StatusLine,
+};
 
 /// State for the base directory dialog
 #[derive(Debug, Clone)]
@@ -180,16 +182,16 @@ impl BaseDirDialog {
 
         // Render label
         let label = Paragraph::new("Where do you keep your projects?")
-            .style(Style::default().fg(Color::White));
+            .style(Style::default().fg(text_primary()));
         label.render(chunks[0], buf);
 
         // Render input field with border
         let input_style = if state.is_valid() {
-            Style::default().fg(Color::Green)
+            Style::default().fg(accent_success())
         } else if state.error().is_some() {
-            Style::default().fg(Color::Red)
+            Style::default().fg(accent_error())
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(text_primary())
         };
 
         let input_block = Block::default()
@@ -204,7 +206,7 @@ impl BaseDirDialog {
         state
             .path
             .text
-            .render(input_inner, buf, Style::default().fg(Color::White));
+            .render(input_inner, buf, Style::default().fg(text_primary()));
 
         // Render status/error
         let status = StatusLine::from_result(state.error(), state.is_valid(), "Directory found");
@@ -212,7 +214,7 @@ impl BaseDirDialog {
 
         // Help text
         let help = Paragraph::new("This directory will be scanned for git projects.")
-            .style(Style::default().fg(Color::DarkGray));
+            .style(Style::default().fg(text_muted()));
         help.render(chunks[4], buf);
     }
 }
@@ -1,9 +1,12 @@
 //! Chat message types and helpers.
 
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
 use super::TurnSummary;
 
 /// Role of a chat message
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum MessageRole {
     User,
     Assistant,
@@ -12,10 +15,12 @@ pub enum MessageRole {
     System,
     Error,
     Summary,
+    /// Plan proposed while in Plan mode (kept after the approval prompt closes)
+    Plan,
 }
 
 /// A single chat message
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
     pub role: MessageRole,
     pub content: String,
@@ -30,6 +35,16 @@ pub struct ChatMessage {
     pub exit_code: Option<i32>,
     /// Cached file size for Read tool on images (avoids fs lookup on session restore)
     pub file_size: Option<u64>,
+    /// Agent tool call ID (for matching completions to the right tool message)
+    pub tool_id: Option<String>,
+    /// Task tool call of the subagent that produced this message (None = main agent)
+    pub parent_tool_id: Option<String>,
+    /// When the message was added to the chat (None for restored history)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Already handed to the persistence queue (or restored from storage)
+    #[serde(skip)]
+    pub persisted: bool,
 }
 
 impl ChatMessage {
@@ -44,6 +59,10 @@ impl ChatMessage {
             is_collapsed: false,
             exit_code: None,
             file_size: None,
+            tool_id: None,
+            parent_tool_id: None,
+            timestamp: None,
+            persisted: false,
         }
     }
 
@@ -58,6 +77,10 @@ impl ChatMessage {
             is_collapsed: false,
             exit_code: None,
             file_size: None,
+            tool_id: None,
+            parent_tool_id: None,
+            timestamp: None,
+            persisted: false,
         }
     }
 
@@ -72,6 +95,28 @@ impl ChatMessage {
             is_collapsed: false,
             exit_code: None,
             file_size: None,
+            tool_id: None,
+            parent_tool_id: None,
+            timestamp: None,
+            persisted: false,
+        }
+    }
+
+    pub fn plan(content: impl Into<String>) -> Self {
+        Self {
+            role: MessageRole::Plan,
+            content: content.into(),
+            tool_name: None,
+            tool_args: None,
+            is_streaming: false,
+            summary: None,
+            is_collapsed: false,
+            exit_code: None,
+            file_size: None,
+            tool_id: None,
+            parent_tool_id: None,
+            timestamp: None,
+            persisted: false,
         }
     }
 
@@ -90,6 +135,10 @@ impl ChatMessage {
             is_collapsed: false, // Default to expanded
             exit_code: None,
             file_size: None,
+            tool_id: None,
+            parent_tool_id: None,
+            timestamp: None,
+            persisted: false,
         }
     }
 
@@ -110,6 +159,10 @@ impl ChatMessage {
             is_collapsed: false,
             exit_code,
             file_size: None,
+            tool_id: None,
+            parent_tool_id: None,
+            timestamp: None,
+            persisted: false,
         }
     }
 
@@ -124,6 +177,10 @@ impl ChatMessage {
             is_collapsed: false,
             exit_code: None,
             file_size: None,
+            tool_id: None,
+            parent_tool_id: None,
+            timestamp: None,
+            persisted: false,
         }
     }
 
@@ -138,6 +195,10 @@ impl ChatMessage {
             is_collapsed: false,
             exit_code: None,
             file_size: None,
+            tool_id: None,
+            parent_tool_id: None,
+            timestamp: None,
+            persisted: false,
         }
     }
 
@@ -160,6 +221,10 @@ impl ChatMessage {
             is_collapsed: false,
             exit_code: None,
             file_size: None,
+            tool_id: None,
+            parent_tool_id: None,
+            timestamp: None,
+            persisted: false,
         }
     }
 
@@ -174,12 +239,28 @@ impl ChatMessage {
             is_collapsed: false,
             exit_code: None,
             file_size: None,
+            tool_id: None,
+            parent_tool_id: None,
+            timestamp: None,
+            persisted: false,
         }
     }
 
-    /// Toggle collapsed state for tool messages
+    /// Attach the agent's tool call ID
+    pub fn with_tool_id(mut self, tool_id: impl Into<String>) -> Self {
+        self.tool_id = Some(tool_id.into());
+        self
+    }
+
+    /// Mark this message as produced by the subagent of the given Task call
+    pub fn with_parent_tool_id(mut self, parent_tool_id: impl Into<String>) -> Self {
+        self.parent_tool_id = Some(parent_tool_id.into());
+        self
+    }
+
+    /// Toggle collapsed state for tool and reasoning messages
     pub fn toggle_collapsed(&mut self) {
-        if self.role == MessageRole::Tool {
+        if matches!(self.role, MessageRole::Tool | MessageRole::Reasoning) {
             self.is_collapsed = !self.is_collapsed;
         }
     }
@@ -1,4 +1,5 @@
 use ansi_to_tui::IntoText;
+use chrono::Utc;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -7,18 +8,39 @@ use ratatui::{
     widgets::{Paragraph, Widget},
 };
 use std::borrow::Cow;
+use std::collections::HashSet;
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use super::{
-    render_minimal_scrollbar,
+    diff_view::{is_diff_tool, is_unified_diff, unified_stats, DiffLineKind, ToolDiff},
+    render_minimal_scrollbar, text_muted, text_primary, text_secondary,
     theme::{
-        accent_error, accent_primary, accent_success, bg_base, bg_highlight, diff_add, diff_remove,
-        markdown_code_bg, theme_revision, tool_block_bg, tool_command, tool_comment, tool_output,
+        accent_error, accent_primary, accent_success, accent_warning, bg_base, bg_highlight,
+        diff_add, diff_remove, markdown_code_bg, theme_revision, tool_block_bg, tool_command,
+        tool_comment, tool_output,
     },
     ChatMessage, MarkdownRenderer, MessageRole, ScrollbarMetrics, TurnSummary,
 };
+use crate::config::ChatTimestamps;
 
 mod chat_view_cache;
+mod chat_view_memory;
+mod chat_view_message_select;
+mod chat_view_persistence;
+mod chat_view_timestamps;
+
+pub use self::chat_view_memory::ChatMemoryLimits;
+
+/// Guide prefixed to every line of a subagent's messages
+const SUBAGENT_INDENT: &str = "  │ ";
+
+/// Tool output longer than this starts collapsed
+const TOOL_COLLAPSE_LINES: usize = 12;
+
+/// Whether a tool message with `content` starts collapsed
+fn tool_starts_collapsed(content: &str, expand_tools: bool) -> bool {
+    !expand_tools && content.lines().count() > TOOL_COLLAPSE_LINES
+}
 
 // =============================================================================
 // Tool Block Builder - Opencode-style tool rendering
@@ -28,6 +50,7 @@ mod chat_view_cache;
 /// Creates lines with ┃ prefix and full-width background.
 struct ToolBlockBuilder {
     width: usize,
+    wrap: bool,
     block_style: Style,
     bg_style: Style,
 }
@@ -36,12 +59,19 @@ impl ToolBlockBuilder {
     fn new(width: usize) -> Self {
         Self {
             width,
+            wrap: true,
             // Use conversation background color as foreground so ┃ blends with surrounding area
             block_style: Style::default().fg(bg_base()).bg(tool_block_bg()),
             bg_style: Style::default().bg(tool_block_bg()),
         }
     }
 
+    /// Keep long output lines intact instead of wrapping them (for horizontal scrolling)
+    fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
     /// Create a line with ┃ prefix and full-width background
     fn line(&self, spans: Vec<Span<'static>>) -> Line<'static> {
         // Note: "┃" is a box-drawing character with ambiguous width.
@@ -137,6 +167,9 @@ impl ToolBlockBuilder {
         let content_width = self.content_width();
         let style = Style::default().fg(color).bg(tool_block_bg());
         let spans = vec![Span::styled(text.to_string(), style)];
+        if !self.wrap {
+            return vec![self.line(spans)];
+        }
         let wrapped = wrap_spans(spans, content_width);
 
         wrapped
@@ -147,6 +180,9 @@ impl ToolBlockBuilder {
 
     /// Wrap custom spans and return multiple lines
     fn wrapped_custom(&self, spans: Vec<Span<'static>>) -> Vec<Line<'static>> {
+        if !self.wrap {
+            return vec![self.line(spans)];
+        }
         let content_width = self.content_width();
         let wrapped = wrap_spans(spans, content_width);
 
@@ -217,6 +253,7 @@ impl UserMessageBlockBuilder {
 }
 
 use self::chat_view_cache::LineCache;
+use self::chat_view_memory::OffloadedChunk;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct SelectionPoint {
@@ -506,6 +543,30 @@ pub struct ChatView {
     last_extra_lines: Vec<Line<'static>>,
     /// Starting line index for extra lines (cached_len + streaming_len)
     last_extra_lines_start: usize,
+    /// Whether code blocks and tool output wrap to the viewport width
+    wrap_code: bool,
+    /// Horizontal scroll offset (columns) for unwrapped code lines
+    horizontal_scroll: usize,
+    /// Whether reasoning blocks are rendered at all
+    show_reasoning: bool,
+    /// Whether reasoning blocks are collapsed to a one-line summary
+    collapse_reasoning: bool,
+    /// Whether long tool output is shown expanded instead of collapsed
+    expand_tools: bool,
+    /// Tool IDs whose message already shows live output (placeholder replaced)
+    live_tool_outputs: HashSet<String>,
+    /// User message last jumped to with prev/next-user-message navigation
+    focused_user_message: Option<usize>,
+    /// History moved out of memory, oldest first; `messages[0]` is their placeholder
+    offloaded: Vec<OffloadedChunk>,
+    /// Whether the last render showed the top of a scrolled-up history
+    at_scroll_top: bool,
+    /// How message timestamps are shown in the gutter
+    timestamps: ChatTimestamps,
+    /// (flat cache line, message index) where each cached message starts
+    message_line_starts: Vec<(usize, usize)>,
+    /// Message highlighted in message selection mode
+    selected_message: Option<usize>,
 }
 
 /// Information about a hovered file path for rendering
@@ -543,18 +604,32 @@ impl ChatView {
             hovered_file_path: None,
             last_extra_lines: Vec::new(),
             last_extra_lines_start: 0,
+            wrap_code: true,
+            horizontal_scroll: 0,
+            show_reasoning: true,
+            collapse_reasoning: false,
+            expand_tools: false,
+            live_tool_outputs: HashSet::new(),
+            focused_user_message: None,
+            offloaded: Vec::new(),
+            at_scroll_top: false,
+            timestamps: ChatTimestamps::Off,
+            message_line_starts: Vec::new(),
+            selected_message: None,
         }
     }
 
-    /// Calculate content area with padding for left margin and optional scrollbar.
-    fn content_area(area: Rect, show_scrollbar: bool) -> Option<Rect> {
+    /// Calculate content area with padding for left margin, the timestamp
+    /// gutter and optional scrollbar.
+    fn content_area(&self, area: Rect, show_scrollbar: bool) -> Option<Rect> {
+        let left = 2 + self.timestamp_gutter_width(); // 2 left margin + gutter
         let width = if show_scrollbar {
-            area.width.saturating_sub(4) // 2 left margin + 1 scrollbar + 1 gap
+            area.width.saturating_sub(left + 2) // + 1 scrollbar + 1 gap
         } else {
-            area.width.saturating_sub(2) // 2 left margin
+            area.width.saturating_sub(left)
         };
         let content = Rect {
-            x: area.x.saturating_add(2),
+            x: area.x.saturating_add(left),
             y: area.y,
             width,
             height: area.height,
@@ -565,12 +640,28 @@ impl ChatView {
         Some(content)
     }
 
-    pub(crate) fn content_area_for(area: Rect, show_scrollbar: bool) -> Option<Rect> {
-        Self::content_area(area, show_scrollbar)
+    pub(crate) fn content_area_for(&self, area: Rect, show_scrollbar: bool) -> Option<Rect> {
+        self.content_area(area, show_scrollbar)
+    }
+
+    /// Add a message to the chat, stamped with the current time
+    pub fn push(&mut self, mut message: ChatMessage) {
+        if message.timestamp.is_none() {
+            message.timestamp = Some(Utc::now());
+        }
+        self.append(message);
+    }
+
+    /// Add a message restored from history, keeping its timestamp (if any).
+    /// History is already stored, so it isn't handed out for persisting.
+    pub fn push_history(&mut self, mut message: ChatMessage) {
+        message.persisted = true;
+        self.append(message);
     }
 
-    /// Add a message to the chat
-    pub fn push(&mut self, message: ChatMessage) {
+    fn append(&mut self, mut message: ChatMessage) {
+        self.apply_default_collapse(&mut message);
+
         // If we were streaming, finalize it
         if !self.streaming_messages.is_empty() {
             self.finalize_streaming();
@@ -597,6 +688,82 @@ impl ChatView {
         // When scroll_offset > 0, user has scrolled up - preserve their position
     }
 
+    /// Collapse reasoning and long tool output according to the view toggles
+    fn apply_default_collapse(&self, message: &mut ChatMessage) {
+        match message.role {
+            MessageRole::Reasoning => message.is_collapsed = self.collapse_reasoning,
+            MessageRole::Tool => {
+                message.is_collapsed = tool_starts_collapsed(&message.content, self.expand_tools)
+            }
+            _ => {}
+        }
+    }
+
+    /// Add a subagent message to its group: right after the Task call or the
+    /// group's latest message, so concurrent subagents don't interleave.
+    pub fn push_nested(&mut self, mut message: ChatMessage) {
+        let Some(parent) = message.parent_tool_id.clone() else {
+            self.push(message);
+            return;
+        };
+        let anchor = self.messages.iter().rposition(|m| {
+            m.parent_tool_id.as_deref() == Some(parent.as_str())
+                || m.tool_id.as_deref() == Some(parent.as_str())
+        });
+        let index = match anchor {
+            Some(anchor) if anchor + 1 < self.messages.len() => anchor + 1,
+            // Group is already last (or unknown): a plain push keeps it together
+            _ => {
+                self.push(message);
+                return;
+            }
+        };
+
+        if message.timestamp.is_none() {
+            message.timestamp = Some(Utc::now());
+        }
+        self.apply_default_collapse(&mut message);
+        self.messages.insert(index, message);
+        if self
+            .selected_message
+            .is_some_and(|selected| selected >= index)
+        {
+            self.selected_message = self.selected_message.map(|selected| selected + 1);
+        }
+
+        // Lines after the insert point shift, so line-based state is stale
+        self.clear_selection();
+        self.hovered_file_path = None;
+        if let Some(width) = self.cache_width {
+            if index <= self.line_cache.entries.len() {
+                self.line_cache.entries.insert(index, None);
+                self.update_cache_entry(index, width);
+                // The previous message's spacing depends on its new neighbour
+                self.invalidate_cache_entry(index - 1);
+                self.update_cache_entry(index - 1, width);
+            }
+        }
+        self.flat_cache_dirty = true;
+    }
+
+    /// Update the tool message for `tool_id`, falling back to the last tool
+    /// message when no message carries that ID.
+    pub fn update_tool_by_id(
+        &mut self,
+        tool_id: &str,
+        content: String,
+        exit_code: Option<i32>,
+    ) -> bool {
+        match self
+            .messages
+            .iter()
+            .rposition(|m| m.role == MessageRole::Tool && m.tool_id.as_deref() == Some(tool_id))
+        {
+            Some(idx) => self.update_tool_at(idx, content, exit_code),
+            None => self.update_last_tool(content, exit_code),
+        }
+    }
+
     /// Update the last tool message with new content and exit code.
     /// Returns true if update was successful, false if no matching tool message was found.
     pub fn update_last_tool(&mut self, content: String, exit_code: Option<i32>) -> bool {
@@ -627,8 +794,22 @@ impl ChatView {
             return false;
         }
 
-        msg.content = content;
+        // Completed edits show the change itself rather than the tool's reply
+        msg.content = match (msg.tool_name.as_deref(), msg.tool_args.as_deref()) {
+            (Some(tool_name), Some(tool_args))
+                if is_diff_tool(tool_name) && !content.starts_with("Error:") =>
+            {
+                ToolDiff::from_tool_args(tool_name, tool_args)
+                    .map(|diff| diff.to_unified())
+                    .unwrap_or(content)
+            }
+            _ => content,
+        };
+        msg.is_collapsed = tool_starts_collapsed(&msg.content, self.expand_tools);
         msg.exit_code = exit_code;
+        if let Some(tool_id) = &msg.tool_id {
+            self.live_tool_outputs.remove(tool_id);
+        }
 
         // For Read tool on images, cache file size now (while file still exists)
         if msg.file_size.is_none() {
@@ -652,6 +833,50 @@ impl ChatView {
         true
     }
 
+    /// Append live output to a running tool message. The first chunk replaces
+    /// the "Running..." placeholder. Returns false if no tool message has this ID.
+    pub fn append_tool_output_by_id(&mut self, tool_id: &str, chunk: &str) -> bool {
+        let Some(index) = self
+            .messages
+            .iter()
+            .rposition(|m| m.role == MessageRole::Tool && m.tool_id.as_deref() == Some(tool_id))
+        else {
+            return false;
+        };
+
+        let msg = &mut self.messages[index];
+        if self.live_tool_outputs.insert(tool_id.to_string()) {
+            msg.content.clear();
+        }
+        msg.content.push_str(chunk);
+
+        if let Some(width) = self.cache_width {
+            self.invalidate_cache_entry(index);
+            self.update_cache_entry(index, width);
+        }
+
+        true
+    }
+
+    /// Finish a tool whose output was streamed with `append_tool_output_by_id`,
+    /// keeping that output. Returns false if no tool message has this ID.
+    pub fn finish_tool_output_by_id(&mut self, tool_id: &str, exit_code: Option<i32>) -> bool {
+        let Some(index) = self
+            .messages
+            .iter()
+            .rposition(|m| m.role == MessageRole::Tool && m.tool_id.as_deref() == Some(tool_id))
+        else {
+            return false;
+        };
+        // Nothing streamed means the placeholder is still showing
+        let content = if self.live_tool_outputs.contains(tool_id) {
+            self.messages[index].content.clone()
+        } else {
+            String::new()
+        };
+        self.update_tool_at(index, content, exit_code)
+    }
+
     /// Start or append to streaming message
     pub fn stream_append(&mut self, text: &str) {
         self.stream_append_role(MessageRole::Assistant, text);
@@ -697,6 +922,7 @@ impl ChatView {
                 MessageRole::System => ChatMessage::system(message.content),
                 MessageRole::Error => ChatMessage::error(message.content),
                 MessageRole::User => ChatMessage::user(message.content),
+                MessageRole::Plan => ChatMessage::plan(message.content),
                 MessageRole::Tool | MessageRole::Summary => ChatMessage::assistant(message.content),
             };
             self.push(chat_message);
@@ -707,7 +933,13 @@ impl ChatView {
     pub fn clear(&mut self) {
         self.messages.clear();
         self.streaming_messages.clear();
+        self.live_tool_outputs.clear();
+        self.offloaded.clear();
+        self.at_scroll_top = false;
+        self.message_line_starts.clear();
+        self.selected_message = None;
         self.scroll_offset = 0;
+        self.focused_user_message = None;
         self.clear_selection();
         self.last_render_extra_lines = 0;
         // Clear all caches
@@ -724,22 +956,142 @@ impl ChatView {
     /// Scroll up by n lines
     pub fn scroll_up(&mut self, n: usize) {
         self.scroll_offset = self.scroll_offset.saturating_add(n);
+        self.focused_user_message = None;
     }
 
     /// Scroll down by n lines
     pub fn scroll_down(&mut self, n: usize) {
         self.scroll_offset = self.scroll_offset.saturating_sub(n);
+        self.focused_user_message = None;
     }
 
     /// Scroll to top
     pub fn scroll_to_top(&mut self) {
         // Will be clamped during render
         self.scroll_offset = usize::MAX;
+        self.focused_user_message = None;
     }
 
     /// Scroll to bottom
     pub fn scroll_to_bottom(&mut self) {
         self.scroll_offset = 0;
+        self.focused_user_message = None;
+    }
+
+    /// Number of user messages in the chat
+    pub fn user_message_count(&self) -> usize {
+        self.messages
+            .iter()
+            .filter(|msg| msg.role == MessageRole::User)
+            .count()
+    }
+
+    /// Text of the user message at `position` among user messages
+    pub fn user_message(&self, position: usize) -> Option<&str> {
+        self.messages
+            .iter()
+            .filter(|msg| msg.role == MessageRole::User)
+            .nth(position)
+            .map(|msg| msg.content.as_str())
+    }
+
+    /// User message a prompt action applies to - the one last jumped to,
+    /// otherwise the most recent - as (position among user messages, text).
+    /// Positions stay stable when subagent output is inserted mid-chat.
+    pub fn target_user_message(&self) -> Option<(usize, &str)> {
+        let user_messages: Vec<(usize, &ChatMessage)> = self
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, msg)| msg.role == MessageRole::User)
+            .collect();
+        let position = self
+            .focused_user_message
+            .and_then(|focused| user_messages.iter().position(|(idx, _)| *idx == focused))
+            .or_else(|| user_messages.len().checked_sub(1))?;
+        Some((position, user_messages[position].1.content.as_str()))
+    }
+
+    /// Whether code blocks and tool output wrap to the viewport width
+    pub fn code_wrap(&self) -> bool {
+        self.wrap_code
+    }
+
+    /// Toggle wrapping of code blocks and tool output. Returns the new state.
+    pub fn toggle_code_wrap(&mut self) -> bool {
+        self.wrap_code = !self.wrap_code;
+        self.horizontal_scroll = 0;
+        // Line layout depends on wrapping, so every cached line must be rebuilt
+        self.reset_line_caches();
+        self.wrap_code
+    }
+
+    /// Show or hide reasoning blocks entirely
+    pub fn set_show_reasoning(&mut self, show: bool) {
+        if self.show_reasoning == show {
+            return;
+        }
+        self.show_reasoning = show;
+        self.reset_line_caches();
+    }
+
+    /// Toggle collapsing of all reasoning blocks. Returns true when collapsed.
+    pub fn toggle_reasoning_collapsed(&mut self) -> bool {
+        self.collapse_reasoning = !self.collapse_reasoning;
+        for msg in self
+            .messages
+            .iter_mut()
+            .filter(|msg| msg.role == MessageRole::Reasoning)
+        {
+            msg.is_collapsed = self.collapse_reasoning;
+        }
+        self.reset_line_caches();
+        self.collapse_reasoning
+    }
+
+    /// Toggle expanding all tool output. Returns true when expanded.
+    ///
+    /// Turning it off collapses long tool output again, including blocks
+    /// expanded one at a time.
+    pub fn toggle_tools_expanded(&mut self) -> bool {
+        self.expand_tools = !self.expand_tools;
+        for msg in self
+            .messages
+            .iter_mut()
+            .filter(|msg| msg.role == MessageRole::Tool)
+        {
+            msg.is_collapsed = tool_starts_collapsed(&msg.content, self.expand_tools);
+        }
+        self.reset_line_caches();
+        self.expand_tools
+    }
+
+    /// Drop every cached line so the next render rebuilds the layout
+    fn reset_line_caches(&mut self) {
+        self.clear_selection();
+        self.line_cache = LineCache::default();
+        self.flat_cache.clear();
+        self.flat_cache_width = None;
+        self.flat_cache_dirty = true;
+        self.streaming_cache = None;
+        self.streaming_joiner_before = None;
+        self.cache_width = None;
+    }
+
+    /// Scroll unwrapped code lines left by n columns
+    pub fn scroll_left(&mut self, n: usize) {
+        self.horizontal_scroll = self.horizontal_scroll.saturating_sub(n);
+    }
+
+    /// Scroll unwrapped code lines right by n columns (no-op while wrapping)
+    pub fn scroll_right(&mut self, n: usize) {
+        if self.wrap_code {
+            return;
+        }
+        // Selection columns don't track the horizontal offset
+        self.clear_selection();
+        // Will be clamped during render
+        self.horizontal_scroll = self.horizontal_scroll.saturating_add(n);
     }
 
     /// Jump to previous user message (returns true if moved).
@@ -793,26 +1145,76 @@ impl ChatView {
         let scroll_from_top = max_scroll.saturating_sub(self.scroll_offset.min(max_scroll));
 
         let target = if prev {
-            user_lines.iter().rev().find(|&&idx| idx < scroll_from_top)
+            user_lines
+                .iter()
+                .rev()
+                .find(|&&(line, _)| line < scroll_from_top)
         } else {
-            user_lines.iter().find(|&&idx| idx > scroll_from_top)
+            user_lines.iter().find(|&&(line, _)| line > scroll_from_top)
         };
 
-        let Some(&target_line) = target else {
+        let Some(&(target_line, msg_idx)) = target else {
             return false;
         };
 
         let clamped_target = target_line.min(max_scroll);
         self.scroll_offset = max_scroll.saturating_sub(clamped_target);
+        self.focused_user_message = Some(msg_idx);
+        true
+    }
+
+    /// Scroll so the user message at `msg_idx` is at the top of the view and
+    /// focus it (returns true if it was found).
+    pub fn scroll_to_user_message_at(
+        &mut self,
+        msg_idx: usize,
+        width: u16,
+        visible_height: usize,
+        extra_lines: usize,
+    ) -> bool {
+        if visible_height == 0 {
+            return false;
+        }
+
+        self.ensure_cache(width);
+        self.ensure_flat_cache();
+        self.ensure_streaming_cache(width);
+
+        let Some((target_line, _)) = self
+            .user_message_line_indices()
+            .into_iter()
+            .find(|&(_, idx)| idx == msg_idx)
+        else {
+            return false;
+        };
+
+        let streaming_len = self
+            .streaming_cache
+            .as_ref()
+            .map(|lines| lines.len())
+            .unwrap_or(0);
+        let total_lines = self.flat_cache.len() + streaming_len + extra_lines;
+        let max_scroll = total_lines.saturating_sub(visible_height);
+        self.scroll_offset = max_scroll.saturating_sub(target_line.min(max_scroll));
+        self.focused_user_message = Some(msg_idx);
         true
     }
 
-    fn user_message_line_indices(&self) -> Vec<usize> {
-        let mut indices = Vec::new();
+    /// First flattened line of each user message, paired with its message index
+    fn user_message_line_indices(&self) -> Vec<(usize, usize)> {
+        self.message_line_starts()
+            .into_iter()
+            .filter(|&(_, msg_idx)| self.messages[msg_idx].role == MessageRole::User)
+            .collect()
+    }
+
+    /// First flattened line of each rendered message, paired with its message index
+    fn message_line_starts(&self) -> Vec<(usize, usize)> {
+        let mut starts = Vec::new();
         let mut flat_index = 0usize;
         let mut last_is_blank = false;
 
-        for (msg_idx, msg) in self.messages.iter().enumerate() {
+        for msg_idx in 0..self.messages.len() {
             let Some(Some(cached)) = self.line_cache.entries.get(msg_idx) else {
                 continue;
             };
@@ -822,20 +1224,106 @@ impl ChatView {
                 if is_blank && last_is_blank {
                     continue;
                 }
-                if msg.role == MessageRole::User && first_included.is_none() {
+                if first_included.is_none() {
                     first_included = Some(flat_index);
                 }
                 flat_index = flat_index.saturating_add(1);
                 last_is_blank = is_blank;
             }
-            if msg.role == MessageRole::User {
-                if let Some(idx) = first_included {
-                    indices.push(idx);
-                }
+            if let Some(idx) = first_included {
+                starts.push((idx, msg_idx));
             }
         }
 
-        indices
+        starts
+    }
+
+    /// Message rendered at flattened line `line_index`
+    fn message_at_line(&self, line_index: usize) -> Option<usize> {
+        if line_index >= self.flat_cache.len() {
+            return None;
+        }
+        self.message_line_starts()
+            .into_iter()
+            .rev()
+            .find(|&(start, _)| start <= line_index)
+            .map(|(_, msg_idx)| msg_idx)
+    }
+
+    /// Flattened line index and column under a screen position
+    fn line_at_position(
+        &mut self,
+        x: u16,
+        y: u16,
+        area: Rect,
+        show_scrollbar: bool,
+    ) -> Option<(usize, usize)> {
+        let content = self.content_area(area, show_scrollbar)?;
+        if x < content.x
+            || y < content.y
+            || x >= content.x + content.width
+            || y >= content.y + content.height
+        {
+            return None;
+        }
+
+        self.ensure_cache(content.width);
+        self.ensure_flat_cache();
+        self.ensure_streaming_cache(content.width);
+
+        let (scroll_from_top, total_lines) = self.viewport_start(content.height as usize);
+        let line_index = scroll_from_top.saturating_add(y.saturating_sub(content.y) as usize);
+        (line_index < total_lines).then_some((line_index, x.saturating_sub(content.x) as usize))
+    }
+
+    /// First visible flattened line and total line count, as of the last render
+    fn viewport_start(&self, visible_height: usize) -> (usize, usize) {
+        let streaming_len = self
+            .streaming_cache
+            .as_ref()
+            .map(|lines| lines.len())
+            .unwrap_or(0);
+        let total_lines = self.flat_cache.len() + streaming_len + self.last_extra_lines.len();
+        let max_scroll = total_lines.saturating_sub(visible_height);
+        (
+            max_scroll.saturating_sub(self.scroll_offset.min(max_scroll)),
+            total_lines,
+        )
+    }
+
+    /// Tool message under a screen position, if any
+    pub fn tool_message_at_position(
+        &mut self,
+        x: u16,
+        y: u16,
+        area: Rect,
+        show_scrollbar: bool,
+    ) -> Option<usize> {
+        let (line_index, _) = self.line_at_position(x, y, area, show_scrollbar)?;
+        self.message_at_line(line_index)
+            .filter(|&msg_idx| self.messages[msg_idx].role == MessageRole::Tool)
+    }
+
+    /// The tool message to expand or collapse from the keyboard: the last
+    /// one starting in view, else the one filling the view
+    pub fn tool_message_in_view(&mut self, area: Rect, show_scrollbar: bool) -> Option<usize> {
+        let content = self.content_area(area, show_scrollbar)?;
+        self.ensure_cache(content.width);
+        self.ensure_flat_cache();
+        self.ensure_streaming_cache(content.width);
+
+        let (top, _) = self.viewport_start(content.height as usize);
+        let bottom = top + content.height as usize;
+        let is_tool = |msg_idx: usize| self.messages[msg_idx].role == MessageRole::Tool;
+        self.message_line_starts()
+            .into_iter()
+            .rev()
+            .find(|&(start, msg_idx)| start >= top && start < bottom && is_tool(msg_idx))
+            .map(|(_, msg_idx)| msg_idx)
+            .or_else(|| {
+                self.message_at_line(top)
+                    .filter(|&msg_idx| is_tool(msg_idx))
+            })
     }
 
     pub fn set_scroll_from_top(&mut self, offset_from_top: usize, total: usize, visible: usize) {
@@ -916,7 +1404,7 @@ impl ChatView {
         // Lock scroll position during streaming to prevent auto-scroll from
         // disrupting the active selection.
         if is_streaming && self.selection_scroll_lock.is_none() {
-            let Some(content) = Self::content_area(area, show_scrollbar) else {
+            let Some(content) = self.content_area(area, show_scrollbar) else {
                 return true;
             };
             let cached_len = self.flat_cache.len();
@@ -992,7 +1480,7 @@ impl ChatView {
         area: Rect,
         show_scrollbar: bool,
     ) -> Option<SelectionPoint> {
-        let content = Self::content_area(area, show_scrollbar)?;
+        let content = self.content_area(area, show_scrollbar)?;
         if click_x < content.x
             || click_y < content.y
             || click_x >= content.x + content.width
@@ -1063,41 +1551,13 @@ impl ChatView {
     ) -> Option<String> {
         use super::file_path_detector::{detect_existing_paths, expand_tilde};
 
-        let content = Self::content_area(area, show_scrollbar)?;
-        if click_x < content.x
-            || click_y < content.y
-            || click_x >= content.x + content.width
-            || click_y >= content.y + content.height
-        {
-            return None;
-        }
-
-        let rel_x = click_x.saturating_sub(content.x) as usize;
-        let rel_y = click_y.saturating_sub(content.y) as usize;
-
-        self.ensure_cache(content.width);
-        self.ensure_flat_cache();
-        self.ensure_streaming_cache(content.width);
-
+        let (line_index, rel_x) = self.line_at_position(click_x, click_y, area, show_scrollbar)?;
         let cached_len = self.flat_cache.len();
         let streaming_len = self
             .streaming_cache
             .as_ref()
             .map(|lines| lines.len())
             .unwrap_or(0);
-        let extra_len = self.last_extra_lines.len();
-        let total_lines = cached_len + streaming_len + extra_len;
-        if total_lines == 0 {
-            return None;
-        }
-
-        let visible_height = content.height as usize;
-        let max_scroll = total_lines.saturating_sub(visible_height);
-        let scroll_from_top = max_scroll.saturating_sub(self.scroll_offset.min(max_scroll));
-        let line_index = scroll_from_top.saturating_add(rel_y);
-        if line_index >= total_lines {
-            return None;
-        }
 
         // Get the line text - check flat_cache, streaming_cache, then extra_lines
         let line = if line_index < cached_len {
@@ -1146,7 +1606,7 @@ impl ChatView {
     ) -> bool {
         use super::file_path_detector::{detect_existing_paths, expand_tilde};
 
-        let content = match Self::content_area(area, show_scrollbar) {
+        let content = match self.content_area(area, show_scrollbar) {
             Some(c) => c,
             None => {
                 let changed = self.hovered_file_path.is_some();
@@ -1359,7 +1819,7 @@ impl ChatView {
             return None;
         }
 
-        let content = Self::content_area(area, true)?;
+        let content = self.content_area(area, true)?;
 
         self.ensure_cache(content.width);
         self.ensure_flat_cache();
@@ -1429,18 +1889,21 @@ impl ChatView {
         self.streaming_message_for(MessageRole::Assistant)
     }
 
-    /// Toggle collapsed state for a tool message at the given index
-    pub fn toggle_tool_at(&mut self, index: usize) {
-        if let Some(msg) = self.messages.get_mut(index) {
-            if msg.role == MessageRole::Tool {
-                msg.is_collapsed = !msg.is_collapsed;
-                // Invalidate and update cache for this message
-                if let Some(width) = self.cache_width {
-                    self.invalidate_cache_entry(index);
-                    self.update_cache_entry(index, width);
-                }
-            }
+    /// Toggle collapsed state for a tool message at the given index.
+    /// Returns the new collapsed state, or None if it isn't a tool message.
+    pub fn toggle_tool_at(&mut self, index: usize) -> Option<bool> {
+        let msg = self.messages.get_mut(index)?;
+        if msg.role != MessageRole::Tool {
+            return None;
         }
+        msg.is_collapsed = !msg.is_collapsed;
+        let collapsed = msg.is_collapsed;
+        // Invalidate and update cache for this message
+        if let Some(width) = self.cache_width {
+            self.invalidate_cache_entry(index);
+            self.update_cache_entry(index, width);
+        }
+        Some(collapsed)
     }
 
     /// Collapse all tool messages
@@ -1500,6 +1963,36 @@ impl ChatView {
         width: usize,
         lines: &mut Vec<Line<'static>>,
         joiner_before: &mut Vec<Option<String>>,
+    ) {
+        if msg.parent_tool_id.is_none() {
+            self.format_message_by_role(msg, width, lines, joiner_before);
+            return;
+        }
+
+        // Subagent messages render indented under their Task call
+        let start = lines.len();
+        let indent_width = UnicodeWidthStr::width(SUBAGENT_INDENT);
+        self.format_message_by_role(
+            msg,
+            width.saturating_sub(indent_width).max(1),
+            lines,
+            joiner_before,
+        );
+        for line in &mut lines[start..] {
+            line.spans.insert(0, Self::subagent_guide_span());
+        }
+    }
+
+    fn subagent_guide_span() -> Span<'static> {
+        Span::styled(SUBAGENT_INDENT, Style::default().fg(text_muted()))
+    }
+
+    fn format_message_by_role(
+        &self,
+        msg: &ChatMessage,
+        width: usize,
+        lines: &mut Vec<Line<'static>>,
+        joiner_before: &mut Vec<Option<String>>,
     ) {
         match msg.role {
             MessageRole::Tool => self.format_tool_message(msg, width, lines, joiner_before),
@@ -1513,6 +2006,7 @@ impl ChatView {
             MessageRole::System => self.format_system_message(msg, width, lines, joiner_before),
             MessageRole::Error => self.format_error_message(msg, width, lines, joiner_before),
             MessageRole::Summary => self.format_summary_message(msg, width, lines, joiner_before),
+            MessageRole::Plan => self.format_plan_message(msg, width, lines, joiner_before),
         }
     }
 
@@ -1558,7 +2052,7 @@ impl ChatView {
         }
 
         let builder = UserMessageBlockBuilder::new(width);
-        let text_style = Style::default().fg(Color::White).bg(tool_block_bg());
+        let text_style = Style::default().fg(text_primary()).bg(tool_block_bg());
 
         // Top padding
         lines.push(builder.empty_line());
@@ -1616,7 +2110,7 @@ impl ChatView {
                     // Apply a slightly dimmer style for assistant text
                     let mut style = s.style;
                     if style.fg.is_none() {
-                        style = style.fg(Color::Rgb(220, 220, 220)); // Slightly dimmer white
+                        style = style.fg(text_secondary()); // Slightly dimmer white
                     }
                     Span::styled(s.content.into_owned(), style)
                 })
@@ -1640,6 +2134,18 @@ impl ChatView {
                 (continuation_prefix.clone(), continuation_width)
             };
 
+            let is_code_line = content_spans
+                .iter()
+                .any(|span| span.style.bg == Some(markdown_code_bg()));
+            if !self.wrap_code && is_code_line {
+                let mut line_spans = first_prefix;
+                line_spans.extend(content_spans);
+                lines.push(Line::from(line_spans));
+                joiner_before.push(None);
+                first_content_line = false;
+                continue;
+            }
+
             self.format_wrapped_lines(
                 lines,
                 joiner_before,
@@ -1663,7 +2169,7 @@ impl ChatView {
                 Span::styled(
                     "…",
                     Style::default()
-                        .fg(Color::Yellow)
+                        .fg(accent_warning())
                         .add_modifier(Modifier::SLOW_BLINK),
                 ),
             ]));
@@ -1671,6 +2177,54 @@ impl ChatView {
         }
     }
 
+    /// Format plan messages - markdown under an accented "Plan" header
+    fn format_plan_message(
+        &self,
+        msg: &ChatMessage,
+        width: usize,
+        lines: &mut Vec<Line<'static>>,
+        joiner_before: &mut Vec<Option<String>>,
+    ) {
+        if msg.content.trim().is_empty() {
+            return;
+        }
+
+        let accent = Style::default().fg(accent_warning());
+        lines.push(Line::from(vec![
+            Span::styled("◆ ", accent),
+            Span::styled("Plan", accent.add_modifier(Modifier::BOLD)),
+        ]));
+        joiner_before.push(None);
+
+        let renderer = MarkdownRenderer::new();
+        let md_text = renderer.render(&msg.content);
+        let prefix = vec![Span::styled("  ", accent)];
+        let prefix_width = UnicodeWidthStr::width("  ");
+
+        for line in md_text.lines {
+            if line.spans.is_empty() {
+                lines.push(Line::from(""));
+                joiner_before.push(None);
+                continue;
+            }
+            let content_spans: Vec<Span<'static>> = line
+                .spans
+                .into_iter()
+                .map(|s| Span::styled(s.content.into_owned(), s.style))
+                .collect();
+            self.format_wrapped_lines(
+                lines,
+                joiner_before,
+                content_spans,
+                prefix.clone(),
+                prefix.clone(),
+                prefix_width,
+                prefix_width,
+                width,
+            );
+        }
+    }
+
     /// Format reasoning messages with subdued styling
     fn format_reasoning_message(
         &self,
@@ -1679,14 +2233,29 @@ impl ChatView {
         lines: &mut Vec<Line<'static>>,
         joiner_before: &mut Vec<Option<String>>,
     ) {
-        if msg.content.is_empty() {
+        if msg.content.is_empty() || !self.show_reasoning {
             return;
         }
 
         let content_lines: Vec<&str> = msg.content.lines().collect();
         let text_style = Style::default()
-            .fg(Color::DarkGray)
+            .fg(text_muted())
             .add_modifier(Modifier::ITALIC);
+
+        // Streaming reasoning stays expanded so progress remains visible
+        if msg.is_collapsed && !msg.is_streaming {
+            let line_word = if content_lines.len() == 1 {
+                "line"
+            } else {
+                "lines"
+            };
+            lines.push(Line::from(vec![Span::styled(
+                format!("▶ Thinking ({} {})", content_lines.len(), line_word),
+                text_style,
+            )]));
+            joiner_before.push(None);
+            return;
+        }
         let prefix_first = vec![Span::styled("... ", text_style)];
         let prefix_next = vec![Span::raw("    ")];
         let prefix_first_width = UnicodeWidthStr::width("... ");
@@ -1717,7 +2286,7 @@ impl ChatView {
                 Span::styled(
                     "...",
                     Style::default()
-                        .fg(Color::DarkGray)
+                        .fg(text_muted())
                         .add_modifier(Modifier::SLOW_BLINK),
                 ),
             ]));
@@ -1734,11 +2303,11 @@ impl ChatView {
         joiner_before: &mut Vec<Option<String>>,
     ) {
         let content_lines: Vec<&str> = msg.content.lines().collect();
-        let prefix_first = vec![Span::styled("ℹ ", Style::default().fg(Color::Blue))];
+        let prefix_first = vec![Span::styled("ℹ ", Style::default().fg(accent_primary()))];
         let prefix_next = vec![Span::raw("  ")];
         let prefix_first_width = UnicodeWidthStr::width("ℹ ");
         let prefix_next_width = UnicodeWidthStr::width("  ");
-        let text_style = Style::default().fg(Color::Blue);
+        let text_style = Style::default().fg(accent_primary());
 
         for (i, line) in content_lines.iter().enumerate() {
             let content_spans = vec![Span::styled(line.to_string(), text_style)];
@@ -1769,11 +2338,13 @@ impl ChatView {
         joiner_before: &mut Vec<Option<String>>,
     ) {
         let content_lines: Vec<&str> = msg.content.lines().collect();
-        let prefix_first = vec![Span::styled("✗ ", Style::default().fg(Color::Red))];
+        let prefix_first = vec![Span::styled("✗ ", Style::default().fg(accent_error()))];
         let prefix_next = vec![Span::raw("  ")];
         let prefix_first_width = UnicodeWidthStr::width("✗ ");
         let prefix_next_width = UnicodeWidthStr::width("  ");
-        let text_style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+        let text_style = Style::default()
+            .fg(accent_error())
+            .add_modifier(Modifier::BOLD);
 
         for (i, line) in content_lines.iter().enumerate() {
             let content_spans = vec![Span::styled(line.to_string(), text_style)];
@@ -1813,6 +2384,8 @@ impl ChatView {
         let tool_args = msg.tool_args.as_deref().unwrap_or("");
         let content_lines: Vec<&str> = msg.content.lines().collect();
         let line_count = content_lines.len();
+        let diff_stats = (is_diff_tool(tool_name) && is_unified_diff(&msg.content))
+            .then(|| unified_stats(&msg.content));
 
         // Check if this is an image file read
         let is_image = if tool_name == "Read" {
@@ -1828,7 +2401,7 @@ impl ChatView {
             msg.content.starts_with("Error:")
         };
 
-        let builder = ToolBlockBuilder::new(width);
+        let builder = ToolBlockBuilder::new(width).with_wrap(self.wrap_code);
 
         // === Top padding ===
         lines.push(builder.empty_line());
@@ -1856,7 +2429,9 @@ impl ChatView {
         let line_word = if line_count == 1 { "line" } else { "lines" };
         if msg.is_collapsed {
             // Collapsed: show summary
-            let summary = if line_count > 0 {
+            let summary = if let Some((added, removed)) = diff_stats {
+                format!("▶ +{} -{} (click to expand)", added, removed)
+            } else if line_count > 0 {
                 format!("▶ {} {} (click to expand)", line_count, line_word)
             } else {
                 "▶ No output".to_string()
@@ -1878,9 +2453,12 @@ impl ChatView {
                 let sanitized = sanitize_tool_output_line(normalized.as_ref());
                 let display_line = sanitized.as_ref();
                 // Check for diff-style lines
-                let (line_color, line_text) = if display_line.starts_with('+')
-                    && !display_line.starts_with("+++")
-                {
+                let (line_color, line_text) = if diff_stats.is_some() {
+                    (
+                        DiffLineKind::of(display_line).color(),
+                        sanitized.to_string(),
+                    )
+                } else if display_line.starts_with('+') && !display_line.starts_with("+++") {
                     (diff_add(), sanitized.to_string())
                 } else if display_line.starts_with('-') && !display_line.starts_with("---") {
                     (diff_remove(), sanitized.to_string())
@@ -1947,6 +2525,8 @@ impl ChatView {
             } else {
                 "✗ Failed".to_string()
             }
+        } else if let Some((added, removed)) = diff_stats {
+            format!("✓ +{} -{}", added, removed)
         } else if let Some(code) = msg.exit_code {
             format!("✓ Completed (exit: {})", code)
         } else if is_image {
@@ -2019,7 +2599,7 @@ impl ChatView {
                         Some(path.to_string())
                     }
                 }
-                "Write" | "write_file" | "Edit" => json
+                "Write" | "write_file" | "Edit" | "MultiEdit" => json
                     .get("file_path")
                     .and_then(|p| p.as_str())
                     .map(String::from),
@@ -2218,7 +2798,7 @@ impl ChatView {
         let status_color = if completed == total && total > 0 {
             accent_success()
         } else if in_progress > 0 {
-            Color::Yellow
+            accent_warning()
         } else {
             // Pending items - use neutral muted color (not success green)
             tool_comment()
@@ -2256,7 +2836,15 @@ impl ChatView {
         let duration = summary.format_duration();
         let input_tokens = TurnSummary::format_tokens(summary.input_tokens);
         let output_tokens = TurnSummary::format_tokens(summary.output_tokens);
-        let mut text = format!("─ ⏱ {duration} │ ↓{input_tokens} ↑{output_tokens} ");
+        let cache = summary
+            .format_cache_tokens()
+            .map(|cache| format!(" ({cache})"))
+            .unwrap_or_default();
+        let ledger = summary
+            .format_ledger()
+            .map(|ledger| format!(" │ {ledger}"))
+            .unwrap_or_default();
+        let mut text = format!("─ ⏱ {duration} │ ↓{input_tokens}{cache} ↑{output_tokens}{ledger} ");
         let target_width = width.max(1);
         let current_width = UnicodeWidthStr::width(text.as_str());
         if current_width < target_width {
@@ -2265,7 +2853,7 @@ impl ChatView {
             // Use display-width-aware truncation for proper UTF-8/wide char handling
             text = truncate_to_width_exact(&text, target_width);
         }
-        Line::from(Span::styled(text, Style::default().fg(Color::DarkGray)))
+        Line::from(Span::styled(text, Style::default().fg(text_muted())))
     }
 
     /// Render the chat view
@@ -2288,7 +2876,7 @@ impl ChatView {
         prompt_lines: Option<Vec<Line<'static>>>,
         show_scrollbar: bool,
     ) {
-        let Some(content) = Self::content_area(area, show_scrollbar) else {
+        let Some(content) = self.content_area(area, show_scrollbar) else {
             return;
         };
 
@@ -2343,6 +2931,7 @@ impl ChatView {
             self.scroll_offset = self.scroll_offset.min(max_scroll);
             max_scroll.saturating_sub(self.scroll_offset)
         };
+        self.at_scroll_top = max_scroll > 0 && self.scroll_offset >= max_scroll;
 
         let start_line = total_lines.saturating_sub(self.scroll_offset + visible_height);
         let end_line = total_lines.saturating_sub(self.scroll_offset);
@@ -2399,7 +2988,23 @@ impl ChatView {
                 }
             }
         }
-        let highlighted = self.apply_selection_highlight(visible_lines, content.width);
+        let mut highlighted = self.apply_selection_highlight(visible_lines, content.width);
+
+        if !self.wrap_code {
+            let max_overflow = highlighted
+                .iter()
+                .map(|line| line.width().saturating_sub(content.width as usize))
+                .max()
+                .unwrap_or(0);
+            self.horizontal_scroll = self.horizontal_scroll.min(max_overflow);
+            if self.horizontal_scroll > 0 {
+                let offset = self.horizontal_scroll.min(u16::MAX as usize) as u16;
+                highlighted = highlighted
+                    .into_iter()
+                    .map(|line| scroll_line_horizontally(line, offset, content.width))
+                    .collect();
+            }
+        }
 
         // When content is shorter than visible area, render at bottom (not top)
         let actual_lines = highlighted.len();
@@ -2416,6 +3021,16 @@ impl ChatView {
             content
         };
         Paragraph::new(highlighted).render(render_area, buf);
+        if self.timestamps != ChatTimestamps::Off && start_line < cached_len {
+            let gutter = Rect {
+                x: render_area.x - self.timestamp_gutter_width(),
+                ..render_area
+            };
+            self.render_timestamps(start_line, gutter, buf);
+        }
+        if start_line < cached_len {
+            self.render_message_highlight(start_line, render_area, area.x, buf);
+        }
 
         if show_scrollbar {
             render_minimal_scrollbar(
@@ -2870,6 +3485,22 @@ fn slice_line_by_cols(line: &Line<'static>, start_col: u16, end_col: u16) -> Lin
     Line::from(out_spans).style(line.style)
 }
 
+/// Shift a line that overflows the viewport left by `offset` columns, keeping its gutter
+/// (tool block stripe or message prefix) in place. Lines that fit are left untouched.
+fn scroll_line_horizontally(line: Line<'static>, offset: u16, width: u16) -> Line<'static> {
+    if offset == 0 || line.width() <= width as usize {
+        return line;
+    }
+    let gutter = line_gutter_cols(&line);
+    let mut spans = if gutter > 0 {
+        slice_line_by_cols(&line, 0, gutter - 1).spans
+    } else {
+        Vec::new()
+    };
+    spans.extend(slice_line_by_cols(&line, gutter.saturating_add(offset), u16::MAX).spans);
+    Line::from(spans).style(line.style)
+}
+
 fn line_to_markdown(line: &Line<'static>, _is_code_block: bool) -> String {
     line_to_flat(line)
 }
@@ -3268,4 +3899,218 @@ mod tests {
         let out = selection_to_copy_text(&lines, &joiners, start, end, 80).unwrap();
         assert_eq!(out, "para1\n\ncode\n\npara2");
     }
+
+    #[test]
+    fn test_code_wrap_toggle_keeps_long_code_lines_intact() {
+        let long_line = format!("    let value = {};", "x".repeat(80));
+        let mut view = ChatView::new();
+        view.push(ChatMessage::assistant(format!(
+            "```rust\n{}\n```",
+            long_line
+        )));
+
+        view.ensure_cache(40);
+        view.ensure_flat_cache();
+        let wrapped_len = view.flat_cache.len();
+
+        assert!(!view.toggle_code_wrap());
+        view.ensure_cache(40);
+        view.ensure_flat_cache();
+        assert!(view.flat_cache.len() < wrapped_len);
+
+        let code = view
+            .flat_cache
+            .iter()
+            .map(line_to_flat)
+            .find(|flat| flat.contains("let value"))
+            .expect("code line present");
+        assert!(code.contains(&long_line), "indentation preserved: {code:?}");
+    }
+
+    #[test]
+    fn test_scroll_line_horizontally_keeps_gutter() {
+        let line = Line::from(vec![Span::raw("  "), Span::raw("abcdefghij")]);
+        let shifted = scroll_line_horizontally(line, 3, 6);
+        assert_eq!(line_to_flat(&shifted), "  defghij");
+
+        let short = Line::from(vec![Span::raw("  "), Span::raw("abc")]);
+        assert_eq!(
+            line_to_flat(&scroll_line_horizontally(short, 3, 6)),
+            "  abc"
+        );
+    }
+
+    #[test]
+    fn test_plan_message_renders_with_header() {
+        let mut view = ChatView::new();
+        view.push(ChatMessage::plan("1. Add the flag\n2. Wire it up"));
+
+        view.ensure_cache(60);
+        view.ensure_flat_cache();
+        let flat: Vec<String> = view.flat_cache.iter().map(line_to_flat).collect();
+
+        assert_eq!(flat.first().map(String::as_str), Some("◆ Plan"));
+        assert!(flat.iter().any(|line| line.contains("Add the flag")));
+    }
+
+    #[test]
+    fn test_reasoning_collapse_and_hide() {
+        let mut view = ChatView::new();
+        view.push(ChatMessage::reasoning("step one\nstep two"));
+        view.push(ChatMessage::assistant("done"));
+
+        let render = |view: &mut ChatView| -> Vec<String> {
+            view.ensure_cache(60);
+            view.ensure_flat_cache();
+            view.flat_cache.iter().map(line_to_flat).collect()
+        };
+
+        assert!(render(&mut view)
+            .iter()
+            .any(|line| line.contains("step two")));
+
+        assert!(view.toggle_reasoning_collapsed());
+        let collapsed = render(&mut view);
+        assert!(collapsed.iter().any(|line| line == "▶ Thinking (2 lines)"));
+        assert!(!collapsed.iter().any(|line| line.contains("step one")));
+
+        // New reasoning follows the current collapse state
+        view.push(ChatMessage::reasoning("later"));
+        assert!(view.messages[2].is_collapsed);
+
+        view.set_show_reasoning(false);
+        let hidden = render(&mut view);
+        assert!(!hidden.iter().any(|line| line.contains("Thinking")));
+        assert!(hidden.iter().any(|line| line.contains("done")));
+    }
+
+    #[test]
+    fn test_append_tool_output_replaces_placeholder_then_appends() {
+        let mut view = ChatView::new();
+        view.push(ChatMessage::tool("Bash", "cargo build", "Running...").with_tool_id("call_1"));
+
+        assert!(view.append_tool_output_by_id("call_1", "Compiling a\n"));
+        assert!(view.append_tool_output_by_id("call_1", "Compiling b\n"));
+        assert_eq!(view.messages[0].content, "Compiling a\nCompiling b\n");
+        assert!(!view.append_tool_output_by_id("missing", "x"));
+
+        // Final output replaces the live output
+        assert!(view.update_tool_by_id("call_1", "done".to_string(), Some(0)));
+        assert_eq!(view.messages[0].content, "done");
+        assert_eq!(view.messages[0].exit_code, Some(0));
+    }
+
+    #[test]
+    fn test_target_user_message_follows_jumps() {
+        let mut view = ChatView::new();
+        assert_eq!(view.target_user_message(), None);
+        for turn in 0..3 {
+            view.push(ChatMessage::user(format!("prompt {turn}")));
+            for line in 0..10 {
+                view.push(ChatMessage::assistant(format!("answer {turn}.{line}")));
+            }
+        }
+
+        // Nothing jumped to yet: the latest prompt
+        assert_eq!(view.target_user_message(), Some((2, "prompt 2")));
+
+        assert!(view.scroll_to_prev_user_message(80, 5, 0));
+        assert_eq!(view.target_user_message(), Some((2, "prompt 2")));
+        assert!(view.scroll_to_prev_user_message(80, 5, 0));
+        assert_eq!(view.target_user_message(), Some((1, "prompt 1")));
+        assert_eq!(view.user_message(0), Some("prompt 0"));
+
+        // Manual scrolling drops the focus
+        view.scroll_to_bottom();
+        assert_eq!(view.target_user_message(), Some((2, "prompt 2")));
+    }
+
+    #[test]
+    fn test_user_message_jumps_land_on_wrapped_prompt_starts() {
+        let mut view = ChatView::new();
+        for turn in 0..3 {
+            // Long enough to wrap at the narrow width below
+            view.push(ChatMessage::user(format!(
+                "prompt {turn} asks for something long enough to wrap across lines"
+            )));
+            for line in 0..6 {
+                view.push(ChatMessage::assistant(format!(
+                    "answer {turn}.{line} also wraps when the view is narrow"
+                )));
+            }
+        }
+
+        let (width, height) = (24, 5);
+        assert!(view.scroll_to_prev_user_message(width, height, 0));
+        assert!(view.scroll_to_prev_user_message(width, height, 0));
+        let starts = view.user_message_line_indices();
+        assert_eq!(starts.len(), 3);
+        assert_eq!(view.viewport_start(height).0, starts[1].0);
+
+        assert!(view.scroll_to_next_user_message(width, height, 0));
+        assert_eq!(view.viewport_start(height).0, starts[2].0);
+        assert!(!view.scroll_to_next_user_message(width, height, 0));
+    }
+
+    #[test]
+    fn test_push_nested_groups_subagent_messages() {
+        let mut view = ChatView::new();
+        view.push(ChatMessage::tool("Task", "{}", "Running...").with_tool_id("task_1"));
+        view.push(ChatMessage::assistant("main agent keeps going"));
+        view.push_nested(
+            ChatMessage::tool("Grep", "{}", "Running...")
+                .with_tool_id("grep_1")
+                .with_parent_tool_id("task_1"),
+        );
+        view.push_nested(ChatMessage::assistant("found it").with_parent_tool_id("task_1"));
+
+        let parents: Vec<Option<&str>> = view
+            .messages
+            .iter()
+            .map(|msg| msg.parent_tool_id.as_deref())
+            .collect();
+        assert_eq!(parents, vec![None, Some("task_1"), Some("task_1"), None]);
+
+        assert!(view.update_tool_by_id("grep_1", "a.rs".to_string(), None));
+        assert_eq!(view.messages[1].content, "a.rs");
+        assert_eq!(view.messages[0].content, "Running...");
+
+        view.ensure_cache(60);
+        view.ensure_flat_cache();
+        let nested = view
+            .flat_cache
+            .iter()
+            .map(line_to_flat)
+            .find(|line| line.contains("found it"))
+            .expect("nested message rendered");
+        assert!(nested.starts_with(SUBAGENT_INDENT), "indented: {nested:?}");
+    }
+
+    #[test]
+    fn test_long_tool_output_starts_collapsed() {
+        let mut view = ChatView::new();
+        view.push(ChatMessage::tool("Bash", "{}", "Running...").with_tool_id("long"));
+        view.push(ChatMessage::tool("Bash", "{}", "Running...").with_tool_id("short"));
+        view.push(ChatMessage::assistant("done"));
+
+        let long_output = (0..TOOL_COLLAPSE_LINES + 5)
+            .map(|i| format!("line {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(view.update_tool_by_id("long", long_output, Some(0)));
+        assert!(view.update_tool_by_id("short", "ok".to_string(), Some(0)));
+        assert!(view.messages[0].is_collapsed);
+        assert!(!view.messages[1].is_collapsed);
+
+        // Expand-all opens every block; toggling back re-collapses long ones
+        assert!(view.toggle_tools_expanded());
+        assert!(!view.messages[0].is_collapsed);
+        assert!(!view.toggle_tools_expanded());
+        assert!(view.messages[0].is_collapsed);
+        assert!(!view.messages[1].is_collapsed);
+
+        assert_eq!(view.toggle_tool_at(0), Some(false));
+        assert_eq!(view.toggle_tool_at(1), Some(true));
+        assert_eq!(view.toggle_tool_at(2), None);
+    }
 }
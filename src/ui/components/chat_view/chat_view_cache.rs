@@ -43,12 +43,17 @@ impl ChatView {
             args.hash(&mut hasher);
         }
         msg.exit_code.hash(&mut hasher);
+        msg.parent_tool_id.hash(&mut hasher);
         // Hash summary fields if present (TurnSummary doesn't derive Hash)
         if let Some(ref summary) = msg.summary {
             summary.duration_secs.hash(&mut hasher);
             summary.input_tokens.hash(&mut hasher);
             summary.output_tokens.hash(&mut hasher);
+            summary.cache_read_tokens.hash(&mut hasher);
+            summary.cache_write_tokens.hash(&mut hasher);
             summary.files_changed.len().hash(&mut hasher);
+            summary.commands.hash(&mut hasher);
+            summary.references.hash(&mut hasher);
         }
         hasher.finish()
     }
@@ -58,13 +63,13 @@ impl ChatView {
         &self,
         msg: &ChatMessage,
         width: usize,
-        add_spacing: bool,
+        spacing: Option<Line<'static>>,
     ) -> CachedMessageLines {
         let mut lines = Vec::new();
         let mut joiner_before = Vec::new();
         self.format_message_with_joiners(msg, width, &mut lines, &mut joiner_before);
-        if add_spacing {
-            lines.push(Line::from(""));
+        if let Some(spacing) = spacing {
+            lines.push(spacing);
             joiner_before.push(None);
         }
         CachedMessageLines {
@@ -94,9 +99,8 @@ impl ChatView {
         self.line_cache.total_line_count = 0;
 
         for i in 0..self.messages.len() {
-            let add_spacing = self.should_add_spacing_after(i);
-            let cached =
-                self.render_message_to_cache(&self.messages[i], width as usize, add_spacing);
+            let spacing = self.spacing_line_after(i);
+            let cached = self.render_message_to_cache(&self.messages[i], width as usize, spacing);
             self.line_cache.total_line_count += cached.lines.len();
             self.line_cache.entries.push(Some(cached));
         }
@@ -105,16 +109,26 @@ impl ChatView {
         self.flat_cache_dirty = true;
     }
 
-    /// Check if spacing should be added after message at index
-    fn should_add_spacing_after(&self, index: usize) -> bool {
+    /// Spacing line to add after the message at index, if any. Spacing inside
+    /// a subagent group keeps the group's guide so the group reads as one block.
+    fn spacing_line_after(&self, index: usize) -> Option<Line<'static>> {
         let msg = &self.messages[index];
+        let next = self.messages.get(index + 1);
         let is_summary = msg.role == MessageRole::Summary;
-        let next_is_summary = self
-            .messages
-            .get(index + 1)
+        let next_is_summary = next
             .map(|m| m.role == MessageRole::Summary)
             .unwrap_or(false);
-        !is_summary && !next_is_summary
+        if is_summary || next_is_summary {
+            return None;
+        }
+
+        let next_in_same_group = msg.parent_tool_id.is_some()
+            && next.and_then(|m| m.parent_tool_id.as_ref()) == msg.parent_tool_id.as_ref();
+        if next_in_same_group {
+            Some(Line::from(vec![Self::subagent_guide_span()]))
+        } else {
+            Some(Line::from(""))
+        }
     }
 
     /// Invalidate cache entry at specific index
@@ -143,9 +157,9 @@ impl ChatView {
                     .saturating_sub(old.lines.len());
             }
 
-            let add_spacing = self.should_add_spacing_after(index);
+            let spacing = self.spacing_line_after(index);
             let cached =
-                self.render_message_to_cache(&self.messages[index], width as usize, add_spacing);
+                self.render_message_to_cache(&self.messages[index], width as usize, spacing);
             self.line_cache.total_line_count += cached.lines.len();
 
             if index < self.line_cache.entries.len() {
@@ -170,7 +184,16 @@ impl ChatView {
         self.flat_cache.reserve(self.line_cache.total_line_count);
         self.joiner_before.clear();
         self.joiner_before.reserve(self.line_cache.total_line_count);
-        for cached in self.line_cache.entries.iter().flatten() {
+        self.message_line_starts.clear();
+        for (index, cached) in self
+            .line_cache
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cached)| Some((index, cached.as_ref()?)))
+        {
+            self.message_line_starts
+                .push((self.flat_cache.len(), index));
             for (line, joiner) in cached.lines.iter().zip(cached.joiner_before.iter()) {
                 // Skip consecutive blank lines to avoid excessive spacing
                 let is_blank = is_blank_line(line);
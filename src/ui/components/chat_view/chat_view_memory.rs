@@ -0,0 +1,274 @@
+//! Per-session memory limits for the chat view.
+//!
+//! Messages longer than the message limit keep their start and end around an
+//! omission note. Once the history grows past the memory cap, the oldest
+//! messages are handed to the app to persist and replaced by a placeholder at
+//! the top of the chat; scrolling up to it brings them back, newest batch first.
+
+use std::ops::Range;
+
+use super::{ChatMessage, ChatView};
+
+/// Offloading brings the history down to this share of the cap, so it doesn't
+/// run again for every new message
+const OFFLOAD_TARGET_PERCENT: usize = 75;
+/// Most recent messages that always stay in memory
+const MIN_KEPT_MESSAGES: usize = 20;
+/// Room left for the omission note when shortening a message
+const OMISSION_NOTE_RESERVE: usize = 64;
+
+/// Memory limits for one session's chat history (0 = unlimited)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChatMemoryLimits {
+    /// Longer messages are shortened to their start and end
+    pub max_message_bytes: usize,
+    /// History beyond this is offloaded, oldest first
+    pub memory_cap_bytes: usize,
+}
+
+/// A batch of messages moved out of memory, stored by the app under `id`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct OffloadedChunk {
+    pub(super) id: i64,
+    pub(super) messages: usize,
+    pub(super) bytes: usize,
+}
+
+impl ChatView {
+    /// Shorten oversized messages and return the oldest messages to offload
+    /// to get back under the cap. Nothing is offloaded while the user is
+    /// scrolled up or selecting, so reloaded history stays where it is.
+    pub fn apply_memory_limits(&mut self, limits: ChatMemoryLimits) -> Option<Range<usize>> {
+        if limits.max_message_bytes > 0 {
+            self.shorten_oversized_messages(limits.max_message_bytes);
+        }
+        if limits.memory_cap_bytes == 0 || self.scroll_offset > 0 || self.has_selection() {
+            return None;
+        }
+
+        let start = self.first_loaded_index();
+        let mut total: usize = self.messages[start..].iter().map(message_bytes).sum();
+        if total <= limits.memory_cap_bytes {
+            return None;
+        }
+        let target = limits.memory_cap_bytes / 100 * OFFLOAD_TARGET_PERCENT;
+        let last_candidate = self.messages.len().saturating_sub(MIN_KEPT_MESSAGES);
+        let mut end = start;
+        while end < last_candidate && total > target {
+            total -= message_bytes(&self.messages[end]);
+            end += 1;
+        }
+        (end > start).then_some(start..end)
+    }
+
+    /// Drop `range` (from [`Self::apply_memory_limits`]) once the app has
+    /// stored those messages as chunk `id`
+    pub fn offload(&mut self, range: Range<usize>, id: i64) {
+        if range.start != self.first_loaded_index() || range.end > self.messages.len() {
+            tracing::warn!(?range, "Ignoring stale chat offload range");
+            return;
+        }
+        let removed: Vec<ChatMessage> = self.messages.drain(range).collect();
+        self.offloaded.push(OffloadedChunk {
+            id,
+            messages: removed.len(),
+            bytes: removed.iter().map(message_bytes).sum(),
+        });
+        if self.offloaded.len() == 1 {
+            let mut placeholder = ChatMessage::system(String::new());
+            placeholder.persisted = true;
+            self.messages.insert(0, placeholder);
+        }
+        self.refresh_offload_placeholder();
+        self.focused_user_message = None;
+        self.selected_message = None;
+        self.reset_line_caches();
+    }
+
+    /// Chunk to bring back once the user has scrolled up to the placeholder
+    pub fn offloaded_chunk_to_load(&self) -> Option<i64> {
+        if !self.at_scroll_top {
+            return None;
+        }
+        self.offloaded.last().map(|chunk| chunk.id)
+    }
+
+    /// Put chunk `id` back below the placeholder; `None` drops a chunk that
+    /// could not be loaded so it isn't requested again.
+    pub fn restore_offloaded(&mut self, id: i64, messages: Option<Vec<ChatMessage>>) {
+        if self.offloaded.last().map(|chunk| chunk.id) != Some(id) {
+            return;
+        }
+        self.offloaded.pop();
+        if let Some(mut messages) = messages {
+            // Persisted before they were offloaded
+            for msg in &mut messages {
+                msg.persisted = true;
+            }
+            let loaded = self.messages.split_off(1);
+            self.messages.extend(messages);
+            self.messages.extend(loaded);
+        }
+        if self.offloaded.is_empty() {
+            self.messages.remove(0);
+        } else {
+            self.refresh_offload_placeholder();
+        }
+        self.at_scroll_top = false;
+        self.focused_user_message = None;
+        self.selected_message = None;
+        self.reset_line_caches();
+    }
+
+    /// Whether older history has been offloaded
+    pub fn has_offloaded_history(&self) -> bool {
+        !self.offloaded.is_empty()
+    }
+
+    /// Index of the oldest message still in memory (after the placeholder)
+    fn first_loaded_index(&self) -> usize {
+        usize::from(!self.offloaded.is_empty())
+    }
+
+    fn refresh_offload_placeholder(&mut self) {
+        let messages: usize = self.offloaded.iter().map(|chunk| chunk.messages).sum();
+        let bytes: usize = self.offloaded.iter().map(|chunk| chunk.bytes).sum();
+        if let Some(placeholder) = self.messages.first_mut() {
+            placeholder.content = format!(
+                "{} earlier message{} ({}) moved out of memory. Scroll up to load them.",
+                messages,
+                if messages == 1 { "" } else { "s" },
+                Self::format_file_size(bytes as u64)
+            );
+        }
+    }
+
+    fn shorten_oversized_messages(&mut self, max_bytes: usize) {
+        let mut shortened = Vec::new();
+        for (index, msg) in self.messages.iter_mut().enumerate() {
+            if msg.content.len() <= max_bytes {
+                continue;
+            }
+            // Output still streaming in is shortened once the tool finishes
+            if msg
+                .tool_id
+                .as_ref()
+                .is_some_and(|id| self.live_tool_outputs.contains(id))
+            {
+                continue;
+            }
+            msg.content = shorten(&msg.content, max_bytes);
+            shortened.push(index);
+        }
+        if let Some(width) = self.cache_width {
+            for index in shortened {
+                self.invalidate_cache_entry(index);
+                self.update_cache_entry(index, width);
+            }
+        }
+    }
+}
+
+/// Bytes a message holds in memory
+fn message_bytes(msg: &ChatMessage) -> usize {
+    msg.content.len()
+        + msg.tool_args.as_ref().map_or(0, String::len)
+        + msg.tool_name.as_ref().map_or(0, String::len)
+}
+
+/// Keep the start and end of `content` within `max_bytes`, cut at line
+/// breaks where possible
+fn shorten(content: &str, max_bytes: usize) -> String {
+    let keep = max_bytes.saturating_sub(OMISSION_NOTE_RESERVE) / 2;
+    let mut head_end = keep;
+    while !content.is_char_boundary(head_end) {
+        head_end -= 1;
+    }
+    if let Some(newline) = content[..head_end].rfind('\n') {
+        head_end = newline + 1;
+    }
+    let mut tail_start = content.len() - keep;
+    while !content.is_char_boundary(tail_start) {
+        tail_start += 1;
+    }
+    if let Some(newline) = content[tail_start..]
+        .find('\n')
+        .filter(|newline| tail_start + newline + 1 < content.len())
+    {
+        tail_start += newline + 1;
+    }
+    let omitted = tail_start.saturating_sub(head_end);
+    format!(
+        "{}… {} omitted to save memory …\n{}",
+        &content[..head_end],
+        ChatView::format_file_size(omitted as u64),
+        &content[tail_start..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(memory_cap_bytes: usize) -> ChatMemoryLimits {
+        ChatMemoryLimits {
+            max_message_bytes: 0,
+            memory_cap_bytes,
+        }
+    }
+
+    #[test]
+    fn test_shorten_keeps_start_and_end_within_limit() {
+        let content: String = (0..2_000).map(|i| format!("line {}\n", i)).collect();
+        let short = shorten(&content, 1_024);
+        assert!(short.len() <= 1_024, "{}", short.len());
+        assert!(short.starts_with("line 0\n"));
+        assert!(short.ends_with("line 1999\n"));
+        assert!(short.contains("omitted to save memory"));
+        // Already short enough: a second pass leaves it alone
+        let mut view = ChatView::new();
+        view.push(ChatMessage::assistant(short.clone()));
+        view.apply_memory_limits(ChatMemoryLimits {
+            max_message_bytes: 1_024,
+            memory_cap_bytes: 0,
+        });
+        assert_eq!(view.messages()[0].content, short);
+    }
+
+    #[test]
+    fn test_offload_and_restore_round_trip() {
+        let mut view = ChatView::new();
+        for i in 0..40 {
+            view.push(ChatMessage::assistant(format!("{:0>100}", i)));
+        }
+
+        let range = view.apply_memory_limits(limits(2_000)).unwrap();
+        assert_eq!(range, 0..20);
+        let offloaded = view.messages()[range.clone()].to_vec();
+        view.offload(range, 7);
+        assert!(view.has_offloaded_history());
+        assert_eq!(view.len(), 21);
+        assert!(view.messages()[0]
+            .content
+            .starts_with("20 earlier messages"));
+
+        // Only requested once the user scrolls up to the placeholder
+        assert_eq!(view.offloaded_chunk_to_load(), None);
+        view.at_scroll_top = true;
+        assert_eq!(view.offloaded_chunk_to_load(), Some(7));
+        view.restore_offloaded(7, Some(offloaded));
+        assert!(!view.has_offloaded_history());
+        assert_eq!(view.len(), 40);
+        assert!(view.messages()[0].content.ends_with('0'));
+    }
+
+    #[test]
+    fn test_no_offload_while_scrolled_up() {
+        let mut view = ChatView::new();
+        for i in 0..40 {
+            view.push(ChatMessage::assistant(format!("{:0>100}", i)));
+        }
+        view.scroll_up(3);
+        assert_eq!(view.apply_memory_limits(limits(2_000)), None);
+    }
+}
@@ -0,0 +1,195 @@
+//! Whole-message selection: a highlight moved between messages with j/k, so
+//! a message can be copied without dragging a text selection over it.
+
+use std::ops::Range;
+
+use ratatui::{buffer::Buffer, layout::Rect, style::Style};
+
+use super::{chat_view_cache, ChatView};
+use crate::ui::components::{accent_primary, selected_bg};
+
+/// Marker drawn in the left margin next to the selected message
+const SELECTED_MARKER: &str = "▌";
+
+impl ChatView {
+    /// Index of the highlighted message, if any
+    pub fn selected_message(&self) -> Option<usize> {
+        self.selected_message
+    }
+
+    /// Text of the highlighted message
+    pub fn selected_message_content(&self) -> Option<&str> {
+        self.selected_message
+            .and_then(|index| self.messages.get(index))
+            .map(|msg| msg.content.as_str())
+    }
+
+    /// Remove the highlight
+    pub fn clear_message_selection(&mut self) {
+        self.selected_message = None;
+    }
+
+    /// Move the highlight one message up (`prev`) or down, starting from the
+    /// last message in view. Scrolls to keep it visible; returns whether a
+    /// message is highlighted.
+    pub fn select_adjacent_message(
+        &mut self,
+        area: Rect,
+        show_scrollbar: bool,
+        prev: bool,
+    ) -> bool {
+        let Some(content) = self.content_area(area, show_scrollbar) else {
+            return false;
+        };
+        self.ensure_cache(content.width);
+        self.ensure_flat_cache();
+        self.ensure_streaming_cache(content.width);
+
+        let selectable: Vec<usize> = self
+            .message_line_starts
+            .iter()
+            .map(|&(_, index)| index)
+            // The offload placeholder has nothing worth copying
+            .filter(|&index| index >= self.first_selectable_index())
+            .collect();
+        let visible_height = content.height as usize;
+        let position = match self
+            .selected_message
+            .and_then(|selected| selectable.iter().position(|&index| index == selected))
+        {
+            Some(position) if prev => position.saturating_sub(1),
+            Some(position) => (position + 1).min(selectable.len().saturating_sub(1)),
+            None => {
+                let (top, _) = self.viewport_start(visible_height);
+                let bottom = top + visible_height;
+                let in_view = selectable.iter().rposition(|&index| {
+                    self.message_lines(index)
+                        .is_some_and(|lines| lines.start < bottom)
+                });
+                match in_view {
+                    Some(position) => position,
+                    None => return false,
+                }
+            }
+        };
+        let Some(&index) = selectable.get(position) else {
+            return false;
+        };
+        self.selected_message = Some(index);
+        self.scroll_message_into_view(index, visible_height);
+        true
+    }
+
+    /// Messages before this index can't be selected
+    fn first_selectable_index(&self) -> usize {
+        usize::from(self.has_offloaded_history())
+    }
+
+    /// Flat cache lines of message `index`, without trailing blank lines
+    fn message_lines(&self, index: usize) -> Option<Range<usize>> {
+        let position = self
+            .message_line_starts
+            .iter()
+            .position(|&(_, msg_idx)| msg_idx == index)?;
+        let start = self.message_line_starts[position].0;
+        let mut end = self
+            .message_line_starts
+            .get(position + 1)
+            .map_or(self.flat_cache.len(), |&(line, _)| line);
+        while end > start + 1 && chat_view_cache::is_blank_line(&self.flat_cache[end - 1]) {
+            end -= 1;
+        }
+        Some(start..end)
+    }
+
+    fn scroll_message_into_view(&mut self, index: usize, visible_height: usize) {
+        let Some(lines) = self.message_lines(index) else {
+            return;
+        };
+        let (top, total) = self.viewport_start(visible_height);
+        let new_top = if lines.start < top {
+            lines.start
+        } else if lines.end > top + visible_height {
+            // Long messages show their start
+            lines.start.min(lines.end.saturating_sub(visible_height))
+        } else {
+            return;
+        };
+        self.set_scroll_from_top(new_top, total, visible_height);
+    }
+
+    /// Highlight the selected message's rows. `area` is where flat cache line
+    /// `first_line` was drawn; the marker goes in the margin left of it.
+    pub(super) fn render_message_highlight(
+        &self,
+        first_line: usize,
+        area: Rect,
+        margin_x: u16,
+        buf: &mut Buffer,
+    ) {
+        let Some(lines) = self
+            .selected_message
+            .and_then(|index| self.message_lines(index))
+        else {
+            return;
+        };
+        let last_line = first_line + area.height as usize;
+        for line in lines.start.max(first_line)..lines.end.min(last_line) {
+            let y = area.y + (line - first_line) as u16;
+            buf.set_style(
+                Rect {
+                    y,
+                    height: 1,
+                    ..area
+                },
+                Style::default().bg(selected_bg()),
+            );
+            buf.set_string(
+                margin_x,
+                y,
+                SELECTED_MARKER,
+                Style::default().fg(accent_primary()),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::components::ChatMessage;
+
+    #[test]
+    fn test_selection_moves_between_messages() {
+        let mut view = ChatView::new();
+        view.push(ChatMessage::user("question"));
+        view.push(ChatMessage::assistant("first answer"));
+        view.push(ChatMessage::assistant("second answer"));
+        let area = Rect::new(0, 0, 40, 20);
+
+        // Starts at the last message in view
+        assert!(view.select_adjacent_message(area, false, true));
+        assert_eq!(view.selected_message_content(), Some("second answer"));
+        assert!(view.select_adjacent_message(area, false, true));
+        assert!(view.select_adjacent_message(area, false, true));
+        assert_eq!(view.selected_message_content(), Some("question"));
+        // Stays on the first message
+        assert!(view.select_adjacent_message(area, false, true));
+        assert_eq!(view.selected_message(), Some(0));
+        assert!(view.select_adjacent_message(area, false, false));
+        assert_eq!(view.selected_message_content(), Some("first answer"));
+
+        let mut buf = Buffer::empty(area);
+        view.render_with_indicator(area, &mut buf, None, None, None, false);
+        let marked: Vec<String> = (0..area.height)
+            .filter(|&y| buf[(0, y)].symbol() == SELECTED_MARKER)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert!(!marked.is_empty());
+        assert!(marked.iter().all(|row| !row.contains("question")));
+        assert!(marked.iter().any(|row| row.contains("first answer")));
+
+        view.clear_message_selection();
+        assert_eq!(view.selected_message(), None);
+    }
+}
@@ -0,0 +1,53 @@
+//! Hand completed messages to the persistence queue, each exactly once.
+
+use std::ops::Range;
+
+use super::{ChatMessage, ChatView};
+
+impl ChatView {
+    /// Messages not yet handed out for persisting, oldest first; they're
+    /// marked persisted. Streaming output isn't included until it's final.
+    pub fn take_unpersisted(&mut self) -> Vec<ChatMessage> {
+        self.take_unpersisted_in(0..self.messages.len())
+    }
+
+    /// Like [`Self::take_unpersisted`], limited to `range`, e.g. messages
+    /// about to be offloaded
+    pub fn take_unpersisted_in(&mut self, range: Range<usize>) -> Vec<ChatMessage> {
+        let end = range.end.min(self.messages.len());
+        let start = range.start.min(end);
+        self.messages[start..end]
+            .iter_mut()
+            .filter(|msg| !msg.persisted && !msg.is_streaming)
+            .map(|msg| {
+                msg.persisted = true;
+                msg.clone()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages_are_handed_out_once() {
+        let mut view = ChatView::new();
+        view.push_history(ChatMessage::assistant("restored"));
+        view.push(ChatMessage::user("first"));
+        view.push(ChatMessage::assistant("second"));
+
+        let taken: Vec<String> = view
+            .take_unpersisted()
+            .into_iter()
+            .map(|msg| msg.content)
+            .collect();
+        assert_eq!(taken, ["first", "second"]);
+        assert!(view.take_unpersisted().is_empty());
+
+        view.push(ChatMessage::user("third"));
+        assert!(view.take_unpersisted_in(0..2).is_empty());
+        assert_eq!(view.take_unpersisted().len(), 1);
+    }
+}
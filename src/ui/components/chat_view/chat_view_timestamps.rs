@@ -0,0 +1,143 @@
+//! Message timestamps in the chat view gutter.
+//!
+//! Messages are stamped when they're pushed. While timestamps are shown, a
+//! gutter left of the messages holds the time next to each message's first
+//! line. Labels are drawn at render time, so relative times stay current
+//! without rebuilding the line cache.
+
+use chrono::{DateTime, Local, Utc};
+use ratatui::{buffer::Buffer, layout::Rect, style::Style};
+
+use super::ChatView;
+use crate::config::ChatTimestamps;
+use crate::ui::components::text_muted;
+
+/// Columns the gutter takes while timestamps are shown (label plus a gap)
+pub(super) const TIMESTAMP_GUTTER_WIDTH: u16 = 6;
+
+impl ChatView {
+    /// How message timestamps are shown
+    pub fn timestamps(&self) -> ChatTimestamps {
+        self.timestamps
+    }
+
+    /// Show or hide message timestamps
+    pub fn set_timestamps(&mut self, mode: ChatTimestamps) {
+        if self.timestamps == mode {
+            return;
+        }
+        self.timestamps = mode;
+        // The gutter changes the content width, so lines wrap differently
+        self.reset_line_caches();
+    }
+
+    /// Columns reserved left of the messages for timestamps
+    pub(super) fn timestamp_gutter_width(&self) -> u16 {
+        match self.timestamps {
+            ChatTimestamps::Off => 0,
+            ChatTimestamps::Absolute | ChatTimestamps::Relative => TIMESTAMP_GUTTER_WIDTH,
+        }
+    }
+
+    /// Draw the timestamp of every message starting within the rendered rows.
+    /// `first_line` is the flat cache line shown on the top row of `area`.
+    pub(super) fn render_timestamps(&self, first_line: usize, area: Rect, buf: &mut Buffer) {
+        let width = self.timestamp_gutter_width().saturating_sub(1);
+        if width == 0 || area.height == 0 {
+            return;
+        }
+        let last_line = first_line + area.height as usize;
+        let now = Utc::now();
+        let first = self
+            .message_line_starts
+            .partition_point(|&(line, _)| line < first_line);
+        for &(line, index) in self.message_line_starts[first..]
+            .iter()
+            .take_while(|&&(line, _)| line < last_line)
+        {
+            let Some(label) = self
+                .messages
+                .get(index)
+                .and_then(|msg| msg.timestamp)
+                .and_then(|timestamp| format_timestamp(self.timestamps, timestamp, now))
+            else {
+                continue;
+            };
+            buf.set_stringn(
+                area.x,
+                area.y + (line - first_line) as u16,
+                format!("{:>width$}", label, width = width as usize),
+                width as usize,
+                Style::default().fg(text_muted()),
+            );
+        }
+    }
+}
+
+/// Gutter label for a message stamped at `timestamp`: `14:05` (or `10-17`
+/// for an earlier day) when absolute, `now`/`12m`/`3h`/`5d` when relative
+fn format_timestamp(
+    mode: ChatTimestamps,
+    timestamp: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Option<String> {
+    match mode {
+        ChatTimestamps::Off => None,
+        ChatTimestamps::Absolute => {
+            let local = timestamp.with_timezone(&Local);
+            if local.date_naive() == now.with_timezone(&Local).date_naive() {
+                Some(local.format("%H:%M").to_string())
+            } else {
+                Some(local.format("%m-%d").to_string())
+            }
+        }
+        ChatTimestamps::Relative => {
+            let secs = (now - timestamp).num_seconds().max(0);
+            Some(match secs {
+                0..=59 => "now".to_string(),
+                60..=3_599 => format!("{}m", secs / 60),
+                3_600..=86_399 => format!("{}h", secs / 3_600),
+                _ => format!("{}d", secs / 86_400),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::components::ChatMessage;
+    use chrono::Duration;
+
+    #[test]
+    fn test_relative_labels() {
+        let now = Utc::now();
+        let label = |ago: Duration| format_timestamp(ChatTimestamps::Relative, now - ago, now);
+        assert_eq!(label(Duration::seconds(5)).as_deref(), Some("now"));
+        assert_eq!(label(Duration::minutes(12)).as_deref(), Some("12m"));
+        assert_eq!(label(Duration::hours(3)).as_deref(), Some("3h"));
+        assert_eq!(label(Duration::days(5)).as_deref(), Some("5d"));
+        assert_eq!(format_timestamp(ChatTimestamps::Off, now, now), None);
+    }
+
+    #[test]
+    fn test_push_stamps_messages_and_gutter_shows_them() {
+        let mut view = ChatView::new();
+        view.push_history(ChatMessage::assistant("restored"));
+        view.push(ChatMessage::user("hello"));
+        assert!(view.messages()[0].timestamp.is_none());
+        assert!(view.messages()[1].timestamp.is_some());
+
+        view.set_timestamps(ChatTimestamps::Relative);
+        let area = Rect::new(0, 0, 40, 6);
+        let mut buf = Buffer::empty(area);
+        view.render_with_indicator(area, &mut buf, None, None, None, false);
+        let rows: Vec<String> = (0..area.height)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        let stamped = rows.iter().find(|row| row.contains("hello")).unwrap();
+        assert!(stamped.starts_with("    now "), "{stamped:?}");
+        let restored = rows.iter().find(|row| row.contains("restored")).unwrap();
+        assert!(!restored.contains("now"), "{restored:?}");
+    }
+}
@@ -16,7 +16,7 @@ use super::{
     render_minimal_scrollbar, text_muted, text_primary, DialogFrame, SearchableListState,
 };
 use crate::config::keys::{KeyCombo, KeybindingConfig};
-use crate::ui::action::Action;
+use crate::ui::action::{Action, PALETTE_ACTIONS};
 
 /// A command entry in the palette
 #[derive(Debug, Clone)]
@@ -51,9 +51,15 @@ impl CommandPaletteState {
 
     /// Show the command palette and populate commands from keybindings
     /// `supports_plan_mode` controls whether the Build/Plan mode toggle is shown
-    pub fn show(&mut self, keybindings: &KeybindingConfig, supports_plan_mode: bool) {
+    /// `is_codex_session` controls whether the Codex sandbox/approval commands are shown
+    pub fn show(
+        &mut self,
+        keybindings: &KeybindingConfig,
+        supports_plan_mode: bool,
+        is_codex_session: bool,
+    ) {
         self.visible = true;
-        self.commands = Self::build_commands(keybindings, supports_plan_mode);
+        self.commands = Self::build_commands(keybindings, supports_plan_mode, is_codex_session);
         self.list.reset();
         // Initialize filtered list with all commands
         self.list.filtered = (0..self.commands.len()).collect();
@@ -73,6 +79,7 @@ impl CommandPaletteState {
     fn build_commands(
         keybindings: &KeybindingConfig,
         supports_plan_mode: bool,
+        is_codex_session: bool,
     ) -> Vec<CommandPaletteEntry> {
         // Build reverse lookup: Action discriminant -> key display string
         let mut keybinding_cache: HashMap<std::mem::Discriminant<Action>, String> = HashMap::new();
@@ -101,46 +108,22 @@ impl CommandPaletteState {
             }
         }
 
-        // Collect all actions that should appear in palette
-        let palette_actions: Vec<Action> = vec![
-            Action::Quit,
-            Action::ToggleSidebar,
-            Action::NewProject,
-            Action::NewWorkspaceUnderCursor,
-            Action::OpenPr,
-            Action::ForkSession,
-            Action::InterruptAgent,
-            Action::ToggleViewMode,
-            Action::ShowModelSelector,
-            Action::ToggleMetrics,
-            Action::DumpDebugState,
-            Action::OpenQueueEditor,
-            Action::CloseTab,
-            Action::NextTab,
-            Action::PrevTab,
-            Action::ScrollPageUp,
-            Action::ScrollPageDown,
-            Action::ScrollToTop,
-            Action::ScrollToBottom,
-            Action::EnterSidebarMode,
-            Action::AddRepository,
-            Action::OpenSettings,
-            Action::ArchiveOrRemove,
-            Action::ToggleAgentMode,
-            Action::OpenSessionImport,
-            Action::ShowHelp,
-        ];
-
-        let mut entries: Vec<CommandPaletteEntry> = palette_actions
-            .into_iter()
-            .filter(|a| a.show_in_palette())
+        let mut entries: Vec<CommandPaletteEntry> = PALETTE_ACTIONS
+            .iter()
             .filter(|a| {
                 // Hide mode toggle when agent doesn't support plan mode
                 if matches!(a, Action::ToggleAgentMode) && !supports_plan_mode {
                     return false;
                 }
+                // Sandbox/approval overrides only apply to Codex sessions
+                if matches!(a, Action::CycleCodexSandbox | Action::CycleCodexApproval)
+                    && !is_codex_session
+                {
+                    return false;
+                }
                 true
             })
+            .cloned()
             .map(|action| {
                 let key = std::mem::discriminant(&action);
                 let keybinding = keybinding_cache.get(&key).cloned();
@@ -162,23 +145,22 @@ impl CommandPaletteState {
         entries
     }
 
-    /// Filter commands based on search query
+    /// Filter commands by fuzzy-matching the search query against their
+    /// descriptions, best matches first
     pub fn filter(&mut self) {
         let query = self.list.search.value().to_lowercase();
-        let filtered: Vec<usize> = self
+        let mut scored: Vec<(i32, usize)> = self
             .commands
             .iter()
             .enumerate()
-            .filter(|(_, cmd)| {
-                if query.is_empty() {
-                    true
-                } else {
-                    cmd.description.to_lowercase().contains(&query)
-                }
-            })
-            .map(|(i, _)| i)
+            .filter_map(|(i, cmd)| Some((fuzzy_score(&query, &cmd.description)?, i)))
             .collect();
-        self.list.set_filtered(filtered);
+        // Stable sort keeps the alphabetical order among equal scores
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        // The ranking changes with every keystroke; keep the best match selected
+        self.list.selected = 0;
+        self.list
+            .set_filtered(scored.into_iter().map(|(_, i)| i).collect());
     }
 
     /// Insert a character into the search field
@@ -213,6 +195,44 @@ impl CommandPaletteState {
     }
 }
 
+/// Score `text` against a lowercase `query` whose characters must all appear
+/// in order. Matches at word starts and runs of consecutive characters score
+/// higher, so "tdv" ranks "Toggle diff view" first.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    let mut query_chars = query.chars().filter(|c| !c.is_whitespace()).peekable();
+    let mut score = 0;
+    let mut prev: Option<char> = None;
+    let mut prev_matched = false;
+    for c in text.chars() {
+        let Some(&wanted) = query_chars.peek() else {
+            break;
+        };
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        if lower == wanted {
+            query_chars.next();
+            score += 1;
+            if !matches!(prev, Some(p) if p.is_alphanumeric()) {
+                score += 8;
+            }
+            if prev_matched {
+                score += 4;
+            }
+            prev_matched = true;
+        } else {
+            prev_matched = false;
+        }
+        prev = Some(c);
+    }
+    if query_chars.peek().is_some() {
+        return None;
+    }
+    // Prefer shorter descriptions among otherwise equal matches
+    Some(score * 4 - text.chars().count() as i32 / 8)
+}
+
 fn truncate_to_width(s: &str, max_width: usize) -> String {
     if max_width == 0 {
         return String::new();
@@ -483,7 +503,7 @@ mod tests {
     #[test]
     fn test_command_palette_shows_toggle_mode_when_supported() {
         let mut state = CommandPaletteState::new();
-        state.show(&default_keybindings(), true);
+        state.show(&default_keybindings(), true, false);
 
         let has_toggle_mode = state
             .commands
@@ -499,7 +519,7 @@ mod tests {
     #[test]
     fn test_command_palette_hides_toggle_mode_when_not_supported() {
         let mut state = CommandPaletteState::new();
-        state.show(&default_keybindings(), false);
+        state.show(&default_keybindings(), false, false);
 
         let has_toggle_mode = state
             .commands
@@ -517,7 +537,7 @@ mod tests {
         let mut state = CommandPaletteState::new();
 
         // With plan mode not supported
-        state.show(&default_keybindings(), false);
+        state.show(&default_keybindings(), false, false);
 
         // Common commands should still be present
         let has_quit = state
@@ -532,4 +552,49 @@ mod tests {
         assert!(has_quit, "Quit should always be visible");
         assert!(has_help, "Help should always be visible");
     }
+
+    #[test]
+    fn test_command_palette_codex_commands_only_for_codex_sessions() {
+        let mut state = CommandPaletteState::new();
+        let has_codex_commands = |state: &CommandPaletteState| {
+            state.commands.iter().any(|cmd| {
+                matches!(
+                    cmd.action,
+                    Action::CycleCodexSandbox | Action::CycleCodexApproval
+                )
+            })
+        };
+
+        state.show(&default_keybindings(), true, false);
+        assert!(!has_codex_commands(&state));
+
+        state.show(&default_keybindings(), false, true);
+        assert!(has_codex_commands(&state));
+    }
+
+    #[test]
+    fn test_command_palette_fuzzy_ranks_word_starts_first() {
+        let mut state = CommandPaletteState::new();
+        state.show(&default_keybindings(), true, false);
+
+        for c in "tdv".chars() {
+            state.insert_char(c);
+        }
+        assert!(matches!(
+            state.selected_entry().map(|cmd| &cmd.action),
+            Some(Action::ToggleDiffView)
+        ));
+
+        for c in "qqq".chars() {
+            state.insert_char(c);
+        }
+        assert!(state.selected_entry().is_none());
+    }
+
+    #[test]
+    fn test_command_palette_lists_every_palette_action() {
+        let mut state = CommandPaletteState::new();
+        state.show(&default_keybindings(), true, true);
+        assert_eq!(state.commands.len(), PALETTE_ACTIONS.len());
+    }
 }
@@ -12,7 +12,9 @@ use ratatui::{
 use uuid::Uuid;
 
 use super::dialog::DialogFrame;
-use crate::git::PrPreflightResult;
+use super::{accent_error, accent_primary, accent_warning, bg_base, text_primary, text_secondary};
+use crate::core::security_scan::SecurityScanReport;
+use crate::git::{LinkedPrPreflight, PrPreflightResult};
 
 /// Confirmation type determines the dialog's appearance and urgency level
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -38,6 +40,25 @@ pub enum ConfirmationContext {
         tab_index: usize,
         working_dir: PathBuf,
         preflight: PrPreflightResult,
+        /// Linked repositories that get PRs of their own
+        linked: Vec<LinkedPrPreflight>,
+    },
+    /// Acknowledging flagged changed files before a PR is offered
+    AcknowledgeChangeFlags {
+        tab_index: usize,
+        working_dir: PathBuf,
+        preflight: PrPreflightResult,
+        linked: Vec<LinkedPrPreflight>,
+        /// Scan results still to be shown once the flags are acknowledged
+        security: Option<SecurityScanReport>,
+        /// Keys of the flags being acknowledged
+        keys: Vec<String>,
+    },
+    /// Asking the agent to fix security findings that block a PR
+    FixSecurityFindings {
+        tab_index: usize,
+        working_dir: PathBuf,
+        prompt: String,
     },
     /// Opening an existing PR in browser
     OpenExistingPr {
@@ -56,24 +77,32 @@ pub enum ConfirmationContext {
     SelectWorkspaceMode { repo_id: Uuid },
     /// Confirm whether to delete a remote branch after archive
     ArchiveWorkspaceRemoteDelete { workspace_id: Uuid },
+    /// Rolling the workspace back to before a past prompt
+    RollbackPrompt {
+        session_id: Uuid,
+        /// Position of the prompt among the session's user messages
+        prompt_index: usize,
+    },
+    /// Quitting while agents are still running; Cancel waits for them instead
+    QuitWithRunningAgents,
 }
 
 impl ConfirmationType {
     /// Get the border color for this confirmation type
     pub fn border_color(&self) -> Color {
         match self {
-            ConfirmationType::Info => Color::Cyan,
-            ConfirmationType::Warning => Color::Yellow,
-            ConfirmationType::Danger => Color::Red,
+            ConfirmationType::Info => accent_primary(),
+            ConfirmationType::Warning => accent_warning(),
+            ConfirmationType::Danger => accent_error(),
         }
     }
 
     /// Get the warning icon color
     pub fn warning_color(&self) -> Color {
         match self {
-            ConfirmationType::Info => Color::Cyan,
-            ConfirmationType::Warning => Color::Yellow,
-            ConfirmationType::Danger => Color::Red,
+            ConfirmationType::Info => accent_primary(),
+            ConfirmationType::Warning => accent_warning(),
+            ConfirmationType::Danger => accent_error(),
         }
     }
 }
@@ -264,7 +293,7 @@ impl Widget for ConfirmationDialog<'_> {
 
             // Render dialog frame (instructions on bottom border)
             let frame = DialogFrame::new(&self.state.title, dialog_width, dialog_height)
-                .border_color(Color::Cyan)
+                .border_color(accent_primary())
                 .instructions(vec![("Esc", "Cancel")]);
             let inner = frame.render(area, buf);
 
@@ -277,11 +306,11 @@ impl Widget for ConfirmationDialog<'_> {
             let loading_line = Line::from(vec![
                 Span::styled(
                     format!("{} ", spinner_char),
-                    Style::default().fg(Color::Cyan),
+                    Style::default().fg(accent_primary()),
                 ),
                 Span::styled(
                     self.state.loading_message.as_str(),
-                    Style::default().fg(Color::White),
+                    Style::default().fg(text_primary()),
                 ),
             ]);
 
@@ -375,16 +404,16 @@ impl Widget for ConfirmationDialog<'_> {
         if buttons_y >= inner.y + y_offset {
             let cancel_style = if self.state.is_cancel_selected() {
                 Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::White)
+                    .fg(bg_base())
+                    .bg(text_primary())
                     .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::Gray)
+                Style::default().fg(text_secondary())
             };
 
             let confirm_style = if self.state.is_confirm_selected() {
                 Style::default()
-                    .fg(Color::Black)
+                    .fg(bg_base())
                     .bg(self.state.confirmation_type.border_color())
                     .add_modifier(Modifier::BOLD)
             } else {
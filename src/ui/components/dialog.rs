@@ -9,7 +9,9 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph, Widget},
 };
 
-use super::{accent_primary, dialog_bg, render_key_hints, KeyHintBarStyle};
+use super::{
+    accent_error, accent_primary, accent_success, dialog_bg, render_key_hints, KeyHintBarStyle,
+};
 // Re-export Widget for use in render methods
 pub use ratatui::widgets::Widget as WidgetTrait;
 
@@ -196,13 +198,13 @@ impl<'a> StatusLine<'a> {
     pub fn render(&self, area: Rect, buf: &mut Buffer) {
         let line = if let Some(error) = self.error {
             Line::from(vec![
-                Span::styled("✗ ", Style::default().fg(Color::Red)),
-                Span::styled(error, Style::default().fg(Color::Red)),
+                Span::styled("✗ ", Style::default().fg(accent_error())),
+                Span::styled(error, Style::default().fg(accent_error())),
             ])
         } else if let Some(success) = self.success {
             Line::from(vec![
-                Span::styled("✓ ", Style::default().fg(Color::Green)),
-                Span::styled(success, Style::default().fg(Color::Green)),
+                Span::styled("✓ ", Style::default().fg(accent_success())),
+                Span::styled(success, Style::default().fg(accent_success())),
             ])
         } else {
             Line::default()
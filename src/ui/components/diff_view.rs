@@ -0,0 +1,499 @@
+//! Unified diffs for file edit tool calls, and the full-screen diff view
+//!
+//! Edit and Write calls carry the text they change in their arguments, so the
+//! diff is rebuilt from those rather than read back from disk. Line numbers
+//! in `Edit` hunks are relative to the replaced snippet, and `Write` has no
+//! old text, so it shows as all additions.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use serde_json::Value;
+
+use super::{
+    accent_primary, accent_warning, diff_add, diff_remove, text_muted, tool_comment, tool_output,
+    ChatMessage, MessageRole,
+};
+use crate::agent::edit_review::diff_hunks;
+use crate::core::change_guard::ChangeFlag;
+
+/// Tools whose completed calls are shown as diffs
+pub const DIFF_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write"];
+
+/// Unchanged lines shown around each change
+const CONTEXT_LINES: usize = 3;
+
+/// Whether completed calls to `tool_name` are shown as diffs
+pub fn is_diff_tool(tool_name: &str) -> bool {
+    DIFF_TOOLS.contains(&tool_name)
+}
+
+/// Whether tool output is a diff produced by [`ToolDiff::to_unified`]
+pub fn is_unified_diff(content: &str) -> bool {
+    content.starts_with("@@ ")
+}
+
+/// Added and removed line counts of a unified diff
+pub fn unified_stats(content: &str) -> (usize, usize) {
+    content
+        .lines()
+        .fold((0, 0), |(added, removed), line| match line.chars().next() {
+            Some('+') => (added + 1, removed),
+            Some('-') => (added, removed + 1),
+            _ => (added, removed),
+        })
+}
+
+/// Kind of a line in a unified diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    /// `@@ -a,b +c,d @@` hunk header
+    Header,
+    Context,
+    Added,
+    Removed,
+}
+
+impl DiffLineKind {
+    /// Classify a line of [`ToolDiff::to_unified`] output
+    pub fn of(line: &str) -> Self {
+        match line.chars().next() {
+            Some('@') => DiffLineKind::Header,
+            Some('+') => DiffLineKind::Added,
+            Some('-') => DiffLineKind::Removed,
+            _ => DiffLineKind::Context,
+        }
+    }
+
+    /// Foreground color for lines of this kind
+    pub fn color(self) -> Color {
+        match self {
+            DiffLineKind::Header => tool_comment(),
+            DiffLineKind::Context => tool_output(),
+            DiffLineKind::Added => diff_add(),
+            DiffLineKind::Removed => diff_remove(),
+        }
+    }
+
+    fn marker(self) -> char {
+        match self {
+            DiffLineKind::Header => '@',
+            DiffLineKind::Context => ' ',
+            DiffLineKind::Added => '+',
+            DiffLineKind::Removed => '-',
+        }
+    }
+}
+
+/// One line of a diff, without its line ending
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub text: String,
+}
+
+impl DiffLine {
+    fn new(kind: DiffLineKind, text: &str) -> Self {
+        Self {
+            kind,
+            text: text.trim_end_matches(['\n', '\r']).to_string(),
+        }
+    }
+}
+
+/// The change made by one edit tool call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolDiff {
+    /// `file_path` argument of the call
+    pub path: String,
+    pub lines: Vec<DiffLine>,
+}
+
+impl ToolDiff {
+    /// Rebuild the change from an edit tool's JSON arguments.
+    ///
+    /// Returns `None` for other tools, unparseable arguments or calls that
+    /// change nothing.
+    pub fn from_tool_args(tool_name: &str, tool_args: &str) -> Option<Self> {
+        if !is_diff_tool(tool_name) {
+            return None;
+        }
+        let args: Value = serde_json::from_str(tool_args).ok()?;
+        let path = args.get("file_path")?.as_str()?.to_string();
+        let str_arg = |value: &Value, key: &str| value.get(key)?.as_str().map(str::to_string);
+
+        let lines = match tool_name {
+            "Write" => unified_lines("", &str_arg(&args, "content")?),
+            "Edit" => unified_lines(
+                &str_arg(&args, "old_string")?,
+                &str_arg(&args, "new_string")?,
+            ),
+            _ => {
+                let mut lines = Vec::new();
+                for edit in args.get("edits")?.as_array()? {
+                    lines.extend(unified_lines(
+                        &str_arg(edit, "old_string")?,
+                        &str_arg(edit, "new_string")?,
+                    ));
+                }
+                lines
+            }
+        };
+        (!lines.is_empty()).then_some(Self { path, lines })
+    }
+
+    pub fn added(&self) -> usize {
+        self.count(DiffLineKind::Added)
+    }
+
+    pub fn removed(&self) -> usize {
+        self.count(DiffLineKind::Removed)
+    }
+
+    fn count(&self, kind: DiffLineKind) -> usize {
+        self.lines.iter().filter(|line| line.kind == kind).count()
+    }
+
+    /// The diff as unified diff text (hunks only, no file headers)
+    pub fn to_unified(&self) -> String {
+        let mut text = String::new();
+        for line in &self.lines {
+            if line.kind != DiffLineKind::Header {
+                text.push(line.kind.marker());
+            }
+            text.push_str(&line.text);
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// Unified diff lines of `old` against `new`; changes closer than twice the
+/// context share a hunk, as in `diff -u`
+fn unified_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let hunks = diff_hunks(old, new);
+    let range = |start: usize, count: usize| match count {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, count),
+    };
+
+    let mut lines = Vec::new();
+    // Lines added minus lines removed by earlier hunks
+    let mut offset = 0isize;
+    let mut first = 0;
+    while first < hunks.len() {
+        let end_of = |index: usize| hunks[index].old_start + hunks[index].old_lines.len();
+        let mut last = first;
+        while last + 1 < hunks.len()
+            && hunks[last + 1].old_start - end_of(last) <= 2 * CONTEXT_LINES
+        {
+            last += 1;
+        }
+
+        let start = hunks[first].old_start.saturating_sub(CONTEXT_LINES);
+        let end = (end_of(last) + CONTEXT_LINES).min(old_lines.len());
+        let mut body = Vec::new();
+        let mut cursor = start;
+        let mut delta = 0isize;
+        for hunk in &hunks[first..=last] {
+            body.extend(
+                old_lines[cursor..hunk.old_start]
+                    .iter()
+                    .map(|text| DiffLine::new(DiffLineKind::Context, text)),
+            );
+            body.extend(
+                hunk.old_lines
+                    .iter()
+                    .map(|text| DiffLine::new(DiffLineKind::Removed, text)),
+            );
+            body.extend(
+                hunk.new_lines
+                    .iter()
+                    .map(|text| DiffLine::new(DiffLineKind::Added, text)),
+            );
+            delta += hunk.new_lines.len() as isize - hunk.old_lines.len() as isize;
+            cursor = hunk.old_start + hunk.old_lines.len();
+        }
+        body.extend(
+            old_lines[cursor..end]
+                .iter()
+                .map(|text| DiffLine::new(DiffLineKind::Context, text)),
+        );
+
+        let old_count = end - start;
+        let new_count = (old_count as isize + delta) as usize;
+        let new_start = (start as isize + offset) as usize;
+        lines.push(DiffLine {
+            kind: DiffLineKind::Header,
+            text: format!(
+                "@@ -{} +{} @@",
+                range(start, old_count),
+                range(new_start, new_count)
+            ),
+        });
+        lines.extend(body);
+
+        offset += delta;
+        first = last + 1;
+    }
+    lines
+}
+
+/// Edits of one session, shown together in the diff view
+#[derive(Debug, Default)]
+pub struct DiffViewState {
+    /// Completed edits, oldest first
+    diffs: Vec<ToolDiff>,
+    /// Changed files flagged by the PR preflight, and whether each was
+    /// acknowledged; listed above the edits
+    flags: Vec<(ChangeFlag, bool)>,
+    /// First visible row
+    scroll_offset: usize,
+}
+
+impl DiffViewState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collect the completed edits in `messages` and scroll to the latest
+    pub fn load(&mut self, messages: &[ChatMessage]) {
+        self.diffs = messages
+            .iter()
+            .filter(|msg| msg.role == MessageRole::Tool && is_unified_diff(&msg.content))
+            .filter_map(|msg| {
+                ToolDiff::from_tool_args(msg.tool_name.as_deref()?, msg.tool_args.as_deref()?)
+            })
+            .collect();
+        let latest_rows = self.diffs.last().map_or(0, |diff| diff.lines.len() + 1);
+        self.scroll_offset = self.row_count().saturating_sub(latest_rows);
+    }
+
+    pub fn diffs(&self) -> &[ToolDiff] {
+        &self.diffs
+    }
+
+    /// Show flagged changes above the edits, keeping the same edits in view
+    pub fn set_flags(&mut self, flags: Vec<(ChangeFlag, bool)>) {
+        let previous_rows = self.flag_rows();
+        self.flags = flags;
+        self.scroll_offset = (self.scroll_offset + self.flag_rows()).saturating_sub(previous_rows);
+    }
+
+    /// Section title plus one row per flag, then a blank row
+    fn flag_rows(&self) -> usize {
+        if self.flags.is_empty() {
+            0
+        } else {
+            self.flags.len() + 2
+        }
+    }
+
+    /// Flagged changes, then file header plus diff lines per edit, with a
+    /// blank row between edits
+    fn row_count(&self) -> usize {
+        let rows: usize = self.diffs.iter().map(|diff| diff.lines.len() + 2).sum();
+        rows.saturating_sub(1) + self.flag_rows()
+    }
+
+    pub fn scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+
+    pub fn scroll_up(&mut self, rows: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(rows);
+    }
+
+    pub fn scroll_down(&mut self, rows: usize) {
+        let max = self.row_count().saturating_sub(1);
+        self.scroll_offset = (self.scroll_offset + rows).min(max);
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_offset = 0;
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_offset = self.row_count().saturating_sub(1);
+    }
+}
+
+/// Full-screen view of every edit in the session
+pub struct DiffView<'a> {
+    state: &'a DiffViewState,
+}
+
+impl<'a> DiffView<'a> {
+    pub fn new(state: &'a DiffViewState) -> Self {
+        Self { state }
+    }
+
+    fn file_header(diff: &ToolDiff) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(
+                diff.path.clone(),
+                Style::default()
+                    .fg(accent_primary())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  +{}", diff.added()),
+                Style::default().fg(diff_add()),
+            ),
+            Span::styled(
+                format!(" -{}", diff.removed()),
+                Style::default().fg(diff_remove()),
+            ),
+        ])
+    }
+}
+
+impl Widget for DiffView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        if self.state.diffs.is_empty() && self.state.flags.is_empty() {
+            Paragraph::new(Line::from(Span::styled(
+                "No file edits yet. Diffs of Edit and Write calls appear here once they complete.",
+                Style::default().fg(text_muted()),
+            )))
+            .render(area, buf);
+            return;
+        }
+
+        let mut rows = Vec::with_capacity(self.state.row_count() + 1);
+        if !self.state.flags.is_empty() {
+            rows.push(Line::from(Span::styled(
+                "Flagged before creating a PR",
+                Style::default()
+                    .fg(accent_warning())
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for (flag, acknowledged) in &self.state.flags {
+                let (text, color) = if *acknowledged {
+                    (
+                        format!("  {} (acknowledged)", flag.describe()),
+                        text_muted(),
+                    )
+                } else {
+                    (format!("! {}", flag.describe()), accent_warning())
+                };
+                rows.push(Line::from(Span::styled(text, Style::default().fg(color))));
+            }
+            rows.push(Line::from(""));
+        }
+        for (index, diff) in self.state.diffs.iter().enumerate() {
+            if index > 0 {
+                rows.push(Line::from(""));
+            }
+            rows.push(Self::file_header(diff));
+            rows.extend(diff.lines.iter().map(|line| {
+                let text = match line.kind {
+                    DiffLineKind::Header => line.text.clone(),
+                    kind => format!("{}{}", kind.marker(), line.text),
+                };
+                Line::from(Span::styled(text, Style::default().fg(line.kind.color())))
+            }));
+        }
+
+        let visible: Vec<Line<'static>> = rows
+            .into_iter()
+            .skip(self.state.scroll_offset)
+            .take(area.height as usize)
+            .collect();
+        Paragraph::new(visible).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(diff: &ToolDiff) -> Vec<String> {
+        diff.to_unified().lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_edit_args_become_unified_diff() {
+        let args = serde_json::json!({
+            "file_path": "src/lib.rs",
+            "old_string": "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\nn",
+            "new_string": "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\nn\no",
+        })
+        .to_string();
+        let diff = ToolDiff::from_tool_args("Edit", &args).unwrap();
+        assert_eq!(diff.path, "src/lib.rs");
+        assert_eq!((diff.added(), diff.removed()), (3, 2));
+        assert_eq!(
+            texts(&diff),
+            vec![
+                "@@ -1,5 +1,5 @@",
+                " a",
+                "-b",
+                "+B",
+                " c",
+                " d",
+                " e",
+                "@@ -11,4 +11,5 @@",
+                " k",
+                " l",
+                " m",
+                "-n",
+                "+n",
+                "+o",
+            ]
+        );
+        assert!(is_unified_diff(&diff.to_unified()));
+        assert_eq!(unified_stats(&diff.to_unified()), (3, 2));
+    }
+
+    #[test]
+    fn test_write_is_all_additions() {
+        let args = serde_json::json!({ "file_path": "new.rs", "content": "fn main() {}\n" });
+        let diff = ToolDiff::from_tool_args("Write", &args.to_string()).unwrap();
+        assert_eq!(texts(&diff), vec!["@@ -0,0 +1 @@", "+fn main() {}"]);
+
+        let unchanged = serde_json::json!({
+            "file_path": "a.rs",
+            "old_string": "x",
+            "new_string": "x",
+        });
+        assert_eq!(
+            ToolDiff::from_tool_args("Edit", &unchanged.to_string()),
+            None
+        );
+        assert_eq!(ToolDiff::from_tool_args("Bash", "{}"), None);
+    }
+
+    #[test]
+    fn test_view_opens_at_latest_edit() {
+        let mut first = ChatMessage::tool(
+            "Write",
+            serde_json::json!({ "file_path": "a.rs", "content": "1\n2\n3\n" }).to_string(),
+            "",
+        );
+        first.content = ToolDiff::from_tool_args("Write", first.tool_args.as_deref().unwrap())
+            .unwrap()
+            .to_unified();
+        let mut second = first.clone();
+        second.tool_args =
+            Some(serde_json::json!({ "file_path": "b.rs", "content": "x\n" }).to_string());
+        let failed = ChatMessage::tool("Write", first.tool_args.clone().unwrap(), "Error: denied");
+
+        let mut state = DiffViewState::new();
+        state.load(&[first, failed, second]);
+        assert_eq!(state.diffs().len(), 2);
+        // a.rs: header + 4 diff lines, blank, then b.rs starts
+        assert_eq!(state.scroll_offset(), 6);
+        state.scroll_down(100);
+        assert_eq!(state.scroll_offset(), 8);
+    }
+}
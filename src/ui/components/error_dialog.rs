@@ -3,12 +3,13 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Paragraph, Widget, Wrap},
 };
 
 use super::dialog::DialogFrame;
+use super::{accent_error, bg_base, text_muted};
 
 /// State for the error dialog
 #[derive(Debug, Clone, Default)]
@@ -159,7 +160,7 @@ impl Widget for ErrorDialog<'_> {
             vec![("Enter/Esc", "Dismiss")]
         };
         let frame = DialogFrame::new(&self.state.title, dialog_width, dialog_height)
-            .border_color(Color::Red)
+            .border_color(accent_error())
             .instructions(instructions);
         let inner = frame.render(area, buf);
 
@@ -198,10 +199,8 @@ impl Widget for ErrorDialog<'_> {
             } else {
                 "▶ Details (press 'd' to show)"
             };
-            let toggle_line = Line::from(Span::styled(
-                toggle_text,
-                Style::default().fg(Color::DarkGray),
-            ));
+            let toggle_line =
+                Line::from(Span::styled(toggle_text, Style::default().fg(text_muted())));
             let toggle_para = Paragraph::new(toggle_line).alignment(Alignment::Center);
             if inner.y + y_offset < inner.y + inner.height.saturating_sub(4) {
                 toggle_para.render(
@@ -220,7 +219,7 @@ impl Widget for ErrorDialog<'_> {
             if self.state.details_expanded {
                 let details_lines = self.calculate_details_lines(dialog_width);
                 let details_para = Paragraph::new(details.as_str())
-                    .style(Style::default().fg(Color::DarkGray))
+                    .style(Style::default().fg(text_muted()))
                     .wrap(Wrap { trim: true });
                 if inner.y + y_offset < inner.y + inner.height.saturating_sub(4) {
                     details_para.render(
@@ -241,8 +240,8 @@ impl Widget for ErrorDialog<'_> {
         let button_y = inner.y + inner.height.saturating_sub(4);
         if button_y >= inner.y + y_offset {
             let button_style = Style::default()
-                .fg(Color::Black)
-                .bg(Color::Red)
+                .fg(bg_base())
+                .bg(accent_error())
                 .add_modifier(Modifier::BOLD);
 
             let button_line = Line::from(Span::styled("  OK  ", button_style));
@@ -5,7 +5,12 @@ use ratatui::{
     text::{Line, Span},
 };
 
+use crossterm::event::{KeyCode, KeyModifiers};
+use unicode_width::UnicodeWidthStr;
+
 use super::KnightRiderSpinner;
+use crate::config::{parse_key_notation, KeyCombo, KeyContext, KeybindingConfig};
+use crate::ui::action::Action;
 use crate::ui::components::{render_key_hints_responsive, text_muted, KeyHintBarStyle};
 use crate::ui::events::{InputMode, ViewMode};
 
@@ -17,10 +22,18 @@ pub enum FooterContext {
     /// Normal chat mode with tabs
     #[default]
     Chat,
+    /// Scrolling through chat history
+    Scrolling,
+    /// Selecting a chat message to copy
+    MessageSelect,
     /// Sidebar navigation mode
     Sidebar,
     /// Raw events view mode
     RawEvents,
+    /// Timeline view mode
+    Timeline,
+    /// Diff view mode
+    Diff,
     /// File viewer mode
     FileViewer,
 }
@@ -34,58 +47,233 @@ impl FooterContext {
 
         match view_mode {
             ViewMode::RawEvents => FooterContext::RawEvents,
-            ViewMode::Chat => {
-                if input_mode == InputMode::SidebarNavigation {
-                    FooterContext::Sidebar
-                } else {
-                    FooterContext::Chat
-                }
-            }
+            ViewMode::Timeline => FooterContext::Timeline,
+            ViewMode::Diff => FooterContext::Diff,
+            ViewMode::Chat => match input_mode {
+                InputMode::SidebarNavigation => FooterContext::Sidebar,
+                InputMode::Scrolling => FooterContext::Scrolling,
+                InputMode::SelectingMessage => FooterContext::MessageSelect,
+                _ => FooterContext::Chat,
+            },
+        }
+    }
+
+    /// Keymap context the hinted keys are dispatched in
+    pub fn key_context(self) -> KeyContext {
+        match self {
+            FooterContext::Empty | FooterContext::Chat => KeyContext::Chat,
+            FooterContext::Scrolling | FooterContext::FileViewer => KeyContext::Scrolling,
+            FooterContext::MessageSelect => KeyContext::MessageSelect,
+            FooterContext::Sidebar => KeyContext::Sidebar,
+            FooterContext::RawEvents => KeyContext::RawEvents,
+            FooterContext::Timeline => KeyContext::Timeline,
+            FooterContext::Diff => KeyContext::DiffView,
         }
     }
+
+    /// Hints for this context: (default key, action) pairs joined with '/', and a label
+    fn hint_specs(self) -> Vec<(Vec<(&'static str, Action)>, &'static str)> {
+        match self {
+            FooterContext::Empty => vec![
+                (vec![("C-n", Action::NewProject)], "new project"),
+                (vec![("C-t", Action::ToggleSidebar)], "sidebar"),
+                (vec![("M-i", Action::OpenSessionImport)], "import session"),
+                (vec![("C-q", Action::Quit)], "quit"),
+            ],
+            FooterContext::Chat => vec![
+                (vec![("M-<Tab>", Action::NextTab)], "next tab"),
+                (vec![("C-o", Action::ShowModelSelector)], "model"),
+                (vec![("C-t", Action::ToggleSidebar)], "sidebar"),
+                (vec![("C-n", Action::NewProject)], "new project"),
+                (vec![("M-S-w", Action::CloseTab)], "close"),
+                (vec![("C-c", Action::InterruptAgent)], "stop"),
+                (vec![("C-q", Action::Quit)], "quit"),
+            ],
+            FooterContext::Scrolling => vec![
+                (
+                    vec![("j", Action::ScrollDown(1)), ("k", Action::ScrollUp(1))],
+                    "scroll",
+                ),
+                (
+                    vec![("g", Action::ScrollToTop), ("G", Action::ScrollToBottom)],
+                    "top/bottom",
+                ),
+                (
+                    vec![
+                        ("<PageDown>", Action::ScrollPageDown),
+                        ("<PageUp>", Action::ScrollPageUp),
+                    ],
+                    "page",
+                ),
+                (vec![("v", Action::SelectMessages)], "select message"),
+                (vec![("<Esc>", Action::Cancel)], "exit"),
+            ],
+            FooterContext::MessageSelect => vec![
+                (
+                    vec![("j", Action::SelectNext), ("k", Action::SelectPrev)],
+                    "select",
+                ),
+                (vec![("y", Action::CopySelectedMessage)], "copy"),
+                (vec![("<Esc>", Action::Cancel)], "exit"),
+            ],
+            FooterContext::Sidebar => vec![
+                (
+                    vec![("j", Action::SelectNext), ("k", Action::SelectPrev)],
+                    "navigate",
+                ),
+                (vec![("<CR>", Action::ExpandOrSelect)], "select"),
+                (
+                    vec![("h", Action::Collapse), ("l", Action::ExpandOrSelect)],
+                    "collapse/expand",
+                ),
+                (vec![("r", Action::AddRepository)], "add repo"),
+                (vec![("C-n", Action::NewProject)], "new project"),
+                (vec![("<Esc>", Action::ExitSidebarMode)], "exit"),
+            ],
+            FooterContext::RawEvents => vec![
+                (
+                    vec![
+                        ("j", Action::RawEventsSelectNext),
+                        ("k", Action::RawEventsSelectPrev),
+                    ],
+                    "nav",
+                ),
+                (vec![("e", Action::EventDetailToggle)], "detail"),
+                (vec![("f", Action::EventDetailFilter)], "filter"),
+                (vec![("/", Action::RawEventsSearch)], "search"),
+                (
+                    vec![
+                        ("z", Action::EventDetailFold),
+                        ("Z", Action::EventDetailUnfold),
+                    ],
+                    "fold",
+                ),
+                (
+                    vec![
+                        ("C-j", Action::EventDetailScrollDown),
+                        ("C-k", Action::EventDetailScrollUp),
+                    ],
+                    "panel",
+                ),
+                (vec![("c", Action::EventDetailCopy)], "copy"),
+                (vec![("C-g", Action::ToggleViewMode)], "chat"),
+            ],
+            FooterContext::Timeline => vec![
+                (
+                    vec![("j", Action::ScrollDown(1)), ("k", Action::ScrollUp(1))],
+                    "scroll",
+                ),
+                (
+                    vec![("g", Action::ScrollToTop), ("G", Action::ScrollToBottom)],
+                    "top/bottom",
+                ),
+                (vec![("M-l", Action::ToggleTimeline)], "chat"),
+            ],
+            FooterContext::Diff => vec![
+                (
+                    vec![("j", Action::ScrollDown(1)), ("k", Action::ScrollUp(1))],
+                    "scroll",
+                ),
+                (
+                    vec![("g", Action::ScrollToTop), ("G", Action::ScrollToBottom)],
+                    "top/bottom",
+                ),
+                (vec![("M-o", Action::ToggleDiffView)], "chat"),
+            ],
+            FooterContext::FileViewer => vec![
+                (
+                    vec![("j", Action::ScrollDown(1)), ("k", Action::ScrollUp(1))],
+                    "scroll",
+                ),
+                (
+                    vec![("g", Action::ScrollToTop), ("G", Action::ScrollToBottom)],
+                    "top/bottom",
+                ),
+                (
+                    vec![
+                        ("<PageDown>", Action::ScrollPageDown),
+                        ("<PageUp>", Action::ScrollPageUp),
+                    ],
+                    "page",
+                ),
+                (vec![("M-<Tab>", Action::NextTab)], "next tab"),
+                (vec![("M-S-w", Action::CloseTab)], "close"),
+            ],
+        }
+    }
+}
+
+/// A footer hint resolved against the keymap
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FooterHint {
+    pub key: String,
+    pub label: &'static str,
+    /// Action run when the hint is clicked
+    pub action: Action,
 }
 
+/// Label for the hint shown when hints overflow the footer
+const MORE_LABEL: &str = "more";
+/// Gap between hints, matching [`KeyHintBarStyle::minimal_footer`]
+const HINT_GAP: usize = 3;
+/// Trailing padding, matching [`KeyHintBarStyle::minimal_footer`]
+const HINT_TRAILING: usize = 2;
+
 /// Global footer showing keyboard shortcuts in minimal style
 /// Layout: [Spinner][Message]                    [Key Hints (right-aligned)]
 pub struct GlobalFooter<'a> {
-    hints: Vec<(&'static str, &'static str)>,
+    hints: Vec<FooterHint>,
+    /// Shown in place of hints that don't fit
+    more_hint: Option<FooterHint>,
     spinner: Option<&'a KnightRiderSpinner>,
     message: Option<&'a str>,
 }
 
 impl<'a> GlobalFooter<'a> {
-    pub fn new() -> Self {
-        Self {
-            hints: Self::chat_hints(),
-            spinner: None,
-            message: None,
-        }
-    }
+    /// Create footer for a specific context, using the keys bound in `keymap`
+    pub fn for_context(context: FooterContext, keymap: &KeybindingConfig) -> Self {
+        let key_context = context.key_context();
+        let hints = context
+            .hint_specs()
+            .into_iter()
+            .filter_map(|(keys, label)| {
+                let action = keys.first()?.1.clone();
+                let key = keys
+                    .iter()
+                    .map(|(default_key, action)| {
+                        Self::resolve_key(keymap, key_context, default_key, action)
+                    })
+                    .collect::<Option<Vec<_>>>()?
+                    .join("/");
+                Some(FooterHint { key, label, action })
+            })
+            .collect();
+
+        // The help overlay lists everything; `:help` opens it when it has no key
+        let more_key = Self::resolve_key(keymap, key_context, ":help", &Action::ShowHelp)
+            .unwrap_or_else(|| ":help".to_string());
 
-    /// Create footer for a specific context
-    pub fn for_context(context: FooterContext) -> Self {
         Self {
-            hints: match context {
-                FooterContext::Empty => Self::empty_hints(),
-                FooterContext::Chat => Self::chat_hints(),
-                FooterContext::Sidebar => Self::sidebar_hints(),
-                FooterContext::RawEvents => Self::raw_events_hints(),
-                FooterContext::FileViewer => Self::file_viewer_hints(),
-            },
+            hints,
+            more_hint: Some(FooterHint {
+                key: more_key,
+                label: MORE_LABEL,
+                action: Action::ShowHelp,
+            }),
             spinner: None,
             message: None,
         }
     }
 
-    /// Create footer for file viewer context
-    pub fn file_viewer_context() -> Self {
-        Self::for_context(FooterContext::FileViewer)
-    }
-
     /// Create footer from app state
-    pub fn from_state(view_mode: ViewMode, input_mode: InputMode, has_tabs: bool) -> Self {
+    pub fn from_state(
+        view_mode: ViewMode,
+        input_mode: InputMode,
+        has_tabs: bool,
+        keymap: &KeybindingConfig,
+    ) -> Self {
         let context = FooterContext::from_state(view_mode, input_mode, has_tabs);
-        Self::for_context(context)
+        Self::for_context(context, keymap)
     }
 
     /// Set spinner for left side of footer
@@ -100,65 +288,89 @@ impl<'a> GlobalFooter<'a> {
         self
     }
 
-    /// Get hints for empty state (no tabs open)
-    pub fn empty_hints() -> Vec<(&'static str, &'static str)> {
-        vec![
-            ("C-n", "new project"),
-            ("C-t", "sidebar"),
-            ("M-i", "import session"),
-            ("C-q", "quit"),
-        ]
-    }
-
-    /// Get hints for chat mode
-    pub fn chat_hints() -> Vec<(&'static str, &'static str)> {
-        vec![
-            ("tab", "next tab"),
-            ("C-o", "model"),
-            ("C-t", "sidebar"),
-            ("C-n", "new project"),
-            ("M-S-w", "close"),
-            ("C-c", "stop"),
-            ("C-q", "quit"),
-        ]
-    }
-
-    /// Get hints for sidebar navigation mode
-    pub fn sidebar_hints() -> Vec<(&'static str, &'static str)> {
-        vec![
-            ("↑↓", "navigate"),
-            ("enter", "select"),
-            ("h/l", "collapse/expand"),
-            ("r", "add repo"),
-            ("C-n", "new project"),
-            ("esc", "exit"),
-        ]
-    }
-
-    /// Get hints for raw events view mode
-    pub fn raw_events_hints() -> Vec<(&'static str, &'static str)> {
-        vec![
-            ("j/k", "nav"),
-            ("e", "detail"),
-            ("C-j/k", "panel"),
-            ("c", "copy"),
-            ("C-g", "chat"),
-        ]
-    }
-
-    /// Get hints for file viewer mode
-    pub fn file_viewer_hints() -> Vec<(&'static str, &'static str)> {
-        vec![
-            ("j/k", "scroll"),
-            ("g/G", "top/bottom"),
-            ("C-d/u", "page"),
-            ("tab", "next tab"),
-            ("M-S-w", "close"),
-        ]
+    /// All hints for the current context, before fitting to the footer width
+    pub fn hints(&self) -> &[FooterHint] {
+        &self.hints
     }
 
-    pub fn render(&self, area: Rect, buf: &mut Buffer) {
-        // Build left side content (spinner + message)
+    /// Footer label for the key bound to `action`, preferring `default_key` while the
+    /// keymap still binds it. `None` when the action has no key in this context.
+    fn resolve_key(
+        keymap: &KeybindingConfig,
+        context: KeyContext,
+        default_key: &str,
+        action: &Action,
+    ) -> Option<String> {
+        if let Ok(combo) = parse_key_notation(default_key) {
+            if keymap.get_action(&combo, context) == Some(action) {
+                return Some(footer_key_label(&combo));
+            }
+        }
+        let context_bindings = keymap.context.get(&context);
+        let mut candidates: Vec<&KeyCombo> = context_bindings
+            .into_iter()
+            .flat_map(|bindings| bindings.iter())
+            .chain(keymap.global.iter())
+            .filter(|(combo, _)| keymap.get_action(combo, context) == Some(action))
+            .map(|(combo, _)| combo)
+            .collect();
+        // Fewest modifiers first (Shift on a letter reads as an uppercase key)
+        candidates.sort_by_key(|combo| {
+            let label = footer_key_label(combo);
+            let mut modifiers = combo.modifiers;
+            if matches!(combo.code, KeyCode::Char(_)) {
+                modifiers.remove(KeyModifiers::SHIFT);
+            }
+            (modifiers.bits().count_ones(), label.width(), label)
+        });
+        match candidates.first() {
+            Some(combo) => Some(footer_key_label(combo)),
+            // Without a key of its own, the agent is stopped by pressing the
+            // clear-or-quit key twice
+            None if *action == Action::InterruptAgent => {
+                Self::resolve_key(keymap, context, default_key, &Action::ClearOrQuit)
+            }
+            None => None,
+        }
+    }
+
+    /// Footer label for any key bound to `action` in `context`
+    pub fn key_label_for(
+        keymap: &KeybindingConfig,
+        context: KeyContext,
+        action: &Action,
+    ) -> Option<String> {
+        Self::resolve_key(keymap, context, "", action)
+    }
+
+    /// Hints that fit in `max_width`. When some are dropped (from the left), the
+    /// "more" hint is shown last so the help overlay stays one key away.
+    fn visible_hints(&self, max_width: usize) -> Vec<&FooterHint> {
+        let hint_width = |hint: &FooterHint| hint.key.width() + 1 + hint.label.width();
+        let total_width = |hints: &[&FooterHint]| {
+            hints.iter().map(|hint| hint_width(hint)).sum::<usize>()
+                + HINT_GAP * hints.len().saturating_sub(1)
+                + HINT_TRAILING
+        };
+
+        let mut visible: Vec<&FooterHint> = self.hints.iter().collect();
+        if total_width(&visible) <= max_width {
+            return visible;
+        }
+        if let Some(more) = self.more_hint.as_ref() {
+            visible.push(more);
+        }
+        while visible.len() > 1 && total_width(&visible) > max_width {
+            visible.remove(0);
+        }
+        if total_width(&visible) > max_width {
+            visible.clear();
+        }
+        visible
+    }
+
+    /// Spinner and message spans for the left side
+    fn left_spans(&self) -> Vec<Span<'a>> {
         let mut left_spans: Vec<Span> = Vec::new();
 
         // Add spinner if present
@@ -177,6 +389,48 @@ impl<'a> GlobalFooter<'a> {
             left_spans.push(Span::styled(message, Style::default().fg(text_muted())));
         }
 
+        left_spans
+    }
+
+    /// Maximum width available to key hints in `area`
+    fn max_hints_width(&self, area: Rect, left_width: u16) -> u16 {
+        // Reserve space for spinner/message, key hints get the rest (right-aligned)
+        let reserved_left = if left_width > 0 { left_width + 2 } else { 0 }; // +2 for gap
+        area.width.saturating_sub(reserved_left)
+    }
+
+    /// Action for the hint rendered at column `x`, if any
+    pub fn action_at(&self, area: Rect, x: u16) -> Option<Action> {
+        let left_width: u16 = self.left_spans().iter().map(|s| s.width() as u16).sum();
+        let max_width = self.max_hints_width(area, left_width) as usize;
+        let visible = self.visible_hints(max_width);
+        if visible.is_empty() {
+            return None;
+        }
+
+        // Hints are right-aligned: walk them left to right from where the line starts
+        let line_width: usize = visible
+            .iter()
+            .map(|hint| hint.key.width() + 1 + hint.label.width())
+            .sum::<usize>()
+            + HINT_GAP * (visible.len() - 1)
+            + HINT_TRAILING;
+        let mut current_x = (area.x + area.width).saturating_sub(line_width as u16) as usize;
+        let x = x as usize;
+        for hint in visible {
+            let width = hint.key.width() + 1 + hint.label.width();
+            if x >= current_x && x < current_x + width {
+                return Some(hint.action.clone());
+            }
+            current_x += width + HINT_GAP;
+        }
+        None
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        // Build left side content (spinner + message)
+        let left_spans = self.left_spans();
+
         // Calculate left side width
         let left_width: u16 = left_spans.iter().map(|s| s.width() as u16).sum();
 
@@ -186,23 +440,133 @@ impl<'a> GlobalFooter<'a> {
             buf.set_line(area.x, area.y, &left_line, left_width);
         }
 
-        // Reserve space for spinner/message, key hints get the rest (right-aligned)
-        let reserved_left = if left_width > 0 { left_width + 2 } else { 0 }; // +2 for gap
-        let max_hints_width = area.width.saturating_sub(reserved_left);
-
-        // Render key hints responsively (right-aligned, removes from left when too wide)
-        render_key_hints_responsive(
-            area,
-            buf,
-            &self.hints,
-            KeyHintBarStyle::minimal_footer(),
-            Some(max_hints_width),
-        );
+        let max_hints_width = self.max_hints_width(area, left_width);
+        let hints: Vec<(&str, &str)> = self
+            .visible_hints(max_hints_width as usize)
+            .into_iter()
+            .map(|hint| (hint.key.as_str(), hint.label))
+            .collect();
+
+        // Render key hints right-aligned; they are already fitted to
+        // `max_hints_width` by display width, so no further trimming is needed
+        render_key_hints_responsive(area, buf, &hints, KeyHintBarStyle::minimal_footer(), None);
+    }
+}
+
+/// Compact key label for the footer (e.g. `C-t`, `M-tab`, `G`, `esc`)
+fn footer_key_label(combo: &KeyCombo) -> String {
+    let mut modifiers = combo.modifiers;
+    let key = match combo.code {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) if modifiers == KeyModifiers::SHIFT && c.is_ascii_lowercase() => {
+            modifiers = KeyModifiers::NONE;
+            c.to_ascii_uppercase().to_string()
+        }
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Esc => "esc".to_string(),
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::BackTab => {
+            modifiers.insert(KeyModifiers::SHIFT);
+            "tab".to_string()
+        }
+        KeyCode::Backspace => "bs".to_string(),
+        KeyCode::Delete => "del".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::PageUp => "pgup".to_string(),
+        KeyCode::PageDown => "pgdn".to_string(),
+        KeyCode::Home => "home".to_string(),
+        KeyCode::End => "end".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        other => format!("{:?}", other).to_lowercase(),
+    };
+
+    let mut label = String::new();
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        label.push_str("C-");
     }
+    if modifiers.contains(KeyModifiers::ALT) {
+        label.push_str("M-");
+    }
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        label.push_str("S-");
+    }
+    label.push_str(&key);
+    label
 }
 
-impl Default for GlobalFooter<'_> {
-    fn default() -> Self {
-        Self::new()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::default_keybindings;
+
+    fn keys(footer: &GlobalFooter<'_>) -> Vec<(String, &'static str)> {
+        footer
+            .hints()
+            .iter()
+            .map(|hint| (hint.key.clone(), hint.label))
+            .collect()
+    }
+
+    #[test]
+    fn test_hints_follow_default_keymap() {
+        let keymap = default_keybindings();
+        let footer = GlobalFooter::for_context(FooterContext::Timeline, &keymap);
+        assert_eq!(
+            keys(&footer),
+            vec![
+                ("j/k".to_string(), "scroll"),
+                ("g/G".to_string(), "top/bottom"),
+                ("M-l".to_string(), "chat"),
+            ]
+        );
+
+        let footer = GlobalFooter::for_context(FooterContext::Chat, &keymap);
+        assert_eq!(footer.hints()[0].key, "M-tab");
+        assert_eq!(footer.hints()[0].action, Action::NextTab);
+    }
+
+    #[test]
+    fn test_hints_follow_rebinding() {
+        let mut keymap = default_keybindings();
+        let default_key = parse_key_notation("C-t").unwrap();
+        keymap.global.remove(&default_key);
+        keymap
+            .global
+            .insert(parse_key_notation("M-s").unwrap(), Action::ToggleSidebar);
+        // Unbinding an action hides its hint
+        keymap.global.remove(&parse_key_notation("C-q").unwrap());
+
+        let footer = GlobalFooter::for_context(FooterContext::Empty, &keymap);
+        assert_eq!(
+            keys(&footer),
+            vec![
+                ("C-n".to_string(), "new project"),
+                ("M-s".to_string(), "sidebar"),
+                ("M-i".to_string(), "import session"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overflow_shows_more_hint_and_hit_testing() {
+        let keymap = default_keybindings();
+        let footer = GlobalFooter::for_context(FooterContext::Timeline, &keymap);
+        let area = Rect::new(0, 0, 80, 1);
+
+        // Everything fits: "j/k scroll   g/G top/bottom   M-l chat  "
+        assert_eq!(footer.visible_hints(80).len(), 3);
+        assert_eq!(footer.action_at(area, 79), None);
+        assert_eq!(footer.action_at(area, 75), Some(Action::ToggleTimeline));
+
+        // Too narrow: leftmost hints are replaced by the help hint
+        let visible = footer.visible_hints(24);
+        let labels: Vec<_> = visible.iter().map(|hint| hint.label).collect();
+        assert_eq!(labels, vec!["chat", MORE_LABEL]);
+        assert_eq!(visible[1].key, ":help");
+        assert_eq!(visible[1].action, Action::ShowHelp);
     }
 }
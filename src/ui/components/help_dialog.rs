@@ -6,16 +6,17 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     symbols::border,
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
-use crate::config::{KeyContext, KeybindingConfig};
+use crate::config::{KeyContext, KeybindingConfig, KeymapIssue};
 use crate::ui::action::Action;
 
 use super::{
-    dialog_content_area, render_minimal_scrollbar, DialogFrame, ScrollbarMetrics, TextInputState,
+    accent_primary, accent_warning, border_default, dialog_content_area, render_minimal_scrollbar,
+    text_muted, text_primary, DialogFrame, ScrollbarMetrics, TextInputState,
 };
 
 /// A keybinding entry for display
@@ -28,6 +29,8 @@ pub struct KeybindingEntry {
 /// Category for grouping keybindings in the help dialog
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum HelpCategory {
+    /// Problems in the user's `[keys]` config
+    Issues,
     Global,
     Readline,
     Chat,
@@ -40,6 +43,7 @@ pub enum HelpCategory {
 impl HelpCategory {
     pub fn title(&self) -> &'static str {
         match self {
+            HelpCategory::Issues => "KEYMAP ISSUES",
             HelpCategory::Global => "GLOBAL",
             HelpCategory::Readline => "READLINE",
             HelpCategory::Chat => "CHAT",
@@ -53,13 +57,14 @@ impl HelpCategory {
     /// Order for display
     fn order(&self) -> usize {
         match self {
-            HelpCategory::Global => 0,
-            HelpCategory::Readline => 1,
-            HelpCategory::Chat => 2,
-            HelpCategory::Scrolling => 3,
-            HelpCategory::Sidebar => 4,
-            HelpCategory::Dialog => 5,
-            HelpCategory::Queue => 6,
+            HelpCategory::Issues => 0,
+            HelpCategory::Global => 1,
+            HelpCategory::Readline => 2,
+            HelpCategory::Chat => 3,
+            HelpCategory::Scrolling => 4,
+            HelpCategory::Sidebar => 5,
+            HelpCategory::Dialog => 6,
+            HelpCategory::Queue => 7,
         }
     }
 }
@@ -99,12 +104,13 @@ impl HelpDialogState {
         }
     }
 
-    /// Show the dialog and populate with keybindings
-    pub fn show(&mut self, config: &KeybindingConfig) {
+    /// Show the dialog and populate with keybindings, listing any problems
+    /// with the user's keybindings first
+    pub fn show(&mut self, config: &KeybindingConfig, issues: &[KeymapIssue]) {
         self.visible = true;
         self.search.clear();
         self.scroll_offset = 0;
-        self.populate_entries(config);
+        self.populate_entries(config, issues);
     }
 
     /// Hide the dialog
@@ -178,13 +184,26 @@ impl HelpDialogState {
     }
 
     /// Populate entries from keybinding config
-    fn populate_entries(&mut self, config: &KeybindingConfig) {
+    fn populate_entries(&mut self, config: &KeybindingConfig, issues: &[KeymapIssue]) {
         use std::collections::HashMap;
 
         self.entries.clear();
 
         let mut categories: HashMap<HelpCategory, Vec<KeybindingEntry>> = HashMap::new();
 
+        if !issues.is_empty() {
+            categories.insert(
+                HelpCategory::Issues,
+                issues
+                    .iter()
+                    .map(|issue| KeybindingEntry {
+                        action_description: issue.to_string(),
+                        key_display: issue.key_display(),
+                    })
+                    .collect(),
+            );
+        }
+
         // Categorize global bindings
         for (combo, action) in &config.global {
             let category = if Self::is_readline_action(action) {
@@ -205,7 +224,9 @@ impl HelpDialogState {
         for (ctx, bindings) in &config.context {
             let category = match ctx {
                 KeyContext::Chat => Some(HelpCategory::Chat),
-                KeyContext::Scrolling => Some(HelpCategory::Scrolling),
+                KeyContext::Scrolling
+                | KeyContext::MessageSelect
+                | KeyContext::ClipboardHistory => Some(HelpCategory::Scrolling),
                 KeyContext::Sidebar => Some(HelpCategory::Sidebar),
                 KeyContext::Dialog | KeyContext::ProjectPicker | KeyContext::ModelSelector => {
                     Some(HelpCategory::Dialog)
@@ -357,7 +378,7 @@ impl HelpDialog {
         let dialog_height = (area.height * 80 / 100).clamp(15, 35);
 
         let frame = DialogFrame::new("Help - Keybindings", dialog_width, dialog_height)
-            .border_color(Color::Cyan)
+            .border_color(accent_primary())
             .instructions(vec![
                 ("Esc/q", "Close"),
                 ("↑↓/jk", "Scroll"),
@@ -407,7 +428,7 @@ impl HelpDialog {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_set(border::ROUNDED)
-            .border_style(Style::default().fg(Color::Rgb(50, 50, 65)))
+            .border_style(Style::default().fg(border_default()))
             .title(" Search ");
 
         let inner = block.inner(area);
@@ -421,9 +442,9 @@ impl HelpDialog {
         };
 
         let search_style = if state.search.value().is_empty() {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(text_muted())
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(text_primary())
         };
 
         Paragraph::new(search_text)
@@ -445,7 +466,7 @@ impl HelpDialog {
 
         if filtered.is_empty() {
             let no_results = Paragraph::new("No matching keybindings")
-                .style(Style::default().fg(Color::DarkGray))
+                .style(Style::default().fg(text_muted()))
                 .alignment(Alignment::Center);
             no_results.render(area, buf);
             return;
@@ -506,7 +527,7 @@ impl HelpDialog {
                     Paragraph::new(left.as_str())
                         .style(
                             Style::default()
-                                .fg(Color::Yellow)
+                                .fg(accent_warning())
                                 .add_modifier(Modifier::BOLD),
                         )
                         .render(
@@ -528,7 +549,7 @@ impl HelpDialog {
                         height: 1,
                     };
                     Paragraph::new(left.as_str())
-                        .style(Style::default().fg(Color::Cyan))
+                        .style(Style::default().fg(accent_primary()))
                         .alignment(Alignment::Right)
                         .render(key_area, buf);
 
@@ -544,7 +565,7 @@ impl HelpDialog {
                         };
 
                         Paragraph::new(desc)
-                            .style(Style::default().fg(Color::White))
+                            .style(Style::default().fg(text_primary()))
                             .render(
                                 Rect {
                                     x: desc_x,
@@ -1,6 +1,7 @@
 //! Inline prompt component for AskUserQuestion and ExitPlanMode tools
 //!
 //! Emulates Claude Code CLI's inline UI patterns for interactive tool responses.
+//! Also hosts the hunk-by-hunk review of file edits held by edit review.
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
@@ -12,9 +13,10 @@ use ratatui::{
 };
 
 use super::{
-    accent_primary, accent_secondary, text_faint, text_muted, text_primary, text_secondary,
-    InstructionBar, TextInputState,
+    accent_primary, accent_secondary, accent_warning, diff_add, diff_remove, text_faint,
+    text_muted, text_primary, text_secondary, InstructionBar, TextInputState,
 };
+use crate::agent::edit_review::{EditReview, HunkDecision, ReviewOutcome};
 use crate::agent::events::{QuestionOption, UserQuestion};
 
 // ============================================================================
@@ -42,6 +44,15 @@ const HORIZONTAL_LINE: char = '─';
 /// Dashed line character for plan box
 const DASHED_LINE: char = '╌';
 
+/// Rejected hunk indicator
+const REJECTED: &str = "✘";
+
+/// Edited hunk indicator
+const EDITED: &str = "✎";
+
+/// Diff lines shown for the current hunk before it's cut off
+const MAX_HUNK_LINES: usize = 30;
+
 // ============================================================================
 // Types
 // ============================================================================
@@ -56,6 +67,8 @@ pub enum InlinePromptType {
         plan_content: String,
         plan_file_path: String,
     },
+    /// File edit held for review, one hunk at a time
+    EditReview { review: EditReview },
 }
 
 /// User's answer to a single question
@@ -80,6 +93,10 @@ pub enum PromptAction {
     Consumed,
     /// Key was not handled
     NotHandled,
+    /// Open the given edit review hunk in the external editor
+    EditHunk(usize),
+    /// User finished reviewing a held file edit
+    EditReviewed(ReviewOutcome),
 }
 
 /// The response to send back to the agent
@@ -171,6 +188,49 @@ impl InlinePromptState {
         }
     }
 
+    /// Create a review prompt for a held file edit
+    pub fn new_edit_review(tool_id: String, review: EditReview) -> Self {
+        Self {
+            tool_id,
+            prompt_type: InlinePromptType::EditReview { review },
+            current_option: 0,
+            current_question_idx: 0,
+            answers: vec![],
+            input_mode: false,
+            text_input: TextInputState::new(),
+        }
+    }
+
+    /// Text to open in the editor for a hunk: the edited text if it was
+    /// rewritten, otherwise the proposed text
+    pub fn hunk_edit_text(&self, index: usize) -> Option<String> {
+        let InlinePromptType::EditReview { review } = &self.prompt_type else {
+            return None;
+        };
+        match review.decisions.get(index)? {
+            HunkDecision::Edited(text) => Some(text.clone()),
+            _ => review.hunks.get(index).map(|hunk| hunk.new_text()),
+        }
+    }
+
+    /// Record text written for a hunk in the external editor
+    pub fn set_hunk_text(&mut self, index: usize, text: String) {
+        let InlinePromptType::EditReview { review } = &mut self.prompt_type else {
+            return;
+        };
+        let Some(hunk) = review.hunks.get(index) else {
+            return;
+        };
+        // Saving the proposal unchanged (modulo the final newline) accepts it
+        let proposed = hunk.new_text();
+        review.decisions[index] = if text.trim_end_matches('\n') == proposed.trim_end_matches('\n')
+        {
+            HunkDecision::Accept
+        } else {
+            HunkDecision::Edited(text)
+        };
+    }
+
     /// Get the current question (for AskUserQuestion only)
     fn current_question(&self) -> Option<&UserQuestion> {
         match &self.prompt_type {
@@ -236,6 +296,7 @@ impl InlinePromptState {
                     .unwrap_or(0)
             }
             InlinePromptType::ExitPlanMode { .. } => 2, // "Yes, start building" and "Type here..."
+            InlinePromptType::EditReview { review } => review.hunks.len(),
         }
     }
 
@@ -253,6 +314,7 @@ impl InlinePromptState {
                 }
             }
             InlinePromptType::ExitPlanMode { .. } => self.current_option == 1,
+            InlinePromptType::EditReview { .. } => false,
         }
     }
 
@@ -262,6 +324,9 @@ impl InlinePromptState {
         if self.input_mode {
             return self.handle_input_mode_key(key);
         }
+        if matches!(self.prompt_type, InlinePromptType::EditReview { .. }) {
+            return self.handle_edit_review_key(key);
+        }
 
         match key.code {
             // Navigation
@@ -385,6 +450,56 @@ impl InlinePromptState {
         }
     }
 
+    /// Handle a key while reviewing an edit; `current_option` is the hunk
+    fn handle_edit_review_key(&mut self, key: KeyEvent) -> PromptAction {
+        let current = self.current_option;
+        let InlinePromptType::EditReview { review } = &mut self.prompt_type else {
+            return PromptAction::NotHandled;
+        };
+        let next = (current + 1).min(review.hunks.len().saturating_sub(1));
+
+        let (decision, move_to) = match key.code {
+            KeyCode::Left
+            | KeyCode::Up
+            | KeyCode::BackTab
+            | KeyCode::Char('h')
+            | KeyCode::Char('k') => (None, current.saturating_sub(1)),
+            KeyCode::Right
+            | KeyCode::Down
+            | KeyCode::Tab
+            | KeyCode::Char('l')
+            | KeyCode::Char('j') => (None, next),
+            KeyCode::Char('y') => (Some(HunkDecision::Accept), next),
+            KeyCode::Char('n') => (Some(HunkDecision::Reject), next),
+            KeyCode::Char(' ') => match review.decisions.get(current) {
+                Some(HunkDecision::Accept) => (Some(HunkDecision::Reject), current),
+                _ => (Some(HunkDecision::Accept), current),
+            },
+            KeyCode::Char('A') => {
+                review.decisions.fill(HunkDecision::Accept);
+                (None, current)
+            }
+            KeyCode::Char('N') => {
+                review.decisions.fill(HunkDecision::Reject);
+                (None, current)
+            }
+            KeyCode::Char('e') if !review.hunks.is_empty() => {
+                return PromptAction::EditHunk(current);
+            }
+            KeyCode::Enter => {
+                return PromptAction::EditReviewed(review.outcome());
+            }
+            KeyCode::Esc => return PromptAction::Cancel,
+            _ => return PromptAction::NotHandled,
+        };
+
+        if let (Some(decision), Some(slot)) = (decision, review.decisions.get_mut(current)) {
+            *slot = decision;
+        }
+        self.current_option = move_to;
+        PromptAction::Consumed
+    }
+
     /// Handle key events in text input mode
     fn handle_input_mode_key(&mut self, key: KeyEvent) -> PromptAction {
         match key.code {
@@ -540,6 +655,7 @@ impl InlinePromptState {
                     PromptAction::Consumed
                 }
             }
+            InlinePromptType::EditReview { .. } => PromptAction::Consumed,
         }
     }
 
@@ -564,6 +680,7 @@ impl InlinePromptState {
             InlinePromptType::ExitPlanMode { .. } => {
                 PromptAction::Submit(PromptResponse::ExitPlanFeedback(text))
             }
+            InlinePromptType::EditReview { .. } => PromptAction::Consumed,
         }
     }
 
@@ -718,11 +835,143 @@ impl InlinePromptState {
                     ]));
                 }
             }
+            InlinePromptType::EditReview { review } => {
+                self.append_edit_review_lines(&mut lines, review, width);
+            }
         }
 
         lines
     }
 
+    /// Append the current hunk of an edit review with its decision bar
+    fn append_edit_review_lines(
+        &self,
+        lines: &mut Vec<Line<'static>>,
+        review: &EditReview,
+        width: usize,
+    ) {
+        let verb = if review.creates_file() {
+            "create"
+        } else {
+            "edit"
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" Claude wants to {} ", verb),
+                Style::default().fg(text_primary()),
+            ),
+            Span::styled(
+                review.display_path.clone(),
+                Style::default()
+                    .fg(accent_primary())
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        lines.push(self.dashed_line(width));
+
+        let current = self.current_option;
+        match (review.hunks.get(current), review.decisions.get(current)) {
+            (Some(hunk), Some(decision)) => {
+                let (status, status_style) = match decision {
+                    HunkDecision::Accept => ("accepted", Style::default().fg(diff_add())),
+                    HunkDecision::Reject => ("rejected", Style::default().fg(diff_remove())),
+                    HunkDecision::Edited(_) => ("edited", Style::default().fg(accent_warning())),
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!(
+                            " Hunk {} of {} · line {} · ",
+                            current + 1,
+                            review.hunks.len(),
+                            hunk.old_start + 1
+                        ),
+                        Style::default().fg(text_muted()),
+                    ),
+                    Span::styled(status, status_style),
+                ]));
+
+                let replacement: Vec<String> = match decision {
+                    HunkDecision::Edited(text) => {
+                        text.split_inclusive('\n').map(str::to_string).collect()
+                    }
+                    _ => hunk.new_lines.clone(),
+                };
+                let diff_lines = hunk
+                    .context_before
+                    .iter()
+                    .map(|line| (' ', line))
+                    .chain(hunk.old_lines.iter().map(|line| ('-', line)))
+                    .chain(replacement.iter().map(|line| ('+', line)))
+                    .chain(hunk.context_after.iter().map(|line| (' ', line)));
+                let total = hunk.context_before.len()
+                    + hunk.old_lines.len()
+                    + replacement.len()
+                    + hunk.context_after.len();
+                for (marker, line) in diff_lines.take(MAX_HUNK_LINES) {
+                    let style = match marker {
+                        '-' => Style::default().fg(diff_remove()),
+                        '+' => Style::default().fg(diff_add()),
+                        _ => Style::default().fg(text_muted()),
+                    };
+                    // A rejected hunk keeps the old lines; dim what won't land
+                    let style = match (marker, decision) {
+                        ('+', HunkDecision::Reject) | ('-', HunkDecision::Accept) => {
+                            style.add_modifier(Modifier::DIM)
+                        }
+                        _ => style,
+                    };
+                    let text = line.trim_end_matches(['\n', '\r']);
+                    lines.push(Line::from(Span::styled(
+                        format!(" {} {}", marker, text),
+                        style,
+                    )));
+                }
+                if total > MAX_HUNK_LINES {
+                    lines.push(Line::from(Span::styled(
+                        format!(" … {} more lines", total - MAX_HUNK_LINES),
+                        Style::default().fg(text_faint()),
+                    )));
+                }
+            }
+            _ => {
+                lines.push(Line::from(Span::styled(
+                    " No changes to the file.",
+                    Style::default().fg(text_muted()),
+                )));
+            }
+        }
+        lines.push(self.dashed_line(width));
+
+        // One marker per hunk, current one highlighted
+        if review.hunks.len() > 1 {
+            let mut spans = vec![Span::raw(" ")];
+            for (i, decision) in review.decisions.iter().enumerate() {
+                let (marker, color) = match decision {
+                    HunkDecision::Accept => (CHECKED, diff_add()),
+                    HunkDecision::Reject => (REJECTED, diff_remove()),
+                    HunkDecision::Edited(_) => (EDITED, accent_warning()),
+                };
+                let mut style = Style::default().fg(color);
+                if i == current {
+                    style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+                }
+                spans.push(Span::styled(format!("{} {}", marker, i + 1), style));
+                spans.push(Span::raw(" "));
+            }
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::from("")); // blank line
+
+        lines.push(self.instruction_bar_line(&[
+            ("y/n", "accept/reject hunk"),
+            ("e", "edit"),
+            ("A/N", "all"),
+            ("←/→", "hunks"),
+            ("Enter", "apply"),
+            ("Esc", "reject edit"),
+        ]));
+    }
+
     /// Build a horizontal separator line
     fn separator_line(&self, width: usize) -> Line<'static> {
         Line::from(Span::styled(
@@ -1700,6 +1949,11 @@ impl Widget for InlinePrompt<'_> {
                     );
                 }
             }
+
+            InlinePromptType::EditReview { .. } => {
+                let lines = self.state.render_as_lines(area.width as usize);
+                Paragraph::new(lines).render(area, buf);
+            }
         }
     }
 }
@@ -5,15 +5,21 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Clear, Paragraph, Widget, Wrap},
 };
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-use super::{bg_highlight, input_bg, render_minimal_scrollbar, text_primary, ScrollbarMetrics};
-use crate::ui::clipboard_paste::normalize_pasted_path;
+use super::{
+    bg_highlight, input_bg, render_minimal_scrollbar, text_muted, text_primary, ScrollbarMetrics,
+};
+use crate::ui::clipboard_paste::{dropped_paths, is_image_path};
 
+// Pastes over either limit are shown as a placeholder and expanded on submit.
 const LARGE_PASTE_CHAR_THRESHOLD: usize = 1000;
+const LARGE_PASTE_LINE_THRESHOLD: usize = 20;
 const HISTORY_MAX: usize = 1000;
+const UNDO_MAX: usize = 200;
+/// Column where the first attachment chip starts
+const CHIP_INDENT: u16 = 1;
 
 #[derive(Debug, Clone)]
 struct VisualLine {
@@ -29,12 +35,37 @@ pub struct InputSubmit {
     pub image_placeholders: Vec<String>,
 }
 
+/// Image attached to the draft, shown as a removable chip below the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttachmentChip {
+    /// File name and dimensions, e.g. `shot.png 640x480`
+    pub label: String,
+    pub path: PathBuf,
+}
+
 #[derive(Debug, Clone)]
 struct AttachedImage {
     placeholder: String,
     path: PathBuf,
 }
 
+/// Draft state restored by undo/redo
+#[derive(Debug, Clone)]
+struct EditSnapshot {
+    input: String,
+    cursor_pos: usize,
+    pending_pastes: Vec<(String, String)>,
+    attached_images: Vec<AttachedImage>,
+}
+
+/// Kind of edit; consecutive typing or deleting at the cursor is undone as one step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Typing,
+    Deleting,
+    Other,
+}
+
 /// Text input component with cursor and history
 pub struct InputBox {
     /// Current input text
@@ -55,8 +86,8 @@ pub struct InputBox {
     last_content_width: Option<u16>,
     /// Large paste placeholders → actual content
     pending_pastes: Vec<(String, String)>,
-    /// Counter for large paste placeholders by size
-    large_paste_counters: HashMap<usize, usize>,
+    /// Large pastes in the current draft, numbering their placeholders
+    paste_count: usize,
     /// Attached images tracked by placeholder
     attached_images: Vec<AttachedImage>,
     /// Selection anchor (byte offset)
@@ -65,6 +96,14 @@ pub struct InputBox {
     selection_head: Option<usize>,
     /// Whether shell input mode is active (triggered by leading '!')
     shell_mode: bool,
+    /// Drafts before each edit, most recent last
+    undo_stack: Vec<EditSnapshot>,
+    /// Drafts undone since the last edit, most recent last
+    redo_stack: Vec<EditSnapshot>,
+    /// Kind of the last edit and the cursor after it, for grouping edits
+    last_edit: Option<(EditKind, usize)>,
+    /// Drop the padding rows above and below the text (zen mode)
+    compact: bool,
 }
 
 impl InputBox {
@@ -79,11 +118,15 @@ impl InputBox {
             scroll_offset: 0,
             last_content_width: None,
             pending_pastes: Vec::new(),
-            large_paste_counters: HashMap::new(),
+            paste_count: 0,
             attached_images: Vec::new(),
             selection_anchor: None,
             selection_head: None,
             shell_mode: false,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            last_edit: None,
+            compact: false,
         }
     }
 
@@ -101,6 +144,11 @@ impl InputBox {
         self.shell_mode = shell_mode;
     }
 
+    /// Render without padding rows, so an empty input takes a single row
+    pub fn set_compact(&mut self, compact: bool) {
+        self.compact = compact;
+    }
+
     /// Get expanded input with large paste placeholders resolved.
     pub fn expanded_input(&self) -> String {
         let mut expanded = self.input.clone();
@@ -143,11 +191,13 @@ impl InputBox {
 
     /// Set input text
     pub fn set_input(&mut self, text: String) {
-        self.input = text;
-        self.cursor_pos = self.input.len();
-        self.pending_pastes.clear();
-        self.attached_images.clear();
-        self.clear_selection();
+        self.edit(EditKind::Other, |this| {
+            this.input = text;
+            this.cursor_pos = this.input.len();
+            this.pending_pastes.clear();
+            this.attached_images.clear();
+            this.clear_selection();
+        });
     }
 
     /// Set input text with attached images restored.
@@ -156,25 +206,29 @@ impl InputBox {
         text: String,
         attachments: Vec<(PathBuf, String)>,
     ) {
-        self.input = text;
-        self.cursor_pos = self.input.len();
-        self.pending_pastes.clear();
-        self.attached_images = attachments
-            .into_iter()
-            .map(|(path, placeholder)| AttachedImage { placeholder, path })
-            .collect();
-        self.clear_selection();
+        self.edit(EditKind::Other, |this| {
+            this.input = text;
+            this.cursor_pos = this.input.len();
+            this.pending_pastes.clear();
+            this.attached_images = attachments
+                .into_iter()
+                .map(|(path, placeholder)| AttachedImage { placeholder, path })
+                .collect();
+            this.clear_selection();
+        });
     }
 
-    /// Clear input
+    /// Clear input (undoable, so a cleared draft can be brought back)
     pub fn clear(&mut self) {
-        self.input.clear();
-        self.cursor_pos = 0;
+        self.edit(EditKind::Other, |this| {
+            this.input.clear();
+            this.cursor_pos = 0;
+            this.pending_pastes.clear();
+            this.attached_images.clear();
+            this.clear_selection();
+        });
         self.history_index = None;
         self.scroll_offset = 0;
-        self.pending_pastes.clear();
-        self.attached_images.clear();
-        self.clear_selection();
     }
 
     /// Add text to history without submitting.
@@ -207,6 +261,10 @@ impl InputBox {
         self.history_index = None;
         self.scroll_offset = 0;
         self.clear_selection();
+        // A sent prompt is recalled through history, not undo
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.last_edit = None;
 
         let mut expanded = input;
         for (placeholder, actual) in &self.pending_pastes {
@@ -215,6 +273,7 @@ impl InputBox {
             }
         }
         self.pending_pastes.clear();
+        self.paste_count = 0;
 
         let (image_paths, image_placeholders) = self.take_attached_images(&expanded);
 
@@ -235,13 +294,38 @@ impl InputBox {
         self.input.split('\n').count().max(1)
     }
 
-    /// Calculate the desired height for the input box (content + padding)
+    /// Calculate the desired height for the input box (content + padding + chips)
     pub fn desired_height(&self, max_height: u16, width: u16) -> u16 {
         let content_lines = self.visual_line_count(width) as u16;
-        // +2 for top and bottom padding
-        let desired = content_lines + 2;
-        // Minimum of 3 (1 line + padding), maximum of max_height
-        desired.clamp(3, max_height)
+        let desired = self.padding_top() + content_lines + self.padding_bottom();
+        // Minimum of 1 line + padding + chips, maximum of max_height
+        let min_height = self.min_height();
+        desired.clamp(min_height, max_height.max(min_height))
+    }
+
+    /// Height with room for one line of text
+    fn min_height(&self) -> u16 {
+        self.padding_top() + 1 + self.padding_bottom()
+    }
+
+    /// Rows below the text taken by attachment chips
+    fn chip_rows(&self) -> u16 {
+        u16::from(
+            self.attached_images
+                .iter()
+                .any(|img| self.input.contains(&img.placeholder)),
+        )
+    }
+
+    /// Rows above the text: one row of padding unless compact
+    fn padding_top(&self) -> u16 {
+        u16::from(!self.compact)
+    }
+
+    /// Rows below the text: the chip row, if any, and one row of padding
+    /// unless compact
+    fn padding_bottom(&self) -> u16 {
+        u16::from(!self.compact) + self.chip_rows()
     }
 
     /// Ensure cursor line is visible, adjusting scroll if needed
@@ -376,9 +460,15 @@ impl InputBox {
 
     /// Insert character at cursor
     pub fn insert_char(&mut self, c: char) {
-        self.delete_selection();
-        self.input.insert(self.cursor_pos, c);
-        self.cursor_pos += c.len_utf8();
+        // Whitespace starts a new undo step, so typing is undone a word at a time
+        if c.is_whitespace() {
+            self.last_edit = None;
+        }
+        self.edit(EditKind::Typing, |this| {
+            this.delete_selection();
+            this.input.insert(this.cursor_pos, c);
+            this.cursor_pos += c.len_utf8();
+        });
     }
 
     /// Insert a string at cursor
@@ -386,9 +476,11 @@ impl InputBox {
         if text.is_empty() {
             return;
         }
-        self.delete_selection();
-        self.input.insert_str(self.cursor_pos, text);
-        self.cursor_pos += text.len();
+        self.edit(EditKind::Other, |this| {
+            this.delete_selection();
+            this.input.insert_str(this.cursor_pos, text);
+            this.cursor_pos += text.len();
+        });
     }
 
     /// Insert newline
@@ -398,29 +490,43 @@ impl InputBox {
 
     /// Delete character before cursor
     pub fn backspace(&mut self) {
-        if self.delete_selection() {
-            return;
-        }
-        if self.cursor_pos > 0 {
-            // Find the previous character boundary
-            let prev_pos = self.input[..self.cursor_pos]
-                .char_indices()
-                .last()
-                .map(|(i, _)| i)
-                .unwrap_or(0);
-            self.input.remove(prev_pos);
-            self.cursor_pos = prev_pos;
-        }
+        let kind = if self.selection_range().is_some() {
+            EditKind::Other
+        } else {
+            EditKind::Deleting
+        };
+        self.edit(kind, |this| {
+            if this.delete_selection() {
+                return;
+            }
+            if this.cursor_pos > 0 {
+                // Find the previous character boundary
+                let prev_pos = this.input[..this.cursor_pos]
+                    .char_indices()
+                    .last()
+                    .map(|(i, _)| i)
+                    .unwrap_or(0);
+                this.input.remove(prev_pos);
+                this.cursor_pos = prev_pos;
+            }
+        });
     }
 
     /// Delete character at cursor
     pub fn delete(&mut self) {
-        if self.delete_selection() {
-            return;
-        }
-        if self.cursor_pos < self.input.len() {
-            self.input.remove(self.cursor_pos);
-        }
+        let kind = if self.selection_range().is_some() {
+            EditKind::Other
+        } else {
+            EditKind::Deleting
+        };
+        self.edit(kind, |this| {
+            if this.delete_selection() {
+                return;
+            }
+            if this.cursor_pos < this.input.len() {
+                this.input.remove(this.cursor_pos);
+            }
+        });
     }
 
     /// Move cursor left
@@ -627,55 +733,61 @@ impl InputBox {
 
     /// Delete word before cursor (Ctrl+W)
     pub fn delete_word_back(&mut self) {
-        if self.delete_selection() {
-            return;
-        }
-        if self.cursor_pos == 0 {
-            return;
-        }
+        self.edit(EditKind::Other, |this| {
+            if this.delete_selection() {
+                return;
+            }
+            if this.cursor_pos == 0 {
+                return;
+            }
 
-        let before_cursor = &self.input[..self.cursor_pos];
+            let before_cursor = &this.input[..this.cursor_pos];
 
-        // Skip trailing whitespace
-        let trimmed_len = before_cursor.trim_end().len();
+            // Skip trailing whitespace
+            let trimmed_len = before_cursor.trim_end().len();
 
-        if trimmed_len == 0 {
-            // Only whitespace before cursor
-            self.input = self.input[self.cursor_pos..].to_string();
-            self.cursor_pos = 0;
-            return;
-        }
+            if trimmed_len == 0 {
+                // Only whitespace before cursor
+                this.input = this.input[this.cursor_pos..].to_string();
+                this.cursor_pos = 0;
+                return;
+            }
 
-        // Find word boundary
-        let word_start = before_cursor[..trimmed_len]
-            .rfind(|c: char| c.is_whitespace())
-            .map(|i| i + 1)
-            .unwrap_or(0);
+            // Find word boundary
+            let word_start = before_cursor[..trimmed_len]
+                .rfind(|c: char| c.is_whitespace())
+                .map(|i| i + 1)
+                .unwrap_or(0);
 
-        let new_before = &self.input[..word_start];
-        let after = &self.input[self.cursor_pos..];
-        self.input = format!("{}{}", new_before, after);
-        self.cursor_pos = word_start;
+            let new_before = &this.input[..word_start];
+            let after = &this.input[this.cursor_pos..];
+            this.input = format!("{}{}", new_before, after);
+            this.cursor_pos = word_start;
+        });
     }
 
     /// Delete from cursor to start of line (Ctrl+U)
     pub fn delete_to_start(&mut self) {
-        if self.delete_selection() {
-            return;
-        }
-        if self.cursor_pos == 0 {
-            return;
-        }
-        self.input = self.input[self.cursor_pos..].to_string();
-        self.cursor_pos = 0;
+        self.edit(EditKind::Other, |this| {
+            if this.delete_selection() {
+                return;
+            }
+            if this.cursor_pos == 0 {
+                return;
+            }
+            this.input = this.input[this.cursor_pos..].to_string();
+            this.cursor_pos = 0;
+        });
     }
 
     /// Delete from cursor to end of line (Ctrl+K)
     pub fn delete_to_end(&mut self) {
-        if self.delete_selection() {
-            return;
-        }
-        self.input.truncate(self.cursor_pos);
+        self.edit(EditKind::Other, |this| {
+            if this.delete_selection() {
+                return;
+            }
+            this.input.truncate(this.cursor_pos);
+        });
     }
 
     /// Navigate to previous history entry
@@ -684,45 +796,111 @@ impl InputBox {
             return;
         }
 
-        match self.history_index {
-            None => {
-                self.saved_input = std::mem::take(&mut self.input);
-                self.history_index = Some(self.history.len() - 1);
-            }
-            Some(0) => {
-                // Already at oldest, do nothing
-                return;
-            }
-            Some(i) => {
-                self.history_index = Some(i - 1);
+        self.edit(EditKind::Other, |this| {
+            match this.history_index {
+                None => {
+                    this.saved_input = std::mem::take(&mut this.input);
+                    this.history_index = Some(this.history.len() - 1);
+                }
+                Some(0) => {
+                    // Already at oldest, do nothing
+                    return;
+                }
+                Some(i) => {
+                    this.history_index = Some(i - 1);
+                }
             }
-        }
 
-        if let Some(i) = self.history_index {
-            self.input = self.history[i].clone();
-            self.cursor_pos = self.input.len();
-            self.clear_selection();
-        }
+            if let Some(i) = this.history_index {
+                this.input = this.history[i].clone();
+                this.cursor_pos = this.input.len();
+                this.clear_selection();
+            }
+        });
     }
 
     /// Navigate to next history entry
     pub fn history_next(&mut self) {
-        match self.history_index {
-            None => {
-                // Not in history mode
-                return;
-            }
-            Some(i) if i >= self.history.len() - 1 => {
-                // Return to current input
-                self.history_index = None;
-                self.input = std::mem::take(&mut self.saved_input);
+        self.edit(EditKind::Other, |this| {
+            match this.history_index {
+                None => {
+                    // Not in history mode
+                    return;
+                }
+                Some(i) if i >= this.history.len() - 1 => {
+                    // Return to current input
+                    this.history_index = None;
+                    this.input = std::mem::take(&mut this.saved_input);
+                }
+                Some(i) => {
+                    this.history_index = Some(i + 1);
+                    this.input = this.history[i + 1].clone();
+                }
             }
-            Some(i) => {
-                self.history_index = Some(i + 1);
-                self.input = self.history[i + 1].clone();
+            this.cursor_pos = this.input.len();
+            this.clear_selection();
+        });
+    }
+
+    /// Restore the draft as it was before the last edit. Returns false when
+    /// there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.undo_stack.pop() else {
+            return false;
+        };
+        let current = self.snapshot();
+        self.redo_stack.push(current);
+        self.restore(snapshot);
+        true
+    }
+
+    /// Reapply the last undone edit. Returns false when there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.redo_stack.pop() else {
+            return false;
+        };
+        let current = self.snapshot();
+        self.undo_stack.push(current);
+        self.restore(snapshot);
+        true
+    }
+
+    /// Apply an edit, recording the draft before it for undo. Edits that
+    /// leave the text unchanged aren't recorded.
+    fn edit(&mut self, kind: EditKind, apply: impl FnOnce(&mut Self)) {
+        let before = self.snapshot();
+        apply(self);
+        if self.input == before.input {
+            return;
+        }
+        let continues_last =
+            kind != EditKind::Other && self.last_edit == Some((kind, before.cursor_pos));
+        if !continues_last {
+            self.undo_stack.push(before);
+            if self.undo_stack.len() > UNDO_MAX {
+                self.undo_stack.remove(0);
             }
         }
-        self.cursor_pos = self.input.len();
+        self.redo_stack.clear();
+        self.last_edit = Some((kind, self.cursor_pos));
+    }
+
+    fn snapshot(&self) -> EditSnapshot {
+        EditSnapshot {
+            input: self.input.clone(),
+            cursor_pos: self.cursor_pos,
+            pending_pastes: self.pending_pastes.clone(),
+            attached_images: self.attached_images.clone(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: EditSnapshot) {
+        self.input = snapshot.input;
+        self.cursor_pos = snapshot.cursor_pos.min(self.input.len());
+        self.pending_pastes = snapshot.pending_pastes;
+        self.attached_images = snapshot.attached_images;
+        self.history_index = None;
+        self.last_edit = None;
         self.clear_selection();
     }
 
@@ -738,29 +916,193 @@ impl InputBox {
 
     pub fn handle_paste(&mut self, pasted: String) {
         let char_count = pasted.chars().count();
-        if char_count > LARGE_PASTE_CHAR_THRESHOLD {
-            let placeholder = self.next_large_paste_placeholder(char_count);
+        let line_count = pasted.lines().count();
+        if char_count > LARGE_PASTE_CHAR_THRESHOLD || line_count > LARGE_PASTE_LINE_THRESHOLD {
+            let placeholder = self.next_large_paste_placeholder(char_count, line_count);
             self.insert_str(&placeholder);
             self.pending_pastes.push((placeholder, pasted));
-        } else if char_count > 1 && self.handle_paste_image_path(&pasted) {
-            self.insert_str(" ");
+        } else if let Some(paths) = dropped_paths(&pasted).filter(|_| char_count > 1) {
+            self.insert_dropped_paths(paths);
         } else {
             self.insert_str(&pasted);
         }
     }
 
     pub fn attach_image(&mut self, path: PathBuf, width: u32, height: u32) {
-        let file_label = path
-            .file_name()
-            .map(|name| name.to_string_lossy().into_owned())
-            .unwrap_or_else(|| "image".to_string());
-        let base_placeholder = format!("{file_label} {width}x{height}");
+        let base_placeholder = Self::image_placeholder_base(&path, width, height);
         let placeholder = self.next_image_placeholder(&base_placeholder);
         self.insert_str(&placeholder);
         self.attached_images
             .push(AttachedImage { placeholder, path });
     }
 
+    /// Most recently attached image whose placeholder is still in the draft
+    pub fn last_attached_image(&self) -> Option<&Path> {
+        self.attached_images
+            .iter()
+            .rev()
+            .find(|img| self.input.contains(&img.placeholder))
+            .map(|img| img.path.as_path())
+    }
+
+    /// Swap an attached image for an edited copy, rewriting its placeholder in place.
+    /// Returns false when `old_path` isn't attached.
+    pub fn replace_attached_image(
+        &mut self,
+        old_path: &Path,
+        new_path: PathBuf,
+        width: u32,
+        height: u32,
+    ) -> bool {
+        let Some(idx) = self
+            .attached_images
+            .iter()
+            .position(|img| img.path == old_path)
+        else {
+            return false;
+        };
+
+        let base_placeholder = Self::image_placeholder_base(&new_path, width, height);
+        let placeholder = self.next_image_placeholder(&base_placeholder);
+        let old_placeholder = std::mem::replace(
+            &mut self.attached_images[idx],
+            AttachedImage {
+                placeholder: placeholder.clone(),
+                path: new_path,
+            },
+        )
+        .placeholder;
+
+        if let Some(start) = self.input.find(&old_placeholder) {
+            let old_end = start + old_placeholder.len();
+            self.input.replace_range(start..old_end, &placeholder);
+            if self.cursor_pos >= old_end {
+                self.cursor_pos = self.cursor_pos - old_placeholder.len() + placeholder.len();
+            } else if self.cursor_pos > start {
+                self.cursor_pos = start + placeholder.len();
+            }
+            self.clear_selection();
+        }
+        true
+    }
+
+    /// Images still referenced by the draft, in the order they were attached
+    pub fn attachment_chips(&self) -> Vec<AttachmentChip> {
+        self.attached_images
+            .iter()
+            .filter(|img| self.input.contains(&img.placeholder))
+            .map(|img| AttachmentChip {
+                label: img.placeholder.trim_matches(['[', ']']).to_string(),
+                path: img.path.clone(),
+            })
+            .collect()
+    }
+
+    /// Remove the most recently attached image and its placeholder.
+    /// Returns false when the draft has no attachments.
+    pub fn remove_last_attachment(&mut self) -> bool {
+        let Some(idx) = self
+            .attached_images
+            .iter()
+            .rposition(|img| self.input.contains(&img.placeholder))
+        else {
+            return false;
+        };
+        self.remove_attachment(idx);
+        true
+    }
+
+    /// Remove the image whose chip was clicked. Returns false when the click
+    /// missed every chip.
+    pub fn remove_attachment_at(&mut self, click_x: u16, click_y: u16, area: Rect) -> bool {
+        if self.chip_rows() == 0
+            || area.height < self.min_height()
+            || click_y != area.y + area.height - self.padding_bottom()
+        {
+            return false;
+        }
+        let (chips, _) = self.chip_layout(area.width);
+        let hit = chips.into_iter().find(|(_, text, x)| {
+            let start = area.x + x;
+            (start..start + UnicodeWidthStr::width(text.as_str()) as u16).contains(&click_x)
+        });
+        match hit {
+            Some((idx, _, _)) => {
+                self.remove_attachment(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn remove_attachment(&mut self, idx: usize) {
+        self.edit(EditKind::Other, |this| {
+            let img = this.attached_images.remove(idx);
+            let Some(start) = this.input.find(&img.placeholder) else {
+                return;
+            };
+            let end = start + img.placeholder.len();
+            this.input.replace_range(start..end, "");
+            if this.cursor_pos >= end {
+                this.cursor_pos -= img.placeholder.len();
+            } else if this.cursor_pos > start {
+                this.cursor_pos = start;
+            }
+            this.clear_selection();
+        });
+    }
+
+    /// Chips that fit in `width` as (attachment index, text, column), plus
+    /// how many didn't fit
+    fn chip_layout(&self, width: u16) -> (Vec<(usize, String, u16)>, usize) {
+        let mut chips = Vec::new();
+        let mut hidden = 0;
+        let mut x = CHIP_INDENT;
+        for (idx, img) in self.attached_images.iter().enumerate() {
+            if !self.input.contains(&img.placeholder) {
+                continue;
+            }
+            let text = format!(" {} × ", img.placeholder.trim_matches(['[', ']']));
+            let text_width = UnicodeWidthStr::width(text.as_str()) as u16;
+            if hidden > 0 || x + text_width > width {
+                hidden += 1;
+                continue;
+            }
+            chips.push((idx, text, x));
+            x += text_width + 1;
+        }
+        (chips, hidden)
+    }
+
+    fn render_chips(&self, area: Rect, y: u16, buf: &mut Buffer) {
+        let chip_style = Style::default().fg(text_primary()).bg(bg_highlight());
+        let (chips, hidden) = self.chip_layout(area.width);
+        let mut end = area.x + CHIP_INDENT;
+        for (_, text, x) in &chips {
+            let (next, _) = buf.set_stringn(area.x + x, y, text, area.width as usize, chip_style);
+            end = next + 1;
+        }
+        if hidden > 0 {
+            let more = format!("+{hidden} more");
+            let room = (area.x + area.width).saturating_sub(end) as usize;
+            buf.set_stringn(
+                end,
+                y,
+                more,
+                room,
+                Style::default().fg(text_muted()).bg(input_bg()),
+            );
+        }
+    }
+
+    fn image_placeholder_base(path: &Path, width: u32, height: u32) -> String {
+        let file_label = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "image".to_string());
+        format!("{file_label} {width}x{height}")
+    }
+
     fn take_attached_images(&mut self, text: &str) -> (Vec<PathBuf>, Vec<String>) {
         let mut images = Vec::new();
         let mut placeholders = Vec::new();
@@ -773,28 +1115,33 @@ impl InputBox {
         (images, placeholders)
     }
 
-    fn handle_paste_image_path(&mut self, pasted: &str) -> bool {
-        let Some(path_buf) = normalize_pasted_path(pasted) else {
-            return false;
-        };
-
-        match image::image_dimensions(&path_buf) {
-            Ok((w, h)) => {
-                self.attach_image(path_buf, w, h);
-                true
+    /// Attach dropped images and mention other dropped files as `@path`
+    fn insert_dropped_paths(&mut self, paths: Vec<PathBuf>) {
+        for path in paths {
+            let dimensions = if is_image_path(&path) {
+                image::image_dimensions(&path)
+                    .inspect_err(|err| {
+                        tracing::debug!(path = %path.display(), error = %err, "Dropped image is unreadable; mentioning it instead");
+                    })
+                    .ok()
+            } else {
+                None
+            };
+            match dimensions {
+                Some((width, height)) => self.attach_image(path, width, height),
+                None => self.insert_str(&file_mention(&path)),
             }
-            Err(_) => false,
+            self.insert_str(" ");
         }
     }
 
-    fn next_large_paste_placeholder(&mut self, char_count: usize) -> String {
-        let base = format!("[Pasted Content {char_count} chars]");
-        let next_suffix = self.large_paste_counters.entry(char_count).or_insert(0);
-        *next_suffix += 1;
-        if *next_suffix == 1 {
-            base
+    fn next_large_paste_placeholder(&mut self, char_count: usize, line_count: usize) -> String {
+        self.paste_count += 1;
+        let number = self.paste_count;
+        if line_count > 1 {
+            format!("[pasted #{number}: {line_count} lines]")
         } else {
-            format!("{base} #{next_suffix}")
+            format!("[pasted #{number}: {char_count} chars]")
         }
     }
 
@@ -816,12 +1163,12 @@ impl InputBox {
 
     /// Get cursor position for rendering, accounting for scroll offset
     pub fn cursor_position(&self, area: Rect, scroll_offset: usize) -> (u16, u16) {
-        if area.height < 3 || area.width == 0 {
+        if area.height < self.min_height() || area.width == 0 {
             return (area.x, area.y);
         }
 
-        let padding_top: u16 = 1;
-        let padding_bottom: u16 = 1;
+        let padding_top = self.padding_top();
+        let padding_bottom = self.padding_bottom();
         let content_height = area.height.saturating_sub(padding_top + padding_bottom);
         if content_height == 0 {
             return (area.x, area.y + padding_top);
@@ -867,12 +1214,12 @@ impl InputBox {
     }
 
     pub fn scrollbar_metrics(&mut self, area: Rect) -> Option<ScrollbarMetrics> {
-        if area.height < 3 || area.width == 0 {
+        if area.height < self.min_height() || area.width == 0 {
             return None;
         }
 
-        let padding_top = 1;
-        let padding_bottom = 1;
+        let padding_top = self.padding_top();
+        let padding_bottom = self.padding_bottom();
         let content_height = area.height.saturating_sub(padding_top + padding_bottom);
         if content_height == 0 {
             return None;
@@ -912,12 +1259,12 @@ impl InputBox {
     }
 
     fn cursor_pos_from_point(&self, click_x: u16, click_y: u16, area: Rect) -> Option<usize> {
-        if area.height < 3 || area.width < 4 {
+        if area.height < self.min_height() || area.width < 4 {
             return None;
         }
 
-        let padding_top: u16 = 1;
-        let padding_bottom: u16 = 1;
+        let padding_top = self.padding_top();
+        let padding_bottom = self.padding_bottom();
         let content_height = area.height.saturating_sub(padding_top + padding_bottom);
         if content_height == 0 {
             return None;
@@ -996,9 +1343,9 @@ impl InputBox {
         };
         self.cursor_pos = pos;
         self.selection_head = Some(pos);
-        if area.height >= 3 && area.width > 0 {
-            let padding_top = 1;
-            let padding_bottom = 1;
+        if area.height >= self.min_height() && area.width > 0 {
+            let padding_top = self.padding_top();
+            let padding_bottom = self.padding_bottom();
             let content_height = area.height.saturating_sub(padding_top + padding_bottom);
             let visible_lines = content_height as usize;
             let base_width = area.width;
@@ -1045,12 +1392,12 @@ impl InputBox {
             }
         }
 
-        if area.height < 3 || area.width == 0 {
+        if area.height < self.min_height() || area.width == 0 {
             return;
         }
 
-        let padding_top = 1;
-        let padding_bottom = 1;
+        let padding_top = self.padding_top();
+        let padding_bottom = self.padding_bottom();
         let content_height = area.height.saturating_sub(padding_top + padding_bottom);
         if content_height == 0 {
             return;
@@ -1136,6 +1483,10 @@ impl InputBox {
 
         paragraph.render(content_area, buf);
 
+        if self.chip_rows() > 0 {
+            self.render_chips(area, content_area.y + content_height, buf);
+        }
+
         // Render scrollbar
         render_minimal_scrollbar(
             Rect {
@@ -1152,6 +1503,16 @@ impl InputBox {
     }
 }
 
+/// `@path` reference to a dropped file, quoted when the path has spaces
+fn file_mention(path: &Path) -> String {
+    let path = path.display().to_string();
+    if path.contains(char::is_whitespace) {
+        format!("@\"{path}\"")
+    } else {
+        format!("@{path}")
+    }
+}
+
 fn wrap_line_segments(line: &str, max_width: usize) -> Vec<(usize, usize)> {
     if line.is_empty() {
         return vec![(0, 0)];
@@ -1275,3 +1636,146 @@ impl Default for InputBox {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(text: &str) -> InputBox {
+        let mut input = InputBox::new();
+        for c in text.chars() {
+            input.insert_char(c);
+        }
+        input
+    }
+
+    #[test]
+    fn test_large_paste_is_a_placeholder_expanded_on_submit() {
+        let log: String = (1..=512).map(|n| format!("line {n}\n")).collect();
+        let mut input = InputBox::new();
+        input.insert_str("why does this fail? ");
+        input.handle_paste(log.clone());
+        input.handle_paste("short".to_string());
+        assert_eq!(
+            input.input(),
+            "why does this fail? [pasted #1: 512 lines]short"
+        );
+
+        let submitted = input.submit();
+        assert_eq!(submitted.text, format!("why does this fail? {log}short"));
+
+        input.handle_paste("x".repeat(LARGE_PASTE_CHAR_THRESHOLD + 1));
+        assert_eq!(input.input(), "[pasted #1: 1001 chars]");
+    }
+
+    #[test]
+    fn test_undo_restores_text_lost_to_a_kill_command() {
+        let mut input = typed("a long prompt");
+        input.delete_to_start();
+        assert_eq!(input.input(), "");
+
+        assert!(input.undo());
+        assert_eq!(input.input(), "a long prompt");
+        assert_eq!(input.cursor_pos, "a long prompt".len());
+
+        assert!(input.redo());
+        assert_eq!(input.input(), "");
+        assert!(!input.redo());
+    }
+
+    #[test]
+    fn test_typing_is_undone_a_word_at_a_time() {
+        let mut input = typed("fix the");
+        input.backspace();
+        input.backspace();
+
+        assert!(input.undo());
+        assert_eq!(input.input(), "fix the");
+        assert!(input.undo());
+        assert_eq!(input.input(), "fix");
+        assert!(input.undo());
+        assert_eq!(input.input(), "");
+        assert!(!input.undo());
+    }
+
+    #[test]
+    fn test_new_edit_drops_redo_and_history_recall_is_undoable() {
+        let mut input = InputBox::new();
+        input.set_history(vec!["earlier prompt".to_string()]);
+        input.insert_str("draft");
+        input.history_prev();
+        assert_eq!(input.input(), "earlier prompt");
+
+        assert!(input.undo());
+        assert_eq!(input.input(), "draft");
+        input.insert_char('!');
+        assert!(!input.redo());
+
+        input.submit();
+        assert!(!input.undo());
+    }
+
+    #[test]
+    fn test_dropped_files_become_mentions_or_attachments() {
+        let dir = tempfile::tempdir().unwrap();
+        let notes = dir.path().join("release notes.md");
+        std::fs::write(&notes, "v1").unwrap();
+        let shot = dir.path().join("shot.png");
+        image::RgbImage::new(4, 3).save(&shot).unwrap();
+
+        let mut input = typed("see ");
+        input.handle_paste(format!("file://{}", shot.display()));
+        input.handle_paste(format!("'{}'", notes.display()));
+        assert_eq!(
+            input.input(),
+            format!("see [shot.png 4x3] @\"{}\" ", notes.display())
+        );
+        assert_eq!(input.attachment_chips().len(), 1);
+
+        let mut input = InputBox::new();
+        input.handle_paste("/no/such/file.rs".to_string());
+        assert_eq!(input.input(), "/no/such/file.rs");
+    }
+
+    #[test]
+    fn test_attachment_chips_are_removable() {
+        let mut input = typed("compare ");
+        input.attach_image(PathBuf::from("/tmp/before.png"), 640, 480);
+        input.insert_str(" and ");
+        input.attach_image(PathBuf::from("/tmp/after.png"), 640, 480);
+        let labels: Vec<String> = input
+            .attachment_chips()
+            .into_iter()
+            .map(|chip| chip.label)
+            .collect();
+        assert_eq!(labels, ["before.png 640x480", "after.png 640x480"]);
+        assert_eq!(input.desired_height(10, 80), 4);
+
+        assert!(input.remove_last_attachment());
+        assert_eq!(input.input(), "compare [before.png 640x480] and ");
+
+        let area = Rect::new(0, 0, 80, 4);
+        assert!(!input.remove_attachment_at(CHIP_INDENT, 1, area));
+        assert!(input.remove_attachment_at(CHIP_INDENT, 2, area));
+        assert_eq!(input.input(), "compare  and ");
+        assert!(input.attachment_chips().is_empty());
+        assert_eq!(input.desired_height(10, 80), 3);
+
+        assert!(input.undo());
+        assert_eq!(input.attachment_chips().len(), 1);
+    }
+
+    #[test]
+    fn test_compact_input_drops_padding_rows() {
+        let mut input = typed("hello");
+        assert_eq!(input.desired_height(10, 80), 3);
+
+        input.set_compact(true);
+        assert_eq!(input.desired_height(10, 80), 1);
+        let area = Rect::new(0, 5, 80, 1);
+        assert_eq!(input.cursor_position(area, 0).1, 5);
+
+        input.insert_str("\nworld");
+        assert_eq!(input.desired_height(10, 80), 2);
+    }
+}
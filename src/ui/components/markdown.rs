@@ -1,10 +1,13 @@
 use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
 use ratatui::{
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span, Text},
 };
 
-use super::theme::{markdown_code_bg, markdown_inline_code_bg};
+use super::theme::{
+    accent_primary, accent_secondary, accent_success, accent_warning, markdown_code_bg,
+    markdown_inline_code_bg, text_muted, text_primary, text_secondary,
+};
 
 /// Custom markdown renderer with table support
 pub struct MarkdownRenderer {
@@ -15,7 +18,7 @@ pub struct MarkdownRenderer {
 impl MarkdownRenderer {
     pub fn new() -> Self {
         Self {
-            base_style: Style::default().fg(Color::White),
+            base_style: Style::default().fg(text_primary()),
         }
     }
 
@@ -57,11 +60,10 @@ impl MarkdownRenderer {
                         style_stack.push(style);
                     }
                     Tag::BlockQuote(_) => {
-                        current_spans
-                            .push(Span::styled("│ ", Style::default().fg(Color::DarkGray)));
+                        current_spans.push(Span::styled("│ ", Style::default().fg(text_muted())));
                         style_stack.push(
                             Style::default()
-                                .fg(Color::Gray)
+                                .fg(text_secondary())
                                 .add_modifier(Modifier::ITALIC),
                         );
                     }
@@ -91,7 +93,8 @@ impl MarkdownRenderer {
                         } else {
                             format!("{}• ", indent)
                         };
-                        current_spans.push(Span::styled(bullet, Style::default().fg(Color::Cyan)));
+                        current_spans
+                            .push(Span::styled(bullet, Style::default().fg(accent_primary())));
                     }
                     Tag::Emphasis => {
                         let current = *style_stack.last().unwrap_or(&self.base_style);
@@ -108,7 +111,7 @@ impl MarkdownRenderer {
                     Tag::Link { .. } => {
                         style_stack.push(
                             Style::default()
-                                .fg(Color::Blue)
+                                .fg(accent_primary())
                                 .add_modifier(Modifier::UNDERLINED),
                         );
                     }
@@ -151,11 +154,12 @@ impl MarkdownRenderer {
                     TagEnd::CodeBlock => {
                         in_code_block = false;
                         // Render code block with background
-                        let code_style = Style::default().fg(Color::Green).bg(markdown_code_bg());
+                        let code_style =
+                            Style::default().fg(accent_success()).bg(markdown_code_bg());
 
                         lines.push(Line::from(Span::styled(
                             "```",
-                            Style::default().fg(Color::DarkGray),
+                            Style::default().fg(text_muted()),
                         )));
                         for code_line in code_block_content.lines() {
                             lines.push(Line::from(Span::styled(
@@ -165,7 +169,7 @@ impl MarkdownRenderer {
                         }
                         lines.push(Line::from(Span::styled(
                             "```",
-                            Style::default().fg(Color::DarkGray),
+                            Style::default().fg(text_muted()),
                         )));
                         lines.push(Line::from(""));
                     }
@@ -225,7 +229,7 @@ impl MarkdownRenderer {
                         current_spans.push(Span::styled(
                             format!("`{}`", code),
                             Style::default()
-                                .fg(Color::Yellow)
+                                .fg(accent_warning())
                                 .bg(markdown_inline_code_bg()),
                         ));
                     }
@@ -245,7 +249,7 @@ impl MarkdownRenderer {
                 Event::Rule => {
                     lines.push(Line::from(Span::styled(
                         "─".repeat(40),
-                        Style::default().fg(Color::DarkGray),
+                        Style::default().fg(text_muted()),
                     )));
                     lines.push(Line::from(""));
                 }
@@ -253,7 +257,11 @@ impl MarkdownRenderer {
                     let marker = if checked { "☑ " } else { "☐ " };
                     current_spans.push(Span::styled(
                         marker,
-                        Style::default().fg(if checked { Color::Green } else { Color::Gray }),
+                        Style::default().fg(if checked {
+                            accent_success()
+                        } else {
+                            text_secondary()
+                        }),
                     ));
                 }
                 _ => {}
@@ -271,19 +279,19 @@ impl MarkdownRenderer {
     fn heading_style(&self, level: HeadingLevel) -> Style {
         match level {
             HeadingLevel::H1 => Style::default()
-                .fg(Color::Magenta)
+                .fg(accent_secondary())
                 .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
             HeadingLevel::H2 => Style::default()
-                .fg(Color::Cyan)
+                .fg(accent_primary())
                 .add_modifier(Modifier::BOLD),
             HeadingLevel::H3 => Style::default()
-                .fg(Color::Blue)
+                .fg(accent_primary())
                 .add_modifier(Modifier::BOLD),
             HeadingLevel::H4 => Style::default()
-                .fg(Color::Yellow)
+                .fg(accent_warning())
                 .add_modifier(Modifier::BOLD),
-            HeadingLevel::H5 => Style::default().fg(Color::Green),
-            HeadingLevel::H6 => Style::default().fg(Color::Gray),
+            HeadingLevel::H5 => Style::default().fg(accent_success()),
+            HeadingLevel::H6 => Style::default().fg(text_secondary()),
         }
     }
 
@@ -314,11 +322,11 @@ impl MarkdownRenderer {
             *w = (*w).max(3);
         }
 
-        let border_style = Style::default().fg(Color::DarkGray);
+        let border_style = Style::default().fg(text_muted());
         let header_style = Style::default()
-            .fg(Color::Cyan)
+            .fg(accent_primary())
             .add_modifier(Modifier::BOLD);
-        let cell_style = Style::default().fg(Color::White);
+        let cell_style = Style::default().fg(text_primary());
 
         // Top border
         let top_border = self.table_border(&col_widths, '┌', '┬', '┐', '─');
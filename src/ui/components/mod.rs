@@ -6,6 +6,7 @@ mod chat_view;
 mod command_palette;
 mod confirmation_dialog;
 mod dialog;
+mod diff_view;
 mod error_dialog;
 pub mod file_path_detector;
 mod file_viewer_view;
@@ -21,8 +22,10 @@ mod missing_tool_dialog;
 mod model_selector;
 mod path_input;
 mod project_picker;
+mod raw_events_json;
 mod raw_events_types;
 mod raw_events_view;
+mod rename_tab_dialog;
 mod scrollbar;
 mod searchable_list;
 mod session_header;
@@ -36,6 +39,7 @@ mod text_input;
 pub mod theme;
 mod theme_picker;
 mod thinking_indicator;
+mod timeline_view;
 mod tree_view;
 mod turn_summary;
 
@@ -43,7 +47,7 @@ pub use add_repo_dialog::{AddRepoDialog, AddRepoDialogState};
 pub use agent_selector::{AgentSelector, AgentSelectorState};
 pub use base_dir_dialog::{BaseDirDialog, BaseDirDialogState};
 pub use chat_message::{ChatMessage, MessageRole};
-pub use chat_view::{ChatView, HoveredFilePath};
+pub use chat_view::{ChatMemoryLimits, ChatView, HoveredFilePath};
 pub use command_palette::{CommandPalette, CommandPaletteEntry, CommandPaletteState};
 pub use confirmation_dialog::{
     ConfirmationContext, ConfirmationDialog, ConfirmationDialogState, ConfirmationType,
@@ -52,14 +56,15 @@ pub use dialog::{
     dialog_content_area, DialogFrame, InstructionBar, StatusLine, DIALOG_CONTENT_PADDING_X,
     DIALOG_CONTENT_PADDING_Y,
 };
+pub use diff_view::{DiffView, DiffViewState};
 pub use error_dialog::{ErrorDialog, ErrorDialogState};
 pub use file_viewer_view::FileViewerView;
-pub use global_footer::{FooterContext, GlobalFooter};
+pub use global_footer::{FooterContext, FooterHint, GlobalFooter};
 pub use help_dialog::{HelpCategory, HelpDialog, HelpDialogState, KeybindingEntry};
 pub use inline_prompt::{
     InlinePrompt, InlinePromptState, InlinePromptType, PromptAction, PromptAnswer, PromptResponse,
 };
-pub use input_box::InputBox;
+pub use input_box::{AttachmentChip, InputBox};
 pub use key_hints::{render_key_hints, render_key_hints_responsive, KeyHintBarStyle};
 pub use knight_rider_spinner::KnightRiderSpinner;
 pub use logo_shine::LogoShineAnimation;
@@ -76,6 +81,7 @@ pub use raw_events_types::{
     EventDetailState, EventDirection, RawEventEntry, DETAIL_PANEL_BREAKPOINT,
 };
 pub use raw_events_view::{RawEventsClick, RawEventsScrollbarMetrics, RawEventsView};
+pub use rename_tab_dialog::{RenameTabDialog, RenameTabDialogState};
 pub use scrollbar::{render_minimal_scrollbar, scrollbar_offset_from_point, ScrollbarMetrics};
 pub use searchable_list::SearchableListState;
 pub use session_header::SessionHeader;
@@ -87,6 +93,7 @@ pub use status_bar::StatusBar;
 pub use tab_bar::{TabBar, TabBarHitTarget};
 pub use text_input::TextInputState;
 pub use theme_picker::{ThemePicker, ThemePickerItem, ThemePickerState};
+pub use timeline_view::{SessionTimeline, TimelineView, ToolSpan, TurnTiming};
 // Theme system - new dynamic API (use these for new code)
 pub use theme::{
     // Accent colors (functions)
@@ -190,4 +197,6 @@ pub use tree_view::{
     ActionType, NodeType, SidebarData, SidebarGitDisplay, TreeNode, TreeView, TreeViewState,
     SIDEBAR_GIT_DISPLAY,
 };
-pub use turn_summary::{FileChange, TurnSummary};
+pub use turn_summary::{
+    find_pr_numbers, CommandRun, FileChange, TurnReference, TurnSummary, VerifyOutcome,
+};
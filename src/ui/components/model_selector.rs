@@ -133,6 +133,14 @@ impl ModelSelectorState {
             }
         }
 
+        let local_models = ModelRegistry::local_models();
+        if !local_models.is_empty() {
+            items.push(ModelSelectorItem::SectionHeader(AgentType::Local));
+            for model in local_models {
+                items.push(ModelSelectorItem::Model(model));
+            }
+        }
+
         items
     }
 
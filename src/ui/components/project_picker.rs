@@ -3,14 +3,14 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Layout, Rect},
-    style::{Color, Style},
+    style::Style,
     widgets::{Paragraph, Widget},
 };
 use std::path::PathBuf;
 
 use super::{
     dialog_bg, dialog_content_area, ensure_contrast_bg, ensure_contrast_fg,
-    render_minimal_scrollbar, selected_bg, text_primary, DialogFrame, ScrollbarMetrics,
+    render_minimal_scrollbar, selected_bg, text_muted, text_primary, DialogFrame, ScrollbarMetrics,
     SearchableListState,
 };
 
@@ -299,9 +299,9 @@ impl ProjectPicker {
             format!("Search: {}", state.list.search.value())
         };
         let search_style = if state.list.search.is_empty() {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(text_muted())
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(text_primary())
         };
         let search_label = Paragraph::new(search_display).style(search_style);
         search_label.render(chunks[0], buf);
@@ -318,7 +318,7 @@ impl ProjectPicker {
 
         // Render separator
         let separator = "─".repeat(inner.width as usize);
-        let sep_paragraph = Paragraph::new(separator).style(Style::default().fg(Color::DarkGray));
+        let sep_paragraph = Paragraph::new(separator).style(Style::default().fg(text_muted()));
         sep_paragraph.render(chunks[1], buf);
 
         // Render project list
@@ -330,7 +330,7 @@ impl ProjectPicker {
                 "No projects match your search"
             };
             let empty = Paragraph::new(empty_msg)
-                .style(Style::default().fg(Color::DarkGray))
+                .style(Style::default().fg(text_muted()))
                 .alignment(Alignment::Center);
             empty.render(list_area, buf);
         } else {
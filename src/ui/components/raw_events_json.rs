@@ -0,0 +1,301 @@
+//! JSON helpers for the raw events detail panel: jq-like path filters and
+//! pretty-printing with folded nested containers.
+
+use serde_json::Value;
+
+/// A single step in a [`JsonPath`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    /// `.key`, `."key"` or `["key"]`
+    Key(String),
+    /// `[0]`
+    Index(usize),
+    /// `[]` (every array element or object value)
+    Iterate,
+}
+
+/// A jq-like path such as `.params.msg.type` or `.content[].text`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath {
+    segments: Vec<PathSegment>,
+}
+
+impl JsonPath {
+    /// Parse a path expression. `.` alone selects the whole value.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let mut chars = input.chars().peekable();
+        if chars.next() != Some('.') {
+            return Err("path must start with '.'".to_string());
+        }
+
+        let mut segments = Vec::new();
+        // The leading '.' may be followed directly by a key
+        let mut expect_key = true;
+        while let Some(&ch) = chars.peek() {
+            match ch {
+                '.' if !expect_key => {
+                    chars.next();
+                    expect_key = true;
+                }
+                '[' => {
+                    chars.next();
+                    let mut inner = String::new();
+                    loop {
+                        match chars.next() {
+                            Some(']') => break,
+                            Some(c) => inner.push(c),
+                            None => return Err("unclosed '['".to_string()),
+                        }
+                    }
+                    let inner = inner.trim();
+                    let segment = if inner.is_empty() {
+                        PathSegment::Iterate
+                    } else if let Some(key) = Self::unquote(inner) {
+                        PathSegment::Key(key)
+                    } else {
+                        inner
+                            .parse::<usize>()
+                            .map(PathSegment::Index)
+                            .map_err(|_| format!("invalid index '{inner}'"))?
+                    };
+                    segments.push(segment);
+                    expect_key = false;
+                }
+                '"' if expect_key => {
+                    chars.next();
+                    let mut key = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some(c) => key.push(c),
+                            None => return Err("unclosed '\"'".to_string()),
+                        }
+                    }
+                    segments.push(PathSegment::Key(key));
+                    expect_key = false;
+                }
+                _ if expect_key && Self::is_key_char(ch) => {
+                    let mut key = String::new();
+                    while let Some(&c) = chars.peek() {
+                        if !Self::is_key_char(c) {
+                            break;
+                        }
+                        key.push(c);
+                        chars.next();
+                    }
+                    segments.push(PathSegment::Key(key));
+                    expect_key = false;
+                }
+                _ => return Err(format!("unexpected '{ch}'")),
+            }
+        }
+
+        if expect_key && !segments.is_empty() {
+            return Err("path ends with '.'".to_string());
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Collect every value the path selects. Missing keys and indices select nothing.
+    pub fn apply<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let mut current = vec![value];
+        for segment in &self.segments {
+            current = current
+                .into_iter()
+                .flat_map(|value| -> Vec<&'a Value> {
+                    match (segment, value) {
+                        (PathSegment::Key(key), Value::Object(map)) => {
+                            map.get(key).into_iter().collect()
+                        }
+                        (PathSegment::Index(index), Value::Array(items)) => {
+                            items.get(*index).into_iter().collect()
+                        }
+                        (PathSegment::Iterate, Value::Array(items)) => items.iter().collect(),
+                        (PathSegment::Iterate, Value::Object(map)) => map.values().collect(),
+                        _ => Vec::new(),
+                    }
+                })
+                .collect();
+        }
+        current
+    }
+
+    fn is_key_char(ch: char) -> bool {
+        !matches!(ch, '.' | '[' | ']' | '"') && !ch.is_whitespace()
+    }
+
+    fn unquote(text: &str) -> Option<String> {
+        text.strip_prefix('"')
+            .and_then(|rest| rest.strip_suffix('"'))
+            .map(str::to_string)
+    }
+}
+
+/// Deepest nesting level of a non-empty object or array (the root is level 0)
+pub fn max_container_level(value: &Value) -> Option<usize> {
+    let children: Vec<&Value> = match value {
+        Value::Object(map) if !map.is_empty() => map.values().collect(),
+        Value::Array(items) if !items.is_empty() => items.iter().collect(),
+        _ => return None,
+    };
+    Some(
+        children
+            .into_iter()
+            .filter_map(max_container_level)
+            .map(|level| level + 1)
+            .max()
+            .unwrap_or(0),
+    )
+}
+
+/// Pretty-print JSON with two-space indentation, collapsing non-empty
+/// containers nested at `fold_level` or deeper into a one-line summary.
+pub fn pretty_lines(value: &Value, fold_level: Option<usize>) -> Vec<String> {
+    let mut lines = Vec::new();
+    push_value(&mut lines, value, String::new(), 0, fold_level, "");
+    lines
+}
+
+fn push_value(
+    lines: &mut Vec<String>,
+    value: &Value,
+    prefix: String,
+    level: usize,
+    fold_level: Option<usize>,
+    suffix: &str,
+) {
+    let indent = "  ".repeat(level);
+    let folded = level > 0 && fold_level.is_some_and(|fold_level| level >= fold_level);
+
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            if folded {
+                let noun = if map.len() == 1 { "key" } else { "keys" };
+                lines.push(format!("{prefix}{{… {} {noun}}}{suffix}", map.len()));
+                return;
+            }
+            lines.push(format!("{prefix}{{"));
+            let last = map.len() - 1;
+            for (i, (key, child)) in map.iter().enumerate() {
+                let child_prefix = format!("{indent}  {}: ", Value::String(key.clone()));
+                let child_suffix = if i < last { "," } else { "" };
+                push_value(
+                    lines,
+                    child,
+                    child_prefix,
+                    level + 1,
+                    fold_level,
+                    child_suffix,
+                );
+            }
+            lines.push(format!("{indent}}}{suffix}"));
+        }
+        Value::Array(items) if !items.is_empty() => {
+            if folded {
+                let noun = if items.len() == 1 { "item" } else { "items" };
+                lines.push(format!("{prefix}[… {} {noun}]{suffix}", items.len()));
+                return;
+            }
+            lines.push(format!("{prefix}["));
+            let last = items.len() - 1;
+            for (i, child) in items.iter().enumerate() {
+                let child_suffix = if i < last { "," } else { "" };
+                push_value(
+                    lines,
+                    child,
+                    format!("{indent}  "),
+                    level + 1,
+                    fold_level,
+                    child_suffix,
+                );
+            }
+            lines.push(format!("{indent}]{suffix}"));
+        }
+        _ => lines.push(format!("{prefix}{value}{suffix}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> Value {
+        json!({
+            "method": "codex/event/agent_message",
+            "params": {
+                "msg": { "type": "agent_message", "message": "hi" },
+                "items": [{ "text": "a" }, { "text": "b" }]
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse_and_apply_key_path() {
+        let value = sample();
+        let path = JsonPath::parse(".params.msg.type").unwrap();
+        assert_eq!(path.apply(&value), vec![&json!("agent_message")]);
+    }
+
+    #[test]
+    fn test_identity_index_and_iterate() {
+        let value = sample();
+        assert_eq!(JsonPath::parse(".").unwrap().apply(&value), vec![&value]);
+        assert_eq!(
+            JsonPath::parse(".params.items[1].text")
+                .unwrap()
+                .apply(&value),
+            vec![&json!("b")]
+        );
+        assert_eq!(
+            JsonPath::parse(".params.items[].text")
+                .unwrap()
+                .apply(&value),
+            vec![&json!("a"), &json!("b")]
+        );
+        assert_eq!(
+            JsonPath::parse(r#".["method"]"#).unwrap().apply(&value),
+            vec![&json!("codex/event/agent_message")]
+        );
+        assert!(JsonPath::parse(".params.missing")
+            .unwrap()
+            .apply(&value)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(JsonPath::parse("params").is_err());
+        assert!(JsonPath::parse(".params.").is_err());
+        assert!(JsonPath::parse(".items[x]").is_err());
+        assert!(JsonPath::parse(".items[0").is_err());
+    }
+
+    #[test]
+    fn test_pretty_lines_match_serde_when_unfolded() {
+        let value = sample();
+        let expected: Vec<String> = serde_json::to_string_pretty(&value)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect();
+        assert_eq!(pretty_lines(&value, None), expected);
+    }
+
+    #[test]
+    fn test_pretty_lines_fold_nested_containers() {
+        let value = sample();
+        assert_eq!(max_container_level(&value), Some(3));
+        assert_eq!(
+            pretty_lines(&value, Some(1)),
+            vec![
+                "{".to_string(),
+                r#"  "method": "codex/event/agent_message","#.to_string(),
+                r#"  "params": {… 2 keys}"#.to_string(),
+                "}".to_string(),
+            ]
+        );
+    }
+}
@@ -8,6 +8,11 @@ use ratatui::{
 };
 use serde_json::Value;
 
+use super::{
+    accent_error, accent_primary, accent_secondary, accent_success, accent_warning, text_muted,
+    text_primary,
+};
+
 /// State for the event detail panel
 #[derive(Debug, Clone, Default)]
 pub struct EventDetailState {
@@ -17,6 +22,8 @@ pub struct EventDetailState {
     pub event_index: usize,
     /// Scroll offset within the detail content
     pub scroll_offset: usize,
+    /// Nesting level at which JSON containers are folded (None = fully expanded)
+    pub fold_level: Option<usize>,
 }
 
 impl EventDetailState {
@@ -75,6 +82,25 @@ impl EventDetailState {
         );
     }
 
+    /// Fold one more level of nesting, starting from the deepest
+    pub fn fold(&mut self, max_level: Option<usize>) {
+        let Some(max_level) = max_level.filter(|level| *level > 0) else {
+            return;
+        };
+        self.fold_level = Some(match self.fold_level {
+            Some(level) => level.min(max_level).saturating_sub(1).max(1),
+            None => max_level,
+        });
+    }
+
+    /// Unfold one level of nesting
+    pub fn unfold(&mut self, max_level: Option<usize>) {
+        self.fold_level = match (self.fold_level, max_level) {
+            (Some(level), Some(max_level)) if level < max_level => Some(level + 1),
+            _ => None,
+        };
+    }
+
     /// Jump to top
     pub fn scroll_to_top(&mut self) {
         self.scroll_offset = 0;
@@ -106,8 +132,8 @@ impl EventDirection {
 
     pub fn color(&self) -> Color {
         match self {
-            EventDirection::Sent => Color::Green,
-            EventDirection::Received => Color::Cyan,
+            EventDirection::Sent => accent_success(),
+            EventDirection::Received => accent_primary(),
         }
     }
 }
@@ -159,17 +185,17 @@ impl RawEventEntry {
         let summary = self.compact_summary();
 
         Line::from(vec![
-            Span::styled(prefix.to_string(), Style::default().fg(Color::Cyan)),
-            Span::styled(format!("[{}] ", timestamp), style.fg(Color::DarkGray)),
+            Span::styled(prefix.to_string(), Style::default().fg(accent_primary())),
+            Span::styled(format!("[{}] ", timestamp), style.fg(text_muted())),
             Span::styled(
                 format!("{} ", self.direction.symbol()),
                 style.fg(self.direction.color()),
             ),
             Span::styled(
                 format!("{}: ", self.event_type),
-                style.fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                style.fg(accent_warning()).add_modifier(Modifier::BOLD),
             ),
-            Span::styled(summary, style.fg(Color::White)),
+            Span::styled(summary, style.fg(text_primary())),
         ])
     }
 
@@ -241,7 +267,7 @@ impl RawEventEntry {
         let Ok(pretty) = serde_json::to_string_pretty(&self.raw_json) else {
             return vec![Line::from(Span::styled(
                 "(invalid json)",
-                Style::default().fg(Color::Red),
+                Style::default().fg(accent_error()),
             ))];
         };
 
@@ -255,7 +281,7 @@ impl RawEventEntry {
             lines.truncate(max_lines);
             lines.push(Line::from(Span::styled(
                 format!("… {} more lines", remaining),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(text_muted()),
             )));
         }
 
@@ -316,9 +342,9 @@ impl RawEventEntry {
                 }
 
                 let style = if is_key {
-                    Style::default().fg(Color::Cyan)
+                    Style::default().fg(accent_primary())
                 } else {
-                    Style::default().fg(Color::Green)
+                    Style::default().fg(accent_success())
                 };
                 spans.push(Span::styled(token.to_string(), style));
 
@@ -346,9 +372,9 @@ impl RawEventEntry {
             }
 
             let style = match ch {
-                '{' | '}' | '[' | ']' | ',' | ':' => Style::default().fg(Color::DarkGray),
+                '{' | '}' | '[' | ']' | ',' | ':' => Style::default().fg(text_muted()),
                 _ if ch.is_whitespace() => Style::default(),
-                _ => Style::default().fg(Color::White),
+                _ => Style::default().fg(text_primary()),
             };
             spans.push(Span::styled(ch.to_string(), style));
         }
@@ -371,13 +397,13 @@ impl RawEventEntry {
     fn get_value_style(value: &str) -> Style {
         let trimmed = value.trim();
         if trimmed == "true" || trimmed == "false" {
-            Style::default().fg(Color::Magenta)
+            Style::default().fg(accent_secondary())
         } else if trimmed == "null" {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(text_muted())
         } else if trimmed.parse::<f64>().is_ok() {
-            Style::default().fg(Color::Yellow)
+            Style::default().fg(accent_warning())
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(text_primary())
         }
     }
 }
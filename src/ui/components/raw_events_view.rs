@@ -1,10 +1,11 @@
 use std::collections::HashSet;
 use std::time::Instant;
 
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     symbols::border,
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
@@ -12,10 +13,14 @@ use ratatui::{
 use serde_json::Value;
 use unicode_width::UnicodeWidthStr;
 
+use super::raw_events_json::{max_container_level, pretty_lines, JsonPath};
 use super::raw_events_types::{
     EventDetailState, EventDirection, RawEventEntry, DETAIL_PANEL_BREAKPOINT,
 };
-use super::{accent_primary, render_minimal_scrollbar, ScrollbarMetrics};
+use super::{
+    accent_error, accent_primary, accent_warning, bg_base, border_default, border_focused,
+    render_minimal_scrollbar, selected_bg, text_muted, text_primary, ScrollbarMetrics,
+};
 
 pub enum RawEventsClick {
     SessionId,
@@ -41,6 +46,17 @@ pub struct RawEventsView {
     session_id: Option<String>,
     /// Whether mouse is hovering over the session ID
     session_id_hovered: bool,
+    /// jq-like path applied to the event shown in the detail panel
+    filter: String,
+    /// Whether the path filter box is capturing typing
+    filter_focused: bool,
+    /// Text searched for across serialized event payloads
+    search: String,
+    /// Whether the search box is capturing typing
+    search_focused: bool,
+    /// Whether new events take the selection; when off, selection and scroll
+    /// stay put while events arrive
+    auto_follow: bool,
 }
 
 pub struct RawEventsScrollbarMetrics {
@@ -60,6 +76,11 @@ impl RawEventsView {
             event_detail: EventDetailState::new(),
             session_id: None,
             session_id_hovered: false,
+            filter: String::new(),
+            filter_focused: false,
+            search: String::new(),
+            search_focused: false,
+            auto_follow: true,
         }
     }
 
@@ -98,7 +119,7 @@ impl RawEventsView {
         was_hovered != self.session_id_hovered
     }
 
-    /// Add a new event and select it
+    /// Add a new event, selecting it when following the newest event
     pub fn push_event(
         &mut self,
         direction: EventDirection,
@@ -111,9 +132,26 @@ impl RawEventsView {
             raw_json,
             self.session_start,
         ));
-        // Auto-select new event (keep existing expansions)
-        self.selected_index = self.events.len().saturating_sub(1);
-        self.follow_selection = true;
+        if self.auto_follow {
+            // Auto-select new event (keep existing expansions)
+            self.selected_index = self.events.len().saturating_sub(1);
+            self.follow_selection = true;
+        }
+    }
+
+    /// Toggle sticking to the newest event. Turning it on jumps there.
+    pub fn toggle_auto_follow(&mut self) {
+        self.auto_follow = !self.auto_follow;
+        if self.auto_follow && !self.events.is_empty() {
+            self.selected_index = self.events.len() - 1;
+            self.follow_selection = true;
+            self.event_detail.sync_to_event(self.selected_index);
+        }
+    }
+
+    /// Whether new events take the selection
+    pub fn is_auto_follow(&self) -> bool {
+        self.auto_follow
     }
 
     /// Move selection to previous event
@@ -205,11 +243,229 @@ impl RawEventsView {
         self.event_detail.visible
     }
 
-    /// Get the selected event's JSON as pretty-printed string (for copy action)
+    /// Get the selected event's JSON as pretty-printed string (for copy action).
+    /// When a valid path filter matches, only the selected values are returned.
     pub fn get_selected_json(&self) -> Option<String> {
+        let event = self.events.get(self.selected_index)?;
+        let values = match self.filtered_values(event) {
+            Ok(values) if !values.is_empty() => values,
+            _ => vec![&event.raw_json],
+        };
+        let pretty: Vec<String> = values
+            .into_iter()
+            .filter_map(|value| serde_json::to_string_pretty(value).ok())
+            .collect();
+        Some(pretty.join("\n"))
+    }
+
+    /// Focus the path filter box, opening the detail panel if needed
+    pub fn focus_filter(&mut self) {
+        if self.events.is_empty() {
+            return;
+        }
+        self.event_detail.sync_to_event(self.selected_index);
+        self.event_detail.visible = true;
+        self.filter_focused = true;
+    }
+
+    /// Check if the path filter box is capturing typing
+    pub fn is_filter_focused(&self) -> bool {
+        self.filter_focused
+    }
+
+    /// Get the current path filter
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    /// Handle a key while the path filter box is focused.
+    /// Enter keeps the filter, Esc clears it.
+    pub fn handle_filter_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.filter.push(c);
+            }
+            KeyCode::Backspace => {
+                self.filter.pop();
+            }
+            KeyCode::Enter => {
+                self.filter_focused = false;
+                return;
+            }
+            KeyCode::Esc => {
+                self.filter.clear();
+                self.filter_focused = false;
+            }
+            _ => return,
+        }
+        self.event_detail.scroll_to_top();
+    }
+
+    /// Append pasted text to the path filter
+    pub fn insert_filter_str(&mut self, text: &str) {
+        self.filter.extend(text.chars().filter(|c| !c.is_control()));
+        self.event_detail.scroll_to_top();
+    }
+
+    /// Focus the search box
+    pub fn focus_search(&mut self) {
+        if self.events.is_empty() {
+            return;
+        }
+        self.search_focused = true;
+    }
+
+    /// Check if the search box is capturing typing
+    pub fn is_search_focused(&self) -> bool {
+        self.search_focused
+    }
+
+    /// Get the current search text
+    pub fn search(&self) -> &str {
+        &self.search
+    }
+
+    /// Handle a key while the search box is focused. Typing jumps to the
+    /// first matching event from the selection; Enter keeps the search, Esc
+    /// clears it.
+    pub fn handle_search_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.search.push(c);
+            }
+            KeyCode::Backspace => {
+                self.search.pop();
+            }
+            KeyCode::Enter => {
+                self.search_focused = false;
+                return;
+            }
+            KeyCode::Esc => {
+                self.search.clear();
+                self.search_focused = false;
+                return;
+            }
+            _ => return,
+        }
+        self.jump_to_match(self.selected_index, true);
+    }
+
+    /// Append pasted text to the search
+    pub fn insert_search_str(&mut self, text: &str) {
+        self.search.extend(text.chars().filter(|c| !c.is_control()));
+        self.jump_to_match(self.selected_index, true);
+    }
+
+    /// Select the next event matching the search, wrapping around.
+    /// Returns false when no event matches.
+    pub fn search_next(&mut self) -> bool {
+        self.jump_to_match(self.selected_index + 1, true)
+    }
+
+    /// Select the previous event matching the search, wrapping around.
+    /// Returns false when no event matches.
+    pub fn search_prev(&mut self) -> bool {
+        let start = self
+            .selected_index
+            .checked_sub(1)
+            .unwrap_or(self.events.len().saturating_sub(1));
+        self.jump_to_match(start, false)
+    }
+
+    /// Indices of events whose serialized JSON contains the search text
+    pub fn search_matches(&self) -> Vec<usize> {
+        let Some(needle) = self.search_needle() else {
+            return Vec::new();
+        };
         self.events
-            .get(self.selected_index)
-            .and_then(|event| serde_json::to_string_pretty(&event.raw_json).ok())
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| Self::event_matches(event, &needle))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Lowercased search text, or None when there is nothing to search for
+    fn search_needle(&self) -> Option<String> {
+        (!self.search.is_empty()).then(|| self.search.to_ascii_lowercase())
+    }
+
+    fn event_matches(event: &RawEventEntry, needle: &str) -> bool {
+        event
+            .raw_json
+            .to_string()
+            .to_ascii_lowercase()
+            .contains(needle)
+    }
+
+    /// Select the first matching event from `start` (inclusive) in the given
+    /// direction, wrapping around, and scroll the detail panel to the match
+    fn jump_to_match(&mut self, start: usize, forward: bool) -> bool {
+        let Some(needle) = self.search_needle() else {
+            return false;
+        };
+        let len = self.events.len();
+        if len == 0 {
+            return false;
+        }
+        let start = start % len;
+        let found = (0..len)
+            .map(|step| {
+                if forward {
+                    (start + step) % len
+                } else {
+                    (start + len - step) % len
+                }
+            })
+            .find(|&index| Self::event_matches(&self.events[index], &needle));
+        let Some(index) = found else {
+            return false;
+        };
+        self.selected_index = index;
+        self.follow_selection = true;
+        self.event_detail.sync_to_event(index);
+        if let Some(line) = self
+            .build_detail_lines()
+            .iter()
+            .position(|line| line_text(line).to_ascii_lowercase().contains(&needle))
+        {
+            self.event_detail.scroll_offset = line.saturating_sub(2);
+        }
+        true
+    }
+
+    /// Fold one more level of nested JSON in the detail panel
+    pub fn fold_detail(&mut self) {
+        let max_level = self.detail_max_level();
+        self.event_detail.fold(max_level);
+    }
+
+    /// Unfold one level of nested JSON in the detail panel
+    pub fn unfold_detail(&mut self) {
+        let max_level = self.detail_max_level();
+        self.event_detail.unfold(max_level);
+    }
+
+    fn detail_max_level(&self) -> Option<usize> {
+        let event = self.events.get(self.event_detail.event_index)?;
+        let values = self.filtered_values(event).ok()?;
+        values.into_iter().filter_map(max_container_level).max()
+    }
+
+    /// Values selected by the path filter (the whole event when no filter is set)
+    fn filtered_values<'a>(&self, event: &'a RawEventEntry) -> Result<Vec<&'a Value>, String> {
+        if self.filter.trim().is_empty() {
+            return Ok(vec![&event.raw_json]);
+        }
+        JsonPath::parse(&self.filter).map(|path| path.apply(&event.raw_json))
     }
 
     /// Get the selected event index
@@ -224,7 +480,7 @@ impl RawEventsView {
         let Some(event) = self.events.get(self.event_detail.event_index) else {
             lines.push(Line::from(Span::styled(
                 "No event selected",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(text_muted()),
             )));
             return lines;
         };
@@ -240,32 +496,64 @@ impl RawEventsView {
                 format!("{} {} ", direction_symbol, direction_label),
                 Style::default().fg(event.direction.color()),
             ),
-            Span::styled(
-                event.format_timestamp(),
-                Style::default().fg(Color::DarkGray),
-            ),
+            Span::styled(event.format_timestamp(), Style::default().fg(text_muted())),
         ]));
 
         // Event type
         lines.push(Line::from(vec![
-            Span::styled("Type: ", Style::default().fg(Color::DarkGray)),
+            Span::styled("Type: ", Style::default().fg(text_muted())),
             Span::styled(
                 event.event_type.clone(),
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(accent_warning())
                     .add_modifier(Modifier::BOLD),
             ),
         ]));
 
+        // Path filter box
+        if self.filter_focused || !self.filter.is_empty() {
+            let mut filter_spans = vec![
+                Span::styled("Filter: ", Style::default().fg(text_muted())),
+                Span::styled(self.filter.clone(), Style::default().fg(text_primary())),
+            ];
+            if self.filter_focused {
+                filter_spans.push(Span::styled("▏", Style::default().fg(accent_primary())));
+            }
+            lines.push(Line::from(filter_spans));
+        }
+
         // Separator
         lines.push(Line::from(""));
 
-        // Full JSON with syntax highlighting (no line limit)
-        if let Ok(pretty) = serde_json::to_string_pretty(&event.raw_json) {
-            for json_line in pretty.lines() {
-                let mut highlighted = Vec::new();
-                highlighted.extend(RawEventEntry::highlight_json_line(json_line));
-                lines.push(Line::from(highlighted));
+        // Filtered JSON with syntax highlighting and folding (no line limit)
+        let values = match self.filtered_values(event) {
+            Ok(values) => values,
+            Err(err) => {
+                lines.push(Line::from(Span::styled(
+                    format!("Invalid path: {}", err),
+                    Style::default().fg(accent_error()),
+                )));
+                return lines;
+            }
+        };
+        let needle = self.search_needle();
+        if values.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No match",
+                Style::default().fg(text_muted()),
+            )));
+        }
+        for (i, value) in values.into_iter().enumerate() {
+            if i > 0 {
+                lines.push(Line::from(""));
+            }
+            for json_line in pretty_lines(value, self.event_detail.fold_level) {
+                let spans = RawEventEntry::highlight_json_line(&json_line);
+                let spans = match needle.as_deref() {
+                    Some(needle) => highlight_search_matches(spans, needle),
+                    None => spans,
+                };
+                lines.push(Line::from(spans));
             }
         }
 
@@ -364,7 +652,7 @@ impl RawEventsView {
         if self.events.is_empty() {
             lines.push(Line::from(Span::styled(
                 "  No events recorded yet",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(text_muted()),
             )));
             return (lines, 0, 0);
         }
@@ -380,7 +668,7 @@ impl RawEventsView {
             if is_expanded {
                 // Expanded view: header with ▼ prefix, then JSON
                 let bg_style = if is_selected {
-                    Style::default().bg(Color::Rgb(40, 40, 50))
+                    Style::default().bg(selected_bg())
                 } else {
                     Style::default()
                 };
@@ -392,7 +680,7 @@ impl RawEventsView {
                 lines.extend(json_lines);
             } else if is_selected {
                 // Selected but collapsed: ▶ prefix with highlight
-                let line = event.render_compact("▶", Style::default().bg(Color::Rgb(40, 40, 50)));
+                let line = event.render_compact("▶", Style::default().bg(selected_bg()));
                 lines.push(line);
             } else {
                 // Normal: space prefix
@@ -473,9 +761,25 @@ impl RawEventsView {
     }
 
     fn build_title_line(&self, max_width: usize) -> Line<'static> {
-        let left = format!(" Raw Events ({}) ", self.events.len());
+        let mut left = format!(" Raw Events ({}) ", self.events.len());
+        if !self.auto_follow {
+            left.push_str("· paused ");
+        }
+        if self.search_focused || !self.search.is_empty() {
+            let cursor = if self.search_focused { "▏" } else { "" };
+            let matches = self.search_matches();
+            let position = matches
+                .iter()
+                .position(|&index| index == self.selected_index)
+                .map_or_else(|| "-".to_string(), |pos| (pos + 1).to_string());
+            left.push_str(&format!(
+                "/{}{cursor} {position}/{} ",
+                self.search,
+                matches.len()
+            ));
+        }
         let left_width = UnicodeWidthStr::width(left.as_str());
-        let default_style = Style::default().fg(Color::DarkGray);
+        let default_style = Style::default().fg(text_muted());
 
         if let Some((prefix, session_id_value, suffix)) =
             self.session_id_parts(max_width.saturating_sub(left_width))
@@ -575,7 +879,7 @@ impl RawEventsView {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_set(border::ROUNDED)
-            .border_style(Style::default().fg(Color::Rgb(50, 50, 65)))
+            .border_style(Style::default().fg(border_default()))
             .title(title);
 
         let inner = block.inner(area);
@@ -626,7 +930,7 @@ impl RawEventsView {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_set(border::ROUNDED)
-            .border_style(Style::default().fg(Color::Rgb(50, 50, 65)));
+            .border_style(Style::default().fg(border_default()));
 
         let inner = block.inner(area);
         if inner.width < 3 || inner.height < 1 {
@@ -664,7 +968,7 @@ impl RawEventsView {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_set(border::ROUNDED)
-            .border_style(Style::default().fg(Color::Rgb(130, 170, 255)))
+            .border_style(Style::default().fg(border_focused()))
             .title(title);
 
         let inner = block.inner(area);
@@ -677,7 +981,7 @@ impl RawEventsView {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_set(border::ROUNDED)
-            .border_style(Style::default().fg(Color::Rgb(130, 170, 255)));
+            .border_style(Style::default().fg(border_focused()));
         let inner = block.inner(area);
         self.detail_content_scrollbar_metrics(inner)
     }
@@ -711,7 +1015,7 @@ impl RawEventsView {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_set(border::ROUNDED)
-            .border_style(Style::default().fg(Color::Rgb(130, 170, 255)))
+            .border_style(Style::default().fg(border_focused()))
             .title(title);
 
         let inner = block.inner(overlay_area);
@@ -732,7 +1036,7 @@ impl RawEventsView {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_set(border::ROUNDED)
-            .border_style(Style::default().fg(Color::Rgb(130, 170, 255)));
+            .border_style(Style::default().fg(border_focused()));
         let inner = block.inner(overlay_area);
         self.detail_content_scrollbar_metrics(inner)
     }
@@ -828,6 +1132,66 @@ impl RawEventsView {
     }
 }
 
+/// Plain text of a rendered line
+fn line_text(line: &Line<'_>) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+/// Split highlighted spans so every case-insensitive occurrence of `needle`
+/// stands out. `needle` must already be ASCII-lowercased.
+fn highlight_search_matches(spans: Vec<Span<'static>>, needle: &str) -> Vec<Span<'static>> {
+    let text: String = spans.iter().map(|span| span.content.as_ref()).collect();
+    let haystack = text.to_ascii_lowercase();
+    let ranges: Vec<(usize, usize)> = haystack
+        .match_indices(needle)
+        .map(|(start, matched)| (start, start + matched.len()))
+        .collect();
+    if ranges.is_empty() {
+        return spans;
+    }
+
+    let match_style = Style::default()
+        .fg(bg_base())
+        .bg(accent_warning())
+        .add_modifier(Modifier::BOLD);
+    let mut result = Vec::new();
+    let mut offset = 0;
+    for span in spans {
+        let content = span.content.as_ref();
+        let span_end = offset + content.len();
+        let mut cut = offset;
+        for &(start, end) in &ranges {
+            let start = start.clamp(offset, span_end);
+            let end = end.clamp(offset, span_end);
+            if start >= end {
+                continue;
+            }
+            if start > cut {
+                result.push(Span::styled(
+                    content[cut - offset..start - offset].to_string(),
+                    span.style,
+                ));
+            }
+            result.push(Span::styled(
+                content[start - offset..end - offset].to_string(),
+                span.style.patch(match_style),
+            ));
+            cut = end;
+        }
+        if cut < span_end {
+            result.push(Span::styled(
+                content[cut - offset..].to_string(),
+                span.style,
+            ));
+        }
+        offset = span_end;
+    }
+    result
+}
+
 impl Default for RawEventsView {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,137 @@
+//! Rename tab dialog component
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    symbols::border,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use super::{border_focused, text_muted, text_primary, DialogFrame, TextInputState};
+
+/// Longest custom tab name, in characters
+const MAX_TAB_NAME_CHARS: usize = 60;
+
+/// State for the rename tab dialog
+#[derive(Debug, Clone, Default)]
+pub struct RenameTabDialogState {
+    /// Name being typed
+    pub text: TextInputState,
+    /// Whether the dialog is visible
+    pub visible: bool,
+}
+
+impl RenameTabDialogState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show the dialog, pre-filled with the tab's current custom name
+    pub fn show(&mut self, current: Option<&str>) {
+        self.text.set(current.unwrap_or_default());
+        self.visible = true;
+    }
+
+    /// Hide the dialog
+    pub fn hide(&mut self) {
+        self.visible = false;
+        self.text.clear();
+    }
+
+    /// Check if dialog is visible
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// The entered name, trimmed; `None` clears the custom name
+    pub fn name(&self) -> Option<String> {
+        let name = self.text.value().trim();
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
+    // Delegate text input methods
+    pub fn insert_char(&mut self, c: char) {
+        if c.is_control() || self.text.value().chars().count() >= MAX_TAB_NAME_CHARS {
+            return;
+        }
+        self.text.insert_char(c);
+    }
+
+    pub fn delete_char(&mut self) {
+        self.text.delete_char();
+    }
+
+    pub fn delete_forward(&mut self) {
+        self.text.delete_forward();
+    }
+
+    pub fn move_left(&mut self) {
+        self.text.move_left();
+    }
+
+    pub fn move_right(&mut self) {
+        self.text.move_right();
+    }
+
+    pub fn move_start(&mut self) {
+        self.text.move_start();
+    }
+
+    pub fn move_end(&mut self) {
+        self.text.move_end();
+    }
+}
+
+/// Rename tab dialog widget
+pub struct RenameTabDialog;
+
+impl RenameTabDialog {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Render the dialog
+    pub fn render(&self, area: Rect, buf: &mut Buffer, state: &RenameTabDialogState) {
+        if !state.is_visible() {
+            return;
+        }
+
+        let frame = DialogFrame::new("Rename Tab", 56, 9)
+            .instructions(vec![("Enter", "save"), ("Esc", "cancel")]);
+        let inner = frame.render(area, buf);
+
+        let chunks = Layout::vertical([
+            Constraint::Length(3), // Input field
+            Constraint::Length(1), // Help text
+            Constraint::Min(0),    // Remaining space
+        ])
+        .split(inner);
+
+        let input_block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(Style::default().fg(border_focused()));
+
+        let input_inner = input_block.inner(chunks[0]);
+        input_block.render(chunks[0], buf);
+
+        state.text.render_with_placeholder(
+            input_inner,
+            buf,
+            Style::default().fg(text_primary()),
+            "Tab name",
+            Style::default().fg(text_muted()),
+        );
+
+        let help = Paragraph::new("Leave empty to use the default name.")
+            .style(Style::default().fg(text_muted()));
+        help.render(chunks[1], buf);
+    }
+}
+
+impl Default for RenameTabDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! This component renders a fixed header below the tab bar showing
 //! the session title/description. Shows "New session" in muted text
-//! when no title has been generated yet.
+//! when no title has been generated yet, and a right-aligned badge for
+//! the session's response language and style preset when either is set.
 
 use ratatui::{
     buffer::Buffer,
@@ -12,18 +13,38 @@ use ratatui::{
     widgets::Widget,
 };
 
-use super::{bg_elevated, text_muted, text_secondary};
+use super::{accent_secondary, bg_elevated, text_muted, text_secondary};
 
 /// Session header component
 pub struct SessionHeader<'a> {
     /// The session title (None = new session)
     title: Option<&'a str>,
+    /// Response language badge (None = no directive)
+    language: Option<&'a str>,
+    /// Response style preset badge (None = no preset)
+    preset: Option<&'a str>,
 }
 
 impl<'a> SessionHeader<'a> {
     /// Create a new session header
     pub fn new(title: Option<&'a str>) -> Self {
-        Self { title }
+        Self {
+            title,
+            language: None,
+            preset: None,
+        }
+    }
+
+    /// Show the session's response language as a badge
+    pub fn with_language(mut self, language: Option<&'a str>) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Show the session's response style preset alongside the language
+    pub fn with_preset(mut self, preset: Option<&'a str>) -> Self {
+        self.preset = preset;
+        self
     }
 }
 
@@ -41,10 +62,16 @@ impl Widget for SessionHeader<'_> {
             }
         }
 
+        let badge_parts: Vec<&str> = self.language.into_iter().chain(self.preset).collect();
+        let badge = (!badge_parts.is_empty()).then(|| format!(" {} ", badge_parts.join(" · ")));
+        let badge_width = badge
+            .as_ref()
+            .map_or(0, |badge| badge.chars().count() as u16 + 2);
+
         // Display text
         let text = self.title.unwrap_or("New session");
         // Reserve 2 chars for leading padding + 1 for ellipsis safety
-        let max_display_chars = area.width.saturating_sub(4) as usize;
+        let max_display_chars = area.width.saturating_sub(4 + badge_width) as usize;
 
         // UTF-8 safe truncation: count by characters, not bytes
         let display = truncate_utf8(text, max_display_chars);
@@ -62,6 +89,17 @@ impl Widget for SessionHeader<'_> {
         ]);
 
         buf.set_line(area.x, area.y, &line, area.width);
+
+        if let Some(badge) = badge {
+            if badge_width < area.width {
+                let x = area.x + area.width - badge_width;
+                let badge_line = Line::from(Span::styled(
+                    badge,
+                    Style::default().fg(bg_elevated()).bg(accent_secondary()),
+                ));
+                buf.set_line(x, area.y, &badge_line, badge_width);
+            }
+        }
     }
 }
 
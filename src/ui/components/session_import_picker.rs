@@ -5,14 +5,15 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     widgets::{Paragraph, Widget},
 };
 
 use super::{
-    agent_claude, agent_codex, agent_gemini, agent_opencode, bg_highlight, dialog_bg,
-    dialog_content_area, ensure_contrast_bg, ensure_contrast_fg, render_minimal_scrollbar,
-    selected_bg, text_muted, text_primary, DialogFrame, ScrollbarMetrics, SearchableListState,
+    accent_error, accent_warning, agent_claude, agent_codex, agent_gemini, agent_opencode,
+    bg_highlight, dialog_bg, dialog_content_area, ensure_contrast_bg, ensure_contrast_fg,
+    render_minimal_scrollbar, selected_bg, text_muted, text_primary, DialogFrame, ScrollbarMetrics,
+    SearchableListState,
 };
 use crate::agent::AgentType;
 use crate::session::ExternalSession;
@@ -435,7 +436,7 @@ impl SessionImportPicker {
 
         // Render separator
         let separator = "─".repeat(inner.width as usize);
-        let sep_paragraph = Paragraph::new(separator).style(Style::default().fg(Color::DarkGray));
+        let sep_paragraph = Paragraph::new(separator).style(Style::default().fg(text_muted()));
         sep_paragraph.render(chunks[2], buf);
 
         // Render session list
@@ -444,12 +445,12 @@ impl SessionImportPicker {
             const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
             let spinner = SPINNER_FRAMES[state.spinner_frame % SPINNER_FRAMES.len()];
             let loading = Paragraph::new(format!("{} Discovering sessions...", spinner))
-                .style(Style::default().fg(Color::Yellow))
+                .style(Style::default().fg(accent_warning()))
                 .alignment(Alignment::Center);
             loading.render(list_area, buf);
         } else if let Some(ref error) = state.error {
             let error_msg = Paragraph::new(error.as_str())
-                .style(Style::default().fg(Color::Red))
+                .style(Style::default().fg(accent_error()))
                 .alignment(Alignment::Center);
             error_msg.render(list_area, buf);
         } else if state.list.filtered.is_empty() {
@@ -459,7 +460,7 @@ impl SessionImportPicker {
                 "No sessions match your filter"
             };
             let empty = Paragraph::new(empty_msg)
-                .style(Style::default().fg(Color::DarkGray))
+                .style(Style::default().fg(text_muted()))
                 .alignment(Alignment::Center);
             empty.render(list_area, buf);
         } else {
@@ -559,12 +560,15 @@ impl SessionImportPicker {
                 AgentType::Codex => "X",
                 AgentType::Gemini => "G",
                 AgentType::Opencode => "O",
+                AgentType::Local => "L",
             };
             let agent_color = match session.agent_type {
                 AgentType::Claude => agent_claude(),
                 AgentType::Codex => agent_codex(),
                 AgentType::Gemini => agent_gemini(),
                 AgentType::Opencode => agent_opencode(),
+                // Local sessions have no external history to import
+                AgentType::Local => text_muted(),
             };
 
             // Calculate widths
@@ -19,6 +19,14 @@ use super::{
 pub enum SlashCommand {
     Model,
     NewSession,
+    SystemPrompt,
+    ResponseStyle,
+    Fetch,
+    Export,
+    Memory,
+    Link,
+    Deps,
+    Coverage,
 }
 
 impl SlashCommand {
@@ -26,6 +34,14 @@ impl SlashCommand {
         match self {
             SlashCommand::Model => "/model",
             SlashCommand::NewSession => "/new",
+            SlashCommand::SystemPrompt => "/system",
+            SlashCommand::ResponseStyle => "/style",
+            SlashCommand::Fetch => "/fetch",
+            SlashCommand::Export => "/export",
+            SlashCommand::Memory => "/memory",
+            SlashCommand::Link => "/link",
+            SlashCommand::Deps => "/deps",
+            SlashCommand::Coverage => "/coverage",
         }
     }
 
@@ -33,6 +49,14 @@ impl SlashCommand {
         match self {
             SlashCommand::Model => "Select model",
             SlashCommand::NewSession => "Start a new session",
+            SlashCommand::SystemPrompt => "Set base instructions",
+            SlashCommand::ResponseStyle => "Set response style",
+            SlashCommand::Fetch => "Attach a web page as context",
+            SlashCommand::Export => "Export transcript (md, json, pdf)",
+            SlashCommand::Memory => "Sync shared memory into CLAUDE.md / AGENTS.md",
+            SlashCommand::Link => "Link a workspace from another repository",
+            SlashCommand::Deps => "Update outdated dependencies, one PR per group",
+            SlashCommand::Coverage => "Add tests where coverage is lowest",
         }
     }
 }
@@ -138,6 +162,14 @@ impl SlashMenuState {
         vec![
             SlashCommandEntry::new(SlashCommand::Model),
             SlashCommandEntry::new(SlashCommand::NewSession),
+            SlashCommandEntry::new(SlashCommand::SystemPrompt),
+            SlashCommandEntry::new(SlashCommand::ResponseStyle),
+            SlashCommandEntry::new(SlashCommand::Fetch),
+            SlashCommandEntry::new(SlashCommand::Export),
+            SlashCommandEntry::new(SlashCommand::Memory),
+            SlashCommandEntry::new(SlashCommand::Link),
+            SlashCommandEntry::new(SlashCommand::Deps),
+            SlashCommandEntry::new(SlashCommand::Coverage),
         ]
     }
 
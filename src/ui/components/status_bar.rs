@@ -10,13 +10,14 @@ use ratatui::{
 use crate::agent::{
     events::ContextWindowState, AgentMode, AgentType, ModelRegistry, SessionId, TokenUsage,
 };
-use crate::git::{CheckState, GitDiffStats, MergeReadiness, MergeableStatus, PrState, PrStatus};
+use crate::git::{
+    CheckState, GitDiffStats, MergeReadiness, MergeableStatus, PrState, PrStatus, UpstreamStatus,
+};
 use crate::ui::components::{
-    accent_error, accent_primary, accent_secondary, accent_success, accent_warning, pr_closed_bg,
-    pr_draft_bg, pr_merged_bg, pr_open_bg, pr_unknown_bg, status_bar_bg, text_bright, text_faint,
-    text_muted,
+    accent_error, accent_primary, accent_secondary, accent_success, accent_warning, bg_base,
+    pr_closed_bg, pr_draft_bg, pr_merged_bg, pr_open_bg, pr_unknown_bg, status_bar_bg, text_bright,
+    text_faint, text_muted,
 };
-use ratatui::style::Color;
 
 /// Spinner frames for checks pending (Ripple)
 const RIPPLE_FRAMES: &[&str] = &["·", "∙", "•", "●", "•", "∙"];
@@ -29,7 +30,11 @@ pub struct StatusBar {
     shell_mode: bool,
     session_id: Option<SessionId>,
     token_usage: TokenUsage,
-    estimated_cost: f64,
+    /// Estimated spend for this session (USD)
+    session_cost: f64,
+    /// Estimated spend across all open tabs (USD)
+    tabs_cost: f64,
+    show_cost: bool,
     /// Whether to show performance metrics
     show_metrics: bool,
     /// Repository name (from git remote or directory)
@@ -42,6 +47,10 @@ pub struct StatusBar {
     pr_status: Option<PrStatus>,
     /// Git diff stats (+/- counts)
     git_diff_stats: GitDiffStats,
+    /// Commits ahead of/behind the branch's upstream, if it has one
+    upstream: Option<UpstreamStatus>,
+    /// URLs of dev servers running in the workspace, oldest first
+    previews: Vec<String>,
     /// Time spent in draw()
     draw_time: Duration,
     /// Time spent processing events
@@ -62,6 +71,12 @@ pub struct StatusBar {
     context_state: Option<ContextWindowState>,
     /// Number of queued messages
     queue_count: usize,
+    /// Queued messages are waiting for a concurrent-agent slot
+    waiting_for_slot: bool,
+    /// Whether the workspace's bootstrap commands are still running
+    bootstrapping: bool,
+    /// Background network calls are failing
+    offline: bool,
     /// Whether plan mode is supported for this agent
     supports_plan_mode: bool,
     /// Spinner frame index (shared animation tick)
@@ -77,13 +92,17 @@ impl StatusBar {
             shell_mode: false,
             session_id: None,
             token_usage: TokenUsage::default(),
-            estimated_cost: 0.0,
+            session_cost: 0.0,
+            tabs_cost: 0.0,
+            show_cost: true,
             show_metrics: false,
             repo_name: None,
             branch_name: None,
             folder_name: None,
             pr_status: None,
             git_diff_stats: GitDiffStats::default(),
+            upstream: None,
+            previews: Vec::new(),
             draw_time: Duration::ZERO,
             event_time: Duration::ZERO,
             fps: 0.0,
@@ -94,6 +113,9 @@ impl StatusBar {
             scroll_active: false,
             context_state: None,
             queue_count: 0,
+            waiting_for_slot: false,
+            bootstrapping: false,
+            offline: false,
             supports_plan_mode: false,
             spinner_frame: 0,
         }
@@ -121,7 +143,16 @@ impl StatusBar {
 
     pub fn set_token_usage(&mut self, usage: TokenUsage) {
         self.token_usage = usage;
-        self.update_cost();
+    }
+
+    pub fn set_session_cost(&mut self, cost: f64) {
+        self.session_cost = cost;
+    }
+
+    /// Set whether costs are shown and the spend summed across open tabs
+    pub fn set_tabs_cost(&mut self, show: bool, tabs_cost: f64) {
+        self.show_cost = show;
+        self.tabs_cost = tabs_cost;
     }
 
     pub fn set_context_state(&mut self, state: ContextWindowState) {
@@ -132,6 +163,18 @@ impl StatusBar {
         self.queue_count = count;
     }
 
+    pub fn set_waiting_for_slot(&mut self, waiting: bool) {
+        self.waiting_for_slot = waiting;
+    }
+
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
+    }
+
+    pub fn set_bootstrapping(&mut self, bootstrapping: bool) {
+        self.bootstrapping = bootstrapping;
+    }
+
     pub fn set_supports_plan_mode(&mut self, supports: bool) {
         self.supports_plan_mode = supports;
     }
@@ -146,12 +189,26 @@ impl StatusBar {
         self.pr_status = status;
     }
 
+    /// PR status shown in the bar, if one was found
+    pub fn pr_status(&self) -> Option<&PrStatus> {
+        self.pr_status.as_ref()
+    }
+
     /// Set git diff stats for display
     pub fn set_git_diff_stats(&mut self, stats: GitDiffStats) {
         self.git_diff_stats = stats;
     }
 
+    /// Set ahead/behind counts (from git tracker)
+    pub fn set_upstream(&mut self, upstream: Option<UpstreamStatus>) {
+        self.upstream = upstream;
+    }
+
     /// Set branch name directly (from git tracker)
+    pub fn set_previews(&mut self, previews: Vec<String>) {
+        self.previews = previews;
+    }
+
     pub fn set_branch_name(&mut self, branch: Option<String>) {
         self.branch_name = branch;
     }
@@ -203,11 +260,12 @@ impl StatusBar {
         self.scroll_active = scroll_active;
     }
 
-    fn update_cost(&mut self) {
-        // Claude Sonnet pricing: $3/1M input, $15/1M output
-        let input_cost = (self.token_usage.input_tokens as f64 / 1_000_000.0) * 3.0;
-        let output_cost = (self.token_usage.output_tokens as f64 / 1_000_000.0) * 15.0;
-        self.estimated_cost = input_cost + output_cost;
+    fn format_cost(cost: f64) -> String {
+        if cost > 0.0 && cost < 0.01 {
+            "<$0.01".to_string()
+        } else {
+            format!("${:.2}", cost)
+        }
     }
 
     #[allow(dead_code)]
@@ -235,9 +293,14 @@ impl StatusBar {
         } else {
             // Mode indicator - only when plan mode is supported
             if self.supports_plan_mode {
+                // Plan mode uses a warning accent so it stands out from Build
+                let mode_color = match self.agent_mode {
+                    AgentMode::Plan => accent_warning(),
+                    AgentMode::Build => accent_primary(),
+                };
                 spans.push(Span::styled(
                     self.agent_mode.display_name(),
-                    Style::default().fg(accent_primary()),
+                    Style::default().fg(mode_color),
                 ));
                 // Two spaces separator between mode and model
                 spans.push(Span::raw("  "));
@@ -265,11 +328,51 @@ impl StatusBar {
                 ));
             }
 
+            if self.waiting_for_slot {
+                spans.push(Span::raw("  "));
+                spans.push(Span::styled(
+                    "waiting for slot",
+                    Style::default().fg(accent_warning()),
+                ));
+            }
+
+            if self.bootstrapping {
+                spans.push(Span::raw("  "));
+                spans.push(Span::styled(
+                    "bootstrapping",
+                    Style::default().fg(accent_warning()),
+                ));
+            }
+
             // Agent name - muted color
             spans.push(Span::styled(
                 format!(" {}", self.agent_type.display_name()),
                 Style::default().fg(text_muted()),
             ));
+
+            // Estimated spend: this session, then all tabs when others add to it
+            if self.show_cost && self.tabs_cost > 0.0 {
+                spans.push(Span::styled(" │ ", Style::default().fg(text_faint())));
+                spans.push(Span::styled(
+                    Self::format_cost(self.session_cost),
+                    Style::default().fg(text_muted()),
+                ));
+                if self.tabs_cost - self.session_cost >= 0.005 {
+                    spans.push(Span::styled(
+                        format!(" · {} all tabs", Self::format_cost(self.tabs_cost)),
+                        Style::default().fg(text_faint()),
+                    ));
+                }
+            }
+        }
+
+        // Offline: PR status and other network calls are paused
+        if self.offline {
+            spans.push(Span::styled(" │ ", Style::default().fg(text_faint())));
+            spans.push(Span::styled(
+                "offline",
+                Style::default().fg(accent_warning()),
+            ));
         }
 
         // Context usage indicator - hidden for now until we decide on presentation
@@ -386,29 +489,51 @@ impl StatusBar {
     }
 
     /// Build project info spans for right side of status bar
-    /// New format: PR #123 ✓ · +44 -10 · feature-branch (or without PR if none)
+    /// New format: preview URL · PR #123 ✓ · +44 -10 · feature-branch ↑2 ↓1 (or without PR if none)
     fn build_project_info_spans(&self) -> Vec<Span<'static>> {
         let mut spans = Vec::new();
         let mut has_content = false;
 
+        // Dev server preview: newest URL, with a count of any others
+        if let Some(url) = self.previews.last() {
+            spans.push(Span::styled(
+                " preview ",
+                Style::default().bg(accent_secondary()).fg(bg_base()),
+            ));
+            spans.push(Span::styled(
+                format!(" {}", url),
+                Style::default().fg(accent_secondary()),
+            ));
+            if self.previews.len() > 1 {
+                spans.push(Span::styled(
+                    format!(" +{}", self.previews.len() - 1),
+                    Style::default().fg(text_faint()),
+                ));
+            }
+            has_content = true;
+        }
+
         // PR badge with colored background (if PR exists and has a valid number)
         if let Some(ref pr) = self.pr_status {
             if pr.exists {
                 if let Some(number) = pr.number {
+                    if has_content {
+                        spans.push(Span::styled(" · ", Style::default().fg(text_faint())));
+                    }
                     // For merged/closed PRs, use state-based coloring
                     // For open PRs, use merge readiness-based coloring
                     let (bg_color, fg_color) = match pr.state {
-                        PrState::Merged => (pr_merged_bg(), Color::White),
-                        PrState::Closed => (pr_closed_bg(), Color::White),
-                        PrState::Unknown => (pr_unknown_bg(), Color::White),
+                        PrState::Merged => (pr_merged_bg(), text_bright()),
+                        PrState::Closed => (pr_closed_bg(), text_bright()),
+                        PrState::Unknown => (pr_unknown_bg(), text_bright()),
                         _ => match pr.merge_readiness {
-                            MergeReadiness::Ready => (pr_open_bg(), Color::White),
-                            MergeReadiness::HasConflicts => (pr_closed_bg(), Color::White),
-                            MergeReadiness::Blocked => (pr_draft_bg(), Color::White),
+                            MergeReadiness::Ready => (pr_open_bg(), text_bright()),
+                            MergeReadiness::HasConflicts => (pr_closed_bg(), text_bright()),
+                            MergeReadiness::Blocked => (pr_draft_bg(), text_bright()),
                             MergeReadiness::Unknown => match pr.state {
-                                PrState::Open => (pr_open_bg(), Color::White),
-                                PrState::Draft => (pr_draft_bg(), Color::White),
-                                _ => (pr_unknown_bg(), Color::White),
+                                PrState::Open => (pr_open_bg(), text_bright()),
+                                PrState::Draft => (pr_draft_bg(), text_bright()),
+                                _ => (pr_unknown_bg(), text_bright()),
                             },
                         },
                     };
@@ -490,6 +615,21 @@ impl StatusBar {
                 branch.clone(),
                 Style::default().fg(text_muted()),
             ));
+            // Unpushed/unpulled commits: ↑2 ↓1 (omit zeros)
+            if let Some(upstream) = self.upstream {
+                if upstream.ahead > 0 {
+                    spans.push(Span::styled(
+                        format!(" ↑{}", upstream.ahead),
+                        Style::default().fg(accent_primary()),
+                    ));
+                }
+                if upstream.behind > 0 {
+                    spans.push(Span::styled(
+                        format!(" ↓{}", upstream.behind),
+                        Style::default().fg(accent_warning()),
+                    ));
+                }
+            }
             has_content = true;
         }
 
@@ -8,9 +8,11 @@ use ratatui::{
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use super::{
-    accent_primary, accent_success, accent_warning, bg_elevated, tab_bar_bg, text_muted,
-    text_primary, text_secondary,
+    accent_error, accent_primary, accent_success, accent_warning, bg_elevated, tab_bar_bg,
+    text_muted, text_primary, text_secondary,
 };
+use crate::git::GitDiffStats;
+
 /// Spinner animation frames (Braille Dots B)
 const SPINNER_FRAMES: &[&str] = &["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"];
 
@@ -71,6 +73,10 @@ pub struct TabBar {
     attention_flags: Vec<bool>,
     /// Whether each tab is awaiting user response (inline prompt active)
     awaiting_response_flags: Vec<bool>,
+    /// Whether each tab had a turn fail since it was last viewed
+    failed_flags: Vec<bool>,
+    /// Uncommitted diff stats for each tab's workspace, shown as a `+N -M` badge
+    diff_stats: Vec<GitDiffStats>,
     /// Current spinner frame index
     spinner_frame: usize,
     /// Horizontal scroll offset in columns
@@ -88,6 +94,8 @@ impl TabBar {
             processing_flags: vec![false; tab_count],
             attention_flags: vec![false; tab_count],
             awaiting_response_flags: vec![false; tab_count],
+            failed_flags: vec![false; tab_count],
+            diff_stats: vec![GitDiffStats::default(); tab_count],
             spinner_frame: 0,
             scroll_offset: 0,
         }
@@ -114,6 +122,18 @@ impl TabBar {
         self
     }
 
+    /// Set which tabs had a turn fail since they were last viewed
+    pub fn with_failed_flags(mut self, failed: Vec<bool>) -> Self {
+        self.failed_flags = failed;
+        self
+    }
+
+    /// Set the cached git diff stats for each tab
+    pub fn with_diff_stats(mut self, diff_stats: Vec<GitDiffStats>) -> Self {
+        self.diff_stats = diff_stats;
+        self
+    }
+
     /// Set horizontal scroll offset for the tab bar
     pub fn with_scroll_offset(mut self, offset: usize) -> Self {
         self.scroll_offset = offset;
@@ -313,6 +333,7 @@ impl TabBar {
             let is_active = i == self.active;
             let is_processing = self.processing_flags.get(i).copied().unwrap_or(false);
             let needs_attention = self.attention_flags.get(i).copied().unwrap_or(false);
+            let turn_failed = self.failed_flags.get(i).copied().unwrap_or(false);
             let awaiting_response = self
                 .awaiting_response_flags
                 .get(i)
@@ -337,7 +358,7 @@ impl TabBar {
             tab_width += span_width(&indicator_span);
             spans.push(indicator_span);
 
-            // Priority: awaiting_response > processing > needs_attention
+            // Priority: awaiting_response > processing > turn_failed > needs_attention
             if awaiting_response {
                 // Show orange dot when awaiting user response (inline prompt active)
                 let awaiting_span = Span::styled("● ", active_bg_style.fg(accent_warning()));
@@ -350,6 +371,10 @@ impl TabBar {
                 );
                 tab_width += span_width(&spinner_span);
                 spans.push(spinner_span);
+            } else if turn_failed {
+                let failed_span = Span::styled("✗ ", active_bg_style.fg(accent_error()));
+                tab_width += span_width(&failed_span);
+                spans.push(failed_span);
             } else if needs_attention {
                 let attention_span = Span::styled("● ", active_bg_style.fg(accent_success()));
                 tab_width += span_width(&attention_span);
@@ -373,6 +398,30 @@ impl TabBar {
             tab_width += span_width(&tab_span);
             spans.push(tab_span);
 
+            // Diff badge: +44 -10 (omit zeros)
+            if let Some(stats) = self.diff_stats.get(i).filter(|s| s.has_changes()) {
+                let mut badge = vec![Span::styled(" ", active_bg_style)];
+                if stats.additions > 0 {
+                    badge.push(Span::styled(
+                        format!("+{}", stats.additions),
+                        active_bg_style.fg(accent_success()),
+                    ));
+                }
+                if stats.additions > 0 && stats.deletions > 0 {
+                    badge.push(Span::styled(" ", active_bg_style));
+                }
+                if stats.deletions > 0 {
+                    badge.push(Span::styled(
+                        format!("-{}", stats.deletions),
+                        active_bg_style.fg(accent_error()),
+                    ));
+                }
+                for span in badge {
+                    tab_width += span_width(&span);
+                    spans.push(span);
+                }
+            }
+
             let trailing_span = if is_active {
                 Span::styled("  ", active_bg_style)
             } else {
@@ -468,3 +517,39 @@ fn slice_text_by_width(text: &str, start: usize, width: usize) -> String {
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rendered_text(tab_bar: &TabBar) -> String {
+        let (spans, _, _) = tab_bar.build_items();
+        spans.iter().map(|span| span.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_failed_glyph_outranks_unread_dot_but_not_spinner() {
+        let names = vec!["api".to_string(), "web".to_string()];
+        let tab_bar = TabBar::new(names.clone(), 0)
+            .with_tab_states(
+                vec![None; 2],
+                vec![false, false],
+                vec![false, true],
+                vec![false, false],
+            )
+            .with_failed_flags(vec![false, true]);
+        let text = rendered_text(&tab_bar);
+        assert!(text.contains("✗ [2] web"));
+        assert!(!text.contains("● [2] web"));
+
+        let processing = TabBar::new(names, 0)
+            .with_tab_states(
+                vec![None; 2],
+                vec![false, true],
+                vec![false, false],
+                vec![false, false],
+            )
+            .with_failed_flags(vec![false, true]);
+        assert!(!rendered_text(&processing).contains('✗'));
+    }
+}
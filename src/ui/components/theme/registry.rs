@@ -73,9 +73,9 @@ impl ThemeRegistry {
         registry
     }
 
-    /// Discover TOML themes from ~/.conduit/themes/.
+    /// Discover TOML themes from <config>/themes/.
     pub fn discover_toml_themes(&mut self) {
-        let themes_dir = util::data_dir().join("themes");
+        let themes_dir = util::themes_dir();
         if !themes_dir.exists() {
             return;
         }
@@ -1,3 +1,4 @@
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
 use ratatui::{
@@ -5,6 +6,8 @@ use ratatui::{
     text::{Line, Span},
 };
 
+use super::{text_muted, text_secondary};
+
 /// Processing words used by Claude Code (subset of ~90 words)
 const PROCESSING_WORDS: &[&str] = &[
     "Accomplishing",
@@ -80,6 +83,10 @@ const SHIMMER_DIM: (u8, u8, u8) = (100, 50, 20); // Very dark for maximum contra
 /// Width of the shimmer "wave" in characters
 const SHIMMER_WIDTH: f32 = 4.0;
 
+/// Streaming throughput is averaged over this trailing window, so a run that
+/// stops streaming drops to 0 tok/s instead of coasting on its earlier rate
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+
 /// Current processing state
 #[derive(Debug, Clone, PartialEq)]
 pub enum ProcessingState {
@@ -100,6 +107,14 @@ impl ProcessingState {
             ProcessingState::Searching => "searching",
         }
     }
+
+    /// Label for the indicator, e.g. "thinking" or "running Bash"
+    pub fn label(&self) -> String {
+        match self {
+            ProcessingState::ToolUse(name) => format!("running {name}"),
+            other => other.as_str().to_string(),
+        }
+    }
 }
 
 /// Thinking indicator that shows while agent is processing
@@ -114,20 +129,27 @@ pub struct ThinkingIndicator {
     start_time: Instant,
     /// Tokens received so far
     tokens: usize,
+    /// Tokens received within the last `RATE_WINDOW`, oldest first
+    recent_tokens: VecDeque<(Instant, usize)>,
     /// Current processing state
     state: ProcessingState,
+    /// When the current processing state began
+    state_start: Instant,
 }
 
 impl ThinkingIndicator {
     /// Create a new thinking indicator with a random word
     pub fn new() -> Self {
+        let now = Instant::now();
         Self {
             word: Self::random_word(),
             spinner_frame: 0,
             shimmer_offset: -SHIMMER_WIDTH, // Start from before the text
-            start_time: Instant::now(),
+            start_time: now,
             tokens: 0,
+            recent_tokens: VecDeque::new(),
             state: ProcessingState::Thinking,
+            state_start: now,
         }
     }
 
@@ -159,16 +181,36 @@ impl ThinkingIndicator {
         if self.shimmer_offset > wrap_point {
             self.shimmer_offset = -SHIMMER_WIDTH; // Start from before the text
         }
+        self.prune_recent_tokens(Instant::now());
     }
 
     /// Add tokens to the count
     pub fn add_tokens(&mut self, count: usize) {
+        self.add_tokens_at(count, Instant::now());
+    }
+
+    fn add_tokens_at(&mut self, count: usize, now: Instant) {
         self.tokens += count;
+        self.recent_tokens.push_back((now, count));
+        self.prune_recent_tokens(now);
+    }
+
+    fn prune_recent_tokens(&mut self, now: Instant) {
+        while self
+            .recent_tokens
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > RATE_WINDOW)
+        {
+            self.recent_tokens.pop_front();
+        }
     }
 
     /// Set the current processing state
     pub fn set_state(&mut self, state: ProcessingState) {
-        self.state = state;
+        if self.state != state {
+            self.state = state;
+            self.state_start = Instant::now();
+        }
     }
 
     /// Get elapsed time
@@ -176,14 +218,37 @@ impl ThinkingIndicator {
         self.start_time.elapsed()
     }
 
+    /// Streaming tokens per second over the last few seconds
+    pub fn tokens_per_sec(&self) -> f64 {
+        self.tokens_per_sec_at(Instant::now())
+    }
+
+    fn tokens_per_sec_at(&self, now: Instant) -> f64 {
+        let recent: usize = self
+            .recent_tokens
+            .iter()
+            .filter(|(at, _)| now.duration_since(*at) <= RATE_WINDOW)
+            .map(|(_, count)| count)
+            .sum();
+        // Early in the turn, average over the time so far rather than the
+        // full window; the floor keeps the first chunk from reading as a spike
+        let span = RATE_WINDOW
+            .min(now.duration_since(self.start_time))
+            .max(Duration::from_secs(1));
+        recent as f64 / span.as_secs_f64()
+    }
+
     /// Reset with a new random word
     pub fn reset(&mut self) {
+        let now = Instant::now();
         self.word = Self::random_word();
         self.spinner_frame = 0;
         self.shimmer_offset = -SHIMMER_WIDTH; // Start from before the text
-        self.start_time = Instant::now();
+        self.start_time = now;
         self.tokens = 0;
+        self.recent_tokens.clear();
         self.state = ProcessingState::Thinking;
+        self.state_start = now;
     }
 
     /// Calculate shimmer color for a character at given position
@@ -232,7 +297,6 @@ impl ThinkingIndicator {
         let duration_str = format_duration(elapsed);
 
         let spinner = SPINNER_FRAMES[self.spinner_frame];
-        let state = self.state.as_str();
 
         // Build the shimmering part: "✳ Tinkering… "
         let shimmer_text = format!("{} {}… ", spinner, self.word);
@@ -240,16 +304,38 @@ impl ThinkingIndicator {
 
         // Add the non-shimmering metadata part
         spans.extend(vec![
-            Span::styled("(", Style::default().fg(Color::DarkGray)),
-            Span::styled("esc", Style::default().fg(Color::Gray)),
-            Span::styled(" to interrupt · ", Style::default().fg(Color::DarkGray)),
-            Span::styled(duration_str, Style::default().fg(Color::Gray)),
-            Span::styled(" · ↓ ", Style::default().fg(Color::DarkGray)),
-            Span::styled(format!("{}", self.tokens), Style::default().fg(Color::Gray)),
-            Span::styled(" tokens · ", Style::default().fg(Color::DarkGray)),
-            Span::styled(state.to_string(), Style::default().fg(Color::Gray)),
-            Span::styled(")", Style::default().fg(Color::DarkGray)),
+            Span::styled("(", Style::default().fg(text_muted())),
+            Span::styled("esc", Style::default().fg(text_secondary())),
+            Span::styled(" to interrupt · ", Style::default().fg(text_muted())),
+            Span::styled(duration_str, Style::default().fg(text_secondary())),
+            Span::styled(" · ↓ ", Style::default().fg(text_muted())),
+            Span::styled(
+                format!("{}", self.tokens),
+                Style::default().fg(text_secondary()),
+            ),
+            Span::styled(" tokens · ", Style::default().fg(text_muted())),
         ]);
+        if self.tokens > 0 {
+            spans.extend(vec![
+                Span::styled(
+                    format_rate(self.tokens_per_sec()),
+                    Style::default().fg(text_secondary()),
+                ),
+                Span::styled(" tok/s · ", Style::default().fg(text_muted())),
+            ]);
+        }
+        spans.push(Span::styled(
+            self.state.label(),
+            Style::default().fg(text_secondary()),
+        ));
+        // How long a tool has been running is what tells a stuck one apart
+        if matches!(self.state, ProcessingState::ToolUse(_)) {
+            spans.push(Span::styled(
+                format!(" {}", format_duration(self.state_start.elapsed())),
+                Style::default().fg(text_muted()),
+            ));
+        }
+        spans.push(Span::styled(")", Style::default().fg(text_muted())));
 
         Line::from(spans)
     }
@@ -261,6 +347,15 @@ fn lerp(a: u8, b: u8, t: f32) -> u8 {
     (a as f32 + (b as f32 - a as f32) * t) as u8
 }
 
+/// Format a token rate, keeping a decimal only for slow streams
+fn format_rate(rate: f64) -> String {
+    if rate < 10.0 {
+        format!("{rate:.1}")
+    } else {
+        format!("{rate:.0}")
+    }
+}
+
 /// Format duration in human-readable format (e.g., "1h 23m 45s")
 fn format_duration(duration: Duration) -> String {
     let total_secs = duration.as_secs();
@@ -297,3 +392,47 @@ impl Default for ThinkingIndicator {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_tokens_per_sec_uses_trailing_window() {
+        let mut indicator = ThinkingIndicator::new();
+        let start = indicator.start_time;
+        indicator.add_tokens_at(100, start + Duration::from_secs(1));
+        indicator.add_tokens_at(100, start + Duration::from_secs(9));
+
+        let rate = indicator.tokens_per_sec_at(start + Duration::from_secs(10));
+        assert_eq!(rate, 100.0 / RATE_WINDOW.as_secs_f64());
+        // Nothing streamed in the last window reads as stalled
+        assert_eq!(
+            indicator.tokens_per_sec_at(start + Duration::from_secs(20)),
+            0.0
+        );
+        assert_eq!(indicator.tokens, 200);
+    }
+
+    #[test]
+    fn test_render_shows_phase_and_rate() {
+        let mut indicator = ThinkingIndicator::new();
+        let text = line_text(&indicator.render());
+        assert!(text.contains("thinking"));
+        assert!(!text.contains("tok/s"));
+
+        indicator.add_tokens(40);
+        indicator.set_state(ProcessingState::ToolUse("Bash".to_string()));
+        let text = line_text(&indicator.render());
+        assert!(text.contains("↓ 40 tokens"));
+        assert!(text.contains("tok/s"));
+        assert!(text.contains("running Bash 0s)"));
+    }
+}
@@ -0,0 +1,526 @@
+//! Session timeline: per-turn timing stats and a bar view of where the time went.
+//!
+//! Each turn is drawn as a horizontal bar scaled to the whole session so long
+//! turns, tool-heavy stretches and idle gaps between turns stand out.
+
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use super::{accent_primary, accent_warning, text_faint, text_muted, text_primary};
+
+/// Lines rendered per turn (bar + stats)
+const LINES_PER_TURN: usize = 2;
+/// Width of the "#12  3m 4s" label in front of each bar
+const LABEL_WIDTH: usize = 14;
+/// Maximum number of tools listed in a turn's breakdown
+const MAX_BREAKDOWN_TOOLS: usize = 3;
+
+/// A tool call within a turn
+#[derive(Debug, Clone)]
+pub struct ToolSpan {
+    /// Tool use ID (None for calls matched by order, e.g. shell output)
+    pub tool_id: Option<String>,
+    pub name: String,
+    pub started_at: Instant,
+    pub ended_at: Option<Instant>,
+}
+
+/// Timing stats for a single agent turn
+#[derive(Debug, Clone)]
+pub struct TurnTiming {
+    pub started_at: Instant,
+    pub ended_at: Option<Instant>,
+    pub tools: Vec<ToolSpan>,
+}
+
+impl TurnTiming {
+    fn new(started_at: Instant) -> Self {
+        Self {
+            started_at,
+            ended_at: None,
+            tools: Vec::new(),
+        }
+    }
+
+    fn end_or(&self, now: Instant) -> Instant {
+        self.ended_at.unwrap_or(now).max(self.started_at)
+    }
+
+    /// Wall-clock duration of the turn (still running turns count up to `now`)
+    pub fn duration(&self, now: Instant) -> Duration {
+        self.end_or(now) - self.started_at
+    }
+
+    /// Time with at least one tool running; overlapping calls count once
+    pub fn tool_time(&self, now: Instant) -> Duration {
+        self.tool_intervals(now)
+            .into_iter()
+            .map(|(start, end)| end - start)
+            .sum()
+    }
+
+    /// Time spent waiting on the model (turn time not covered by tools)
+    pub fn model_time(&self, now: Instant) -> Duration {
+        self.duration(now).saturating_sub(self.tool_time(now))
+    }
+
+    /// Tool time per tool name, longest first
+    pub fn tool_breakdown(&self, now: Instant) -> Vec<(String, Duration)> {
+        let end = self.end_or(now);
+        let mut totals: Vec<(String, Duration)> = Vec::new();
+        for tool in &self.tools {
+            let tool_end = tool.ended_at.unwrap_or(end).min(end);
+            let elapsed = tool_end.saturating_duration_since(tool.started_at);
+            match totals.iter_mut().find(|(name, _)| *name == tool.name) {
+                Some((_, total)) => *total += elapsed,
+                None => totals.push((tool.name.clone(), elapsed)),
+            }
+        }
+        totals.sort_by(|a, b| b.1.cmp(&a.1));
+        totals
+    }
+
+    /// Merged, non-overlapping tool intervals clipped to the turn
+    fn tool_intervals(&self, now: Instant) -> Vec<(Instant, Instant)> {
+        let end = self.end_or(now);
+        let mut intervals: Vec<(Instant, Instant)> = self
+            .tools
+            .iter()
+            .map(|tool| {
+                let start = tool.started_at.clamp(self.started_at, end);
+                let stop = tool.ended_at.unwrap_or(end).clamp(start, end);
+                (start, stop)
+            })
+            .filter(|(start, stop)| stop > start)
+            .collect();
+        intervals.sort_by_key(|(start, _)| *start);
+
+        let mut merged: Vec<(Instant, Instant)> = Vec::with_capacity(intervals.len());
+        for (start, stop) in intervals {
+            match merged.last_mut() {
+                Some((_, last_stop)) if start <= *last_stop => {
+                    *last_stop = (*last_stop).max(stop);
+                }
+                _ => merged.push((start, stop)),
+            }
+        }
+        merged
+    }
+}
+
+/// Per-session record of turn and tool timings
+#[derive(Debug, Default)]
+pub struct SessionTimeline {
+    turns: Vec<TurnTiming>,
+    /// Index of the first turn shown
+    scroll_offset: usize,
+}
+
+impl SessionTimeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn turns(&self) -> &[TurnTiming] {
+        &self.turns
+    }
+
+    fn open_turn_mut(&mut self) -> Option<&mut TurnTiming> {
+        self.turns.last_mut().filter(|turn| turn.ended_at.is_none())
+    }
+
+    /// Begin a turn (no-op while one is already open)
+    pub fn start_turn(&mut self, now: Instant) {
+        if self.open_turn_mut().is_none() {
+            self.turns.push(TurnTiming::new(now));
+        }
+    }
+
+    /// Close the open turn and any tools still running in it
+    pub fn finish_turn(&mut self, now: Instant) {
+        if let Some(turn) = self.open_turn_mut() {
+            for tool in turn.tools.iter_mut().filter(|tool| tool.ended_at.is_none()) {
+                tool.ended_at = Some(now);
+            }
+            turn.ended_at = Some(now);
+        }
+    }
+
+    /// Record a tool call starting in the open turn
+    pub fn tool_started(&mut self, tool_id: Option<String>, name: &str, now: Instant) {
+        if let Some(turn) = self.open_turn_mut() {
+            turn.tools.push(ToolSpan {
+                tool_id,
+                name: name.to_string(),
+                started_at: now,
+                ended_at: None,
+            });
+        }
+    }
+
+    /// Close the running tool call with the given ID
+    pub fn tool_completed(&mut self, tool_id: &str, now: Instant) {
+        if let Some(tool) = self.open_turn_mut().and_then(|turn| {
+            turn.tools
+                .iter_mut()
+                .rev()
+                .find(|tool| tool.ended_at.is_none() && tool.tool_id.as_deref() == Some(tool_id))
+        }) {
+            tool.ended_at = Some(now);
+        }
+    }
+
+    /// Close the most recently started tool call that is still running
+    pub fn last_tool_completed(&mut self, now: Instant) {
+        if let Some(tool) = self.open_turn_mut().and_then(|turn| {
+            turn.tools
+                .iter_mut()
+                .rev()
+                .find(|tool| tool.ended_at.is_none())
+        }) {
+            tool.ended_at = Some(now);
+        }
+    }
+
+    /// Time between turns, including the gap since the last turn ended
+    pub fn idle_time(&self, now: Instant) -> Duration {
+        let mut idle = Duration::ZERO;
+        for pair in self.turns.windows(2) {
+            idle += pair[1]
+                .started_at
+                .saturating_duration_since(pair[0].end_or(now));
+        }
+        if let Some(last) = self.turns.last() {
+            if let Some(ended_at) = last.ended_at {
+                idle += now.saturating_duration_since(ended_at);
+            }
+        }
+        idle
+    }
+
+    /// Start of the first turn
+    pub fn started_at(&self) -> Option<Instant> {
+        self.turns.first().map(|turn| turn.started_at)
+    }
+
+    pub fn scroll_offset(&self) -> usize {
+        self.scroll_offset
+    }
+
+    pub fn scroll_up(&mut self, turns: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(turns);
+    }
+
+    pub fn scroll_down(&mut self, turns: usize) {
+        let max = self.turns.len().saturating_sub(1);
+        self.scroll_offset = (self.scroll_offset + turns).min(max);
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_offset = 0;
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_offset = self.turns.len().saturating_sub(1);
+    }
+}
+
+/// What a single bar cell represents
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment {
+    /// Outside this turn
+    Outside,
+    Model,
+    Tool,
+}
+
+/// Timeline view widget
+pub struct TimelineView<'a> {
+    timeline: &'a SessionTimeline,
+    now: Instant,
+}
+
+impl<'a> TimelineView<'a> {
+    pub fn new(timeline: &'a SessionTimeline, now: Instant) -> Self {
+        Self { timeline, now }
+    }
+
+    /// Number of turns that fit in `height` rows below the header
+    pub fn visible_turns(height: u16) -> usize {
+        (height as usize).saturating_sub(2) / LINES_PER_TURN
+    }
+
+    fn header_line(&self, session_start: Instant) -> Line<'static> {
+        let turns = self.timeline.turns();
+        let model: Duration = turns.iter().map(|turn| turn.model_time(self.now)).sum();
+        let tools: Duration = turns.iter().map(|turn| turn.tool_time(self.now)).sum();
+        let idle = self.timeline.idle_time(self.now);
+        let total = self.now.saturating_duration_since(session_start);
+        let turn_word = if turns.len() == 1 { "turn" } else { "turns" };
+
+        Line::from(vec![
+            Span::styled(
+                format!(
+                    "{} {} over {}   ",
+                    turns.len(),
+                    turn_word,
+                    format_duration(total)
+                ),
+                Style::default().fg(text_primary()),
+            ),
+            Span::styled("█", Style::default().fg(accent_primary())),
+            Span::styled(
+                format!(" model {}   ", format_duration(model)),
+                Style::default().fg(text_muted()),
+            ),
+            Span::styled("█", Style::default().fg(accent_warning())),
+            Span::styled(
+                format!(" tools {}   ", format_duration(tools)),
+                Style::default().fg(text_muted()),
+            ),
+            Span::styled("·", Style::default().fg(text_faint())),
+            Span::styled(
+                format!(" idle {}", format_duration(idle)),
+                Style::default().fg(text_muted()),
+            ),
+        ])
+    }
+
+    /// Classify each bar cell for a turn across `[session_start, now]`
+    fn segments(&self, turn: &TurnTiming, session_start: Instant, width: usize) -> Vec<Segment> {
+        let total = self
+            .now
+            .saturating_duration_since(session_start)
+            .as_secs_f64();
+        if width == 0 || total <= 0.0 {
+            return vec![Segment::Outside; width];
+        }
+
+        let offset = |instant: Instant| {
+            instant
+                .saturating_duration_since(session_start)
+                .as_secs_f64()
+        };
+        let turn_start = offset(turn.started_at);
+        let turn_end = offset(turn.end_or(self.now));
+        let tool_intervals: Vec<(f64, f64)> = turn
+            .tool_intervals(self.now)
+            .into_iter()
+            .map(|(start, end)| (offset(start), offset(end)))
+            .collect();
+        let overlap = |a: (f64, f64), b: (f64, f64)| (a.1.min(b.1) - a.0.max(b.0)).max(0.0);
+
+        let cell = total / width as f64;
+        (0..width)
+            .map(|i| {
+                let bounds = (i as f64 * cell, (i + 1) as f64 * cell);
+                let in_turn = overlap(bounds, (turn_start, turn_end));
+                if in_turn <= 0.0 {
+                    return Segment::Outside;
+                }
+                let in_tools: f64 = tool_intervals
+                    .iter()
+                    .map(|interval| overlap(bounds, *interval))
+                    .sum();
+                if in_tools * 2.0 >= in_turn {
+                    Segment::Tool
+                } else {
+                    Segment::Model
+                }
+            })
+            .collect()
+    }
+
+    fn bar_line(
+        &self,
+        index: usize,
+        turn: &TurnTiming,
+        session_start: Instant,
+        width: usize,
+    ) -> Line<'static> {
+        let running = if turn.ended_at.is_none() { "…" } else { "" };
+        let label = format!(
+            "#{:<3} {}{}",
+            index + 1,
+            format_duration(turn.duration(self.now)),
+            running
+        );
+        let mut spans = vec![Span::styled(
+            format!("{label:<LABEL_WIDTH$}"),
+            Style::default().fg(text_primary()),
+        )];
+
+        let bar_width = width.saturating_sub(LABEL_WIDTH);
+        let segments = self.segments(turn, session_start, bar_width);
+        let mut start = 0;
+        while start < segments.len() {
+            let segment = segments[start];
+            let run = segments[start..]
+                .iter()
+                .take_while(|other| **other == segment)
+                .count();
+            let (symbol, color) = match segment {
+                Segment::Outside => ("·", text_faint()),
+                Segment::Model => ("█", accent_primary()),
+                Segment::Tool => ("█", accent_warning()),
+            };
+            spans.push(Span::styled(symbol.repeat(run), Style::default().fg(color)));
+            start += run;
+        }
+
+        Line::from(spans)
+    }
+
+    fn stats_line(&self, turn: &TurnTiming) -> Line<'static> {
+        let mut text = format!(
+            "{:LABEL_WIDTH$}model {} · tools {}",
+            "",
+            format_duration(turn.model_time(self.now)),
+            format_duration(turn.tool_time(self.now))
+        );
+        let breakdown = turn.tool_breakdown(self.now);
+        if !breakdown.is_empty() {
+            let listed: Vec<String> = breakdown
+                .iter()
+                .take(MAX_BREAKDOWN_TOOLS)
+                .map(|(name, elapsed)| format!("{name} {}", format_duration(*elapsed)))
+                .collect();
+            let more = breakdown.len().saturating_sub(MAX_BREAKDOWN_TOOLS);
+            text.push_str(&format!(" ({}", listed.join(", ")));
+            if more > 0 {
+                text.push_str(&format!(", +{more} more"));
+            }
+            text.push(')');
+        }
+        Line::from(Span::styled(text, Style::default().fg(text_muted())))
+    }
+}
+
+impl Widget for TimelineView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let Some(session_start) = self.timeline.started_at() else {
+            Paragraph::new(Line::from(Span::styled(
+                "No turns yet. Timings appear here once the agent starts working.",
+                Style::default().fg(text_muted()),
+            )))
+            .render(area, buf);
+            return;
+        };
+
+        let width = area.width as usize;
+        let mut lines = vec![self.header_line(session_start), Line::from("")];
+        let turns = self.timeline.turns();
+        let first = self
+            .timeline
+            .scroll_offset()
+            .min(turns.len().saturating_sub(1));
+        for (index, turn) in turns
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(Self::visible_turns(area.height))
+        {
+            lines.push(self.bar_line(index, turn, session_start, width));
+            lines.push(self.stats_line(turn));
+        }
+
+        Paragraph::new(lines).render(area, buf);
+    }
+}
+
+/// Format a duration as "2m 34s" / "34s"
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 60 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(n: u64) -> Duration {
+        Duration::from_secs(n)
+    }
+
+    #[test]
+    fn test_turn_splits_model_and_tool_time() {
+        let t0 = Instant::now();
+        let mut timeline = SessionTimeline::new();
+        timeline.start_turn(t0);
+        timeline.tool_started(Some("a".to_string()), "Bash", t0 + secs(10));
+        // Overlapping call only counts once
+        timeline.tool_started(Some("b".to_string()), "Read", t0 + secs(20));
+        timeline.tool_completed("b", t0 + secs(30));
+        timeline.tool_completed("a", t0 + secs(40));
+        timeline.finish_turn(t0 + secs(60));
+
+        let turn = &timeline.turns()[0];
+        let now = t0 + secs(100);
+        assert_eq!(turn.duration(now), secs(60));
+        assert_eq!(turn.tool_time(now), secs(30));
+        assert_eq!(turn.model_time(now), secs(30));
+        assert_eq!(
+            turn.tool_breakdown(now),
+            vec![
+                ("Bash".to_string(), secs(30)),
+                ("Read".to_string(), secs(10))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_finish_turn_closes_running_tools_and_tracks_idle() {
+        let t0 = Instant::now();
+        let mut timeline = SessionTimeline::new();
+        timeline.start_turn(t0);
+        timeline.tool_started(None, "Bash", t0 + secs(5));
+        // Restarting while a turn is open keeps the same turn
+        timeline.start_turn(t0 + secs(6));
+        timeline.finish_turn(t0 + secs(10));
+        timeline.start_turn(t0 + secs(30));
+        timeline.last_tool_completed(t0 + secs(31));
+        timeline.finish_turn(t0 + secs(40));
+
+        let now = t0 + secs(45);
+        assert_eq!(timeline.turns().len(), 2);
+        assert_eq!(timeline.turns()[0].tool_time(now), secs(5));
+        assert_eq!(timeline.idle_time(now), secs(25));
+    }
+
+    #[test]
+    fn test_segments_scale_to_session() {
+        let t0 = Instant::now();
+        let mut timeline = SessionTimeline::new();
+        timeline.start_turn(t0);
+        timeline.tool_started(Some("a".to_string()), "Bash", t0 + secs(5));
+        timeline.tool_completed("a", t0 + secs(10));
+        timeline.finish_turn(t0 + secs(10));
+
+        let view = TimelineView::new(&timeline, t0 + secs(20));
+        let segments = view.segments(&timeline.turns()[0], t0, 4);
+        assert_eq!(
+            segments,
+            vec![
+                Segment::Model,
+                Segment::Tool,
+                Segment::Outside,
+                Segment::Outside
+            ]
+        );
+    }
+}
@@ -1,5 +1,7 @@
 //! Tree view widget for repository/workspace navigation
 
+use std::collections::HashMap;
+
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -13,8 +15,8 @@ use uuid::Uuid;
 use crate::git::{CheckState, CheckStatus, GitDiffStats, MergeReadiness, PrState, PrStatus};
 
 use super::{
-    accent_error, accent_primary, accent_success, pr_closed_bg, pr_draft_bg, pr_merged_bg,
-    pr_open_bg, pr_unknown_bg, selected_bg, text_muted,
+    accent_error, accent_primary, accent_success, accent_warning, pr_closed_bg, pr_draft_bg,
+    pr_merged_bg, pr_open_bg, pr_unknown_bg, selected_bg, text_bright, text_muted,
 };
 
 /// Enable mock PR display for layout testing.
@@ -71,6 +73,10 @@ pub enum NodeType {
     Workspace,
     /// Action node (e.g., "+ New workspace")
     Action(ActionType),
+    /// Header grouping persisted sessions (can have children)
+    SessionGroup,
+    /// Persisted session tab (leaf node)
+    Session,
 }
 
 /// A node in the tree view
@@ -136,6 +142,23 @@ impl TreeNode {
         }
     }
 
+    /// Create a new session node; the suffix names its workspace or directory
+    pub fn session(id: Uuid, label: impl Into<String>, suffix: Option<String>) -> Self {
+        Self {
+            id,
+            parent_id: None, // Will be set when added as child
+            label: label.into(),
+            suffix,
+            children: Vec::new(),
+            expanded: false,
+            depth: 1,
+            node_type: NodeType::Session,
+            git_stats: None,
+            pr_status: None,
+            is_busy: false,
+        }
+    }
+
     /// Create a new action node
     pub fn action(parent_id: Uuid, action_type: ActionType) -> Self {
         let label = match action_type {
@@ -156,9 +179,12 @@ impl TreeNode {
         }
     }
 
-    /// Check if this is a leaf node (workspace or action)
+    /// Check if this is a leaf node (workspace, session, or action)
     pub fn is_leaf(&self) -> bool {
-        matches!(self.node_type, NodeType::Workspace | NodeType::Action(_))
+        matches!(
+            self.node_type,
+            NodeType::Workspace | NodeType::Session | NodeType::Action(_)
+        )
     }
 
     /// Check if this is an action node
@@ -302,7 +328,7 @@ impl<'a> TreeView<'a> {
             selected_style: Style::default()
                 .bg(selected_bg())
                 .add_modifier(Modifier::BOLD),
-            expand_style: Style::default().fg(Color::Yellow),
+            expand_style: Style::default().fg(accent_warning()),
             suffix_style: Style::default().fg(text_muted()),
             spinner_frame: 0,
         }
@@ -447,7 +473,7 @@ impl StatefulWidget for TreeView<'_> {
 
             // Style based on node type
             let label_style = if node.is_action() {
-                Style::default().fg(Color::Cyan)
+                Style::default().fg(accent_primary())
             } else {
                 self.style
             };
@@ -657,6 +683,41 @@ impl SidebarData {
         self.nodes.push(repo_node);
     }
 
+    /// Replace the group of persisted sessions, newest first. The group is
+    /// left out when there are no sessions.
+    pub fn set_sessions(&mut self, sessions: Vec<(Uuid, String, Option<String>)>, expanded: bool) {
+        self.nodes
+            .retain(|node| node.node_type != NodeType::SessionGroup);
+        if sessions.is_empty() {
+            return;
+        }
+        let mut group = TreeNode::parent(Uuid::nil(), "Sessions");
+        group.node_type = NodeType::SessionGroup;
+        group.expanded = expanded;
+        for (id, label, suffix) in sessions {
+            group = group.with_child(TreeNode::session(id, label, suffix));
+        }
+        self.nodes.push(group);
+    }
+
+    /// Names of all workspaces in the tree, by ID
+    pub fn workspace_names(&self) -> HashMap<Uuid, String> {
+        self.nodes
+            .iter()
+            .filter(|node| node.node_type == NodeType::Repository)
+            .flat_map(|node| node.children.iter())
+            .filter(|child| child.node_type == NodeType::Workspace)
+            .map(|child| (child.id, child.label.clone()))
+            .collect()
+    }
+
+    /// Whether the persisted sessions group is expanded
+    pub fn sessions_expanded(&self) -> bool {
+        self.nodes
+            .iter()
+            .any(|node| node.node_type == NodeType::SessionGroup && node.expanded)
+    }
+
     /// Mark a repository node as busy.
     pub fn set_repo_busy(&mut self, repo_id: Uuid, is_busy: bool) {
         for node in &mut self.nodes {
@@ -1183,18 +1244,18 @@ fn build_right_side_spans(node: &TreeNode, spinner_frame: usize) -> Vec<Span<'st
                 // For merged/closed PRs, use state-based coloring
                 // For open PRs, use merge readiness-based coloring
                 let (bg_color, fg_color) = match pr.state {
-                    PrState::Merged => (pr_merged_bg(), Color::White),
-                    PrState::Closed => (pr_closed_bg(), Color::White),
-                    PrState::Unknown => (pr_unknown_bg(), Color::White),
+                    PrState::Merged => (pr_merged_bg(), text_bright()),
+                    PrState::Closed => (pr_closed_bg(), text_bright()),
+                    PrState::Unknown => (pr_unknown_bg(), text_bright()),
                     PrState::Open | PrState::Draft => match pr.merge_readiness {
-                        MergeReadiness::Ready => (pr_open_bg(), Color::White),
-                        MergeReadiness::HasConflicts => (pr_closed_bg(), Color::White),
-                        MergeReadiness::Blocked => (pr_draft_bg(), Color::White),
+                        MergeReadiness::Ready => (pr_open_bg(), text_bright()),
+                        MergeReadiness::HasConflicts => (pr_closed_bg(), text_bright()),
+                        MergeReadiness::Blocked => (pr_draft_bg(), text_bright()),
                         MergeReadiness::Unknown => {
                             if pr.state == PrState::Draft {
-                                (pr_draft_bg(), Color::White)
+                                (pr_draft_bg(), text_bright())
                             } else {
-                                (pr_open_bg(), Color::White)
+                                (pr_open_bg(), text_bright())
                             }
                         }
                     },
@@ -1541,6 +1602,40 @@ mod tests {
     }
 
     /// Ensure MOCK_SIDEBAR_PR_DISPLAY is false in committed code.
+    #[test]
+    fn test_sessions_group_lists_sessions_when_expanded() {
+        let mut sidebar = create_test_sidebar();
+        let session_id = Uuid::new_v4();
+        sidebar.set_sessions(
+            vec![(
+                session_id,
+                "Fix flaky test".to_string(),
+                Some("my-workspace-name".to_string()),
+            )],
+            false,
+        );
+        assert_eq!(
+            sidebar.workspace_names().values().collect::<Vec<_>>(),
+            vec!["my-workspace-name"]
+        );
+
+        // repo + action + workspace + collapsed group
+        assert_eq!(sidebar.visible_nodes().len(), 4);
+        assert!(!sidebar.sessions_expanded());
+
+        sidebar.toggle_at(3);
+        assert!(sidebar.sessions_expanded());
+        let session = sidebar.get_at(4).unwrap();
+        assert_eq!(session.node_type, NodeType::Session);
+        assert_eq!(session.id, session_id);
+        assert_eq!(session.depth, 1);
+        assert!(session.is_leaf());
+
+        // Replacing with no sessions removes the group
+        sidebar.set_sessions(Vec::new(), true);
+        assert_eq!(sidebar.visible_nodes().len(), 3);
+    }
+
     /// This test will fail CI if someone accidentally commits with the flag enabled.
     #[test]
     fn test_mock_sidebar_pr_display_is_disabled() {
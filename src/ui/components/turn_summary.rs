@@ -1,18 +1,62 @@
 use ratatui::{
-    style::{Color, Style},
+    style::Style,
     text::{Line, Span},
 };
+use serde::{Deserialize, Serialize};
+
+use super::{
+    accent_error, accent_primary, accent_secondary, accent_success, accent_warning, text_muted,
+    text_primary, text_secondary,
+};
 
 /// Represents a file change with diff stats
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileChange {
     pub filename: String,
     pub additions: usize,
     pub deletions: usize,
 }
 
+/// A shell command the agent ran during the turn
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CommandRun {
+    pub command: String,
+    /// Exit code, when the agent reports one
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+    /// Whether the command succeeded; `None` while it runs or when unknown
+    #[serde(default)]
+    pub succeeded: Option<bool>,
+}
+
+/// Outcome of the last test or check command in a turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyOutcome {
+    Passed,
+    Failed,
+}
+
+/// A pull request or commit created during the turn
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "id", rename_all = "snake_case")]
+pub enum TurnReference {
+    PullRequest(u32),
+    Commit(String),
+}
+
+impl TurnReference {
+    /// Short label such as "PR #12" or "commit 1a2b3c4"
+    pub fn label(&self) -> String {
+        match self {
+            Self::PullRequest(number) => format!("PR #{number}"),
+            Self::Commit(sha) => format!("commit {}", sha.get(..7).unwrap_or(sha)),
+        }
+    }
+}
+
 /// Summary of a completed turn
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TurnSummary {
     /// Duration in seconds
     pub duration_secs: u64,
@@ -20,8 +64,18 @@ pub struct TurnSummary {
     pub input_tokens: u64,
     /// Output tokens generated
     pub output_tokens: u64,
+    /// Portion of the input read from the prompt cache
+    pub cache_read_tokens: u64,
+    /// Portion of the input written to the prompt cache
+    pub cache_write_tokens: u64,
     /// Files that were modified
     pub files_changed: Vec<FileChange>,
+    /// Shell commands run, in order
+    #[serde(default)]
+    pub commands: Vec<CommandRun>,
+    /// Pull requests and commits created by those commands
+    #[serde(default)]
+    pub references: Vec<TurnReference>,
 }
 
 impl TurnSummary {
@@ -42,15 +96,134 @@ impl TurnSummary {
         self
     }
 
-    /// Add a file change
+    /// Set the cached share of the input tokens
+    pub fn with_cache_tokens(mut self, read: u64, write: u64) -> Self {
+        self.cache_read_tokens = read;
+        self.cache_write_tokens = write;
+        self
+    }
+
+    /// Cache activity such as "cache 30.0k read · 1.2k write", if any
+    pub fn format_cache_tokens(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if self.cache_read_tokens > 0 {
+            parts.push(format!(
+                "{} read",
+                Self::format_tokens(self.cache_read_tokens)
+            ));
+        }
+        if self.cache_write_tokens > 0 {
+            parts.push(format!(
+                "{} write",
+                Self::format_tokens(self.cache_write_tokens)
+            ));
+        }
+        if parts.is_empty() {
+            None
+        } else {
+            Some(format!("cache {}", parts.join(" · ")))
+        }
+    }
+
+    /// Add a file change, folding repeat changes to the same file into one entry
     pub fn add_file(&mut self, filename: impl Into<String>, additions: usize, deletions: usize) {
+        let filename = filename.into();
+        if let Some(existing) = self
+            .files_changed
+            .iter_mut()
+            .find(|change| change.filename == filename)
+        {
+            existing.additions += additions;
+            existing.deletions += deletions;
+            return;
+        }
         self.files_changed.push(FileChange {
-            filename: filename.into(),
+            filename,
             additions,
             deletions,
         });
     }
 
+    /// Record a command that just started; returns its index for [`Self::finish_command`]
+    pub fn start_command(&mut self, command: impl Into<String>) -> usize {
+        self.commands.push(CommandRun {
+            command: command.into(),
+            exit_code: None,
+            succeeded: None,
+        });
+        self.commands.len() - 1
+    }
+
+    /// Record how a started command ended and pick up any PR or commit it created
+    pub fn finish_command(
+        &mut self,
+        index: usize,
+        succeeded: Option<bool>,
+        exit_code: Option<i32>,
+        output: &str,
+    ) {
+        let Some(run) = self.commands.get_mut(index) else {
+            return;
+        };
+        run.succeeded = succeeded;
+        run.exit_code = exit_code;
+        let references = find_references(&run.command, output);
+        for reference in references {
+            if !self.references.contains(&reference) {
+                self.references.push(reference);
+            }
+        }
+    }
+
+    /// Record a command that already finished
+    pub fn add_command(
+        &mut self,
+        command: impl Into<String>,
+        succeeded: Option<bool>,
+        exit_code: Option<i32>,
+        output: &str,
+    ) {
+        let index = self.start_command(command);
+        self.finish_command(index, succeeded, exit_code, output);
+    }
+
+    /// Outcome of the last finished test or check command, if any ran
+    pub fn verification(&self) -> Option<VerifyOutcome> {
+        self.commands
+            .iter()
+            .rev()
+            .filter(|run| is_verify_command(&run.command))
+            .find_map(|run| run.succeeded)
+            .map(|passed| {
+                if passed {
+                    VerifyOutcome::Passed
+                } else {
+                    VerifyOutcome::Failed
+                }
+            })
+    }
+
+    /// Commands, checks and references, e.g. "3 commands · checks passed · PR #12"
+    pub fn format_ledger(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        match self.commands.len() {
+            0 => {}
+            1 => parts.push("1 command".to_string()),
+            n => parts.push(format!("{n} commands")),
+        }
+        match self.verification() {
+            Some(VerifyOutcome::Passed) => parts.push("checks passed".to_string()),
+            Some(VerifyOutcome::Failed) => parts.push("checks failed".to_string()),
+            None => {}
+        }
+        parts.extend(self.references.iter().map(TurnReference::label));
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(" · "))
+        }
+    }
+
     /// Format duration as human-readable string
     pub fn format_duration(&self) -> String {
         let secs = self.duration_secs;
@@ -76,24 +249,57 @@ impl TurnSummary {
         // Separator
         // Tokens: ⬇1.2k ⬆856
         let mut spans = vec![
-            Span::styled("⏱ ", Style::default().fg(Color::DarkGray)),
-            Span::styled(self.format_duration(), Style::default().fg(Color::Gray)),
-            Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
-            Span::styled("↓", Style::default().fg(Color::Cyan)),
+            Span::styled("⏱ ", Style::default().fg(text_muted())),
             Span::styled(
-                Self::format_tokens(self.input_tokens),
-                Style::default().fg(Color::Cyan),
+                self.format_duration(),
+                Style::default().fg(text_secondary()),
             ),
-            Span::styled(" ↑", Style::default().fg(Color::Magenta)),
+            Span::styled(" │ ", Style::default().fg(text_muted())),
+            Span::styled("↓", Style::default().fg(accent_primary())),
             Span::styled(
-                Self::format_tokens(self.output_tokens),
-                Style::default().fg(Color::Magenta),
+                Self::format_tokens(self.input_tokens),
+                Style::default().fg(accent_primary()),
             ),
         ];
+        if let Some(cache) = self.format_cache_tokens() {
+            spans.push(Span::styled(
+                format!(" ({})", cache),
+                Style::default().fg(text_muted()),
+            ));
+        }
+        spans.push(Span::styled(" ↑", Style::default().fg(accent_secondary())));
+        spans.push(Span::styled(
+            Self::format_tokens(self.output_tokens),
+            Style::default().fg(accent_secondary()),
+        ));
+
+        if !self.commands.is_empty() {
+            spans.push(Span::styled(" │ ", Style::default().fg(text_muted())));
+            spans.push(Span::styled(
+                format!("$ {}", self.commands.len()),
+                Style::default().fg(text_secondary()),
+            ));
+            match self.verification() {
+                Some(VerifyOutcome::Passed) => {
+                    spans.push(Span::styled(" ✓", Style::default().fg(accent_success())));
+                }
+                Some(VerifyOutcome::Failed) => {
+                    spans.push(Span::styled(" ✗", Style::default().fg(accent_error())));
+                }
+                None => {}
+            }
+        }
+        for reference in &self.references {
+            spans.push(Span::styled(" │ ", Style::default().fg(text_muted())));
+            spans.push(Span::styled(
+                reference.label(),
+                Style::default().fg(accent_primary()),
+            ));
+        }
 
         // Files changed (show up to 3, then overflow)
         if !self.files_changed.is_empty() {
-            spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(" │ ", Style::default().fg(text_muted())));
 
             let max_files = 3;
             let overflow = self.files_changed.len().saturating_sub(max_files);
@@ -110,38 +316,38 @@ impl TurnSummary {
 
             for (i, file) in display_files.iter().enumerate() {
                 if i > 0 {
-                    spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+                    spans.push(Span::styled(" │ ", Style::default().fg(text_muted())));
                 }
 
                 // Shorten filename if needed
                 let filename = Self::shorten_filename(&file.filename, 15);
 
-                spans.push(Span::styled("◉ ", Style::default().fg(Color::Yellow)));
-                spans.push(Span::styled(filename, Style::default().fg(Color::White)));
+                spans.push(Span::styled("◉ ", Style::default().fg(accent_warning())));
+                spans.push(Span::styled(filename, Style::default().fg(text_primary())));
                 spans.push(Span::styled(
                     format!(" +{}", file.additions),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(accent_success()),
                 ));
                 spans.push(Span::styled(
                     format!(" -{}", file.deletions),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(accent_error()),
                 ));
             }
 
             // Overflow indicator
             if overflow > 0 {
-                spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+                spans.push(Span::styled(" │ ", Style::default().fg(text_muted())));
                 spans.push(Span::styled(
                     format!("+{} more", overflow),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(text_muted()),
                 ));
                 spans.push(Span::styled(
                     format!(" +{}", overflow_add),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(accent_success()),
                 ));
                 spans.push(Span::styled(
                     format!(" -{}", overflow_del),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(accent_error()),
                 ));
             }
         }
@@ -164,3 +370,123 @@ impl TurnSummary {
         }
     }
 }
+
+/// Whether a command runs tests or other checks whose result is worth reporting
+pub fn is_verify_command(command: &str) -> bool {
+    const VERIFY_COMMANDS: &[&str] = &[
+        "cargo test",
+        "cargo nextest",
+        "cargo clippy",
+        "cargo check",
+        "npm test",
+        "npm run test",
+        "npm run lint",
+        "pnpm test",
+        "yarn test",
+        "bun test",
+        "pytest",
+        "go test",
+        "go vet",
+        "make test",
+        "make check",
+        "vitest",
+        "jest",
+    ];
+    let command = command.to_lowercase();
+    VERIFY_COMMANDS
+        .iter()
+        .any(|verify| command.contains(verify))
+}
+
+/// GitHub pull request numbers from `/pull/<n>` URLs in the text
+pub fn find_pr_numbers(text: &str) -> Vec<u32> {
+    text.split_whitespace()
+        .filter_map(|word| {
+            let after_pull = &word[word.find("/pull/")? + "/pull/".len()..];
+            let digits: String = after_pull
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect();
+            digits.parse().ok()
+        })
+        .collect()
+}
+
+/// PRs opened by `gh pr create` and commits made by `git commit`
+fn find_references(command: &str, output: &str) -> Vec<TurnReference> {
+    let mut references = Vec::new();
+    if command.contains("gh pr create") {
+        references.extend(
+            find_pr_numbers(output)
+                .into_iter()
+                .map(TurnReference::PullRequest),
+        );
+    }
+    if command.contains("git commit") {
+        // git reports new commits as "[branch 1a2b3c4] subject"
+        references.extend(output.lines().filter_map(|line| {
+            let inner = line.trim().strip_prefix('[')?.split(']').next()?;
+            let sha = inner.split_whitespace().last()?;
+            let is_sha =
+                (7..=40).contains(&sha.len()) && sha.chars().all(|c| c.is_ascii_hexdigit());
+            is_sha.then(|| TurnReference::Commit(sha.to_string()))
+        }));
+    }
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verification_follows_last_check_command() {
+        let mut summary = TurnSummary::new();
+        summary.add_command("cargo test", Some(false), Some(101), "test result: FAILED");
+        summary.add_command("ls src", Some(true), Some(0), "");
+        assert_eq!(summary.verification(), Some(VerifyOutcome::Failed));
+
+        let rerun = summary.start_command("cargo test -p conduit");
+        assert_eq!(summary.verification(), Some(VerifyOutcome::Failed));
+        summary.finish_command(rerun, Some(true), Some(0), "test result: ok");
+        assert_eq!(summary.verification(), Some(VerifyOutcome::Passed));
+        assert_eq!(
+            summary.format_ledger().as_deref(),
+            Some("3 commands · checks passed")
+        );
+    }
+
+    #[test]
+    fn test_references_from_commit_and_pr_commands() {
+        let mut summary = TurnSummary::new();
+        summary.add_command(
+            "git commit -m 'Fix login'",
+            Some(true),
+            Some(0),
+            "[fix/login 1a2b3c4d] Fix login\n 1 file changed, 2 insertions(+)",
+        );
+        summary.add_command(
+            "gh pr view 7",
+            Some(true),
+            Some(0),
+            "https://github.com/o/r/pull/7",
+        );
+        summary.add_command(
+            "gh pr create --fill",
+            Some(true),
+            Some(0),
+            "https://github.com/o/r/pull/12\n",
+        );
+        assert_eq!(
+            summary.references,
+            vec![
+                TurnReference::Commit("1a2b3c4d".to_string()),
+                TurnReference::PullRequest(12),
+            ]
+        );
+        assert_eq!(
+            summary.format_ledger().as_deref(),
+            Some("3 commands · commit 1a2b3c4 · PR #12")
+        );
+    }
+}
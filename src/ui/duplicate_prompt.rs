@@ -0,0 +1,158 @@
+//! Detection of prompts that repeat one already sent in the session, so the
+//! user can read the earlier answer instead of paying for a second one.
+
+use std::collections::HashSet;
+
+use crate::ui::components::{ChatMessage, MessageRole};
+
+/// Normalized prompts shorter than this are never flagged; short replies
+/// like "yes" or "continue" are repeated on purpose
+const MIN_PROMPT_CHARS: usize = 24;
+
+/// Word-pair overlap at or above which two prompts count as near-duplicates
+const NEAR_DUPLICATE_SIMILARITY: f64 = 0.7;
+
+/// An earlier user message a new prompt repeats
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicatePrompt {
+    /// Chat message index of the earlier prompt
+    pub message_index: usize,
+    /// Prompts sent since the earlier one (0 = the previous prompt)
+    pub prompts_ago: usize,
+    /// Same text once case and whitespace are ignored
+    pub exact: bool,
+}
+
+impl DuplicatePrompt {
+    /// Inline warning shown instead of sending the prompt
+    pub fn warning(&self) -> String {
+        let when = match self.prompts_ago {
+            0 => "as your previous prompt".to_string(),
+            1 => "1 prompt ago".to_string(),
+            n => format!("{n} prompts ago"),
+        };
+        let what = if self.exact {
+            "You already sent this prompt"
+        } else {
+            "You sent a very similar prompt"
+        };
+        format!(
+            "{what} {when}. Press Enter to send it anyway, or Alt+J to jump to the earlier answer."
+        )
+    }
+}
+
+/// Duplicate the user was warned about; submitting the same prompt again
+/// sends it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateWarning {
+    /// Normalized text of the prompt that was held back
+    pub prompt: String,
+    /// Chat message index of the earlier prompt, for jumping to its answer
+    pub message_index: usize,
+}
+
+/// Lowercased prompt with whitespace collapsed and trailing punctuation dropped
+pub fn normalize_prompt(prompt: &str) -> String {
+    prompt
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(|c: char| c.is_ascii_punctuation())
+        .to_lowercase()
+}
+
+/// Most recent user message in `messages` that `prompt` repeats, if any
+pub fn find_duplicate_prompt(messages: &[ChatMessage], prompt: &str) -> Option<DuplicatePrompt> {
+    let prompt = normalize_prompt(prompt);
+    if prompt.chars().count() < MIN_PROMPT_CHARS {
+        return None;
+    }
+    let pairs = word_pairs(&prompt);
+
+    messages
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, message)| message.role == MessageRole::User)
+        .enumerate()
+        .find_map(|(prompts_ago, (message_index, message))| {
+            let earlier = normalize_prompt(&message.content);
+            let exact = earlier == prompt;
+            (exact || similarity(&pairs, &word_pairs(&earlier)) >= NEAR_DUPLICATE_SIMILARITY)
+                .then_some(DuplicatePrompt {
+                    message_index,
+                    prompts_ago,
+                    exact,
+                })
+        })
+}
+
+/// Adjacent word pairs, so reordered prompts aren't mistaken for repeats
+fn word_pairs(prompt: &str) -> HashSet<(&str, &str)> {
+    let words: Vec<&str> = prompt.split(' ').collect();
+    words.windows(2).map(|pair| (pair[0], pair[1])).collect()
+}
+
+/// Jaccard similarity of two word-pair sets
+fn similarity(a: &HashSet<(&str, &str)>, b: &HashSet<(&str, &str)>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chat(prompts: &[&str]) -> Vec<ChatMessage> {
+        prompts
+            .iter()
+            .flat_map(|prompt| [ChatMessage::user(*prompt), ChatMessage::assistant("Done.")])
+            .collect()
+    }
+
+    #[test]
+    fn test_exact_repeat_ignores_case_and_whitespace() {
+        let messages = chat(&[
+            "Add a retry to the webhook sender",
+            "Now write tests for the parser",
+        ]);
+        let duplicate =
+            find_duplicate_prompt(&messages, "add a retry to the  webhook sender.").unwrap();
+        assert_eq!(
+            duplicate,
+            DuplicatePrompt {
+                message_index: 0,
+                prompts_ago: 1,
+                exact: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_near_duplicate_is_flagged_but_different_prompts_are_not() {
+        let messages = chat(&["Refactor the session store so that it batches every write"]);
+        let near = find_duplicate_prompt(
+            &messages,
+            "Refactor the session store so that it batches every single write",
+        )
+        .unwrap();
+        assert!(!near.exact);
+        assert_eq!(near.prompts_ago, 0);
+
+        assert!(find_duplicate_prompt(
+            &messages,
+            "Refactor the workspace store so it caches every read"
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_short_prompts_are_never_flagged() {
+        let messages = chat(&["continue", "continue"]);
+        assert!(find_duplicate_prompt(&messages, "continue").is_none());
+    }
+}
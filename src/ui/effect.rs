@@ -1,7 +1,18 @@
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
-use crate::agent::{AgentStartConfig, AgentType};
+use crate::agent::{AgentStartConfig, AgentType, ExecutionTarget};
+use crate::config::{
+    ChangeGuardConfig, FetchConfig, ReadAloudConfig, SecurityScanConfig, VoiceConfig,
+};
+use crate::core::coverage::CoverageTool;
+use crate::core::hooks::{HookContext, HookRegistry, TurnReport};
 use crate::session::ExternalSession;
+use crate::ui::components::ChatMessage;
+use crate::ui::transcript_export::TranscriptFormat;
+use crate::util::voice::Recording;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 /// Side effects that should be executed outside the reducer.
@@ -11,15 +22,30 @@ pub enum Effect {
         session_id: Uuid,
         agent_type: AgentType,
         config: AgentStartConfig,
+        /// Snapshot the worktree before starting, keyed by this prompt position
+        snapshot_prompt: Option<usize>,
     },
     PrPreflight {
         tab_index: usize,
         working_dir: PathBuf,
+        /// Linked repositories (name, directory) checked alongside
+        linked_dirs: Vec<(String, PathBuf)>,
+        /// Scanners run on the workspace before a new PR is offered
+        security_scan: SecurityScanConfig,
+        /// Checks on the changed files before a new PR is offered
+        change_guard: ChangeGuardConfig,
     },
     OpenPrInBrowser {
         working_dir: PathBuf,
     },
     DumpDebugState,
+    /// Export a transcript to Markdown, JSON, or HTML/PDF
+    ExportTranscript {
+        format: TranscriptFormat,
+        title: String,
+        file_stem: String,
+        messages: Vec<ChatMessage>,
+    },
     CreateWorkspace {
         repo_id: Uuid,
     },
@@ -34,7 +60,11 @@ pub enum Effect {
     RemoveProject {
         repo_id: Uuid,
     },
-    CopyToClipboard(String),
+    CopyToClipboard {
+        text: String,
+        /// Shown in the clipboard history, e.g. "message"
+        source: &'static str,
+    },
     /// Discover external sessions (Claude Code and Codex CLI; Gemini not supported yet)
     DiscoverSessions,
     /// Import an external session
@@ -55,4 +85,89 @@ pub enum Effect {
         command: String,
         working_dir: Option<PathBuf>,
     },
+    /// Transcribe a finished voice recording into the session's input box
+    TranscribeVoice {
+        session_id: Uuid,
+        recording: Recording,
+        config: VoiceConfig,
+    },
+    /// Speak a finished response, cutting off anything still being read
+    ReadAloud {
+        session_id: Uuid,
+        text: String,
+        config: ReadAloudConfig,
+    },
+    /// Tell the user a background tab finished or failed its turn
+    DesktopNotification {
+        session_id: Uuid,
+        summary: String,
+        body: String,
+    },
+    /// Download a page for `/fetch` and attach its text to the session
+    FetchUrl {
+        session_id: Uuid,
+        url: String,
+        config: FetchConfig,
+    },
+    /// Pass a prompt through the pre-submit hooks before it is sent
+    RunPreSubmitHooks {
+        hooks: Arc<HookRegistry>,
+        context: HookContext,
+        prompt: String,
+        images: Vec<PathBuf>,
+        image_placeholders: Vec<String>,
+    },
+    /// Hand a completed turn to the post-turn hooks
+    RunPostTurnHooks {
+        hooks: Arc<HookRegistry>,
+        context: HookContext,
+        turn: TurnReport,
+    },
+    /// Resend a rate-limited turn once `delay` has passed
+    RetryFailedTurn {
+        session_id: Uuid,
+        delay: Duration,
+    },
+    /// Restore the worktree snapshot taken before a past prompt
+    RollbackWorkspace {
+        session_id: Uuid,
+        prompt_index: usize,
+        working_dir: PathBuf,
+        tree: String,
+    },
+    /// Run a new workspace's bootstrap commands, streaming progress back
+    RunBootstrap {
+        session_id: Uuid,
+        working_dir: PathBuf,
+        target: ExecutionTarget,
+        commands: Vec<String>,
+        cancel: CancellationToken,
+    },
+    /// Look for outdated dependencies in a workspace's manifests
+    ScanDependencies {
+        session_id: Uuid,
+        working_dir: PathBuf,
+    },
+    /// Run a dependency update group's verification commands
+    RunDependencyVerification {
+        session_id: Uuid,
+        working_dir: PathBuf,
+        target: ExecutionTarget,
+        commands: Vec<String>,
+        cancel: CancellationToken,
+    },
+    /// Measure a workspace's test coverage
+    MeasureCoverage {
+        session_id: Uuid,
+        working_dir: PathBuf,
+        tool: CoverageTool,
+        cancel: CancellationToken,
+    },
+    /// Relay a workspace's dev server so it's reachable outside its container
+    ForwardPreview {
+        workspace_id: Uuid,
+        port: u16,
+        target: ExecutionTarget,
+        working_dir: PathBuf,
+    },
 }
@@ -1,9 +1,19 @@
 use std::path::PathBuf;
 
 use crate::agent::{AgentEvent, AgentInput, AgentType};
-use crate::git::PrPreflightResult;
+use crate::core::bootstrap::BootstrapProgress;
+use crate::core::change_guard::ChangeFlag;
+use crate::core::coverage::CoverageReport;
+use crate::core::dep_update::DependencyScan;
+use crate::core::hooks::HookRejection;
+use crate::core::previews::Preview;
+use crate::core::security_scan::SecurityScanReport;
+use crate::git::{LinkedPrPreflight, PrPreflightResult, RestoreSummary};
 use crate::ui::git_tracker::GitTrackerUpdate;
+use crate::ui::transcript_export::TranscriptExport;
+use crate::util::url_fetch::FetchedPage;
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 /// Application-level events
@@ -23,6 +33,8 @@ pub enum AppEvent {
         session_id: Uuid,
         pid: u32,
         input_tx: Option<mpsc::Sender<AgentInput>>,
+        /// Stops an in-process agent (pid is `NO_PID`)
+        cancel: Option<CancellationToken>,
     },
     /// Agent failed to start for a specific session
     AgentStartFailed { session_id: Uuid, error: String },
@@ -78,6 +90,11 @@ pub enum AppEvent {
         tab_index: usize,
         working_dir: PathBuf,
         result: PrPreflightResult,
+        linked: Vec<LinkedPrPreflight>,
+        /// None when no scanners are configured or no new PR is needed
+        security: Option<SecurityScanReport>,
+        /// Flagged changed files; None when the guard is off or no new PR is needed
+        change_flags: Option<Result<Vec<ChangeFlag>, String>>,
     },
 
     /// Open PR in browser completed
@@ -86,6 +103,52 @@ pub enum AppEvent {
     /// Debug export completed
     DebugDumped { result: Result<String, String> },
 
+    /// Voice prompt transcription completed
+    VoiceTranscribed {
+        session_id: Uuid,
+        result: Result<String, String>,
+    },
+
+    /// `/fetch` download finished
+    UrlFetched {
+        session_id: Uuid,
+        url: String,
+        result: Result<FetchedPage, String>,
+    },
+
+    /// Pre-submit hooks finished; `Ok` holds the (possibly rewritten) prompt to send
+    PreSubmitHooksFinished {
+        session_id: Uuid,
+        result: Result<String, HookRejection>,
+        /// Prompt as the user wrote it, restored to the draft on rejection
+        original_prompt: String,
+        images: Vec<PathBuf>,
+        image_placeholders: Vec<String>,
+    },
+
+    /// Rate-limit wait elapsed; resend the failed turn
+    FailedTurnRetryDue { session_id: Uuid },
+
+    /// Worktree snapshot taken before a prompt was sent
+    PromptSnapshotTaken {
+        session_id: Uuid,
+        /// Position of the prompt among the session's user messages
+        prompt_index: usize,
+        tree: String,
+    },
+
+    /// Workspace rollback to before a past prompt completed
+    WorkspaceRolledBack {
+        session_id: Uuid,
+        prompt_index: usize,
+        result: Result<RestoreSummary, String>,
+    },
+
+    /// Transcript export completed
+    TranscriptExported {
+        result: Result<TranscriptExport, String>,
+    },
+
     /// Workspace creation completed
     WorkspaceCreated {
         repo_id: Uuid,
@@ -97,6 +160,42 @@ pub enum AppEvent {
         result: Result<ForkWorkspaceCreated, String>,
     },
 
+    /// Output or step change from a running workspace bootstrap
+    BootstrapProgress {
+        session_id: Uuid,
+        progress: BootstrapProgress,
+    },
+    /// Workspace bootstrap finished, failed or was skipped
+    BootstrapFinished {
+        session_id: Uuid,
+        outcome: BootstrapOutcome,
+    },
+    /// Dependency scan for `/deps` finished
+    DependenciesScanned {
+        session_id: Uuid,
+        result: Result<DependencyScan, String>,
+    },
+    /// Output or step change from a dependency update's verification
+    DependencyVerifyProgress {
+        session_id: Uuid,
+        progress: BootstrapProgress,
+    },
+    /// A dependency update's verification passed, failed or was stopped
+    DependencyVerifyFinished {
+        session_id: Uuid,
+        result: Result<(), String>,
+    },
+    /// Coverage measurement for `/coverage` finished
+    CoverageMeasured {
+        session_id: Uuid,
+        result: Result<CoverageReport, String>,
+    },
+    /// A workspace's dev server previews were forwarded or pruned
+    PreviewsChanged {
+        workspace_id: Uuid,
+        previews: Vec<Preview>,
+    },
+
     /// Workspace archive completed
     WorkspaceArchived {
         workspace_id: Uuid,
@@ -171,6 +270,8 @@ pub struct ShellCommandResult {
 pub struct WorkspaceCreated {
     pub repo_id: Uuid,
     pub workspace_id: Uuid,
+    /// Bootstrap commands to run before the workspace's first turn
+    pub bootstrap_commands: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -179,6 +280,15 @@ pub struct ForkWorkspaceCreated {
     pub workspace_id: Uuid,
 }
 
+/// How a workspace bootstrap ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BootstrapOutcome {
+    Succeeded,
+    /// Interrupted by the user before it finished
+    Skipped,
+    Failed(String),
+}
+
 #[derive(Debug, Clone)]
 pub struct WorkspaceArchived {
     pub workspace_id: Uuid,
@@ -212,6 +322,8 @@ pub enum InputMode {
     SelectingTheme,
     /// Setting base projects directory
     SettingBaseDir,
+    /// Typing a custom name for the current tab
+    RenamingTab,
     /// Picking a project from the list
     PickingProject,
     /// Showing a confirmation dialog
@@ -234,6 +346,10 @@ pub enum InputMode {
     MissingTool,
     /// Editing queued messages inline
     QueueEditing,
+    /// Moving a highlight between chat messages to copy one
+    SelectingMessage,
+    /// Browsing recent clipboard copies
+    ClipboardHistory,
 }
 
 /// View mode for the main content area
@@ -244,4 +360,8 @@ pub enum ViewMode {
     Chat,
     /// Raw events debug view
     RawEvents,
+    /// Per-turn timing bars
+    Timeline,
+    /// Diffs of the session's file edits
+    Diff,
 }
@@ -0,0 +1,338 @@
+//! Per-section draw timings and event handling latency for the frame
+//! diagnostics overlay. Nothing is timed while the overlay is hidden, so the
+//! draw path only pays for an `Option` check.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::symbols::border;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Widget};
+
+use crate::ui::components::{
+    accent_error, accent_warning, border_focused, text_muted, text_primary,
+};
+
+/// Section times above this are highlighted (a 60 FPS frame is ~16ms)
+const SLOW_SECTION: Duration = Duration::from_millis(4);
+/// Draw or event times above this are highlighted
+const SLOW_FRAME: Duration = Duration::from_millis(16);
+/// Frames kept for each section's averages
+const FRAME_WINDOW: usize = 120;
+/// Events kept for the latency percentiles
+const EVENT_WINDOW: usize = 500;
+
+/// Part of the screen timed separately
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawSection {
+    Sidebar,
+    TabBar,
+    ChatView,
+    InputBox,
+    StatusBar,
+    RawEvents,
+    /// Diff, timeline and file viewer tabs
+    Panels,
+    Footer,
+    /// Dialogs and pickers drawn over the view
+    Dialogs,
+}
+
+impl DrawSection {
+    pub const ALL: [DrawSection; 9] = [
+        DrawSection::Sidebar,
+        DrawSection::TabBar,
+        DrawSection::ChatView,
+        DrawSection::InputBox,
+        DrawSection::StatusBar,
+        DrawSection::RawEvents,
+        DrawSection::Panels,
+        DrawSection::Footer,
+        DrawSection::Dialogs,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DrawSection::Sidebar => "sidebar",
+            DrawSection::TabBar => "tabs+header",
+            DrawSection::ChatView => "chat view",
+            DrawSection::InputBox => "input box",
+            DrawSection::StatusBar => "status bar",
+            DrawSection::RawEvents => "raw events",
+            DrawSection::Panels => "panels",
+            DrawSection::Footer => "footer",
+            DrawSection::Dialogs => "dialogs",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Timings of one section over the recent frames that drew it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectionStats {
+    pub last: Duration,
+    pub avg: Duration,
+    pub max: Duration,
+}
+
+impl SectionStats {
+    fn from_samples(samples: &VecDeque<Duration>) -> Option<Self> {
+        let last = *samples.back()?;
+        let total: Duration = samples.iter().sum();
+        Some(Self {
+            last,
+            avg: total / samples.len() as u32,
+            max: samples.iter().copied().max().unwrap_or_default(),
+        })
+    }
+}
+
+/// Event handling latency percentiles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyPercentiles {
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+    pub samples: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct FrameDiagnostics {
+    enabled: bool,
+    /// Section time in the frame being drawn; `None` when not drawn
+    current: [Option<Duration>; DrawSection::ALL.len()],
+    sections: [VecDeque<Duration>; DrawSection::ALL.len()],
+    draws: VecDeque<Duration>,
+    events: VecDeque<Duration>,
+}
+
+impl FrameDiagnostics {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Show or hide the overlay; samples start over each time it's shown
+    pub fn toggle(&mut self) {
+        *self = Self {
+            enabled: !self.enabled,
+            ..Self::default()
+        };
+    }
+
+    /// Start timing a section; `None` while the overlay is hidden
+    pub fn start(&self) -> Option<Instant> {
+        self.enabled.then(Instant::now)
+    }
+
+    /// Add the time since `started` to a section of the current frame
+    pub fn record(&mut self, section: DrawSection, started: Option<Instant>) {
+        let Some(started) = started else {
+            return;
+        };
+        let slot = &mut self.current[section.index()];
+        *slot = Some(slot.unwrap_or_default() + started.elapsed());
+    }
+
+    /// Close the frame with its total draw time
+    pub fn end_frame(&mut self, draw_time: Duration) {
+        if !self.enabled {
+            return;
+        }
+        for (current, samples) in self.current.iter_mut().zip(self.sections.iter_mut()) {
+            if let Some(elapsed) = current.take() {
+                push_bounded(samples, elapsed, FRAME_WINDOW);
+            }
+        }
+        push_bounded(&mut self.draws, draw_time, FRAME_WINDOW);
+    }
+
+    /// Record how long the event loop took to handle one event
+    pub fn record_event(&mut self, latency: Duration) {
+        if self.enabled {
+            push_bounded(&mut self.events, latency, EVENT_WINDOW);
+        }
+    }
+
+    pub fn section_stats(&self, section: DrawSection) -> Option<SectionStats> {
+        SectionStats::from_samples(&self.sections[section.index()])
+    }
+
+    pub fn draw_stats(&self) -> Option<SectionStats> {
+        SectionStats::from_samples(&self.draws)
+    }
+
+    pub fn event_latency(&self) -> Option<LatencyPercentiles> {
+        if self.events.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = self.events.iter().copied().collect();
+        sorted.sort_unstable();
+        // Nearest-rank percentile
+        let rank = |pct: usize| sorted[(sorted.len() * pct).div_ceil(100).max(1) - 1];
+        Some(LatencyPercentiles {
+            p50: rank(50),
+            p95: rank(95),
+            p99: rank(99),
+            max: sorted[sorted.len() - 1],
+            samples: sorted.len(),
+        })
+    }
+}
+
+/// Draw the timings in a box at the top right, over whatever is there
+pub fn render_frame_diagnostics_overlay(
+    area: Rect,
+    buf: &mut Buffer,
+    diagnostics: &FrameDiagnostics,
+) {
+    const WIDTH: u16 = 44;
+
+    let header = |label: &str, columns: [&str; 3]| {
+        Line::from(Span::styled(
+            format!(
+                "{label:<12}{:>10}{:>10}{:>10}",
+                columns[0], columns[1], columns[2]
+            ),
+            Style::default().fg(text_muted()),
+        ))
+    };
+    let row = |label: &str, stats: SectionStats, slow: Duration| {
+        let style = if stats.max > slow * 2 {
+            Style::default().fg(accent_error())
+        } else if stats.avg > slow {
+            Style::default().fg(accent_warning())
+        } else {
+            Style::default().fg(text_primary())
+        };
+        Line::from(Span::styled(
+            format!(
+                "{label:<12}{:>10}{:>10}{:>10}",
+                format_ms(stats.last),
+                format_ms(stats.avg),
+                format_ms(stats.max)
+            ),
+            style,
+        ))
+    };
+
+    let mut lines = vec![header("draw", ["last", "avg", "max"])];
+    for section in DrawSection::ALL {
+        if let Some(stats) = diagnostics.section_stats(section) {
+            lines.push(row(section.label(), stats, SLOW_SECTION));
+        }
+    }
+    if let Some(stats) = diagnostics.draw_stats() {
+        lines.push(row("total", stats, SLOW_FRAME));
+    }
+    lines.push(Line::default());
+    match diagnostics.event_latency() {
+        Some(latency) => {
+            lines.push(header(
+                &format!("events ({})", latency.samples),
+                ["p50", "p95", "p99"],
+            ));
+            let style = if latency.p95 > SLOW_FRAME {
+                Style::default().fg(accent_warning())
+            } else {
+                Style::default().fg(text_primary())
+            };
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{:<12}{:>10}{:>10}{:>10}",
+                    format!("max {}", format_ms(latency.max)),
+                    format_ms(latency.p50),
+                    format_ms(latency.p95),
+                    format_ms(latency.p99)
+                ),
+                style,
+            )));
+        }
+        None => lines.push(Line::from(Span::styled(
+            "No events handled yet",
+            Style::default().fg(text_muted()),
+        ))),
+    }
+
+    let width = WIDTH.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let overlay = Rect {
+        x: area.x + area.width - width,
+        y: area.y,
+        width,
+        height,
+    };
+    Clear.render(overlay, buf);
+    let block = Block::default()
+        .title(" Frame diagnostics ")
+        .borders(Borders::ALL)
+        .border_set(border::ROUNDED)
+        .border_style(Style::default().fg(border_focused()));
+    let inner = block.inner(overlay);
+    block.render(overlay, buf);
+    Paragraph::new(lines).render(inner, buf);
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.2}ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn push_bounded(samples: &mut VecDeque<Duration>, value: Duration, limit: usize) {
+    samples.push_back(value);
+    if samples.len() > limit {
+        samples.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections_only_count_frames_that_drew_them() {
+        let mut diagnostics = FrameDiagnostics::default();
+        assert!(diagnostics.start().is_none());
+
+        diagnostics.toggle();
+        let started = Some(Instant::now() - Duration::from_millis(4));
+        diagnostics.record(DrawSection::ChatView, started);
+        diagnostics.end_frame(Duration::from_millis(5));
+        diagnostics.end_frame(Duration::from_millis(1));
+
+        let chat = diagnostics.section_stats(DrawSection::ChatView).unwrap();
+        assert!(chat.last >= Duration::from_millis(4));
+        assert_eq!(chat.avg, chat.last);
+        assert!(diagnostics.section_stats(DrawSection::RawEvents).is_none());
+        let draws = diagnostics.draw_stats().unwrap();
+        assert_eq!(draws.last, Duration::from_millis(1));
+        assert_eq!(draws.max, Duration::from_millis(5));
+        assert_eq!(draws.avg, Duration::from_millis(3));
+
+        diagnostics.toggle();
+        assert!(diagnostics.draw_stats().is_none());
+    }
+
+    #[test]
+    fn test_event_latency_percentiles() {
+        let mut diagnostics = FrameDiagnostics::default();
+        diagnostics.record_event(Duration::from_millis(9));
+        assert!(diagnostics.event_latency().is_none());
+
+        diagnostics.toggle();
+        for ms in 1..=100 {
+            diagnostics.record_event(Duration::from_millis(ms));
+        }
+        let latency = diagnostics.event_latency().unwrap();
+        assert_eq!(latency.p50, Duration::from_millis(50));
+        assert_eq!(latency.p95, Duration::from_millis(95));
+        assert_eq!(latency.p99, Duration::from_millis(99));
+        assert_eq!(latency.max, Duration::from_millis(100));
+        assert_eq!(latency.samples, 100);
+    }
+}
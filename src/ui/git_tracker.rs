@@ -9,7 +9,8 @@ use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
-use crate::git::{GitDiffStats, PrManager, PrStatus};
+use crate::git::{GitDiffStats, PrManager, PrStatus, UpstreamStatus};
+use crate::util::network;
 
 /// Configuration for the background tracker
 pub struct GitTrackerConfig {
@@ -34,6 +35,7 @@ struct WorkspaceGitState {
     pr_status: Option<PrStatus>,
     diff_stats: GitDiffStats,
     branch_name: Option<String>,
+    upstream: Option<UpstreamStatus>,
     #[allow(dead_code)]
     last_pr_check: Option<Instant>,
     #[allow(dead_code)]
@@ -62,6 +64,11 @@ pub enum GitTrackerUpdate {
         workspace_id: Uuid,
         branch: Option<String>,
     },
+    /// Ahead/behind counts changed (None means the branch has no upstream)
+    UpstreamChanged {
+        workspace_id: Uuid,
+        upstream: Option<UpstreamStatus>,
+    },
 }
 
 /// Commands to the background tracker
@@ -266,6 +273,25 @@ impl GitTracker {
                         "branch_changed",
                     );
                 }
+
+                let dir = working_dir.clone();
+                let new_upstream =
+                    tokio::task::spawn_blocking(move || UpstreamStatus::from_working_dir(&dir))
+                        .await
+                        .ok()
+                        .flatten();
+
+                if new_upstream != state.upstream {
+                    state.upstream = new_upstream;
+                    send_update(
+                        &update_tx,
+                        GitTrackerUpdate::UpstreamChanged {
+                            workspace_id,
+                            upstream: new_upstream,
+                        },
+                        "upstream_changed",
+                    );
+                }
             }
         }
     }
@@ -276,6 +302,10 @@ impl GitTracker {
         let update_tx = self.update_tx.clone();
 
         for workspace_id in workspace_ids {
+            // Offline, only the occasional retry goes out
+            if !network::should_attempt() {
+                break;
+            }
             if let Some((working_dir, state)) = self.workspaces.get_mut(&workspace_id) {
                 let dir = working_dir.clone();
 
@@ -285,6 +315,10 @@ impl GitTracker {
                         .await
                         .ok()
                         .flatten();
+                // Keep showing the last known status while offline
+                if new_pr_status.is_none() && network::is_offline() {
+                    continue;
+                }
 
                 // Compare with current state - include check status and merge readiness
                 let pr_changed = match (&state.pr_status, &new_pr_status) {
@@ -341,19 +375,36 @@ impl GitTracker {
         .ok()
         .flatten();
 
-        // Get PR status
-        let new_pr_status = tokio::task::spawn_blocking(move || PrManager::get_existing_pr(&dir))
-            .await
-            .ok()
-            .flatten();
+        // Get ahead/behind counts
+        let new_upstream = tokio::task::spawn_blocking({
+            let dir = dir.clone();
+            move || UpstreamStatus::from_working_dir(&dir)
+        })
+        .await
+        .ok()
+        .flatten();
+
+        // Get PR status, unless offline and waiting to retry
+        let new_pr_status = if network::should_attempt() {
+            tokio::task::spawn_blocking(move || PrManager::get_existing_pr(&dir))
+                .await
+                .ok()
+                .flatten()
+        } else {
+            None
+        };
+        let pr_unavailable_offline = new_pr_status.is_none() && network::is_offline();
 
         // Update state and send updates
         if let Some((_, state)) = self.workspaces.get_mut(&workspace_id) {
             state.diff_stats = new_stats.clone();
             state.branch_name = new_branch.clone();
-            state.pr_status = new_pr_status.clone();
+            state.upstream = new_upstream;
             state.last_git_check = Some(Instant::now());
-            state.last_pr_check = Some(Instant::now());
+            if !pr_unavailable_offline {
+                state.pr_status = new_pr_status.clone();
+                state.last_pr_check = Some(Instant::now());
+            }
         }
 
         // Send all updates
@@ -376,6 +427,20 @@ impl GitTracker {
             "branch_changed",
         );
 
+        send_update(
+            &update_tx,
+            GitTrackerUpdate::UpstreamChanged {
+                workspace_id,
+                upstream: new_upstream,
+            },
+            "upstream_changed",
+        );
+
+        // The PR poll fills it in once the network is back
+        if pr_unavailable_offline {
+            return;
+        }
+
         // Send PR update: None means status unavailable (gh failed),
         // Some with exists=false means no PR, Some with exists=true means PR exists
         tracing::debug!(
@@ -0,0 +1,323 @@
+//! Crop and markup for attached images.
+//!
+//! Screenshots usually need a highlight before an agent knows where to look.
+//! `:annotate` takes a short spec such as
+//! `crop 0,0,1280,720; box 420,180,300,90; arrow 200,600 -> 430,260`
+//! and renders it onto a copy of the most recently attached image. Steps run
+//! in order, so coordinates after a `crop` refer to the cropped image.
+
+use std::f32::consts::FRAC_PI_6;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use tempfile::Builder;
+use thiserror::Error;
+
+/// Markup color: a saturated red that stands out on light and dark UIs
+const MARK_COLOR: Rgba<u8> = Rgba([255, 59, 48, 255]);
+
+/// Usage line shown when a spec can't be parsed
+pub const ANNOTATE_USAGE: &str =
+    "Usage: :annotate crop X,Y,W,H | box X,Y,W,H | arrow X1,Y1 -> X2,Y2 (join steps with ;)";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Annotation {
+    /// Keep only the given region
+    Crop {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+    /// Outline a region
+    Box {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+    /// Arrow pointing from `from` to `to`
+    Arrow { from: (u32, u32), to: (u32, u32) },
+}
+
+#[derive(Debug, Error)]
+pub enum AnnotateError {
+    #[error("{0}")]
+    Parse(String),
+    #[error("{0}")]
+    OutOfBounds(String),
+    #[error("Image error: {0}")]
+    Image(#[from] image::ImageError),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Parse a `;`-separated annotation spec.
+pub fn parse_annotations(spec: &str) -> Result<Vec<Annotation>, AnnotateError> {
+    let mut annotations = Vec::new();
+    for step in spec
+        .split(';')
+        .map(str::trim)
+        .filter(|step| !step.is_empty())
+    {
+        let (kind, args) = step.split_once(char::is_whitespace).unwrap_or((step, ""));
+        let numbers = parse_numbers(args)?;
+        let annotation = match (kind.to_ascii_lowercase().as_str(), numbers.as_slice()) {
+            ("crop", &[x, y, width, height]) => Annotation::Crop {
+                x,
+                y,
+                width,
+                height,
+            },
+            ("box", &[x, y, width, height]) => Annotation::Box {
+                x,
+                y,
+                width,
+                height,
+            },
+            ("arrow", &[x1, y1, x2, y2]) => Annotation::Arrow {
+                from: (x1, y1),
+                to: (x2, y2),
+            },
+            ("crop" | "box" | "arrow", _) => {
+                return Err(AnnotateError::Parse(format!(
+                    "'{}' takes 4 numbers, got {}",
+                    kind,
+                    numbers.len()
+                )));
+            }
+            _ => {
+                return Err(AnnotateError::Parse(format!(
+                    "Unknown annotation '{}'",
+                    kind
+                )));
+            }
+        };
+        annotations.push(annotation);
+    }
+
+    if annotations.is_empty() {
+        return Err(AnnotateError::Parse(ANNOTATE_USAGE.to_string()));
+    }
+    Ok(annotations)
+}
+
+fn parse_numbers(args: &str) -> Result<Vec<u32>, AnnotateError> {
+    args.replace("->", " ")
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            part.parse::<u32>()
+                .map_err(|_| AnnotateError::Parse(format!("'{}' is not a pixel coordinate", part)))
+        })
+        .collect()
+}
+
+/// Apply annotations in order. Marks are clipped to the image; crops must fit.
+pub fn apply_annotations(
+    mut image: RgbaImage,
+    annotations: &[Annotation],
+) -> Result<RgbaImage, AnnotateError> {
+    for annotation in annotations {
+        match *annotation {
+            Annotation::Crop {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let fits = width > 0
+                    && height > 0
+                    && x.checked_add(width)
+                        .is_some_and(|right| right <= image.width())
+                    && y.checked_add(height)
+                        .is_some_and(|bottom| bottom <= image.height());
+                if !fits {
+                    return Err(AnnotateError::OutOfBounds(format!(
+                        "crop {},{},{},{} doesn't fit a {}x{} image",
+                        x,
+                        y,
+                        width,
+                        height,
+                        image.width(),
+                        image.height()
+                    )));
+                }
+                image = image::imageops::crop_imm(&image, x, y, width, height).to_image();
+            }
+            Annotation::Box {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let stroke = stroke_width(&image);
+                let (left, top) = (x as f32, y as f32);
+                let right = left + width.saturating_sub(1) as f32;
+                let bottom = top + height.saturating_sub(1) as f32;
+                draw_line(&mut image, (left, top), (right, top), stroke);
+                draw_line(&mut image, (right, top), (right, bottom), stroke);
+                draw_line(&mut image, (right, bottom), (left, bottom), stroke);
+                draw_line(&mut image, (left, bottom), (left, top), stroke);
+            }
+            Annotation::Arrow { from, to } => {
+                let stroke = stroke_width(&image);
+                let from = (from.0 as f32, from.1 as f32);
+                let to = (to.0 as f32, to.1 as f32);
+                draw_line(&mut image, from, to, stroke);
+
+                let (dx, dy) = (from.0 - to.0, from.1 - to.1);
+                let length = (dx * dx + dy * dy).sqrt();
+                if length > 0.0 {
+                    // Two barbs swept back 30° from the shaft
+                    let head = (stroke * 5.0).min(length * 0.5);
+                    let angle = dy.atan2(dx);
+                    for offset in [-FRAC_PI_6, FRAC_PI_6] {
+                        let barb = (
+                            to.0 + head * (angle + offset).cos(),
+                            to.1 + head * (angle + offset).sin(),
+                        );
+                        draw_line(&mut image, to, barb, stroke);
+                    }
+                }
+            }
+        }
+    }
+    Ok(image)
+}
+
+/// Load `path`, apply `annotations`, and encode the result as PNG.
+/// Returns the PNG bytes and the final dimensions.
+pub fn annotate_to_png(
+    path: &Path,
+    annotations: &[Annotation],
+) -> Result<(Vec<u8>, u32, u32), AnnotateError> {
+    let image = image::open(path)?.to_rgba8();
+    let annotated = apply_annotations(image, annotations)?;
+    let (width, height) = annotated.dimensions();
+
+    let mut png = Vec::new();
+    DynamicImage::ImageRgba8(annotated).write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    Ok((png, width, height))
+}
+
+/// Write annotated PNG bytes to a kept tempfile (used when managed
+/// attachment storage is unavailable).
+pub fn write_temp_png(png: &[u8]) -> Result<PathBuf, AnnotateError> {
+    let tmp = Builder::new()
+        .prefix("conduit-annotated-")
+        .suffix(".png")
+        .tempfile()?;
+    std::fs::write(tmp.path(), png)?;
+    let (_file, path) = tmp.keep().map_err(|e| e.error)?;
+    Ok(path)
+}
+
+/// Stroke width that stays visible on both small and large screenshots
+fn stroke_width(image: &RgbaImage) -> f32 {
+    (image.width().min(image.height()) as f32 / 200.0).clamp(2.0, 8.0)
+}
+
+/// Draw a thick line by stamping squares along it, clipped to the image
+fn draw_line(image: &mut RgbaImage, from: (f32, f32), to: (f32, f32), stroke: f32) {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let steps = dx.abs().max(dy.abs()).ceil().max(1.0) as u32;
+    let radius = stroke / 2.0;
+    for step in 0..=steps {
+        let t = step as f32 / steps as f32;
+        let (cx, cy) = (from.0 + dx * t, from.1 + dy * t);
+        let x_start = (cx - radius).round().max(0.0) as u32;
+        let y_start = (cy - radius).round().max(0.0) as u32;
+        let x_end = (cx + radius).round().min(image.width() as f32 - 1.0);
+        let y_end = (cy + radius).round().min(image.height() as f32 - 1.0);
+        if x_end < 0.0 || y_end < 0.0 {
+            continue;
+        }
+        for y in y_start..=y_end as u32 {
+            for x in x_start..=x_end as u32 {
+                image.put_pixel(x, y, MARK_COLOR);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_annotations_accepts_steps_and_arrow_syntax() {
+        let annotations =
+            parse_annotations("crop 0,0,100,80; BOX 10 10 20 20; arrow 5,5 -> 40,30").unwrap();
+        assert_eq!(
+            annotations,
+            vec![
+                Annotation::Crop {
+                    x: 0,
+                    y: 0,
+                    width: 100,
+                    height: 80
+                },
+                Annotation::Box {
+                    x: 10,
+                    y: 10,
+                    width: 20,
+                    height: 20
+                },
+                Annotation::Arrow {
+                    from: (5, 5),
+                    to: (40, 30)
+                },
+            ]
+        );
+
+        assert!(parse_annotations("").is_err());
+        assert!(parse_annotations("circle 1,2,3").is_err());
+        assert!(parse_annotations("box 1,2,3").is_err());
+        assert!(parse_annotations("crop -1,0,5,5").is_err());
+    }
+
+    #[test]
+    fn test_apply_annotations_crops_then_marks() {
+        let image = RgbaImage::from_pixel(100, 60, Rgba([255, 255, 255, 255]));
+        let annotated = apply_annotations(
+            image,
+            &[
+                Annotation::Crop {
+                    x: 10,
+                    y: 10,
+                    width: 50,
+                    height: 40,
+                },
+                Annotation::Box {
+                    x: 5,
+                    y: 5,
+                    width: 20,
+                    height: 10,
+                },
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(annotated.dimensions(), (50, 40));
+        assert_eq!(*annotated.get_pixel(5, 5), MARK_COLOR);
+        assert_eq!(*annotated.get_pixel(15, 10), Rgba([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn test_apply_annotations_rejects_crop_outside_image() {
+        let image = RgbaImage::new(20, 20);
+        let err = apply_annotations(
+            image,
+            &[Annotation::Crop {
+                x: 10,
+                y: 0,
+                width: 20,
+                height: 5,
+            }],
+        )
+        .unwrap_err();
+        assert!(matches!(err, AnnotateError::OutOfBounds(_)));
+    }
+}
@@ -0,0 +1,162 @@
+//! Workspaces of other repositories linked to a session, for tasks that span
+//! several repos (e.g. an API and its client). The agent runs in the primary
+//! workspace and is given the linked paths; git state is tracked per workspace.
+
+use std::path::{Path, PathBuf};
+
+use uuid::Uuid;
+
+use crate::git::{GitDiffStats, PrState, PrStatus};
+
+/// A workspace linked to a session in addition to its own
+#[derive(Debug, Clone)]
+pub struct LinkedWorkspace {
+    pub id: Uuid,
+    /// Workspace name
+    pub name: String,
+    /// Repository the workspace belongs to
+    pub repo_name: Option<String>,
+    pub path: PathBuf,
+    /// Latest branch reported by the git tracker
+    pub branch: Option<String>,
+    /// Latest diff stats reported by the git tracker
+    pub git_stats: Option<GitDiffStats>,
+    /// Latest PR reported by the git tracker
+    pub pr: Option<PrStatus>,
+}
+
+impl LinkedWorkspace {
+    pub fn new(id: Uuid, name: String, repo_name: Option<String>, path: PathBuf) -> Self {
+        Self {
+            id,
+            name,
+            repo_name,
+            path,
+            branch: None,
+            git_stats: None,
+            pr: None,
+        }
+    }
+
+    /// Repository name, falling back to the workspace name
+    pub fn display_name(&self) -> &str {
+        self.repo_name.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Whether `query` names this workspace or its repository
+    pub fn matches(&self, query: &str) -> bool {
+        self.name.eq_ignore_ascii_case(query)
+            || self
+                .repo_name
+                .as_deref()
+                .is_some_and(|repo| repo.eq_ignore_ascii_case(query))
+    }
+
+    /// One-line summary of the workspace's git state for `/link`
+    pub fn summary(&self) -> String {
+        let mut parts = vec![format!("{} ({})", self.display_name(), self.name)];
+        if let Some(branch) = &self.branch {
+            parts.push(branch.clone());
+        }
+        match &self.git_stats {
+            Some(stats) if stats.has_changes() => parts.push(format!(
+                "+{} -{} in {} file(s)",
+                stats.additions, stats.deletions, stats.files_changed
+            )),
+            Some(_) => parts.push("no changes".to_string()),
+            None => {}
+        }
+        if let Some(pr) = self.pr.as_ref().filter(|pr| pr.exists) {
+            if let Some(number) = pr.number {
+                parts.push(match pr_state_label(pr.state) {
+                    Some(state) => format!("PR #{number} {state}"),
+                    None => format!("PR #{number}"),
+                });
+            }
+        }
+        format!("{} — {}", parts.join(" · "), self.path.display())
+    }
+}
+
+/// Layout note appended to the agent's system prompt so it knows where the
+/// linked repositories live
+pub fn layout_note(working_dir: &Path, linked: &[LinkedWorkspace]) -> Option<String> {
+    if linked.is_empty() {
+        return None;
+    }
+    let repos: Vec<String> = linked
+        .iter()
+        .map(|workspace| {
+            format!(
+                "- {}: {}",
+                workspace.display_name(),
+                workspace.path.display()
+            )
+        })
+        .collect();
+    Some(format!(
+        "This task spans several repositories. Your working directory is {}. \
+These linked repositories are part of the task; read and edit them by absolute path, \
+and run git commands from the repository they apply to:\n{}",
+        working_dir.display(),
+        repos.join("\n")
+    ))
+}
+
+fn pr_state_label(state: PrState) -> Option<&'static str> {
+    match state {
+        PrState::Open => Some("open"),
+        PrState::Draft => Some("draft"),
+        PrState::Merged => Some("merged"),
+        PrState::Closed => Some("closed"),
+        PrState::Unknown => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> LinkedWorkspace {
+        LinkedWorkspace::new(
+            Uuid::new_v4(),
+            "orders".to_string(),
+            Some("acme-client".to_string()),
+            PathBuf::from("/work/acme-client/orders"),
+        )
+    }
+
+    #[test]
+    fn test_layout_note_lists_linked_paths() {
+        assert_eq!(layout_note(Path::new("/work/api"), &[]), None);
+
+        let note = layout_note(Path::new("/work/api"), &[client()]).unwrap();
+        assert!(note.contains("Your working directory is /work/api."));
+        assert!(note.ends_with("- acme-client: /work/acme-client/orders"));
+    }
+
+    #[test]
+    fn test_summary_shows_tracked_git_state() {
+        let mut workspace = client();
+        assert!(workspace.matches("ACME-client"));
+        assert!(workspace.matches("orders"));
+        assert!(!workspace.matches("api"));
+
+        workspace.branch = Some("feature/orders".to_string());
+        workspace.git_stats = Some(GitDiffStats {
+            additions: 12,
+            deletions: 3,
+            files_changed: 2,
+        });
+        workspace.pr = Some(PrStatus {
+            exists: true,
+            number: Some(41),
+            state: PrState::Open,
+            ..PrStatus::default()
+        });
+        assert_eq!(
+            workspace.summary(),
+            "acme-client (orders) · feature/orders · +12 -3 in 2 file(s) · PR #41 open — /work/acme-client/orders"
+        );
+    }
+}
@@ -3,22 +3,29 @@ pub mod app;
 pub mod app_prompt;
 pub mod app_queue;
 pub mod app_state;
-pub mod capabilities;
+pub mod clipboard_copy;
+pub mod clipboard_history;
 pub mod clipboard_paste;
 pub mod components;
+pub mod duplicate_prompt;
 pub mod effect;
 pub mod events;
 pub mod file_viewer;
+pub mod frame_diagnostics;
 pub mod git_tracker;
+pub mod image_annotation;
+pub mod linked_workspaces;
 pub mod session;
+pub mod session_report;
 pub mod tab;
 pub mod tab_manager;
 pub mod terminal_guard;
+pub mod transcript_export;
 
+pub use crate::agent::AgentCapabilities;
 pub use action::Action;
 pub use app::App;
 pub use app_state::{AppState, PerformanceMetrics};
-pub use capabilities::AgentCapabilities;
 pub use effect::Effect;
 pub use events::{AppEvent, InputMode};
 pub use file_viewer::FileViewerSession;
@@ -1,22 +1,33 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use serde_json::Value;
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use crate::agent::{
     events::{ContextCompactionEvent, ContextWarningLevel, ContextWindowState, TokenUsageEvent},
     models::ModelRegistry,
-    AgentHandle, AgentInput, AgentMode, AgentType, SessionId, TokenUsage,
+    AgentCapabilities, AgentHandle, AgentInput, AgentMode, AgentType, CodexApprovalPolicy,
+    CodexSandboxMode, FailureKind, SessionId, StderrBuffer, TokenUsage,
 };
+use crate::core::change_guard::ChangeFlag;
+use crate::core::coverage::CoverageTask;
+use crate::core::dep_update::DepUpdateRun;
 use crate::data::{QueuedMessage, QueuedMessageMode};
 use crate::git::PrManager;
-use crate::ui::capabilities::AgentCapabilities;
+use crate::ui::app_prompt::{self, ResponsePreset};
 use crate::ui::components::{
-    ChatView, EventDirection, InlinePromptState, InputBox, ProcessingState, RawEventsView,
-    StatusBar, ThinkingIndicator, TurnSummary,
+    ChatView, DiffViewState, EventDirection, InlinePromptState, InputBox, ProcessingState,
+    RawEventsView, SessionTimeline, StatusBar, ThinkingIndicator, TurnSummary,
 };
+use crate::ui::duplicate_prompt::DuplicateWarning;
+use crate::ui::linked_workspaces::LinkedWorkspace;
+use crate::util::normalize_workspace_path;
+use crate::util::url_fetch::FetchedPage;
 
 /// Represents a single agent session (one tab)
 pub struct AgentSession {
@@ -30,6 +41,22 @@ pub struct AgentSession {
     pub last_mode_prompt: Option<AgentMode>,
     /// Selected model for this session
     pub model: Option<String>,
+    /// Codex sandbox mode override (None = environment/default)
+    pub codex_sandbox: Option<CodexSandboxMode>,
+    /// Codex approval policy override (None = environment/default)
+    pub codex_approval: Option<CodexApprovalPolicy>,
+    /// Edit review override for Claude (None = `[review] edits`)
+    pub edit_review: Option<bool>,
+    /// Custom base instructions appended to the agent's system prompt
+    pub system_prompt: Option<String>,
+    /// Language appended as a directive to every prompt (shown as a header badge)
+    pub response_language: Option<String>,
+    /// Response style preset appended to every prompt (shown in the header badge)
+    pub response_preset: Option<ResponsePreset>,
+    /// Pages attached with `/fetch`, sent along with the next prompt
+    pub pending_context: Vec<FetchedPage>,
+    /// Billing tag overriding the repository's cost center (`:cost-center`)
+    pub cost_center: Option<String>,
     /// Whether the selected model is invalid and needs re-selection
     pub model_invalid: bool,
     /// Associated workspace ID (for project context)
@@ -40,10 +67,25 @@ pub struct AgentSession {
     pub project_name: Option<String>,
     /// Workspace name (for display in tab)
     pub workspace_name: Option<String>,
+    /// Workspaces of other repositories this session also works in
+    pub linked_workspaces: Vec<LinkedWorkspace>,
+    /// Dependency update run started with `/deps`
+    pub dep_update: Option<DepUpdateRun>,
+    /// Cancels the dependency update run's verification commands
+    pub dep_verify_cancel: Option<CancellationToken>,
+    /// Coverage gap task started with `/coverage`
+    pub coverage_task: Option<CoverageTask>,
+    /// Changed files flagged by the last PR preflight
+    pub change_flags: Vec<ChangeFlag>,
+    /// Keys of flagged changes the user accepted for a PR
+    pub acknowledged_change_flags: HashSet<String>,
     /// Session ID to resume on next prompt (set when restoring from saved state)
     pub resume_session_id: Option<SessionId>,
     /// Chat view component
     pub chat_view: ChatView,
+    /// Worktree tree id captured before each prompt, keyed by the prompt's
+    /// position among the chat's user messages
+    pub prompt_snapshots: HashMap<usize, String>,
     /// Raw events view (debug)
     pub raw_events_view: RawEventsView,
     /// Input box component
@@ -54,6 +96,10 @@ pub struct AgentSession {
     pub thinking_indicator: ThinkingIndicator,
     /// Current turn summary (built during processing)
     pub current_turn_summary: TurnSummary,
+    /// Per-turn model/tool timings for the timeline view
+    pub timeline: SessionTimeline,
+    /// File edits shown in the diff view (loaded when it opens)
+    pub diff_view: DiffViewState,
     /// Handle to the running agent process (if any)
     pub agent_handle: Option<AgentHandle>,
     /// Agent session ID (from the agent itself)
@@ -62,24 +108,46 @@ pub struct AgentSession {
     pub is_processing: bool,
     /// Accumulated token usage
     pub total_usage: TokenUsage,
+    /// Estimated USD spend, priced per turn at the model in use for that turn
+    pub total_cost: f64,
     /// Turn count
     pub turn_count: u32,
     /// PR number if current branch has an open PR
     pub pr_number: Option<u32>,
     /// Whether this tab has unread content (new messages arrived while not focused)
     pub needs_attention: bool,
+    /// A turn failed while this tab wasn't active, and it hasn't been viewed since
+    pub turn_failed_unseen: bool,
     /// PID of the running agent subprocess (for interrupt/kill)
     pub agent_pid: Option<u32>,
     /// Best-effort PID start time to reduce kill reuse risk (platform-dependent)
     pub agent_pid_start_time: Option<u64>,
     /// Optional input channel for streaming stdin payloads
     pub agent_input_tx: Option<mpsc::Sender<AgentInput>>,
+    /// Stops an in-process agent, which has no PID to signal
+    pub agent_cancel: Option<CancellationToken>,
     /// Pending user message that hasn't been confirmed by agent yet
     pub pending_user_message: Option<String>,
+    /// Last visible prompt sent to the agent, kept so a crashed turn can be resent
+    pub last_prompt: Option<QueuedMessage>,
+    /// Prompt held back because it repeats an earlier one in this session
+    pub duplicate_warning: Option<DuplicateWarning>,
+    /// The agent process exited mid-turn; `restart_agent` resends `last_prompt`
+    pub agent_crashed: bool,
+    /// When the running turn last produced an agent event
+    pub last_agent_event_at: Option<Instant>,
+    /// The running turn was flagged as possibly stalled; cleared by the next event
+    pub stall_notified: bool,
+    /// Classified failure of the last turn; `apply_failure_remedy` acts on it
+    pub failure: Option<FailureKind>,
     /// Queued messages waiting to be delivered
     pub queued_messages: Vec<QueuedMessage>,
     /// Selected queued message index (for inline queue editing)
     pub queue_selection: Option<usize>,
+    /// Queued messages are held back until a concurrent-agent slot frees
+    pub waiting_for_slot: bool,
+    /// Cancels the workspace bootstrap while it runs; prompts queue meanwhile
+    pub bootstrap_cancel: Option<CancellationToken>,
     /// Agent capability flags
     pub capabilities: AgentCapabilities,
     /// Context window tracking state
@@ -88,6 +156,11 @@ pub struct AgentSession {
     pub pending_context_warning: Option<ContextWarning>,
     /// Fork seed ID (if this tab was created via fork)
     pub fork_seed_id: Option<Uuid>,
+    /// Hash of the seed prompt this tab was started from
+    pub fork_seed_hash: Option<String>,
+    /// Seed prompt built from this tab's transcript, reused by repeated
+    /// forks until the next turn starts
+    pub fork_seed_cache: Option<ForkSeedCache>,
     /// Whether the fork welcome message has been shown (one-shot)
     pub fork_welcome_shown: bool,
     /// Suppress the next assistant reply (used for fork seed ack)
@@ -96,18 +169,44 @@ pub struct AgentSession {
     pub suppress_next_turn_summary: bool,
     /// AI-generated session title/description (set after first message)
     pub title: Option<String>,
+    /// Name the user gave the tab; overrides the tab name and title
+    pub custom_title: Option<String>,
     /// Whether title generation is currently in flight (prevents duplicate calls)
     pub title_generation_pending: bool,
     /// Turn summary buffered until stream end
     pub pending_turn_summary: Option<TurnSummary>,
     /// Number of tools currently in flight for this turn
     pub tools_in_flight: usize,
+    /// Shell commands still running this turn: tool ID to index in the turn summary
+    pub running_commands: HashMap<String, usize>,
     /// Active inline prompt (AskUserQuestion or ExitPlanMode)
     pub inline_prompt: Option<InlinePromptState>,
     /// Pending permission prompt requests keyed by tool use ID
     pub pending_tool_permissions: HashMap<String, String>,
     /// Pending control responses waiting for a permission request
     pub pending_tool_permission_responses: HashMap<String, serde_json::Value>,
+    /// Recent stderr lines from this session's agent processes
+    pub stderr: StderrBuffer,
+    /// Chat history of a restored tab, loaded the first time the tab is shown
+    pub deferred_history: Option<DeferredHistory>,
+    /// Seed prompt of a session imported from a bundle, sent to a fresh agent
+    /// the first time the tab is shown
+    pub pending_seed_path: Option<PathBuf>,
+}
+
+/// What a restored tab still needs once its history is loaded
+#[derive(Debug, Clone, Default)]
+pub struct DeferredHistory {
+    /// Prompt that was awaiting the agent when the tab was saved
+    pub pending_user_message: Option<String>,
+}
+
+/// Fork seed prompt built for a transcript of `message_count` messages
+#[derive(Debug, Clone)]
+pub struct ForkSeedCache {
+    pub message_count: usize,
+    pub seed_prompt: Arc<str>,
+    pub hash: String,
 }
 
 /// Context warning notification
@@ -126,45 +225,80 @@ impl AgentSession {
             agent_mode: AgentMode::default(),
             last_mode_prompt: None,
             model: None,
+            codex_sandbox: None,
+            codex_approval: None,
+            edit_review: None,
+            system_prompt: None,
+            response_language: None,
+            response_preset: None,
+            pending_context: Vec::new(),
+            cost_center: None,
             model_invalid: false,
             workspace_id: None,
             working_dir: None,
             project_name: None,
             workspace_name: None,
+            linked_workspaces: Vec::new(),
+            dep_update: None,
+            dep_verify_cancel: None,
+            coverage_task: None,
+            change_flags: Vec::new(),
+            acknowledged_change_flags: HashSet::new(),
             resume_session_id: None,
             chat_view: ChatView::new(),
+            prompt_snapshots: HashMap::new(),
             raw_events_view: RawEventsView::new(),
             input_box: InputBox::new(),
             status_bar: StatusBar::new(agent_type),
             thinking_indicator: ThinkingIndicator::new(),
             current_turn_summary: TurnSummary::new(),
+            timeline: SessionTimeline::new(),
+            diff_view: DiffViewState::new(),
             agent_handle: None,
             agent_session_id: None,
             is_processing: false,
             total_usage: TokenUsage::default(),
+            total_cost: 0.0,
             turn_count: 0,
             pr_number: None,
             needs_attention: false,
+            turn_failed_unseen: false,
             agent_pid: None,
             agent_pid_start_time: None,
             agent_input_tx: None,
+            agent_cancel: None,
             pending_user_message: None,
+            last_prompt: None,
+            duplicate_warning: None,
+            agent_crashed: false,
+            last_agent_event_at: None,
+            stall_notified: false,
+            failure: None,
             context_state: ContextWindowState::new(default_context),
             pending_context_warning: None,
             queued_messages: Vec::new(),
+            waiting_for_slot: false,
+            bootstrap_cancel: None,
             queue_selection: None,
             capabilities: AgentCapabilities::for_agent(agent_type),
             fork_seed_id: None,
+            fork_seed_hash: None,
+            fork_seed_cache: None,
             fork_welcome_shown: false,
             suppress_next_assistant_reply: false,
             suppress_next_turn_summary: false,
             title: None,
+            custom_title: None,
             title_generation_pending: false,
             pending_turn_summary: None,
             tools_in_flight: 0,
             inline_prompt: None,
+            running_commands: HashMap::new(),
             pending_tool_permissions: HashMap::new(),
             pending_tool_permission_responses: HashMap::new(),
+            stderr: StderrBuffer::default(),
+            deferred_history: None,
+            pending_seed_path: None,
         };
         session.update_status();
         session
@@ -180,6 +314,9 @@ impl AgentSession {
 
     /// Get display name for the tab
     pub fn tab_name(&self) -> String {
+        if let Some(custom_title) = &self.custom_title {
+            return custom_title.clone();
+        }
         // Use project_name and workspace_name if available
         match (&self.project_name, &self.workspace_name) {
             (Some(project), Some(workspace)) => {
@@ -204,6 +341,11 @@ impl AgentSession {
         }
     }
 
+    /// Title for the session header: the user's name, else the generated one
+    pub fn display_title(&self) -> Option<&str> {
+        self.custom_title.as_deref().or(self.title.as_deref())
+    }
+
     /// Update status bar with current state
     pub fn update_status(&mut self) {
         self.status_bar.set_agent_type(self.agent_type);
@@ -214,9 +356,13 @@ impl AgentSession {
         self.status_bar
             .set_session_id(self.agent_session_id.clone());
         self.status_bar.set_token_usage(self.total_usage.clone());
+        self.status_bar.set_session_cost(self.total_cost);
         self.status_bar
             .set_context_state(self.context_state.clone());
         self.status_bar.set_queue_count(self.queued_messages.len());
+        self.status_bar.set_waiting_for_slot(self.waiting_for_slot);
+        self.status_bar
+            .set_bootstrapping(self.bootstrap_cancel.is_some());
         self.status_bar
             .set_supports_plan_mode(self.capabilities.supports_plan_mode);
 
@@ -266,12 +412,18 @@ impl AgentSession {
         // Update turn summary with this turn's tokens
         self.current_turn_summary.input_tokens = usage.input_tokens.max(0) as u64;
         self.current_turn_summary.output_tokens = usage.output_tokens.max(0) as u64;
+        self.current_turn_summary.cache_read_tokens = usage.cached_tokens.max(0) as u64;
+        self.current_turn_summary.cache_write_tokens = usage.cache_creation_tokens.max(0) as u64;
 
         // Accumulate total usage
         self.total_usage.input_tokens += usage.input_tokens;
         self.total_usage.output_tokens += usage.output_tokens;
         self.total_usage.cached_tokens += usage.cached_tokens;
+        self.total_usage.cache_creation_tokens += usage.cache_creation_tokens;
         self.total_usage.total_tokens += usage.total_tokens;
+        if let Some(cost) = ModelRegistry::cost(self.agent_type, self.model.as_deref(), &usage) {
+            self.total_cost += cost;
+        }
         self.turn_count += 1;
         self.update_status();
     }
@@ -279,33 +431,108 @@ impl AgentSession {
     /// Start processing (resets thinking indicator and turn summary)
     pub fn start_processing(&mut self) {
         self.is_processing = true;
+        self.fork_seed_cache = None;
         self.thinking_indicator.reset();
         self.current_turn_summary = TurnSummary::new();
         self.pending_turn_summary = None;
         self.tools_in_flight = 0;
+        self.running_commands.clear();
+        self.timeline.start_turn(Instant::now());
+        self.note_agent_activity();
         self.update_status();
     }
 
+    /// Record that the agent just produced output, so the turn isn't stalled
+    pub fn note_agent_activity(&mut self) {
+        self.last_agent_event_at = Some(Instant::now());
+        self.stall_notified = false;
+    }
+
+    /// Whether the running turn has been quiet for at least `threshold` while
+    /// its process is alive and no tool is running (tools may legitimately
+    /// run silently for a long time)
+    pub fn is_stalled(&self, now: Instant, threshold: Duration) -> bool {
+        self.is_processing
+            && self.agent_pid.is_some()
+            && self.tools_in_flight == 0
+            && self
+                .last_agent_event_at
+                .is_some_and(|last| now.saturating_duration_since(last) >= threshold)
+    }
+
+    /// Seed prompt for forking this session, rebuilt only when the
+    /// transcript changed since the last fork
+    pub fn fork_seed(&mut self) -> ForkSeedCache {
+        let message_count = self.chat_view.len();
+        if let Some(cache) = self
+            .fork_seed_cache
+            .as_ref()
+            .filter(|cache| cache.message_count == message_count)
+        {
+            return cache.clone();
+        }
+        let seed_prompt = app_prompt::build_fork_seed_prompt(self.chat_view.messages());
+        let cache = ForkSeedCache {
+            message_count,
+            hash: app_prompt::compute_seed_prompt_hash(&seed_prompt),
+            seed_prompt: Arc::from(seed_prompt),
+        };
+        self.fork_seed_cache = Some(cache.clone());
+        cache
+    }
+
     /// Stop processing and finalize turn summary
     pub fn stop_processing(&mut self) {
         self.is_processing = false;
+        self.timeline.finish_turn(Instant::now());
         // Finalize the turn summary with duration and tokens
         let duration = self.thinking_indicator.elapsed();
         self.current_turn_summary.duration_secs = duration.as_secs();
         self.update_status();
     }
 
-    /// Record a file change for the current turn
-    pub fn record_file_change(
-        &mut self,
-        filename: impl Into<String>,
-        additions: usize,
-        deletions: usize,
-    ) {
+    /// Record a file change for the current turn, keyed by its path relative
+    /// to the workspace so differently spelled paths count once
+    pub fn record_file_change(&mut self, path: &str, additions: usize, deletions: usize) {
+        let filename = normalize_workspace_path(path, self.working_dir.as_deref());
         self.current_turn_summary
             .add_file(filename, additions, deletions);
     }
 
+    /// Record a shell command the agent started this turn
+    pub fn record_command_started(&mut self, tool_id: &str, command: &str) {
+        let index = self.current_turn_summary.start_command(command);
+        self.running_commands.insert(tool_id.to_string(), index);
+    }
+
+    /// Record the result of a command started under `tool_id`; other tools are ignored
+    pub fn record_command_finished(&mut self, tool_id: &str, succeeded: bool, output: &str) {
+        if let Some(index) = self.running_commands.remove(tool_id) {
+            self.current_turn_summary
+                .finish_command(index, Some(succeeded), None, output);
+        }
+    }
+
+    /// Record the result of a command known only by its text, as reported with
+    /// an exit code by agents whose command output carries no tool ID
+    pub fn record_command_output(&mut self, command: &str, exit_code: Option<i32>, output: &str) {
+        let succeeded = exit_code.map(|code| code == 0);
+        let running = self.running_commands.iter().find_map(|(tool_id, &index)| {
+            let run = self.current_turn_summary.commands.get(index)?;
+            (run.command == command).then(|| (tool_id.clone(), index))
+        });
+        match running {
+            Some((tool_id, index)) => {
+                self.running_commands.remove(&tool_id);
+                self.current_turn_summary
+                    .finish_command(index, succeeded, exit_code, output);
+            }
+            None => self
+                .current_turn_summary
+                .add_command(command, succeeded, exit_code, output),
+        }
+    }
+
     /// Add tokens to the thinking indicator
     pub fn add_streaming_tokens(&mut self, count: usize) {
         self.thinking_indicator.add_tokens(count);
@@ -500,6 +727,46 @@ impl AgentSession {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ui::components::{ChatMessage, VerifyOutcome};
+
+    #[test]
+    fn test_fork_seed_is_reused_until_transcript_changes() {
+        let mut session = AgentSession::new(AgentType::Claude);
+        session.chat_view.push(ChatMessage::user("hello"));
+
+        let first = session.fork_seed();
+        let second = session.fork_seed();
+        assert!(Arc::ptr_eq(&first.seed_prompt, &second.seed_prompt));
+        assert_eq!(first.hash, second.hash);
+
+        session.chat_view.push(ChatMessage::assistant("hi"));
+        let third = session.fork_seed();
+        assert_eq!(third.message_count, 2);
+        assert_ne!(third.hash, first.hash);
+
+        session.start_processing();
+        assert!(session.fork_seed_cache.is_none());
+    }
+
+    #[test]
+    fn test_quiet_turn_is_stalled_only_with_live_process_and_no_tools() {
+        let threshold = Duration::from_secs(60);
+        let mut session = AgentSession::new(AgentType::Claude);
+        session.start_processing();
+        let later = Instant::now() + threshold;
+        assert!(!session.is_stalled(later, threshold));
+
+        session.agent_pid = Some(4242);
+        assert!(session.is_stalled(later, threshold));
+        assert!(!session.is_stalled(later - Duration::from_secs(1), threshold));
+
+        session.tools_in_flight = 1;
+        assert!(!session.is_stalled(later, threshold));
+        session.tools_in_flight = 0;
+
+        session.stop_processing();
+        assert!(!session.is_stalled(later, threshold));
+    }
 
     #[test]
     fn test_set_agent_and_model_updates_capabilities() {
@@ -570,4 +837,85 @@ mod tests {
         assert!(session.capabilities.supports_plan_mode);
         assert_eq!(session.agent_type, AgentType::Claude);
     }
+
+    #[test]
+    fn test_gemini_session_capabilities_disable_images_and_resume() {
+        let mut session = AgentSession::new(AgentType::Gemini);
+
+        assert!(!session.capabilities.supports_images);
+        assert!(!session.capabilities.supports_resume);
+
+        session.set_agent_and_model(AgentType::Claude, None);
+        assert!(session.capabilities.supports_images);
+        assert!(session.capabilities.supports_resume);
+    }
+
+    #[test]
+    fn test_add_usage_prices_each_turn_at_current_model() {
+        let mut session = AgentSession::new(AgentType::Claude);
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 0,
+            cached_tokens: 0,
+            cache_creation_tokens: 0,
+            total_tokens: 1_000_000,
+        };
+
+        session.model = Some("opus".to_string());
+        session.add_usage(usage.clone());
+        session.model = Some("sonnet".to_string());
+        session.add_usage(usage);
+
+        assert!((session.total_cost - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_record_file_change_counts_each_file_once() {
+        let mut session = AgentSession::new(AgentType::Claude);
+        session.working_dir = Some(PathBuf::from("/work/repo"));
+
+        session.record_file_change("/work/repo/src/main.rs", 5, 2);
+        session.record_file_change("./src/main.rs", 1, 0);
+        session.record_file_change("src\\main.rs", 0, 0);
+        session.record_file_change("README.md", 3, 1);
+
+        let files: Vec<(&str, usize, usize)> = session
+            .current_turn_summary
+            .files_changed
+            .iter()
+            .map(|f| (f.filename.as_str(), f.additions, f.deletions))
+            .collect();
+        assert_eq!(files, vec![("src/main.rs", 6, 2), ("README.md", 3, 1)]);
+    }
+
+    #[test]
+    fn test_recorded_commands_finish_by_tool_id_or_text() {
+        let mut session = AgentSession::new(AgentType::Codex);
+        session.start_processing();
+
+        session.record_command_started("call-1", "cargo test");
+        session.record_command_started("call-2", "git status");
+        session.record_command_output("cargo test", Some(0), "test result: ok");
+        session.record_command_finished("call-2", false, "fatal: not a git repository");
+        session.record_command_finished("call-3", true, "not a shell command");
+
+        let runs: Vec<_> = session
+            .current_turn_summary
+            .commands
+            .iter()
+            .map(|run| (run.command.as_str(), run.exit_code, run.succeeded))
+            .collect();
+        assert_eq!(
+            runs,
+            vec![
+                ("cargo test", Some(0), Some(true)),
+                ("git status", None, Some(false)),
+            ]
+        );
+        assert!(session.running_commands.is_empty());
+        assert_eq!(
+            session.current_turn_summary.verification(),
+            Some(VerifyOutcome::Passed)
+        );
+    }
 }
@@ -0,0 +1,113 @@
+//! Final session reports, built from a session's transcript when its tab is
+//! closed or its workspace archived.
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::data::{SessionReport, SessionTab};
+use crate::ui::components::{ChatMessage, MessageRole, VerifyOutcome};
+
+/// Report from a session's transcript: prompts sent, files changed and the
+/// outcome of the last check recorded in a turn summary
+pub fn build_session_report(
+    session_id: Uuid,
+    title: Option<String>,
+    total_cost_usd: f64,
+    pr_number: Option<i32>,
+    messages: &[ChatMessage],
+) -> SessionReport {
+    let mut report = SessionReport {
+        session_id,
+        title,
+        total_cost_usd,
+        turns: 0,
+        files_changed: Vec::new(),
+        pr_number,
+        pr_url: None,
+        checks_passed: None,
+        created_at: Utc::now(),
+    };
+    for message in messages {
+        match message.role {
+            MessageRole::User => report.turns += 1,
+            MessageRole::Summary => {
+                let Some(summary) = message.summary.as_ref() else {
+                    continue;
+                };
+                for file in &summary.files_changed {
+                    if !report.files_changed.contains(&file.filename) {
+                        report.files_changed.push(file.filename.clone());
+                    }
+                }
+                if let Some(outcome) = summary.verification() {
+                    report.checks_passed = Some(outcome == VerifyOutcome::Passed);
+                }
+            }
+            _ => {}
+        }
+    }
+    report
+}
+
+/// Sidebar suffix for a saved session: its location, then the report digest
+pub fn report_suffix(location: Option<String>, report: Option<&SessionReport>) -> Option<String> {
+    match (location, report) {
+        (Some(location), Some(report)) => Some(format!("{location} · {}", report.headline())),
+        (None, Some(report)) => Some(report.headline()),
+        (location, None) => location,
+    }
+}
+
+/// Report for a saved tab, e.g. one closed from the web UI
+pub fn build_tab_report(tab: &SessionTab, messages: &[ChatMessage]) -> SessionReport {
+    build_session_report(
+        tab.id,
+        tab.custom_title.clone().or_else(|| tab.title.clone()),
+        tab.total_cost_usd,
+        tab.pr_number,
+        messages,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::AgentType;
+    use crate::ui::components::TurnSummary;
+
+    #[test]
+    fn test_report_counts_turns_files_and_last_check() {
+        let mut tab = SessionTab::new(0, AgentType::Claude, None, None, None, Some(7));
+        tab.title = Some("Generated".to_string());
+        tab.custom_title = Some("Login fix".to_string());
+        tab.total_cost_usd = 0.5;
+
+        let mut first = TurnSummary::new();
+        first.add_file("src/auth.rs", 3, 1);
+        first.add_command("cargo test", Some(false), Some(101), "");
+        let mut second = TurnSummary::new();
+        second.add_file("src/auth.rs", 1, 0);
+        second.add_file("tests/login.rs", 4, 0);
+        second.add_command("cargo test", Some(true), Some(0), "");
+        let messages = vec![
+            ChatMessage::user("fix login"),
+            ChatMessage::turn_summary(first),
+            ChatMessage::user("tests still fail"),
+            ChatMessage::turn_summary(second),
+        ];
+
+        let report = build_tab_report(&tab, &messages);
+        assert_eq!(report.title.as_deref(), Some("Login fix"));
+        assert_eq!(report.turns, 2);
+        assert_eq!(report.files_changed, vec!["src/auth.rs", "tests/login.rs"]);
+        assert_eq!(report.checks_passed, Some(true));
+        assert_eq!(
+            report.headline(),
+            "2 turns · $0.50 · 2 files · PR #7 · checks passed"
+        );
+        assert_eq!(
+            report_suffix(Some("main".to_string()), Some(&report)).as_deref(),
+            Some("main · 2 turns · $0.50 · 2 files · PR #7 · checks passed")
+        );
+    }
+}
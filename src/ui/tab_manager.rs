@@ -95,9 +95,10 @@ impl TabManager {
     pub fn switch_to(&mut self, index: usize) -> bool {
         if index < self.tabs.len() {
             self.active_tab = index;
-            // Clear needs_attention flag when switching to an agent tab
+            // Clear unread and failure indicators when switching to an agent tab
             if let Some(Tab::Agent(session)) = self.tabs.get_mut(index) {
                 session.needs_attention = false;
+                session.turn_failed_unseen = false;
             }
             true
         } else {
@@ -109,9 +110,10 @@ impl TabManager {
     pub fn next_tab(&mut self) {
         if !self.tabs.is_empty() {
             self.active_tab = (self.active_tab + 1) % self.tabs.len();
-            // Clear needs_attention flag when switching to an agent tab
+            // Clear unread and failure indicators when switching to an agent tab
             if let Some(Tab::Agent(session)) = self.tabs.get_mut(self.active_tab) {
                 session.needs_attention = false;
+                session.turn_failed_unseen = false;
             }
         }
     }
@@ -124,9 +126,10 @@ impl TabManager {
             } else {
                 self.active_tab - 1
             };
-            // Clear needs_attention flag when switching to an agent tab
+            // Clear unread and failure indicators when switching to an agent tab
             if let Some(Tab::Agent(session)) = self.tabs.get_mut(self.active_tab) {
                 session.needs_attention = false;
+                session.turn_failed_unseen = false;
             }
         }
     }
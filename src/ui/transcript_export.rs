@@ -0,0 +1,498 @@
+//! Transcript export: renders a session's chat history to Markdown, JSON, or
+//! a standalone HTML document that is printed to PDF when a converter is
+//! installed.
+//!
+//! Chat messages are first turned into a [`Transcript`], a flat list of
+//! messages, tool calls and turn summaries, which each output format renders
+//! through [`TranscriptRenderer`]. A new format is one more renderer.
+
+mod html;
+mod json;
+mod markdown;
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use chrono::{DateTime, Local, Utc};
+
+use crate::ui::components::{ChatMessage, CommandRun, MessageRole, TurnSummary};
+
+pub use html::HtmlRenderer;
+pub use json::JsonRenderer;
+pub use markdown::MarkdownRenderer;
+
+/// File format of a transcript export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptFormat {
+    Markdown,
+    Json,
+    /// HTML, printed to PDF when a converter is available
+    Pdf,
+}
+
+impl TranscriptFormat {
+    pub const ALL: [TranscriptFormat; 3] = [
+        TranscriptFormat::Markdown,
+        TranscriptFormat::Json,
+        TranscriptFormat::Pdf,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TranscriptFormat::Markdown => "md",
+            TranscriptFormat::Json => "json",
+            TranscriptFormat::Pdf => "pdf",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Some(TranscriptFormat::Markdown),
+            "json" => Some(TranscriptFormat::Json),
+            "pdf" | "html" => Some(TranscriptFormat::Pdf),
+            _ => None,
+        }
+    }
+
+    /// Renderer for the file written by this format (HTML for PDF exports)
+    pub fn renderer(self) -> &'static dyn TranscriptRenderer {
+        match self {
+            TranscriptFormat::Markdown => &MarkdownRenderer,
+            TranscriptFormat::Json => &JsonRenderer,
+            TranscriptFormat::Pdf => &HtmlRenderer,
+        }
+    }
+}
+
+/// Renders a [`Transcript`] to one output format.
+pub trait TranscriptRenderer {
+    /// File extension of the rendered output, without the dot
+    fn extension(&self) -> &'static str;
+
+    fn render(&self, transcript: &Transcript<'_>) -> Result<String, String>;
+}
+
+/// Output-independent view of a session's chat history.
+#[derive(Debug, Clone)]
+pub struct Transcript<'a> {
+    pub title: &'a str,
+    pub entries: Vec<TranscriptEntry<'a>>,
+}
+
+/// One message, tool call or turn summary of a [`Transcript`].
+#[derive(Debug, Clone)]
+pub struct TranscriptEntry<'a> {
+    pub role: MessageRole,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub body: EntryBody<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub enum EntryBody<'a> {
+    /// User, assistant, reasoning, system, error or plan text
+    Text(&'a str),
+    ToolCall(ToolCall<'a>),
+    Summary(&'a TurnSummary),
+}
+
+/// A tool invocation with its output.
+#[derive(Debug, Clone)]
+pub struct ToolCall<'a> {
+    pub name: Option<&'a str>,
+    pub args: Option<&'a str>,
+    pub exit_code: Option<i32>,
+    pub output: &'a str,
+    /// Whether the output is a unified diff
+    pub is_diff: bool,
+}
+
+impl<'a> Transcript<'a> {
+    /// Build a transcript from chat messages; summary messages without a
+    /// summary carry nothing to render and are skipped.
+    pub fn from_messages(title: &'a str, messages: &'a [ChatMessage]) -> Self {
+        let entries = messages
+            .iter()
+            .filter_map(|msg| {
+                let body = match msg.role {
+                    MessageRole::Summary => EntryBody::Summary(msg.summary.as_ref()?),
+                    MessageRole::Tool => EntryBody::ToolCall(ToolCall {
+                        name: msg.tool_name.as_deref(),
+                        args: msg.tool_args.as_deref(),
+                        exit_code: msg.exit_code,
+                        output: &msg.content,
+                        is_diff: looks_like_diff(&msg.content),
+                    }),
+                    _ => EntryBody::Text(&msg.content),
+                };
+                Some(TranscriptEntry {
+                    role: msg.role,
+                    timestamp: msg.timestamp,
+                    body,
+                })
+            })
+            .collect();
+        Self { title, entries }
+    }
+}
+
+/// Result of a transcript export.
+#[derive(Debug, Clone)]
+pub struct TranscriptExport {
+    pub format: TranscriptFormat,
+    /// Markdown, JSON, or the HTML intermediate of a PDF (always written)
+    pub path: PathBuf,
+    /// PDF output, if a converter was available
+    pub pdf_path: Option<PathBuf>,
+}
+
+/// External tool used to print HTML to PDF.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PdfConverter {
+    Wkhtmltopdf(PathBuf),
+    /// Chrome/Chromium/Edge in headless mode
+    Chromium(PathBuf),
+}
+
+impl PdfConverter {
+    /// Find the first available converter on PATH.
+    pub fn detect() -> Option<Self> {
+        if let Ok(path) = which::which("wkhtmltopdf") {
+            return Some(Self::Wkhtmltopdf(path));
+        }
+        const CHROMIUM_BINARIES: [&str; 5] = [
+            "chromium",
+            "chromium-browser",
+            "google-chrome",
+            "google-chrome-stable",
+            "microsoft-edge",
+        ];
+        CHROMIUM_BINARIES
+            .iter()
+            .find_map(|name| which::which(name).ok())
+            .map(Self::Chromium)
+    }
+
+    fn command(&self, html_path: &Path, pdf_path: &Path) -> Command {
+        match self {
+            Self::Wkhtmltopdf(bin) => {
+                let mut cmd = Command::new(bin);
+                cmd.arg("--quiet").arg(html_path).arg(pdf_path);
+                cmd
+            }
+            Self::Chromium(bin) => {
+                let mut cmd = Command::new(bin);
+                cmd.arg("--headless")
+                    .arg("--disable-gpu")
+                    .arg("--no-pdf-header-footer")
+                    .arg(format!("--print-to-pdf={}", pdf_path.display()))
+                    .arg(html_path);
+                cmd
+            }
+        }
+    }
+
+    /// Print an HTML file to PDF.
+    pub fn convert(&self, html_path: &Path, pdf_path: &Path) -> Result<(), String> {
+        let output = self
+            .command(html_path, pdf_path)
+            .output()
+            .map_err(|e| format!("Failed to run PDF converter: {e}"))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!(
+                "PDF converter exited with {}: {}",
+                output.status,
+                stderr.trim()
+            ));
+        }
+        if !pdf_path.exists() {
+            return Err("PDF converter did not produce an output file".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Write `messages` to `<out_dir>/<file_stem>.<ext>` in the given format.
+pub fn export_transcript_as(
+    format: TranscriptFormat,
+    title: &str,
+    messages: &[ChatMessage],
+    out_dir: &Path,
+    file_stem: &str,
+) -> Result<TranscriptExport, String> {
+    if format == TranscriptFormat::Pdf {
+        return export_transcript(title, messages, out_dir, file_stem);
+    }
+    let renderer = format.renderer();
+    let contents = renderer.render(&Transcript::from_messages(title, messages))?;
+    std::fs::create_dir_all(out_dir)
+        .map_err(|e| format!("Could not create export directory: {e}"))?;
+
+    let path = out_dir.join(format!("{file_stem}.{}", renderer.extension()));
+    std::fs::write(&path, contents).map_err(|e| format!("Could not write transcript: {e}"))?;
+    Ok(TranscriptExport {
+        format,
+        path,
+        pdf_path: None,
+    })
+}
+
+/// Write `messages` to `<out_dir>/<file_stem>.html` and convert it to PDF when possible.
+///
+/// A missing converter is not an error: the HTML file can still be printed from a browser.
+pub fn export_transcript(
+    title: &str,
+    messages: &[ChatMessage],
+    out_dir: &Path,
+    file_stem: &str,
+) -> Result<TranscriptExport, String> {
+    std::fs::create_dir_all(out_dir)
+        .map_err(|e| format!("Could not create export directory: {e}"))?;
+
+    let html_path = out_dir.join(format!("{file_stem}.html"));
+    std::fs::write(&html_path, render_transcript_html(title, messages))
+        .map_err(|e| format!("Could not write transcript HTML: {e}"))?;
+
+    let pdf_path = match PdfConverter::detect() {
+        Some(converter) => {
+            let pdf_path = out_dir.join(format!("{file_stem}.pdf"));
+            match converter.convert(&html_path, &pdf_path) {
+                Ok(()) => Some(pdf_path),
+                Err(err) => {
+                    tracing::warn!(
+                        error = %err,
+                        converter = ?converter,
+                        "Transcript PDF conversion failed; keeping HTML"
+                    );
+                    None
+                }
+            }
+        }
+        None => {
+            tracing::info!("No PDF converter found (wkhtmltopdf or Chromium); keeping HTML");
+            None
+        }
+    };
+
+    Ok(TranscriptExport {
+        format: TranscriptFormat::Pdf,
+        path: html_path,
+        pdf_path,
+    })
+}
+
+/// Render messages as Markdown, with tool output in fenced blocks.
+pub fn render_transcript_markdown(title: &str, messages: &[ChatMessage]) -> String {
+    MarkdownRenderer.render_markdown(&Transcript::from_messages(title, messages))
+}
+
+/// Render messages as pretty-printed JSON.
+pub fn render_transcript_json(title: &str, messages: &[ChatMessage]) -> Result<String, String> {
+    JsonRenderer.render(&Transcript::from_messages(title, messages))
+}
+
+/// Render messages as a self-contained, print-friendly HTML document.
+pub fn render_transcript_html(title: &str, messages: &[ChatMessage]) -> String {
+    HtmlRenderer.render_html(&Transcript::from_messages(title, messages))
+}
+
+fn looks_like_diff(text: &str) -> bool {
+    text.lines().any(|line| {
+        line.starts_with("@@") || line.starts_with("+++ ") || line.starts_with("diff --git")
+    })
+}
+
+fn role_label(role: MessageRole) -> &'static str {
+    match role {
+        MessageRole::User => "User",
+        MessageRole::Assistant => "Assistant",
+        MessageRole::Reasoning => "Reasoning",
+        MessageRole::Tool => "Tool",
+        MessageRole::System => "System",
+        MessageRole::Error => "Error",
+        MessageRole::Summary => "Turn summary",
+        MessageRole::Plan => "Plan",
+    }
+}
+
+fn summary_line(summary: &TurnSummary) -> String {
+    let mut line = format!(
+        "{}s · {} in / {} out tokens",
+        summary.duration_secs, summary.input_tokens, summary.output_tokens
+    );
+    if let Some(ledger) = summary.format_ledger() {
+        line.push_str(&format!(" · {ledger}"));
+    }
+    line
+}
+
+/// A command as listed under its turn summary, e.g. "cargo test (exit 101)"
+fn command_line(run: &CommandRun) -> String {
+    match (run.exit_code, run.succeeded) {
+        (Some(code), _) => format!("{} (exit {code})", run.command),
+        (None, Some(false)) => format!("{} (failed)", run.command),
+        _ => run.command.clone(),
+    }
+}
+
+/// Local time shown next to messages in Markdown and HTML exports
+fn format_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp
+        .with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::components::FileChange;
+
+    #[test]
+    fn test_render_escapes_and_marks_diff_lines() {
+        let messages = vec![
+            ChatMessage::user("fix <b>"),
+            ChatMessage::tool("Bash", "git diff", "+added\n-removed\n context"),
+        ];
+        let html = render_transcript_html("Session", &messages);
+
+        assert!(html.contains("fix &lt;b&gt;"));
+        assert!(html.contains("<span class=\"add\">+added</span>"));
+        assert!(html.contains("<span class=\"del\">-removed</span>"));
+        assert!(html.contains(" context"));
+    }
+
+    #[test]
+    fn test_render_includes_turn_summary_files() {
+        let mut summary = TurnSummary::new().with_duration(12).with_tokens(100, 50);
+        summary.files_changed.push(FileChange {
+            filename: "src/lib.rs".to_string(),
+            additions: 3,
+            deletions: 1,
+        });
+        let html = render_transcript_html("S", &[ChatMessage::turn_summary(summary)]);
+
+        assert!(html.contains("12s · 100 in / 50 out tokens"));
+        assert!(html.contains("src/lib.rs"));
+    }
+
+    #[test]
+    fn test_markdown_fences_tool_output_and_summaries() {
+        let mut summary = TurnSummary::new().with_duration(3).with_tokens(10, 5);
+        summary.files_changed.push(FileChange {
+            filename: "src/lib.rs".to_string(),
+            additions: 1,
+            deletions: 0,
+        });
+        let messages = vec![
+            ChatMessage::user("fix it"),
+            ChatMessage::tool("Bash", "git diff", "@@ -1 +1 @@\n-a\n+b\n```"),
+            ChatMessage::turn_summary(summary),
+        ];
+        let md = render_transcript_markdown("Session", &messages);
+
+        assert!(md.starts_with("# Session\n"));
+        assert!(md.contains("\n## User\n\nfix it\n"));
+        assert!(md.contains("**Bash** `git diff`\n\n````diff\n@@ -1 +1 @@"));
+        assert!(md.contains("> 3s · 10 in / 5 out tokens\n> - `src/lib.rs` +1 -0\n"));
+    }
+
+    #[test]
+    fn test_json_includes_tool_calls_and_summary() {
+        let messages = vec![
+            ChatMessage::tool("Bash", "ls", "Cargo.toml"),
+            ChatMessage::turn_summary(TurnSummary::new().with_duration(2)),
+        ];
+        let json: serde_json::Value =
+            serde_json::from_str(&render_transcript_json("S", &messages).unwrap()).unwrap();
+
+        assert_eq!(json["title"], "S");
+        assert_eq!(json["messages"][0]["role"], "tool");
+        assert_eq!(json["messages"][0]["tool_name"], "Bash");
+        assert_eq!(json["messages"][0]["content"], "Cargo.toml");
+        assert_eq!(json["messages"][1]["summary"]["duration_secs"], 2);
+    }
+
+    #[test]
+    fn test_exports_include_command_ledger() {
+        let mut summary = TurnSummary::new().with_duration(4);
+        summary.add_command("cargo test", Some(false), Some(101), "");
+        summary.add_command(
+            "git commit -am wip",
+            Some(true),
+            Some(0),
+            "[main abc1234] wip",
+        );
+        let messages = vec![ChatMessage::turn_summary(summary)];
+
+        let md = render_transcript_markdown("S", &messages);
+        assert!(md.contains("· 2 commands · checks failed · commit abc1234\n"));
+        assert!(md.contains("> - `$ cargo test (exit 101)`\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_transcript_json("S", &messages).unwrap()).unwrap();
+        let summary = &json["messages"][0]["summary"];
+        assert_eq!(summary["commands"][0]["command"], "cargo test");
+        assert_eq!(summary["verification"], "failed");
+        assert_eq!(summary["references"][0]["kind"], "commit");
+        assert_eq!(summary["references"][0]["id"], "abc1234");
+    }
+
+    #[test]
+    fn test_transcript_entries_feed_custom_renderers() {
+        struct OrgRenderer;
+        impl TranscriptRenderer for OrgRenderer {
+            fn extension(&self) -> &'static str {
+                "org"
+            }
+
+            fn render(&self, transcript: &Transcript<'_>) -> Result<String, String> {
+                let mut org = format!("* {}\n", transcript.title);
+                for entry in &transcript.entries {
+                    match &entry.body {
+                        EntryBody::Text(content) => {
+                            org.push_str(&format!("** {}\n{content}\n", role_label(entry.role)))
+                        }
+                        EntryBody::ToolCall(call) if call.is_diff => {
+                            org.push_str(&format!("#+begin_src diff\n{}\n#+end_src\n", call.output))
+                        }
+                        EntryBody::ToolCall(_) | EntryBody::Summary(_) => {}
+                    }
+                }
+                Ok(org)
+            }
+        }
+
+        let mut empty_summary = ChatMessage::turn_summary(TurnSummary::new());
+        empty_summary.summary = None;
+        let messages = vec![
+            ChatMessage::user("fix it"),
+            ChatMessage::tool("Bash", "git diff", "@@ -1 +1 @@\n-a\n+b"),
+            ChatMessage::tool("Bash", "ls", "Cargo.toml"),
+            empty_summary,
+        ];
+        let transcript = Transcript::from_messages("Session", &messages);
+        assert_eq!(transcript.entries.len(), 3);
+
+        let org = OrgRenderer.render(&transcript).unwrap();
+        assert_eq!(
+            org,
+            "* Session\n** User\nfix it\n#+begin_src diff\n@@ -1 +1 @@\n-a\n+b\n#+end_src\n"
+        );
+        assert_eq!(TranscriptFormat::Pdf.renderer().extension(), "html");
+        assert_eq!(TranscriptFormat::Markdown.renderer().extension(), "md");
+    }
+
+    #[test]
+    fn test_transcript_format_parse() {
+        assert_eq!(
+            TranscriptFormat::parse("Markdown"),
+            Some(TranscriptFormat::Markdown)
+        );
+        assert_eq!(
+            TranscriptFormat::parse("json"),
+            Some(TranscriptFormat::Json)
+        );
+        assert_eq!(TranscriptFormat::parse("docx"), None);
+    }
+}
@@ -0,0 +1,180 @@
+//! Self-contained, print-friendly HTML transcripts, used for PDF exports and
+//! shared transcript pages.
+
+use super::{
+    command_line, format_timestamp, role_label, summary_line, EntryBody, ToolCall, Transcript,
+    TranscriptEntry, TranscriptRenderer,
+};
+use crate::ui::app_prompt::message_role_name;
+use crate::ui::components::TurnSummary;
+
+/// Renders transcripts as a standalone HTML document.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HtmlRenderer;
+
+const TRANSCRIPT_CSS: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; font-size: 11pt; color: #1f2328; margin: 2em; }
+h1 { font-size: 16pt; border-bottom: 1px solid #d0d7de; padding-bottom: .3em; }
+.msg { margin: 1em 0; page-break-inside: avoid; }
+.role { font-size: 8pt; font-weight: 600; text-transform: uppercase; letter-spacing: .05em; color: #656d76; }
+.user .body { background: #f6f8fa; border-left: 3px solid #0969da; padding: .5em .75em; }
+.plan .body { border-left: 3px solid #bf8700; padding: .25em .75em; }
+.error .body { color: #cf222e; }
+.reasoning .body { color: #656d76; font-style: italic; }
+.body { white-space: pre-wrap; word-wrap: break-word; }
+pre { font-family: "SFMono-Regular", Menlo, Consolas, monospace; font-size: 9pt; background: #f6f8fa; padding: .5em; white-space: pre-wrap; word-wrap: break-word; margin: .25em 0; }
+.tool-header { font-family: monospace; font-size: 9pt; color: #57606a; }
+.time { font-weight: normal; text-transform: none; letter-spacing: 0; color: #8c959f; margin-left: .5em; }
+.add { color: #116329; background: #dafbe1; }
+.del { color: #82071e; background: #ffebe9; }
+.summary { font-size: 9pt; color: #57606a; border-top: 1px dashed #d0d7de; padding-top: .25em; }
+.summary ul { margin: .25em 0; }
+"#;
+
+impl HtmlRenderer {
+    pub fn render_html(&self, transcript: &Transcript<'_>) -> String {
+        let mut html = String::new();
+        let title = escape_html(transcript.title);
+        html.push_str("<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">");
+        html.push_str(&format!(
+            "<title>{title}</title><style>{TRANSCRIPT_CSS}</style>"
+        ));
+        html.push_str(&format!("</head><body>\n<h1>{title}</h1>\n"));
+
+        for entry in &transcript.entries {
+            render_entry(&mut html, entry);
+        }
+
+        html.push_str("</body></html>\n");
+        html
+    }
+}
+
+impl TranscriptRenderer for HtmlRenderer {
+    fn extension(&self) -> &'static str {
+        "html"
+    }
+
+    fn render(&self, transcript: &Transcript<'_>) -> Result<String, String> {
+        Ok(self.render_html(transcript))
+    }
+}
+
+fn render_entry(html: &mut String, entry: &TranscriptEntry<'_>) {
+    let class = message_role_name(entry.role);
+    let time = entry
+        .timestamp
+        .map(|timestamp| {
+            format!(
+                "<span class=\"time\">{}</span>",
+                format_timestamp(timestamp)
+            )
+        })
+        .unwrap_or_default();
+
+    match &entry.body {
+        EntryBody::Summary(summary) => render_summary(html, summary),
+        EntryBody::ToolCall(call) => render_tool_call(html, class, &time, call),
+        EntryBody::Text(content) => {
+            if content.trim().is_empty() {
+                return;
+            }
+            html.push_str(&format!("<div class=\"msg {class}\"><div class=\"role\">{}{time}</div><div class=\"body\">{}</div></div>\n",
+                role_label(entry.role),
+                escape_html(content)
+            ));
+        }
+    }
+}
+
+fn render_tool_call(html: &mut String, class: &str, time: &str, call: &ToolCall<'_>) {
+    let name = call.name.unwrap_or("Tool");
+    html.push_str(&format!(
+        "<div class=\"msg {class}\"><div class=\"tool-header\">{}",
+        escape_html(name)
+    ));
+    if let Some(args) = call.args.filter(|a| !a.is_empty()) {
+        html.push_str(&format!(" {}", escape_html(args)));
+    }
+    if let Some(code) = call.exit_code {
+        html.push_str(&format!(" (exit {code})"));
+    }
+    html.push_str(time);
+    html.push_str("</div>");
+    if !call.output.is_empty() {
+        html.push_str("<pre>");
+        render_diff_aware(html, call.output);
+        html.push_str("</pre>");
+    }
+    html.push_str("</div>\n");
+}
+
+fn render_summary(html: &mut String, summary: &TurnSummary) {
+    html.push_str(&format!(
+        "<div class=\"msg summary\">{}",
+        summary_line(summary)
+    ));
+    if !summary.files_changed.is_empty() {
+        html.push_str("<ul>");
+        for file in &summary.files_changed {
+            html.push_str(&format!(
+                "<li>{} <span class=\"add\">+{}</span> <span class=\"del\">-{}</span></li>",
+                escape_html(&file.filename),
+                file.additions,
+                file.deletions
+            ));
+        }
+        html.push_str("</ul>");
+    }
+    if !summary.commands.is_empty() {
+        html.push_str("<ul>");
+        for run in &summary.commands {
+            html.push_str(&format!(
+                "<li><code>$ {}</code></li>",
+                escape_html(&command_line(run))
+            ));
+        }
+        html.push_str("</ul>");
+    }
+    html.push_str("</div>\n");
+}
+
+/// Escape tool output, highlighting unified-diff added/removed lines.
+fn render_diff_aware(html: &mut String, text: &str) {
+    for (idx, line) in text.lines().enumerate() {
+        if idx > 0 {
+            html.push('\n');
+        }
+        let class = if line.starts_with('+') && !line.starts_with("+++") {
+            Some("add")
+        } else if line.starts_with('-') && !line.starts_with("---") {
+            Some("del")
+        } else {
+            None
+        };
+        match class {
+            Some(class) => {
+                html.push_str(&format!(
+                    "<span class=\"{class}\">{}</span>",
+                    escape_html(line)
+                ));
+            }
+            None => html.push_str(&escape_html(line)),
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
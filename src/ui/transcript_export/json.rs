@@ -0,0 +1,99 @@
+//! JSON transcripts for tooling: every entry with its role, tool call fields
+//! and turn summary.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::{EntryBody, Transcript, TranscriptRenderer};
+use crate::ui::app_prompt::message_role_name;
+use crate::ui::components::{CommandRun, FileChange, TurnReference, VerifyOutcome};
+
+/// Renders transcripts as pretty-printed JSON.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonRenderer;
+
+impl TranscriptRenderer for JsonRenderer {
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn render(&self, transcript: &Transcript<'_>) -> Result<String, String> {
+        let json = JsonTranscript {
+            title: transcript.title,
+            exported_at: Utc::now(),
+            messages: transcript
+                .entries
+                .iter()
+                .map(|entry| {
+                    let mut message = JsonMessage {
+                        role: message_role_name(entry.role),
+                        content: "",
+                        tool_name: None,
+                        tool_args: None,
+                        exit_code: None,
+                        timestamp: entry.timestamp,
+                        summary: None,
+                    };
+                    match &entry.body {
+                        EntryBody::Text(content) => message.content = *content,
+                        EntryBody::ToolCall(call) => {
+                            message.content = call.output;
+                            message.tool_name = call.name;
+                            message.tool_args = call.args;
+                            message.exit_code = call.exit_code;
+                        }
+                        EntryBody::Summary(summary) => {
+                            message.summary = Some(JsonSummary {
+                                duration_secs: summary.duration_secs,
+                                input_tokens: summary.input_tokens,
+                                output_tokens: summary.output_tokens,
+                                files_changed: &summary.files_changed,
+                                commands: &summary.commands,
+                                verification: summary.verification(),
+                                references: &summary.references,
+                            });
+                        }
+                    }
+                    message
+                })
+                .collect(),
+        };
+        serde_json::to_string_pretty(&json)
+            .map_err(|e| format!("Could not serialize transcript: {e}"))
+    }
+}
+
+#[derive(Serialize)]
+struct JsonTranscript<'a> {
+    title: &'a str,
+    exported_at: DateTime<Utc>,
+    messages: Vec<JsonMessage<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonMessage<'a> {
+    role: &'static str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    content: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_args: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exit_code: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<JsonSummary<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonSummary<'a> {
+    duration_secs: u64,
+    input_tokens: u64,
+    output_tokens: u64,
+    files_changed: &'a [FileChange],
+    commands: &'a [CommandRun],
+    verification: Option<VerifyOutcome>,
+    references: &'a [TurnReference],
+}
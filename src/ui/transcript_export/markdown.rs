@@ -0,0 +1,91 @@
+//! Markdown transcripts, with tool output in fenced blocks.
+
+use super::{
+    command_line, format_timestamp, role_label, summary_line, EntryBody, Transcript,
+    TranscriptRenderer,
+};
+
+/// Renders transcripts as Markdown.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarkdownRenderer;
+
+impl MarkdownRenderer {
+    pub fn render_markdown(&self, transcript: &Transcript<'_>) -> String {
+        let mut md = format!("# {}\n", transcript.title.trim());
+        for entry in &transcript.entries {
+            let time = entry
+                .timestamp
+                .map(|timestamp| format!(" · {}", format_timestamp(timestamp)))
+                .unwrap_or_default();
+            match &entry.body {
+                EntryBody::Summary(summary) => {
+                    md.push_str(&format!("\n> {}\n", summary_line(summary)));
+                    for file in &summary.files_changed {
+                        md.push_str(&format!(
+                            "> - `{}` +{} -{}\n",
+                            file.filename, file.additions, file.deletions
+                        ));
+                    }
+                    for run in &summary.commands {
+                        md.push_str(&format!(
+                            "> - `$ {}`\n",
+                            command_line(run).replace('`', "'")
+                        ));
+                    }
+                }
+                EntryBody::ToolCall(call) => {
+                    let name = call.name.unwrap_or("Tool");
+                    md.push_str(&format!("\n**{name}**"));
+                    if let Some(args) = call.args.filter(|a| !a.is_empty()) {
+                        md.push_str(&format!(" `{}`", args.replace('`', "'")));
+                    }
+                    if let Some(code) = call.exit_code {
+                        md.push_str(&format!(" (exit {code})"));
+                    }
+                    md.push_str(&time);
+                    md.push('\n');
+                    if !call.output.is_empty() {
+                        push_fenced(&mut md, call.output, call.is_diff);
+                    }
+                }
+                EntryBody::Text(content) => {
+                    if content.trim().is_empty() {
+                        continue;
+                    }
+                    md.push_str(&format!(
+                        "\n## {}{time}\n\n{}\n",
+                        role_label(entry.role),
+                        content.trim_end()
+                    ));
+                }
+            }
+        }
+        md
+    }
+}
+
+impl TranscriptRenderer for MarkdownRenderer {
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+
+    fn render(&self, transcript: &Transcript<'_>) -> Result<String, String> {
+        Ok(self.render_markdown(transcript))
+    }
+}
+
+/// Fence `text` with more backticks than it contains, tagging diffs for highlighting.
+fn push_fenced(md: &mut String, text: &str, is_diff: bool) {
+    let mut longest_run = 0;
+    let mut run = 0;
+    for ch in text.chars() {
+        run = if ch == '`' { run + 1 } else { 0 };
+        longest_run = longest_run.max(run);
+    }
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    let language = if is_diff { "diff" } else { "" };
+    md.push_str(&format!(
+        "\n{fence}{language}\n{}\n{fence}\n",
+        text.trim_end()
+    ));
+}
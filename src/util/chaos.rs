@@ -0,0 +1,271 @@
+//! Fault injection for resilience testing, enabled by the hidden
+//! `conduit --chaos <SPEC>` flag.
+//!
+//! With chaos on, agent streams randomly report errors and crashes, arrive
+//! late or carry malformed JSON lines, and database commits randomly fail, so
+//! the recovery, retry and error rendering paths run in everyday use. Nothing
+//! is injected unless [`enable`] has been called.
+//!
+//! Every decision draws from one generator seeded by `--chaos-seed` (or a
+//! random seed that is logged), so a run can be repeated with the same seed.
+
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::agent::events::{AgentEvent, ErrorEvent};
+use crate::agent::AGENT_CRASHED_CODE;
+
+/// Longest injected delay unless the spec sets `max_delay_ms`
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(3);
+
+static CHAOS: OnceLock<Chaos> = OnceLock::new();
+
+/// A kind of injected fault
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// An agent reports an error, or crashes
+    AgentError,
+    /// An agent event is held back before delivery
+    Delay,
+    /// An agent output line is cut short before parsing
+    MalformedJson,
+    /// A database commit is rolled back with an error
+    DbFailure,
+}
+
+/// Probability of each fault, per agent event, output line or commit
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChaosConfig {
+    pub agent_error: f64,
+    pub delay: f64,
+    pub malformed_json: f64,
+    pub db_failure: f64,
+    pub max_delay: Duration,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            agent_error: 0.0,
+            delay: 0.0,
+            malformed_json: 0.0,
+            db_failure: 0.0,
+            max_delay: DEFAULT_MAX_DELAY,
+        }
+    }
+}
+
+impl ChaosConfig {
+    /// Parse a spec such as `agent_error=0.02,delay=0.1,max_delay_ms=500`.
+    /// Keys are `agent_error`, `delay`, `malformed_json`, `db` and `all`
+    /// (each a probability from 0 to 1), plus `max_delay_ms`; faults left
+    /// out are never injected.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut config = Self::default();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (key, value) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("expected key=value, got `{entry}`"))?;
+            let (key, value) = (key.trim(), value.trim());
+            if key == "max_delay_ms" {
+                let ms: u64 = value
+                    .parse()
+                    .map_err(|_| format!("max_delay_ms must be a whole number, got `{value}`"))?;
+                config.max_delay = Duration::from_millis(ms);
+                continue;
+            }
+            let probability: f64 = value
+                .parse()
+                .ok()
+                .filter(|p| (0.0..=1.0).contains(p))
+                .ok_or_else(|| format!("{key} must be a probability from 0 to 1, got `{value}`"))?;
+            match key {
+                "agent_error" => config.agent_error = probability,
+                "delay" => config.delay = probability,
+                "malformed_json" => config.malformed_json = probability,
+                "db" => config.db_failure = probability,
+                "all" => {
+                    config.agent_error = probability;
+                    config.delay = probability;
+                    config.malformed_json = probability;
+                    config.db_failure = probability;
+                }
+                _ => {
+                    return Err(format!(
+                        "unknown key `{key}` (expected agent_error, delay, malformed_json, db, all or max_delay_ms)"
+                    ))
+                }
+            }
+        }
+        Ok(config)
+    }
+
+    fn probability(&self, fault: Fault) -> f64 {
+        match fault {
+            Fault::AgentError => self.agent_error,
+            Fault::Delay => self.delay,
+            Fault::MalformedJson => self.malformed_json,
+            Fault::DbFailure => self.db_failure,
+        }
+    }
+}
+
+/// Fault probabilities plus the seeded generator every decision draws from
+struct Chaos {
+    config: ChaosConfig,
+    seed: u64,
+    rng: Mutex<StdRng>,
+}
+
+impl Chaos {
+    fn new(config: ChaosConfig, seed: u64) -> Self {
+        Self {
+            config,
+            seed,
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    fn inject(&self, fault: Fault) -> bool {
+        let probability = self.config.probability(fault);
+        probability > 0.0 && self.rng.lock().unwrap().random_bool(probability)
+    }
+
+    fn delay(&self) -> Option<Duration> {
+        let max = self.config.max_delay;
+        if max.is_zero() || !self.inject(Fault::Delay) {
+            return None;
+        }
+        Some(self.rng.lock().unwrap().random_range(Duration::ZERO..=max))
+    }
+
+    fn crash(&self) -> bool {
+        self.rng.lock().unwrap().random_bool(0.5)
+    }
+}
+
+/// Turn fault injection on for the rest of the process. Without a `seed`
+/// one is picked at random; [`log_settings`] reports it so the run can be
+/// repeated.
+pub fn enable(config: ChaosConfig, seed: Option<u64>) {
+    let seed = seed.unwrap_or_else(rand::random);
+    if CHAOS.set(Chaos::new(config, seed)).is_err() {
+        tracing::debug!("Chaos mode already enabled");
+    }
+}
+
+/// Log the fault probabilities and seed, if chaos is on. Called once the
+/// log subscriber is installed, since [`enable`] runs before it is.
+pub fn log_settings() {
+    if let Some(chaos) = CHAOS.get() {
+        tracing::warn!(
+            config = ?chaos.config,
+            seed = chaos.seed,
+            "Chaos mode enabled; faults will be injected (repeat with --chaos-seed {})",
+            chaos.seed
+        );
+    }
+}
+
+pub fn is_enabled() -> bool {
+    CHAOS.get().is_some()
+}
+
+/// Whether to inject `fault` this time
+pub fn inject(fault: Fault) -> bool {
+    let injected = CHAOS.get().is_some_and(|chaos| chaos.inject(fault));
+    if injected {
+        tracing::info!(?fault, "Chaos: injecting fault");
+    }
+    injected
+}
+
+/// How long to hold back the next agent event, if at all
+pub fn delay() -> Option<Duration> {
+    let delay = CHAOS.get()?.delay()?;
+    tracing::info!(fault = ?Fault::Delay, ?delay, "Chaos: injecting fault");
+    Some(delay)
+}
+
+/// The line cut short, if it should arrive malformed
+pub fn corrupt_line(line: &str) -> Option<String> {
+    if !inject(Fault::MalformedJson) {
+        return None;
+    }
+    Some(truncate_line(line))
+}
+
+/// An error to deliver ahead of the next agent event. Half are crashes,
+/// after which the agent should be stopped as if its process had died.
+pub fn agent_error() -> Option<AgentEvent> {
+    if !inject(Fault::AgentError) {
+        return None;
+    }
+    let event = if CHAOS.get().is_some_and(Chaos::crash) {
+        ErrorEvent {
+            message: "Agent process failed: injected crash (chaos mode)".to_string(),
+            is_fatal: true,
+            code: Some(AGENT_CRASHED_CODE.to_string()),
+            details: None,
+        }
+    } else {
+        ErrorEvent {
+            message: "Injected agent error (chaos mode)".to_string(),
+            is_fatal: false,
+            code: Some("chaos".to_string()),
+            details: None,
+        }
+    };
+    Some(AgentEvent::Error(event))
+}
+
+fn truncate_line(line: &str) -> String {
+    let mut end = line.len() / 2;
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    line[..end].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spec() {
+        let config = ChaosConfig::parse("all=0.1, delay=0.5,max_delay_ms=250").unwrap();
+        assert_eq!(config.agent_error, 0.1);
+        assert_eq!(config.delay, 0.5);
+        assert_eq!(config.db_failure, 0.1);
+        assert_eq!(config.max_delay, Duration::from_millis(250));
+
+        assert_eq!(ChaosConfig::parse("").unwrap(), ChaosConfig::default());
+        assert!(ChaosConfig::parse("db=2").is_err());
+        assert!(ChaosConfig::parse("db").is_err());
+        assert!(ChaosConfig::parse("network=0.1").is_err());
+    }
+
+    #[test]
+    fn test_same_seed_repeats_decisions() {
+        let config = ChaosConfig::parse("all=0.3,max_delay_ms=100").unwrap();
+        let decisions = |seed| {
+            let chaos = Chaos::new(config.clone(), seed);
+            (0..64)
+                .map(|_| (chaos.inject(Fault::DbFailure), chaos.delay(), chaos.crash()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(decisions(7), decisions(7));
+        assert_ne!(decisions(7), decisions(8));
+    }
+
+    #[test]
+    fn test_truncated_line_is_not_json() {
+        let line = r#"{"type":"assistant","text":"héllo wörld"}"#;
+        let truncated = truncate_line(line);
+        assert!(line.starts_with(&truncated));
+        assert!(serde_json::from_str::<serde_json::Value>(&truncated).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+//! Desktop notifications for turns that end in background tabs.
+
+use notify_rust::Notification;
+
+/// Longest error excerpt put in a notification body
+const MAX_BODY_CHARS: usize = 160;
+
+/// Summary and body for a tab whose turn completed or failed with `error`
+pub fn turn_notification(tab_name: &str, error: Option<&str>) -> (String, String) {
+    match error {
+        None => (
+            format!("{tab_name} finished"),
+            "The agent completed its turn".to_string(),
+        ),
+        Some(error) => {
+            let error = error.lines().next().unwrap_or_default().trim();
+            let mut body: String = error.chars().take(MAX_BODY_CHARS).collect();
+            if error.chars().count() > MAX_BODY_CHARS {
+                body.push('…');
+            }
+            (format!("{tab_name} failed"), body)
+        }
+    }
+}
+
+/// Show a desktop notification. Talks to the notification daemon
+/// synchronously, so run it off the UI thread.
+pub fn show(summary: &str, body: &str) -> Result<(), notify_rust::error::Error> {
+    Notification::new()
+        .appname("Conduit")
+        .summary(summary)
+        .body(body)
+        .show()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_body_keeps_first_line_and_truncates() {
+        let (summary, body) = turn_notification("api (fix-login)", None);
+        assert_eq!(summary, "api (fix-login) finished");
+        assert_eq!(body, "The agent completed its turn");
+
+        let error = format!("{}\nstack trace", "x".repeat(200));
+        let (summary, body) = turn_notification("api", Some(&error));
+        assert_eq!(summary, "api failed");
+        assert_eq!(body.chars().count(), MAX_BODY_CHARS + 1);
+        assert!(body.ends_with('…'));
+        assert!(!body.contains("stack trace"));
+    }
+}
@@ -0,0 +1,297 @@
+//! Instance lock for a data directory.
+//!
+//! Only one Conduit process (TUI or `serve`) may own a data directory at a
+//! time: the owner restores tabs and starts agents. A second TUI can attach as
+//! an observer, which shows the same sessions but never starts agents or
+//! writes tab state.
+
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::paths::data_dir;
+
+/// How long to wait for a new owner to record who it is
+const OWNER_RECORD_WAIT: Duration = Duration::from_secs(1);
+
+/// Which kind of process holds the lock
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstanceKind {
+    Tui,
+    Serve,
+}
+
+impl InstanceKind {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            InstanceKind::Tui => "the TUI",
+            InstanceKind::Serve => "conduit serve",
+        }
+    }
+}
+
+/// Process recorded in the lock file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstanceInfo {
+    pub pid: u32,
+    pub kind: InstanceKind,
+    pub started_at: DateTime<Utc>,
+}
+
+impl InstanceInfo {
+    /// One-line description, e.g. "the TUI (pid 4242, started 2026-01-05 09:15)"
+    pub fn describe(&self) -> String {
+        format!(
+            "{} (pid {}, started {})",
+            self.kind.display_name(),
+            self.pid,
+            self.started_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+        )
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum InstanceError {
+    #[error("Conduit is already running: {}", .0.describe())]
+    Held(InstanceInfo),
+    #[error("Failed to write instance lock {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Ownership of the data directory; released when dropped
+#[derive(Debug)]
+pub struct InstanceLock {
+    path: PathBuf,
+    info: InstanceInfo,
+    /// Holds the OS lock; closing it releases the lock
+    _file: File,
+}
+
+impl InstanceLock {
+    pub fn info(&self) -> &InstanceInfo {
+        &self.info
+    }
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        // The lock file stays; only the owner record goes, while still locked
+        let info_path = owner_info_path(&self.path);
+        if let Err(e) = fs::remove_file(&info_path) {
+            if e.kind() != ErrorKind::NotFound {
+                tracing::warn!(
+                    path = %info_path.display(),
+                    error = %e,
+                    "Failed to remove instance owner record"
+                );
+            }
+        }
+    }
+}
+
+/// Get the instance lock file path (<data>/instance.lock)
+pub fn instance_lock_path() -> PathBuf {
+    data_dir().join("instance.lock")
+}
+
+/// Take ownership of the data directory for this process
+pub fn acquire_instance_lock(kind: InstanceKind) -> Result<InstanceLock, InstanceError> {
+    acquire_at(&instance_lock_path(), kind, std::process::id())
+}
+
+/// The live process that owns the data directory, if any
+pub fn instance_owner() -> Option<InstanceInfo> {
+    owner_at(&instance_lock_path())
+}
+
+/// Where the owner records who it is (<data>/instance.json). Kept apart from
+/// the lock file because Windows locks block reads of the locked file.
+fn owner_info_path(lock_path: &Path) -> PathBuf {
+    lock_path.with_extension("json")
+}
+
+fn acquire_at(path: &Path, kind: InstanceKind, pid: u32) -> Result<InstanceLock, InstanceError> {
+    let io_error = |source| InstanceError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(io_error)?;
+    }
+
+    // The OS releases the lock when its holder exits, however it exits, so
+    // a stale lock needs no takeover and two starters can't both win
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .map_err(io_error)?;
+    match file.try_lock() {
+        Ok(()) => {}
+        Err(TryLockError::WouldBlock) => return Err(held_by(path)),
+        Err(TryLockError::Error(e)) => return Err(io_error(e)),
+    }
+
+    let info = InstanceInfo {
+        pid,
+        kind,
+        started_at: Utc::now(),
+    };
+    let contents = serde_json::to_string(&info).map_err(|e| io_error(e.into()))?;
+    write_replacing(&owner_info_path(path), &contents).map_err(io_error)?;
+    Ok(InstanceLock {
+        path: path.to_path_buf(),
+        info,
+        _file: file,
+    })
+}
+
+/// Error for a lock someone else holds. The owner writes its record right
+/// after locking, so wait briefly for one that isn't there yet.
+fn held_by(path: &Path) -> InstanceError {
+    let deadline = Instant::now() + OWNER_RECORD_WAIT;
+    loop {
+        if let Some(owner) = read_owner(&owner_info_path(path)) {
+            return InstanceError::Held(owner);
+        }
+        if Instant::now() >= deadline {
+            return InstanceError::Io {
+                path: path.to_path_buf(),
+                source: std::io::Error::new(
+                    ErrorKind::WouldBlock,
+                    "instance lock is held by a process that hasn't recorded itself",
+                ),
+            };
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+fn owner_at(path: &Path) -> Option<InstanceInfo> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == ErrorKind::NotFound => return None,
+        Err(e) => {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to open instance lock");
+            return None;
+        }
+    };
+    match file.try_lock_shared() {
+        // Nobody holds it; closing the file drops our probe lock
+        Ok(()) => None,
+        Err(TryLockError::WouldBlock) => read_owner(&owner_info_path(path)),
+        Err(TryLockError::Error(e)) => {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to check instance lock");
+            None
+        }
+    }
+}
+
+/// Replace `path` with `contents` in one step, via a temporary file, so a
+/// reader never sees a half-written record
+fn write_replacing(path: &Path, contents: &str) -> std::io::Result<()> {
+    let temp = path.with_extension(format!("{}.tmp", Uuid::new_v4()));
+    fs::write(&temp, contents)?;
+    fs::rename(&temp, path).inspect_err(|_| {
+        if let Err(e) = fs::remove_file(&temp) {
+            tracing::warn!(
+                path = %temp.display(),
+                error = %e,
+                "Failed to remove temporary instance owner record"
+            );
+        }
+    })
+}
+
+fn read_owner(path: &Path) -> Option<InstanceInfo> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => return None,
+        Err(e) => {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to read instance owner record");
+            return None;
+        }
+    };
+    match serde_json::from_str(&contents) {
+        Ok(info) => Some(info),
+        Err(e) => {
+            tracing::warn!(path = %path.display(), error = %e, "Unreadable instance owner record");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_second_acquire_reports_owner() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("instance.lock");
+        let own_pid = std::process::id();
+
+        let lock = acquire_at(&path, InstanceKind::Serve, own_pid).unwrap();
+        assert_eq!(owner_at(&path).as_ref(), Some(lock.info()));
+        // Another starter sees the live owner
+        let err = acquire_at(&path, InstanceKind::Tui, own_pid.wrapping_add(1)).unwrap_err();
+        match err {
+            InstanceError::Held(owner) => {
+                assert_eq!(owner.pid, own_pid);
+                assert_eq!(owner.kind, InstanceKind::Serve);
+            }
+            other => panic!("expected Held, got {other:?}"),
+        }
+
+        drop(lock);
+        assert_eq!(owner_at(&path), None);
+        assert!(!owner_info_path(&path).exists());
+        let relocked = acquire_at(&path, InstanceKind::Tui, own_pid).unwrap();
+        assert_eq!(relocked.info().kind, InstanceKind::Tui);
+    }
+
+    #[test]
+    fn test_record_left_by_dead_owner_is_ignored() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("instance.lock");
+        fs::write(&path, "").unwrap();
+        fs::write(owner_info_path(&path), "not json").unwrap();
+
+        assert_eq!(owner_at(&path), None);
+        let lock = acquire_at(&path, InstanceKind::Tui, std::process::id()).unwrap();
+        assert_eq!(
+            read_owner(&owner_info_path(&path)).as_ref(),
+            Some(lock.info())
+        );
+    }
+
+    #[test]
+    fn test_only_the_lock_and_owner_record_are_left() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("instance.lock");
+
+        let _lock = acquire_at(&path, InstanceKind::Serve, std::process::id()).unwrap();
+        let mut entries: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        entries.sort();
+        assert_eq!(entries, ["instance.json", "instance.lock"]);
+    }
+}
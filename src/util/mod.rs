@@ -1,14 +1,30 @@
 //! Utility modules
 
+pub mod chaos;
+pub mod desktop_notify;
+pub mod instance;
 pub mod names;
+pub mod network;
 pub mod paths;
+pub mod preview;
+pub mod read_aloud;
+pub mod startup_profile;
 pub mod title_generator;
 pub mod tools;
+pub mod url_fetch;
+pub mod voice;
+pub mod workspace_path;
 
+pub use instance::{
+    acquire_instance_lock, instance_owner, InstanceError, InstanceInfo, InstanceKind, InstanceLock,
+};
 pub use names::{generate_branch_name, generate_workspace_name, get_git_username};
 pub use paths::{
-    data_dir, database_path, init_data_dir, log_file_path, logs_dir,
-    migrate_worktrees_to_workspaces, workspaces_dir,
+    attachments_dir, cache_dir, config_dir, config_path, data_dir, database_path, debug_dir,
+    dir_layout, exports_dir, fork_seeds_dir, init_data_dir, init_dirs, log_file_path, logs_dir,
+    migrate_worktrees_to_workspaces, state_dir, themes_dir, workspaces_dir, DirLayout,
+    DirOverrides,
 };
 pub use title_generator::{generate_title_and_branch, sanitize_branch_suffix, GeneratedMetadata};
 pub use tools::{Tool, ToolAvailability, ToolPaths, ToolStatus};
+pub use workspace_path::{normalize_workspace_path, resolve_workspace_path};
@@ -0,0 +1,164 @@
+//! Network reachability as seen by background calls.
+//!
+//! `gh` calls that fail for lack of a network mark Conduit offline, and the
+//! next background call that gets through marks it online again. While
+//! offline, pollers ask [`should_attempt`] first so they retry with a growing
+//! delay instead of failing on every interval.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Delay before the first retry after going offline
+const RETRY_INITIAL: Duration = Duration::from_secs(30);
+/// Longest delay between retries while offline
+const RETRY_MAX: Duration = Duration::from_secs(5 * 60);
+
+/// Output fragments from `gh`/`git` (and the libraries under them) that mean
+/// the network, not the request, was the problem
+const NETWORK_FAILURES: &[&str] = &[
+    "error connecting to",
+    "could not resolve host",
+    "temporary failure in name resolution",
+    "name or service not known",
+    "nodename nor servname provided",
+    "network is unreachable",
+    "no route to host",
+    "connection timed out",
+    "connection refused",
+    "i/o timeout",
+    "tls handshake timeout",
+    "check your internet connection",
+];
+
+#[derive(Debug)]
+struct Offline {
+    /// Delay before the retry after the next failure
+    retry_delay: Duration,
+    /// When the next call may go out
+    next_attempt: Instant,
+}
+
+static STATE: Mutex<Option<Offline>> = Mutex::new(None);
+
+fn with_state<T>(f: impl FnOnce(&mut Option<Offline>) -> T) -> T {
+    match STATE.lock() {
+        Ok(mut guard) => f(&mut guard),
+        Err(poisoned) => f(&mut poisoned.into_inner()),
+    }
+}
+
+/// Whether the last network call failed for lack of a network
+pub fn is_offline() -> bool {
+    with_state(|state| state.is_some())
+}
+
+/// Whether a background network call should go out now. Always while online;
+/// while offline, one call is let through each time the retry delay passes.
+pub fn should_attempt() -> bool {
+    should_attempt_at(Instant::now())
+}
+
+/// Record a call that failed for lack of a network
+pub fn record_failure() {
+    record_failure_at(Instant::now());
+}
+
+/// Record a call that reached its server, whatever the answer
+pub fn record_success() {
+    with_state(|state| {
+        if state.take().is_some() {
+            tracing::info!("Network reachable again");
+        }
+    });
+}
+
+/// Whether command output (usually stderr) describes a network failure
+pub fn is_network_failure(output: &str) -> bool {
+    let output = output.to_ascii_lowercase();
+    NETWORK_FAILURES
+        .iter()
+        .any(|fragment| output.contains(fragment))
+}
+
+/// Whether an HTTP error means the server couldn't be reached
+pub fn is_network_error(err: &reqwest::Error) -> bool {
+    err.is_connect() || err.is_timeout()
+}
+
+fn should_attempt_at(now: Instant) -> bool {
+    with_state(|state| match state {
+        None => true,
+        Some(offline) if now >= offline.next_attempt => {
+            // Hold off everyone else until this attempt reports back
+            offline.next_attempt = now + offline.retry_delay;
+            true
+        }
+        Some(_) => false,
+    })
+}
+
+fn record_failure_at(now: Instant) {
+    with_state(|state| match state {
+        None => {
+            tracing::info!(
+                retry_secs = RETRY_INITIAL.as_secs(),
+                "Network unreachable; backing off background calls"
+            );
+            *state = Some(Offline {
+                retry_delay: (RETRY_INITIAL * 2).min(RETRY_MAX),
+                next_attempt: now + RETRY_INITIAL,
+            });
+        }
+        Some(offline) => {
+            tracing::debug!(
+                retry_secs = offline.retry_delay.as_secs(),
+                "Network still unreachable"
+            );
+            offline.next_attempt = now + offline.retry_delay;
+            offline.retry_delay = (offline.retry_delay * 2).min(RETRY_MAX);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_network_failure() {
+        assert!(is_network_failure(
+            "error connecting to api.github.com\ncheck your internet connection or https://githubstatus.com"
+        ));
+        assert!(is_network_failure(
+            "fatal: unable to access 'https://github.com/o/r/': Could not resolve host: github.com"
+        ));
+        assert!(!is_network_failure(
+            "no pull requests found for branch \"feature\""
+        ));
+    }
+
+    #[test]
+    fn test_backoff_lets_one_attempt_through_per_delay() {
+        // The state is process-wide, so this is the only test that touches it
+        let start = Instant::now();
+        record_success();
+        assert!(should_attempt_at(start));
+
+        record_failure_at(start);
+        assert!(is_offline());
+        assert!(!should_attempt_at(start + Duration::from_secs(10)));
+        assert!(should_attempt_at(start + RETRY_INITIAL));
+        // Another poller in the same window waits for that attempt
+        assert!(!should_attempt_at(start + RETRY_INITIAL));
+
+        // A second failure doubles the delay
+        let second = start + RETRY_INITIAL;
+        record_failure_at(second);
+        assert!(!should_attempt_at(second + RETRY_INITIAL));
+        assert!(should_attempt_at(second + RETRY_INITIAL * 2));
+
+        record_success();
+        assert!(!is_offline());
+        assert!(should_attempt_at(second));
+    }
+}
@@ -3,57 +3,217 @@
 use std::path::PathBuf;
 use std::sync::OnceLock;
 
-/// Global storage for custom data directory path
-static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// Environment variables that override each root when no flag is given
+const CONFIG_DIR_ENV: &str = "CONDUIT_CONFIG_DIR";
+const DATA_DIR_ENV: &str = "CONDUIT_DATA_DIR";
+const STATE_DIR_ENV: &str = "CONDUIT_STATE_DIR";
+const CACHE_DIR_ENV: &str = "CONDUIT_CACHE_DIR";
 
-/// Initialize the data directory with an optional custom path.
+/// Global storage for the resolved directory layout
+static LAYOUT: OnceLock<DirLayout> = OnceLock::new();
+
+/// Roots chosen explicitly on the command line
+#[derive(Debug, Clone, Default)]
+pub struct DirOverrides {
+    pub config: Option<PathBuf>,
+    pub data: Option<PathBuf>,
+    pub state: Option<PathBuf>,
+    pub cache: Option<PathBuf>,
+}
+
+impl DirOverrides {
+    /// Fill roots not set on the command line from `CONDUIT_*_DIR`
+    fn or_env(self) -> Self {
+        let env_dir = |name: &str| {
+            std::env::var_os(name)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        };
+        Self {
+            config: self.config.or_else(|| env_dir(CONFIG_DIR_ENV)),
+            data: self.data.or_else(|| env_dir(DATA_DIR_ENV)),
+            state: self.state.or_else(|| env_dir(STATE_DIR_ENV)),
+            cache: self.cache.or_else(|| env_dir(CACHE_DIR_ENV)),
+        }
+    }
+}
+
+/// Where Conduit keeps its files.
+///
+/// - config: `config.toml` and custom themes
+/// - data: the database, workspaces, attachments and exports
+/// - state: logs and debug dumps
+/// - cache: files that can be rebuilt, like the session import cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirLayout {
+    pub config: PathBuf,
+    pub data: PathBuf,
+    pub state: PathBuf,
+    pub cache: PathBuf,
+    /// All roots share the pre-XDG `~/.conduit` folder
+    pub legacy: bool,
+}
+
+/// Platform directories the default layout is built from
+#[derive(Debug, Clone, Default)]
+struct PlatformDirs {
+    home: Option<PathBuf>,
+    config: Option<PathBuf>,
+    data: Option<PathBuf>,
+    state: Option<PathBuf>,
+    cache: Option<PathBuf>,
+}
+
+impl PlatformDirs {
+    fn current() -> Self {
+        Self {
+            home: dirs::home_dir(),
+            config: dirs::config_dir(),
+            data: dirs::data_dir(),
+            // Only Linux has a state directory; elsewhere state is local data
+            state: dirs::state_dir().or_else(dirs::data_local_dir),
+            cache: dirs::cache_dir(),
+        }
+    }
+
+    /// The single folder older versions kept everything in
+    fn legacy_dir(&self) -> PathBuf {
+        self.home
+            .as_ref()
+            .map(|h| h.join(".conduit"))
+            .unwrap_or_else(|| PathBuf::from(".conduit"))
+    }
+}
+
+impl DirLayout {
+    /// Resolve each root: its own override first, then the data directory
+    /// override (which keeps everything in one folder, as `--data-dir` always
+    /// has), then an existing `~/.conduit`, then the platform's directories.
+    fn resolve(overrides: DirOverrides, platform: &PlatformDirs, legacy_exists: bool) -> Self {
+        let legacy_dir = platform.legacy_dir();
+        let fallback = match &overrides.data {
+            Some(data) => Some(data.clone()),
+            None if legacy_exists => Some(legacy_dir.clone()),
+            None => None,
+        };
+        let root = |explicit: Option<PathBuf>, platform_dir: &Option<PathBuf>| {
+            explicit
+                .or_else(|| fallback.clone())
+                .or_else(|| platform_dir.as_ref().map(|dir| dir.join("conduit")))
+                .unwrap_or_else(|| legacy_dir.clone())
+        };
+
+        let config = root(overrides.config, &platform.config);
+        let data = root(overrides.data, &platform.data);
+        let state = root(overrides.state, &platform.state);
+        let cache = root(overrides.cache, &platform.cache);
+        let legacy = [&config, &data, &state, &cache]
+            .iter()
+            .all(|dir| **dir == legacy_dir);
+        Self {
+            config,
+            data,
+            state,
+            cache,
+            legacy,
+        }
+    }
+
+    fn current(overrides: DirOverrides) -> Self {
+        let platform = PlatformDirs::current();
+        let legacy_exists = platform.legacy_dir().is_dir();
+        Self::resolve(overrides.or_env(), &platform, legacy_exists)
+    }
+}
+
+/// Initialize the directory layout.
 /// Must be called early in main() before any other path functions are used.
-/// If custom_path is None, uses the default ~/.conduit location.
-pub fn init_data_dir(custom_path: Option<PathBuf>) {
-    let path = custom_path.unwrap_or_else(default_data_dir);
+pub fn init_dirs(overrides: DirOverrides) {
+    let layout = DirLayout::current(overrides);
     // Log a debug message if already set (shouldn't happen in normal usage)
-    if DATA_DIR.set(path.clone()).is_err() {
-        let existing = DATA_DIR
+    if let Err(layout) = LAYOUT.set(layout) {
+        let existing = LAYOUT
             .get()
-            .map(|p| p.display().to_string())
+            .map(|l| l.data.display().to_string())
             .unwrap_or_else(|| "<unknown>".to_string());
         tracing::debug!(
-            path = %path.display(),
+            path = %layout.data.display(),
             existing = %existing,
-            "Data directory already initialized"
+            "Directory layout already initialized"
         );
     }
 }
 
-/// Get the default data directory path (~/.conduit)
-fn default_data_dir() -> PathBuf {
-    dirs::home_dir()
-        .map(|h| h.join(".conduit"))
-        .unwrap_or_else(|| PathBuf::from(".conduit"))
+/// Initialize the directory layout with every root in one folder.
+/// If custom_path is None, uses the default layout.
+pub fn init_data_dir(custom_path: Option<PathBuf>) {
+    init_dirs(DirOverrides {
+        data: custom_path,
+        ..DirOverrides::default()
+    });
+}
+
+/// Get the resolved directory layout
+pub fn dir_layout() -> &'static DirLayout {
+    LAYOUT.get_or_init(|| DirLayout::current(DirOverrides::default()))
+}
+
+/// Get the configuration directory (config.toml, themes)
+pub fn config_dir() -> PathBuf {
+    dir_layout().config.clone()
 }
 
-/// Get the base Conduit data directory.
-/// Returns the custom path if set via init_data_dir(), otherwise ~/.conduit
+/// Get the data directory (database, workspaces, attachments, exports)
 pub fn data_dir() -> PathBuf {
-    DATA_DIR.get().cloned().unwrap_or_else(default_data_dir)
+    dir_layout().data.clone()
+}
+
+/// Get the state directory (logs, debug dumps)
+pub fn state_dir() -> PathBuf {
+    dir_layout().state.clone()
+}
+
+/// Get the cache directory (files Conduit can rebuild)
+pub fn cache_dir() -> PathBuf {
+    dir_layout().cache.clone()
 }
 
-/// Get the database file path (~/.conduit/conduit.db)
+/// Get the database file path (<data>/conduit.db)
 pub fn database_path() -> PathBuf {
     data_dir().join("conduit.db")
 }
 
-/// Get the logs directory (~/.conduit/logs)
+/// Get the logs directory (<state>/logs)
 pub fn logs_dir() -> PathBuf {
-    data_dir().join("logs")
+    state_dir().join("logs")
 }
 
-/// Get the default log file path (~/.conduit/logs/conduit.log)
+/// Get the debug state dumps directory (<state>/debug)
+pub fn debug_dir() -> PathBuf {
+    state_dir().join("debug")
+}
+
+/// Get the default log file path (<state>/logs/conduit.log)
 pub fn log_file_path() -> PathBuf {
     logs_dir().join("conduit.log")
 }
 
-/// Get the workspaces directory (~/.conduit/workspaces)
+/// Get the transcript exports directory (<data>/exports)
+pub fn exports_dir() -> PathBuf {
+    data_dir().join("exports")
+}
+
+/// Get the stored fork seed prompts directory (<data>/fork_seeds)
+pub fn fork_seeds_dir() -> PathBuf {
+    data_dir().join("fork_seeds")
+}
+
+/// Get the managed attachments directory (<data>/attachments)
+pub fn attachments_dir() -> PathBuf {
+    data_dir().join("attachments")
+}
+
+/// Get the workspaces directory (<data>/workspaces)
 pub fn workspaces_dir() -> PathBuf {
     data_dir().join("workspaces")
 }
@@ -61,7 +221,7 @@ pub fn workspaces_dir() -> PathBuf {
 /// Migrate old worktrees folder to workspaces folder if needed
 ///
 /// This is a one-time migration for users upgrading from older versions.
-/// If ~/.conduit/worktrees exists and ~/.conduit/workspaces doesn't,
+/// If <data>/worktrees exists and <data>/workspaces doesn't,
 /// we rename the folder.
 pub fn migrate_worktrees_to_workspaces() {
     let old_path = data_dir().join("worktrees");
@@ -88,7 +248,60 @@ pub fn migrate_worktrees_to_workspaces() {
     }
 }
 
-/// Get the config file path (~/.conduit/config.toml)
+/// Get the config file path (<config>/config.toml)
 pub fn config_path() -> PathBuf {
-    data_dir().join("config.toml")
+    config_dir().join("config.toml")
+}
+
+/// Get the custom themes directory (<config>/themes)
+pub fn themes_dir() -> PathBuf {
+    config_dir().join("themes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn platform() -> PlatformDirs {
+        PlatformDirs {
+            home: Some(PathBuf::from("/home/u")),
+            config: Some(PathBuf::from("/home/u/.config")),
+            data: Some(PathBuf::from("/home/u/.local/share")),
+            state: Some(PathBuf::from("/home/u/.local/state")),
+            cache: Some(PathBuf::from("/home/u/.cache")),
+        }
+    }
+
+    #[test]
+    fn test_default_layout_uses_platform_dirs() {
+        let layout = DirLayout::resolve(DirOverrides::default(), &platform(), false);
+        assert_eq!(layout.config, PathBuf::from("/home/u/.config/conduit"));
+        assert_eq!(layout.data, PathBuf::from("/home/u/.local/share/conduit"));
+        assert_eq!(layout.state, PathBuf::from("/home/u/.local/state/conduit"));
+        assert_eq!(layout.cache, PathBuf::from("/home/u/.cache/conduit"));
+        assert!(!layout.legacy);
+    }
+
+    #[test]
+    fn test_existing_legacy_dir_keeps_single_folder() {
+        let layout = DirLayout::resolve(DirOverrides::default(), &platform(), true);
+        assert_eq!(layout.config, PathBuf::from("/home/u/.conduit"));
+        assert_eq!(layout.cache, PathBuf::from("/home/u/.conduit"));
+        assert!(layout.legacy);
+    }
+
+    #[test]
+    fn test_data_override_holds_other_roots_unless_set() {
+        let overrides = DirOverrides {
+            data: Some(PathBuf::from("/srv/conduit")),
+            cache: Some(PathBuf::from("/tmp/conduit-cache")),
+            ..DirOverrides::default()
+        };
+        let layout = DirLayout::resolve(overrides, &platform(), true);
+        assert_eq!(layout.config, PathBuf::from("/srv/conduit"));
+        assert_eq!(layout.data, PathBuf::from("/srv/conduit"));
+        assert_eq!(layout.state, PathBuf::from("/srv/conduit"));
+        assert_eq!(layout.cache, PathBuf::from("/tmp/conduit-cache"));
+        assert!(!layout.legacy);
+    }
 }
@@ -0,0 +1,173 @@
+//! Detection of dev servers announced in tool output.
+//!
+//! Dev servers print a line such as `Local: http://localhost:5173/`,
+//! `Listening on http://127.0.0.1:3000` or `listening on port 8080` once they
+//! accept connections. A line only counts when it contains one of the
+//! announcement phrases below, so URLs that merely appear in output (e.g. a
+//! `curl` response) are not mistaken for a server.
+
+/// Phrases dev servers use when announcing their address (matched lowercase)
+const ANNOUNCEMENT_PATTERNS: &[&str] = &[
+    "listening",
+    "listen on",
+    "running on",
+    "running at",
+    "server running",
+    "server started",
+    "started server",
+    "serving",
+    "local:",
+    "ready on",
+    "available on",
+    "development server at",
+    "dev server",
+];
+
+/// Ports below this are system services rather than dev servers
+const MIN_PREVIEW_PORT: u16 = 1024;
+
+/// A dev server address found in tool output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedPreview {
+    pub port: u16,
+    /// Browser URL on this machine, e.g. `http://localhost:5173/`
+    pub url: String,
+}
+
+/// Dev servers announced in `text`, one per port, in order of appearance
+pub fn detect_previews(text: &str) -> Vec<DetectedPreview> {
+    let mut found: Vec<DetectedPreview> = Vec::new();
+    for line in text.lines() {
+        let Some(preview) = detect_line(line) else {
+            continue;
+        };
+        if !found.iter().any(|existing| existing.port == preview.port) {
+            found.push(preview);
+        }
+    }
+    found
+}
+
+fn detect_line(line: &str) -> Option<DetectedPreview> {
+    let lower = line.to_lowercase();
+    if !ANNOUNCEMENT_PATTERNS.iter().any(|p| lower.contains(p)) {
+        return None;
+    }
+    // Lines with a URL are judged by the URL alone, so a remote address's
+    // port isn't picked up as a bare port
+    let preview = if lower.contains("://") {
+        detect_url(line)?
+    } else {
+        detect_bare_port(&lower)?
+    };
+    (preview.port >= MIN_PREVIEW_PORT).then_some(preview)
+}
+
+/// `http://localhost:3000/app`, `http://0.0.0.0:8000/`, `http://[::1]:4000`
+fn detect_url(line: &str) -> Option<DetectedPreview> {
+    let start = line.find("http://").or_else(|| line.find("https://"))?;
+    let url = &line[start..];
+    let end = url
+        .find(|c: char| c.is_whitespace() || matches!(c, ')' | ',' | '"' | '\'' | '>'))
+        .unwrap_or(url.len());
+    let url = url[..end].trim_end_matches('.');
+    let (scheme, rest) = url.split_once("://")?;
+
+    let host_end = if rest.starts_with('[') {
+        rest.find(']')? + 1
+    } else {
+        rest.find([':', '/']).unwrap_or(rest.len())
+    };
+    let host = rest[..host_end].to_ascii_lowercase();
+    if !matches!(
+        host.as_str(),
+        "localhost" | "127.0.0.1" | "0.0.0.0" | "[::]" | "[::1]"
+    ) {
+        return None;
+    }
+    let after_host = rest[host_end..].strip_prefix(':')?;
+    let port_end = after_host
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(after_host.len());
+    let port: u16 = after_host[..port_end].parse().ok()?;
+    let path = &after_host[port_end..];
+    Some(DetectedPreview {
+        port,
+        url: format!("{}://localhost:{}{}", scheme, port, path),
+    })
+}
+
+/// `listening on :3000`, `listening on port 3000`, `started server on 0.0.0.0:3000`
+fn detect_bare_port(line: &str) -> Option<DetectedPreview> {
+    let digits_after = |rest: &str| -> Option<u16> {
+        let end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        rest[..end].parse().ok()
+    };
+    let port = line
+        .find("port ")
+        .and_then(|idx| digits_after(&line[idx + "port ".len()..]))
+        .or_else(|| {
+            line.match_indices(':')
+                .find_map(|(idx, _)| digits_after(&line[idx + 1..]))
+        })?;
+    Some(DetectedPreview {
+        port,
+        url: format!("http://localhost:{}", port),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ports(text: &str) -> Vec<u16> {
+        detect_previews(text).iter().map(|p| p.port).collect()
+    }
+
+    #[test]
+    fn test_detects_common_dev_servers() {
+        let vite = "  VITE v5.0.0  ready in 300 ms\n\n  ➜  Local:   http://localhost:5173/\n  ➜  Network: use --host to expose";
+        assert_eq!(
+            detect_previews(vite),
+            vec![DetectedPreview {
+                port: 5173,
+                url: "http://localhost:5173/".to_string()
+            }]
+        );
+        assert_eq!(ports("* Listening on http://127.0.0.1:3000"), [3000]);
+        assert_eq!(
+            ports("Starting development server at http://127.0.0.1:8000/"),
+            [8000]
+        );
+        assert_eq!(
+            ports("Serving HTTP on 0.0.0.0 port 8080 (http://0.0.0.0:8080/) ..."),
+            [8080]
+        );
+        assert_eq!(ports("Server listening on port 4000"), [4000]);
+        assert_eq!(ports("app listening on :3001"), [3001]);
+        assert_eq!(
+            detect_previews("ready - started server on 0.0.0.0:3000, url: http://0.0.0.0:3000")[0]
+                .url,
+            "http://localhost:3000"
+        );
+    }
+
+    #[test]
+    fn test_ignores_urls_that_are_not_announcements() {
+        assert!(detect_previews("curl http://localhost:3000/api -> 500").is_empty());
+        assert!(
+            detect_previews("Error: listen EADDRINUSE: address already in use :::3000").is_empty()
+        );
+        assert!(detect_previews("Listening on https://example.com:8443").is_empty());
+        assert!(detect_previews("sshd listening on port 22").is_empty());
+    }
+
+    #[test]
+    fn test_one_preview_per_port() {
+        let text =
+            "Local: http://localhost:3000\nListening on port 3000\nLocal: http://localhost:3001";
+        assert_eq!(ports(text), [3000, 3001]);
+    }
+}
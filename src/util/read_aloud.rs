@@ -0,0 +1,171 @@
+//! Read-aloud of assistant responses through an external TTS command.
+//!
+//! The configured command (`say`, `piper`, `espeak-ng`, ...) receives the
+//! response on stdin once a turn completes in the active tab. Markdown is
+//! flattened first and code blocks are skipped, since neither reads well.
+
+use std::process::Stdio;
+
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::config::ReadAloudConfig;
+
+#[derive(Debug, Error)]
+pub enum ReadAloudError {
+    #[error("failed to run {command}: {source}")]
+    Spawn {
+        command: String,
+        source: std::io::Error,
+    },
+    #[error("{command} exited with {status}: {stderr}")]
+    Failed {
+        command: String,
+        status: std::process::ExitStatus,
+        stderr: String,
+    },
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Pipe `text` to the configured TTS command and wait for it to finish.
+///
+/// The child is killed if the returned future is dropped, so aborting the
+/// task running this stops speech mid-sentence.
+pub async fn speak(config: &ReadAloudConfig, text: &str) -> Result<(), ReadAloudError> {
+    let mut child = Command::new(&config.command)
+        .args(&config.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|source| ReadAloudError::Spawn {
+            command: config.command.clone(),
+            source,
+        })?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes()).await?;
+        // Dropping stdin sends EOF, which is what tells most TTS tools to start
+    }
+
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        return Err(ReadAloudError::Failed {
+            command: config.command.clone(),
+            status: output.status,
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Flatten a markdown response into plain prose worth speaking.
+///
+/// Fenced code blocks are replaced by a short marker, inline markup is
+/// stripped, and the result is cut at a sentence boundary near `max_chars`.
+/// Returns `None` when nothing speakable remains.
+pub fn speakable_text(markdown: &str, max_chars: usize) -> Option<String> {
+    let mut text = String::new();
+    let mut in_code_block = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            if !in_code_block {
+                text.push_str(" Code block omitted. ");
+            }
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block || trimmed.is_empty() {
+            continue;
+        }
+        let content = trimmed
+            .trim_start_matches('#')
+            .trim_start_matches('>')
+            .trim_start();
+        let content = content
+            .strip_prefix("- ")
+            .or_else(|| content.strip_prefix("* "))
+            .unwrap_or(content);
+        text.push_str(&strip_inline_markup(content));
+        // Keep list items and headings from running together
+        if !text.ends_with(['.', '!', '?', ':']) {
+            text.push('.');
+        }
+        text.push(' ');
+    }
+
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() || text == "Code block omitted." {
+        return None;
+    }
+    Some(truncate_at_sentence(&text, max_chars))
+}
+
+/// Drop emphasis markers and backticks, and keep only the label of links.
+/// Underscores are left alone since they are far more common in identifiers.
+fn strip_inline_markup(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' | '`' => {}
+            ']' if chars.peek() == Some(&'(') => {
+                // Skip the link target
+                for next in chars.by_ref() {
+                    if next == ')' {
+                        break;
+                    }
+                }
+            }
+            '[' => {}
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+fn truncate_at_sentence(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars).collect();
+    match cut.rfind(['.', '!', '?']) {
+        Some(end) if end > cut.len() / 2 => cut[..=end].to_string(),
+        _ => format!("{}…", cut.trim_end()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speakable_text_skips_code_and_markup() {
+        let markdown = "## Done\n\nI updated **`main.rs`** per [the docs](https://example.com).\n\n```rust\nfn main() {}\n```\n\n- tests pass\n- clippy is clean";
+        assert_eq!(
+            speakable_text(markdown, 1000).as_deref(),
+            Some(
+                "Done. I updated main.rs per the docs. Code block omitted. tests pass. clippy is clean."
+            )
+        );
+    }
+
+    #[test]
+    fn test_speakable_text_ignores_code_only_responses() {
+        assert_eq!(speakable_text("```\nls -la\n```", 1000), None);
+        assert_eq!(speakable_text("  \n\n", 1000), None);
+    }
+
+    #[test]
+    fn test_speakable_text_truncates_at_sentence_boundary() {
+        let text = "First sentence is here. Second sentence runs on for a while longer.";
+        assert_eq!(
+            speakable_text(text, 40).as_deref(),
+            Some("First sentence is here.")
+        );
+    }
+}
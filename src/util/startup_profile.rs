@@ -0,0 +1,108 @@
+//! Startup phase timing, reported by `conduit --profile-startup`.
+//!
+//! Phases are only recorded once [`enable`] has been called, so the timers
+//! cost nothing in a normal run.
+
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+static PROFILE: OnceLock<Mutex<StartupProfile>> = OnceLock::new();
+
+struct StartupProfile {
+    started: Instant,
+    phases: Vec<(&'static str, Duration)>,
+    first_frame: Option<Duration>,
+}
+
+/// Start recording; call as early in `main` as possible
+pub fn enable() {
+    let profile = StartupProfile {
+        started: Instant::now(),
+        phases: Vec::new(),
+        first_frame: None,
+    };
+    if PROFILE.set(Mutex::new(profile)).is_err() {
+        tracing::debug!("Startup profiling already enabled");
+    }
+}
+
+/// Run `f` as the startup phase `name`
+pub fn phase<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    let Some(profile) = PROFILE.get() else {
+        return f();
+    };
+    let started = Instant::now();
+    let result = f();
+    let elapsed = started.elapsed();
+    let mut profile = profile.lock().unwrap();
+    if profile.first_frame.is_none() {
+        profile.phases.push((name, elapsed));
+    }
+    result
+}
+
+/// Record that the first frame has been drawn; later phases are ignored
+pub fn first_frame_drawn() {
+    let Some(profile) = PROFILE.get() else {
+        return;
+    };
+    let mut profile = profile.lock().unwrap();
+    if profile.first_frame.is_none() {
+        let elapsed = profile.started.elapsed();
+        profile.first_frame = Some(elapsed);
+        tracing::info!(elapsed_ms = elapsed.as_millis() as u64, "First frame drawn");
+    }
+}
+
+/// The phase report, when profiling is enabled
+pub fn report() -> Option<String> {
+    let profile = PROFILE.get()?.lock().unwrap();
+    Some(format_report(&profile.phases, profile.first_frame))
+}
+
+fn format_report(phases: &[(&'static str, Duration)], first_frame: Option<Duration>) -> String {
+    let width = phases
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0)
+        .max("first frame".len());
+    let mut report = String::from("Startup profile\n");
+    for (name, elapsed) in phases {
+        report.push_str(&format!("  {name:<width$}  {}\n", format_ms(*elapsed)));
+    }
+    match first_frame {
+        Some(elapsed) => report.push_str(&format!(
+            "  {:<width$}  {}\n",
+            "first frame",
+            format_ms(elapsed)
+        )),
+        None => report.push_str("  No frame was drawn\n"),
+    }
+    report
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:>8.1}ms", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_report() {
+        let phases = [
+            ("config", Duration::from_micros(2_500)),
+            ("database", Duration::from_millis(12)),
+        ];
+        assert_eq!(
+            format_report(&phases, Some(Duration::from_millis(48))),
+            "Startup profile\n\
+             \x20 config            2.5ms\n\
+             \x20 database         12.0ms\n\
+             \x20 first frame      48.0ms\n"
+        );
+        assert!(format_report(&[], None).ends_with("No frame was drawn\n"));
+    }
+}
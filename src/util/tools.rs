@@ -329,6 +329,21 @@ impl ToolAvailability {
         is_available
     }
 
+    /// Mark a tool as available without validating the path
+    ///
+    /// Used when a fixture replay stands in for an agent binary.
+    pub fn set_available(&mut self, tool: Tool, path: PathBuf) {
+        let status = ToolStatus::Available(path);
+        match tool {
+            Tool::Git => self.git = status,
+            Tool::Gh => self.gh = status,
+            Tool::Claude => self.claude = status,
+            Tool::Codex => self.codex = status,
+            Tool::Gemini => self.gemini = status,
+            Tool::Opencode => self.opencode = status,
+        }
+    }
+
     /// Validate a path for a tool without updating state
     ///
     /// Returns Ok(canonical_path) if valid, Err(message) if invalid
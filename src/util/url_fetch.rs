@@ -0,0 +1,303 @@
+//! `/fetch <url>`: download a page and keep its readable text, so it can be
+//! attached to a prompt for agents without web access.
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use regex::Regex;
+use thiserror::Error;
+
+use crate::config::FetchConfig;
+
+/// Elements whose content is never readable text
+static NON_CONTENT: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    [
+        "head", "script", "style", "noscript", "svg", "template", "iframe", "nav", "footer",
+        "aside", "form",
+    ]
+    .iter()
+    .map(|tag| {
+        Regex::new(&format!(r"(?is)<{tag}\b[^>]*>.*?</{tag}\s*>"))
+            .expect("non-content pattern is valid")
+    })
+    .collect()
+});
+static COMMENT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<!--.*?-->").expect("comment pattern is valid"));
+static TITLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)<title\b[^>]*>(.*?)</title\s*>").expect("title pattern is valid")
+});
+/// The page's main content, when it marks one
+static MAIN_CONTENT: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    ["article", "main"]
+        .iter()
+        .map(|tag| {
+            Regex::new(&format!(r"(?is)<{tag}\b[^>]*>(.*)</{tag}\s*>"))
+                .expect("main content pattern is valid")
+        })
+        .collect()
+});
+static HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)<h[1-6]\b[^>]*>").expect("heading pattern is valid"));
+static LIST_ITEM: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)<li\b[^>]*>").expect("list item pattern is valid"));
+static BLOCK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)</?(p|div|br|h[1-6]|li|ul|ol|tr|table|section|article|main|pre|blockquote|dd|dt|hr)\b[^>]*>",
+    )
+    .expect("block pattern is valid")
+});
+static TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<[^>]*>").expect("tag pattern is valid"));
+static ENTITY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"&(#[0-9]{1,7}|#[xX][0-9a-fA-F]{1,6}|[a-zA-Z]{2,8});")
+        .expect("entity pattern is valid")
+});
+
+#[derive(Error, Debug)]
+pub enum UrlFetchError {
+    #[error("Not an http(s) URL: {0}")]
+    InvalidUrl(String),
+    #[error("{0} isn't in [fetch] allowed_hosts")]
+    HostNotAllowed(String),
+    #[error("Request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Server answered {0}")]
+    Status(reqwest::StatusCode),
+    #[error("Page is larger than {0} KB")]
+    TooLarge(usize),
+    #[error("Can't extract text from {0} content")]
+    UnsupportedContent(String),
+}
+
+/// Readable text of a fetched page
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedPage {
+    /// URL after redirects
+    pub url: String,
+    pub title: Option<String>,
+    pub text: String,
+    /// Whether `text` was cut to `[fetch] max_text_kb`
+    pub truncated: bool,
+}
+
+/// Download `url` and extract its readable text within the configured limits
+pub async fn fetch_page(url: &str, config: &FetchConfig) -> Result<FetchedPage, UrlFetchError> {
+    let parsed =
+        reqwest::Url::parse(url.trim()).map_err(|_| UrlFetchError::InvalidUrl(url.to_string()))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(UrlFetchError::InvalidUrl(url.to_string()));
+    }
+    check_host(&parsed, &config.allowed_hosts)?;
+
+    let allowed_hosts = config.allowed_hosts.clone();
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(config.timeout_secs))
+        .user_agent(concat!("conduit/", env!("CARGO_PKG_VERSION")))
+        // Redirects must stay on allowed hosts too
+        .redirect(reqwest::redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() >= 5 {
+                attempt.error("too many redirects")
+            } else if check_host(attempt.url(), &allowed_hosts).is_err() {
+                attempt.stop()
+            } else {
+                attempt.follow()
+            }
+        }))
+        .build()?;
+
+    let mut response = client.get(parsed).send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(UrlFetchError::Status(status));
+    }
+    let final_url = response.url().clone();
+    check_host(&final_url, &config.allowed_hosts)?;
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("text/html")
+        .to_ascii_lowercase();
+    let is_html = content_type.contains("html");
+    let is_text = content_type.starts_with("text/")
+        || content_type.contains("json")
+        || content_type.contains("xml");
+    if !is_html && !is_text {
+        return Err(UrlFetchError::UnsupportedContent(content_type));
+    }
+
+    let max_download = config.max_download_kb * 1024;
+    if response
+        .content_length()
+        .is_some_and(|len| len as usize > max_download)
+    {
+        return Err(UrlFetchError::TooLarge(config.max_download_kb));
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > max_download {
+            return Err(UrlFetchError::TooLarge(config.max_download_kb));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    let body = String::from_utf8_lossy(&body);
+
+    let (title, text) = if is_html {
+        extract_readable_text(&body)
+    } else {
+        (None, body.trim().to_string())
+    };
+    let (text, truncated) = truncate_text(text, config.max_text_kb * 1024);
+    Ok(FetchedPage {
+        url: final_url.to_string(),
+        title,
+        text,
+        truncated,
+    })
+}
+
+/// Whether `url`'s host is allowed; an empty allowlist allows every host
+fn check_host(url: &reqwest::Url, allowed_hosts: &[String]) -> Result<(), UrlFetchError> {
+    let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+    if allowed_hosts.is_empty()
+        || allowed_hosts
+            .iter()
+            .any(|allowed| host_matches(&host, allowed))
+    {
+        Ok(())
+    } else {
+        Err(UrlFetchError::HostNotAllowed(host))
+    }
+}
+
+fn host_matches(host: &str, allowed: &str) -> bool {
+    host == allowed
+        || host
+            .strip_suffix(allowed)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+fn truncate_text(mut text: String, max_bytes: usize) -> (String, bool) {
+    if text.len() <= max_bytes {
+        return (text, false);
+    }
+    let mut cut = max_bytes;
+    while !text.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    text.truncate(cut);
+    (text, true)
+}
+
+/// Page title and readable text of an HTML document: scripts, navigation and
+/// markup are dropped, and block elements become line breaks.
+pub fn extract_readable_text(html: &str) -> (Option<String>, String) {
+    let title = TITLE
+        .captures(html)
+        .map(|caps| collapse_spaces(&decode_entities(&TAG.replace_all(&caps[1], ""))))
+        .filter(|title| !title.is_empty());
+
+    let mut doc = COMMENT.replace_all(html, "").into_owned();
+    for pattern in NON_CONTENT.iter() {
+        doc = pattern.replace_all(&doc, " ").into_owned();
+    }
+    if let Some(main) = MAIN_CONTENT
+        .iter()
+        .find_map(|pattern| pattern.captures(&doc).map(|caps| caps[1].to_string()))
+    {
+        doc = main;
+    }
+    let doc = HEADING.replace_all(&doc, "\n\n# ");
+    let doc = LIST_ITEM.replace_all(&doc, "\n- ");
+    let doc = BLOCK.replace_all(&doc, "\n");
+    let doc = TAG.replace_all(&doc, "");
+    let doc = decode_entities(&doc);
+
+    let mut text = String::new();
+    for line in doc.lines().map(collapse_spaces) {
+        if line.is_empty() || line == "#" || line == "-" {
+            continue;
+        }
+        if !text.is_empty() {
+            // Headings start a new section
+            text.push_str(if line.starts_with("# ") { "\n\n" } else { "\n" });
+        }
+        text.push_str(&line);
+    }
+    (title, text)
+}
+
+fn collapse_spaces(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn decode_entities(text: &str) -> String {
+    ENTITY
+        .replace_all(text, |caps: &regex::Captures<'_>| {
+            let entity = &caps[1];
+            let decoded = if let Some(hex) = entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+            {
+                u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+            } else if let Some(dec) = entity.strip_prefix('#') {
+                dec.parse().ok().and_then(char::from_u32)
+            } else {
+                match entity {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    "nbsp" => Some(' '),
+                    "mdash" => Some('—'),
+                    "ndash" => Some('–'),
+                    "hellip" => Some('…'),
+                    "copy" => Some('©'),
+                    _ => None,
+                }
+            };
+            decoded
+                .map(String::from)
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_readable_text_keeps_main_content() {
+        let html = r#"<html><head><title>Guide &amp; Notes</title>
+            <script>var x = "<p>not text</p>";</script></head>
+            <body><nav><a href="/">Home</a></nav>
+            <main><h1>Install</h1><p>Run <code>cargo   install</code>.</p>
+            <ul><li>Linux</li><li>macOS</li></ul><!-- hidden --></main>
+            <footer>Copyright</footer></body></html>"#;
+
+        let (title, text) = extract_readable_text(html);
+        assert_eq!(title.as_deref(), Some("Guide & Notes"));
+        assert_eq!(text, "# Install\nRun cargo install.\n- Linux\n- macOS");
+    }
+
+    #[test]
+    fn test_host_allowlist_includes_subdomains() {
+        let allowed = vec!["example.com".to_string()];
+        let url = |s: &str| reqwest::Url::parse(s).unwrap();
+        assert!(check_host(&url("https://example.com/a"), &allowed).is_ok());
+        assert!(check_host(&url("https://docs.example.com/a"), &allowed).is_ok());
+        assert!(check_host(&url("https://badexample.com/a"), &allowed).is_err());
+        assert!(check_host(&url("https://anything.org"), &[]).is_ok());
+    }
+
+    #[test]
+    fn test_truncate_text_respects_char_boundaries() {
+        let (text, truncated) = truncate_text("ééé".to_string(), 3);
+        assert_eq!(text, "é");
+        assert!(truncated);
+    }
+}
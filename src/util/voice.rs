@@ -0,0 +1,428 @@
+//! Voice prompt input: microphone capture and speech-to-text.
+//!
+//! Capture uses cpal and is only compiled with the `voice` feature, since it
+//! links against the platform audio stack (ALSA on Linux). Transcription runs
+//! through either a local whisper.cpp binary or an OpenAI-compatible
+//! `/audio/transcriptions` endpoint, as configured in `[voice]`.
+
+use std::process::Stdio;
+use std::sync::mpsc as std_mpsc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use serde::Deserialize;
+use thiserror::Error;
+use tokio::process::Command;
+
+use crate::config::{VoiceBackend, VoiceConfig};
+
+/// Sample rate whisper models expect
+pub const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+/// Recordings shorter than this are treated as accidental presses
+const MIN_RECORDING: Duration = Duration::from_millis(300);
+
+const TRANSCRIBE_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Error)]
+pub enum VoiceError {
+    #[error("Voice input isn't available in this build (rebuild with --features voice)")]
+    Unsupported,
+    #[error("Microphone error: {0}")]
+    Device(String),
+    #[error("No speech recorded")]
+    NoAudio,
+    #[error("Transcription failed: {0}")]
+    Transcription(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Mono audio captured from the microphone
+#[derive(Debug, Clone, Default)]
+pub struct Recording {
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+}
+
+impl Recording {
+    pub fn duration(&self) -> Duration {
+        if self.sample_rate == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(self.samples.len() as f64 / self.sample_rate as f64)
+    }
+
+    /// Resample to 16 kHz and encode as 16-bit PCM WAV
+    pub fn to_whisper_wav(&self) -> Result<Vec<u8>, VoiceError> {
+        if self.duration() < MIN_RECORDING {
+            return Err(VoiceError::NoAudio);
+        }
+        let samples = resample_linear(&self.samples, self.sample_rate, WHISPER_SAMPLE_RATE);
+        Ok(encode_wav(&samples, WHISPER_SAMPLE_RATE))
+    }
+}
+
+/// An in-progress microphone recording.
+///
+/// The cpal stream isn't `Send` on every platform, so it lives on a dedicated
+/// thread that records until [`VoiceRecorder::stop`] or the time limit.
+pub struct VoiceRecorder {
+    stop_tx: std_mpsc::Sender<()>,
+    handle: JoinHandle<Result<Recording, VoiceError>>,
+}
+
+impl VoiceRecorder {
+    /// Start recording from the default input device
+    #[cfg_attr(not(feature = "voice"), allow(unused_variables))]
+    pub fn start(max_duration: Duration) -> Result<Self, VoiceError> {
+        #[cfg(feature = "voice")]
+        {
+            capture::start(max_duration)
+        }
+        #[cfg(not(feature = "voice"))]
+        {
+            Err(VoiceError::Unsupported)
+        }
+    }
+
+    /// Whether the recording already ended on its own (time limit or device error)
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Stop recording and return the captured audio
+    pub fn stop(self) -> Result<Recording, VoiceError> {
+        if self.stop_tx.send(()).is_err() {
+            tracing::debug!("Voice recorder already stopped");
+        }
+        self.handle
+            .join()
+            .map_err(|_| VoiceError::Device("recording thread panicked".to_string()))?
+    }
+}
+
+#[cfg(feature = "voice")]
+mod capture {
+    use std::sync::Arc;
+
+    use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+    use cpal::{SampleFormat, SizedSample};
+    use parking_lot::Mutex;
+
+    use super::*;
+
+    pub(super) fn start(max_duration: Duration) -> Result<VoiceRecorder, VoiceError> {
+        let (stop_tx, stop_rx) = std_mpsc::channel();
+        let (ready_tx, ready_rx) = std_mpsc::channel();
+        let handle = std::thread::Builder::new()
+            .name("conduit-voice".to_string())
+            .spawn(move || record(max_duration, stop_rx, ready_tx))?;
+
+        // Surface device errors now rather than when the user presses stop
+        match ready_rx.recv() {
+            Ok(Ok(())) => Ok(VoiceRecorder { stop_tx, handle }),
+            Ok(Err(err)) => Err(err),
+            Err(_) => Err(VoiceError::Device(
+                "recording thread exited before starting".to_string(),
+            )),
+        }
+    }
+
+    fn record(
+        max_duration: Duration,
+        stop_rx: std_mpsc::Receiver<()>,
+        ready_tx: std_mpsc::Sender<Result<(), VoiceError>>,
+    ) -> Result<Recording, VoiceError> {
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let (stream, sample_rate) = match open_stream(Arc::clone(&samples)) {
+            Ok(opened) => {
+                if ready_tx.send(Ok(())).is_err() {
+                    tracing::debug!("Voice recorder start was abandoned");
+                }
+                opened
+            }
+            Err(err) => {
+                let message = err.to_string();
+                if ready_tx.send(Err(err)).is_err() {
+                    tracing::debug!(error = %message, "Voice recorder start was abandoned");
+                }
+                return Err(VoiceError::Device(message));
+            }
+        };
+
+        match stop_rx.recv_timeout(max_duration) {
+            Ok(()) | Err(std_mpsc::RecvTimeoutError::Disconnected) => {}
+            Err(std_mpsc::RecvTimeoutError::Timeout) => {
+                tracing::info!(
+                    max_secs = max_duration.as_secs(),
+                    "Voice recording hit its time limit"
+                );
+            }
+        }
+        drop(stream);
+
+        let samples = std::mem::take(&mut *samples.lock());
+        Ok(Recording {
+            samples,
+            sample_rate,
+        })
+    }
+
+    fn open_stream(samples: Arc<Mutex<Vec<f32>>>) -> Result<(cpal::Stream, u32), VoiceError> {
+        let device = cpal::default_host()
+            .default_input_device()
+            .ok_or_else(|| VoiceError::Device("no default input device".to_string()))?;
+        let supported = device
+            .default_input_config()
+            .map_err(|e| VoiceError::Device(e.to_string()))?;
+        let sample_rate = supported.sample_rate().0;
+        let channels = usize::from(supported.channels()).max(1);
+        let format = supported.sample_format();
+        let config: cpal::StreamConfig = supported.into();
+
+        let stream = match format {
+            SampleFormat::F32 => build::<f32>(&device, &config, channels, samples),
+            SampleFormat::I16 => build::<i16>(&device, &config, channels, samples),
+            SampleFormat::U16 => build::<u16>(&device, &config, channels, samples),
+            other => {
+                return Err(VoiceError::Device(format!(
+                    "unsupported sample format {:?}",
+                    other
+                )))
+            }
+        }?;
+        stream
+            .play()
+            .map_err(|e| VoiceError::Device(e.to_string()))?;
+        Ok((stream, sample_rate))
+    }
+
+    fn build<T>(
+        device: &cpal::Device,
+        config: &cpal::StreamConfig,
+        channels: usize,
+        samples: Arc<Mutex<Vec<f32>>>,
+    ) -> Result<cpal::Stream, VoiceError>
+    where
+        T: SizedSample,
+        f32: cpal::FromSample<T>,
+    {
+        device
+            .build_input_stream(
+                config,
+                move |data: &[T], _: &cpal::InputCallbackInfo| {
+                    // Downmix interleaved frames to mono
+                    let mut buffer = samples.lock();
+                    buffer.extend(data.chunks(channels).map(|frame| {
+                        frame
+                            .iter()
+                            .map(|sample| cpal::Sample::to_sample::<f32>(*sample))
+                            .sum::<f32>()
+                            / frame.len() as f32
+                    }));
+                },
+                |err| tracing::warn!(error = %err, "Microphone stream error"),
+                None,
+            )
+            .map_err(|e| VoiceError::Device(e.to_string()))
+    }
+}
+
+/// Linear-interpolation resampler; plenty for speech headed to whisper
+pub fn resample_linear(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() || from_rate == 0 || to_rate == 0 {
+        return samples.to_vec();
+    }
+    let ratio = from_rate as f64 / to_rate as f64;
+    let output_len = (samples.len() as f64 / ratio).floor() as usize;
+    (0..output_len)
+        .map(|i| {
+            let position = i as f64 * ratio;
+            let index = position.floor() as usize;
+            let fraction = (position - index as f64) as f32;
+            let current = samples[index];
+            let next = samples.get(index + 1).copied().unwrap_or(current);
+            current + (next - current) * fraction
+        })
+        .collect()
+}
+
+/// Encode mono samples as a 16-bit PCM WAV file
+pub fn encode_wav(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16;
+        wav.extend_from_slice(&value.to_le_bytes());
+    }
+    wav
+}
+
+/// Transcribe a 16 kHz WAV with the configured backend
+pub async fn transcribe(config: &VoiceConfig, wav: Vec<u8>) -> Result<String, VoiceError> {
+    let text = match &config.backend {
+        VoiceBackend::WhisperCpp { binary, model } => {
+            let audio = tempfile::Builder::new()
+                .prefix("conduit-voice-")
+                .suffix(".wav")
+                .tempfile()?;
+            tokio::fs::write(audio.path(), &wav).await?;
+
+            let mut command = Command::new(binary);
+            command
+                .arg("-m")
+                .arg(model)
+                .arg("-f")
+                .arg(audio.path())
+                .args(["-nt", "-np"])
+                .stdin(Stdio::null())
+                .kill_on_drop(true);
+            if let Some(language) = &config.language {
+                command.arg("-l").arg(language);
+            }
+            let output = tokio::time::timeout(TRANSCRIBE_TIMEOUT, command.output())
+                .await
+                .map_err(|_| VoiceError::Transcription("whisper.cpp timed out".to_string()))?
+                .map_err(|e| {
+                    VoiceError::Transcription(format!("failed to run {}: {}", binary.display(), e))
+                })?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(VoiceError::Transcription(format!(
+                    "whisper.cpp exited with {}: {}",
+                    output.status,
+                    stderr.trim()
+                )));
+            }
+            clean_whisper_output(&String::from_utf8_lossy(&output.stdout))
+        }
+        VoiceBackend::Api {
+            base_url,
+            api_key,
+            model,
+        } => {
+            #[derive(Deserialize)]
+            struct TranscriptionResponse {
+                text: String,
+            }
+
+            let file = reqwest::multipart::Part::bytes(wav)
+                .file_name("prompt.wav")
+                .mime_str("audio/wav")
+                .map_err(|e| VoiceError::Transcription(e.to_string()))?;
+            let mut form = reqwest::multipart::Form::new()
+                .part("file", file)
+                .text("model", model.clone());
+            if let Some(language) = &config.language {
+                form = form.text("language", language.clone());
+            }
+
+            let mut request = reqwest::Client::new()
+                .post(format!("{}/audio/transcriptions", base_url))
+                .timeout(TRANSCRIBE_TIMEOUT)
+                .multipart(form);
+            if let Some(key) = api_key {
+                request = request.bearer_auth(key);
+            }
+            let response = request
+                .send()
+                .await
+                .map_err(|e| VoiceError::Transcription(e.to_string()))?;
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                return Err(VoiceError::Transcription(format!(
+                    "{} returned {}: {}",
+                    base_url,
+                    status,
+                    body.trim()
+                )));
+            }
+            let response: TranscriptionResponse = response
+                .json()
+                .await
+                .map_err(|e| VoiceError::Transcription(e.to_string()))?;
+            response.text.trim().to_string()
+        }
+    };
+
+    if text.is_empty() {
+        return Err(VoiceError::NoAudio);
+    }
+    Ok(text)
+}
+
+/// Join whisper.cpp's per-segment lines and drop non-speech markers like `[BLANK_AUDIO]`
+fn clean_whisper_output(stdout: &str) -> String {
+    stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !(line.starts_with('[') && line.ends_with(']')))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resample_linear_halves_and_interpolates() {
+        let samples = [0.0, 1.0, 0.0, -1.0, 0.0, 1.0];
+        assert_eq!(
+            resample_linear(&samples, 32_000, 16_000),
+            vec![0.0, 0.0, 0.0]
+        );
+
+        let upsampled = resample_linear(&[0.0, 1.0], 8_000, 16_000);
+        assert_eq!(upsampled, vec![0.0, 0.5, 1.0, 1.0]);
+
+        assert_eq!(resample_linear(&samples, 16_000, 16_000), samples.to_vec());
+    }
+
+    #[test]
+    fn test_encode_wav_writes_pcm_header() {
+        let wav = encode_wav(&[0.0, 1.0, -1.0], 16_000);
+        assert_eq!(wav.len(), 44 + 6);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16_000);
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 6);
+        assert_eq!(i16::from_le_bytes([wav[46], wav[47]]), i16::MAX);
+        assert_eq!(i16::from_le_bytes([wav[48], wav[49]]), -i16::MAX);
+    }
+
+    #[test]
+    fn test_short_recording_is_rejected() {
+        let recording = Recording {
+            samples: vec![0.0; 1_000],
+            sample_rate: 16_000,
+        };
+        assert!(matches!(
+            recording.to_whisper_wav(),
+            Err(VoiceError::NoAudio)
+        ));
+    }
+
+    #[test]
+    fn test_clean_whisper_output_joins_segments() {
+        let stdout = "\n [BLANK_AUDIO]\n Fix the failing test\n in the parser module.\n";
+        assert_eq!(
+            clean_whisper_output(stdout),
+            "Fix the failing test in the parser module."
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! Normalization of file paths reported by agent tools.
+//!
+//! Agents report the same file as `/repo/src/main.rs`, `src/main.rs`,
+//! `./src/main.rs`, `~/repo/src/main.rs` or `src\main.rs` depending on the
+//! tool. Change tracking keys files by one canonical form: forward slashes,
+//! relative to the workspace root when inside it, absolute otherwise. The
+//! cleanup is purely lexical so it also works for deleted files.
+
+use std::path::{Path, PathBuf};
+
+/// Lexically cleaned path: an optional root prefix plus normal components
+#[derive(Debug, PartialEq, Eq)]
+struct CleanPath {
+    /// `"/"` or a drive like `"C:/"` for absolute paths, empty when relative
+    root: String,
+    parts: Vec<String>,
+}
+
+impl CleanPath {
+    fn parse(raw: &str) -> Self {
+        let trimmed = raw.trim();
+        let unquoted = trimmed
+            .strip_prefix('"')
+            .and_then(|s| s.strip_suffix('"'))
+            .or_else(|| {
+                trimmed
+                    .strip_prefix('\'')
+                    .and_then(|s| s.strip_suffix('\''))
+            })
+            .unwrap_or(trimmed);
+        let mut path = unquoted.replace('\\', "/");
+
+        if path == "~" || path.starts_with("~/") {
+            if let Some(home) = dirs::home_dir() {
+                let home = home.to_string_lossy().replace('\\', "/");
+                path = format!("{}{}", home.trim_end_matches('/'), &path[1..]);
+            }
+        }
+
+        let (root, rest) = if let Some(rest) = path.strip_prefix('/') {
+            ("/".to_string(), rest)
+        } else if Self::has_drive_prefix(&path) {
+            let drive = path[..1].to_ascii_uppercase();
+            (format!("{}:/", drive), path[2..].trim_start_matches('/'))
+        } else {
+            (String::new(), path.as_str())
+        };
+
+        let mut parts: Vec<String> = Vec::new();
+        for part in rest.split('/') {
+            match part {
+                "" | "." => {}
+                ".." => match parts.last() {
+                    Some(last) if last != ".." => {
+                        parts.pop();
+                    }
+                    // Can't go above the filesystem root
+                    _ if !root.is_empty() => {}
+                    _ => parts.push(part.to_string()),
+                },
+                _ => parts.push(part.to_string()),
+            }
+        }
+        Self { root, parts }
+    }
+
+    /// `C:/...` or `C:` followed by nothing or a separator
+    fn has_drive_prefix(path: &str) -> bool {
+        let bytes = path.as_bytes();
+        bytes.len() >= 2
+            && bytes[0].is_ascii_alphabetic()
+            && bytes[1] == b':'
+            && (bytes.len() == 2 || bytes[2] == b'/')
+    }
+
+    fn is_absolute(&self) -> bool {
+        !self.root.is_empty()
+    }
+
+    /// Components of `self` below `base`, if `self` is inside it
+    fn relative_to(&self, base: &CleanPath) -> Option<&[String]> {
+        if self.root != base.root || self.parts.len() < base.parts.len() {
+            return None;
+        }
+        self.parts
+            .iter()
+            .zip(&base.parts)
+            .all(|(a, b)| a == b)
+            .then(|| &self.parts[base.parts.len()..])
+    }
+
+    fn to_string_with(root: &str, parts: &[String]) -> String {
+        let joined = parts.join("/");
+        match (root.is_empty(), joined.is_empty()) {
+            (true, true) => ".".to_string(),
+            (true, false) => joined,
+            (false, _) => format!("{}{}", root, joined),
+        }
+    }
+}
+
+/// Canonical key for a tool-reported path: relative to `root` with forward
+/// slashes when inside the workspace, absolute otherwise.
+pub fn normalize_workspace_path(raw: &str, root: Option<&Path>) -> String {
+    let path = CleanPath::parse(raw);
+    if path.is_absolute() {
+        if let Some(root) = root {
+            let root = CleanPath::parse(&root.to_string_lossy());
+            if let Some(rest) = path.relative_to(&root) {
+                return CleanPath::to_string_with("", rest);
+            }
+        }
+    }
+    CleanPath::to_string_with(&path.root, &path.parts)
+}
+
+/// Absolute location of a tool-reported path, for opening or diffing it
+pub fn resolve_workspace_path(raw: &str, root: &Path) -> PathBuf {
+    let normalized = normalize_workspace_path(raw, Some(root));
+    let path = PathBuf::from(&normalized);
+    if CleanPath::parse(&normalized).is_absolute() {
+        path
+    } else if normalized == "." {
+        root.to_path_buf()
+    } else {
+        root.join(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spellings_of_one_file_normalize_alike() {
+        let root = Path::new("/work/repo");
+        for raw in [
+            "/work/repo/src/main.rs",
+            "src/main.rs",
+            "./src/main.rs",
+            "src\\main.rs",
+            "src//lib/../main.rs",
+            "\"/work/repo/src/./main.rs\"",
+        ] {
+            assert_eq!(
+                normalize_workspace_path(raw, Some(root)),
+                "src/main.rs",
+                "{raw}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_paths_outside_workspace_stay_absolute() {
+        let root = Path::new("/work/repo");
+        assert_eq!(
+            normalize_workspace_path("/work/repo-other/a.rs", Some(root)),
+            "/work/repo-other/a.rs"
+        );
+        assert_eq!(
+            normalize_workspace_path("/work/repo/../other/a.rs", Some(root)),
+            "/work/other/a.rs"
+        );
+        assert_eq!(normalize_workspace_path("../a.rs", Some(root)), "../a.rs");
+    }
+
+    #[test]
+    fn test_windows_paths() {
+        let root = Path::new("C:\\work\\repo");
+        assert_eq!(
+            normalize_workspace_path("c:\\work\\repo\\src\\main.rs", Some(root)),
+            "src/main.rs"
+        );
+        assert_eq!(
+            normalize_workspace_path("D:\\other\\a.rs", Some(root)),
+            "D:/other/a.rs"
+        );
+    }
+
+    #[test]
+    fn test_home_relative_paths_expand() {
+        let Some(home) = dirs::home_dir() else {
+            return;
+        };
+        let root = home.join("repo");
+        assert_eq!(
+            normalize_workspace_path("~/repo/src/main.rs", Some(&root)),
+            "src/main.rs"
+        );
+    }
+
+    #[test]
+    fn test_resolve_joins_relative_paths_to_root() {
+        let root = Path::new("/work/repo");
+        assert_eq!(
+            resolve_workspace_path("./src/main.rs", root),
+            PathBuf::from("/work/repo/src/main.rs")
+        );
+        assert_eq!(
+            resolve_workspace_path("/etc/hosts", root),
+            PathBuf::from("/etc/hosts")
+        );
+        assert_eq!(
+            resolve_workspace_path(".", root),
+            PathBuf::from("/work/repo")
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! Session action handlers for the Conduit web API.
+//!
+//! Lets remote clients and hooks trigger a whitelisted subset of TUI actions
+//! by their keybinding config name (e.g. `interrupt_agent`).
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::agent::{AgentMode, AgentType, CodexApprovalPolicy, CodexSandboxMode};
+use crate::config::parse_action;
+use crate::core::services::{ServiceError, SessionService, UpdateSessionParams};
+use crate::ui::action::Action;
+use crate::ui::transcript_export;
+use crate::web::error::WebError;
+use crate::web::handlers::sessions::{load_history_for_session, SessionResponse};
+use crate::web::state::WebAppState;
+
+/// Actions that can be executed through the API, by config name.
+pub const API_ACTIONS: [&str; 5] = [
+    "interrupt_agent",
+    "toggle_agent_mode",
+    "cycle_codex_sandbox",
+    "cycle_codex_approval",
+    "export_transcript_pdf",
+];
+
+/// Request to execute a session action.
+#[derive(Debug, Deserialize)]
+pub struct ExecuteActionRequest {
+    /// Action config name, as used in `[keys]` bindings
+    pub action: String,
+}
+
+/// Files written by an export action.
+#[derive(Debug, Serialize)]
+pub struct ExportResponse {
+    pub html_path: String,
+    pub pdf_path: Option<String>,
+}
+
+/// Response after executing a session action.
+#[derive(Debug, Serialize)]
+pub struct ExecuteActionResponse {
+    pub action: String,
+    pub message: String,
+    /// Updated session, for actions that change session settings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<SessionResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub export: Option<ExportResponse>,
+}
+
+/// Resolve a normalized action name against the API whitelist.
+fn resolve_api_action(name: &str) -> Result<Action, WebError> {
+    if !API_ACTIONS.contains(&name) {
+        return Err(WebError::BadRequest(format!(
+            "Action '{}' is not available via the API. Must be one of: {}",
+            name,
+            API_ACTIONS.join(", ")
+        )));
+    }
+    parse_action(name).ok_or_else(|| WebError::Internal(format!("Unknown action: {}", name)))
+}
+
+/// Execute a whitelisted TUI action against a session.
+pub async fn execute_session_action(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<ExecuteActionRequest>,
+) -> Result<Json<ExecuteActionResponse>, WebError> {
+    let name = req.action.trim().to_lowercase();
+    let action = resolve_api_action(&name)?;
+    let session = {
+        let core = state.core().await;
+        SessionService::get_session(&core, id).map_err(map_service_error)?
+    };
+
+    let mut response = ExecuteActionResponse {
+        action: name,
+        message: String::new(),
+        session: None,
+        export: None,
+    };
+
+    match action {
+        Action::InterruptAgent => {
+            let session_manager = state.session_manager();
+            if session_manager.get_agent_type(id).await.is_none() {
+                return Err(WebError::Conflict(format!(
+                    "Session {} has no running agent",
+                    id
+                )));
+            }
+            session_manager
+                .stop_session(id)
+                .await
+                .map_err(WebError::Internal)?;
+            response.message = "Agent interrupted".to_string();
+        }
+        Action::ToggleAgentMode => {
+            if !session.agent_type.supports_plan_mode() {
+                return Err(WebError::BadRequest(format!(
+                    "{} sessions do not support plan mode",
+                    session.agent_type.display_name()
+                )));
+            }
+            let mode = session
+                .agent_mode
+                .as_deref()
+                .map(AgentMode::parse)
+                .unwrap_or_default()
+                .toggle();
+            let updated = update_session(
+                &state,
+                id,
+                UpdateSessionParams {
+                    agent_mode: Some(mode),
+                    ..Default::default()
+                },
+            )
+            .await?;
+            response.message = format!("Agent mode: {}", mode.display_name());
+            response.session = Some(SessionResponse::from(updated));
+        }
+        Action::CycleCodexSandbox => {
+            require_codex(session.agent_type)?;
+            let mode = session
+                .codex_sandbox
+                .as_deref()
+                .and_then(CodexSandboxMode::parse)
+                .unwrap_or_default()
+                .next();
+            let updated = update_session(
+                &state,
+                id,
+                UpdateSessionParams {
+                    codex_sandbox: Some(mode),
+                    ..Default::default()
+                },
+            )
+            .await?;
+            response.message = format!(
+                "Codex sandbox: {} (applies to next run)",
+                mode.display_name()
+            );
+            response.session = Some(SessionResponse::from(updated));
+        }
+        Action::CycleCodexApproval => {
+            require_codex(session.agent_type)?;
+            let policy = session
+                .codex_approval
+                .as_deref()
+                .and_then(CodexApprovalPolicy::parse)
+                .unwrap_or_default()
+                .next();
+            let updated = update_session(
+                &state,
+                id,
+                UpdateSessionParams {
+                    codex_approval: Some(policy),
+                    ..Default::default()
+                },
+            )
+            .await?;
+            response.message = format!(
+                "Codex approvals: {} (applies to next run)",
+                policy.display_name()
+            );
+            response.session = Some(SessionResponse::from(updated));
+        }
+        Action::ExportTranscriptPdf => {
+            let messages = load_history_for_session(&session);
+            if messages.is_empty() {
+                return Err(WebError::Conflict("Nothing to export yet".to_string()));
+            }
+            let title = session
+                .title
+                .clone()
+                .unwrap_or_else(|| "Conduit session".to_string());
+            let file_stem = format!(
+                "transcript_{}_{}",
+                chrono::Local::now().format("%Y%m%d_%H%M%S"),
+                session.id.simple()
+            );
+            let export = tokio::task::spawn_blocking(move || {
+                transcript_export::export_transcript(
+                    &title,
+                    &messages,
+                    &crate::util::exports_dir(),
+                    &file_stem,
+                )
+            })
+            .await
+            .map_err(|e| WebError::Internal(format!("Transcript export task failed: {}", e)))?
+            .map_err(WebError::Internal)?;
+
+            response.message = match export.pdf_path.as_ref() {
+                Some(pdf_path) => format!("Transcript exported to {}", pdf_path.display()),
+                None => format!("Transcript exported to {}", export.path.display()),
+            };
+            response.export = Some(ExportResponse {
+                html_path: export.path.to_string_lossy().to_string(),
+                pdf_path: export
+                    .pdf_path
+                    .map(|path| path.to_string_lossy().to_string()),
+            });
+        }
+        other => {
+            return Err(WebError::Internal(format!(
+                "Whitelisted action has no API handler: {:?}",
+                other
+            )));
+        }
+    }
+
+    Ok(Json(response))
+}
+
+fn require_codex(agent_type: AgentType) -> Result<(), WebError> {
+    if agent_type == AgentType::Codex {
+        Ok(())
+    } else {
+        Err(WebError::BadRequest(
+            "Sandbox and approval settings only apply to Codex sessions".to_string(),
+        ))
+    }
+}
+
+async fn update_session(
+    state: &WebAppState,
+    id: Uuid,
+    params: UpdateSessionParams,
+) -> Result<crate::data::SessionTab, WebError> {
+    let core = state.core().await;
+    SessionService::update_session(&core, id, params).map_err(map_service_error)
+}
+
+fn map_service_error(error: ServiceError) -> WebError {
+    match error {
+        ServiceError::InvalidInput(message) => WebError::BadRequest(message),
+        ServiceError::NotFound(message) => WebError::NotFound(message),
+        ServiceError::Internal(message) => WebError::Internal(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_api_action_accepts_whitelisted_names() {
+        for name in API_ACTIONS {
+            assert!(resolve_api_action(name).is_ok(), "{name} should resolve");
+        }
+        assert!(matches!(
+            resolve_api_action("interrupt_agent"),
+            Ok(Action::InterruptAgent)
+        ));
+    }
+
+    #[test]
+    fn test_resolve_api_action_rejects_other_actions() {
+        assert!(matches!(
+            resolve_api_action("quit"),
+            Err(WebError::BadRequest(_))
+        ));
+        assert!(matches!(
+            resolve_api_action("not_an_action"),
+            Err(WebError::BadRequest(_))
+        ));
+    }
+}
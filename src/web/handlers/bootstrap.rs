@@ -80,12 +80,16 @@ pub async fn get_bootstrap(
 
     Ok(Json(BootstrapResponse {
         ui_state: WebUiStateResponse::from(ui_state),
-        sessions: sessions.into_iter().map(SessionResponse::from).collect(),
+        sessions: sessions
+            .into_iter()
+            .map(|session| SessionResponse::from(session).with_git_stats(state.status_manager()))
+            .collect(),
         workspaces: workspaces
             .into_iter()
             .map(WorkspaceResponse::from)
             .collect(),
-        active_session: active_session.map(SessionResponse::from),
+        active_session: active_session
+            .map(|session| SessionResponse::from(session).with_git_stats(state.status_manager())),
         active_workspace: active_workspace.map(WorkspaceResponse::from),
     }))
 }
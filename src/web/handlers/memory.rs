@@ -0,0 +1,169 @@
+//! Workspace memory handlers for the Conduit web API.
+//!
+//! The shared context is saved per workspace and previewed as a diff against
+//! each agent's memory file; nothing is written until it is synced.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::memory::{self, MemoryFilePlan};
+use crate::core::ConduitCore;
+use crate::data::{Workspace, WorkspaceMemoryStore};
+use crate::web::error::WebError;
+use crate::web::state::WebAppState;
+
+/// Unchanged lines kept around each change in previews
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Shared context of a workspace with a preview of syncing it.
+#[derive(Debug, Serialize)]
+pub struct MemoryResponse {
+    pub workspace_id: Uuid,
+    pub content: String,
+    /// When the shared context was last saved (None = never saved)
+    pub updated_at: Option<String>,
+    pub files: Vec<MemoryFileResponse>,
+}
+
+/// One agent memory file and how a sync would change it.
+#[derive(Debug, Serialize)]
+pub struct MemoryFileResponse {
+    pub file_name: String,
+    pub exists: bool,
+    /// Shared context the file holds today, if it has a Conduit block
+    pub synced_content: Option<String>,
+    pub in_sync: bool,
+    /// Changes a sync would make, unified-diff style
+    pub diff: String,
+}
+
+impl From<&MemoryFilePlan> for MemoryFileResponse {
+    fn from(plan: &MemoryFilePlan) -> Self {
+        Self {
+            file_name: plan.file_name.to_string(),
+            exists: plan.current.is_some(),
+            synced_content: plan
+                .current
+                .as_deref()
+                .and_then(memory::managed_block)
+                .map(str::to_string),
+            in_sync: !plan.needs_write(),
+            diff: memory::format_diff(&plan.diff(), DIFF_CONTEXT_LINES),
+        }
+    }
+}
+
+/// Request to replace a workspace's shared context.
+#[derive(Debug, Deserialize)]
+pub struct UpdateMemoryRequest {
+    pub content: String,
+}
+
+/// Result of syncing the shared context into the memory files.
+#[derive(Debug, Serialize)]
+pub struct SyncMemoryResponse {
+    /// Files that were written; files already in sync are left untouched
+    pub written: Vec<String>,
+}
+
+/// Get a workspace's shared context and preview syncing it.
+///
+/// Before anything is saved, the context is read back from the first memory
+/// file that already has a Conduit block.
+pub async fn get_workspace_memory(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<MemoryResponse>, WebError> {
+    let core = state.core().await;
+    let workspace = load_workspace(&core, id)?;
+    let saved = memory_store(&core)?
+        .get(id)
+        .map_err(|e| WebError::Internal(format!("Failed to get workspace memory: {}", e)))?;
+
+    let (content, updated_at) = match saved {
+        Some(saved) => (saved.content, Some(saved.updated_at.to_rfc3339())),
+        None => {
+            let plans = plan(&workspace, "")?;
+            let synced = memory::synced_content(&plans).unwrap_or_default();
+            (synced.to_string(), None)
+        }
+    };
+    let files = plan(&workspace, &content)?
+        .iter()
+        .map(MemoryFileResponse::from)
+        .collect();
+    Ok(Json(MemoryResponse {
+        workspace_id: id,
+        content,
+        updated_at,
+        files,
+    }))
+}
+
+/// Save a workspace's shared context and preview syncing it.
+pub async fn update_workspace_memory(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<UpdateMemoryRequest>,
+) -> Result<Json<MemoryResponse>, WebError> {
+    let core = state.core().await;
+    let workspace = load_workspace(&core, id)?;
+    let saved = memory_store(&core)?
+        .save(id, req.content.trim())
+        .map_err(|e| WebError::Internal(format!("Failed to save workspace memory: {}", e)))?;
+
+    let files = plan(&workspace, &saved.content)?
+        .iter()
+        .map(MemoryFileResponse::from)
+        .collect();
+    Ok(Json(MemoryResponse {
+        workspace_id: id,
+        content: saved.content,
+        updated_at: Some(saved.updated_at.to_rfc3339()),
+        files,
+    }))
+}
+
+/// Write the saved shared context into the workspace's memory files.
+pub async fn sync_workspace_memory(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SyncMemoryResponse>, WebError> {
+    let core = state.core().await;
+    let workspace = load_workspace(&core, id)?;
+    let saved = memory_store(&core)?
+        .get(id)
+        .map_err(|e| WebError::Internal(format!("Failed to get workspace memory: {}", e)))?
+        .ok_or_else(|| {
+            WebError::BadRequest("Save the workspace memory before syncing it".to_string())
+        })?;
+
+    let plans = plan(&workspace, &saved.content)?;
+    let written = memory::apply_sync(&plans)
+        .map_err(|e| WebError::Internal(format!("Failed to sync workspace memory: {}", e)))?;
+    Ok(Json(SyncMemoryResponse {
+        written: written.into_iter().map(str::to_string).collect(),
+    }))
+}
+
+fn load_workspace(core: &ConduitCore, id: Uuid) -> Result<Workspace, WebError> {
+    core.workspace_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?
+        .get_by_id(id)
+        .map_err(|e| WebError::Internal(format!("Failed to get workspace: {}", e)))?
+        .ok_or_else(|| WebError::NotFound(format!("Workspace {} not found", id)))
+}
+
+fn memory_store(core: &ConduitCore) -> Result<&WorkspaceMemoryStore, WebError> {
+    core.workspace_memory_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))
+}
+
+fn plan(workspace: &Workspace, content: &str) -> Result<Vec<MemoryFilePlan>, WebError> {
+    memory::plan_sync(&workspace.path, content)
+        .map_err(|e| WebError::Internal(format!("Failed to read memory files: {}", e)))
+}
@@ -1,12 +1,16 @@
 //! HTTP request handlers for the Conduit web API.
 
+pub mod actions;
 pub mod bootstrap;
 pub mod external_sessions;
+pub mod memory;
 pub mod models;
 pub mod onboarding;
+pub mod public;
 pub mod queue;
 pub mod repositories;
 pub mod sessions;
+pub mod shares;
 pub mod themes;
 pub mod ui_state;
 pub mod workspaces;
@@ -3,9 +3,10 @@
 use axum::{extract::State, http::StatusCode, Json};
 use serde::Deserialize;
 
-use crate::agent::AgentType;
+use crate::agent::{AgentType, ModelRegistry};
 use crate::core::dto::ListModelsDto;
 use crate::core::services::{ConfigService, ModelService, ServiceError};
+use crate::util::Tool;
 use crate::web::error::WebError;
 use crate::web::state::WebAppState;
 
@@ -17,6 +18,25 @@ pub async fn list_models(
     Ok(Json(ModelService::list_models(&core)))
 }
 
+/// Re-query the installed agent CLIs for their models, bypassing the daily cache.
+pub async fn refresh_models(
+    State(state): State<WebAppState>,
+) -> Result<Json<ListModelsDto>, WebError> {
+    let (claude, codex) = {
+        let core = state.core().await;
+        (
+            core.tools().get_path(Tool::Claude).cloned(),
+            core.tools().get_path(Tool::Codex).cloned(),
+        )
+    };
+    tokio::task::spawn_blocking(move || ModelRegistry::refresh_cli_models(claude, codex, true))
+        .await
+        .map_err(|e| WebError::Internal(format!("Model refresh task failed: {}", e)))?;
+
+    let core = state.core().await;
+    Ok(Json(ModelService::list_models(&core)))
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SetDefaultModelRequest {
     pub agent_type: String,
@@ -33,9 +53,10 @@ pub async fn set_default_model(
         "codex" => AgentType::Codex,
         "gemini" => AgentType::Gemini,
         "opencode" => AgentType::Opencode,
+        "local" => AgentType::Local,
         _ => {
             return Err(WebError::BadRequest(format!(
-                "Invalid agent type: {}. Must be one of: claude, codex, gemini, opencode",
+                "Invalid agent type: {}. Must be one of: claude, codex, gemini, opencode, local",
                 payload.agent_type
             )));
         }
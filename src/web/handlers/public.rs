@@ -0,0 +1,278 @@
+//! Public, read-only transcript feed for share links.
+//!
+//! The types here are a published contract for external consumers (blog
+//! embeds, doc generators). They are intentionally decoupled from the
+//! internal chat types: add optional fields freely, but bump
+//! [`TRANSCRIPT_SCHEMA_VERSION`] for any rename, removal or change in meaning.
+//! The schema is documented in `docs/src/reference/transcript-schema.md`.
+
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::IntoResponse,
+    Json,
+};
+use serde::Serialize;
+
+use crate::ui::components::{ChatMessage, MessageRole, TurnReference, TurnSummary, VerifyOutcome};
+use crate::web::error::WebError;
+use crate::web::handlers::sessions::load_history_for_session;
+use crate::web::handlers::shares::{redact_tool_outputs, resolve_share};
+use crate::web::state::WebAppState;
+
+/// Current version of the public transcript schema.
+pub const TRANSCRIPT_SCHEMA_VERSION: u32 = 1;
+
+/// Top-level document served at `GET /public/sessions/{token}`.
+#[derive(Debug, Serialize)]
+pub struct PublicTranscript {
+    pub schema_version: u32,
+    pub session: PublicSession,
+    pub share: PublicShare,
+    pub messages: Vec<PublicMessage>,
+}
+
+/// Session metadata safe to expose publicly (no paths or internal IDs).
+#[derive(Debug, Serialize)]
+pub struct PublicSession {
+    pub title: Option<String>,
+    pub agent: &'static str,
+    pub model: Option<String>,
+    pub created_at: String,
+}
+
+/// Share link metadata.
+#[derive(Debug, Serialize)]
+pub struct PublicShare {
+    pub expires_at: String,
+    pub tool_output_redacted: bool,
+}
+
+/// Message role in the public schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PublicRole {
+    User,
+    Assistant,
+    Reasoning,
+    Plan,
+    Tool,
+    System,
+    Error,
+    TurnSummary,
+}
+
+/// A single transcript entry.
+#[derive(Debug, Serialize)]
+pub struct PublicMessage {
+    pub role: PublicRole,
+    /// Message text (tool output for `tool` messages; empty for `turn_summary`)
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<PublicToolCall>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turn_summary: Option<PublicTurnSummary>,
+}
+
+/// Tool invocation details for `tool` messages.
+#[derive(Debug, Serialize)]
+pub struct PublicToolCall {
+    pub name: String,
+    pub arguments: Option<String>,
+    pub exit_code: Option<i32>,
+}
+
+/// Per-turn statistics for `turn_summary` messages.
+#[derive(Debug, Serialize)]
+pub struct PublicTurnSummary {
+    pub duration_secs: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub files_changed: Vec<PublicFileChange>,
+    pub commands: Vec<PublicCommand>,
+    /// `passed` or `failed` for the last test or check command, if any ran
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checks: Option<&'static str>,
+    pub pull_requests: Vec<u32>,
+    pub commits: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicFileChange {
+    pub path: String,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicCommand {
+    pub command: String,
+    pub exit_code: Option<i32>,
+}
+
+impl From<&TurnSummary> for PublicTurnSummary {
+    fn from(summary: &TurnSummary) -> Self {
+        Self {
+            duration_secs: summary.duration_secs,
+            input_tokens: summary.input_tokens,
+            output_tokens: summary.output_tokens,
+            files_changed: summary
+                .files_changed
+                .iter()
+                .map(|file| PublicFileChange {
+                    path: file.filename.clone(),
+                    additions: file.additions,
+                    deletions: file.deletions,
+                })
+                .collect(),
+            commands: summary
+                .commands
+                .iter()
+                .map(|run| PublicCommand {
+                    command: run.command.clone(),
+                    exit_code: run.exit_code,
+                })
+                .collect(),
+            checks: summary.verification().map(|outcome| match outcome {
+                VerifyOutcome::Passed => "passed",
+                VerifyOutcome::Failed => "failed",
+            }),
+            pull_requests: summary
+                .references
+                .iter()
+                .filter_map(|reference| match reference {
+                    TurnReference::PullRequest(number) => Some(*number),
+                    TurnReference::Commit(_) => None,
+                })
+                .collect(),
+            commits: summary
+                .references
+                .iter()
+                .filter_map(|reference| match reference {
+                    TurnReference::Commit(sha) => Some(sha.clone()),
+                    TurnReference::PullRequest(_) => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&ChatMessage> for PublicMessage {
+    fn from(message: &ChatMessage) -> Self {
+        let role = match message.role {
+            MessageRole::User => PublicRole::User,
+            MessageRole::Assistant => PublicRole::Assistant,
+            MessageRole::Reasoning => PublicRole::Reasoning,
+            MessageRole::Plan => PublicRole::Plan,
+            MessageRole::Tool => PublicRole::Tool,
+            MessageRole::System => PublicRole::System,
+            MessageRole::Error => PublicRole::Error,
+            MessageRole::Summary => PublicRole::TurnSummary,
+        };
+        let tool = (role == PublicRole::Tool).then(|| PublicToolCall {
+            name: message
+                .tool_name
+                .clone()
+                .unwrap_or_else(|| "Tool".to_string()),
+            arguments: message.tool_args.clone().filter(|args| !args.is_empty()),
+            exit_code: message.exit_code,
+        });
+        let content = if role == PublicRole::TurnSummary {
+            String::new()
+        } else {
+            message.content.clone()
+        };
+
+        Self {
+            role,
+            content,
+            tool,
+            turn_summary: message.summary.as_ref().map(PublicTurnSummary::from),
+        }
+    }
+}
+
+/// Serve the shared transcript as versioned JSON.
+pub async fn get_public_transcript(
+    State(state): State<WebAppState>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, WebError> {
+    let core = state.core().await;
+    let (share, session) = resolve_share(&core, &token)?;
+
+    let mut messages = load_history_for_session(&session);
+    if share.redact_tool_output {
+        redact_tool_outputs(&mut messages);
+    }
+
+    let transcript = PublicTranscript {
+        schema_version: TRANSCRIPT_SCHEMA_VERSION,
+        session: PublicSession {
+            title: session.custom_title.clone().or(session.title.clone()),
+            agent: session.agent_type.as_str(),
+            model: session.model.clone(),
+            created_at: session.created_at.to_rfc3339(),
+        },
+        share: PublicShare {
+            expires_at: share.expires_at.to_rfc3339(),
+            tool_output_redacted: share.redact_tool_output,
+        },
+        messages: messages
+            .iter()
+            .filter(|message| !message.is_streaming)
+            .map(PublicMessage::from)
+            .collect(),
+    };
+
+    Ok(([(header::CACHE_CONTROL, "no-store")], Json(transcript)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::components::FileChange;
+
+    #[test]
+    fn test_tool_message_maps_to_tool_call() {
+        let message = ChatMessage::tool("Bash", "ls", "file.txt");
+        let json = serde_json::to_value(PublicMessage::from(&message)).unwrap();
+
+        assert_eq!(json["role"], "tool");
+        assert_eq!(json["content"], "file.txt");
+        assert_eq!(json["tool"]["name"], "Bash");
+        assert_eq!(json["tool"]["arguments"], "ls");
+        assert!(json.get("turn_summary").is_none());
+    }
+
+    #[test]
+    fn test_summary_message_maps_to_turn_summary() {
+        let mut summary = TurnSummary::new().with_duration(3).with_tokens(10, 5);
+        summary.files_changed.push(FileChange {
+            filename: "README.md".to_string(),
+            additions: 2,
+            deletions: 0,
+        });
+        summary.add_command(
+            "gh pr create --fill",
+            Some(true),
+            Some(0),
+            "https://github.com/o/r/pull/9",
+        );
+        let json =
+            serde_json::to_value(PublicMessage::from(&ChatMessage::turn_summary(summary))).unwrap();
+
+        assert_eq!(json["role"], "turn_summary");
+        assert_eq!(json["content"], "");
+        assert_eq!(
+            json["turn_summary"]["files_changed"][0]["path"],
+            "README.md"
+        );
+        assert_eq!(
+            json["turn_summary"]["commands"][0]["command"],
+            "gh pr create --fill"
+        );
+        assert_eq!(json["turn_summary"]["pull_requests"][0], 9);
+        assert!(json["turn_summary"].get("checks").is_none());
+        assert!(json.get("tool").is_none());
+    }
+}
@@ -28,6 +28,8 @@ pub struct RepositoryResponse {
     pub archive_delete_branch_effective: bool,
     pub archive_remote_prompt: Option<bool>,
     pub archive_remote_prompt_effective: bool,
+    pub system_prompt: Option<String>,
+    pub cost_center: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -46,6 +48,8 @@ impl RepositoryResponse {
             archive_delete_branch_effective: settings.archive_delete_branch,
             archive_remote_prompt: repo.archive_remote_prompt,
             archive_remote_prompt_effective: settings.archive_remote_prompt,
+            system_prompt: repo.system_prompt,
+            cost_center: repo.cost_center,
             created_at: repo.created_at.to_rfc3339(),
             updated_at: repo.updated_at.to_rfc3339(),
         }
@@ -72,6 +76,10 @@ pub struct UpdateRepositorySettingsRequest {
     pub workspace_mode: Option<WorkspaceMode>,
     pub archive_delete_branch: Option<bool>,
     pub archive_remote_prompt: Option<bool>,
+    /// Default base instructions for new sessions (empty string clears)
+    pub system_prompt: Option<String>,
+    /// Billing tag for spend in this repository's sessions (empty string clears)
+    pub cost_center: Option<String>,
 }
 
 /// List all repositories.
@@ -203,6 +211,20 @@ pub async fn update_repository_settings(
         )
         .map_err(|e| WebError::Internal(format!("Failed to update repository: {}", e)))?;
 
+    if let Some(system_prompt) = req.system_prompt.as_deref() {
+        let system_prompt = Some(system_prompt.trim()).filter(|p| !p.is_empty());
+        repo_store
+            .update_system_prompt(id, system_prompt)
+            .map_err(|e| WebError::Internal(format!("Failed to update repository: {}", e)))?;
+    }
+
+    if let Some(cost_center) = req.cost_center.as_deref() {
+        let cost_center = Some(cost_center.trim()).filter(|c| !c.is_empty());
+        repo_store
+            .update_cost_center(id, cost_center)
+            .map_err(|e| WebError::Internal(format!("Failed to update repository: {}", e)))?;
+    }
+
     let updated = repo_store
         .get_by_id(id)
         .map_err(|e| WebError::Internal(format!("Failed to load repository: {}", e)))?
@@ -2,28 +2,34 @@
 
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 use crate::agent::{
-    load_claude_history_with_debug, load_codex_history_with_debug,
-    load_opencode_history_with_debug, AgentMode, AgentType, ModelRegistry,
+    load_claude_history_with_debug, load_codex_history_with_debug, load_gemini_history_with_debug,
+    load_opencode_history_with_debug, AgentCapabilities, AgentMode, AgentType, CodexApprovalPolicy,
+    CodexSandboxMode, ModelRegistry, StderrLine,
 };
 use crate::core::resolve_repo_workspace_settings;
 use crate::core::services::session_service::CreateForkedSessionParams;
 use crate::core::services::{
     CreateSessionParams, ServiceError, SessionService, UpdateSessionParams,
 };
-use crate::data::{ForkSeed, SessionTab, Workspace};
+use crate::core::ConduitCore;
+use crate::data::{ForkSeed, ForkSeedStore, SessionReport, SessionTab, Workspace};
 use crate::ui::app_prompt;
-use crate::ui::components::{ChatMessage, MessageRole};
+use crate::ui::components::{ChatMessage, CommandRun, MessageRole, TurnReference, VerifyOutcome};
+use crate::ui::session_report::build_tab_report;
 use crate::util::names::{generate_branch_name, generate_workspace_name, get_git_username};
 use crate::web::error::WebError;
 use crate::web::handlers::workspaces::WorkspaceResponse;
 use crate::web::state::WebAppState;
+use crate::web::{GitDiffStatsResponse, StatusManager};
 
 /// Response for a single session.
 #[derive(Debug, Serialize)]
@@ -40,6 +46,45 @@ pub struct SessionResponse {
     pub pr_number: Option<i32>,
     pub created_at: String,
     pub title: Option<String>,
+    pub codex_sandbox: Option<String>,
+    pub codex_approval: Option<String>,
+    pub system_prompt: Option<String>,
+    pub total_cost_usd: f64,
+    pub response_language: Option<String>,
+    pub response_preset: Option<String>,
+    pub cost_center: Option<String>,
+    pub capabilities: AgentCapabilities,
+    /// Cached uncommitted diff stats for the session's workspace
+    pub git_stats: Option<GitDiffStatsResponse>,
+    /// Fork seed the session was started from, if it was forked or imported
+    pub fork_seed_id: Option<Uuid>,
+    /// Hash of that seed prompt, for tracing which transcript seeded it
+    pub fork_seed_hash: Option<String>,
+}
+
+impl SessionResponse {
+    /// Attach the workspace's cached git diff stats; never runs git itself.
+    pub fn with_git_stats(mut self, status_manager: &StatusManager) -> Self {
+        self.git_stats = self
+            .workspace_id
+            .and_then(|id| status_manager.get_status(id))
+            .and_then(|status| status.git_stats);
+        self
+    }
+
+    /// Attach the hash of the seed prompt the session was forked from.
+    pub fn with_fork_seed_hash(mut self, fork_seed_store: Option<&ForkSeedStore>) -> Self {
+        let (Some(seed_id), Some(store)) = (self.fork_seed_id, fork_seed_store) else {
+            return self;
+        };
+        match store.get_by_id(seed_id) {
+            Ok(seed) => self.fork_seed_hash = seed.map(|seed| seed.seed_prompt_hash),
+            Err(err) => {
+                tracing::warn!(%seed_id, error = %err, "Failed to load fork seed for session");
+            }
+        }
+        self
+    }
 }
 
 impl From<SessionTab> for SessionResponse {
@@ -61,7 +106,19 @@ impl From<SessionTab> for SessionResponse {
             model_invalid: session.model_invalid,
             pr_number: session.pr_number,
             created_at: session.created_at.to_rfc3339(),
-            title: session.title,
+            // A name the user gave the tab wins over the generated title
+            title: session.custom_title.or(session.title),
+            codex_sandbox: session.codex_sandbox,
+            codex_approval: session.codex_approval,
+            system_prompt: session.system_prompt,
+            total_cost_usd: session.total_cost_usd,
+            response_language: session.response_language,
+            response_preset: session.response_preset,
+            cost_center: session.cost_center,
+            capabilities: AgentCapabilities::for_agent(session.agent_type),
+            git_stats: None,
+            fork_seed_id: session.fork_seed_id,
+            fork_seed_hash: None,
         }
     }
 }
@@ -78,6 +135,8 @@ pub struct CreateSessionRequest {
     pub workspace_id: Option<Uuid>,
     pub agent_type: String,
     pub model: Option<String>,
+    /// Base instructions for the agent (defaults to the repository's)
+    pub system_prompt: Option<String>,
 }
 
 /// Request to update an existing session.
@@ -86,6 +145,10 @@ pub struct UpdateSessionRequest {
     pub model: Option<String>,
     pub agent_type: Option<String>,
     pub agent_mode: Option<String>,
+    pub codex_sandbox: Option<String>,
+    pub codex_approval: Option<String>,
+    /// Billing tag overriding the repository's (empty string clears)
+    pub cost_center: Option<String>,
 }
 
 /// List all sessions.
@@ -96,7 +159,10 @@ pub async fn list_sessions(
     let sessions = SessionService::list_sessions(&core).map_err(map_service_error)?;
 
     Ok(Json(ListSessionsResponse {
-        sessions: sessions.into_iter().map(SessionResponse::from).collect(),
+        sessions: sessions
+            .into_iter()
+            .map(|session| SessionResponse::from(session).with_git_stats(state.status_manager()))
+            .collect(),
     }))
 }
 
@@ -108,7 +174,11 @@ pub async fn get_session(
     let core = state.core().await;
     let session = SessionService::get_session(&core, id).map_err(map_service_error)?;
 
-    Ok(Json(SessionResponse::from(session)))
+    Ok(Json(
+        SessionResponse::from(session)
+            .with_git_stats(state.status_manager())
+            .with_fork_seed_hash(core.fork_seed_store()),
+    ))
 }
 
 /// Create a new session.
@@ -122,9 +192,10 @@ pub async fn create_session(
         "codex" => AgentType::Codex,
         "gemini" => AgentType::Gemini,
         "opencode" => AgentType::Opencode,
+        "local" => AgentType::Local,
         _ => {
             return Err(WebError::BadRequest(format!(
-                "Invalid agent type: {}. Must be one of: claude, codex, gemini, opencode",
+                "Invalid agent type: {}. Must be one of: claude, codex, gemini, opencode, local",
                 req.agent_type
             )));
         }
@@ -137,6 +208,7 @@ pub async fn create_session(
             workspace_id: req.workspace_id,
             agent_type,
             model: req.model,
+            system_prompt: req.system_prompt,
         },
     )
     .map_err(map_service_error)?;
@@ -150,7 +222,9 @@ pub async fn close_session(
     Path(id): Path<Uuid>,
 ) -> Result<StatusCode, WebError> {
     let core = state.core().await;
+    let session = SessionService::get_session(&core, id).map_err(map_service_error)?;
     SessionService::close_session(&core, id).map_err(map_service_error)?;
+    store_session_report(&core, &session);
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -171,8 +245,9 @@ pub async fn update_session(
                 "codex" => Ok(AgentType::Codex),
                 "gemini" => Ok(AgentType::Gemini),
                 "opencode" => Ok(AgentType::Opencode),
+                "local" => Ok(AgentType::Local),
                 _ => Err(WebError::BadRequest(format!(
-                    "Invalid agent type: {}. Must be one of: claude, codex, gemini, opencode",
+                    "Invalid agent type: {}. Must be one of: claude, codex, gemini, opencode, local",
                     agent_type_str
                 ))),
             },
@@ -192,6 +267,32 @@ pub async fn update_session(
         })
         .transpose()?;
 
+    let codex_sandbox = req
+        .codex_sandbox
+        .as_ref()
+        .map(|mode| {
+            CodexSandboxMode::parse(mode).ok_or_else(|| {
+                WebError::BadRequest(format!(
+                    "Invalid sandbox mode: {}. Must be one of: read-only, workspace-write, danger-full-access",
+                    mode
+                ))
+            })
+        })
+        .transpose()?;
+
+    let codex_approval = req
+        .codex_approval
+        .as_ref()
+        .map(|policy| {
+            CodexApprovalPolicy::parse(policy).ok_or_else(|| {
+                WebError::BadRequest(format!(
+                    "Invalid approval policy: {}. Must be one of: untrusted, on-failure, on-request, never",
+                    policy
+                ))
+            })
+        })
+        .transpose()?;
+
     let session = SessionService::update_session(
         &core,
         id,
@@ -199,6 +300,9 @@ pub async fn update_session(
             model: req.model.clone(),
             agent_type,
             agent_mode,
+            codex_sandbox,
+            codex_approval,
+            cost_center: req.cost_center.clone(),
         },
     )
     .map_err(map_service_error)?;
@@ -214,7 +318,7 @@ fn map_service_error(error: ServiceError) -> WebError {
     }
 }
 
-fn load_history_for_session(session: &SessionTab) -> Vec<ChatMessage> {
+pub(crate) fn load_history_for_session(session: &SessionTab) -> Vec<ChatMessage> {
     let Some(agent_session_id) = session.agent_session_id.as_deref() else {
         return Vec::new();
     };
@@ -232,13 +336,20 @@ fn load_history_for_session(session: &SessionTab) -> Vec<ChatMessage> {
                 tracing::warn!("Failed to load Codex history: {}", e);
                 Vec::new()
             }),
-        AgentType::Gemini => Vec::new(),
+        AgentType::Gemini => load_gemini_history_with_debug(agent_session_id)
+            .map(|(messages, _, _)| messages)
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to load Gemini history: {}", e);
+                Vec::new()
+            }),
         AgentType::Opencode => load_opencode_history_with_debug(agent_session_id)
             .map(|(messages, _, _)| messages)
             .unwrap_or_else(|e| {
                 tracing::warn!("Failed to load OpenCode history: {}", e);
                 Vec::new()
             }),
+        // Local model conversations only live in memory
+        AgentType::Local => Vec::new(),
     };
 
     if let Some(pending) = session.pending_user_message.as_ref() {
@@ -283,6 +394,12 @@ pub struct TurnSummaryResponse {
     pub duration_secs: u64,
     pub input_tokens: u64,
     pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_write_tokens: u64,
+    pub commands: Vec<CommandRun>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification: Option<VerifyOutcome>,
+    pub references: Vec<TurnReference>,
 }
 
 /// Response for session events.
@@ -304,6 +421,45 @@ pub struct InputHistoryResponse {
     pub history: Vec<String>,
 }
 
+/// Response for agent diagnostics.
+/// Final report of a closed or archived session.
+#[derive(Debug, Serialize)]
+pub struct SessionReportResponse {
+    pub session_id: Uuid,
+    pub title: Option<String>,
+    pub total_cost_usd: f64,
+    pub turns: u32,
+    pub files_changed: Vec<String>,
+    pub pr_number: Option<i32>,
+    pub pr_url: Option<String>,
+    pub checks_passed: Option<bool>,
+    pub created_at: String,
+}
+
+impl From<SessionReport> for SessionReportResponse {
+    fn from(report: SessionReport) -> Self {
+        Self {
+            session_id: report.session_id,
+            title: report.title,
+            total_cost_usd: report.total_cost_usd,
+            turns: report.turns,
+            files_changed: report.files_changed,
+            pr_number: report.pr_number,
+            pr_url: report.pr_url,
+            checks_passed: report.checks_passed,
+            created_at: report.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionDiagnosticsResponse {
+    pub session_id: Uuid,
+    pub agent_type: String,
+    /// Recent agent stderr lines, oldest first
+    pub stderr: Vec<StderrLine>,
+}
+
 /// Response for a forked session.
 #[derive(Debug, Serialize)]
 pub struct ForkSessionResponse {
@@ -335,11 +491,64 @@ pub struct SessionEventsQuery {
 }
 
 /// Get events/history for a session.
+///
+/// Responses carry an `ETag` derived from the body; a matching
+/// `If-None-Match` yields `304 Not Modified` so unchanged history is not resent.
 pub async fn get_session_events(
     State(state): State<WebAppState>,
     Path(id): Path<Uuid>,
     Query(query): Query<SessionEventsQuery>,
-) -> Result<Json<ListSessionEventsResponse>, WebError> {
+    headers: HeaderMap,
+) -> Result<Response, WebError> {
+    let response = load_session_events(&state, id, query).await?;
+    let body = serde_json::to_vec(&response)
+        .map_err(|e| WebError::Internal(format!("Failed to serialize session events: {}", e)))?;
+    let etag = body_etag(&body);
+
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        // Allow caching but force revalidation so new events always show up
+        (header::CACHE_CONTROL, "no-cache".to_string()),
+    ];
+    if if_none_match_matches(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+
+    Ok((
+        cache_headers,
+        [(header::CONTENT_TYPE, "application/json")],
+        body,
+    )
+        .into_response())
+}
+
+/// Strong entity tag for a response body.
+fn body_etag(body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+    let hex: String = digest[..16]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("\"{}\"", hex)
+}
+
+/// Check whether an `If-None-Match` header matches `etag`. Weak validators
+/// compare equal to their strong form, as required for GET revalidation.
+fn if_none_match_matches(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+async fn load_session_events(
+    state: &WebAppState,
+    id: Uuid,
+    query: SessionEventsQuery,
+) -> Result<ListSessionEventsResponse, WebError> {
     let core = state.core().await;
     let store = core
         .session_tab_store()
@@ -356,14 +565,14 @@ pub async fn get_session_events(
         Some(id) => id.clone(),
         None => {
             // No agent session ID means no history yet
-            return Ok(Json(ListSessionEventsResponse {
+            return Ok(ListSessionEventsResponse {
                 events: vec![],
                 total: 0,
                 offset: 0,
                 limit: 0,
                 debug_file: None,
                 debug_entries: vec![],
-            }));
+            });
         }
     };
 
@@ -393,10 +602,17 @@ pub async fn get_session_events(
                 vec![]
             }
         },
-        AgentType::Gemini => {
-            // Gemini history loading not supported yet
-            vec![]
-        }
+        AgentType::Gemini => match load_gemini_history_with_debug(&agent_session_id) {
+            Ok((msgs, entries, file_path)) => {
+                debug_entries = entries;
+                debug_file = Some(file_path.to_string_lossy().to_string());
+                msgs
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load Gemini history: {}", e);
+                vec![]
+            }
+        },
         AgentType::Opencode => match load_opencode_history_with_debug(&agent_session_id) {
             Ok((msgs, entries, file_path)) => {
                 debug_entries = entries;
@@ -408,6 +624,7 @@ pub async fn get_session_events(
                 vec![]
             }
         },
+        AgentType::Local => vec![],
     };
 
     let messages: Vec<ChatMessage> = messages
@@ -449,6 +666,7 @@ pub async fn get_session_events(
                 MessageRole::System => "system",
                 MessageRole::Error => "error",
                 MessageRole::Summary => "summary",
+                MessageRole::Plan => "plan",
             }
             .to_string();
 
@@ -456,6 +674,11 @@ pub async fn get_session_events(
                 duration_secs: s.duration_secs,
                 input_tokens: s.input_tokens,
                 output_tokens: s.output_tokens,
+                cache_read_tokens: s.cache_read_tokens,
+                cache_write_tokens: s.cache_write_tokens,
+                verification: s.verification(),
+                commands: s.commands,
+                references: s.references,
             });
 
             SessionEventResponse {
@@ -480,14 +703,14 @@ pub async fn get_session_events(
         })
         .collect();
 
-    Ok(Json(ListSessionEventsResponse {
+    Ok(ListSessionEventsResponse {
         events,
         total,
         offset,
         limit,
         debug_file,
         debug_entries,
-    }))
+    })
 }
 
 /// Get input history for a session.
@@ -500,6 +723,104 @@ pub async fn get_session_history(
     Ok(Json(InputHistoryResponse { history }))
 }
 
+/// Get the final report stored when a session was closed or archived.
+pub async fn get_session_report(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SessionReportResponse>, WebError> {
+    let core = state.core().await;
+    let store = core
+        .session_report_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+    let report = store
+        .get_by_session(id)
+        .map_err(|e| WebError::Internal(format!("Failed to get session report: {}", e)))?
+        .ok_or_else(|| WebError::NotFound(format!("No report for session {}", id)))?;
+    Ok(Json(SessionReportResponse::from(report)))
+}
+
+/// Build and store the final report of a session that was just closed.
+/// Failures are logged; closing the session doesn't depend on the report.
+pub(crate) fn store_session_report(core: &ConduitCore, session: &SessionTab) {
+    let Some(store) = core.session_report_store() else {
+        return;
+    };
+    let messages = load_history_for_session(session);
+    let report = build_tab_report(session, &messages);
+    if let Err(e) = store.save(&report) {
+        tracing::warn!(session_id = %session.id, error = %e, "Failed to store session report");
+    }
+}
+
+/// Get captured agent stderr for a session.
+pub async fn get_session_diagnostics(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SessionDiagnosticsResponse>, WebError> {
+    let agent_type = {
+        let core = state.core().await;
+        SessionService::get_session(&core, id)
+            .map_err(map_service_error)?
+            .agent_type
+    };
+    let stderr = state.session_manager().diagnostics(id).await;
+    Ok(Json(SessionDiagnosticsResponse {
+        session_id: id,
+        agent_type: agent_type.as_str().to_string(),
+        stderr,
+    }))
+}
+
+/// Serve a stored image attachment belonging to a session.
+pub async fn get_session_attachment(
+    State(state): State<WebAppState>,
+    Path((id, attachment_id)): Path<(Uuid, Uuid)>,
+) -> Result<Response, WebError> {
+    let (attachment, path) = {
+        let core = state.core().await;
+        let store = core
+            .attachment_store()
+            .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+        let attachment = store
+            .get_by_id(attachment_id)?
+            .filter(|attachment| attachment.session_id == id)
+            .ok_or_else(|| {
+                WebError::NotFound(format!(
+                    "Attachment {} not found in session {}",
+                    attachment_id, id
+                ))
+            })?;
+        let path = store.file_path(&attachment);
+        (attachment, path)
+    };
+
+    let body = match tokio::fs::read(&path).await {
+        Ok(body) => body,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(WebError::NotFound(format!(
+                "Attachment {} file is missing",
+                attachment_id
+            )));
+        }
+        Err(e) => {
+            return Err(WebError::Internal(format!(
+                "Failed to read attachment {}: {}",
+                attachment_id, e
+            )));
+        }
+    };
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, attachment.media_type),
+            // Attachment files never change once written
+            (header::CACHE_CONTROL, "private, max-age=86400".to_string()),
+        ],
+        body,
+    )
+        .into_response())
+}
+
 /// Fork a session into a new workspace and return the seed prompt.
 pub async fn fork_session(
     State(state): State<WebAppState>,
@@ -676,7 +997,10 @@ pub async fn fork_session(
     state.status_manager().refresh_workspace(new_workspace.id);
 
     Ok(Json(ForkSessionResponse {
-        session: SessionResponse::from(forked_session),
+        session: SessionResponse {
+            fork_seed_hash: Some(fork_seed.seed_prompt_hash),
+            ..SessionResponse::from(forked_session)
+        },
         workspace: WorkspaceResponse::from(new_workspace),
         warnings,
         token_estimate,
@@ -0,0 +1,214 @@
+//! Transcript share link handlers for the Conduit web API.
+
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{Html, IntoResponse},
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::core::services::{ServiceError, SessionService};
+use crate::core::ConduitCore;
+use crate::data::{SessionShare, SessionTab};
+use crate::ui::components::{ChatMessage, MessageRole};
+use crate::ui::transcript_export::render_transcript_html;
+use crate::web::error::WebError;
+use crate::web::handlers::sessions::load_history_for_session;
+use crate::web::state::WebAppState;
+
+/// Lifetime of a share link when the request does not specify one.
+const DEFAULT_SHARE_EXPIRY_HOURS: u32 = 24 * 7;
+/// Upper bound on share link lifetime.
+const MAX_SHARE_EXPIRY_HOURS: u32 = 24 * 30;
+/// Placeholder shown instead of tool output on redacted shares.
+const REDACTED_TOOL_OUTPUT: &str = "[output redacted]";
+
+/// Response for a single share link.
+#[derive(Debug, Serialize)]
+pub struct ShareResponse {
+    pub id: Uuid,
+    pub session_id: Uuid,
+    pub token: String,
+    /// Server-relative URL of the read-only transcript page
+    pub url: String,
+    pub redact_tool_output: bool,
+    pub active: bool,
+    pub created_at: String,
+    pub expires_at: String,
+    pub revoked_at: Option<String>,
+}
+
+impl From<SessionShare> for ShareResponse {
+    fn from(share: SessionShare) -> Self {
+        Self {
+            id: share.id,
+            session_id: share.session_id,
+            url: format!("/share/{}", share.token),
+            active: share.is_active_at(Utc::now()),
+            token: share.token,
+            redact_tool_output: share.redact_tool_output,
+            created_at: share.created_at.to_rfc3339(),
+            expires_at: share.expires_at.to_rfc3339(),
+            revoked_at: share.revoked_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+/// Response for listing a session's share links.
+#[derive(Debug, Serialize)]
+pub struct ListSharesResponse {
+    pub shares: Vec<ShareResponse>,
+}
+
+/// Request to create a share link.
+#[derive(Debug, Deserialize)]
+pub struct CreateShareRequest {
+    /// Hours until the link expires (defaults to 7 days, max 30 days)
+    pub expires_in_hours: Option<u32>,
+    /// Hide tool outputs from viewers
+    #[serde(default)]
+    pub redact_tool_output: bool,
+}
+
+/// Create a read-only share link for a session transcript.
+pub async fn create_share(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<CreateShareRequest>,
+) -> Result<(StatusCode, Json<ShareResponse>), WebError> {
+    let expires_in_hours = req.expires_in_hours.unwrap_or(DEFAULT_SHARE_EXPIRY_HOURS);
+    if !(1..=MAX_SHARE_EXPIRY_HOURS).contains(&expires_in_hours) {
+        return Err(WebError::BadRequest(format!(
+            "expires_in_hours must be between 1 and {}",
+            MAX_SHARE_EXPIRY_HOURS
+        )));
+    }
+
+    let core = state.core().await;
+    let session = SessionService::get_session(&core, id).map_err(map_service_error)?;
+    let store = core
+        .session_share_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+
+    let expires_at = Utc::now() + Duration::hours(i64::from(expires_in_hours));
+    let share = SessionShare::new(session.id, expires_at, req.redact_tool_output);
+    store
+        .create(&share)
+        .map_err(|e| WebError::Internal(format!("Failed to create share link: {}", e)))?;
+
+    Ok((StatusCode::CREATED, Json(ShareResponse::from(share))))
+}
+
+/// List share links for a session, including revoked and expired ones.
+pub async fn list_shares(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ListSharesResponse>, WebError> {
+    let core = state.core().await;
+    let store = core
+        .session_share_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+
+    let shares = store
+        .get_by_session(id)
+        .map_err(|e| WebError::Internal(format!("Failed to list share links: {}", e)))?;
+
+    Ok(Json(ListSharesResponse {
+        shares: shares.into_iter().map(ShareResponse::from).collect(),
+    }))
+}
+
+/// Revoke a share link.
+pub async fn revoke_share(
+    State(state): State<WebAppState>,
+    Path((id, share_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, WebError> {
+    let core = state.core().await;
+    let store = core
+        .session_share_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+
+    let share = store
+        .get_by_id(share_id)
+        .map_err(|e| WebError::Internal(format!("Failed to get share link: {}", e)))?
+        .filter(|share| share.session_id == id)
+        .ok_or_else(|| WebError::NotFound(format!("Share link {} not found", share_id)))?;
+
+    store
+        .revoke(share.id)
+        .map_err(|e| WebError::Internal(format!("Failed to revoke share link: {}", e)))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Serve the rendered transcript for an active share token.
+pub async fn view_shared_transcript(
+    State(state): State<WebAppState>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, WebError> {
+    let core = state.core().await;
+    let (share, session) = resolve_share(&core, &token)?;
+
+    let mut messages = load_history_for_session(&session);
+    if share.redact_tool_output {
+        redact_tool_outputs(&mut messages);
+    }
+    let title = session
+        .custom_title
+        .as_deref()
+        .or(session.title.as_deref())
+        .unwrap_or("Conduit session");
+    let html = render_transcript_html(title, &messages);
+
+    Ok((
+        [
+            (header::CACHE_CONTROL, "no-store"),
+            // Keep the token out of Referer headers on outbound links.
+            (header::REFERRER_POLICY, "no-referrer"),
+            (header::HeaderName::from_static("x-robots-tag"), "noindex"),
+        ],
+        Html(html),
+    ))
+}
+
+/// Look up an active share and its session. Unknown, revoked and expired
+/// tokens are all reported as not found so they cannot be told apart.
+pub(crate) fn resolve_share(
+    core: &ConduitCore,
+    token: &str,
+) -> Result<(SessionShare, SessionTab), WebError> {
+    let store = core
+        .session_share_store()
+        .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+
+    let share = store
+        .get_by_token(token)
+        .map_err(|e| WebError::Internal(format!("Failed to get share link: {}", e)))?
+        .filter(|share| share.is_active_at(Utc::now()))
+        .ok_or_else(|| WebError::NotFound("Share link not found".to_string()))?;
+
+    let session = SessionService::get_session(core, share.session_id).map_err(map_service_error)?;
+
+    Ok((share, session))
+}
+
+/// Replace tool outputs with a placeholder, keeping the tool name and arguments.
+pub(crate) fn redact_tool_outputs(messages: &mut [ChatMessage]) {
+    for message in messages
+        .iter_mut()
+        .filter(|message| message.role == MessageRole::Tool)
+    {
+        message.content = REDACTED_TOOL_OUTPUT.to_string();
+    }
+}
+
+fn map_service_error(error: ServiceError) -> WebError {
+    match error {
+        ServiceError::InvalidInput(message) => WebError::BadRequest(message),
+        ServiceError::NotFound(message) => WebError::NotFound(message),
+        ServiceError::Internal(message) => WebError::Internal(message),
+    }
+}
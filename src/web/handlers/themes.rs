@@ -4,6 +4,7 @@ use axum::Json;
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
 
+use crate::config::save_theme_config;
 use crate::ui::components::theme::{
     current_theme, list_themes, load_theme_by_name, ThemeInfo, ThemeSource,
 };
@@ -237,7 +238,8 @@ pub async fn get_current_theme() -> Json<ThemeResponse> {
     })
 }
 
-/// Set the current theme by name.
+/// Set the current theme by name and save it to the config file, so the
+/// TUI and the next launch pick the same theme.
 pub async fn set_current_theme(
     Json(req): Json<SetThemeRequest>,
 ) -> Result<Json<ThemeResponse>, WebError> {
@@ -248,5 +250,21 @@ pub async fn set_current_theme(
         )));
     }
 
+    let custom_path = list_themes()
+        .into_iter()
+        .find(|info| info.name == req.name)
+        .and_then(|info| match info.source {
+            ThemeSource::CustomPath { path } => Some(path),
+            _ => None,
+        });
+    let name = custom_path.is_none().then(|| req.name.clone());
+    tokio::task::spawn_blocking(move || save_theme_config(name.as_deref(), custom_path.as_deref()))
+        .await
+        .map_err(|err| WebError::Internal(format!("Failed to save theme: {err}")))?
+        .map_err(|err| {
+            tracing::warn!(theme = %req.name, error = %err, "Failed to save theme selection");
+            WebError::Internal(format!("Failed to save theme: {err}"))
+        })?;
+
     Ok(get_current_theme().await)
 }
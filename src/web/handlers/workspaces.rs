@@ -9,13 +9,14 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use uuid::Uuid;
 
+use crate::core::previews::Preview;
 use crate::core::resolve_repo_workspace_settings;
 use crate::core::services::{ServiceError, SessionService};
 use crate::data::Workspace;
 use crate::git::PrManager;
 use crate::util::names::{generate_branch_name, generate_workspace_name, get_git_username};
 use crate::web::error::WebError;
-use crate::web::handlers::sessions::SessionResponse;
+use crate::web::handlers::sessions::{self, SessionResponse};
 use crate::web::state::WebAppState;
 use crate::web::status_types::{PrStatusResponse, WorkspaceStatusResponse};
 
@@ -55,6 +56,12 @@ pub struct ListWorkspacesResponse {
     pub workspaces: Vec<WorkspaceResponse>,
 }
 
+/// Dev server previews running in a workspace.
+#[derive(Debug, Serialize)]
+pub struct WorkspacePreviewsResponse {
+    pub previews: Vec<Preview>,
+}
+
 /// PR preflight response for a workspace.
 #[derive(Debug, Serialize)]
 pub struct PrPreflightResponse {
@@ -448,6 +455,13 @@ pub async fn archive_workspace(
         .archive(id, archived_commit_sha)
         .map_err(|e| WebError::Internal(format!("Failed to archive workspace: {}", e)))?;
 
+    match session_store.get_open_by_workspace_id(id) {
+        Ok(Some(session)) => sessions::store_session_report(&core, &session),
+        Ok(None) => {}
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to load session of archived workspace for its report")
+        }
+    }
     if let Err(e) = session_store.set_open_by_workspace(id, false) {
         tracing::warn!(error = %e, "Failed to close sessions for archived workspace");
     }
@@ -607,6 +621,28 @@ pub async fn get_workspace_status(
     ))
 }
 
+/// Get the dev servers currently running in a workspace.
+pub async fn get_workspace_previews(
+    State(state): State<WebAppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<WorkspacePreviewsResponse>, WebError> {
+    let registry = {
+        let core = state.core().await;
+        let store = core
+            .workspace_store()
+            .ok_or_else(|| WebError::Internal("Database not available".to_string()))?;
+        store
+            .get_by_id(id)
+            .map_err(|e| WebError::Internal(format!("Failed to get workspace: {}", e)))?
+            .ok_or_else(|| WebError::NotFound(format!("Workspace {} not found", id)))?;
+        core.previews().clone()
+    };
+
+    // Probe outside the core lock; stopped servers are dropped
+    let previews = registry.prune(id).await;
+    Ok(Json(WorkspacePreviewsResponse { previews }))
+}
+
 /// Run PR preflight checks for a workspace.
 pub async fn get_workspace_pr_preflight(
     State(state): State<WebAppState>,
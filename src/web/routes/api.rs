@@ -1,13 +1,13 @@
 //! REST API route definitions.
 
 use axum::{
-    routing::{delete, get, patch, post},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 
 use crate::web::handlers::{
-    bootstrap, external_sessions, models, onboarding, queue, repositories, sessions, themes,
-    ui_state, workspaces,
+    actions, bootstrap, external_sessions, memory, models, onboarding, queue, repositories,
+    sessions, shares, themes, ui_state, workspaces,
 };
 use crate::web::state::WebAppState;
 
@@ -60,6 +60,10 @@ pub fn api_routes() -> Router<WebAppState> {
             "/workspaces/{id}/status",
             get(workspaces::get_workspace_status),
         )
+        .route(
+            "/workspaces/{id}/previews",
+            get(workspaces::get_workspace_previews),
+        )
         .route(
             "/workspaces/{id}/archive/preflight",
             get(workspaces::get_workspace_archive_preflight),
@@ -77,6 +81,15 @@ pub fn api_routes() -> Router<WebAppState> {
             "/workspaces/{id}/files/read",
             post(workspaces::read_workspace_file),
         )
+        .route("/workspaces/{id}/memory", get(memory::get_workspace_memory))
+        .route(
+            "/workspaces/{id}/memory",
+            put(memory::update_workspace_memory),
+        )
+        .route(
+            "/workspaces/{id}/memory/sync",
+            post(memory::sync_workspace_memory),
+        )
         // Session routes
         .route("/sessions", get(sessions::list_sessions))
         .route("/sessions", post(sessions::create_session))
@@ -85,7 +98,26 @@ pub fn api_routes() -> Router<WebAppState> {
         .route("/sessions/{id}", delete(sessions::close_session))
         .route("/sessions/{id}/events", get(sessions::get_session_events))
         .route("/sessions/{id}/history", get(sessions::get_session_history))
+        .route("/sessions/{id}/report", get(sessions::get_session_report))
+        .route(
+            "/sessions/{id}/diagnostics",
+            get(sessions::get_session_diagnostics),
+        )
+        .route(
+            "/sessions/{id}/attachments/{attachment_id}",
+            get(sessions::get_session_attachment),
+        )
         .route("/sessions/{id}/fork", post(sessions::fork_session))
+        .route(
+            "/sessions/{id}/actions",
+            post(actions::execute_session_action),
+        )
+        .route("/sessions/{id}/share", post(shares::create_share))
+        .route("/sessions/{id}/shares", get(shares::list_shares))
+        .route(
+            "/sessions/{id}/shares/{share_id}",
+            delete(shares::revoke_share),
+        )
         .route("/sessions/{id}/queue", get(queue::list_queue))
         .route("/sessions/{id}/queue", post(queue::add_queue_message))
         .route(
@@ -113,6 +145,7 @@ pub fn api_routes() -> Router<WebAppState> {
         // Model routes
         .route("/models", get(models::list_models))
         .route("/models/default", patch(models::set_default_model))
+        .route("/models/refresh", post(models::refresh_models))
         // Theme routes
         .route("/themes", get(themes::list_available_themes))
         .route("/themes/current", get(themes::get_current_theme))
@@ -10,13 +10,18 @@ use axum::{
     Json, Router,
 };
 use serde::Serialize;
+use tower_http::compression::CompressionLayer;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 
+use crate::agent::{AgentCapabilities, AgentRunner};
+
+use super::handlers::public::get_public_transcript;
+use super::handlers::shares::view_shared_transcript;
 use super::routes::api::api_routes;
 use super::routes::static_files::{serve_index, serve_static_file};
 use super::state::WebAppState;
-use super::ws::handle_websocket;
+use super::ws::{handle_websocket, WireFormat};
 
 /// Server configuration options.
 #[derive(Debug, Clone)]
@@ -27,6 +32,8 @@ pub struct ServerConfig {
     pub port: u16,
     /// Enable CORS for development (allows any origin).
     pub cors_permissive: bool,
+    /// Compress responses with gzip or brotli when the client accepts it.
+    pub compression: bool,
 }
 
 impl Default for ServerConfig {
@@ -35,6 +42,7 @@ impl Default for ServerConfig {
             host: "127.0.0.1".to_string(),
             port: 3000,
             cors_permissive: true,
+            compression: true,
         }
     }
 }
@@ -65,6 +73,7 @@ struct AgentInfo {
     id: &'static str,
     name: &'static str,
     available: bool,
+    capabilities: AgentCapabilities,
 }
 
 /// List available agents.
@@ -80,30 +89,48 @@ async fn list_agents(State(state): State<WebAppState>) -> Json<AgentsResponse> {
                 id: "claude",
                 name: "Claude Code",
                 available: tools.is_available(Tool::Claude),
+                capabilities: core.claude_runner().capabilities(),
             },
             AgentInfo {
                 id: "codex",
                 name: "Codex CLI",
                 available: tools.is_available(Tool::Codex),
+                capabilities: core.codex_runner().capabilities(),
             },
             AgentInfo {
                 id: "gemini",
                 name: "Gemini CLI",
                 available: tools.is_available(Tool::Gemini),
+                capabilities: core.gemini_runner().capabilities(),
             },
             AgentInfo {
                 id: "opencode",
                 name: "OpenCode",
                 available: tools.is_available(Tool::Opencode),
+                capabilities: core.opencode_runner().capabilities(),
+            },
+            AgentInfo {
+                id: "local",
+                name: "Local model",
+                available: core.local_runner().is_available(),
+                capabilities: core.local_runner().capabilities(),
             },
         ],
     })
 }
 
 /// WebSocket upgrade handler.
+///
+/// Clients may request `conduit.msgpack` via `Sec-WebSocket-Protocol` to
+/// receive MessagePack binary frames instead of JSON text frames.
 async fn ws_handler(ws: WebSocketUpgrade, State(state): State<WebAppState>) -> impl IntoResponse {
+    let ws = ws.protocols(WireFormat::SUBPROTOCOLS);
+    let format = WireFormat::from_subprotocol(
+        ws.selected_protocol()
+            .and_then(|protocol| protocol.to_str().ok()),
+    );
     ws.on_upgrade(move |socket| async move {
-        handle_websocket(socket, state.session_manager().clone()).await
+        handle_websocket(socket, state.session_manager().clone(), format).await
     })
 }
 
@@ -126,10 +153,22 @@ fn build_router(state: WebAppState, cors_permissive: bool) -> Router {
         .route("/health", get(health))
         .route("/agents", get(list_agents));
 
+    // Public transcript feed: read-only and token-gated, so any origin may embed it
+    let public_routes = Router::new()
+        .route("/sessions/{token}", get(get_public_transcript))
+        .layer(
+            CorsLayer::new()
+                .allow_origin(Any)
+                .allow_methods([Method::GET]),
+        );
+
     // Build main router combining core routes, REST API routes, and static files
     Router::new()
         .nest("/api", core_routes.merge(api_routes()))
+        .nest("/public", public_routes)
         .route("/ws", get(ws_handler))
+        // Read-only transcript pages for share links
+        .route("/share/{token}", get(view_shared_transcript))
         // Static file routes for frontend assets
         .route("/assets/{*path}", get(serve_static_file))
         .route("/", get(serve_index))
@@ -146,7 +185,10 @@ fn build_router(state: WebAppState, cors_permissive: bool) -> Router {
 pub async fn run_server(state: WebAppState, config: ServerConfig) -> anyhow::Result<()> {
     let addr: SocketAddr = format!("{}:{}", config.host, config.port).parse()?;
     state.start_status_manager().await;
-    let app = build_router(state, config.cors_permissive);
+    let mut app = build_router(state, config.cors_permissive);
+    if config.compression {
+        app = app.layer(CompressionLayer::new().gzip(true).br(true));
+    }
 
     tracing::info!("Starting web server at http://{}", addr);
 
@@ -430,4 +472,185 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
+
+    async fn post_json(app: &Router, uri: &str, body: serde_json::Value) -> serde_json::Value {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(uri)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_share_link_serves_transcript_and_feed_until_revoked() {
+        let state = test_state();
+        let app = build_router(state, true);
+
+        let session = post_json(
+            &app,
+            "/api/sessions",
+            serde_json::json!({ "agent_type": "claude", "model": "sonnet" }),
+        )
+        .await;
+        let session_id = session["id"].as_str().unwrap().to_string();
+
+        let share = post_json(
+            &app,
+            &format!("/api/sessions/{session_id}/share"),
+            serde_json::json!({ "expires_in_hours": 1, "redact_tool_output": true }),
+        )
+        .await;
+        let url = share["url"].as_str().unwrap().to_string();
+        let share_id = share["id"].as_str().unwrap().to_string();
+        let public_url = format!("/public/sessions/{}", share["token"].as_str().unwrap());
+        assert_eq!(share["active"], serde_json::json!(true));
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(&public_url)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let feed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(feed["schema_version"], serde_json::json!(1));
+        assert_eq!(feed["session"]["agent"], "claude");
+        assert_eq!(
+            feed["share"]["tool_output_redacted"],
+            serde_json::json!(true)
+        );
+        assert!(feed["messages"].is_array());
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(&url).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CACHE_CONTROL).unwrap(),
+            "no-store"
+        );
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri(format!("/api/sessions/{session_id}/shares/{share_id}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(&url).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(&public_url)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_share_link_rejects_invalid_expiry() {
+        let state = test_state();
+        let app = build_router(state, true);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/api/sessions/00000000-0000-0000-0000-000000000000/share")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(r#"{"expires_in_hours": 0}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_session_events_etag_returns_not_modified() {
+        let state = test_state();
+        let app = build_router(state, true);
+
+        let session = post_json(
+            &app,
+            "/api/sessions",
+            serde_json::json!({ "agent_type": "claude", "model": "sonnet" }),
+        )
+        .await;
+        let uri = format!("/api/sessions/{}/events", session["id"].as_str().unwrap());
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(&uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response
+            .headers()
+            .get(header::ETAG)
+            .expect("etag header")
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(&uri)
+                    .header(header::IF_NONE_MATCH, format!("W/{etag}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(body.is_empty());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(&uri)
+                    .header(header::IF_NONE_MATCH, "\"stale\"")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
 }
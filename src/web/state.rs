@@ -26,8 +26,9 @@ impl WebAppState {
     /// Create a new web application state from a ConduitCore.
     pub fn new(core: ConduitCore) -> Self {
         let status_config = StatusManagerConfig::from_config(core.config());
+        let max_concurrent_agents = core.config().queue.max_concurrent_agents;
         let inner = Arc::new(RwLock::new(core));
-        let session_manager = Arc::new(SessionManager::new(inner.clone()));
+        let session_manager = Arc::new(SessionManager::new(inner.clone(), max_concurrent_agents));
         let status_manager = Arc::new(StatusManager::new(status_config));
         Self {
             inner,
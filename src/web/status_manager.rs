@@ -17,6 +17,7 @@ use uuid::Uuid;
 use crate::config::Config;
 use crate::data::Workspace;
 use crate::git::{GitDiffStats, PrManager};
+use crate::util::network;
 use crate::web::status_types::{GitDiffStatsResponse, PrStatusResponse, WorkspaceStatusResponse};
 
 #[derive(Debug, Clone)]
@@ -264,6 +265,8 @@ impl StatusManager {
                 return;
             }
 
+            // Offline, PR status waits for the next retry and keeps its last value
+            let do_pr = do_pr && network::should_attempt();
             let pr_status = if do_pr {
                 let path = path.clone();
                 match tokio::task::spawn_blocking(move || {
@@ -307,7 +310,9 @@ impl StatusManager {
                 entry.last_git_at = Some(now);
             }
             if do_pr {
-                entry.status.pr_status = pr_status;
+                if pr_status.is_some() || !network::is_offline() {
+                    entry.status.pr_status = pr_status;
+                }
                 entry.last_pr_at = Some(now);
             }
             entry.status.updated_at = Some(Utc::now());
@@ -0,0 +1,299 @@
+//! Low-bandwidth relay for clients on slow links.
+//!
+//! A connection in low-bandwidth mode gets finalized messages instead of
+//! streaming deltas, and tool output longer than [`LIVE_OUTPUT_LIMIT`] is cut
+//! short. The full text stays on the server; the client fetches it with
+//! `fetch_content` when the user asks for it.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::messages::ServerMessage;
+use crate::agent::events::{AgentEvent, AssistantMessageEvent, ReasoningEvent};
+
+/// Bytes of tool or command output relayed live in low-bandwidth mode
+pub const LIVE_OUTPUT_LIMIT: usize = 4 * 1024;
+
+/// Full texts kept per connection for `fetch_content`; older ones are dropped
+const HELD_CONTENT_LIMIT: usize = 64;
+
+/// How much a connection wants relayed live
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BandwidthMode {
+    /// Every event as it happens, including streaming deltas
+    #[default]
+    Full,
+    /// Finalized messages only, with long output truncated
+    Low,
+}
+
+/// Marks an event whose output was truncated for the wire
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TruncatedContent {
+    /// ID to pass to `fetch_content` for the full text
+    pub content_id: Uuid,
+    /// Length of the full text in bytes
+    pub full_len: usize,
+}
+
+/// Relay settings of one connection, shared by its forwarding tasks
+#[derive(Debug, Default)]
+pub struct EventRelay {
+    low_bandwidth: AtomicBool,
+    held: Mutex<VecDeque<(Uuid, String)>>,
+}
+
+impl EventRelay {
+    pub fn set_mode(&self, mode: BandwidthMode) {
+        self.low_bandwidth
+            .store(mode == BandwidthMode::Low, Ordering::Relaxed);
+    }
+
+    pub fn mode(&self) -> BandwidthMode {
+        if self.low_bandwidth.load(Ordering::Relaxed) {
+            BandwidthMode::Low
+        } else {
+            BandwidthMode::Full
+        }
+    }
+
+    /// Full text of a truncated event, if it's still held
+    pub fn content(&self, content_id: Uuid) -> Option<String> {
+        self.held
+            .lock()
+            .iter()
+            .find(|(id, _)| *id == content_id)
+            .map(|(_, text)| text.clone())
+    }
+
+    /// Cut `text` to the live limit, holding on to the full text
+    fn truncate(&self, text: &mut String) -> Option<TruncatedContent> {
+        if text.len() <= LIVE_OUTPUT_LIMIT {
+            return None;
+        }
+        let mut cut = LIVE_OUTPUT_LIMIT;
+        while !text.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        let kept = text[..cut].to_string();
+        let full = std::mem::replace(text, kept);
+        let truncated = TruncatedContent {
+            content_id: Uuid::new_v4(),
+            full_len: full.len(),
+        };
+
+        let mut held = self.held.lock();
+        if held.len() >= HELD_CONTENT_LIMIT {
+            held.pop_front();
+        }
+        held.push_back((truncated.content_id, full));
+        Some(truncated)
+    }
+}
+
+/// Text one session has streamed that low-bandwidth mode hasn't sent yet
+#[derive(Debug, Default)]
+pub struct SessionRelay {
+    assistant: String,
+    reasoning: String,
+}
+
+impl SessionRelay {
+    /// Messages to send for one agent event. In low-bandwidth mode, deltas
+    /// are collected and sent as one message when something else happens.
+    pub fn relay(
+        &mut self,
+        session_id: Uuid,
+        event: AgentEvent,
+        relay: &EventRelay,
+    ) -> Vec<ServerMessage> {
+        let low = relay.mode() == BandwidthMode::Low;
+        let mut messages = Vec::new();
+        if !matches!(event, AgentEvent::AssistantReasoning(_)) {
+            messages.extend(self.flush_reasoning(session_id));
+        }
+        if !matches!(event, AgentEvent::AssistantMessage(_)) {
+            messages.extend(self.flush_assistant(session_id));
+        }
+
+        match event {
+            AgentEvent::AssistantReasoning(reasoning) if low => {
+                self.reasoning.push_str(&reasoning.text);
+            }
+            AgentEvent::AssistantReasoning(mut reasoning) => {
+                reasoning.text = std::mem::take(&mut self.reasoning) + &reasoning.text;
+                messages.push(ServerMessage::agent_event(
+                    session_id,
+                    AgentEvent::AssistantReasoning(reasoning),
+                ));
+            }
+            AgentEvent::AssistantMessage(message) if low && !message.is_final => {
+                self.assistant.push_str(&message.text);
+            }
+            AgentEvent::AssistantMessage(mut message) => {
+                message.text = std::mem::take(&mut self.assistant) + &message.text;
+                messages.push(ServerMessage::agent_event(
+                    session_id,
+                    AgentEvent::AssistantMessage(message),
+                ));
+            }
+            // The finished command's output follows as CommandOutput
+            AgentEvent::CommandOutputDelta(_) if low => {}
+            AgentEvent::CommandOutput(mut output) if low => {
+                let truncated = relay.truncate(&mut output.output);
+                messages.push(ServerMessage::AgentEvent {
+                    session_id,
+                    event: AgentEvent::CommandOutput(output),
+                    truncated,
+                });
+            }
+            AgentEvent::ToolCompleted(mut completed) if low => {
+                let truncated = completed
+                    .result
+                    .as_mut()
+                    .and_then(|result| relay.truncate(result));
+                messages.push(ServerMessage::AgentEvent {
+                    session_id,
+                    event: AgentEvent::ToolCompleted(completed),
+                    truncated,
+                });
+            }
+            event => messages.push(ServerMessage::agent_event(session_id, event)),
+        }
+        messages
+    }
+
+    /// Whatever is still collected when the session's events end
+    pub fn finish(&mut self, session_id: Uuid) -> Vec<ServerMessage> {
+        self.flush_reasoning(session_id)
+            .into_iter()
+            .chain(self.flush_assistant(session_id))
+            .collect()
+    }
+
+    fn flush_reasoning(&mut self, session_id: Uuid) -> Option<ServerMessage> {
+        if self.reasoning.is_empty() {
+            return None;
+        }
+        Some(ServerMessage::agent_event(
+            session_id,
+            AgentEvent::AssistantReasoning(ReasoningEvent {
+                text: std::mem::take(&mut self.reasoning),
+            }),
+        ))
+    }
+
+    fn flush_assistant(&mut self, session_id: Uuid) -> Option<ServerMessage> {
+        if self.assistant.is_empty() {
+            return None;
+        }
+        Some(ServerMessage::agent_event(
+            session_id,
+            AgentEvent::AssistantMessage(AssistantMessageEvent {
+                text: std::mem::take(&mut self.assistant),
+                is_final: false,
+            }),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::events::{ToolCompletedEvent, TurnCompletedEvent};
+
+    fn delta(text: &str, is_final: bool) -> AgentEvent {
+        AgentEvent::AssistantMessage(AssistantMessageEvent {
+            text: text.to_string(),
+            is_final,
+        })
+    }
+
+    fn assistant_texts(messages: &[ServerMessage]) -> Vec<(String, bool)> {
+        messages
+            .iter()
+            .filter_map(|message| match message {
+                ServerMessage::AgentEvent {
+                    event: AgentEvent::AssistantMessage(m),
+                    ..
+                } => Some((m.text.clone(), m.is_final)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_low_bandwidth_sends_only_the_finished_message() {
+        let relay = EventRelay::default();
+        relay.set_mode(BandwidthMode::Low);
+        let mut session = SessionRelay::default();
+        let id = Uuid::nil();
+
+        assert!(session.relay(id, delta("Hel", false), &relay).is_empty());
+        assert!(session.relay(id, delta("lo", false), &relay).is_empty());
+        let sent = session.relay(id, delta("", true), &relay);
+        assert_eq!(assistant_texts(&sent), vec![("Hello".to_string(), true)]);
+    }
+
+    #[test]
+    fn test_low_bandwidth_flushes_text_before_other_events() {
+        let relay = EventRelay::default();
+        relay.set_mode(BandwidthMode::Low);
+        let mut session = SessionRelay::default();
+        let id = Uuid::nil();
+
+        session.relay(id, delta("partial", false), &relay);
+        let sent = session.relay(
+            id,
+            AgentEvent::TurnCompleted(TurnCompletedEvent {
+                usage: Default::default(),
+            }),
+            &relay,
+        );
+        assert_eq!(sent.len(), 2);
+        assert_eq!(assistant_texts(&sent), vec![("partial".to_string(), false)]);
+    }
+
+    #[test]
+    fn test_long_tool_output_is_truncated_and_fetchable() {
+        let relay = EventRelay::default();
+        relay.set_mode(BandwidthMode::Low);
+        let mut session = SessionRelay::default();
+        let output = "é".repeat(LIVE_OUTPUT_LIMIT);
+
+        let sent = session.relay(
+            Uuid::nil(),
+            AgentEvent::ToolCompleted(ToolCompletedEvent {
+                tool_id: "t1".to_string(),
+                success: true,
+                result: Some(output.clone()),
+                error: None,
+            }),
+            &relay,
+        );
+        let [ServerMessage::AgentEvent {
+            event: AgentEvent::ToolCompleted(completed),
+            truncated: Some(truncated),
+            ..
+        }] = sent.as_slice()
+        else {
+            panic!("expected a truncated ToolCompleted, got {sent:?}");
+        };
+        assert!(completed.result.as_ref().unwrap().len() <= LIVE_OUTPUT_LIMIT);
+        assert_eq!(truncated.full_len, output.len());
+        assert_eq!(relay.content(truncated.content_id), Some(output));
+    }
+
+    #[test]
+    fn test_full_mode_passes_deltas_through() {
+        let relay = EventRelay::default();
+        let mut session = SessionRelay::default();
+        let sent = session.relay(Uuid::nil(), delta("Hel", false), &relay);
+        assert_eq!(assistant_texts(&sent), vec![("Hel".to_string(), false)]);
+    }
+}
@@ -2,26 +2,39 @@
 
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use axum::extract::ws::{Message, WebSocket};
 use base64::engine::general_purpose;
 use base64::Engine as _;
 use futures::{SinkExt, StreamExt};
-use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, OwnedSemaphorePermit, RwLock, Semaphore, TryAcquireError};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
-use crate::agent::events::AgentEvent;
-use crate::agent::runner::{AgentInput, AgentRunner, AgentStartConfig, AgentType};
+use crate::agent::diagnostics::{StderrBuffer, StderrLine};
+use crate::agent::events::{AgentEvent, TokenUsage};
+use crate::agent::exec_target::ExecutionTarget;
+use crate::agent::models::ModelRegistry;
+use crate::agent::runner::{
+    AgentCapabilities, AgentInput, AgentStartConfig, AgentType, CodexApprovalPolicy,
+    CodexSandboxMode, NO_PID,
+};
 use crate::agent::session::SessionId;
+use crate::config::PreviewConfig;
+use crate::core::hooks::{HookContext, HookRejection, HookStage, TurnReport};
+use crate::core::previews::{self, PreviewRegistry};
 use crate::core::services::{SessionService, UpdateSessionParams};
 use crate::core::ConduitCore;
+use crate::data::{AttachmentStore, SessionTab};
 use crate::ui::app_prompt;
+use crate::util::preview::detect_previews;
 use crate::util::{generate_title_and_branch, get_git_username, sanitize_branch_suffix};
 use serde_json::json;
 
-use super::messages::{ClientMessage, ImageAttachment, ServerMessage};
+use super::bandwidth::{EventRelay, SessionRelay};
+use super::messages::{ClientMessage, ImageAttachment, ServerMessage, WireFormat};
 
 /// Active session state tracked by the WebSocket handler.
 struct ActiveSession {
@@ -32,12 +45,28 @@ struct ActiveSession {
     event_tx: broadcast::Sender<AgentEvent>,
     /// Input sender for sending follow-up messages
     input_tx: Option<mpsc::Sender<AgentInput>>,
+    /// Stops an in-process agent, which has no PID to kill
+    cancel: Option<CancellationToken>,
+    /// Concurrent-agent slot, held from the start of a turn until it ends
+    slot: Option<OwnedSemaphorePermit>,
 }
 
 /// Manages active agent sessions and their event streams.
 pub struct SessionManager {
     sessions: Arc<RwLock<HashMap<Uuid, ActiveSession>>>,
     core: Arc<RwLock<ConduitCore>>,
+    /// Slots for `queue.max_concurrent_agents` (`None` = unlimited)
+    slots: Option<(Arc<Semaphore>, usize)>,
+    /// Captured agent stderr per session, kept after the run ends
+    stderr: Arc<RwLock<HashMap<Uuid, StderrBuffer>>>,
+}
+
+/// Outcome of asking for a concurrent-agent slot without waiting.
+enum SlotAttempt {
+    /// No limit is configured, or a slot was free
+    Ready(Option<OwnedSemaphorePermit>),
+    /// Every slot is busy
+    Full { limit: usize },
 }
 
 struct StartSessionArgs {
@@ -49,6 +78,7 @@ struct StartSessionArgs {
     images: Vec<PathBuf>,
     input_format: Option<String>,
     stdin_payload: Option<String>,
+    slot: Option<OwnedSemaphorePermit>,
 }
 
 struct TitleGenerationOutcome {
@@ -85,6 +115,154 @@ async fn persist_agent_session_id(
     Ok(())
 }
 
+async fn persist_turn_cost(
+    core: &Arc<RwLock<ConduitCore>>,
+    session_id: Uuid,
+    usage: &TokenUsage,
+) -> Result<(), String> {
+    let store = {
+        let core = core.read().await;
+        core.session_tab_store_clone()
+            .ok_or_else(|| "Database not available".to_string())?
+    };
+
+    let tab = store
+        .get_by_id(session_id)
+        .map_err(|e| format!("Failed to get session {}: {}", session_id, e))?
+        .ok_or_else(|| format!("Session {} not found in database", session_id))?;
+
+    // Unpriced models still report usage; there is simply nothing to add
+    let cost = core.read().await.record_turn_cost(
+        session_id,
+        tab.workspace_id,
+        tab.cost_center.as_deref(),
+        tab.agent_type,
+        tab.model.as_deref(),
+        usage,
+    );
+    let Some(cost) = cost else {
+        return Ok(());
+    };
+
+    store
+        .add_cost(session_id, cost)
+        .map_err(|e| format!("Failed to update cost for session {}: {}", session_id, e))
+}
+
+/// Hook context for a web session, falling back to its workspace path
+/// Register dev servers announced in tool output as workspace previews,
+/// forwarding new ones in the background when configured.
+fn record_previews(
+    registry: &PreviewRegistry,
+    config: &PreviewConfig,
+    target: &ExecutionTarget,
+    workspace_id: Uuid,
+    session_id: Uuid,
+    working_dir: &Path,
+    event: &AgentEvent,
+) {
+    let Some(output) = previews::event_output(event) else {
+        return;
+    };
+    let detected = detect_previews(output);
+    if detected.is_empty() {
+        return;
+    }
+    let added = registry.record(workspace_id, session_id, target, detected);
+    if added.is_empty() || !previews::needs_forward(config, target) {
+        return;
+    }
+    for preview in added {
+        let registry = registry.clone();
+        let config = config.clone();
+        let target = target.clone();
+        let working_dir = working_dir.to_path_buf();
+        tokio::spawn(async move {
+            if let Err(error) = previews::forward_preview(
+                &registry,
+                &config,
+                &target,
+                workspace_id,
+                &working_dir,
+                preview.port,
+            )
+            .await
+            {
+                tracing::warn!(
+                    %workspace_id,
+                    port = preview.port,
+                    error = %error,
+                    "Failed to forward dev server preview"
+                );
+            }
+        });
+    }
+}
+
+fn hook_context(core: &ConduitCore, tab: &SessionTab, working_dir: Option<PathBuf>) -> HookContext {
+    let working_dir = working_dir.or_else(|| {
+        let workspace_id = tab.workspace_id?;
+        match core.workspace_store()?.get_by_id(workspace_id) {
+            Ok(workspace) => workspace.map(|workspace| workspace.path),
+            Err(error) => {
+                tracing::warn!(%workspace_id, error = %error, "Failed to load workspace for hooks");
+                None
+            }
+        }
+    });
+    HookContext {
+        session_id: tab.id,
+        agent: tab.agent_type.as_str().to_string(),
+        model: tab.model.clone(),
+        working_dir,
+    }
+}
+
+async fn run_pre_submit_hooks(
+    core: &Arc<RwLock<ConduitCore>>,
+    tab: &SessionTab,
+    working_dir: Option<PathBuf>,
+    prompt: String,
+) -> Result<String, HookRejection> {
+    let (hooks, context) = {
+        let core = core.read().await;
+        let hooks = core.hooks();
+        if !hooks.has(HookStage::PreSubmit) {
+            return Ok(prompt);
+        }
+        (hooks, hook_context(&core, tab, working_dir))
+    };
+    hooks.run_pre_submit(&context, prompt).await
+}
+
+/// Hand a finished turn to the post-turn hooks without holding up the event stream
+async fn spawn_post_turn_hooks(
+    core: &Arc<RwLock<ConduitCore>>,
+    session_id: Uuid,
+    usage: &TokenUsage,
+) -> Result<(), String> {
+    let (hooks, context, cost_usd) = {
+        let core = core.read().await;
+        let hooks = core.hooks();
+        if !hooks.has(HookStage::PostTurn) {
+            return Ok(());
+        }
+        let tab = SessionService::get_session(&core, session_id)
+            .map_err(|e| format!("Failed to get session {}: {}", session_id, e))?;
+        let cost_usd = ModelRegistry::cost(tab.agent_type, tab.model.as_deref(), usage);
+        (hooks, hook_context(&core, &tab, None), cost_usd)
+    };
+    let turn = TurnReport {
+        usage: usage.clone(),
+        cost_usd,
+        ..TurnReport::default()
+    };
+    tokio::spawn(async move {
+        hooks.run_post_turn(&context, &turn).await;
+    });
+    Ok(())
+}
+
 async fn append_input_history(
     core: &Arc<RwLock<ConduitCore>>,
     session_id: Uuid,
@@ -125,11 +303,65 @@ async fn persist_pending_user_message(
 }
 
 impl SessionManager {
-    pub fn new(core: Arc<RwLock<ConduitCore>>) -> Self {
+    pub fn new(core: Arc<RwLock<ConduitCore>>, max_concurrent_agents: Option<usize>) -> Self {
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             core,
+            slots: max_concurrent_agents.map(|limit| (Arc::new(Semaphore::new(limit)), limit)),
+            stderr: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Stderr buffer for a session, created on first use.
+    async fn stderr_buffer(&self, session_id: Uuid) -> StderrBuffer {
+        let mut buffers = self.stderr.write().await;
+        buffers.entry(session_id).or_default().clone()
+    }
+
+    /// Recent stderr lines written by a session's agent processes.
+    pub async fn diagnostics(&self, session_id: Uuid) -> Vec<StderrLine> {
+        let buffers = self.stderr.read().await;
+        buffers
+            .get(&session_id)
+            .map(StderrBuffer::lines)
+            .unwrap_or_default()
+    }
+
+    /// Take a concurrent-agent slot if one is free.
+    fn try_acquire_slot(&self) -> SlotAttempt {
+        let Some((slots, limit)) = &self.slots else {
+            return SlotAttempt::Ready(None);
+        };
+        match slots.clone().try_acquire_owned() {
+            Ok(permit) => SlotAttempt::Ready(Some(permit)),
+            Err(TryAcquireError::NoPermits) => SlotAttempt::Full { limit: *limit },
+            // The semaphore is never closed; treat it as unlimited rather than blocking
+            Err(TryAcquireError::Closed) => SlotAttempt::Ready(None),
+        }
+    }
+
+    /// Wait for a concurrent-agent slot.
+    async fn acquire_slot(&self) -> Result<Option<OwnedSemaphorePermit>, String> {
+        let Some((slots, _)) = &self.slots else {
+            return Ok(None);
+        };
+        slots
+            .clone()
+            .acquire_owned()
+            .await
+            .map(Some)
+            .map_err(|e| format!("Failed to acquire agent slot: {}", e))
+    }
+
+    /// Whether input for a session starts a new turn that needs a slot.
+    async fn needs_slot(&self, session_id: Uuid) -> bool {
+        if self.slots.is_none() {
+            return false;
         }
+        let sessions = self.sessions.read().await;
+        sessions
+            .get(&session_id)
+            .is_some_and(|session| session.slot.is_none())
     }
 
     /// Start a new agent session.
@@ -146,6 +378,7 @@ impl SessionManager {
             images,
             input_format,
             stdin_payload,
+            slot,
         } = args;
 
         // Check if session already exists
@@ -159,21 +392,22 @@ impl SessionManager {
             }
         }
 
+        let stderr = self.stderr_buffer(session_id).await;
+
         // Get the appropriate runner
         let core = self.core.read().await;
-        let runner: Arc<dyn AgentRunner> = match agent_type {
-            AgentType::Claude => core.claude_runner().clone(),
-            AgentType::Codex => core.codex_runner().clone(),
-            AgentType::Gemini => core.gemini_runner().clone(),
-            AgentType::Opencode => core.opencode_runner().clone(),
-        };
+        let runner = core.runner_for(agent_type);
 
         if !runner.is_available() {
             return Err(format!("{} is not available", agent_type.display_name()));
         }
 
         // Build start config
-        let mut config = AgentStartConfig::new(prompt, working_dir);
+        let profile = core.config().agent_profile(agent_type);
+        let mut config = AgentStartConfig::new(prompt, working_dir)
+            .with_additional_args(profile.args)
+            .with_env(profile.env)
+            .with_stderr_buffer(stderr);
         if let Some(m) = model {
             config = config.with_model(m);
         }
@@ -187,23 +421,53 @@ impl SessionManager {
             config = config.with_stdin_payload(payload);
         }
 
-        if agent_type == AgentType::Opencode {
-            match SessionService::get_session(&core, session_id) {
-                Ok(session_tab) => {
+        // Workspace and execution target, for dev server previews
+        let mut preview_workspace: Option<(Uuid, ExecutionTarget)> = None;
+        match SessionService::get_session(&core, session_id) {
+            Ok(session_tab) => {
+                if agent_type == AgentType::Opencode {
                     if let Some(agent_session_id) = session_tab.agent_session_id {
                         config = config.with_resume(SessionId::from_string(agent_session_id));
                     }
                 }
-                Err(error) => {
-                    tracing::warn!(
-                        %session_id,
-                        error = %error,
-                        "Failed to load session for OpenCode resume"
-                    );
+                if agent_type == AgentType::Codex {
+                    if let Some(mode) = session_tab
+                        .codex_sandbox
+                        .as_deref()
+                        .and_then(CodexSandboxMode::parse)
+                    {
+                        config = config.with_codex_sandbox(mode);
+                    }
+                    if let Some(policy) = session_tab
+                        .codex_approval
+                        .as_deref()
+                        .and_then(CodexApprovalPolicy::parse)
+                    {
+                        config = config.with_codex_approval(policy);
+                    }
+                }
+                if let Some(system_prompt) = session_tab.system_prompt {
+                    config = config.with_system_prompt(system_prompt);
+                }
+                if let Some(workspace_id) = session_tab.workspace_id {
+                    let target = core.execution_target_for_workspace(workspace_id);
+                    config = config.with_execution_target(target.clone());
+                    preview_workspace = Some((workspace_id, target));
                 }
             }
+            Err(error) => {
+                tracing::warn!(
+                    %session_id,
+                    error = %error,
+                    "Failed to load session settings"
+                );
+            }
         }
 
+        let preview_dir = config.working_dir.clone();
+        let preview_registry = core.previews().clone();
+        let preview_config = core.config().preview.clone();
+
         // Start the agent
         let mut handle = runner
             .start(config)
@@ -225,6 +489,7 @@ impl SessionManager {
 
         let pid = handle.pid;
         let input_tx = handle.input_tx.take();
+        let cancel = handle.cancel.clone();
 
         // Reuse an existing event channel if we already have one (e.g. if the UI subscribed
         // before the session started). This prevents "Session <id> not found" errors when
@@ -239,6 +504,8 @@ impl SessionManager {
                 existing.agent_type = agent_type;
                 existing.pid = Some(pid);
                 existing.input_tx = input_tx;
+                existing.cancel = cancel;
+                existing.slot = slot;
                 (existing.event_tx.clone(), existing.event_tx.subscribe())
             } else {
                 let (event_tx, event_rx) = broadcast::channel(256);
@@ -249,6 +516,8 @@ impl SessionManager {
                         pid: Some(pid),
                         event_tx: event_tx.clone(),
                         input_tx,
+                        cancel,
+                        slot,
                     },
                 );
                 (event_tx, event_rx)
@@ -260,6 +529,19 @@ impl SessionManager {
         let core_ref = self.core.clone();
         tokio::spawn(async move {
             while let Some(event) = handle.events.recv().await {
+                if let Some((workspace_id, target)) = &preview_workspace {
+                    if preview_config.enabled {
+                        record_previews(
+                            &preview_registry,
+                            &preview_config,
+                            target,
+                            *workspace_id,
+                            session_id,
+                            &preview_dir,
+                            &event,
+                        );
+                    }
+                }
                 if let AgentEvent::SessionInit(init) = &event {
                     if let Err(error) =
                         persist_agent_session_id(&core_ref, session_id, init.session_id.as_str())
@@ -288,6 +570,38 @@ impl SessionManager {
                     }
                 }
 
+                if let AgentEvent::TurnCompleted(turn) = &event {
+                    core_ref.read().await.usage_stats().record_turn(agent_type);
+                    if let Err(error) = persist_turn_cost(&core_ref, session_id, &turn.usage).await
+                    {
+                        tracing::warn!(
+                            %session_id,
+                            error = %error,
+                            "Failed to persist session cost"
+                        );
+                    }
+                    if let Err(error) =
+                        spawn_post_turn_hooks(&core_ref, session_id, &turn.usage).await
+                    {
+                        tracing::warn!(
+                            %session_id,
+                            error = %error,
+                            "Failed to run post-turn hooks"
+                        );
+                    }
+                }
+
+                // The turn is over: free its slot for other sessions
+                if matches!(
+                    event,
+                    AgentEvent::TurnCompleted(_) | AgentEvent::TurnFailed(_)
+                ) {
+                    let mut sessions = sessions_ref.write().await;
+                    if let Some(active) = sessions.get_mut(&session_id) {
+                        active.slot = None;
+                    }
+                }
+
                 if let Err(error) = event_tx.send(event) {
                     tracing::debug!(
                         %session_id,
@@ -344,6 +658,8 @@ impl SessionManager {
                 pid: None,
                 event_tx,
                 input_tx: None,
+                cancel: None,
+                slot: None,
             },
         );
 
@@ -354,11 +670,15 @@ impl SessionManager {
     pub async fn stop_session(&self, session_id: Uuid) -> Result<(), String> {
         let mut sessions = self.sessions.write().await;
         if let Some(session) = sessions.remove(&session_id) {
+            if let Some(cancel) = &session.cancel {
+                cancel.cancel();
+            }
             // Kill the process by PID
             #[cfg(unix)]
             {
                 use std::process::Command;
-                if let Some(pid) = session.pid {
+                // Replayed sessions are not backed by a process
+                if let Some(pid) = session.pid.filter(|pid| *pid != NO_PID) {
                     match Command::new("kill")
                         .arg("-TERM")
                         .arg(pid.to_string())
@@ -385,7 +705,8 @@ impl SessionManager {
             #[cfg(windows)]
             {
                 use std::process::Command;
-                if let Some(pid) = session.pid {
+                // Replayed sessions are not backed by a process
+                if let Some(pid) = session.pid.filter(|pid| *pid != NO_PID) {
                     match Command::new("taskkill")
                         .args(["/PID", &pid.to_string(), "/F"])
                         .status()
@@ -419,27 +740,33 @@ impl SessionManager {
         input: String,
         images: Vec<PathBuf>,
         model: Option<String>,
+        slot: Option<OwnedSemaphorePermit>,
     ) -> Result<(), String> {
         let (input_tx, agent_type) = {
-            let sessions = self.sessions.read().await;
+            let mut sessions = self.sessions.write().await;
             let session = sessions
-                .get(&session_id)
+                .get_mut(&session_id)
                 .ok_or_else(|| format!("Session {} not found", session_id))?;
             let input_tx = session
                 .input_tx
                 .clone()
                 .ok_or_else(|| "Session does not support input".to_string())?;
+            if slot.is_some() {
+                session.slot = slot;
+            }
             (input_tx, session.agent_type)
         };
 
         // Send as appropriate input type based on agent
         let agent_input = match agent_type {
             AgentType::Claude => AgentInput::ClaudeJsonl(input),
-            AgentType::Codex | AgentType::Gemini | AgentType::Opencode => AgentInput::CodexPrompt {
-                text: input,
-                images,
-                model,
-            },
+            AgentType::Codex | AgentType::Gemini | AgentType::Opencode | AgentType::Local => {
+                AgentInput::CodexPrompt {
+                    text: input,
+                    images,
+                    model,
+                }
+            }
         };
 
         input_tx
@@ -676,20 +1003,33 @@ async fn generate_title_and_branch_for_session(
     }
 
     Ok(Some(TitleGenerationOutcome {
-        title: sanitized_title,
+        // A tab the user already named keeps showing that name
+        title: session.custom_title.unwrap_or(sanitized_title),
         workspace_id,
         new_branch,
     }))
 }
 
-fn decode_image_attachments(images: &[ImageAttachment]) -> Result<Vec<PathBuf>, String> {
+/// Write decoded images to the session's managed attachments, each referenced
+/// by the message being sent. Falls back to the shared uploads dir when the
+/// database is unavailable.
+fn decode_image_attachments(
+    images: &[ImageAttachment],
+    store: Option<&AttachmentStore>,
+    session_id: Uuid,
+) -> Result<Vec<PathBuf>, String> {
     if images.is_empty() {
         return Ok(Vec::new());
     }
 
     let mut paths = Vec::with_capacity(images.len());
     for image in images {
-        paths.push(decode_image_attachment(image)?);
+        paths.push(decode_image_attachment(image, store, session_id)?);
+    }
+    if let Some(store) = store {
+        if let Err(e) = store.retain_paths(&paths) {
+            tracing::warn!(error = %e, %session_id, "Failed to retain sent attachments");
+        }
     }
     Ok(paths)
 }
@@ -753,8 +1093,19 @@ fn build_claude_prompt_jsonl(prompt: &str, images: &[ImageAttachment]) -> Result
     Ok(format!("{json}\n"))
 }
 
-fn decode_image_attachment(image: &ImageAttachment) -> Result<PathBuf, String> {
+fn decode_image_attachment(
+    image: &ImageAttachment,
+    store: Option<&AttachmentStore>,
+    session_id: Uuid,
+) -> Result<PathBuf, String> {
     let (bytes, media_type) = decode_base64_image(&image.data, &image.media_type)?;
+    if let Some(store) = store {
+        let attachment = store
+            .create_from_bytes(session_id, &bytes, &media_type)
+            .map_err(|e| format!("Failed to store image: {}", e))?;
+        return Ok(store.file_path(&attachment));
+    }
+
     let ext = match media_type.as_str() {
         "image/png" => "png",
         "image/jpeg" => "jpg",
@@ -795,8 +1146,259 @@ fn uploads_dir() -> Result<PathBuf, String> {
     Ok(dir)
 }
 
+/// Encode a server message as a frame in the negotiated format.
+fn encode_server_message(msg: &ServerMessage, format: WireFormat) -> Result<Message, String> {
+    match format {
+        WireFormat::Json => serde_json::to_string(msg)
+            .map(|json| Message::Text(json.into()))
+            .map_err(|e| e.to_string()),
+        WireFormat::MessagePack => msg
+            .to_msgpack()
+            .map(|bytes| Message::Binary(bytes.into()))
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// Send a session's agent events to this connection until the session's
+/// events end. Returns false once the connection is closed.
+async fn forward_events(
+    session_id: Uuid,
+    mut event_rx: broadcast::Receiver<AgentEvent>,
+    tx: &mpsc::Sender<ServerMessage>,
+    relay: &EventRelay,
+) -> bool {
+    let mut session_relay = SessionRelay::default();
+    while let Ok(event) = event_rx.recv().await {
+        for message in session_relay.relay(session_id, event, relay) {
+            if tx.send(message).await.is_err() {
+                return false;
+            }
+        }
+    }
+    for message in session_relay.finish(session_id) {
+        if tx.send(message).await.is_err() {
+            return false;
+        }
+    }
+    true
+}
+
+/// Tasks owned by one WebSocket connection, keyed by session.
+type ConnectionTasks = Arc<RwLock<HashMap<Uuid, tokio::task::JoinHandle<()>>>>;
+
+/// A validated StartSession request, ready to launch once a slot is free.
+struct PendingLaunch {
+    args: StartSessionArgs,
+    /// Prompt as typed by the user (for history and title generation)
+    prompt: String,
+    hidden: bool,
+    should_generate: bool,
+}
+
+/// Start the agent and subscribe this connection to its events.
+///
+/// Only fails when the connection's outgoing channel is closed.
+async fn launch_session(
+    session_manager: &Arc<SessionManager>,
+    tx: &mpsc::Sender<ServerMessage>,
+    subscriptions: &ConnectionTasks,
+    relay: &Arc<EventRelay>,
+    launch: PendingLaunch,
+) -> Result<(), mpsc::error::SendError<ServerMessage>> {
+    let PendingLaunch {
+        args,
+        prompt,
+        hidden,
+        should_generate,
+    } = launch;
+    let session_id = args.session_id;
+    let agent_type = args.agent_type;
+    let working_dir_path = args.working_dir.clone();
+
+    match session_manager.start_session(args).await {
+        Ok(event_rx) => {
+            if !hidden {
+                if let Err(error) =
+                    append_input_history(&session_manager.core, session_id, &prompt).await
+                {
+                    tracing::warn!(
+                        %session_id,
+                        error = %error,
+                        "Failed to persist input history"
+                    );
+                }
+            }
+
+            if should_generate {
+                let core_ref = session_manager.core.clone();
+                let tx_clone = tx.clone();
+                let prompt_for_title = prompt.clone();
+                let working_dir_for_title = working_dir_path.clone();
+                tokio::spawn(async move {
+                    match generate_title_and_branch_for_session(
+                        core_ref,
+                        session_id,
+                        prompt_for_title,
+                        working_dir_for_title,
+                    )
+                    .await
+                    {
+                        Ok(Some(outcome)) => {
+                            if let Err(error) = tx_clone
+                                .send(ServerMessage::SessionMetadata {
+                                    session_id,
+                                    title: Some(outcome.title),
+                                    workspace_id: outcome.workspace_id,
+                                    workspace_branch: outcome.new_branch,
+                                })
+                                .await
+                            {
+                                tracing::debug!(
+                                    %session_id,
+                                    error = ?error,
+                                    "Failed to send session metadata update"
+                                );
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(error) => {
+                            tracing::warn!(
+                                %session_id,
+                                error = %error,
+                                "Failed to generate session title"
+                            );
+                        }
+                    }
+                });
+            }
+
+            // Auto-subscribe to the new session
+            let tx_clone = tx.clone();
+            let relay = relay.clone();
+            let task = tokio::spawn(async move {
+                if !forward_events(session_id, event_rx, &tx_clone, &relay).await {
+                    return;
+                }
+                // Session ended
+                if let Err(send_err) = tx_clone
+                    .send(ServerMessage::SessionEnded {
+                        session_id,
+                        reason: "completed".to_string(),
+                        error: None,
+                    })
+                    .await
+                {
+                    tracing::debug!(
+                        %session_id,
+                        error = ?send_err,
+                        "Failed to send session ended"
+                    );
+                }
+            });
+
+            let mut subs = subscriptions.write().await;
+            if let Some(existing) = subs.insert(session_id, task) {
+                existing.abort();
+            }
+
+            if let Err(send_err) = tx
+                .send(ServerMessage::session_started(session_id, agent_type, None))
+                .await
+            {
+                tracing::debug!(
+                    %session_id,
+                    error = ?send_err,
+                    "Failed to send session started"
+                );
+                return Err(send_err);
+            }
+        }
+        Err(e) => {
+            if let Err(send_err) = tx.send(ServerMessage::session_error(session_id, e)).await {
+                tracing::debug!(
+                    %session_id,
+                    error = ?send_err,
+                    "Failed to send session error"
+                );
+                return Err(send_err);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A validated SendInput request, ready to deliver once a slot is free.
+struct PendingInput {
+    session_id: Uuid,
+    /// Input as typed by the user (for history)
+    input: String,
+    /// Agent-specific payload sent to the running process
+    payload: String,
+    images: Vec<PathBuf>,
+    model: Option<String>,
+    hidden: bool,
+    slot: Option<OwnedSemaphorePermit>,
+}
+
+/// Send follow-up input to a running session and persist it.
+///
+/// Only fails when the connection's outgoing channel is closed.
+async fn deliver_input(
+    session_manager: &Arc<SessionManager>,
+    tx: &mpsc::Sender<ServerMessage>,
+    pending: PendingInput,
+) -> Result<(), mpsc::error::SendError<ServerMessage>> {
+    let PendingInput {
+        session_id,
+        input,
+        payload,
+        images,
+        model,
+        hidden,
+        slot,
+    } = pending;
+
+    if let Err(e) = session_manager
+        .send_input(session_id, payload, images, model, slot)
+        .await
+    {
+        if let Err(send_err) = tx.send(ServerMessage::session_error(session_id, e)).await {
+            tracing::debug!(
+                %session_id,
+                error = ?send_err,
+                "Failed to send session error"
+            );
+            return Err(send_err);
+        }
+    } else if !hidden {
+        if let Err(error) =
+            persist_pending_user_message(&session_manager.core, session_id, &input).await
+        {
+            tracing::warn!(
+                %session_id,
+                error = %error,
+                "Failed to persist pending user message"
+            );
+        }
+        if let Err(error) = append_input_history(&session_manager.core, session_id, &input).await {
+            tracing::warn!(
+                %session_id,
+                error = %error,
+                "Failed to persist input history"
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Handle a WebSocket connection.
-pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionManager>) {
+pub async fn handle_websocket(
+    socket: WebSocket,
+    session_manager: Arc<SessionManager>,
+    format: WireFormat,
+) {
     let (mut ws_sender, mut ws_receiver) = socket.split();
 
     // Channel for sending messages to the WebSocket
@@ -805,27 +1407,37 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
     // Spawn task to forward messages to WebSocket
     let send_task = tokio::spawn(async move {
         while let Some(msg) = rx.recv().await {
-            let json = match serde_json::to_string(&msg) {
-                Ok(j) => j,
+            let frame = match encode_server_message(&msg, format) {
+                Ok(frame) => frame,
                 Err(e) => {
-                    tracing::error!("Failed to serialize message: {}", e);
+                    tracing::error!(?format, "Failed to serialize message: {}", e);
                     continue;
                 }
             };
-            if ws_sender.send(Message::Text(json.into())).await.is_err() {
+            if ws_sender.send(frame).await.is_err() {
                 break;
             }
         }
     });
 
     // Track subscriptions for this connection
-    let subscriptions: Arc<RwLock<HashMap<Uuid, tokio::task::JoinHandle<()>>>> =
-        Arc::new(RwLock::new(HashMap::new()));
+    let subscriptions: ConnectionTasks = Arc::new(RwLock::new(HashMap::new()));
+    // Bandwidth mode and truncated output shared by its forwarding tasks
+    let relay = Arc::new(EventRelay::default());
+    // Starts and inputs waiting for a concurrent-agent slot
+    let waiting: ConnectionTasks = Arc::new(RwLock::new(HashMap::new()));
 
     // Handle incoming messages
     'ws_loop: while let Some(result) = ws_receiver.next().await {
-        let msg = match result {
-            Ok(Message::Text(text)) => text,
+        // JSON text frames are always accepted; binary frames only once
+        // MessagePack has been negotiated.
+        let decoded = match result {
+            Ok(Message::Text(text)) => {
+                serde_json::from_str::<ClientMessage>(&text).map_err(|e| e.to_string())
+            }
+            Ok(Message::Binary(bytes)) if format == WireFormat::MessagePack => {
+                ClientMessage::from_msgpack(&bytes).map_err(|e| e.to_string())
+            }
             Ok(Message::Close(_)) => break,
             Ok(Message::Ping(_)) => {
                 // Pings are handled automatically by axum
@@ -838,7 +1450,7 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
             }
         };
 
-        let client_msg: ClientMessage = match serde_json::from_str(&msg) {
+        let client_msg = match decoded {
             Ok(m) => m,
             Err(e) => {
                 if let Err(send_err) = tx
@@ -863,20 +1475,34 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                 }
             }
 
+            ClientMessage::SetBandwidth { mode } => {
+                relay.set_mode(mode);
+                if let Err(send_err) = tx.send(ServerMessage::BandwidthSet { mode }).await {
+                    tracing::debug!(error = ?send_err, "Failed to send bandwidth set");
+                    break 'ws_loop;
+                }
+            }
+
+            ClientMessage::FetchContent { content_id } => {
+                let reply = match relay.content(content_id) {
+                    Some(text) => ServerMessage::Content { content_id, text },
+                    None => {
+                        ServerMessage::error(format!("Content {content_id} is no longer available"))
+                    }
+                };
+                if let Err(send_err) = tx.send(reply).await {
+                    tracing::debug!(error = ?send_err, "Failed to send content");
+                    break 'ws_loop;
+                }
+            }
+
             ClientMessage::Subscribe { session_id } => {
                 match session_manager.subscribe(session_id).await {
-                    Ok(mut event_rx) => {
+                    Ok(event_rx) => {
                         let tx_clone = tx.clone();
+                        let relay = relay.clone();
                         let task = tokio::spawn(async move {
-                            while let Ok(event) = event_rx.recv().await {
-                                if tx_clone
-                                    .send(ServerMessage::agent_event(session_id, event))
-                                    .await
-                                    .is_err()
-                                {
-                                    break;
-                                }
-                            }
+                            forward_events(session_id, event_rx, &tx_clone, &relay).await;
                         });
 
                         let mut subs = subscriptions.write().await;
@@ -999,6 +1625,9 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                                 model: Some(model_id),
                                 agent_type: None,
                                 agent_mode: None,
+                                codex_sandbox: None,
+                                codex_approval: None,
+                                cost_center: None,
                             },
                         ) {
                             if let Err(send_err) = tx
@@ -1035,24 +1664,97 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                 let agent_type = session_tab.agent_type;
                 let should_generate = should_generate_title(hidden, &session_tab);
                 drop(core);
-
-                let mut input_format: Option<String> = None;
-                let mut stdin_payload: Option<String> = None;
-                let working_dir_path = PathBuf::from(working_dir);
-                let prompt_for_agent = if agent_type == AgentType::Claude {
-                    String::new()
-                } else {
+                // Hooks rewrite what the agent sees; history keeps what was typed
+                let hooked_prompt = if hidden {
                     prompt.clone()
-                };
-
-                let image_paths = if images.is_empty() {
-                    Vec::new()
                 } else {
-                    match agent_type {
-                        AgentType::Codex => match decode_image_attachments(&images) {
-                            Ok(paths) => paths,
-                            Err(error) => {
-                                if let Err(send_err) = tx
+                    match run_pre_submit_hooks(
+                        &session_manager.core,
+                        &session_tab,
+                        Some(PathBuf::from(&working_dir)),
+                        prompt.clone(),
+                    )
+                    .await
+                    {
+                        Ok(prompt) => prompt,
+                        Err(rejection) => {
+                            if let Err(send_err) = tx
+                                .send(ServerMessage::session_error(
+                                    session_id,
+                                    rejection.to_string(),
+                                ))
+                                .await
+                            {
+                                tracing::debug!(
+                                    %session_id,
+                                    error = ?send_err,
+                                    "Failed to send session error"
+                                );
+                                break 'ws_loop;
+                            }
+                            continue;
+                        }
+                    }
+                };
+                let agent_prompt = if hidden {
+                    hooked_prompt
+                } else {
+                    let hooked_prompt = app_prompt::append_language_directive(
+                        &hooked_prompt,
+                        session_tab.response_language.as_deref(),
+                    );
+                    app_prompt::append_preset_directive(
+                        &hooked_prompt,
+                        session_tab
+                            .response_preset
+                            .as_deref()
+                            .and_then(app_prompt::ResponsePreset::parse),
+                    )
+                };
+
+                let mut input_format: Option<String> = None;
+                let mut stdin_payload: Option<String> = None;
+                let working_dir_path = PathBuf::from(working_dir);
+                let prompt_for_agent = if agent_type == AgentType::Claude {
+                    String::new()
+                } else {
+                    agent_prompt.clone()
+                };
+
+                if !images.is_empty() && !AgentCapabilities::for_agent(agent_type).supports_images {
+                    if let Err(send_err) = tx
+                        .send(ServerMessage::session_error(
+                            session_id,
+                            format!(
+                                "Image attachments are not supported for {} sessions",
+                                agent_type.display_name()
+                            ),
+                        ))
+                        .await
+                    {
+                        tracing::debug!(
+                            %session_id,
+                            error = ?send_err,
+                            "Failed to send session error"
+                        );
+                        break 'ws_loop;
+                    }
+                    continue;
+                }
+
+                let attachment_store = session_manager.core.read().await.attachment_store_clone();
+                let image_paths = if images.is_empty() {
+                    Vec::new()
+                } else {
+                    match agent_type {
+                        AgentType::Codex => match decode_image_attachments(
+                            &images,
+                            attachment_store.as_ref(),
+                            session_id,
+                        ) {
+                            Ok(paths) => paths,
+                            Err(error) => {
+                                if let Err(send_err) = tx
                                     .send(ServerMessage::session_error(session_id, error))
                                     .await
                                 {
@@ -1067,7 +1769,7 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                             }
                         },
                         AgentType::Claude => {
-                            match build_claude_prompt_jsonl(&prompt, &images) {
+                            match build_claude_prompt_jsonl(&agent_prompt, &images) {
                                 Ok(payload) => {
                                     input_format = Some("stream-json".to_string());
                                     stdin_payload = Some(payload);
@@ -1089,45 +1791,13 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                             }
                             Vec::new()
                         }
-                        AgentType::Gemini => {
-                            if let Err(send_err) = tx
-                                .send(ServerMessage::session_error(
-                                    session_id,
-                                    "Image attachments are not supported for Gemini sessions",
-                                ))
-                                .await
-                            {
-                                tracing::debug!(
-                                    %session_id,
-                                    error = ?send_err,
-                                    "Failed to send session error"
-                                );
-                                break 'ws_loop;
-                            }
-                            continue;
-                        }
-                        AgentType::Opencode => {
-                            if let Err(send_err) = tx
-                                .send(ServerMessage::session_error(
-                                    session_id,
-                                    "Image attachments are not supported for OpenCode sessions",
-                                ))
-                                .await
-                            {
-                                tracing::debug!(
-                                    %session_id,
-                                    error = ?send_err,
-                                    "Failed to send session error"
-                                );
-                                break 'ws_loop;
-                            }
-                            continue;
-                        }
+                        // Rejected above: these agents don't support images
+                        AgentType::Gemini | AgentType::Opencode | AgentType::Local => Vec::new(),
                     }
                 };
 
                 if agent_type == AgentType::Claude && stdin_payload.is_none() {
-                    match build_claude_prompt_jsonl(&prompt, &[]) {
+                    match build_claude_prompt_jsonl(&agent_prompt, &[]) {
                         Ok(payload) => {
                             input_format = Some("stream-json".to_string());
                             stdin_payload = Some(payload);
@@ -1149,131 +1819,85 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                     }
                 }
 
-                let prompt_for_history = prompt.clone();
-
-                match session_manager
-                    .start_session(StartSessionArgs {
+                let launch = PendingLaunch {
+                    args: StartSessionArgs {
                         session_id,
                         agent_type,
                         prompt: prompt_for_agent,
-                        working_dir: working_dir_path.clone(),
+                        working_dir: working_dir_path,
                         model,
                         images: image_paths,
                         input_format,
                         stdin_payload,
-                    })
-                    .await
-                {
-                    Ok(mut event_rx) => {
-                        if !hidden {
-                            if let Err(error) = append_input_history(
-                                &session_manager.core,
-                                session_id,
-                                &prompt_for_history,
-                            )
-                            .await
-                            {
-                                tracing::warn!(
-                                    %session_id,
-                                    error = %error,
-                                    "Failed to persist input history"
-                                );
-                            }
-                        }
-
-                        if should_generate {
-                            let core_ref = session_manager.core.clone();
-                            let tx_clone = tx.clone();
-                            let prompt_for_title = prompt.clone();
-                            let working_dir_for_title = working_dir_path.clone();
-                            tokio::spawn(async move {
-                                match generate_title_and_branch_for_session(
-                                    core_ref,
-                                    session_id,
-                                    prompt_for_title,
-                                    working_dir_for_title,
-                                )
-                                .await
-                                {
-                                    Ok(Some(outcome)) => {
-                                        if let Err(error) = tx_clone
-                                            .send(ServerMessage::SessionMetadata {
-                                                session_id,
-                                                title: Some(outcome.title),
-                                                workspace_id: outcome.workspace_id,
-                                                workspace_branch: outcome.new_branch,
-                                            })
-                                            .await
-                                        {
-                                            tracing::debug!(
-                                                %session_id,
-                                                error = ?error,
-                                                "Failed to send session metadata update"
-                                            );
-                                        }
-                                    }
-                                    Ok(None) => {}
-                                    Err(error) => {
-                                        tracing::warn!(
-                                            %session_id,
-                                            error = %error,
-                                            "Failed to generate session title"
-                                        );
-                                    }
-                                }
-                            });
-                        }
-
-                        // Auto-subscribe to the new session
-                        let tx_clone = tx.clone();
-                        let task = tokio::spawn(async move {
-                            while let Ok(event) = event_rx.recv().await {
-                                if tx_clone
-                                    .send(ServerMessage::agent_event(session_id, event))
-                                    .await
-                                    .is_err()
-                                {
-                                    break;
-                                }
-                            }
-                            // Session ended
-                            let _ = tx_clone
-                                .send(ServerMessage::SessionEnded {
-                                    session_id,
-                                    reason: "completed".to_string(),
-                                    error: None,
-                                })
-                                .await;
-                        });
-
-                        let mut subs = subscriptions.write().await;
-                        if let Some(existing) = subs.insert(session_id, task) {
-                            existing.abort();
-                        }
+                        slot: None,
+                    },
+                    prompt,
+                    hidden,
+                    should_generate,
+                };
 
-                        if let Err(send_err) = tx
-                            .send(ServerMessage::session_started(session_id, agent_type, None))
+                match session_manager.try_acquire_slot() {
+                    SlotAttempt::Ready(slot) => {
+                        let mut launch = launch;
+                        launch.args.slot = slot;
+                        if launch_session(&session_manager, &tx, &subscriptions, &relay, launch)
                             .await
+                            .is_err()
                         {
-                            tracing::debug!(
-                                %session_id,
-                                error = ?send_err,
-                                "Failed to send session started"
-                            );
                             break 'ws_loop;
                         }
                     }
-                    Err(e) => {
-                        if let Err(send_err) =
-                            tx.send(ServerMessage::session_error(session_id, e)).await
+                    SlotAttempt::Full { limit } => {
+                        if let Err(send_err) = tx
+                            .send(ServerMessage::SessionWaiting { session_id, limit })
+                            .await
                         {
                             tracing::debug!(
                                 %session_id,
                                 error = ?send_err,
-                                "Failed to send session error"
+                                "Failed to send session waiting"
                             );
                             break 'ws_loop;
                         }
+
+                        let session_manager = session_manager.clone();
+                        let tx = tx.clone();
+                        let subscriptions = subscriptions.clone();
+                        let relay = relay.clone();
+                        let waiting_ref = waiting.clone();
+                        let task = tokio::spawn(async move {
+                            let slot = match session_manager.acquire_slot().await {
+                                Ok(slot) => slot,
+                                Err(error) => {
+                                    if let Err(send_err) = tx
+                                        .send(ServerMessage::session_error(session_id, error))
+                                        .await
+                                    {
+                                        tracing::debug!(
+                                            %session_id,
+                                            error = ?send_err,
+                                            "Failed to send session error"
+                                        );
+                                    }
+                                    return;
+                                }
+                            };
+                            waiting_ref.write().await.remove(&session_id);
+                            let mut launch = launch;
+                            launch.args.slot = slot;
+                            if launch_session(&session_manager, &tx, &subscriptions, &relay, launch)
+                                .await
+                                .is_err()
+                            {
+                                tracing::debug!(
+                                    %session_id,
+                                    "Connection closed while starting a queued session"
+                                );
+                            }
+                        });
+                        if let Some(existing) = waiting.write().await.insert(session_id, task) {
+                            existing.abort();
+                        }
                     }
                 }
             }
@@ -1322,12 +1946,88 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                 }
                 let model = session_tab.model.clone();
                 drop(core);
-                let mut input_payload = input.clone();
+                let hooked_input = if hidden {
+                    input.clone()
+                } else {
+                    match run_pre_submit_hooks(
+                        &session_manager.core,
+                        &session_tab,
+                        None,
+                        input.clone(),
+                    )
+                    .await
+                    {
+                        Ok(input) => input,
+                        Err(rejection) => {
+                            if let Err(send_err) = tx
+                                .send(ServerMessage::session_error(
+                                    session_id,
+                                    rejection.to_string(),
+                                ))
+                                .await
+                            {
+                                tracing::debug!(
+                                    %session_id,
+                                    error = ?send_err,
+                                    "Failed to send session error"
+                                );
+                                break 'ws_loop;
+                            }
+                            continue;
+                        }
+                    }
+                };
+                let agent_input = if hidden {
+                    hooked_input
+                } else {
+                    let hooked_input = app_prompt::append_language_directive(
+                        &hooked_input,
+                        session_tab.response_language.as_deref(),
+                    );
+                    app_prompt::append_preset_directive(
+                        &hooked_input,
+                        session_tab
+                            .response_preset
+                            .as_deref()
+                            .and_then(app_prompt::ResponsePreset::parse),
+                    )
+                };
+                let mut input_payload = agent_input.clone();
+                if let Some(agent_type) = agent_type {
+                    if !images.is_empty()
+                        && !AgentCapabilities::for_agent(agent_type).supports_images
+                    {
+                        if let Err(send_err) = tx
+                            .send(ServerMessage::session_error(
+                                session_id,
+                                format!(
+                                    "Image attachments are not supported for {} sessions",
+                                    agent_type.display_name()
+                                ),
+                            ))
+                            .await
+                        {
+                            tracing::debug!(
+                                %session_id,
+                                error = ?send_err,
+                                "Failed to send session error"
+                            );
+                            break 'ws_loop;
+                        }
+                        continue;
+                    }
+                }
+
+                let attachment_store = session_manager.core.read().await.attachment_store_clone();
                 let image_paths = if images.is_empty() {
                     Vec::new()
                 } else {
                     match agent_type {
-                        Some(AgentType::Codex) => match decode_image_attachments(&images) {
+                        Some(AgentType::Codex) => match decode_image_attachments(
+                            &images,
+                            attachment_store.as_ref(),
+                            session_id,
+                        ) {
                             Ok(paths) => paths,
                             Err(error) => {
                                 if let Err(send_err) = tx
@@ -1345,7 +2045,7 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                             }
                         },
                         Some(AgentType::Claude) => {
-                            match build_claude_prompt_jsonl(&input, &images) {
+                            match build_claude_prompt_jsonl(&agent_input, &images) {
                                 Ok(payload) => {
                                     input_payload = payload;
                                     Vec::new()
@@ -1366,46 +2066,16 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                                 }
                             }
                         }
-                        Some(AgentType::Gemini) => {
-                            if let Err(send_err) = tx
-                                .send(ServerMessage::session_error(
-                                    session_id,
-                                    "Image attachments are not supported for Gemini sessions",
-                                ))
-                                .await
-                            {
-                                tracing::debug!(
-                                    %session_id,
-                                    error = ?send_err,
-                                    "Failed to send session error"
-                                );
-                                break 'ws_loop;
-                            }
-                            continue;
-                        }
-                        Some(AgentType::Opencode) => {
-                            if let Err(send_err) = tx
-                                .send(ServerMessage::session_error(
-                                    session_id,
-                                    "Image attachments are not supported for OpenCode sessions",
-                                ))
-                                .await
-                            {
-                                tracing::debug!(
-                                    %session_id,
-                                    error = ?send_err,
-                                    "Failed to send session error"
-                                );
-                                break 'ws_loop;
-                            }
-                            continue;
+                        // Rejected above: these agents don't support images
+                        Some(AgentType::Gemini | AgentType::Opencode | AgentType::Local) => {
+                            Vec::new()
                         }
                         None => Vec::new(),
                     }
                 };
 
                 if matches!(agent_type, Some(AgentType::Claude)) && images.is_empty() {
-                    match build_claude_prompt_jsonl(&input, &[]) {
+                    match build_claude_prompt_jsonl(&agent_input, &[]) {
                         Ok(payload) => {
                             input_payload = payload;
                         }
@@ -1426,39 +2096,78 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
                     }
                 }
 
-                if let Err(e) = session_manager
-                    .send_input(session_id, input_payload, image_paths, model)
-                    .await
-                {
-                    if let Err(send_err) =
-                        tx.send(ServerMessage::session_error(session_id, e)).await
-                    {
-                        tracing::debug!(
-                            %session_id,
-                            error = ?send_err,
-                            "Failed to send session error"
-                        );
+                let pending = PendingInput {
+                    session_id,
+                    input,
+                    payload: input_payload,
+                    images: image_paths,
+                    model,
+                    hidden,
+                    slot: None,
+                };
+
+                // A new turn on an idle session needs a slot; input mid-turn does not
+                if !session_manager.needs_slot(session_id).await {
+                    if deliver_input(&session_manager, &tx, pending).await.is_err() {
                         break 'ws_loop;
                     }
-                } else if !hidden {
-                    if let Err(error) =
-                        persist_pending_user_message(&session_manager.core, session_id, &input)
-                            .await
-                    {
-                        tracing::warn!(
-                            %session_id,
-                            error = %error,
-                            "Failed to persist pending user message"
-                        );
+                    continue;
+                }
+
+                match session_manager.try_acquire_slot() {
+                    SlotAttempt::Ready(slot) => {
+                        let mut pending = pending;
+                        pending.slot = slot;
+                        if deliver_input(&session_manager, &tx, pending).await.is_err() {
+                            break 'ws_loop;
+                        }
                     }
-                    if let Err(error) =
-                        append_input_history(&session_manager.core, session_id, &input).await
-                    {
-                        tracing::warn!(
-                            %session_id,
-                            error = %error,
-                            "Failed to persist input history"
-                        );
+                    SlotAttempt::Full { limit } => {
+                        if let Err(send_err) = tx
+                            .send(ServerMessage::SessionWaiting { session_id, limit })
+                            .await
+                        {
+                            tracing::debug!(
+                                %session_id,
+                                error = ?send_err,
+                                "Failed to send session waiting"
+                            );
+                            break 'ws_loop;
+                        }
+
+                        let session_manager = session_manager.clone();
+                        let tx = tx.clone();
+                        let waiting_ref = waiting.clone();
+                        let task = tokio::spawn(async move {
+                            let slot = match session_manager.acquire_slot().await {
+                                Ok(slot) => slot,
+                                Err(error) => {
+                                    if let Err(send_err) = tx
+                                        .send(ServerMessage::session_error(session_id, error))
+                                        .await
+                                    {
+                                        tracing::debug!(
+                                            %session_id,
+                                            error = ?send_err,
+                                            "Failed to send session error"
+                                        );
+                                    }
+                                    return;
+                                }
+                            };
+                            waiting_ref.write().await.remove(&session_id);
+                            let mut pending = pending;
+                            pending.slot = slot;
+                            if deliver_input(&session_manager, &tx, pending).await.is_err() {
+                                tracing::debug!(
+                                    %session_id,
+                                    "Connection closed while delivering queued input"
+                                );
+                            }
+                        });
+                        if let Some(existing) = waiting.write().await.insert(session_id, task) {
+                            existing.abort();
+                        }
                     }
                 }
             }
@@ -1486,13 +2195,16 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
             }
 
             ClientMessage::StopSession { session_id } => {
-                // Clean up subscription first
+                // Clean up subscription and any start still waiting for a slot first
                 {
                     let mut subs = subscriptions.write().await;
                     if let Some(task) = subs.remove(&session_id) {
                         task.abort();
                     }
                 }
+                if let Some(task) = waiting.write().await.remove(&session_id) {
+                    task.abort();
+                }
 
                 match session_manager.stop_session(session_id).await {
                     Ok(()) => {
@@ -1529,11 +2241,14 @@ pub async fn handle_websocket(socket: WebSocket, session_manager: Arc<SessionMan
         }
     }
 
-    // Clean up all subscriptions when connection closes
+    // Clean up all subscriptions and waiting starts when connection closes
     let subs = subscriptions.read().await;
     for (_, task) in subs.iter() {
         task.abort();
     }
+    for (_, task) in waiting.read().await.iter() {
+        task.abort();
+    }
 
     send_task.abort();
 }
@@ -1,14 +1,43 @@
 //! WebSocket message types for the Conduit web API.
 //!
-//! This module defines the JSON protocol used for real-time communication
-//! between the web client and the Conduit server.
+//! This module defines the protocol used for real-time communication
+//! between the web client and the Conduit server. Messages are JSON text
+//! frames by default; clients can negotiate MessagePack binary frames via the
+//! `Sec-WebSocket-Protocol` handshake header (see [`WireFormat`]).
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::bandwidth::{BandwidthMode, TruncatedContent};
 use crate::agent::events::AgentEvent;
 use crate::agent::runner::AgentType;
 
+/// Subprotocol for JSON text frames (the default when none is requested).
+pub const JSON_SUBPROTOCOL: &str = "conduit.json";
+/// Subprotocol for MessagePack binary frames.
+pub const MSGPACK_SUBPROTOCOL: &str = "conduit.msgpack";
+
+/// Frame encoding negotiated at the WebSocket handshake.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WireFormat {
+    #[default]
+    Json,
+    MessagePack,
+}
+
+impl WireFormat {
+    /// Subprotocols offered by the server, in order of preference.
+    pub const SUBPROTOCOLS: [&'static str; 2] = [MSGPACK_SUBPROTOCOL, JSON_SUBPROTOCOL];
+
+    /// Resolve the format from the subprotocol selected during the handshake.
+    pub fn from_subprotocol(protocol: Option<&str>) -> Self {
+        match protocol {
+            Some(MSGPACK_SUBPROTOCOL) => Self::MessagePack,
+            _ => Self::Json,
+        }
+    }
+}
+
 /// Image attachment for WebSocket prompts (base64-encoded).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImageAttachment {
@@ -70,6 +99,12 @@ pub enum ClientMessage {
     /// Stop a running agent session
     StopSession { session_id: Uuid },
 
+    /// Choose how much of each session is relayed live on this connection
+    SetBandwidth { mode: BandwidthMode },
+
+    /// Fetch the full text of an event truncated in low-bandwidth mode
+    FetchContent { content_id: Uuid },
+
     /// Ping to keep connection alive
     Ping,
 }
@@ -92,6 +127,9 @@ pub enum ServerMessage {
         agent_session_id: Option<String>,
     },
 
+    /// Every concurrent-agent slot is busy; the prompt starts once one frees
+    SessionWaiting { session_id: Uuid, limit: usize },
+
     /// Session metadata update (title/branch rename)
     SessionMetadata {
         session_id: Uuid,
@@ -101,7 +139,19 @@ pub enum ServerMessage {
     },
 
     /// Agent event forwarded from a session
-    AgentEvent { session_id: Uuid, event: AgentEvent },
+    AgentEvent {
+        session_id: Uuid,
+        event: AgentEvent,
+        /// Set when the event's output was cut short in low-bandwidth mode
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        truncated: Option<TruncatedContent>,
+    },
+
+    /// Acknowledgment of a bandwidth mode change
+    BandwidthSet { mode: BandwidthMode },
+
+    /// Full text requested with `fetch_content`
+    Content { content_id: Uuid, text: String },
 
     /// Session ended (completed or stopped)
     SessionEnded {
@@ -143,7 +193,11 @@ impl ServerMessage {
 
     /// Create an agent event message.
     pub fn agent_event(session_id: Uuid, event: AgentEvent) -> Self {
-        Self::AgentEvent { session_id, event }
+        Self::AgentEvent {
+            session_id,
+            event,
+            truncated: None,
+        }
     }
 
     /// Create a session started message.
@@ -159,3 +213,24 @@ impl ServerMessage {
         }
     }
 }
+
+impl ServerMessage {
+    /// Encode as MessagePack. Structs are written as maps and UUIDs as strings
+    /// so the payload mirrors the JSON shape.
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        let mut buf = Vec::new();
+        let mut serializer = rmp_serde::Serializer::new(&mut buf)
+            .with_struct_map()
+            .with_human_readable();
+        self.serialize(&mut serializer)?;
+        Ok(buf)
+    }
+}
+
+impl ClientMessage {
+    /// Decode a MessagePack binary frame.
+    pub fn from_msgpack(bytes: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
+        let mut deserializer = rmp_serde::Deserializer::new(bytes).with_human_readable();
+        Self::deserialize(&mut deserializer)
+    }
+}
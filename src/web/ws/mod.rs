@@ -1,10 +1,12 @@
 //! WebSocket module for real-time agent communication.
 
+mod bandwidth;
 mod handler;
 mod messages;
 
 #[cfg(test)]
 mod tests;
 
+pub use bandwidth::{BandwidthMode, TruncatedContent, LIVE_OUTPUT_LIMIT};
 pub use handler::{handle_websocket, SessionManager};
-pub use messages::{ClientMessage, ServerMessage};
+pub use messages::{ClientMessage, ServerMessage, WireFormat};
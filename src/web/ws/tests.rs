@@ -1,8 +1,9 @@
 //! Tests for WebSocket message types and serialization.
 
-use super::messages::{ClientMessage, ServerMessage};
+use super::messages::{ClientMessage, ServerMessage, WireFormat, MSGPACK_SUBPROTOCOL};
 use crate::agent::events::{AgentEvent, AssistantMessageEvent, SessionInitEvent};
 use crate::agent::session::SessionId;
+use serde::Serialize;
 use uuid::Uuid;
 
 #[test]
@@ -140,6 +141,7 @@ fn test_server_message_agent_event_serialization() {
     if let ServerMessage::AgentEvent {
         session_id: sid,
         event: parsed_event,
+        ..
     } = parsed
     {
         assert_eq!(sid, session_id);
@@ -274,3 +276,105 @@ fn test_server_message_session_ended_serialization() {
         panic!("Expected SessionEnded message");
     }
 }
+
+#[test]
+fn test_wire_format_from_subprotocol() {
+    assert_eq!(
+        WireFormat::from_subprotocol(Some(MSGPACK_SUBPROTOCOL)),
+        WireFormat::MessagePack
+    );
+    assert_eq!(
+        WireFormat::from_subprotocol(Some("conduit.json")),
+        WireFormat::Json
+    );
+    assert_eq!(WireFormat::from_subprotocol(None), WireFormat::Json);
+}
+
+#[test]
+fn test_server_message_msgpack_matches_json_shape() {
+    let msg = ServerMessage::agent_event(
+        Uuid::nil(),
+        AgentEvent::AssistantMessage(AssistantMessageEvent {
+            text: "Hello".to_string(),
+            is_final: true,
+        }),
+    );
+
+    let bytes = msg.to_msgpack().unwrap();
+    let decoded: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+    assert_eq!(decoded, serde_json::to_value(&msg).unwrap());
+}
+
+#[test]
+fn test_client_message_msgpack_roundtrip() {
+    let session_id = Uuid::new_v4();
+    let msg = ClientMessage::SendInput {
+        session_id,
+        input: "continue".to_string(),
+        hidden: false,
+        images: Vec::new(),
+    };
+    let mut bytes = Vec::new();
+    msg.serialize(
+        &mut rmp_serde::Serializer::new(&mut bytes)
+            .with_struct_map()
+            .with_human_readable(),
+    )
+    .unwrap();
+
+    match ClientMessage::from_msgpack(&bytes).unwrap() {
+        ClientMessage::SendInput {
+            session_id: parsed_id,
+            input,
+            ..
+        } => {
+            assert_eq!(parsed_id, session_id);
+            assert_eq!(input, "continue");
+        }
+        other => panic!("Expected SendInput message, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_server_message_session_waiting_serialization() {
+    let session_id = Uuid::new_v4();
+    let msg = ServerMessage::SessionWaiting {
+        session_id,
+        limit: 2,
+    };
+    let json = serde_json::to_string(&msg).unwrap();
+    assert_eq!(
+        json,
+        format!(
+            r#"{{"type":"session_waiting","session_id":"{}","limit":2}}"#,
+            session_id
+        )
+    );
+}
+
+#[test]
+fn test_client_message_set_bandwidth_serialization() {
+    use super::BandwidthMode;
+
+    let parsed: ClientMessage =
+        serde_json::from_str(r#"{"type":"set_bandwidth","mode":"low"}"#).unwrap();
+    assert!(matches!(
+        parsed,
+        ClientMessage::SetBandwidth {
+            mode: BandwidthMode::Low
+        }
+    ));
+}
+
+#[test]
+fn test_untruncated_agent_event_omits_truncated_field() {
+    let msg = ServerMessage::agent_event(
+        Uuid::nil(),
+        AgentEvent::AssistantMessage(AssistantMessageEvent {
+            text: "hi".to_string(),
+            is_final: true,
+        }),
+    );
+    let json = serde_json::to_string(&msg).unwrap();
+    assert!(!json.contains("truncated"));
+}
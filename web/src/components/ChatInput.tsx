@@ -21,7 +21,7 @@ interface ChatInputProps {
   notice?: string | null;
   // Session/workspace info for status line
   modelDisplayName?: string | null;
-  agentType?: 'claude' | 'codex' | 'gemini' | 'opencode' | null;
+  agentType?: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local' | null;
   agentMode?: string | null;
   gitStats?: { additions: number; deletions: number } | null;
   branch?: string | null;
@@ -409,6 +409,8 @@ export function ChatInput({
                   ? 'Codex CLI'
                   : agentType === 'opencode'
                     ? 'OpenCode'
+                    : agentType === 'local'
+                      ? 'Local model'
                     : 'Gemini CLI'}
             </span>
           )}
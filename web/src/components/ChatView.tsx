@@ -10,6 +10,7 @@ import { RawEventsPanel } from './RawEventsPanel';
 import { ModelSelectorDialog } from './ModelSelectorDialog';
 import {
   useSessionEvents,
+  useWaitingSessions,
   useWebSocket,
   useWorkspace,
   useWorkspaceStatus,
@@ -198,6 +199,7 @@ export function ChatView({
   const scrollStateBySession = useRef<Record<string, { top: number; pinned: boolean }>>({});
   const scrollSessionId = useRef<string | null>(null);
   const { sendPrompt, respondToControl, stopSession } = useWebSocket();
+  const waitingSessionIds = useWaitingSessions();
   const wsEvents = useSessionEvents(session?.id ?? null);
   const updateSessionMutation = useUpdateSession();
   const setDefaultModelMutation = useSetDefaultModel();
@@ -1014,14 +1016,21 @@ export function ChatView({
 
   const renderableWsEvents = useMemo(() => {
     const toolIdToName = new Map<string, string>();
+    const toolIdToCommand = new Map<string, string>();
     visibleWsEvents.forEach((event) => {
       if (event.type === 'ToolStarted') {
         toolIdToName.set(event.tool_id, event.tool_name);
+        const command = (event.arguments as { command?: unknown } | null)?.command;
+        if (typeof command === 'string') {
+          toolIdToCommand.set(event.tool_id, command);
+        }
       }
     });
 
     const merged: RenderableEvent[] = [];
     const toolIndexById = new Map<string, number>();
+    // Live command output built from deltas, replaced by the final CommandOutput
+    const liveOutputIndexById = new Map<string, number>();
 
     const shouldSkip = (event: typeof wsEvents[number]) => {
       if (
@@ -1092,6 +1101,48 @@ export function ChatView({
         continue;
       }
 
+      if (event.type === 'CommandOutputDelta') {
+        const toolIndex = toolIndexById.get(event.tool_id);
+        const toolRun = toolIndex !== undefined ? merged[toolIndex] : undefined;
+        if (toolIndex !== undefined && toolRun?.type === 'ToolRun') {
+          merged[toolIndex] = { ...toolRun, output: (toolRun.output ?? '') + event.chunk };
+          continue;
+        }
+
+        const liveIndex = liveOutputIndexById.get(event.tool_id);
+        const live = liveIndex !== undefined ? merged[liveIndex] : undefined;
+        if (liveIndex !== undefined && live?.type === 'CommandOutput') {
+          merged[liveIndex] = { ...live, output: live.output + event.chunk };
+        } else {
+          liveOutputIndexById.set(event.tool_id, merged.length);
+          merged.push({
+            type: 'CommandOutput',
+            command: toolIdToCommand.get(event.tool_id) ?? '',
+            output: event.chunk,
+            exit_code: null,
+            is_streaming: true,
+          });
+        }
+        continue;
+      }
+
+      if (event.type === 'CommandOutput' && !event.is_streaming) {
+        const live = [...liveOutputIndexById.entries()].find(([, index]) => {
+          const candidate = merged[index];
+          return (
+            candidate.type === 'CommandOutput' &&
+            candidate.is_streaming &&
+            candidate.command === event.command
+          );
+        });
+        if (live) {
+          const [toolId, index] = live;
+          merged[index] = event;
+          liveOutputIndexById.delete(toolId);
+          continue;
+        }
+      }
+
       merged.push(event);
     }
 
@@ -1120,9 +1171,12 @@ export function ChatView({
   const hasHistory = historyEvents.length > 0;
   const hasWsEvents = renderableWsEvents.length > 0;
   const showStatusIndicator = !inlinePrompt && (isProcessing || isAwaitingResponse);
-  const statusLabel = activeToolName
-    ? `Running ${activeToolName}…`
-    : `${processingWord}…`;
+  const isWaitingForSlot = !!session && waitingSessionIds.has(session.id);
+  const statusLabel = isWaitingForSlot
+    ? 'Waiting for a free agent slot…'
+    : activeToolName
+      ? `Running ${activeToolName}…`
+      : `${processingWord}…`;
   const hasOptimisticMessages = optimisticUserMessages.length > 0;
   const hasContent = hasHistory || hasWsEvents || hasOptimisticMessages;
   const rawEventsForView = useMemo(() => {
@@ -1142,7 +1196,7 @@ export function ChatView({
 
   // Can only change model if session hasn't started (no agent_session_id) and not processing
   const canChangeModel = !!session && !isProcessing &&
-    (session.agent_type === 'opencode'
+    (session.agent_type === 'opencode' || session.agent_type === 'local'
       ? true
       : (!session.agent_session_id || session.model_invalid || !session.model));
   const canChangeMode =
@@ -1184,10 +1238,10 @@ export function ChatView({
   const currentAttachments = session ? attachmentsBySession[session.id] ?? [] : [];
   const canStop = isProcessing || isAwaitingResponse;
 
-  const handleModelSelect = useCallback((modelId: string, newAgentType: 'claude' | 'codex' | 'gemini' | 'opencode') => {
+  const handleModelSelect = useCallback((modelId: string, newAgentType: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local') => {
     if (!session) return;
     // Only include agent_type in the request if it's different from current
-    const data: { model: string; agent_type?: 'claude' | 'codex' | 'gemini' | 'opencode' } = { model: modelId };
+    const data: { model: string; agent_type?: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local' } = { model: modelId };
     if (newAgentType !== session.agent_type) {
       data.agent_type = newAgentType;
     }
@@ -1206,7 +1260,7 @@ export function ChatView({
   }, [session, updateSessionMutation, onNotify]);
 
   const handleSetDefaultModel = useCallback(
-    (modelId: string, newAgentType: 'claude' | 'codex' | 'gemini' | 'opencode') => {
+    (modelId: string, newAgentType: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local') => {
       setDefaultModelMutation.mutate({ agent_type: newAgentType, model_id: modelId });
     },
     [setDefaultModelMutation]
@@ -1295,6 +1349,8 @@ export function ChatView({
                 ? 'bg-green-400'
                 : session.agent_type === 'opencode'
                 ? 'bg-teal-400'
+                : session.agent_type === 'local'
+                ? 'bg-slate-400'
                 : 'bg-blue-400'
             )}
           />
@@ -1312,6 +1368,8 @@ export function ChatView({
                   ? 'Codex CLI'
                   : session.agent_type === 'opencode'
                   ? 'OpenCode'
+                  : session.agent_type === 'local'
+                  ? 'Local model'
                   : 'Gemini CLI'}
             </span>
             </p>
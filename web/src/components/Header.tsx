@@ -1,5 +1,14 @@
-import { useHealth } from '../hooks';
-import { Circle, Settings, PanelLeft, GitBranch, GitPullRequest, Activity, Download } from 'lucide-react';
+import { useHealth, useWorkspacePreviews } from '../hooks';
+import {
+  Circle,
+  Settings,
+  PanelLeft,
+  GitBranch,
+  GitPullRequest,
+  Activity,
+  Download,
+  Globe,
+} from 'lucide-react';
 import { cn } from '../lib/cn';
 import { supportsPlanMode } from '../lib/agentCapabilities';
 import { ThemeSwitcher } from './ThemeSwitcher';
@@ -36,6 +45,8 @@ export function Header({
 
   const gitStats = workspaceStatus?.git_stats;
   const prStatus = workspaceStatus?.pr_status;
+  const { data: previews } = useWorkspacePreviews(activeWorkspace?.id ?? null);
+  const latestPreview = previews?.[previews.length - 1];
   const effectiveAgentMode = activeSession?.agent_mode ?? 'build';
 
   return (
@@ -60,6 +71,8 @@ export function Header({
                   ? 'bg-green-400'
                   : activeSession.agent_type === 'opencode'
                   ? 'bg-teal-400'
+                  : activeSession.agent_type === 'local'
+                  ? 'bg-slate-400'
                   : 'bg-blue-400'
               )}
             />
@@ -70,6 +83,8 @@ export function Header({
                 ? 'Codex'
                 : activeSession.agent_type === 'opencode'
                 ? 'OpenCode'
+                : activeSession.agent_type === 'local'
+                ? 'Local'
                 : 'Gemini'}
             </span>
             {supportsPlanMode(activeSession.agent_type) && (
@@ -113,6 +128,21 @@ export function Header({
               <span>#{prStatus.number}</span>
             </div>
           )}
+          {latestPreview && (
+            <a
+              href={latestPreview.forwarded_url ?? latestPreview.url}
+              target="_blank"
+              rel="noreferrer"
+              title={previews?.map((preview) => preview.forwarded_url ?? preview.url).join('\n')}
+              className="flex items-center gap-1 rounded-full bg-accent/15 px-2 py-0.5 text-accent hover:bg-accent/25"
+            >
+              <Globe className="h-3.5 w-3.5" />
+              <span>preview :{latestPreview.port}</span>
+              {previews && previews.length > 1 && (
+                <span className="text-text-muted">+{previews.length - 1}</span>
+              )}
+            </a>
+          )}
         </div>
 
         <div className="flex items-center gap-4">
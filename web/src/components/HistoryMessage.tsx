@@ -1,5 +1,5 @@
 import { memo } from 'react';
-import { User, Bot, AlertCircle, Clock, Coins } from 'lucide-react';
+import { User, Bot, AlertCircle, Clock, Coins, Terminal, GitCommit } from 'lucide-react';
 import type { SessionEvent } from '../types';
 import { MarkdownBody } from './markdown';
 import { ToolRunMessage } from './ToolRunMessage';
@@ -85,8 +85,24 @@ export const HistoryMessage = memo(function HistoryMessage({ event }: HistoryMes
               <span className="flex items-center gap-1">
                 <Coins className="h-3 w-3" />
                 {event.summary.input_tokens} in / {event.summary.output_tokens} out
+                {(event.summary.cache_read_tokens > 0 || event.summary.cache_write_tokens > 0) &&
+                  ` (cache ${event.summary.cache_read_tokens} read / ${event.summary.cache_write_tokens} write)`}
               </span>
             )}
+            {event.summary.commands?.length > 0 && (
+              <span className="flex items-center gap-1" title={event.summary.commands.map((run) => run.command).join('\n')}>
+                <Terminal className="h-3 w-3" />
+                {event.summary.commands.length} {event.summary.commands.length === 1 ? 'command' : 'commands'}
+                {event.summary.verification === 'passed' && <span className="text-success">· checks passed</span>}
+                {event.summary.verification === 'failed' && <span className="text-error">· checks failed</span>}
+              </span>
+            )}
+            {event.summary.references?.map((reference) => (
+              <span key={`${reference.kind}-${reference.id}`} className="flex items-center gap-1">
+                <GitCommit className="h-3 w-3" />
+                {reference.kind === 'pull_request' ? `PR #${reference.id}` : `commit ${reference.id.slice(0, 7)}`}
+              </span>
+            ))}
           </div>
         </div>
       );
@@ -1,6 +1,6 @@
 import { useEffect, useRef, useState, useMemo } from 'react';
-import { X, Loader2, Search, Check } from 'lucide-react';
-import { useModels } from '../hooks';
+import { X, Loader2, Search, Check, RefreshCw } from 'lucide-react';
+import { useModels, useRefreshModels } from '../hooks';
 import type { ModelInfo } from '../types';
 import { cn } from '../lib/cn';
 
@@ -8,9 +8,9 @@ interface ModelSelectorDialogProps {
   isOpen: boolean;
   onClose: () => void;
   currentModel: string | null;
-  agentType: 'claude' | 'codex' | 'gemini' | 'opencode';
-  onSelect: (modelId: string, newAgentType: 'claude' | 'codex' | 'gemini' | 'opencode') => void;
-  onSetDefault: (modelId: string, newAgentType: 'claude' | 'codex' | 'gemini' | 'opencode') => void;
+  agentType: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local';
+  onSelect: (modelId: string, newAgentType: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local') => void;
+  onSetDefault: (modelId: string, newAgentType: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local') => void;
   isUpdating?: boolean;
   isSettingDefault?: boolean;
 }
@@ -30,6 +30,7 @@ export function ModelSelectorDialog({
   const [searchQuery, setSearchQuery] = useState('');
   const [selectedIndex, setSelectedIndex] = useState(0);
   const { data: modelsData, isLoading } = useModels();
+  const refreshModels = useRefreshModels();
   const isBusy = isUpdating || isSettingDefault;
 
   // Filter models based on search query - show all agent types
@@ -56,10 +57,10 @@ export function ModelSelectorDialog({
 
   // Flatten models for keyboard navigation
   const flatModels = useMemo(() => {
-    const models: { model: ModelInfo; groupAgentType: 'claude' | 'codex' | 'gemini' | 'opencode' }[] = [];
+    const models: { model: ModelInfo; groupAgentType: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local' }[] = [];
     filteredGroups.forEach((group) => {
       group.models.forEach((model) => {
-        models.push({ model, groupAgentType: group.agent_type as 'claude' | 'codex' | 'gemini' | 'opencode' });
+        models.push({ model, groupAgentType: group.agent_type as 'claude' | 'codex' | 'gemini' | 'opencode' | 'local' });
       });
     });
     return models;
@@ -118,12 +119,12 @@ export function ModelSelectorDialog({
     }
   };
 
-  const handleSelect = (modelId: string, modelAgentType: 'claude' | 'codex' | 'gemini' | 'opencode') => {
+  const handleSelect = (modelId: string, modelAgentType: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local') => {
     if (isBusy) return;
     onSelect(modelId, modelAgentType);
   };
 
-  const handleSetDefault = (modelId: string, modelAgentType: 'claude' | 'codex' | 'gemini' | 'opencode') => {
+  const handleSetDefault = (modelId: string, modelAgentType: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local') => {
     if (isBusy) return;
     onSetDefault(modelId, modelAgentType);
   };
@@ -148,14 +149,25 @@ export function ModelSelectorDialog({
         {/* Header */}
         <div className="flex shrink-0 items-center justify-between border-b border-border px-6 py-4">
           <h2 className="text-lg font-semibold text-text">Select Model</h2>
-          <button
-            onClick={onClose}
-            disabled={isBusy}
-            className="rounded-md p-1 text-text-muted transition-colors hover:bg-surface-elevated hover:text-text disabled:opacity-50"
-            aria-label="Close dialog"
-          >
-            <X className="h-5 w-5" />
-          </button>
+          <div className="flex items-center gap-1">
+            <button
+              onClick={() => refreshModels.mutate()}
+              disabled={isBusy || refreshModels.isPending}
+              className="rounded-md p-1 text-text-muted transition-colors hover:bg-surface-elevated hover:text-text disabled:opacity-50"
+              aria-label="Refresh models from installed agents"
+              title="Refresh models from installed agents"
+            >
+              <RefreshCw className={cn('h-4 w-4', refreshModels.isPending && 'animate-spin')} />
+            </button>
+            <button
+              onClick={onClose}
+              disabled={isBusy}
+              className="rounded-md p-1 text-text-muted transition-colors hover:bg-surface-elevated hover:text-text disabled:opacity-50"
+              aria-label="Close dialog"
+            >
+              <X className="h-5 w-5" />
+            </button>
+          </div>
         </div>
 
         {/* Search input */}
@@ -197,7 +209,7 @@ export function ModelSelectorDialog({
                     const isSelected = model.id === currentModel && model.agent_type === agentType;
                     const isHighlighted = currentFlatIndex === selectedIndex;
                     const flatIndex = currentFlatIndex;
-                    const groupAgentType = group.agent_type as 'claude' | 'codex' | 'gemini' | 'opencode';
+                    const groupAgentType = group.agent_type as 'claude' | 'codex' | 'gemini' | 'opencode' | 'local';
                     currentFlatIndex++;
 
                     return (
@@ -145,7 +145,7 @@ function getEventMeta(event: AgentEvent): EventMeta {
     return {
       type: 'token_usage',
       badge: 'TOKENS',
-      summary: `in: ${usage.input_tokens} • out: ${usage.output_tokens} • cached: ${usage.cached_tokens}${percent}`,
+      summary: `in: ${usage.input_tokens} • out: ${usage.output_tokens} • cache read: ${usage.cached_tokens} • cache write: ${usage.cache_creation_tokens ?? 0}${percent}`,
       icon: <Zap className={iconClass} />,
       accentClass: 'text-warning',
       borderClass: 'border-warning/20',
@@ -113,6 +113,7 @@ export function SessionTabs({
         const isActive = session.id === activeSessionId && !activeFileViewerId;
         const isProcessing = processingSessionIds.has(session.id);
         const hasUnseen = unseenSessionIds.has(session.id) && !isActive;
+        const gitStats = session.git_stats;
 
         // Render tab indicator with priority: processing > unseen > agent type
         const renderIndicator = () => {
@@ -133,6 +134,8 @@ export function SessionTabs({
                   ? 'bg-green-400'
                   : session.agent_type === 'opencode'
                   ? 'bg-teal-400'
+                  : session.agent_type === 'local'
+                  ? 'bg-slate-400'
                   : 'bg-blue-400'
               )}
             />
@@ -153,6 +156,12 @@ export function SessionTabs({
           >
             {renderIndicator()}
             <span className="max-w-36 truncate">{label}</span>
+            {gitStats && (gitStats.additions > 0 || gitStats.deletions > 0) && (
+              <span className="flex items-center gap-1 text-[10px] tabular-nums">
+                <span className="text-green-400">+{gitStats.additions}</span>
+                <span className="text-red-400">-{gitStats.deletions}</span>
+              </span>
+            )}
             {index < 9 && (
               <span className="ml-0.5 text-[10px] text-text-muted/50 tabular-nums">
                 {tabShortcutPrefix}{index + 1}
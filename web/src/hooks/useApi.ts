@@ -29,6 +29,7 @@ export const queryKeys = {
   repositoryWorkspaces: (id: string) => ['repositories', id, 'workspaces'] as const,
   workspace: (id: string) => ['workspaces', id] as const,
   workspaceStatus: (id: string) => ['workspaces', id, 'status'] as const,
+  workspacePreviews: (id: string) => ['workspaces', id, 'previews'] as const,
   workspaceArchivePreflight: (id: string) => ['workspaces', id, 'archive-preflight'] as const,
   workspacePrPreflight: (id: string) => ['workspaces', id, 'pr-preflight'] as const,
   workspaceSession: (id: string) => ['workspaces', id, 'session'] as const,
@@ -277,6 +278,16 @@ export function useWorkspaceStatus(
   });
 }
 
+export function useWorkspacePreviews(workspaceId: string | null) {
+  return useQuery({
+    queryKey: queryKeys.workspacePreviews(workspaceId ?? ''),
+    queryFn: () => api.getWorkspacePreviews(workspaceId!),
+    enabled: !!workspaceId,
+    refetchInterval: 10000,
+    select: (data) => data.previews,
+  });
+}
+
 export function useWorkspaceArchivePreflight(
   workspaceId: string | null,
   options?: { enabled?: boolean }
@@ -539,6 +550,16 @@ export function useSetDefaultModel() {
   });
 }
 
+export function useRefreshModels() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: api.refreshModels,
+    onSuccess: (models) => {
+      queryClient.setQueryData(queryKeys.models, models);
+    },
+  });
+}
+
 export function useSessionEventsFromApi(
   id: string | null,
   options?: { enabled?: boolean; staleTime?: number; query?: SessionEventsQuery }
@@ -11,6 +11,7 @@ interface WebSocketContextValue {
   ws: ConduitWebSocket;
   connectionState: ConnectionState;
   processingSessionIds: Set<string>;
+  waitingSessionIds: Set<string>;
   unseenSessionIds: Set<string>;
   clearUnseenSession: (sessionId: string) => void;
   sendInput: (
@@ -51,6 +52,7 @@ export function WebSocketProvider({ children }: WebSocketProviderProps) {
   const [connectionState, setConnectionState] = useState<ConnectionState>('disconnected');
   const [processingSessionIds, setProcessingSessionIds] = useState<Set<string>>(new Set());
   const [unseenSessionIds, setUnseenSessionIds] = useState<Set<string>>(new Set());
+  const [waitingSessionIds, setWaitingSessionIds] = useState<Set<string>>(new Set());
   const activeSessionIdRef = useRef<string | null>(null);
   const runningSessionsRef = useRef(new Set<string>());
   const pendingPromptsRef = useRef(
@@ -63,6 +65,27 @@ export function WebSocketProvider({ children }: WebSocketProviderProps) {
 
   const handleServerMessage = useCallback(
     (message: ServerMessage) => {
+      if (message.type === 'session_waiting') {
+        setWaitingSessionIds((prev) => {
+          const next = new Set(prev);
+          next.add(message.session_id);
+          return next;
+        });
+      } else if (
+        message.type === 'session_started' ||
+        message.type === 'session_ended' ||
+        message.type === 'agent_event' ||
+        (message.type === 'error' && message.session_id)
+      ) {
+        const sessionId = message.session_id;
+        setWaitingSessionIds((prev) => {
+          if (!sessionId || !prev.has(sessionId)) return prev;
+          const next = new Set(prev);
+          next.delete(sessionId);
+          return next;
+        });
+      }
+
       if (message.type === 'session_started') {
         runningSessionsRef.current.add(message.session_id);
         pendingPromptsRef.current.delete(message.session_id);
@@ -242,6 +265,12 @@ export function WebSocketProvider({ children }: WebSocketProviderProps) {
       ws.stopSession(sessionId);
       runningSessionsRef.current.delete(sessionId);
       pendingPromptsRef.current.delete(sessionId);
+      setWaitingSessionIds((prev) => {
+        if (!prev.has(sessionId)) return prev;
+        const next = new Set(prev);
+        next.delete(sessionId);
+        return next;
+      });
       setProcessingSessionIds((prev) => {
         if (!prev.has(sessionId)) return prev;
         const next = new Set(prev);
@@ -273,6 +302,7 @@ export function WebSocketProvider({ children }: WebSocketProviderProps) {
     ws,
     connectionState,
     processingSessionIds,
+    waitingSessionIds,
     unseenSessionIds,
     clearUnseenSession,
     sendInput,
@@ -306,6 +336,12 @@ export function useProcessingSessions(): Set<string> {
   return processingSessionIds;
 }
 
+// Hook for accessing which sessions are waiting for a concurrent-agent slot
+export function useWaitingSessions(): Set<string> {
+  const { waitingSessionIds } = useWebSocket();
+  return waitingSessionIds;
+}
+
 // Hook for accessing which sessions have unseen content
 export function useUnseenSessions(): Set<string> {
   const { unseenSessionIds } = useWebSocket();
@@ -348,6 +384,13 @@ export function useSessionEvents(sessionId: string | null): AgentEvent[] {
           } else {
             next = [...prev, event];
           }
+        } else if (event.type === 'CommandOutputDelta') {
+          const last = prev[prev.length - 1];
+          if (last?.type === 'CommandOutputDelta' && last.tool_id === event.tool_id) {
+            next = [...prev.slice(0, -1), { ...event, chunk: last.chunk + event.chunk }];
+          } else {
+            next = [...prev, event];
+          }
         } else if (event.type === 'AssistantMessage') {
           const last = prev[prev.length - 1];
           if (last?.type === 'AssistantMessage' && !last.is_final) {
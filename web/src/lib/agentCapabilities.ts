@@ -1,5 +1,7 @@
 import type { Session } from '../types';
 
 export function supportsPlanMode(agentType?: Session['agent_type'] | null): boolean {
-  return agentType === 'claude' || agentType === 'codex' || agentType === 'gemini';
+  return (
+    agentType === 'claude' || agentType === 'codex' || agentType === 'gemini' || agentType === 'local'
+  );
 }
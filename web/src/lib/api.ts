@@ -20,10 +20,15 @@ import type {
   UpdateSessionRequest,
   SetDefaultModelRequest,
   WorkspaceStatus,
+  WorkspacePreviewsResponse,
+  WorkspaceMemory,
+  SyncWorkspaceMemoryResponse,
   UiState,
   BootstrapResponse,
   SessionEventsQuery,
   InputHistoryResponse,
+  SessionDiagnosticsResponse,
+  SessionReport,
   SessionQueueResponse,
   AddQueueMessageRequest,
   UpdateQueueMessageRequest,
@@ -32,6 +37,11 @@ import type {
   ListExternalSessionsResponse,
   ImportExternalSessionResponse,
   ForkSessionResponse,
+  SessionActionName,
+  ExecuteSessionActionResponse,
+  SessionShare,
+  ListSessionSharesResponse,
+  CreateSessionShareRequest,
   PrPreflightResponse,
   PrCreateResponse,
   ArchivePreflightResponse,
@@ -224,6 +234,10 @@ export async function getModels(): Promise<ListModelsResponse> {
   return request('/models');
 }
 
+export async function refreshModels(): Promise<ListModelsResponse> {
+  return request('/models/refresh', { method: 'POST' });
+}
+
 export async function setDefaultModel(payload: SetDefaultModelRequest): Promise<void> {
   await request('/models/default', {
     method: 'PATCH',
@@ -264,6 +278,18 @@ export async function getSessionHistory(id: string): Promise<InputHistoryRespons
   return request(`/sessions/${id}/history`);
 }
 
+export async function getSessionDiagnostics(id: string): Promise<SessionDiagnosticsResponse> {
+  return request(`/sessions/${id}/diagnostics`);
+}
+
+export async function getSessionReport(id: string): Promise<SessionReport> {
+  return request(`/sessions/${id}/report`);
+}
+
+export function getSessionAttachmentUrl(sessionId: string, attachmentId: string): string {
+  return `${API_BASE}/sessions/${sessionId}/attachments/${attachmentId}`;
+}
+
 export async function getSessionQueue(id: string): Promise<SessionQueueResponse> {
   return request(`/sessions/${id}/queue`);
 }
@@ -298,6 +324,10 @@ export async function getWorkspaceStatus(id: string): Promise<WorkspaceStatus> {
   return request(`/workspaces/${id}/status`);
 }
 
+export async function getWorkspacePreviews(id: string): Promise<WorkspacePreviewsResponse> {
+  return request(`/workspaces/${id}/previews`);
+}
+
 export async function getWorkspacePrPreflight(id: string): Promise<PrPreflightResponse> {
   return request(`/workspaces/${id}/pr/preflight`);
 }
@@ -306,6 +336,22 @@ export async function createWorkspacePr(id: string): Promise<PrCreateResponse> {
   return request(`/workspaces/${id}/pr`, { method: 'POST' });
 }
 
+// Workspace memory (shared context synced into CLAUDE.md / AGENTS.md)
+export async function getWorkspaceMemory(id: string): Promise<WorkspaceMemory> {
+  return request(`/workspaces/${id}/memory`);
+}
+
+export async function updateWorkspaceMemory(id: string, content: string): Promise<WorkspaceMemory> {
+  return request(`/workspaces/${id}/memory`, {
+    method: 'PUT',
+    body: JSON.stringify({ content }),
+  });
+}
+
+export async function syncWorkspaceMemory(id: string): Promise<SyncWorkspaceMemoryResponse> {
+  return request(`/workspaces/${id}/memory/sync`, { method: 'POST' });
+}
+
 // Auto-create workspace (generates name/branch automatically)
 export async function autoCreateWorkspace(repositoryId: string): Promise<Workspace> {
   return request(`/repositories/${repositoryId}/workspaces/auto`, {
@@ -340,6 +386,37 @@ export async function forkSession(id: string): Promise<ForkSessionResponse> {
   });
 }
 
+// Session actions
+export async function executeSessionAction(
+  id: string,
+  action: SessionActionName
+): Promise<ExecuteSessionActionResponse> {
+  return request(`/sessions/${id}/actions`, {
+    method: 'POST',
+    body: JSON.stringify({ action }),
+  });
+}
+
+// Share links
+export async function createSessionShare(
+  id: string,
+  data: CreateSessionShareRequest = {}
+): Promise<SessionShare> {
+  return request(`/sessions/${id}/share`, {
+    method: 'POST',
+    body: JSON.stringify(data),
+  });
+}
+
+export async function listSessionShares(id: string): Promise<SessionShare[]> {
+  const response = await request<ListSessionSharesResponse>(`/sessions/${id}/shares`);
+  return response.shares;
+}
+
+export async function revokeSessionShare(id: string, shareId: string): Promise<void> {
+  await request(`/sessions/${id}/shares/${shareId}`, { method: 'DELETE' });
+}
+
 // Themes
 export async function getThemes(): Promise<ThemeListResponse> {
   return request('/themes');
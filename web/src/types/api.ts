@@ -13,6 +13,8 @@ export interface Repository {
   archive_delete_branch_effective: boolean;
   archive_remote_prompt: boolean | null;
   archive_remote_prompt_effective: boolean;
+  system_prompt: string | null;
+  cost_center: string | null;
   created_at: string;
   updated_at: string;
 }
@@ -57,7 +59,7 @@ export interface Session {
   id: string;
   tab_index: number;
   workspace_id: string | null;
-  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode';
+  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local';
   agent_mode: string | null;
   agent_session_id: string | null;
   model: string | null;
@@ -66,12 +68,36 @@ export interface Session {
   pr_number: number | null;
   created_at: string;
   title: string | null;
+  codex_sandbox: CodexSandboxMode | null;
+  codex_approval: CodexApprovalPolicy | null;
+  system_prompt: string | null;
+  total_cost_usd: number;
+  response_language: string | null;
+  response_preset: ResponsePreset | null;
+  cost_center: string | null;
+  capabilities: AgentCapabilities;
+  git_stats: GitDiffStats | null;
+  fork_seed_id: string | null;
+  fork_seed_hash: string | null;
+}
+
+export type ResponsePreset = 'concise' | 'detailed' | 'code-only';
+export type CodexSandboxMode = 'read-only' | 'workspace-write' | 'danger-full-access';
+export type CodexApprovalPolicy = 'untrusted' | 'on-failure' | 'on-request' | 'never';
+
+export interface AgentCapabilities {
+  supports_images: boolean;
+  supports_resume: boolean;
+  supports_plan_mode: boolean;
+  supports_interrupts: boolean;
+  supports_mcp: boolean;
 }
 
 export interface Agent {
   id: string;
   name: string;
   available: boolean;
+  capabilities: AgentCapabilities;
 }
 
 export interface HealthResponse {
@@ -105,6 +131,8 @@ export interface UpdateRepositorySettingsRequest {
   workspace_mode?: WorkspaceMode;
   archive_delete_branch?: boolean;
   archive_remote_prompt?: boolean;
+  system_prompt?: string;
+  cost_center?: string;
 }
 
 export interface CreateWorkspaceRequest {
@@ -120,18 +148,34 @@ export interface ArchiveWorkspaceRequest {
 
 export interface CreateSessionRequest {
   workspace_id?: string;
-  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode';
+  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local';
   model?: string;
+  system_prompt?: string;
 }
 
 export interface TurnSummary {
   duration_secs: number;
   input_tokens: number;
   output_tokens: number;
+  cache_read_tokens: number;
+  cache_write_tokens: number;
+  commands: CommandRun[];
+  verification?: 'passed' | 'failed';
+  references: TurnReference[];
 }
 
+export interface CommandRun {
+  command: string;
+  exit_code: number | null;
+  succeeded: boolean | null;
+}
+
+export type TurnReference =
+  | { kind: 'pull_request'; id: number }
+  | { kind: 'commit'; id: string };
+
 export interface SessionEvent {
-  role: 'user' | 'assistant' | 'reasoning' | 'tool' | 'system' | 'error' | 'summary';
+  role: 'user' | 'assistant' | 'reasoning' | 'tool' | 'system' | 'error' | 'summary' | 'plan';
   content: string;
   tool_name?: string;
   tool_args?: string;
@@ -166,6 +210,29 @@ export interface InputHistoryResponse {
   history: string[];
 }
 
+export interface StderrLine {
+  timestamp: string;
+  line: string;
+}
+
+export interface SessionDiagnosticsResponse {
+  session_id: string;
+  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local';
+  stderr: StderrLine[];
+}
+
+export interface SessionReport {
+  session_id: string;
+  title: string | null;
+  total_cost_usd: number;
+  turns: number;
+  files_changed: string[];
+  pr_number: number | null;
+  pr_url: string | null;
+  checks_passed: boolean | null;
+  created_at: string;
+}
+
 export interface BootstrapResponse {
   ui_state: UiState;
   sessions: Session[];
@@ -176,7 +243,7 @@ export interface BootstrapResponse {
 
 export interface ExternalSession {
   id: string;
-  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode';
+  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local';
   display: string;
   project?: string | null;
   project_name?: string | null;
@@ -206,6 +273,44 @@ export interface ForkSessionResponse {
   seed_prompt: string;
 }
 
+export type SessionActionName =
+  | 'interrupt_agent'
+  | 'toggle_agent_mode'
+  | 'cycle_codex_sandbox'
+  | 'cycle_codex_approval'
+  | 'export_transcript_pdf';
+
+export interface ExecuteSessionActionResponse {
+  action: SessionActionName;
+  message: string;
+  session?: Session;
+  export?: {
+    html_path: string;
+    pdf_path: string | null;
+  };
+}
+
+export interface SessionShare {
+  id: string;
+  session_id: string;
+  token: string;
+  url: string;
+  redact_tool_output: boolean;
+  active: boolean;
+  created_at: string;
+  expires_at: string;
+  revoked_at: string | null;
+}
+
+export interface ListSessionSharesResponse {
+  shares: SessionShare[];
+}
+
+export interface CreateSessionShareRequest {
+  expires_in_hours?: number;
+  redact_tool_output?: boolean;
+}
+
 export interface GitDiffStats {
   additions: number;
   deletions: number;
@@ -233,6 +338,36 @@ export interface WorkspaceStatus {
   updated_at?: string;
 }
 
+export interface WorkspacePreview {
+  port: number;
+  url: string;
+  forwarded_url: string | null;
+  session_id: string;
+}
+
+export interface WorkspacePreviewsResponse {
+  previews: WorkspacePreview[];
+}
+
+export interface WorkspaceMemoryFile {
+  file_name: string;
+  exists: boolean;
+  synced_content: string | null;
+  in_sync: boolean;
+  diff: string;
+}
+
+export interface WorkspaceMemory {
+  workspace_id: string;
+  content: string;
+  updated_at: string | null;
+  files: WorkspaceMemoryFile[];
+}
+
+export interface SyncWorkspaceMemoryResponse {
+  written: string[];
+}
+
 export interface PrPreflightResponse {
   gh_installed: boolean;
   gh_authenticated: boolean;
@@ -313,7 +448,7 @@ export interface ModelInfo {
   display_name: string;
   description: string;
   is_default: boolean;
-  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode';
+  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local';
   context_window: number;
 }
 
@@ -330,12 +465,15 @@ export interface ListModelsResponse {
 
 export interface UpdateSessionRequest {
   model?: string;
-  agent_type?: 'claude' | 'codex' | 'gemini' | 'opencode';
+  agent_type?: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local';
   agent_mode?: 'build' | 'plan';
+  codex_sandbox?: CodexSandboxMode;
+  codex_approval?: CodexApprovalPolicy;
+  cost_center?: string;
 }
 
 export interface SetDefaultModelRequest {
-  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode';
+  agent_type: 'claude' | 'codex' | 'gemini' | 'opencode' | 'local';
   model_id: string;
 }
 
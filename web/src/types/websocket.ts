@@ -16,7 +16,18 @@ export type ClientMessage =
     }
   | { type: 'send_input'; session_id: string; input: string; hidden?: boolean; images?: ImageAttachment[] }
   | { type: 'respond_to_control'; session_id: string; request_id: string; response: unknown }
-  | { type: 'stop_session'; session_id: string };
+  | { type: 'stop_session'; session_id: string }
+  | { type: 'set_bandwidth'; mode: BandwidthMode }
+  | { type: 'fetch_content'; content_id: string };
+
+// 'low' relays finished messages only and truncates long tool output
+export type BandwidthMode = 'full' | 'low';
+
+// Set on agent events whose output was truncated in low-bandwidth mode
+export interface TruncatedContent {
+  content_id: string;
+  full_len: number;
+}
 
 // Server -> Client messages
 export type ServerMessage =
@@ -31,7 +42,10 @@ export type ServerMessage =
       workspace_id: string | null;
       workspace_branch: string | null;
     }
-  | { type: 'agent_event'; session_id: string; event: AgentEvent }
+  | { type: 'session_waiting'; session_id: string; limit: number }
+  | { type: 'agent_event'; session_id: string; event: AgentEvent; truncated?: TruncatedContent }
+  | { type: 'bandwidth_set'; mode: BandwidthMode }
+  | { type: 'content'; content_id: string; text: string }
   | { type: 'session_ended'; session_id: string; reason: string; error: string | null }
   | { type: 'error'; message: string; session_id: string | null };
 
@@ -45,9 +59,19 @@ export type AgentEvent =
   | { type: 'AssistantReasoning'; text: string }
   | { type: 'ToolStarted'; tool_name: string; tool_id: string; arguments: unknown }
   | { type: 'ToolCompleted'; tool_id: string; success: boolean; result: string | null; error: string | null }
+  | {
+      type: 'SubagentStarted';
+      tool_id: string;
+      description: string;
+      subagent_type: string | null;
+      prompt: string | null;
+    }
+  | { type: 'SubagentCompleted'; tool_id: string; success: boolean; result: string | null; error: string | null }
+  | { type: 'Subagent'; parent_tool_id: string; event: AgentEvent }
   | { type: 'ControlRequest'; request_id: string; tool_name: string; tool_use_id: string | null; input: unknown }
   | { type: 'FileChanged'; path: string; operation: 'create' | 'update' | 'delete' }
   | { type: 'CommandOutput'; command: string; output: string; exit_code: number | null; is_streaming: boolean }
+  | { type: 'CommandOutputDelta'; tool_id: string; chunk: string }
   | { type: 'TokenUsage'; usage: TokenUsage; context_window: number | null; usage_percent: number | null }
   | { type: 'ContextCompaction'; reason: string; tokens_before: number; tokens_after: number }
   | {
@@ -68,6 +92,7 @@ export interface TokenUsage {
   input_tokens: number;
   output_tokens: number;
   cached_tokens: number;
+  cache_creation_tokens: number;
   total_tokens: number;
 }
 